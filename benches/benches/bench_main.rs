@@ -48,9 +48,16 @@ mod benchmarks {
     pub mod aoc_2020_19b;
     pub mod aoc_2020_1a;
     pub mod aoc_2020_1b;
+    pub mod borrowed_args;
     pub mod brainfuck;
+    pub mod call_with_slice;
+    pub mod early_bound;
     pub mod external_functions;
     pub mod fib;
+    pub mod objects;
+    pub mod unit_loading;
+    pub mod vec_conversion;
+    pub mod vec_reductions;
 }
 
 criterion::criterion_main! {
@@ -58,7 +65,14 @@ criterion::criterion_main! {
     benchmarks::aoc_2020_1b::benches,
     benchmarks::aoc_2020_11a::benches,
     benchmarks::aoc_2020_19b::benches,
+    benchmarks::borrowed_args::benches,
     benchmarks::brainfuck::benches,
+    benchmarks::call_with_slice::benches,
+    benchmarks::early_bound::benches,
     benchmarks::fib::benches,
     benchmarks::external_functions::benches,
+    benchmarks::objects::benches,
+    benchmarks::unit_loading::benches,
+    benchmarks::vec_conversion::benches,
+    benchmarks::vec_reductions::benches,
 }
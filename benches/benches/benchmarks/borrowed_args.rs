@@ -0,0 +1,76 @@
+//! Benchmark passing script strings and bytes to native functions.
+//!
+//! Functions which take `&str` or `&[u8]` borrow the argument directly from
+//! the script value, while functions which take `String` or `Bytes` take
+//! ownership of it, which forces scripts to clone any value they want to keep
+//! using.
+
+use criterion::Criterion;
+use rune::runtime::Bytes;
+use rune::{Context, Diagnostics, Module, Vm};
+
+criterion::criterion_group!(benches, borrowed_args);
+
+fn vm() -> Vm {
+    let mut module = Module::new();
+
+    module
+        .function(["str_len"], |s: &str| s.len())
+        .expect("failed to install");
+    module
+        .function(["string_len"], |s: String| s.len())
+        .expect("failed to install");
+    module
+        .function(["bytes_len"], |b: &[u8]| b.len())
+        .expect("failed to install");
+    module
+        .function(["owned_bytes_len"], |b: Bytes| b.len())
+        .expect("failed to install");
+
+    let mut context = Context::with_default_modules().expect("failed to build context");
+    context.install(module).expect("failed to install module");
+
+    let mut sources = crate::sources(
+        r#"
+        pub fn borrowed(s, b) {
+            let n = 0;
+            for _ in 0..100 { n += str_len(s) + bytes_len(b); }
+            n
+        }
+
+        pub fn owned(s, b) {
+            let n = 0;
+            for _ in 0..100 { n += string_len(s.clone()) + owned_bytes_len(b.clone()); }
+            n
+        }
+        "#,
+    );
+
+    let mut diagnostics = Diagnostics::new();
+    crate::vm(&context, &mut sources, &mut diagnostics).expect("failed to build vm")
+}
+
+fn borrowed_args(b: &mut Criterion) {
+    let mut vm = vm();
+
+    let borrowed = rune::Hash::type_hash(["borrowed"]);
+    let owned = rune::Hash::type_hash(["owned"]);
+
+    let text = "a".repeat(1024);
+    let s = rune::to_value(text.as_str()).expect("failed to convert");
+    let bytes = rune::to_value(Bytes::from_slice(&[0u8; 1024])).expect("failed to convert");
+
+    b.bench_function("borrowed_args", |b| {
+        b.iter(|| {
+            vm.call(borrowed, (s.clone(), bytes.clone()))
+                .expect("failed call")
+        });
+    });
+
+    b.bench_function("owned_args", |b| {
+        b.iter(|| {
+            vm.call(owned, (s.clone(), bytes.clone()))
+                .expect("failed call")
+        });
+    });
+}
@@ -0,0 +1,47 @@
+//! Benchmark call-heavy workloads driven from the host with borrowed
+//! arguments.
+
+use criterion::Criterion;
+use rune::runtime::Function;
+use rune::Value;
+
+criterion::criterion_group!(benches, call_with_slice);
+
+fn call_with_slice(b: &mut Criterion) {
+    let mut vm = rune_vm! {
+        fn add(a, b, c) {
+            a + b + c
+        }
+
+        pub fn main(a, b, c) {
+            add(a, b, c)
+        }
+
+        pub fn function() {
+            add
+        }
+    };
+
+    let entry = rune::Hash::type_hash(["main"]);
+    let args = [Value::from(1i64), Value::from(2i64), Value::from(3i64)];
+
+    b.bench_function("call_with_vec", |b| {
+        b.iter(|| vm.call(entry, args.to_vec()).expect("failed call"));
+    });
+
+    b.bench_function("call_with_slice", |b| {
+        b.iter(|| vm.call_with_slice(entry, &args).expect("failed call"));
+    });
+
+    let function = vm.call(["function"], ()).expect("failed to fetch function");
+    let function: Function = rune::from_value(function).expect("not a function");
+
+    b.bench_function("function_call_with_slice", |b| {
+        b.iter(|| {
+            function
+                .call::<_, Value>(&args[..])
+                .into_result()
+                .expect("failed call")
+        });
+    });
+}
@@ -0,0 +1,58 @@
+//! Benchmark instance function calls on receivers whose type is known at
+//! compile time, with and without early-bound calls.
+
+use std::sync::Arc;
+
+use criterion::Criterion;
+use rune::{Context, Diagnostics, Options, Vm};
+
+criterion::criterion_group!(benches, early_bound);
+
+const SOURCE: &str = r#"
+pub fn main(count) {
+    let s = "hello world";
+    let v = [1, 2, 3];
+    let n = 0;
+
+    for _ in 0..count {
+        n += s.len() + v.len();
+    }
+
+    n
+}
+"#;
+
+fn build(early_bound: bool) -> Vm {
+    let context = Context::with_default_modules().expect("failed to build context");
+
+    let mut options = Options::default();
+    options.early_bound(early_bound);
+
+    let mut sources = crate::sources(SOURCE);
+    let mut diagnostics = Diagnostics::new();
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(&options)
+        .build()
+        .expect("program to compile successfully");
+
+    Vm::new(Arc::new(context.runtime()), Arc::new(unit))
+}
+
+fn early_bound(b: &mut Criterion) {
+    let entry = rune::Hash::type_hash(["main"]);
+
+    let mut vm = build(false);
+
+    b.bench_function("late_bound_calls", |b| {
+        b.iter(|| vm.call(entry, (1000,)).expect("failed call"));
+    });
+
+    let mut vm = build(true);
+
+    b.bench_function("early_bound_calls", |b| {
+        b.iter(|| vm.call(entry, (1000,)).expect("failed call"));
+    });
+}
@@ -0,0 +1,29 @@
+//! Construct many objects with the same set of keys and read fields from them.
+
+use criterion::Criterion;
+
+criterion::criterion_group!(benches, objects);
+
+fn objects(b: &mut Criterion) {
+    let mut vm = rune_vm! {
+        struct Point { y, x }
+
+        pub fn main(n) {
+            let sum = 0;
+
+            for i in 0..n {
+                let object = #{ name: "point", z: i, y: i + 1, x: i + 2 };
+                let point = Point { y: object.y, x: object.x };
+                sum += object.z + point.x + point.y;
+            }
+
+            sum
+        }
+    };
+
+    let entry = rune::Hash::type_hash(["main"]);
+
+    b.bench_function("objects", |b| {
+        b.iter(|| vm.call(entry, (1000,)).expect("failed call"));
+    });
+}
@@ -0,0 +1,65 @@
+//! Measure how long it takes to load a serialized unit with many functions,
+//! when only a single function in it is used.
+
+use std::fmt::Write;
+
+use criterion::Criterion;
+
+use rune::runtime::unit::{ArrayUnit, LazyUnit, UnitStorage};
+use rune::{Sources, Unit};
+
+criterion::criterion_group!(benches, unit_loading);
+
+const FUNCTIONS: usize = 1000;
+
+fn sources() -> Sources {
+    let mut source = String::new();
+
+    for n in 0..FUNCTIONS {
+        writeln!(
+            source,
+            "fn f{n}(a) {{ let b = a + {n}; if b > 10 {{ b * 2 }} else {{ [b, a, {n}] }} }}"
+        )
+        .expect("failed to write source");
+    }
+
+    source.push_str("pub fn main(a) { f0(a) }");
+    crate::sources(&source)
+}
+
+fn unit_loading(b: &mut Criterion) {
+    let array = rune::prepare_with::<ArrayUnit>(&mut sources())
+        .build()
+        .expect("failed to build unit");
+    let array = bincode::serialize(&array).expect("failed to serialize unit");
+
+    let lazy = rune::prepare_with::<LazyUnit>(&mut sources())
+        .build()
+        .expect("failed to build unit");
+    let lazy = bincode::serialize(&lazy).expect("failed to serialize unit");
+
+    b.bench_function("unit_loading_array", |b| {
+        b.iter(|| {
+            let unit: Unit<ArrayUnit> =
+                bincode::deserialize(&array).expect("failed to deserialize unit");
+            unit.storage().get(0).expect("bad instruction")
+        });
+    });
+
+    b.bench_function("unit_loading_lazy", |b| {
+        b.iter(|| {
+            let unit: Unit<LazyUnit> =
+                bincode::deserialize(&lazy).expect("failed to deserialize unit");
+            unit.storage().get(0).expect("bad instruction")
+        });
+    });
+
+    b.bench_function("unit_loading_lazy_verified", |b| {
+        b.iter(|| {
+            let unit: Unit<LazyUnit> =
+                bincode::deserialize(&lazy).expect("failed to deserialize unit");
+            unit.verify().expect("failed to verify unit");
+            unit.storage().get(0).expect("bad instruction")
+        });
+    });
+}
@@ -0,0 +1,44 @@
+//! Benchmark conversions between Rust vectors and script vectors.
+
+use criterion::Criterion;
+use rune::runtime::Vec;
+use rune::ToValue;
+
+criterion::criterion_group!(benches, vec_conversion);
+
+fn vec_conversion(b: &mut Criterion) {
+    let values = (0..10_000)
+        .map(|n| n as f64)
+        .collect::<std::vec::Vec<f64>>();
+
+    b.bench_function("vec_to_value_f64", |b| {
+        b.iter(|| {
+            values
+                .clone()
+                .to_value()
+                .into_result()
+                .expect("failed to convert")
+        })
+    });
+
+    b.bench_function("vec_from_slice_f64", |b| {
+        b.iter(|| Vec::from_slice(&values))
+    });
+
+    let vec = Vec::from_slice(&values);
+
+    b.bench_function("vec_from_value_f64", |b| {
+        b.iter(|| {
+            let value = rune::to_value(vec.clone()).expect("failed to convert");
+            rune::from_value::<std::vec::Vec<f64>>(value).expect("failed to convert")
+        })
+    });
+
+    b.bench_function("vec_to_primitives_f64", |b| {
+        b.iter(|| {
+            vec.to_primitives::<f64>()
+                .into_result()
+                .expect("failed to convert")
+        })
+    });
+}
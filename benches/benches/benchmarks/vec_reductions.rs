@@ -0,0 +1,55 @@
+//! Benchmark numeric reductions over vectors, comparing the native fast paths
+//! against folding over the elements in a script.
+
+use criterion::Criterion;
+use rune::runtime::Vec;
+
+criterion::criterion_group!(benches, vec_reductions);
+
+fn vec_reductions(b: &mut Criterion) {
+    let mut vm = rune_vm! {
+        pub fn sum(values) {
+            values.sum()
+        }
+
+        pub fn sum_fold(values) {
+            values.iter().fold(0, |acc, n| acc + n)
+        }
+
+        pub fn max(values) {
+            values.max()
+        }
+
+        pub fn max_fold(values) {
+            values.iter().fold(0, |acc, n| std::cmp::max(acc, n))
+        }
+    };
+
+    let integers = Vec::from_slice(&(0..10_000i64).collect::<std::vec::Vec<_>>());
+    let floats = Vec::from_slice(&(0..10_000).map(|n| n as f64).collect::<std::vec::Vec<_>>());
+
+    let sum = rune::Hash::type_hash(["sum"]);
+    let sum_fold = rune::Hash::type_hash(["sum_fold"]);
+    let max = rune::Hash::type_hash(["max"]);
+    let max_fold = rune::Hash::type_hash(["max_fold"]);
+
+    b.bench_function("vec_sum_i64", |b| {
+        b.iter(|| vm.call(sum, (integers.clone(),)).expect("failed call"))
+    });
+
+    b.bench_function("vec_sum_fold_i64", |b| {
+        b.iter(|| vm.call(sum_fold, (integers.clone(),)).expect("failed call"))
+    });
+
+    b.bench_function("vec_sum_f64", |b| {
+        b.iter(|| vm.call(sum, (floats.clone(),)).expect("failed call"))
+    });
+
+    b.bench_function("vec_max_i64", |b| {
+        b.iter(|| vm.call(max, (integers.clone(),)).expect("failed call"))
+    });
+
+    b.bench_function("vec_max_fold_i64", |b| {
+        b.iter(|| vm.call(max_fold, (integers.clone(),)).expect("failed call"))
+    });
+}
@@ -173,7 +173,7 @@ impl Derive {
     }
 }
 
-fn variant_name(name: &str) -> String {
+pub(crate) fn variant_name(name: &str) -> String {
     let mut out = String::new();
     let mut first = true;
 
@@ -0,0 +1,248 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::inst_display::variant_name;
+
+/// The heading of the section documenting the stack effect of an instruction.
+const OPERATION: &str = "# Operation";
+
+/// The `InstMeta` derive.
+pub struct Derive {
+    input: syn::DeriveInput,
+}
+
+impl syn::parse::Parse for Derive {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            input: input.parse()?,
+        })
+    }
+}
+
+impl Derive {
+    pub(super) fn expand(self) -> Result<TokenStream, Vec<syn::Error>> {
+        let mut errors = Vec::new();
+
+        let syn::Data::Enum(en) = &self.input.data else {
+            errors.push(syn::Error::new_spanned(
+                &self.input.ident,
+                "InstMeta is only supported for enums",
+            ));
+            return Err(errors);
+        };
+
+        let ident = &self.input.ident;
+
+        let mut metas = Vec::new();
+        let mut arms = Vec::new();
+
+        for (index, variant) in en.variants.iter().enumerate() {
+            let variant_ident = &variant.ident;
+
+            let lines = doc_lines(&variant.attrs);
+
+            if lines.iter().all(|line| line.trim().is_empty()) {
+                errors.push(syn::Error::new_spanned(
+                    variant_ident,
+                    "Instructions must be documented",
+                ));
+                continue;
+            }
+
+            let (docs, stack) = match split_operation(&lines) {
+                Ok(split) => split,
+                Err(message) => {
+                    errors.push(syn::Error::new_spanned(variant_ident, message));
+                    continue;
+                }
+            };
+
+            let mut operands = Vec::new();
+
+            for (n, field) in variant.fields.iter().enumerate() {
+                let name = match &field.ident {
+                    Some(ident) => ident.to_string(),
+                    None => n.to_string(),
+                };
+
+                let field_docs = join(&doc_lines(&field.attrs));
+
+                if field_docs.is_empty() {
+                    errors.push(syn::Error::new_spanned(
+                        field,
+                        "Instruction operands must be documented",
+                    ));
+                    continue;
+                }
+
+                let ty = &field.ty;
+                let ty = quote!(#ty).to_string().replace(' ', "");
+
+                operands.push(quote! {
+                    crate::runtime::InstOperand {
+                        name: #name,
+                        ty: #ty,
+                        docs: #field_docs,
+                    }
+                });
+            }
+
+            let stack = match stack {
+                Some((inputs, outputs)) => quote! {
+                    Some(crate::runtime::InstStackEffect {
+                        inputs: &[#(#inputs),*],
+                        outputs: &[#(#outputs),*],
+                    })
+                },
+                None => quote!(None),
+            };
+
+            let name = variant_ident.to_string();
+            let display = variant_name(&name);
+            let docs = join(&docs);
+
+            metas.push(quote! {
+                crate::runtime::InstMeta {
+                    name: #name,
+                    display: #display,
+                    docs: #docs,
+                    operands: &[#(#operands),*],
+                    stack: #stack,
+                }
+            });
+
+            arms.push(quote! {
+                #ident::#variant_ident { .. } => #index
+            });
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let (impl_g, ty_g, where_g) = self.input.generics.split_for_impl();
+
+        Ok(quote! {
+            impl #impl_g #ident #ty_g #where_g {
+                /// Metadata for every instruction, in declaration order.
+                pub const METADATA: &'static [crate::runtime::InstMeta] = &[#(#metas),*];
+
+                /// Get the metadata of this instruction.
+                pub fn meta(&self) -> &'static crate::runtime::InstMeta {
+                    let index = match self {
+                        #(#arms,)*
+                    };
+
+                    &Self::METADATA[index]
+                }
+            }
+        })
+    }
+}
+
+/// Collect the lines of documentation from the given attributes.
+fn doc_lines(attrs: &[syn::Attribute]) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for attr in attrs {
+        if !attr.path().is_ident("doc") {
+            continue;
+        }
+
+        let syn::Meta::NameValue(meta) = &attr.meta else {
+            continue;
+        };
+
+        let syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(s),
+            ..
+        }) = &meta.value
+        else {
+            continue;
+        };
+
+        let value = s.value();
+
+        for line in value.lines() {
+            lines.push(line.strip_prefix(' ').unwrap_or(line).to_owned());
+        }
+    }
+
+    lines
+}
+
+/// Join lines of documentation, trimming surrounding blank lines.
+fn join(lines: &[String]) -> String {
+    lines.join("\n").trim().to_owned()
+}
+
+type StackEffect = (Vec<String>, Vec<String>);
+
+/// Split out the `# Operation` section from the documentation of an
+/// instruction, parsing it into its stack inputs and outputs.
+fn split_operation(lines: &[String]) -> Result<(Vec<String>, Option<StackEffect>), String> {
+    let Some(start) = lines.iter().position(|line| line.trim() == OPERATION) else {
+        return Ok((lines.to_vec(), None));
+    };
+
+    let mut it = lines[start + 1..].iter().enumerate();
+
+    let Some((open, _)) = it.by_ref().find(|(_, line)| !line.trim().is_empty()) else {
+        return Err(format!("Expected a code block after `{OPERATION}`"));
+    };
+
+    if lines[start + 1 + open].trim() != "```text" {
+        return Err(format!("Expected a ```text code block after `{OPERATION}`"));
+    }
+
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+    let mut arrow = false;
+    let mut end = None;
+
+    for (n, line) in it {
+        let line = line.trim();
+
+        if line == "```" {
+            end = Some(start + 1 + n);
+            break;
+        }
+
+        if let Some(rest) = line.strip_prefix("=>") {
+            if arrow {
+                return Err(format!("Multiple `=>` in `{OPERATION}`"));
+            }
+
+            arrow = true;
+            push_effect(&mut outputs, rest);
+        } else if arrow {
+            push_effect(&mut outputs, line);
+        } else {
+            push_effect(&mut inputs, line);
+        }
+    }
+
+    let Some(end) = end else {
+        return Err(format!("Unterminated code block in `{OPERATION}`"));
+    };
+
+    if !arrow {
+        return Err(format!(
+            "Expected `=>` separating inputs from outputs in `{OPERATION}`"
+        ));
+    }
+
+    let mut docs = lines[..start].to_vec();
+    docs.extend(lines[end + 1..].iter().cloned());
+    Ok((docs, Some((inputs, outputs))))
+}
+
+fn push_effect(out: &mut Vec<String>, value: &str) {
+    let value = value.trim();
+
+    if value.is_empty() || value == "*nothing*" || value == "*noop*" {
+        return;
+    }
+
+    out.push(value.to_owned());
+}
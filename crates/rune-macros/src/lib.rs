@@ -35,6 +35,7 @@ mod from_value;
 mod function;
 mod hash;
 mod inst_display;
+mod inst_meta;
 mod instrument;
 mod internals;
 mod macro_;
@@ -250,6 +251,13 @@ pub fn inst_display(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive.expand().unwrap_or_else(to_compile_errors).into()
 }
 
+#[proc_macro_derive(InstMeta)]
+#[doc(hidden)]
+pub fn inst_meta(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let derive = syn::parse_macro_input!(input as inst_meta::Derive);
+    derive.expand().unwrap_or_else(to_compile_errors).into()
+}
+
 fn to_compile_errors<I>(errors: I) -> proc_macro2::TokenStream
 where
     I: IntoIterator<Item = syn::Error>,
@@ -0,0 +1,255 @@
+use std::fmt;
+
+use rune::{Context, ContextError};
+
+/// A capability which a module requires from the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Capability {
+    /// Access to standard input and output.
+    Io,
+    /// Access to the network.
+    Network,
+    /// Access to the filesystem.
+    Filesystem,
+    /// Spawning processes and handling signals.
+    Process,
+    /// Access to timers and the system clock.
+    Time,
+    /// Access to the system source of randomness.
+    Random,
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Capability::Io => write!(f, "io"),
+            Capability::Network => write!(f, "network"),
+            Capability::Filesystem => write!(f, "filesystem"),
+            Capability::Process => write!(f, "process"),
+            Capability::Time => write!(f, "time"),
+            Capability::Random => write!(f, "random"),
+        }
+    }
+}
+
+/// Builder for a [Context] with the modules in this crate installed, where
+/// modules are selected through the capabilities they require rather than one
+/// by one.
+///
+/// Modules which don't require any capability, like `json`, are
+/// always installed if their feature is enabled. All capabilities are enabled
+/// by default.
+///
+/// # Examples
+///
+/// ```
+/// use rune_modules::{ModuleStatus, ModulesBuilder};
+///
+/// let builder = ModulesBuilder::new().with_io(false).with_network(false);
+/// let context = builder.build()?;
+///
+/// let manifest = builder.manifest();
+/// assert_eq!(manifest.status("io"), Some(ModuleStatus::Disabled));
+/// assert!(manifest.enabled().any(|name| name == "core"));
+/// println!("{manifest}");
+/// # Ok::<_, rune::ContextError>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ModulesBuilder {
+    io: bool,
+    network: bool,
+    filesystem: bool,
+    process: bool,
+    time: bool,
+    random: bool,
+}
+
+impl ModulesBuilder {
+    /// Construct a new builder with all capabilities enabled.
+    pub fn new() -> Self {
+        Self {
+            io: true,
+            network: true,
+            filesystem: true,
+            process: true,
+            time: true,
+            random: true,
+        }
+    }
+
+    /// Set if modules accessing standard input and output are installed.
+    ///
+    /// This also controls if the default modules of the context have access
+    /// to standard input and output, see [Context::with_config].
+    pub fn with_io(mut self, enabled: bool) -> Self {
+        self.io = enabled;
+        self
+    }
+
+    /// Set if modules accessing the network are installed.
+    pub fn with_network(mut self, enabled: bool) -> Self {
+        self.network = enabled;
+        self
+    }
+
+    /// Set if modules accessing the filesystem are installed.
+    pub fn with_filesystem(mut self, enabled: bool) -> Self {
+        self.filesystem = enabled;
+        self
+    }
+
+    /// Set if modules spawning processes and handling signals are installed.
+    pub fn with_process(mut self, enabled: bool) -> Self {
+        self.process = enabled;
+        self
+    }
+
+    /// Set if modules accessing timers are installed.
+    pub fn with_time(mut self, enabled: bool) -> Self {
+        self.time = enabled;
+        self
+    }
+
+    /// Set if modules accessing the system source of randomness are
+    /// installed.
+    pub fn with_random(mut self, enabled: bool) -> Self {
+        self.random = enabled;
+        self
+    }
+
+    /// Test if the given capability is enabled.
+    pub fn allows(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::Io => self.io,
+            Capability::Network => self.network,
+            Capability::Filesystem => self.filesystem,
+            Capability::Process => self.process,
+            Capability::Time => self.time,
+            Capability::Random => self.random,
+        }
+    }
+
+    /// Build a context with all the default modules and the modules allowed
+    /// by the current configuration installed.
+    pub fn build(&self) -> Result<Context, ContextError> {
+        let mut context = Context::with_config(self.io)?;
+
+        for entry in self.manifest().entries {
+            if entry.status == ModuleStatus::Enabled {
+                crate::install_module(&mut context, entry.name, self.io)?;
+            }
+        }
+
+        Ok(context)
+    }
+
+    /// Get the manifest of which modules are installed by [ModulesBuilder::build],
+    /// which can be used to audit what a context provides access to.
+    pub fn manifest(&self) -> Manifest {
+        let entries = crate::MODULES
+            .iter()
+            .map(|&(name, capability, available)| {
+                let status = if !available {
+                    ModuleStatus::Unavailable
+                } else if capability.map_or(true, |c| self.allows(c)) {
+                    ModuleStatus::Enabled
+                } else {
+                    ModuleStatus::Disabled
+                };
+
+                ManifestEntry {
+                    name,
+                    capability,
+                    status,
+                }
+            })
+            .collect();
+
+        Manifest { entries }
+    }
+}
+
+impl Default for ModulesBuilder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The status of a module in a [Manifest].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ModuleStatus {
+    /// The module is installed.
+    Enabled,
+    /// The module is not installed, because the capability it requires is
+    /// disabled.
+    Disabled,
+    /// The module is not installed, because the feature it belongs to is not
+    /// enabled.
+    Unavailable,
+}
+
+/// A single module in a [Manifest].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ManifestEntry {
+    /// The name of the module, which is the same as its feature.
+    pub name: &'static str,
+    /// The capability required by the module, if any.
+    pub capability: Option<Capability>,
+    /// The status of the module.
+    pub status: ModuleStatus,
+}
+
+/// A manifest of the modules installed by a [ModulesBuilder].
+///
+/// The [Display][fmt::Display] implementation prints one line per module,
+/// which is suitable for logging.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Iterate over all modules known to this crate.
+    pub fn entries(&self) -> impl Iterator<Item = &ManifestEntry> + '_ {
+        self.entries.iter()
+    }
+
+    /// Iterate over the names of all installed modules.
+    pub fn enabled(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries
+            .iter()
+            .filter(|e| e.status == ModuleStatus::Enabled)
+            .map(|e| e.name)
+    }
+
+    /// Get the status of the module with the given name.
+    pub fn status(&self, name: &str) -> Option<ModuleStatus> {
+        let entry = self.entries.iter().find(|e| e.name == name)?;
+        Some(entry.status)
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            write!(f, "{}: ", entry.name)?;
+
+            match (entry.status, entry.capability) {
+                (ModuleStatus::Enabled, _) => write!(f, "enabled")?,
+                (ModuleStatus::Disabled, Some(capability)) => {
+                    write!(f, "disabled (requires {capability})")?
+                }
+                (ModuleStatus::Disabled, None) => write!(f, "disabled")?,
+                (ModuleStatus::Unavailable, _) => write!(f, "unavailable (feature not enabled)")?,
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
@@ -30,6 +30,7 @@
 use std::io;
 use tokio::fs;
 use rune::{Module, ContextError};
+use rune::runtime::Ref;
 
 /// Construct the `fs` module.
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
@@ -38,6 +39,6 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     Ok(module)
 }
 
-async fn read_to_string(path: String) -> io::Result<String> {
-    fs::read_to_string(&path).await
+async fn read_to_string(path: Ref<str>) -> io::Result<String> {
+    fs::read_to_string(&*path).await
 }
@@ -33,8 +33,10 @@
 //! * [io]
 //! * [json]
 //! * [macros]
+//! * [net]
 //! * [process]
 //! * [rand]
+//! * [regex]
 //! * [signal]
 //! * [test]
 //! * [time]
@@ -53,13 +55,23 @@
 //! * `io` for the [io module][io]
 //! * `json` for the [json module][json]
 //! * `macros` for the [macros module][macros]
+//! * `net` for the [net module][net], which has to be installed explicitly
 //! * `process` for the [process module][process]
 //! * `rand` for the [rand module][rand]
+//! * `regex` for the [regex module][regex]
 //! * `signal` for the [signal module][signal]
 //! * `test` for the [test module][test]
 //! * `time` for the [time module][time]
+//! * `time-tz` for converting dates and times into named timezones in the
+//!   [time module][time]
 //! * `toml` for the [toml module][toml]
 //!
+//! Modules can also be selected through the capabilities they require, like
+//! access to the network or the filesystem, using [ModulesBuilder]. It also
+//! produces a manifest of which modules were installed, which can be used to
+//! audit what scripts have access to.
+//!
+//! [ModulesBuilder]: https://docs.rs/rune-modules/0/rune_modules/struct.ModulesBuilder.html
 //! [core]: https://docs.rs/rune-modules/0/rune_modules/core/
 //! [experiments]: https://docs.rs/rune-modules/0/rune_modules/experiments/
 //! [fmt]: https://docs.rs/rune-modules/0/rune_modules/fmt/
@@ -68,8 +80,10 @@
 //! [io]: https://docs.rs/rune-modules/0/rune_modules/io/
 //! [json]: https://docs.rs/rune-modules/0/rune_modules/json/
 //! [macros]: https://docs.rs/rune-modules/0/rune_modules/macros/
+//! [net]: https://docs.rs/rune-modules/0/rune_modules/net/
 //! [process]: https://docs.rs/rune-modules/0/rune_modules/process/
 //! [rand]: https://docs.rs/rune-modules/0/rune_modules/rand/
+//! [regex]: https://docs.rs/rune-modules/0/rune_modules/regex/
 //! [signal]: https://docs.rs/rune-modules/0/rune_modules/signal/
 //! [test]: https://docs.rs/rune-modules/0/rune_modules/test/
 //! [time]: https://docs.rs/rune-modules/0/rune_modules/time/
@@ -82,13 +96,45 @@
 #[cfg(feature = "experiments")]
 pub mod experiments;
 
+#[cfg(feature = "net")]
+pub mod net;
+
+mod builder;
+pub use self::builder::{Capability, Manifest, ManifestEntry, ModuleStatus, ModulesBuilder};
+
 macro_rules! modules {
-    ($($ident:ident, $name:literal),* $(,)?) => {
+    ($($ident:ident, $name:literal, $capability:expr),* $(,)?) => {
         $(
             #[cfg(feature = $name)]
             pub mod $ident;
         )*
 
+        /// All modules in this crate, the capability they require and if
+        /// their feature is enabled.
+        const MODULES: &[(&str, Option<Capability>, bool)] = &[
+            $(($name, $capability, cfg!(feature = $name)),)*
+        ];
+
+        /// Install the module with the given name, if its feature is enabled.
+        fn install_module(
+            #[allow(unused)] context: &mut rune::Context,
+            name: &str,
+            #[allow(unused)] stdio: bool,
+        ) -> Result<(), rune::ContextError> {
+            match name {
+                $(
+                    #[allow(deprecated)]
+                    #[cfg(feature = $name)]
+                    $name => {
+                        context.install(self::$ident::module(stdio)?)?;
+                    }
+                )*
+                _ => {}
+            }
+
+            Ok(())
+        }
+
         /// Construct a a default rune context with all enabled modules provided
         /// based on the [default rune
         /// context](rune::Context::with_default_modules).
@@ -115,17 +161,18 @@ macro_rules! modules {
 }
 
 modules! {
-    core, "core",
-    fmt, "fmt",
-    fs, "fs",
-    http, "http",
-    io, "io",
-    json, "json",
-    macros, "macros",
-    process, "process",
-    rand, "rand",
-    signal, "signal",
-    test, "test",
-    time, "time",
-    toml, "toml",
+    core, "core", None,
+    fmt, "fmt", None,
+    fs, "fs", Some(Capability::Filesystem),
+    http, "http", Some(Capability::Network),
+    io, "io", Some(Capability::Io),
+    json, "json", None,
+    macros, "macros", None,
+    process, "process", Some(Capability::Process),
+    rand, "rand", Some(Capability::Random),
+    regex, "regex", None,
+    signal, "signal", Some(Capability::Process),
+    test, "test", None,
+    time, "time", Some(Capability::Time),
+    toml, "toml", None,
 }
@@ -0,0 +1,625 @@
+//! The native `std::net::tcp` and `std::net::udp` modules for the [Rune
+//! Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! These provide raw asynchronous sockets, which scripts can only use to
+//! connect to, bind, and accept traffic from addresses approved by a [Policy]
+//! provided by the host.
+//! Since access is decided by the policy, they are never installed as part of
+//! [`with_config`][crate::with_config] or [ModulesBuilder][crate::ModulesBuilder]
+//! and have to be installed explicitly.
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["net"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! use rune_modules::net::{Access, Policy};
+//!
+//! let policy = Policy::new(|access| match access {
+//!     Access::TcpConnect(addr) => addr.ip().is_loopback(),
+//!     _ => false,
+//! });
+//!
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::net::tcp_module(&policy)?)?;
+//! context.install(rune_modules::net::udp_module(&policy)?)?;
+//! # Ok::<_, rune::Error>(())
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use std::net::tcp;
+//!
+//! pub async fn main() {
+//!     let stream = tcp::connect("127.0.0.1:8080").await?;
+//!     stream.set_timeout(Some(1000));
+//!     stream.write_all(b"ping").await?;
+//!     let reply = stream.read(1024).await?;
+//!     dbg(reply);
+//! }
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rune::runtime::{Bytes, Ref};
+use rune::{Any, ContextError, Module};
+use tokio::net;
+
+/// An attempt by a script to access the network, which has to be approved by
+/// the [Policy] of the module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Access {
+    /// Connecting a TCP stream to the given address.
+    TcpConnect(SocketAddr),
+    /// Binding a TCP listener to the given address.
+    TcpBind(SocketAddr),
+    /// Binding a UDP socket to the given address.
+    UdpBind(SocketAddr),
+    /// Connecting a UDP socket to, or sending a datagram to the given address.
+    UdpSend(SocketAddr),
+    /// Accepting a TCP connection from a peer with the given address.
+    TcpAccept(SocketAddr),
+    /// Receiving a datagram sent from the given address.
+    UdpRecv(SocketAddr),
+}
+
+impl fmt::Display for Access {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Access::TcpConnect(addr) => write!(f, "tcp connect to {addr}"),
+            Access::TcpBind(addr) => write!(f, "tcp bind to {addr}"),
+            Access::UdpBind(addr) => write!(f, "udp bind to {addr}"),
+            Access::UdpSend(addr) => write!(f, "udp send to {addr}"),
+            Access::TcpAccept(addr) => write!(f, "tcp accept from {addr}"),
+            Access::UdpRecv(addr) => write!(f, "udp receive from {addr}"),
+        }
+    }
+}
+
+/// A host policy deciding which addresses scripts are allowed to connect to
+/// and bind, and which peers they're allowed to accept traffic from.
+///
+/// Host names are resolved before the policy is consulted, so the policy is
+/// called once for every address a name resolves to and only approved
+/// addresses are used.
+///
+/// Inbound traffic is checked as it arrives. Connections from peers which
+/// aren't approved with [Access::TcpAccept] are closed, and datagrams from
+/// peers which aren't approved with [Access::UdpRecv] are discarded, without
+/// being seen by the script.
+#[derive(Clone)]
+pub struct Policy {
+    inner: Arc<dyn Fn(Access) -> bool + Send + Sync>,
+}
+
+impl Policy {
+    /// Construct a policy from a callback approving each access.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(Access) -> bool + Send + Sync + 'static,
+    {
+        Self { inner: Arc::new(f) }
+    }
+
+    /// Test if the given access is allowed.
+    pub fn allows(&self, access: Access) -> bool {
+        (self.inner)(access)
+    }
+
+    /// Resolve the given address, keeping the addresses the policy allows.
+    async fn resolve(
+        &self,
+        addr: &str,
+        access: fn(SocketAddr) -> Access,
+    ) -> io::Result<Vec<SocketAddr>> {
+        let mut allowed = Vec::new();
+
+        for resolved in net::lookup_host(addr).await? {
+            if self.allows(access(resolved)) {
+                allowed.push(resolved);
+            }
+        }
+
+        if allowed.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("Access to `{addr}` denied by policy"),
+            ));
+        }
+
+        Ok(allowed)
+    }
+}
+
+impl fmt::Debug for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Policy").finish_non_exhaustive()
+    }
+}
+
+/// Construct the `std::net::tcp` module.
+pub fn tcp_module(policy: &Policy) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["net", "tcp"]);
+    module.ty::<TcpStream>()?;
+    module.ty::<TcpListener>()?;
+
+    let p = policy.clone();
+
+    module
+        .async_function(["connect"], move |addr: &str| {
+            let (policy, addr) = (p.clone(), addr.to_owned());
+            async move { TcpStream::connect(&policy, &addr, None).await }
+        })?
+        .docs([
+            "Connect to the given address, like `\"localhost:8080\"`.",
+            "",
+            "Errors if the policy of the host doesn't allow connecting to any of the",
+            "addresses the host name resolves to.",
+        ]);
+
+    let p = policy.clone();
+
+    module
+        .async_function(["connect_timeout"], move |addr: &str, millis: u64| {
+            let (policy, addr) = (p.clone(), addr.to_owned());
+            let timeout = Some(Duration::from_millis(millis));
+            async move { TcpStream::connect(&policy, &addr, timeout).await }
+        })?
+        .docs([
+            "Connect to the given address, failing if it takes longer than the given",
+            "number of milliseconds.",
+        ]);
+
+    let p = policy.clone();
+
+    module
+        .async_function(["bind"], move |addr: &str| {
+            let (policy, addr) = (p.clone(), addr.to_owned());
+            async move { TcpListener::bind(policy, &addr).await }
+        })?
+        .docs(["Bind a listener to the given address, like `\"127.0.0.1:0\"`."]);
+
+    module.function_meta(TcpListener::accept)?;
+    module.function_meta(TcpListener::local_addr)?;
+    module.function_meta(TcpListener::set_timeout)?;
+    module.function_meta(TcpStream::read)?;
+    module.function_meta(TcpStream::write)?;
+    module.function_meta(TcpStream::write_all)?;
+    module.function_meta(TcpStream::peer_addr)?;
+    module.function_meta(TcpStream::local_addr)?;
+    module.function_meta(TcpStream::set_timeout)?;
+    Ok(module)
+}
+
+/// Construct the `std::net::udp` module.
+pub fn udp_module(policy: &Policy) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["net", "udp"]);
+    module.ty::<UdpSocket>()?;
+
+    let p = policy.clone();
+
+    module
+        .async_function(["bind"], move |addr: &str| {
+            let (policy, addr) = (p.clone(), addr.to_owned());
+            async move { UdpSocket::bind(policy, &addr).await }
+        })?
+        .docs(["Bind a socket to the given address, like `\"127.0.0.1:0\"`."]);
+
+    module.function_meta(UdpSocket::connect)?;
+    module.function_meta(UdpSocket::send)?;
+    module.function_meta(UdpSocket::send_to)?;
+    module.function_meta(UdpSocket::recv)?;
+    module.function_meta(UdpSocket::recv_from)?;
+    module.function_meta(UdpSocket::local_addr)?;
+    module.function_meta(UdpSocket::set_timeout)?;
+    Ok(module)
+}
+
+/// A TCP stream connected to a peer.
+#[derive(Any)]
+#[rune(item = ::std::net::tcp)]
+struct TcpStream {
+    inner: net::TcpStream,
+    timeout: Option<Duration>,
+}
+
+impl TcpStream {
+    async fn connect(policy: &Policy, addr: &str, timeout: Option<Duration>) -> io::Result<Self> {
+        let allowed = policy.resolve(addr, Access::TcpConnect).await?;
+        let inner = with_timeout(timeout, net::TcpStream::connect(&allowed[..])).await?;
+        Ok(Self {
+            inner,
+            timeout: None,
+        })
+    }
+
+    /// Read at most `max` bytes from the stream.
+    ///
+    /// Returns empty bytes once the stream has been closed by the peer.
+    #[rune::function(instance, path = Self::read)]
+    async fn read(this: Ref<Self>, max: usize) -> io::Result<Bytes> {
+        with_timeout(this.timeout, async {
+            let mut buf = vec![0; max];
+
+            loop {
+                this.inner.readable().await?;
+
+                match this.inner.try_read(&mut buf) {
+                    Ok(n) => {
+                        buf.truncate(n);
+                        return Ok(Bytes::from_vec(buf));
+                    }
+                    Err(error) if error.kind() == io::ErrorKind::WouldBlock => continue,
+                    Err(error) => return Err(error),
+                }
+            }
+        })
+        .await
+    }
+
+    /// Write bytes to the stream, returning how many were written.
+    #[rune::function(instance, path = Self::write)]
+    async fn write(this: Ref<Self>, bytes: Ref<[u8]>) -> io::Result<usize> {
+        with_timeout(this.timeout, this.write_some(&bytes)).await
+    }
+
+    /// Write all bytes to the stream.
+    #[rune::function(instance, path = Self::write_all)]
+    async fn write_all(this: Ref<Self>, bytes: Ref<[u8]>) -> io::Result<()> {
+        with_timeout(this.timeout, async {
+            let mut bytes = &bytes[..];
+
+            while !bytes.is_empty() {
+                let n = this.write_some(bytes).await?;
+                bytes = &bytes[n..];
+            }
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// The address of the peer the stream is connected to.
+    #[rune::function(instance)]
+    fn peer_addr(&self) -> io::Result<String> {
+        Ok(self.inner.peer_addr()?.to_string())
+    }
+
+    /// The local address of the stream.
+    #[rune::function(instance)]
+    fn local_addr(&self) -> io::Result<String> {
+        Ok(self.inner.local_addr()?.to_string())
+    }
+
+    /// Set the timeout in milliseconds for reads and writes, or `None` to wait
+    /// indefinitely.
+    #[rune::function(instance)]
+    fn set_timeout(&mut self, millis: Option<u64>) {
+        self.timeout = millis.map(Duration::from_millis);
+    }
+
+    async fn write_some(&self, bytes: &[u8]) -> io::Result<usize> {
+        loop {
+            self.inner.writable().await?;
+
+            match self.inner.try_write(bytes) {
+                Ok(n) => return Ok(n),
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+/// A TCP listener accepting connections.
+#[derive(Any)]
+#[rune(item = ::std::net::tcp)]
+struct TcpListener {
+    inner: net::TcpListener,
+    policy: Policy,
+    timeout: Option<Duration>,
+}
+
+impl TcpListener {
+    async fn bind(policy: Policy, addr: &str) -> io::Result<Self> {
+        let allowed = policy.resolve(addr, Access::TcpBind).await?;
+
+        Ok(Self {
+            inner: net::TcpListener::bind(&allowed[..]).await?,
+            policy,
+            timeout: None,
+        })
+    }
+
+    /// Accept a new connection, returning the stream and the address of the
+    /// peer.
+    ///
+    /// Connections from peers which the policy of the host doesn't allow are
+    /// closed, and the listener keeps waiting for the next one.
+    #[rune::function(instance, path = Self::accept)]
+    async fn accept(this: Ref<Self>) -> io::Result<(TcpStream, String)> {
+        let (stream, addr) = this.accept_allowed().await?;
+        Ok((stream, addr.to_string()))
+    }
+
+    /// The local address the listener is bound to.
+    #[rune::function(instance)]
+    fn local_addr(&self) -> io::Result<String> {
+        Ok(self.inner.local_addr()?.to_string())
+    }
+
+    /// Set the timeout in milliseconds for accepting connections, or `None` to
+    /// wait indefinitely.
+    #[rune::function(instance)]
+    fn set_timeout(&mut self, millis: Option<u64>) {
+        self.timeout = millis.map(Duration::from_millis);
+    }
+
+    async fn accept_allowed(&self) -> io::Result<(TcpStream, SocketAddr)> {
+        with_timeout(self.timeout, async {
+            loop {
+                let (inner, addr) = self.inner.accept().await?;
+
+                if !self.policy.allows(Access::TcpAccept(addr)) {
+                    continue;
+                }
+
+                let stream = TcpStream {
+                    inner,
+                    timeout: self.timeout,
+                };
+
+                return Ok((stream, addr));
+            }
+        })
+        .await
+    }
+}
+
+/// A UDP socket.
+#[derive(Any)]
+#[rune(item = ::std::net::udp)]
+struct UdpSocket {
+    inner: net::UdpSocket,
+    policy: Policy,
+    timeout: Option<Duration>,
+}
+
+impl UdpSocket {
+    async fn bind(policy: Policy, addr: &str) -> io::Result<Self> {
+        let allowed = policy.resolve(addr, Access::UdpBind).await?;
+
+        Ok(Self {
+            inner: net::UdpSocket::bind(&allowed[..]).await?,
+            policy,
+            timeout: None,
+        })
+    }
+
+    /// Connect the socket to the given address, which is where datagrams are
+    /// sent with `send` and received from with `recv`.
+    #[rune::function(instance, path = Self::connect)]
+    async fn connect(this: Ref<Self>, addr: Ref<str>) -> io::Result<()> {
+        let allowed = this.policy.resolve(&addr, Access::UdpSend).await?;
+        this.inner.connect(&allowed[..]).await
+    }
+
+    /// Send a datagram to the address the socket is connected to.
+    #[rune::function(instance, path = Self::send)]
+    async fn send(this: Ref<Self>, bytes: Ref<[u8]>) -> io::Result<usize> {
+        with_timeout(this.timeout, this.inner.send(&bytes)).await
+    }
+
+    /// Send a datagram to the given address.
+    ///
+    /// Errors if the policy of the host doesn't allow sending to any of the
+    /// addresses the host name resolves to.
+    #[rune::function(instance, path = Self::send_to)]
+    async fn send_to(this: Ref<Self>, bytes: Ref<[u8]>, addr: Ref<str>) -> io::Result<usize> {
+        with_timeout(this.timeout, this.send_to_allowed(&bytes, &addr)).await
+    }
+
+    /// Receive a datagram of at most `max` bytes from the address the socket
+    /// is connected to.
+    ///
+    /// Datagrams from peers which the policy of the host doesn't allow are
+    /// discarded.
+    #[rune::function(instance, path = Self::recv)]
+    async fn recv(this: Ref<Self>, max: usize) -> io::Result<Bytes> {
+        let mut buf = vec![0; max];
+        let (n, _) = with_timeout(this.timeout, this.recv_allowed(&mut buf)).await?;
+        buf.truncate(n);
+        Ok(Bytes::from_vec(buf))
+    }
+
+    /// Receive a datagram of at most `max` bytes, returning it and the address
+    /// it was sent from.
+    ///
+    /// Datagrams from peers which the policy of the host doesn't allow are
+    /// discarded.
+    #[rune::function(instance, path = Self::recv_from)]
+    async fn recv_from(this: Ref<Self>, max: usize) -> io::Result<(Bytes, String)> {
+        let mut buf = vec![0; max];
+        let (n, addr) = with_timeout(this.timeout, this.recv_allowed(&mut buf)).await?;
+        buf.truncate(n);
+        Ok((Bytes::from_vec(buf), addr.to_string()))
+    }
+
+    /// The local address of the socket.
+    #[rune::function(instance)]
+    fn local_addr(&self) -> io::Result<String> {
+        Ok(self.inner.local_addr()?.to_string())
+    }
+
+    /// Set the timeout in milliseconds for sending and receiving, or `None` to
+    /// wait indefinitely.
+    #[rune::function(instance)]
+    fn set_timeout(&mut self, millis: Option<u64>) {
+        self.timeout = millis.map(Duration::from_millis);
+    }
+
+    /// Send to the first of the allowed addresses which accepts the datagram,
+    /// like connecting does.
+    async fn send_to_allowed(&self, bytes: &[u8], addr: &str) -> io::Result<usize> {
+        let mut last_error = None;
+
+        for addr in self.policy.resolve(addr, Access::UdpSend).await? {
+            match self.inner.send_to(bytes, addr).await {
+                Ok(n) => return Ok(n),
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "No addresses to send to")
+        }))
+    }
+
+    async fn recv_allowed(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        loop {
+            let (n, addr) = self.inner.recv_from(buf).await?;
+
+            if self.policy.allows(Access::UdpRecv(addr)) {
+                return Ok((n, addr));
+            }
+        }
+    }
+}
+
+/// Run the given operation, failing if it doesn't complete within the timeout.
+async fn with_timeout<T>(
+    timeout: Option<Duration>,
+    future: impl Future<Output = io::Result<T>>,
+) -> io::Result<T> {
+    let Some(timeout) = timeout else {
+        return future.await;
+    };
+
+    match tokio::time::timeout(timeout, future).await {
+        Ok(result) => result,
+        Err(..) => Err(io::Error::new(
+            io::ErrorKind::TimedOut,
+            "Operation timed out",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::time::Duration;
+
+    use tokio::io::AsyncReadExt;
+    use tokio::net;
+
+    use super::{Access, Policy, TcpListener, TcpStream, UdpSocket};
+
+    const TIMEOUT: Option<Duration> = Some(Duration::from_millis(100));
+
+    #[tokio::test]
+    async fn test_outbound_denied() {
+        let policy = Policy::new(|access| matches!(access, Access::UdpBind(..)));
+
+        let error = TcpStream::connect(&policy, "127.0.0.1:80", None)
+            .await
+            .err()
+            .expect("connect should be denied");
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+
+        let error = TcpListener::bind(policy.clone(), "127.0.0.1:0")
+            .await
+            .err()
+            .expect("bind should be denied");
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+
+        let socket = UdpSocket::bind(policy, "127.0.0.1:0").await.unwrap();
+
+        let error = socket
+            .send_to_allowed(b"ping", "127.0.0.1:80")
+            .await
+            .expect_err("send should be denied");
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_tcp_accept_allowed() {
+        let policy = Policy::new(|_| true);
+
+        let listener = TcpListener::bind(policy, "127.0.0.1:0").await.unwrap();
+        let addr = listener.inner.local_addr().unwrap();
+        let client = net::TcpStream::connect(addr).await.unwrap();
+
+        let (_, peer) = listener.accept_allowed().await.unwrap();
+        assert_eq!(peer, client.local_addr().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_tcp_accept_denied() {
+        let policy = Policy::new(|access| !matches!(access, Access::TcpAccept(..)));
+
+        let mut listener = TcpListener::bind(policy, "127.0.0.1:0").await.unwrap();
+        listener.timeout = TIMEOUT;
+
+        let addr = listener.inner.local_addr().unwrap();
+        let mut client = net::TcpStream::connect(addr).await.unwrap();
+
+        let error = listener
+            .accept_allowed()
+            .await
+            .err()
+            .expect("accept should time out");
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+
+        // The denied connection is closed by the listener.
+        let mut buf = [0; 1];
+        assert_eq!(client.read(&mut buf).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_udp_recv_allowed() {
+        let policy = Policy::new(|_| true);
+
+        let socket = UdpSocket::bind(policy, "127.0.0.1:0").await.unwrap();
+        let addr = socket.inner.local_addr().unwrap();
+
+        let peer = net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        peer.send_to(b"ping", addr).await.unwrap();
+
+        let mut buf = [0; 16];
+        let (n, from) = socket.recv_allowed(&mut buf).await.unwrap();
+        assert_eq!(&buf[..n], b"ping");
+        assert_eq!(from, peer.local_addr().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_udp_recv_denied() {
+        let policy = Policy::new(|access| !matches!(access, Access::UdpRecv(..)));
+
+        let socket = UdpSocket::bind(policy, "127.0.0.1:0").await.unwrap();
+        let addr = socket.inner.local_addr().unwrap();
+
+        let peer = net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        peer.send_to(b"ping", addr).await.unwrap();
+
+        let mut buf = [0; 16];
+        let error = super::with_timeout(TIMEOUT, socket.recv_allowed(&mut buf))
+            .await
+            .expect_err("datagram should be discarded");
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    }
+}
@@ -0,0 +1,526 @@
+//! The native `regex` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.12.3", features = ["regex"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::regex::module(true)?)?;
+//! # Ok::<_, rune::Error>(())
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use regex::Regex;
+//!
+//! fn main() {
+//!     let re = Regex::new(r"(?<key>\w+)=(?<value>\d+)")?;
+//!
+//!     for caps in re.captures_iter("a=1, b=2") {
+//!         println(`${caps.name("key")?} is ${caps.name("value")?}`);
+//!     }
+//! }
+//! ```
+//!
+//! Matches and capture groups are values of the [Match] and [Captures] types,
+//! which can be converted back into Rust with [rune::from_value] when they are
+//! returned to the host.
+
+use std::fmt::{self, Write};
+use std::sync::Arc;
+
+use rune::runtime::{Formatter, Iterator};
+use rune::{Any, ContextError, Module};
+
+/// Construct the `regex` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("regex");
+    module.ty::<Regex>()?;
+    module.ty::<Match>()?;
+    module.ty::<Captures>()?;
+    module.ty::<Error>()?;
+
+    module.function_meta(Regex::new__meta)?;
+    module.function_meta(Regex::is_match__meta)?;
+    module.function_meta(Regex::find__meta)?;
+    module.function_meta(Regex::find_iter__meta)?;
+    module.function_meta(Regex::captures__meta)?;
+    module.function_meta(Regex::captures_iter__meta)?;
+    module.function_meta(Regex::replace__meta)?;
+    module.function_meta(Regex::replace_all__meta)?;
+    module.function_meta(Regex::split__meta)?;
+    module.function_meta(Regex::as_str__meta)?;
+    module.function_meta(Regex::string_display__meta)?;
+    module.function_meta(Regex::string_debug__meta)?;
+
+    module.function_meta(Match::as_str__meta)?;
+    module.function_meta(Match::start__meta)?;
+    module.function_meta(Match::end__meta)?;
+    module.function_meta(Match::string_display__meta)?;
+    module.function_meta(Match::string_debug__meta)?;
+
+    module.function_meta(Captures::get__meta)?;
+    module.function_meta(Captures::name__meta)?;
+    module.function_meta(Captures::len__meta)?;
+    module.function_meta(Captures::is_empty__meta)?;
+    module.function_meta(Captures::iter__meta)?;
+    module.function_meta(Captures::into_iter__meta)?;
+    module.function_meta(Captures::to_vec__meta)?;
+    module.function_meta(Captures::string_debug__meta)?;
+
+    module.function_meta(Error::string_display__meta)?;
+    Ok(module)
+}
+
+/// A compiled regular expression.
+#[derive(Debug, Clone, Any)]
+#[rune(item = ::regex)]
+pub struct Regex {
+    inner: regex::Regex,
+}
+
+impl Regex {
+    /// Compile a regular expression.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(r"\d+")?;
+    /// assert!(Regex::new(r"(unclosed").is_err());
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    pub fn new(pattern: &str) -> Result<Self, Error> {
+        match regex::Regex::new(pattern) {
+            Ok(inner) => Ok(Self { inner }),
+            Err(error) => Err(Error { inner: error }),
+        }
+    }
+
+    /// Test if the regular expression matches anywhere in `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(r"^\d{4}-\d{2}-\d{2}$")?;
+    /// assert!(re.is_match("2023-06-01"));
+    /// assert!(!re.is_match("June 1st"));
+    /// ```
+    #[rune::function(keep, instance)]
+    pub fn is_match(&self, text: &str) -> bool {
+        self.inner.is_match(text)
+    }
+
+    /// Find the leftmost match in `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(r"\d+")?;
+    /// let m = re.find("abc 123 def")?;
+    /// assert_eq!(m.as_str(), "123");
+    /// assert_eq!(m.start(), 4);
+    /// assert_eq!(m.end(), 7);
+    /// ```
+    #[rune::function(keep, instance)]
+    pub fn find(&self, text: &str) -> Option<Match> {
+        let m = self.inner.find(text)?;
+        Some(Match::new(text.into(), m))
+    }
+
+    /// Iterate over every non-overlapping match in `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(r"\d+")?;
+    /// let numbers = re.find_iter("1, 22 and 333").map(|m| m.as_str()).collect::<Vec>();
+    /// assert_eq!(numbers, ["1", "22", "333"]);
+    /// ```
+    #[rune::function(keep, instance)]
+    pub fn find_iter(&self, text: &str) -> Iterator {
+        let haystack: Arc<str> = text.into();
+
+        let matches = self
+            .inner
+            .find_iter(text)
+            .map(|m| Match::new(haystack.clone(), m))
+            .collect::<Vec<_>>();
+
+        Iterator::from_double_ended("regex::FindIter", matches.into_iter())
+    }
+
+    /// Find the capture groups of the leftmost match in `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(r"(?<year>\d{4})-(?<month>\d{2})")?;
+    /// let caps = re.captures("Released 2023-06")?;
+    /// assert_eq!(caps.get(0)?.as_str(), "2023-06");
+    /// assert_eq!(caps.name("year")?.as_str(), "2023");
+    /// assert_eq!(caps.get(2)?.as_str(), "06");
+    /// ```
+    #[rune::function(keep, instance)]
+    pub fn captures(&self, text: &str) -> Option<Captures> {
+        let caps = self.inner.captures(text)?;
+        Some(Captures::new(&self.inner, text.into(), &caps))
+    }
+
+    /// Iterate over the capture groups of every non-overlapping match in
+    /// `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(r"(\w+)=(\d+)")?;
+    ///
+    /// let pairs = re
+    ///     .captures_iter("a=1 b=2")
+    ///     .map(|caps| (caps.get(1)?.as_str(), caps.get(2)?.as_str()))
+    ///     .collect::<Vec>();
+    ///
+    /// assert_eq!(pairs, [("a", "1"), ("b", "2")]);
+    /// ```
+    #[rune::function(keep, instance)]
+    pub fn captures_iter(&self, text: &str) -> Iterator {
+        let haystack: Arc<str> = text.into();
+
+        let captures = self
+            .inner
+            .captures_iter(text)
+            .map(|caps| Captures::new(&self.inner, haystack.clone(), &caps))
+            .collect::<Vec<_>>();
+
+        Iterator::from_double_ended("regex::CapturesIter", captures.into_iter())
+    }
+
+    /// Replace the leftmost match in `text` with `replacement`.
+    ///
+    /// The replacement can refer to capture groups by index or name, like `$1`
+    /// or `${name}`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(r"(\w+) (\w+)")?;
+    /// assert_eq!(re.replace("hello world", "$2 $1"), "world hello");
+    /// ```
+    #[rune::function(keep, instance)]
+    pub fn replace(&self, text: &str, replacement: &str) -> String {
+        self.inner.replace(text, replacement).into_owned()
+    }
+
+    /// Replace every non-overlapping match in `text` with `replacement`.
+    ///
+    /// The replacement can refer to capture groups by index or name, like `$1`
+    /// or `${name}`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(r"\s+")?;
+    /// assert_eq!(re.replace_all("a  b \t c", " "), "a b c");
+    ///
+    /// let re = Regex::new(r"(?<key>\w+)=(?<value>\w+)")?;
+    /// assert_eq!(re.replace_all("a=1 b=2", "${value}=${key}"), "1=a 2=b");
+    /// ```
+    #[rune::function(keep, instance)]
+    pub fn replace_all(&self, text: &str, replacement: &str) -> String {
+        self.inner.replace_all(text, replacement).into_owned()
+    }
+
+    /// Split `text` by every match of the regular expression.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(r"[,;]\s*")?;
+    /// assert_eq!(re.split("a, b;c"), ["a", "b", "c"]);
+    /// ```
+    #[rune::function(keep, instance)]
+    pub fn split(&self, text: &str) -> Vec<String> {
+        self.inner.split(text).map(String::from).collect()
+    }
+
+    /// The pattern the regular expression was compiled from.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// assert_eq!(Regex::new(r"\d+")?.as_str(), r"\d+");
+    /// ```
+    #[rune::function(keep, instance)]
+    pub fn as_str(&self) -> String {
+        self.inner.as_str().to_owned()
+    }
+
+    #[rune::function(keep, instance, protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.inner)
+    }
+
+    #[rune::function(keep, instance, protocol = STRING_DEBUG)]
+    fn string_debug(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
+/// A single match of a [Regex] in some text.
+#[derive(Debug, Clone, Any)]
+#[rune(item = ::regex)]
+pub struct Match {
+    haystack: Arc<str>,
+    start: usize,
+    end: usize,
+}
+
+impl Match {
+    fn new(haystack: Arc<str>, m: regex::Match<'_>) -> Self {
+        Self {
+            haystack,
+            start: m.start(),
+            end: m.end(),
+        }
+    }
+
+    /// The matched text.
+    #[rune::function(keep, instance)]
+    pub fn as_str(&self) -> String {
+        self.haystack[self.start..self.end].to_owned()
+    }
+
+    /// The byte offset of the start of the match.
+    #[rune::function(keep, instance)]
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The byte offset of the end of the match, which is exclusive.
+    #[rune::function(keep, instance)]
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    #[rune::function(keep, instance, protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", &self.haystack[self.start..self.end])
+    }
+
+    #[rune::function(keep, instance, protocol = STRING_DEBUG)]
+    fn string_debug(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Match {{ start: {}, end: {}, string: {:?} }}",
+            self.start,
+            self.end,
+            &self.haystack[self.start..self.end]
+        )
+    }
+}
+
+/// The capture groups of a match of a [Regex].
+///
+/// The group at index `0` always corresponds to the whole match, while groups
+/// which didn't participate in the match are `None`.
+#[derive(Debug, Clone, Any)]
+#[rune(item = ::regex)]
+pub struct Captures {
+    regex: regex::Regex,
+    groups: Vec<Option<Match>>,
+}
+
+impl Captures {
+    fn new(regex: &regex::Regex, haystack: Arc<str>, caps: &regex::Captures<'_>) -> Self {
+        let groups = caps
+            .iter()
+            .map(|m| Some(Match::new(haystack.clone(), m?)))
+            .collect();
+
+        Self {
+            regex: regex.clone(),
+            groups,
+        }
+    }
+
+    /// Get the group at the given index, if it participated in the match.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(r"(a)|(b)")?;
+    /// let caps = re.captures("b")?;
+    /// assert!(caps.get(1).is_none());
+    /// assert_eq!(caps.get(2)?.as_str(), "b");
+    /// ```
+    #[rune::function(keep, instance)]
+    pub fn get(&self, index: usize) -> Option<Match> {
+        self.groups.get(index)?.clone()
+    }
+
+    /// Get the group with the given name, if it participated in the match.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(r"(?<word>\w+)")?;
+    /// let caps = re.captures("hello")?;
+    /// assert_eq!(caps.name("word")?.as_str(), "hello");
+    /// assert!(caps.name("missing").is_none());
+    /// ```
+    #[rune::function(keep, instance)]
+    pub fn name(&self, name: &str) -> Option<Match> {
+        let index = self.regex.capture_names().position(|n| n == Some(name))?;
+
+        self.get(index)
+    }
+
+    /// The number of groups, including the group of the whole match and
+    /// groups which didn't participate in it.
+    #[rune::function(keep, instance)]
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Test if there are no groups, which is never the case since the group of
+    /// the whole match is always present.
+    #[rune::function(keep, instance)]
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Iterate over the groups, where groups which didn't participate in the
+    /// match are `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(r"(\d)(x)?")?;
+    /// let groups = re.captures("1")?.iter().map(|m| m.map(|m| m.as_str())).collect::<Vec>();
+    /// assert_eq!(groups, [Some("1"), Some("1"), None]);
+    /// ```
+    #[rune::function(keep, instance)]
+    pub fn iter(&self) -> Iterator {
+        Iterator::from_double_ended("regex::Captures", self.groups.clone().into_iter())
+    }
+
+    #[rune::function(keep, instance, protocol = INTO_ITER)]
+    fn into_iter(&self) -> Iterator {
+        self.iter()
+    }
+
+    /// Convert the groups into a vector of the strings they matched.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(r"(\w+)@(\w+)")?;
+    /// assert_eq!(re.captures("me@home")?.to_vec(), [Some("me@home"), Some("me"), Some("home")]);
+    /// ```
+    #[rune::function(keep, instance)]
+    pub fn to_vec(&self) -> Vec<Option<String>> {
+        self.groups
+            .iter()
+            .map(|m| Some(m.as_ref()?.as_str()))
+            .collect()
+    }
+
+    #[rune::function(keep, instance, protocol = STRING_DEBUG)]
+    fn string_debug(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Captures {:?}", self.to_vec())
+    }
+}
+
+/// An error raised when compiling a [Regex] fails.
+#[derive(Debug, Any)]
+#[rune(item = ::regex)]
+pub struct Error {
+    inner: regex::Error,
+}
+
+impl Error {
+    #[rune::function(keep, instance, protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.inner)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.inner, f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::Regex;
+
+    #[test]
+    fn test_captures_round_trip() {
+        let re = Regex::new(r"(?<key>\w+)=(?<value>\d+)?").unwrap();
+        let caps = re.captures("answer=42").unwrap();
+
+        let value = rune::to_value(caps).unwrap();
+        let caps = rune::from_value::<super::Captures>(value).unwrap();
+
+        assert_eq!(caps.len(), 3);
+        assert_eq!(caps.name("key").unwrap().as_str(), "answer");
+        assert_eq!(caps.get(2).unwrap().start(), 7);
+        assert!(caps.name("missing").is_none());
+    }
+
+    #[test]
+    fn test_unmatched_groups_are_none() {
+        let re = Regex::new(r"(a)|(b)").unwrap();
+        let caps = re.captures("b").unwrap();
+        assert_eq!(
+            caps.to_vec(),
+            [Some(String::from("b")), None, Some(String::from("b"))]
+        );
+    }
+
+    #[test]
+    fn test_replace_all_expands_groups() {
+        let re = Regex::new(r"(\w+)@(\w+)").unwrap();
+        assert_eq!(re.replace_all("a@b c@d", "$2@$1"), "b@a d@c");
+    }
+}
@@ -24,23 +24,368 @@
 //! use time;
 //!
 //! fn main() {
+//!     let start = time::DateTime::now();
 //!     time::sleep(time::Duration::from_secs(10)).await;
-//!     println("Message after 10 seconds!");
+//!     println(`Message after ${start.elapsed()?}!`);
 //! }
 //! ```
+//!
+//! Converting dates and times into named timezones, like
+//! `Europe/Stockholm`, requires the `time-tz` feature.
+//!
+//! Measuring time with `Instant` and waiting with `sleep` is done using a
+//! [Clock]. By default this is the [SystemClock], but for tests and
+//! deterministic simulations a [VirtualClock] can be installed instead, whose
+//! time only moves when it's advanced by the host:
+//!
+//! ```rust
+//! use rune_modules::time::{self, VirtualClock};
+//!
+//! let clock = VirtualClock::new();
+//!
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(time::module_with_clock(clock.clone())?)?;
+//! # Ok::<_, rune::Error>(())
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt::{self, Write};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
 
+use chrono::{FixedOffset, Offset, TimeZone, Utc};
+use rune::runtime::{Formatter, Hasher, VmResult};
 use rune::{Any, ContextError, Module};
 
-/// Construct the `time` module.
+/// Construct the `time` module, which measures time using the
+/// [SystemClock].
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    module_with_clock(SystemClock::new())
+}
+
+/// Construct the `time` module, which measures time using the given `clock`.
+pub fn module_with_clock<C>(clock: C) -> Result<Module, ContextError>
+where
+    C: Clock,
+{
     let mut module = Module::with_crate("time");
     module.ty::<Duration>()?;
+    module.ty::<Instant>()?;
+    module.ty::<DateTime>()?;
+    module.ty::<ParseError>()?;
+
+    let clock: Arc<dyn Clock> = Arc::new(clock);
+
+    let c = clock.clone();
+
+    module
+        .function(["Instant", "now"], move || Instant::now(&c))?
+        .docs([
+            "The current instant of the clock installed by the host.",
+            "",
+            "# Examples",
+            "",
+            "```rune,ignore",
+            "use time::Instant;",
+            "",
+            "let start = Instant::now();",
+            "println!(`Took ${start.elapsed()}`);",
+            "```",
+        ]);
+
+    let c = clock;
+
+    module
+        .function(["sleep"], move |duration: Duration| {
+            c.sleep_until(c.now().saturating_add(duration.inner))
+        })?
+        .docs([
+            "Sleep for the given [`Duration`], as measured by the clock installed",
+            "by the host.",
+            "",
+            "# Examples",
+            "",
+            "```rune,no_run",
+            "use time::Duration;",
+            "",
+            "let d = Duration::from_secs(10);",
+            "time::sleep(d).await;",
+            "println!(\"Surprise!\");",
+            "```",
+        ])
+        .argument_names(["duration"]);
+
     module.function_meta(Duration::from_secs__meta)?;
-    module.function_meta(sleep)?;
+    module.function_meta(Duration::from_millis__meta)?;
+    module.function_meta(Duration::from_micros__meta)?;
+    module.function_meta(Duration::from_nanos__meta)?;
+    module.function_meta(Duration::from_secs_f64__meta)?;
+    module.function_meta(Duration::as_secs__meta)?;
+    module.function_meta(Duration::as_millis__meta)?;
+    module.function_meta(Duration::as_micros__meta)?;
+    module.function_meta(Duration::as_nanos__meta)?;
+    module.function_meta(Duration::as_secs_f64__meta)?;
+    module.function_meta(Duration::subsec_nanos__meta)?;
+    module.function_meta(Duration::add__meta)?;
+    module.function_meta(Duration::add_assign__meta)?;
+    module.function_meta(Duration::sub__meta)?;
+    module.function_meta(Duration::sub_assign__meta)?;
+    module.function_meta(Duration::mul__meta)?;
+    module.function_meta(Duration::partial_eq__meta)?;
+    module.function_meta(Duration::eq__meta)?;
+    module.function_meta(Duration::partial_cmp__meta)?;
+    module.function_meta(Duration::cmp__meta)?;
+    module.function_meta(Duration::hash__meta)?;
+    module.function_meta(Duration::string_display__meta)?;
+    module.function_meta(Duration::string_debug__meta)?;
+
+    module.function_meta(DateTime::now__meta)?;
+    module.function_meta(DateTime::from_timestamp__meta)?;
+    module.function_meta(DateTime::from_timestamp_millis__meta)?;
+    module.function_meta(DateTime::from_ymd_hms__meta)?;
+    module.function_meta(DateTime::parse_rfc3339__meta)?;
+    module.function_meta(DateTime::parse__meta)?;
+    module.function_meta(DateTime::to_rfc3339__meta)?;
+    module.function_meta(DateTime::format__meta)?;
+    module.function_meta(DateTime::year__meta)?;
+    module.function_meta(DateTime::month__meta)?;
+    module.function_meta(DateTime::day__meta)?;
+    module.function_meta(DateTime::hour__meta)?;
+    module.function_meta(DateTime::minute__meta)?;
+    module.function_meta(DateTime::second__meta)?;
+    module.function_meta(DateTime::nanosecond__meta)?;
+    module.function_meta(DateTime::weekday__meta)?;
+    module.function_meta(DateTime::ordinal__meta)?;
+    module.function_meta(DateTime::timestamp__meta)?;
+    module.function_meta(DateTime::timestamp_millis__meta)?;
+    module.function_meta(DateTime::offset__meta)?;
+    module.function_meta(DateTime::to_utc__meta)?;
+    module.function_meta(DateTime::with_offset__meta)?;
+    #[cfg(feature = "time-tz")]
+    module.function_meta(DateTime::to_timezone__meta)?;
+    module.function_meta(DateTime::duration_since__meta)?;
+    module.function_meta(DateTime::elapsed__meta)?;
+    module.function_meta(DateTime::add__meta)?;
+    module.function_meta(DateTime::add_assign__meta)?;
+    module.function_meta(DateTime::sub__meta)?;
+    module.function_meta(DateTime::sub_assign__meta)?;
+    module.function_meta(DateTime::partial_eq__meta)?;
+    module.function_meta(DateTime::eq__meta)?;
+    module.function_meta(DateTime::partial_cmp__meta)?;
+    module.function_meta(DateTime::cmp__meta)?;
+    module.function_meta(DateTime::hash__meta)?;
+    module.function_meta(DateTime::string_display__meta)?;
+    module.function_meta(DateTime::string_debug__meta)?;
+
+    module.function_meta(Instant::elapsed__meta)?;
+    module.function_meta(Instant::duration_since__meta)?;
+    module.function_meta(Instant::add__meta)?;
+    module.function_meta(Instant::partial_eq__meta)?;
+    module.function_meta(Instant::eq__meta)?;
+    module.function_meta(Instant::partial_cmp__meta)?;
+    module.function_meta(Instant::cmp__meta)?;
+    module.function_meta(Instant::string_debug__meta)?;
+
+    module.function_meta(ParseError::string_display__meta)?;
     Ok(module)
 }
 
-#[derive(Debug, Clone, Copy, Any)]
+/// A source of time for the `time` module, which determines what
+/// `Instant::now` returns and how long `sleep` waits for.
+pub trait Clock: 'static + Send + Sync {
+    /// The current time of the clock, as the duration since the clock was
+    /// started.
+    ///
+    /// This must never go backwards.
+    fn now(&self) -> tokio::time::Duration;
+
+    /// Construct a future which completes once [now][Clock::now] has reached
+    /// `deadline`.
+    fn sleep_until(
+        &self,
+        deadline: tokio::time::Duration,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+}
+
+/// A [Clock] which follows the monotonic clock of the system, and which
+/// sleeps using the timer of the current tokio runtime.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    start: tokio::time::Instant,
+}
+
+impl SystemClock {
+    /// Construct a new system clock which starts now.
+    pub fn new() -> Self {
+        Self {
+            start: tokio::time::Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> tokio::time::Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep_until(
+        &self,
+        deadline: tokio::time::Duration,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(tokio::time::sleep_until(self.start + deadline))
+    }
+}
+
+/// A [Clock] whose time only moves when it's [advanced][VirtualClock::advance]
+/// by the host, which makes scripts which measure or wait for time
+/// deterministic.
+///
+/// Clones of a virtual clock share the same time, so one clone can be
+/// installed in the context while another is used to control it. Scripts which
+/// are sleeping are woken up as soon as the clock is advanced past their
+/// deadline, without waiting for any actual time to pass.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// use rune::{Context, Vm};
+/// use rune_modules::time::{self, VirtualClock};
+///
+/// let clock = VirtualClock::new();
+///
+/// let mut context = Context::with_default_modules()?;
+/// context.install(time::module_with_clock(clock.clone())?)?;
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn start() { time::Instant::now() }
+///         pub fn elapsed(start) { start.elapsed().as_secs_f64() }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+///
+/// let start = vm.call(["start"], ())?;
+/// clock.advance(Duration::from_millis(1500));
+///
+/// let elapsed: f64 = rune::from_value(vm.call(["elapsed"], (start,))?)?;
+/// assert_eq!(elapsed, 1.5);
+/// # Ok::<_, rune::Error>(())
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct VirtualClock {
+    inner: Arc<Mutex<VirtualClockInner>>,
+}
+
+#[derive(Debug, Default)]
+struct VirtualClockInner {
+    now: tokio::time::Duration,
+    sleepers: Vec<(tokio::time::Duration, Waker)>,
+}
+
+impl VirtualClock {
+    /// Construct a new virtual clock, which starts at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current time of the clock.
+    pub fn elapsed(&self) -> tokio::time::Duration {
+        self.lock().now
+    }
+
+    /// Advance the clock by the given duration, waking up any sleeps whose
+    /// deadline has been reached.
+    pub fn advance(&self, duration: tokio::time::Duration) {
+        let mut wakers = Vec::new();
+
+        {
+            let mut inner = self.lock();
+            inner.now = inner.now.saturating_add(duration);
+            let now = inner.now;
+
+            inner.sleepers.retain(|(deadline, waker)| {
+                if *deadline > now {
+                    return true;
+                }
+
+                wakers.push(waker.clone());
+                false
+            });
+        }
+
+        // NB: wakers are called outside of the lock, since waking might poll
+        // the sleep again.
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VirtualClockInner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> tokio::time::Duration {
+        self.elapsed()
+    }
+
+    fn sleep_until(
+        &self,
+        deadline: tokio::time::Duration,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(VirtualSleep {
+            clock: self.clone(),
+            deadline,
+        })
+    }
+}
+
+/// A sleep on a [VirtualClock].
+struct VirtualSleep {
+    clock: VirtualClock,
+    deadline: tokio::time::Duration,
+}
+
+impl Future for VirtualSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.clock.lock();
+
+        if inner.now >= self.deadline {
+            return Poll::Ready(());
+        }
+
+        let registered = inner
+            .sleepers
+            .iter()
+            .any(|(deadline, waker)| *deadline == self.deadline && waker.will_wake(cx.waker()));
+
+        if !registered {
+            inner.sleepers.push((self.deadline, cx.waker().clone()));
+        }
+
+        Poll::Pending
+    }
+}
+
+/// A span of time, like `10s` or `1.5ms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Any)]
 #[rune(item = ::time)]
 struct Duration {
     inner: tokio::time::Duration,
@@ -48,9 +393,9 @@ struct Duration {
 
 impl Duration {
     /// Construct a duration from the given number of seconds.
-    /// 
+    ///
     /// # Examples
-    /// 
+    ///
     /// ```rune
     /// use time::Duration;
     ///
@@ -62,20 +407,771 @@ impl Duration {
             inner: tokio::time::Duration::from_secs(secs),
         }
     }
+
+    /// Construct a duration from the given number of milliseconds.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// assert_eq!(Duration::from_millis(1500).as_secs_f64(), 1.5);
+    /// ```
+    #[rune::function(keep, path = Self::from_millis)]
+    fn from_millis(millis: u64) -> Self {
+        Self {
+            inner: tokio::time::Duration::from_millis(millis),
+        }
+    }
+
+    /// Construct a duration from the given number of microseconds.
+    #[rune::function(keep, path = Self::from_micros)]
+    fn from_micros(micros: u64) -> Self {
+        Self {
+            inner: tokio::time::Duration::from_micros(micros),
+        }
+    }
+
+    /// Construct a duration from the given number of nanoseconds.
+    #[rune::function(keep, path = Self::from_nanos)]
+    fn from_nanos(nanos: u64) -> Self {
+        Self {
+            inner: tokio::time::Duration::from_nanos(nanos),
+        }
+    }
+
+    /// Construct a duration from a number of seconds with a fractional part.
+    ///
+    /// Returns `None` if the number of seconds is negative, too large or not
+    /// finite.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// assert_eq!(Duration::from_secs_f64(0.5), Some(Duration::from_millis(500)));
+    /// assert_eq!(Duration::from_secs_f64(-1.0), None);
+    /// ```
+    #[rune::function(keep, path = Self::from_secs_f64)]
+    fn from_secs_f64(secs: f64) -> Option<Self> {
+        let inner = tokio::time::Duration::try_from_secs_f64(secs).ok()?;
+        Some(Self { inner })
+    }
+
+    /// The number of whole seconds in the duration.
+    #[rune::function(keep, instance)]
+    fn as_secs(&self) -> u64 {
+        self.inner.as_secs()
+    }
+
+    /// The number of whole milliseconds in the duration.
+    #[rune::function(keep, instance)]
+    fn as_millis(&self) -> u128 {
+        self.inner.as_millis()
+    }
+
+    /// The number of whole microseconds in the duration.
+    #[rune::function(keep, instance)]
+    fn as_micros(&self) -> u128 {
+        self.inner.as_micros()
+    }
+
+    /// The number of nanoseconds in the duration.
+    #[rune::function(keep, instance)]
+    fn as_nanos(&self) -> u128 {
+        self.inner.as_nanos()
+    }
+
+    /// The number of seconds in the duration, including the fractional part.
+    #[rune::function(keep, instance)]
+    fn as_secs_f64(&self) -> f64 {
+        self.inner.as_secs_f64()
+    }
+
+    /// The fractional part of the duration in nanoseconds.
+    #[rune::function(keep, instance)]
+    fn subsec_nanos(&self) -> u32 {
+        self.inner.subsec_nanos()
+    }
+
+    /// Add two durations.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// let d = Duration::from_secs(1) + Duration::from_millis(500);
+    /// assert_eq!(d.as_millis(), 1500);
+    /// ```
+    #[rune::function(keep, instance, protocol = ADD)]
+    fn add(&self, rhs: &Self) -> VmResult<Self> {
+        let Some(inner) = self.inner.checked_add(rhs.inner) else {
+            return VmResult::panic("overflow when adding durations");
+        };
+
+        VmResult::Ok(Self { inner })
+    }
+
+    #[rune::function(keep, instance, protocol = ADD_ASSIGN)]
+    fn add_assign(&mut self, rhs: &Self) -> VmResult<()> {
+        *self = rune::vm_try!(self.add(rhs));
+        VmResult::Ok(())
+    }
+
+    /// Subtract two durations.
+    ///
+    /// Durations can't be negative, so subtracting a longer duration from a
+    /// shorter one panics.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// let d = Duration::from_secs(2) - Duration::from_millis(500);
+    /// assert_eq!(d.as_millis(), 1500);
+    /// ```
+    #[rune::function(keep, instance, protocol = SUB)]
+    fn sub(&self, rhs: &Self) -> VmResult<Self> {
+        let Some(inner) = self.inner.checked_sub(rhs.inner) else {
+            return VmResult::panic("overflow when subtracting durations");
+        };
+
+        VmResult::Ok(Self { inner })
+    }
+
+    #[rune::function(keep, instance, protocol = SUB_ASSIGN)]
+    fn sub_assign(&mut self, rhs: &Self) -> VmResult<()> {
+        *self = rune::vm_try!(self.sub(rhs));
+        VmResult::Ok(())
+    }
+
+    /// Multiply a duration by a whole number.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// assert_eq!(Duration::from_millis(500) * 3, Duration::from_millis(1500));
+    /// ```
+    #[rune::function(keep, instance, protocol = MUL)]
+    fn mul(&self, rhs: u32) -> VmResult<Self> {
+        let Some(inner) = self.inner.checked_mul(rhs) else {
+            return VmResult::panic("overflow when multiplying duration");
+        };
+
+        VmResult::Ok(Self { inner })
+    }
+
+    #[rune::function(keep, instance, protocol = PARTIAL_EQ)]
+    fn partial_eq(&self, rhs: &Self) -> bool {
+        self == rhs
+    }
+
+    #[rune::function(keep, instance, protocol = EQ)]
+    fn eq(&self, rhs: &Self) -> bool {
+        self == rhs
+    }
+
+    /// Compare two durations.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// assert!(Duration::from_millis(999) < Duration::from_secs(1));
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_CMP)]
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(self, rhs)
+    }
+
+    #[rune::function(keep, instance, protocol = CMP)]
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        Ord::cmp(self, rhs)
+    }
+
+    #[rune::function(keep, instance, protocol = HASH)]
+    fn hash(&self, hasher: &mut Hasher) {
+        hasher.write(&self.inner.as_nanos().to_le_bytes());
+    }
+
+    /// Format the duration, like `1.5s`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// assert_eq!(`${Duration::from_millis(1500)}`, "1.5s");
+    /// ```
+    #[rune::function(keep, instance, protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.inner)
+    }
+
+    #[rune::function(keep, instance, protocol = STRING_DEBUG)]
+    fn string_debug(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
+/// An instant of the clock installed by the host, which is used to measure how
+/// much time has passed.
+///
+/// Instants are only meaningful relative to other instants taken from the same
+/// clock.
+///
+/// # Examples
+///
+/// ```rune
+/// use time::{Duration, Instant};
+///
+/// let start = Instant::now();
+/// let later = start + Duration::from_secs(1);
+///
+/// assert!(later > start);
+/// assert_eq!(later.duration_since(start), Some(Duration::from_secs(1)));
+/// assert_eq!(start.duration_since(later), None);
+/// ```
+#[derive(Clone, Any)]
+#[rune(item = ::time)]
+struct Instant {
+    at: tokio::time::Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl Instant {
+    fn now(clock: &Arc<dyn Clock>) -> Self {
+        Self {
+            at: clock.now(),
+            clock: clock.clone(),
+        }
+    }
+
+    /// The duration which has passed since this instant.
+    #[rune::function(keep, instance)]
+    fn elapsed(&self) -> Duration {
+        Duration {
+            inner: self.clock.now().saturating_sub(self.at),
+        }
+    }
+
+    /// The duration since an earlier instant.
+    ///
+    /// Returns `None` if `earlier` is later than this instant.
+    #[rune::function(keep, instance)]
+    fn duration_since(&self, earlier: &Self) -> Option<Duration> {
+        let inner = self.at.checked_sub(earlier.at)?;
+        Some(Duration { inner })
+    }
+
+    /// Add a duration to the instant.
+    #[rune::function(keep, instance, protocol = ADD)]
+    fn add(&self, rhs: &Duration) -> VmResult<Self> {
+        let Some(at) = self.at.checked_add(rhs.inner) else {
+            return VmResult::panic("overflow when adding duration to instant");
+        };
+
+        VmResult::Ok(Self {
+            at,
+            clock: self.clock.clone(),
+        })
+    }
+
+    #[rune::function(keep, instance, protocol = PARTIAL_EQ)]
+    fn partial_eq(&self, rhs: &Self) -> bool {
+        self.at == rhs.at
+    }
+
+    #[rune::function(keep, instance, protocol = EQ)]
+    fn eq(&self, rhs: &Self) -> bool {
+        self.at == rhs.at
+    }
+
+    #[rune::function(keep, instance, protocol = PARTIAL_CMP)]
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&self.at, &rhs.at)
+    }
+
+    #[rune::function(keep, instance, protocol = CMP)]
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        Ord::cmp(&self.at, &rhs.at)
+    }
+
+    #[rune::function(keep, instance, protocol = STRING_DEBUG)]
+    fn string_debug(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Instant({:?})", self.at)
+    }
 }
 
-/// Sleep for the given [`Duration`].
-/// 
+/// A date and time with a fixed offset from UTC.
+///
+/// Comparing and hashing date times only considers the instant in time they
+/// refer to, so the same instant with different offsets are equal.
+///
 /// # Examples
-/// 
-/// ```rune,no_run
-/// use time::Duration;
 ///
-/// let d = Duration::from_secs(10);
-/// time::sleep(d).await;
-/// println!("Surprise!");
+/// ```rune
+/// use time::{DateTime, Duration};
+///
+/// let a = DateTime::parse_rfc3339("2023-06-01T12:00:00+02:00")?;
+/// let b = DateTime::parse_rfc3339("2023-06-01T10:00:00Z")?;
+/// assert_eq!(a, b);
+///
+/// let c = a + Duration::from_secs(90);
+/// assert_eq!(`${c}`, "2023-06-01T12:01:30+02:00");
+/// assert_eq!(c.duration_since(a), Some(Duration::from_secs(90)));
 /// ```
-#[rune::function]
-async fn sleep(duration: Duration) {
-    tokio::time::sleep(duration.inner).await;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Any)]
+#[rune(item = ::time)]
+struct DateTime {
+    inner: chrono::DateTime<FixedOffset>,
+}
+
+impl DateTime {
+    fn utc(inner: chrono::DateTime<Utc>) -> Self {
+        Self {
+            inner: inner.with_timezone(&Utc.fix()),
+        }
+    }
+
+    /// The current date and time in UTC.
+    #[rune::function(keep, path = Self::now)]
+    fn now() -> Self {
+        Self::utc(Utc::now())
+    }
+
+    /// Construct a date time in UTC from a unix timestamp in seconds.
+    ///
+    /// Returns `None` if the timestamp is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::from_timestamp(1685620800)?;
+    /// assert_eq!(`${dt}`, "2023-06-01T12:00:00+00:00");
+    /// ```
+    #[rune::function(keep, path = Self::from_timestamp)]
+    fn from_timestamp(secs: i64) -> Option<Self> {
+        Some(Self::utc(Utc.timestamp_opt(secs, 0).single()?))
+    }
+
+    /// Construct a date time in UTC from a unix timestamp in milliseconds.
+    ///
+    /// Returns `None` if the timestamp is out of range.
+    #[rune::function(keep, path = Self::from_timestamp_millis)]
+    fn from_timestamp_millis(millis: i64) -> Option<Self> {
+        Some(Self::utc(Utc.timestamp_millis_opt(millis).single()?))
+    }
+
+    /// Construct a date time in UTC from its components.
+    ///
+    /// Returns `None` if the components don't form a valid date and time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::from_ymd_hms(2023, 6, 1, 12, 0, 0)?;
+    /// assert_eq!(dt.timestamp(), 1685620800);
+    /// assert_eq!(DateTime::from_ymd_hms(2023, 2, 30, 0, 0, 0), None);
+    /// ```
+    #[rune::function(keep, path = Self::from_ymd_hms)]
+    fn from_ymd_hms(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: u32,
+    ) -> Option<Self> {
+        let inner = Utc
+            .with_ymd_and_hms(year, month, day, hour, minute, second)
+            .single()?;
+
+        Some(Self::utc(inner))
+    }
+
+    /// Parse a date time in the [RFC 3339] format, like
+    /// `2023-06-01T12:00:00+02:00`.
+    ///
+    /// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::parse_rfc3339("2023-06-01T12:00:00+02:00")?;
+    /// assert_eq!(dt.hour(), 12);
+    /// assert_eq!(dt.offset(), 7200);
+    ///
+    /// assert!(DateTime::parse_rfc3339("yesterday").is_err());
+    /// ```
+    #[rune::function(keep, path = Self::parse_rfc3339)]
+    fn parse_rfc3339(string: &str) -> Result<Self, ParseError> {
+        match chrono::DateTime::parse_from_rfc3339(string) {
+            Ok(inner) => Ok(Self { inner }),
+            Err(error) => Err(ParseError { inner: error }),
+        }
+    }
+
+    /// Parse a date time using a [strftime] format string, which must include
+    /// the offset from UTC.
+    ///
+    /// [strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::parse("2023-06-01 12:00 +0000", "%Y-%m-%d %H:%M %z")?;
+    /// assert_eq!(dt.timestamp(), 1685620800);
+    /// ```
+    #[rune::function(keep, path = Self::parse)]
+    fn parse(string: &str, format: &str) -> Result<Self, ParseError> {
+        match chrono::DateTime::parse_from_str(string, format) {
+            Ok(inner) => Ok(Self { inner }),
+            Err(error) => Err(ParseError { inner: error }),
+        }
+    }
+
+    /// Format the date time in the [RFC 3339] format.
+    ///
+    /// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339
+    #[rune::function(keep, instance)]
+    fn to_rfc3339(&self) -> String {
+        self.inner.to_rfc3339()
+    }
+
+    /// Format the date time using a [strftime] format string.
+    ///
+    /// Returns `None` if the format string is invalid.
+    ///
+    /// [strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::from_ymd_hms(2023, 6, 1, 12, 0, 0)?;
+    /// assert_eq!(dt.format("%Y-%m-%d"), Some("2023-06-01"));
+    /// assert_eq!(dt.format("%Q"), None);
+    /// ```
+    #[rune::function(keep, instance)]
+    fn format(&self, format: &str) -> Option<String> {
+        let mut string = String::new();
+        write!(string, "{}", self.inner.format(format)).ok()?;
+        Some(string)
+    }
+
+    /// The year.
+    #[rune::function(keep, instance)]
+    fn year(&self) -> i32 {
+        chrono::Datelike::year(&self.inner)
+    }
+
+    /// The month, starting at `1` for January.
+    #[rune::function(keep, instance)]
+    fn month(&self) -> u32 {
+        chrono::Datelike::month(&self.inner)
+    }
+
+    /// The day of the month, starting at `1`.
+    #[rune::function(keep, instance)]
+    fn day(&self) -> u32 {
+        chrono::Datelike::day(&self.inner)
+    }
+
+    /// The hour.
+    #[rune::function(keep, instance)]
+    fn hour(&self) -> u32 {
+        chrono::Timelike::hour(&self.inner)
+    }
+
+    /// The minute.
+    #[rune::function(keep, instance)]
+    fn minute(&self) -> u32 {
+        chrono::Timelike::minute(&self.inner)
+    }
+
+    /// The second.
+    #[rune::function(keep, instance)]
+    fn second(&self) -> u32 {
+        chrono::Timelike::second(&self.inner)
+    }
+
+    /// The number of nanoseconds past the second.
+    #[rune::function(keep, instance)]
+    fn nanosecond(&self) -> u32 {
+        chrono::Timelike::nanosecond(&self.inner)
+    }
+
+    /// The day of the week, starting at `1` for Monday.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::from_ymd_hms(2023, 6, 1, 12, 0, 0)?;
+    /// assert_eq!(dt.weekday(), 4);
+    /// ```
+    #[rune::function(keep, instance)]
+    fn weekday(&self) -> u32 {
+        chrono::Datelike::weekday(&self.inner).number_from_monday()
+    }
+
+    /// The day of the year, starting at `1`.
+    #[rune::function(keep, instance)]
+    fn ordinal(&self) -> u32 {
+        chrono::Datelike::ordinal(&self.inner)
+    }
+
+    /// The number of seconds since the unix epoch.
+    #[rune::function(keep, instance)]
+    fn timestamp(&self) -> i64 {
+        self.inner.timestamp()
+    }
+
+    /// The number of milliseconds since the unix epoch.
+    #[rune::function(keep, instance)]
+    fn timestamp_millis(&self) -> i64 {
+        self.inner.timestamp_millis()
+    }
+
+    /// The offset from UTC in seconds.
+    #[rune::function(keep, instance)]
+    fn offset(&self) -> i32 {
+        self.inner.offset().local_minus_utc()
+    }
+
+    /// Convert into the same instant in UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::parse_rfc3339("2023-06-01T12:00:00+02:00")?;
+    /// assert_eq!(`${dt.to_utc()}`, "2023-06-01T10:00:00+00:00");
+    /// ```
+    #[rune::function(keep, instance)]
+    fn to_utc(&self) -> Self {
+        Self::utc(self.inner.with_timezone(&Utc))
+    }
+
+    /// Convert into the same instant with the given offset from UTC in
+    /// seconds.
+    ///
+    /// Returns `None` if the offset is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::from_ymd_hms(2023, 6, 1, 12, 0, 0)?;
+    /// assert_eq!(`${dt.with_offset(-3600)?}`, "2023-06-01T11:00:00-01:00");
+    /// ```
+    #[rune::function(keep, instance)]
+    fn with_offset(&self, offset: i32) -> Option<Self> {
+        let offset = FixedOffset::east_opt(offset)?;
+
+        Some(Self {
+            inner: self.inner.with_timezone(&offset),
+        })
+    }
+
+    /// Convert into the same instant in the named timezone, like
+    /// `Europe/Stockholm`, using the offset which is in effect in that
+    /// timezone at that instant.
+    ///
+    /// Returns `None` if the timezone isn't known.
+    ///
+    /// This requires the `time-tz` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::from_ymd_hms(2023, 6, 1, 12, 0, 0)?;
+    /// assert_eq!(`${dt.to_timezone("Europe/Stockholm")?}`, "2023-06-01T14:00:00+02:00");
+    /// assert_eq!(dt.to_timezone("Nowhere/Special"), None);
+    /// ```
+    #[cfg(feature = "time-tz")]
+    #[rune::function(keep, instance)]
+    fn to_timezone(&self, timezone: &str) -> Option<Self> {
+        let timezone = timezone.parse::<chrono_tz::Tz>().ok()?;
+        let offset = self.inner.with_timezone(&timezone).offset().fix();
+
+        Some(Self {
+            inner: self.inner.with_timezone(&offset),
+        })
+    }
+
+    /// The duration since an earlier date time.
+    ///
+    /// Returns `None` if `earlier` is later than this date time.
+    #[rune::function(keep, instance)]
+    fn duration_since(&self, earlier: &Self) -> Option<Duration> {
+        let inner = self
+            .inner
+            .signed_duration_since(earlier.inner)
+            .to_std()
+            .ok()?;
+
+        Some(Duration { inner })
+    }
+
+    /// The duration which has passed since this date time.
+    ///
+    /// Returns `None` if this date time is in the future.
+    #[rune::function(keep, instance)]
+    fn elapsed(&self) -> Option<Duration> {
+        Self::now().duration_since(self)
+    }
+
+    /// Add a duration to the date time.
+    #[rune::function(keep, instance, protocol = ADD)]
+    fn add(&self, rhs: &Duration) -> VmResult<Self> {
+        let inner = chrono::Duration::from_std(rhs.inner)
+            .ok()
+            .and_then(|rhs| self.inner.checked_add_signed(rhs));
+
+        let Some(inner) = inner else {
+            return VmResult::panic("overflow when adding duration to date time");
+        };
+
+        VmResult::Ok(Self { inner })
+    }
+
+    #[rune::function(keep, instance, protocol = ADD_ASSIGN)]
+    fn add_assign(&mut self, rhs: &Duration) -> VmResult<()> {
+        *self = rune::vm_try!(self.add(rhs));
+        VmResult::Ok(())
+    }
+
+    /// Subtract a duration from the date time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let dt = DateTime::from_ymd_hms(2023, 6, 1, 0, 0, 0)?;
+    /// let dt = dt - Duration::from_secs(1);
+    /// assert_eq!(`${dt}`, "2023-05-31T23:59:59+00:00");
+    /// ```
+    #[rune::function(keep, instance, protocol = SUB)]
+    fn sub(&self, rhs: &Duration) -> VmResult<Self> {
+        let inner = chrono::Duration::from_std(rhs.inner)
+            .ok()
+            .and_then(|rhs| self.inner.checked_sub_signed(rhs));
+
+        let Some(inner) = inner else {
+            return VmResult::panic("overflow when subtracting duration from date time");
+        };
+
+        VmResult::Ok(Self { inner })
+    }
+
+    #[rune::function(keep, instance, protocol = SUB_ASSIGN)]
+    fn sub_assign(&mut self, rhs: &Duration) -> VmResult<()> {
+        *self = rune::vm_try!(self.sub(rhs));
+        VmResult::Ok(())
+    }
+
+    #[rune::function(keep, instance, protocol = PARTIAL_EQ)]
+    fn partial_eq(&self, rhs: &Self) -> bool {
+        self == rhs
+    }
+
+    #[rune::function(keep, instance, protocol = EQ)]
+    fn eq(&self, rhs: &Self) -> bool {
+        self == rhs
+    }
+
+    /// Compare two date times.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let mut dates = [
+    ///     DateTime::from_timestamp(300)?,
+    ///     DateTime::from_timestamp(100)?,
+    ///     DateTime::from_timestamp(200)?,
+    /// ];
+    ///
+    /// dates.sort();
+    /// assert_eq!(dates.iter().map(|d| d.timestamp()).collect::<Vec>(), [100, 200, 300]);
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_CMP)]
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(self, rhs)
+    }
+
+    #[rune::function(keep, instance, protocol = CMP)]
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        Ord::cmp(self, rhs)
+    }
+
+    /// Hash the date time, which makes it possible to use as a key in maps
+    /// and sets.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::HashSet;
+    /// use time::DateTime;
+    ///
+    /// let mut set = HashSet::new();
+    /// set.insert(DateTime::parse_rfc3339("2023-06-01T12:00:00+02:00")?);
+    /// assert!(set.contains(DateTime::parse_rfc3339("2023-06-01T10:00:00Z")?));
+    /// ```
+    #[rune::function(keep, instance, protocol = HASH)]
+    fn hash(&self, hasher: &mut Hasher) {
+        hasher.write_i64(self.inner.timestamp());
+        hasher.write_i64(i64::from(self.inner.timestamp_subsec_nanos()));
+    }
+
+    /// Format the date time in the [RFC 3339] format.
+    ///
+    /// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339
+    #[rune::function(keep, instance, protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.inner.to_rfc3339())
+    }
+
+    #[rune::function(keep, instance, protocol = STRING_DEBUG)]
+    fn string_debug(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
+/// An error raised when parsing a [`DateTime`] fails.
+#[derive(Debug, Any)]
+#[rune(item = ::time)]
+struct ParseError {
+    inner: chrono::ParseError,
+}
+
+impl ParseError {
+    #[rune::function(keep, instance, protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.inner)
+    }
 }
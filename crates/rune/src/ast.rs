@@ -133,8 +133,10 @@ mod ident;
 mod item;
 mod item_const;
 mod item_enum;
+mod item_extern_const;
 mod item_fn;
 mod item_impl;
+mod item_macro;
 mod item_mod;
 mod item_struct;
 mod item_use;
@@ -156,6 +158,7 @@ mod span;
 pub(crate) mod spanned;
 mod stmt;
 mod token;
+mod ty;
 pub(super) mod unescape;
 mod utils;
 mod vis;
@@ -182,7 +185,9 @@ pub use self::expr_let::ExprLet;
 pub use self::expr_lit::ExprLit;
 pub use self::expr_loop::ExprLoop;
 pub use self::expr_match::{ExprMatch, ExprMatchBranch};
-pub use self::expr_object::{ExprObject, FieldAssign, ObjectIdent, ObjectKey};
+pub use self::expr_object::{
+    ExprObject, FieldAssign, ObjectEntry, ObjectIdent, ObjectKey, ObjectSpread,
+};
 pub use self::expr_range::{ExprRange, ExprRangeLimits};
 pub use self::expr_return::ExprReturn;
 pub use self::expr_select::{ExprSelect, ExprSelectBranch, ExprSelectPatBranch};
@@ -194,14 +199,16 @@ pub use self::expr_while::ExprWhile;
 pub use self::expr_yield::ExprYield;
 pub use self::fields::Fields;
 pub use self::file::{File, Shebang};
-pub use self::fn_arg::FnArg;
+pub use self::fn_arg::{FnArg, PatType};
 pub use self::grouped::{AngleBracketed, Braced, Bracketed, Parenthesized};
 pub use self::ident::Ident;
 pub use self::item::Item;
 pub use self::item_const::ItemConst;
 pub use self::item_enum::{ItemEnum, ItemVariant};
+pub use self::item_extern_const::ItemExternConst;
 pub use self::item_fn::ItemFn;
 pub use self::item_impl::ItemImpl;
+pub use self::item_macro::ItemMacro;
 pub use self::item_mod::{ItemInlineBody, ItemMod, ItemModBody};
 pub use self::item_struct::{Field, ItemStruct};
 pub use self::item_use::{ItemUse, ItemUsePath, ItemUseSegment};
@@ -217,17 +224,18 @@ pub use self::local::Local;
 pub use self::macro_call::MacroCall;
 pub use self::macro_utils::{EqValue, Group};
 pub use self::pat::{
-    Pat, PatBinding, PatIgnore, PatLit, PatObject, PatPath, PatRest, PatTuple, PatVec,
+    Pat, PatAt, PatBinding, PatIgnore, PatLit, PatObject, PatPath, PatRest, PatTuple, PatVec,
 };
 pub use self::path::{Path, PathKind, PathSegment, PathSegmentExpr};
 use self::prelude::*;
 pub use self::span::{ByteIndex, Span};
 pub use self::spanned::{OptionSpanned, Spanned};
-pub use self::stmt::{ItemOrExpr, Stmt, StmtSemi, StmtSortKey};
+pub use self::stmt::{ItemOrExpr, Stmt, StmtDefer, StmtSemi, StmtSortKey};
 pub use self::token::{
     BuiltIn, CopySource, Delimiter, LitSource, Number, NumberBase, NumberSource, NumberSuffix,
     NumberText, NumberValue, StrSource, StrText, Token,
 };
+pub use self::ty::Type;
 pub use self::vis::Visibility;
 
 macro_rules! decl_tokens {
@@ -12,6 +12,7 @@ fn ast_parse() {
     rt::<ast::Expr>("foo.bar()");
     rt::<ast::Expr>("var()");
     rt::<ast::Expr>("var");
+    rt::<ast::Expr>("select.default");
     rt::<ast::Expr>("42");
     rt::<ast::Expr>("1 + 2 / 3 - 4 * 1");
     rt::<ast::Expr>("foo[\"bar\"]");
@@ -414,7 +415,7 @@ impl Expr {
             K!['('] => true,
             K!['['] => true,
             K!['{'] if *eager_brace => true,
-            _ => false,
+            kind => ast::Ident::is_kind(kind),
         }
     }
 }
@@ -442,14 +443,22 @@ fn primary(
     chain(p, expr, callable)
 }
 
+/// Test if we're looking at a contextual keyword which starts an expression,
+/// rather than an identifier.
+fn peek_keyword_expr(p: &mut Peeker<'_>) -> bool {
+    matches!((p.nth(0), p.nth(1)), (K![select], K!['{']) | (K![yield], _))
+}
+
 /// Parse a basic expression.
 fn base(
     p: &mut Parser<'_>,
     attributes: &mut Vec<ast::Attribute>,
     eager_brace: EagerBrace,
 ) -> Result<Expr> {
-    if let Some(path) = p.parse::<Option<ast::Path>>()? {
-        return Expr::parse_with_meta_path(p, attributes, path, eager_brace);
+    if !peek_keyword_expr(p.peeker()) {
+        if let Some(path) = p.parse::<Option<ast::Path>>()? {
+            return Expr::parse_with_meta_path(p, attributes, path, eager_brace);
+        }
     }
 
     if ast::Lit::peek_in_expr(p.peeker()) {
@@ -609,7 +618,7 @@ fn chain(p: &mut Parser<'_>, mut expr: Expr, callable: Callable) -> Result<Expr>
                         });
                     }
                     // <expr>.field
-                    K![ident] => {
+                    kind if ast::Ident::is_kind(kind) => {
                         expr = Expr::FieldAccess(ast::ExprFieldAccess {
                             attributes: expr.take_attributes(),
                             expr: Box::new(expr),
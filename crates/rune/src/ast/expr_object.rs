@@ -9,6 +9,8 @@ fn ast_parse() {
     rt::<ast::ExprObject>("Foo {\"foo\": 42}");
     rt::<ast::ExprObject>("#{\"foo\": 42}");
     rt::<ast::ExprObject>("#{\"foo\": 42,}");
+    rt::<ast::ExprObject>("#{..defaults, \"foo\": 42}");
+    rt::<ast::ExprObject>("#{a: 1, ..b(), c: 2}");
 
     rt::<ast::FieldAssign>("\"foo\": 42");
     rt::<ast::FieldAssign>("\"foo\": 42");
@@ -32,7 +34,7 @@ pub struct ExprObject {
     #[rune(meta)]
     pub ident: ObjectIdent,
     /// Assignments in the object.
-    pub assignments: ast::Braced<FieldAssign, T![,]>,
+    pub assignments: ast::Braced<ObjectEntry, T![,]>,
 }
 
 impl Peek for ExprObject {
@@ -64,6 +66,37 @@ impl Parse for ObjectIdent {
     }
 }
 
+/// An entry in an object expression.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub enum ObjectEntry {
+    /// A field assignment, like `x: 1`.
+    Assign(FieldAssign),
+    /// Spread the fields of another value into the object, like `..defaults`.
+    Spread(ObjectSpread),
+}
+
+impl Parse for ObjectEntry {
+    fn parse(p: &mut Parser) -> Result<Self> {
+        Ok(match p.nth(0)? {
+            K![..] => Self::Spread(p.parse()?),
+            _ => Self::Assign(p.parse()?),
+        })
+    }
+}
+
+/// A spread entry in an object expression.
+///
+/// * `..<expr>`.
+#[derive(Debug, Clone, PartialEq, Eq, Parse, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct ObjectSpread {
+    /// The `..` token.
+    pub dot_dot: T![..],
+    /// The value being spread.
+    pub expr: ast::Expr,
+}
+
 /// A single field assignment in an object expression.
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
 #[non_exhaustive]
@@ -105,7 +138,7 @@ impl Parse for ObjectKey {
     fn parse(p: &mut Parser) -> Result<Self> {
         Ok(match p.nth(0)? {
             K![str] => Self::LitStr(p.parse()?),
-            K![ident] => Self::Path(p.parse()?),
+            kind if ast::Ident::is_kind(kind) => Self::Path(p.parse()?),
             _ => {
                 return Err(compile::Error::expected(p.tok_at(0)?, "literal object key"));
             }
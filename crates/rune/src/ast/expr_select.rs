@@ -109,7 +109,7 @@ impl ExprSelectBranch {
 
 impl Parse for ExprSelectBranch {
     fn parse(p: &mut Parser) -> Result<Self> {
-        Ok(if p.peek::<T![default]>()? {
+        Ok(if matches!((p.nth(0)?, p.nth(1)?), (K![default], K![=>])) {
             Self::Default(p.parse()?)
         } else {
             Self::Pat(p.parse()?)
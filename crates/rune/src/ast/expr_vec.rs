@@ -7,11 +7,15 @@ fn ast_parse() {
     rt::<ast::ExprVec>("[1, \"two\"]");
     rt::<ast::ExprVec>("[1, 2,]");
     rt::<ast::ExprVec>("[1, 2, foo()]");
+    rt::<ast::ExprVec>("[..a, 1, ..b]");
 }
 
 /// A literal vector.
 ///
 /// * `[<expr>,*]`
+///
+/// Items which are half-open ranges without a start, like `..a`, spread the
+/// values of `a` into the vector.
 #[derive(Debug, Clone, PartialEq, Eq, Parse, ToTokens, Spanned)]
 #[non_exhaustive]
 pub struct ExprVec {
@@ -7,6 +7,8 @@ fn ast_parse() {
     rt::<ast::FnArg>("self");
     rt::<ast::FnArg>("_");
     rt::<ast::FnArg>("abc");
+    rt::<ast::FnArg>("abc: i64");
+    rt::<ast::FnArg>("(a, b): Tuple");
 }
 
 /// A single argument in a closure.
@@ -17,13 +19,39 @@ pub enum FnArg {
     SelfValue(T![self]),
     /// Function argument is a pattern binding.
     Pat(ast::Pat),
+    /// Function argument is a pattern binding with a type annotation.
+    Typed(PatType),
 }
 
 impl Parse for FnArg {
     fn parse(p: &mut Parser<'_>) -> Result<Self> {
         Ok(match p.nth(0)? {
             K![self] => Self::SelfValue(p.parse()?),
-            _ => Self::Pat(p.parse()?),
+            _ => {
+                let pat = p.parse()?;
+
+                if p.peek::<T![:]>()? {
+                    Self::Typed(PatType {
+                        pat,
+                        colon: p.parse()?,
+                        ty: p.parse()?,
+                    })
+                } else {
+                    Self::Pat(pat)
+                }
+            }
         })
     }
 }
+
+/// A pattern binding with a type annotation, like `name: String`.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct PatType {
+    /// The pattern being bound.
+    pub pat: ast::Pat,
+    /// The `:` token.
+    pub colon: T![:],
+    /// The type annotation.
+    pub ty: ast::Type,
+}
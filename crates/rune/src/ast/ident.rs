@@ -7,6 +7,8 @@ fn ast_parse() {
     rt::<ast::Ident>("foo");
     rt::<ast::Ident>("a42");
     rt::<ast::Ident>("_ignored");
+    rt::<ast::Ident>("select");
+    rt::<ast::Ident>("abstract");
 }
 
 /// An identifier, like `foo` or `Hello`.
@@ -44,14 +46,35 @@ impl Parse for Ident {
                 span: t.span,
                 source,
             }),
-            _ => Err(compile::Error::expected(t, "ident")),
+            kind => {
+                let Some(keyword) = kind.as_contextual_keyword() else {
+                    return Err(compile::Error::expected(t, "ident"));
+                };
+
+                if kind.is_reserved_keyword() {
+                    parser.reserved_ident(t.span);
+                }
+
+                Ok(Self {
+                    span: t.span,
+                    source: ast::LitSource::Keyword(keyword),
+                })
+            }
         }
     }
 }
 
 impl Peek for Ident {
     fn peek(p: &mut Peeker<'_>) -> bool {
-        matches!(p.nth(0), K![ident])
+        Self::is_kind(p.nth(0))
+    }
+}
+
+impl Ident {
+    /// Test if the given token kind can be parsed as an identifier, which
+    /// includes contextual keywords.
+    pub(crate) fn is_kind(kind: ast::Kind) -> bool {
+        matches!(kind, K![ident]) || kind.as_contextual_keyword().is_some()
     }
 }
 
@@ -84,6 +107,7 @@ impl<'a> Resolve<'a> for Ident {
                 Ok(ident)
             }
             ast::LitSource::BuiltIn(builtin) => Ok(builtin.as_str()),
+            ast::LitSource::Keyword(keyword) => Ok(keyword),
         }
     }
 }
@@ -23,6 +23,10 @@ pub enum Item {
     Mod(ast::ItemMod),
     /// A const declaration.
     Const(ast::ItemConst),
+    /// An external const declaration.
+    ExternConst(ast::ItemExternConst),
+    /// A macro declaration.
+    Macro(ast::ItemMacro),
     /// A macro call expanding into an item.
     MacroCall(ast::MacroCall),
 }
@@ -38,6 +42,8 @@ impl Item {
             Self::Impl(item) => &item.attributes,
             Self::Mod(item) => &item.attributes,
             Self::Const(item) => &item.attributes,
+            Self::ExternConst(item) => &item.attributes,
+            Self::Macro(item) => &item.attributes,
             Self::MacroCall(item) => &item.attributes,
         }
     }
@@ -51,6 +57,8 @@ impl Item {
             Self::Impl(item) => &mut item.attributes,
             Self::Mod(item) => &mut item.attributes,
             Self::Const(item) => &mut item.attributes,
+            Self::ExternConst(item) => &mut item.attributes,
+            Self::Macro(item) => &mut item.attributes,
             Self::MacroCall(item) => &mut item.attributes,
         }
     }
@@ -61,6 +69,7 @@ impl Item {
             Self::Use(..) => true,
             Self::Struct(st) => st.needs_semi_colon(),
             Self::Const(..) => true,
+            Self::ExternConst(..) => true,
             _ => false,
         }
     }
@@ -76,6 +85,8 @@ impl Item {
             K![fn] => true,
             K![mod] => true,
             K![const] => true,
+            K![macro] => true,
+            K![extern] => matches!(p.nth(1), K![const]),
             _ => false,
         }
     }
@@ -93,6 +104,13 @@ impl Item {
                 take(&mut attributes),
                 path,
             )?)
+        } else if let Some(extern_token) = p.parse::<Option<T![extern]>>()? {
+            Self::ExternConst(ast::ItemExternConst::parse_with_meta(
+                p,
+                take(&mut attributes),
+                take(&mut visibility),
+                extern_token,
+            )?)
         } else {
             let mut const_token = p.parse::<Option<T![const]>>()?;
             let mut async_token = p.parse::<Option<T![async]>>()?;
@@ -129,7 +147,10 @@ impl Item {
                     take(&mut attributes),
                     take(&mut visibility),
                 )?),
-                K![ident] => {
+                K![macro] => {
+                    Self::Macro(ast::ItemMacro::parse_with_meta(p, take(&mut attributes))?)
+                }
+                kind if ast::Ident::is_kind(kind) => {
                     if let Some(const_token) = const_token.take() {
                         Self::Const(ast::ItemConst::parse_with_meta(
                             p,
@@ -144,7 +165,7 @@ impl Item {
                 _ => {
                     return Err(compile::Error::expected(
                         p.tok_at(0)?,
-                        "`fn`, `mod`, `struct`, `enum`, `use`, or macro call",
+                        "`fn`, `mod`, `struct`, `enum`, `use`, `macro`, or macro call",
                     ))
                 }
             };
@@ -10,6 +10,7 @@ fn ast_parse() {
         "#[repr(Rune)] enum Foo { Bar(a), Baz(b), #[default_value = \"zombie\"] Empty() }",
     );
     rt::<ast::ItemEnum>("pub enum Color { Blue, Red, Green }");
+    rt::<ast::ItemEnum>("pub enum Color { Blue = 1, Red = -2, Green }");
 
     rt::<ast::Fields>("( a, b, c )");
     rt::<ast::Fields>("{ a, b, c }");
@@ -53,4 +54,7 @@ pub struct ItemVariant {
     /// The body of the variant.
     #[rune(iter)]
     pub body: ast::Fields,
+    /// The explicit discriminant of the variant, like `= 1`.
+    #[rune(iter)]
+    pub discriminant: Option<(T![=], ast::Expr)>,
 }
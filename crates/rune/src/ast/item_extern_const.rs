@@ -0,0 +1,41 @@
+use crate::ast::prelude::*;
+
+#[test]
+fn ast_parse() {
+    use crate::testing::rt;
+
+    rt::<ast::ItemExternConst>("extern const BUILD_ID");
+    rt::<ast::ItemExternConst>("pub extern const BUILD_ID");
+}
+
+/// An external const declaration, whose value is provided by the host.
+#[derive(Debug, Clone, PartialEq, Eq, Parse, ToTokens, Spanned, Opaque)]
+#[rune(parse = "meta_only")]
+#[non_exhaustive]
+pub struct ItemExternConst {
+    /// Opaque identifier for the constant.
+    #[rune(id)]
+    pub(crate) id: Id,
+    /// The *inner* attributes that are applied to the const declaration.
+    #[rune(iter, meta)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The visibility of the const.
+    #[rune(option, meta)]
+    pub visibility: ast::Visibility,
+    /// The `extern` keyword.
+    #[rune(meta)]
+    pub extern_token: T![extern],
+    /// The `const` keyword.
+    pub const_token: T![const],
+    /// The name of the constant.
+    pub name: ast::Ident,
+}
+
+impl ItemExternConst {
+    /// Get the descriptive span of this item, e.g. `extern const ITEM`.
+    pub(crate) fn descriptive_span(&self) -> Span {
+        self.extern_token.span().join(self.name.span())
+    }
+}
+
+item_parse!(ExternConst, ItemExternConst, "external constant item");
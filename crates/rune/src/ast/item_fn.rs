@@ -19,6 +19,7 @@ fn ast_parse() {
 
     rt::<ast::ItemFn>("pub fn hello(foo, bar) {}");
     rt::<ast::ItemFn>("pub async fn hello(foo, bar) {}");
+    rt::<ast::ItemFn>("fn hello(foo: i64, bar: String) -> f64 {}");
     rt::<ast::ItemFn>("#[inline] fn hello(foo, bar) {}");
 
     let item = rt::<ast::ItemFn>("#[inline] pub async fn hello(foo, bar) {}");
@@ -64,6 +65,9 @@ pub struct ItemFn {
     pub name: ast::Ident,
     /// The arguments of the function.
     pub args: ast::Parenthesized<ast::FnArg, T![,]>,
+    /// The return type annotation of the function.
+    #[rune(iter)]
+    pub output: Option<(T![->], ast::Type)>,
     /// The body of the function.
     pub body: ast::Block,
 }
@@ -10,6 +10,8 @@ fn ast_parse() {
         "#[variant(enum_= \"SuperHero\", x = \"1\")] impl Foo { fn test(self) { } }",
     );
     rt::<ast::ItemImpl>("#[xyz] impl Foo { #[jit] fn test(self) { } }");
+    rt::<ast::ItemImpl>("impl Foo { const COUNT = 3; fn test(self) { } }");
+    rt::<ast::ItemImpl>("impl Foo { pub const A = 1; const B = A + 1; }");
 }
 
 /// An impl item.
@@ -25,6 +27,9 @@ pub struct ItemImpl {
     pub path: ast::Path,
     /// The open brace.
     pub open: T!['{'],
+    /// The associated constants.
+    #[rune(iter)]
+    pub consts: Vec<(ast::ItemConst, T![;])>,
     /// The collection of functions.
     #[rune(iter)]
     pub functions: Vec<ast::ItemFn>,
@@ -42,10 +47,37 @@ impl ItemImpl {
         let path = parser.parse()?;
         let open = parser.parse()?;
 
+        let mut consts = vec![];
         let mut functions = vec![];
 
         while !parser.peek::<ast::CloseBrace>()? {
-            functions.push(ast::ItemFn::parse(parser)?);
+            let attributes = parser.parse()?;
+            let visibility = parser.parse()?;
+            let const_token = parser.parse::<Option<T![const]>>()?;
+
+            match const_token {
+                Some(const_token) if ast::Ident::is_kind(parser.nth(0)?) => {
+                    let item = ast::ItemConst::parse_with_meta(
+                        parser,
+                        attributes,
+                        visibility,
+                        const_token,
+                    )?;
+
+                    consts.push((item, parser.parse()?));
+                }
+                const_token => {
+                    let async_token = parser.parse()?;
+
+                    functions.push(ast::ItemFn::parse_with_meta(
+                        parser,
+                        attributes,
+                        visibility,
+                        const_token,
+                        async_token,
+                    )?);
+                }
+            }
         }
 
         let close = parser.parse()?;
@@ -55,6 +87,7 @@ impl ItemImpl {
             impl_,
             path,
             open,
+            consts,
             functions,
             close,
         })
@@ -0,0 +1,39 @@
+use crate::ast::prelude::*;
+
+#[test]
+fn ast_parse() {
+    use crate::testing::rt;
+
+    rt::<ast::ItemMacro>("macro square($e:expr) { $e * $e }");
+    rt::<ast::ItemMacro>("macro unit() {}");
+    rt::<ast::ItemMacro>("/// Squares a number.\nmacro square($e:expr) { $e * $e }");
+}
+
+/// A macro declaration.
+///
+/// * `macro <name>(<matcher>) { <body> }`.
+#[derive(Debug, Clone, PartialEq, Eq, Parse, ToTokens, Spanned)]
+#[rune(parse = "meta_only")]
+#[non_exhaustive]
+pub struct ItemMacro {
+    /// The attributes of the macro.
+    #[rune(iter, meta)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The `macro` keyword.
+    pub macro_token: T![macro],
+    /// The name of the macro.
+    pub name: ast::Ident,
+    /// The tokens which the input of a macro call is matched against.
+    pub matcher: ast::Group,
+    /// The tokens which the macro call expands into.
+    pub body: ast::Group,
+}
+
+impl ItemMacro {
+    /// Get the descriptive span of this item, e.g. `macro square`.
+    pub(crate) fn descriptive_span(&self) -> Span {
+        self.macro_token.span().join(self.name.span())
+    }
+}
+
+item_parse!(Macro, ItemMacro, "macro item");
@@ -81,6 +81,7 @@ impl<'a> Resolve<'a> for Label {
                 Ok(ident)
             }
             ast::LitSource::BuiltIn(builtin) => Ok(builtin.as_str()),
+            ast::LitSource::Keyword(keyword) => Ok(keyword),
         }
     }
 }
@@ -84,7 +84,9 @@ impl<'a> Resolve<'a> for LitNumber {
             _ => {
                 return Err(compile::Error::new(
                     text.suffix,
-                    ErrorKind::UnsupportedSuffix,
+                    ErrorKind::UnsupportedSuffix {
+                        suffix: suffix.into(),
+                    },
                 ))
             }
         };
@@ -7,11 +7,13 @@ fn ast_parse() {
     rt::<ast::Local>("let x = 1;");
     rt::<ast::Local>("#[attr] let a = f();");
     rt::<ast::Local>("let a = b{}().foo[0].await;");
+    rt::<ast::Local>("let Some(a) = b else { return; };");
 }
 
 /// A local variable declaration.
 ///
 /// * `let <pattern> = <expr>;`
+/// * `let <pattern> = <expr> else { <diverging block> };`
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Parse, Spanned)]
 #[non_exhaustive]
 pub struct Local {
@@ -30,6 +32,10 @@ pub struct Local {
     /// The expression the binding is assigned to.
     #[rune(parse_with = "parse_expr")]
     pub expr: ast::Expr,
+    /// The optional `else` block, which is evaluated if the pattern doesn't
+    /// match. It must diverge.
+    #[rune(iter)]
+    pub else_: Option<ast::ExprElse>,
     /// Trailing semicolon of the local.
     pub semi: T![;],
 }
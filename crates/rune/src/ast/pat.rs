@@ -16,6 +16,8 @@ fn ast_parse() {
     rt::<ast::Pat>("var");
     rt::<ast::Pat>("_");
     rt::<ast::Pat>("Foo(n)");
+    rt::<ast::Pat>("n @ Foo(..)");
+    rt::<ast::Pat>("n @ [1, ..]");
 }
 
 /// A pattern match.
@@ -38,6 +40,8 @@ pub enum Pat {
     Binding(PatBinding),
     /// The rest pattern `..`.
     Rest(PatRest),
+    /// A binding of a sub-pattern `n @ pattern`.
+    At(PatAt),
 }
 
 impl Parse for Pat {
@@ -155,6 +159,18 @@ impl Parse for Pat {
                         colon: p.parse()?,
                         pat: p.parse()?,
                     }),
+                    K![@] => {
+                        let Some(ident) = path.try_as_ident() else {
+                            return Err(compile::Error::new(&path, ErrorKind::UnsupportedBinding));
+                        };
+
+                        Self::At(PatAt {
+                            attributes,
+                            ident: *ident,
+                            at: p.parse()?,
+                            pat: p.parse()?,
+                        })
+                    }
                     _ => Self::Path(PatPath { attributes, path }),
                 });
             }
@@ -256,6 +272,23 @@ pub struct PatBinding {
     pub pat: Box<ast::Pat>,
 }
 
+/// A binding of a sub-pattern, like `n @ Some(..)`.
+///
+/// The value is bound to `ident` if it matches `pat`.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct PatAt {
+    /// Attributes associated with the binding.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The name of the binding.
+    pub ident: ast::Ident,
+    /// The `@` token.
+    pub at: T![@],
+    /// The pattern the value has to match.
+    pub pat: Box<ast::Pat>,
+}
+
 /// A path pattern.
 #[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
 #[non_exhaustive]
@@ -13,6 +13,7 @@ fn ast_parse() {
     rt::<ast::Path>("super::bar");
     rt::<ast::Path>("HashMap::<Foo, Bar>");
     rt::<ast::Path>("super::HashMap::<Foo, Bar>");
+    rt::<ast::Path>("select::default");
 }
 
 /// A path, where each element is separated by a `::`.
@@ -228,10 +229,10 @@ impl Parse for PathSegment {
         let segment = match p.nth(0)? {
             K![Self] => Self::SelfType(p.parse()?),
             K![self] => Self::SelfValue(p.parse()?),
-            K![ident] => Self::Ident(p.parse()?),
             K![crate] => Self::Crate(p.parse()?),
             K![super] => Self::Super(p.parse()?),
             K![<] => Self::Generics(p.parse()?),
+            kind if ast::Ident::is_kind(kind) => Self::Ident(p.parse()?),
             _ => {
                 return Err(compile::Error::expected(p.tok_at(0)?, "path segment"));
             }
@@ -245,8 +246,8 @@ impl Peek for PathSegment {
     fn peek(p: &mut Peeker<'_>) -> bool {
         matches!(
             p.nth(0),
-            K![<] | K![Self] | K![self] | K![crate] | K![super] | K![ident]
-        )
+            K![<] | K![Self] | K![self] | K![crate] | K![super]
+        ) || ast::Ident::peek(p)
     }
 }
 
@@ -8,6 +8,8 @@ fn ast_parse() {
 
     rt::<ast::Stmt>("let x = 1;");
     rt::<ast::Stmt>("#[attr] let a = f();");
+    rt::<ast::Stmt>("defer { close(file); }");
+    rt::<ast::Stmt>("defer(file);");
 }
 
 /// A statement within a block.
@@ -25,11 +27,13 @@ pub enum Stmt {
     ///
     /// And absent semicolon indicates that it is synthetic.
     Semi(StmtSemi),
+    /// A deferred block.
+    Defer(Box<StmtDefer>),
 }
 
 impl Peek for Stmt {
     fn peek(p: &mut Peeker<'_>) -> bool {
-        matches!(p.nth(0), K![let]) || ItemOrExpr::peek(p)
+        matches!(p.nth(0), K![let]) || StmtDefer::peek(p) || ItemOrExpr::peek(p)
     }
 }
 
@@ -58,6 +62,8 @@ impl Parse for Stmt {
         let stmt = if let K![let] = p.nth(0)? {
             let local = Box::new(ast::Local::parse_with_meta(p, take(&mut attributes))?);
             Self::Local(local)
+        } else if p.peek::<StmtDefer>()? {
+            Self::Defer(Box::new(p.parse()?))
         } else {
             let expr = ast::Expr::parse_with_meta(p, &mut attributes, ast::expr::CALLABLE)?;
 
@@ -98,6 +104,7 @@ impl Peek for ItemOrExpr {
             K![fn] => true,
             K![mod] => true,
             K![const] => true,
+            K![macro] => true,
             K![ident(..)] => true,
             K![::] => true,
             _ => ast::Expr::peek(p),
@@ -169,6 +176,24 @@ impl StmtSemi {
     }
 }
 
+/// A deferred block, which is executed when the enclosing scope is exited.
+///
+/// * `defer <block>`.
+#[derive(Debug, Clone, PartialEq, Eq, Parse, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct StmtDefer {
+    /// The `defer` token.
+    pub defer_token: T![defer],
+    /// The block to execute on scope exit.
+    pub block: ast::Block,
+}
+
+impl Peek for StmtDefer {
+    fn peek(p: &mut Peeker<'_>) -> bool {
+        matches!((p.nth(0), p.nth(1)), (K![defer], K!['{']))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ast;
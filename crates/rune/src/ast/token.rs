@@ -376,6 +376,8 @@ pub enum LitSource {
     Synthetic(SyntheticId),
     /// Built-in strings.
     BuiltIn(BuiltIn),
+    /// A contextual keyword which is used as an identifier.
+    Keyword(&'static str),
 }
 
 /// The source of the literal string. This need to be treated separately from
@@ -0,0 +1,24 @@
+use crate::ast::prelude::*;
+
+#[test]
+fn ast_parse() {
+    use crate::testing::rt;
+
+    rt::<ast::Type>("i64");
+    rt::<ast::Type>("String");
+    rt::<ast::Type>("std::string::String");
+}
+
+/// A type annotation, like `String` in `fn hello(name: String)`.
+#[derive(Debug, Clone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub enum Type {
+    /// A type referenced by path.
+    Path(ast::Path),
+}
+
+impl Parse for Type {
+    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+        Ok(Self::Path(p.parse()?))
+    }
+}
@@ -3,14 +3,22 @@ use core::marker::PhantomData;
 use core::mem::take;
 
 use crate::no_std::prelude::*;
+use crate::no_std::sync::Arc;
 
 use crate::ast::{Span, Spanned};
 use crate::compile;
-use crate::compile::{CompileVisitor, FileSourceLoader, Located, Options, Pool, SourceLoader};
-use crate::runtime::unit::{DefaultStorage, UnitEncoder};
+use crate::compile::{
+    CompilePass, CompileVisitor, FileSourceLoader, Located, Options, Pool, SourceLoader,
+};
+use crate::runtime::unit::{DefaultStorage, UnitEncoder, UnitStorage};
 use crate::runtime::Unit;
 use crate::{Context, Diagnostics, SourceId, Sources};
 
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "std")]
+pub use self::batch::{prepare_batch, BatchBuild, BatchOutput};
+
 /// Error raised when we failed to load sources.
 ///
 /// Look at the passed in [Diagnostics] instance for details.
@@ -86,7 +94,9 @@ where
         diagnostics: None,
         options: None,
         visitors: Vec::new(),
+        passes: Vec::new(),
         source_loader: None,
+        base: None,
         _unit_storage: PhantomData,
     }
 }
@@ -98,7 +108,9 @@ pub struct Build<'a, S> {
     diagnostics: Option<&'a mut Diagnostics>,
     options: Option<&'a Options>,
     visitors: Vec<&'a mut dyn compile::CompileVisitor>,
+    passes: Vec<&'a mut dyn compile::CompilePass>,
     source_loader: Option<&'a mut dyn SourceLoader>,
+    base: Option<Arc<Unit>>,
     _unit_storage: PhantomData<S>,
 }
 
@@ -137,6 +149,35 @@ impl<'a> compile::CompileVisitor for CompileVisitorGroup<'a> {
         }
     }
 
+    fn visit_macro_call(&mut self, location: &dyn Located) {
+        for v in self.visitors.iter_mut() {
+            v.visit_macro_call(location)
+        }
+    }
+
+    fn visit_template(
+        &mut self,
+        location: &dyn Located,
+        item: &compile::Item,
+        parts: &[compile::TemplatePart<'_>],
+    ) {
+        for v in self.visitors.iter_mut() {
+            v.visit_template(location, item, parts)
+        }
+    }
+
+    fn visit_assembly_dump(
+        &mut self,
+        location: &dyn Located,
+        item: &compile::Item,
+        pass: Option<compile::Optimization>,
+        assembly: &str,
+    ) {
+        for v in self.visitors.iter_mut() {
+            v.visit_assembly_dump(location, item, pass, assembly)
+        }
+    }
+
     fn visit_doc_comment(
         &mut self,
         location: &dyn Located,
@@ -163,6 +204,49 @@ impl<'a> compile::CompileVisitor for CompileVisitorGroup<'a> {
     }
 }
 
+/// Wraps a collection of CompilePass
+struct CompilePassGroup<'a> {
+    passes: Vec<&'a mut dyn compile::CompilePass>,
+}
+
+impl<'a> compile::CompilePass for CompilePassGroup<'a> {
+    fn visit_fn(
+        &mut self,
+        cx: &mut compile::PassContext<'_>,
+        f: &compile::PassFn<'_>,
+    ) -> compile::Result<()> {
+        for p in self.passes.iter_mut() {
+            p.visit_fn(cx, f)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_assembly(
+        &mut self,
+        cx: &mut compile::PassContext<'_>,
+        f: &compile::PassAssembly<'_>,
+    ) -> compile::Result<()> {
+        for p in self.passes.iter_mut() {
+            p.visit_assembly(cx, f)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_call(
+        &mut self,
+        cx: &mut compile::PassContext<'_>,
+        call: &mut compile::PassCall<'_>,
+    ) -> compile::Result<()> {
+        for p in self.passes.iter_mut() {
+            p.visit_call(cx, call)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<'a, S> Build<'a, S> {
     /// Modify the current [Build] to use the given [Context] while building.
     ///
@@ -201,6 +285,17 @@ impl<'a, S> Build<'a, S> {
         self
     }
 
+    /// Modify the current [Build] to run the given [CompilePass].
+    ///
+    /// Compile passes are called with every function as it's being compiled,
+    /// and can be used to enforce custom rules or perform limited rewrites.
+    /// Passes are called in the order in which they were added.
+    #[inline]
+    pub fn with_pass(mut self, pass: &'a mut dyn CompilePass) -> Self {
+        self.passes.push(pass);
+        self
+    }
+
     /// Modify the current [Build] to configure the given [SourceLoader].
     ///
     /// Source loaders are used to determine how sources are loaded externally
@@ -211,10 +306,24 @@ impl<'a, S> Build<'a, S> {
         self
     }
 
+    /// Modify the current [Build] to layer the built unit on top of the given
+    /// base unit.
+    ///
+    /// Functions defined in the base unit can be called from the sources being
+    /// built, but none of the items in the base unit can be redefined. The
+    /// built unit is intended to be executed through a [LayeredVm].
+    ///
+    /// [LayeredVm]: crate::runtime::LayeredVm
+    #[inline]
+    pub fn with_base_unit(mut self, base: Arc<Unit>) -> Self {
+        self.base = Some(base);
+        self
+    }
+
     /// Build a [`Unit`] with the current configuration.
     pub fn build(mut self) -> Result<Unit<S>, BuildError>
     where
-        S: Default + UnitEncoder,
+        S: Default + UnitEncoder + UnitStorage,
     {
         let default_context;
 
@@ -226,13 +335,7 @@ impl<'a, S> Build<'a, S> {
             }
         };
 
-        let mut unit = compile::UnitBuilder::default();
-
-        let prelude = if context.has_default_modules() {
-            compile::Prelude::with_default_prelude()
-        } else {
-            compile::Prelude::default()
-        };
+        let prelude = prelude_for(context);
 
         let mut default_diagnostics;
 
@@ -268,6 +371,10 @@ impl<'a, S> Build<'a, S> {
             }
         };
 
+        let mut passes = CompilePassGroup {
+            passes: take(&mut self.passes),
+        };
+
         let mut default_source_loader;
 
         let source_loader = match self.source_loader.take() {
@@ -278,40 +385,84 @@ impl<'a, S> Build<'a, S> {
             }
         };
 
-        let mut pool = Pool::default();
-        let mut unit_storage = S::default();
-
-        let result = compile::compile(
-            &mut unit,
-            &prelude,
+        build_unit(
             self.sources,
-            &mut pool,
             context,
+            &prelude,
             visitors,
+            &mut passes,
             diagnostics,
             source_loader,
             options,
-            &mut unit_storage,
-        );
+            self.base.take(),
+        )
+    }
+}
 
-        if let Err(()) = result {
-            return Err(BuildError);
-        }
+/// Construct the prelude to use when building with the given context.
+fn prelude_for(context: &Context) -> compile::Prelude {
+    if context.has_default_modules() {
+        compile::Prelude::with_default_prelude()
+    } else {
+        compile::Prelude::default()
+    }
+}
 
-        if options.link_checks {
-            unit.link(context, diagnostics);
+/// Build a single collection of sources into a [Unit].
+fn build_unit<S>(
+    sources: &mut Sources,
+    context: &Context,
+    prelude: &compile::Prelude,
+    visitor: &mut dyn CompileVisitor,
+    passes: &mut dyn CompilePass,
+    diagnostics: &mut Diagnostics,
+    source_loader: &mut dyn SourceLoader,
+    options: &Options,
+    base: Option<Arc<Unit>>,
+) -> Result<Unit<S>, BuildError>
+where
+    S: Default + UnitEncoder + UnitStorage,
+{
+    let mut unit = compile::UnitBuilder::default();
 
-            if diagnostics.has_error() {
-                return Err(BuildError);
-            }
+    if let Some(base) = base {
+        unit.set_base(base);
+    }
+
+    let mut pool = Pool::default();
+    let mut unit_storage = S::default();
+
+    let result = compile::compile(
+        &mut unit,
+        prelude,
+        sources,
+        &mut pool,
+        context,
+        visitor,
+        passes,
+        diagnostics,
+        source_loader,
+        options,
+        &mut unit_storage,
+    );
+
+    if let Err(()) = result {
+        return Err(BuildError);
+    }
+
+    if options.link_checks {
+        unit.link(context, diagnostics);
+
+        if diagnostics.has_error() {
+            return Err(BuildError);
         }
+    }
 
-        match unit.build(Span::empty(), unit_storage) {
-            Ok(unit) => Ok(unit),
-            Err(error) => {
-                diagnostics.error(SourceId::empty(), error);
-                Err(BuildError)
-            }
+    match unit.build(Span::empty(), unit_storage, options.debug_level) {
+        Ok(unit) => Ok(unit),
+        Err(error) => {
+            diagnostics.error(SourceId::empty(), error);
+            Err(BuildError)
         }
     }
 }
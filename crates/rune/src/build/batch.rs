@@ -0,0 +1,192 @@
+use std::sync::Mutex;
+use std::thread;
+
+use crate::no_std::prelude::*;
+
+use crate::build::{build_unit, prelude_for, BuildError, CompilePassGroup, CompileVisitorGroup};
+use crate::compile::{FileSourceLoader, Options};
+use crate::runtime::Unit;
+use crate::{Context, Diagnostics, Sources};
+
+/// Entry point to building many independent collections of [Sources] at
+/// once, like when compiling a large number of scripts provided by users.
+///
+/// Every collection of sources is built into its own [Unit] as if it was
+/// built through [prepare][crate::prepare], but the context, options and the
+/// prelude derived from the context are shared between them and the builds
+/// are spread out over multiple threads. A collection which fails to build
+/// doesn't affect any of the others.
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Source, Sources};
+///
+/// let context = Context::with_default_modules()?;
+///
+/// let mut batch = Vec::new();
+///
+/// for source in ["pub fn main() { 1 }", "pub fn main() { 1 + }", "pub fn main() { 3 }"] {
+///     let mut sources = Sources::new();
+///     sources.insert(Source::new("script", source));
+///     batch.push(sources);
+/// }
+///
+/// let outputs = rune::prepare_batch(&mut batch).with_context(&context).build();
+///
+/// assert_eq!(outputs.len(), 3);
+/// assert!(outputs[0].unit.is_ok());
+/// assert!(outputs[1].unit.is_err());
+/// assert!(outputs[1].diagnostics.has_error());
+/// assert!(outputs[2].unit.is_ok());
+/// # Ok::<_, rune::Error>(())
+/// ```
+pub fn prepare_batch(sources: &mut [Sources]) -> BatchBuild<'_> {
+    BatchBuild {
+        sources,
+        context: None,
+        options: None,
+        jobs: None,
+    }
+}
+
+/// High level helper for setting up a batch build of many independent
+/// collections of [Sources], as constructed through [prepare_batch].
+pub struct BatchBuild<'a> {
+    sources: &'a mut [Sources],
+    context: Option<&'a Context>,
+    options: Option<&'a Options>,
+    jobs: Option<usize>,
+}
+
+/// The outcome of building one collection of sources in a [BatchBuild].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct BatchOutput {
+    /// The built unit, or an error if the sources failed to build in which
+    /// case the reason is found in the diagnostics.
+    pub unit: Result<Unit, BuildError>,
+    /// The diagnostics produced while building the sources.
+    pub diagnostics: Diagnostics,
+}
+
+impl<'a> BatchBuild<'a> {
+    /// Modify the current [BatchBuild] to use the given [Context] while
+    /// building.
+    ///
+    /// If unspecified the empty context constructed with [Context::new] will
+    /// be used.
+    #[inline]
+    pub fn with_context(mut self, context: &'a Context) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Modify the current [BatchBuild] to use the given [Options].
+    #[inline]
+    pub fn with_options(mut self, options: &'a Options) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Modify the current [BatchBuild] to use the given number of threads.
+    ///
+    /// If unspecified this is the amount of parallelism available, and it's
+    /// never more than the number of collections of sources being built.
+    #[inline]
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = Some(jobs.max(1));
+        self
+    }
+
+    /// Build every collection of sources, returning one [BatchOutput] for
+    /// each of them in the order they were provided.
+    pub fn build(self) -> Vec<BatchOutput> {
+        let default_context;
+
+        let context = match self.context {
+            Some(context) => context,
+            None => {
+                default_context = Context::new();
+                &default_context
+            }
+        };
+
+        let default_options;
+
+        let options = match self.options {
+            Some(options) => options,
+            None => {
+                default_options = Options::default();
+                &default_options
+            }
+        };
+
+        let prelude = prelude_for(context);
+
+        let jobs = match self.jobs {
+            Some(jobs) => jobs,
+            None => thread::available_parallelism().map_or(1, |n| n.get()),
+        };
+
+        let jobs = jobs.min(self.sources.len());
+        let mut outputs = Vec::new();
+        outputs.resize_with(self.sources.len(), || None);
+
+        let queue = Mutex::new(self.sources.iter_mut().enumerate());
+
+        thread::scope(|s| {
+            let handles = (0..jobs)
+                .map(|_| {
+                    let (queue, prelude) = (&queue, &prelude);
+
+                    s.spawn(move || {
+                        let mut outputs = Vec::new();
+
+                        loop {
+                            // NB: the lock is released before building.
+                            let next = queue.lock().unwrap_or_else(|e| e.into_inner()).next();
+
+                            let Some((index, sources)) = next else {
+                                break;
+                            };
+
+                            let mut diagnostics = Diagnostics::new();
+
+                            let unit = build_unit(
+                                sources,
+                                context,
+                                prelude,
+                                &mut CompileVisitorGroup { visitors: vec![] },
+                                &mut CompilePassGroup { passes: vec![] },
+                                &mut diagnostics,
+                                &mut FileSourceLoader::new(),
+                                options,
+                                None,
+                            );
+
+                            outputs.push((index, BatchOutput { unit, diagnostics }));
+                        }
+
+                        outputs
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            for handle in handles {
+                // NB: a panic in a worker is propagated to the caller, just
+                // like it would be when building the sources one by one.
+                let built = match handle.join() {
+                    Ok(built) => built,
+                    Err(panic) => std::panic::resume_unwind(panic),
+                };
+
+                for (index, output) in built {
+                    outputs[index] = Some(output);
+                }
+            }
+        });
+
+        outputs.into_iter().flatten().collect()
+    }
+}
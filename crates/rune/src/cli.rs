@@ -5,6 +5,7 @@
 //! * Generate documentation using types only available in your context.
 //! * Build a language server, which is aware of things only available in your
 //!   context.
+//! * Run tests in your own harness through the [TestRunner].
 
 mod benches;
 mod check;
@@ -12,11 +13,16 @@ mod doc;
 mod format;
 mod languageserver;
 mod loader;
+mod repl;
 mod run;
+mod test_runner;
 mod tests;
 mod visitor;
 mod naming;
 
+pub use self::repl::{Repl, ReplError};
+pub use self::test_runner::{Test, TestOutcome, TestReport, TestResult, TestRunner};
+
 use std::fmt;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
@@ -30,6 +36,7 @@ use tracing_subscriber::filter::EnvFilter;
 
 use crate::compile::{ItemBuf, ParseOptionError};
 use crate::modules::capture_io::CaptureIo;
+use crate::runtime::Inst;
 use crate::termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 use crate::{Context, ContextError, Options, Hash};
 
@@ -332,6 +339,21 @@ struct HashFlags {
     item: Vec<String>,
 }
 
+#[derive(Parser, Debug)]
+struct ExplainFlags {
+    /// Error or warning codes to explain, like `E0020` or `W0001`. If none are
+    /// specified, all codes are listed.
+    #[arg(name = "code")]
+    code: Vec<String>,
+}
+
+#[derive(Parser, Debug)]
+struct InstructionsFlags {
+    /// Output the instruction set as JSON instead of a markdown reference.
+    #[arg(long)]
+    json: bool,
+}
+
 enum AssetKind {
     Bin,
     Test,
@@ -375,24 +397,33 @@ enum Command {
     Bench(CommandShared<benches::Flags>),
     /// Run the designated script
     Run(CommandShared<run::Flags>),
+    /// Start an interactive session
+    Repl(CommandShared<repl::Flags>),
     /// Format the provided file
     Fmt(CommandShared<format::Flags>),
     /// Run a language server.
     LanguageServer(SharedFlags),
     /// Helper command to generate type hashes.
     Hash(HashFlags),
+    /// Generate a reference of the instruction set of the virtual machine.
+    Instructions(InstructionsFlags),
+    /// Explain the given error or warning codes.
+    Explain(ExplainFlags),
 }
 
 impl Command {
-    const ALL: [&str; 8] = [
+    const ALL: [&str; 11] = [
         "check",
         "doc",
         "test",
         "bench",
         "run",
+        "repl",
         "fmt",
         "languageserver",
         "hash",
+        "instructions",
+        "explain",
     ];
 
     fn as_command_base_mut(&mut self) -> Option<(&mut SharedFlags, &mut dyn CommandBase)> {
@@ -402,9 +433,12 @@ impl Command {
             Command::Test(shared) => (&mut shared.shared, &mut shared.command),
             Command::Bench(shared) => (&mut shared.shared, &mut shared.command),
             Command::Run(shared) => (&mut shared.shared, &mut shared.command),
+            Command::Repl(shared) => (&mut shared.shared, &mut shared.command),
             Command::Fmt(shared) => (&mut shared.shared, &mut shared.command),
             Command::LanguageServer(..) => return None,
             Command::Hash(..) => return None,
+            Command::Instructions(..) => return None,
+            Command::Explain(..) => return None,
         };
 
         Some((shared, command))
@@ -417,9 +451,12 @@ impl Command {
             Command::Test(shared) => (&shared.shared, &shared.command),
             Command::Bench(shared) => (&shared.shared, &shared.command),
             Command::Run(shared) => (&shared.shared, &shared.command),
+            Command::Repl(shared) => (&shared.shared, &shared.command),
             Command::Fmt(shared) => (&shared.shared, &shared.command),
             Command::LanguageServer(..) => return None,
             Command::Hash(..) => return None,
+            Command::Instructions(..) => return None,
+            Command::Explain(..) => return None,
         };
 
         Some(CommandSharedRef {
@@ -513,6 +550,40 @@ impl SharedFlags {
 
         Ok(context)
     }
+
+    /// Emit diagnostics in the configured message format.
+    fn emit_diagnostics<O>(
+        &self,
+        out: &mut O,
+        diagnostics: &crate::Diagnostics,
+        sources: &crate::Sources,
+    ) -> Result<()>
+    where
+        O: WriteColor,
+    {
+        match self.message_format {
+            MessageFormat::Human => {
+                diagnostics.emit(out, sources)?;
+            }
+            MessageFormat::Json => {
+                for diagnostic in diagnostics.to_json(sources)? {
+                    serde_json::to_writer(&mut *out, &diagnostic)?;
+                    writeln!(out)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The format diagnostics are emitted in.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum MessageFormat {
+    /// Human-readable diagnostics.
+    Human,
+    /// Machine-readable diagnostics, one JSON object per line.
+    Json,
 }
 
 #[derive(Parser, Debug)]
@@ -558,6 +629,15 @@ struct SharedFlags {
     #[arg(long)]
     verbose: bool,
 
+    /// The format to emit diagnostics in.
+    ///
+    /// Valid options are:
+    /// * `human` - human-readable diagnostics with source snippets.
+    /// * `json` - one JSON object per diagnostic and line, with spans as byte
+    ///   offsets into the source they point into.
+    #[arg(long, value_enum, default_value = "human")]
+    message_format: MessageFormat,
+
     /// Collect sources to operate over from the workspace.
     ///
     /// This is what happens by default, but is disabled in case any `<paths>`
@@ -573,9 +653,21 @@ struct SharedFlags {
     ///
     /// debug-info[=<true/false>] - Enable or disable debug info.
     ///
+    /// debug-level=<none/lines/full> - Set how much debug info is included, where lines only includes what's needed for backtraces.
+    ///
     /// macros[=<true/false>] - Enable or disable macros (experimental).
     ///
-    /// bytecode[=<true/false>] - Enable or disable bytecode caching (experimental).
+    /// bytecode[=<true/false>] - Enable or disable bytecode caching (experimental). Cached bytecode is discarded when the contents of its sources or the compiler options change.
+    ///
+    /// bytecode-verify[=<true/false>] - Verify bytecode loaded from the cache before it's used.
+    ///
+    /// early-bound[=<true/false>] - Call instance functions directly when the type of the instance is known.
+    ///
+    /// type-checks[=<true/false>] - Check type annotations of function arguments, or ignore them entirely.
+    ///
+    /// const-budget=<n> - The number of evaluation steps constant evaluation is allowed to take.
+    ///
+    /// const-recursion-limit=<n> - How deeply constant functions are allowed to call each other.
     #[arg(name = "option", short = 'O', number_of_values = 1)]
     compiler_options: Vec<String>,
 
@@ -820,6 +912,7 @@ where
         }
         Command::Bench(f) => {
             let options = f.options()?;
+            let mut results = Vec::new();
 
             for e in entries {
                 let capture_io = crate::modules::capture_io::CaptureIo::new();
@@ -842,6 +935,7 @@ where
                     load.unit,
                     &load.sources,
                     &load.functions,
+                    &mut results,
                 )
                 .await?
                 {
@@ -849,6 +943,8 @@ where
                     other => return Ok(other),
                 }
             }
+
+            benches::write_json(&f.command, &results)?;
         }
         Command::Run(f) => {
             let options = f.options()?;
@@ -870,6 +966,11 @@ where
                 }
             }
         }
+        Command::Repl(f) => {
+            let options = f.options()?;
+            let context = f.shared.context(entry, c, None)?;
+            return repl::run(io, &f.command, context, options, entries).await;
+        }
         Command::LanguageServer(shared) => {
             let context = shared.context(entry, c, None)?;
             languageserver::run(context).await?;
@@ -888,6 +989,36 @@ where
                 writeln!(io.stdout, "{item} => {hash}")?;
             }
         }
+        Command::Instructions(args) => {
+            if args.json {
+                serde_json::to_writer_pretty(&mut *io.stdout, Inst::METADATA)?;
+                writeln!(io.stdout)?;
+            } else {
+                let mut out = String::from("# Instructions\n\n");
+
+                for meta in Inst::METADATA {
+                    meta.write_markdown(&mut out)?;
+                }
+
+                write!(io.stdout, "{out}")?;
+            }
+        }
+        Command::Explain(args) => {
+            if args.code.is_empty() {
+                for explanation in crate::diagnostics::explanations() {
+                    writeln!(io.stdout, "{}: {}", explanation.code, explanation.title)?;
+                }
+            }
+
+            for code in &args.code {
+                let Some(explanation) = crate::diagnostics::explain(code) else {
+                    writeln!(io.stderr, "error: no such code `{code}`")?;
+                    return Ok(ExitCode::Failure);
+                };
+
+                writeln!(io.stdout, "{explanation}")?;
+            }
+        }
     }
 
     Ok(ExitCode::Success)
@@ -1,13 +1,20 @@
+#[cfg(test)]
+mod tests;
+
 use std::fmt;
+use std::fs;
 use std::io::Write;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 
 use crate::no_std::prelude::*;
 
+use anyhow::Context as _;
 use clap::Parser;
 
 use crate::cli::{ExitCode, Io, CommandBase, AssetKind, Config, SharedFlags};
+use crate::cli::test_runner;
 use crate::compile::{Item, ItemBuf};
 use crate::modules::capture_io::CaptureIo;
 use crate::runtime::{Function, Unit, Value};
@@ -20,9 +27,30 @@ pub(super) struct Flags {
     #[arg(long, default_value = "100")]
     warmup: u32,
 
-    /// Iterations to run of the benchmark
-    #[arg(long, default_value = "100")]
+    /// Number of samples to collect of the benchmark
+    #[arg(long, default_value = "100", value_parser = clap::value_parser!(u32).range(1..))]
+    samples: u32,
+
+    /// Iterations to run of the benchmark for each sample. Increase this for
+    /// benchmarks which are too fast to be timed accurately one at a time.
+    #[arg(long, default_value = "1", value_parser = clap::value_parser!(u32).range(1..))]
     iterations: u32,
+
+    /// Keep samples which are outliers, instead of rejecting the ones outside
+    /// of the inner fences of their interquartile range.
+    #[arg(long)]
+    keep_outliers: bool,
+
+    /// Only run benchmarks whose name matches the given filter. A filter
+    /// containing `*` or `?` is matched as a glob against the whole name of the
+    /// benchmark, otherwise it matches any benchmark it is a substring of.
+    #[arg(long = "filter")]
+    filters: Vec<String>,
+
+    /// Write a JSON report of the benchmarks to the given path, with all times
+    /// in nanoseconds.
+    #[arg(long)]
+    json: Option<PathBuf>,
 }
 
 impl CommandBase for Flags {
@@ -42,7 +70,9 @@ impl CommandBase for Flags {
     }
 }
 
-/// Run benchmarks.
+/// Run benchmarks, adding the statistics of the ones which completed to
+/// `results`.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run(
     io: &mut Io<'_>,
     args: &Flags,
@@ -51,10 +81,16 @@ pub(super) async fn run(
     unit: Arc<Unit>,
     sources: &Sources,
     fns: &[(Hash, ItemBuf)],
+    results: &mut Vec<(String, Stats)>,
 ) -> anyhow::Result<ExitCode> {
     let runtime = Arc::new(context.runtime());
     let mut vm = Vm::new(runtime, unit);
 
+    let fns = fns
+        .iter()
+        .filter(|(_, item)| test_runner::is_match(&args.filters, &item.to_string()))
+        .collect::<Vec<_>>();
+
     if fns.is_empty() {
         return Ok(ExitCode::Success);
     }
@@ -85,16 +121,24 @@ pub(super) async fn run(
         let multiple = fns.len() > 1;
 
         for (i, f) in fns.iter().enumerate() {
-            if let Err(e) = bench_fn(io, i, item, args, f, multiple) {
-                writeln!(io.stdout, "{}: Error in bench iteration: {}", item, e)?;
+            let name = bench_name(item, i, multiple);
 
-                if let Some(capture_io) = capture_io {
-                    writeln!(io.stdout, "-- output --")?;
-                    capture_io.drain_into(&mut *io.stdout)?;
-                    writeln!(io.stdout, "-- end output --")?;
+            match bench_fn(args, f) {
+                Ok(stats) => {
+                    writeln!(io.stdout, "bench {}: {}", name, stats)?;
+                    results.push((name, stats));
                 }
+                Err(e) => {
+                    writeln!(io.stdout, "{}: Error in bench iteration: {}", item, e)?;
 
-                any_error = true;
+                    if let Some(capture_io) = capture_io {
+                        writeln!(io.stdout, "-- output --")?;
+                        capture_io.drain_into(&mut *io.stdout)?;
+                        writeln!(io.stdout, "-- end output --")?;
+                    }
+
+                    any_error = true;
+                }
             }
         }
     }
@@ -106,79 +150,161 @@ pub(super) async fn run(
     }
 }
 
-fn bench_fn(
-    io: &mut Io<'_>,
-    i: usize,
-    item: &Item,
-    args: &Flags,
-    f: &Function,
-    multiple: bool,
-) -> anyhow::Result<()> {
+/// Write the JSON report of the given results, if one was requested.
+pub(super) fn write_json(args: &Flags, results: &[(String, Stats)]) -> anyhow::Result<()> {
+    let Some(path) = &args.json else {
+        return Ok(());
+    };
+
+    let benches = results
+        .iter()
+        .map(|(name, stats)| {
+            serde_json::json!({
+                "name": name,
+                "mean": stats.mean,
+                "median": stats.median,
+                "stddev": stats.stddev,
+                "min": stats.min,
+                "max": stats.max,
+                "samples": stats.samples,
+                "outliers": stats.outliers,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let report = serde_json::json!({
+        "warmup": args.warmup,
+        "iterations": args.iterations,
+        "benches": benches,
+    });
+
+    let mut out = Vec::new();
+    serde_json::to_writer_pretty(&mut out, &report)?;
+    fs::write(path, out).with_context(|| path.display().to_string())?;
+    Ok(())
+}
+
+fn bench_name(item: &Item, i: usize, multiple: bool) -> String {
+    if multiple {
+        format!("{}#{}", item, i)
+    } else {
+        item.to_string()
+    }
+}
+
+fn bench_fn(args: &Flags, f: &Function) -> anyhow::Result<Stats> {
     for _ in 0..args.warmup {
         let value = f.call::<_, Value>(()).into_result()?;
         drop(value);
     }
 
-    let iterations = usize::try_from(args.iterations).expect("iterations out of bounds");
-    let mut collected = Vec::with_capacity(iterations);
+    let samples = usize::try_from(args.samples).expect("samples out of bounds");
+    let mut collected = Vec::with_capacity(samples);
 
-    for _ in 0..args.iterations {
+    for _ in 0..args.samples {
         let start = Instant::now();
-        let value = f.call::<_, Value>(()).into_result()?;
-        let duration = Instant::now().duration_since(start);
-        collected.push(duration.as_nanos() as i128);
-        drop(value);
-    }
-
-    collected.sort_unstable();
 
-    let len = collected.len() as f64;
-    let average = collected.iter().copied().sum::<i128>() as f64 / len;
-    let variance = collected
-        .iter()
-        .copied()
-        .map(|n| (n as f64 - average).powf(2.0))
-        .sum::<f64>()
-        / len;
-    let stddev = variance.sqrt();
-
-    let format = Format {
-        average: average as u128,
-        stddev: stddev as u128,
-        iterations,
-    };
+        for _ in 0..args.iterations {
+            let value = f.call::<_, Value>(()).into_result()?;
+            drop(value);
+        }
 
-    if multiple {
-        writeln!(io.stdout, "bench {}#{}: {}", item, i, format)?;
-    } else {
-        writeln!(io.stdout, "bench {}: {}", item, format)?;
+        let duration = Instant::now().duration_since(start);
+        collected.push(duration.as_nanos() as f64 / f64::from(args.iterations));
     }
 
-    Ok(())
+    Ok(Stats::new(&mut collected, !args.keep_outliers))
+}
+
+/// Statistics over the samples of a benchmark, in nanoseconds per iteration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Stats {
+    mean: f64,
+    median: f64,
+    stddev: f64,
+    min: f64,
+    max: f64,
+    /// The number of samples the statistics were calculated over.
+    samples: usize,
+    /// The number of samples which were rejected as outliers.
+    outliers: usize,
 }
 
-struct Format {
-    average: u128,
-    stddev: u128,
-    iterations: usize,
+impl Stats {
+    /// Calculate statistics over the given non-empty samples.
+    ///
+    /// If `reject_outliers` is set, samples outside of the inner fences of the
+    /// interquartile range are not included.
+    fn new(samples: &mut [f64], reject_outliers: bool) -> Self {
+        samples.sort_unstable_by(f64::total_cmp);
+
+        let mut kept = &samples[..];
+
+        if reject_outliers {
+            let q1 = quantile(samples, 0.25);
+            let q3 = quantile(samples, 0.75);
+            let iqr = q3 - q1;
+            let (low, high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+            let start = samples.iter().take_while(|n| **n < low).count();
+            let end = samples.len() - samples.iter().rev().take_while(|n| **n > high).count();
+            kept = &samples[start..end];
+        }
+
+        let len = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / len;
+        let variance = kept.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / len;
+
+        Self {
+            mean,
+            median: quantile(kept, 0.5),
+            stddev: variance.sqrt(),
+            min: kept[0],
+            max: kept[kept.len() - 1],
+            samples: kept.len(),
+            outliers: samples.len() - kept.len(),
+        }
+    }
 }
 
-impl fmt::Display for Format {
+impl fmt::Display for Stats {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "mean={:.2}, stddev={:.2}, iterations={}",
-            Time(self.average),
+            "mean={}, median={}, stddev={}, min={}, max={}, samples={}, outliers={}",
+            Time(self.mean),
+            Time(self.median),
             Time(self.stddev),
-            self.iterations
+            Time(self.min),
+            Time(self.max),
+            self.samples,
+            self.outliers
         )
     }
 }
 
-struct Time(u128);
+/// Get the given quantile of sorted samples, interpolating between the two
+/// closest samples.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = (sorted.len() - 1) as f64 * q;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - lower as f64)
+}
+
+/// A duration in nanoseconds, displayed in the largest unit it fits in.
+struct Time(f64);
 
 impl fmt::Display for Time {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}ns", self.0)
+        const UNITS: [(f64, &str); 3] = [(1e9, "s"), (1e6, "ms"), (1e3, "µs")];
+
+        for (scale, unit) in UNITS {
+            if self.0 >= scale {
+                return write!(f, "{:.2}{}", self.0 / scale, unit);
+            }
+        }
+
+        write!(f, "{:.2}ns", self.0)
     }
 }
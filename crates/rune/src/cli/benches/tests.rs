@@ -0,0 +1,34 @@
+use super::{Stats, Time};
+
+#[test]
+fn test_stats() {
+    let stats = Stats::new(&mut [5.0, 1.0, 3.0, 2.0, 4.0], true);
+
+    assert_eq!(stats.mean, 3.0);
+    assert_eq!(stats.median, 3.0);
+    assert_eq!(stats.stddev, 2.0f64.sqrt());
+    assert_eq!((stats.min, stats.max), (1.0, 5.0));
+    assert_eq!((stats.samples, stats.outliers), (5, 0));
+}
+
+#[test]
+fn test_stats_outliers() {
+    let mut samples = [10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 100.0, 1.0];
+
+    let stats = Stats::new(&mut samples, true);
+    assert_eq!(stats.mean, 12.5);
+    assert_eq!(stats.median, 12.5);
+    assert_eq!((stats.min, stats.max), (10.0, 15.0));
+    assert_eq!((stats.samples, stats.outliers), (6, 2));
+
+    let stats = Stats::new(&mut samples, false);
+    assert_eq!((stats.min, stats.max), (1.0, 100.0));
+    assert_eq!((stats.samples, stats.outliers), (8, 0));
+}
+
+#[test]
+fn test_time() {
+    assert_eq!(Time(12.0).to_string(), "12.00ns");
+    assert_eq!(Time(1500.0).to_string(), "1.50µs");
+    assert_eq!(Time(2.5e9).to_string(), "2.50s");
+}
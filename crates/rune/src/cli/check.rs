@@ -4,7 +4,7 @@ use std::path::Path;
 use anyhow::{Context, Result};
 use clap::Parser;
 
-use crate::cli::{visitor, Config, Entry, ExitCode, Io, SharedFlags, CommandBase, AssetKind};
+use crate::cli::{visitor, Config, Entry, ExitCode, Io, MessageFormat, SharedFlags, CommandBase, AssetKind};
 use crate::compile::FileSourceLoader;
 use crate::{Diagnostics, Options, Source, Sources};
 
@@ -41,7 +41,9 @@ pub(super) fn run(
     options: &Options,
     path: &Path,
 ) -> Result<ExitCode> {
-    writeln!(io.stdout, "Checking: {}", path.display())?;
+    if shared.message_format == MessageFormat::Human {
+        writeln!(io.stdout, "Checking: {}", path.display())?;
+    }
 
     let context = shared.context(entry, c, None)?;
 
@@ -69,7 +71,7 @@ pub(super) fn run(
         .with_source_loader(&mut source_loader)
         .build();
 
-    diagnostics.emit(&mut io.stdout.lock(), &sources)?;
+    shared.emit_diagnostics(&mut io.stdout.lock(), &diagnostics, &sources)?;
 
     if diagnostics.has_error() || flags.warnings_are_errors && diagnostics.has_warning() {
         Ok(ExitCode::Failure)
@@ -103,7 +103,7 @@ where
             .with_source_loader(&mut source_loader)
             .build();
 
-        diagnostics.emit(&mut io.stdout.lock(), &sources)?;
+        shared.emit_diagnostics(&mut io.stdout.lock(), &diagnostics, &sources)?;
 
         if diagnostics.has_error() || flags.warnings_are_errors && diagnostics.has_warning() {
             return Ok(ExitCode::Failure);
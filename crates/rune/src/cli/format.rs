@@ -63,7 +63,7 @@ pub(super) fn run<'m, I>(io: &mut Io<'_>, entry: &mut Entry<'_>, c: &Config, ent
             .with_options(options)
             .build();
 
-        diagnostics.emit(&mut io.stdout.lock(), &sources)?;
+        shared.emit_diagnostics(&mut io.stdout.lock(), &diagnostics, &sources)?;
 
         if diagnostics.has_error() || flags.warnings_are_errors && diagnostics.has_warning() {
             failed_builds += 1;
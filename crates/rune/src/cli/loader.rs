@@ -1,6 +1,9 @@
+#[cfg(test)]
+mod tests;
+
 use std::ffi::OsStr;
 use std::fs;
-use std::io;
+use std::io::{self, Write as _};
 use std::path::PathBuf;
 use std::{path::Path, sync::Arc};
 
@@ -8,9 +11,13 @@ use crate::no_std::collections::VecDeque;
 use crate::no_std::prelude::*;
 
 use anyhow::{anyhow, Context as _, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
+use crate::ast::Spanned;
 use crate::cli::{visitor, Io, SharedFlags};
-use crate::compile::{FileSourceLoader, ItemBuf};
+use crate::compile::{self, FileSourceLoader, Item, ItemBuf, Optimization, SourceLoader};
+use crate::runtime::unit::UnitFormatError;
 use crate::Diagnostics;
 use crate::{Context, Hash, Options, Source, Sources, Unit};
 
@@ -37,22 +44,9 @@ pub(super) fn load(
     let mut sources = Sources::new();
     sources.insert(source);
 
-    let use_cache = options.bytecode && should_cache_be_used(path, &bytecode_path)?;
-
     // TODO: how do we deal with tests discovery for bytecode loading
-    let maybe_unit = if use_cache {
-        let f = fs::File::open(&bytecode_path)?;
-
-        match bincode::deserialize_from::<_, Unit>(f) {
-            Ok(unit) => {
-                tracing::trace!("Using cache: {}", bytecode_path.display());
-                Some(Arc::new(unit))
-            }
-            Err(e) => {
-                tracing::error!("Failed to deserialize: {}: {}", bytecode_path.display(), e);
-                None
-            }
-        }
+    let maybe_unit = if options.bytecode {
+        load_cache(&bytecode_path, &sources, options)?
     } else {
         None
     };
@@ -69,7 +63,7 @@ pub(super) fn load(
             };
 
             let mut functions = visitor::FunctionVisitor::new(attribute);
-            let mut source_loader = FileSourceLoader::new();
+            let mut source_loader = RecordingSourceLoader::default();
 
             let result = crate::prepare(&mut sources)
                 .with_context(context)
@@ -79,13 +73,13 @@ pub(super) fn load(
                 .with_source_loader(&mut source_loader)
                 .build();
 
-            diagnostics.emit(io.stdout, &sources)?;
+            shared.emit_diagnostics(io.stdout, &diagnostics, &sources)?;
             let unit = result?;
 
             if options.bytecode {
                 tracing::trace!("serializing cache: {}", bytecode_path.display());
-                let f = fs::File::create(&bytecode_path)?;
-                bincode::serialize_into(f, &unit)?;
+                let header = CacheHeader::new(&sources, source_loader.included, options);
+                write_cache(&bytecode_path, &header, &unit)?;
             }
 
             (Arc::new(unit), functions.into_functions())
@@ -99,17 +93,241 @@ pub(super) fn load(
     })
 }
 
-/// Test if path `a` is newer than path `b`.
-fn should_cache_be_used(source: &Path, cached: &Path) -> io::Result<bool> {
-    let source = fs::metadata(source)?;
+/// Version of the cache format, which needs to be bumped whenever the layout
+/// of [CacheHeader] changes.
+///
+/// Changes to the layout of [Unit] are detected by [Unit::deserialize_from].
+const CACHE_VERSION: u32 = 3;
+
+/// Header written in front of cached bytecode, used to tell if the cache is
+/// still valid.
+///
+/// Content hashes are used instead of modification times, since the latter
+/// are unreliable with build systems which normalize timestamps.
+#[derive(PartialEq, Eq, Serialize, Deserialize)]
+struct CacheHeader {
+    version: u32,
+    /// Hash of the options the unit was compiled with.
+    options: [u8; 32],
+    /// Hashes of every source file the unit was compiled from.
+    sources: Vec<(PathBuf, [u8; 32])>,
+    /// Hashes of every file included by the sources, like through
+    /// `include_str!`.
+    included: Vec<(PathBuf, [u8; 32])>,
+}
+
+impl CacheHeader {
+    fn new(sources: &Sources, included: Vec<(PathBuf, [u8; 32])>, options: &Options) -> Self {
+        let sources = sources
+            .iter()
+            .filter_map(|source| {
+                let path = source.path()?;
+                Some((path.to_owned(), digest(source.as_str().as_bytes())))
+            })
+            .collect();
+
+        Self {
+            version: CACHE_VERSION,
+            options: options_digest(options),
+            sources,
+            included,
+        }
+    }
+
+    /// Test if the header matches the current options and sources on disk.
+    fn is_valid(&self, sources: &Sources, options: &Options) -> io::Result<bool> {
+        if self.version != CACHE_VERSION || self.options != options_digest(options) {
+            return Ok(false);
+        }
+
+        for (path, hash) in &self.sources {
+            let loaded = sources
+                .iter()
+                .find(|source| source.path() == Some(path.as_path()));
+
+            let current = match loaded {
+                Some(source) => digest(source.as_str().as_bytes()),
+                None => match fs::read(path) {
+                    Ok(bytes) => digest(&bytes),
+                    Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(false),
+                    Err(error) => return Err(error),
+                },
+            };
+
+            if current != *hash {
+                return Ok(false);
+            }
+        }
+
+        for (path, hash) in &self.included {
+            let current = match fs::read(path) {
+                Ok(bytes) => digest(&bytes),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(false),
+                Err(error) => return Err(error),
+            };
+
+            if current != *hash {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// A source loader which records the hashes of the files included by the
+/// sources being built, so that the cache can be invalidated when they change.
+#[derive(Default)]
+struct RecordingSourceLoader {
+    base: FileSourceLoader,
+    included: Vec<(PathBuf, [u8; 32])>,
+}
+
+impl SourceLoader for RecordingSourceLoader {
+    fn load(&mut self, root: &Path, item: &Item, span: &dyn Spanned) -> compile::Result<Source> {
+        self.base.load(root, item, span)
+    }
+
+    fn load_data(
+        &mut self,
+        root: &Path,
+        path: &str,
+        span: &dyn Spanned,
+    ) -> compile::Result<Vec<u8>> {
+        let data = self.base.load_data(root, path, span)?;
+        let path = compile::data_path(root, path, span)?;
+
+        if !self.included.iter().any(|(p, _)| *p == path) {
+            self.included.push((path, digest(&data)));
+        }
+
+        Ok(data)
+    }
+}
+
+/// Write a unit to the cache, behind a header used to tell if it's valid.
+fn write_cache(path: &Path, header: &CacheHeader, unit: &Unit) -> Result<()> {
+    let mut f = io::BufWriter::new(fs::File::create(path)?);
+    bincode::serialize_into(&mut f, header)?;
+    unit.serialize_into(&mut f)?;
+    f.flush()?;
+    Ok(())
+}
 
-    let cached = match fs::metadata(cached) {
-        Ok(cached) => cached,
-        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(false),
-        Err(error) => return Err(error),
+/// Load a cached unit, unless it's missing or stale.
+fn load_cache(path: &Path, sources: &Sources, options: &Options) -> Result<Option<Arc<Unit>>> {
+    let f = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error.into()),
     };
 
-    Ok(source.modified()? < cached.modified()?)
+    let mut f = io::BufReader::new(f);
+
+    match bincode::deserialize_from::<_, CacheHeader>(&mut f) {
+        Ok(header) if header.is_valid(sources, options)? => {}
+        Ok(..) => {
+            tracing::trace!("Stale cache: {}", path.display());
+            return Ok(None);
+        }
+        Err(e) => {
+            tracing::error!("Failed to deserialize header: {}: {}", path.display(), e);
+            return Ok(None);
+        }
+    }
+
+    let unit = match Unit::deserialize_from(f) {
+        Ok(unit) => unit,
+        Err(
+            e @ (UnitFormatError::UnsupportedVersion { .. }
+            | UnitFormatError::CompilerMismatch { .. }),
+        ) => {
+            tracing::trace!("Stale cache: {}: {}", path.display(), e);
+            return Ok(None);
+        }
+        Err(e) => {
+            tracing::error!("Failed to deserialize: {}: {}", path.display(), e);
+            return Ok(None);
+        }
+    };
+
+    if options.bytecode_verify {
+        if let Err(e) = unit.verify() {
+            tracing::error!("Failed to verify: {}: {}", path.display(), e);
+            return Ok(None);
+        }
+
+        tracing::trace!("Using verified cache: {}", path.display());
+    } else {
+        tracing::trace!("Using cache: {}", path.display());
+    }
+
+    Ok(Some(Arc::new(unit)))
+}
+
+/// Hash the options which affect how a unit is compiled.
+///
+/// The options are destructured so that adding an option is a compile error
+/// until it's accounted for here.
+fn options_digest(options: &Options) -> [u8; 32] {
+    let Options {
+        link_checks,
+        memoize_instance_fn,
+        debug_level,
+        macros,
+        bytecode: _,
+        bytecode_verify: _,
+        cfg_test,
+        v2,
+        function_body,
+        early_bound,
+        type_checks,
+        const_budget,
+        const_recursion_limit,
+        extern_consts,
+        passes,
+    } = options;
+
+    let mut hasher = Sha256::new();
+
+    hasher.update([
+        *link_checks as u8,
+        *memoize_instance_fn as u8,
+        *debug_level as u8,
+        *macros as u8,
+        *cfg_test as u8,
+        *v2 as u8,
+        *function_body as u8,
+        *early_bound as u8,
+        *type_checks as u8,
+    ]);
+
+    hasher.update((*const_budget as u64).to_le_bytes());
+    hasher.update((*const_recursion_limit as u64).to_le_bytes());
+
+    for optimization in Optimization::ALL {
+        hasher.update([passes.is_enabled(optimization) as u8]);
+    }
+
+    hasher.update((passes.threading_depth() as u64).to_le_bytes());
+
+    let mut extern_consts = extern_consts.iter().collect::<Vec<_>>();
+    extern_consts.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (name, value) in extern_consts {
+        hasher.update((name.len() as u64).to_le_bytes());
+        hasher.update(name.as_bytes());
+        // NB: a value which can't be serialized only results in a cache miss.
+        hasher.update(bincode::serialize(value).unwrap_or_default());
+    }
+
+    hasher.finalize().into()
+}
+
+fn digest(bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    hasher.finalize().into()
 }
 
 pub(super) fn recurse_paths(
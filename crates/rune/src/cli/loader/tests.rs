@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use super::{load_cache, write_cache, CacheHeader, RecordingSourceLoader};
+use crate::{Context, Options, Source, Sources};
+
+/// Get a directory to write the files of a test to.
+fn test_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rune-{name}-{}", std::process::id()))
+}
+
+#[test]
+fn test_cache_included_files() -> Result<()> {
+    let dir = test_dir("cache-included-files");
+    fs::create_dir_all(&dir)?;
+
+    let main = dir.join("main.rn");
+    let data = dir.join("data.txt");
+    let cache = dir.join("main.rnc");
+
+    fs::write(&main, r#"pub fn main() { include_str!("data.txt") }"#)?;
+    fs::write(&data, "hello")?;
+
+    let context = Context::with_default_modules()?;
+    let options = Options::default();
+
+    let mut sources = Sources::new();
+    sources.insert(Source::from_path(&main)?);
+
+    let mut source_loader = RecordingSourceLoader::default();
+
+    let unit = crate::prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .with_source_loader(&mut source_loader)
+        .build()?;
+
+    assert_eq!(source_loader.included.len(), 1);
+
+    let header = CacheHeader::new(&sources, source_loader.included, &options);
+    write_cache(&cache, &header, &unit)?;
+
+    assert!(load_cache(&cache, &sources, &options)?.is_some());
+
+    // Editing the included file means that the unit has to be recompiled.
+    fs::write(&data, "world")?;
+    assert!(load_cache(&cache, &sources, &options)?.is_none());
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
@@ -0,0 +1,409 @@
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+
+use crate::no_std::error;
+use crate::no_std::prelude::*;
+
+use anyhow::{anyhow, Context as _, Result};
+use clap::Parser;
+
+use crate::ast::{self, Spanned};
+use crate::cli::{CommandBase, EntryPoint, ExitCode, Io};
+use crate::diagnostics::EmitError;
+use crate::parse::Lexer;
+use crate::runtime::{Formatter, Object, RuntimeContext, Shared, Unit, Value, Vm, VmError};
+use crate::termcolor::WriteColor;
+use crate::{Context, Diagnostics, Options, Source, SourceId, Sources};
+
+/// The name of the function every snippet is compiled into.
+const ENTRY: &str = "__repl";
+/// The name of the argument used to pass variables in and out of a snippet.
+const ENV: &str = "__repl_env";
+/// The name of the variable holding the value of a snippet.
+const VALUE: &str = "__repl_value";
+
+#[derive(Parser, Debug)]
+pub(super) struct Flags {
+    /// Don't print a prompt before reading input.
+    ///
+    /// The prompt is only printed by default if input is read from a
+    /// terminal.
+    #[arg(long)]
+    no_prompt: bool,
+}
+
+impl CommandBase for Flags {
+    #[inline]
+    fn is_debug(&self) -> bool {
+        true
+    }
+}
+
+/// Error raised when evaluating input through [Repl::eval].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ReplError {
+    /// The input failed to compile.
+    Compile {
+        /// Diagnostics produced while compiling.
+        diagnostics: Diagnostics,
+        /// Sources referenced by the diagnostics.
+        sources: Sources,
+    },
+    /// Evaluating the input errored.
+    Vm {
+        /// The error raised by the virtual machine.
+        error: VmError,
+        /// Sources the input was compiled from.
+        sources: Sources,
+    },
+}
+
+impl ReplError {
+    /// Emit the error to the given output, referencing the input where
+    /// appropriate.
+    pub fn emit<O>(&self, out: &mut O) -> Result<(), EmitError>
+    where
+        O: WriteColor,
+    {
+        match self {
+            ReplError::Compile {
+                diagnostics,
+                sources,
+            } => diagnostics.emit(out, sources),
+            ReplError::Vm { error, sources } => error.emit(out, sources),
+        }
+    }
+}
+
+impl fmt::Display for ReplError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplError::Compile { .. } => write!(f, "Failed to compile input"),
+            ReplError::Vm { error, .. } => write!(f, "Failed to evaluate input: {error}"),
+        }
+    }
+}
+
+impl error::Error for ReplError {}
+
+/// An interactive session, which evaluates snippets of Rune one at a time.
+///
+/// Items, like functions and structs, are kept around and are visible to
+/// every later snippet. Variables bound with a `let` statement at the top
+/// level of a snippet are carried forward and can be used by later snippets.
+///
+/// Every snippet is compiled into a function together with the items defined
+/// so far, variables are passed in and out of it through an object.
+///
+/// # Examples
+///
+/// ```
+/// use rune::cli::Repl;
+/// use rune::Context;
+///
+/// # futures_executor::block_on(async {
+/// let mut repl = Repl::new(Context::with_default_modules()?);
+///
+/// repl.eval("fn square(n) { n * n }").await?;
+/// repl.eval("let values = [1, 2, 3];").await?;
+/// repl.eval("values.push(square(4));").await?;
+///
+/// let value = repl.eval("values").await?;
+/// assert_eq!(repl.format(&value)?, "[1, 2, 3, 16]");
+/// # Ok::<_, rune::Error>(())
+/// # })?;
+/// # Ok::<_, rune::Error>(())
+/// ```
+pub struct Repl {
+    context: Context,
+    runtime: Arc<RuntimeContext>,
+    options: Options,
+    /// Source of every item defined so far.
+    items: String,
+    /// Names of the variables defined so far.
+    variables: Vec<Box<str>>,
+    /// Values of the variables defined so far.
+    env: Shared<Object>,
+    /// The unit most recently compiled, which is used when formatting values.
+    unit: Arc<Unit>,
+}
+
+impl Repl {
+    /// Construct a new session which compiles snippets with the given
+    /// context.
+    pub fn new(context: Context) -> Self {
+        let runtime = Arc::new(context.runtime());
+
+        Self {
+            context,
+            runtime,
+            options: Options::default(),
+            items: String::new(),
+            variables: Vec::new(),
+            env: Shared::new(Object::new()),
+            unit: Arc::new(Unit::default()),
+        }
+    }
+
+    /// Set the options to compile snippets with.
+    pub fn with_options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Iterate over the names of the variables defined so far.
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        self.variables.iter().map(|name| name.as_ref())
+    }
+
+    /// Evaluate the given input, returning the value of its trailing
+    /// expression.
+    ///
+    /// Items are only defined if the input compiles, and variables are only
+    /// defined if it's evaluated without errors.
+    pub async fn eval(&mut self, input: &str) -> Result<Value, ReplError> {
+        let mut sources = Sources::new();
+        let source_id = sources.insert(Source::new("<repl>", input));
+
+        let block = match crate::parse::parse_all::<ast::EmptyBlock>(input, source_id, false) {
+            Ok(block) => block,
+            Err(error) => {
+                let mut diagnostics = Diagnostics::new();
+                diagnostics.error(source_id, error);
+
+                return Err(ReplError::Compile {
+                    diagnostics,
+                    sources,
+                });
+            }
+        };
+
+        let mut items = self.items.clone();
+        let mut body = Vec::new();
+        let mut variables = self.variables.clone();
+
+        for stmt in &block.statements {
+            let text = &input[stmt.span().range()];
+
+            match stmt {
+                ast::Stmt::Item(..) => {
+                    items.push_str(text);
+                    items.push('\n');
+                }
+                ast::Stmt::Local(local) => {
+                    for name in bindings(&local.pat, input) {
+                        if !variables.iter().any(|v| v.as_ref() == name) {
+                            variables.push(name.into());
+                        }
+                    }
+
+                    body.push((stmt, text));
+                }
+                _ => {
+                    body.push((stmt, text));
+                }
+            }
+        }
+
+        let mut source = items.clone();
+        let _ = writeln!(source, "pub async fn {ENTRY}({ENV}) {{");
+
+        for name in &self.variables {
+            let _ = writeln!(source, "let {name} = {ENV}[{name:?}];");
+        }
+
+        let value = match body.last() {
+            Some((ast::Stmt::Expr(..), text)) => Some(*text),
+            _ => None,
+        };
+
+        for (_, text) in &body[..body.len() - usize::from(value.is_some())] {
+            let _ = writeln!(source, "{text}");
+        }
+
+        let _ = writeln!(source, "let {VALUE} = {};", value.unwrap_or("()"));
+
+        for name in &variables {
+            let _ = writeln!(source, "{ENV}[{name:?}] = {name};");
+        }
+
+        let _ = writeln!(source, "{VALUE}\n}}");
+
+        let mut sources = Sources::new();
+        sources.insert(Source::new("<repl>", source));
+
+        let mut diagnostics = Diagnostics::without_warnings();
+
+        let result = crate::prepare(&mut sources)
+            .with_context(&self.context)
+            .with_diagnostics(&mut diagnostics)
+            .with_options(&self.options)
+            .build();
+
+        let Ok(unit) = result else {
+            return Err(ReplError::Compile {
+                diagnostics,
+                sources,
+            });
+        };
+
+        let unit = Arc::new(unit);
+        self.items = items;
+        self.unit = unit.clone();
+
+        if body.is_empty() {
+            return Ok(Value::EmptyTuple);
+        }
+
+        let mut vm = Vm::new(self.runtime.clone(), unit);
+
+        match vm
+            .async_call([ENTRY], (Value::Object(self.env.clone()),))
+            .await
+        {
+            Ok(value) => {
+                self.variables = variables;
+                Ok(value)
+            }
+            Err(error) => Err(ReplError::Vm { error, sources }),
+        }
+    }
+
+    /// Format a value like with `{:?}`, which uses its [`STRING_DEBUG`]
+    /// protocol.
+    ///
+    /// [`STRING_DEBUG`]: crate::runtime::Protocol::STRING_DEBUG
+    pub fn format(&self, value: &Value) -> Result<String, VmError> {
+        let mut vm = Vm::new(self.runtime.clone(), self.unit.clone());
+        let mut f = Formatter::new();
+        let _ = vm.with(|| value.string_debug(&mut f)).into_result()?;
+        Ok(f.into_string())
+    }
+}
+
+/// Collect the names of the variables bound by a pattern.
+fn bindings<'a>(pat: &ast::Pat, input: &'a str) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    let mut queue = vec![pat];
+
+    while let Some(pat) = queue.pop() {
+        match pat {
+            ast::Pat::Path(pat) => {
+                if let Some(ident) = pat.path.try_as_ident() {
+                    names.push(&input[ident.span().range()]);
+                }
+            }
+            ast::Pat::Vec(pat) => {
+                queue.extend(pat.items.iter().map(|(pat, _)| pat));
+            }
+            ast::Pat::Tuple(pat) => {
+                queue.extend(pat.items.iter().map(|(pat, _)| pat));
+            }
+            ast::Pat::Object(pat) => {
+                queue.extend(pat.items.iter().map(|(pat, _)| pat));
+            }
+            ast::Pat::Binding(pat) => {
+                queue.push(&pat.pat);
+            }
+            ast::Pat::At(pat) => {
+                names.push(&input[pat.ident.span().range()]);
+                queue.push(&pat.pat);
+            }
+            _ => {}
+        }
+    }
+
+    names
+}
+
+/// Test if the input has unclosed delimiters, in which case more input should
+/// be read before it's evaluated.
+fn is_incomplete(input: &str) -> bool {
+    let mut lexer = Lexer::with_trivia(input, SourceId::empty(), false);
+    let mut depth = 0usize;
+
+    while let Ok(Some(token)) = lexer.next() {
+        match token.kind {
+            ast::Kind::Open(..) => depth += 1,
+            ast::Kind::Close(..) => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    depth > 0
+}
+
+pub(super) async fn run<'p, I>(
+    io: &mut Io<'_>,
+    flags: &Flags,
+    context: Context,
+    options: Options,
+    entries: I,
+) -> Result<ExitCode>
+where
+    I: IntoIterator<Item = EntryPoint<'p>>,
+{
+    let mut repl = Repl::new(context).with_options(options);
+
+    // Items in the given paths are made available to the session.
+    for e in entries {
+        let path = e.path();
+        let input = fs::read_to_string(path)
+            .with_context(|| anyhow!("cannot read file: {}", path.display()))?;
+
+        if let Err(error) = repl.eval(&input).await {
+            error.emit(io.stderr)?;
+            return Ok(ExitCode::Failure);
+        }
+    }
+
+    let prompt = !flags.no_prompt && atty::is(atty::Stream::Stdin);
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut input = String::new();
+
+    loop {
+        if prompt {
+            write!(io.stdout, "{}", if input.is_empty() { "> " } else { ". " })?;
+            io.stdout.flush()?;
+        }
+
+        let Some(line) = lines.next() else {
+            break;
+        };
+
+        let line = line?;
+
+        if input.is_empty() && matches!(line.trim(), ":q" | ":quit" | ":exit") {
+            break;
+        }
+
+        // An empty line forces evaluation of incomplete input, which reports
+        // what's missing.
+        let force = line.trim().is_empty();
+        input.push_str(&line);
+        input.push('\n');
+
+        if !force && is_incomplete(&input) {
+            continue;
+        }
+
+        match repl.eval(&input).await {
+            Ok(Value::EmptyTuple) => {}
+            Ok(value) => match repl.format(&value) {
+                Ok(string) => writeln!(io.stdout, "{string}")?,
+                Err(error) => writeln!(io.stderr, "error: {error}")?,
+            },
+            Err(error) => {
+                error.emit(io.stderr)?;
+            }
+        }
+
+        input.clear();
+    }
+
+    Ok(ExitCode::Success)
+}
@@ -46,6 +46,9 @@ pub(super) struct Flags {
     /// Dump native types.
     #[arg(long)]
     dump_native_types: bool,
+    /// Dump native aliases.
+    #[arg(long)]
+    dump_native_aliases: bool,
     /// Include source code references where appropriate (only available if -O debug-info=true).
     #[arg(long)]
     with_source: bool,
@@ -74,6 +77,7 @@ impl CommandBase for Flags {
             self.dump_types = true;
             self.dump_native_functions = true;
             self.dump_native_types = true;
+            self.dump_native_aliases = true;
         }
 
         if self.trace_limit.is_some() {
@@ -144,8 +148,26 @@ pub(super) async fn run(
         }
     }
 
+    if args.dump_native_aliases {
+        writeln!(io.stdout, "# aliases")?;
+
+        for (i, alias) in context.aliases().enumerate() {
+            writeln!(io.stdout, "{:04} = {}", i, alias)?;
+        }
+    }
+
     if args.dump_unit() {
-        writeln!(io.stdout, "Unit size: {} bytes", unit.instructions().bytes())?;
+        writeln!(io.stdout, "Unit size: {} bytes", unit.storage().bytes())?;
+
+        match unit.debug_info() {
+            Some(debug) => {
+                let size = bincode::serialized_size(debug)?;
+                writeln!(io.stdout, "Debug info size: {size} bytes")?;
+            }
+            None => {
+                writeln!(io.stdout, "Debug info size: none")?;
+            }
+        }
 
         if args.emit_instructions() {
             let mut o = io.stdout.lock();
@@ -192,6 +214,14 @@ pub(super) async fn run(
             for (hash, keys) in keys {
                 writeln!(io.stdout, "{} = {:?}", hash, keys)?;
             }
+
+            let stats = unit.object_key_stats();
+
+            writeln!(
+                io.stdout,
+                "# {} keys ({} bytes), {} interned ({} bytes)",
+                stats.keys, stats.bytes, stats.unique, stats.unique_bytes
+            )?;
         }
     }
 
@@ -227,7 +257,8 @@ pub(super) async fn run(
             let duration = Instant::now().duration_since(last);
 
             if c.verbose {
-                writeln!(io.stderr, "== {:?} ({:?})", result, duration)?;
+                let vm = execution.vm();
+                vm.with(|| writeln!(io.stderr, "== {:?} ({:?})", result, duration))?;
             }
 
             None
@@ -270,7 +301,7 @@ pub(super) async fn run(
             }
 
             for (n, value) in stack.iter().enumerate() {
-                writeln!(io.stdout, "{}+{} = {:?}", frame.stack_bottom, n, value)?;
+                vm.with(|| writeln!(io.stdout, "{}+{} = {:?}", frame.stack_bottom, n, value))?;
             }
         }
 
@@ -289,13 +320,15 @@ pub(super) async fn run(
         }
 
         for (n, value) in values.iter().enumerate() {
-            writeln!(
-                io.stdout,
-                "    {}+{} = {:?}",
-                stack.stack_bottom(),
-                n,
-                value
-            )?;
+            vm.with(|| {
+                writeln!(
+                    io.stdout,
+                    "    {}+{} = {:?}",
+                    stack.stack_bottom(),
+                    n,
+                    value
+                )
+            })?;
         }
     }
 
@@ -393,7 +426,7 @@ where
             }
 
             for (n, value) in values.iter().enumerate() {
-                writeln!(o, "    {}+{} = {:?}", stack.stack_bottom(), n, value)?;
+                vm.with(|| writeln!(o, "    {}+{} = {:?}", stack.stack_bottom(), n, value))?;
             }
         }
 
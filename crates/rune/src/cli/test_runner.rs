@@ -0,0 +1,619 @@
+use std::fmt;
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::no_std::prelude::*;
+
+use anyhow::{anyhow, Result};
+
+use crate::compile::ItemBuf;
+use crate::modules::capture_io::CaptureIo;
+use crate::runtime::{RuntimeContext, Unit, Value, Vm, VmError, VmResult};
+use crate::{Context, Hash, Sources};
+
+/// A test which can be run by a [TestRunner].
+#[derive(Debug, Clone)]
+pub struct Test {
+    hash: Hash,
+    item: ItemBuf,
+    unit: Arc<Unit>,
+    sources: Arc<Sources>,
+    should_panic: bool,
+    ignore: bool,
+}
+
+impl Test {
+    /// Construct a test which calls the function with the given `hash` in
+    /// `unit`.
+    ///
+    /// The `sources` are used to report where a test panicked.
+    pub fn new(hash: Hash, item: ItemBuf, unit: Arc<Unit>, sources: Arc<Sources>) -> Self {
+        Self {
+            hash,
+            item,
+            unit,
+            sources,
+            should_panic: false,
+            ignore: false,
+        }
+    }
+
+    /// Mark the test as one which is expected to panic.
+    pub fn should_panic(mut self, should_panic: bool) -> Self {
+        self.should_panic = should_panic;
+        self
+    }
+
+    /// Mark the test as one which is ignored, and only runs if the runner is
+    /// configured with [TestRunner::with_include_ignored].
+    pub fn ignore(mut self, ignore: bool) -> Self {
+        self.ignore = ignore;
+        self
+    }
+
+    /// The item of the test.
+    pub fn item(&self) -> &ItemBuf {
+        &self.item
+    }
+
+    async fn execute(
+        &self,
+        runtime: &Arc<RuntimeContext>,
+        capture: &CaptureIo,
+    ) -> Result<TestResult> {
+        let start = Instant::now();
+        let mut vm = Vm::new(runtime.clone(), self.unit.clone());
+        vm.set_test_mode(true);
+
+        let result = match vm.execute(self.hash, ()) {
+            Ok(mut execution) => execution.async_complete().await,
+            Err(err) => VmResult::Err(err),
+        };
+
+        let mut output = Vec::new();
+        capture.drain_into(&mut output)?;
+
+        let mut outcome = match result {
+            VmResult::Ok(v) => match v {
+                Value::Result(result) => match result.take()? {
+                    Ok(..) => TestOutcome::Passed,
+                    Err(error) => TestOutcome::ReturnedErr(format!("{:?}", error)),
+                },
+                Value::Option(option) => match *option.borrow_ref()? {
+                    Some(..) => TestOutcome::Passed,
+                    None => TestOutcome::ReturnedNone,
+                },
+                _ => TestOutcome::Passed,
+            },
+            VmResult::Err(e) => TestOutcome::Panicked(e),
+        };
+
+        if self.should_panic {
+            if matches!(outcome, TestOutcome::Panicked(..)) {
+                outcome = TestOutcome::Passed;
+            } else {
+                outcome = TestOutcome::ExpectedPanic;
+            }
+        }
+
+        Ok(TestResult {
+            item: self.item.clone(),
+            sources: self.sources.clone(),
+            outcome,
+            output,
+            duration: start.elapsed(),
+        })
+    }
+}
+
+/// Runs a collection of tests in parallel.
+///
+/// Every worker thread runs tests in a fresh virtual machine using its own
+/// runtime context, which is constructed with a dedicated [CaptureIo] so that
+/// the output of each test is captured separately.
+///
+/// Tests run in [test mode][Vm::set_test_mode], so they can shadow functions in
+/// the context through `std::test::mock`. Since every test runs in its own
+/// virtual machine, mocks never leak between tests.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rune::cli::{Test, TestRunner};
+/// use rune::modules::capture_io;
+/// use rune::{Context, Hash};
+/// use std::sync::Arc;
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         #[test]
+///         fn test_add() {
+///             assert_eq!(1 + 1, 2);
+///         }
+///     }
+/// };
+///
+/// let unit = Arc::new(rune::prepare(&mut sources).build()?);
+/// let sources = Arc::new(sources);
+///
+/// let mut runner = TestRunner::new().with_filter("add");
+/// runner.push(Test::new(Hash::type_hash(["test_add"]), "test_add".parse()?, unit, sources));
+///
+/// let report = runner.run(|capture| {
+///     let mut context = Context::with_config(false)?;
+///     context.install(capture_io::module(capture)?)?;
+///     Ok(context)
+/// })?;
+///
+/// assert!(report.is_success());
+/// report.write_junit(std::io::stdout())?;
+/// # Ok::<_, rune::Error>(())
+/// ```
+pub struct TestRunner {
+    filters: Vec<String>,
+    jobs: usize,
+    fail_fast: bool,
+    include_ignored: bool,
+    tests: Vec<Test>,
+}
+
+impl TestRunner {
+    /// Construct a new test runner which uses one worker thread per available
+    /// core.
+    pub fn new() -> Self {
+        Self {
+            filters: Vec::new(),
+            jobs: thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+            fail_fast: false,
+            include_ignored: false,
+            tests: Vec::new(),
+        }
+    }
+
+    /// Only run tests whose item matches the given filter.
+    ///
+    /// A filter containing `*` or `?` is matched as a glob against the whole
+    /// item of the test, like `tests::parse_*`. Any other filter matches if it
+    /// is a substring of the item. If multiple filters are specified, a test
+    /// runs if it matches any of them.
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filters.push(filter.into());
+        self
+    }
+
+    /// Set the number of worker threads to run tests with.
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    /// Stop running tests after the first failure.
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    /// Also run tests which are marked as ignored.
+    pub fn with_include_ignored(mut self, include_ignored: bool) -> Self {
+        self.include_ignored = include_ignored;
+        self
+    }
+
+    /// Add a test to run.
+    pub fn push(&mut self, test: Test) {
+        self.tests.push(test);
+    }
+
+    /// Run all tests matching the configured filters.
+    ///
+    /// The `context` callback is called once for every worker thread to
+    /// construct the context tests are run in, it must install the capture
+    /// module for the given [CaptureIo] for output to be captured.
+    pub fn run<F>(&self, mut context: F) -> Result<TestReport>
+    where
+        F: FnMut(&CaptureIo) -> Result<Context>,
+    {
+        let start = Instant::now();
+
+        let tests = self
+            .tests
+            .iter()
+            .filter(|test| is_match(&self.filters, &test.item.to_string()))
+            .collect::<Vec<_>>();
+
+        let filtered = self.tests.len() - tests.len();
+
+        let (tests, ignored) = tests
+            .into_iter()
+            .partition::<Vec<_>, _>(|test| self.include_ignored || !test.ignore);
+
+        let mut workers = Vec::new();
+
+        for _ in 0..self.jobs.min(tests.len()) {
+            let capture = CaptureIo::new();
+            let runtime = Arc::new(context(&capture)?.runtime());
+            workers.push((capture, runtime));
+        }
+
+        let fail_fast = self.fail_fast;
+        let next = AtomicUsize::new(0);
+        let stop = AtomicBool::new(false);
+        let (tx, rx) = mpsc::channel();
+
+        thread::scope(|s| {
+            let handles = workers
+                .into_iter()
+                .map(|(capture, runtime)| {
+                    let tx = tx.clone();
+                    let (tests, next, stop) = (&tests, &next, &stop);
+
+                    s.spawn(move || -> Result<()> {
+                        let rt = tokio::runtime::Builder::new_current_thread()
+                            .enable_all()
+                            .build()?;
+
+                        while !stop.load(Ordering::Relaxed) {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+
+                            let Some(test) = tests.get(index) else {
+                                break;
+                            };
+
+                            let result = rt.block_on(test.execute(&runtime, &capture))?;
+
+                            if fail_fast && !result.outcome.is_passed() {
+                                stop.store(true, Ordering::Relaxed);
+                            }
+
+                            if tx.send((index, result)).is_err() {
+                                break;
+                            }
+                        }
+
+                        Ok(())
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            for handle in handles {
+                handle
+                    .join()
+                    .map_err(|_| anyhow!("Test worker panicked"))??;
+            }
+
+            Ok::<_, anyhow::Error>(())
+        })?;
+
+        drop(tx);
+
+        let mut results = rx.into_iter().collect::<Vec<_>>();
+        results.sort_by_key(|(index, _)| *index);
+        let results = results
+            .into_iter()
+            .map(|(_, result)| result)
+            .collect::<Vec<_>>();
+
+        Ok(TestReport {
+            skipped: tests.len() - results.len(),
+            ignored: ignored.len(),
+            filtered,
+            results,
+            duration: start.elapsed(),
+        })
+    }
+}
+
+impl Default for TestRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The outcome of a single test.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TestOutcome {
+    /// The test passed.
+    Passed,
+    /// The test panicked.
+    Panicked(VmError),
+    /// The test was expected to panic, but ran without issue.
+    ExpectedPanic,
+    /// The test returned `None`.
+    ReturnedNone,
+    /// The test returned an error, which is included in its debug
+    /// representation.
+    ReturnedErr(String),
+}
+
+impl TestOutcome {
+    /// Test if the test passed.
+    pub fn is_passed(&self) -> bool {
+        matches!(self, TestOutcome::Passed)
+    }
+}
+
+impl fmt::Display for TestOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestOutcome::Passed => write!(f, "ok"),
+            TestOutcome::Panicked(error) => write!(f, "panicked: {error}"),
+            TestOutcome::ExpectedPanic => write!(
+                f,
+                "expected panic because of `should_panic`, but ran without issue"
+            ),
+            TestOutcome::ReturnedNone => write!(f, "returned none"),
+            TestOutcome::ReturnedErr(error) => write!(f, "err: {error}"),
+        }
+    }
+}
+
+/// The result of running a single test.
+#[derive(Debug)]
+pub struct TestResult {
+    item: ItemBuf,
+    sources: Arc<Sources>,
+    outcome: TestOutcome,
+    output: Vec<u8>,
+    duration: Duration,
+}
+
+impl TestResult {
+    /// The item of the test.
+    pub fn item(&self) -> &ItemBuf {
+        &self.item
+    }
+
+    /// The sources the test was compiled from.
+    pub fn sources(&self) -> &Sources {
+        &self.sources
+    }
+
+    /// The outcome of the test.
+    pub fn outcome(&self) -> &TestOutcome {
+        &self.outcome
+    }
+
+    /// The output captured while running the test.
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+
+    /// How long the test took to run.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+/// The report produced by [TestRunner::run].
+#[derive(Debug)]
+pub struct TestReport {
+    results: Vec<TestResult>,
+    skipped: usize,
+    ignored: usize,
+    filtered: usize,
+    duration: Duration,
+}
+
+impl TestReport {
+    /// The results of the tests which were run, in the order they were added
+    /// to the runner.
+    pub fn results(&self) -> &[TestResult] {
+        &self.results
+    }
+
+    /// The number of tests which passed.
+    pub fn passed(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| r.outcome.is_passed())
+            .count()
+    }
+
+    /// The number of tests which failed.
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.passed()
+    }
+
+    /// The number of tests which were not run because of a failure, when
+    /// running with [TestRunner::with_fail_fast].
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// The number of tests which were not run because they are ignored, see
+    /// [Test::ignore].
+    pub fn ignored(&self) -> usize {
+        self.ignored
+    }
+
+    /// The number of tests which didn't match any filter.
+    pub fn filtered(&self) -> usize {
+        self.filtered
+    }
+
+    /// How long it took to run all tests.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Test if all tests passed.
+    pub fn is_success(&self) -> bool {
+        self.failed() == 0
+    }
+
+    /// Write the report as JUnit XML, which is understood by most CI
+    /// systems.
+    pub fn write_junit<O>(&self, mut out: O) -> io::Result<()>
+    where
+        O: io::Write,
+    {
+        let skipped = self.skipped + self.ignored;
+        let tests = self.results.len() + skipped;
+        let failures = self.failed();
+        let time = self.duration.as_secs_f64();
+
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            out,
+            r#"<testsuites tests="{tests}" failures="{failures}" time="{time:.3}">"#
+        )?;
+        writeln!(
+            out,
+            r#"  <testsuite name="rune" tests="{tests}" failures="{failures}" skipped="{skipped}" time="{time:.3}">"#
+        )?;
+
+        for result in &self.results {
+            let item = result.item.to_string();
+
+            let class = match item.rfind("::") {
+                Some(n) => &item[..n],
+                None => "",
+            };
+
+            write!(
+                out,
+                r#"    <testcase name="{}" classname="{}" time="{:.3}""#,
+                Xml(&item),
+                Xml(class),
+                result.duration.as_secs_f64()
+            )?;
+
+            if result.outcome.is_passed() && result.output.is_empty() {
+                writeln!(out, " />")?;
+                continue;
+            }
+
+            writeln!(out, ">")?;
+
+            if !result.outcome.is_passed() {
+                let message = result.outcome.to_string();
+                writeln!(
+                    out,
+                    r#"      <failure message="{}">{}</failure>"#,
+                    Xml(&message),
+                    Xml(&message)
+                )?;
+            }
+
+            if !result.output.is_empty() {
+                let output = String::from_utf8_lossy(&result.output);
+                writeln!(out, "      <system-out>{}</system-out>", Xml(&output))?;
+            }
+
+            writeln!(out, "    </testcase>")?;
+        }
+
+        writeln!(out, "  </testsuite>")?;
+        writeln!(out, "</testsuites>")?;
+        Ok(())
+    }
+
+    /// Write the report as JSON.
+    pub fn write_json<O>(&self, out: O) -> Result<()>
+    where
+        O: io::Write,
+    {
+        let tests = self
+            .results
+            .iter()
+            .map(|result| {
+                serde_json::json!({
+                    "name": result.item.to_string(),
+                    "passed": result.outcome.is_passed(),
+                    "message": (!result.outcome.is_passed()).then(|| result.outcome.to_string()),
+                    "output": String::from_utf8_lossy(&result.output),
+                    "duration": result.duration.as_secs_f64(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let report = serde_json::json!({
+            "passed": self.passed(),
+            "failed": self.failed(),
+            "skipped": self.skipped,
+            "ignored": self.ignored,
+            "filtered": self.filtered,
+            "duration": self.duration.as_secs_f64(),
+            "tests": tests,
+        });
+
+        serde_json::to_writer_pretty(out, &report)?;
+        Ok(())
+    }
+}
+
+/// Test if `item` matches any of the given filters, see
+/// [TestRunner::with_filter].
+pub(super) fn is_match(filters: &[String], item: &str) -> bool {
+    if filters.is_empty() {
+        return true;
+    }
+
+    filters.iter().any(|filter| {
+        if filter.contains(['*', '?']) {
+            glob_match(filter.as_bytes(), item.as_bytes())
+        } else {
+            item.contains(filter.as_str())
+        }
+    })
+}
+
+/// Match a glob where `*` matches any sequence of characters and `?` matches
+/// any single character.
+fn glob_match(pattern: &[u8], input: &[u8]) -> bool {
+    let (mut p, mut i) = (0, 0);
+    let mut backtrack = None;
+
+    while i < input.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, i));
+                p += 1;
+                continue;
+            }
+            Some(&c) if c == b'?' || c == input[i] => {
+                p += 1;
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        let Some((bp, bi)) = backtrack else {
+            return false;
+        };
+
+        backtrack = Some((bp, bi + 1));
+        p = bp + 1;
+        i = bi + 1;
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// Helper to escape XML.
+struct Xml<'a>(&'a str);
+
+impl fmt::Display for Xml<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&apos;")?,
+                c if c.is_control() && !matches!(c, '\n' | '\t' | '\r') => {}
+                c => write!(f, "{c}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
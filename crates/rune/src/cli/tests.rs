@@ -1,4 +1,6 @@
+use std::fs;
 use std::io::Write;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -8,13 +10,12 @@ use anyhow::{bail, Result, Context};
 use clap::Parser;
 
 use crate::cli::{ExitCode, Io, CommandBase, AssetKind, Config, SharedFlags, EntryPoint, Entry, Options};
+use crate::cli::test_runner::{Test, TestOutcome, TestResult, TestRunner};
 use crate::cli::visitor;
 use crate::cli::naming::Naming;
 use crate::compile::{ItemBuf, FileSourceLoader};
-use crate::modules::capture_io::CaptureIo;
-use crate::runtime::{Value, Vm, VmError, VmResult, UnitFn};
-use crate::doc::TestParams;
-use crate::{Hash, Sources, Unit, Diagnostics, Source};
+use crate::runtime::UnitFn;
+use crate::{Sources, Diagnostics, Source};
 use crate::termcolor::{WriteColor, ColorSpec, Color};
 
 #[derive(Parser, Debug, Clone)]
@@ -31,6 +32,24 @@ pub(super) struct Flags {
     /// Break on the first test failed.
     #[arg(long)]
     fail_fast: bool,
+    /// Also run tests which are marked with `#[ignore]`.
+    #[arg(long)]
+    include_ignored: bool,
+    /// Only run tests whose name matches the given filter. A filter containing
+    /// `*` or `?` is matched as a glob against the whole name of the test, like
+    /// `tests::parse_*`, otherwise it matches any test it is a substring of.
+    #[arg(long = "filter")]
+    filters: Vec<String>,
+    /// The number of threads to run tests with. Defaults to the number of
+    /// available cores.
+    #[arg(long, short = 'j')]
+    jobs: Option<usize>,
+    /// Write a JUnit XML report of the test run to the given path.
+    #[arg(long)]
+    junit: Option<PathBuf>,
+    /// Write a JSON report of the test run to the given path.
+    #[arg(long)]
+    json: Option<PathBuf>,
 }
 
 impl CommandBase for Flags {
@@ -73,7 +92,6 @@ where
     let start = Instant::now();
 
     let mut build_errors = 0usize;
-    let mut executed = 0usize;
 
     let capture = crate::modules::capture_io::CaptureIo::new();
     let context = shared.context(entry, c, Some(&capture))?;
@@ -125,7 +143,7 @@ where
             .with_source_loader(&mut source_loader)
             .build();
 
-        diagnostics.emit(&mut io.stdout.lock(), &sources)?;
+        shared.emit_diagnostics(&mut io.stdout.lock(), &diagnostics, &sources)?;
 
         if diagnostics.has_error() || flags.warnings_are_errors && diagnostics.has_warning() {
             build_errors = build_errors.wrapping_add(1);
@@ -137,8 +155,12 @@ where
 
         doc_visitors.push(doc_visitor);
 
-        for (hash, item) in functions.into_functions() {
-            cases.push(TestCase::new(hash, item, unit.clone(), sources.clone(), TestParams::default()));
+        for (hash, item, params) in functions.into_tests() {
+            cases.push(
+                Test::new(hash, item, unit.clone(), sources.clone())
+                    .ignore(params.ignore)
+                    .should_panic(params.should_panic),
+            );
         }
     }
 
@@ -173,7 +195,7 @@ where
             .with_source_loader(&mut source_loader)
             .build();
 
-        diagnostics.emit(&mut io.stdout.lock(), &sources)?;
+        shared.emit_diagnostics(&mut io.stdout.lock(), &diagnostics, &sources)?;
 
         if diagnostics.has_error() || flags.warnings_are_errors && diagnostics.has_warning() {
             build_errors = build_errors.wrapping_add(1);
@@ -188,26 +210,39 @@ where
                 bail!("Compiling source did not result in a function at offset 0");
             };
 
-            cases.push(TestCase::new(hash, test.item.clone(), unit.clone(), sources.clone(), test.params));
+            cases.push(
+                Test::new(hash, test.item.clone(), unit, sources)
+                    .should_panic(test.params.should_panic),
+            );
         }
     }
 
-    let runtime = Arc::new(context.runtime());
-    let mut failed = Vec::new();
+    let mut runner = TestRunner::new()
+        .with_fail_fast(flags.fail_fast)
+        .with_include_ignored(flags.include_ignored);
 
-    let total = cases.len();
+    if let Some(jobs) = flags.jobs {
+        runner = runner.with_jobs(jobs);
+    }
 
-    for mut case in cases {
-        executed = executed.wrapping_add(1);
+    for filter in &flags.filters {
+        runner = runner.with_filter(filter.as_str());
+    }
 
-        let mut vm = Vm::new(runtime.clone(), case.unit.clone());
-        case.execute(&mut vm, &capture).await?;
+    for case in cases {
+        runner.push(case);
+    }
+
+    let report = runner.run(|capture| shared.context(entry, c, Some(capture)))?;
 
-        if case.outcome.is_ok() {
+    let mut failed = Vec::new();
+
+    for result in report.results() {
+        if result.outcome().is_passed() {
             if flags.quiet {
                 write!(io.stdout, ".")?;
             } else {
-                case.emit(io, &colors)?;
+                emit(io, &colors, result)?;
             }
 
             continue;
@@ -217,164 +252,87 @@ where
             write!(io.stdout, "f")?;
         }
 
-        failed.push(case);
-
-        if flags.fail_fast {
-            break;
-        }
+        failed.push(result);
     }
 
     if flags.quiet {
         writeln!(io.stdout)?;
     }
 
-    let failures = failed.len();
+    for result in failed {
+        emit(io, &colors, result)?;
+    }
+
+    if let Some(path) = &flags.junit {
+        let mut out = Vec::new();
+        report.write_junit(&mut out)?;
+        fs::write(path, out).with_context(|| path.display().to_string())?;
+    }
 
-    for case in failed {
-        case.emit(io, &colors)?;
+    if let Some(path) = &flags.json {
+        let mut out = Vec::new();
+        report.write_json(&mut out)?;
+        fs::write(path, out).with_context(|| path.display().to_string())?;
     }
 
     let elapsed = start.elapsed();
 
     writeln!(
         io.stdout,
-        "Executed {} tests with {} failures ({} skipped, {} build errors) in {:.3} seconds",
-        executed,
-        failures,
-        total - executed,
+        "Executed {} tests with {} failures ({} skipped, {} ignored, {} filtered out, {} build errors) in {:.3} seconds",
+        report.results().len(),
+        report.failed(),
+        report.skipped(),
+        report.ignored(),
+        report.filtered(),
         build_errors,
         elapsed.as_secs_f64()
     )?;
 
-    if build_errors == 0 && failures == 0 {
+    if build_errors == 0 && report.is_success() {
         Ok(ExitCode::Success)
     } else {
         Ok(ExitCode::Failure)
     }
 }
 
-#[derive(Debug)]
-enum Outcome {
-    Ok,
-    Panic(VmError),
-    ExpectedPanic,
-    None,
-    Err(Value),
-}
+fn emit(io: &mut Io<'_>, colors: &Colors, result: &TestResult) -> Result<()> {
+    write!(io.stdout, "Test {}: ", result.item())?;
 
-impl Outcome {
-    fn is_ok(&self) -> bool {
-        matches!(self, Outcome::Ok)
-    }
-}
-
-struct TestCase {
-    hash: Hash,
-    item: ItemBuf,
-    unit: Arc<Unit>,
-    sources: Arc<Sources>,
-    params: TestParams,
-    outcome: Outcome,
-    output: Vec<u8>,
-}
-
-impl TestCase {
-    fn new(hash: Hash, item: ItemBuf, unit: Arc<Unit>, sources: Arc<Sources>, params: TestParams) -> Self {
-        Self {
-            hash,
-            item,
-            unit,
-            sources,
-            params,
-            outcome: Outcome::Ok,
-            output: Vec::new(),
+    match result.outcome() {
+        TestOutcome::Passed => {
+            io.stdout.set_color(&colors.passed)?;
+            writeln!(io.stdout, "ok")?;
+            io.stdout.reset()?;
         }
-    }
+        TestOutcome::Panicked(error) => {
+            io.stdout.set_color(&colors.error)?;
+            writeln!(io.stdout, "panicked")?;
+            io.stdout.reset()?;
 
-    async fn execute(
-        &mut self,
-        vm: &mut Vm,
-        capture_io: &CaptureIo,
-    ) -> Result<()> {
-        let result = match vm.execute(self.hash, ()) {
-            Ok(mut execution) => execution.async_complete().await,
-            Err(err) => VmResult::Err(err),
-        };
-
-        capture_io.drain_into(&mut self.output)?;
-
-        self.outcome = match result {
-            VmResult::Ok(v) => match v {
-                Value::Result(result) => match result.take()? {
-                    Ok(..) => Outcome::Ok,
-                    Err(error) => Outcome::Err(error),
-                },
-                Value::Option(option) => match *option.borrow_ref()? {
-                    Some(..) => Outcome::Ok,
-                    None => Outcome::None,
-                },
-                _ => Outcome::Ok,
-            },
-            VmResult::Err(e) => {
-                Outcome::Panic(e)
-            }
-        };
-
-        if self.params.should_panic {
-            if matches!(self.outcome, Outcome::Panic(..)) {
-                self.outcome = Outcome::Ok;
-            } else {
-                self.outcome = Outcome::ExpectedPanic;
-            }
+            error.emit(io.stdout, result.sources())?;
         }
-
-        Ok(())
-    }
-
-    fn emit(self, io: &mut Io<'_>, colors: &Colors) -> Result<()> {
-        write!(io.stdout, "Test {}: ", self.item)?;
-
-        match &self.outcome {
-            Outcome::Panic(error) => {
-                io.stdout.set_color(&colors.error)?;
-                writeln!(io.stdout, "panicked")?;
-                io.stdout.reset()?;
-
-                error.emit(io.stdout, &self.sources)?;
-            }
-            Outcome::ExpectedPanic => {
-                io.stdout.set_color(&colors.error)?;
-                writeln!(io.stdout, "expected panic because of `should_panic`, but ran without issue")?;
-                io.stdout.reset()?;
-            }
-            Outcome::Err(error) => {
-                io.stdout.set_color(&colors.error)?;
-                write!(io.stdout, "err: ")?;
-                io.stdout.reset()?;
-                writeln!(io.stdout, "{:?}", error)?;
-            }
-            Outcome::None => {
-                io.stdout.set_color(&colors.error)?;
-                writeln!(io.stdout, "returned none")?;
-                io.stdout.reset()?;
-            }
-            Outcome::Ok => {
-                io.stdout.set_color(&colors.passed)?;
-                writeln!(io.stdout, "ok")?;
-                io.stdout.reset()?;
-            }
+        TestOutcome::ReturnedErr(error) => {
+            io.stdout.set_color(&colors.error)?;
+            write!(io.stdout, "err: ")?;
+            io.stdout.reset()?;
+            writeln!(io.stdout, "{}", error)?;
         }
-
-        if !self.outcome.is_ok() && !self.output.is_empty() {
-            writeln!(io.stdout, "-- output --")?;
-            io.stdout.write_all(&self.output)?;
-            writeln!(io.stdout, "-- end of output --")?;
+        outcome => {
+            io.stdout.set_color(&colors.error)?;
+            writeln!(io.stdout, "{}", outcome)?;
+            io.stdout.reset()?;
         }
+    }
 
-        Ok(())
+    if !result.outcome().is_passed() && !result.output().is_empty() {
+        writeln!(io.stdout, "-- output --")?;
+        io.stdout.write_all(result.output())?;
+        writeln!(io.stdout, "-- end of output --")?;
     }
-}
 
+    Ok(())
+}
 struct Colors {
     error: ColorSpec,
     passed: ColorSpec,
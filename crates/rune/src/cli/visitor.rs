@@ -18,7 +18,7 @@ pub(super) enum Attribute {
 /// A compile visitor that collects functions with a specific attribute.
 pub(super) struct FunctionVisitor {
     attribute: Attribute,
-    functions: Vec<(Hash, ItemBuf)>,
+    functions: Vec<(Hash, ItemBuf, meta::TestParams)>,
 }
 
 impl FunctionVisitor {
@@ -31,18 +31,37 @@ impl FunctionVisitor {
 
     /// Convert visitor into test functions.
     pub(super) fn into_functions(self) -> Vec<(Hash, ItemBuf)> {
+        self.functions
+            .into_iter()
+            .map(|(hash, item, _)| (hash, item))
+            .collect()
+    }
+
+    /// Convert visitor into test functions, together with the parameters
+    /// they were declared with.
+    pub(super) fn into_tests(self) -> Vec<(Hash, ItemBuf, meta::TestParams)> {
         self.functions
     }
 }
 
 impl CompileVisitor for FunctionVisitor {
     fn register_meta(&mut self, meta: MetaRef<'_>) {
-        let type_hash = match (self.attribute, &meta.kind) {
-            (Attribute::Test, meta::Kind::Function { is_test, .. }) if *is_test => meta.hash,
-            (Attribute::Bench, meta::Kind::Function { is_bench, .. }) if *is_bench => meta.hash,
+        let (type_hash, params) = match (self.attribute, &meta.kind) {
+            (
+                Attribute::Test,
+                meta::Kind::Function {
+                    is_test,
+                    test_params,
+                    ..
+                },
+            ) if *is_test => (meta.hash, *test_params),
+            (Attribute::Bench, meta::Kind::Function { is_bench, .. }) if *is_bench => {
+                (meta.hash, meta::TestParams::default())
+            }
             _ => return,
         };
 
-        self.functions.push((type_hash, meta.item.to_owned()));
+        self.functions
+            .push((type_hash, meta.item.to_owned(), params));
     }
 }
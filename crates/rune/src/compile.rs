@@ -3,27 +3,51 @@
 //! The main entry to compiling rune source is [prepare][crate::prepare] which
 //! uses this compiler. In here you'll just find compiler-specific types.
 
+mod assembler;
+pub use self::assembler::{AssemblerError, FunctionAssembler, UnitAssembler};
+
 mod assembly;
 pub(crate) use self::assembly::{Assembly, AssemblyInst};
 
 pub(crate) mod attrs;
 
 pub(crate) mod error;
-pub use self::error::{Error, ImportStep};
+pub use self::error::{Error, ImportStep, Suggestion};
 pub(crate) use self::error::{ErrorKind, IrErrorKind};
 
 mod compile_visitor;
-pub use self::compile_visitor::CompileVisitor;
 pub(crate) use self::compile_visitor::NoopCompileVisitor;
+pub use self::compile_visitor::{CompileVisitor, TemplatePart};
+
+mod references;
+pub use self::references::{Reference, ReferenceKind, References, Symbol};
+
+mod semantic_tokens;
+pub use self::semantic_tokens::{SemanticToken, SemanticTokenKind};
+
+mod folding_ranges;
+pub use self::folding_ranges::{folding_ranges, FoldingRange, FoldingRangeKind};
+
+mod compile_pass;
+pub use self::compile_pass::{
+    CompilePass, PassAssembly, PassCall, PassContext, PassExpr, PassExprKind, PassFn, PassInst,
+};
+pub(crate) use self::compile_pass::{NoopCompilePass, PassBody};
+
+pub(crate) mod purity;
+pub use self::purity::Purity;
 
 pub(crate) mod context;
-pub use self::context::Context;
+pub use self::context::{Conflict, ConflictPolicy, Context, ContextAlias, ModuleInfo};
 
 pub(crate) mod context_error;
 pub use self::context_error::ContextError;
 
 pub(crate) mod meta_info;
-pub use meta_info::MetaInfo;
+pub use meta_info::{MetaInfo, MetaKind};
+
+mod meta_query;
+pub use self::meta_query::{MetaAttribute, MetaEntry, MetaIndex, MetaQuery};
 
 mod docs;
 pub(crate) use self::docs::Docs;
@@ -36,7 +60,11 @@ pub(crate) mod ir;
 pub use rune_core::{Component, ComponentRef, IntoComponent, Item, ItemBuf};
 
 mod source_loader;
+#[cfg(feature = "std")]
+pub(crate) use self::source_loader::data_path;
 pub use self::source_loader::{FileSourceLoader, NoopSourceLoader, SourceLoader};
+#[cfg(feature = "std")]
+pub use self::source_loader::{OverlaySourceLoader, SourceOverlay};
 
 mod unit_builder;
 pub use self::unit_builder::LinkerError;
@@ -45,7 +73,11 @@ pub(crate) use self::unit_builder::UnitBuilder;
 pub(crate) mod v1;
 
 mod options;
-pub use self::options::{Options, ParseOptionError};
+pub use self::options::{DebugLevel, Options, ParseOptionError};
+
+mod pass_manager;
+pub(crate) use self::pass_manager::dump_assembly;
+pub use self::pass_manager::{Optimization, PassManager};
 
 mod location;
 pub(crate) use self::location::DynLocation;
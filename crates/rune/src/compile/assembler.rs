@@ -0,0 +1,334 @@
+//! Programmatic construction of units.
+
+use core::fmt;
+
+use crate::no_std::error;
+use crate::no_std::prelude::*;
+
+use crate::ast::Span;
+use crate::compile::{self, Assembly, DebugLevel, IntoComponent, ItemBuf, Location, UnitBuilder};
+use crate::runtime::unit::{DefaultStorage, UnitEncoder, UnitStorage, VerifyError};
+use crate::runtime::{Call, ConstValue, Inst, InstAddress, Label, Unit};
+use crate::SourceId;
+
+/// Error raised when building a unit through [UnitAssembler::build].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum AssemblerError {
+    /// The unit couldn't be assembled, like when two functions are declared
+    /// with the same name or a jump refers to a label which was never placed.
+    Compile {
+        /// The underlying compile error.
+        error: compile::Error,
+    },
+    /// The assembled unit failed [verification][Unit::verify].
+    Verify {
+        /// The error raised when verifying the unit.
+        error: VerifyError,
+    },
+}
+
+impl fmt::Display for AssemblerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssemblerError::Compile { error } => {
+                write!(f, "Failed to assemble unit: {error}")
+            }
+            AssemblerError::Verify { error } => {
+                write!(f, "Assembled unit is invalid: {error}")
+            }
+        }
+    }
+}
+
+impl error::Error for AssemblerError {}
+
+impl From<compile::Error> for AssemblerError {
+    #[inline]
+    fn from(error: compile::Error) -> Self {
+        AssemblerError::Compile { error }
+    }
+}
+
+impl From<VerifyError> for AssemblerError {
+    #[inline]
+    fn from(error: VerifyError) -> Self {
+        AssemblerError::Verify { error }
+    }
+}
+
+/// Builder for constructing a [Unit] directly out of instructions, without
+/// going through Rune source.
+///
+/// This is intended for code generators, like a DSL which wants to target the
+/// Rune virtual machine. Functions are assembled through a
+/// [FunctionAssembler] and declared in the unit with
+/// [declare_function][UnitAssembler::declare_function]. The unit is
+/// [verified][Unit::verify] when it's [built][UnitAssembler::build], so a unit
+/// produced by the assembler is always safe to load into a virtual machine.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use rune::compile::UnitAssembler;
+/// use rune::runtime::{Call, Inst, InstAddress, InstOp};
+/// use rune::{Context, Vm};
+///
+/// let mut unit = UnitAssembler::new();
+///
+/// // fn max(a, b) { if a < b { b } else { a } }
+/// let mut f = unit.new_function();
+/// let other = f.new_label("other");
+///
+/// f.push(Inst::Copy { offset: 0 });
+/// f.push(Inst::Copy { offset: 1 });
+/// f.push(Inst::Op { op: InstOp::Lt, a: InstAddress::Top, b: InstAddress::Top });
+/// f.jump_if(&other);
+/// f.push(Inst::Return { address: InstAddress::Offset(0), clean: 2 });
+/// f.label(&other)?;
+/// f.push(Inst::Return { address: InstAddress::Offset(1), clean: 2 });
+///
+/// unit.declare_function(["max"], &["a", "b"], Call::Immediate, f)?;
+/// let unit = unit.build()?;
+///
+/// let context = Context::new();
+/// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+/// let out: i64 = rune::from_value(vm.call(["max"], (10i64, 42i64))?)?;
+/// assert_eq!(out, 42);
+/// # Ok::<_, rune::Error>(())
+/// ```
+pub struct UnitAssembler<S = DefaultStorage> {
+    builder: UnitBuilder,
+    storage: S,
+    debug_level: DebugLevel,
+}
+
+impl UnitAssembler {
+    /// Construct a new assembler using the default unit storage.
+    pub fn new() -> Self {
+        Self::with_storage(DefaultStorage::default())
+    }
+}
+
+impl Default for UnitAssembler {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> UnitAssembler<S>
+where
+    S: UnitEncoder + UnitStorage,
+{
+    /// Construct a new assembler which encodes instructions into the given
+    /// storage.
+    pub fn with_storage(storage: S) -> Self {
+        Self {
+            builder: UnitBuilder::default(),
+            storage,
+            debug_level: DebugLevel::Full,
+        }
+    }
+
+    /// Set the amount of debug information to include in the built unit.
+    ///
+    /// Defaults to [DebugLevel::Full].
+    pub fn with_debug_level(mut self, debug_level: DebugLevel) -> Self {
+        self.debug_level = debug_level;
+        self
+    }
+
+    /// Insert a static string and return the slot which can be used with
+    /// instructions like [Inst::String].
+    ///
+    /// Only uses up space if the static string is unique.
+    pub fn new_static_string(&mut self, string: &str) -> compile::Result<usize> {
+        self.builder.new_static_string(&Span::empty(), string)
+    }
+
+    /// Insert a static byte string and return the slot which can be used with
+    /// instructions like [Inst::Bytes].
+    ///
+    /// Only uses up space if the static byte string is unique.
+    pub fn new_static_bytes(&mut self, bytes: &[u8]) -> compile::Result<usize> {
+        self.builder.new_static_bytes(&Span::empty(), bytes)
+    }
+
+    /// Insert a collection of static object keys and return the slot which
+    /// can be used with instructions like [Inst::Object].
+    ///
+    /// Only uses up space if the collection of keys is unique.
+    pub fn new_static_object_keys<I>(&mut self, keys: I) -> compile::Result<usize>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.builder
+            .new_static_object_keys_iter(&Span::empty(), keys)
+    }
+
+    /// Insert static data and return the slot which can be used with
+    /// [Inst::StaticData].
+    pub fn new_static_data(&mut self, value: ConstValue) -> usize {
+        self.builder.new_static_data(value)
+    }
+
+    /// Construct a new function assembler whose labels are unique in this
+    /// unit.
+    pub fn new_function(&self) -> FunctionAssembler {
+        FunctionAssembler {
+            assembly: self.builder.new_assembly(location()),
+        }
+    }
+
+    /// Declare a function with the given name and named arguments, consisting
+    /// of the instructions assembled in `function`.
+    ///
+    /// Errors if a function with the same name has already been declared or
+    /// if a jump in the function refers to a label which hasn't been placed.
+    pub fn declare_function<N>(
+        &mut self,
+        name: N,
+        args: &[&str],
+        call: Call,
+        function: FunctionAssembler,
+    ) -> compile::Result<()>
+    where
+        N: IntoIterator,
+        N::Item: IntoComponent,
+    {
+        let item = ItemBuf::with_item(name);
+        let debug_args = args.iter().map(|&arg| arg.into()).collect();
+
+        self.builder.new_function(
+            location(),
+            &item,
+            args.len(),
+            function.assembly,
+            call,
+            debug_args,
+            &mut self.storage,
+        )
+    }
+
+    /// Build and [verify][Unit::verify] the assembled unit.
+    pub fn build(self) -> Result<Unit<S>, AssemblerError> {
+        let unit = self
+            .builder
+            .build(Span::empty(), self.storage, self.debug_level)?;
+        unit.verify()?;
+        Ok(unit)
+    }
+}
+
+/// Instructions of a single function being built through a [UnitAssembler].
+///
+/// Jumps are expressed in terms of [Label]s which are resolved to
+/// instruction offsets when the function is declared.
+pub struct FunctionAssembler {
+    assembly: Assembly,
+}
+
+impl FunctionAssembler {
+    /// Construct a new label with the given name, which is used in debug
+    /// information.
+    ///
+    /// The label has to be placed with [label][FunctionAssembler::label]
+    /// before the function is declared.
+    pub fn new_label(&mut self, name: &'static str) -> Label {
+        self.assembly.new_label(name)
+    }
+
+    /// Place the label at the next instruction.
+    pub fn label(&mut self, label: &Label) -> compile::Result<()> {
+        self.assembly.label(label)
+    }
+
+    /// Push an instruction.
+    pub fn push(&mut self, inst: Inst) {
+        self.assembly.push(inst, &Span::empty());
+    }
+
+    /// Push an instruction with a comment, which is included in debug
+    /// information.
+    pub fn push_with_comment(
+        &mut self,
+        inst: Inst,
+        comment: &dyn fmt::Display,
+    ) -> compile::Result<()> {
+        self.assembly
+            .push_with_comment(inst, &Span::empty(), comment)
+    }
+
+    /// Unconditionally jump to the given label, like [Inst::Jump].
+    pub fn jump(&mut self, label: &Label) {
+        self.assembly.jump(label, &Span::empty());
+    }
+
+    /// Pop a boolean and jump to the given label if it's `true`, like
+    /// [Inst::JumpIf].
+    pub fn jump_if(&mut self, label: &Label) {
+        self.assembly.jump_if(label, &Span::empty());
+    }
+
+    /// Jump to the given label if the top of the stack is `true`, only popping
+    /// it if the jump isn't taken, like [Inst::JumpIfOrPop].
+    pub fn jump_if_or_pop(&mut self, label: &Label) {
+        self.assembly.jump_if_or_pop(label, &Span::empty());
+    }
+
+    /// Jump to the given label if the top of the stack is `false`, only
+    /// popping it if the jump isn't taken, like [Inst::JumpIfNotOrPop].
+    pub fn jump_if_not_or_pop(&mut self, label: &Label) {
+        self.assembly.jump_if_not_or_pop(label, &Span::empty());
+    }
+
+    /// Jump to the given label if the branch on the top of the stack matches
+    /// `branch`, like [Inst::JumpIfBranch].
+    pub fn jump_if_branch(&mut self, branch: i64, label: &Label) {
+        self.assembly.jump_if_branch(branch, label, &Span::empty());
+    }
+
+    /// Pop a boolean and if it's `false`, pop `count` values and jump to the
+    /// given label, like [Inst::PopAndJumpIfNot].
+    pub fn pop_and_jump_if_not(&mut self, count: usize, label: &Label) {
+        self.assembly
+            .pop_and_jump_if_not(count, label, &Span::empty());
+    }
+
+    /// Advance the iterator at `offset`, jumping to the given label once it's
+    /// exhausted, like [Inst::IterNext].
+    pub fn iter_next(&mut self, offset: usize, label: &Label) {
+        self.assembly.iter_next(offset, label, &Span::empty());
+    }
+
+    /// Advance the integer counter at `offset` by `step` towards the integer at
+    /// `end`, jumping to the given label once it's reached, like
+    /// [Inst::IterStep].
+    pub fn iter_step(
+        &mut self,
+        offset: usize,
+        end: usize,
+        step: i64,
+        inclusive: bool,
+        label: &Label,
+    ) {
+        self.assembly
+            .iter_step(offset, end, step, inclusive, label, &Span::empty());
+    }
+
+    /// Jump to the given label with the residual value on the stack instead
+    /// of returning, like [Inst::TryOrJump].
+    pub fn try_or_jump(&mut self, address: InstAddress, preserve: bool, label: &Label) {
+        self.assembly
+            .try_or_jump(address, preserve, label, &Span::empty());
+    }
+}
+
+/// Assembled code doesn't correspond to any source.
+fn location() -> Location {
+    Location::new(SourceId::empty(), Span::empty())
+}
@@ -7,19 +7,50 @@ use crate::no_std::prelude::*;
 
 use crate::ast::{Span, Spanned};
 use crate::compile::{self, Location};
-use crate::runtime::{Inst, Label};
+use crate::runtime::{Inst, InstAddress, Label};
 use crate::{Hash, SourceId};
 
 #[derive(Debug, Clone)]
 pub(crate) enum AssemblyInst {
-    Jump { label: Label },
-    JumpIf { label: Label },
-    JumpIfOrPop { label: Label },
-    JumpIfNotOrPop { label: Label },
-    JumpIfBranch { branch: i64, label: Label },
-    PopAndJumpIfNot { count: usize, label: Label },
-    IterNext { offset: usize, label: Label },
-    Raw { raw: Inst },
+    Jump {
+        label: Label,
+    },
+    JumpIf {
+        label: Label,
+    },
+    JumpIfOrPop {
+        label: Label,
+    },
+    JumpIfNotOrPop {
+        label: Label,
+    },
+    JumpIfBranch {
+        branch: i64,
+        label: Label,
+    },
+    PopAndJumpIfNot {
+        count: usize,
+        label: Label,
+    },
+    IterNext {
+        offset: usize,
+        label: Label,
+    },
+    IterStep {
+        offset: usize,
+        end: usize,
+        step: i64,
+        inclusive: bool,
+        label: Label,
+    },
+    TryOrJump {
+        address: InstAddress,
+        preserve: bool,
+        label: Label,
+    },
+    Raw {
+        raw: Inst,
+    },
 }
 
 /// Helper structure to build instructions and maintain certain invariants.
@@ -33,6 +64,9 @@ pub(crate) struct Assembly {
     pub(crate) instructions: Vec<(AssemblyInst, Span)>,
     /// Comments associated with instructions.
     pub(crate) comments: HashMap<usize, String>,
+    /// Variables declared by the position they're declared at, with their
+    /// name and stack offset.
+    pub(crate) variables: Vec<(usize, Box<str>, usize)>,
     /// The number of labels.
     pub(crate) label_count: usize,
     /// The collection of functions required by this assembly.
@@ -47,6 +81,7 @@ impl Assembly {
             labels: Default::default(),
             instructions: Default::default(),
             comments: Default::default(),
+            variables: Default::default(),
             label_count,
             required_functions: Default::default(),
         }
@@ -153,6 +188,47 @@ impl Assembly {
         );
     }
 
+    /// Add an instruction that advances an integer counter.
+    pub(crate) fn iter_step(
+        &mut self,
+        offset: usize,
+        end: usize,
+        step: i64,
+        inclusive: bool,
+        label: &Label,
+        span: &dyn Spanned,
+    ) {
+        self.inner_push(
+            AssemblyInst::IterStep {
+                offset,
+                end,
+                step,
+                inclusive,
+                label: label.clone(),
+            },
+            span,
+        );
+    }
+
+    /// Add a try instruction which jumps to the given label with the residual
+    /// value on the stack instead of returning.
+    pub(crate) fn try_or_jump(
+        &mut self,
+        address: InstAddress,
+        preserve: bool,
+        label: &Label,
+        span: &dyn Spanned,
+    ) {
+        self.inner_push(
+            AssemblyInst::TryOrJump {
+                address,
+                preserve,
+                label: label.clone(),
+            },
+            span,
+        );
+    }
+
     /// Push a raw instruction.
     pub(crate) fn push(&mut self, raw: Inst, span: &dyn Spanned) {
         if let Inst::Call { hash, .. } = raw {
@@ -190,6 +266,12 @@ impl Assembly {
         Ok(())
     }
 
+    /// Record that a variable has been declared at the current position.
+    pub(crate) fn variable(&mut self, name: &str, offset: usize) {
+        self.variables
+            .push((self.instructions.len(), name.into(), offset));
+    }
+
     fn inner_push(&mut self, inst: AssemblyInst, span: &dyn Spanned) {
         self.instructions.push((inst, span.span()));
     }
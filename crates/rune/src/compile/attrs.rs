@@ -8,6 +8,7 @@ use crate::ast;
 use crate::ast::{LitStr, Spanned};
 use crate::compile::{self, ErrorKind};
 use crate::parse::{self, Parse, Resolve, ResolveContext};
+use crate::runtime::Derives;
 
 /// Helper for parsing internal attributes.
 pub(crate) struct Parser {
@@ -195,6 +196,26 @@ impl Attribute for Test {
     const PATH: &'static str = "test";
 }
 
+/// The `#[ignore]` attribute, which marks a `#[test]` function as one which is
+/// not run by default.
+#[derive(Parse)]
+pub(crate) struct Ignore {}
+
+impl Attribute for Ignore {
+    /// Must match the specified name.
+    const PATH: &'static str = "ignore";
+}
+
+/// The `#[should_panic]` attribute, which marks a `#[test]` function as one
+/// which is expected to panic.
+#[derive(Parse)]
+pub(crate) struct ShouldPanic {}
+
+impl Attribute for ShouldPanic {
+    /// Must match the specified name.
+    const PATH: &'static str = "should_panic";
+}
+
 /// NB: at this point we don't support attributes beyond the empty `#[bench]`.
 #[derive(Parse)]
 pub(crate) struct Bench {}
@@ -204,6 +225,160 @@ impl Attribute for Bench {
     const PATH: &'static str = "bench";
 }
 
+/// Arguments to the `#[memo]` attribute.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct MemoArgs {
+    /// The number of results to cache, or `0` to use the default capacity of
+    /// the virtual machine.
+    pub(crate) capacity: usize,
+}
+
+/// The `#[memo]` attribute, which optionally takes a `capacity = <n>`
+/// argument.
+#[derive(Parse)]
+pub(crate) struct Memo {
+    /// Arguments to the attribute.
+    pub args: Option<ast::Parenthesized<MemoArg, T![,]>>,
+}
+
+impl Memo {
+    /// Parse memoization arguments.
+    pub(crate) fn args(&self, cx: ResolveContext<'_>) -> compile::Result<MemoArgs> {
+        let mut out = MemoArgs::default();
+
+        if let Some(args) = &self.args {
+            for (arg, _) in args {
+                match arg.key.resolve(cx)? {
+                    "capacity" => {
+                        let capacity = match arg.value.resolve(cx)?.as_usize(false) {
+                            Some(capacity) if capacity > 0 => capacity,
+                            _ => {
+                                return Err(compile::Error::msg(
+                                    &arg.value,
+                                    "capacity must be a positive integer",
+                                ));
+                            }
+                        };
+
+                        out.capacity = capacity;
+                    }
+                    _ => {
+                        return Err(compile::Error::msg(&arg.key, "unsupported attribute"));
+                    }
+                }
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+impl Attribute for Memo {
+    /// Must match the specified name.
+    const PATH: &'static str = "memo";
+}
+
+/// Strictness enabled for a module through `#![strict(..)]`.
+///
+/// Strictness is inherited by submodules.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct StrictArgs {
+    /// Deny bindings which shadow another variable in scope.
+    pub(crate) shadowing: bool,
+    /// Require type annotations on the arguments of functions.
+    pub(crate) annotations: bool,
+}
+
+impl StrictArgs {
+    /// Enable everything which is enabled in `other`.
+    pub(crate) fn merge(&mut self, other: StrictArgs) {
+        self.shadowing |= other.shadowing;
+        self.annotations |= other.annotations;
+    }
+}
+
+/// The `#![strict(..)]` module attribute, like `#![strict(shadowing)]`.
+#[derive(Parse)]
+pub(crate) struct Strict {
+    /// The kinds of strictness to enable.
+    pub args: ast::Parenthesized<ast::Ident, T![,]>,
+}
+
+impl Strict {
+    /// Parse strictness arguments.
+    pub(crate) fn args(&self, cx: ResolveContext<'_>) -> compile::Result<StrictArgs> {
+        let mut out = StrictArgs::default();
+
+        for (ident, _) in &self.args {
+            match ident.resolve(cx)? {
+                "shadowing" => out.shadowing = true,
+                "annotations" => out.annotations = true,
+                _ => {
+                    return Err(compile::Error::msg(
+                        ident,
+                        "unsupported strictness, expected `shadowing` or `annotations`",
+                    ));
+                }
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+impl Attribute for Strict {
+    /// Must match the specified name.
+    const PATH: &'static str = "strict";
+}
+
+/// A `<key> = <value>` argument to the `#[memo]` attribute.
+#[derive(Parse)]
+pub(crate) struct MemoArg {
+    /// The name of the argument.
+    pub key: ast::Ident,
+    /// The `=` token.
+    #[allow(dead_code)]
+    pub eq_token: T![=],
+    /// The value of the argument.
+    pub value: ast::LitNumber,
+}
+
+/// The `#[derive(..)]` attribute on structs and enums, like
+/// `#[derive(PartialEq, Hash, Debug)]`.
+#[derive(Parse)]
+pub(crate) struct Derive {
+    /// The protocols to derive.
+    pub args: ast::Parenthesized<ast::Ident, T![,]>,
+}
+
+impl Derive {
+    /// Add the derived protocols to `derives`.
+    pub(crate) fn apply(
+        &self,
+        cx: ResolveContext<'_>,
+        derives: &mut Derives,
+    ) -> compile::Result<()> {
+        for (ident, _) in &self.args {
+            match ident.resolve(cx)? {
+                "PartialEq" => derives.partial_eq = true,
+                "Eq" => derives.eq = true,
+                "Hash" => derives.hash = true,
+                "Debug" => derives.debug = true,
+                _ => {
+                    return Err(compile::Error::msg(ident, "unsupported derive"));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Attribute for Derive {
+    /// Must match the specified name.
+    const PATH: &'static str = "derive";
+}
+
 #[derive(Parse)]
 pub(crate) struct Doc {
     /// The `=` token.
@@ -4,14 +4,15 @@ use crate::ast;
 use crate::ast::{Span, Spanned};
 use crate::compile::v1;
 use crate::compile::{
-    self, Assembly, CompileVisitor, Context, ErrorKind, Location, Options, Pool, Prelude,
-    SourceLoader, UnitBuilder,
+    self, purity, Assembly, CompilePass, CompileVisitor, Context, ErrorKind, ItemId, Location,
+    Optimization, Options, PassBody, Pool, Prelude, Purity, SourceLoader, UnitBuilder,
 };
 use crate::hir;
 use crate::macros::Storage;
 use crate::parse::Resolve;
 use crate::query::{Build, BuildEntry, GenericsParameters, Query};
 use crate::runtime::unit::UnitEncoder;
+use crate::runtime::{Call, Inst, InstAddress, InstVariant};
 use crate::shared::{Consts, Gen};
 use crate::worker::{LoadFileKind, Task, Worker};
 use crate::{Diagnostics, Sources};
@@ -24,6 +25,7 @@ pub(crate) fn compile(
     pool: &mut Pool,
     context: &Context,
     visitor: &mut dyn CompileVisitor,
+    passes: &mut dyn CompilePass,
     diagnostics: &mut Diagnostics,
     source_loader: &mut dyn SourceLoader,
     options: &Options,
@@ -45,6 +47,7 @@ pub(crate) fn compile(
         sources,
         pool,
         visitor,
+        passes,
         diagnostics,
         source_loader,
         options,
@@ -136,10 +139,41 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
             scopes: self::v1::Scopes::new(location.source_id),
             contexts: vec![span.span()],
             loops: self::v1::Loops::new(),
+            defers: self::v1::Defers::new(),
             options: self.options,
         }
     }
 
+    /// Perform the enabled optimization passes over the assembly of a function
+    /// before it's added to the unit.
+    fn optimize(&mut self, location: Location, item: ItemId, asm: &mut Assembly) {
+        let passes = &self.options.passes;
+
+        if passes.is_dump() {
+            let assembly = compile::dump_assembly(asm);
+            let item = self.q.pool.item(item);
+            self.q
+                .visitor
+                .visit_assembly_dump(&location, item, None, &assembly);
+        }
+
+        for optimization in Optimization::ALL {
+            if !optimization.is_assembly_pass() || !passes.is_enabled(optimization) {
+                continue;
+            }
+
+            passes.run(optimization, asm);
+
+            if passes.is_dump() {
+                let assembly = compile::dump_assembly(asm);
+                let item = self.q.pool.item(item);
+                self.q
+                    .visitor
+                    .visit_assembly_dump(&location, item, Some(optimization), &assembly);
+            }
+        }
+    }
+
     #[tracing::instrument(skip_all)]
     fn compile(
         mut self,
@@ -185,14 +219,19 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                     &arena,
                     self.q.borrow(),
                     item_meta.location.source_id,
+                    item_meta.module,
                 );
                 let hir = hir::lowering::empty_fn(&mut cx, &f.ast, &f.span)?;
+                self.q.pass_fn(&item_meta, span, 0, PassBody::Fn(&hir))?;
                 let mut c = self.compiler1(location, span, &mut asm);
                 assemble::fn_from_item_fn(&mut c, &hir, false)?;
+                c.q.pass_assembly(location.source_id, item_meta.item, span, 0, c.asm)?;
 
                 if used.is_unused() {
                     self.q.diagnostics.not_used(location.source_id, span, None);
                 } else {
+                    self.optimize(location, item_meta.item, &mut asm);
+
                     self.q.unit.new_function(
                         location,
                         self.q.pool.item(item_meta.item),
@@ -220,14 +259,42 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                     &arena,
                     self.q.borrow(),
                     item_meta.location.source_id,
+                    item_meta.module,
                 );
                 let hir = hir::lowering::item_fn(&mut cx, &f.ast)?;
+
+                let hash = self.q.pool.item_type_hash(item_meta.item);
+                let summary = purity::summarize_fn(item_meta, &f.ast, &hir, f.call);
+                self.q.insert_purity_summary(hash, summary);
+                self.q
+                    .pass_fn(&item_meta, span, count, PassBody::Fn(&hir))?;
+
+                if let Some(memo) = f.memo {
+                    if let Some(Purity::Impure { span: effect }) = self.q.purity(hash) {
+                        self.q
+                            .diagnostics
+                            .impure_memo(location.source_id, &f.ast.name, effect);
+                    }
+
+                    asm.push(
+                        Inst::Memo {
+                            hash,
+                            args: count,
+                            capacity: memo.capacity,
+                        },
+                        span,
+                    );
+                }
+
                 let mut c = self.compiler1(location, span, &mut asm);
                 assemble::fn_from_item_fn(&mut c, &hir, false)?;
+                c.q.pass_assembly(location.source_id, item_meta.item, span, count, c.asm)?;
 
                 if used.is_unused() {
                     self.q.diagnostics.not_used(location.source_id, span, None);
                 } else {
+                    self.optimize(location, item_meta.item, &mut asm);
+
                     self.q.unit.new_function(
                         location,
                         self.q.pool.item(item_meta.item),
@@ -266,13 +333,18 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                     &arena,
                     c.q.borrow(),
                     item_meta.location.source_id,
+                    item_meta.module,
                 );
                 let hir = hir::lowering::item_fn(&mut cx, &f.ast)?;
+                c.q.pass_fn(&item_meta, &f.ast, count, PassBody::Fn(&hir))?;
                 assemble::fn_from_item_fn(&mut c, &hir, true)?;
+                c.q.pass_assembly(location.source_id, item_meta.item, &f.ast, count, c.asm)?;
 
                 if used.is_unused() {
                     c.q.diagnostics.not_used(location.source_id, &f.ast, None);
                 } else {
+                    self.optimize(location, item_meta.item, &mut asm);
+
                     let name = f.ast.name.resolve(resolve_context!(self.q))?;
 
                     self.q.unit.new_instance_function(
@@ -306,15 +378,31 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                     &arena,
                     self.q.borrow(),
                     item_meta.location.source_id,
+                    item_meta.module,
                 );
                 let hir = hir::lowering::expr_closure_secondary(&mut cx, &closure.ast, captures)?;
+                self.q.pass_fn(
+                    &item_meta,
+                    &closure.ast,
+                    closure.ast.args.len(),
+                    PassBody::Closure(&hir),
+                )?;
                 let mut c = self.compiler1(location, &closure.ast, &mut asm);
                 assemble::expr_closure_secondary(&mut c, &hir, &closure.ast)?;
+                c.q.pass_assembly(
+                    location.source_id,
+                    item_meta.item,
+                    &closure.ast,
+                    closure.ast.args.len(),
+                    c.asm,
+                )?;
 
                 if used.is_unused() {
                     c.q.diagnostics
                         .not_used(location.source_id, &location.span, None);
                 } else {
+                    self.optimize(location, item_meta.item, &mut asm);
+
                     self.q.unit.new_function(
                         location,
                         self.q.pool.item(item_meta.item),
@@ -338,10 +426,24 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                     &arena,
                     self.q.borrow(),
                     item_meta.location.source_id,
+                    item_meta.module,
                 );
                 let hir = hir::lowering::async_block_secondary(&mut cx, &b.ast, captures)?;
+                self.q.pass_fn(
+                    &item_meta,
+                    &b.ast,
+                    hir.captures.len(),
+                    PassBody::AsyncBlock(&hir),
+                )?;
                 let mut c = self.compiler1(location, &b.ast, &mut asm);
                 assemble::async_block_secondary(&mut c, &hir)?;
+                c.q.pass_assembly(
+                    location.source_id,
+                    item_meta.item,
+                    &b.ast,
+                    hir.captures.len(),
+                    c.asm,
+                )?;
 
                 if used.is_unused() {
                     self.q
@@ -350,6 +452,8 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                 } else {
                     let args = hir.captures.len();
 
+                    self.optimize(location, item_meta.item, &mut asm);
+
                     self.q.unit.new_function(
                         location,
                         self.q.pool.item(item_meta.item),
@@ -361,6 +465,76 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                     )?;
                 }
             }
+            Build::EnumFromInt(f) => {
+                tracing::trace!("enum from int: {}", self.q.pool.item(item_meta.item));
+
+                if !used.is_unused() {
+                    let span = &location.span;
+                    let mut variants = Vec::with_capacity(f.variants.len());
+
+                    for (discriminant, item) in f.variants {
+                        // Ensure that the variant is built into the unit.
+                        let meta =
+                            self.q
+                                .lookup_meta(&location, item, GenericsParameters::default())?;
+
+                        let label = asm.new_label("enum_from_int");
+                        asm.push(Inst::Copy { offset: 0 }, span);
+                        asm.push(
+                            Inst::EqInteger {
+                                integer: discriminant,
+                            },
+                            span,
+                        );
+                        asm.jump_if(&label, span);
+                        variants.push((label, meta.hash));
+                    }
+
+                    asm.push(
+                        Inst::Variant {
+                            variant: InstVariant::None,
+                        },
+                        span,
+                    );
+                    asm.push(
+                        Inst::Return {
+                            address: InstAddress::Top,
+                            clean: 1,
+                        },
+                        span,
+                    );
+
+                    for (label, hash) in variants {
+                        asm.label(&label)?;
+                        asm.push(Inst::UnitVariant { hash }, span);
+                        asm.push(
+                            Inst::Variant {
+                                variant: InstVariant::Some,
+                            },
+                            span,
+                        );
+                        asm.push(
+                            Inst::Return {
+                                address: InstAddress::Top,
+                                clean: 1,
+                            },
+                            span,
+                        );
+                    }
+
+                    self.optimize(location, item_meta.item, &mut asm);
+
+                    self.q.unit.new_function(
+                        location,
+                        self.q.pool.item(item_meta.item),
+                        1,
+                        asm,
+                        Call::Immediate,
+                        Box::from([Box::from("value")]),
+                        unit_storage,
+                    )?;
+                }
+            }
             Build::Unused => {
                 tracing::trace!("unused: {}", self.q.pool.item(item_meta.item));
 
@@ -448,8 +622,8 @@ where
             ast::FnArg::SelfValue(..) => {
                 args.push("self".into());
             }
-            ast::FnArg::Pat(pat) => {
-                let span = pat.span();
+            ast::FnArg::Pat(..) | ast::FnArg::Typed(..) => {
+                let span = arg.span();
 
                 if let Some(s) = sources.source(location.source_id, span) {
                     args.push(s.into());
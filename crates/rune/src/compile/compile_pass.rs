@@ -0,0 +1,627 @@
+use core::fmt;
+
+use crate::no_std::collections::HashMap;
+use crate::no_std::prelude::*;
+
+use crate::ast::{self, Span, Spanned};
+use crate::compile::{self, Assembly, AssemblyInst, Item, Location, MetaRef, Purity};
+use crate::diagnostics::WarningDiagnosticKind;
+use crate::hash::Hash;
+use crate::hir;
+use crate::runtime::{Inst, InstAddress, Label};
+use crate::{Diagnostics, SourceId};
+
+/// A user-provided pass which is called while functions are being compiled.
+///
+/// Functions are visited once they've been lowered, with access to the
+/// expressions in their body. Calls are visited while a function is being
+/// assembled, which allows for limited rewrites such as redirecting them to a
+/// different function. Once a function has been assembled, it's visited again
+/// with access to its instructions. Passes can enforce custom rules by
+/// returning an error or emitting warnings.
+///
+/// Passes are registered with [Build::with_pass][crate::Build::with_pass].
+///
+/// # Examples
+///
+/// ```
+/// use rune::compile::{self, CompilePass, ItemBuf, PassCall, PassContext};
+///
+/// /// Forbid calls to `std::io::dbg`.
+/// struct NoDbg {
+///     dbg: ItemBuf,
+/// }
+///
+/// impl CompilePass for NoDbg {
+///     fn visit_call(
+///         &mut self,
+///         _cx: &mut PassContext<'_>,
+///         call: &mut PassCall<'_>,
+///     ) -> compile::Result<()> {
+///         if call.item() == self.dbg {
+///             return Err(compile::Error::msg(call, "Calls to `dbg` are not allowed"));
+///         }
+///
+///         Ok(())
+///     }
+/// }
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             dbg(42);
+///         }
+///     }
+/// };
+///
+/// let context = rune::Context::with_default_modules()?;
+/// let mut pass = NoDbg {
+///     dbg: ItemBuf::with_crate_item("std", ["io", "dbg"]),
+/// };
+///
+/// let result = rune::prepare(&mut sources)
+///     .with_context(&context)
+///     .with_pass(&mut pass)
+///     .build();
+///
+/// assert!(result.is_err());
+/// # Ok::<_, rune::Error>(())
+/// ```
+pub trait CompilePass {
+    /// Called for every function, closure and async block after it's been
+    /// lowered, and before it's assembled.
+    fn visit_fn(&mut self, _cx: &mut PassContext<'_>, _f: &PassFn<'_>) -> compile::Result<()> {
+        Ok(())
+    }
+
+    /// Called for every function, closure and async block after it's been
+    /// assembled, and before it's optimized and added to the unit.
+    fn visit_assembly(
+        &mut self,
+        _cx: &mut PassContext<'_>,
+        _f: &PassAssembly<'_>,
+    ) -> compile::Result<()> {
+        Ok(())
+    }
+
+    /// Called for every call to a statically known item, like a function or
+    /// a tuple struct constructor.
+    ///
+    /// The target of the call can be replaced with [PassCall::redirect].
+    fn visit_call(
+        &mut self,
+        _cx: &mut PassContext<'_>,
+        _call: &mut PassCall<'_>,
+    ) -> compile::Result<()> {
+        Ok(())
+    }
+}
+
+/// The context in which a [CompilePass] is called.
+pub struct PassContext<'a> {
+    pub(crate) source_id: SourceId,
+    pub(crate) diagnostics: &'a mut Diagnostics,
+    pub(crate) purity: Option<Purity>,
+}
+
+impl PassContext<'_> {
+    /// The source being compiled.
+    pub fn source_id(&self) -> SourceId {
+        self.source_id
+    }
+
+    /// The purity of the function being visited by [CompilePass::visit_fn] or
+    /// [CompilePass::visit_assembly].
+    ///
+    /// This is only available for functions declared in the unit, and not for
+    /// closures, async blocks or instance functions.
+    pub fn purity(&self) -> Option<Purity> {
+        self.purity
+    }
+
+    /// Emit a warning with a custom message at the given span.
+    ///
+    /// ```
+    /// use rune::compile::{self, CompilePass, PassCall, PassContext};
+    ///
+    /// struct Deprecated;
+    ///
+    /// impl CompilePass for Deprecated {
+    ///     fn visit_call(
+    ///         &mut self,
+    ///         cx: &mut PassContext<'_>,
+    ///         call: &mut PassCall<'_>,
+    ///     ) -> compile::Result<()> {
+    ///         if call.item().last().and_then(|c| c.as_str()) == Some("old") {
+    ///             cx.warning(&*call, format_args!("`{}` is deprecated", call.item()));
+    ///         }
+    ///
+    ///         Ok(())
+    ///     }
+    /// }
+    /// ```
+    pub fn warning<S, M>(&mut self, span: S, message: M)
+    where
+        S: Spanned,
+        M: fmt::Display,
+    {
+        self.diagnostics.warning(
+            self.source_id,
+            WarningDiagnosticKind::Custom {
+                span: span.span(),
+                message: message.to_string().into(),
+            },
+        );
+    }
+}
+
+/// The lowered body of a function.
+#[derive(Clone, Copy)]
+pub(crate) enum PassBody<'a> {
+    /// The body of a function.
+    Fn(&'a hir::ItemFn<'a>),
+    /// The body of a closure.
+    Closure(&'a hir::ExprClosure<'a>),
+    /// The body of an async block.
+    AsyncBlock(&'a hir::AsyncBlock<'a>),
+}
+
+/// A function which has been lowered, as seen by [CompilePass::visit_fn].
+pub struct PassFn<'a> {
+    pub(crate) item: &'a Item,
+    pub(crate) hash: Hash,
+    pub(crate) location: Location,
+    pub(crate) is_public: bool,
+    pub(crate) span: Span,
+    pub(crate) args: usize,
+    pub(crate) body: PassBody<'a>,
+}
+
+impl<'a> PassFn<'a> {
+    /// The item of the function.
+    pub fn item(&self) -> &'a Item {
+        self.item
+    }
+
+    /// The hash of the function.
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// The location where the function is declared.
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// Test if the function is public and exported from the unit.
+    pub fn is_public(&self) -> bool {
+        self.is_public
+    }
+
+    /// The number of arguments the function takes. For async blocks this is
+    /// the number of variables they capture.
+    pub fn args(&self) -> usize {
+        self.args
+    }
+
+    /// Iterate over every expression in the body of the function, with parent
+    /// expressions coming before the expressions they contain.
+    ///
+    /// The bodies of closures and async blocks are not included, since they're
+    /// visited as functions of their own.
+    pub fn exprs(&self) -> impl Iterator<Item = PassExpr> {
+        let mut exprs = Vec::new();
+
+        let mut visit = |hir: &hir::Expr<'_>| {
+            exprs.push(PassExpr {
+                span: hir.span,
+                kind: expr_kind(hir),
+            });
+        };
+
+        match self.body {
+            PassBody::Fn(hir) => hir::walk::block(&hir.body, &mut visit),
+            PassBody::Closure(hir) => hir::walk::expr(&hir.body, &mut visit),
+            PassBody::AsyncBlock(hir) => hir::walk::block(&hir.block, &mut visit),
+        }
+
+        exprs.into_iter()
+    }
+}
+
+impl Spanned for PassFn<'_> {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// An expression in the body of a function, as seen by [PassFn::exprs].
+#[derive(Debug, Clone, Copy)]
+pub struct PassExpr {
+    span: Span,
+    kind: PassExprKind,
+}
+
+impl PassExpr {
+    /// The kind of the expression.
+    pub fn kind(&self) -> PassExprKind {
+        self.kind
+    }
+}
+
+impl Spanned for PassExpr {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// The kind of a [PassExpr].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum PassExprKind {
+    /// A literal, like `42` or `"hello"`.
+    Lit,
+    /// A variable.
+    Variable,
+    /// A path to an item, like a function or a type.
+    Path,
+    /// A constant value.
+    Const,
+    /// An assignment, like `a = b`.
+    Assign,
+    /// A binary operation, like `a + b` or `a += b`.
+    Binary(ast::BinOp),
+    /// A unary operation, like `-a`.
+    Unary(ast::UnOp),
+    /// A field access, like `a.b`.
+    FieldAccess,
+    /// An index, like `a[b]`.
+    Index,
+    /// A call. If the function being called is statically known, `hash` is
+    /// the hash of that function.
+    Call {
+        /// The hash of the function being called.
+        hash: Option<Hash>,
+    },
+    /// A call to an instance function, like `a.b()`.
+    CallInstance {
+        /// The hash of the name of the instance function.
+        hash: Hash,
+    },
+    /// A closure.
+    Closure,
+    /// An async block.
+    AsyncBlock,
+    /// A block.
+    Block,
+    /// A `let` condition.
+    Let,
+    /// An `if` expression.
+    If,
+    /// A `match` expression.
+    Match,
+    /// A `loop` or `while` loop.
+    Loop,
+    /// A `for` loop.
+    For,
+    /// A `break` expression.
+    Break,
+    /// A `continue` expression.
+    Continue,
+    /// A `return` expression.
+    Return,
+    /// A `yield` expression.
+    Yield,
+    /// An `.await` expression.
+    Await,
+    /// A `?` expression.
+    Try,
+    /// A `select` expression.
+    Select,
+    /// An object, like `#{a: 1}`.
+    Object,
+    /// A tuple, like `(1, 2)`.
+    Tuple,
+    /// A vector, like `[1, 2]`.
+    Vec,
+    /// A range, like `a..b`.
+    Range,
+    /// A vector or an object which spreads other values into it.
+    Spread,
+    /// An expression in parenthesis.
+    Group,
+    /// A template string, or a value being formatted in a template.
+    Template,
+}
+
+fn expr_kind(hir: &hir::Expr<'_>) -> PassExprKind {
+    match hir.kind {
+        hir::ExprKind::Variable(..) => PassExprKind::Variable,
+        hir::ExprKind::Type(..) | hir::ExprKind::Fn(..) | hir::ExprKind::Path => PassExprKind::Path,
+        hir::ExprKind::Const(..) => PassExprKind::Const,
+        hir::ExprKind::Lit(..) | hir::ExprKind::StaticData(..) => PassExprKind::Lit,
+        hir::ExprKind::Assign(..) => PassExprKind::Assign,
+        hir::ExprKind::Loop(..) => PassExprKind::Loop,
+        hir::ExprKind::For(..) => PassExprKind::For,
+        hir::ExprKind::Let(..) => PassExprKind::Let,
+        hir::ExprKind::If(..) => PassExprKind::If,
+        hir::ExprKind::Match(..) => PassExprKind::Match,
+        hir::ExprKind::Call(call) => match call.call {
+            hir::Call::Meta { hash, .. } => PassExprKind::Call { hash: Some(hash) },
+            hir::Call::Associated { hash, .. } => PassExprKind::CallInstance { hash },
+            hir::Call::ConstFn { .. } => PassExprKind::Const,
+            hir::Call::Var { .. } | hir::Call::Expr { .. } => PassExprKind::Call { hash: None },
+        },
+        hir::ExprKind::FieldAccess(..) => PassExprKind::FieldAccess,
+        hir::ExprKind::Binary(binary) => PassExprKind::Binary(binary.op),
+        hir::ExprKind::Unary(unary) => PassExprKind::Unary(unary.op),
+        hir::ExprKind::Index(..) => PassExprKind::Index,
+        hir::ExprKind::AsyncBlock(..) => PassExprKind::AsyncBlock,
+        hir::ExprKind::CallClosure(..) => PassExprKind::Closure,
+        hir::ExprKind::Block(..) => PassExprKind::Block,
+        hir::ExprKind::Break(..) => PassExprKind::Break,
+        hir::ExprKind::Continue(..) => PassExprKind::Continue,
+        hir::ExprKind::Yield(..) => PassExprKind::Yield,
+        hir::ExprKind::Return(..) => PassExprKind::Return,
+        hir::ExprKind::Await(..) => PassExprKind::Await,
+        hir::ExprKind::Try(..) => PassExprKind::Try,
+        hir::ExprKind::Select(..) => PassExprKind::Select,
+        hir::ExprKind::Object(..) => PassExprKind::Object,
+        hir::ExprKind::Tuple(..) => PassExprKind::Tuple,
+        hir::ExprKind::Vec(..) => PassExprKind::Vec,
+        hir::ExprKind::Range(..) => PassExprKind::Range,
+        hir::ExprKind::Spread(..) => PassExprKind::Spread,
+        hir::ExprKind::Group(..) => PassExprKind::Group,
+        hir::ExprKind::Template(..) | hir::ExprKind::Format(..) => PassExprKind::Template,
+    }
+}
+
+/// A function which has been assembled, as seen by
+/// [CompilePass::visit_assembly].
+pub struct PassAssembly<'a> {
+    pub(crate) item: &'a Item,
+    pub(crate) span: Span,
+    pub(crate) args: usize,
+    pub(crate) asm: &'a Assembly,
+}
+
+impl<'a> PassAssembly<'a> {
+    /// The item of the function.
+    pub fn item(&self) -> &'a Item {
+        self.item
+    }
+
+    /// The number of arguments the function takes. For async blocks this is
+    /// the number of variables they capture.
+    pub fn args(&self) -> usize {
+        self.args
+    }
+
+    /// Iterate over the labels of the function, together with the offset of
+    /// the instruction they point to.
+    ///
+    /// A label can point to one past the last instruction, if it's placed at
+    /// the end of the function.
+    pub fn labels(&self) -> impl Iterator<Item = (usize, &'a Label)> + 'a {
+        let mut labels = self
+            .asm
+            .labels
+            .iter()
+            .flat_map(|(offset, (_, labels))| labels.iter().map(move |label| (*offset, label)))
+            .collect::<Vec<_>>();
+
+        labels.sort_by_key(|(offset, label)| (*offset, label.index));
+        labels.into_iter()
+    }
+
+    /// Iterate over all instructions of the function in order, together with
+    /// the span of the code which produced them.
+    ///
+    /// Instructions which jump, like the ones produced by `if` expressions and
+    /// loops, refer to the offset of the instruction they jump to.
+    pub fn instructions(&self) -> impl Iterator<Item = (PassInst<'a>, Span)> + 'a {
+        let mut targets = HashMap::new();
+
+        for (offset, (_, labels)) in &self.asm.labels {
+            for label in labels {
+                targets.insert(label.index, *offset);
+            }
+        }
+
+        let end = self.asm.instructions.len();
+
+        self.asm.instructions.iter().map(move |(inst, span)| {
+            let target = |label: &Label| targets.get(&label.index).copied().unwrap_or(end);
+
+            let inst = match inst {
+                AssemblyInst::Jump { label } => PassInst::Jump {
+                    target: target(label),
+                },
+                AssemblyInst::JumpIf { label } => PassInst::JumpIf {
+                    target: target(label),
+                },
+                AssemblyInst::JumpIfOrPop { label } => PassInst::JumpIfOrPop {
+                    target: target(label),
+                },
+                AssemblyInst::JumpIfNotOrPop { label } => PassInst::JumpIfNotOrPop {
+                    target: target(label),
+                },
+                AssemblyInst::JumpIfBranch { branch, label } => PassInst::JumpIfBranch {
+                    branch: *branch,
+                    target: target(label),
+                },
+                AssemblyInst::PopAndJumpIfNot { count, label } => PassInst::PopAndJumpIfNot {
+                    count: *count,
+                    target: target(label),
+                },
+                AssemblyInst::IterNext { offset, label } => PassInst::IterNext {
+                    offset: *offset,
+                    target: target(label),
+                },
+                AssemblyInst::IterStep {
+                    offset,
+                    end,
+                    step,
+                    inclusive,
+                    label,
+                } => PassInst::IterStep {
+                    offset: *offset,
+                    end: *end,
+                    step: *step,
+                    inclusive: *inclusive,
+                    target: target(label),
+                },
+                AssemblyInst::TryOrJump {
+                    address,
+                    preserve,
+                    label,
+                } => PassInst::TryOrJump {
+                    address: *address,
+                    preserve: *preserve,
+                    target: target(label),
+                },
+                AssemblyInst::Raw { raw } => PassInst::Inst(raw),
+            };
+
+            (inst, *span)
+        })
+    }
+}
+
+impl Spanned for PassAssembly<'_> {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// An instruction of an assembled function, as seen by
+/// [PassAssembly::instructions].
+///
+/// Instructions which jump are represented by their own variants, since their
+/// targets are only resolved to an [Inst] once the function is added to the
+/// unit. The `target` of a jump is the offset of the instruction it jumps to.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum PassInst<'a> {
+    /// An instruction which doesn't jump.
+    Inst(&'a Inst),
+    /// Unconditionally jump to `target`.
+    Jump {
+        /// The offset of the instruction to jump to.
+        target: usize,
+    },
+    /// Jump to `target` if the top of the stack is `true`.
+    JumpIf {
+        /// The offset of the instruction to jump to.
+        target: usize,
+    },
+    /// Jump to `target` if the top of the stack is `true`, otherwise pop it.
+    JumpIfOrPop {
+        /// The offset of the instruction to jump to.
+        target: usize,
+    },
+    /// Jump to `target` if the top of the stack is `false`, otherwise pop it.
+    JumpIfNotOrPop {
+        /// The offset of the instruction to jump to.
+        target: usize,
+    },
+    /// Jump to `target` if the top of the stack is equal to `branch`.
+    JumpIfBranch {
+        /// The branch to compare against.
+        branch: i64,
+        /// The offset of the instruction to jump to.
+        target: usize,
+    },
+    /// Pop `count` values, and jump to `target` if the top of the stack is
+    /// `false`.
+    PopAndJumpIfNot {
+        /// The number of values to pop.
+        count: usize,
+        /// The offset of the instruction to jump to.
+        target: usize,
+    },
+    /// Advance the iterator at `offset`, jumping to `target` once it's
+    /// exhausted.
+    IterNext {
+        /// The stack offset of the iterator.
+        offset: usize,
+        /// The offset of the instruction to jump to.
+        target: usize,
+    },
+    /// Step the counter at `offset` towards `end`, jumping to `target` once
+    /// it's been reached.
+    IterStep {
+        /// The stack offset of the counter.
+        offset: usize,
+        /// The stack offset of the end of the range.
+        end: usize,
+        /// The value to step by.
+        step: i64,
+        /// Whether the end is inclusive.
+        inclusive: bool,
+        /// The offset of the instruction to jump to.
+        target: usize,
+    },
+    /// Unwrap the value at `address` like `?` does, jumping to `target` if it
+    /// short-circuits.
+    TryOrJump {
+        /// The address of the value being unwrapped.
+        address: InstAddress,
+        /// Whether to preserve the value.
+        preserve: bool,
+        /// The offset of the instruction to jump to.
+        target: usize,
+    },
+}
+
+/// A call to a statically known item, as seen by [CompilePass::visit_call].
+pub struct PassCall<'a> {
+    pub(crate) span: Span,
+    pub(crate) meta: MetaRef<'a>,
+    pub(crate) hash: Hash,
+}
+
+impl<'a> PassCall<'a> {
+    /// Metadata of the item being called.
+    pub fn meta(&self) -> MetaRef<'a> {
+        self.meta
+    }
+
+    /// The item being called.
+    pub fn item(&self) -> &'a Item {
+        self.meta.item
+    }
+
+    /// The hash of the function which will be called. This differs from the
+    /// hash of [PassCall::meta] if the call has been redirected.
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// Redirect the call to the function with the given hash.
+    ///
+    /// The arguments of the call are left as-is, and the function is not
+    /// looked up until the call is performed, so the new target must accept
+    /// the same arguments and exist at runtime.
+    pub fn redirect(&mut self, hash: Hash) {
+        self.hash = hash;
+    }
+}
+
+impl Spanned for PassCall<'_> {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A [CompilePass] which does nothing.
+pub(crate) struct NoopCompilePass(());
+
+impl NoopCompilePass {
+    /// Construct a new noop compile pass.
+    pub(crate) const fn new() -> Self {
+        Self(())
+    }
+}
+
+impl CompilePass for NoopCompilePass {}
@@ -1,5 +1,5 @@
-use crate::ast::Spanned;
-use crate::compile::{Item, Located, MetaRef};
+use crate::ast::{Span, Spanned};
+use crate::compile::{Item, Located, MetaRef, Optimization};
 use crate::hash::Hash;
 use crate::SourceId;
 
@@ -23,6 +23,44 @@ pub trait CompileVisitor {
     /// Visit something that is a module.
     fn visit_mod(&mut self, _location: &dyn Located) {}
 
+    /// Visit a macro call.
+    ///
+    /// Anything visited inside of the location of a macro call might have
+    /// been produced by the macro rather than written in the source.
+    fn visit_macro_call(&mut self, _location: &dyn Located) {}
+
+    /// Visit a template literal, like `` `Hello ${name}!` ``, inside of the
+    /// given enclosing item.
+    ///
+    /// The template is provided as the sequence of parts it consists of, which
+    /// makes it possible for tooling to extract templates into translation
+    /// catalogs. Templates constructed by calling `template!` directly are not
+    /// visited.
+    fn visit_template(
+        &mut self,
+        _location: &dyn Located,
+        _item: &Item,
+        _parts: &[TemplatePart<'_>],
+    ) {
+    }
+
+    /// Visit the assembly of a function after an optimization pass has been
+    /// performed over it, or before any passes have been performed if `pass`
+    /// is `None`.
+    ///
+    /// This is only called if dumping is enabled through
+    /// [PassManager::dump][crate::compile::PassManager::dump], and is intended
+    /// to help debug miscompilations. The format of the assembly is not
+    /// stable.
+    fn visit_assembly_dump(
+        &mut self,
+        _location: &dyn Located,
+        _item: &Item,
+        _pass: Option<Optimization>,
+        _assembly: &str,
+    ) {
+    }
+
     /// Visit anterior `///`-style comments, and interior `//!`-style doc
     /// comments for an item.
     ///
@@ -56,6 +94,18 @@ pub trait CompileVisitor {
     }
 }
 
+/// A part of a template literal, as provided to
+/// [CompileVisitor::visit_template].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TemplatePart<'a> {
+    /// A literal string in the template, with escape sequences resolved.
+    Str(&'a str),
+    /// An interpolation slot in the template, with the span of the expression
+    /// being interpolated.
+    Slot(Span),
+}
+
 /// A [CompileVisitor] which does nothing.
 pub(crate) struct NoopCompileVisitor(());
 
@@ -12,14 +12,15 @@ use crate::compile::MetaInfo;
 use crate::compile::{ComponentRef, ContextError, IntoComponent, Item, ItemBuf, Names};
 use crate::hash;
 use crate::module::{
-    Fields, InternalEnum, Module, ModuleAssociated, ModuleAttributeMacro, ModuleConstant,
-    ModuleFunction, ModuleMacro, ModuleType, TypeSpecification,
+    Fields, InternalEnum, Module, ModuleAlias, ModuleAssociated, ModuleAttributeMacro,
+    ModuleConstant, ModuleFunction, ModuleMacro, ModuleType, TypeSpecification,
 };
 use crate::runtime::{
-    AttributeMacroHandler, ConstValue, FunctionHandler, MacroHandler, Protocol, RuntimeContext,
-    StaticType, TypeCheck, TypeInfo, VariantRtti,
+    AttributeMacroHandler, BorrowMode, ConstValue, Derives, Formatter, FunctionHandler,
+    MacroHandler, MessageCatalog, PanicHook, PrettyPrinterHandler, Protocol, RuntimeContext,
+    StaticType, TypeCheck, TypeInfo, Value, VariantRtti, VmErrorKind, VmResult,
 };
-use crate::Hash;
+use crate::{Any, Hash};
 
 /// Context metadata.
 #[derive(Debug)]
@@ -59,6 +60,174 @@ pub(crate) struct ContextType {
     type_parameters: Hash,
 }
 
+/// How conflicts are resolved when installing a [Module] which declares items
+/// that are already present in a [Context].
+///
+/// See [Context::install_with_policy].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConflictPolicy {
+    /// Refuse to install the module, and report every conflicting item
+    /// through [ContextError::ConflictingItems].
+    #[default]
+    Error,
+    /// Keep items which are already present in the context, and skip the
+    /// conflicting items of the module being installed.
+    FirstWins,
+    /// Replace items which are already present in the context with the
+    /// conflicting items of the module being installed.
+    LastWins,
+}
+
+/// Information on a [Module] which has been installed into a [Context].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleInfo {
+    item: ItemBuf,
+    version: Option<Box<str>>,
+}
+
+impl ModuleInfo {
+    fn new(module: &Module) -> Self {
+        Self {
+            item: module.item.clone(),
+            version: module.version.clone(),
+        }
+    }
+
+    /// The item the module was installed under.
+    pub fn item(&self) -> &Item {
+        &self.item
+    }
+
+    /// The version of the module, if any.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+}
+
+impl fmt::Display for ModuleInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`", self.item)?;
+
+        if let Some(version) = &self.version {
+            write!(f, " (version {version})")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An item declared by a [Module] which conflicts with an item that is already
+/// present in a [Context].
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    item: ItemBuf,
+    hash: Hash,
+    module: ModuleInfo,
+    existing: ModuleInfo,
+}
+
+impl Conflict {
+    /// The item which is conflicting.
+    pub fn item(&self) -> &Item {
+        &self.item
+    }
+
+    /// The hash of the conflicting item.
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// The module which is being installed.
+    pub fn module(&self) -> &ModuleInfo {
+        &self.module
+    }
+
+    /// The module which provided the item that is already present.
+    pub fn existing(&self) -> &ModuleInfo {
+        &self.existing
+    }
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` from module {} conflicts with the same item from module {}",
+            self.item, self.module, self.existing
+        )
+    }
+}
+
+/// An alias of an item which has been installed into a [Context] through
+/// [Module::alias].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextAlias {
+    item: ItemBuf,
+    target: ItemBuf,
+}
+
+impl ContextAlias {
+    /// The item of the alias.
+    pub fn item(&self) -> &Item {
+        &self.item
+    }
+
+    /// The item being aliased.
+    ///
+    /// If the alias was registered with a target which is itself an alias,
+    /// this is the item that alias refers to.
+    pub fn target(&self) -> &Item {
+        &self.target
+    }
+}
+
+impl fmt::Display for ContextAlias {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} => {}", self.item, self.target)
+    }
+}
+
+/// The namespace an item occupies, since functions and macros might share the
+/// same item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Namespace {
+    Item,
+    Macro,
+    AttributeMacro,
+}
+
+impl Namespace {
+    fn from_kind(kind: &meta::Kind) -> Self {
+        match kind {
+            meta::Kind::Macro => Namespace::Macro,
+            meta::Kind::AttributeMacro => Namespace::AttributeMacro,
+            _ => Namespace::Item,
+        }
+    }
+}
+
+/// An item declared by a module, and all the hashes it occupies.
+struct ModuleEntry {
+    item: ItemBuf,
+    namespace: Namespace,
+    hashes: Vec<Hash>,
+    /// The type hash of the type the item is an associated function of.
+    container: Option<Hash>,
+}
+
+/// An item which has been installed into the context from a module.
+struct ContextEntry {
+    /// Index of the module in [Context::modules].
+    module: usize,
+    /// The namespace of the item.
+    namespace: Namespace,
+    /// Hashes occupied by the item.
+    hashes: Box<[Hash]>,
+    /// The type hash of the type the item is an associated function of.
+    container: Option<Hash>,
+}
+
 impl fmt::Display for ContextType {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(fmt, "{} => {}", self.item, self.type_info)?;
@@ -91,6 +260,8 @@ pub struct Context {
     item_to_hash: HashMap<ItemBuf, BTreeSet<Hash>>,
     /// Registered native function handlers.
     functions: hash::Map<Arc<FunctionHandler>>,
+    /// Argument names of native functions, used to describe argument errors.
+    argument_names: hash::Map<&'static [&'static str]>,
     /// Information on associated types.
     #[cfg(feature = "doc")]
     associated: HashMap<Hash, Vec<Hash>>,
@@ -98,6 +269,8 @@ pub struct Context {
     macros: HashMap<Hash, Arc<MacroHandler>>,
     /// Registered native attribute macro handlers.
     attribute_macros: HashMap<Hash, Arc<AttributeMacroHandler>>,
+    /// Registered handlers for suffixed literals, keyed by their suffix.
+    literal_suffixes: HashMap<Box<str>, Arc<MacroHandler>>,
     /// Registered types.
     types: HashMap<Hash, ContextType>,
     /// Registered internal enums.
@@ -108,6 +281,25 @@ pub struct Context {
     crates: HashSet<Box<str>>,
     /// Constants visible in this context
     constants: hash::Map<ConstValue>,
+    /// Registered aliases, in the order they were registered.
+    aliases: Vec<ContextAlias>,
+    /// Item of an alias to its index in [Context::aliases].
+    item_to_alias: HashMap<ItemBuf, usize>,
+    /// Modules which have been installed, in the order they were installed.
+    modules: Vec<ModuleInfo>,
+    /// Items which have been installed from modules.
+    entries: Vec<ContextEntry>,
+    /// Hash to the index of the entry which occupies it.
+    owners: HashMap<(Namespace, Hash), usize>,
+    /// Hook called when a virtual machine panics.
+    panic_hook: Option<Arc<dyn PanicHook>>,
+    /// Catalog used to localize runtime messages.
+    message_catalog: Option<Arc<dyn MessageCatalog>>,
+    /// Registered pretty printers, keyed by the type hash of the type they
+    /// format.
+    pretty_printers: hash::Map<Arc<PrettyPrinterHandler>>,
+    /// How virtual machines handle values borrowed by other executions.
+    borrow_mode: BorrowMode,
 }
 
 impl Context {
@@ -142,6 +334,8 @@ impl Context {
         this.install(crate::modules::hash::module()?)?;
         this.install(crate::modules::cmp::module()?)?;
         this.install(crate::modules::collections::module()?)?;
+        #[cfg(feature = "std")]
+        this.install(crate::modules::error::module()?)?;
         this.install(crate::modules::f64::module()?)?;
         this.install(crate::modules::tuple::module()?)?;
         this.install(crate::modules::fmt::module()?)?;
@@ -151,14 +345,19 @@ impl Context {
         this.install(crate::modules::io::module(stdio)?)?;
         this.install(crate::modules::iter::module()?)?;
         this.install(crate::modules::macros::module()?)?;
+        #[cfg(feature = "std")]
+        this.install(crate::modules::math::module()?)?;
         this.install(crate::modules::mem::module()?)?;
         this.install(crate::modules::object::module()?)?;
         this.install(crate::modules::ops::module()?)?;
         this.install(crate::modules::option::module()?)?;
         this.install(crate::modules::result::module()?)?;
+        this.install(crate::modules::signal::module()?)?;
         this.install(crate::modules::stream::module()?)?;
         this.install(crate::modules::string::module()?)?;
         this.install(crate::modules::test::module()?)?;
+        #[cfg(feature = "uuid")]
+        this.install(crate::modules::uuid::module()?)?;
         this.install(crate::modules::vec::module()?)?;
         this.has_default_modules = true;
         Ok(this)
@@ -187,7 +386,121 @@ impl Context {
     /// # Ok::<_, rune::Error>(())
     /// ```
     pub fn runtime(&self) -> RuntimeContext {
-        RuntimeContext::new(self.functions.clone(), self.constants.clone())
+        RuntimeContext::new(
+            self.functions.clone(),
+            self.argument_names.clone(),
+            self.constants.clone(),
+            self.panic_hook.clone(),
+            self.message_catalog.clone(),
+            self.pretty_printers.clone(),
+            self.types.keys().copied().collect(),
+            self.borrow_mode,
+        )
+    }
+
+    /// Set the hook to call when a virtual machine constructed from this
+    /// context panics.
+    ///
+    /// The hook only applies to runtime contexts constructed through
+    /// [Context::runtime] after it has been set. See [PanicHook] for more
+    /// details.
+    pub fn set_panic_hook<H>(&mut self, hook: H)
+    where
+        H: 'static + PanicHook,
+    {
+        self.panic_hook = Some(Arc::new(hook));
+    }
+
+    /// Set the catalog used to localize the messages of runtime errors, when
+    /// they are displayed through [RuntimeContext::localize].
+    ///
+    /// Like [Context::set_panic_hook], this only applies to runtime contexts
+    /// constructed through [Context::runtime] after it has been set. See
+    /// [MessageCatalog] for more details.
+    pub fn set_message_catalog<C>(&mut self, catalog: C)
+    where
+        C: 'static + MessageCatalog,
+    {
+        self.message_catalog = Some(Arc::new(catalog));
+    }
+
+    /// Set how virtual machines constructed from this context handle calls
+    /// which fail because a value is borrowed by another execution. Defaults
+    /// to [BorrowMode::Error].
+    ///
+    /// Like [Context::set_panic_hook], this only applies to runtime contexts
+    /// constructed through [Context::runtime] after it has been set.
+    pub fn set_borrow_mode(&mut self, mode: BorrowMode) {
+        self.borrow_mode = mode;
+    }
+
+    /// Set the pretty printer to use when formatting values of type `T`.
+    ///
+    /// A pretty printer is consulted before falling back to the
+    /// [`Protocol::STRING_DISPLAY`] and [`Protocol::STRING_DEBUG`] protocols,
+    /// so it applies to everything which formats values, like template
+    /// strings, `dbg`, `print` and `println`. Setting a pretty printer for a
+    /// type replaces any previously set one.
+    ///
+    /// Like [Context::set_panic_hook], this only applies to runtime contexts
+    /// constructed through [Context::runtime] after it has been set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Any, Context, Module, Vm};
+    /// use rune::runtime::Formatter;
+    /// use std::fmt::Write;
+    /// use std::sync::Arc;
+    ///
+    /// #[derive(Any)]
+    /// struct Point {
+    ///     x: i64,
+    ///     y: i64,
+    /// }
+    ///
+    /// let mut module = Module::new();
+    /// module.ty::<Point>()?;
+    /// module.function(["point"], |x: i64, y: i64| Point { x, y })?;
+    ///
+    /// let mut context = Context::with_default_modules()?;
+    /// context.install(module)?;
+    ///
+    /// context.set_pretty_printer(|point: &Point, f: &mut Formatter| {
+    ///     write!(f, "({}, {})", point.x, point.y)
+    /// });
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             `${point(1, 2)}`
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    ///
+    /// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    /// let output: String = rune::from_value(vm.call(["main"], ())?)?;
+    /// assert_eq!(output, "(1, 2)");
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn set_pretty_printer<T, F>(&mut self, printer: F)
+    where
+        T: Any,
+        F: 'static + Fn(&T, &mut Formatter) -> fmt::Result + Send + Sync,
+    {
+        let handler = move |value: &Value, f: &mut Formatter| {
+            let Value::Any(any) = value else {
+                return VmResult::err(VmErrorKind::expected_any(vm_try!(value.type_info())));
+            };
+
+            let value = vm_try!(any.downcast_borrow_ref::<T>());
+            VmResult::Ok(printer(&value, f))
+        };
+
+        self.pretty_printers
+            .insert(T::type_hash(), Arc::new(handler));
     }
 
     /// Install the specified module.
@@ -195,18 +508,142 @@ impl Context {
     /// This installs everything that has been declared in the given [Module]
     /// and ensures that they are compatible with the overall context, like
     /// ensuring that a given type is only declared once.
+    ///
+    /// If any item in the module is already present in the context, this
+    /// fails with [ContextError::ConflictingItems] which lists every
+    /// conflicting item. Use [Context::install_with_policy] to deliberately
+    /// replace or keep existing items instead.
     pub fn install<M>(&mut self, module: M) -> Result<(), ContextError>
+    where
+        M: AsRef<Module>,
+    {
+        self.install_with_policy(module, ConflictPolicy::Error)
+    }
+
+    /// Install the specified module, resolving conflicts with items that are
+    /// already present in the context according to the given
+    /// [ConflictPolicy].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::compile::ConflictPolicy;
+    /// use rune::{Context, ContextError, Module};
+    ///
+    /// let mut a = Module::with_crate("http").with_version("1.0.0");
+    /// a.function(["get"], || 1i64)?;
+    ///
+    /// let mut b = Module::with_crate("http").with_version("2.0.0");
+    /// b.function(["get"], || 2i64)?;
+    ///
+    /// let mut context = Context::new();
+    /// context.install(&a)?;
+    ///
+    /// let Err(ContextError::ConflictingItems { conflicts }) = context.install(&b) else {
+    ///     panic!("expected conflict");
+    /// };
+    ///
+    /// assert_eq!(conflicts.len(), 1);
+    /// assert_eq!(conflicts[0].item().to_string(), "::http::get");
+    /// assert_eq!(conflicts[0].existing().version(), Some("1.0.0"));
+    ///
+    /// context.install_with_policy(&b, ConflictPolicy::LastWins)?;
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn install_with_policy<M>(
+        &mut self,
+        module: M,
+        policy: ConflictPolicy,
+    ) -> Result<(), ContextError>
     where
         M: AsRef<Module>,
     {
         let module = module.as_ref();
 
         if let Some(id) = module.unique {
-            if !self.unique.insert(id) {
+            if self.unique.contains(id) {
                 return Ok(());
             }
         }
 
+        let mut skipped_suffixes = HashSet::new();
+
+        for s in &module.literal_suffixes {
+            if !self.literal_suffixes.contains_key(&s.suffix) {
+                continue;
+            }
+
+            match policy {
+                ConflictPolicy::Error => {
+                    return Err(ContextError::ConflictingLiteralSuffix {
+                        suffix: s.suffix.clone(),
+                    });
+                }
+                ConflictPolicy::FirstWins => {
+                    skipped_suffixes.insert(&*s.suffix);
+                }
+                ConflictPolicy::LastWins => {}
+            }
+        }
+
+        let entries = self.module_entries(module);
+        let conflicts = self.find_conflicts(&entries);
+
+        let mut skipped = HashSet::new();
+        let mut removed = BTreeSet::new();
+
+        if !conflicts.is_empty() {
+            match policy {
+                ConflictPolicy::Error => {
+                    let info = ModuleInfo::new(module);
+
+                    return Err(ContextError::ConflictingItems {
+                        conflicts: conflicts
+                            .into_iter()
+                            .map(|(entry, existing)| {
+                                self.conflict(&info, &entries[entry], existing)
+                            })
+                            .collect(),
+                    });
+                }
+                ConflictPolicy::FirstWins => {
+                    for (entry, _) in conflicts {
+                        let entry = &entries[entry];
+                        skipped.insert((entry.namespace, entry.hashes[0]));
+                    }
+                }
+                ConflictPolicy::LastWins => {
+                    removed.extend(conflicts.into_iter().map(|(_, existing)| existing));
+
+                    // NB: associated functions of types which are replaced
+                    // are removed along with them, since they would otherwise
+                    // linger on the new type.
+                    let types = removed
+                        .iter()
+                        .flat_map(|&index| self.entries[index].hashes.iter())
+                        .filter(|hash| self.types.contains_key(hash))
+                        .copied()
+                        .collect::<HashSet<_>>();
+
+                    for (index, entry) in self.entries.iter().enumerate() {
+                        if entry.container.is_some_and(|hash| types.contains(&hash)) {
+                            removed.insert(index);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.validate_install(module, &entries, &removed)?;
+
+        for index in removed {
+            self.remove_entry(index);
+        }
+
+        if let Some(id) = module.unique {
+            self.unique.insert(id);
+        }
+
         if let Some(ComponentRef::Crate(name)) = module.item.first() {
             self.crates.insert(name.into());
         }
@@ -214,36 +651,112 @@ impl Context {
         self.install_module(module)?;
 
         for ty in &module.types {
-            self.install_type(module, ty)?;
+            if !skipped.contains(&(Namespace::Item, ty.hash)) {
+                self.install_type(module, ty)?;
+            }
         }
 
+        let is_skipped = |namespace, item: &ItemBuf| {
+            !skipped.is_empty()
+                && skipped.contains(&(namespace, Hash::type_hash(module.item.join(item))))
+        };
+
         for f in &module.functions {
-            self.install_function(module, f)?;
+            if !is_skipped(Namespace::Item, &f.item) {
+                self.install_function(module, f)?;
+            }
         }
 
         for m in &module.macros {
-            self.install_macro(module, m)?;
+            if !is_skipped(Namespace::Macro, &m.item) {
+                self.install_macro(module, m)?;
+            }
         }
 
         for m in &module.attribute_macros {
-            self.install_attribute_macro(module, m)?;
+            if !is_skipped(Namespace::AttributeMacro, &m.item) {
+                self.install_attribute_macro(module, m)?;
+            }
+        }
+
+        for s in &module.literal_suffixes {
+            if !skipped_suffixes.contains(&*s.suffix) {
+                self.literal_suffixes
+                    .insert(s.suffix.clone(), s.handler.clone());
+            }
         }
 
         for m in &module.constants {
-            self.install_constant(module, m)?;
+            if !is_skipped(Namespace::Item, &m.item) {
+                self.install_constant(module, m)?;
+            }
         }
 
         for internal_enum in &module.internal_enums {
-            self.install_internal_enum(module, internal_enum)?;
+            if !skipped.contains(&(Namespace::Item, internal_enum.static_type.hash)) {
+                self.install_internal_enum(module, internal_enum)?;
+            }
         }
 
         for assoc in &module.associated {
-            self.install_associated(assoc)?;
+            if !skipped.contains(&(Namespace::Item, associated_hash(assoc))) {
+                self.install_associated(assoc)?;
+            }
+        }
+
+        for alias in &module.aliases {
+            self.install_alias(module, alias)?;
+        }
+
+        let index = self.modules.len();
+        self.modules.push(ModuleInfo::new(module));
+
+        for entry in entries {
+            if skipped.contains(&(entry.namespace, entry.hashes[0])) {
+                continue;
+            }
+
+            let owner = self.entries.len();
+
+            for hash in &entry.hashes {
+                self.owners.insert((entry.namespace, *hash), owner);
+            }
+
+            self.entries.push(ContextEntry {
+                module: index,
+                namespace: entry.namespace,
+                hashes: entry.hashes.into(),
+                container: entry.container,
+            });
         }
 
         Ok(())
     }
 
+    /// Report all items in the given [Module] which conflict with items that
+    /// are already present in the context, without installing it.
+    pub fn conflicts(&self, module: &Module) -> Vec<Conflict> {
+        let entries = self.module_entries(module);
+        let info = ModuleInfo::new(module);
+
+        self.find_conflicts(&entries)
+            .into_iter()
+            .map(|(entry, existing)| self.conflict(&info, &entries[entry], existing))
+            .collect()
+    }
+
+    /// Iterate over all modules which have been installed into the context,
+    /// in the order in which they were installed.
+    pub fn modules(&self) -> impl Iterator<Item = &ModuleInfo> {
+        self.modules.iter()
+    }
+
+    /// Iterate over all aliases which have been installed into the context,
+    /// in the order in which they were installed.
+    pub fn aliases(&self) -> impl Iterator<Item = &ContextAlias> {
+        self.aliases.iter()
+    }
+
     /// Iterate over all available functions in the [Context].
     #[cfg(any(feature = "cli", feature = "languageserver"))]
     pub(crate) fn iter_functions(&self) -> impl Iterator<Item = (&ContextMeta, &meta::Signature)> {
@@ -253,6 +766,12 @@ impl Context {
         })
     }
 
+    /// Iterate over all metadata in the [Context], in the order in which it was
+    /// registered.
+    pub(crate) fn iter_meta(&self) -> impl Iterator<Item = &ContextMeta> {
+        self.meta.iter()
+    }
+
     /// Iterate over all available types in the [Context].
     #[cfg(feature = "cli")]
     pub(crate) fn iter_types(&self) -> impl Iterator<Item = (Hash, &Item)> {
@@ -275,7 +794,12 @@ impl Context {
         I: IntoIterator,
         I::Item: IntoComponent,
     {
-        self.names.iter_components(iter)
+        let item = ItemBuf::with_item(iter);
+
+        match self.resolve_alias(&item) {
+            Some(target) => self.names.iter_components(target),
+            None => self.names.iter_components(item),
+        }
     }
 
     /// Access the context meta for the given item.
@@ -286,7 +810,10 @@ impl Context {
         &self,
         item: &Item,
     ) -> Option<impl Iterator<Item = &ContextMeta> + Clone> {
-        let hashes = self.item_to_hash.get(item)?;
+        let hashes = match self.item_to_hash.get(item) {
+            Some(hashes) => hashes,
+            None => self.item_to_hash.get(&self.resolve_alias(item)?)?,
+        };
 
         Some(hashes.iter().flat_map(|hash| {
             let indexes = self
@@ -315,7 +842,36 @@ impl Context {
 
     /// Check if unit contains the given name by prefix.
     pub(crate) fn contains_prefix(&self, item: &Item) -> bool {
-        self.names.contains_prefix(item)
+        if self.names.contains_prefix(item) {
+            return true;
+        }
+
+        match self.resolve_alias(item) {
+            Some(target) => self.names.contains_prefix(&target),
+            None => false,
+        }
+    }
+
+    /// Resolve the longest prefix of the given item which is an alias,
+    /// returning the item it refers to.
+    fn resolve_alias(&self, item: &Item) -> Option<ItemBuf> {
+        if self.aliases.is_empty() {
+            return None;
+        }
+
+        let mut prefix = ItemBuf::new();
+        let mut found = None;
+
+        for (n, c) in item.iter().enumerate() {
+            prefix.push(c);
+
+            if let Some(&index) = self.item_to_alias.get(&prefix) {
+                found = Some((index, n + 1));
+            }
+        }
+
+        let (index, n) = found?;
+        Some(self.aliases[index].target.join(item.iter().skip(n)))
     }
 
     /// Lookup the given native function handler in the context.
@@ -344,6 +900,11 @@ impl Context {
         self.attribute_macros.get(&hash)
     }
 
+    /// Lookup the handler for literals with the given suffix.
+    pub(crate) fn lookup_literal_suffix(&self, suffix: &str) -> Option<&Arc<MacroHandler>> {
+        self.literal_suffixes.get(suffix)
+    }
+
     /// Look up the type check implementation for the specified type hash.
     pub(crate) fn type_check_for(&self, hash: Hash) -> Option<TypeCheck> {
         let ty = self.types.get(&hash)?;
@@ -441,7 +1002,6 @@ impl Context {
                                 is_async: false,
                                 #[cfg(feature = "doc")]
                                 deprecated: None,
-                                #[cfg(feature = "doc")]
                                 args: Some(match fields {
                                     Fields::Named(names) => names.len(),
                                     Fields::Unnamed(args) => *args,
@@ -476,6 +1036,7 @@ impl Context {
                         },
                         constructor,
                         parameters,
+                        derives: Derives::default(),
                     }
                 }
                 TypeSpecification::Enum(en) => {
@@ -495,6 +1056,8 @@ impl Context {
                                 enum_hash: ty.hash,
                                 hash,
                                 item: item.clone(),
+                                discriminant: None,
+                                derives: Derives::default(),
                             })),
                             type_parameters: Hash::EMPTY,
                         })?;
@@ -505,7 +1068,6 @@ impl Context {
                                 is_async: false,
                                 #[cfg(feature = "doc")]
                                 deprecated: None,
-                                #[cfg(feature = "doc")]
                                 args: Some(match fields {
                                     Fields::Named(names) => names.len(),
                                     Fields::Unnamed(args) => *args,
@@ -529,6 +1091,8 @@ impl Context {
                             kind: meta::Kind::Variant {
                                 enum_hash: ty.hash,
                                 index,
+                                discriminant: None,
+                                derives: Derives::default(),
                                 fields: match fields {
                                     Fields::Named(names) => {
                                         meta::Fields::Named(meta::FieldsNamed {
@@ -622,7 +1186,6 @@ impl Context {
             is_async: f.is_async,
             #[cfg(feature = "doc")]
             deprecated: f.deprecated.clone(),
-            #[cfg(feature = "doc")]
             args: f.args,
             #[cfg(feature = "doc")]
             return_type: f.return_type.as_ref().map(|f| f.hash),
@@ -635,12 +1198,14 @@ impl Context {
         };
 
         self.insert_native_fn(hash, &f.handler)?;
+        self.insert_argument_names(hash, f.argument_names);
 
         self.install_meta(ContextMeta {
             hash,
             item: Some(item),
             kind: meta::Kind::Function {
                 is_test: false,
+                test_params: meta::TestParams::default(),
                 is_bench: false,
                 signature,
                 parameters: Hash::EMPTY,
@@ -711,6 +1276,29 @@ impl Context {
         Ok(())
     }
 
+    /// Install an alias, resolving its target.
+    fn install_alias(&mut self, module: &Module, m: &ModuleAlias) -> Result<(), ContextError> {
+        let item = module.item.join(&m.item);
+
+        if self.item_to_hash.contains_key(&item) || self.item_to_alias.contains_key(&item) {
+            return Err(ContextError::ConflictingAlias { item });
+        }
+
+        let target = match self.resolve_alias(&m.target) {
+            Some(target) => target,
+            None => m.target.clone(),
+        };
+
+        if !self.item_to_hash.contains_key(&target) {
+            return Err(ContextError::MissingAliasTarget { item, target });
+        }
+
+        self.names.insert(&item);
+        self.item_to_alias.insert(item.clone(), self.aliases.len());
+        self.aliases.push(ContextAlias { item, target });
+        Ok(())
+    }
+
     fn install_associated(&mut self, assoc: &ModuleAssociated) -> Result<(), ContextError> {
         let Some(info) = self.types.get(&assoc.container.hash).cloned() else {
             return Err(ContextError::MissingContainer {
@@ -718,20 +1306,13 @@ impl Context {
             });
         };
 
-        // NB: `assoc.container.hash` already contains the type hash, so it
-        // should not be mixed in again.
-        let hash = assoc
-            .name
-            .kind
-            .hash(assoc.container.hash)
-            .with_function_parameters(assoc.name.function_parameters);
+        let hash = associated_hash(assoc);
 
         let signature = meta::Signature {
             #[cfg(feature = "doc")]
             is_async: assoc.is_async,
             #[cfg(feature = "doc")]
             deprecated: assoc.deprecated.clone(),
-            #[cfg(feature = "doc")]
             args: assoc.args,
             #[cfg(feature = "doc")]
             return_type: assoc.return_type.as_ref().map(|f| f.hash),
@@ -744,6 +1325,7 @@ impl Context {
         };
 
         self.insert_native_fn(hash, &assoc.handler)?;
+        self.insert_argument_names(hash, assoc.argument_names);
 
         // If the associated function is a named instance function - register it
         // under the name of the item it corresponds to unless it's a field
@@ -764,6 +1346,7 @@ impl Context {
             );
 
             self.insert_native_fn(hash, &assoc.handler)?;
+            self.insert_argument_names(hash, assoc.argument_names);
             Some(item)
         } else {
             None
@@ -848,7 +1431,6 @@ impl Context {
                     is_async: false,
                     #[cfg(feature = "doc")]
                     deprecated: None,
-                    #[cfg(feature = "doc")]
                     args: Some(match fields {
                         Fields::Named(names) => names.len(),
                         Fields::Unnamed(args) => *args,
@@ -869,6 +1451,8 @@ impl Context {
                 kind: meta::Kind::Variant {
                     enum_hash,
                     index,
+                    discriminant: None,
+                    derives: Derives::default(),
                     fields: match fields {
                         Fields::Named(fields) => meta::Fields::Named(meta::FieldsNamed {
                             fields: fields
@@ -906,12 +1490,331 @@ impl Context {
         Ok(())
     }
 
+    fn insert_argument_names(&mut self, hash: Hash, names: &'static [&'static str]) {
+        if !names.is_empty() {
+            self.argument_names.insert(hash, names);
+        }
+    }
+
+    /// Collect all items declared by the given module, along with the hashes
+    /// they will occupy once installed.
+    fn module_entries(&self, module: &Module) -> Vec<ModuleEntry> {
+        let mut entries = Vec::new();
+
+        for ty in &module.types {
+            let item = module.item.join(&ty.item);
+            let mut hashes = vec![ty.hash];
+
+            match &ty.spec {
+                Some(TypeSpecification::Struct(..)) if ty.constructor.is_some() => {
+                    let hash = Hash::type_hash(&item);
+
+                    if hash != ty.hash {
+                        hashes.push(hash);
+                    }
+                }
+                Some(TypeSpecification::Enum(en)) => {
+                    for variant in &en.variants {
+                        if variant.fields.is_some() {
+                            hashes.push(Hash::type_hash(&item.extended(variant.name)));
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            entries.push(ModuleEntry {
+                item,
+                namespace: Namespace::Item,
+                hashes,
+                container: None,
+            });
+        }
+
+        let items = module
+            .functions
+            .iter()
+            .map(|f| (&f.item, Namespace::Item))
+            .chain(module.macros.iter().map(|m| (&m.item, Namespace::Macro)))
+            .chain(
+                module
+                    .attribute_macros
+                    .iter()
+                    .map(|m| (&m.item, Namespace::AttributeMacro)),
+            )
+            .chain(module.constants.iter().map(|m| (&m.item, Namespace::Item)));
+
+        for (item, namespace) in items {
+            let item = module.item.join(item);
+            let hash = Hash::type_hash(&item);
+
+            entries.push(ModuleEntry {
+                item,
+                namespace,
+                hashes: vec![hash],
+                container: None,
+            });
+        }
+
+        for internal_enum in &module.internal_enums {
+            let item = module.item.join(&internal_enum.base_type);
+            let mut hashes = vec![internal_enum.static_type.hash];
+
+            for variant in &internal_enum.variants {
+                if variant.fields.is_some() {
+                    hashes.push(Hash::type_hash(&item.extended(variant.name)));
+                }
+            }
+
+            entries.push(ModuleEntry {
+                item,
+                namespace: Namespace::Item,
+                hashes,
+                container: None,
+            });
+        }
+
+        for assoc in &module.associated {
+            let container = match self.types.get(&assoc.container.hash) {
+                Some(ty) => Some((ty.item.clone(), ty.type_parameters)),
+                None => module.types_hash.get(&assoc.container.hash).map(|&index| {
+                    let ty = &module.types[index];
+                    (module.item.join(&ty.item), ty.type_parameters)
+                }),
+            };
+
+            // Missing containers are reported when the function is installed.
+            let Some((container, type_parameters)) = container else {
+                continue;
+            };
+
+            let mut hashes = vec![associated_hash(assoc)];
+
+            let item = if let meta::AssociatedKind::Instance(name) = &assoc.name.kind {
+                let item = container.extended(name.as_ref());
+
+                hashes.push(
+                    Hash::type_hash(&item)
+                        .with_type_parameters(type_parameters)
+                        .with_function_parameters(assoc.name.function_parameters),
+                );
+
+                item
+            } else {
+                container.extended(assoc.name.kind.to_string())
+            };
+
+            entries.push(ModuleEntry {
+                item,
+                namespace: Namespace::Item,
+                hashes,
+                container: Some(assoc.container.hash),
+            });
+        }
+
+        entries
+    }
+
+    /// Find conflicting entries, returning the index of the conflicting
+    /// module entry and the index of the existing context entry.
+    fn find_conflicts(&self, entries: &[ModuleEntry]) -> Vec<(usize, usize)> {
+        let mut conflicts = Vec::new();
+
+        for (index, entry) in entries.iter().enumerate() {
+            let existing = entry
+                .hashes
+                .iter()
+                .find_map(|hash| self.owners.get(&(entry.namespace, *hash)).copied());
+
+            if let Some(existing) = existing {
+                conflicts.push((index, existing));
+            }
+        }
+
+        conflicts
+    }
+
+    fn conflict(&self, module: &ModuleInfo, entry: &ModuleEntry, existing: usize) -> Conflict {
+        let existing = &self.entries[existing];
+
+        Conflict {
+            item: entry.item.clone(),
+            hash: entry.hashes[0],
+            module: module.clone(),
+            existing: self.modules[existing.module].clone(),
+        }
+    }
+
+    /// Check that the given module can be installed once the entries at the
+    /// `removed` indexes have been removed, so that a module which can't be
+    /// installed leaves the context untouched.
+    fn validate_install(
+        &self,
+        module: &Module,
+        entries: &[ModuleEntry],
+        removed: &BTreeSet<usize>,
+    ) -> Result<(), ContextError> {
+        let removed = removed
+            .iter()
+            .flat_map(|&index| self.entries[index].hashes.iter())
+            .copied()
+            .collect::<HashSet<_>>();
+
+        for ty in &module.types {
+            let item = module.item.join(&ty.item);
+            let item_hash = Hash::type_hash(&item).with_type_parameters(ty.type_parameters);
+
+            if ty.hash != item_hash {
+                return Err(ContextError::TypeHashMismatch {
+                    type_info: ty.type_info.clone(),
+                    item,
+                    hash: ty.hash,
+                    item_hash,
+                });
+            }
+        }
+
+        for internal_enum in &module.internal_enums {
+            let hash = internal_enum.static_type.hash;
+
+            if self.internal_enums.contains(&internal_enum.static_type) && !removed.contains(&hash)
+            {
+                return Err(ContextError::InternalAlreadyPresent {
+                    name: internal_enum.name,
+                });
+            }
+        }
+
+        for assoc in &module.associated {
+            let hash = assoc.container.hash;
+
+            let exists = (self.types.contains_key(&hash) && !removed.contains(&hash))
+                || module.types_hash.contains_key(&hash);
+
+            if !exists {
+                return Err(ContextError::MissingContainer {
+                    container: assoc.container_type_info.clone(),
+                });
+            }
+        }
+
+        // Items which will be present once the module is installed.
+        let mut items = entries
+            .iter()
+            .map(|entry| entry.item.clone())
+            .collect::<HashSet<_>>();
+
+        let mut current = Some(module.item.as_ref());
+
+        while let Some(item) = current {
+            items.insert(item.to_owned());
+            current = item.parent();
+        }
+
+        let exists = |item: &Item| {
+            items.contains(item)
+                || self
+                    .item_to_hash
+                    .get(item)
+                    .is_some_and(|hashes| hashes.iter().any(|hash| !removed.contains(hash)))
+        };
+
+        let mut aliases = HashSet::new();
+
+        for m in &module.aliases {
+            let item = module.item.join(&m.item);
+
+            if exists(&item) || self.item_to_alias.contains_key(&item) || aliases.contains(&item) {
+                return Err(ContextError::ConflictingAlias { item });
+            }
+
+            let target = match self.resolve_alias(&m.target) {
+                Some(target) => target,
+                None => m.target.clone(),
+            };
+
+            if !exists(&target) && !aliases.contains(&target) {
+                return Err(ContextError::MissingAliasTarget { item, target });
+            }
+
+            aliases.insert(item);
+        }
+
+        Ok(())
+    }
+
+    /// Remove everything associated with the given entry from the context.
+    fn remove_entry(&mut self, index: usize) {
+        let namespace = self.entries[index].namespace;
+        let hashes = core::mem::take(&mut self.entries[index].hashes);
+
+        for hash in hashes.iter() {
+            self.owners.remove(&(namespace, *hash));
+
+            match namespace {
+                Namespace::Item => {
+                    self.functions.remove(hash);
+                    self.argument_names.remove(hash);
+                    self.constants.remove(hash);
+                    self.types.remove(hash);
+                    self.internal_enums.retain(|ty| ty.hash != *hash);
+                }
+                Namespace::Macro => {
+                    self.macros.remove(hash);
+                }
+                Namespace::AttributeMacro => {
+                    self.attribute_macros.remove(hash);
+                }
+            }
+        }
+
+        // Modules might share a hash with an item of the same name, so they
+        // are left alone.
+        self.meta.retain(|meta| {
+            matches!(meta.kind, meta::Kind::Module)
+                || Namespace::from_kind(&meta.kind) != namespace
+                || !hashes.contains(&meta.hash)
+        });
+
+        self.hash_to_meta.clear();
+        self.item_to_hash.clear();
+
+        for (index, meta) in self.meta.iter().enumerate() {
+            self.hash_to_meta.entry(meta.hash).or_default().push(index);
+
+            if let Some(item) = &meta.item {
+                self.item_to_hash
+                    .entry(item.clone())
+                    .or_default()
+                    .insert(meta.hash);
+            }
+        }
+
+        #[cfg(feature = "doc")]
+        if namespace == Namespace::Item {
+            for associated in self.associated.values_mut() {
+                associated.retain(|hash| !hashes.contains(hash));
+            }
+        }
+    }
+
     /// Get a constant value.
     pub(crate) fn get_const_value(&self, hash: Hash) -> Option<&ConstValue> {
         self.constants.get(&hash)
     }
 }
 
+/// Calculate the hash an associated function is installed under.
+fn associated_hash(assoc: &ModuleAssociated) -> Hash {
+    // NB: `assoc.container.hash` already contains the type hash, so it
+    // should not be mixed in again.
+    assoc
+        .name
+        .kind
+        .hash(assoc.container.hash)
+        .with_function_parameters(assoc.name.function_parameters)
+}
+
 impl fmt::Debug for Context {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Context")
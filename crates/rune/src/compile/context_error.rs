@@ -2,7 +2,7 @@ use core::fmt;
 
 use crate::no_std::prelude::*;
 
-use crate::compile::ItemBuf;
+use crate::compile::{Conflict, ItemBuf};
 use crate::runtime::{TypeInfo, VmError};
 use crate::Hash;
 
@@ -30,6 +30,15 @@ pub enum ContextError {
         item: ItemBuf,
         hash: Hash,
     },
+    ConflictingLiteralSuffix {
+        suffix: Box<str>,
+    },
+    InvalidLiteralSuffix {
+        suffix: Box<str>,
+    },
+    ConflictingAlias {
+        item: ItemBuf,
+    },
     ConflictingInstanceFunction {
         type_info: TypeInfo,
         name: Box<str>,
@@ -74,6 +83,9 @@ pub enum ContextError {
         hash: Hash,
         existing: Hash,
     },
+    ConflictingItems {
+        conflicts: Vec<Conflict>,
+    },
     ConflictingVariant {
         item: ItemBuf,
     },
@@ -102,6 +114,10 @@ pub enum ContextError {
         index: usize,
         type_info: TypeInfo,
     },
+    MissingAliasTarget {
+        item: ItemBuf,
+        target: ItemBuf,
+    },
     ExpectedAssociated,
     TypeHashMismatch {
         type_info: TypeInfo,
@@ -132,6 +148,21 @@ impl fmt::Display for ContextError {
             ContextError::ConflictingConstantName { item, hash } => {
                 write!(f, "Constant `{item}` already exists with hash `{hash}`")?;
             }
+            ContextError::ConflictingLiteralSuffix { suffix } => {
+                write!(f, "Literal suffix `{suffix}` already exists")?;
+            }
+            ContextError::InvalidLiteralSuffix { suffix } => {
+                write!(
+                    f,
+                    "Literal suffix `{suffix}` must be an identifier which isn't `u8`, `i64` or `f64`"
+                )?;
+            }
+            ContextError::ConflictingAlias { item } => {
+                write!(
+                    f,
+                    "Alias `{item}` conflicts with an item which already exists"
+                )?;
+            }
             ContextError::ConflictingInstanceFunction { type_info, name } => {
                 write!(
                     f,
@@ -196,6 +227,13 @@ impl fmt::Display for ContextError {
                     "Tried to insert conflicting hash `{hash}` for `{existing}`"
                 )?;
             }
+            ContextError::ConflictingItems { conflicts } => {
+                write!(f, "Module contains items which are already present:")?;
+
+                for conflict in conflicts {
+                    write!(f, "\n- {conflict}")?;
+                }
+            }
             ContextError::ConflictingVariant { item } => {
                 write!(f, "Variant with `{item}` already exists")?;
             }
@@ -229,6 +267,12 @@ impl fmt::Display for ContextError {
             ContextError::MissingVariant { index, type_info } => {
                 write!(f, "Missing variant {index} for `{type_info}`")?;
             }
+            ContextError::MissingAliasTarget { item, target } => {
+                write!(
+                    f,
+                    "Alias `{item}` refers to `{target}` which isn't registered"
+                )?;
+            }
             ContextError::ExpectedAssociated {} => {
                 write!(f, "Expected associated function")?;
             }
@@ -12,6 +12,9 @@ pub(crate) struct Docs {
     /// Names of arguments.
     #[cfg(feature = "doc")]
     arguments: Option<Vec<String>>,
+    /// If the item is hidden from generated documentation.
+    #[cfg(feature = "doc")]
+    hidden: bool,
 }
 
 impl Docs {
@@ -20,6 +23,8 @@ impl Docs {
         docs: Vec::new(),
         #[cfg(feature = "doc")]
         arguments: None,
+        #[cfg(feature = "doc")]
+        hidden: false,
     };
 
     /// Get arguments associated with documentation.
@@ -34,6 +39,21 @@ impl Docs {
         &self.docs
     }
 
+    /// Test if the item is hidden from generated documentation.
+    #[cfg(feature = "doc")]
+    pub(crate) fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    /// Hide the item from generated documentation.
+    #[cfg(feature = "doc")]
+    pub(crate) fn set_hidden(&mut self) {
+        self.hidden = true;
+    }
+
+    #[cfg(not(feature = "doc"))]
+    pub(crate) fn set_hidden(&mut self) {}
+
     /// Update documentation.
     #[cfg(feature = "doc")]
     pub(crate) fn set_docs<S>(&mut self, docs: S)
@@ -9,6 +9,7 @@ use crate::ast::unescape;
 use crate::ast::{Span, Spanned};
 use crate::compile::ir;
 use crate::compile::{HasSpan, ItemBuf, Location, MetaInfo, Visibility};
+use crate::diagnostics::ErrorCode;
 use crate::indexing::items::{GuardMismatch, MissingLastId};
 use crate::macros::{SyntheticId, SyntheticKind};
 use crate::parse::{Expectation, IntoExpectation, LexerMode};
@@ -23,6 +24,7 @@ use crate::{Hash, SourceId};
 pub struct Error {
     span: Span,
     kind: Box<ErrorKind>,
+    suggestions: Vec<Suggestion>,
 }
 
 impl Error {
@@ -35,6 +37,7 @@ impl Error {
         Self {
             span: span.span(),
             kind: Box::new(ErrorKind::from(kind)),
+            suggestions: Vec::new(),
         }
     }
 
@@ -49,9 +52,99 @@ impl Error {
             kind: Box::new(ErrorKind::Custom {
                 message: message.to_string().into(),
             }),
+            suggestions: Vec::new(),
         }
     }
 
+    /// Attach suggestions for how the error can be fixed.
+    pub(crate) fn with_suggestions(mut self, suggestions: Vec<Suggestion>) -> Self {
+        self.suggestions.extend(suggestions);
+        self
+    }
+
+    /// Attach the trace of constant function calls which were being evaluated
+    /// when the error was raised.
+    ///
+    /// Errors which already have a trace are left as-is, since that trace is
+    /// the most detailed one.
+    pub(crate) fn with_const_trace(self, trace: &[ir::EvalFrame]) -> Self {
+        if trace.is_empty() || matches!(*self.kind, ErrorKind::ConstEvaluation { .. }) {
+            return self;
+        }
+
+        Self {
+            span: self.span,
+            kind: Box::new(ErrorKind::ConstEvaluation {
+                error: self.kind,
+                trace: trace.into(),
+            }),
+            suggestions: self.suggestions,
+        }
+    }
+
+    /// Report an error raised in the given source, which was expanded from a
+    /// macro, at the span of the macro invocation instead.
+    pub(crate) fn in_macro_expansion(self, source_id: SourceId, span: Span) -> Self {
+        Self {
+            span,
+            kind: Box::new(ErrorKind::MacroExpansion {
+                source_id,
+                span: self.span,
+                error: self.kind,
+            }),
+            // NB: suggestions refer to the expanded source, which they can't
+            // be reported in.
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Get the stable code identifying the kind of the error.
+    ///
+    /// An extended explanation of the error can be looked up through
+    /// [ErrorCode::explanation].
+    pub fn code(&self) -> ErrorCode {
+        self.kind.code()
+    }
+
+    /// Get suggestions for how the error can be fixed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Diagnostics, Source, Sources};
+    /// use rune::diagnostics::{Diagnostic, FatalDiagnosticKind};
+    ///
+    /// let context = Context::with_default_modules()?;
+    ///
+    /// let mut sources = Sources::new();
+    /// sources.insert(Source::memory("pub fn main() { HashMap::new() }"));
+    ///
+    /// let mut diagnostics = Diagnostics::new();
+    ///
+    /// let result = rune::prepare(&mut sources)
+    ///     .with_context(&context)
+    ///     .with_diagnostics(&mut diagnostics)
+    ///     .build();
+    ///
+    /// assert!(result.is_err());
+    ///
+    /// let Some(Diagnostic::Fatal(fatal)) = diagnostics.diagnostics().first() else {
+    ///     panic!("expected a fatal diagnostic");
+    /// };
+    ///
+    /// let FatalDiagnosticKind::CompileError(error) = fatal.kind() else {
+    ///     panic!("expected a compile error");
+    /// };
+    ///
+    /// let suggestion = &error.suggestions()[0];
+    /// assert_eq!(suggestion.replacement(), "std::collections::HashMap");
+    /// assert_eq!(suggestion.span().range(), 16..23);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+
     /// Get the kind of the error.
     #[cfg(feature = "emit")]
     pub(crate) fn kind(&self) -> &ErrorKind {
@@ -80,7 +173,21 @@ impl crate::no_std::error::Error for Error {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-        ::core::fmt::Display::fmt(&self.kind, f)
+        ::core::fmt::Display::fmt(&self.kind, f)?;
+
+        match self.suggestions.as_slice() {
+            [] => {}
+            [suggestion] => write!(f, ", {suggestion}")?,
+            [first, rest @ ..] => {
+                write!(f, ", consider importing one of `{}`", first.replacement)?;
+
+                for suggestion in rest {
+                    write!(f, ", `{}`", suggestion.replacement)?;
+                }
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -92,6 +199,7 @@ where
         Error {
             span: spanned.span,
             kind: Box::new(ErrorKind::from(spanned.error)),
+            suggestions: Vec::new(),
         }
     }
 }
@@ -194,6 +302,19 @@ pub(crate) enum ErrorKind {
         what: Expectation,
     },
     IrError(IrErrorKind),
+    /// An error raised while evaluating a constant function call, along with
+    /// the trace of calls that led up to it.
+    ConstEvaluation {
+        error: Box<ErrorKind>,
+        trace: Box<[ir::EvalFrame]>,
+    },
+    /// An error raised in a source which was expanded from a macro, reported
+    /// at the macro invocation.
+    MacroExpansion {
+        source_id: SourceId,
+        span: Span,
+        error: Box<ErrorKind>,
+    },
     MetaConflict(MetaConflict),
     AccessError(AccessError),
     EncodeError(EncodeError),
@@ -274,12 +395,21 @@ pub(crate) enum ErrorKind {
         #[cfg(feature = "emit")]
         object: Span,
     },
+    DuplicateDiscriminant {
+        #[cfg(feature = "emit")]
+        existing: Span,
+        discriminant: i64,
+    },
+    UnsupportedDiscriminant,
+    DiscriminantOverflow,
     InstanceFunctionOutsideImpl,
     UnsupportedTupleIndex {
         number: ast::Number,
     },
     BreakOutsideOfLoop,
     ContinueOutsideOfLoop,
+    ControlFlowInDefer,
+    LetElseMustDiverge,
     SelectMultipleDefaults,
     ExpectedBlockSemiColon {
         #[cfg(feature = "emit")]
@@ -473,13 +603,173 @@ pub(crate) enum ErrorKind {
     },
     UnsupportedPatternRest,
     UnsupportedMut,
-    UnsupportedSuffix,
+    UnsupportedSuffix {
+        suffix: Box<str>,
+    },
+    UnsupportedObjectSpread,
+    MissingExternConst {
+        item: ItemBuf,
+    },
+    BaseItemConflict {
+        item: ItemBuf,
+    },
+    StrictShadowing {
+        name: Box<str>,
+    },
+    StrictMissingAnnotation,
+}
+
+impl ErrorKind {
+    /// Get the stable code of the error.
+    ///
+    /// NB: codes are assigned in declaration order and must never change or
+    /// be reused, so new variants must be assigned new codes.
+    pub(crate) fn code(&self) -> ErrorCode {
+        let code = match self {
+            ErrorKind::IrError(error) => return error.code(),
+            ErrorKind::ConstEvaluation { error, .. } => return error.code(),
+            ErrorKind::MacroExpansion { error, .. } => return error.code(),
+            ErrorKind::Custom { .. } => 1,
+            ErrorKind::Expected { .. } => 2,
+            ErrorKind::Unsupported { .. } => 3,
+            ErrorKind::MetaConflict(..) => 6,
+            ErrorKind::AccessError(..) => 7,
+            ErrorKind::EncodeError(..) => 8,
+            ErrorKind::MissingLastId(..) => 9,
+            ErrorKind::GuardMismatch(..) => 10,
+            ErrorKind::MissingScope(..) => 11,
+            ErrorKind::PopError(..) => 12,
+            ErrorKind::MissingId(..) => 13,
+            ErrorKind::UnescapeError(..) => 14,
+            ErrorKind::FileError { .. } => 15,
+            ErrorKind::ModNotFound { .. } => 16,
+            ErrorKind::ModAlreadyLoaded { .. } => 17,
+            ErrorKind::MissingMacro { .. } => 18,
+            ErrorKind::MissingSelf => 19,
+            ErrorKind::MissingLocal { .. } => 20,
+            ErrorKind::MissingItem { .. } => 21,
+            ErrorKind::MissingItemHash { .. } => 22,
+            ErrorKind::MissingItemParameters { .. } => 23,
+            ErrorKind::UnsupportedGlobal => 24,
+            ErrorKind::UnsupportedModuleSource => 25,
+            ErrorKind::UnsupportedModuleRoot { .. } => 26,
+            ErrorKind::UnsupportedModuleItem { .. } => 27,
+            ErrorKind::UnsupportedSelf => 28,
+            ErrorKind::UnsupportedUnaryOp { .. } => 29,
+            ErrorKind::UnsupportedBinaryOp { .. } => 30,
+            ErrorKind::UnsupportedLitObject { .. } => 31,
+            ErrorKind::LitObjectMissingField { .. } => 32,
+            ErrorKind::LitObjectNotField { .. } => 33,
+            ErrorKind::UnsupportedAssignExpr => 34,
+            ErrorKind::UnsupportedBinaryExpr => 35,
+            ErrorKind::UnsupportedRef => 36,
+            ErrorKind::UnsupportedSelectPattern => 37,
+            ErrorKind::UnsupportedArgumentCount { .. } => 38,
+            ErrorKind::UnsupportedPatternExpr => 39,
+            ErrorKind::UnsupportedBinding => 40,
+            ErrorKind::DuplicateObjectKey { .. } => 41,
+            ErrorKind::DuplicateDiscriminant { .. } => 42,
+            ErrorKind::UnsupportedDiscriminant => 43,
+            ErrorKind::DiscriminantOverflow => 44,
+            ErrorKind::InstanceFunctionOutsideImpl => 45,
+            ErrorKind::UnsupportedTupleIndex { .. } => 46,
+            ErrorKind::BreakOutsideOfLoop => 47,
+            ErrorKind::ContinueOutsideOfLoop => 48,
+            ErrorKind::ControlFlowInDefer => 49,
+            ErrorKind::LetElseMustDiverge => 50,
+            ErrorKind::SelectMultipleDefaults => 51,
+            ErrorKind::ExpectedBlockSemiColon { .. } => 52,
+            ErrorKind::FnConstAsyncConflict => 53,
+            ErrorKind::BlockConstAsyncConflict => 54,
+            ErrorKind::ClosureKind => 55,
+            ErrorKind::UnsupportedSelfType => 56,
+            ErrorKind::UnsupportedSuper => 57,
+            ErrorKind::UnsupportedSuperInSelfType => 58,
+            ErrorKind::UnsupportedAfterGeneric => 59,
+            ErrorKind::IllegalUseSegment => 60,
+            ErrorKind::UseAliasNotSupported => 61,
+            ErrorKind::FunctionConflict { .. } => 62,
+            ErrorKind::FunctionReExportConflict { .. } => 63,
+            ErrorKind::ConstantConflict { .. } => 64,
+            ErrorKind::StaticStringMissing { .. } => 65,
+            ErrorKind::StaticBytesMissing { .. } => 66,
+            ErrorKind::StaticStringHashConflict { .. } => 67,
+            ErrorKind::StaticBytesHashConflict { .. } => 68,
+            ErrorKind::StaticObjectKeysMissing { .. } => 69,
+            ErrorKind::StaticObjectKeysHashConflict { .. } => 70,
+            ErrorKind::MissingLoopLabel { .. } => 71,
+            ErrorKind::ExpectedLeadingPathSegment => 72,
+            ErrorKind::UnsupportedVisibility => 73,
+            ErrorKind::ExpectedMeta { .. } => 74,
+            ErrorKind::NoSuchBuiltInMacro { .. } => 75,
+            ErrorKind::VariableMoved { .. } => 76,
+            ErrorKind::UnsupportedGenerics => 77,
+            ErrorKind::NestedTest { .. } => 78,
+            ErrorKind::NestedBench { .. } => 79,
+            ErrorKind::MissingFunctionHash { .. } => 80,
+            ErrorKind::FunctionConflictHash { .. } => 81,
+            ErrorKind::PatternMissingFields { .. } => 82,
+            ErrorKind::MissingLabelLocation { .. } => 83,
+            ErrorKind::MaxMacroRecursion { .. } => 84,
+            ErrorKind::YieldInConst => 85,
+            ErrorKind::AwaitInConst => 86,
+            ErrorKind::AwaitOutsideAsync => 87,
+            ErrorKind::ExpectedEof { .. } => 88,
+            ErrorKind::UnexpectedEof => 89,
+            ErrorKind::BadLexerMode { .. } => 90,
+            ErrorKind::ExpectedEscape => 91,
+            ErrorKind::UnterminatedStrLit => 92,
+            ErrorKind::UnterminatedByteStrLit => 93,
+            ErrorKind::UnterminatedCharLit => 94,
+            ErrorKind::UnterminatedByteLit => 95,
+            ErrorKind::ExpectedCharClose => 96,
+            ErrorKind::ExpectedCharOrLabel => 97,
+            ErrorKind::ExpectedByteClose => 98,
+            ErrorKind::UnexpectedChar { .. } => 99,
+            ErrorKind::PrecedenceGroupRequired => 100,
+            ErrorKind::BadNumberOutOfBounds => 101,
+            ErrorKind::BadFieldAccess => 102,
+            ErrorKind::ExpectedMacroCloseDelimiter { .. } => 103,
+            ErrorKind::MultipleMatchingAttributes { .. } => 104,
+            ErrorKind::MissingSourceId { .. } => 105,
+            ErrorKind::ExpectedMultilineCommentTerm => 106,
+            ErrorKind::BadSlice => 107,
+            ErrorKind::BadSyntheticId { .. } => 108,
+            ErrorKind::BadCharLiteral => 109,
+            ErrorKind::BadByteLiteral => 110,
+            ErrorKind::BadNumberLiteral => 111,
+            ErrorKind::AmbiguousItem { .. } => 112,
+            ErrorKind::AmbiguousContextItem { .. } => 113,
+            ErrorKind::NotVisible { .. } => 114,
+            ErrorKind::NotVisibleMod { .. } => 115,
+            ErrorKind::MissingMod { .. } => 116,
+            ErrorKind::ImportCycle { .. } => 117,
+            ErrorKind::ImportRecursionLimit { .. } => 118,
+            ErrorKind::LastUseComponent => 119,
+            ErrorKind::VariantRttiConflict { .. } => 120,
+            ErrorKind::TypeRttiConflict { .. } => 121,
+            ErrorKind::ArenaWriteSliceOutOfBounds { .. } => 122,
+            ErrorKind::ArenaAllocError { .. } => 123,
+            ErrorKind::UnsupportedPatternRest => 124,
+            ErrorKind::UnsupportedMut => 125,
+            ErrorKind::UnsupportedSuffix { .. } => 126,
+            ErrorKind::UnsupportedObjectSpread => 127,
+            ErrorKind::MissingExternConst { .. } => 140,
+            ErrorKind::BaseItemConflict { .. } => 141,
+            ErrorKind::StrictShadowing { .. } => 142,
+            ErrorKind::StrictMissingAnnotation => 143,
+        };
+
+        ErrorCode::new(code)
+    }
 }
 
 impl crate::no_std::error::Error for ErrorKind {
     fn source(&self) -> Option<&(dyn crate::no_std::error::Error + 'static)> {
         match self {
             ErrorKind::IrError(source) => Some(source),
+            ErrorKind::ConstEvaluation { error, .. } => error.source(),
+            ErrorKind::MacroExpansion { error, .. } => error.source(),
             ErrorKind::MetaConflict(source) => Some(source),
             ErrorKind::AccessError(source) => Some(source),
             ErrorKind::EncodeError(source) => Some(source),
@@ -515,6 +805,12 @@ impl fmt::Display for ErrorKind {
             ErrorKind::IrError(error) => {
                 error.fmt(f)?;
             }
+            ErrorKind::ConstEvaluation { error, .. } => {
+                error.fmt(f)?;
+            }
+            ErrorKind::MacroExpansion { error, .. } => {
+                error.fmt(f)?;
+            }
             ErrorKind::MetaConflict(error) => {
                 error.fmt(f)?;
             }
@@ -643,6 +939,18 @@ impl fmt::Display for ErrorKind {
             ErrorKind::DuplicateObjectKey { .. } => {
                 write!(f, "Duplicate key in literal object")?;
             }
+            ErrorKind::DuplicateDiscriminant { discriminant, .. } => {
+                write!(f, "Duplicate discriminant `{discriminant}` in enum")?;
+            }
+            ErrorKind::UnsupportedDiscriminant => {
+                write!(
+                    f,
+                    "Discriminants must be integer literals and are only supported on unit variants"
+                )?;
+            }
+            ErrorKind::DiscriminantOverflow => {
+                write!(f, "Enum discriminant overflowed")?;
+            }
             ErrorKind::InstanceFunctionOutsideImpl => {
                 write!(f, "Instance function declared outside of `impl` block")?;
             }
@@ -655,6 +963,15 @@ impl fmt::Display for ErrorKind {
             ErrorKind::ContinueOutsideOfLoop => {
                 write!(f, "Continue outside of loop")?;
             }
+            ErrorKind::ControlFlowInDefer => {
+                write!(f, "Control flow can't escape a deferred block")?;
+            }
+            ErrorKind::LetElseMustDiverge => {
+                write!(
+                    f,
+                    "The `else` block of a `let` statement must diverge, like with `return`, `break` or `continue`"
+                )?;
+            }
             ErrorKind::SelectMultipleDefaults => {
                 write!(f, "Multiple `default` branches in select")?;
             }
@@ -977,10 +1294,34 @@ impl fmt::Display for ErrorKind {
                     "The `mut` modifier is not supported in Rune, everything is mutable by default"
                 )?;
             }
-            ErrorKind::UnsupportedSuffix => {
+            ErrorKind::UnsupportedSuffix { suffix } => {
                 write!(
                     f,
-                    "Unsupported suffix, expected one of `u8`, `i64`, or `f64`"
+                    "Unsupported suffix `{suffix}`, expected one of `u8`, `i64`, `f64`, or a literal suffix registered in the context"
+                )?;
+            }
+            ErrorKind::UnsupportedObjectSpread => {
+                write!(f, "Spread `..` is only supported in anonymous objects")?;
+            }
+            ErrorKind::MissingExternConst { item } => {
+                write!(
+                    f,
+                    "Missing value for external constant `{item}`, which must be provided through the compile options"
+                )?;
+            }
+            ErrorKind::BaseItemConflict { item } => {
+                write!(f, "Item `{item}` is already defined in the base unit")?;
+            }
+            ErrorKind::StrictShadowing { name } => {
+                write!(
+                    f,
+                    "Variable `{name}` shadows another variable, which is denied by `#![strict(shadowing)]`"
+                )?;
+            }
+            ErrorKind::StrictMissingAnnotation => {
+                write!(
+                    f,
+                    "Missing type annotation, which is required by `#![strict(annotations)]`"
                 )?;
             }
         }
@@ -1082,6 +1423,11 @@ pub(crate) enum IrErrorKind {
     },
     /// Exceeded evaluation budget.
     BudgetExceeded,
+    /// Constant functions called each other too deeply.
+    RecursionLimitExceeded {
+        /// The recursion limit which was exceeded.
+        limit: usize,
+    },
     /// Missing a tuple index.
     MissingIndex {
         /// The index that was missing.
@@ -1103,6 +1449,36 @@ pub(crate) enum IrErrorKind {
         actual: usize,
         expected: usize,
     },
+    /// Integer arithmetic overflowed.
+    Overflow,
+    /// Integer arithmetic underflowed.
+    Underflow,
+    /// Tried to divide an integer by zero.
+    DivideByZero,
+}
+
+impl IrErrorKind {
+    /// Get the stable code of the error.
+    pub(crate) fn code(&self) -> ErrorCode {
+        let code = match self {
+            IrErrorKind::NotConst => 128,
+            IrErrorKind::ConstCycle => 129,
+            IrErrorKind::UnsupportedMeta { .. } => 130,
+            IrErrorKind::Expected { .. } => 131,
+            IrErrorKind::BudgetExceeded => 132,
+            IrErrorKind::RecursionLimitExceeded { .. } => 133,
+            IrErrorKind::MissingIndex { .. } => 134,
+            IrErrorKind::MissingField { .. } => 135,
+            IrErrorKind::MissingConst { .. } => 136,
+            IrErrorKind::BreakOutsideOfLoop => 137,
+            IrErrorKind::ArgumentCountMismatch { .. } => 138,
+            IrErrorKind::Overflow => 144,
+            IrErrorKind::Underflow => 145,
+            IrErrorKind::DivideByZero => 146,
+        };
+
+        ErrorCode::new(code)
+    }
 }
 
 impl crate::no_std::error::Error for IrErrorKind {}
@@ -1125,6 +1501,12 @@ impl fmt::Display for IrErrorKind {
             IrErrorKind::BudgetExceeded => {
                 write!(f, "Evaluation budget exceeded")?;
             }
+            IrErrorKind::RecursionLimitExceeded { limit } => {
+                write!(
+                    f,
+                    "Constant function recursion limit of {limit} calls exceeded"
+                )?;
+            }
             IrErrorKind::MissingIndex { index } => {
                 write!(f, "Missing index {index}",)?;
             }
@@ -1143,6 +1525,15 @@ impl fmt::Display for IrErrorKind {
                     "Argument count mismatch, got {actual} but expected {expected}",
                 )?;
             }
+            IrErrorKind::Overflow => {
+                write!(f, "Integer overflow in constant evaluation")?;
+            }
+            IrErrorKind::Underflow => {
+                write!(f, "Integer underflow in constant evaluation")?;
+            }
+            IrErrorKind::DivideByZero => {
+                write!(f, "Division by zero in constant evaluation")?;
+            }
         }
 
         Ok(())
@@ -1151,6 +1542,51 @@ impl fmt::Display for IrErrorKind {
 
 /// A single step in an import.
 ///
+/// A machine-applicable suggestion for how to fix an error, which is applied
+/// by replacing the source at [Suggestion::span] with
+/// [Suggestion::replacement].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    span: Span,
+    message: Box<str>,
+    replacement: Box<str>,
+}
+
+impl Suggestion {
+    /// Suggest that the name at the given span could be resolved by importing
+    /// the given path.
+    ///
+    /// This is applied by replacing the name with the full path.
+    pub(crate) fn import(span: Span, path: &str) -> Self {
+        Self {
+            span,
+            message: format!("consider importing `{path}`").into(),
+            replacement: path.into(),
+        }
+    }
+
+    /// The span of the source which is replaced.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// A description of the suggestion.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// What the span is replaced with.
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+}
+
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
 /// This is used to indicate a step in an import chain in an error message.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -0,0 +1,170 @@
+use crate::no_std::prelude::*;
+
+use crate::ast::{self, Span, Spanned};
+use crate::parse::Lexer;
+use crate::SourceId;
+
+/// The kind of a [`FoldingRange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FoldingRangeKind {
+    /// A delimited group, like a block or the fields of a struct.
+    Region,
+    /// A run of comments.
+    Comment,
+    /// A run of `use` declarations.
+    Imports,
+}
+
+/// A range of a source which can be folded, as produced by
+/// [`folding_ranges`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FoldingRange {
+    /// The span of the range, from the start of its first line to the end of
+    /// its last line.
+    pub span: Span,
+    /// The kind of the range.
+    pub kind: FoldingRangeKind,
+}
+
+/// Compute the ranges of a source which can be folded, ordered by where they
+/// start.
+///
+/// Ranges are only produced if they span multiple lines. Since delimiters and
+/// comments are found by lexing, this also produces ranges for sources which
+/// fail to parse, but runs of `use` declarations are only found if the source
+/// parses.
+///
+/// # Examples
+///
+/// ```
+/// use rune::compile::{folding_ranges, FoldingRangeKind};
+///
+/// let code = r#"
+/// use std::iter;
+/// use std::string;
+///
+/// // The entrypoint.
+/// // Does nothing interesting.
+/// pub fn main() {
+///     42
+/// }
+/// "#;
+///
+/// let kinds = folding_ranges(code)
+///     .into_iter()
+///     .map(|range| range.kind)
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     kinds,
+///     [
+///         FoldingRangeKind::Imports,
+///         FoldingRangeKind::Comment,
+///         FoldingRangeKind::Region,
+///     ]
+/// );
+/// ```
+pub fn folding_ranges(source: &str) -> Vec<FoldingRange> {
+    let mut ranges = Ranges {
+        source,
+        ranges: Vec::new(),
+    };
+
+    let mut lexer = Lexer::with_trivia(source, SourceId::empty(), true);
+    let mut open = Vec::<(ast::Delimiter, Span)>::new();
+    let mut comments = None::<Span>;
+
+    while let Ok(Some(token)) = lexer.next() {
+        match token.kind {
+            ast::Kind::Whitespace => {
+                // NB: line comments include their newline, so any newline
+                // in whitespace means that a run of comments has ended.
+                if source[token.span.range()].contains('\n') {
+                    ranges.push(comments.take(), FoldingRangeKind::Comment);
+                }
+
+                continue;
+            }
+            ast::Kind::Comment => {
+                let text = &source[token.span.range()];
+                let start = token.span.start.into_usize();
+                let span = Span::new(start, start + text.trim_end().len());
+
+                comments = Some(match comments {
+                    Some(comments) => comments.join(span),
+                    None => span,
+                });
+
+                continue;
+            }
+            ast::Kind::MultilineComment(..) => {
+                ranges.push(comments.take(), FoldingRangeKind::Comment);
+                ranges.push(Some(token.span), FoldingRangeKind::Comment);
+                continue;
+            }
+            ast::Kind::Open(delimiter) => {
+                open.push((delimiter, token.span));
+            }
+            ast::Kind::Close(delimiter) => {
+                if matches!(open.last(), Some((d, _)) if *d == delimiter) {
+                    let span = open.pop().map(|(_, span)| span.join(token.span));
+                    ranges.push(span, FoldingRangeKind::Region);
+                }
+            }
+            _ => {}
+        }
+
+        ranges.push(comments.take(), FoldingRangeKind::Comment);
+    }
+
+    ranges.push(comments.take(), FoldingRangeKind::Comment);
+
+    if let Ok(file) = crate::parse::parse_all::<ast::File>(source, SourceId::empty(), true) {
+        let mut imports = None::<Span>;
+
+        for (item, semi) in &file.items {
+            let ast::Item::Use(..) = item else {
+                ranges.push(imports.take(), FoldingRangeKind::Imports);
+                continue;
+            };
+
+            let span = match semi {
+                Some(semi) => item.span().join(semi.span()),
+                None => item.span(),
+            };
+
+            imports = Some(match imports {
+                Some(imports) => imports.join(span),
+                None => span,
+            });
+        }
+
+        ranges.push(imports.take(), FoldingRangeKind::Imports);
+    }
+
+    let mut ranges = ranges.ranges;
+    ranges.sort_by_key(|range| (range.span.start, range.span.end));
+    ranges
+}
+
+struct Ranges<'a> {
+    source: &'a str,
+    ranges: Vec<FoldingRange>,
+}
+
+impl Ranges<'_> {
+    /// Push a range if it spans multiple lines.
+    fn push(&mut self, span: Option<Span>, kind: FoldingRangeKind) {
+        let Some(span) = span else {
+            return;
+        };
+
+        if !self.source[span.range()].contains('\n') {
+            return;
+        }
+
+        self.ranges.push(FoldingRange { span, kind });
+    }
+}
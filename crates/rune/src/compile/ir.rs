@@ -9,23 +9,24 @@ mod interpreter;
 pub(crate) mod scopes;
 mod value;
 
-use core::ops::{AddAssign, MulAssign, ShlAssign, ShrAssign, SubAssign};
-
 use crate::no_std::prelude::*;
 
 use crate as rune;
 use crate::ast::{self, Span, Spanned};
 use crate::compile::ir;
-use crate::compile::{self, WithSpan};
+use crate::compile::{self, IrErrorKind, WithSpan};
 use crate::hir;
 use crate::indexing::index;
 use crate::macros::MacroContext;
+#[cfg(feature = "std")]
+use crate::modules::math::MathFn;
 use crate::parse::NonZeroId;
 use crate::query::Used;
+use crate::runtime::TypeCheck;
 
 pub(crate) use self::compiler::Ctxt;
 pub(crate) use self::eval::{eval_ir, EvalOutcome};
-pub(crate) use self::interpreter::{Budget, Interpreter};
+pub(crate) use self::interpreter::{Budget, EvalFrame, Interpreter};
 pub(crate) use self::scopes::Scopes;
 pub(crate) use self::value::Value;
 
@@ -53,11 +54,15 @@ impl ast::Expr {
         };
 
         let mut ir_interpreter = Interpreter {
-            budget: Budget::new(1_000_000),
+            budget: Budget::new(
+                cx.idx.q.options.const_budget,
+                cx.idx.q.options.const_recursion_limit,
+            ),
             scopes: Default::default(),
             module: cx.item_meta.module,
             item: cx.item_meta.item,
             q: cx.idx.q.borrow(),
+            trace: Vec::new(),
         };
 
         ir_interpreter.eval_value(&ir, Used::Used)
@@ -127,6 +132,9 @@ pub(crate) enum IrTargetKind {
     Field(Box<IrTarget>, Box<str>),
     /// An index target.
     Index(Box<IrTarget>, usize),
+    /// A dynamic index target, like `<target>[<index>]`. This is resolved
+    /// into a field or an index target when evaluated.
+    IndexExpr(Box<IrTarget>, Box<Ir>),
 }
 
 decl_kind! {
@@ -139,6 +147,8 @@ decl_kind! {
         Binary(IrBinary),
         /// Declare a local variable with the value of the operand.
         Decl(IrDecl),
+        /// Declare local variables by destructuring the value of the operand.
+        Local(IrLocal),
         /// Set the given target.
         Set(IrSet),
         /// Assign the given target.
@@ -156,6 +166,10 @@ decl_kind! {
         Branches(IrBranches),
         /// A loop.
         Loop(IrLoop),
+        /// A for loop.
+        For(IrFor),
+        /// A match expression.
+        Match(IrMatch),
         /// A break to the given target.
         Break(IrBreak),
         /// Constructing a vector.
@@ -189,10 +203,17 @@ impl IrFn {
         let mut args = Vec::new();
 
         for arg in hir.args {
-            if let hir::FnArg::Pat(hir::Pat {
+            // Type annotations have no effect when evaluating constants.
+            let pat = match arg {
+                hir::FnArg::Pat(pat) => Some(*pat),
+                hir::FnArg::Typed(typed) => Some(&typed.pat),
+                hir::FnArg::SelfValue(..) => None,
+            };
+
+            if let Some(hir::Pat {
                 kind: hir::PatKind::Path(&hir::PatPathKind::Ident(name)),
                 ..
-            }) = arg
+            }) = pat
             {
                 args.push(hir::Name::Str(name).into_owned());
                 continue;
@@ -249,6 +270,20 @@ pub(crate) struct IrDecl {
     pub(crate) value: Box<Ir>,
 }
 
+/// A local variable declaration which destructures its value.
+#[derive(Debug, Clone, Spanned)]
+pub(crate) struct IrLocal {
+    /// The span of the declaration.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The pattern the value is destructured with.
+    pub(crate) pat: IrPat,
+    /// The value being destructured.
+    pub(crate) value: Box<Ir>,
+    /// The diverging block to evaluate if the pattern doesn't match.
+    pub(crate) else_: Option<IrScope>,
+}
+
 /// Set a target.
 #[derive(Debug, Clone, Spanned)]
 pub(crate) struct IrSet {
@@ -334,21 +369,111 @@ pub(crate) enum IrPat {
     Ignore,
     /// A named binding.
     Binding(hir::OwnedName),
+    /// A literal which is compared against the value.
+    Lit(Box<Ir>),
+    /// A tuple pattern, like `(a, b, ..)`.
+    Tuple(IrPatSequence),
+    /// A vector pattern, like `[a, b, ..]`.
+    Vec(IrPatSequence),
+    /// An object pattern, like `#{a, b: c, ..}`.
+    Object(IrPatObject),
+    /// A binding of a sub-pattern, like `n @ Some(..)`.
+    At(hir::OwnedName, Box<IrPat>),
+}
+
+/// A sequence of patterns.
+#[derive(Debug, Clone)]
+pub(crate) struct IrPatSequence {
+    /// Patterns of the items in the sequence.
+    pub(crate) items: Box<[IrPat]>,
+    /// If the sequence can have more items than there are patterns.
+    pub(crate) is_open: bool,
+}
+
+/// An object pattern.
+#[derive(Debug, Clone)]
+pub(crate) struct IrPatObject {
+    /// Patterns of the fields in the object.
+    pub(crate) fields: Box<[(Box<str>, IrPat)]>,
+    /// If the object can have more fields than there are patterns.
+    pub(crate) is_open: bool,
 }
 
 impl IrPat {
-    fn compile_ast(hir: &hir::Pat<'_>) -> compile::Result<Self> {
+    fn compile_ast(hir: &hir::Pat<'_>, cx: &mut Ctxt<'_, '_>) -> compile::Result<Self> {
         match hir.kind {
             hir::PatKind::Ignore => return Ok(ir::IrPat::Ignore),
             hir::PatKind::Path(&hir::PatPathKind::Ident(name)) => {
                 return Ok(ir::IrPat::Binding(hir::Name::Str(name).into_owned()));
             }
+            hir::PatKind::Lit(hir) => {
+                return Ok(ir::IrPat::Lit(Box::new(compiler::expr(hir, cx)?)));
+            }
+            hir::PatKind::Sequence(hir::PatSequence {
+                kind:
+                    hir::PatSequenceKind::Anonymous {
+                        type_check: type_check @ (TypeCheck::Tuple | TypeCheck::Vec),
+                        is_open,
+                        ..
+                    },
+                items,
+            }) => {
+                let mut pats = Vec::with_capacity(items.len());
+
+                for hir in items.iter() {
+                    pats.push(IrPat::compile_ast(hir, cx)?);
+                }
+
+                let sequence = IrPatSequence {
+                    items: pats.into_boxed_slice(),
+                    is_open: *is_open,
+                };
+
+                return Ok(match type_check {
+                    TypeCheck::Vec => ir::IrPat::Vec(sequence),
+                    _ => ir::IrPat::Tuple(sequence),
+                });
+            }
+            hir::PatKind::Object(hir::PatObject {
+                kind:
+                    hir::PatSequenceKind::Anonymous {
+                        type_check: TypeCheck::Object,
+                        is_open,
+                        ..
+                    },
+                bindings,
+            }) => {
+                let mut fields = Vec::with_capacity(bindings.len());
+
+                for binding in bindings.iter() {
+                    let (key, pat) = match *binding {
+                        hir::Binding::Binding(_, key, hir) => (key, IrPat::compile_ast(hir, cx)?),
+                        hir::Binding::Ident(_, name) => {
+                            (name, IrPat::Binding(hir::Name::Str(name).into_owned()))
+                        }
+                    };
+
+                    fields.push((key.into(), pat));
+                }
+
+                return Ok(ir::IrPat::Object(IrPatObject {
+                    fields: fields.into_boxed_slice(),
+                    is_open: *is_open,
+                }));
+            }
+            hir::PatKind::At(hir) => {
+                let pat = IrPat::compile_ast(hir.pat, cx)?;
+                let name = hir::Name::Str(hir.name).into_owned();
+                return Ok(ir::IrPat::At(name, Box::new(pat)));
+            }
             _ => (),
         }
 
         Err(compile::Error::msg(hir, "pattern not supported yet"))
     }
 
+    /// Match the pattern against the given value, declaring any bindings in
+    /// the current scope.
     fn matches<S>(
         &self,
         interp: &mut Interpreter<'_, '_>,
@@ -356,7 +481,7 @@ impl IrPat {
         spanned: S,
     ) -> Result<bool, ir::EvalOutcome>
     where
-        S: Spanned,
+        S: Copy + Spanned,
     {
         match self {
             IrPat::Ignore => Ok(true),
@@ -364,10 +489,88 @@ impl IrPat {
                 interp.scopes.decl(name, value).with_span(spanned)?;
                 Ok(true)
             }
+            IrPat::Lit(ir) => {
+                let expected = ir::eval_ir(ir, interp, Used::Used)?;
+                Ok(value.lit_eq(&expected, spanned)?)
+            }
+            IrPat::Tuple(pat) => {
+                let items = match value {
+                    Value::EmptyTuple => Vec::new(),
+                    Value::Tuple(items) => items.borrow_ref().with_span(spanned)?.to_vec(),
+                    _ => return Ok(false),
+                };
+
+                pat.matches(interp, items, spanned)
+            }
+            IrPat::Vec(pat) => {
+                let Value::Vec(items) = value else {
+                    return Ok(false);
+                };
+
+                let items = items.borrow_ref().with_span(spanned)?.clone();
+                pat.matches(interp, items, spanned)
+            }
+            IrPat::Object(pat) => {
+                let Value::Object(object) = value else {
+                    return Ok(false);
+                };
+
+                let object = object.borrow_ref().with_span(spanned)?.clone();
+
+                if pat.fields.len() > object.len()
+                    || !pat.is_open && pat.fields.len() < object.len()
+                {
+                    return Ok(false);
+                }
+
+                for (key, pat) in pat.fields.iter() {
+                    let Some(value) = object.get(key.as_ref()) else {
+                        return Ok(false);
+                    };
+
+                    if !pat.matches(interp, value.clone(), spanned)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
+            IrPat::At(name, pat) => {
+                if !pat.matches(interp, value.clone(), spanned)? {
+                    return Ok(false);
+                }
+
+                interp.scopes.decl(name, value).with_span(spanned)?;
+                Ok(true)
+            }
         }
     }
 }
 
+impl IrPatSequence {
+    fn matches<S>(
+        &self,
+        interp: &mut Interpreter<'_, '_>,
+        items: Vec<Value>,
+        spanned: S,
+    ) -> Result<bool, ir::EvalOutcome>
+    where
+        S: Copy + Spanned,
+    {
+        if self.items.len() > items.len() || !self.is_open && self.items.len() < items.len() {
+            return Ok(false);
+        }
+
+        for (pat, value) in self.items.iter().zip(items) {
+            if !pat.matches(interp, value, spanned)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
 /// A loop with an optional condition.
 #[derive(Debug, Clone, Spanned)]
 pub(crate) struct IrLoop {
@@ -382,6 +585,62 @@ pub(crate) struct IrLoop {
     pub(crate) body: IrScope,
 }
 
+/// A for loop over the items of a vector or a tuple, or over a range of
+/// integers.
+#[derive(Debug, Clone, Spanned)]
+pub(crate) struct IrFor {
+    /// The span of the loop.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The label of the loop.
+    pub(crate) label: Option<Box<str>>,
+    /// The pattern every item is bound to.
+    pub(crate) binding: IrPat,
+    /// What's being iterated over.
+    pub(crate) iter: IrForIter,
+    /// The body of the loop.
+    pub(crate) body: IrScope,
+}
+
+/// What a for loop iterates over.
+#[derive(Debug, Clone)]
+pub(crate) enum IrForIter {
+    /// A range of integers, like `start..end` or `start..=end`.
+    Range {
+        start: Box<Ir>,
+        end: Box<Ir>,
+        inclusive: bool,
+    },
+    /// The items of a vector or a tuple.
+    Items(Box<Ir>),
+}
+
+/// A match expression.
+#[derive(Debug, Clone, Spanned)]
+pub(crate) struct IrMatch {
+    /// The span of the match.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The value being matched.
+    pub(crate) expr: Box<Ir>,
+    /// The branches of the match.
+    pub(crate) branches: Vec<IrMatchBranch>,
+}
+
+/// A branch in a match expression.
+#[derive(Debug, Clone, Spanned)]
+pub(crate) struct IrMatchBranch {
+    /// The span of the branch.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The pattern of the branch.
+    pub(crate) pat: IrPat,
+    /// The guard of the branch.
+    pub(crate) condition: Option<Box<Ir>>,
+    /// The body of the branch.
+    pub(crate) body: Ir,
+}
+
 /// A break operation.
 #[derive(Debug, Clone, Spanned)]
 pub(crate) struct IrBreak {
@@ -457,11 +716,21 @@ pub(crate) struct IrCall {
     #[rune(span)]
     pub(crate) span: Span,
     /// The target of the call.
-    pub(crate) id: NonZeroId,
+    pub(crate) target: IrCallTarget,
     /// Arguments to the call.
     pub(crate) args: Vec<Ir>,
 }
 
+/// The target of a call expression.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum IrCallTarget {
+    /// A constant function.
+    ConstFn(NonZeroId),
+    /// A function in the `std::math` module.
+    #[cfg(feature = "std")]
+    Math(MathFn),
+}
+
 /// Vector expression.
 #[derive(Debug, Clone, Spanned)]
 pub(crate) struct IrVec {
@@ -541,38 +810,29 @@ impl IrAssignOp {
     where
         S: Copy + Spanned,
     {
-        match self {
-            IrAssignOp::Add => {
-                target.add_assign(operand);
-            }
-            IrAssignOp::Sub => {
-                target.sub_assign(operand);
-            }
-            IrAssignOp::Mul => {
-                target.mul_assign(operand);
-            }
-            IrAssignOp::Div => {
-                *target = target
-                    .checked_div(operand)
-                    .ok_or("division by zero")
-                    .with_span(spanned)?;
-            }
+        let value = match self {
+            IrAssignOp::Add => target.checked_add(operand).ok_or(IrErrorKind::Overflow),
+            IrAssignOp::Sub => target.checked_sub(operand).ok_or(IrErrorKind::Underflow),
+            IrAssignOp::Mul => target.checked_mul(operand).ok_or(IrErrorKind::Overflow),
+            IrAssignOp::Div if operand == 0 => Err(IrErrorKind::DivideByZero),
+            IrAssignOp::Div => target.checked_div(operand).ok_or(IrErrorKind::Overflow),
             IrAssignOp::Shl => {
                 let operand = u32::try_from(operand)
                     .map_err(|_| "bad operand")
                     .with_span(spanned)?;
 
-                target.shl_assign(operand);
+                target.checked_shl(operand).ok_or(IrErrorKind::Overflow)
             }
             IrAssignOp::Shr => {
                 let operand = u32::try_from(operand)
                     .map_err(|_| "bad operand")
                     .with_span(spanned)?;
 
-                target.shr_assign(operand);
+                target.checked_shr(operand).ok_or(IrErrorKind::Overflow)
             }
-        }
+        };
 
+        *target = value.with_span(spanned)?;
         Ok(())
     }
 }
@@ -6,6 +6,8 @@ use crate::ast::{self, Span, Spanned};
 use crate::compile::ir;
 use crate::compile::{self, ErrorKind};
 use crate::hir;
+#[cfg(feature = "std")]
+use crate::modules::math::MathFn;
 use crate::query::Query;
 use crate::runtime::{Bytes, Shared};
 use crate::SourceId;
@@ -34,9 +36,13 @@ pub(crate) fn expr(hir: &hir::Expr<'_>, c: &mut Ctxt<'_, '_>) -> compile::Result
         hir::ExprKind::Call(hir) => ir::Ir::new(span, expr_call(span, c, hir)?),
         hir::ExprKind::If(hir) => ir::Ir::new(span, expr_if(span, c, hir)?),
         hir::ExprKind::Loop(hir) => ir::Ir::new(span, expr_loop(span, c, hir)?),
+        hir::ExprKind::For(hir) => ir::Ir::new(span, expr_for(span, c, hir)?),
+        hir::ExprKind::Match(hir) => ir::Ir::new(span, expr_match(span, c, hir)?),
         hir::ExprKind::Lit(hir) => lit(c, span, hir)?,
         hir::ExprKind::Block(hir) => ir::Ir::new(span, block(hir, c)?),
-        hir::ExprKind::FieldAccess(..) => ir::Ir::new(span, ir_target(hir)?),
+        hir::ExprKind::FieldAccess(..) | hir::ExprKind::Index(..) => {
+            ir::Ir::new(span, ir_target(hir, c)?)
+        }
         hir::ExprKind::Break(hir) => ir::Ir::new(span, ir::IrBreak::compile_ast(span, c, hir)?),
         hir::ExprKind::Template(template) => {
             let ir_template = builtin_template(template, c)?;
@@ -65,7 +71,7 @@ pub(crate) fn expr(hir: &hir::Expr<'_>, c: &mut Ctxt<'_, '_>) -> compile::Result
 }
 
 /// Resolve an ir target from an expression.
-fn ir_target(expr: &hir::Expr<'_>) -> compile::Result<ir::IrTarget> {
+fn ir_target(expr: &hir::Expr<'_>, c: &mut Ctxt<'_, '_>) -> compile::Result<ir::IrTarget> {
     match expr.kind {
         hir::ExprKind::Variable(name) => {
             return Ok(ir::IrTarget {
@@ -74,7 +80,7 @@ fn ir_target(expr: &hir::Expr<'_>) -> compile::Result<ir::IrTarget> {
             });
         }
         hir::ExprKind::FieldAccess(expr_field_access) => {
-            let target = ir_target(&expr_field_access.expr)?;
+            let target = ir_target(&expr_field_access.expr, c)?;
 
             match expr_field_access.expr_field {
                 hir::ExprField::Ident(name) => {
@@ -94,6 +100,15 @@ fn ir_target(expr: &hir::Expr<'_>) -> compile::Result<ir::IrTarget> {
                 }
             }
         }
+        hir::ExprKind::Index(expr_index_get) => {
+            let target = ir_target(&expr_index_get.target, c)?;
+            let index = self::expr(&expr_index_get.index, c)?;
+
+            return Ok(ir::IrTarget {
+                span: expr.span(),
+                kind: ir::IrTargetKind::IndexExpr(Box::new(target), Box::new(index)),
+            });
+        }
         _ => (),
     }
 
@@ -106,7 +121,7 @@ fn expr_assign(
     c: &mut Ctxt<'_, '_>,
     hir: &hir::ExprAssign<'_>,
 ) -> compile::Result<ir::Ir> {
-    let target = ir_target(&hir.lhs)?;
+    let target = ir_target(&hir.lhs, c)?;
 
     Ok(ir::Ir::new(
         span,
@@ -130,8 +145,38 @@ fn expr_call(
         args.push(expr(e, c)?);
     }
 
-    if let hir::Call::ConstFn { id, .. } = hir.call {
-        return Ok(ir::IrCall { span, id, args });
+    match hir.call {
+        hir::Call::ConstFn { id, .. } => {
+            return Ok(ir::IrCall {
+                span,
+                target: ir::IrCallTarget::ConstFn(id),
+                args,
+            });
+        }
+        hir::Call::Meta {
+            hash, local: true, ..
+        } => {
+            if let Some(id) = c.q.pure_const_fn(hash)? {
+                return Ok(ir::IrCall {
+                    span,
+                    target: ir::IrCallTarget::ConstFn(id),
+                    args,
+                });
+            }
+        }
+        #[cfg(feature = "std")]
+        hir::Call::Meta {
+            hash, local: false, ..
+        } => {
+            if let Some(f) = MathFn::lookup(hash) {
+                return Ok(ir::IrCall {
+                    span,
+                    target: ir::IrCallTarget::Math(f),
+                    args,
+                });
+            }
+        }
+        _ => {}
     }
 
     Err(compile::Error::msg(
@@ -157,7 +202,7 @@ fn expr_binary(
             _ => return Err(compile::Error::msg(hir.op, "op not supported yet")),
         };
 
-        let target = ir_target(&hir.lhs)?;
+        let target = ir_target(&hir.lhs, c)?;
 
         return Ok(ir::Ir::new(
             span,
@@ -295,6 +340,12 @@ pub(crate) fn block(hir: &hir::Block<'_>, c: &mut Ctxt<'_, '_>) -> compile::Resu
             }
             hir::Stmt::Expr(e) => (e, false),
             hir::Stmt::Semi(e) => (e, true),
+            hir::Stmt::Defer(block) => {
+                return Err(compile::Error::msg(
+                    block,
+                    "Defer not supported in constant contexts",
+                ))
+            }
             hir::Stmt::Item(..) => continue,
         };
 
@@ -322,7 +373,7 @@ pub(crate) fn block(hir: &hir::Block<'_>, c: &mut Ctxt<'_, '_>) -> compile::Resu
 }
 
 #[instrument]
-fn builtin_template(
+pub(crate) fn builtin_template(
     template: &hir::BuiltInTemplate,
     c: &mut Ctxt<'_, '_>,
 ) -> compile::Result<ir::IrTemplate> {
@@ -352,7 +403,23 @@ fn local(hir: &hir::Local<'_>, c: &mut Ctxt<'_, '_>) -> compile::Result<ir::Ir>
         }
         hir::PatKind::Path(&hir::PatPathKind::Ident(name)) => name,
         _ => {
-            return Err(compile::Error::msg(span, "not supported yet"));
+            let pat = ir::IrPat::compile_ast(&hir.pat, c)?;
+            let value = Box::new(expr(&hir.expr, c)?);
+
+            let else_ = match hir.else_ {
+                Some(hir) => Some(block(hir, c)?),
+                None => None,
+            };
+
+            return Ok(ir::Ir::new(
+                span,
+                ir::IrLocal {
+                    span,
+                    pat,
+                    value,
+                    else_,
+                },
+            ));
         }
     };
 
@@ -371,7 +438,7 @@ fn condition(hir: &hir::Condition<'_>, c: &mut Ctxt<'_, '_>) -> compile::Result<
     match hir {
         hir::Condition::Expr(e) => Ok(ir::IrCondition::Ir(expr(e, c)?)),
         hir::Condition::ExprLet(hir) => {
-            let pat = ir::IrPat::compile_ast(&hir.pat)?;
+            let pat = ir::IrPat::compile_ast(&hir.pat, c)?;
             let ir = expr(&hir.expr, c)?;
 
             Ok(ir::IrCondition::Let(ir::IrLet {
@@ -427,3 +494,70 @@ fn expr_loop(
         body: block(&hir.body, c)?,
     })
 }
+
+#[instrument]
+fn expr_for(
+    span: Span,
+    c: &mut Ctxt<'_, '_>,
+    hir: &hir::ExprFor<'_>,
+) -> compile::Result<ir::IrFor> {
+    let iter = match hir.iter.kind {
+        hir::ExprKind::Range(hir::ExprRange::Range { start, end }) => ir::IrForIter::Range {
+            start: Box::new(expr(start, c)?),
+            end: Box::new(expr(end, c)?),
+            inclusive: false,
+        },
+        hir::ExprKind::Range(hir::ExprRange::RangeInclusive { start, end }) => {
+            ir::IrForIter::Range {
+                start: Box::new(expr(start, c)?),
+                end: Box::new(expr(end, c)?),
+                inclusive: true,
+            }
+        }
+        hir::ExprKind::Range(..) => {
+            return Err(compile::Error::msg(
+                &hir.iter,
+                "Only bounded ranges can be iterated over in constant contexts",
+            ));
+        }
+        _ => ir::IrForIter::Items(Box::new(expr(&hir.iter, c)?)),
+    };
+
+    Ok(ir::IrFor {
+        span,
+        label: hir.label.map(|l| l.into()),
+        binding: ir::IrPat::compile_ast(&hir.binding, c)?,
+        iter,
+        body: block(&hir.body, c)?,
+    })
+}
+
+#[instrument]
+fn expr_match(
+    span: Span,
+    c: &mut Ctxt<'_, '_>,
+    hir: &hir::ExprMatch<'_>,
+) -> compile::Result<ir::IrMatch> {
+    let value = Box::new(expr(&hir.expr, c)?);
+    let mut branches = Vec::with_capacity(hir.branches.len());
+
+    for branch in hir.branches {
+        let condition = match branch.condition {
+            Some(hir) => Some(Box::new(expr(hir, c)?)),
+            None => None,
+        };
+
+        branches.push(ir::IrMatchBranch {
+            span: branch.span,
+            pat: ir::IrPat::compile_ast(&branch.pat, c)?,
+            condition,
+            body: expr(&branch.body, c)?,
+        });
+    }
+
+    Ok(ir::IrMatch {
+        span,
+        expr: value,
+        branches,
+    })
+}
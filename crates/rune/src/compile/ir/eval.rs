@@ -1,14 +1,24 @@
 use core::fmt::Write;
-use core::ops::{Add, Mul, Shl, Shr, Sub};
 
+use crate::no_std::borrow::Cow;
 use crate::no_std::collections::HashMap;
 use crate::no_std::prelude::*;
 
 use crate::ast::{Span, Spanned};
 use crate::compile::ir;
-use crate::compile::{self, WithSpan};
+use crate::compile::{self, IrErrorKind, WithSpan};
+#[cfg(feature = "std")]
+use crate::modules::math::{MathFn, Number};
 use crate::query::Used;
-use crate::runtime::Shared;
+use crate::runtime::{self, Shared};
+
+/// Process an ir value as an integer.
+fn as_integer(span: Span, value: ir::Value) -> compile::Result<i64> {
+    match value {
+        ir::Value::Integer(n) => Ok(n),
+        actual => Err(compile::Error::expected_type::<_, i64>(span, &actual)),
+    }
+}
 
 /// Process an ir value as a boolean.
 fn as_bool(span: Span, value: ir::Value) -> compile::Result<bool> {
@@ -52,15 +62,65 @@ fn eval_ir_assign(
     used: Used,
 ) -> Result<ir::Value, EvalOutcome> {
     interp.budget.take(ir)?;
+    let target = resolve_target(&ir.target, interp, used)?;
     let value = eval_ir(&ir.value, interp, used)?;
 
     interp
         .scopes
-        .mut_target(&ir.target, move |t| ir.op.assign(ir, t, value))?;
+        .mut_target(&target, move |t| ir.op.assign(ir, t, value))?;
 
     Ok(ir::Value::EmptyTuple)
 }
 
+/// Resolve any dynamic indexes in the given target by evaluating them.
+fn resolve_target<'a>(
+    target: &'a ir::IrTarget,
+    interp: &mut ir::Interpreter<'_, '_>,
+    used: Used,
+) -> Result<Cow<'a, ir::IrTarget>, EvalOutcome> {
+    let kind = match &target.kind {
+        ir::IrTargetKind::Name(..) => return Ok(Cow::Borrowed(target)),
+        ir::IrTargetKind::Field(inner, field) => match resolve_target(inner, interp, used)? {
+            Cow::Borrowed(..) => return Ok(Cow::Borrowed(target)),
+            Cow::Owned(inner) => ir::IrTargetKind::Field(Box::new(inner), field.clone()),
+        },
+        ir::IrTargetKind::Index(inner, index) => match resolve_target(inner, interp, used)? {
+            Cow::Borrowed(..) => return Ok(Cow::Borrowed(target)),
+            Cow::Owned(inner) => ir::IrTargetKind::Index(Box::new(inner), *index),
+        },
+        ir::IrTargetKind::IndexExpr(inner, index) => {
+            let inner = Box::new(resolve_target(inner, interp, used)?.into_owned());
+
+            match eval_ir(index, interp, used)? {
+                ir::Value::Integer(n) => {
+                    let Ok(n) = usize::try_from(n) else {
+                        return Err(EvalOutcome::from(compile::Error::msg(
+                            &**index,
+                            format_args!("Index `{n}` is out of bounds"),
+                        )));
+                    };
+
+                    ir::IrTargetKind::Index(inner, n)
+                }
+                ir::Value::String(string) => {
+                    let string = string.borrow_ref().with_span(&**index)?;
+                    ir::IrTargetKind::Field(inner, string.as_str().into())
+                }
+                actual => {
+                    return Err(EvalOutcome::from(compile::Error::expected_type::<_, i64>(
+                        &**index, &actual,
+                    )));
+                }
+            }
+        }
+    };
+
+    Ok(Cow::Owned(ir::IrTarget {
+        span: target.span,
+        kind,
+    }))
+}
+
 fn eval_ir_binary(
     ir: &ir::IrBinary,
     interp: &mut ir::Interpreter<'_, '_>,
@@ -75,26 +135,44 @@ fn eval_ir_binary(
     match (a, b) {
         (ir::Value::Integer(a), ir::Value::Integer(b)) => match ir.op {
             ir::IrBinaryOp::Add => {
-                return Ok(ir::Value::Integer(a.add(&b)));
+                let n = a
+                    .checked_add(b)
+                    .ok_or_else(|| compile::Error::new(span, IrErrorKind::Overflow))?;
+                return Ok(ir::Value::Integer(n));
             }
             ir::IrBinaryOp::Sub => {
-                return Ok(ir::Value::Integer(a.sub(&b)));
+                let n = a
+                    .checked_sub(b)
+                    .ok_or_else(|| compile::Error::new(span, IrErrorKind::Underflow))?;
+                return Ok(ir::Value::Integer(n));
             }
             ir::IrBinaryOp::Mul => {
-                return Ok(ir::Value::Integer(a.mul(&b)));
+                let n = a
+                    .checked_mul(b)
+                    .ok_or_else(|| compile::Error::new(span, IrErrorKind::Overflow))?;
+                return Ok(ir::Value::Integer(n));
             }
             ir::IrBinaryOp::Div => {
-                let number = a
+                if b == 0 {
+                    return Err(EvalOutcome::from(compile::Error::new(
+                        span,
+                        IrErrorKind::DivideByZero,
+                    )));
+                }
+
+                let n = a
                     .checked_div(b)
-                    .ok_or_else(|| compile::Error::msg(span, "division by zero"))?;
-                return Ok(ir::Value::Integer(number));
+                    .ok_or_else(|| compile::Error::new(span, IrErrorKind::Overflow))?;
+                return Ok(ir::Value::Integer(n));
             }
             ir::IrBinaryOp::Shl => {
                 let b = u32::try_from(b).map_err(|_| {
                     compile::Error::msg(&ir.rhs, "cannot be converted to shift operand")
                 })?;
 
-                let n = a.shl(b);
+                let n = a
+                    .checked_shl(b)
+                    .ok_or_else(|| compile::Error::new(span, IrErrorKind::Overflow))?;
                 return Ok(ir::Value::Integer(n));
             }
             ir::IrBinaryOp::Shr => {
@@ -102,7 +180,9 @@ fn eval_ir_binary(
                     compile::Error::msg(&ir.rhs, "cannot be converted to shift operand")
                 })?;
 
-                let n = a.shr(b);
+                let n = a
+                    .checked_shr(b)
+                    .ok_or_else(|| compile::Error::new(span, IrErrorKind::Overflow))?;
                 return Ok(ir::Value::Integer(n));
             }
             ir::IrBinaryOp::Lt => return Ok(ir::Value::Bool(a < b)),
@@ -190,7 +270,33 @@ fn eval_ir_call(
         args.push(eval_ir(arg, interp, used)?);
     }
 
-    Ok(interp.call_const_fn(ir, ir.id, args, used)?)
+    match ir.target {
+        ir::IrCallTarget::ConstFn(id) => Ok(interp.call_const_fn(ir, id, args, used)?),
+        #[cfg(feature = "std")]
+        ir::IrCallTarget::Math(f) => Ok(eval_math_fn(ir, f, args)?),
+    }
+}
+
+/// Evaluate a function in the `std::math` module.
+#[cfg(feature = "std")]
+fn eval_math_fn(ir: &ir::IrCall, f: MathFn, args: Vec<ir::Value>) -> compile::Result<ir::Value> {
+    let mut numbers = Vec::with_capacity(args.len());
+
+    for (arg, value) in ir.args.iter().zip(args) {
+        numbers.push(match value {
+            ir::Value::Integer(n) => Number::Integer(n),
+            ir::Value::Float(n) => Number::Float(n),
+            value => {
+                return Err(compile::Error::expected_type::<_, f64>(arg, &value));
+            }
+        });
+    }
+
+    match f.call(&numbers) {
+        Ok(Number::Integer(n)) => Ok(ir::Value::Integer(n)),
+        Ok(Number::Float(n)) => Ok(ir::Value::Float(n)),
+        Err(error) => Err(compile::Error::msg(ir, error)),
+    }
 }
 
 fn eval_ir_condition(
@@ -221,6 +327,108 @@ fn eval_ir_decl(
     Ok(ir::Value::EmptyTuple)
 }
 
+fn eval_ir_for(
+    ir: &ir::IrFor,
+    interp: &mut ir::Interpreter<'_, '_>,
+    used: Used,
+) -> Result<ir::Value, EvalOutcome> {
+    let span = ir.span();
+    interp.budget.take(span)?;
+
+    let items: Box<dyn Iterator<Item = ir::Value>> = match &ir.iter {
+        ir::IrForIter::Range {
+            start,
+            end,
+            inclusive,
+        } => {
+            let start = as_integer(start.span(), eval_ir(start, interp, used)?)?;
+            let end = as_integer(end.span(), eval_ir(end, interp, used)?)?;
+
+            if *inclusive {
+                Box::new((start..=end).map(ir::Value::Integer))
+            } else {
+                Box::new((start..end).map(ir::Value::Integer))
+            }
+        }
+        ir::IrForIter::Items(iter) => match eval_ir(iter, interp, used)? {
+            ir::Value::Vec(vec) => {
+                Box::new(vec.borrow_ref().with_span(&**iter)?.clone().into_iter())
+            }
+            ir::Value::Tuple(tuple) => {
+                Box::new(tuple.borrow_ref().with_span(&**iter)?.to_vec().into_iter())
+            }
+            actual => {
+                return Err(EvalOutcome::from(compile::Error::expected_type::<
+                    _,
+                    runtime::Vec,
+                >(&**iter, &actual)));
+            }
+        },
+    };
+
+    let guard = interp.scopes.push();
+
+    for item in items {
+        interp.scopes.clear_current().with_span(span)?;
+
+        if !ir.binding.matches(interp, item, span)? {
+            return Err(EvalOutcome::from(compile::Error::msg(
+                span,
+                "Item doesn't match the pattern of the loop",
+            )));
+        }
+
+        match eval_ir_scope(&ir.body, interp, used) {
+            Ok(..) => (),
+            Err(EvalOutcome::Break(span, label, expr)) => {
+                if label.as_deref() != ir.label.as_deref() {
+                    return Err(EvalOutcome::Break(span, label, expr));
+                }
+
+                if expr.is_some() {
+                    return Err(EvalOutcome::from(compile::Error::msg(
+                        span,
+                        "break with value is not supported for for loops",
+                    )));
+                }
+
+                break;
+            }
+            Err(outcome) => return Err(outcome),
+        }
+    }
+
+    interp.scopes.pop(guard).with_span(ir)?;
+    Ok(ir::Value::EmptyTuple)
+}
+
+fn eval_ir_local(
+    ir: &ir::IrLocal,
+    interp: &mut ir::Interpreter<'_, '_>,
+    used: Used,
+) -> Result<ir::Value, EvalOutcome> {
+    interp.budget.take(ir)?;
+    let value = eval_ir(&ir.value, interp, used)?;
+
+    if ir.pat.matches(interp, value, ir.span)? {
+        return Ok(ir::Value::EmptyTuple);
+    }
+
+    let Some(else_) = &ir.else_ else {
+        return Err(EvalOutcome::from(compile::Error::msg(
+            ir,
+            "Value doesn't match the pattern",
+        )));
+    };
+
+    eval_ir_scope(else_, interp, used)?;
+
+    Err(EvalOutcome::from(compile::Error::msg(
+        else_,
+        "Expected the else block of the let statement to diverge",
+    )))
+}
+
 fn eval_ir_loop(
     ir: &ir::IrLoop,
     interp: &mut ir::Interpreter<'_, '_>,
@@ -263,7 +471,7 @@ fn eval_ir_loop(
         if ir.condition.is_some() {
             return Err(EvalOutcome::from(compile::Error::msg(
                 span,
-                "break with value is not supported for unconditional loops",
+                "break with value is not supported for conditional loops",
             )));
         }
 
@@ -273,6 +481,43 @@ fn eval_ir_loop(
     }
 }
 
+fn eval_ir_match(
+    ir: &ir::IrMatch,
+    interp: &mut ir::Interpreter<'_, '_>,
+    used: Used,
+) -> Result<ir::Value, EvalOutcome> {
+    interp.budget.take(ir)?;
+    let value = eval_ir(&ir.expr, interp, used)?;
+
+    for branch in &ir.branches {
+        let guard = interp.scopes.push();
+
+        let mut matched = branch.pat.matches(interp, value.clone(), branch.span)?;
+
+        if let (true, Some(condition)) = (matched, &branch.condition) {
+            let value = eval_ir(condition, interp, used)?;
+            matched = as_bool(condition.span(), value)?;
+        }
+
+        let output = if matched {
+            Some(eval_ir(&branch.body, interp, used)?)
+        } else {
+            None
+        };
+
+        interp.scopes.pop(guard).with_span(branch)?;
+
+        if let Some(output) = output {
+            return Ok(output);
+        }
+    }
+
+    Err(EvalOutcome::from(compile::Error::msg(
+        ir,
+        "No branch matched the value",
+    )))
+}
+
 fn eval_ir_object(
     ir: &ir::IrObject,
     interp: &mut ir::Interpreter<'_, '_>,
@@ -315,8 +560,9 @@ fn eval_ir_set(
     used: Used,
 ) -> Result<ir::Value, EvalOutcome> {
     interp.budget.take(ir)?;
+    let target = resolve_target(&ir.target, interp, used)?;
     let value = eval_ir(&ir.value, interp, used)?;
-    interp.scopes.set_target(&ir.target, value)?;
+    interp.scopes.set_target(&target, value)?;
     Ok(ir::Value::EmptyTuple)
 }
 
@@ -403,14 +649,20 @@ pub(crate) fn eval_ir(
         ir::IrKind::Scope(ir) => eval_ir_scope(ir, interp, used),
         ir::IrKind::Binary(ir) => eval_ir_binary(ir, interp, used),
         ir::IrKind::Decl(ir) => eval_ir_decl(ir, interp, used),
+        ir::IrKind::Local(ir) => eval_ir_local(ir, interp, used),
         ir::IrKind::Set(ir) => eval_ir_set(ir, interp, used),
         ir::IrKind::Assign(ir) => eval_ir_assign(ir, interp, used),
         ir::IrKind::Template(ir) => eval_ir_template(ir, interp, used),
         ir::IrKind::Name(name) => Ok(interp.resolve_var(ir, name, used)?),
-        ir::IrKind::Target(target) => Ok(interp.scopes.get_target(target)?),
+        ir::IrKind::Target(target) => {
+            let target = resolve_target(target, interp, used)?;
+            Ok(interp.scopes.get_target(&target)?)
+        }
         ir::IrKind::Value(value) => Ok(value.clone()),
         ir::IrKind::Branches(ir) => eval_ir_branches(ir, interp, used),
         ir::IrKind::Loop(ir) => eval_ir_loop(ir, interp, used),
+        ir::IrKind::For(ir) => eval_ir_for(ir, interp, used),
+        ir::IrKind::Match(ir) => eval_ir_match(ir, interp, used),
         ir::IrKind::Break(ir) => Err(ir.as_outcome(interp, used)),
         ir::IrKind::Vec(ir) => eval_ir_vec(ir, interp, used),
         ir::IrKind::Tuple(ir) => eval_ir_tuple(ir, interp, used),
@@ -1,14 +1,15 @@
 use crate::no_std::prelude::*;
 
-use crate::ast::Spanned;
+use crate::ast::{Span, Spanned};
 use crate::compile::ir;
 use crate::compile::ir::scopes::MissingLocal;
 use crate::compile::meta;
-use crate::compile::{self, IrErrorKind, ItemId, ModId, WithSpan};
+use crate::compile::{self, IrErrorKind, ItemBuf, ItemId, ModId, WithSpan};
 use crate::hir;
 use crate::parse::NonZeroId;
-use crate::query::{Query, Used};
+use crate::query::{ConstFn, Query, Used};
 use crate::runtime::{ConstValue, Object, OwnedTuple};
+use crate::SourceId;
 
 /// The interpreter that executed [Ir][crate::ir::Ir].
 pub struct Interpreter<'a, 'arena> {
@@ -23,6 +24,20 @@ pub struct Interpreter<'a, 'arena> {
     pub(crate) scopes: ir::Scopes,
     /// Query engine to look for constant expressions.
     pub(crate) q: Query<'a, 'arena>,
+    /// The constant function calls currently being evaluated, outermost
+    /// first.
+    pub(crate) trace: Vec<EvalFrame>,
+}
+
+/// A constant function call in the trace of a constant evaluation.
+#[derive(Debug, Clone)]
+pub(crate) struct EvalFrame {
+    /// The span of the call.
+    pub(crate) span: Span,
+    /// The function being called.
+    pub(crate) item: ItemBuf,
+    /// The source the called function is defined in.
+    pub(crate) source_id: SourceId,
 }
 
 impl Interpreter<'_, '_> {
@@ -177,10 +192,43 @@ impl Interpreter<'_, '_> {
             self.scopes.decl(name, value).with_span(span)?;
         }
 
-        let value = self.eval_value(&const_fn.ir_fn.ir, used)?;
+        let value = self.eval_call(span, &const_fn, used)?;
         self.scopes.pop(guard).with_span(span)?;
         Ok(value)
     }
+
+    /// Evaluate the body of a constant function called at the given span.
+    ///
+    /// The call is recorded in the evaluation trace, which is attached to any
+    /// error raised while evaluating it.
+    pub(crate) fn eval_call(
+        &mut self,
+        span: Span,
+        const_fn: &ConstFn<'_>,
+        used: Used,
+    ) -> compile::Result<ir::Value> {
+        let limit = self.budget.recursion_limit;
+
+        if self.trace.len() >= limit {
+            return Err(compile::Error::new(
+                span,
+                IrErrorKind::RecursionLimitExceeded { limit },
+            ));
+        }
+
+        self.trace.push(EvalFrame {
+            span,
+            item: self.q.pool.item(const_fn.item_meta.item).to_owned(),
+            source_id: const_fn.item_meta.location.source_id,
+        });
+
+        let result = self
+            .eval_value(&const_fn.ir_fn.ir, used)
+            .map_err(|error| error.with_const_trace(&self.trace));
+
+        self.trace.pop();
+        result
+    }
 }
 
 impl ir::Scopes {
@@ -243,6 +291,7 @@ impl ir::Scopes {
                     IrErrorKind::MissingIndex { index: *index },
                 ))
             }
+            ir::IrTargetKind::IndexExpr(..) => Err(unresolved_target(ir_target)),
         }
     }
 
@@ -303,6 +352,7 @@ impl ir::Scopes {
 
                 Err(compile::Error::msg(ir_target, "missing index"))
             }
+            ir::IrTargetKind::IndexExpr(..) => Err(unresolved_target(ir_target)),
         }
     }
 
@@ -373,19 +423,30 @@ impl ir::Scopes {
                     )),
                 }
             }
+            ir::IrTargetKind::IndexExpr(..) => Err(unresolved_target(ir_target)),
         }
     }
 }
 
+/// Error raised if a dynamic index target is used before it's been resolved.
+fn unresolved_target(ir_target: &ir::IrTarget) -> compile::Error {
+    compile::Error::msg(ir_target, "Dynamic index target has not been resolved")
+}
+
 /// A budget dictating the number of evaluations the compiler is allowed to do.
 pub(crate) struct Budget {
     budget: usize,
+    /// How deeply constant functions are allowed to call each other.
+    recursion_limit: usize,
 }
 
 impl Budget {
-    /// Construct a new constant evaluation budget with the given constraint.
-    pub(crate) fn new(budget: usize) -> Self {
-        Self { budget }
+    /// Construct a new constant evaluation budget with the given constraints.
+    pub(crate) fn new(budget: usize, recursion_limit: usize) -> Self {
+        Self {
+            budget,
+            recursion_limit,
+        }
     }
 
     /// Take an item from the budget. Errors if the budget is exceeded.
@@ -156,6 +156,32 @@ impl Value {
         })
     }
 
+    /// Test if the value is equal to a literal value, as used in literal
+    /// patterns. Values of different types are never equal.
+    pub(crate) fn lit_eq<S>(&self, other: &Value, spanned: S) -> compile::Result<bool>
+    where
+        S: Copy + Spanned,
+    {
+        #[allow(clippy::float_cmp)]
+        let eq = match (self, other) {
+            (Value::EmptyTuple, Value::EmptyTuple) => true,
+            (Value::Byte(a), Value::Byte(b)) => a == b,
+            (Value::Char(a), Value::Char(b)) => a == b,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Integer(a), Value::Integer(b)) => a == b,
+            (Value::Float(a), Value::Float(b)) => a == b,
+            (Value::String(a), Value::String(b)) => {
+                *a.borrow_ref().with_span(spanned)? == *b.borrow_ref().with_span(spanned)?
+            }
+            (Value::Bytes(a), Value::Bytes(b)) => {
+                *a.borrow_ref().with_span(spanned)? == *b.borrow_ref().with_span(spanned)?
+            }
+            _ => false,
+        };
+
+        Ok(eq)
+    }
+
     /// Get the type information of the value.
     pub(crate) fn type_info(&self) -> TypeInfo {
         match self {
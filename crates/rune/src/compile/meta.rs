@@ -14,7 +14,7 @@ use crate::compile::attrs::Parser;
 use crate::compile::{self, Item, ItemId, Location, MetaInfo, ModId, Pool, Visibility};
 use crate::hash::Hash;
 use crate::parse::{NonZeroId, ResolveContext};
-use crate::runtime::{Call, Protocol};
+use crate::runtime::{Call, Derives, Protocol};
 
 /// A meta reference to an item being compiled.
 #[derive(Debug, Clone, Copy)]
@@ -142,6 +142,17 @@ pub enum Fields {
     Empty,
 }
 
+/// Parameters of a `#[test]` function, which are specified through additional
+/// attributes on the function.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TestParams {
+    /// The test is marked with `#[ignore]`, and is not run by default.
+    pub ignore: bool,
+    /// The test is marked with `#[should_panic]`, and is expected to panic.
+    pub should_panic: bool,
+}
+
 /// Compile-time metadata kind about a unit.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -160,6 +171,8 @@ pub enum Kind {
         constructor: Option<Signature>,
         /// Hash of generic parameters.
         parameters: Hash,
+        /// Protocols derived for this struct.
+        derives: Derives,
     },
     /// Metadata about an empty variant.
     Variant {
@@ -167,10 +180,14 @@ pub enum Kind {
         enum_hash: Hash,
         /// The index of the variant.
         index: usize,
+        /// The discriminant of the variant, if it has one.
+        discriminant: Option<i64>,
         /// Fields information.
         fields: Fields,
         /// Native constructor for this variant.
         constructor: Option<Signature>,
+        /// Protocols derived for the enum this variant belongs to.
+        derives: Derives,
     },
     /// An enum item.
     Enum {
@@ -187,6 +204,8 @@ pub enum Kind {
         signature: Signature,
         /// Whether this function has a `#[test]` annotation
         is_test: bool,
+        /// Parameters of a `#[test]` function.
+        test_params: TestParams,
         /// Whether this function has a `#[bench]` annotation.
         is_bench: bool,
         /// Hash of generic parameters.
@@ -236,7 +255,6 @@ pub enum Kind {
 
 impl Kind {
     /// Access the underlying signature of the kind, if available.
-    #[cfg(feature = "doc")]
     pub(crate) fn as_signature(&self) -> Option<&Signature> {
         match self {
             Kind::Struct { constructor, .. } => constructor.as_ref(),
@@ -330,7 +348,6 @@ pub struct Signature {
     #[cfg(feature = "doc")]
     pub(crate) deprecated: Option<Box<str>>,
     /// Arguments.
-    #[cfg(feature = "doc")]
     pub(crate) args: Option<usize>,
     /// Return type of the function.
     #[cfg(feature = "doc")]
@@ -10,7 +10,7 @@ use crate::Hash;
 #[non_exhaustive]
 pub struct MetaInfo {
     /// The kind of the item.
-    kind: MetaInfoKind,
+    kind: MetaKind,
     /// The hash of the meta item.
     hash: Hash,
     /// The item being described.
@@ -21,7 +21,7 @@ impl MetaInfo {
     /// Construct a new meta info.
     pub(crate) fn new(kind: &meta::Kind, hash: Hash, item: Option<&Item>) -> Self {
         Self {
-            kind: MetaInfoKind::from_kind(kind),
+            kind: MetaKind::from_kind(kind),
             hash,
             item: item.map(|item| item.to_owned()),
         }
@@ -45,46 +45,46 @@ impl fmt::Display for MetaInfo {
         let name = Name(self.hash, self.item.as_deref());
 
         match self.kind {
-            MetaInfoKind::Type => {
+            MetaKind::Type => {
                 write!(fmt, "type {name}")?;
             }
-            MetaInfoKind::Struct => {
+            MetaKind::Struct => {
                 write!(fmt, "struct {name}")?;
             }
-            MetaInfoKind::Variant => {
+            MetaKind::Variant => {
                 write!(fmt, "variant {name}")?;
             }
-            MetaInfoKind::Enum => {
+            MetaKind::Enum => {
                 write!(fmt, "enum {name}")?;
             }
-            MetaInfoKind::Macro => {
+            MetaKind::Macro => {
                 write!(fmt, "macro {name}")?;
             }
-            MetaInfoKind::AttributeMacro => {
+            MetaKind::AttributeMacro => {
                 write!(fmt, "attribute macro {name}")?;
             }
-            MetaInfoKind::Function => {
+            MetaKind::Function => {
                 write!(fmt, "fn {name}")?;
             }
-            MetaInfoKind::Associated => {
+            MetaKind::Associated => {
                 write!(fmt, "associated fn {name}")?;
             }
-            MetaInfoKind::Closure => {
+            MetaKind::Closure => {
                 write!(fmt, "closure {name}")?;
             }
-            MetaInfoKind::AsyncBlock => {
+            MetaKind::AsyncBlock => {
                 write!(fmt, "async block {name}")?;
             }
-            MetaInfoKind::Const => {
+            MetaKind::Const => {
                 write!(fmt, "const {name}")?;
             }
-            MetaInfoKind::ConstFn => {
+            MetaKind::ConstFn => {
                 write!(fmt, "const fn {name}")?;
             }
-            MetaInfoKind::Import => {
+            MetaKind::Import => {
                 write!(fmt, "import {name}")?;
             }
-            MetaInfoKind::Module => {
+            MetaKind::Module => {
                 write!(fmt, "module {name}")?;
             }
         }
@@ -93,41 +93,57 @@ impl fmt::Display for MetaInfo {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-pub(crate) enum MetaInfoKind {
+/// The kind of a meta item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MetaKind {
+    /// An opaque type.
     Type,
+    /// A struct.
     Struct,
+    /// An enum variant.
     Variant,
+    /// An enum.
     Enum,
+    /// A macro.
     Macro,
+    /// An attribute macro.
     AttributeMacro,
+    /// A function.
     Function,
+    /// An associated function.
     Associated,
+    /// A closure.
     Closure,
+    /// An async block.
     AsyncBlock,
+    /// A constant.
     Const,
+    /// A constant function.
     ConstFn,
+    /// An import.
     Import,
+    /// A module.
     Module,
 }
 
-impl MetaInfoKind {
-    fn from_kind(value: &meta::Kind) -> Self {
+impl MetaKind {
+    pub(crate) fn from_kind(value: &meta::Kind) -> Self {
         match value {
-            meta::Kind::Type { .. } => MetaInfoKind::Type,
-            meta::Kind::Struct { .. } => MetaInfoKind::Struct,
-            meta::Kind::Variant { .. } => MetaInfoKind::Variant,
-            meta::Kind::Enum { .. } => MetaInfoKind::Enum,
-            meta::Kind::Macro { .. } => MetaInfoKind::Macro,
-            meta::Kind::AttributeMacro { .. } => MetaInfoKind::AttributeMacro,
-            meta::Kind::Function { .. } => MetaInfoKind::Function,
-            meta::Kind::AssociatedFunction { .. } => MetaInfoKind::Associated,
-            meta::Kind::Closure { .. } => MetaInfoKind::Closure,
-            meta::Kind::AsyncBlock { .. } => MetaInfoKind::AsyncBlock,
-            meta::Kind::Const { .. } => MetaInfoKind::Const,
-            meta::Kind::ConstFn { .. } => MetaInfoKind::ConstFn,
-            meta::Kind::Import { .. } => MetaInfoKind::Import,
-            meta::Kind::Module { .. } => MetaInfoKind::Module,
+            meta::Kind::Type { .. } => MetaKind::Type,
+            meta::Kind::Struct { .. } => MetaKind::Struct,
+            meta::Kind::Variant { .. } => MetaKind::Variant,
+            meta::Kind::Enum { .. } => MetaKind::Enum,
+            meta::Kind::Macro { .. } => MetaKind::Macro,
+            meta::Kind::AttributeMacro { .. } => MetaKind::AttributeMacro,
+            meta::Kind::Function { .. } => MetaKind::Function,
+            meta::Kind::AssociatedFunction { .. } => MetaKind::Associated,
+            meta::Kind::Closure { .. } => MetaKind::Closure,
+            meta::Kind::AsyncBlock { .. } => MetaKind::AsyncBlock,
+            meta::Kind::Const { .. } => MetaKind::Const,
+            meta::Kind::ConstFn { .. } => MetaKind::ConstFn,
+            meta::Kind::Import { .. } => MetaKind::Import,
+            meta::Kind::Module { .. } => MetaKind::Module,
         }
     }
 }
@@ -0,0 +1,383 @@
+use crate::no_std::collections::HashMap;
+use crate::no_std::prelude::*;
+
+use crate::compile::meta;
+use crate::compile::{
+    CompileVisitor, ComponentRef, Context, Item, ItemBuf, Located, MetaKind, MetaRef,
+};
+use crate::hash::Hash;
+
+/// An attribute which can be associated with a [MetaEntry].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MetaAttribute {
+    /// The item is a function annotated with `#[test]`.
+    Test,
+    /// The item is a function annotated with `#[bench]`.
+    Bench,
+    /// The item has documentation comments.
+    Doc,
+}
+
+/// A single item collected by a [MetaIndex].
+#[derive(Debug, Clone)]
+pub struct MetaEntry {
+    item: ItemBuf,
+    hash: Hash,
+    kind: MetaKind,
+    args: Option<usize>,
+    is_test: bool,
+    is_bench: bool,
+    from_context: bool,
+    docs: Vec<String>,
+}
+
+impl MetaEntry {
+    fn new(item: &Item, hash: Hash, kind: &meta::Kind, from_context: bool) -> Self {
+        let (is_test, is_bench) = match kind {
+            meta::Kind::Function {
+                is_test, is_bench, ..
+            } => (*is_test, *is_bench),
+            _ => (false, false),
+        };
+
+        Self {
+            item: item.to_owned(),
+            hash,
+            kind: MetaKind::from_kind(kind),
+            args: kind.as_signature().and_then(|signature| signature.args),
+            is_test,
+            is_bench,
+            from_context,
+            docs: Vec::new(),
+        }
+    }
+
+    /// The item of the entry.
+    pub fn item(&self) -> &Item {
+        &self.item
+    }
+
+    /// The hash of the entry.
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// The kind of the entry.
+    pub fn kind(&self) -> MetaKind {
+        self.kind
+    }
+
+    /// The number of arguments the entry accepts, if it's callable and the
+    /// number of arguments is known.
+    pub fn args(&self) -> Option<usize> {
+        self.args
+    }
+
+    /// Test if the entry has the given attribute.
+    pub fn has_attribute(&self, attribute: MetaAttribute) -> bool {
+        match attribute {
+            MetaAttribute::Test => self.is_test,
+            MetaAttribute::Bench => self.is_bench,
+            MetaAttribute::Doc => !self.docs.is_empty(),
+        }
+    }
+
+    /// Test if the entry was provided by a [Context], as opposed to being
+    /// compiled from sources.
+    pub fn is_from_context(&self) -> bool {
+        self.from_context
+    }
+
+    /// Documentation lines associated with the entry.
+    ///
+    /// Documentation is only collected for items compiled from sources.
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+}
+
+/// A [CompileVisitor] which collects compile metadata so that it can be
+/// queried by item pattern once compilation is done.
+///
+/// Items are matched against patterns where components are separated by `::`.
+/// A component of `*` matches any single component and can be combined with
+/// other characters like `get_*`, while a component of `**` matches any number
+/// of components, including none.
+///
+/// # Examples
+///
+/// ```
+/// use rune::compile::{MetaIndex, MetaKind};
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         mod handlers {
+///             pub fn index(request, response) {}
+///             pub fn health() {}
+///         }
+///
+///         pub fn main() {}
+///     }
+/// };
+///
+/// let context = rune::Context::with_default_modules()?;
+/// let mut index = MetaIndex::new();
+///
+/// rune::prepare(&mut sources)
+///     .with_context(&context)
+///     .with_visitor(&mut index)
+///     .build()?;
+///
+/// let found = index
+///     .query("handlers::*")
+///     .kind(MetaKind::Function)
+///     .args(2)
+///     .map(|entry| entry.item().to_string())
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(found, ["handlers::index"]);
+/// # Ok::<_, rune::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct MetaIndex {
+    entries: Vec<MetaEntry>,
+    by_hash: HashMap<Hash, Vec<usize>>,
+    pending_docs: HashMap<Hash, Vec<String>>,
+}
+
+impl MetaIndex {
+    /// Construct a new empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add all metadata from the given [Context] to the index, so that it can
+    /// be queried alongside the metadata of compiled sources.
+    pub fn with_context(mut self, context: &Context) -> Self {
+        for meta in context.iter_meta() {
+            if let Some(item) = &meta.item {
+                self.insert(MetaEntry::new(item, meta.hash, &meta.kind, true));
+            }
+        }
+
+        self
+    }
+
+    /// Iterate over all entries in the index, in the order in which they were
+    /// collected.
+    pub fn iter(&self) -> impl Iterator<Item = &MetaEntry> {
+        self.entries.iter()
+    }
+
+    /// Query the index for entries whose item matches the given pattern.
+    ///
+    /// The returned query can be further narrowed down with filters before
+    /// being iterated over.
+    pub fn query(&self, pattern: &str) -> MetaQuery<'_> {
+        MetaQuery {
+            entries: self.entries.iter(),
+            pattern: Pattern::parse(pattern),
+            kind: None,
+            args: None,
+            attributes: Vec::new(),
+            sources_only: false,
+        }
+    }
+
+    fn insert(&mut self, mut entry: MetaEntry) {
+        if let Some(docs) = self.pending_docs.remove(&entry.hash) {
+            entry.docs = docs;
+        }
+
+        self.by_hash
+            .entry(entry.hash)
+            .or_default()
+            .push(self.entries.len());
+        self.entries.push(entry);
+    }
+}
+
+impl CompileVisitor for MetaIndex {
+    fn register_meta(&mut self, meta: MetaRef<'_>) {
+        self.insert(MetaEntry::new(meta.item, meta.hash, meta.kind, false));
+    }
+
+    fn visit_doc_comment(&mut self, _: &dyn Located, _: &Item, hash: Hash, docstr: &str) {
+        match self.by_hash.get(&hash) {
+            Some(indexes) => {
+                for &index in indexes {
+                    self.entries[index].docs.push(docstr.to_owned());
+                }
+            }
+            None => {
+                self.pending_docs
+                    .entry(hash)
+                    .or_default()
+                    .push(docstr.to_owned());
+            }
+        }
+    }
+}
+
+/// A query over a [MetaIndex], constructed through [MetaIndex::query].
+///
+/// This is an iterator over all matching entries.
+pub struct MetaQuery<'a> {
+    entries: core::slice::Iter<'a, MetaEntry>,
+    pattern: Pattern,
+    kind: Option<MetaKind>,
+    args: Option<usize>,
+    attributes: Vec<MetaAttribute>,
+    sources_only: bool,
+}
+
+impl MetaQuery<'_> {
+    /// Only match entries of the given kind.
+    pub fn kind(mut self, kind: MetaKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Only match entries which accept the given number of arguments.
+    pub fn args(mut self, args: usize) -> Self {
+        self.args = Some(args);
+        self
+    }
+
+    /// Only match entries which have the given attribute.
+    ///
+    /// This can be called multiple times, in which case entries must have all
+    /// of the given attributes.
+    pub fn attribute(mut self, attribute: MetaAttribute) -> Self {
+        self.attributes.push(attribute);
+        self
+    }
+
+    /// Only match entries which were compiled from sources, as opposed to
+    /// being provided by a [Context].
+    pub fn sources_only(mut self) -> Self {
+        self.sources_only = true;
+        self
+    }
+
+    fn matches(&self, entry: &MetaEntry) -> bool {
+        if matches!(self.kind, Some(kind) if kind != entry.kind) {
+            return false;
+        }
+
+        if self.args.is_some() && self.args != entry.args {
+            return false;
+        }
+
+        if self.sources_only && entry.from_context {
+            return false;
+        }
+
+        if !self.attributes.iter().all(|a| entry.has_attribute(*a)) {
+            return false;
+        }
+
+        self.pattern.matches(&entry.item)
+    }
+}
+
+impl<'a> Iterator for MetaQuery<'a> {
+    type Item = &'a MetaEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let entry = self.entries.next()?;
+
+            if self.matches(entry) {
+                return Some(entry);
+            }
+        }
+    }
+}
+
+/// A parsed item pattern.
+#[derive(Debug)]
+struct Pattern {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug)]
+enum Segment {
+    /// Matches any number of components.
+    Any,
+    /// Matches a single component, where `*` matches any sequence of
+    /// characters.
+    Glob(Box<str>),
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> Self {
+        let pattern = pattern.trim_start_matches("::");
+
+        let segments = pattern
+            .split("::")
+            .filter(|s| !s.is_empty())
+            .map(|s| match s {
+                "**" => Segment::Any,
+                s => Segment::Glob(s.into()),
+            })
+            .collect();
+
+        Self { segments }
+    }
+
+    fn matches(&self, item: &Item) -> bool {
+        let components = item.iter().collect::<Vec<_>>();
+        match_segments(&self.segments, &components)
+    }
+}
+
+fn match_segments(segments: &[Segment], components: &[ComponentRef<'_>]) -> bool {
+    let Some((first, rest)) = segments.split_first() else {
+        return components.is_empty();
+    };
+
+    match first {
+        Segment::Any => (0..=components.len()).any(|n| match_segments(rest, &components[n..])),
+        Segment::Glob(glob) => match components.split_first() {
+            Some((c, components)) => match_component(glob, *c) && match_segments(rest, components),
+            None => false,
+        },
+    }
+}
+
+fn match_component(glob: &str, component: ComponentRef<'_>) -> bool {
+    match component {
+        ComponentRef::Crate(name) | ComponentRef::Str(name) => match_glob(glob, name),
+        ComponentRef::Id(..) => glob == "*",
+    }
+}
+
+/// Match a string against a glob where `*` matches any sequence of characters.
+fn match_glob(glob: &str, string: &str) -> bool {
+    let mut parts = glob.split('*');
+
+    let Some(first) = parts.next() else {
+        return string.is_empty();
+    };
+
+    let Some(mut rest) = string.strip_prefix(first) else {
+        return false;
+    };
+
+    let mut parts = parts.collect::<Vec<_>>();
+
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+
+    for part in parts {
+        match rest.find(part) {
+            Some(n) => rest = &rest[n + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.len() >= last.len() && rest.ends_with(last)
+}
@@ -1,7 +1,11 @@
 use core::fmt;
 
+use crate::no_std::collections::HashMap;
 use crate::no_std::prelude::*;
 
+use crate::compile::{Optimization, PassManager};
+use crate::runtime::ConstValue;
+
 /// Error raised when trying to parse an invalid option.
 #[derive(Debug, Clone)]
 pub struct ParseOptionError {
@@ -16,6 +20,25 @@ impl fmt::Display for ParseOptionError {
 
 impl crate::no_std::error::Error for ParseOptionError {}
 
+/// The level of debug information included in a compiled unit.
+///
+/// Lower levels produce smaller units, at the cost of less detailed
+/// diagnostics. Errors raised by a unit compiled without debug information
+/// are still reported, but without referencing the source they came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum DebugLevel {
+    /// No debug information is included.
+    None,
+    /// Function signatures and the source location of every instruction are
+    /// included, which is enough to produce backtraces.
+    Lines,
+    /// Everything in [`DebugLevel::Lines`], as well as instruction comments
+    /// and labels, argument and variable names, and identifiers used to
+    /// describe errors.
+    Full,
+}
+
 /// Options that can be provided to the compiler.
 ///
 /// See [Build::with_options][crate::Build::with_options].
@@ -25,18 +48,33 @@ pub struct Options {
     pub(crate) link_checks: bool,
     /// Memoize the instance function in a loop.
     pub(crate) memoize_instance_fn: bool,
-    /// Include debug information when compiling.
-    pub(crate) debug_info: bool,
+    /// The level of debug information to include when compiling.
+    pub(crate) debug_level: DebugLevel,
     /// Support (experimental) macros.
     pub(crate) macros: bool,
     /// Support (experimental) bytecode caching.
     pub(crate) bytecode: bool,
+    /// Verify bytecode which is loaded from the cache.
+    pub(crate) bytecode_verify: bool,
     /// Compile for and enable test features
     pub(crate) cfg_test: bool,
     /// Use the second version of the compiler in parallel.
     pub(crate) v2: bool,
     /// Build sources as function bodies.
     pub(crate) function_body: bool,
+    /// Resolve instance calls directly when the type of the instance is known.
+    pub(crate) early_bound: bool,
+    /// Check type annotations of function arguments at runtime.
+    pub(crate) type_checks: bool,
+    /// The number of evaluation steps constant evaluation is allowed to take.
+    pub(crate) const_budget: usize,
+    /// How deeply constant functions are allowed to call each other.
+    pub(crate) const_recursion_limit: usize,
+    /// Values of constants declared with `extern const`, indexed by their
+    /// item.
+    pub(crate) extern_consts: HashMap<String, ConstValue>,
+    /// The optimization passes to perform.
+    pub(crate) passes: PassManager,
 }
 
 impl Options {
@@ -54,7 +92,23 @@ impl Options {
                 self.memoize_instance_fn = it.next() == Some("true");
             }
             Some("debug-info") => {
-                self.debug_info = it.next() == Some("true");
+                self.debug_level = if it.next() == Some("true") {
+                    DebugLevel::Full
+                } else {
+                    DebugLevel::None
+                };
+            }
+            Some("debug-level") => {
+                self.debug_level = match it.next() {
+                    Some("none") => DebugLevel::None,
+                    Some("lines") => DebugLevel::Lines,
+                    Some("full") => DebugLevel::Full,
+                    _ => {
+                        return Err(ParseOptionError {
+                            option: option.into(),
+                        });
+                    }
+                };
             }
             Some("link-checks") => {
                 self.link_checks = it.next() == Some("true");
@@ -65,6 +119,9 @@ impl Options {
             Some("bytecode") => {
                 self.bytecode = it.next() == Some("true");
             }
+            Some("bytecode-verify") => {
+                self.bytecode_verify = it.next() == Some("true");
+            }
             Some("test") => {
                 self.cfg_test = it.next() == Some("true");
             }
@@ -74,6 +131,46 @@ impl Options {
             Some("function-body") => {
                 self.function_body = it.next() == Some("true");
             }
+            Some("early-bound") => {
+                self.early_bound = it.next() == Some("true");
+            }
+            Some("type-checks") => {
+                self.type_checks = it.next() == Some("true");
+            }
+            Some("const-budget") => {
+                self.const_budget = parse_usize(option, it.next())?;
+            }
+            Some("const-recursion-limit") => {
+                self.const_recursion_limit = parse_usize(option, it.next())?;
+            }
+            Some("extern-const") => {
+                let Some((name, value)) = option
+                    .split_once('=')
+                    .and_then(|(_, rest)| rest.split_once('='))
+                else {
+                    return Err(ParseOptionError {
+                        option: option.into(),
+                    });
+                };
+
+                self.extern_const(name, ConstValue::String(value.into()));
+            }
+            Some("jump-threading-depth") => {
+                self.passes
+                    .jump_threading_depth(parse_usize(option, it.next())?);
+            }
+            Some("dump-passes") => {
+                self.passes.dump(it.next() == Some("true"));
+            }
+            Some(name) if name.starts_with("pass-") => {
+                let Some(optimization) = Optimization::from_name(&name[5..]) else {
+                    return Err(ParseOptionError {
+                        option: option.into(),
+                    });
+                };
+
+                self.passes.enable(optimization, it.next() == Some("true"));
+            }
             _ => {
                 return Err(ParseOptionError {
                     option: option.into(),
@@ -90,8 +187,21 @@ impl Options {
     }
 
     /// Set if debug info is enabled or not. Defaults to `true`.
+    ///
+    /// This is the same as setting the debug level to either
+    /// [`DebugLevel::Full`] or [`DebugLevel::None`].
     pub fn debug_info(&mut self, enabled: bool) {
-        self.debug_info = enabled;
+        self.debug_level = if enabled {
+            DebugLevel::Full
+        } else {
+            DebugLevel::None
+        };
+    }
+
+    /// Set the level of debug information to include in the unit. Defaults to
+    /// [`DebugLevel::Full`].
+    pub fn debug_level(&mut self, level: DebugLevel) {
+        self.debug_level = level;
     }
 
     /// Set if link checks are enabled or not. Defaults to `true`. This will
@@ -111,10 +221,112 @@ impl Options {
         self.bytecode = enabled;
     }
 
+    /// Set if bytecode loaded from the cache should be verified before it's
+    /// used. Defaults to `false`.
+    ///
+    /// Verification requires every function in the unit to be decoded, so
+    /// with a lazily decoded unit this gives up on the startup cost saved by
+    /// only decoding functions as they are called.
+    pub fn bytecode_verify(&mut self, enabled: bool) {
+        self.bytecode_verify = enabled;
+    }
+
     /// Memoize the instance function in a loop. Defaults to `false`.
     pub fn memoize_instance_fn(&mut self, enabled: bool) {
         self.memoize_instance_fn = enabled;
     }
+
+    /// Set if instance calls should be early-bound when the type of the
+    /// instance can be determined at compile time. Defaults to `false`.
+    ///
+    /// Early-bound calls use a precomputed function hash and skip looking up
+    /// the type of the instance at runtime. The type is only considered known
+    /// for literals, and for variables bound to them which are never assigned
+    /// to.
+    pub fn early_bound(&mut self, enabled: bool) {
+        self.early_bound = enabled;
+    }
+
+    /// Set if type annotations of function arguments, like `a: i64` in
+    /// `fn f(a: i64)`, should be checked. Defaults to `true`.
+    ///
+    /// When enabled, calling a function with an argument of the wrong type
+    /// results in an error. When disabled, type annotations are ignored
+    /// entirely and are not even required to refer to an existing type.
+    pub fn type_checks(&mut self, enabled: bool) {
+        self.type_checks = enabled;
+    }
+
+    /// Set the number of evaluation steps constant evaluation is allowed to
+    /// take for a single constant. Defaults to `1000000`.
+    ///
+    /// Exceeding the budget is a compile error, which protects the compiler
+    /// against constant expressions which never terminate.
+    pub fn const_budget(&mut self, budget: usize) {
+        self.const_budget = budget;
+    }
+
+    /// Set how deeply constant functions are allowed to call each other during
+    /// constant evaluation. Defaults to `64`.
+    pub fn const_recursion_limit(&mut self, limit: usize) {
+        self.const_recursion_limit = limit;
+    }
+
+    /// Provide the value of a constant declared in a script with
+    /// `extern const <name>;`.
+    ///
+    /// The name is the full path of the constant, like `BUILD_ID` or
+    /// `config::BUILD_ID` for a constant declared in the `config` module.
+    /// Compilation fails if a script declares an external constant which
+    /// doesn't have a value.
+    ///
+    /// Through [Options::parse_option] external constants can be provided as
+    /// strings with `extern-const=<name>=<value>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Options, Sources};
+    /// use rune::runtime::ConstValue;
+    ///
+    /// let mut options = Options::default();
+    /// options.extern_const("BUILD_ID", ConstValue::Integer(42));
+    ///
+    /// let mut sources = Sources::new();
+    /// sources.insert(rune::Source::new(
+    ///     "main",
+    ///     "extern const BUILD_ID; pub fn main() { BUILD_ID }",
+    /// ));
+    ///
+    /// rune::prepare(&mut sources).with_options(&options).build()?;
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn extern_const(&mut self, name: &str, value: ConstValue) {
+        self.extern_consts.insert(name.into(), value);
+    }
+
+    /// Access the optimization passes performed by the compiler.
+    ///
+    /// Through [Options::parse_option] passes can be configured with
+    /// `pass-<name>=true|false` using the name of the [Optimization],
+    /// `jump-threading-depth=<n>` and `dump-passes=true`.
+    pub fn passes(&self) -> &PassManager {
+        &self.passes
+    }
+
+    /// Modify the optimization passes performed by the compiler.
+    pub fn passes_mut(&mut self) -> &mut PassManager {
+        &mut self.passes
+    }
+}
+
+fn parse_usize(option: &str, value: Option<&str>) -> Result<usize, ParseOptionError> {
+    match value.and_then(|value| value.parse().ok()) {
+        Some(value) => Ok(value),
+        None => Err(ParseOptionError {
+            option: option.into(),
+        }),
+    }
 }
 
 impl Default for Options {
@@ -122,12 +334,19 @@ impl Default for Options {
         Self {
             link_checks: true,
             memoize_instance_fn: true,
-            debug_info: true,
+            debug_level: DebugLevel::Full,
             macros: true,
             bytecode: false,
+            bytecode_verify: false,
             cfg_test: false,
             v2: false,
             function_body: false,
+            early_bound: false,
+            type_checks: true,
+            const_budget: 1_000_000,
+            const_recursion_limit: 64,
+            extern_consts: HashMap::new(),
+            passes: PassManager::default(),
         }
     }
 }
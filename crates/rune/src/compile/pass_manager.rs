@@ -0,0 +1,385 @@
+use core::fmt;
+
+use crate::no_std::collections::HashMap;
+use crate::no_std::prelude::*;
+
+use crate::compile::assembly::{Assembly, AssemblyInst};
+use crate::runtime::{Inst, Label};
+
+/// An optimization performed by the compiler, which can be enabled or
+/// disabled through the [PassManager].
+///
+/// Every optimization has a stable name, as returned by
+/// [Optimization::name], which is used to refer to it in compiler options
+/// and when dumping the assembly produced by it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Optimization {
+    /// Evaluate templates which only consist of constants at compile time.
+    ConstFold,
+    /// Eliminate calls to pure functions whose results are unused.
+    DeadCalls,
+    /// Compile `for` loops over integer ranges into counter loops, instead of
+    /// constructing an iterator.
+    RangeLoops,
+    /// Redirect jumps which lead to another unconditional jump straight to
+    /// its final destination.
+    JumpThreading,
+    /// Remove instructions which can never be reached.
+    DeadCode,
+}
+
+impl Optimization {
+    /// Every optimization, in the order they are performed.
+    pub const ALL: [Optimization; 5] = [
+        Optimization::ConstFold,
+        Optimization::DeadCalls,
+        Optimization::RangeLoops,
+        Optimization::JumpThreading,
+        Optimization::DeadCode,
+    ];
+
+    /// The stable name of the optimization.
+    pub fn name(self) -> &'static str {
+        match self {
+            Optimization::ConstFold => "const-fold",
+            Optimization::DeadCalls => "dead-calls",
+            Optimization::RangeLoops => "range-loops",
+            Optimization::JumpThreading => "jump-threading",
+            Optimization::DeadCode => "dead-code",
+        }
+    }
+
+    /// Look up an optimization by its stable name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|o| o.name() == name)
+    }
+
+    /// Test if the optimization is a pass over the assembly of a function,
+    /// after which the assembly can be dumped.
+    pub(crate) fn is_assembly_pass(self) -> bool {
+        matches!(self, Optimization::JumpThreading | Optimization::DeadCode)
+    }
+}
+
+impl fmt::Display for Optimization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Configures the optimizations performed by the compiler.
+///
+/// Accessed through [Options::passes][crate::Options::passes] and
+/// [Options::passes_mut][crate::Options::passes_mut]. Every optimization is
+/// enabled by default.
+///
+/// # Examples
+///
+/// ```
+/// use rune::Options;
+/// use rune::compile::Optimization;
+///
+/// let mut options = Options::default();
+/// options.passes_mut().enable(Optimization::DeadCode, false);
+/// options.passes_mut().dump(true);
+///
+/// assert!(!options.passes().is_enabled(Optimization::DeadCode));
+///
+/// // Options can also be parsed, like from the command line.
+/// options.parse_option("pass-dead-code=true")?;
+/// assert!(options.passes().is_enabled(Optimization::DeadCode));
+/// # Ok::<_, rune::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PassManager {
+    /// Optimizations which have been disabled.
+    disabled: Vec<Optimization>,
+    /// The longest chain of jumps which is threaded.
+    jump_threading_depth: usize,
+    /// Dump the assembly after each pass.
+    dump: bool,
+}
+
+impl PassManager {
+    /// Enable or disable the given optimization.
+    pub fn enable(&mut self, optimization: Optimization, enabled: bool) {
+        self.disabled.retain(|o| *o != optimization);
+
+        if !enabled {
+            self.disabled.push(optimization);
+        }
+    }
+
+    /// Test if the given optimization is enabled.
+    pub fn is_enabled(&self, optimization: Optimization) -> bool {
+        !self.disabled.contains(&optimization)
+    }
+
+    /// Set the longest chain of jumps which is followed when threading jumps.
+    /// Defaults to `8`.
+    pub fn jump_threading_depth(&mut self, depth: usize) {
+        self.jump_threading_depth = depth;
+    }
+
+    /// Set if the assembly of every function should be dumped after each
+    /// pass over it. Defaults to `false`.
+    ///
+    /// Dumps are provided to
+    /// [CompileVisitor::visit_assembly_dump][crate::compile::CompileVisitor::visit_assembly_dump],
+    /// starting with the assembly before any passes have been performed.
+    pub fn dump(&mut self, enabled: bool) {
+        self.dump = enabled;
+    }
+
+    /// Test if dumping assembly is enabled.
+    pub(crate) fn is_dump(&self) -> bool {
+        self.dump
+    }
+
+    /// The longest chain of jumps which is threaded.
+    pub(crate) fn threading_depth(&self) -> usize {
+        self.jump_threading_depth
+    }
+
+    /// Perform the given pass over the assembly of a function.
+    pub(crate) fn run(&self, optimization: Optimization, asm: &mut Assembly) {
+        match optimization {
+            Optimization::JumpThreading => jump_threading(asm, self.jump_threading_depth),
+            Optimization::DeadCode => dead_code(asm),
+            _ => {}
+        }
+    }
+}
+
+impl Default for PassManager {
+    fn default() -> Self {
+        Self {
+            disabled: Vec::new(),
+            jump_threading_depth: 8,
+            dump: false,
+        }
+    }
+}
+
+/// Redirect jumps to labels pointing to an unconditional jump to the final
+/// destination of that jump.
+fn jump_threading(asm: &mut Assembly, depth: usize) {
+    let mut targets = HashMap::new();
+
+    for (&pos, (_, labels)) in &asm.labels {
+        for label in labels {
+            targets.insert(label.index, pos);
+        }
+    }
+
+    for n in 0..asm.instructions.len() {
+        let Some(mut label) = label_of(&asm.instructions[n].0).cloned() else {
+            continue;
+        };
+
+        for _ in 0..depth {
+            let next = match targets.get(&label.index) {
+                Some(&pos) => asm.instructions.get(pos),
+                None => None,
+            };
+
+            match next {
+                Some((AssemblyInst::Jump { label: next }, _)) if next.index != label.index => {
+                    label = next.clone();
+                }
+                _ => break,
+            }
+        }
+
+        if let Some(current) = label_mut(&mut asm.instructions[n].0) {
+            *current = label;
+        }
+    }
+}
+
+/// Remove instructions following an instruction which never continues to the
+/// next instruction, up until the next label.
+fn dead_code(asm: &mut Assembly) {
+    let mut removed = vec![false; asm.instructions.len()];
+    let mut dead = false;
+
+    for (pos, (inst, _)) in asm.instructions.iter().enumerate() {
+        if asm.labels.contains_key(&pos) {
+            dead = false;
+        }
+
+        removed[pos] = dead;
+
+        if is_terminator(inst) {
+            dead = true;
+        }
+    }
+
+    if !removed.contains(&true) {
+        return;
+    }
+
+    // Map every position to its position after instructions are removed.
+    let mut positions = Vec::with_capacity(removed.len() + 1);
+    let mut pos = 0;
+
+    for &removed in &removed {
+        positions.push(pos);
+        pos += usize::from(!removed);
+    }
+
+    positions.push(pos);
+
+    let mut n = 0;
+    asm.instructions.retain(|_| {
+        n += 1;
+        !removed[n - 1]
+    });
+
+    asm.labels = asm
+        .labels
+        .drain()
+        .map(|(pos, labels)| (positions[pos], labels))
+        .collect();
+
+    asm.comments = asm
+        .comments
+        .drain()
+        .filter(|(pos, _)| !removed.get(*pos).copied().unwrap_or_default())
+        .map(|(pos, comment)| (positions[pos], comment))
+        .collect();
+
+    for (pos, _, _) in &mut asm.variables {
+        *pos = positions[*pos];
+    }
+}
+
+/// Test if the instruction never continues to the next instruction.
+fn is_terminator(inst: &AssemblyInst) -> bool {
+    matches!(
+        inst,
+        AssemblyInst::Jump { .. }
+            | AssemblyInst::Raw {
+                raw: Inst::Return { .. }
+                    | Inst::ReturnUnit
+                    | Inst::ReturnTuple { .. }
+                    | Inst::Panic { .. }
+            }
+    )
+}
+
+fn label_of(inst: &AssemblyInst) -> Option<&Label> {
+    match inst {
+        AssemblyInst::Jump { label }
+        | AssemblyInst::JumpIf { label }
+        | AssemblyInst::JumpIfOrPop { label }
+        | AssemblyInst::JumpIfNotOrPop { label }
+        | AssemblyInst::JumpIfBranch { label, .. }
+        | AssemblyInst::PopAndJumpIfNot { label, .. }
+        | AssemblyInst::IterNext { label, .. }
+        | AssemblyInst::IterStep { label, .. }
+        | AssemblyInst::TryOrJump { label, .. } => Some(label),
+        AssemblyInst::Raw { .. } => None,
+    }
+}
+
+fn label_mut(inst: &mut AssemblyInst) -> Option<&mut Label> {
+    match inst {
+        AssemblyInst::Jump { label }
+        | AssemblyInst::JumpIf { label }
+        | AssemblyInst::JumpIfOrPop { label }
+        | AssemblyInst::JumpIfNotOrPop { label }
+        | AssemblyInst::JumpIfBranch { label, .. }
+        | AssemblyInst::PopAndJumpIfNot { label, .. }
+        | AssemblyInst::IterNext { label, .. }
+        | AssemblyInst::IterStep { label, .. }
+        | AssemblyInst::TryOrJump { label, .. } => Some(label),
+        AssemblyInst::Raw { .. } => None,
+    }
+}
+
+/// Format the given assembly for dumping.
+pub(crate) fn dump_assembly(asm: &Assembly) -> String {
+    use core::fmt::Write;
+
+    let mut out = String::new();
+
+    for pos in 0..=asm.instructions.len() {
+        if let Some((_, labels)) = asm.labels.get(&pos) {
+            for label in labels {
+                let _ = writeln!(out, "{}_{}:", label.name, label.index);
+            }
+        }
+
+        let Some((inst, _)) = asm.instructions.get(pos) else {
+            continue;
+        };
+
+        let _ = match inst {
+            AssemblyInst::Jump { label } => write!(out, "  {pos:04} = jump {}", Target(label)),
+            AssemblyInst::JumpIf { label } => {
+                write!(out, "  {pos:04} = jump-if {}", Target(label))
+            }
+            AssemblyInst::JumpIfOrPop { label } => {
+                write!(out, "  {pos:04} = jump-if-or-pop {}", Target(label))
+            }
+            AssemblyInst::JumpIfNotOrPop { label } => {
+                write!(out, "  {pos:04} = jump-if-not-or-pop {}", Target(label))
+            }
+            AssemblyInst::JumpIfBranch { branch, label } => write!(
+                out,
+                "  {pos:04} = jump-if-branch branch={branch}, {}",
+                Target(label)
+            ),
+            AssemblyInst::PopAndJumpIfNot { count, label } => write!(
+                out,
+                "  {pos:04} = pop-and-jump-if-not count={count}, {}",
+                Target(label)
+            ),
+            AssemblyInst::IterNext { offset, label } => write!(
+                out,
+                "  {pos:04} = iter-next offset={offset}, {}",
+                Target(label)
+            ),
+            AssemblyInst::IterStep {
+                offset,
+                end,
+                step,
+                inclusive,
+                label,
+            } => write!(
+                out,
+                "  {pos:04} = iter-step offset={offset}, end={end}, step={step}, inclusive={inclusive}, {}",
+                Target(label)
+            ),
+            AssemblyInst::TryOrJump {
+                address,
+                preserve,
+                label,
+            } => write!(
+                out,
+                "  {pos:04} = try-or-jump address={address}, preserve={preserve}, {}",
+                Target(label)
+            ),
+            AssemblyInst::Raw { raw } => write!(out, "  {pos:04} = {raw}"),
+        };
+
+        if let Some(comment) = asm.comments.get(&pos) {
+            let _ = write!(out, " // {comment}");
+        }
+
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Helper to format the target of a jump.
+struct Target<'a>(&'a Label);
+
+impl fmt::Display for Target<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "label={}_{}", self.0.name, self.0.index)
+    }
+}
@@ -4,6 +4,7 @@ use core::fmt;
 
 use crate::no_std::collections::HashMap;
 
+use crate::compile::attrs::StrictArgs;
 #[cfg(feature = "emit")]
 use crate::compile::Location;
 use crate::compile::{Item, ItemBuf, Visibility};
@@ -44,6 +45,8 @@ pub(crate) struct ModMeta {
     pub(crate) visibility: Visibility,
     /// The kind of the module.
     pub(crate) parent: Option<ModId>,
+    /// Strictness enabled for the module.
+    pub(crate) strict: StrictArgs,
 }
 
 impl ModMeta {
@@ -115,6 +118,16 @@ impl Pool {
         }
     }
 
+    /// Lookup mutable mod meta by the given identifier.
+    pub(crate) fn module_mut(&mut self, ModId(id): ModId) -> &mut ModMeta {
+        let id = usize::try_from(id).expect("module id overflow");
+
+        match self.modules.get_mut(id) {
+            Some(item) => item,
+            None => panic!("missing module by id {id}"),
+        }
+    }
+
     /// Get the item associated with a module.
     pub(crate) fn module_item(&self, id: ModId) -> &Item {
         let id = self.module(id).item;
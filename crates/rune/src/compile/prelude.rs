@@ -24,6 +24,9 @@ impl Prelude {
         this.add_prelude("u8", ["u8"]);
         this.add_prelude("f64", ["f64"]);
         this.add_prelude("i64", ["i64"]);
+        this.add_prelude("include_bytes", ["macros", "builtin", "include_bytes"]);
+        this.add_prelude("include_data", ["macros", "builtin", "include_data"]);
+        this.add_prelude("include_str", ["macros", "builtin", "include_str"]);
         this.add_prelude("char", ["char"]);
         this.add_prelude("dbg", ["io", "dbg"]);
         this.add_prelude("drop", ["mem", "drop"]);
@@ -34,6 +37,7 @@ impl Prelude {
         this.add_prelude("is_writable", ["is_writable"]);
         this.add_prelude("line", ["macros", "builtin", "line"]);
         this.add_prelude("None", ["option", "Option", "None"]);
+        this.add_prelude("tr", ["i18n", "tr"]);
         this.add_prelude("Tuple", ["tuple", "Tuple"]);
         this.add_prelude("Object", ["object", "Object"]);
         this.add_prelude("Ok", ["result", "Result", "Ok"]);
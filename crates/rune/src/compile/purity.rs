@@ -0,0 +1,396 @@
+use crate::no_std::prelude::*;
+
+use crate::ast::{self, Span, Spanned};
+use crate::compile::ItemMeta;
+use crate::hir;
+use crate::runtime::Call;
+use crate::Hash;
+
+/// The purity of a function defined in the unit being compiled.
+///
+/// A function is pure if calling it has no effects besides producing a value.
+/// It may only call other pure functions and constructors of structs and
+/// variants, and it may not call functions provided by the context, instance
+/// functions or function values, assign to fields or indexes, or yield and
+/// await. Async functions and generators are never pure, since calling them
+/// doesn't run their body.
+///
+/// Operators, field accesses, indexing, iteration in `for` loops and template
+/// strings are assumed to be free of side effects, even though they might be
+/// implemented through protocols. Calls to functions which use them are still
+/// never eliminated when their result is unused.
+///
+/// The same goes for functions which might not be total, because they loop,
+/// recurse, use operations which might overflow or divide by zero, or match
+/// refutable patterns which might fail. Such functions are pure, but calling
+/// them might not return normally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Purity {
+    /// The function is pure.
+    Pure,
+    /// The function might have side effects.
+    Impure {
+        /// The span of the expression in the function which might have a side
+        /// effect, which is either a side effect of its own or a call to a
+        /// function which is impure.
+        span: Span,
+    },
+}
+
+impl Purity {
+    /// Test if the function is pure.
+    pub fn is_pure(&self) -> bool {
+        matches!(self, Purity::Pure)
+    }
+}
+
+/// The effects of a single function or expression, which are combined with
+/// the summaries of the functions it calls to determine its [Purity].
+#[derive(Default)]
+pub(crate) struct Summary {
+    /// The first expression which has a side effect of its own.
+    pub(crate) effect: Option<Span>,
+    /// The first expression which might call a protocol function, like an
+    /// operator applied to something which isn't a literal. These don't affect
+    /// the [Purity] of a function, but prevent calls to it from being
+    /// eliminated.
+    pub(crate) protocol: Option<Span>,
+    /// The first expression which might not terminate or might raise an error,
+    /// like a loop, a division or a refutable pattern. Like
+    /// [Summary::protocol], these don't affect the [Purity] of a function, but
+    /// prevent calls to it from being eliminated.
+    pub(crate) partial: Option<Span>,
+    /// Calls to functions defined in the unit, which are pure if the function
+    /// being called is.
+    pub(crate) calls: Vec<(Hash, Span)>,
+    /// The declaration of the function, which is kept if it might be pure so
+    /// that it can be evaluated in constant contexts.
+    pub(crate) item_fn: Option<(ItemMeta, Box<ast::ItemFn>)>,
+}
+
+/// Summarize the effects of the function declared by `ast`, which has been
+/// lowered into `hir`.
+pub(crate) fn summarize_fn(
+    item_meta: ItemMeta,
+    ast: &ast::ItemFn,
+    hir: &hir::ItemFn<'_>,
+    call: Call,
+) -> Summary {
+    let mut summary = Summary::default();
+
+    if matches!(call, Call::Immediate) {
+        for arg in hir.args {
+            match arg {
+                hir::FnArg::SelfValue(..) => {}
+                hir::FnArg::Pat(pat) => {
+                    if !is_irrefutable(pat) {
+                        partial(&mut summary, pat.span);
+                    }
+                }
+                // NB: type annotations are checked when the function is
+                // called.
+                hir::FnArg::Typed(pat) => {
+                    partial(&mut summary, pat.span);
+                }
+            }
+        }
+
+        block(&mut summary, &hir.body);
+    } else {
+        summary.effect = Some(ast.span());
+    }
+
+    if summary.effect.is_none() {
+        summary.item_fn = Some((item_meta, Box::new(ast.clone())));
+    }
+
+    summary
+}
+
+/// Summarize the effects of evaluating a single expression.
+pub(crate) fn summarize_expr(hir: &hir::Expr<'_>) -> Summary {
+    let mut summary = Summary::default();
+    expr(&mut summary, hir);
+    summary
+}
+
+fn effect(summary: &mut Summary, span: Span) {
+    if summary.effect.is_none() {
+        summary.effect = Some(span);
+    }
+}
+
+fn protocol(summary: &mut Summary, span: Span) {
+    if summary.protocol.is_none() {
+        summary.protocol = Some(span);
+    }
+}
+
+fn partial(summary: &mut Summary, span: Span) {
+    if summary.partial.is_none() {
+        summary.partial = Some(span);
+    }
+}
+
+fn is_lit(hir: &hir::Expr<'_>) -> bool {
+    matches!(hir.kind, hir::ExprKind::Lit(..))
+}
+
+/// Test if the given pattern matches any value.
+fn is_irrefutable(hir: &hir::Pat<'_>) -> bool {
+    match hir.kind {
+        hir::PatKind::Ignore | hir::PatKind::Path(hir::PatPathKind::Ident(..)) => true,
+        hir::PatKind::At(hir) => is_irrefutable(hir.pat),
+        _ => false,
+    }
+}
+
+/// Test if the given binary operation might overflow, divide by zero or fail
+/// a conversion, even if it's applied to literals.
+fn is_partial_op(op: ast::BinOp) -> bool {
+    matches!(
+        op,
+        ast::BinOp::Add(..)
+            | ast::BinOp::Sub(..)
+            | ast::BinOp::Mul(..)
+            | ast::BinOp::Div(..)
+            | ast::BinOp::Rem(..)
+            | ast::BinOp::Shl(..)
+            | ast::BinOp::Shr(..)
+            | ast::BinOp::As(..)
+            | ast::BinOp::AddAssign(..)
+            | ast::BinOp::SubAssign(..)
+            | ast::BinOp::MulAssign(..)
+            | ast::BinOp::DivAssign(..)
+            | ast::BinOp::RemAssign(..)
+            | ast::BinOp::ShlAssign(..)
+            | ast::BinOp::ShrAssign(..)
+    )
+}
+
+fn block(summary: &mut Summary, hir: &hir::Block<'_>) {
+    for stmt in hir.statements {
+        match stmt {
+            hir::Stmt::Local(local) => {
+                expr(summary, &local.expr);
+
+                if let Some(else_) = local.else_ {
+                    block(summary, else_);
+                } else if !is_irrefutable(&local.pat) {
+                    partial(summary, local.pat.span);
+                }
+            }
+            hir::Stmt::Expr(hir) | hir::Stmt::Semi(hir) => {
+                expr(summary, hir);
+            }
+            hir::Stmt::Defer(hir) => {
+                block(summary, hir);
+            }
+            hir::Stmt::Item(..) => {}
+        }
+    }
+}
+
+fn condition(summary: &mut Summary, hir: &hir::Condition<'_>) {
+    match hir {
+        hir::Condition::Expr(hir) => expr(summary, hir),
+        hir::Condition::ExprLet(hir) => expr(summary, &hir.expr),
+    }
+}
+
+fn exprs(summary: &mut Summary, hir: &[hir::Expr<'_>]) {
+    for hir in hir {
+        expr(summary, hir);
+    }
+}
+
+fn expr(summary: &mut Summary, hir: &hir::Expr<'_>) {
+    // NB: only the first side effect is recorded, so there's no need to look
+    // any further.
+    if summary.effect.is_some() {
+        return;
+    }
+
+    match hir.kind {
+        hir::ExprKind::Variable(..)
+        | hir::ExprKind::Type(..)
+        | hir::ExprKind::Fn(..)
+        | hir::ExprKind::Path
+        | hir::ExprKind::Lit(..)
+        | hir::ExprKind::Const(..)
+        | hir::ExprKind::StaticData(..)
+        | hir::ExprKind::AsyncBlock(..)
+        | hir::ExprKind::CallClosure(..)
+        | hir::ExprKind::Continue(..) => {}
+        hir::ExprKind::Assign(hir) => {
+            if !matches!(hir.lhs.kind, hir::ExprKind::Variable(..)) {
+                effect(summary, hir.lhs.span);
+            }
+
+            expr(summary, &hir.rhs);
+        }
+        hir::ExprKind::Loop(loop_) => {
+            // NB: there's no telling if a loop terminates, even if it has a
+            // condition or breaks out of it.
+            partial(summary, hir.span);
+
+            if let Some(hir) = loop_.condition {
+                condition(summary, hir);
+            }
+
+            block(summary, &loop_.body);
+        }
+        hir::ExprKind::For(hir) => {
+            protocol(summary, hir.iter.span);
+            expr(summary, &hir.iter);
+            block(summary, &hir.body);
+        }
+        hir::ExprKind::Let(hir) => {
+            if !is_irrefutable(&hir.pat) {
+                partial(summary, hir.pat.span);
+            }
+
+            expr(summary, &hir.expr);
+        }
+        hir::ExprKind::If(hir) => {
+            for branch in hir.branches {
+                if let Some(hir) = branch.condition {
+                    condition(summary, hir);
+                }
+
+                block(summary, &branch.block);
+            }
+        }
+        hir::ExprKind::Match(hir) => {
+            expr(summary, &hir.expr);
+
+            for branch in hir.branches {
+                if let Some(hir) = branch.condition {
+                    expr(summary, hir);
+                }
+
+                expr(summary, &branch.body);
+            }
+        }
+        hir::ExprKind::Call(call) => {
+            match call.call {
+                hir::Call::Meta {
+                    hash, local: true, ..
+                } => {
+                    summary.calls.push((hash, hir.span));
+                }
+                hir::Call::Meta {
+                    constructor: true, ..
+                }
+                | hir::Call::ConstFn { .. } => {}
+                _ => {
+                    effect(summary, hir.span);
+                }
+            }
+
+            exprs(summary, call.args);
+        }
+        hir::ExprKind::FieldAccess(access) => {
+            protocol(summary, hir.span);
+            expr(summary, &access.expr);
+        }
+        hir::ExprKind::Binary(binary) => {
+            if binary.op.is_assign() && !matches!(binary.lhs.kind, hir::ExprKind::Variable(..)) {
+                effect(summary, binary.lhs.span);
+            }
+
+            if !is_lit(&binary.lhs) || !is_lit(&binary.rhs) {
+                protocol(summary, hir.span);
+            }
+
+            if is_partial_op(binary.op) {
+                partial(summary, hir.span);
+            }
+
+            expr(summary, &binary.lhs);
+            expr(summary, &binary.rhs);
+        }
+        hir::ExprKind::Unary(unary) => {
+            if !is_lit(&unary.expr) {
+                protocol(summary, hir.span);
+            }
+
+            expr(summary, &unary.expr);
+        }
+        hir::ExprKind::Index(index) => {
+            protocol(summary, hir.span);
+            expr(summary, &index.target);
+            expr(summary, &index.index);
+        }
+        hir::ExprKind::Block(hir) => {
+            block(summary, hir);
+        }
+        hir::ExprKind::Break(hir) => {
+            if let Some(hir) = hir.expr {
+                expr(summary, hir);
+            }
+        }
+        hir::ExprKind::Yield(..) | hir::ExprKind::Await(..) | hir::ExprKind::Select(..) => {
+            effect(summary, hir.span);
+        }
+        hir::ExprKind::Return(hir) => {
+            if let Some(hir) = hir {
+                expr(summary, hir);
+            }
+        }
+        hir::ExprKind::Try(value) => {
+            protocol(summary, hir.span);
+            expr(summary, value);
+        }
+        hir::ExprKind::Group(hir) => {
+            expr(summary, hir);
+        }
+        hir::ExprKind::Object(hir) => {
+            for assign in hir.assignments {
+                expr(summary, &assign.assign);
+            }
+        }
+        hir::ExprKind::Tuple(hir) | hir::ExprKind::Vec(hir) => {
+            exprs(summary, hir.items);
+        }
+        hir::ExprKind::Range(hir) => match hir {
+            hir::ExprRange::RangeFrom { start } => {
+                expr(summary, start);
+            }
+            hir::ExprRange::RangeFull => {}
+            hir::ExprRange::RangeInclusive { start, end }
+            | hir::ExprRange::Range { start, end } => {
+                expr(summary, start);
+                expr(summary, end);
+            }
+            hir::ExprRange::RangeToInclusive { end } | hir::ExprRange::RangeTo { end } => {
+                expr(summary, end);
+            }
+        },
+        hir::ExprKind::Spread(hir) => {
+            for part in hir.parts {
+                match part {
+                    hir::SpreadPart::Items(hir) => exprs(summary, hir),
+                    hir::SpreadPart::Fields(hir) => {
+                        for assign in *hir {
+                            expr(summary, &assign.assign);
+                        }
+                    }
+                    hir::SpreadPart::Spread(hir) => expr(summary, hir),
+                }
+            }
+        }
+        hir::ExprKind::Template(template) => {
+            if !template.exprs.iter().all(is_lit) {
+                protocol(summary, hir.span);
+            }
+
+            exprs(summary, template.exprs);
+        }
+        hir::ExprKind::Format(format) => {
+            protocol(summary, hir.span);
+            expr(summary, &format.value);
+        }
+    }
+}
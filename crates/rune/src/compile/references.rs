@@ -0,0 +1,467 @@
+use core::ops::Range;
+
+use crate::no_std::collections::HashMap;
+use crate::no_std::prelude::*;
+
+use crate::ast::{Span, Spanned};
+use crate::compile::{
+    CompileVisitor, ComponentRef, Located, MetaRef, SemanticToken, SemanticTokenKind,
+};
+use crate::hash::Hash;
+use crate::{SourceId, Sources};
+
+/// A symbol which can be referenced in compiled sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Symbol {
+    /// A language item, like a function or a type, identified by its hash.
+    Item(Hash),
+    /// A local variable, identified by the location of its declaration.
+    Local {
+        /// The source the variable is declared in.
+        source_id: SourceId,
+        /// The span of the variable declaration.
+        span: Span,
+    },
+}
+
+/// How a [`Symbol`] is being referenced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum ReferenceKind {
+    /// The declaration of the symbol.
+    Definition,
+    /// A use of the symbol.
+    Use,
+}
+
+/// A single reference to a [`Symbol`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Reference {
+    /// The source the reference is in.
+    pub source_id: SourceId,
+    /// The span of the reference.
+    ///
+    /// Where possible this only covers the name of the symbol, making it
+    /// suitable as the range of a rename edit.
+    pub span: Span,
+    /// How the symbol is being referenced.
+    pub kind: ReferenceKind,
+    /// The reference is inside of a macro call, so it might have been
+    /// produced by the macro rather than written in the source.
+    ///
+    /// Editing such a reference during a rename might not have the intended
+    /// effect, so it should be presented to the user rather than silently
+    /// rewritten.
+    pub in_macro: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Occurrence {
+    source_id: SourceId,
+    span: Span,
+    kind: ReferenceKind,
+}
+
+/// A [`CompileVisitor`] which collects the definitions and uses of language
+/// items and local variables, to support finding references to and renaming
+/// symbols, and classifying the names in a source through
+/// [`References::semantic_tokens`].
+///
+/// # Examples
+///
+/// ```
+/// use rune::compile::{ReferenceKind, References};
+/// use rune::{Source, Sources};
+///
+/// let code = r#"
+/// fn add(a, b) { a + b }
+/// pub fn main() { add(1, 2) + add(3, 4) }
+/// "#;
+///
+/// let mut sources = Sources::new();
+/// let source_id = sources.insert(Source::new("main", code));
+///
+/// let mut references = References::new();
+///
+/// let _ = rune::prepare(&mut sources)
+///     .with_visitor(&mut references)
+///     .build()?;
+///
+/// let offset = code.find("add(1").unwrap();
+/// let symbol = references.symbol_at(&sources, source_id, offset).unwrap();
+///
+/// let found = references.references(&sources, &symbol);
+/// assert_eq!(found.len(), 3);
+/// assert_eq!(found[0].kind, ReferenceKind::Definition);
+/// assert!(found.iter().all(|r| !r.in_macro));
+/// # Ok::<_, rune::Error>(())
+/// ```
+#[derive(Default)]
+pub struct References {
+    /// The names of language items.
+    names: HashMap<Hash, Box<str>>,
+    /// The classification of language items.
+    kinds: HashMap<Hash, SemanticTokenKind>,
+    /// Occurrences of symbols.
+    symbols: HashMap<Symbol, Vec<Occurrence>>,
+    /// The spans of macro calls, by source.
+    macros: HashMap<SourceId, Vec<Span>>,
+}
+
+impl References {
+    /// Construct a new collection of references.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Find the symbol referenced at the given byte offset in a source.
+    ///
+    /// If references are nested, the innermost one is picked.
+    pub fn symbol_at(
+        &self,
+        sources: &Sources,
+        source_id: SourceId,
+        offset: usize,
+    ) -> Option<Symbol> {
+        let mut found = None::<(Span, Symbol)>;
+
+        for (symbol, occurrences) in &self.symbols {
+            for o in occurrences {
+                if o.source_id != source_id {
+                    continue;
+                }
+
+                let span = self.narrow(sources, symbol, o);
+
+                if !(span.start.into_usize()..=span.end.into_usize()).contains(&offset) {
+                    continue;
+                }
+
+                if matches!(found, Some((existing, _)) if len(existing) <= len(span)) {
+                    continue;
+                }
+
+                found = Some((span, *symbol));
+            }
+        }
+
+        found.map(|(_, symbol)| symbol)
+    }
+
+    /// Get all references to the given symbol, ordered by source and
+    /// position.
+    pub fn references(&self, sources: &Sources, symbol: &Symbol) -> Vec<Reference> {
+        let Some(occurrences) = self.symbols.get(symbol) else {
+            return Vec::new();
+        };
+
+        let mut references = occurrences
+            .iter()
+            .map(|o| Reference {
+                source_id: o.source_id,
+                span: self.narrow(sources, symbol, o),
+                kind: o.kind,
+                in_macro: self.in_macro(o),
+            })
+            .collect::<Vec<_>>();
+
+        references.sort_by_key(|r| (r.source_id, r.span, r.kind));
+        // NB: an item might be resolved at its own declaration, which
+        // narrows down to the same span as its definition.
+        references.dedup_by_key(|r| (r.source_id, r.span));
+        references
+    }
+
+    /// Classify the names of symbols and macros in the given source, ordered
+    /// by position.
+    ///
+    /// Only names which can be located are included, so that the spans of
+    /// tokens never overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::compile::{References, SemanticTokenKind};
+    /// use rune::{Source, Sources};
+    ///
+    /// let code = "const N = 2; fn double(n) { n * N } pub fn main() { double(4) }";
+    ///
+    /// let mut sources = Sources::new();
+    /// let source_id = sources.insert(Source::new("main", code));
+    ///
+    /// let mut references = References::new();
+    ///
+    /// let _ = rune::prepare(&mut sources)
+    ///     .with_visitor(&mut references)
+    ///     .build()?;
+    ///
+    /// let tokens = references
+    ///     .semantic_tokens(&sources, source_id)
+    ///     .into_iter()
+    ///     .map(|t| (&code[t.span.range()], t.kind))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert!(tokens.contains(&("N", SemanticTokenKind::Constant)));
+    /// assert!(tokens.contains(&("double", SemanticTokenKind::Function)));
+    /// assert!(tokens.contains(&("n", SemanticTokenKind::Variable)));
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn semantic_tokens(&self, sources: &Sources, source_id: SourceId) -> Vec<SemanticToken> {
+        let mut tokens = Vec::new();
+
+        for (symbol, occurrences) in &self.symbols {
+            let kind = match symbol {
+                Symbol::Item(hash) => match self.kinds.get(hash) {
+                    Some(kind) => *kind,
+                    None => continue,
+                },
+                Symbol::Local { .. } => SemanticTokenKind::Variable,
+            };
+
+            for o in occurrences {
+                if o.source_id != source_id {
+                    continue;
+                }
+
+                let Some(span) = self.narrow_name(sources, symbol, o) else {
+                    continue;
+                };
+
+                tokens.push(SemanticToken {
+                    span,
+                    kind,
+                    definition: o.kind == ReferenceKind::Definition,
+                });
+            }
+        }
+
+        if let Some(macros) = self.macros.get(&source_id) {
+            for span in macros {
+                let Some(text) = sources.source(source_id, *span) else {
+                    continue;
+                };
+
+                let Some(range) = macro_name(text) else {
+                    continue;
+                };
+
+                let start = span.start.into_usize();
+
+                tokens.push(SemanticToken {
+                    span: Span::new(start + range.start, start + range.end),
+                    kind: SemanticTokenKind::Macro,
+                    definition: false,
+                });
+            }
+        }
+
+        tokens.sort_by_key(|t| (t.span.start, t.span.end, !t.definition));
+
+        let mut end = 0;
+
+        // NB: a name might be located for several symbols, like an item
+        // which is resolved at its own declaration.
+        tokens.retain(|t| {
+            if t.span.start.into_usize() < end {
+                return false;
+            }
+
+            end = t.span.end.into_usize();
+            true
+        });
+
+        tokens
+    }
+
+    fn insert(&mut self, symbol: Symbol, source_id: SourceId, span: Span, kind: ReferenceKind) {
+        let occurrences = self.symbols.entry(symbol).or_default();
+
+        // NB: items might be visited once for every time they're resolved,
+        // including at their own declaration.
+        if occurrences
+            .iter()
+            .any(|o| o.source_id == source_id && o.span == span)
+        {
+            return;
+        }
+
+        occurrences.push(Occurrence {
+            source_id,
+            span,
+            kind,
+        });
+    }
+
+    fn in_macro(&self, o: &Occurrence) -> bool {
+        let Some(macros) = self.macros.get(&o.source_id) else {
+            return false;
+        };
+
+        macros
+            .iter()
+            .any(|m| m.start <= o.span.start && o.span.end <= m.end)
+    }
+
+    /// Narrow the span of an occurrence down to the name of the symbol.
+    fn narrow(&self, sources: &Sources, symbol: &Symbol, o: &Occurrence) -> Span {
+        self.narrow_name(sources, symbol, o).unwrap_or(o.span)
+    }
+
+    /// Locate the name of the symbol in an occurrence, if it can be found.
+    fn narrow_name(&self, sources: &Sources, symbol: &Symbol, o: &Occurrence) -> Option<Span> {
+        let name = match symbol {
+            Symbol::Item(hash) => self.names.get(hash).map(|name| name.as_ref()),
+            Symbol::Local { source_id, span } => sources.source(*source_id, *span),
+        }?;
+
+        let text = sources.source(o.source_id, o.span)?;
+        let mut it = find_ident(text, name);
+
+        // Definitions declare their name up front, while uses are paths
+        // which end with the name.
+        let range = match o.kind {
+            ReferenceKind::Definition => it.next(),
+            ReferenceKind::Use => it.last(),
+        }?;
+
+        let start = o.span.start.into_usize();
+        Some(Span::new(start + range.start, start + range.end))
+    }
+}
+
+impl CompileVisitor for References {
+    fn register_meta(&mut self, meta: MetaRef<'_>) {
+        if meta.context {
+            return;
+        }
+
+        let Some(source) = meta.source else {
+            return;
+        };
+
+        if let Some(ComponentRef::Str(name)) = meta.item.last() {
+            self.names.insert(meta.hash, name.into());
+        }
+
+        if let Some(kind) = SemanticTokenKind::from_meta(meta.kind) {
+            self.kinds.insert(meta.hash, kind);
+        }
+
+        self.insert(
+            Symbol::Item(meta.hash),
+            source.location.source_id,
+            source.location.span,
+            ReferenceKind::Definition,
+        );
+    }
+
+    fn visit_meta(&mut self, location: &dyn Located, meta: MetaRef<'_>) {
+        if let Some(ComponentRef::Str(name)) = meta.item.last() {
+            self.names.entry(meta.hash).or_insert_with(|| name.into());
+        }
+
+        if let Some(kind) = SemanticTokenKind::from_meta(meta.kind) {
+            self.kinds.entry(meta.hash).or_insert(kind);
+        }
+
+        let location = location.location();
+
+        self.insert(
+            Symbol::Item(meta.hash),
+            location.source_id,
+            location.span,
+            ReferenceKind::Use,
+        );
+    }
+
+    fn visit_variable_use(
+        &mut self,
+        source_id: SourceId,
+        var_span: &dyn Spanned,
+        span: &dyn Spanned,
+    ) {
+        let symbol = Symbol::Local {
+            source_id,
+            span: var_span.span(),
+        };
+
+        self.insert(
+            symbol,
+            source_id,
+            var_span.span(),
+            ReferenceKind::Definition,
+        );
+        self.insert(symbol, source_id, span.span(), ReferenceKind::Use);
+    }
+
+    fn visit_macro_call(&mut self, location: &dyn Located) {
+        let location = location.location();
+
+        self.macros
+            .entry(location.source_id)
+            .or_default()
+            .push(location.span);
+    }
+}
+
+fn len(span: Span) -> usize {
+    span.end
+        .into_usize()
+        .saturating_sub(span.start.into_usize())
+}
+
+/// Locate the name of the macro called in `text`, which is the last component
+/// of the path leading up to the `!`.
+fn macro_name(text: &str) -> Option<Range<usize>> {
+    let (path, _) = text.split_once('!')?;
+
+    if !path
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == ':' || c.is_whitespace())
+    {
+        return None;
+    }
+
+    let end = path.trim_end().len();
+    let start = path[..end]
+        .rfind(|c: char| c == ':' || c.is_whitespace())
+        .map_or(0, |n| n + 1);
+
+    if start == end {
+        return None;
+    }
+
+    Some(start..end)
+}
+
+/// Find all occurrences of `name` as a whole identifier in `text`, skipping
+/// over line comments.
+fn find_ident<'a>(text: &'a str, name: &'a str) -> impl Iterator<Item = Range<usize>> + 'a {
+    fn is_ident(c: char) -> bool {
+        c.is_alphanumeric() || c == '_'
+    }
+
+    text.match_indices(name).filter_map(move |(start, _)| {
+        let end = start + name.len();
+
+        let before = text[..start].chars().next_back();
+        let after = text[end..].chars().next();
+
+        if before.map_or(false, is_ident) || after.map_or(false, is_ident) {
+            return None;
+        }
+
+        let line = match text[..start].rfind('\n') {
+            Some(n) => &text[n + 1..start],
+            None => &text[..start],
+        };
+
+        if line.contains("//") {
+            return None;
+        }
+
+        Some(start..end)
+    })
+}
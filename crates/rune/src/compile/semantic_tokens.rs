@@ -0,0 +1,90 @@
+use core::fmt;
+
+use crate::ast::Span;
+use crate::compile::meta;
+
+/// The classification of a [`SemanticToken`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum SemanticTokenKind {
+    /// A function, including associated and constant functions.
+    Function,
+    /// A type, like a struct, an enum or one of its variants.
+    Type,
+    /// A local variable.
+    Variable,
+    /// A constant.
+    Constant,
+    /// A macro.
+    Macro,
+}
+
+impl SemanticTokenKind {
+    /// Every kind of token, in the order of their [`index`].
+    ///
+    /// This is suitable as the legend of a language server.
+    ///
+    /// [`index`]: SemanticTokenKind::index
+    pub const ALL: [Self; 5] = [
+        Self::Function,
+        Self::Type,
+        Self::Variable,
+        Self::Constant,
+        Self::Macro,
+    ];
+
+    /// The index of the kind in [`SemanticTokenKind::ALL`].
+    pub fn index(self) -> usize {
+        match self {
+            Self::Function => 0,
+            Self::Type => 1,
+            Self::Variable => 2,
+            Self::Constant => 3,
+            Self::Macro => 4,
+        }
+    }
+
+    /// Classify the given kind of meta, if it's a named item which should be
+    /// highlighted.
+    pub(crate) fn from_meta(kind: &meta::Kind) -> Option<Self> {
+        match kind {
+            meta::Kind::Function { .. }
+            | meta::Kind::AssociatedFunction { .. }
+            | meta::Kind::ConstFn { .. } => Some(Self::Function),
+            meta::Kind::Type { .. }
+            | meta::Kind::Struct { .. }
+            | meta::Kind::Variant { .. }
+            | meta::Kind::Enum { .. } => Some(Self::Type),
+            meta::Kind::Const => Some(Self::Constant),
+            meta::Kind::Macro | meta::Kind::AttributeMacro => Some(Self::Macro),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SemanticTokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Function => write!(f, "function"),
+            Self::Type => write!(f, "type"),
+            Self::Variable => write!(f, "variable"),
+            Self::Constant => write!(f, "constant"),
+            Self::Macro => write!(f, "macro"),
+        }
+    }
+}
+
+/// A classified name in a source, as produced by
+/// [`References::semantic_tokens`].
+///
+/// [`References::semantic_tokens`]: crate::compile::References::semantic_tokens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SemanticToken {
+    /// The span of the name.
+    pub span: Span,
+    /// How the name is classified.
+    pub kind: SemanticTokenKind,
+    /// The token is the declaration of what it names.
+    pub definition: bool,
+}
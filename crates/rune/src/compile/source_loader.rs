@@ -1,4 +1,8 @@
-use crate::no_std::path::Path;
+#[cfg(feature = "std")]
+use crate::no_std::collections::HashMap;
+#[cfg(feature = "std")]
+use crate::no_std::io;
+use crate::no_std::path::{Path, PathBuf};
 use crate::no_std::prelude::*;
 
 use crate::ast::Spanned;
@@ -9,6 +13,21 @@ use crate::Source;
 pub trait SourceLoader {
     /// Load the given URL.
     fn load(&mut self, root: &Path, item: &Item, span: &dyn Spanned) -> compile::Result<Source>;
+
+    /// Load the raw contents of a data file, like the ones referenced by the
+    /// `include_data!`, `include_str!` and `include_bytes!` macros.
+    ///
+    /// The `path` is the one specified in the macro, and is typically resolved
+    /// relative to `root`. By default, loading data is not supported.
+    fn load_data(
+        &mut self,
+        root: &Path,
+        path: &str,
+        span: &dyn Spanned,
+    ) -> compile::Result<Vec<u8>> {
+        let _ = (root, path);
+        Err(compile::Error::msg(span, "Data loading is not supported"))
+    }
 }
 
 /// A source loader which does not support loading anything and will error.
@@ -36,31 +55,7 @@ impl FileSourceLoader {
 
 impl SourceLoader for FileSourceLoader {
     fn load(&mut self, root: &Path, item: &Item, span: &dyn Spanned) -> compile::Result<Source> {
-        let mut base = root.to_owned();
-
-        if !base.pop() {
-            return Err(compile::Error::new(
-                span,
-                ErrorKind::UnsupportedModuleRoot {
-                    root: root.to_owned(),
-                },
-            ));
-        }
-
-        for c in item {
-            if let ComponentRef::Str(string) = c {
-                base.push(string);
-            } else {
-                return Err(compile::Error::new(
-                    span,
-                    ErrorKind::UnsupportedModuleItem {
-                        item: item.to_owned(),
-                    },
-                ));
-            }
-        }
-
-        let candidates = [base.join("mod.rn"), base.with_extension("rn")];
+        let (base, candidates) = module_candidates(root, item, span)?;
 
         let mut found = None;
 
@@ -92,4 +87,271 @@ impl SourceLoader for FileSourceLoader {
             )),
         }
     }
+
+    #[cfg(feature = "std")]
+    fn load_data(
+        &mut self,
+        root: &Path,
+        path: &str,
+        span: &dyn Spanned,
+    ) -> compile::Result<Vec<u8>> {
+        let path = data_path(root, path, span)?;
+
+        match std::fs::read(&path) {
+            Ok(data) => Ok(data),
+            Err(error) => Err(compile::Error::new(
+                span,
+                ErrorKind::FileError { path, error },
+            )),
+        }
+    }
+}
+
+/// In-memory contents of files which take precedence over the contents on
+/// disk, like the unsaved buffers of an editor.
+///
+/// Use it through an [OverlaySourceLoader] to compile against the overlaid
+/// contents, and [SourceOverlay::source] to load the root sources of a build.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use rune::compile::SourceOverlay;
+///
+/// let mut overlay = SourceOverlay::new();
+/// assert!(overlay.insert("/project/main.rn", "pub fn main() { 42 }"));
+/// assert!(!overlay.insert("/project/main.rn", "pub fn main() { 42 }"));
+/// assert_eq!(overlay.version(), 1);
+///
+/// let source = overlay.source("/project/main.rn")?;
+/// assert_eq!(source.path(), Some(Path::new("/project/main.rn")));
+/// assert_eq!(overlay.get("/project/main.rn"), Some("pub fn main() { 42 }"));
+/// # Ok::<_, std::io::Error>(())
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone)]
+pub struct SourceOverlay {
+    files: HashMap<PathBuf, Box<str>>,
+    version: u64,
+}
+
+#[cfg(feature = "std")]
+impl SourceOverlay {
+    /// Construct a new empty overlay.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the contents of the file at the given path.
+    ///
+    /// Returns `true` if this changed the contents of the overlay.
+    pub fn insert(&mut self, path: impl AsRef<Path>, contents: impl AsRef<str>) -> bool {
+        let path = path.as_ref();
+        let contents = contents.as_ref();
+
+        if self.files.get(path).map(|c| c.as_ref()) == Some(contents) {
+            return false;
+        }
+
+        self.files.insert(path.to_owned(), contents.into());
+        self.version = self.version.wrapping_add(1);
+        true
+    }
+
+    /// Remove the file at the given path from the overlay, so that it is
+    /// loaded from disk again.
+    ///
+    /// Returns `true` if this changed the contents of the overlay.
+    pub fn remove(&mut self, path: impl AsRef<Path>) -> bool {
+        if self.files.remove(path.as_ref()).is_none() {
+            return false;
+        }
+
+        self.version = self.version.wrapping_add(1);
+        true
+    }
+
+    /// Clear all files in the overlay.
+    pub fn clear(&mut self) {
+        if !self.files.is_empty() {
+            self.files.clear();
+            self.version = self.version.wrapping_add(1);
+        }
+    }
+
+    /// Get the overlaid contents of the file at the given path.
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<&str> {
+        Some(self.files.get(path.as_ref())?.as_ref())
+    }
+
+    /// Test if the file at the given path is overlaid.
+    pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+        self.files.contains_key(path.as_ref())
+    }
+
+    /// The version of the overlay, which changes every time its contents
+    /// change.
+    ///
+    /// Anything derived from a build using this overlay, like a compiled unit
+    /// or its diagnostics, is out of date when the version differs from the
+    /// one at the time of the build.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Load the source at the given path, preferring its overlaid contents
+    /// over the ones on disk.
+    pub fn source(&self, path: impl AsRef<Path>) -> io::Result<Source> {
+        let path = path.as_ref();
+
+        match self.get(path) {
+            Some(contents) => Ok(Source::with_path(path.to_string_lossy(), contents, path)),
+            None => Source::from_path(path),
+        }
+    }
+}
+
+/// A source loader which loads files from a [SourceOverlay] when they are
+/// present in it, and otherwise falls back to another loader.
+///
+/// # Examples
+///
+/// ```
+/// use rune::compile::{OverlaySourceLoader, SourceOverlay};
+/// use rune::{Source, Sources};
+///
+/// let mut overlay = SourceOverlay::new();
+/// overlay.insert("/project/foo.rn", "pub fn value() { 42 }");
+///
+/// let mut sources = Sources::new();
+/// sources.insert(Source::with_path(
+///     "main",
+///     "mod foo; pub fn main() { foo::value() }",
+///     "/project/main.rn",
+/// ));
+///
+/// let mut source_loader = OverlaySourceLoader::new(&overlay);
+///
+/// let unit = rune::prepare(&mut sources)
+///     .with_source_loader(&mut source_loader)
+///     .build()?;
+/// # Ok::<_, rune::Error>(())
+/// ```
+#[cfg(feature = "std")]
+pub struct OverlaySourceLoader<'a, L = FileSourceLoader> {
+    overlay: &'a SourceOverlay,
+    base: L,
+}
+
+#[cfg(feature = "std")]
+impl<'a> OverlaySourceLoader<'a> {
+    /// Construct a new overlay source loader which falls back to loading
+    /// files from the filesystem.
+    pub fn new(overlay: &'a SourceOverlay) -> Self {
+        Self::with_base(overlay, FileSourceLoader::new())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, L> OverlaySourceLoader<'a, L> {
+    /// Construct a new overlay source loader which falls back to the given
+    /// loader.
+    pub fn with_base(overlay: &'a SourceOverlay, base: L) -> Self {
+        Self { overlay, base }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<L> SourceLoader for OverlaySourceLoader<'_, L>
+where
+    L: SourceLoader,
+{
+    fn load(&mut self, root: &Path, item: &Item, span: &dyn Spanned) -> compile::Result<Source> {
+        if let Ok((_, candidates)) = module_candidates(root, item, span) {
+            for path in &candidates[..] {
+                if self.overlay.contains(path) {
+                    return self.overlay.source(path).map_err(|error| {
+                        compile::Error::new(
+                            span,
+                            ErrorKind::FileError {
+                                path: path.to_owned(),
+                                error,
+                            },
+                        )
+                    });
+                }
+            }
+        }
+
+        self.base.load(root, item, span)
+    }
+
+    fn load_data(
+        &mut self,
+        root: &Path,
+        path: &str,
+        span: &dyn Spanned,
+    ) -> compile::Result<Vec<u8>> {
+        if let Ok(data_path) = data_path(root, path, span) {
+            if let Some(contents) = self.overlay.get(data_path) {
+                return Ok(contents.as_bytes().to_vec());
+            }
+        }
+
+        self.base.load_data(root, path, span)
+    }
+}
+
+/// Get the base path of the module `item` relative to `root`, and the
+/// candidate paths of the file which declares it in order of preference.
+fn module_candidates(
+    root: &Path,
+    item: &Item,
+    span: &dyn Spanned,
+) -> compile::Result<(PathBuf, [PathBuf; 2])> {
+    let mut base = root.to_owned();
+
+    if !base.pop() {
+        return Err(compile::Error::new(
+            span,
+            ErrorKind::UnsupportedModuleRoot {
+                root: root.to_owned(),
+            },
+        ));
+    }
+
+    for c in item {
+        if let ComponentRef::Str(string) = c {
+            base.push(string);
+        } else {
+            return Err(compile::Error::new(
+                span,
+                ErrorKind::UnsupportedModuleItem {
+                    item: item.to_owned(),
+                },
+            ));
+        }
+    }
+
+    let candidates = [base.join("mod.rn"), base.with_extension("rn")];
+    Ok((base, candidates))
+}
+
+/// Get the path of a data file relative to `root`.
+#[cfg(feature = "std")]
+pub(crate) fn data_path(root: &Path, path: &str, span: &dyn Spanned) -> compile::Result<PathBuf> {
+    let mut base = root.to_owned();
+
+    if !base.pop() {
+        return Err(compile::Error::new(
+            span,
+            ErrorKind::UnsupportedModuleRoot {
+                root: root.to_owned(),
+            },
+        ));
+    }
+
+    Ok(base.join(path))
 }
@@ -11,14 +11,17 @@ use crate::no_std::sync::Arc;
 
 use crate::ast::{Span, Spanned};
 use crate::compile::meta;
-use crate::compile::{self, Assembly, AssemblyInst, ErrorKind, Item, Location, Pool, WithSpan};
+use crate::compile::{
+    self, Assembly, AssemblyInst, DebugLevel, ErrorKind, Item, Location, Pool, WithSpan,
+};
+use crate::diagnostics::ErrorCode;
 use crate::hash;
 use crate::query::QueryInner;
-use crate::runtime::debug::{DebugArgs, DebugSignature};
-use crate::runtime::unit::UnitEncoder;
+use crate::runtime::debug::{DebugArgs, DebugSignature, DebugVariable};
+use crate::runtime::unit::{UnitEncoder, UnitStorage};
 use crate::runtime::{
-    Call, ConstValue, DebugInfo, DebugInst, Inst, Protocol, Rtti, StaticString, Unit, UnitFn,
-    VariantRtti,
+    Call, ConstValue, DebugInfo, DebugInst, Derives, Inst, Protocol, Rtti, StaticString, Unit,
+    UnitFn, VariantRtti,
 };
 use crate::{Context, Diagnostics, Hash, SourceId};
 
@@ -33,6 +36,17 @@ pub enum LinkerError {
     },
 }
 
+impl LinkerError {
+    /// Get the stable code identifying the kind of the error.
+    pub fn code(&self) -> ErrorCode {
+        let code = match self {
+            LinkerError::MissingFunction { .. } => 139,
+        };
+
+        ErrorCode::new(code)
+    }
+}
+
 impl fmt::Display for LinkerError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -71,6 +85,8 @@ pub(crate) struct UnitBuilder {
     static_object_keys: Vec<Box<[String]>>,
     /// Used to detect duplicates in the collection of static object keys.
     static_object_keys_rev: HashMap<Hash, usize>,
+    /// Static data.
+    static_data: Vec<ConstValue>,
     /// Runtime type information for types.
     rtti: hash::Map<Arc<Rtti>>,
     /// Runtime type information for variants.
@@ -85,9 +101,21 @@ pub(crate) struct UnitBuilder {
     constants: hash::Map<ConstValue>,
     /// Hash to identifiers.
     hash_to_ident: HashMap<Hash, Box<str>>,
+    /// The base unit this unit is layered on top of.
+    base: Option<Arc<Unit>>,
 }
 
 impl UnitBuilder {
+    /// Layer the unit being built on top of the given base unit.
+    pub(crate) fn set_base(&mut self, base: Arc<Unit>) {
+        self.base = Some(base);
+    }
+
+    /// Access the base unit this unit is layered on top of.
+    pub(crate) fn base(&self) -> Option<&Arc<Unit>> {
+        self.base.as_ref()
+    }
+
     /// Insert an identifier for debug purposes.
     pub(crate) fn insert_debug_ident(&mut self, ident: &str) {
         self.hash_to_ident.insert(Hash::ident(ident), ident.into());
@@ -96,10 +124,29 @@ impl UnitBuilder {
     /// Convert into a runtime unit, shedding our build metadata in the process.
     ///
     /// Returns `None` if the builder is still in use.
-    pub(crate) fn build<S>(mut self, span: Span, storage: S) -> compile::Result<Unit<S>> {
+    ///
+    /// Debug information is only included up to the given `debug_level`.
+    pub(crate) fn build<S>(
+        mut self,
+        span: Span,
+        storage: S,
+        debug_level: DebugLevel,
+    ) -> compile::Result<Unit<S>>
+    where
+        S: UnitStorage,
+    {
+        if debug_level == DebugLevel::None {
+            self.debug = None;
+        }
+
         if let Some(debug) = &mut self.debug {
             debug.functions_rev = self.functions_rev;
-            debug.hash_to_ident = self.hash_to_ident;
+
+            if debug_level == DebugLevel::Full {
+                debug.hash_to_ident = self.hash_to_ident;
+            } else {
+                strip_debug_info(debug);
+            }
         }
 
         for (from, to) in self.reexports {
@@ -139,6 +186,7 @@ impl UnitBuilder {
             self.static_strings,
             self.static_bytes,
             self.static_object_keys,
+            self.static_data,
             self.rtti,
             self.variant_rtti,
             self.debug,
@@ -231,6 +279,17 @@ impl UnitBuilder {
         Ok(new_slot)
     }
 
+    /// Insert static data and return its associated slot that can later be
+    /// looked up through [lookup_static_data][Unit::lookup_static_data].
+    ///
+    /// Unlike static strings, static data is not de-duplicated since it's
+    /// expected to be large and expensive to compare.
+    pub(crate) fn new_static_data(&mut self, value: ConstValue) -> usize {
+        let slot = self.static_data.len();
+        self.static_data.push(value);
+        slot
+    }
+
     /// Insert a new collection of static object keys, or return one already
     /// existing.
     pub(crate) fn new_static_object_keys_iter<I>(
@@ -298,6 +357,20 @@ impl UnitBuilder {
         pool: &mut Pool,
         query: &mut QueryInner,
     ) -> compile::Result<()> {
+        if let Some(base) = &self.base {
+            if base.function(meta.hash).is_some()
+                || base.lookup_rtti(meta.hash).is_some()
+                || base.lookup_variant_rtti(meta.hash).is_some()
+            {
+                return Err(compile::Error::new(
+                    span,
+                    ErrorKind::BaseItemConflict {
+                        item: pool.item(meta.item_meta.item).to_owned(),
+                    },
+                ));
+            }
+        }
+
         match meta.kind {
             meta::Kind::Type { .. } => {
                 let hash = pool.item_type_hash(meta.item_meta.item);
@@ -305,6 +378,7 @@ impl UnitBuilder {
                 let rtti = Arc::new(Rtti {
                     hash,
                     item: pool.item(meta.item_meta.item).to_owned(),
+                    derives: Derives::default(),
                 });
 
                 self.constants.insert(
@@ -321,6 +395,7 @@ impl UnitBuilder {
             }
             meta::Kind::Struct {
                 fields: meta::Fields::Empty,
+                derives,
                 ..
             } => {
                 let info = UnitFn::EmptyStruct { hash: meta.hash };
@@ -333,6 +408,7 @@ impl UnitBuilder {
                 let rtti = Arc::new(Rtti {
                     hash: meta.hash,
                     item: pool.item(meta.item_meta.item).to_owned(),
+                    derives,
                 });
 
                 if self.rtti.insert(meta.hash, rtti).is_some() {
@@ -360,6 +436,7 @@ impl UnitBuilder {
             }
             meta::Kind::Struct {
                 fields: meta::Fields::Unnamed(args),
+                derives,
                 ..
             } => {
                 let info = UnitFn::TupleStruct {
@@ -375,6 +452,7 @@ impl UnitBuilder {
                 let rtti = Arc::new(Rtti {
                     hash: meta.hash,
                     item: pool.item(meta.item_meta.item).to_owned(),
+                    derives,
                 });
 
                 if self.rtti.insert(meta.hash, rtti).is_some() {
@@ -400,12 +478,13 @@ impl UnitBuilder {
 
                 self.debug_info_mut().functions.insert(meta.hash, signature);
             }
-            meta::Kind::Struct { .. } => {
+            meta::Kind::Struct { derives, .. } => {
                 let hash = pool.item_type_hash(meta.item_meta.item);
 
                 let rtti = Arc::new(Rtti {
                     hash,
                     item: pool.item(meta.item_meta.item).to_owned(),
+                    derives,
                 });
 
                 self.constants.insert(
@@ -422,6 +501,8 @@ impl UnitBuilder {
             }
             meta::Kind::Variant {
                 enum_hash,
+                discriminant,
+                derives,
                 fields: meta::Fields::Empty,
                 ..
             } => {
@@ -429,6 +510,8 @@ impl UnitBuilder {
                     enum_hash,
                     hash: meta.hash,
                     item: pool.item(meta.item_meta.item).to_owned(),
+                    discriminant,
+                    derives,
                 });
 
                 if self.variant_rtti.insert(meta.hash, rtti).is_some() {
@@ -458,6 +541,8 @@ impl UnitBuilder {
             }
             meta::Kind::Variant {
                 enum_hash,
+                discriminant,
+                derives,
                 fields: meta::Fields::Unnamed(args),
                 ..
             } => {
@@ -465,6 +550,8 @@ impl UnitBuilder {
                     enum_hash,
                     hash: meta.hash,
                     item: pool.item(meta.item_meta.item).to_owned(),
+                    discriminant,
+                    derives,
                 });
 
                 if self.variant_rtti.insert(meta.hash, rtti).is_some() {
@@ -497,6 +584,8 @@ impl UnitBuilder {
             }
             meta::Kind::Variant {
                 enum_hash,
+                discriminant,
+                derives,
                 fields: meta::Fields::Named(..),
                 ..
             } => {
@@ -506,6 +595,8 @@ impl UnitBuilder {
                     enum_hash,
                     hash,
                     item: pool.item(meta.item_meta.item).to_owned(),
+                    discriminant,
+                    derives,
                 });
 
                 if self.variant_rtti.insert(hash, rtti).is_some() {
@@ -666,7 +757,10 @@ impl UnitBuilder {
     /// This can prevent a number of runtime errors, like missing functions.
     pub(crate) fn link(&mut self, context: &Context, diagnostics: &mut Diagnostics) {
         for (hash, spans) in &self.required_functions {
-            if self.functions.get(hash).is_none() && context.lookup_function(*hash).is_none() {
+            if self.functions.get(hash).is_none()
+                && context.lookup_function(*hash).is_none()
+                && !matches!(&self.base, Some(base) if base.function(*hash).is_some())
+            {
                 diagnostics.error(
                     SourceId::empty(),
                     LinkerError::MissingFunction {
@@ -694,6 +788,7 @@ impl UnitBuilder {
 
         self.label_count = assembly.label_count;
 
+        storage.begin_function();
         let base = storage.extend_offsets(assembly.labels.len());
         self.required_functions.extend(assembly.required_functions);
 
@@ -705,11 +800,21 @@ impl UnitBuilder {
             }
         }
 
+        let mut variables = assembly.variables.into_iter().peekable();
+
         for (pos, (inst, span)) in assembly.instructions.into_iter().enumerate() {
             let mut comment = String::new();
 
             let at = storage.offset();
 
+            while let Some((_, name, offset)) = variables.next_if(|(p, ..)| *p == pos) {
+                self.debug_info_mut()
+                    .variables
+                    .entry(at)
+                    .or_default()
+                    .push(DebugVariable::new(name, offset));
+            }
+
             let mut labels = Vec::new();
 
             for label in assembly
@@ -839,6 +944,60 @@ impl UnitBuilder {
                         .encode(Inst::IterNext { offset, jump })
                         .with_span(span)?;
                 }
+                AssemblyInst::IterStep {
+                    offset,
+                    end,
+                    step,
+                    inclusive,
+                    label,
+                } => {
+                    let jump = label
+                        .jump()
+                        .ok_or(ErrorKind::MissingLabelLocation {
+                            name: label.name,
+                            index: label.index,
+                        })
+                        .with_span(span)?;
+
+                    if let Err(fmt::Error) = write!(comment, "label:{}", label) {
+                        return Err(compile::Error::msg(span, "Failed to write comment"));
+                    }
+
+                    storage
+                        .encode(Inst::IterStep {
+                            offset,
+                            end,
+                            step,
+                            inclusive,
+                            jump,
+                        })
+                        .with_span(span)?;
+                }
+                AssemblyInst::TryOrJump {
+                    address,
+                    preserve,
+                    label,
+                } => {
+                    let jump = label
+                        .jump()
+                        .ok_or(ErrorKind::MissingLabelLocation {
+                            name: label.name,
+                            index: label.index,
+                        })
+                        .with_span(span)?;
+
+                    if let Err(fmt::Error) = write!(comment, "label:{}", label) {
+                        return Err(compile::Error::msg(span, "Failed to write comment"));
+                    }
+
+                    storage
+                        .encode(Inst::TryOrJump {
+                            address,
+                            preserve,
+                            jump,
+                        })
+                        .with_span(span)?;
+                }
                 AssemblyInst::Raw { raw } => {
                     // Optimization to avoid performing lookups for recursive
                     // function calls.
@@ -888,3 +1047,20 @@ impl UnitBuilder {
         Ok(())
     }
 }
+
+/// Strip debug information which is only included at [`DebugLevel::Full`],
+/// keeping function signatures and instruction locations.
+fn strip_debug_info(debug: &mut DebugInfo) {
+    for inst in debug.instructions.values_mut() {
+        inst.comment = None;
+        inst.labels = Vec::new();
+    }
+
+    debug.variables.clear();
+
+    for signature in debug.functions.values_mut() {
+        if let DebugArgs::Named(args) = &signature.args {
+            signature.args = DebugArgs::TupleArgs(args.len());
+        }
+    }
+}
@@ -1,6 +1,9 @@
 pub(crate) mod assemble;
 pub(crate) use self::assemble::{Ctxt, Needs};
 
+mod defers;
+pub(crate) use self::defers::{Defer, Defers};
+
 mod loops;
 pub(crate) use self::loops::{Loop, Loops};
 
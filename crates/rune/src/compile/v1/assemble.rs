@@ -4,13 +4,15 @@ use crate::no_std::prelude::*;
 
 use crate::ast::{self, Span, Spanned};
 use crate::compile::ir;
-use crate::compile::v1::{Layer, Loop, Loops, ScopeGuard, Scopes, Var};
-use crate::compile::{self, Assembly, ErrorKind, ItemId, ModId, Options, WithSpan};
+use crate::compile::v1::{Defer, Defers, Layer, Loop, Loops, ScopeGuard, Scopes, Var};
+use crate::compile::{
+    self, purity, Assembly, ErrorKind, ItemId, ModId, Optimization, Options, Purity, WithSpan,
+};
 use crate::hir;
 use crate::query::{ConstFn, Query, Used};
 use crate::runtime::{
-    ConstValue, Inst, InstAddress, InstAssignOp, InstOp, InstRange, InstTarget, InstValue,
-    InstVariant, Label, PanicReason, Protocol, TypeCheck,
+    static_type, ConstValue, Inst, InstAddress, InstAssignOp, InstOp, InstRange, InstTarget,
+    InstValue, InstVariant, Label, PanicReason, Protocol, TypeCheck,
 };
 use crate::{Hash, SourceId};
 
@@ -47,6 +49,8 @@ pub(crate) struct Ctxt<'a, 'hir, 'arena> {
     pub(crate) contexts: Vec<Span>,
     /// The nesting of loop we are currently in.
     pub(crate) loops: Loops<'hir>,
+    /// Blocks deferred until the scope they are declared in is exited.
+    pub(crate) defers: Defers<'hir>,
     /// Enabled optimizations.
     pub(crate) options: &'a Options,
 }
@@ -97,6 +101,21 @@ impl<'a, 'hir, 'arena> Ctxt<'a, 'hir, 'arena> {
         Ok(())
     }
 
+    /// Define a variable, recording where it's declared for debugging.
+    pub(crate) fn define(
+        &mut self,
+        name: hir::Name<'hir>,
+        span: &'hir dyn Spanned,
+    ) -> compile::Result<usize> {
+        let offset = self.scopes.define(name, span)?;
+
+        if let hir::Name::Str(name) = name {
+            self.asm.variable(name, offset);
+        }
+
+        Ok(offset)
+    }
+
     /// Get the latest relevant warning context.
     pub(crate) fn context(&self) -> Option<Span> {
         self.contexts.last().copied()
@@ -134,11 +153,15 @@ impl<'a, 'hir, 'arena> Ctxt<'a, 'hir, 'arena> {
         }
 
         let mut interpreter = ir::Interpreter {
-            budget: ir::Budget::new(1_000_000),
+            budget: ir::Budget::new(
+                self.options.const_budget,
+                self.options.const_recursion_limit,
+            ),
             scopes: Default::default(),
             module: from_module,
             item: from_item,
             q: self.q.borrow(),
+            trace: Vec::new(),
         };
 
         for (ir, name) in compiled {
@@ -148,7 +171,7 @@ impl<'a, 'hir, 'arena> Ctxt<'a, 'hir, 'arena> {
 
         interpreter.module = query_const_fn.item_meta.module;
         interpreter.item = query_const_fn.item_meta.item;
-        let value = interpreter.eval_value(&query_const_fn.ir_fn.ir, Used::Used)?;
+        let value = interpreter.eval_call(span.span(), query_const_fn, Used::Used)?;
         value.into_const(span)
     }
 }
@@ -220,6 +243,10 @@ pub(crate) fn fn_from_item_fn<'hir>(
     let mut patterns = Vec::new();
     let mut first = true;
 
+    hir::walk::block(&hir.body, &mut |hir: &hir::Expr<'hir>| {
+        mark_reassigned(&mut cx.scopes, hir)
+    });
+
     for arg in hir.args {
         match arg {
             hir::FnArg::SelfValue(span) => {
@@ -227,11 +254,16 @@ pub(crate) fn fn_from_item_fn<'hir>(
                     return Err(compile::Error::new(*span, ErrorKind::UnsupportedSelf));
                 }
 
-                cx.scopes.define(hir::Name::SelfValue, span)?;
+                cx.define(hir::Name::SelfValue, span)?;
             }
             hir::FnArg::Pat(pat) => {
                 let offset = cx.scopes.alloc(pat)?;
-                patterns.push((pat, offset));
+                patterns.push((*pat, offset));
+            }
+            hir::FnArg::Typed(typed) => {
+                let offset = cx.scopes.alloc(typed)?;
+                check_argument(cx, typed, offset)?;
+                patterns.push((&typed.pat, offset));
             }
         }
 
@@ -249,7 +281,9 @@ pub(crate) fn fn_from_item_fn<'hir>(
         return Ok(());
     }
 
-    if !hir.body.produces_nothing() {
+    if let Some(tuple) = returns_tuple(&hir.body) {
+        block_return_tuple(cx, &hir.body, tuple)?;
+    } else if !hir.body.produces_nothing() {
         return_(cx, hir, &hir.body, block)?;
     } else {
         block(cx, &hir.body, Needs::None)?.apply(cx)?;
@@ -269,8 +303,12 @@ pub(crate) fn async_block_secondary<'hir>(
     cx: &mut Ctxt<'_, 'hir, '_>,
     hir: &'hir hir::AsyncBlock<'hir>,
 ) -> compile::Result<()> {
+    hir::walk::block(&hir.block, &mut |hir: &hir::Expr<'hir>| {
+        mark_reassigned(&mut cx.scopes, hir)
+    });
+
     for name in hir.captures.iter().copied() {
-        cx.scopes.define(name, &hir.block)?;
+        cx.define(name, &hir.block)?;
     }
 
     return_(cx, &hir.block, &hir.block, block)?;
@@ -287,6 +325,10 @@ pub(crate) fn expr_closure_secondary<'hir>(
 ) -> compile::Result<()> {
     let mut patterns = Vec::new();
 
+    hir::walk::expr(&hir.body, &mut |hir: &hir::Expr<'hir>| {
+        mark_reassigned(&mut cx.scopes, hir)
+    });
+
     for arg in hir.args {
         match arg {
             hir::FnArg::SelfValue(..) => {
@@ -294,7 +336,12 @@ pub(crate) fn expr_closure_secondary<'hir>(
             }
             hir::FnArg::Pat(pat) => {
                 let offset = cx.scopes.alloc(pat)?;
-                patterns.push((pat, offset));
+                patterns.push((*pat, offset));
+            }
+            hir::FnArg::Typed(typed) => {
+                let offset = cx.scopes.alloc(typed)?;
+                check_argument(cx, typed, offset)?;
+                patterns.push((&typed.pat, offset));
             }
         }
     }
@@ -303,7 +350,7 @@ pub(crate) fn expr_closure_secondary<'hir>(
         cx.asm.push(Inst::PushTuple, span);
 
         for capture in hir.captures.iter().copied() {
-            cx.scopes.define(capture, span)?;
+            cx.define(capture, span)?;
         }
     }
 
@@ -316,6 +363,40 @@ pub(crate) fn expr_closure_secondary<'hir>(
     Ok(())
 }
 
+/// Mark the variable assigned to by the given expression, if any, since the
+/// type of its value can't be statically known.
+fn mark_reassigned<'hir>(scopes: &mut Scopes<'hir>, hir: &hir::Expr<'hir>) {
+    let lhs = match hir.kind {
+        hir::ExprKind::Assign(hir) => &hir.lhs,
+        hir::ExprKind::Binary(hir) if hir.op.is_assign() => &hir.lhs,
+        _ => return,
+    };
+
+    if let hir::ExprKind::Variable(name) = lhs.kind {
+        scopes.mark_reassigned(name);
+    }
+}
+
+/// Check the type annotation of the argument at the given offset.
+fn check_argument(
+    cx: &mut Ctxt<'_, '_, '_>,
+    typed: &hir::PatType<'_>,
+    offset: usize,
+) -> compile::Result<()> {
+    let slot = cx.q.unit.new_static_string(typed, typed.name)?;
+
+    cx.asm.push(
+        Inst::CheckArgument {
+            offset,
+            hash: typed.hash,
+            slot,
+        },
+        typed,
+    );
+
+    Ok(())
+}
+
 /// Assemble a return statement from the given Assemble.
 fn return_<'hir, T>(
     cx: &mut Ctxt<'_, 'hir, '_>,
@@ -325,7 +406,20 @@ fn return_<'hir, T>(
 ) -> compile::Result<()> {
     let clean = cx.scopes.total(span)?;
 
-    let address = asm(cx, hir, Needs::Value)?.apply_targeted(cx)?;
+    let mut address = asm(cx, hir, Needs::Value)?.apply_targeted(cx)?;
+
+    if !cx.defers.is_empty() {
+        // Deferred blocks might modify the variable being returned, so it has
+        // to be copied before they run.
+        if let InstAddress::Offset(offset) = address {
+            cx.asm.push(Inst::Copy { offset }, span);
+            cx.scopes.alloc(span)?;
+            address = InstAddress::Top;
+        }
+
+        defers(cx, 0, span)?;
+    }
+
     cx.asm.push(Inst::Return { address, clean }, span);
 
     // Top address produces an anonymous variable, which is consumed by the
@@ -337,6 +431,92 @@ fn return_<'hir, T>(
     Ok(())
 }
 
+/// Test if the given function body ends in a tuple literal which can be
+/// returned through [Inst::ReturnTuple].
+///
+/// Bodies with deferred blocks are excluded, since they run after the returned
+/// value has been evaluated.
+fn returns_tuple<'hir>(hir: &hir::Block<'hir>) -> Option<&'hir hir::ExprSeq<'hir>> {
+    let Some(hir::Stmt::Expr(e)) = hir.statements.last() else {
+        return None;
+    };
+
+    let hir::ExprKind::Tuple(tuple) = e.kind else {
+        return None;
+    };
+
+    if tuple.items.is_empty()
+        || hir
+            .statements
+            .iter()
+            .any(|stmt| matches!(stmt, hir::Stmt::Defer(..)))
+    {
+        return None;
+    }
+
+    Some(tuple)
+}
+
+/// Assemble a function body which ends in a tuple literal, returning its items
+/// through [Inst::ReturnTuple].
+#[instrument(span = hir)]
+fn block_return_tuple<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    hir: &'hir hir::Block<'hir>,
+    tuple: &'hir hir::ExprSeq<'hir>,
+) -> compile::Result<()> {
+    cx.contexts.push(hir.span());
+    let scopes_count = cx.scopes.child(hir)?;
+
+    let (_, statements) = hir
+        .statements
+        .split_last()
+        .ok_or("Missing tail expression")
+        .with_span(hir)?;
+
+    for stmt in statements {
+        match stmt {
+            hir::Stmt::Local(l) => {
+                local(cx, l, Needs::None)?.apply(cx)?;
+            }
+            hir::Stmt::Expr(e) | hir::Stmt::Semi(e) => {
+                expr(cx, e, Needs::None)?.apply(cx)?;
+            }
+            hir::Stmt::Defer(..) | hir::Stmt::Item(..) => {}
+        }
+    }
+
+    return_tuple(cx, tuple, hir)?;
+
+    cx.scopes.pop(scopes_count, hir)?;
+
+    cx.contexts
+        .pop()
+        .ok_or("Missing parent context")
+        .with_span(hir)?;
+
+    Ok(())
+}
+
+/// Return the items of a tuple literal through [Inst::ReturnTuple].
+fn return_tuple<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    hir: &'hir hir::ExprSeq<'hir>,
+    span: &dyn Spanned,
+) -> compile::Result<()> {
+    let clean = cx.scopes.total(span)?;
+
+    for e in hir.items {
+        expr(cx, e, Needs::Value)?.apply(cx)?;
+        cx.scopes.alloc(e)?;
+    }
+
+    let count = hir.items.len();
+    cx.asm.push(Inst::ReturnTuple { count, clean }, span);
+    cx.scopes.free(span, count)?;
+    Ok(())
+}
+
 /// Compile a pattern based on the given offset.
 #[instrument(span = hir)]
 fn pat_with_offset<'hir>(
@@ -406,7 +586,7 @@ fn pat<'hir>(
             }
             hir::PatPathKind::Ident(name) => {
                 load(cx, Needs::Value)?;
-                cx.scopes.define(hir::Name::Str(name), hir)?;
+                cx.define(hir::Name::Str(name), hir)?;
                 Ok(false)
             }
         },
@@ -419,6 +599,22 @@ fn pat<'hir>(
             pat_object(cx, hir, span, false_label, &load)?;
             Ok(true)
         }
+        hir::PatKind::At(hir) => {
+            // NB: the binding holds the value being matched, so the
+            // sub-pattern loads from it.
+            load(cx, Needs::Value)?;
+            let offset = cx.define(hir::Name::Str(hir.name), span)?;
+
+            let load = move |cx: &mut Ctxt<'_, 'hir, '_>, needs: Needs| {
+                if needs.value() {
+                    cx.asm.push(Inst::Copy { offset }, span);
+                }
+
+                Ok(())
+            };
+
+            pat(cx, hir.pat, false_label, &load)
+        }
     }
 }
 
@@ -650,7 +846,7 @@ fn pat_object<'hir>(
             }
             hir::Binding::Ident(span, name) => {
                 cx.asm.push(Inst::ObjectIndexGetAt { offset, slot }, &span);
-                cx.scopes.define(hir::Name::Str(name), binding)?;
+                cx.define(hir::Name::Str(name), binding)?;
             }
         }
     }
@@ -667,6 +863,7 @@ fn block<'hir>(
 ) -> compile::Result<Asm<'hir>> {
     cx.contexts.push(hir.span());
     let scopes_count = cx.scopes.child(hir)?;
+    let defers_count = cx.defers.len();
 
     let mut last = None::<(&hir::Expr<'_>, bool)>;
 
@@ -681,6 +878,19 @@ fn block<'hir>(
                 local(cx, l, Needs::None)?.apply(cx)?;
                 continue;
             }
+            hir::Stmt::Defer(block) => {
+                if let Some((e, _)) = take(&mut last) {
+                    // NB: terminated expressions do not need to produce a value.
+                    expr(cx, e, Needs::None)?.apply(cx)?;
+                }
+
+                cx.defers.push(Defer {
+                    block,
+                    layers: cx.scopes.snapshot(),
+                });
+
+                continue;
+            }
             hir::Stmt::Expr(expr) => (expr, false),
             hir::Stmt::Semi(expr) => (expr, true),
             hir::Stmt::Item(..) => continue,
@@ -704,6 +914,24 @@ fn block<'hir>(
         false
     };
 
+    if cx.defers.len() > defers_count {
+        let value = produced && needs.value();
+
+        // NB: the produced value is on top of the stack while the deferred
+        // blocks run.
+        if value {
+            cx.scopes.alloc(hir)?;
+        }
+
+        defers(cx, defers_count, hir)?;
+
+        if value {
+            cx.scopes.free(hir, 1)?;
+        }
+
+        cx.defers.truncate(defers_count);
+    }
+
     let scope = cx.scopes.pop(scopes_count, hir)?;
 
     if needs.value() {
@@ -725,6 +953,32 @@ fn block<'hir>(
     Ok(Asm::top(hir))
 }
 
+/// Emit the blocks which were deferred since `from`, most recently deferred
+/// first.
+///
+/// Each block is assembled with the variables which were visible where it was
+/// deferred, and the stack is left unchanged.
+fn defers<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    from: usize,
+    span: &dyn Spanned,
+) -> compile::Result<()> {
+    for defer in cx.defers.since(from) {
+        let total = cx.scopes.total(span)?;
+        let layers = cx.scopes.replace(defer.layers, total);
+        let loops = replace(&mut cx.loops, Loops::new());
+
+        cx.defers.enter();
+        block(cx, defer.block, Needs::None)?.apply(cx)?;
+        cx.defers.exit();
+
+        cx.loops = loops;
+        cx.scopes.restore(layers);
+    }
+
+    Ok(())
+}
+
 /// Assemble #[builtin] format_args!(...) macro.
 #[instrument(span = format)]
 fn builtin_format<'hir>(
@@ -762,9 +1016,34 @@ fn builtin_template<'hir>(
 ) -> compile::Result<Asm<'hir>> {
     let span = template;
 
+    let expansions = template
+        .exprs
+        .iter()
+        .filter(|hir| !matches!(hir.kind, hir::ExprKind::Lit(hir::Lit::Str(..))))
+        .count();
+
+    if template.from_literal && expansions == 0 {
+        cx.q.diagnostics
+            .template_without_expansions(cx.source_id, span, cx.context());
+    }
+
+    let string = if cx.options.passes.is_enabled(Optimization::ConstFold) {
+        const_template(cx, template)?
+    } else {
+        None
+    };
+
+    if let Some(string) = string {
+        if needs.value() {
+            let slot = cx.q.unit.new_static_string(span, &string)?;
+            cx.asm.push(Inst::String { slot }, span);
+        }
+
+        return Ok(Asm::top(span));
+    }
+
     let expected = cx.scopes.child(span)?;
     let mut size_hint = 0;
-    let mut expansions = 0;
 
     for hir in template.exprs {
         if let hir::ExprKind::Lit(hir::Lit::Str(s)) = hir.kind {
@@ -775,17 +1054,10 @@ fn builtin_template<'hir>(
             continue;
         }
 
-        expansions += 1;
-
         expr(cx, hir, Needs::Value)?.apply(cx)?;
         cx.scopes.alloc(span)?;
     }
 
-    if template.from_literal && expansions == 0 {
-        cx.q.diagnostics
-            .template_without_expansions(cx.source_id, span, cx.context());
-    }
-
     cx.asm.push(
         Inst::StringConcat {
             len: template.exprs.len(),
@@ -802,6 +1074,62 @@ fn builtin_template<'hir>(
     Ok(Asm::top(span))
 }
 
+/// Try to evaluate a template which only consists of literals and constants
+/// into a static string at compile time.
+///
+/// Returns `None` if the template contains anything which has to be formatted
+/// at runtime.
+fn const_template(
+    cx: &mut Ctxt<'_, '_, '_>,
+    template: &hir::BuiltInTemplate<'_>,
+) -> compile::Result<Option<String>> {
+    let is_const = template.exprs.iter().all(|hir| {
+        matches!(
+            hir.kind,
+            hir::ExprKind::Lit(
+                hir::Lit::Str(..)
+                    | hir::Lit::Integer(..)
+                    | hir::Lit::Float(..)
+                    | hir::Lit::Bool(..)
+            ) | hir::ExprKind::Const(..)
+        )
+    });
+
+    if !is_const {
+        return Ok(None);
+    }
+
+    let mut compiler = ir::Ctxt {
+        source_id: cx.source_id,
+        q: cx.q.borrow(),
+    };
+
+    let ir = ir::Ir::new(
+        template.span,
+        ir::compiler::builtin_template(template, &mut compiler)?,
+    );
+
+    let mut interpreter = ir::Interpreter {
+        budget: ir::Budget::new(cx.options.const_budget, cx.options.const_recursion_limit),
+        scopes: Default::default(),
+        module: ModId::default(),
+        item: ItemId::default(),
+        q: cx.q.borrow(),
+        trace: Vec::new(),
+    };
+
+    // Constants which can't be formatted at compile time, like vectors, are
+    // left to be formatted at runtime.
+    match ir::eval_ir(&ir, &mut interpreter, Used::Used) {
+        Ok(value) => match value.into_const(template)? {
+            ConstValue::String(string) => Ok(Some(string)),
+            _ => Ok(None),
+        },
+        Err(ir::EvalOutcome::Error(error)) => Err(error),
+        Err(ir::EvalOutcome::NotConst(..) | ir::EvalOutcome::Break(..)) => Ok(None),
+    }
+}
+
 /// Assemble a constant value.
 #[instrument(span = span)]
 fn const_<'hir>(
@@ -947,10 +1275,12 @@ fn expr<'hir>(
         hir::ExprKind::Vec(hir) => expr_vec(cx, hir, span, needs)?,
         hir::ExprKind::Object(hir) => expr_object(cx, hir, span, needs)?,
         hir::ExprKind::Range(hir) => expr_range(cx, hir, span, needs)?,
+        hir::ExprKind::Spread(hir) => expr_spread(cx, hir, span, needs)?,
         hir::ExprKind::Template(template) => builtin_template(cx, template, needs)?,
         hir::ExprKind::Format(format) => builtin_format(cx, format, needs)?,
         hir::ExprKind::AsyncBlock(hir) => expr_async_block(cx, hir, span, needs)?,
         hir::ExprKind::Const(id) => const_item(cx, id, span, needs)?,
+        hir::ExprKind::StaticData(slot) => static_data(cx, slot, span, needs)?,
         hir::ExprKind::Path => {
             return Err(compile::Error::msg(
                 span,
@@ -1197,6 +1527,18 @@ fn expr_binary<'hir>(
                     }
                 }
             }
+            // <expr>[<index>] <op> <value>
+            hir::ExprKind::Index(expr_index_get) => {
+                expr(cx, &expr_index_get.target, Needs::Value)?.apply(cx)?;
+                cx.scopes.alloc(span)?;
+
+                expr(cx, &expr_index_get.index, Needs::Value)?.apply(cx)?;
+                cx.scopes.alloc(span)?;
+
+                expr(cx, rhs, Needs::Value)?.apply(cx)?;
+                cx.scopes.free(span, 2)?;
+                Some(InstTarget::Index)
+            }
             _ => None,
         };
 
@@ -1284,6 +1626,23 @@ fn const_item<'hir>(
     Ok(Asm::top(span))
 }
 
+/// Assemble loading of static data.
+#[instrument(span = span)]
+fn static_data<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    slot: usize,
+    span: &dyn Spanned,
+    needs: Needs,
+) -> compile::Result<Asm<'hir>> {
+    if !needs.value() {
+        cx.q.diagnostics.not_used(cx.source_id, span, cx.context());
+        return Ok(Asm::top(span));
+    }
+
+    cx.asm.push(Inst::StaticData { slot }, span);
+    Ok(Asm::top(span))
+}
+
 /// Assemble a break expression.
 ///
 /// NB: loops are expected to produce a value at the end of their expression.
@@ -1295,9 +1654,15 @@ fn expr_break<'hir>(
     _: Needs,
 ) -> compile::Result<Asm<'hir>> {
     let Some(current_loop) = cx.loops.last().cloned() else {
+        if cx.defers.is_emitting() {
+            return Err(compile::Error::new(span, ErrorKind::ControlFlowInDefer));
+        }
+
         return Err(compile::Error::new(span, ErrorKind::BreakOutsideOfLoop));
     };
 
+    let value_needs = current_loop.needs;
+
     let (last_loop, to_drop, has_value) = match (hir.label, hir.expr) {
         (None, Some(e)) => {
             expr(cx, e, current_loop.needs)?.apply(cx)?;
@@ -1319,6 +1684,22 @@ fn expr_break<'hir>(
         }
     };
 
+    if cx.defers.len() > last_loop.defers {
+        let value = has_value && value_needs.value();
+
+        // NB: the value being broken with is on top of the stack while the
+        // deferred blocks run.
+        if value {
+            cx.scopes.alloc(span)?;
+        }
+
+        defers(cx, last_loop.defers, span)?;
+
+        if value {
+            cx.scopes.free(span, 1)?;
+        }
+    }
+
     // Drop loop temporaries. Typically an iterator.
     for offset in to_drop {
         cx.asm.push(Inst::Drop { offset }, span);
@@ -1356,6 +1737,24 @@ fn expr_call<'hir>(
 ) -> compile::Result<Asm<'hir>> {
     let args = hir.args.len();
 
+    if !needs.value() && is_pure_call(cx, hir) {
+        cx.q.diagnostics
+            .unused_pure_call(cx.source_id, span, cx.context());
+
+        // NB: the call can only be eliminated if neither the function nor
+        // evaluating its arguments might call protocol functions, since they
+        // might have side effects.
+        if cx.options.passes.is_enabled(Optimization::DeadCalls)
+            && is_strictly_pure_call(cx, hir)
+            && hir
+                .args
+                .iter()
+                .all(|e| cx.q.is_strictly_pure_summary(&purity::summarize_expr(e)))
+        {
+            return Ok(Asm::top(span));
+        }
+    }
+
     match hir.call {
         hir::Call::Var { name, .. } => {
             let var = cx.scopes.get(&mut cx.q, name, span)?;
@@ -1381,10 +1780,27 @@ fn expr_call<'hir>(
                 cx.scopes.alloc(span)?;
             }
 
-            cx.asm.push(Inst::CallAssociated { hash, args }, span);
+            let type_hash = if cx.options.early_bound {
+                static_type_hash(cx, target)
+            } else {
+                None
+            };
+
+            if let Some(type_hash) = type_hash {
+                cx.asm.push(
+                    Inst::CallAssociatedTyped {
+                        hash: Hash::associated_function(type_hash, hash),
+                        args,
+                    },
+                    span,
+                );
+            } else {
+                cx.asm.push(Inst::CallAssociated { hash, args }, span);
+            }
+
             cx.scopes.free(span, hir.args.len() + 1)?;
         }
-        hir::Call::Meta { hash } => {
+        hir::Call::Meta { hash, .. } => {
             for e in hir.args {
                 expr(cx, e, Needs::Value)?.apply(cx)?;
                 cx.scopes.alloc(span)?;
@@ -1424,6 +1840,31 @@ fn expr_call<'hir>(
     Ok(Asm::top(span))
 }
 
+/// Test if the given call is to a pure function in the unit.
+fn is_pure_call(cx: &mut Ctxt<'_, '_, '_>, hir: &hir::ExprCall<'_>) -> bool {
+    let hir::Call::Meta {
+        hash, local: true, ..
+    } = hir.call
+    else {
+        return false;
+    };
+
+    matches!(cx.q.purity(hash), Some(Purity::Pure))
+}
+
+/// Test if the given call is to a pure function in the unit which doesn't
+/// perform any operations which might call protocol functions.
+fn is_strictly_pure_call(cx: &mut Ctxt<'_, '_, '_>, hir: &hir::ExprCall<'_>) -> bool {
+    let hir::Call::Meta {
+        hash, local: true, ..
+    } = hir.call
+    else {
+        return false;
+    };
+
+    cx.q.is_strictly_pure(hash)
+}
+
 /// Assemble a closure expression.
 #[instrument(span = span)]
 fn expr_call_closure<'hir>(
@@ -1470,6 +1911,10 @@ fn expr_continue<'hir>(
     _: Needs,
 ) -> compile::Result<Asm<'hir>> {
     let Some(current_loop) = cx.loops.last().cloned() else {
+        if cx.defers.is_emitting() {
+            return Err(compile::Error::new(span, ErrorKind::ControlFlowInDefer));
+        }
+
         return Err(compile::Error::new(span, ErrorKind::ContinueOutsideOfLoop));
     };
 
@@ -1487,6 +1932,7 @@ fn expr_continue<'hir>(
         .ok_or("Var count should be larger")
         .with_span(span)?;
 
+    defers(cx, last_loop.defers, span)?;
     cx.locals_pop(vars, span);
 
     cx.asm.jump(&last_loop.continue_label, span);
@@ -1557,6 +2003,110 @@ fn expr_field_access<'hir>(
     }
 }
 
+/// Determine the type of the value an expression evaluates to, if it can be
+/// known at compile time without any further analysis.
+///
+/// Variables only have a type if they're bound to a value of a known type and
+/// never assigned to, so the type is proven for as long as the variable lives.
+fn static_type_hash<'hir>(cx: &Ctxt<'_, 'hir, '_>, hir: &hir::Expr<'hir>) -> Option<Hash> {
+    let ty = match hir.kind {
+        hir::ExprKind::Lit(lit) => match lit {
+            hir::Lit::Bool(..) => static_type::BOOL_TYPE,
+            hir::Lit::Integer(..) => static_type::INTEGER_TYPE,
+            hir::Lit::Float(..) => static_type::FLOAT_TYPE,
+            hir::Lit::Byte(..) => static_type::BYTE_TYPE,
+            hir::Lit::Char(..) => static_type::CHAR_TYPE,
+            hir::Lit::Str(..) => static_type::STRING_TYPE,
+            hir::Lit::ByteStr(..) => static_type::BYTES_TYPE,
+        },
+        hir::ExprKind::Template(..) => static_type::STRING_TYPE,
+        hir::ExprKind::Vec(..) => static_type::VEC_TYPE,
+        hir::ExprKind::Tuple(..) => static_type::TUPLE_TYPE,
+        hir::ExprKind::Object(object) => match object.kind {
+            hir::ExprObjectKind::EmptyStruct { hash }
+            | hir::ExprObjectKind::Struct { hash }
+            | hir::ExprObjectKind::ExternalType { hash, .. } => return Some(hash),
+            hir::ExprObjectKind::Anonymous => static_type::OBJECT_TYPE,
+            hir::ExprObjectKind::StructVariant { .. } => return None,
+        },
+        hir::ExprKind::Spread(spread) => match spread.kind {
+            hir::ExprSpreadKind::Vec => static_type::VEC_TYPE,
+            hir::ExprSpreadKind::Object => static_type::OBJECT_TYPE,
+        },
+        hir::ExprKind::Group(expr) => return static_type_hash(cx, expr),
+        hir::ExprKind::Variable(name) => return cx.scopes.static_type(name),
+        _ => return None,
+    };
+
+    Some(ty.hash)
+}
+
+/// A `for` loop over an integer range which can be compiled into a counter
+/// loop, like `0..10` or `(10..=0).step_by(-2)`.
+struct RangeLoop<'hir> {
+    start: i64,
+    end: &'hir hir::Expr<'hir>,
+    step: i64,
+    inclusive: bool,
+}
+
+/// Test if the given iterator expression is a range with a literal integer
+/// start, optionally stepped by a literal integer.
+fn range_loop<'hir>(hir: &'hir hir::Expr<'hir>) -> compile::Result<Option<RangeLoop<'hir>>> {
+    fn range<'hir>(hir: &'hir hir::Expr<'hir>) -> Option<(i64, &'hir hir::Expr<'hir>, bool)> {
+        let (start, end, inclusive) = match hir.kind {
+            hir::ExprKind::Group(expr) => return range(expr),
+            hir::ExprKind::Range(hir::ExprRange::Range { start, end }) => (start, end, false),
+            hir::ExprKind::Range(hir::ExprRange::RangeInclusive { start, end }) => {
+                (start, end, true)
+            }
+            _ => return None,
+        };
+
+        let hir::ExprKind::Lit(hir::Lit::Integer(start)) = start.kind else {
+            return None;
+        };
+
+        Some((start, end, inclusive))
+    }
+
+    let (range, step) = match hir.kind {
+        hir::ExprKind::Group(expr) => return range_loop(expr),
+        hir::ExprKind::Call(hir::ExprCall {
+            call: hir::Call::Associated { target, hash },
+            args: [arg],
+        }) if *hash == Hash::ident("step_by") => {
+            let (Some(range), hir::ExprKind::Lit(hir::Lit::Integer(step))) =
+                (range(target), arg.kind)
+            else {
+                return Ok(None);
+            };
+
+            if step == 0 {
+                return Err(compile::Error::msg(
+                    arg,
+                    "`step_by` requires a non-zero step",
+                ));
+            }
+
+            (range, step)
+        }
+        _ => match range(hir) {
+            Some(range) => (range, 1),
+            None => return Ok(None),
+        },
+    };
+
+    let (start, end, inclusive) = range;
+
+    Ok(Some(RangeLoop {
+        start,
+        end,
+        step,
+        inclusive,
+    }))
+}
+
 /// Assemble an expression for loop.
 #[instrument(span = span)]
 fn expr_for<'hir>(
@@ -1565,6 +2115,12 @@ fn expr_for<'hir>(
     span: &dyn Spanned,
     needs: Needs,
 ) -> compile::Result<Asm<'hir>> {
+    if let Some(range) = range_loop(&hir.iter)? {
+        if cx.options.passes.is_enabled(Optimization::RangeLoops) {
+            return expr_for_range(cx, hir, range, span, needs);
+        }
+    }
+
     let continue_label = cx.asm.new_label("for_continue");
     let end_label = cx.asm.new_label("for_end");
     let break_label = cx.asm.new_label("for_break");
@@ -1632,6 +2188,7 @@ fn expr_for<'hir>(
         break_var_count,
         needs,
         drop: Some(iter_offset),
+        defers: cx.defers.len(),
     });
 
     // Use the memoized loop variable.
@@ -1721,6 +2278,89 @@ fn expr_for<'hir>(
     Ok(Asm::top(span))
 }
 
+/// Assemble a for loop over an integer range as a counter loop.
+#[instrument(span = span)]
+fn expr_for_range<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    hir: &'hir hir::ExprFor<'hir>,
+    range: RangeLoop<'hir>,
+    span: &dyn Spanned,
+    needs: Needs,
+) -> compile::Result<Asm<'hir>> {
+    let continue_label = cx.asm.new_label("for_continue");
+    let end_label = cx.asm.new_label("for_end");
+    let break_label = cx.asm.new_label("for_break");
+
+    let break_var_count = cx.scopes.total(span)?;
+    let loop_scope_expected = cx.scopes.child(span)?;
+
+    cx.asm
+        .push_with_comment(Inst::integer(range.start), &hir.iter, &"range start")?;
+    let counter_offset = cx.scopes.alloc(&hir.iter)?;
+
+    expr(cx, range.end, Needs::Value)?.apply(cx)?;
+    let end_offset = cx.scopes.alloc(range.end)?;
+
+    // Declare named loop variable.
+    let binding_offset = {
+        cx.asm.push(Inst::unit(), &hir.iter);
+        cx.scopes.alloc(&hir.binding)?
+    };
+
+    let continue_var_count = cx.scopes.total(span)?;
+    cx.asm.label(&continue_label)?;
+
+    cx.loops.push(Loop {
+        label: hir.label,
+        continue_label: continue_label.clone(),
+        continue_var_count,
+        break_label: break_label.clone(),
+        break_var_count,
+        needs,
+        drop: None,
+        defers: cx.defers.len(),
+    });
+
+    // Step the counter, or jump to `end_label` once it's reached the end.
+    cx.asm.iter_step(
+        counter_offset,
+        end_offset,
+        range.step,
+        range.inclusive,
+        &end_label,
+        &hir.iter,
+    );
+
+    cx.asm.push(
+        Inst::Replace {
+            offset: binding_offset,
+        },
+        &hir.binding,
+    );
+
+    let guard = cx.scopes.child(&hir.body)?;
+
+    pat_with_offset(cx, &hir.binding, binding_offset)?;
+
+    block(cx, &hir.body, Needs::None)?.apply(cx)?;
+    cx.clean_last_scope(span, guard, Needs::None)?;
+
+    cx.asm.jump(&continue_label, span);
+    cx.asm.label(&end_label)?;
+
+    cx.clean_last_scope(span, loop_scope_expected, Needs::None)?;
+
+    // NB: If a value is needed from a for loop, encode it as a unit.
+    if needs.value() {
+        cx.asm.push(Inst::unit(), span);
+    }
+
+    // NB: breaks produce their own value.
+    cx.asm.label(&break_label)?;
+    cx.loops.pop();
+    Ok(Asm::top(span))
+}
+
 /// Assemble an if expression.
 #[instrument(span = span)]
 fn expr_if<'hir>(
@@ -2090,6 +2730,10 @@ fn expr_return<'hir>(
     span: &dyn Spanned,
     _: Needs,
 ) -> compile::Result<Asm<'hir>> {
+    if cx.defers.is_emitting() {
+        return Err(compile::Error::new(span, ErrorKind::ControlFlowInDefer));
+    }
+
     // NB: drop any loop temporaries.
     for l in cx.loops.iter() {
         if let Some(offset) = l.drop {
@@ -2098,11 +2742,19 @@ fn expr_return<'hir>(
     }
 
     if let Some(e) = hir {
+        if let hir::ExprKind::Tuple(tuple) = e.kind {
+            if !tuple.items.is_empty() && cx.defers.is_empty() {
+                return_tuple(cx, tuple, span)?;
+                return Ok(Asm::top(span));
+            }
+        }
+
         return_(cx, span, e, expr)?;
     } else {
         // NB: we actually want total_var_count here since we need to clean up
         // _every_ variable declared until we reached the current return.
         let clean = cx.scopes.total(span)?;
+        defers(cx, 0, span)?;
         cx.locals_pop(clean, span);
         cx.asm.push(Inst::ReturnUnit, span);
     }
@@ -2171,7 +2823,7 @@ fn expr_select<'hir>(
 
         match branch.pat.kind {
             hir::PatKind::Path(&hir::PatPathKind::Ident(name)) => {
-                cx.scopes.define(hir::Name::Str(name), &branch.pat)?;
+                cx.define(hir::Name::Str(name), &branch.pat)?;
             }
             hir::PatKind::Ignore => {
                 cx.asm.push(Inst::Pop, &branch.body);
@@ -2213,20 +2865,55 @@ fn expr_try<'hir>(
     span: &dyn Spanned,
     needs: Needs,
 ) -> compile::Result<Asm<'hir>> {
+    if cx.defers.is_emitting() {
+        return Err(compile::Error::new(span, ErrorKind::ControlFlowInDefer));
+    }
+
     let clean = cx.scopes.total(span)?;
     let address = expr(cx, hir, Needs::Value)?.apply_targeted(cx)?;
 
-    cx.asm.push(
-        Inst::Try {
-            address,
-            clean,
-            preserve: needs.value(),
-        },
-        span,
-    );
+    if cx.defers.is_empty() {
+        cx.asm.push(
+            Inst::Try {
+                address,
+                clean,
+                preserve: needs.value(),
+            },
+            span,
+        );
+
+        if let InstAddress::Top = address {
+            cx.scopes.free(span, 1)?;
+        }
+    } else {
+        let break_label = cx.asm.new_label("try_break");
+        let end_label = cx.asm.new_label("try_end");
+
+        cx.asm
+            .try_or_jump(address, needs.value(), &break_label, span);
+
+        if let InstAddress::Top = address {
+            cx.scopes.free(span, 1)?;
+        }
+
+        cx.asm.jump(&end_label, span);
+
+        // NB: the residual value is on top of the stack, and deferred blocks
+        // have to run before it's returned.
+        cx.asm.label(&break_label)?;
+        cx.scopes.alloc(span)?;
+        defers(cx, 0, span)?;
+
+        cx.asm.push(
+            Inst::Return {
+                address: InstAddress::Top,
+                clean,
+            },
+            span,
+        );
 
-    if let InstAddress::Top = address {
         cx.scopes.free(span, 1)?;
+        cx.asm.label(&end_label)?;
     }
 
     // Why no needs.value() check here to declare another anonymous
@@ -2365,6 +3052,59 @@ fn expr_vec<'hir>(
     Ok(Asm::top(span))
 }
 
+/// Assemble a vector or object literal which spreads other values into it.
+///
+/// Each part is assembled into a value of its own, which are then combined
+/// by iterating over them.
+#[instrument(span = span)]
+fn expr_spread<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    hir: &hir::ExprSpread<'hir>,
+    span: &dyn Spanned,
+    needs: Needs,
+) -> compile::Result<Asm<'hir>> {
+    let count = hir.parts.len();
+
+    for part in hir.parts {
+        match *part {
+            hir::SpreadPart::Items(items) => {
+                expr_vec(cx, &hir::ExprSeq { items }, span, Needs::Value)?.apply(cx)?;
+            }
+            hir::SpreadPart::Fields(assignments) => {
+                let object = hir::ExprObject {
+                    kind: hir::ExprObjectKind::Anonymous,
+                    assignments,
+                };
+
+                expr_object(cx, &object, span, Needs::Value)?.apply(cx)?;
+            }
+            hir::SpreadPart::Spread(ref e) => {
+                expr(cx, e, Needs::Value)?.apply(cx)?;
+            }
+        }
+
+        cx.scopes.alloc(span)?;
+    }
+
+    match hir.kind {
+        hir::ExprSpreadKind::Vec => {
+            cx.asm.push(Inst::VecSpread { count }, span);
+        }
+        hir::ExprSpreadKind::Object => {
+            cx.asm.push(Inst::ObjectSpread { count }, span);
+        }
+    }
+
+    cx.scopes.free(span, count)?;
+
+    if !needs.value() {
+        cx.q.diagnostics.not_used(cx.source_id, span, cx.context());
+        cx.asm.push(Inst::Pop, span);
+    }
+
+    Ok(Asm::top(span))
+}
+
 /// Assemble a while loop.
 #[instrument(span = span)]
 fn expr_loop<'hir>(
@@ -2388,6 +3128,7 @@ fn expr_loop<'hir>(
         break_var_count: var_count,
         needs,
         drop: None,
+        defers: cx.defers.len(),
     });
 
     cx.asm.label(&continue_label)?;
@@ -2487,6 +3228,44 @@ fn lit<'hir>(
     Ok(Asm::top(span))
 }
 
+/// Assemble a local expression with a diverging `else` block.
+///
+/// The pattern is matched in a child scope, so that if the match fails any
+/// temporaries are cleaned up before the `else` block is evaluated in the
+/// enclosing scope. If the match succeeds the bindings in the pattern are moved
+/// into the enclosing scope.
+#[instrument(span = hir)]
+fn local_else<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    hir: &'hir hir::Local<'hir>,
+    else_: &'hir hir::Block<'hir>,
+    load: &dyn Fn(&mut Ctxt<'_, 'hir, '_>, Needs) -> compile::Result<()>,
+    needs: Needs,
+) -> compile::Result<Asm<'hir>> {
+    let false_label = cx.asm.new_label("let_else");
+    let ok_label = cx.asm.new_label("let_ok");
+
+    let expected = cx.scopes.child(hir)?;
+    let refutable = pat(cx, &hir.pat, &false_label, load)?;
+    let layer = cx.scopes.pop(expected, hir)?;
+
+    if refutable {
+        cx.asm.jump(&ok_label, hir);
+        cx.asm.label(&false_label)?;
+        block(cx, else_, Needs::None)?.apply(cx)?;
+        cx.asm.label(&ok_label)?;
+    }
+
+    cx.scopes.merge(layer, hir)?;
+
+    // If a value is needed for a let expression, it is evaluated as a unit.
+    if needs.value() {
+        cx.asm.push(Inst::unit(), hir);
+    }
+
+    Ok(Asm::top(hir))
+}
+
 /// Assemble a local expression.
 #[instrument(span = hir)]
 fn local<'hir>(
@@ -2500,23 +3279,39 @@ fn local<'hir>(
         Ok(())
     };
 
-    let false_label = cx.asm.new_label("let_panic");
+    if let Some(else_) = hir.else_ {
+        return local_else(cx, hir, else_, &load, needs);
+    }
 
-    if pat(cx, &hir.pat, &false_label, &load)? {
+    if local_unpack(cx, hir)? {
         cx.q.diagnostics
             .let_pattern_might_panic(cx.source_id, hir, cx.context());
+    } else {
+        // NB: the type is determined before the binding is defined, since the
+        // expression might refer to a variable it shadows.
+        let ty = static_type_hash(cx, &hir.expr);
+        let false_label = cx.asm.new_label("let_panic");
+
+        if pat(cx, &hir.pat, &false_label, &load)? {
+            cx.q.diagnostics
+                .let_pattern_might_panic(cx.source_id, hir, cx.context());
+
+            let ok_label = cx.asm.new_label("let_ok");
+            cx.asm.jump(&ok_label, hir);
+            cx.asm.label(&false_label)?;
+            cx.asm.push(
+                Inst::Panic {
+                    reason: PanicReason::UnmatchedPattern,
+                },
+                hir,
+            );
 
-        let ok_label = cx.asm.new_label("let_ok");
-        cx.asm.jump(&ok_label, hir);
-        cx.asm.label(&false_label)?;
-        cx.asm.push(
-            Inst::Panic {
-                reason: PanicReason::UnmatchedPattern,
-            },
-            hir,
-        );
+            cx.asm.label(&ok_label)?;
+        }
 
-        cx.asm.label(&ok_label)?;
+        if let hir::PatKind::Path(&hir::PatPathKind::Ident(name)) = hir.pat.kind {
+            cx.scopes.set_static_type(hir::Name::Str(name), ty);
+        }
     }
 
     // If a value is needed for a let expression, it is evaluated as a unit.
@@ -2526,3 +3321,72 @@ fn local<'hir>(
 
     Ok(Asm::top(hir))
 }
+
+/// Assemble a local which destructures the tuple returned by a function in
+/// the unit into plain bindings, like `let (a, b) = f()`.
+///
+/// The returned tuple is unpacked by the call itself through
+/// [Inst::CallUnpack], which avoids constructing the tuple entirely if the
+/// function returns a tuple literal.
+///
+/// Returns `false` if the local is not of this form.
+fn local_unpack<'hir>(
+    cx: &mut Ctxt<'_, 'hir, '_>,
+    hir: &'hir hir::Local<'hir>,
+) -> compile::Result<bool> {
+    let hir::PatKind::Sequence(sequence) = hir.pat.kind else {
+        return Ok(false);
+    };
+
+    let hir::PatSequenceKind::Anonymous {
+        type_check: TypeCheck::Tuple,
+        count,
+        is_open: false,
+    } = sequence.kind
+    else {
+        return Ok(false);
+    };
+
+    let is_binding = |p: &hir::Pat<'_>| {
+        matches!(
+            p.kind,
+            hir::PatKind::Ignore | hir::PatKind::Path(&hir::PatPathKind::Ident(..))
+        )
+    };
+
+    if count == 0 || !sequence.items.iter().all(is_binding) {
+        return Ok(false);
+    }
+
+    let hir::ExprKind::Call(call) = hir.expr.kind else {
+        return Ok(false);
+    };
+
+    let hir::Call::Meta { hash, local: true } = call.call else {
+        return Ok(false);
+    };
+
+    let span = &hir.expr;
+
+    for e in call.args {
+        expr(cx, e, Needs::Value)?.apply(cx)?;
+        cx.scopes.alloc(span)?;
+    }
+
+    let args = call.args.len();
+    cx.asm.push(Inst::CallUnpack { hash, args, count }, span);
+    cx.scopes.free(span, args)?;
+
+    for p in sequence.items {
+        match p.kind {
+            hir::PatKind::Path(&hir::PatPathKind::Ident(name)) => {
+                cx.define(hir::Name::Str(name), p)?;
+            }
+            _ => {
+                cx.scopes.alloc(p)?;
+            }
+        }
+    }
+
+    Ok(true)
+}
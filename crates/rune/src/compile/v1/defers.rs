@@ -0,0 +1,82 @@
+use crate::no_std::prelude::*;
+
+use crate::compile::v1::Layer;
+use crate::hir;
+
+/// A block which is deferred until the scope it was declared in is exited.
+#[derive(Clone)]
+pub(crate) struct Defer<'hir> {
+    /// The deferred block.
+    pub(crate) block: &'hir hir::Block<'hir>,
+    /// The scopes at the point where the block was deferred, which are used to
+    /// resolve the variables it refers to.
+    pub(crate) layers: Vec<Layer<'hir>>,
+}
+
+/// Deferred blocks that we are inside of.
+pub(crate) struct Defers<'hir> {
+    defers: Vec<Defer<'hir>>,
+    /// The number of deferred blocks currently being emitted.
+    emitting: usize,
+}
+
+impl<'hir> Defers<'hir> {
+    /// Construct a new collection of deferred blocks.
+    pub(crate) fn new() -> Self {
+        Self {
+            defers: vec![],
+            emitting: 0,
+        }
+    }
+
+    /// Get the number of deferred blocks.
+    pub(crate) fn len(&self) -> usize {
+        self.defers.len()
+    }
+
+    /// Test if there are no deferred blocks.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.defers.is_empty()
+    }
+
+    /// Push a deferred block.
+    pub(crate) fn push(&mut self, defer: Defer<'hir>) {
+        self.defers.push(defer);
+    }
+
+    /// Discard the deferred blocks which were pushed after `len`.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.defers.truncate(len);
+    }
+
+    /// Get the deferred blocks which were pushed after `from` in the order
+    /// they should be emitted, which is the reverse of the order they were
+    /// declared in.
+    pub(crate) fn since(&self, from: usize) -> Vec<Defer<'hir>> {
+        self.defers
+            .get(from..)
+            .unwrap_or_default()
+            .iter()
+            .rev()
+            .cloned()
+            .collect()
+    }
+
+    /// Mark that a deferred block is being emitted.
+    pub(crate) fn enter(&mut self) {
+        self.emitting += 1;
+    }
+
+    /// Mark that a deferred block has been emitted.
+    pub(crate) fn exit(&mut self) {
+        let emitting = self.emitting.checked_sub(1);
+        debug_assert!(emitting.is_some());
+        self.emitting = emitting.unwrap_or_default();
+    }
+
+    /// Test if we are currently emitting a deferred block, in which case
+    /// control flow must not escape it.
+    pub(crate) fn is_emitting(&self) -> bool {
+        self.emitting > 0
+    }
+}
@@ -22,6 +22,8 @@ pub(crate) struct Loop<'hir> {
     pub(crate) needs: Needs,
     /// Locals to drop when breaking.
     pub(crate) drop: Option<usize>,
+    /// The number of deferred blocks before the loop.
+    pub(crate) defers: usize,
 }
 
 pub(crate) struct Loops<'hir> {
@@ -1,6 +1,7 @@
 use core::fmt;
+use core::mem::replace;
 
-use crate::no_std::collections::HashMap;
+use crate::no_std::collections::{HashMap, HashSet};
 use crate::no_std::prelude::*;
 
 use crate::ast::Spanned;
@@ -9,7 +10,7 @@ use crate::compile::{self, Assembly, ErrorKind, WithSpan};
 use crate::hir;
 use crate::query::Query;
 use crate::runtime::Inst;
-use crate::SourceId;
+use crate::{Hash, SourceId};
 
 /// A locally declared variable, its calculated stack offset and where it was
 /// declared in its source file.
@@ -23,6 +24,9 @@ pub struct Var<'hir> {
     span: &'hir dyn Spanned,
     /// Variable has been taken at the given position.
     moved_at: Option<&'hir dyn Spanned>,
+    /// The type of the value stored in the variable, if it's statically
+    /// known.
+    ty: Option<Hash>,
 }
 
 impl<'hir> fmt::Debug for Var<'hir> {
@@ -32,6 +36,7 @@ impl<'hir> fmt::Debug for Var<'hir> {
             .field("name", &self.name)
             .field("span", &self.span.span())
             .field("moved_at", &self.moved_at.map(|s| s.span()))
+            .field("ty", &self.ty)
             .finish()
     }
 }
@@ -117,6 +122,8 @@ pub(crate) struct ScopeGuard(usize);
 pub(crate) struct Scopes<'hir> {
     layers: Vec<Layer<'hir>>,
     source_id: SourceId,
+    /// Names of variables which are assigned to after they've been declared.
+    reassigned: HashSet<hir::Name<'hir>>,
 }
 
 impl<'hir> Scopes<'hir> {
@@ -125,6 +132,7 @@ impl<'hir> Scopes<'hir> {
         Self {
             layers: vec![Layer::new()],
             source_id,
+            reassigned: HashSet::new(),
         }
     }
 
@@ -219,6 +227,7 @@ impl<'hir> Scopes<'hir> {
             name,
             span,
             moved_at: None,
+            ty: None,
         };
 
         layer.total += 1;
@@ -227,6 +236,38 @@ impl<'hir> Scopes<'hir> {
         Ok(offset)
     }
 
+    /// Get the statically known type of the variable with the given name.
+    pub(crate) fn static_type(&self, name: hir::Name<'hir>) -> Option<Hash> {
+        if self.reassigned.contains(&name) {
+            return None;
+        }
+
+        for layer in self.layers.iter().rev() {
+            if let Some(var) = layer.variables.get(&name) {
+                return var.ty;
+            }
+        }
+
+        None
+    }
+
+    /// Mark that a variable with the given name is assigned to somewhere in
+    /// the function being assembled, so the type of its value can't be
+    /// statically known.
+    pub(crate) fn mark_reassigned(&mut self, name: hir::Name<'hir>) {
+        self.reassigned.insert(name);
+    }
+
+    /// Set the statically known type of the variable with the given name.
+    pub(crate) fn set_static_type(&mut self, name: hir::Name<'hir>, ty: Option<Hash>) {
+        for layer in self.layers.iter_mut().rev() {
+            if let Some(var) = layer.variables.get_mut(&name) {
+                var.ty = ty;
+                return;
+            }
+        }
+    }
+
     /// Declare an anonymous variable.
     #[tracing::instrument(skip_all)]
     pub(crate) fn alloc(&mut self, span: &dyn Spanned) -> compile::Result<usize> {
@@ -328,6 +369,55 @@ impl<'hir> Scopes<'hir> {
         Ok(layer.local)
     }
 
+    /// Merge a layer which has been popped from the top of the scope back into
+    /// the current scope, so that its variables remain accessible.
+    pub(crate) fn merge(&mut self, child: Layer<'hir>, span: &dyn Spanned) -> compile::Result<()> {
+        let Some(layer) = self.layers.last_mut() else {
+            return Err(compile::Error::msg(span, "Missing head layer"));
+        };
+
+        if child.total != layer.total + child.local {
+            return Err(compile::Error::msg(
+                span,
+                "Merged scope does not follow the current scope",
+            ));
+        }
+
+        layer.variables.extend(child.variables);
+        layer.total = child.total;
+        layer.local += child.local;
+        Ok(())
+    }
+
+    /// Take a snapshot of the current scopes.
+    pub(crate) fn snapshot(&self) -> Vec<Layer<'hir>> {
+        self.layers.clone()
+    }
+
+    /// Replace the current scopes with a snapshot, with a new empty child
+    /// scope which starts at `total` variables.
+    ///
+    /// The previous scopes are returned so that they can be restored with
+    /// [restore][Scopes::restore].
+    pub(crate) fn replace(
+        &mut self,
+        mut layers: Vec<Layer<'hir>>,
+        total: usize,
+    ) -> Vec<Layer<'hir>> {
+        layers.push(Layer {
+            variables: HashMap::new(),
+            total,
+            local: 0,
+        });
+
+        replace(&mut self.layers, layers)
+    }
+
+    /// Restore scopes which were previously replaced.
+    pub(crate) fn restore(&mut self, layers: Vec<Layer<'hir>>) {
+        self.layers = layers;
+    }
+
     /// Push a scope and return an index.
     pub(crate) fn push(&mut self, layer: Layer<'hir>) -> ScopeGuard {
         self.layers.push(layer);
@@ -35,6 +35,9 @@ use crate::no_std::prelude::*;
 use crate::ast::{Span, Spanned};
 use crate::SourceId;
 
+mod explain;
+pub use self::explain::{explain, explanations, ErrorCode, Explanation};
+
 mod fatal;
 pub use self::fatal::{FatalDiagnostic, FatalDiagnosticKind};
 
@@ -45,6 +48,9 @@ cfg_emit! {
     mod emit;
     #[doc(inline)]
     pub use self::emit::EmitError;
+
+    mod json;
+    pub use self::json::{JsonDiagnostic, JsonLabel, Severity};
 }
 
 /// A single diagnostic.
@@ -269,6 +275,40 @@ impl Diagnostics {
         );
     }
 
+    /// Add a warning about an identifier which collides with a keyword reserved
+    /// for future use.
+    pub(crate) fn future_keyword(&mut self, source_id: SourceId, span: Span) {
+        self.warning(source_id, WarningDiagnosticKind::FutureKeyword { span });
+    }
+
+    /// Add a warning about a memoized function which might have side effects.
+    pub(crate) fn impure_memo(&mut self, source_id: SourceId, span: &dyn Spanned, effect: Span) {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::ImpureMemo {
+                span: span.span(),
+                effect,
+            },
+        );
+    }
+
+    /// Add a warning about the result of a call to a pure function not being
+    /// used.
+    pub(crate) fn unused_pure_call(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+        context: Option<Span>,
+    ) {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UnusedPureCall {
+                span: span.span(),
+                context,
+            },
+        );
+    }
+
     /// Push a warning to the collection of diagnostics.
     pub(crate) fn warning<T>(&mut self, source_id: SourceId, kind: T)
     where
@@ -121,23 +121,16 @@ impl VmError {
         let mut backtrace = vec![];
         let config = term::Config::default();
 
-        for l in &self.inner.stacktrace {
-            let debug_info = match l.unit.debug_info() {
-                Some(debug_info) => debug_info,
+        for frame in self.backtrace().frames() {
+            let debug_inst = match frame.debug_inst() {
+                Some(debug_inst) => debug_inst,
                 None => continue,
             };
 
-            for ip in [l.ip].into_iter().chain(l.frames.iter().rev().map(|v| v.ip)) {
-                let debug_inst = match debug_info.instruction_at(ip) {
-                    Some(debug_inst) => debug_inst,
-                    None => continue,
-                };
-
-                let source_id = debug_inst.source_id;
-                let span = debug_inst.span;
+            let source_id = debug_inst.source_id;
+            let span = debug_inst.span;
 
-                backtrace.push(StackFrame { source_id, span });
-            }
+            backtrace.push(StackFrame { source_id, span });
         }
 
         let mut labels = Vec::new();
@@ -187,6 +180,17 @@ impl VmError {
                 d::Label::primary(source_id, span.range())
                     .with_message(self.inner.error.to_string()),
             );
+
+            // NB: the spans a panic points out are in the source it was
+            // raised in.
+            if let VmErrorKind::Panic { reason } = self.inner.error.kind() {
+                for (span, message) in reason.labels() {
+                    labels.push(
+                        d::Label::secondary(source_id, span.range())
+                            .with_message(message),
+                    );
+                }
+            }
         };
 
         for at in [&self.inner.error].into_iter().chain(&self.inner.chain) {
@@ -216,6 +220,17 @@ impl VmError {
                     }
                 }
             };
+
+            if let VmErrorKind::AccessError { error } = at.kind() {
+                if let Some((source_id, span)) =
+                    error.holder().and_then(|holder| holder.location())
+                {
+                    labels.push(
+                        d::Label::secondary(source_id, span.range())
+                            .with_message("Value is borrowed here"),
+                    );
+                }
+            }
         }
 
         let diagnostic = d::Diagnostic::error().with_message(self.inner.error.to_string())
@@ -388,6 +403,22 @@ pub fn line_for(source: &Source, span: Span) -> Option<(usize, &str, Span)> {
     ))
 }
 
+/// Point out the macro invocations which the given source was expanded from.
+fn expansion_labels(sources: &Sources, source_id: SourceId, labels: &mut Vec<d::Label<SourceId>>) {
+    let mut current = sources.get(source_id).and_then(|source| source.expanded_from());
+
+    // NB: a source is always expanded from a source which was inserted before
+    // it, so this terminates.
+    while let Some(location) = current {
+        labels.push(
+            d::Label::secondary(location.source_id, location.span.range())
+                .with_message("In this macro invocation"),
+        );
+
+        current = sources.get(location.source_id).and_then(|source| source.expanded_from());
+    }
+}
+
 /// Helper to emit diagnostics for a warning.
 fn warning_diagnostics_emit<O>(
     this: &WarningDiagnostic,
@@ -398,6 +429,16 @@ fn warning_diagnostics_emit<O>(
 where
     O: WriteColor,
 {
+    let diagnostic = warning_diagnostic(this, sources)?;
+    term::emit(out, config, sources, &diagnostic)?;
+    Ok(())
+}
+
+/// Build the diagnostic describing a warning.
+pub(super) fn warning_diagnostic(
+    this: &WarningDiagnostic,
+    sources: &Sources,
+) -> Result<d::Diagnostic<SourceId>, fmt::Error> {
     let mut notes = Vec::new();
     let mut labels = Vec::new();
 
@@ -424,6 +465,18 @@ where
                 notes.push(note);
             }
         }
+        WarningDiagnosticKind::FutureKeyword { .. } => {
+            notes.push("Hint: Rename the identifier, since it will stop working once it becomes a keyword".to_string());
+        }
+        WarningDiagnosticKind::ImpureMemo { effect, .. } => {
+            labels.push(
+                d::Label::secondary(this.source_id(), effect.range())
+                    .with_message("Might have a side effect"),
+            );
+        }
+        WarningDiagnosticKind::UnusedPureCall { .. } => {
+            notes.push("Hint: Remove the call, or use its result".to_string());
+        }
         _ => {}
     };
 
@@ -433,13 +486,15 @@ where
         );
     }
 
+    expansion_labels(sources, this.source_id(), &mut labels);
+
     let diagnostic = d::Diagnostic::warning()
         .with_message("Warning")
+        .with_code(this.code().to_string())
         .with_labels(labels)
         .with_notes(notes);
 
-    term::emit(out, config, sources, &diagnostic)?;
-    Ok(())
+    Ok(diagnostic)
 }
 
 /// Custom shared helper for emitting diagnostics for a single error.
@@ -452,6 +507,21 @@ fn fatal_diagnostics_emit<O>(
 where
     O: WriteColor,
 {
+    if let FatalDiagnosticKind::Internal(message) = this.kind() {
+        writeln!(out, "internal error: {}", message)?;
+        return Ok(());
+    }
+
+    let diagnostic = fatal_diagnostic(this, sources)?;
+    term::emit(out, config, sources, &diagnostic)?;
+    Ok(())
+}
+
+/// Build the diagnostic describing a single error.
+pub(super) fn fatal_diagnostic(
+    this: &FatalDiagnostic,
+    sources: &Sources,
+) -> Result<d::Diagnostic<SourceId>, fmt::Error> {
     let mut labels = Vec::new();
     let mut notes = Vec::new();
 
@@ -461,8 +531,7 @@ where
 
     match this.kind() {
         FatalDiagnosticKind::Internal(message) => {
-            writeln!(out, "internal error: {}", message)?;
-            return Ok(());
+            return Ok(d::Diagnostic::bug().with_message(format!("internal error: {}", message)));
         }
         FatalDiagnosticKind::LinkError(error) => {
             match error {
@@ -477,17 +546,16 @@ where
                     }
 
                     let diagnostic = d::Diagnostic::error()
+                        .with_code(error.code().to_string())
                         .with_message(format!(
                             "linker error: missing function with hash `{}`",
                             hash
                         ))
                         .with_labels(labels);
 
-                    term::emit(out, config, sources, &diagnostic)?;
+                    return Ok(diagnostic);
                 }
             }
-
-            return Ok(());
         }
         FatalDiagnosticKind::CompileError(error) => {
             format_compile_error(
@@ -498,16 +566,30 @@ where
                 &mut labels,
                 &mut notes,
             )?;
+
+            for suggestion in error.suggestions() {
+                labels.push(
+                    d::Label::secondary(this.source_id(), suggestion.span().range())
+                        .with_message(suggestion.message()),
+                );
+            }
         }
     };
 
-    let diagnostic = d::Diagnostic::error()
+    if this.span().is_some() {
+        expansion_labels(sources, this.source_id(), &mut labels);
+    }
+
+    let mut diagnostic = d::Diagnostic::error()
         .with_message(this.kind().to_string())
         .with_labels(labels)
         .with_notes(notes);
 
-    term::emit(out, config, sources, &diagnostic)?;
-    return Ok(());
+    if let Some(code) = this.code() {
+        diagnostic = diagnostic.with_code(code.to_string());
+    }
+
+    return Ok(diagnostic);
 
     fn format_compile_error(
         this: &FatalDiagnostic,
@@ -596,6 +678,12 @@ where
                         .with_message("Object being defined here"),
                 );
             }
+            ErrorKind::DuplicateDiscriminant { existing, .. } => {
+                labels.push(
+                    d::Label::secondary(this.source_id(), existing.range())
+                        .with_message("Previously used here"),
+                );
+            }
             ErrorKind::ModAlreadyLoaded { existing, .. } => {
                 let (existing_source_id, existing_span) = *existing;
 
@@ -652,6 +740,34 @@ where
 
                 notes.push("You can also make the pattern non-exhaustive by adding `..`".to_string());
             }
+            ErrorKind::ConstEvaluation { error, trace } => {
+                // Each call is made from inside of the function called by
+                // the frame before it.
+                let mut source_id = this.source_id();
+
+                for frame in trace.iter() {
+                    labels.push(
+                        d::Label::secondary(source_id, frame.span.range())
+                            .with_message(format!("In call to `{}`", frame.item)),
+                    );
+
+                    source_id = frame.source_id;
+                }
+
+                return format_compile_error(this, sources, span, error, labels, notes);
+            }
+            ErrorKind::MacroExpansion {
+                source_id,
+                span: expanded,
+                error,
+            } => {
+                labels.push(
+                    d::Label::secondary(*source_id, expanded.range())
+                        .with_message("In this macro expansion"),
+                );
+
+                return format_compile_error(this, sources, span, error, labels, notes);
+            }
             _ => (),
         }
 
@@ -0,0 +1,326 @@
+use core::fmt;
+
+/// A stable code identifying the kind of a compile error or warning, which is
+/// displayed like `E0020` for errors and like `W0001` for warnings.
+///
+/// Codes are never reused, so they can be used to search for an error or to
+/// look up an extended explanation of it with [explain].
+///
+/// # Examples
+///
+/// ```
+/// use rune::diagnostics::ErrorCode;
+///
+/// let code = ErrorCode::parse("E0020").expect("valid code");
+/// assert_eq!(code.to_string(), "E0020");
+///
+/// let explanation = code.explanation().expect("code has an explanation");
+/// assert_eq!(explanation.title, "Missing local variable");
+///
+/// let code = ErrorCode::parse("W0001").expect("valid code");
+/// assert!(code.is_warning());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ErrorCode {
+    // NB: declared first so that all errors are ordered before warnings.
+    kind: CodeKind,
+    code: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum CodeKind {
+    Error,
+    Warning,
+}
+
+impl ErrorCode {
+    /// Construct a new error code.
+    pub(crate) const fn new(code: u16) -> Self {
+        Self {
+            kind: CodeKind::Error,
+            code,
+        }
+    }
+
+    /// Construct a new warning code.
+    pub(crate) const fn warning(code: u16) -> Self {
+        Self {
+            kind: CodeKind::Warning,
+            code,
+        }
+    }
+
+    /// Parse an error code like `E0020`, or a warning code like `W0001`.
+    ///
+    /// The leading letter can be lowercase, and codes without it are parsed
+    /// as error codes.
+    pub fn parse(code: &str) -> Option<Self> {
+        let code = code.trim();
+
+        let (kind, code) = match code.as_bytes().first() {
+            Some(b'E' | b'e') => (CodeKind::Error, &code[1..]),
+            Some(b'W' | b'w') => (CodeKind::Warning, &code[1..]),
+            _ => (CodeKind::Error, code),
+        };
+
+        if code.is_empty() || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let code = code.parse().ok()?;
+        Some(Self { kind, code })
+    }
+
+    /// Get the numerical value of the code.
+    pub fn get(self) -> u16 {
+        self.code
+    }
+
+    /// Test if this is the code of a warning.
+    pub fn is_warning(self) -> bool {
+        matches!(self.kind, CodeKind::Warning)
+    }
+
+    /// Look up the extended explanation of this error code.
+    pub fn explanation(self) -> Option<&'static Explanation> {
+        let index = EXPLANATIONS.binary_search_by(|e| e.code.cmp(&self)).ok()?;
+
+        EXPLANATIONS.get(index)
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self.kind {
+            CodeKind::Error => 'E',
+            CodeKind::Warning => 'W',
+        };
+
+        write!(f, "{prefix}{:04}", self.code)
+    }
+}
+
+/// The extended explanation of an [ErrorCode].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Explanation {
+    /// The error code being explained.
+    pub code: ErrorCode,
+    /// A short title of the error.
+    pub title: &'static str,
+    /// The explanation of the error, in markdown. This typically includes an
+    /// example program which causes the error.
+    pub description: &'static str,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.code, self.title)?;
+        writeln!(f)?;
+        self.description.fmt(f)
+    }
+}
+
+/// Look up the extended explanation of an error code like `E0020`, or of a
+/// warning code like `W0001`.
+///
+/// Returns `None` if the code is malformed or doesn't exist.
+///
+/// # Examples
+///
+/// ```
+/// let explanation = rune::diagnostics::explain("E0020").expect("known code");
+/// assert_eq!(explanation.code.to_string(), "E0020");
+/// assert!(explanation.description.contains("let"));
+///
+/// assert!(rune::diagnostics::explain("W0001").is_some());
+/// assert!(rune::diagnostics::explain("E9999").is_none());
+/// assert!(rune::diagnostics::explain("nonsense").is_none());
+/// ```
+pub fn explain(code: &str) -> Option<&'static Explanation> {
+    ErrorCode::parse(code)?.explanation()
+}
+
+/// Iterate over the explanations of all error and warning codes, with error
+/// codes ordered before warning codes.
+pub fn explanations() -> impl Iterator<Item = &'static Explanation> {
+    EXPLANATIONS.iter()
+}
+
+macro_rules! explanation {
+    ($code:literal, $name:literal, $title:literal) => {
+        Explanation {
+            code: ErrorCode::new($code),
+            title: $title,
+            description: include_str!(concat!("explain/", $name, ".md")),
+        }
+    };
+
+    (warning $code:literal, $name:literal, $title:literal) => {
+        Explanation {
+            code: ErrorCode::warning($code),
+            title: $title,
+            description: include_str!(concat!("explain/", $name, ".md")),
+        }
+    };
+}
+
+/// Explanations sorted by code, with error codes before warning codes.
+static EXPLANATIONS: &[Explanation] = &[
+    explanation!(1, "E0001", "A custom compile error"),
+    explanation!(2, "E0002", "Unexpected syntax"),
+    explanation!(3, "E0003", "Unsupported syntax"),
+    explanation!(4, "E0004", "Constant evaluation error"),
+    explanation!(5, "E0005", "Error in a constant function call"),
+    explanation!(6, "E0006", "Conflicting items"),
+    explanation!(7, "E0007", "Value already accessed"),
+    explanation!(8, "E0008", "Failed to encode instructions"),
+    explanation!(9, "E0009", "Missing last item id"),
+    explanation!(10, "E0010", "Item guard mismatch"),
+    explanation!(11, "E0011", "Missing scope"),
+    explanation!(12, "E0012", "Failed to pop scope"),
+    explanation!(13, "E0013", "Missing query id"),
+    explanation!(14, "E0014", "Invalid escape sequence"),
+    explanation!(15, "E0015", "Failed to load a source file"),
+    explanation!(16, "E0016", "Module file not found"),
+    explanation!(17, "E0017", "Module loaded more than once"),
+    explanation!(18, "E0018", "Missing macro"),
+    explanation!(19, "E0019", "Missing `self`"),
+    explanation!(20, "E0020", "Missing local variable"),
+    explanation!(21, "E0021", "Missing item"),
+    explanation!(22, "E0022", "Missing item for hash"),
+    explanation!(23, "E0023", "Missing item with generic parameters"),
+    explanation!(24, "E0024", "Unsupported crate prefix"),
+    explanation!(25, "E0025", "Module loaded from a source without a path"),
+    explanation!(26, "E0026", "Unsupported module root"),
+    explanation!(27, "E0027", "Unsupported module item"),
+    explanation!(28, "E0028", "Unsupported `self`"),
+    explanation!(29, "E0029", "Unsupported unary operator"),
+    explanation!(30, "E0030", "Unsupported binary operator"),
+    explanation!(31, "E0031", "Not an object"),
+    explanation!(32, "E0032", "Missing field in struct literal"),
+    explanation!(33, "E0033", "Unknown field in struct literal"),
+    explanation!(34, "E0034", "Unsupported assignment"),
+    explanation!(35, "E0035", "Unsupported binary expression"),
+    explanation!(36, "E0036", "Unsupported reference"),
+    explanation!(37, "E0037", "Unsupported select pattern"),
+    explanation!(38, "E0038", "Wrong number of arguments"),
+    explanation!(39, "E0039", "Unsupported pattern"),
+    explanation!(40, "E0040", "Unsupported binding"),
+    explanation!(41, "E0041", "Duplicate key in object"),
+    explanation!(42, "E0042", "Duplicate enum discriminant"),
+    explanation!(43, "E0043", "Unsupported enum discriminant"),
+    explanation!(44, "E0044", "Enum discriminant overflow"),
+    explanation!(45, "E0045", "Instance function outside of `impl`"),
+    explanation!(46, "E0046", "Unsupported tuple index"),
+    explanation!(47, "E0047", "`break` outside of loop"),
+    explanation!(48, "E0048", "`continue` outside of loop"),
+    explanation!(49, "E0049", "Control flow escaping a `defer` block"),
+    explanation!(50, "E0050", "`let else` block must diverge"),
+    explanation!(51, "E0051", "Multiple `default` branches in `select`"),
+    explanation!(52, "E0052", "Missing semicolon"),
+    explanation!(53, "E0053", "Function both `async` and `const`"),
+    explanation!(54, "E0054", "Block both `async` and `const`"),
+    explanation!(55, "E0055", "Unsupported closure"),
+    explanation!(56, "E0056", "`Self` outside of `impl`"),
+    explanation!(57, "E0057", "`super` at the root module"),
+    explanation!(58, "E0058", "`super` in path starting with `Self`"),
+    explanation!(59, "E0059", "Path component after generic arguments"),
+    explanation!(60, "E0060", "Segment after wildcard or group import"),
+    explanation!(61, "E0061", "Alias of wildcard or group import"),
+    explanation!(62, "E0062", "Conflicting function"),
+    explanation!(63, "E0063", "Conflicting function re-export"),
+    explanation!(64, "E0064", "Conflicting constant"),
+    explanation!(65, "E0065", "Missing static string"),
+    explanation!(66, "E0066", "Missing static byte string"),
+    explanation!(67, "E0067", "Conflicting static string"),
+    explanation!(68, "E0068", "Conflicting static byte string"),
+    explanation!(69, "E0069", "Missing static object keys"),
+    explanation!(70, "E0070", "Conflicting static object keys"),
+    explanation!(71, "E0071", "Missing loop label"),
+    explanation!(72, "E0072", "Segment only supported first in path"),
+    explanation!(73, "E0073", "Unsupported visibility"),
+    explanation!(74, "E0074", "Unexpected kind of item"),
+    explanation!(75, "E0075", "No such built-in macro"),
+    explanation!(76, "E0076", "Use of moved variable"),
+    explanation!(77, "E0077", "Unsupported generic arguments"),
+    explanation!(78, "E0078", "Nested `#[test]` function"),
+    explanation!(79, "E0079", "Nested `#[bench]` function"),
+    explanation!(80, "E0080", "Missing function for hash"),
+    explanation!(81, "E0081", "Conflicting function hash"),
+    explanation!(82, "E0082", "Non-exhaustive struct pattern"),
+    explanation!(83, "E0083", "Missing label location"),
+    explanation!(84, "E0084", "Macro recursion limit reached"),
+    explanation!(85, "E0085", "`yield` in constant function"),
+    explanation!(86, "E0086", "`.await` in constant context"),
+    explanation!(87, "E0087", "`.await` outside of async context"),
+    explanation!(88, "E0088", "Expected end of input"),
+    explanation!(89, "E0089", "Unexpected end of input"),
+    explanation!(90, "E0090", "Bad lexer mode"),
+    explanation!(91, "E0091", "Expected escape sequence"),
+    explanation!(92, "E0092", "Unterminated string literal"),
+    explanation!(93, "E0093", "Unterminated byte string literal"),
+    explanation!(94, "E0094", "Unterminated character literal"),
+    explanation!(95, "E0095", "Unterminated byte literal"),
+    explanation!(96, "E0096", "Unclosed character literal"),
+    explanation!(97, "E0097", "Expected character or label"),
+    explanation!(98, "E0098", "Unclosed byte literal"),
+    explanation!(99, "E0099", "Unexpected character"),
+    explanation!(100, "E0100", "Parentheses required for precedence"),
+    explanation!(101, "E0101", "Number literal out of bounds"),
+    explanation!(102, "E0102", "Unsupported field access"),
+    explanation!(103, "E0103", "Unbalanced macro delimiters"),
+    explanation!(104, "E0104", "Duplicate attribute"),
+    explanation!(105, "E0105", "Missing source"),
+    explanation!(106, "E0106", "Unterminated block comment"),
+    explanation!(107, "E0107", "Bad source slice"),
+    explanation!(108, "E0108", "Bad synthetic identifier"),
+    explanation!(109, "E0109", "Invalid character literal"),
+    explanation!(110, "E0110", "Invalid byte literal"),
+    explanation!(111, "E0111", "Invalid number literal"),
+    explanation!(112, "E0112", "Ambiguous item"),
+    explanation!(113, "E0113", "Ambiguous context item"),
+    explanation!(114, "E0114", "Item not visible"),
+    explanation!(115, "E0115", "Module not visible"),
+    explanation!(116, "E0116", "Missing module"),
+    explanation!(117, "E0117", "Import cycle"),
+    explanation!(118, "E0118", "Import recursion limit reached"),
+    explanation!(119, "E0119", "Incomplete import"),
+    explanation!(120, "E0120", "Conflicting variant type information"),
+    explanation!(121, "E0121", "Conflicting type information"),
+    explanation!(122, "E0122", "Arena slice out of bounds"),
+    explanation!(123, "E0123", "Arena allocation error"),
+    explanation!(124, "E0124", "Unsupported `..` in pattern"),
+    explanation!(125, "E0125", "Unsupported `mut`"),
+    explanation!(126, "E0126", "Unsupported number suffix"),
+    explanation!(127, "E0127", "Unsupported object spread"),
+    explanation!(128, "E0128", "Not a constant expression"),
+    explanation!(129, "E0129", "Constant cycle"),
+    explanation!(130, "E0130", "Unsupported item in constant"),
+    explanation!(131, "E0131", "Type mismatch in constant"),
+    explanation!(132, "E0132", "Constant evaluation budget exceeded"),
+    explanation!(133, "E0133", "Constant recursion limit exceeded"),
+    explanation!(134, "E0134", "Missing tuple index in constant"),
+    explanation!(135, "E0135", "Missing field in constant"),
+    explanation!(136, "E0136", "Missing constant"),
+    explanation!(137, "E0137", "`break` outside of loop in constant"),
+    explanation!(138, "E0138", "Wrong number of constant function arguments"),
+    explanation!(139, "E0139", "Missing function"),
+    explanation!(140, "E0140", "Missing external constant"),
+    explanation!(141, "E0141", "Item defined in the base unit"),
+    explanation!(142, "E0142", "Variable shadowing denied"),
+    explanation!(143, "E0143", "Missing type annotation"),
+    explanation!(144, "E0144", "Integer overflow in constant"),
+    explanation!(145, "E0145", "Integer underflow in constant"),
+    explanation!(146, "E0146", "Division by zero in constant"),
+    explanation!(warning 1, "W0001", "Value not used"),
+    explanation!(warning 2, "W0002", "Pattern might panic"),
+    explanation!(warning 3, "W0003", "Template string without expansions"),
+    explanation!(warning 4, "W0004", "Unnecessary call parentheses"),
+    explanation!(warning 5, "W0005", "Unnecessary semicolon"),
+    explanation!(warning 6, "W0006", "Identifier reserved as a future keyword"),
+    explanation!(warning 7, "W0007", "Memoized function with side effects"),
+    explanation!(warning 8, "W0008", "Unused result of pure call"),
+    explanation!(warning 9, "W0009", "A custom compile warning"),
+];
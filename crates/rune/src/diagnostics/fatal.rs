@@ -5,6 +5,7 @@ use crate::no_std::prelude::*;
 #[cfg(feature = "emit")]
 use crate::ast::{Span, Spanned};
 use crate::compile::{self, LinkerError};
+use crate::diagnostics::ErrorCode;
 use crate::SourceId;
 
 /// Fatal diagnostic emitted during compilation. Fatal diagnostics indicates an
@@ -28,6 +29,18 @@ impl FatalDiagnostic {
         &self.kind
     }
 
+    /// The stable code identifying the kind of the error, if it has one.
+    ///
+    /// See [explain][crate::diagnostics::explain] for how to look up an
+    /// extended explanation of the error.
+    pub fn code(&self) -> Option<ErrorCode> {
+        match &*self.kind {
+            FatalDiagnosticKind::CompileError(error) => Some(error.code()),
+            FatalDiagnosticKind::LinkError(error) => Some(error.code()),
+            FatalDiagnosticKind::Internal(..) => None,
+        }
+    }
+
     /// The kind of the load error.
     #[cfg(test)]
     pub(crate) fn into_kind(self) -> FatalDiagnosticKind {
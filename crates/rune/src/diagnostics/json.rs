@@ -0,0 +1,170 @@
+use crate::no_std::prelude::*;
+
+use codespan_reporting::diagnostic as d;
+use serde::Serialize;
+
+use crate::ast::{Span, Spanned};
+use crate::diagnostics::emit::{fatal_diagnostic, warning_diagnostic};
+use crate::diagnostics::{Diagnostic, EmitError, FatalDiagnosticKind};
+use crate::{Diagnostics, SourceId, Sources};
+
+/// The severity of a [JsonDiagnostic].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Severity {
+    /// An error, which causes compilation to fail.
+    Error,
+    /// A warning.
+    Warning,
+}
+
+/// A serializable representation of a [Diagnostic], as produced by
+/// [Diagnostics::to_json].
+///
+/// Spans are serialized as a pair of byte offsets into the source they point
+/// into, like `[12, 18]`.
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct JsonDiagnostic {
+    /// The severity of the diagnostic.
+    pub severity: Severity,
+    /// The kind of the diagnostic, which is one of `compile-error`,
+    /// `link-error` or `internal` for errors, and the name of the warning like
+    /// `not-used` for warnings.
+    pub kind: &'static str,
+    /// The stable code of the diagnostic, like `E0020` for errors and `W0001`
+    /// for warnings.
+    pub code: Option<String>,
+    /// The title of the code, like `Missing local variable`.
+    pub title: Option<&'static str>,
+    /// The message of the diagnostic.
+    pub message: String,
+    /// The id of the source the diagnostic originates from.
+    pub source_id: SourceId,
+    /// The name of the source the diagnostic originates from, which is its
+    /// path if it was loaded from a file.
+    pub file: Option<String>,
+    /// The span of the source the diagnostic points to.
+    pub span: Option<Span>,
+    /// Labels pointing to the parts of sources relevant to the diagnostic.
+    pub labels: Vec<JsonLabel>,
+    /// Help text, like hints for how to address the diagnostic.
+    pub help: Vec<String>,
+}
+
+/// A label of a [JsonDiagnostic].
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct JsonLabel {
+    /// If this is a primary label, which points to the cause of the
+    /// diagnostic.
+    pub primary: bool,
+    /// The id of the source the label points into.
+    pub source_id: SourceId,
+    /// The span the label points to.
+    pub span: Span,
+    /// The message of the label.
+    pub message: String,
+}
+
+impl Diagnostics {
+    /// Convert the diagnostics into a serializable representation, which can
+    /// be used to report them in a machine-readable format.
+    ///
+    /// This is what `rune` emits with `--message-format=json`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Diagnostics, Source, Sources};
+    /// use rune::diagnostics::Severity;
+    ///
+    /// let mut sources = Sources::new();
+    /// sources.insert(Source::new("main", "pub fn main() { x }"));
+    ///
+    /// let mut diagnostics = Diagnostics::new();
+    ///
+    /// let result = rune::prepare(&mut sources)
+    ///     .with_diagnostics(&mut diagnostics)
+    ///     .build();
+    ///
+    /// assert!(result.is_err());
+    ///
+    /// let json = diagnostics.to_json(&sources)?;
+    /// assert_eq!(json.len(), 1);
+    /// assert_eq!(json[0].severity, Severity::Error);
+    /// assert_eq!(json[0].code.as_deref(), Some("E0020"));
+    /// assert_eq!(json[0].file.as_deref(), Some("main"));
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn to_json(&self, sources: &Sources) -> Result<Vec<JsonDiagnostic>, EmitError> {
+        let mut output = Vec::with_capacity(self.diagnostics().len());
+
+        for diagnostic in self.diagnostics() {
+            let json = match diagnostic {
+                Diagnostic::Fatal(error) => {
+                    let kind = match error.kind() {
+                        FatalDiagnosticKind::CompileError(..) => "compile-error",
+                        FatalDiagnosticKind::LinkError(..) => "link-error",
+                        FatalDiagnosticKind::Internal(..) => "internal",
+                    };
+
+                    let code = error.code();
+                    let diagnostic = fatal_diagnostic(error, sources)?;
+
+                    JsonDiagnostic {
+                        severity: Severity::Error,
+                        kind,
+                        code: code.map(|code| code.to_string()),
+                        title: code.and_then(|code| Some(code.explanation()?.title)),
+                        message: diagnostic.message.clone(),
+                        source_id: error.source_id(),
+                        file: file(sources, error.source_id()),
+                        span: error.span(),
+                        labels: labels(&diagnostic),
+                        help: diagnostic.notes,
+                    }
+                }
+                Diagnostic::Warning(warning) => {
+                    let code = warning.code();
+                    let diagnostic = warning_diagnostic(warning, sources)?;
+
+                    JsonDiagnostic {
+                        severity: Severity::Warning,
+                        kind: warning.kind().name(),
+                        code: Some(code.to_string()),
+                        title: code.explanation().map(|explanation| explanation.title),
+                        message: warning.to_string(),
+                        source_id: warning.source_id(),
+                        file: file(sources, warning.source_id()),
+                        span: Some(warning.span()),
+                        labels: labels(&diagnostic),
+                        help: diagnostic.notes,
+                    }
+                }
+            };
+
+            output.push(json);
+        }
+
+        Ok(output)
+    }
+}
+
+fn file(sources: &Sources, source_id: SourceId) -> Option<String> {
+    Some(sources.get(source_id)?.name().to_owned())
+}
+
+fn labels(diagnostic: &d::Diagnostic<SourceId>) -> Vec<JsonLabel> {
+    diagnostic
+        .labels
+        .iter()
+        .map(|label| JsonLabel {
+            primary: label.style == d::LabelStyle::Primary,
+            source_id: label.file_id,
+            span: Span::new(label.range.start, label.range.end),
+            message: label.message.clone(),
+        })
+        .collect()
+}
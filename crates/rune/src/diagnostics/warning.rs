@@ -1,12 +1,15 @@
 use core::fmt;
 
+use crate::no_std::prelude::*;
+
 use crate::ast::Span;
 use crate::ast::Spanned;
+use crate::diagnostics::ErrorCode;
 use crate::SourceId;
 
 /// Warning diagnostic emitted during compilation. Warning diagnostics indicates
 /// an recoverable issues.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct WarningDiagnostic {
     /// The id of the source where the warning happened.
     pub(crate) source_id: SourceId,
@@ -26,6 +29,14 @@ impl WarningDiagnostic {
         &self.kind
     }
 
+    /// The stable code identifying the kind of the warning, like `W0001`.
+    ///
+    /// See [explain][crate::diagnostics::explain] for how to look up an
+    /// extended explanation of the warning.
+    pub fn code(&self) -> ErrorCode {
+        self.kind.code()
+    }
+
     #[cfg(test)]
     pub(crate) fn into_kind(self) -> WarningDiagnosticKind {
         self.kind
@@ -38,8 +49,12 @@ impl WarningDiagnostic {
             WarningDiagnosticKind::LetPatternMightPanic { context, .. }
             | WarningDiagnosticKind::RemoveTupleCallParams { context, .. }
             | WarningDiagnosticKind::NotUsed { context, .. }
-            | WarningDiagnosticKind::TemplateWithoutExpansions { context, .. } => *context,
-            WarningDiagnosticKind::UnnecessarySemiColon { .. } => None,
+            | WarningDiagnosticKind::TemplateWithoutExpansions { context, .. }
+            | WarningDiagnosticKind::UnusedPureCall { context, .. } => *context,
+            WarningDiagnosticKind::UnnecessarySemiColon { .. }
+            | WarningDiagnosticKind::FutureKeyword { .. }
+            | WarningDiagnosticKind::ImpureMemo { .. }
+            | WarningDiagnosticKind::Custom { .. } => None,
         }
     }
 }
@@ -53,6 +68,10 @@ impl Spanned for WarningDiagnostic {
             WarningDiagnosticKind::TemplateWithoutExpansions { span, .. } => *span,
             WarningDiagnosticKind::RemoveTupleCallParams { span, .. } => *span,
             WarningDiagnosticKind::UnnecessarySemiColon { span, .. } => *span,
+            WarningDiagnosticKind::FutureKeyword { span, .. } => *span,
+            WarningDiagnosticKind::ImpureMemo { span, .. } => *span,
+            WarningDiagnosticKind::UnusedPureCall { span, .. } => *span,
+            WarningDiagnosticKind::Custom { span, .. } => *span,
         }
     }
 }
@@ -72,7 +91,7 @@ impl crate::no_std::error::Error for WarningDiagnostic {
 }
 
 /// The kind of a [WarningDiagnostic].
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 #[allow(missing_docs)]
 #[non_exhaustive]
 pub enum WarningDiagnosticKind {
@@ -112,6 +131,75 @@ pub enum WarningDiagnosticKind {
         /// Span where the semi-colon is.
         span: Span,
     },
+    /// An identifier is used which is reserved to become a keyword in the
+    /// future.
+    FutureKeyword {
+        /// Span of the identifier.
+        span: Span,
+    },
+    /// A function marked with `#[memo]` might have side effects, which are
+    /// skipped when its result is cached.
+    ImpureMemo {
+        /// Span of the memoized function.
+        span: Span,
+        /// Span of the expression which might have a side effect.
+        effect: Span,
+    },
+    /// The result of a call to a pure function is not used, so the call has
+    /// no effect.
+    UnusedPureCall {
+        /// Span of the call.
+        span: Span,
+        /// The context in which the call is made.
+        context: Option<Span>,
+    },
+    /// A custom warning, like one emitted by a compile pass.
+    Custom {
+        /// Span of the warning.
+        span: Span,
+        /// The warning message.
+        message: Box<str>,
+    },
+}
+
+impl WarningDiagnosticKind {
+    /// Get the stable code of the warning.
+    ///
+    /// NB: codes are assigned in declaration order and must never change or
+    /// be reused, so new variants must be assigned new codes.
+    pub(crate) fn code(&self) -> ErrorCode {
+        let code = match self {
+            WarningDiagnosticKind::NotUsed { .. } => 1,
+            WarningDiagnosticKind::LetPatternMightPanic { .. } => 2,
+            WarningDiagnosticKind::TemplateWithoutExpansions { .. } => 3,
+            WarningDiagnosticKind::RemoveTupleCallParams { .. } => 4,
+            WarningDiagnosticKind::UnnecessarySemiColon { .. } => 5,
+            WarningDiagnosticKind::FutureKeyword { .. } => 6,
+            WarningDiagnosticKind::ImpureMemo { .. } => 7,
+            WarningDiagnosticKind::UnusedPureCall { .. } => 8,
+            WarningDiagnosticKind::Custom { .. } => 9,
+        };
+
+        ErrorCode::warning(code)
+    }
+
+    /// A stable name identifying the kind of the warning, like `not-used`.
+    #[cfg(feature = "emit")]
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            WarningDiagnosticKind::NotUsed { .. } => "not-used",
+            WarningDiagnosticKind::LetPatternMightPanic { .. } => "let-pattern-might-panic",
+            WarningDiagnosticKind::TemplateWithoutExpansions { .. } => {
+                "template-without-expansions"
+            }
+            WarningDiagnosticKind::RemoveTupleCallParams { .. } => "remove-tuple-call-params",
+            WarningDiagnosticKind::UnnecessarySemiColon { .. } => "unnecessary-semi-colon",
+            WarningDiagnosticKind::FutureKeyword { .. } => "future-keyword",
+            WarningDiagnosticKind::ImpureMemo { .. } => "impure-memo",
+            WarningDiagnosticKind::UnusedPureCall { .. } => "unused-pure-call",
+            WarningDiagnosticKind::Custom { .. } => "custom",
+        }
+    }
 }
 
 impl fmt::Display for WarningDiagnosticKind {
@@ -131,6 +219,16 @@ impl fmt::Display for WarningDiagnosticKind {
             WarningDiagnosticKind::UnnecessarySemiColon { .. } => {
                 write!(f, "Unnecessary semicolon")
             }
+            WarningDiagnosticKind::FutureKeyword { .. } => {
+                write!(f, "Identifier is reserved as a future keyword")
+            }
+            WarningDiagnosticKind::ImpureMemo { .. } => {
+                write!(f, "Memoized function might have side effects")
+            }
+            WarningDiagnosticKind::UnusedPureCall { .. } => {
+                write!(f, "Result of call to pure function is not used")
+            }
+            WarningDiagnosticKind::Custom { message, .. } => write!(f, "{message}"),
         }
     }
 }
@@ -285,6 +285,10 @@ impl<'a> Context<'a> {
     }
 
     fn context_meta_to_meta(&self, meta: &'a ContextMeta) -> Option<Meta<'a>> {
+        if meta.docs.is_hidden() {
+            return None;
+        }
+
         let kind = match &meta.kind {
             meta::Kind::Type { .. } => Kind::Type,
             meta::Kind::Struct { .. } => Kind::Struct,
@@ -1,4 +1,17 @@
 //! Helper to format Rune code.
+//!
+//! Sources are formatted with a canonical style, regardless of how they were
+//! laid out originally. Comments and empty lines separating items and
+//! statements are preserved.
+//!
+//! # Examples
+//!
+//! ```
+//! let formatted = rune::fmt::format_source("fn main(){let x=1;x+2}")?;
+//!
+//! assert_eq!(formatted, "fn main() {\n    let x = 1;\n    x + 2\n}\n");
+//! # Ok::<_, rune::Error>(())
+//! ```
 
 #[cfg(test)]
 mod tests;
@@ -15,9 +28,22 @@ use crate::ast;
 use crate::parse::{Parse, Parser};
 use crate::SourceId;
 
-use self::error::FormattingError;
+pub use self::error::FormattingError;
+use self::error::FormattingErrorKind;
 use self::printer::Printer;
 
+/// Format the given source, returning the formatted source.
+///
+/// Formatting fails if the source can't be parsed.
+pub fn format_source(source: &str) -> Result<String, FormattingError> {
+    let output = layout_source(source)?;
+
+    match String::from_utf8(output) {
+        Ok(output) => Ok(output),
+        Err(error) => Err(FormattingErrorKind::Utf8(error.utf8_error()).into()),
+    }
+}
+
 /// Format the given source.
 pub(crate) fn layout_source(source: &str) -> Result<Vec<u8>, FormattingError> {
     let mut parser = Parser::new(source, SourceId::new(0), true);
@@ -8,7 +8,8 @@ use core::str::CharIndices;
 use crate::no_std::prelude::*;
 
 use crate::ast::Span;
-use crate::fmt::FormattingError;
+
+use super::error::{FormattingError, FormattingErrorKind};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(super) enum CommentKind {
@@ -48,7 +49,7 @@ pub(super) fn parse_comments(input: &str) -> Result<Vec<Comment>, FormattingErro
                     }
                 }
                 Some((_, '*')) => {
-                    let end = parse_block_comment(&mut chars).ok_or(FormattingError::Eof)?;
+                    let end = parse_block_comment(&mut chars).ok_or(FormattingErrorKind::Eof)?;
 
                     if !input[idx..end].starts_with("/**") && !input[idx..end].starts_with("/*!") {
                         comments.push(Comment {
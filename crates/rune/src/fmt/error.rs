@@ -1,50 +1,76 @@
 use core::fmt;
+use core::str;
 
 use crate::no_std::io;
 
 use crate::compile;
 
+/// Error raised when formatting Rune code fails.
 #[derive(Debug)]
-#[non_exhaustive]
-pub(crate) enum FormattingError {
+pub struct FormattingError {
+    kind: FormattingErrorKind,
+}
+
+impl FormattingError {
+    /// Get the compile error which caused formatting to fail, if the source
+    /// couldn't be parsed.
+    pub fn as_compile_error(&self) -> Option<&compile::Error> {
+        match &self.kind {
+            FormattingErrorKind::CompileError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum FormattingErrorKind {
     Io(io::Error),
     InvalidSpan(usize, usize, usize),
     CompileError(compile::Error),
+    Utf8(str::Utf8Error),
     Eof,
 }
 
 impl fmt::Display for FormattingError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            FormattingError::Io(error) => error.fmt(f),
-            FormattingError::InvalidSpan(from, to, max) => {
+        match &self.kind {
+            FormattingErrorKind::Io(error) => error.fmt(f),
+            FormattingErrorKind::InvalidSpan(from, to, max) => {
                 write!(f, "Invalid span {from}..{to} but max is {max}",)
             }
-            FormattingError::CompileError(error) => error.fmt(f),
-            FormattingError::Eof {} => write!(f, "Unexpected end of input"),
+            FormattingErrorKind::CompileError(error) => error.fmt(f),
+            FormattingErrorKind::Utf8(error) => error.fmt(f),
+            FormattingErrorKind::Eof {} => write!(f, "Unexpected end of input"),
         }
     }
 }
 
+impl From<FormattingErrorKind> for FormattingError {
+    #[inline]
+    fn from(kind: FormattingErrorKind) -> Self {
+        FormattingError { kind }
+    }
+}
+
 impl From<io::Error> for FormattingError {
     #[inline]
     fn from(error: io::Error) -> Self {
-        FormattingError::Io(error)
+        FormattingError::from(FormattingErrorKind::Io(error))
     }
 }
 
 impl From<compile::Error> for FormattingError {
     #[inline]
     fn from(error: compile::Error) -> Self {
-        FormattingError::CompileError(error)
+        FormattingError::from(FormattingErrorKind::CompileError(error))
     }
 }
 
 impl crate::no_std::error::Error for FormattingError {
     fn source(&self) -> Option<&(dyn crate::no_std::error::Error + 'static)> {
-        match self {
-            FormattingError::Io(error) => Some(error),
-            FormattingError::CompileError(error) => Some(error),
+        match &self.kind {
+            FormattingErrorKind::Io(error) => Some(error),
+            FormattingErrorKind::CompileError(error) => Some(error),
             _ => None,
         }
     }
@@ -12,7 +12,7 @@ use crate::no_std::prelude::*;
 use crate::ast::Span;
 
 use super::comments::Comment;
-use super::error::FormattingError;
+use super::error::{FormattingError, FormattingErrorKind};
 use super::whitespace::EmptyLine;
 
 pub(super) struct IndentedWriter {
@@ -171,11 +171,12 @@ impl<'a> SpanInjectionWriter<'a> {
 
     fn resolve(&self, span: Span) -> Result<&'a str, FormattingError> {
         let Some(s) = self.source.get(span.range()) else {
-            return Err(FormattingError::InvalidSpan(
+            return Err(FormattingErrorKind::InvalidSpan(
                 span.start.into_usize(),
                 span.end.into_usize(),
                 self.source.len(),
-            ));
+            )
+            .into());
         };
 
         Ok(s)
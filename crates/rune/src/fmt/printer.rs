@@ -7,7 +7,7 @@ use crate::no_std::prelude::*;
 
 use crate::ast::{self, Span, Spanned};
 
-use super::error::FormattingError;
+use super::error::{FormattingError, FormattingErrorKind};
 use super::indent_writer::IndentedWriter;
 use super::indent_writer::SpanInjectionWriter;
 
@@ -55,11 +55,12 @@ impl<'a> Printer<'a> {
 
     pub(super) fn resolve(&self, span: Span) -> Result<&'a str> {
         let Some(s) = self.source.get(span.range()) else {
-            return Err(FormattingError::InvalidSpan(
+            return Err(FormattingErrorKind::InvalidSpan(
                 span.start.into_usize(),
                 span.end.into_usize(),
                 self.source.len(),
-            ));
+            )
+            .into());
         };
 
         Ok(s)
@@ -132,6 +133,8 @@ impl<'a> Printer<'a> {
             ast::Item::Impl(item) => self.visit_impl(item, semi)?,
             ast::Item::Mod(item) => self.visit_mod(item, semi)?,
             ast::Item::Const(item) => self.visit_const(item, semi)?,
+            ast::Item::ExternConst(item) => self.visit_extern_const(item, semi)?,
+            ast::Item::Macro(item) => self.visit_item_macro(item, semi)?,
             ast::Item::MacroCall(item) => self.visit_macro_call(item, semi)?,
         }
 
@@ -142,6 +145,73 @@ impl<'a> Printer<'a> {
         Ok(())
     }
 
+    fn visit_extern_const(
+        &mut self,
+        ast: &ast::ItemExternConst,
+        semi: Option<ast::SemiColon>,
+    ) -> Result<()> {
+        let ast::ItemExternConst {
+            id: _,
+            attributes,
+            visibility,
+            extern_token,
+            const_token,
+            name,
+        } = ast;
+
+        for attribute in attributes {
+            self.visit_attribute(attribute)?;
+        }
+        self.writer.newline()?;
+
+        self.emit_visibility(visibility)?;
+
+        self.writer
+            .write_spanned_raw(extern_token.span, false, true)?;
+        self.writer
+            .write_spanned_raw(const_token.span, false, true)?;
+        self.writer.write_spanned_raw(name.span, false, false)?;
+
+        if let Some(semi) = semi {
+            self.writer.write_spanned_raw(semi.span, false, false)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_item_macro(
+        &mut self,
+        ast: &ast::ItemMacro,
+        semi: Option<ast::SemiColon>,
+    ) -> Result<()> {
+        // Note: the matcher and the body are token streams, which are emitted
+        // as they are written.
+        let ast::ItemMacro {
+            attributes,
+            macro_token,
+            name,
+            matcher,
+            body,
+        } = ast;
+
+        for attribute in attributes {
+            self.visit_attribute(attribute)?;
+        }
+        self.writer.newline()?;
+
+        self.writer
+            .write_spanned_raw(macro_token.span, false, true)?;
+        self.writer.write_spanned_raw(name.span, false, false)?;
+        self.writer.write_spanned_raw(matcher.span(), false, true)?;
+        self.writer.write_spanned_raw(body.span(), false, false)?;
+
+        if let Some(semi) = semi {
+            self.writer.write_spanned_raw(semi.span, false, false)?;
+        }
+
+        Ok(())
+    }
+
     fn visit_const(&mut self, ast: &ast::ItemConst, semi: Option<ast::SemiColon>) -> Result<()> {
         let ast::ItemConst {
             id: _,
@@ -223,6 +293,7 @@ impl<'a> Printer<'a> {
             impl_,
             path,
             open,
+            consts,
             functions,
             close,
         } = item;
@@ -240,6 +311,11 @@ impl<'a> Printer<'a> {
 
         self.writer.indent();
 
+        for (item, semi) in consts {
+            self.visit_const(item, Some(*semi))?;
+            self.writer.newline()?;
+        }
+
         for function in functions {
             self.visit_fn(function, None)?;
             self.writer.newline()?;
@@ -370,6 +446,7 @@ impl<'a> Printer<'a> {
             attributes,
             name,
             body,
+            discriminant,
         } = ast;
 
         for attribute in attributes {
@@ -381,6 +458,12 @@ impl<'a> Printer<'a> {
 
         self.visit_variant_body(body)?;
 
+        if let Some((eq, expr)) = discriminant {
+            self.writer.write_unspanned(" ")?;
+            self.writer.write_spanned_raw(eq.span, false, true)?;
+            self.visit_expr(expr)?;
+        }
+
         Ok(())
     }
 
@@ -457,6 +540,7 @@ impl<'a> Printer<'a> {
             fn_token,
             name,
             args,
+            output,
             body,
         } = item;
 
@@ -491,10 +575,7 @@ impl<'a> Printer<'a> {
         };
 
         for (arg, comma) in args {
-            match arg {
-                ast::FnArg::SelfValue(selfvalue) => self.visit_self_value(selfvalue)?,
-                ast::FnArg::Pat(pattern) => self.visit_pattern(pattern)?,
-            }
+            self.visit_fn_arg(arg)?;
 
             if let Some(comma) = comma {
                 self.writer
@@ -509,6 +590,13 @@ impl<'a> Printer<'a> {
 
         self.writer
             .write_spanned_raw(args.close.span, false, true)?;
+
+        if let Some((arrow, ty)) = output {
+            self.writer.write_spanned_raw(arrow.span, false, true)?;
+            self.visit_type(ty)?;
+            self.writer.write_unspanned(" ")?;
+        }
+
         self.visit_block(body)?;
 
         if let Some(semi) = semi {
@@ -518,6 +606,26 @@ impl<'a> Printer<'a> {
         Ok(())
     }
 
+    fn visit_fn_arg(&mut self, arg: &ast::FnArg) -> Result<()> {
+        match arg {
+            ast::FnArg::SelfValue(selfvalue) => self.visit_self_value(selfvalue)?,
+            ast::FnArg::Pat(pattern) => self.visit_pattern(pattern)?,
+            ast::FnArg::Typed(ast::PatType { pat, colon, ty }) => {
+                self.visit_pattern(pat)?;
+                self.writer.write_spanned_raw(colon.span, false, true)?;
+                self.visit_type(ty)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_type(&mut self, ty: &ast::Type) -> Result<()> {
+        match ty {
+            ast::Type::Path(path) => self.visit_path(path),
+        }
+    }
+
     fn visit_use(&mut self, usage: &ast::ItemUse, semi: Option<ast::SemiColon>) -> Result<()> {
         let ast::ItemUse {
             attributes,
@@ -806,7 +914,14 @@ impl<'a> Printer<'a> {
 
         let count = assignments.len();
         for (idx, (assignment, comma)) in assignments.iter().enumerate() {
-            self.visit_object_assignment(assignment)?;
+            match assignment {
+                ast::ObjectEntry::Assign(assignment) => self.visit_object_assignment(assignment)?,
+                ast::ObjectEntry::Spread(spread) => {
+                    self.writer
+                        .write_spanned_raw(spread.dot_dot.span, false, false)?;
+                    self.visit_expr(&spread.expr)?;
+                }
+            }
 
             if multiline {
                 if let Some(comma) = comma {
@@ -1263,10 +1378,7 @@ impl<'a> Printer<'a> {
             ast::ExprClosureArgs::List { args, open, close } => {
                 self.writer.write_spanned_raw(open.span, false, false)?;
                 for (arg, comma) in args {
-                    match arg {
-                        ast::FnArg::SelfValue(self_) => self.visit_self_value(self_)?,
-                        ast::FnArg::Pat(pat) => self.visit_pattern(pat)?,
-                    }
+                    self.visit_fn_arg(arg)?;
                     if let Some(comma) = comma {
                         self.writer.write_spanned_raw(comma.span, false, true)?;
                     }
@@ -1450,6 +1562,7 @@ impl<'a> Printer<'a> {
             ast::Pat::Object(ast) => self.visit_pat_object(ast)?,
             ast::Pat::Binding(binding) => self.visit_pat_binding(binding)?,
             ast::Pat::Rest(rest) => self.visit_pat_rest(rest)?,
+            ast::Pat::At(at) => self.visit_pat_at(at)?,
         }
 
         Ok(())
@@ -1470,6 +1583,25 @@ impl<'a> Printer<'a> {
         Ok(())
     }
 
+    fn visit_pat_at(&mut self, ast: &ast::PatAt) -> Result<()> {
+        let ast::PatAt {
+            attributes,
+            ident,
+            at,
+            pat,
+        } = ast;
+
+        for attribute in attributes {
+            self.visit_attribute(attribute)?;
+        }
+
+        self.writer.write_spanned_raw(ident.span, false, false)?;
+        self.writer.write_unspanned(" ")?;
+        self.writer.write_spanned_raw(at.span, false, true)?;
+        self.visit_pattern(pat)?;
+        Ok(())
+    }
+
     fn visit_pat_binding(&mut self, ast: &ast::PatBinding) -> Result<()> {
         let ast::PatBinding {
             attributes,
@@ -1822,6 +1954,14 @@ impl<'a> Printer<'a> {
                     .write_spanned_raw(semi_token.span, false, false)?;
                 self.writer.newline()?;
             }
+            ast::Stmt::Defer(defer) => {
+                let ast::StmtDefer { defer_token, block } = &**defer;
+
+                self.writer
+                    .write_spanned_raw(defer_token.span, false, true)?;
+                self.visit_block(block)?;
+                self.writer.newline()?;
+            }
         }
 
         Ok(())
@@ -1835,6 +1975,7 @@ impl<'a> Printer<'a> {
             pat,
             eq,
             expr,
+            else_,
             semi,
         } = ast;
 
@@ -1852,6 +1993,11 @@ impl<'a> Printer<'a> {
         self.writer.write_unspanned(" ")?;
         self.writer.write_spanned_raw(eq.span, false, true)?;
         self.visit_expr(expr)?;
+
+        if let Some(else_) = else_ {
+            self.visit_expr_else(else_)?;
+        }
+
         self.writer.write_spanned_raw(semi.span, false, false)?;
 
         Ok(())
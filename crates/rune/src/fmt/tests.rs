@@ -156,3 +156,24 @@ fn test_macrocall_whitespace3() {
     let output = layout_string(String::from_utf8(output).unwrap()).unwrap();
     assert_eq!(std::str::from_utf8(&output).unwrap(), expected);
 }
+
+#[test]
+fn test_format_source() {
+    let input = "fn main(){let x=1;let y=2;x+y}";
+
+    let expected = r#"fn main() {
+    let x = 1;
+    let y = 2;
+    x + y
+}
+"#;
+
+    let output = super::format_source(input).unwrap();
+    assert_eq!(output, expected);
+
+    // Formatting is idempotent.
+    assert_eq!(super::format_source(&output).unwrap(), expected);
+
+    let error = super::format_source("fn main() {").unwrap_err();
+    assert!(error.as_compile_error().is_some());
+}
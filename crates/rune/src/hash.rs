@@ -1,4 +1,4 @@
-use crate::no_std::collections::HashMap;
+use crate::no_std::collections::{HashMap, HashSet};
 
 use core::hash::{BuildHasher, Hasher};
 
@@ -12,6 +12,9 @@ pub(crate) use rune_core::{IntoHash, ParametersBuilder};
 /// A hash map suitable for storing values with hash keys.
 pub(crate) type Map<T> = HashMap<Hash, T, HashBuildHasher>;
 
+/// A hash set suitable for storing hashes.
+pub(crate) type Set = HashSet<Hash, HashBuildHasher>;
+
 #[derive(Default, Clone, Copy)]
 pub(crate) struct HashBuildHasher;
 
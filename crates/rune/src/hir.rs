@@ -13,3 +13,5 @@ pub(crate) mod scopes;
 pub(crate) use self::scopes::Scopes;
 
 pub(crate) mod interpreter;
+
+pub(crate) mod walk;
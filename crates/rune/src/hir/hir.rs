@@ -110,6 +110,8 @@ pub(crate) enum PatKind<'hir> {
     Sequence(&'hir PatSequence<'hir>),
     /// An object pattern.
     Object(&'hir PatObject<'hir>),
+    /// A binding of a sub-pattern.
+    At(&'hir PatAt<'hir>),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -152,6 +154,16 @@ pub(crate) struct PatObject<'hir> {
     pub(crate) bindings: &'hir [Binding<'hir>],
 }
 
+/// A binding of a sub-pattern, like `n @ Some(..)`.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub(crate) struct PatAt<'hir> {
+    /// The name the value is bound to.
+    pub(crate) name: &'hir str,
+    /// The pattern the value has to match.
+    pub(crate) pat: &'hir Pat<'hir>,
+}
+
 #[derive(Debug, Clone, Copy)]
 #[non_exhaustive]
 pub(crate) enum Binding<'hir> {
@@ -235,10 +247,12 @@ pub(crate) enum ExprKind<'hir> {
     Tuple(&'hir ExprSeq<'hir>),
     Vec(&'hir ExprSeq<'hir>),
     Range(&'hir ExprRange<'hir>),
+    Spread(&'hir ExprSpread<'hir>),
     Group(&'hir Expr<'hir>),
     Template(&'hir BuiltInTemplate<'hir>),
     Format(&'hir BuiltInFormat<'hir>),
     Const(Hash),
+    StaticData(usize),
 }
 
 /// An internally resolved template.
@@ -402,6 +416,11 @@ pub(crate) enum Call<'hir> {
     Meta {
         /// Hash being called.
         hash: Hash,
+        /// If this is a call to a function defined in the unit being compiled,
+        /// as opposed to a constructor or a function provided by the context.
+        local: bool,
+        /// If this is a call to the constructor of a struct or a variant.
+        constructor: bool,
     },
     /// An expression being called.
     Expr { expr: &'hir Expr<'hir> },
@@ -614,6 +633,38 @@ pub(crate) struct ExprSeq<'hir> {
     pub(crate) items: &'hir [Expr<'hir>],
 }
 
+/// A vector or object literal which spreads other values into it, like
+/// `[..a, 1, ..b]` or `#{..defaults, x: 1}`.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub(crate) struct ExprSpread<'hir> {
+    /// The kind of value being constructed.
+    pub(crate) kind: ExprSpreadKind,
+    /// The parts of the literal, in order.
+    pub(crate) parts: &'hir [SpreadPart<'hir>],
+}
+
+/// The kind of value constructed by a spread literal.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ExprSpreadKind {
+    /// A vector.
+    Vec,
+    /// An anonymous object.
+    Object,
+}
+
+/// A part of a spread literal.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub(crate) enum SpreadPart<'hir> {
+    /// A sequence of plain vector items.
+    Items(&'hir [Expr<'hir>]),
+    /// A sequence of plain object fields.
+    Fields(&'hir [FieldAssign<'hir>]),
+    /// A value being spread.
+    Spread(Expr<'hir>),
+}
+
 /// A range expression such as `a .. b` or `a ..= b`.
 #[derive(Debug, Clone, Copy)]
 #[non_exhaustive]
@@ -662,6 +713,24 @@ pub(crate) enum FnArg<'hir> {
     SelfValue(Span),
     /// Function argument is a pattern binding.
     Pat(&'hir Pat<'hir>),
+    /// Function argument is a pattern binding with a type annotation which is
+    /// checked when the function is called.
+    Typed(&'hir PatType<'hir>),
+}
+
+/// A pattern binding with a type annotation.
+#[derive(Debug, Clone, Copy, Spanned)]
+#[non_exhaustive]
+pub(crate) struct PatType<'hir> {
+    /// The span of the argument.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The pattern being bound.
+    pub(crate) pat: Pat<'hir>,
+    /// The hash of the annotated type.
+    pub(crate) hash: Hash,
+    /// The annotated type as it was written, used in diagnostics.
+    pub(crate) name: &'hir str,
 }
 
 /// A block of statements.
@@ -703,6 +772,8 @@ pub(crate) enum Stmt<'hir> {
     Expr(&'hir Expr<'hir>),
     /// An expression with a trailing semi-colon.
     Semi(&'hir Expr<'hir>),
+    /// A block which is deferred until the enclosing scope is exited.
+    Defer(&'hir Block<'hir>),
     /// An ignored item.
     Item(Span),
 }
@@ -718,4 +789,6 @@ pub(crate) struct Local<'hir> {
     pub(crate) pat: Pat<'hir>,
     /// The expression the binding is assigned to.
     pub(crate) expr: Expr<'hir>,
+    /// The diverging block to evaluate if the pattern doesn't match.
+    pub(crate) else_: Option<&'hir Block<'hir>>,
 }
@@ -7,8 +7,9 @@ use crate::no_std::prelude::*;
 use num::ToPrimitive;
 
 use crate::ast::{self, Spanned};
+use crate::compile::attrs::StrictArgs;
 use crate::compile::meta;
-use crate::compile::{self, DynLocation, ErrorKind, Item, ItemId, WithSpan};
+use crate::compile::{self, DynLocation, ErrorKind, Item, ItemBuf, ItemId, ModId, WithSpan};
 use crate::hash::{Hash, ParametersBuilder};
 use crate::hir;
 use crate::indexing;
@@ -36,6 +37,10 @@ pub(crate) struct Ctxt<'hir, 'a, 'arena> {
     needs: Cell<Needs>,
     scopes: hir::Scopes<'hir>,
     const_eval: bool,
+    /// Lowering is only performed to analyze the result, so compile passes
+    /// aren't invoked and no diagnostics are emitted.
+    analysis: bool,
+    strict: StrictArgs,
 }
 
 impl<'hir, 'a, 'arena> Ctxt<'hir, 'a, 'arena> {
@@ -52,12 +57,18 @@ impl<'hir, 'a, 'arena> Ctxt<'hir, 'a, 'arena> {
 
     /// Construct a new context for used when constants are built separately
     /// through the query system.
+    ///
+    /// The strictness of `module` is enforced while lowering.
     pub(crate) fn with_query(
         arena: &'hir hir::arena::Arena,
         q: Query<'a, 'arena>,
         source_id: SourceId,
+        module: ModId,
     ) -> Self {
-        Self::inner(arena, q, source_id, false)
+        let strict = q.pool.module(module).strict;
+        let mut cx = Self::inner(arena, q, source_id, false);
+        cx.strict = strict;
+        cx
     }
 
     /// Construct a new context used in a constant context where the resulting
@@ -70,6 +81,22 @@ impl<'hir, 'a, 'arena> Ctxt<'hir, 'a, 'arena> {
         Self::inner(arena, q, source_id, true)
     }
 
+    /// Construct a new context used to analyze a function which has not yet
+    /// been compiled.
+    ///
+    /// Lowering in this context doesn't invoke compile passes or emit
+    /// diagnostics, since they are produced once the function is compiled.
+    pub(crate) fn with_analysis(
+        arena: &'hir hir::arena::Arena,
+        q: Query<'a, 'arena>,
+        source_id: SourceId,
+        module: ModId,
+    ) -> Self {
+        let mut cx = Self::with_query(arena, q, source_id, module);
+        cx.analysis = true;
+        cx
+    }
+
     fn inner(
         arena: &'hir hir::arena::Arena,
         q: Query<'a, 'arena>,
@@ -85,6 +112,8 @@ impl<'hir, 'a, 'arena> Ctxt<'hir, 'a, 'arena> {
             needs: Cell::new(Needs::default()),
             scopes: hir::Scopes::default(),
             const_eval,
+            analysis: false,
+            strict: StrictArgs::default(),
         }
     }
 
@@ -147,9 +176,25 @@ pub(crate) fn item_fn<'hir>(
 ) -> compile::Result<hir::ItemFn<'hir>> {
     alloc_with!(cx, ast);
 
+    if cx.strict.annotations {
+        for (arg, _) in &ast.args {
+            if let ast::FnArg::Pat(arg) = arg {
+                return Err(compile::Error::new(arg, ErrorKind::StrictMissingAnnotation));
+            }
+        }
+    }
+
+    let args = iter!(&ast.args, |(ast, _)| fn_arg(cx, ast)?);
+
+    // Return types are only resolved so that they're validated, they are not
+    // checked at runtime.
+    if let (true, Some((_, output))) = (cx.q.options.type_checks, &ast.output) {
+        ty(cx, output)?;
+    }
+
     Ok(hir::ItemFn {
         span: ast.span(),
-        args: iter!(&ast.args, |(ast, _)| fn_arg(cx, ast)?),
+        args,
         body: block(cx, &ast.body)?,
     })
 }
@@ -367,46 +412,22 @@ pub(crate) fn expr_object<'hir>(
     alloc_with!(cx, ast);
 
     let span = ast;
-    let mut keys_dup = HashMap::new();
-
-    let assignments = &mut *iter!(&ast.assignments, |(ast, _)| {
-        let key = object_key(cx, &ast.key)?;
 
-        if let Some(_existing) = keys_dup.insert(key.1, key.0) {
-            return Err(compile::Error::new(
-                key.0,
-                ErrorKind::DuplicateObjectKey {
-                    #[cfg(feature = "emit")]
-                    existing: _existing.span(),
-                    #[cfg(feature = "emit")]
-                    object: key.0.span(),
-                },
-            ));
-        }
+    let is_spread = ast
+        .assignments
+        .iter()
+        .any(|(entry, _)| matches!(entry, ast::ObjectEntry::Spread(..)));
 
-        let assign = match &ast.assign {
-            Some((_, ast)) => expr(cx, ast)?,
-            None => {
-                let Some((name, _)) = cx.scopes.get(hir::Name::Str(key.1)) else {
-                    return Err(compile::Error::new(
-                        key.0,
-                        ErrorKind::MissingLocal {
-                            name: key.1.to_owned(),
-                        },
-                    ));
-                };
+    if is_spread && matches!(ast.ident, ast::ObjectIdent::Anonymous(..)) {
+        return expr_object_spread(cx, ast);
+    }
 
-                hir::Expr {
-                    span: ast.span(),
-                    kind: hir::ExprKind::Variable(name),
-                }
-            }
-        };
+    let mut keys_dup = HashMap::new();
 
-        hir::FieldAssign {
-            key: (key.0.span(), key.1),
-            assign,
-            position: None,
+    let assignments = &mut *iter!(&ast.assignments, |(ast, _)| match ast {
+        ast::ObjectEntry::Assign(ast) => field_assign(cx, ast, &mut keys_dup)?,
+        ast::ObjectEntry::Spread(ast) => {
+            return Err(compile::Error::new(ast, ErrorKind::UnsupportedObjectSpread));
         }
     });
 
@@ -499,6 +520,140 @@ pub(crate) fn expr_object<'hir>(
     })))
 }
 
+/// Lower a single field assignment in an object expression.
+fn field_assign<'hir, 'ast>(
+    cx: &mut Ctxt<'hir, '_, '_>,
+    ast: &'ast ast::FieldAssign,
+    keys_dup: &mut HashMap<&'hir str, &'ast dyn Spanned>,
+) -> compile::Result<hir::FieldAssign<'hir>> {
+    let key = object_key(cx, &ast.key)?;
+
+    if let Some(_existing) = keys_dup.insert(key.1, key.0) {
+        return Err(compile::Error::new(
+            key.0,
+            ErrorKind::DuplicateObjectKey {
+                #[cfg(feature = "emit")]
+                existing: _existing.span(),
+                #[cfg(feature = "emit")]
+                object: key.0.span(),
+            },
+        ));
+    }
+
+    let assign = match &ast.assign {
+        Some((_, ast)) => expr(cx, ast)?,
+        None => {
+            let Some((name, _)) = cx.scopes.get(hir::Name::Str(key.1)) else {
+                return Err(compile::Error::new(
+                    key.0,
+                    ErrorKind::MissingLocal {
+                        name: key.1.to_owned(),
+                    },
+                ));
+            };
+
+            hir::Expr {
+                span: ast.span(),
+                kind: hir::ExprKind::Variable(name),
+            }
+        }
+    };
+
+    Ok(hir::FieldAssign {
+        key: (key.0.span(), key.1),
+        assign,
+        position: None,
+    })
+}
+
+/// Lower an anonymous object expression which spreads other values into it.
+fn expr_object_spread<'hir>(
+    cx: &mut Ctxt<'hir, '_, '_>,
+    ast: &ast::ExprObject,
+) -> compile::Result<hir::ExprKind<'hir>> {
+    alloc_with!(cx, ast);
+
+    let mut keys_dup = HashMap::new();
+    let mut parts = Vec::new();
+    let mut fields = Vec::new();
+
+    for (entry, _) in &ast.assignments {
+        match entry {
+            ast::ObjectEntry::Assign(ast) => {
+                fields.push(field_assign(cx, ast, &mut keys_dup)?);
+            }
+            ast::ObjectEntry::Spread(ast) => {
+                if !fields.is_empty() {
+                    parts.push(hir::SpreadPart::Fields(iter!(fields.drain(..))));
+                }
+
+                parts.push(hir::SpreadPart::Spread(expr(cx, &ast.expr)?));
+            }
+        }
+    }
+
+    if !fields.is_empty() {
+        parts.push(hir::SpreadPart::Fields(iter!(fields.drain(..))));
+    }
+
+    Ok(hir::ExprKind::Spread(alloc!(hir::ExprSpread {
+        kind: hir::ExprSpreadKind::Object,
+        parts: iter!(parts),
+    })))
+}
+
+/// Get the value being spread if the given vector item is a spread, like
+/// `..a`.
+fn vec_spread(ast: &ast::Expr) -> Option<&ast::Expr> {
+    let ast::Expr::Range(range) = ast else {
+        return None;
+    };
+
+    if range.start.is_some()
+        || !range.attributes.is_empty()
+        || !matches!(range.limits, ast::ExprRangeLimits::HalfOpen(..))
+    {
+        return None;
+    }
+
+    range.end.as_deref()
+}
+
+/// Lower a vector expression which spreads other values into it.
+fn expr_vec_spread<'hir>(
+    cx: &mut Ctxt<'hir, '_, '_>,
+    ast: &ast::ExprVec,
+) -> compile::Result<hir::ExprKind<'hir>> {
+    alloc_with!(cx, ast);
+
+    let mut parts = Vec::new();
+    let mut items = Vec::new();
+
+    for (ast, _) in &ast.items {
+        match vec_spread(ast) {
+            Some(ast) => {
+                if !items.is_empty() {
+                    parts.push(hir::SpreadPart::Items(iter!(items.drain(..))));
+                }
+
+                parts.push(hir::SpreadPart::Spread(expr(cx, ast)?));
+            }
+            None => {
+                items.push(expr(cx, ast)?);
+            }
+        }
+    }
+
+    if !items.is_empty() {
+        parts.push(hir::SpreadPart::Items(iter!(items.drain(..))));
+    }
+
+    Ok(hir::ExprKind::Spread(alloc!(hir::ExprSpread {
+        kind: hir::ExprSpreadKind::Vec,
+        parts: iter!(parts),
+    })))
+}
+
 /// Lower an expression.
 #[instrument(span = ast)]
 pub(crate) fn expr<'hir>(
@@ -672,6 +827,9 @@ pub(crate) fn expr<'hir>(
         ast::Expr::Tuple(ast) => hir::ExprKind::Tuple(alloc!(hir::ExprSeq {
             items: iter!(&ast.items, |(ast, _)| expr(cx, ast)?),
         })),
+        ast::Expr::Vec(ast) if ast.items.iter().any(|(ast, _)| vec_spread(ast).is_some()) => {
+            expr_vec_spread(cx, ast)?
+        }
         ast::Expr::Vec(ast) => hir::ExprKind::Vec(alloc!(hir::ExprSeq {
             items: iter!(&ast.items, |(ast, _)| expr(cx, ast)?),
         })),
@@ -702,6 +860,9 @@ pub(crate) fn expr<'hir>(
             })),
             query::BuiltInMacro::File(ast) => hir::ExprKind::Lit(lit(cx, &ast.value)?),
             query::BuiltInMacro::Line(ast) => hir::ExprKind::Lit(lit(cx, &ast.value)?),
+            query::BuiltInMacro::IncludeData(ast) => hir::ExprKind::StaticData(ast.slot),
+            query::BuiltInMacro::IncludeStr(ast) => hir::ExprKind::Lit(lit(cx, &ast.value)?),
+            query::BuiltInMacro::IncludeBytes(ast) => hir::ExprKind::Lit(lit(cx, &ast.value)?),
         },
     };
 
@@ -1047,23 +1208,159 @@ fn fn_arg<'hir>(
             hir::FnArg::SelfValue(ast.span())
         }
         ast::FnArg::Pat(ast) => hir::FnArg::Pat(alloc!(pat(cx, ast)?)),
+        ast::FnArg::Typed(ast) if !cx.q.options.type_checks => {
+            hir::FnArg::Pat(alloc!(pat(cx, &ast.pat)?))
+        }
+        ast::FnArg::Typed(ast) => {
+            let (hash, name) = ty(cx, &ast.ty)?;
+
+            hir::FnArg::Typed(alloc!(hir::PatType {
+                span: ast.span(),
+                pat: pat(cx, &ast.pat)?,
+                hash,
+                name,
+            }))
+        }
     })
 }
 
+/// Define a variable bound by a pattern, denying it from shadowing another
+/// variable if the module is `#![strict(shadowing)]`.
+fn define_binding<'hir>(
+    cx: &mut Ctxt<'hir, '_, '_>,
+    name: &'hir str,
+    span: &dyn Spanned,
+) -> compile::Result<()> {
+    if cx.strict.shadowing && cx.scopes.contains(hir::Name::Str(name)) {
+        return Err(compile::Error::new(
+            span,
+            ErrorKind::StrictShadowing { name: name.into() },
+        ));
+    }
+
+    cx.scopes.define(hir::Name::Str(name)).with_span(span)?;
+    Ok(())
+}
+
+/// Lower a type annotation into the hash of the type it refers to, and the
+/// annotation as it was written.
+fn ty<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Type) -> compile::Result<(Hash, &'hir str)> {
+    alloc_with!(cx, ast);
+
+    let hash = match ast {
+        ast::Type::Path(ast) => {
+            let named = cx.q.convert_path(ast)?;
+            let parameters = generics_parameters(cx, &named)?;
+            let meta = cx.lookup_meta(ast, named.item, parameters)?;
+
+            match &meta.kind {
+                meta::Kind::Type { .. } | meta::Kind::Struct { .. } | meta::Kind::Enum { .. } => {
+                    meta.hash
+                }
+                _ => {
+                    return Err(compile::Error::expected_meta(
+                        ast,
+                        meta.info(cx.q.pool),
+                        "a type",
+                    ));
+                }
+            }
+        }
+    };
+
+    let name = match cx.q.sources.source(cx.source_id, ast.span()) {
+        Some(name) => alloc_str!(name),
+        None => "",
+    };
+
+    Ok((hash, name))
+}
+
 /// Lower an assignment.
 fn local<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Local) -> compile::Result<hir::Local<'hir>> {
+    alloc_with!(cx, ast);
+
     // Note: expression needs to be assembled before pattern, otherwise the
     // expression will see declarations in the pattern.
     let expr = expr(cx, &ast.expr)?;
+
+    let else_ = match &ast.else_ {
+        Some(else_) => {
+            let block = block(cx, &else_.block)?;
+
+            if !block_diverges(&block) {
+                return Err(compile::Error::new(else_, ErrorKind::LetElseMustDiverge));
+            }
+
+            Some(&*alloc!(block))
+        }
+        None => None,
+    };
+
     let pat = pat(cx, &ast.pat)?;
 
     Ok(hir::Local {
         span: ast.span(),
         pat,
         expr,
+        else_,
+    })
+}
+
+/// Test if the given block is known to diverge, so that evaluation can never
+/// reach its end.
+///
+/// This is a conservative syntactical check, which only considers `return`,
+/// `break`, `continue` and calls to `panic` along with the blocks, conditionals
+/// with an `else` branch and matches with a catch-all branch which wrap them.
+fn block_diverges(block: &hir::Block<'_>) -> bool {
+    block.statements.iter().any(|stmt| match stmt {
+        hir::Stmt::Local(local) => expr_diverges(&local.expr),
+        hir::Stmt::Expr(expr) | hir::Stmt::Semi(expr) => expr_diverges(expr),
+        hir::Stmt::Defer(..) | hir::Stmt::Item(..) => false,
     })
 }
 
+/// Test if the given expression is known to diverge.
+fn expr_diverges(hir: &hir::Expr<'_>) -> bool {
+    match hir.kind {
+        hir::ExprKind::Return(..) | hir::ExprKind::Break(..) | hir::ExprKind::Continue(..) => true,
+        hir::ExprKind::Block(block) => block_diverges(block),
+        hir::ExprKind::Group(expr) => expr_diverges(expr),
+        hir::ExprKind::If(hir) => {
+            hir.branches.last().map_or(false, |b| b.condition.is_none())
+                && hir.branches.iter().all(|b| block_diverges(&b.block))
+        }
+        hir::ExprKind::Match(hir) => {
+            // NB: a match without a catch-all branch evaluates to `()` if no
+            // branch matches.
+            let catch_all = hir
+                .branches
+                .iter()
+                .any(|b| b.condition.is_none() && pat_is_catch_all(&b.pat));
+
+            expr_diverges(&hir.expr)
+                || catch_all && hir.branches.iter().all(|b| expr_diverges(&b.body))
+        }
+        hir::ExprKind::Call(call) => match call.call {
+            hir::Call::Meta { hash, .. } => {
+                hash == Hash::type_hash(&ItemBuf::with_crate_item("std", ["panic"]))
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Test if the given pattern matches any value.
+fn pat_is_catch_all(hir: &hir::Pat<'_>) -> bool {
+    match hir.kind {
+        hir::PatKind::Ignore | hir::PatKind::Path(hir::PatPathKind::Ident(..)) => true,
+        hir::PatKind::At(hir) => pat_is_catch_all(hir.pat),
+        _ => false,
+    }
+}
+
 /// Lower a statement
 fn stmt<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Stmt) -> compile::Result<hir::Stmt<'hir>> {
     alloc_with!(cx, ast);
@@ -1072,6 +1369,7 @@ fn stmt<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Stmt) -> compile::Result<h
         ast::Stmt::Local(ast) => hir::Stmt::Local(alloc!(local(cx, ast)?)),
         ast::Stmt::Expr(ast) => hir::Stmt::Expr(alloc!(expr(cx, ast)?)),
         ast::Stmt::Semi(ast) => hir::Stmt::Semi(alloc!(expr(cx, &ast.expr)?)),
+        ast::Stmt::Defer(ast) => hir::Stmt::Defer(alloc!(block(cx, &ast.block)?)),
         ast::Stmt::Item(..) => hir::Stmt::Item(ast.span()),
     })
 }
@@ -1102,7 +1400,7 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
 
                 if let Some(ident) = ast.path.try_as_ident() {
                     let name = alloc_str!(ident.resolve(resolve_context!(cx.q))?);
-                    cx.scopes.define(hir::Name::Str(name)).with_span(ast)?;
+                    define_binding(cx, name, ast)?;
                     break 'ok hir::PatPathKind::Ident(name);
                 }
 
@@ -1200,7 +1498,7 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
                         };
 
                         let key = alloc_str!(ident.resolve(resolve_context!(cx.q))?);
-                        cx.scopes.define(hir::Name::Str(key)).with_span(ident)?;
+                        define_binding(cx, key, ident)?;
                         (key, hir::Binding::Ident(path.span(), key))
                     }
                     _ => {
@@ -1280,6 +1578,15 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
 
             hir::PatKind::Object(alloc!(hir::PatObject { kind, bindings }))
         }
+        ast::Pat::At(ast) => {
+            let name = alloc_str!(ast.ident.resolve(resolve_context!(cx.q))?);
+            define_binding(cx, name, ast)?;
+
+            hir::PatKind::At(alloc!(hir::PatAt {
+                name,
+                pat: alloc!(pat(cx, &ast.pat)?),
+            }))
+        }
         _ => {
             return Err(compile::Error::new(ast, ErrorKind::UnsupportedPatternExpr));
         }
@@ -1359,27 +1666,21 @@ pub(crate) fn expr_path<'hir>(
         // light heuristics, treat it as a type error in case the first
         // character is uppercase.
         if !local.starts_with(char::is_uppercase) {
+            let suggestions = cx.q.import_suggestions(ast)?;
+
             return Err(compile::Error::new(
                 ast,
                 ErrorKind::MissingLocal {
                     name: local.to_owned(),
                 },
-            ));
+            )
+            .with_suggestions(suggestions));
         }
     }
 
-    let kind = if !parameters.parameters.is_empty() {
-        ErrorKind::MissingItemParameters {
-            item: cx.q.pool.item(named.item).to_owned(),
-            parameters: parameters.parameters.as_ref().into(),
-        }
-    } else {
-        ErrorKind::MissingItem {
-            item: cx.q.pool.item(named.item).to_owned(),
-        }
-    };
-
-    Err(compile::Error::new(ast, kind))
+    let error = cx.q.missing_item(ast, named.item, &parameters);
+    let suggestions = cx.q.import_suggestions(ast)?;
+    Err(error.with_suggestions(suggestions))
 }
 
 /// Compile an item.
@@ -1401,7 +1702,11 @@ fn expr_path_meta<'hir>(
                 fields: meta::Fields::Empty,
                 ..
             } => Ok(hir::ExprKind::Call(alloc!(hir::ExprCall {
-                call: hir::Call::Meta { hash: meta.hash },
+                call: hir::Call::Meta {
+                    hash: meta.hash,
+                    local: false,
+                    constructor: true,
+                },
                 args: &[],
             }))),
             meta::Kind::Variant {
@@ -1412,7 +1717,11 @@ fn expr_path_meta<'hir>(
                 fields: meta::Fields::Unnamed(0),
                 ..
             } => Ok(hir::ExprKind::Call(alloc!(hir::ExprCall {
-                call: hir::Call::Meta { hash: meta.hash },
+                call: hir::Call::Meta {
+                    hash: meta.hash,
+                    local: false,
+                    constructor: true,
+                },
                 args: &[],
             }))),
             meta::Kind::Struct {
@@ -1640,7 +1949,12 @@ fn expr_call<'hir>(
                 let named = cx.q.convert_path(path)?;
                 let parameters = generics_parameters(cx, &named)?;
 
-                let meta = cx.lookup_meta(path, named.item, parameters)?;
+                let Some(meta) = cx.try_lookup_meta(path, named.item, &parameters)? else {
+                    let error = cx.q.missing_item(path, named.item, &parameters);
+                    let suggestions = cx.q.import_suggestions(path)?;
+                    return Err(error.with_suggestions(suggestions));
+                };
+
                 debug_assert_eq!(meta.item_meta.item, named.item);
 
                 match &meta.kind {
@@ -1680,7 +1994,7 @@ fn expr_call<'hir>(
                             ));
                         }
 
-                        if *args == 0 {
+                        if *args == 0 && !cx.analysis {
                             cx.q.diagnostics.remove_tuple_call_parens(
                                 cx.source_id,
                                 &ast.args,
@@ -1709,7 +2023,23 @@ fn expr_call<'hir>(
                     }
                 };
 
-                break 'ok hir::Call::Meta { hash: meta.hash };
+                let local = !meta.context && matches!(meta.kind, meta::Kind::Function { .. });
+                let constructor = matches!(
+                    meta.kind,
+                    meta::Kind::Struct { .. } | meta::Kind::Variant { .. }
+                );
+
+                let hash = if cx.analysis {
+                    meta.hash
+                } else {
+                    cx.q.pass_call(cx.source_id, path, &meta)?
+                };
+
+                break 'ok hir::Call::Meta {
+                    hash,
+                    local,
+                    constructor,
+                };
             }
             hir::ExprKind::FieldAccess(&hir::ExprFieldAccess {
                 expr_field,
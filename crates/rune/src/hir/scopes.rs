@@ -137,6 +137,26 @@ impl<'hir> Scopes<'hir> {
         Ok(name)
     }
 
+    /// Test if the given variable is visible in the current scope, without
+    /// capturing it.
+    pub(crate) fn contains(&self, name: hir::Name<'hir>) -> bool {
+        let mut scope = self.scopes.get(self.scope.0);
+
+        while let Some(layer) = scope.take() {
+            if layer.variables.contains(&name) {
+                return true;
+            }
+
+            let Some(parent) = layer.parent() else {
+                break;
+            };
+
+            scope = self.scopes.get(parent);
+        }
+
+        false
+    }
+
     /// Try to lookup the given variable.
     #[tracing::instrument(skip_all, fields(?self.scope, ?name))]
     pub(crate) fn get(&mut self, name: hir::Name<'hir>) -> Option<(hir::Name<'hir>, Scope)> {
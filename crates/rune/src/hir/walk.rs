@@ -0,0 +1,191 @@
+//! Helpers for walking over lowered expressions.
+
+use crate::hir;
+
+/// Call `f` with every expression in the given block, with parent expressions
+/// coming before the expressions they contain.
+///
+/// The bodies of closures and async blocks are not visited, since they're
+/// lowered separately.
+pub(crate) fn block<'hir>(hir: &hir::Block<'hir>, f: &mut dyn FnMut(&hir::Expr<'hir>)) {
+    for stmt in hir.statements {
+        match stmt {
+            hir::Stmt::Local(hir) => {
+                expr(&hir.expr, f);
+
+                if let Some(else_) = hir.else_ {
+                    block(else_, f);
+                }
+            }
+            hir::Stmt::Expr(hir) | hir::Stmt::Semi(hir) => {
+                expr(hir, f);
+            }
+            hir::Stmt::Defer(hir) => {
+                block(hir, f);
+            }
+            hir::Stmt::Item(..) => {}
+        }
+    }
+}
+
+fn condition<'hir>(hir: &hir::Condition<'hir>, f: &mut dyn FnMut(&hir::Expr<'hir>)) {
+    match hir {
+        hir::Condition::Expr(hir) => expr(hir, f),
+        hir::Condition::ExprLet(hir) => expr(&hir.expr, f),
+    }
+}
+
+fn exprs<'hir>(hir: &[hir::Expr<'hir>], f: &mut dyn FnMut(&hir::Expr<'hir>)) {
+    for hir in hir {
+        expr(hir, f);
+    }
+}
+
+fn fields<'hir>(hir: &[hir::FieldAssign<'hir>], f: &mut dyn FnMut(&hir::Expr<'hir>)) {
+    for hir in hir {
+        expr(&hir.assign, f);
+    }
+}
+
+/// Call `f` with the given expression and every expression it contains.
+pub(crate) fn expr<'hir>(hir: &hir::Expr<'hir>, f: &mut dyn FnMut(&hir::Expr<'hir>)) {
+    f(hir);
+
+    match hir.kind {
+        hir::ExprKind::Variable(..)
+        | hir::ExprKind::Type(..)
+        | hir::ExprKind::Fn(..)
+        | hir::ExprKind::Path
+        | hir::ExprKind::Const(..)
+        | hir::ExprKind::Lit(..)
+        | hir::ExprKind::StaticData(..)
+        | hir::ExprKind::AsyncBlock(..)
+        | hir::ExprKind::CallClosure(..)
+        | hir::ExprKind::Continue(..) => {}
+        hir::ExprKind::Assign(hir) => {
+            expr(&hir.lhs, f);
+            expr(&hir.rhs, f);
+        }
+        hir::ExprKind::Loop(hir) => {
+            if let Some(hir) = hir.condition {
+                condition(hir, f);
+            }
+
+            block(&hir.body, f);
+        }
+        hir::ExprKind::For(hir) => {
+            expr(&hir.iter, f);
+            block(&hir.body, f);
+        }
+        hir::ExprKind::Let(hir) => {
+            expr(&hir.expr, f);
+        }
+        hir::ExprKind::If(hir) => {
+            for branch in hir.branches {
+                if let Some(hir) = branch.condition {
+                    condition(hir, f);
+                }
+
+                block(&branch.block, f);
+            }
+        }
+        hir::ExprKind::Match(hir) => {
+            expr(&hir.expr, f);
+
+            for branch in hir.branches {
+                if let Some(hir) = branch.condition {
+                    expr(hir, f);
+                }
+
+                expr(&branch.body, f);
+            }
+        }
+        hir::ExprKind::Call(hir) => {
+            match hir.call {
+                hir::Call::Associated { target, .. } => expr(target, f),
+                hir::Call::Expr { expr: hir } => expr(hir, f),
+                hir::Call::Var { .. } | hir::Call::Meta { .. } | hir::Call::ConstFn { .. } => {}
+            }
+
+            exprs(hir.args, f);
+        }
+        hir::ExprKind::FieldAccess(hir) => {
+            expr(&hir.expr, f);
+        }
+        hir::ExprKind::Binary(hir) => {
+            expr(&hir.lhs, f);
+            expr(&hir.rhs, f);
+        }
+        hir::ExprKind::Unary(hir) => {
+            expr(&hir.expr, f);
+        }
+        hir::ExprKind::Index(hir) => {
+            expr(&hir.target, f);
+            expr(&hir.index, f);
+        }
+        hir::ExprKind::Block(hir) => {
+            block(hir, f);
+        }
+        hir::ExprKind::Break(hir) => {
+            if let Some(hir) = hir.expr {
+                expr(hir, f);
+            }
+        }
+        hir::ExprKind::Yield(hir) | hir::ExprKind::Return(hir) => {
+            if let Some(hir) = hir {
+                expr(hir, f);
+            }
+        }
+        hir::ExprKind::Await(hir) | hir::ExprKind::Try(hir) | hir::ExprKind::Group(hir) => {
+            expr(hir, f);
+        }
+        hir::ExprKind::Select(hir) => {
+            for branch in hir.branches {
+                match branch {
+                    hir::ExprSelectBranch::Pat(hir) => {
+                        expr(&hir.expr, f);
+                        expr(&hir.body, f);
+                    }
+                    hir::ExprSelectBranch::Default(hir) => {
+                        expr(hir, f);
+                    }
+                }
+            }
+        }
+        hir::ExprKind::Object(hir) => {
+            fields(hir.assignments, f);
+        }
+        hir::ExprKind::Tuple(hir) | hir::ExprKind::Vec(hir) => {
+            exprs(hir.items, f);
+        }
+        hir::ExprKind::Range(hir) => match hir {
+            hir::ExprRange::RangeFull => {}
+            hir::ExprRange::RangeFrom { start } => {
+                expr(start, f);
+            }
+            hir::ExprRange::RangeTo { end } | hir::ExprRange::RangeToInclusive { end } => {
+                expr(end, f);
+            }
+            hir::ExprRange::Range { start, end }
+            | hir::ExprRange::RangeInclusive { start, end } => {
+                expr(start, f);
+                expr(end, f);
+            }
+        },
+        hir::ExprKind::Spread(hir) => {
+            for part in hir.parts {
+                match part {
+                    hir::SpreadPart::Items(hir) => exprs(hir, f),
+                    hir::SpreadPart::Fields(hir) => fields(hir, f),
+                    hir::SpreadPart::Spread(hir) => expr(hir, f),
+                }
+            }
+        }
+        hir::ExprKind::Template(hir) => {
+            exprs(hir.exprs, f);
+        }
+        hir::ExprKind::Format(hir) => {
+            expr(&hir.value, f);
+        }
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "json")]
+mod include_data;
 pub(crate) mod index;
 pub(crate) mod items;
 mod scopes;
@@ -5,10 +7,10 @@ mod scopes;
 use crate::no_std::prelude::*;
 
 use crate::ast::{self, Span};
-use crate::compile::meta;
+use crate::compile::{attrs, meta};
 use crate::compile::{ItemId, ItemMeta};
 use crate::parse::NonZeroId;
-use crate::runtime::Call;
+use crate::runtime::{Call, Derives};
 
 pub(crate) use self::index::{IndexItem, Indexer};
 pub(crate) use self::items::Items;
@@ -41,6 +43,8 @@ pub(crate) enum Indexed {
     Struct(Struct),
     /// A variant.
     Variant(Variant),
+    /// The `from_int` constructor of an enum.
+    EnumFromInt(EnumFromInt),
     /// An empty function.
     EmptyFunction(EmptyFunction),
     /// A function.
@@ -51,6 +55,8 @@ pub(crate) enum Indexed {
     ConstExpr(ConstExpr),
     /// A constant block.
     ConstBlock(ConstBlock),
+    /// A constant provided by the host.
+    ExternConst,
     /// A constant function.
     ConstFn(ConstFn),
     /// An import.
@@ -67,8 +73,12 @@ pub(crate) struct Function {
     pub(crate) call: Call,
     /// If this is a test function.
     pub(crate) is_test: bool,
+    /// Parameters of a test function.
+    pub(crate) test_params: meta::TestParams,
     /// If this is a bench function.
     pub(crate) is_bench: bool,
+    /// If the results of the function are memoized.
+    pub(crate) memo: Option<attrs::MemoArgs>,
 }
 
 #[derive(Debug, Clone)]
@@ -105,6 +115,8 @@ pub(crate) struct Import {
 pub(crate) struct Struct {
     /// The ast of the struct.
     pub(crate) ast: Box<ast::ItemStruct>,
+    /// The protocols derived for the struct.
+    pub(crate) derives: Derives,
 }
 
 #[derive(Debug, Clone)]
@@ -115,6 +127,16 @@ pub(crate) struct Variant {
     pub(crate) ast: ast::ItemVariant,
     /// The index of the variant in its source.
     pub(crate) index: usize,
+    /// The discriminant of the variant.
+    pub(crate) discriminant: i64,
+    /// The protocols derived for the enum of the variant.
+    pub(crate) derives: Derives,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct EnumFromInt {
+    /// Discriminants and items of the unit variants in the enum.
+    pub(crate) variants: Vec<(i64, ItemId)>,
 }
 
 #[derive(Debug, Clone)]
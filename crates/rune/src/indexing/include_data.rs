@@ -0,0 +1,131 @@
+//! Support for loading data through the `include_data!` macro.
+
+use core::fmt;
+
+use serde::de::{self, Deserialize, Deserializer};
+
+use crate::no_std::collections::HashMap;
+use crate::no_std::prelude::*;
+use crate::no_std::vec;
+
+use crate::runtime::ConstValue;
+
+/// Parse a JSON document into a constant value.
+///
+/// The document is deserialized straight into the constant value without
+/// building any intermediate representation, so that large data files can be
+/// loaded without constructing a syntax tree for them.
+pub(crate) fn from_json(bytes: &[u8]) -> serde_json::Result<ConstValue> {
+    let Data(value) = serde_json::from_slice(bytes)?;
+    Ok(value)
+}
+
+/// Helper to deserialize a self-describing format into a [ConstValue].
+struct Data(ConstValue);
+
+impl<'de> Deserialize<'de> for Data {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DataVisitor).map(Data)
+    }
+}
+
+struct DataVisitor;
+
+impl<'de> de::Visitor<'de> for DataVisitor {
+    type Value = ConstValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any valid data value")
+    }
+
+    #[inline]
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ConstValue::String(value.to_owned()))
+    }
+
+    #[inline]
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ConstValue::String(value))
+    }
+
+    #[inline]
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ConstValue::Bool(v))
+    }
+
+    #[inline]
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ConstValue::Integer(v))
+    }
+
+    #[inline]
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match i64::try_from(v) {
+            Ok(v) => Ok(ConstValue::Integer(v)),
+            Err(..) => Err(E::custom(format_args!("integer `{v}` is out of range"))),
+        }
+    }
+
+    #[inline]
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ConstValue::Float(v))
+    }
+
+    #[inline]
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ConstValue::EmptyTuple)
+    }
+
+    #[inline]
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: de::SeqAccess<'de>,
+    {
+        let mut vec = vec::Vec::with_capacity(visitor.size_hint().unwrap_or_default());
+
+        while let Some(Data(elem)) = visitor.next_element()? {
+            vec.push(elem);
+        }
+
+        Ok(ConstValue::Vec(vec))
+    }
+
+    #[inline]
+    fn visit_map<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: de::MapAccess<'de>,
+    {
+        let mut object = HashMap::with_capacity(visitor.size_hint().unwrap_or_default());
+
+        while let Some((key, Data(value))) = visitor.next_entry::<String, Data>()? {
+            object.insert(key, value);
+        }
+
+        Ok(ConstValue::Object(object))
+    }
+}
@@ -12,14 +12,18 @@ use crate::compile::attrs;
 use crate::compile::{self, Doc, ErrorKind, ItemId, ModId, Visibility, WithSpan};
 use crate::compile::{meta, DynLocation};
 use crate::indexing::{self, Indexed, Items, Layer, Scopes};
-use crate::macros::MacroCompiler;
-use crate::parse::{NonZeroId, Parse, Parser, Resolve};
-use crate::query::{BuiltInFile, BuiltInFormat, BuiltInLine, BuiltInMacro, BuiltInTemplate, Query};
+use crate::macros::{DeclarativeMacro, MacroCompiler};
+use crate::parse::{NonZeroId, Parse, Parser, Resolve, ResolveContext};
+use crate::query::{
+    BuiltInFile, BuiltInFormat, BuiltInIncludeBytes, BuiltInIncludeData, BuiltInIncludeStr,
+    BuiltInLine, BuiltInMacro, BuiltInTemplate, Query,
+};
 use crate::runtime::format;
-use crate::runtime::Call;
+use crate::runtime::{Call, ConstValue, Derives};
 use crate::worker::{Import, ImportKind, LoadFileKind, Task};
 use crate::SourceId;
 
+use num::ToPrimitive;
 use rune_macros::instrument;
 
 /// Macros are only allowed to expand recursively into other macros 64 times.
@@ -103,6 +107,10 @@ impl<'a, 'arena> Indexer<'a, 'arena> {
 
         let args = builtin.args(resolve_context!(self.q))?;
 
+        self.q
+            .visitor
+            .visit_macro_call(&DynLocation::new(self.source_id, &*ast));
+
         // NB: internal macros are
         let Some(ident) = ast.path.try_as_ident() else {
             return Err(compile::Error::new(
@@ -120,6 +128,9 @@ impl<'a, 'arena> Indexer<'a, 'arena> {
             "format" => self.expand_format_macro(ast, &args)?,
             "file" => self.expand_file_macro(ast)?,
             "line" => self.expand_line_macro(ast)?,
+            "include_data" => self.expand_include_data_macro(ast)?,
+            "include_str" => self.expand_include_str_macro(ast)?,
+            "include_bytes" => self.expand_include_bytes_macro(ast)?,
             _ => {
                 return Err(compile::Error::new(
                     &ast.path,
@@ -140,7 +151,11 @@ impl<'a, 'arena> Indexer<'a, 'arena> {
                 expr(self, &mut format.value)?;
             }
 
-            BuiltInMacro::Line(_) | BuiltInMacro::File(_) => { /* Nothing to index */ }
+            BuiltInMacro::Line(_)
+            | BuiltInMacro::File(_)
+            | BuiltInMacro::IncludeData(_)
+            | BuiltInMacro::IncludeStr(_)
+            | BuiltInMacro::IncludeBytes(_) => { /* Nothing to index */ }
         }
 
         let id = self.q.insert_new_builtin_macro(internal_macro)?;
@@ -167,6 +182,10 @@ impl<'a, 'arena> Indexer<'a, 'arena> {
 
         p.eof()?;
 
+        if args.literal {
+            self.visit_template(ast, &exprs)?;
+        }
+
         Ok(BuiltInMacro::Template(BuiltInTemplate {
             span: ast.span(),
             from_literal: args.literal,
@@ -174,6 +193,48 @@ impl<'a, 'arena> Indexer<'a, 'arena> {
         }))
     }
 
+    /// Report the parts of a template literal to the compile visitor.
+    fn visit_template(&mut self, ast: &ast::MacroCall, exprs: &[ast::Expr]) -> compile::Result<()> {
+        let mut strings = Vec::new();
+
+        for expr in exprs {
+            if let ast::Expr::Lit(ast::ExprLit {
+                lit: ast::Lit::Str(lit),
+                ..
+            }) = expr
+            {
+                let string = lit.resolve_template_string(resolve_context!(self.q))?;
+                strings.push(string.into_owned());
+            }
+        }
+
+        let mut strings = strings.iter();
+        let mut parts = Vec::with_capacity(exprs.len());
+
+        for expr in exprs {
+            let part = match expr {
+                ast::Expr::Lit(ast::ExprLit {
+                    lit: ast::Lit::Str(..),
+                    ..
+                }) => match strings.next() {
+                    Some(string) => compile::TemplatePart::Str(string),
+                    None => continue,
+                },
+                expr => compile::TemplatePart::Slot(expr.span()),
+            };
+
+            parts.push(part);
+        }
+
+        self.q.visitor.visit_template(
+            &DynLocation::new(self.source_id, ast),
+            self.items.item(),
+            &parts,
+        );
+
+        Ok(())
+    }
+
     /// Expand the template macro.
     fn expand_format_macro(
         &mut self,
@@ -362,6 +423,79 @@ impl<'a, 'arena> Indexer<'a, 'arena> {
         }))
     }
 
+    /// Expand a macro which loads a data file into the unit.
+    ///
+    /// The data is deserialized directly into static unit storage, without
+    /// constructing syntax trees for it.
+    fn expand_include_data_macro(&mut self, ast: &ast::MacroCall) -> compile::Result<BuiltInMacro> {
+        let (lit, path, data) = self.load_included_file(ast)?;
+        let value = self::load_data(&lit, &path, &data)?;
+        let slot = self.q.unit.new_static_data(value);
+
+        Ok(BuiltInMacro::IncludeData(BuiltInIncludeData {
+            span: ast.span(),
+            slot,
+        }))
+    }
+
+    /// Expand a macro which loads a file into the unit as a static string.
+    fn expand_include_str_macro(&mut self, ast: &ast::MacroCall) -> compile::Result<BuiltInMacro> {
+        let (lit, path, data) = self.load_included_file(ast)?;
+
+        let Ok(string) = String::from_utf8(data) else {
+            return Err(compile::Error::msg(
+                &lit,
+                format_args!("Failed to load `{path}`, file is not valid UTF-8"),
+            ));
+        };
+
+        let id = self.q.storage.insert_string(string);
+
+        Ok(BuiltInMacro::IncludeStr(BuiltInIncludeStr {
+            value: ast::Lit::Str(ast::LitStr {
+                span: ast.span(),
+                source: ast::StrSource::Synthetic(id),
+            }),
+        }))
+    }
+
+    /// Expand a macro which loads a file into the unit as static bytes.
+    fn expand_include_bytes_macro(
+        &mut self,
+        ast: &ast::MacroCall,
+    ) -> compile::Result<BuiltInMacro> {
+        let (_, _, data) = self.load_included_file(ast)?;
+        let id = self.q.storage.insert_byte_string(&data);
+
+        Ok(BuiltInMacro::IncludeBytes(BuiltInIncludeBytes {
+            value: ast::Lit::ByteStr(ast::LitByteStr {
+                span: ast.span(),
+                source: ast::StrSource::Synthetic(id),
+            }),
+        }))
+    }
+
+    /// Parse the path argument of a macro which includes a file, and load the
+    /// file relative to the current source through the source loader.
+    fn load_included_file(
+        &mut self,
+        ast: &ast::MacroCall,
+    ) -> compile::Result<(ast::LitStr, String, Vec<u8>)> {
+        let mut p = Parser::from_token_stream(&ast.input, ast.span());
+        let lit = p.parse::<ast::LitStr>()?;
+        p.parse::<Option<T![,]>>()?;
+        p.eof()?;
+
+        let path = lit.resolve(resolve_context!(self.q))?.into_owned();
+
+        let Some(root) = &self.root else {
+            return Err(compile::Error::new(ast, ErrorKind::UnsupportedModuleSource));
+        };
+
+        let data = self.q.source_loader.load_data(root, &path, &lit)?;
+        Ok((lit, path, data))
+    }
+
     /// Get or insert an item id.
     fn item_id(&mut self) -> NonZeroId {
         if let Some(id) = self.item.id {
@@ -383,6 +517,10 @@ impl<'a, 'arena> Indexer<'a, 'arena> {
     {
         ast.path.id.set(self.item_id());
 
+        self.q
+            .visitor
+            .visit_macro_call(&DynLocation::new(self.source_id, &*ast));
+
         let id = self.items.id().with_span(&ast)?;
         let item = self.q.item_for(id).with_span(&ast)?;
 
@@ -394,6 +532,45 @@ impl<'a, 'arena> Indexer<'a, 'arena> {
         compiler.eval_macro::<T>(ast)
     }
 
+    /// Expand a number literal whose suffix is handled by the context, like
+    /// `10px`.
+    ///
+    /// Returns `None` if the literal doesn't have such a suffix.
+    fn expand_literal_suffix(&mut self, ast: &ast::ExprLit) -> compile::Result<Option<ast::Expr>> {
+        let ast::Lit::Number(lit) = &ast.lit else {
+            return Ok(None);
+        };
+
+        let ast::NumberSource::Text(text) = lit.source else {
+            return Ok(None);
+        };
+
+        let suffix = self
+            .q
+            .sources
+            .source(text.source_id, text.suffix)
+            .ok_or_else(|| compile::Error::new(text.suffix, ErrorKind::BadSlice))?;
+
+        // NB: unsupported suffixes are reported when the literal is resolved.
+        let Some(handler) = self.q.context.lookup_literal_suffix(suffix).cloned() else {
+            return Ok(None);
+        };
+
+        self.q
+            .visitor
+            .visit_macro_call(&DynLocation::new(self.source_id, ast));
+
+        let id = self.items.id().with_span(ast)?;
+        let item = self.q.item_for(id).with_span(ast)?;
+
+        let mut compiler = MacroCompiler {
+            item_meta: item,
+            idx: self,
+        };
+
+        Ok(Some(compiler.eval_literal_suffix(lit, text, &handler)?))
+    }
+
     /// Perform an attribute macro expansion.
     fn expand_attribute_macro<T>(
         &mut self,
@@ -405,6 +582,11 @@ impl<'a, 'arena> Indexer<'a, 'arena> {
     {
         attr.path.id.set(self.item_id());
 
+        // NB: the attribute macro replaces the whole item it's attached to.
+        self.q
+            .visitor
+            .visit_macro_call(&DynLocation::new(self.source_id, &*item));
+
         let id = self.items.id().with_span(&*attr)?;
 
         let containing = self.q.item_for(id).with_span(&*attr)?;
@@ -507,6 +689,13 @@ pub(crate) fn file(idx: &mut Indexer<'_, '_>, ast: &mut ast::File) -> compile::R
         );
     }
 
+    if let Some((_, strict)) =
+        p.try_parse::<attrs::Strict>(resolve_context!(idx.q), &ast.attributes)?
+    {
+        let strict = strict.args(resolve_context!(idx.q))?;
+        idx.q.pool.module_mut(idx.item.module).strict.merge(strict);
+    }
+
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             first,
@@ -517,12 +706,20 @@ pub(crate) fn file(idx: &mut Indexer<'_, '_>, ast: &mut ast::File) -> compile::R
     // Items take priority.
     let mut head = VecDeque::new();
 
+    // Macro declarations are indexed before any other items, so that they can
+    // be called anywhere in the module.
+    let mut macros = 0;
+
     // Macros and items with attributes are expanded as they are encountered, but after regular items have
     // been processed.
     let mut queue = VecDeque::new();
 
     for (item, semi) in ast.items.drain(..) {
         match item {
+            i @ ast::Item::Macro(_) => {
+                head.insert(macros, (i, semi));
+                macros += 1;
+            }
             i @ ast::Item::MacroCall(_) => {
                 queue.push_back((0, i, Vec::new(), semi));
             }
@@ -722,11 +919,22 @@ fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compile::Result<(
     idx.scopes.push();
 
     for (arg, _) in &mut ast.args {
-        if let ast::FnArg::Pat(p) = arg {
-            pat(idx, p)?;
+        match arg {
+            ast::FnArg::SelfValue(..) => {}
+            ast::FnArg::Pat(p) => {
+                pat(idx, p)?;
+            }
+            ast::FnArg::Typed(typed) => {
+                pat(idx, &mut typed.pat)?;
+                ty(idx, &mut typed.ty)?;
+            }
         }
     }
 
+    if let Some((_, output)) = &mut ast.output {
+        ty(idx, output)?;
+    }
+
     // Take and restore item nesting.
     let last = idx.nested_item.replace(ast.descriptive_span());
     block(idx, &mut ast.body)?;
@@ -772,6 +980,34 @@ fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compile::Result<(
         _ => false,
     };
 
+    let mut test_params = meta::TestParams::default();
+
+    if let Some((attr, _)) =
+        p.try_parse::<attrs::Ignore>(resolve_context!(idx.q), &ast.attributes)?
+    {
+        if !is_test {
+            return Err(compile::Error::msg(
+                attr,
+                "The #[ignore] attribute is only supported on #[test] functions",
+            ));
+        }
+
+        test_params.ignore = true;
+    }
+
+    if let Some((attr, _)) =
+        p.try_parse::<attrs::ShouldPanic>(resolve_context!(idx.q), &ast.attributes)?
+    {
+        if !is_test {
+            return Err(compile::Error::msg(
+                attr,
+                "The #[should_panic] attribute is only supported on #[test] functions",
+            ));
+        }
+
+        test_params.should_panic = true;
+    }
+
     let is_bench = match p.try_parse::<attrs::Bench>(resolve_context!(idx.q), &ast.attributes)? {
         Some((attr, _)) => {
             if let Some(_nested_span) = idx.nested_item {
@@ -791,6 +1027,22 @@ fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compile::Result<(
         _ => false,
     };
 
+    let memo = match p.try_parse::<attrs::Memo>(resolve_context!(idx.q), &ast.attributes)? {
+        Some((attr, memo)) => {
+            if !matches!(call, Call::Immediate) {
+                let span = attr.span().join(ast.descriptive_span());
+
+                return Err(compile::Error::msg(
+                    span,
+                    "The #[memo] attribute is not supported on async functions or generators",
+                ));
+            }
+
+            Some(memo.args(resolve_context!(idx.q))?)
+        }
+        _ => None,
+    };
+
     if let Some(attrs) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             attrs,
@@ -813,6 +1065,13 @@ fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compile::Result<(
             ));
         }
 
+        if memo.is_some() {
+            return Err(compile::Error::msg(
+                &ast,
+                "The #[memo] attribute is not supported on member functions",
+            ));
+        }
+
         let Some(impl_item) = idx.item.impl_item else {
             return Err(compile::Error::new(
                 &ast,
@@ -835,7 +1094,9 @@ fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compile::Result<(
                 ast: Box::new(ast),
                 call,
                 is_test,
+                test_params,
                 is_bench,
+                memo,
             }),
         };
 
@@ -920,19 +1181,12 @@ fn expr_block(idx: &mut Indexer<'_, '_>, ast: &mut ast::ExprBlock) -> compile::R
 
 fn statements(idx: &mut Indexer<'_, '_>, ast: &mut Vec<ast::Stmt>) -> compile::Result<()> {
     let mut statements = Vec::new();
+    let mut items = Vec::new();
 
     for stmt in ast.drain(..) {
         match stmt {
             ast::Stmt::Item(i, semi) => {
-                if let Some(semi) = semi {
-                    if !i.needs_semi_colon() {
-                        idx.q
-                            .diagnostics
-                            .unnecessary_semi_colon(idx.source_id, &semi);
-                    }
-                }
-
-                item(idx, i)?;
+                items.push((i, semi));
             }
             stmt => {
                 statements.push(stmt);
@@ -940,6 +1194,22 @@ fn statements(idx: &mut Indexer<'_, '_>, ast: &mut Vec<ast::Stmt>) -> compile::R
         }
     }
 
+    // NB: items in a block are indexed like the items of a file, so that
+    // attribute macros are expanded the same way wherever the item is.
+    if !items.is_empty() {
+        let mut block_file = ast::File {
+            shebang: None,
+            attributes: Vec::new(),
+            items,
+        };
+
+        file(idx, &mut block_file)?;
+
+        for (i, semi) in block_file.items {
+            statements.push(ast::Stmt::Item(i, semi));
+        }
+    }
+
     let mut must_be_last = None;
 
     for stmt in &mut statements {
@@ -973,6 +1243,9 @@ fn statements(idx: &mut Indexer<'_, '_>, ast: &mut Vec<ast::Stmt>) -> compile::R
 
                 expr(idx, &mut semi.expr)?;
             }
+            ast::Stmt::Defer(defer) => {
+                block(idx, &mut defer.block)?;
+            }
             ast::Stmt::Item(i, ..) => {
                 return Err(compile::Error::msg(i, "Unexpected item in this stage"));
             }
@@ -1018,6 +1291,12 @@ fn local(idx: &mut Indexer<'_, '_>, ast: &mut ast::Local) -> compile::Result<()>
     // We index the rhs expression first so that it doesn't see it's own
     // declaration and use that instead of capturing from the outside.
     expr(idx, &mut ast.expr)?;
+
+    // The else block can't see the bindings in the pattern either.
+    if let Some(else_) = &mut ast.else_ {
+        block(idx, &mut else_.block)?;
+    }
+
     pat(idx, &mut ast.pat)?;
     Ok(())
 }
@@ -1051,6 +1330,9 @@ fn pat(idx: &mut Indexer<'_, '_>, ast: &mut ast::Pat) -> compile::Result<()> {
         ast::Pat::Binding(pat) => {
             pat_binding(idx, pat)?;
         }
+        ast::Pat::At(at) => {
+            pat(idx, &mut at.pat)?;
+        }
         ast::Pat::Ignore(..) => (),
         ast::Pat::Lit(..) => (),
         ast::Pat::Rest(..) => (),
@@ -1185,7 +1467,14 @@ pub(crate) fn expr(idx: &mut Indexer<'_, '_>, ast: &mut ast::Expr) -> compile::R
         ast::Expr::Call(e) => {
             expr_call(idx, e)?;
         }
-        ast::Expr::Lit(..) => {}
+        ast::Expr::Lit(e) => {
+            if let Some(out) = idx.expand_literal_suffix(e)? {
+                idx.enter_macro(&*e)?;
+                *ast = out;
+                expr(idx, ast)?;
+                idx.leave_macro();
+            }
+        }
         ast::Expr::Tuple(e) => {
             expr_tuple(idx, e)?;
         }
@@ -1310,6 +1599,7 @@ fn item_enum(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemEnum) -> compile::Resu
     let mut p = attrs::Parser::new(&ast.attributes);
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+    let derives = derives(resolve_context!(idx.q), &mut p, &ast.attributes)?;
 
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
@@ -1333,6 +1623,10 @@ fn item_enum(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemEnum) -> compile::Resu
 
     idx.q.index_enum(enum_item)?;
 
+    let mut discriminants = HashMap::new();
+    let mut next_discriminant = Some(0);
+    let mut unit_variants = Vec::new();
+
     for (index, (mut variant, _)) in ast.variants.drain().enumerate() {
         let mut p = attrs::Parser::new(&variant.attributes);
 
@@ -1385,10 +1679,81 @@ fn item_enum(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemEnum) -> compile::Resu
             }
         }
 
+        let discriminant = match &variant.discriminant {
+            Some((_, expr)) => {
+                if !matches!(variant.body, ast::Fields::Empty) {
+                    return Err(compile::Error::new(
+                        expr,
+                        ErrorKind::UnsupportedDiscriminant,
+                    ));
+                }
+
+                variant_discriminant(resolve_context!(idx.q), expr)?
+            }
+            None => {
+                let Some(discriminant) = next_discriminant else {
+                    return Err(compile::Error::new(
+                        &variant.name,
+                        ErrorKind::DiscriminantOverflow,
+                    ));
+                };
+
+                discriminant
+            }
+        };
+
+        if let Some(_existing) = discriminants.insert(discriminant, variant.name.span()) {
+            return Err(compile::Error::new(
+                &variant.name,
+                ErrorKind::DuplicateDiscriminant {
+                    #[cfg(feature = "emit")]
+                    existing: _existing,
+                    discriminant,
+                },
+            ));
+        }
+
+        next_discriminant = discriminant.checked_add(1);
+
+        if matches!(variant.body, ast::Fields::Empty) {
+            unit_variants.push((discriminant, item_meta.item));
+        }
+
         idx.item = idx_item;
         idx.items.pop(guard).with_span(&variant)?;
-        idx.q
-            .index_variant(item_meta, enum_item.id, variant, index)?;
+        idx.q.index_variant(
+            item_meta,
+            enum_item.id,
+            variant,
+            index,
+            discriminant,
+            derives,
+        )?;
+    }
+
+    let guard = idx.items.push_name("from_int");
+
+    let item_meta = idx.q.insert_new_item(
+        &idx.items,
+        &DynLocation::new(idx.source_id, &ast.name),
+        idx.item.module,
+        visibility,
+        &[],
+    )?;
+
+    idx.items.pop(guard).with_span(&ast)?;
+
+    let entry = indexing::Entry {
+        item_meta,
+        indexed: Indexed::EnumFromInt(indexing::EnumFromInt {
+            variants: unit_variants,
+        }),
+    };
+
+    if visibility.is_public() {
+        idx.q.index_and_build(entry);
+    } else {
+        idx.q.index(entry);
     }
 
     idx.item = idx_item;
@@ -1396,11 +1761,71 @@ fn item_enum(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemEnum) -> compile::Resu
     Ok(())
 }
 
+/// Collect the protocols derived through `#[derive(..)]` attributes.
+fn derives(
+    cx: ResolveContext<'_>,
+    p: &mut attrs::Parser,
+    attributes: &[ast::Attribute],
+) -> compile::Result<Derives> {
+    let mut derives = Derives::default();
+
+    for result in p.parse_all::<attrs::Derive>(cx, attributes) {
+        let (_, derive) = result?;
+        derive.apply(cx, &mut derives)?;
+    }
+
+    Ok(derives)
+}
+
+/// Resolve the explicit discriminant of an enum variant, which must be an
+/// integer literal.
+fn variant_discriminant(cx: ResolveContext<'_>, ast: &ast::Expr) -> compile::Result<i64> {
+    let (neg, lit) = match ast {
+        ast::Expr::Lit(ast::ExprLit {
+            lit: ast::Lit::Number(lit),
+            ..
+        }) => (false, lit),
+        ast::Expr::Unary(ast::ExprUnary {
+            op: ast::UnOp::Neg(..),
+            expr,
+            ..
+        }) => match &**expr {
+            ast::Expr::Lit(ast::ExprLit {
+                lit: ast::Lit::Number(lit),
+                ..
+            }) => (true, lit),
+            _ => return Err(compile::Error::new(ast, ErrorKind::UnsupportedDiscriminant)),
+        },
+        _ => return Err(compile::Error::new(ast, ErrorKind::UnsupportedDiscriminant)),
+    };
+
+    let number = lit.resolve(cx)?;
+
+    if number.suffix.is_some() {
+        return Err(compile::Error::new(ast, ErrorKind::UnsupportedDiscriminant));
+    }
+
+    let ast::NumberValue::Integer(mut int) = number.value else {
+        return Err(compile::Error::new(ast, ErrorKind::UnsupportedDiscriminant));
+    };
+
+    if neg {
+        int = -int;
+    }
+
+    let Some(int) = int.to_i64() else {
+        return Err(compile::Error::new(ast, ErrorKind::BadNumberOutOfBounds));
+    };
+
+    Ok(int)
+}
+
 #[instrument(span = ast)]
 fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::Result<()> {
     let mut p = attrs::Parser::new(&ast.attributes);
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+    let derives = derives(resolve_context!(idx.q), &mut p, &ast.attributes)?;
 
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
@@ -1458,7 +1883,7 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
 
     idx.item = idx_item;
     idx.items.pop(guard).with_span(&ast)?;
-    idx.q.index_struct(item_meta, Box::new(ast))?;
+    idx.q.index_struct(item_meta, Box::new(ast), derives)?;
     Ok(())
 }
 
@@ -1495,6 +1920,10 @@ fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Resu
     let new = idx.q.pool.alloc_item(idx.items.item());
     let idx_item = idx.item.replace_impl(new);
 
+    for (i, _) in ast.consts.drain(..) {
+        item_const(idx, i)?;
+    }
+
     for i in ast.functions.drain(..) {
         item_fn(idx, i)?;
     }
@@ -1595,6 +2024,67 @@ fn item_const(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemConst) -> compile::Re
     Ok(())
 }
 
+#[instrument(span = ast)]
+fn item_macro(idx: &mut Indexer<'_, '_>, ast: ast::ItemMacro) -> compile::Result<()> {
+    let mut p = attrs::Parser::new(&ast.attributes);
+
+    let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+
+    if let Some(first) = p.remaining(&ast.attributes).next() {
+        return Err(compile::Error::msg(
+            first,
+            "Attributes on macros are not supported",
+        ));
+    }
+
+    let declarative_macro = DeclarativeMacro::new(resolve_context!(idx.q), &ast)?;
+
+    let name = ast.name.resolve(resolve_context!(idx.q))?;
+    let guard = idx.items.push_name(name.as_ref());
+    let item = idx.q.pool.alloc_item(idx.items.item());
+
+    idx.q.insert_declarative_macro(
+        &DynLocation::new(idx.source_id, &ast.descriptive_span()),
+        item,
+        &docs,
+        declarative_macro,
+    )?;
+
+    idx.items.pop(guard).with_span(&ast)?;
+    Ok(())
+}
+
+#[instrument(span = ast)]
+fn item_extern_const(idx: &mut Indexer<'_, '_>, ast: ast::ItemExternConst) -> compile::Result<()> {
+    let mut p = attrs::Parser::new(&ast.attributes);
+
+    let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+
+    if let Some(first) = p.remaining(&ast.attributes).next() {
+        return Err(compile::Error::msg(
+            first,
+            "Attributes on constants are not supported",
+        ));
+    }
+
+    let name = ast.name.resolve(resolve_context!(idx.q))?;
+    let guard = idx.items.push_name(name.as_ref());
+
+    let item_meta = idx.q.insert_new_item(
+        &idx.items,
+        &DynLocation::new(idx.source_id, &ast),
+        idx.item.module,
+        ast_to_visibility(&ast.visibility)?,
+        &docs,
+    )?;
+
+    ast.id.set(item_meta.id);
+    idx.q.index_extern_const(item_meta)?;
+
+    idx.items.pop(guard).with_span(&ast)?;
+    Ok(())
+}
+
 #[instrument(span = ast)]
 fn item(idx: &mut Indexer<'_, '_>, ast: ast::Item) -> compile::Result<()> {
     match ast {
@@ -1616,6 +2106,12 @@ fn item(idx: &mut Indexer<'_, '_>, ast: ast::Item) -> compile::Result<()> {
         ast::Item::Const(item) => {
             item_const(idx, item)?;
         }
+        ast::Item::ExternConst(item) => {
+            item_extern_const(idx, item)?;
+        }
+        ast::Item::Macro(item) => {
+            item_macro(idx, item)?;
+        }
         ast::Item::MacroCall(macro_call) => {
             // Note: There is a preprocessing step involved with items for
             // which the macro must have been expanded to a built-in macro
@@ -1684,6 +2180,13 @@ fn path(idx: &mut Indexer<'_, '_>, ast: &mut ast::Path) -> compile::Result<()> {
     Ok(())
 }
 
+#[instrument(span = ast)]
+fn ty(idx: &mut Indexer<'_, '_>, ast: &mut ast::Type) -> compile::Result<()> {
+    match ast {
+        ast::Type::Path(p) => path(idx, p),
+    }
+}
+
 #[instrument(span = ast)]
 fn path_segment(idx: &mut Indexer<'_, '_>, ast: &mut ast::PathSegment) -> compile::Result<()> {
     if let ast::PathSegment::Generics(generics) = ast {
@@ -1741,6 +2244,10 @@ fn expr_closure(idx: &mut Indexer<'_, '_>, ast: &mut ast::ExprClosure) -> compil
             ast::FnArg::Pat(p) => {
                 pat(idx, p)?;
             }
+            ast::FnArg::Typed(typed) => {
+                pat(idx, &mut typed.pat)?;
+                ty(idx, &mut typed.ty)?;
+            }
         }
     }
 
@@ -1912,9 +2419,16 @@ fn expr_object(idx: &mut Indexer<'_, '_>, ast: &mut ast::ExprObject) -> compile:
         ast::ObjectIdent::Anonymous(..) => (),
     }
 
-    for (assign, _) in &mut ast.assignments {
-        if let Some((_, e)) = &mut assign.assign {
-            expr(idx, e)?;
+    for (entry, _) in &mut ast.assignments {
+        match entry {
+            ast::ObjectEntry::Assign(assign) => {
+                if let Some((_, e)) = &mut assign.assign {
+                    expr(idx, e)?;
+                }
+            }
+            ast::ObjectEntry::Spread(spread) => {
+                expr(idx, &mut spread.expr)?;
+            }
         }
     }
 
@@ -2028,3 +2542,31 @@ impl IndexItem {
         }
     }
 }
+
+/// Deserialize loaded data based on the extension of its path.
+#[cfg(feature = "json")]
+fn load_data(span: &dyn Spanned, path: &str, data: &[u8]) -> compile::Result<ConstValue> {
+    if !path.ends_with(".json") {
+        return Err(compile::Error::msg(
+            span,
+            format_args!("Unsupported data format for `{path}`, expected a `.json` file"),
+        ));
+    }
+
+    match indexing::include_data::from_json(data) {
+        Ok(value) => Ok(value),
+        Err(error) => Err(compile::Error::msg(
+            span,
+            format_args!("Failed to load data from `{path}`: {error}"),
+        )),
+    }
+}
+
+/// Deserialize loaded data based on the extension of its path.
+#[cfg(not(feature = "json"))]
+fn load_data(span: &dyn Spanned, path: &str, _: &[u8]) -> compile::Result<ConstValue> {
+    Err(compile::Error::msg(
+        span,
+        format_args!("Loading `{path}` requires the `json` feature to be enabled"),
+    ))
+}
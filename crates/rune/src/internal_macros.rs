@@ -59,6 +59,24 @@ macro_rules! repeat_macro {
     };
 }
 
+/// Like [`repeat_macro!`], but continues up to an arity of 24.
+///
+/// This is only used for tuple conversions, since the macros used for function
+/// traits declare their own `T` and `U` type parameters.
+macro_rules! repeat_macro_extended {
+    ($macro:ident) => {
+        repeat_macro!($macro);
+        $macro!(17, A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10, L l 11, M m 12, N n 13, O o 14, P p 15, Q q 16);
+        $macro!(18, A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10, L l 11, M m 12, N n 13, O o 14, P p 15, Q q 16, R r 17);
+        $macro!(19, A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10, L l 11, M m 12, N n 13, O o 14, P p 15, Q q 16, R r 17, S s 18);
+        $macro!(20, A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10, L l 11, M m 12, N n 13, O o 14, P p 15, Q q 16, R r 17, S s 18, T t 19);
+        $macro!(21, A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10, L l 11, M m 12, N n 13, O o 14, P p 15, Q q 16, R r 17, S s 18, T t 19, U u 20);
+        $macro!(22, A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10, L l 11, M m 12, N n 13, O o 14, P p 15, Q q 16, R r 17, S s 18, T t 19, U u 20, V v 21);
+        $macro!(23, A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10, L l 11, M m 12, N n 13, O o 14, P p 15, Q q 16, R r 17, S s 18, T t 19, U u 20, V v 21, W w 22);
+        $macro!(24, A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10, L l 11, M m 12, N n 13, O o 14, P p 15, Q q 16, R r 17, S s 18, T t 19, U u 20, V v 21, W w 22, X x 23);
+    };
+}
+
 macro_rules! cfg_emit {
     ($($item:item)*) => {
         $(
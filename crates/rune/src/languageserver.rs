@@ -6,6 +6,7 @@ mod completion;
 mod connection;
 pub mod envelope;
 mod fs;
+mod hover;
 mod state;
 mod url;
 
@@ -16,6 +17,7 @@ use lsp::request::Request;
 use serde::Deserialize;
 use tokio::sync::Notify;
 
+use crate::compile;
 use crate::languageserver::connection::stdio;
 use crate::languageserver::envelope::Code;
 use crate::languageserver::state::State;
@@ -101,7 +103,13 @@ pub async fn run(context: Context, options: Options) -> Result<()> {
                     req(lsp::request::Shutdown, shutdown),
                     req(lsp::request::GotoDefinition, goto_definition),
                     req(lsp::request::Completion, completion),
+                    req(lsp::request::HoverRequest, hover),
                     req(lsp::request::Formatting, formatting),
+                    req(lsp::request::References, references),
+                    req(lsp::request::Rename, rename),
+                    req(lsp::request::SemanticTokensFullRequest, semantic_tokens_full),
+                    req(lsp::request::FoldingRangeRequest, folding_range),
+                    req(lsp::request::DocumentSymbolRequest, document_symbol),
                     notif(lsp::notification::DidOpenTextDocument, did_open_text_document),
                     notif(lsp::notification::DidChangeTextDocument, did_change_text_document),
                     notif(lsp::notification::DidCloseTextDocument, did_close_text_document),
@@ -142,7 +150,20 @@ async fn initialize(
                 label_details_support: Some(true),
             }),
         }),
+        hover_provider: Some(lsp::HoverProviderCapability::Simple(true)),
         document_formatting_provider: Some(lsp::OneOf::Left(true)),
+        references_provider: Some(lsp::OneOf::Left(true)),
+        rename_provider: Some(lsp::OneOf::Left(true)),
+        semantic_tokens_provider: Some(
+            lsp::SemanticTokensOptions {
+                legend: semantic_tokens_legend(),
+                full: Some(lsp::SemanticTokensFullOptions::Bool(true)),
+                ..Default::default()
+            }
+            .into(),
+        ),
+        folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
+        document_symbol_provider: Some(lsp::OneOf::Left(true)),
         ..Default::default()
     };
 
@@ -219,6 +240,14 @@ async fn completion(
     Ok(results)
 }
 
+/// Handle hover request.
+async fn hover(state: &mut State<'_>, params: lsp::HoverParams) -> Result<Option<lsp::Hover>> {
+    Ok(state.hover(
+        &params.text_document_position_params.text_document.uri,
+        params.text_document_position_params.position,
+    ))
+}
+
 /// Handle formatting request.
 async fn formatting(
     state: &mut State<'_>,
@@ -229,6 +258,110 @@ async fn formatting(
         .map(|option| option.map(|formatted| vec![formatted]))
 }
 
+/// Handle find references request.
+async fn references(
+    state: &mut State<'_>,
+    params: lsp::ReferenceParams,
+) -> Result<Option<Vec<lsp::Location>>> {
+    let Some(found) = state.find_references(
+        &params.text_document_position.text_document.uri,
+        params.text_document_position.position,
+    ) else {
+        return Ok(None);
+    };
+
+    let include_declaration = params.context.include_declaration;
+
+    let locations = found
+        .into_iter()
+        .filter(|(_, reference)| {
+            include_declaration || reference.kind != compile::ReferenceKind::Definition
+        })
+        .map(|(location, _)| location)
+        .collect();
+
+    Ok(Some(locations))
+}
+
+/// Handle rename request.
+async fn rename(
+    state: &mut State<'_>,
+    params: lsp::RenameParams,
+) -> Result<Option<lsp::WorkspaceEdit>> {
+    let Some((edit, skipped)) = state.rename(
+        &params.text_document_position.text_document.uri,
+        params.text_document_position.position,
+        &params.new_name,
+    ) else {
+        return Ok(None);
+    };
+
+    if !skipped.is_empty() {
+        let message = lsp::ShowMessageParams {
+            typ: lsp::MessageType::WARNING,
+            message: format!(
+                "{} reference(s) inside of macro calls were not renamed",
+                skipped.len()
+            ),
+        };
+
+        state
+            .output
+            .notification::<lsp::notification::ShowMessage>(message)
+            .await?;
+    }
+
+    Ok(Some(edit))
+}
+
+/// The legend of semantic tokens, where the index of a token type corresponds
+/// to [compile::SemanticTokenKind::index] and the only modifier marks
+/// definitions.
+fn semantic_tokens_legend() -> lsp::SemanticTokensLegend {
+    let token_types = compile::SemanticTokenKind::ALL
+        .into_iter()
+        .map(|kind| match kind {
+            compile::SemanticTokenKind::Function => lsp::SemanticTokenType::FUNCTION,
+            compile::SemanticTokenKind::Type => lsp::SemanticTokenType::TYPE,
+            compile::SemanticTokenKind::Variable => lsp::SemanticTokenType::VARIABLE,
+            compile::SemanticTokenKind::Constant => lsp::SemanticTokenType::new("constant"),
+            compile::SemanticTokenKind::Macro => lsp::SemanticTokenType::MACRO,
+        })
+        .collect();
+
+    lsp::SemanticTokensLegend {
+        token_types,
+        token_modifiers: vec![lsp::SemanticTokenModifier::DECLARATION],
+    }
+}
+
+/// Handle semantic tokens request.
+async fn semantic_tokens_full(
+    state: &mut State<'_>,
+    params: lsp::SemanticTokensParams,
+) -> Result<Option<lsp::SemanticTokensResult>> {
+    Ok(state
+        .semantic_tokens(&params.text_document.uri)
+        .map(lsp::SemanticTokensResult::Tokens))
+}
+
+/// Handle folding range request.
+async fn folding_range(
+    state: &mut State<'_>,
+    params: lsp::FoldingRangeParams,
+) -> Result<Option<Vec<lsp::FoldingRange>>> {
+    Ok(state.folding_ranges(&params.text_document.uri))
+}
+
+/// Handle document symbol request.
+async fn document_symbol(
+    state: &mut State<'_>,
+    params: lsp::DocumentSymbolParams,
+) -> Result<Option<lsp::DocumentSymbolResponse>> {
+    let symbols = state.document_symbols(&params.text_document.uri);
+    Ok(symbols.map(lsp::DocumentSymbolResponse::Flat))
+}
+
 /// Handle open text document.
 async fn did_open_text_document(
     s: &mut State<'_>,
@@ -0,0 +1,116 @@
+use core::fmt::Write;
+
+use crate::no_std::prelude::*;
+
+use lsp::{Hover, HoverContents, MarkupContent, MarkupKind};
+
+use crate::compile::meta;
+use crate::{Context, Hash, Unit};
+
+use super::state::Source;
+
+/// Construct hover information for a function compiled from sources.
+pub(super) fn hover_for_unit(source: &Source, unit: &Unit, hash: Hash) -> Option<Hover> {
+    let signature = unit.debug_info()?.functions.get(&hash)?;
+
+    let docs = source
+        .get_docs_by_hash(hash)
+        .map(|docs| docs.docs.as_slice())
+        .unwrap_or_default();
+
+    Some(hover(&format!("fn {signature}"), docs))
+}
+
+/// Construct hover information for a native function provided by the
+/// context, like `fn push(self, value: Value)`.
+pub(super) fn hover_for_context(context: &Context, hash: Hash) -> Option<Hover> {
+    let meta = context.lookup_meta_by_hash(hash).next()?;
+    let signature = meta.kind.as_signature()?;
+    let item = meta.item.as_deref()?;
+
+    // Instance functions are associated with the item of their type.
+    let (path, name) = match &meta.kind {
+        meta::Kind::AssociatedFunction {
+            kind: meta::AssociatedKind::Instance(name),
+            ..
+        } => (Some(item), name.to_string()),
+        _ => (item.parent(), item.last()?.to_string()),
+    };
+
+    let mut code = String::new();
+
+    if let Some(path) = path.filter(|path| !path.is_empty()) {
+        let _ = writeln!(code, "{}", path.to_string().trim_start_matches("::"));
+    }
+
+    if signature.is_async {
+        code.push_str("async ");
+    }
+
+    let _ = write!(code, "fn {name}(");
+
+    let mut types = signature.argument_types.iter();
+
+    let names = match meta.docs.args() {
+        Some(names) => names.to_vec(),
+        None => match signature.args {
+            Some(args) => (0..args).map(|n| format!("_{n}")).collect(),
+            None => vec![String::from("..")],
+        },
+    };
+
+    let mut it = names.iter().peekable();
+
+    while let Some(arg) = it.next() {
+        code.push_str(arg);
+
+        let ty = types.next().and_then(|ty| type_name(context, *ty));
+
+        // NB: the type of `self` is evident from the path of the function.
+        if let (Some(ty), false) = (ty, arg == "self") {
+            let _ = write!(code, ": {ty}");
+        }
+
+        if it.peek().is_some() {
+            code.push_str(", ");
+        }
+    }
+
+    code.push(')');
+
+    if let Some(ty) = type_name(context, signature.return_type) {
+        let _ = write!(code, " -> {ty}");
+    }
+
+    let mut docs = meta.docs.lines().to_vec();
+
+    if let Some(deprecated) = &signature.deprecated {
+        docs.insert(0, format!("**Deprecated:** {deprecated}\n"));
+    }
+
+    Some(hover(&code, &docs))
+}
+
+/// Get the name of the type with the given hash.
+fn type_name(context: &Context, hash: Option<Hash>) -> Option<String> {
+    let meta = context.lookup_meta_by_hash(hash?).next()?;
+    let item = meta.item.as_deref()?;
+    Some(item.last()?.to_string())
+}
+
+fn hover(code: &str, docs: &[String]) -> Hover {
+    let mut value = format!("```rune\n{code}\n```");
+
+    if !docs.is_empty() {
+        value.push_str("\n\n");
+        value.push_str(&docs.join("\n"));
+    }
+
+    Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        }),
+        range: None,
+    }
+}
@@ -14,8 +14,7 @@ use tokio::sync::Notify;
 use crate::ast::{Span, Spanned};
 use crate::compile::meta;
 use crate::compile::{
-    self, CompileVisitor, ComponentRef, Item, ItemBuf, LinkerError, Located, Location, MetaRef,
-    SourceMeta,
+    self, CompileVisitor, ItemBuf, LinkerError, Located, Location, MetaRef, References, SourceMeta,
 };
 use crate::diagnostics::{Diagnostic, FatalDiagnosticKind};
 use crate::doc::VisitorData;
@@ -195,6 +194,230 @@ impl<'a> State<'a> {
         Some(location)
     }
 
+    /// Find all references to the symbol at the given uri and LSP position.
+    pub(super) fn find_references(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+    ) -> Option<Vec<(lsp::Location, compile::Reference)>> {
+        let source = self.workspace.get(uri)?;
+        let source_id = source.source_id?;
+        let references = source.references.as_ref()?;
+        let sources = source.build_sources.as_ref()?;
+
+        let offset = source.lsp_position_to_offset(position);
+        let symbol = references.symbol_at(sources, source_id, offset)?;
+
+        let mut locations = Vec::new();
+
+        for reference in references.references(sources, &symbol) {
+            let url = if reference.source_id == source_id {
+                uri.clone()
+            } else {
+                let Some(path) = sources.path(reference.source_id) else {
+                    continue;
+                };
+
+                let Ok(url) = crate::languageserver::url::from_file_path(path) else {
+                    continue;
+                };
+
+                url
+            };
+
+            let Some(range) = sources
+                .get(reference.source_id)
+                .and_then(|s| span_to_lsp_range(s, reference.span))
+            else {
+                continue;
+            };
+
+            locations.push((lsp::Location { uri: url, range }, reference));
+        }
+
+        Some(locations)
+    }
+
+    /// Describe the symbol at the given uri and LSP position, like the
+    /// signature and documentation of a function.
+    pub(super) fn hover(&self, uri: &Url, position: lsp::Position) -> Option<lsp::Hover> {
+        let source = self.workspace.get(uri)?;
+        let source_id = source.source_id?;
+        let references = source.references.as_ref()?;
+        let sources = source.build_sources.as_ref()?;
+
+        let offset = source.lsp_position_to_offset(position);
+
+        let compile::Symbol::Item(hash) = references.symbol_at(sources, source_id, offset)? else {
+            return None;
+        };
+
+        if let Some(hover) = source
+            .unit
+            .as_ref()
+            .and_then(|unit| super::hover::hover_for_unit(source, unit, hash))
+        {
+            return Some(hover);
+        }
+
+        super::hover::hover_for_context(&self.context, hash)
+    }
+
+    /// Rename the symbol at the given uri and LSP position.
+    ///
+    /// References inside of macro calls are not renamed, since they might
+    /// have been produced by the macro. Instead they're returned so that they
+    /// can be reported.
+    pub(super) fn rename(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        new_name: &str,
+    ) -> Option<(lsp::WorkspaceEdit, Vec<lsp::Location>)> {
+        let mut changes = HashMap::<Url, Vec<lsp::TextEdit>>::new();
+        let mut skipped = Vec::new();
+
+        for (location, reference) in self.find_references(uri, position)? {
+            if reference.in_macro {
+                skipped.push(location);
+                continue;
+            }
+
+            changes
+                .entry(location.uri)
+                .or_default()
+                .push(lsp::TextEdit::new(location.range, new_name.to_owned()));
+        }
+
+        let edit = lsp::WorkspaceEdit {
+            changes: Some(changes.into_iter().collect()),
+            ..Default::default()
+        };
+
+        Some((edit, skipped))
+    }
+
+    /// Classify the names in the source at the given uri.
+    pub(super) fn semantic_tokens(&self, uri: &Url) -> Option<lsp::SemanticTokens> {
+        let source = self.workspace.get(uri)?;
+        let source_id = source.source_id?;
+        let references = source.references.as_ref()?;
+        let sources = source.build_sources.as_ref()?;
+        let input = sources.get(source_id)?;
+
+        let mut data = Vec::new();
+        let mut last = lsp::Position::new(0, 0);
+
+        for token in references.semantic_tokens(sources, source_id) {
+            let Some(range) = span_to_lsp_range(input, token.span) else {
+                continue;
+            };
+
+            // NB: tokens are encoded relative to each other, and can't span
+            // multiple lines.
+            if range.start.line != range.end.line {
+                continue;
+            }
+
+            let delta_line = range.start.line - last.line;
+
+            let delta_start = if delta_line == 0 {
+                range.start.character - last.character
+            } else {
+                range.start.character
+            };
+
+            data.push(lsp::SemanticToken {
+                delta_line,
+                delta_start,
+                length: range.end.character - range.start.character,
+                token_type: token.kind.index() as u32,
+                token_modifiers_bitset: u32::from(token.definition),
+            });
+
+            last = range.start;
+        }
+
+        Some(lsp::SemanticTokens {
+            result_id: None,
+            data,
+        })
+    }
+
+    /// Collect the symbols declared in the source at the given uri, like its
+    /// functions, types and modules.
+    pub(super) fn document_symbols(&self, uri: &Url) -> Option<Vec<lsp::SymbolInformation>> {
+        let source = self.workspace.get(uri)?;
+        let input = source.build_sources.as_ref()?.get(source.source_id?)?;
+
+        let mut symbols = Vec::new();
+
+        for symbol in &source.index.symbols {
+            let Some(range) = span_to_lsp_range(input, symbol.span) else {
+                continue;
+            };
+
+            // NB: the deprecated field has to be specified.
+            #[allow(deprecated)]
+            symbols.push(lsp::SymbolInformation {
+                name: symbol.name.clone(),
+                kind: symbol.kind,
+                tags: None,
+                deprecated: None,
+                location: lsp::Location {
+                    uri: uri.clone(),
+                    range,
+                },
+                container_name: symbol.container.clone(),
+            });
+        }
+
+        Some(symbols)
+    }
+
+    /// Compute the ranges which can be folded in the source at the given uri.
+    pub(super) fn folding_ranges(&self, uri: &Url) -> Option<Vec<lsp::FoldingRange>> {
+        let source = self.workspace.get(uri)?;
+        let input = crate::Source::memory(source.to_string());
+
+        let mut ranges = Vec::new();
+
+        for range in compile::folding_ranges(input.as_str()) {
+            let Some(lsp_range) = span_to_lsp_range(&input, range.span) else {
+                continue;
+            };
+
+            let (kind, end_line) = match range.kind {
+                // NB: the line with the closing delimiter is kept visible.
+                compile::FoldingRangeKind::Region => (
+                    lsp::FoldingRangeKind::Region,
+                    lsp_range.end.line.saturating_sub(1),
+                ),
+                compile::FoldingRangeKind::Comment => {
+                    (lsp::FoldingRangeKind::Comment, lsp_range.end.line)
+                }
+                compile::FoldingRangeKind::Imports => {
+                    (lsp::FoldingRangeKind::Imports, lsp_range.end.line)
+                }
+            };
+
+            if end_line <= lsp_range.start.line {
+                continue;
+            }
+
+            ranges.push(lsp::FoldingRange {
+                start_line: lsp_range.start.line,
+                start_character: None,
+                end_line,
+                end_character: None,
+                kind: Some(kind),
+                collapsed_text: None,
+            });
+        }
+
+        Some(ranges)
+    }
+
     /// Find definition at the given uri and LSP position.
     #[tracing::instrument(skip_all)]
     pub(super) fn complete(
@@ -356,12 +579,15 @@ impl<'a> State<'a> {
             emit_workspace(diagnostics, &build, &mut reporter);
         }
 
-        for (diagnostics, mut build, source_visitor, doc_visitor, unit) in script_results {
+        for (diagnostics, mut build, source_visitor, doc_visitor, references, unit) in
+            script_results
+        {
             build.populate(&mut reporter);
             emit_scripts(diagnostics, &build, &mut reporter);
 
             let sources = Arc::new(build.sources);
             let doc_visitor = Arc::new(doc_visitor);
+            let references = Arc::new(references);
 
             for (source_id, value) in source_visitor.into_indexes() {
                 let Some(url) = build.id_to_url.get(&source_id) else {
@@ -373,7 +599,9 @@ impl<'a> State<'a> {
                 };
 
                 source.index = value;
+                source.source_id = Some(source_id);
                 source.build_sources = Some(sources.clone());
+                source.references = Some(references.clone());
 
                 if let Ok(unit) = unit.as_ref().map(|v| v.clone()) {
                     source.unit = Some(unit.clone());
@@ -462,13 +690,16 @@ impl<'a> State<'a> {
         Build,
         Visitor,
         crate::doc::Visitor,
+        References,
         Result<Unit, BuildError>,
     ) {
         let mut diagnostics = crate::Diagnostics::new();
         let mut source_visitor = Visitor::default();
         let mut doc_visitor = crate::doc::Visitor::new(ItemBuf::new());
+        let mut references = References::new();
 
-        let mut source_loader = ScriptSourceLoader::new(&self.workspace.sources);
+        let overlay = self.workspace.overlay();
+        let mut source_loader = compile::OverlaySourceLoader::new(&overlay);
 
         let unit = crate::prepare(&mut build.sources)
             .with_context(&self.context)
@@ -476,6 +707,7 @@ impl<'a> State<'a> {
             .with_options(&self.options)
             .with_visitor(&mut doc_visitor)
             .with_visitor(&mut source_visitor)
+            .with_visitor(&mut references)
             .with_source_loader(&mut source_loader)
             .build();
 
@@ -483,7 +715,14 @@ impl<'a> State<'a> {
             build.visit(built);
         }
 
-        (diagnostics, build, source_visitor, doc_visitor, unit)
+        (
+            diagnostics,
+            build,
+            source_visitor,
+            doc_visitor,
+            references,
+            unit,
+        )
     }
 }
 
@@ -582,7 +821,9 @@ impl Workspace {
         let source = Source {
             content: Rope::from(text),
             index: Default::default(),
+            source_id: None,
             build_sources: None,
+            references: None,
             language,
             unit: None,
             docs: None,
@@ -606,6 +847,20 @@ impl Workspace {
             self.removed.push(url.clone());
         }
     }
+
+    /// Construct an overlay with the current contents of all sources, which
+    /// takes precedence over the files on disk when building.
+    fn overlay(&self) -> compile::SourceOverlay {
+        let mut overlay = compile::SourceOverlay::new();
+
+        for (url, source) in &self.sources {
+            if let Ok(path) = url.to_file_path() {
+                overlay.insert(path, source.to_string());
+            }
+        }
+
+        overlay
+    }
 }
 
 /// A single open source.
@@ -614,9 +869,14 @@ pub(super) struct Source {
     content: Rope,
     /// Indexes used to answer queries.
     index: Index,
+    /// The identifier of this source in its build.
+    source_id: Option<SourceId>,
     /// Loaded Rune sources for this source file. Will be present after the
     /// source file has been built.
     build_sources: Option<Arc<crate::Sources>>,
+    /// References to symbols, used to answer find references and rename
+    /// queries.
+    references: Option<Arc<References>>,
     /// The language of the source.
     language: Language,
     /// The compiled unit
@@ -815,6 +1075,21 @@ where
 pub(super) struct Index {
     /// Spans mapping to their corresponding definitions.
     definitions: BTreeMap<Span, Definition>,
+    /// Symbols declared in the source, in the order they were registered.
+    symbols: Vec<DocumentSymbol>,
+}
+
+/// A symbol declared in a source.
+#[derive(Debug, Clone)]
+pub(super) struct DocumentSymbol {
+    /// The name of the symbol.
+    name: String,
+    /// The kind of the symbol.
+    kind: lsp::SymbolKind,
+    /// The span of the declaration.
+    span: Span,
+    /// The item the symbol is declared in, if it's not declared in the root.
+    container: Option<String>,
 }
 
 /// A definition source.
@@ -900,6 +1175,47 @@ impl Visitor {
 }
 
 impl CompileVisitor for Visitor {
+    fn register_meta(&mut self, meta: MetaRef<'_>) {
+        let Some(source) = meta.source else {
+            return;
+        };
+
+        if meta.context {
+            return;
+        }
+
+        let kind = match &meta.kind {
+            meta::Kind::Struct { .. } => lsp::SymbolKind::STRUCT,
+            meta::Kind::Variant { .. } => lsp::SymbolKind::ENUM_MEMBER,
+            meta::Kind::Enum { .. } => lsp::SymbolKind::ENUM,
+            meta::Kind::Function { .. } | meta::Kind::ConstFn { .. } => lsp::SymbolKind::FUNCTION,
+            meta::Kind::AssociatedFunction { .. } => lsp::SymbolKind::METHOD,
+            meta::Kind::Const => lsp::SymbolKind::CONSTANT,
+            meta::Kind::Module => lsp::SymbolKind::MODULE,
+            _ => return,
+        };
+
+        let Some(name) = meta.item.last() else {
+            return;
+        };
+
+        let container = meta
+            .item
+            .parent()
+            .filter(|parent| !parent.is_empty())
+            .map(|parent| parent.to_string());
+
+        let symbol = DocumentSymbol {
+            name: name.to_string(),
+            kind,
+            span: source.location.span,
+            container,
+        };
+
+        let index = self.indexes.entry(source.location.source_id).or_default();
+        index.symbols.push(symbol);
+    }
+
     fn visit_meta(&mut self, location: &dyn Located, meta: MetaRef<'_>) {
         let source = match meta.source {
             Some(source) => source,
@@ -985,81 +1301,6 @@ impl CompileVisitor for Visitor {
     }
 }
 
-struct ScriptSourceLoader<'a> {
-    sources: &'a HashMap<Url, Source>,
-    base: compile::FileSourceLoader,
-}
-
-impl<'a> ScriptSourceLoader<'a> {
-    /// Construct a new source loader.
-    pub(super) fn new(sources: &'a HashMap<Url, Source>) -> Self {
-        Self {
-            sources,
-            base: compile::FileSourceLoader::new(),
-        }
-    }
-
-    /// Generate a collection of URl candidates.
-    fn candidates(root: &Path, item: &Item) -> Option<[(Url, PathBuf); 2]> {
-        let mut base = root.to_owned();
-
-        let mut it = item.iter().peekable();
-        let mut last = None;
-
-        while let Some(c) = it.next() {
-            if it.peek().is_none() {
-                last = match c {
-                    ComponentRef::Str(string) => Some(string),
-                    _ => return None,
-                };
-
-                break;
-            }
-
-            if let ComponentRef::Str(string) = c {
-                base.push(string);
-            } else {
-                return None;
-            }
-        }
-
-        let last = last?;
-
-        let mut a = base.clone();
-        a.push(&format!("{}.rn", last));
-
-        let mut b = base;
-        b.push(last);
-        b.push("mod.rn");
-
-        let a_url = crate::languageserver::url::from_file_path(&a).ok()?;
-        let b_url = crate::languageserver::url::from_file_path(&b).ok()?;
-
-        Some([(a_url, a), (b_url, b)])
-    }
-}
-
-impl<'a> crate::compile::SourceLoader for ScriptSourceLoader<'a> {
-    fn load(
-        &mut self,
-        root: &Path,
-        item: &Item,
-        span: &dyn Spanned,
-    ) -> compile::Result<crate::Source> {
-        tracing::trace!("load {} (root: {})", item, root.display());
-
-        if let Some(candidates) = Self::candidates(root, item) {
-            for (url, path) in candidates {
-                if let Some(s) = self.sources.get(&url) {
-                    return Ok(crate::Source::with_path(url, s.to_string(), path));
-                }
-            }
-        }
-
-        self.base.load(root, item, span)
-    }
-}
-
 struct WorkspaceSourceLoader<'a> {
     sources: &'a HashMap<Url, Source>,
     base: workspace::FileSourceLoader,
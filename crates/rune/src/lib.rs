@@ -208,8 +208,8 @@ mod exported_macros;
 #[macro_use]
 pub mod ast;
 
-#[cfg(all(feature = "fmt", feature = "cli"))]
-pub(crate) mod fmt;
+#[cfg(feature = "fmt")]
+pub mod fmt;
 
 cfg_emit! {
     pub use ::codespan_reporting::term::termcolor;
@@ -219,7 +219,9 @@ mod any;
 pub use self::any::Any;
 
 mod build;
-pub use self::build::{prepare, Build, BuildError};
+pub use self::build::{prepare, prepare_with, Build, BuildError};
+#[cfg(feature = "std")]
+pub use self::build::{prepare_batch, BatchBuild, BatchOutput};
 
 pub mod compile;
 #[doc(inline)]
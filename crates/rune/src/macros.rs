@@ -102,7 +102,42 @@
 //! assert_eq!(value, 42);
 //! # Ok::<_, rune::Error>(())
 //! ```
+//!
+//! Macros can also be declared in sources with the `macro` keyword. The input
+//! of a macro call is matched against the parameters of the macro, and the
+//! call expands into the body of the macro with every parameter substituted
+//! for the fragment it matched. A macro which expands into statements has to
+//! wrap them in a block, like `swap` below.
+//!
+//! ```rune
+//! macro square($e:expr) { $e * $e }
+//!
+//! macro swap($a:ident, $b:ident) {{
+//!     let tmp = $a;
+//!     $a = $b;
+//!     $b = tmp;
+//! }}
+//!
+//! pub fn main() {
+//!     let a = square!(1 + 2);
+//!     let b = 1;
+//!     swap!(a, b);
+//!     (a, b)
+//! }
+//! ```
+//!
+//! Parameters match fragments of the following kinds: `expr`, `ident`, `lit`,
+//! `path`, `pat`, `ty`, `block`, and `tt` for a single token or a delimited
+//! group of tokens. Any other token in the parameter list of a macro must be
+//! matched exactly.
+//!
+//! Macros declared in a module or a block can be called anywhere in it, and
+//! macros declared in a module can be called from other modules through their
+//! path like `crate::utils::square!(2)`. They aren't
+//! hygienic, so names used in the body of a macro refer to whatever they refer
+//! to where it's called.
 
+mod declarative;
 mod format_args;
 mod into_lit;
 mod macro_compiler;
@@ -111,6 +146,7 @@ mod quote_fn;
 mod storage;
 mod token_stream;
 
+pub(crate) use self::declarative::DeclarativeMacro;
 pub use self::format_args::FormatArgs;
 pub use self::into_lit::IntoLit;
 pub(crate) use self::macro_compiler::MacroCompiler;
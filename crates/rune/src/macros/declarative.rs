@@ -0,0 +1,279 @@
+//! Macros which are declared in sources, like:
+//!
+//! ```text
+//! macro square($e:expr) { $e * $e }
+//! ```
+
+use crate::no_std::prelude::*;
+
+use crate::ast;
+use crate::ast::Spanned;
+use crate::compile;
+use crate::macros::{MacroContext, ToTokens, TokenStream};
+use crate::parse::{Parser, Resolve, ResolveContext};
+
+/// A macro declared in a source, which expands into its body with the
+/// parameters substituted for the fragments matched in the input of a macro
+/// call.
+#[derive(Debug)]
+pub(crate) struct DeclarativeMacro {
+    /// What the input of a macro call is matched against.
+    matchers: Vec<Matcher>,
+    /// The body the macro expands into.
+    body: Vec<Expansion>,
+}
+
+impl DeclarativeMacro {
+    /// Compile the given macro declaration.
+    pub(crate) fn new(cx: ResolveContext<'_>, ast: &ast::ItemMacro) -> compile::Result<Self> {
+        let mut matchers = Vec::new();
+        let mut parameters = Vec::<&str>::new();
+        let mut it = ast.matcher.content.iter();
+
+        while let Some(token) = it.next() {
+            match token.kind {
+                K![$] => {
+                    let (name_token, kind_token) = match (it.next(), it.next(), it.next()) {
+                        (Some(name), Some(colon), Some(kind)) if colon.kind == K![:] => {
+                            (name, kind)
+                        }
+                        _ => {
+                            return Err(compile::Error::msg(
+                                token,
+                                "Expected a macro parameter like `$name:expr`",
+                            ));
+                        }
+                    };
+
+                    let Some(name) = ident(cx, name_token)? else {
+                        return Err(compile::Error::expected(name_token, "parameter name"));
+                    };
+
+                    let Some(kind) = ident(cx, kind_token)?.and_then(FragmentKind::from_name)
+                    else {
+                        return Err(compile::Error::msg(
+                            kind_token,
+                            "Expected a fragment kind, like `expr`, `ident`, `lit`, `path`, `pat`, `ty`, `block`, or `tt`",
+                        ));
+                    };
+
+                    if parameters.contains(&name) {
+                        return Err(compile::Error::msg(
+                            token.span.join(kind_token.span),
+                            format_args!("Duplicate macro parameter `${name}`"),
+                        ));
+                    }
+
+                    parameters.push(name);
+                    matchers.push(Matcher::Fragment(kind));
+                }
+                ast::Kind::Label(..)
+                | ast::Kind::Byte(..)
+                | ast::Kind::ByteStr(..)
+                | ast::Kind::Char(..)
+                | ast::Kind::Number(..)
+                | ast::Kind::Str(..) => {
+                    return Err(compile::Error::msg(
+                        token,
+                        "Literals are not supported in macro matchers",
+                    ));
+                }
+                _ => {
+                    let text = ident(cx, token)?.map(Box::from);
+                    matchers.push(Matcher::Token(token, text));
+                }
+            }
+        }
+
+        let mut body = Vec::new();
+        let mut it = ast.body.content.iter().peekable();
+
+        while let Some(token) = it.next() {
+            if token.kind == K![$] {
+                if let Some(&name) = it.peek() {
+                    let Some(text) = ident(cx, name)? else {
+                        return Err(compile::Error::expected(name, "parameter name"));
+                    };
+
+                    let Some(index) = parameters.iter().position(|p| *p == text) else {
+                        return Err(compile::Error::msg(
+                            token.span.join(name.span),
+                            format_args!("Missing macro parameter `${text}`"),
+                        ));
+                    };
+
+                    it.next();
+                    body.push(Expansion::Parameter(index));
+                    continue;
+                }
+            }
+
+            body.push(Expansion::Token(token));
+        }
+
+        Ok(Self { matchers, body })
+    }
+
+    /// Expand the macro with the given input.
+    pub(crate) fn expand(
+        &self,
+        cx: &mut MacroContext<'_, '_, '_>,
+        input: &TokenStream,
+    ) -> compile::Result<TokenStream> {
+        let mut p = Parser::from_token_stream(input, cx.input_span());
+        let mut fragments = Vec::new();
+
+        for matcher in &self.matchers {
+            match matcher {
+                Matcher::Token(expected, text) => {
+                    let actual = p.next()?;
+
+                    let matches = match text {
+                        Some(text) => ident(resolve_context!(cx.idx.q), actual)? == Some(&**text),
+                        None => actual.kind == expected.kind,
+                    };
+
+                    if !matches {
+                        return Err(compile::Error::expected(actual, expected.kind));
+                    }
+                }
+                Matcher::Fragment(kind) => {
+                    fragments.push(kind.parse(cx, &mut p)?);
+                }
+            }
+        }
+
+        p.eof()?;
+
+        let mut stream = TokenStream::new();
+
+        for expansion in &self.body {
+            match expansion {
+                Expansion::Token(token) => stream.push(*token),
+                Expansion::Parameter(index) => {
+                    stream.extend(fragments[*index].iter());
+                }
+            }
+        }
+
+        Ok(stream)
+    }
+}
+
+/// The kind of fragment matched by a macro parameter, like `expr` in
+/// `$e:expr`.
+#[derive(Debug, Clone, Copy)]
+enum FragmentKind {
+    Expr,
+    Ident,
+    Lit,
+    Path,
+    Pat,
+    Ty,
+    Block,
+    Tt,
+}
+
+impl FragmentKind {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "expr" => Self::Expr,
+            "ident" => Self::Ident,
+            "lit" => Self::Lit,
+            "path" => Self::Path,
+            "pat" => Self::Pat,
+            "ty" => Self::Ty,
+            "block" => Self::Block,
+            "tt" => Self::Tt,
+            _ => return None,
+        })
+    }
+
+    /// Parse a fragment of this kind, returning its tokens.
+    fn parse(
+        self,
+        cx: &mut MacroContext<'_, '_, '_>,
+        p: &mut Parser<'_>,
+    ) -> compile::Result<TokenStream> {
+        let mut stream = TokenStream::new();
+
+        match self {
+            Self::Expr => {
+                let expr = p.parse::<ast::Expr>()?;
+                let span = expr.span();
+
+                // NB: expressions are put in an empty group, so that they keep
+                // their precedence when substituted.
+                stream.push(ast::Token {
+                    span,
+                    kind: ast::Kind::Open(ast::Delimiter::Empty),
+                });
+                expr.to_tokens(cx, &mut stream);
+                stream.push(ast::Token {
+                    span,
+                    kind: ast::Kind::Close(ast::Delimiter::Empty),
+                });
+            }
+            Self::Ident => p.parse::<ast::Ident>()?.to_tokens(cx, &mut stream),
+            Self::Lit => p.parse::<ast::Lit>()?.to_tokens(cx, &mut stream),
+            Self::Path => p.parse::<ast::Path>()?.to_tokens(cx, &mut stream),
+            Self::Pat => p.parse::<ast::Pat>()?.to_tokens(cx, &mut stream),
+            Self::Ty => p.parse::<ast::Type>()?.to_tokens(cx, &mut stream),
+            Self::Block => p.parse::<ast::Block>()?.to_tokens(cx, &mut stream),
+            Self::Tt => {
+                let mut level = 0usize;
+
+                loop {
+                    let token = p.next()?;
+
+                    match token.kind {
+                        ast::Kind::Open(..) => level += 1,
+                        ast::Kind::Close(..) => level = level.saturating_sub(1),
+                        _ => {}
+                    }
+
+                    stream.push(token);
+
+                    if level == 0 {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(stream)
+    }
+}
+
+/// What the input of a macro call is matched against.
+#[derive(Debug)]
+enum Matcher {
+    /// A token which must be matched exactly, with the text of identifiers.
+    Token(ast::Token, Option<Box<str>>),
+    /// A fragment which is bound to a parameter, like `$e:expr`.
+    Fragment(FragmentKind),
+}
+
+/// A part of the body of a macro.
+#[derive(Debug)]
+enum Expansion {
+    /// A token which is expanded as-is.
+    Token(ast::Token),
+    /// A parameter which is substituted for the fragment it's bound to.
+    Parameter(usize),
+}
+
+/// Get the text of the given token if it's an identifier.
+fn ident(cx: ResolveContext<'_>, token: ast::Token) -> compile::Result<Option<&str>> {
+    match token.kind {
+        ast::Kind::Ident(source) => {
+            let ident = ast::Ident {
+                span: token.span,
+                source,
+            };
+
+            Ok(Some(ident.resolve(cx)?))
+        }
+        kind => Ok(kind.as_contextual_keyword()),
+    }
+}
@@ -1,13 +1,17 @@
 //! Macro compiler.
 
+use core::fmt::Write;
+
 use crate::no_std::prelude::*;
+use crate::no_std::sync::Arc;
 
 use crate::ast;
-use crate::ast::Spanned;
-use crate::compile::{self, ErrorKind, ItemMeta};
+use crate::ast::{Span, Spanned};
+use crate::compile::{self, ErrorKind, ItemId, ItemMeta};
 use crate::indexing::Indexer;
-use crate::macros::{MacroContext, ToTokens};
-use crate::parse::{Parse, Parser};
+use crate::macros::{DeclarativeMacro, MacroContext, ToTokens};
+use crate::parse::{Parse, Parser, Resolve};
+use crate::runtime::MacroHandler;
 
 use super::TokenStream;
 
@@ -33,23 +37,34 @@ impl MacroCompiler<'_, '_, '_> {
 
         let named = self.idx.q.convert_path(&macro_call.path)?;
 
-        let hash = self.idx.q.pool.item_type_hash(named.item);
+        let input_stream = &macro_call.input;
 
-        let handler = match self.idx.q.context.lookup_macro(hash) {
-            Some(handler) => handler,
-            None => {
-                return Err(compile::Error::new(
-                    span,
-                    ErrorKind::MissingMacro {
-                        item: self.idx.q.pool.item(named.item).to_owned(),
-                    },
-                ));
-            }
-        };
+        let declarative_macro = self.declarative_macro(&macro_call.path, named.item)?;
 
-        let input_stream = &macro_call.input;
+        let token_stream = if let Some(declarative_macro) = declarative_macro {
+            let mut macro_context = MacroContext {
+                macro_span: span,
+                input_span: macro_call.input_span(),
+                item_meta: self.item_meta,
+                idx: self.idx,
+            };
+
+            declarative_macro.expand(&mut macro_context, input_stream)?
+        } else {
+            let hash = self.idx.q.pool.item_type_hash(named.item);
+
+            let handler = match self.idx.q.context.lookup_macro(hash) {
+                Some(handler) => handler,
+                None => {
+                    return Err(compile::Error::new(
+                        span,
+                        ErrorKind::MissingMacro {
+                            item: self.idx.q.pool.item(named.item).to_owned(),
+                        },
+                    ));
+                }
+            };
 
-        let token_stream = {
             let mut macro_context = MacroContext {
                 macro_span: span,
                 input_span: macro_call.input_span(),
@@ -60,11 +75,83 @@ impl MacroCompiler<'_, '_, '_> {
             handler(&mut macro_context, input_stream)?
         };
 
-        let mut parser = Parser::from_token_stream(&token_stream, span);
-        let output = parser.parse::<T>()?;
-        parser.eof()?;
+        self.parse_expansion(&token_stream, span)
+    }
+
+    /// Look up a macro declared in a source.
+    ///
+    /// A macro called by name can be declared in the item the call is in, or
+    /// in any item containing it.
+    fn declarative_macro(
+        &mut self,
+        path: &ast::Path,
+        named: ItemId,
+    ) -> compile::Result<Option<Arc<DeclarativeMacro>>> {
+        if let Some(ident) = path.try_as_ident() {
+            let name = ident.resolve(resolve_context!(self.idx.q))?;
+            let mut base = self.idx.q.pool.item(self.item_meta.item).to_owned();
+
+            loop {
+                base.push(name);
+                let item = self.idx.q.pool.alloc_item(&base);
+
+                if let Some(declarative_macro) = self.idx.q.declarative_macro(item) {
+                    return Ok(Some(declarative_macro));
+                }
+
+                base.pop();
+
+                if base.pop().is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(self.idx.q.declarative_macro(named))
+    }
+
+    /// Compile a number literal with a suffix registered in the context into
+    /// the expression produced by its handler.
+    pub(crate) fn eval_literal_suffix(
+        &mut self,
+        lit: &ast::LitNumber,
+        text: ast::NumberText,
+        handler: &MacroHandler,
+    ) -> compile::Result<ast::Expr> {
+        let span = lit.span();
+
+        if !self.idx.q.options.macros {
+            return Err(compile::Error::msg(
+                span,
+                "literal suffixes must be enabled with `-O macros=true`",
+            ));
+        }
+
+        // NB: the handler is passed the literal without its suffix.
+        let input_span = Span::new(span.start, text.number.end);
+
+        let mut input_stream = TokenStream::new();
+
+        input_stream.push(ast::Token {
+            span: input_span,
+            kind: ast::Kind::Number(ast::NumberSource::Text(ast::NumberText {
+                suffix: Span::new(text.number.end, text.number.end),
+                ..text
+            })),
+        });
+
+        let token_stream = {
+            let mut macro_context = MacroContext {
+                macro_span: span,
+                input_span,
+                item_meta: self.item_meta,
+                idx: self.idx,
+            };
+
+            handler(&mut macro_context, &input_stream)?
+        };
 
-        Ok(output)
+        self.parse_expansion(&token_stream, span)
     }
 
     /// Compile the given macro into the given output type.
@@ -109,8 +196,48 @@ impl MacroCompiler<'_, '_, '_> {
             handler(&mut macro_context, input_stream, &item_stream)?
         };
 
-        let mut parser = Parser::from_token_stream(&token_stream, span);
+        self.parse_expansion(&token_stream, span).map(Some)
+    }
+
+    /// Parse the token stream which the macro called at `span` expanded into.
+    ///
+    /// Tokens synthesized by the macro, like the ones produced by `quote!`,
+    /// all span the macro call. So if the expansion can't be parsed, it's
+    /// stringified into a source linked to the macro call and parsed again,
+    /// which reports the error in the expansion as well as at the call.
+    fn parse_expansion<T>(&mut self, token_stream: &TokenStream, span: Span) -> compile::Result<T>
+    where
+        T: Parse,
+    {
+        let mut parser = Parser::from_token_stream(token_stream, span);
+
+        let error = match parser.parse_all::<T>() {
+            Ok(output) => return Ok(output),
+            Err(error) => error,
+        };
 
-        parser.parse_all().map(Some)
+        let mut macro_context = MacroContext {
+            macro_span: span,
+            input_span: span,
+            item_meta: self.item_meta,
+            idx: self.idx,
+        };
+
+        let mut expansion = String::new();
+
+        // NB: marker tokens can't be stringified, in which case the error in
+        // the token stream is the best we can do.
+        if write!(expansion, "{}", macro_context.stringify(token_stream)).is_err() {
+            return Err(error);
+        }
+
+        let id = macro_context.insert_source("macro expansion", &expansion);
+
+        match macro_context.parse_source::<T>(id) {
+            Err(error) => Err(error),
+            // NB: the stringified expansion might parse even though the
+            // tokens didn't, like if they were separated by whitespace.
+            Ok(..) => Err(error),
+        }
     }
 }
@@ -6,8 +6,8 @@ use crate::ast;
 use crate::ast::Span;
 use crate::compile::ir;
 use crate::compile::{
-    self, Context, ErrorKind, Item, ItemMeta, NoopCompileVisitor, NoopSourceLoader, Pool, Prelude,
-    UnitBuilder,
+    self, Context, ErrorKind, Item, ItemMeta, Location, NoopCompilePass, NoopCompileVisitor,
+    NoopSourceLoader, Pool, Prelude, UnitBuilder,
 };
 use crate::hir;
 use crate::indexing::{IndexItem, Indexer, Items, Scopes};
@@ -52,6 +52,7 @@ impl<'a, 'b, 'arena> MacroContext<'a, 'b, 'arena> {
         let mut sources = Sources::default();
         let mut pool = Pool::default();
         let mut visitor = NoopCompileVisitor::new();
+        let mut passes = NoopCompilePass::new();
         let mut diagnostics = Diagnostics::default();
         let mut source_loader = NoopSourceLoader::default();
         let options = Options::default();
@@ -67,6 +68,7 @@ impl<'a, 'b, 'arena> MacroContext<'a, 'b, 'arena> {
             &mut sources,
             &mut pool,
             &mut visitor,
+            &mut passes,
             &mut diagnostics,
             &mut source_loader,
             &options,
@@ -226,18 +228,44 @@ impl<'a, 'b, 'arena> MacroContext<'a, 'b, 'arena> {
             ast::LitSource::Text(source_id) => self.idx.q.sources.source(source_id, span),
             ast::LitSource::Synthetic(id) => self.idx.q.storage.get_string(id),
             ast::LitSource::BuiltIn(builtin) => Some(builtin.as_str()),
+            ast::LitSource::Keyword(keyword) => Some(keyword),
         }
     }
 
+    /// Access the source text of the given span, if it's part of the input of
+    /// the macro.
+    ///
+    /// Returns `None` for spans outside of the input, like the spans of tokens
+    /// which were produced by another macro.
+    pub(crate) fn input_source(&self, span: Span) -> Option<&str> {
+        if span.start < self.input_span.start || span.end > self.input_span.end {
+            return None;
+        }
+
+        self.idx
+            .q
+            .sources
+            .source(self.item_meta.location.source_id, span)
+    }
+
     /// Insert the given source so that it has a [SourceId] that can be used in
     /// combination with parsing functions such as
     /// [parse_source][MacroContext::parse_source].
+    ///
+    /// The source is marked as expanded from the current macro call, so that
+    /// diagnostics for it also point out the macro call. See
+    /// [Source::expanded_from].
     pub fn insert_source(&mut self, name: &str, source: &str) -> SourceId {
-        self.idx.q.sources.insert(Source::new(name, source))
+        let location = Location::new(self.item_meta.location.source_id, self.macro_span);
+        let source = Source::new(name, source).with_expanded_from(location);
+        self.idx.q.sources.insert(source)
     }
 
     /// Parse the given input as the given type that implements
     /// [Parse][crate::parse::Parse].
+    ///
+    /// Errors are reported at the current macro call, pointing out where in
+    /// the source they were raised.
     pub fn parse_source<T>(&self, id: SourceId) -> compile::Result<T>
     where
         T: Parse,
@@ -247,6 +275,7 @@ impl<'a, 'b, 'arena> MacroContext<'a, 'b, 'arena> {
         })?;
 
         crate::parse::parse_all(source.as_str(), id, false)
+            .map_err(|error| error.in_macro_expansion(id, self.macro_span))
     }
 
     /// The span of the macro call including the name of the macro.
@@ -24,6 +24,8 @@ pub(crate) use self::function_meta::{AssociatedFunctionName, ToFieldFunction, To
 
 #[doc(hidden)]
 pub use self::function_meta::{FunctionMetaData, FunctionMetaKind, MacroMetaData, MacroMetaKind};
+#[cfg(feature = "std")]
+pub use self::function_traits::FallibleFunction;
 pub use self::function_traits::{Async, Function, FunctionKind, InstanceFunction, Plain};
 #[doc(hidden)]
 pub use self::module::{Module, ModuleMeta, ModuleMetaData};
@@ -192,8 +194,8 @@ pub(crate) struct ModuleFunction {
     pub(crate) is_async: bool,
     #[cfg(feature = "doc")]
     pub(crate) deprecated: Option<Box<str>>,
-    #[cfg(feature = "doc")]
     pub(crate) args: Option<usize>,
+    pub(crate) argument_names: &'static [&'static str],
     #[cfg(feature = "doc")]
     pub(crate) return_type: Option<FullTypeOf>,
     #[cfg(feature = "doc")]
@@ -211,8 +213,8 @@ pub(crate) struct ModuleAssociated {
     pub(crate) is_async: bool,
     #[cfg(feature = "doc")]
     pub(crate) deprecated: Option<Box<str>>,
-    #[cfg(feature = "doc")]
     pub(crate) args: Option<usize>,
+    pub(crate) argument_names: &'static [&'static str],
     #[cfg(feature = "doc")]
     pub(crate) return_type: Option<FullTypeOf>,
     #[cfg(feature = "doc")]
@@ -234,6 +236,12 @@ pub(crate) struct ModuleAttributeMacro {
     pub(crate) docs: Docs,
 }
 
+/// Handle to a literal suffix inserted into a module.
+pub(crate) struct ModuleLiteralSuffix {
+    pub(crate) suffix: Box<str>,
+    pub(crate) handler: Arc<MacroHandler>,
+}
+
 /// A constant registered in a module.
 pub(crate) struct ModuleConstant {
     pub(crate) item: ItemBuf,
@@ -241,6 +249,12 @@ pub(crate) struct ModuleConstant {
     pub(crate) docs: Docs,
 }
 
+/// An alias of an existing item registered in a module.
+pub(crate) struct ModuleAlias {
+    pub(crate) item: ItemBuf,
+    pub(crate) target: ItemBuf,
+}
+
 /// Handle to a an item inserted into a module which allows for mutation of item
 /// metadata.
 pub struct ItemMut<'a> {
@@ -281,6 +295,7 @@ impl fmt::Debug for ItemMut<'_> {
 ///
 /// This is returned by methods which insert meta items, such as:
 /// * [`Module::raw_fn`].
+/// * [`Module::variadic_function`].
 /// * [`Module::function`].
 /// * [`Module::associated_function`].
 ///
@@ -295,7 +310,6 @@ pub struct ItemFnMut<'a> {
     is_async: &'a mut bool,
     #[cfg(feature = "doc")]
     deprecated: &'a mut Option<Box<str>>,
-    #[cfg(feature = "doc")]
     args: &'a mut Option<usize>,
     #[cfg(feature = "doc")]
     return_type: &'a mut Option<FullTypeOf>,
@@ -342,13 +356,32 @@ impl ItemFnMut<'_> {
         self
     }
 
+    /// Hide the function from generated documentation.
+    ///
+    /// This is intended for functions which are only called by the expansion
+    /// of macros, and which aren't meant to be called directly.
+    pub fn hidden(self) -> Self {
+        self.docs.set_hidden();
+        self
+    }
+
     /// Indicate the number of arguments this function accepts.
-    pub fn args(self, #[cfg_attr(not(feature = "doc"), allow(unused))] args: usize) -> Self {
-        #[cfg(feature = "doc")]
-        {
-            *self.args = Some(args);
-        }
+    pub fn args(self, args: usize) -> Self {
+        *self.args = Some(args);
+        self
+    }
 
+    /// Set the names of the arguments this function accepts, which are used
+    /// when documenting the function and when showing its signature in the
+    /// language server.
+    ///
+    /// This is captured automatically by [`Module::function_meta`].
+    pub fn argument_names<I>(self, names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.docs.set_arguments(names);
         self
     }
 
@@ -7,6 +7,8 @@ use crate::no_std::sync::Arc;
 use crate::compile::{self, meta, IntoComponent, ItemBuf, Named};
 use crate::hash::Hash;
 use crate::macros::{MacroContext, TokenStream};
+#[cfg(feature = "std")]
+use crate::module::FallibleFunction;
 use crate::module::{AssociatedKey, Function, FunctionKind, InstanceFunction};
 use crate::runtime::{
     AttributeMacroHandler, FullTypeOf, FunctionHandler, MacroHandler, MaybeTypeOf, Protocol,
@@ -55,8 +57,8 @@ pub struct FunctionData {
     pub(crate) is_async: bool,
     #[cfg(feature = "doc")]
     pub(crate) deprecated: Option<Box<str>>,
-    #[cfg(feature = "doc")]
     pub(crate) args: Option<usize>,
+    pub(crate) argument_names: &'static [&'static str],
     #[cfg(feature = "doc")]
     pub(crate) return_type: Option<FullTypeOf>,
     #[cfg(feature = "doc")]
@@ -81,8 +83,34 @@ impl FunctionData {
             is_async: K::is_async(),
             #[cfg(feature = "doc")]
             deprecated: None,
+            args: Some(F::args()),
+            argument_names: &[],
+            #[cfg(feature = "doc")]
+            return_type: F::Return::maybe_type_of(),
+            #[cfg(feature = "doc")]
+            argument_types: A::into_box(),
+        }
+    }
+
+    #[inline]
+    #[cfg(feature = "std")]
+    pub(crate) fn fallible<F, A, N>(name: N, f: F) -> Self
+    where
+        F: FallibleFunction<A>,
+        F::Return: MaybeTypeOf,
+        N: IntoIterator,
+        N::Item: IntoComponent,
+        A: FunctionArgs,
+    {
+        Self {
+            item: ItemBuf::with_item(name),
+            handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
+            #[cfg(feature = "doc")]
+            is_async: false,
             #[cfg(feature = "doc")]
+            deprecated: None,
             args: Some(F::args()),
+            argument_names: &[],
             #[cfg(feature = "doc")]
             return_type: F::Return::maybe_type_of(),
             #[cfg(feature = "doc")]
@@ -217,8 +245,8 @@ pub struct AssociatedFunctionData {
     pub(crate) is_async: bool,
     #[cfg(feature = "doc")]
     pub(crate) deprecated: Option<Box<str>>,
-    #[cfg(feature = "doc")]
     pub(crate) args: Option<usize>,
+    pub(crate) argument_names: &'static [&'static str],
     #[cfg(feature = "doc")]
     pub(crate) return_type: Option<FullTypeOf>,
     #[cfg(feature = "doc")]
@@ -243,8 +271,8 @@ impl AssociatedFunctionData {
             is_async: K::is_async(),
             #[cfg(feature = "doc")]
             deprecated: None,
-            #[cfg(feature = "doc")]
             args: Some(F::args()),
+            argument_names: &[],
             #[cfg(feature = "doc")]
             return_type: F::Return::maybe_type_of(),
             #[cfg(feature = "doc")]
@@ -346,8 +374,8 @@ where
             is_async: K::is_async(),
             #[cfg(feature = "doc")]
             deprecated: None,
-            #[cfg(feature = "doc")]
             args: Some(F::args()),
+            argument_names: &[],
             #[cfg(feature = "doc")]
             return_type: F::Return::maybe_type_of(),
             #[cfg(feature = "doc")]
@@ -22,9 +22,7 @@ macro_rules! drain_stack {
         let [$($var,)*] = vm_try!($stack.drain_vec($count + $add));
 
         $(
-            let $var = vm_try!($from_fn($var).with_error(|| VmErrorKind::BadArgument {
-                arg: $num,
-            }));
+            let $var = vm_try!($from_fn($var).with_argument($num));
         )*
     };
 }
@@ -74,6 +72,23 @@ pub trait Function<A, K>: 'static + Send + Sync {
     fn fn_call(&self, stack: &mut Stack, args: usize) -> VmResult<()>;
 }
 
+/// Trait used to provide the
+/// [fallible_function][crate::module::Module::fallible_function] function.
+#[cfg(feature = "std")]
+pub trait FallibleFunction<A>: 'static + Send + Sync {
+    /// The return type of the function.
+    #[doc(hidden)]
+    type Return;
+
+    /// Get the number of arguments.
+    #[doc(hidden)]
+    fn args() -> usize;
+
+    /// Perform the vm call.
+    #[doc(hidden)]
+    fn fn_call(&self, stack: &mut Stack, args: usize) -> VmResult<()>;
+}
+
 /// Trait used to provide the [`associated_function`] function.
 ///
 /// [`associated_function`]: crate::module::Module::associated_function
@@ -285,6 +300,33 @@ macro_rules! impl_function_traits {
                 VmResult::Ok(())
             }
         }
+
+        #[cfg(feature = "std")]
+        impl<T, U, E, $($ty,)*> FallibleFunction<($($place,)*)> for T
+        where
+            T: 'static + Send + Sync + Fn($($($mut)* $ty),*) -> Result<U, E>,
+            U: ToValue,
+            E: 'static + std::error::Error + Send + Sync,
+            $($ty: $($trait)*,)*
+        {
+            type Return = Result<U, runtime::NativeError>;
+
+            fn args() -> usize {
+                $count
+            }
+
+            #[allow(clippy::drop_non_drop)]
+            fn fn_call(&self, stack: &mut Stack, args: usize) -> VmResult<()> {
+                drain_stack!($count, 0, stack, args, $($from_fn, $var, $num,)*);
+
+                let ret = self($($var.0),*);
+                $(drop($var.1);)*
+
+                let ret = vm_try!(ToValue::to_value(ret.map_err(runtime::NativeError::new)));
+                stack.push(ret);
+                VmResult::Ok(())
+            }
+        }
     };
 }
 
@@ -4,21 +4,23 @@ use crate::no_std::collections::{HashMap, HashSet};
 use crate::no_std::prelude::*;
 use crate::no_std::sync::Arc;
 
-use crate::compile::{self, meta, ContextError, Docs, IntoComponent, ItemBuf, Named};
+use crate::compile::{self, meta, ContextError, Docs, IntoComponent, Item, ItemBuf, Named};
 use crate::macros::{MacroContext, TokenStream};
 use crate::module::function_meta::{
     AssociatedFunctionData, AssociatedFunctionName, FunctionArgs, FunctionData, FunctionMeta,
     FunctionMetaKind, MacroMeta, MacroMetaKind, ToFieldFunction, ToInstance,
 };
+#[cfg(feature = "std")]
+use crate::module::FallibleFunction;
 use crate::module::{
     AssociatedKey, Async, EnumMut, Function, FunctionKind, InstallWith, InstanceFunction,
-    InternalEnum, InternalEnumMut, ItemFnMut, ItemMut, ModuleAssociated, ModuleAttributeMacro,
-    ModuleConstant, ModuleFunction, ModuleMacro, ModuleType, Plain, TypeMut, TypeSpecification,
-    VariantMut,
+    InternalEnum, InternalEnumMut, ItemFnMut, ItemMut, ModuleAlias, ModuleAssociated,
+    ModuleAttributeMacro, ModuleConstant, ModuleFunction, ModuleLiteralSuffix, ModuleMacro,
+    ModuleType, Plain, TypeMut, TypeSpecification, VariantMut,
 };
 use crate::runtime::{
     AttributeMacroHandler, ConstValue, FromValue, GeneratorState, MacroHandler, MaybeTypeOf,
-    Protocol, Stack, ToValue, TypeCheck, TypeOf, Value, VmResult,
+    Protocol, Stack, StackError, ToValue, TypeCheck, TypeOf, Value, VmResult,
 };
 use crate::Hash;
 
@@ -62,6 +64,8 @@ pub struct Module {
     names: HashSet<Name>,
     /// A special identifier for this module, which will cause it to not conflict if installed multiple times.
     pub(crate) unique: Option<&'static str>,
+    /// The version of the module, used when reporting conflicts.
+    pub(crate) version: Option<Box<str>>,
     /// The name of the module.
     pub(crate) item: ItemBuf,
     /// Functions.
@@ -70,8 +74,12 @@ pub struct Module {
     pub(crate) macros: Vec<ModuleMacro>,
     /// AttributeMacroHandler handlers.
     pub(crate) attribute_macros: Vec<ModuleAttributeMacro>,
+    /// Handlers for suffixed literals.
+    pub(crate) literal_suffixes: Vec<ModuleLiteralSuffix>,
     /// Constant values.
     pub(crate) constants: Vec<ModuleConstant>,
+    /// Aliases of existing items.
+    pub(crate) aliases: Vec<ModuleAlias>,
     /// Associated items.
     pub(crate) associated: Vec<ModuleAssociated>,
     /// Registered types.
@@ -99,6 +107,28 @@ impl Module {
         }
     }
 
+    /// Modify the current module to carry the given version.
+    ///
+    /// The version is informational, and is used to tell modules apart when
+    /// reporting conflicts during [Context::install].
+    ///
+    /// [Context::install]: crate::compile::Context::install
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Module;
+    ///
+    /// let m = Module::with_crate("http").with_version("1.2.0");
+    /// assert_eq!(m.version(), Some("1.2.0"));
+    /// ```
+    pub fn with_version(self, version: &str) -> Self {
+        Self {
+            version: Some(version.into()),
+            ..self
+        }
+    }
+
     /// Construct a new module for the given item.
     pub fn with_item<I>(iter: I) -> Self
     where
@@ -134,19 +164,34 @@ impl Module {
         Self {
             names: HashSet::new(),
             unique: None,
+            version: None,
             item,
             functions: Vec::new(),
             macros: Vec::new(),
             attribute_macros: Vec::new(),
+            literal_suffixes: Vec::new(),
             associated: Vec::new(),
             types: Vec::new(),
             types_hash: HashMap::new(),
             internal_enums: Vec::new(),
             constants: Vec::new(),
+            aliases: Vec::new(),
             docs: Docs::EMPTY,
         }
     }
 
+    /// The item this module is installed under, which acts as the namespace of
+    /// everything declared in it.
+    pub fn item(&self) -> &Item {
+        &self.item
+    }
+
+    /// The version of the module, if one has been set through
+    /// [Module::with_version].
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
     /// Mutate item-level properties for this module.
     pub fn item_mut(&mut self) -> ItemMut<'_> {
         ItemMut {
@@ -514,6 +559,70 @@ impl Module {
         Ok(ItemMut { docs: &mut c.docs })
     }
 
+    /// Register an alias of an existing item, which makes it available to
+    /// scripts under the given name without registering it again.
+    ///
+    /// The `target` is the full path of the aliased item, which can be a type,
+    /// function, constant or module. It's resolved when the module is
+    /// installed into a [Context][crate::compile::Context], at which point it
+    /// has to exist. Aliasing a type or a module also aliases every item inside
+    /// of it, so associated functions can be called through the alias.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use rune::compile::ItemBuf;
+    /// use rune::{Context, Module, Vm};
+    ///
+    /// let mut internal = Module::with_crate_item("internal", ["math"]);
+    /// internal.function(["add"], |a: i64, b: i64| a + b)?;
+    /// internal.constant(["ZERO"], 0i64)?;
+    ///
+    /// let mut engine = Module::with_crate("engine");
+    /// engine.alias(["add"], "::internal::math::add".parse::<ItemBuf>()?)?;
+    /// engine.alias(["math"], "::internal::math".parse::<ItemBuf>()?)?;
+    ///
+    /// let mut context = Context::new();
+    /// context.install(internal)?;
+    /// context.install(engine)?;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             engine::add(1, 2) + engine::math::ZERO
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    /// let output: i64 = rune::from_value(vm.call(["main"], ())?)?;
+    /// assert_eq!(output, 3);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn alias<N, T>(&mut self, name: N, target: T) -> Result<(), ContextError>
+    where
+        N: IntoIterator,
+        N::Item: IntoComponent,
+        T: AsRef<Item>,
+    {
+        let item = ItemBuf::with_item(name);
+        let hash = Hash::type_hash(&item);
+
+        if !self.names.insert(Name::Item(hash)) {
+            return Err(ContextError::ConflictingAlias { item });
+        }
+
+        self.aliases.push(ModuleAlias {
+            item,
+            target: target.as_ref().to_owned(),
+        });
+
+        Ok(())
+    }
+
     /// Register a native macro handler through its meta.
     ///
     /// The metadata must be provided by annotating the function with
@@ -717,6 +826,88 @@ impl Module {
         Ok(ItemMut { docs: &mut m.docs })
     }
 
+    /// Register a handler for number literals with the given `suffix`, like
+    /// `px` in `10px`.
+    ///
+    /// Like a macro, the handler is called at compile time with a token
+    /// stream containing the literal without its suffix, and the tokens it
+    /// produces are parsed as the expression which replaces the literal.
+    /// Errors raised by the handler are reported as diagnostics on the
+    /// literal.
+    ///
+    /// Suffixes are global, so they're not affected by which module they're
+    /// declared in or what's imported where they're used. The suffix has to
+    /// be an identifier which isn't one of the built-in suffixes `u8`, `i64`
+    /// and `f64`. Note that suffixes which start with a hex digit like `deg`
+    /// can't be used on hex literals, since the digit is parsed as part of the
+    /// number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Module, Vm};
+    /// use rune::ast;
+    /// use rune::compile;
+    /// use rune::macros::{quote, MacroContext, TokenStream};
+    /// use rune::parse::Parser;
+    /// use std::sync::Arc;
+    ///
+    /// fn ms(cx: &mut MacroContext<'_, '_, '_>, stream: &TokenStream) -> compile::Result<TokenStream> {
+    ///     let mut p = Parser::from_token_stream(stream, cx.input_span());
+    ///     let lit = p.parse_all::<ast::LitNumber>()?;
+    ///     Ok(quote!(#lit / 1000.0).into_token_stream(cx))
+    /// }
+    ///
+    /// let mut m = Module::new();
+    /// m.literal_suffix("ms", ms)?;
+    ///
+    /// let mut context = Context::with_default_modules()?;
+    /// context.install(m)?;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             1500.0ms
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    ///
+    /// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    /// let output: f64 = rune::from_value(vm.call(["main"], ())?)?;
+    /// assert_eq!(output, 1.5);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn literal_suffix<M>(&mut self, suffix: &str, f: M) -> Result<(), ContextError>
+    where
+        M: 'static
+            + Send
+            + Sync
+            + Fn(&mut MacroContext<'_, '_, '_>, &TokenStream) -> compile::Result<TokenStream>,
+    {
+        if !is_literal_suffix(suffix) {
+            return Err(ContextError::InvalidLiteralSuffix {
+                suffix: suffix.into(),
+            });
+        }
+
+        if self.literal_suffixes.iter().any(|s| &*s.suffix == suffix) {
+            return Err(ContextError::ConflictingLiteralSuffix {
+                suffix: suffix.into(),
+            });
+        }
+
+        let handler: Arc<MacroHandler> = Arc::new(f);
+
+        self.literal_suffixes.push(ModuleLiteralSuffix {
+            suffix: suffix.into(),
+            handler,
+        });
+
+        Ok(())
+    }
+
     /// Register a function handler through its meta.
     ///
     /// The metadata must be provided by annotating the function with
@@ -793,13 +984,15 @@ impl Module {
         let meta = meta();
 
         match meta.kind {
-            FunctionMetaKind::Function(data) => {
+            FunctionMetaKind::Function(mut data) => {
+                data.argument_names = meta.arguments;
                 let mut docs = Docs::EMPTY;
                 docs.set_docs(meta.docs);
                 docs.set_arguments(meta.arguments);
                 self.function_inner(data, docs)
             }
-            FunctionMetaKind::AssociatedFunction(data) => {
+            FunctionMetaKind::AssociatedFunction(mut data) => {
+                data.argument_names = meta.arguments;
                 let mut docs = Docs::EMPTY;
                 docs.set_docs(meta.docs);
                 docs.set_arguments(meta.arguments);
@@ -861,6 +1054,44 @@ impl Module {
         self.function_inner(FunctionData::new(name, f), Docs::EMPTY)
     }
 
+    /// Register a function which returns a [`Result`] with an arbitrary Rust
+    /// error type.
+    ///
+    /// Any error returned by the function is converted into a
+    /// [`NativeError`], which scripts see as a `std::error::Error` exposing
+    /// the message of the error and its chain of sources. The original error
+    /// can be recovered by the host through [`NativeError::downcast`].
+    ///
+    /// [`NativeError`]: crate::runtime::NativeError
+    /// [`NativeError::downcast`]: crate::runtime::NativeError::downcast
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Module;
+    ///
+    /// let mut module = Module::default();
+    ///
+    /// module.fallible_function(["read_to_string"], |path: &str| std::fs::read_to_string(path))?
+    ///     .docs(["Read the contents of a file."]);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn fallible_function<F, A, N>(
+        &mut self,
+        name: N,
+        f: F,
+    ) -> Result<ItemFnMut<'_>, ContextError>
+    where
+        F: FallibleFunction<A>,
+        F::Return: MaybeTypeOf,
+        N: IntoIterator,
+        N::Item: IntoComponent,
+        A: FunctionArgs,
+    {
+        self.function_inner(FunctionData::fallible(name, f), Docs::EMPTY)
+    }
+
     /// See [`Module::function`].
     #[deprecated = "Use Module::function() instead"]
     pub fn async_function<F, A, N>(&mut self, name: N, f: F) -> Result<ItemFnMut<'_>, ContextError>
@@ -1224,8 +1455,8 @@ impl Module {
             is_async: false,
             #[cfg(feature = "doc")]
             deprecated: None,
-            #[cfg(feature = "doc")]
             args: None,
+            argument_names: &[],
             #[cfg(feature = "doc")]
             return_type: None,
             #[cfg(feature = "doc")]
@@ -1241,7 +1472,6 @@ impl Module {
             is_async: &mut last.is_async,
             #[cfg(feature = "doc")]
             deprecated: &mut last.deprecated,
-            #[cfg(feature = "doc")]
             args: &mut last.args,
             #[cfg(feature = "doc")]
             return_type: &mut last.return_type,
@@ -1250,6 +1480,98 @@ impl Module {
         })
     }
 
+    /// Register a variadic function which receives all of its arguments as a
+    /// slice of values.
+    ///
+    /// Unlike [`Module::function`], the function is not registered with a
+    /// fixed number of arguments. Instead it accepts however many arguments it
+    /// is called with, which is determined by the `args` operand of the call
+    /// instruction at the call site. The arguments are popped off the stack in
+    /// the order they were passed before the function is called, and the value
+    /// it returns is pushed in their place.
+    ///
+    /// Variadic functions are linked like any other function, so calling one
+    /// which isn't installed in the context is still reported by the linker.
+    /// Since they accept any number of arguments, no arity checks are
+    /// performed, which makes validating the arguments the responsibility of
+    /// the function.
+    ///
+    /// If the function needs to interact with the stack directly, use
+    /// [`Module::raw_fn`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Module, Value, Vm};
+    /// use rune::runtime::VmResult;
+    /// use rune::vm_try;
+    /// use std::sync::Arc;
+    ///
+    /// fn sum(args: &[Value]) -> VmResult<i64> {
+    ///     let mut number = 0;
+    ///
+    ///     for value in args {
+    ///         number += vm_try!(value.as_integer());
+    ///     }
+    ///
+    ///     VmResult::Ok(number)
+    /// }
+    ///
+    /// let mut module = Module::default();
+    ///
+    /// module.variadic_function(["sum"], sum)?.docs([
+    ///     "Sum all numbers provided to the function."
+    /// ]);
+    ///
+    /// let mut context = Context::new();
+    /// context.install(module)?;
+    /// let runtime = Arc::new(context.runtime());
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             (sum(), sum(1), sum(1, 2, 3))
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let mut vm = Vm::new(runtime, Arc::new(unit));
+    ///
+    /// let output: (i64, i64, i64) = rune::from_value(vm.call(["main"], ())?)?;
+    /// assert_eq!(output, (0, 1, 6));
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn variadic_function<F, R, N>(
+        &mut self,
+        name: N,
+        f: F,
+    ) -> Result<ItemFnMut<'_>, ContextError>
+    where
+        F: 'static + Fn(&[Value]) -> R + Send + Sync,
+        R: ToValue + MaybeTypeOf,
+        N: IntoIterator,
+        N::Item: IntoComponent,
+    {
+        let item = self.raw_fn(name, move |stack, args| {
+            let start = match stack.len().checked_sub(args) {
+                Some(start) if start >= stack.stack_bottom() => start,
+                _ => return VmResult::err(StackError),
+            };
+
+            let Some(values) = stack.get(start..) else {
+                return VmResult::err(StackError);
+            };
+
+            let value = vm_try!(f(values).to_value());
+            vm_try!(stack.popn(args));
+            stack.push(value);
+            VmResult::Ok(())
+        })?;
+
+        Ok(item.return_type::<R>())
+    }
+
     fn function_inner(
         &mut self,
         data: FunctionData,
@@ -1271,8 +1593,8 @@ impl Module {
             is_async: data.is_async,
             #[cfg(feature = "doc")]
             deprecated: data.deprecated,
-            #[cfg(feature = "doc")]
             args: data.args,
+            argument_names: data.argument_names,
             #[cfg(feature = "doc")]
             return_type: data.return_type,
             #[cfg(feature = "doc")]
@@ -1288,7 +1610,6 @@ impl Module {
             is_async: &mut last.is_async,
             #[cfg(feature = "doc")]
             deprecated: &mut last.deprecated,
-            #[cfg(feature = "doc")]
             args: &mut last.args,
             #[cfg(feature = "doc")]
             return_type: &mut last.return_type,
@@ -1341,8 +1662,8 @@ impl Module {
             is_async: data.is_async,
             #[cfg(feature = "doc")]
             deprecated: data.deprecated,
-            #[cfg(feature = "doc")]
             args: data.args,
+            argument_names: data.argument_names,
             #[cfg(feature = "doc")]
             return_type: data.return_type,
             #[cfg(feature = "doc")]
@@ -1358,7 +1679,6 @@ impl Module {
             is_async: &mut last.is_async,
             #[cfg(feature = "doc")]
             deprecated: &mut last.deprecated,
-            #[cfg(feature = "doc")]
             args: &mut last.args,
             #[cfg(feature = "doc")]
             return_type: &mut last.return_type,
@@ -1374,3 +1694,17 @@ impl AsRef<Module> for Module {
         self
     }
 }
+
+/// Test if the given string can be used as a literal suffix, which is the
+/// case for identifiers which aren't built-in suffixes.
+fn is_literal_suffix(suffix: &str) -> bool {
+    let mut chars = suffix.chars();
+
+    let Some(first) = chars.next() else {
+        return false;
+    };
+
+    first.is_alphabetic()
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+        && !matches!(suffix, "u8" | "i64" | "f64")
+}
@@ -14,24 +14,34 @@ pub mod collections;
 pub mod core;
 #[cfg(feature = "disable-io")]
 pub mod disable_io;
+#[cfg(feature = "std")]
+pub mod env;
+#[cfg(feature = "std")]
+pub mod error;
 pub mod f64;
 pub mod fmt;
 pub mod future;
 pub mod generator;
 pub mod hash;
+pub mod i18n;
 pub mod i64;
 #[cfg(feature = "std")]
 pub mod io;
 pub mod iter;
 pub mod macros;
+#[cfg(feature = "std")]
+pub mod math;
 pub mod mem;
 pub mod num;
 pub mod object;
 pub mod ops;
 pub mod option;
 pub mod result;
+pub mod signal;
 pub mod stream;
 pub mod string;
 pub mod test;
 pub mod tuple;
+#[cfg(feature = "uuid")]
+pub mod uuid;
 pub mod vec;
@@ -1,5 +1,6 @@
 //! `std::collections` module.
 
+mod btree_map;
 #[cfg(feature = "std")]
 mod hash_map;
 #[cfg(feature = "std")]
@@ -8,6 +9,7 @@ mod vec_deque;
 
 use crate::{ContextError, Module};
 
+pub(crate) use self::btree_map::BTreeMap;
 #[cfg(feature = "std")]
 pub(crate) use self::hash_map::HashMap;
 #[cfg(feature = "std")]
@@ -19,6 +21,7 @@ use crate as rune;
 /// The `std::collections` module.
 pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::from_meta(self::module_meta);
+    btree_map::setup(&mut module)?;
     #[cfg(feature = "std")]
     hash_map::setup(&mut module)?;
     #[cfg(feature = "std")]
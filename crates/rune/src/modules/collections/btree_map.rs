@@ -0,0 +1,677 @@
+use core::fmt::{self, Write};
+
+use crate::no_std::collections;
+use crate::no_std::prelude::*;
+
+use crate as rune;
+use crate::runtime::{
+    EnvProtocolCaller, Formatter, FromValue, Iterator, Key, ProtocolCaller, Value, VmErrorKind,
+    VmResult,
+};
+use crate::{Any, ContextError, Module};
+
+pub(super) fn setup(module: &mut Module) -> Result<(), ContextError> {
+    module.ty::<BTreeMap>()?;
+    module.function_meta(BTreeMap::new__meta)?;
+    module.function_meta(BTreeMap::len__meta)?;
+    module.function_meta(BTreeMap::is_empty__meta)?;
+    module.function_meta(BTreeMap::insert__meta)?;
+    module.function_meta(BTreeMap::get__meta)?;
+    module.function_meta(BTreeMap::contains_key__meta)?;
+    module.function_meta(BTreeMap::remove__meta)?;
+    module.function_meta(BTreeMap::clear__meta)?;
+    module.function_meta(BTreeMap::first_key_value__meta)?;
+    module.function_meta(BTreeMap::last_key_value__meta)?;
+    module.function_meta(BTreeMap::pop_first__meta)?;
+    module.function_meta(BTreeMap::pop_last__meta)?;
+    module.function_meta(BTreeMap::iter__meta)?;
+    module.function_meta(BTreeMap::keys__meta)?;
+    module.function_meta(BTreeMap::values__meta)?;
+    module.function_meta(BTreeMap::extend__meta)?;
+    module.function_meta(BTreeMap::from__meta)?;
+    module.function_meta(BTreeMap::clone__meta)?;
+    module.function_meta(BTreeMap::index_set__meta)?;
+    module.function_meta(BTreeMap::index_get__meta)?;
+    module.function_meta(BTreeMap::string_debug__meta)?;
+    module.function_meta(BTreeMap::partial_eq__meta)?;
+    module.function_meta(BTreeMap::eq__meta)?;
+    module.function_meta(BTreeMap::into_iter__meta)?;
+    Ok(())
+}
+
+/// An ordered map, where keys are ordered using their total ordering like with
+/// [`std::ops::cmp`].
+///
+/// Entries are stored sorted by key, so iterating over the map visits them in
+/// ascending key order.
+///
+/// Keys are copied into the map when they are inserted, so they can only be
+/// values which can be used as constant keys, like unit, bytes, characters,
+/// booleans, integers, strings and byte strings, or options, vectors, tuples
+/// and variants of them. Other keys, like floats, raise an error. Since keys are
+/// copied, modifying a value after it's been used as a key doesn't affect the
+/// map.
+///
+/// Looking up, inserting and removing keys takes *O(log n)* time. Iterating
+/// over the map copies its entries up front in *O(n)*, so that the map can be
+/// modified while it's iterated over.
+#[derive(Any, Clone)]
+#[rune(item = ::std::collections)]
+pub(crate) struct BTreeMap {
+    inner: collections::BTreeMap<Key, Value>,
+}
+
+impl BTreeMap {
+    /// Makes a new, empty `BTreeMap`.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    fn new() -> Self {
+        Self {
+            inner: collections::BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let a = BTreeMap::new();
+    /// assert_eq!(a.len(), 0);
+    /// a.insert(1, "a");
+    /// assert_eq!(a.len(), 1);
+    /// ```
+    #[rune::function(keep)]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let a = BTreeMap::new();
+    /// assert!(a.is_empty());
+    /// a.insert(1, "a");
+    /// assert!(!a.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned. The key is not updated.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// assert_eq!(map.insert(37, "a"), None);
+    /// assert_eq!(map.is_empty(), false);
+    ///
+    /// map.insert(37, "b");
+    /// assert_eq!(map.insert(37, "c"), Some("b"));
+    /// assert_eq!(map[37], "c");
+    /// ```
+    #[rune::function(keep)]
+    fn insert(&mut self, key: Value, value: Value) -> VmResult<Option<Value>> {
+        let key = vm_try!(Key::from_value(&key));
+        VmResult::Ok(self.inner.insert(key, value))
+    }
+
+    /// Returns the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.get(1), Some("a"));
+    /// assert_eq!(map.get(2), None);
+    /// ```
+    #[rune::function(keep)]
+    fn get(&self, key: Value) -> VmResult<Option<Value>> {
+        let key = vm_try!(Key::from_value(&key));
+        VmResult::Ok(self.inner.get(&key).cloned())
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.contains_key(1), true);
+    /// assert_eq!(map.contains_key(2), false);
+    /// ```
+    #[rune::function(keep)]
+    fn contains_key(&self, key: Value) -> VmResult<bool> {
+        let key = vm_try!(Key::from_value(&key));
+        VmResult::Ok(self.inner.contains_key(&key))
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove(1), Some("a"));
+    /// assert_eq!(map.remove(1), None);
+    /// ```
+    #[rune::function(keep)]
+    fn remove(&mut self, key: Value) -> VmResult<Option<Value>> {
+        let key = vm_try!(Key::from_value(&key));
+        VmResult::Ok(self.inner.remove(&key))
+    }
+
+    /// Clears the map, removing all elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.clear();
+    /// assert!(a.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    /// Returns the first key-value pair in the map. The key in this pair is the
+    /// minimum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// assert_eq!(map.first_key_value(), None);
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.first_key_value(), Some((1, "b")));
+    /// ```
+    #[rune::function(keep)]
+    fn first_key_value(&self) -> Option<(Value, Value)> {
+        let (key, value) = self.inner.first_key_value()?;
+        Some((key.clone().into_value(), value.clone()))
+    }
+
+    /// Returns the last key-value pair in the map. The key in this pair is the
+    /// maximum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.last_key_value(), Some((2, "a")));
+    /// ```
+    #[rune::function(keep)]
+    fn last_key_value(&self) -> Option<(Value, Value)> {
+        let (key, value) = self.inner.last_key_value()?;
+        Some((key.clone().into_value(), value.clone()))
+    }
+
+    /// Removes and returns the first element in the map. The key of this
+    /// element is the minimum key that was in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from([(2, "b"), (1, "a")]);
+    /// assert_eq!(map.pop_first(), Some((1, "a")));
+    /// assert_eq!(map.pop_first(), Some((2, "b")));
+    /// assert_eq!(map.pop_first(), None);
+    /// ```
+    #[rune::function(keep)]
+    fn pop_first(&mut self) -> Option<(Value, Value)> {
+        let (key, value) = self.inner.pop_first()?;
+        Some((key.into_value(), value))
+    }
+
+    /// Removes and returns the last element in the map. The key of this element
+    /// is the maximum key that was in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from([(2, "b"), (1, "a")]);
+    /// assert_eq!(map.pop_last(), Some((2, "b")));
+    /// assert_eq!(map.pop_last(), Some((1, "a")));
+    /// assert_eq!(map.pop_last(), None);
+    /// ```
+    #[rune::function(keep)]
+    fn pop_last(&mut self) -> Option<(Value, Value)> {
+        let (key, value) = self.inner.pop_last()?;
+        Some((key.into_value(), value))
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    ///
+    /// The iterator is over a copy of the entries at the time it was created.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from([
+    ///     ("c", 3),
+    ///     ("a", 1),
+    ///     ("b", 2),
+    /// ]);
+    ///
+    /// let pairs = map.iter().collect::<Vec>();
+    /// assert_eq!(pairs, [("a", 1), ("b", 2), ("c", 3)]);
+    ///
+    /// let pairs = map.iter().rev().collect::<Vec>();
+    /// assert_eq!(pairs, [("c", 3), ("b", 2), ("a", 1)]);
+    /// ```
+    #[rune::function(keep)]
+    fn iter(&self) -> Iterator {
+        let iter = self
+            .inner
+            .iter()
+            .map(|(key, value)| (key.clone().into_value(), value.clone()))
+            .collect::<Vec<_>>()
+            .into_iter();
+
+        Iterator::from_double_ended("std::collections::btree_map::Iter", iter)
+    }
+
+    /// Gets an iterator over the keys of the map, in sorted order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from([
+    ///     ("c", 3),
+    ///     ("a", 1),
+    ///     ("b", 2),
+    /// ]);
+    ///
+    /// let keys = map.keys().collect::<Vec>();
+    /// assert_eq!(keys, ["a", "b", "c"]);
+    /// ```
+    #[rune::function(keep)]
+    fn keys(&self) -> Iterator {
+        let iter = self
+            .inner
+            .keys()
+            .map(|key| key.clone().into_value())
+            .collect::<Vec<_>>()
+            .into_iter();
+
+        Iterator::from_double_ended("std::collections::btree_map::Keys", iter)
+    }
+
+    /// Gets an iterator over the values of the map, in order by key.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from([
+    ///     ("c", 3),
+    ///     ("a", 1),
+    ///     ("b", 2),
+    /// ]);
+    ///
+    /// let values = map.values().collect::<Vec>();
+    /// assert_eq!(values, [1, 2, 3]);
+    /// ```
+    #[rune::function(keep)]
+    fn values(&self) -> Iterator {
+        let iter = self.inner.values().cloned().collect::<Vec<_>>().into_iter();
+
+        Iterator::from_double_ended("std::collections::btree_map::Values", iter)
+    }
+
+    /// Extend this map from an iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    ///
+    /// map.extend([
+    ///     ("b", 2),
+    ///     ("a", 1),
+    /// ]);
+    ///
+    /// assert_eq!(map.keys().collect::<Vec>(), ["a", "b"]);
+    /// ```
+    #[rune::function(keep)]
+    fn extend(&mut self, value: Value) -> VmResult<()> {
+        let mut it = vm_try!(value.into_iter());
+
+        while let Some(value) = vm_try!(it.next()) {
+            let (key, value) = vm_try!(<(Value, Value)>::from_value(value));
+            vm_try!(self.insert(key, value));
+        }
+
+        VmResult::Ok(())
+    }
+
+    /// Convert a map from a `value`.
+    ///
+    /// The map can be converted from anything that implements the
+    /// [`INTO_ITER`] protocol, and each item produces should be a tuple pair.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from([(2, "b"), (1, "a")]);
+    /// assert_eq!(map.keys().collect::<Vec>(), [1, 2]);
+    /// ```
+    #[rune::function(keep, path = Self::from)]
+    fn from(value: Value) -> VmResult<BTreeMap> {
+        BTreeMap::from_iter(vm_try!(value.into_iter()))
+    }
+
+    /// Clone the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let a = BTreeMap::from([("a", 1), ("b", 2)]);
+    /// let b = a.clone();
+    ///
+    /// b.insert("c", 3);
+    ///
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(b.len(), 3);
+    /// ```
+    #[rune::function(keep, instance, path = Self::clone)]
+    fn clone(this: &BTreeMap) -> BTreeMap {
+        Clone::clone(this)
+    }
+
+    pub(crate) fn from_iter(mut it: Iterator) -> VmResult<Self> {
+        let mut map = Self::new();
+
+        while let Some(value) = vm_try!(it.next()) {
+            let (key, value) = vm_try!(<(Value, Value)>::from_value(value));
+            vm_try!(map.insert(key, value));
+        }
+
+        VmResult::Ok(map)
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did have this key present, the value is updated.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map[37] = "a";
+    /// assert!(!map.is_empty());
+    ///
+    /// map[37] = "c";
+    /// assert_eq!(map[37], "c");
+    /// ```
+    #[rune::function(keep, protocol = INDEX_SET)]
+    fn index_set(&mut self, key: Value, value: Value) -> VmResult<()> {
+        let _ = vm_try!(self.insert(key, value));
+        VmResult::Ok(())
+    }
+
+    /// Returns a the value corresponding to the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given value is not present in the map.
+    ///
+    /// ```rune,should_panic
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// let _ = map[1];
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map[1] = "a";
+    /// assert_eq!(map[1], "a");
+    /// ```
+    #[rune::function(keep, protocol = INDEX_GET)]
+    fn index_get(&self, key: Value) -> VmResult<Value> {
+        use crate::runtime::TypeOf;
+
+        let key = vm_try!(Key::from_value(&key));
+
+        let Some(value) = self.inner.get(&key) else {
+            return VmResult::err(VmErrorKind::MissingIndexKey {
+                target: Self::type_info(),
+            });
+        };
+
+        VmResult::Ok(value.clone())
+    }
+
+    /// Debug format the current map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map[2] = "b";
+    /// map[1] = "a";
+    ///
+    /// assert_eq!(format!("{:?}", map), "{1: \"a\", 2: \"b\"}");
+    /// ```
+    #[rune::function(keep, protocol = STRING_DEBUG)]
+    fn string_debug(&self, f: &mut Formatter) -> VmResult<fmt::Result> {
+        self.string_debug_with(f, &mut EnvProtocolCaller)
+    }
+
+    pub(crate) fn string_debug_with(
+        &self,
+        f: &mut Formatter,
+        caller: &mut impl ProtocolCaller,
+    ) -> VmResult<fmt::Result> {
+        vm_write!(f, "{{");
+
+        let mut it = self.inner.iter().peekable();
+
+        while let Some((key, value)) = it.next() {
+            let key = key.clone().into_value();
+
+            if let Err(fmt::Error) = vm_try!(key.string_debug_with(f, caller)) {
+                return VmResult::Ok(Err(fmt::Error));
+            }
+
+            vm_write!(f, ": ");
+
+            if let Err(fmt::Error) = vm_try!(value.string_debug_with(f, caller)) {
+                return VmResult::Ok(Err(fmt::Error));
+            }
+
+            if it.peek().is_some() {
+                vm_write!(f, ", ");
+            }
+        }
+
+        vm_write!(f, "}}");
+        VmResult::Ok(Ok(()))
+    }
+
+    /// Perform a partial equality check over two maps.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map1 = BTreeMap::from([
+    ///     ("a", 1.0),
+    ///     ("c", 3.0),
+    ///     ("b", 2.0),
+    /// ]);
+    ///
+    /// let map2 = BTreeMap::from([
+    ///     ("c", 3.0),
+    ///     ("a", 1.0),
+    ///     ("b", 2.0),
+    /// ]);
+    ///
+    /// assert!(map1 == map2);
+    ///
+    /// map1["b"] = f64::NAN;
+    /// map2["b"] = f64::NAN;
+    ///
+    /// assert!(map1 != map2);
+    /// ```
+    #[rune::function(keep, protocol = PARTIAL_EQ)]
+    fn partial_eq(&self, other: &Self) -> VmResult<bool> {
+        let mut caller = EnvProtocolCaller;
+
+        if self.inner.len() != other.inner.len() {
+            return VmResult::Ok(false);
+        }
+
+        for ((k1, v1), (k2, v2)) in self.inner.iter().zip(&other.inner) {
+            if k1 != k2 {
+                return VmResult::Ok(false);
+            }
+
+            if !vm_try!(Value::partial_eq_with(v1, v2, &mut caller)) {
+                return VmResult::Ok(false);
+            }
+        }
+
+        VmResult::Ok(true)
+    }
+
+    /// Perform a total equality check over two maps.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    /// use std::ops::eq;
+    ///
+    /// let map1 = BTreeMap::from([
+    ///     ("a", 1),
+    ///     ("c", 3),
+    ///     ("b", 2),
+    /// ]);
+    ///
+    /// let map2 = BTreeMap::from([
+    ///     ("c", 3),
+    ///     ("a", 1),
+    ///     ("b", 2),
+    /// ]);
+    ///
+    /// assert!(eq(map1, map2));
+    /// ```
+    #[rune::function(keep, protocol = EQ)]
+    fn eq(&self, other: &Self) -> VmResult<bool> {
+        let mut caller = EnvProtocolCaller;
+
+        if self.inner.len() != other.inner.len() {
+            return VmResult::Ok(false);
+        }
+
+        for ((k1, v1), (k2, v2)) in self.inner.iter().zip(&other.inner) {
+            if k1 != k2 {
+                return VmResult::Ok(false);
+            }
+
+            if !vm_try!(v1.eq_with(v2, &mut caller)) {
+                return VmResult::Ok(false);
+            }
+        }
+
+        VmResult::Ok(true)
+    }
+
+    /// An iterator visiting all key-value pairs in order by key.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from([
+    ///     ("c", 3),
+    ///     ("a", 1),
+    ///     ("b", 2),
+    /// ]);
+    ///
+    /// let pairs = [];
+    ///
+    /// for pair in map {
+    ///     pairs.push(pair);
+    /// }
+    ///
+    /// assert_eq!(pairs, [("a", 1), ("b", 2), ("c", 3)]);
+    /// ```
+    #[rune::function(keep, protocol = INTO_ITER)]
+    fn into_iter(&self) -> Iterator {
+        self.iter()
+    }
+}
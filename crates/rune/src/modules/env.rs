@@ -0,0 +1,213 @@
+//! The `std::env` module.
+//!
+//! This module is not installed by default, since it gives scripts access to
+//! the environment of the host. It has to be explicitly installed with an
+//! [EnvProvider], which determines what the environment looks like to scripts.
+//!
+//! To inherit the environment of the current process, use [ProcessEnv]:
+//!
+//! ```
+//! use rune::modules::env::{self, ProcessEnv};
+//!
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(env::module(ProcessEnv::new())?)?;
+//! # Ok::<_, rune::ContextError>(())
+//! ```
+//!
+//! To provide a virtual environment which is completely separate from the
+//! current process, use [VirtualEnv]:
+//!
+//! ```
+//! use rune::modules::env::{self, VirtualEnv};
+//!
+//! let env = VirtualEnv::new()
+//!     .with_var("HOME", "/home/rune")
+//!     .with_args(["script.rn", "--verbose"])
+//!     .with_current_dir("/home/rune/project");
+//!
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(env::module(env)?)?;
+//! # Ok::<_, rune::ContextError>(())
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::{ContextError, Module};
+
+/// Construct the `std::env` module, which gets its environment from the given
+/// `provider`.
+pub fn module<P>(provider: P) -> Result<Module, ContextError>
+where
+    P: EnvProvider,
+{
+    let mut module = Module::with_crate_item("std", ["env"]).with_unique("std::env");
+
+    module.item_mut().docs([
+        "Inspection of the environment the script is running in.",
+        "",
+        "What the environment looks like is determined by the host, which might",
+        "provide a virtual environment instead of the one of the current process.",
+    ]);
+
+    let provider = Arc::new(provider);
+
+    let p = provider.clone();
+
+    module
+        .function(["var"], move |key: &str| p.var(key))?
+        .docs([
+            "Get the value of the environment variable `key`.",
+            "",
+            "Returns `None` if the variable isn't set.",
+            "",
+            "# Examples",
+            "",
+            "```rune,ignore",
+            "let home = std::env::var(\"HOME\");",
+            "```",
+        ]);
+
+    let p = provider.clone();
+
+    module.function(["args"], move || p.args())?.docs([
+        "Get the arguments the script was started with.",
+        "",
+        "The first argument is conventionally the name of the script.",
+        "",
+        "# Examples",
+        "",
+        "```rune,ignore",
+        "for arg in std::env::args() {",
+        "    println!(\"{arg}\");",
+        "}",
+        "```",
+    ]);
+
+    let p = provider;
+
+    module
+        .function(["current_dir"], move || p.current_dir())?
+        .docs([
+            "Get the current working directory.",
+            "",
+            "Returns `None` if the current directory is not available.",
+            "",
+            "# Examples",
+            "",
+            "```rune,ignore",
+            "let dir = std::env::current_dir();",
+            "```",
+        ]);
+
+    Ok(module)
+}
+
+/// A provider of the environment which is visible to scripts through the
+/// `std::env` module.
+pub trait EnvProvider: 'static + Send + Sync {
+    /// Get the value of the environment variable `key`.
+    fn var(&self, key: &str) -> Option<String>;
+
+    /// Get the arguments the script was started with.
+    fn args(&self) -> Vec<String>;
+
+    /// Get the current working directory.
+    fn current_dir(&self) -> Option<String>;
+}
+
+/// An [EnvProvider] which inherits the environment of the current process.
+///
+/// Variables, arguments and directories which are not valid unicode are
+/// treated as if they're not set, or converted lossily in the case of
+/// arguments.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct ProcessEnv;
+
+impl ProcessEnv {
+    /// Construct a new provider which inherits the environment of the current
+    /// process.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl EnvProvider for ProcessEnv {
+    fn var(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok()
+    }
+
+    fn args(&self) -> Vec<String> {
+        std::env::args_os()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn current_dir(&self) -> Option<String> {
+        let dir = std::env::current_dir().ok()?;
+        Some(dir.to_str()?.to_owned())
+    }
+}
+
+/// An [EnvProvider] which provides a virtual environment configured by the
+/// host.
+///
+/// By default the virtual environment is empty, which means that no variables
+/// are set, there are no arguments, and there is no current directory.
+#[derive(Debug, Default, Clone)]
+pub struct VirtualEnv {
+    vars: HashMap<String, String>,
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+impl VirtualEnv {
+    /// Construct a new empty virtual environment.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the environment variable `key` to `value`.
+    pub fn with_var<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.vars.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the arguments the script was started with.
+    pub fn with_args<I>(mut self, args: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the current working directory.
+    pub fn with_current_dir<D>(mut self, dir: D) -> Self
+    where
+        D: Into<String>,
+    {
+        self.current_dir = Some(dir.into());
+        self
+    }
+}
+
+impl EnvProvider for VirtualEnv {
+    fn var(&self, key: &str) -> Option<String> {
+        self.vars.get(key).cloned()
+    }
+
+    fn args(&self) -> Vec<String> {
+        self.args.clone()
+    }
+
+    fn current_dir(&self) -> Option<String> {
+        self.current_dir.clone()
+    }
+}
@@ -0,0 +1,46 @@
+//! The `std::error` module.
+
+use core::fmt::{self, Write};
+
+use crate::no_std::prelude::*;
+
+use crate as rune;
+use crate::runtime::{Formatter, NativeError};
+use crate::{ContextError, Module};
+
+#[rune::module(::std::error)]
+/// Errors raised by native functions.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::from_meta(self::module_meta);
+    module.ty::<NativeError>()?;
+    module.function_meta(message)?;
+    module.function_meta(chain)?;
+    module.function_meta(string_display)?;
+    module.function_meta(string_debug)?;
+    Ok(module)
+}
+
+/// The message of the error.
+#[rune::function(instance)]
+fn message(error: &NativeError) -> String {
+    error.message()
+}
+
+/// The messages of the error followed by those of the errors which caused it,
+/// starting with the error itself.
+#[rune::function(instance)]
+fn chain(error: &NativeError) -> Vec<String> {
+    error.chain().map(|e| e.to_string()).collect()
+}
+
+/// Display the message of the error.
+#[rune::function(instance, protocol = STRING_DISPLAY)]
+fn string_display(error: &NativeError, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{error}")
+}
+
+/// Debug format the error.
+#[rune::function(instance, protocol = STRING_DEBUG)]
+fn string_debug(error: &NativeError, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{error:?}")
+}
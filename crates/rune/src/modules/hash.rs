@@ -1,8 +1,13 @@
 //! The `std::hash` module.
 
+use crate::no_std::prelude::*;
+
 use crate as rune;
+#[cfg(feature = "crypto")]
+use crate::runtime::Bytes;
 #[cfg(feature = "std")]
 use crate::runtime::Hasher;
+use crate::runtime::{Value, VmErrorKind, VmResult};
 use crate::{ContextError, Module};
 
 #[rune::module(::std::hash)]
@@ -12,5 +17,109 @@ pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::from_meta(self::module_meta);
     #[cfg(feature = "std")]
     module.ty::<Hasher>()?;
+    module.function_meta(xxh64)?;
+    #[cfg(feature = "crypto")]
+    module.function_meta(sha256)?;
+    #[cfg(feature = "crypto")]
+    module.function_meta(sha256_hex)?;
     Ok(module)
 }
+
+/// Calculate the stable, non-cryptographic [xxHash64] of a string or a byte
+/// array using the given `seed`.
+///
+/// The hash is stable across platforms and releases, so it's suitable for
+/// things like sharding or content-addressing. The 64-bit result is returned as
+/// an integer with the same bit pattern, so it might be negative.
+///
+/// [xxHash64]: https://github.com/Cyan4973/xxHash
+///
+/// # Examples
+///
+/// ```rune
+/// use std::hash::xxh64;
+///
+/// assert_eq!(xxh64("", 0), -1205034819632174695);
+/// assert_eq!(xxh64("hello", 0), xxh64(b"hello", 0));
+/// assert!(xxh64("hello", 0) != xxh64("hello", 1));
+/// ```
+#[rune::function]
+fn xxh64(value: Value, seed: i64) -> VmResult<i64> {
+    let hash = vm_try!(with_bytes(&value, |bytes| {
+        xxhash_rust::xxh64::xxh64(bytes, seed as u64)
+    }));
+
+    VmResult::Ok(hash as i64)
+}
+
+/// Calculate the SHA-256 digest of a string or a byte array.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::hash::sha256;
+///
+/// let digest = sha256("hello");
+/// assert_eq!(digest.len(), 32);
+/// assert_eq!(digest, sha256(b"hello"));
+/// ```
+#[cfg(feature = "crypto")]
+#[rune::function]
+fn sha256(value: Value) -> VmResult<Bytes> {
+    let digest = vm_try!(with_bytes(&value, sha256_digest));
+    VmResult::Ok(Bytes::from_vec(digest.to_vec()))
+}
+
+/// Calculate the SHA-256 digest of a string or a byte array, and return it as
+/// a lowercase hexadecimal string.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::hash::sha256_hex;
+///
+/// assert_eq!(sha256_hex(""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+/// ```
+#[cfg(feature = "crypto")]
+#[rune::function]
+fn sha256_hex(value: Value) -> VmResult<String> {
+    use core::fmt::Write;
+
+    let digest = vm_try!(with_bytes(&value, sha256_digest));
+    let mut string = String::with_capacity(digest.len() * 2);
+
+    for b in digest {
+        // NB: Writing to a string can't fail.
+        let _ = write!(string, "{b:02x}");
+    }
+
+    VmResult::Ok(string)
+}
+
+#[cfg(feature = "crypto")]
+fn sha256_digest(bytes: &[u8]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    hasher.finalize().into()
+}
+
+/// Access the underlying bytes of a value which is either a string or a byte
+/// array.
+fn with_bytes<T>(value: &Value, f: impl FnOnce(&[u8]) -> T) -> VmResult<T> {
+    match value {
+        Value::String(string) => {
+            let string = vm_try!(string.borrow_ref());
+            VmResult::Ok(f(string.as_bytes()))
+        }
+        Value::Bytes(bytes) => {
+            let bytes = vm_try!(bytes.borrow_ref());
+            VmResult::Ok(f(bytes.as_slice()))
+        }
+        actual => VmResult::err([
+            VmErrorKind::expected::<String>(vm_try!(actual.type_info())),
+            VmErrorKind::bad_argument(0),
+        ]),
+    }
+}
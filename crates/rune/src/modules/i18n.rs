@@ -0,0 +1,69 @@
+//! Localization module, which translates text through a lookup provided by
+//! the host.
+//!
+//! ```
+//! use rune::{Context, ContextError};
+//! use rune::modules::i18n;
+//!
+//! let mut context = Context::with_default_modules()?;
+//!
+//! context.install(i18n::module(|key| match key {
+//!     "greeting" => Some(String::from("Hej")),
+//!     _ => None,
+//! })?)?;
+//! # Ok::<_, ContextError>(())
+//! ```
+//!
+//! Template literals used for text which should be translated can be
+//! extracted into translation catalogs through
+//! [CompileVisitor::visit_template][crate::compile::CompileVisitor::visit_template].
+
+use crate as rune;
+use crate::ast;
+use crate::compile;
+use crate::macros::{quote, MacroContext, TokenStream};
+use crate::no_std::prelude::*;
+use crate::parse::Parser;
+use crate::{ContextError, Module};
+
+/// Construct the `std::i18n` module, which translates keys through the given
+/// `lookup`.
+///
+/// Keys for which the lookup returns `None` are translated into the key
+/// itself, so that missing translations are visible but don't cause errors.
+pub fn module<F>(lookup: F) -> Result<Module, ContextError>
+where
+    F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+{
+    let mut module = Module::with_crate_item("std", ["i18n"]);
+
+    module.macro_meta(tr)?;
+
+    module
+        .function(["translate"], move |key: &str| {
+            lookup(key).unwrap_or_else(|| key.to_owned())
+        })?
+        .docs(["Translate the given key through the lookup provided by the host."]);
+
+    Ok(module)
+}
+
+/// Translate the given key at runtime through the lookup provided by the host.
+///
+/// # Examples
+///
+/// ```rune,ignore
+/// println!("{}", tr!("greeting"));
+/// ```
+#[rune::macro_]
+pub(crate) fn tr(
+    cx: &mut MacroContext<'_, '_, '_>,
+    stream: &TokenStream,
+) -> compile::Result<TokenStream> {
+    let mut parser = Parser::from_token_stream(stream, cx.input_span());
+    let key = parser.parse::<ast::LitStr>()?;
+    parser.parse::<Option<ast::Comma>>()?;
+    parser.eof()?;
+
+    Ok(quote!(::std::i18n::translate(#key)).into_token_stream(cx))
+}
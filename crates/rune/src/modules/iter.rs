@@ -3,13 +3,12 @@
 use crate::no_std::prelude::*;
 
 use crate as rune;
-use crate::modules::collections::VecDeque;
+use crate::modules::collections::{BTreeMap, VecDeque};
 #[cfg(feature = "std")]
 use crate::modules::collections::{HashMap, HashSet};
-#[cfg(feature = "std")]
-use crate::runtime::EnvProtocolCaller;
 use crate::runtime::{
-    FromValue, Function, Iterator, Object, OwnedTuple, Protocol, Value, Vec, VmResult,
+    EnvProtocolCaller, FromValue, Function, Iterator, Object, OwnedTuple, Protocol, Value, Vec,
+    VmResult,
 };
 use crate::{ContextError, Module};
 
@@ -56,6 +55,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.function_meta(collect_hash_set)?;
     #[cfg(feature = "std")]
     module.function_meta(collect_hash_map)?;
+    module.function_meta(collect_btree_map)?;
     module.function_meta(collect_tuple)?;
     module.function_meta(collect_object)?;
     module.function_meta(collect_string)?;
@@ -1099,6 +1099,22 @@ fn collect_hash_map(it: Iterator) -> VmResult<HashMap> {
     HashMap::from_iter(it, &mut caller)
 }
 
+/// Collect the iterator as a [`BTreeMap`].
+///
+/// # Examples
+///
+/// ```rune
+/// use std::collections::BTreeMap;
+///
+/// let actual = [2, 0, 1].iter().map(|n| (n, n.to_string())).collect::<BTreeMap>();
+/// let expected = BTreeMap::from([(0, "0"), (1, "1"), (2, "2")]);
+/// assert_eq!(actual, expected);
+/// ```
+#[rune::function(instance, path = collect::<BTreeMap>)]
+fn collect_btree_map(it: Iterator) -> VmResult<BTreeMap> {
+    BTreeMap::from_iter(it)
+}
+
 /// Collect the iterator as a [`Tuple`].
 ///
 /// # Examples
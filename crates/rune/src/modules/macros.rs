@@ -3,6 +3,7 @@
 //! [Rune Language]: https://rune-rs.github.io
 
 use crate as rune;
+use crate::ast;
 use crate::compile;
 use crate::macros::{quote, MacroContext, TokenStream};
 use crate::parse::Parser;
@@ -14,6 +15,9 @@ pub fn module() -> Result<Module, ContextError> {
         Module::with_crate_item("std", ["macros", "builtin"]).with_unique("std::macros::builtin");
     builtins.macro_meta(file)?;
     builtins.macro_meta(line)?;
+    builtins.macro_meta(include_data)?;
+    builtins.macro_meta(include_str)?;
+    builtins.macro_meta(include_bytes)?;
     Ok(builtins)
 }
 
@@ -64,3 +68,100 @@ pub(crate) fn file(
     )
     .into_token_stream(cx))
 }
+
+/// Load a data file into the unit as a constant value.
+///
+/// The path is resolved relative to the file the macro is used in, and the
+/// data is loaded directly into the unit without constructing syntax trees for
+/// it. This makes it suitable for embedding large data tables.
+///
+/// Only JSON files are currently supported, and loading them requires the
+/// `json` feature to be enabled. Arrays are loaded as vectors, maps as objects
+/// and `null` as the unit value `()`.
+///
+/// # Examples
+///
+/// ```rune,ignore
+/// let table = include_data!("table.json");
+/// println!("{}", table[0].name);
+/// ```
+#[rune::macro_]
+pub(crate) fn include_data(
+    cx: &mut MacroContext<'_, '_, '_>,
+    stream: &TokenStream,
+) -> compile::Result<TokenStream> {
+    use crate as rune;
+
+    let mut parser = Parser::from_token_stream(stream, cx.input_span());
+    let path = parser.parse::<ast::LitStr>()?;
+    parser.parse::<Option<ast::Comma>>()?;
+    parser.eof()?;
+
+    Ok(quote!(
+        #[builtin]
+        include_data!(#path)
+    )
+    .into_token_stream(cx))
+}
+
+/// Include the contents of a UTF-8 encoded file as a string.
+///
+/// The path is resolved relative to the file the macro is used in, and the file
+/// is loaded at compile time through the source loader used by the build.
+/// The contents are stored in the unit as a static string.
+///
+/// # Examples
+///
+/// ```rune,ignore
+/// let template = include_str!("template.html");
+/// println!("{}", template);
+/// ```
+#[rune::macro_]
+pub(crate) fn include_str(
+    cx: &mut MacroContext<'_, '_, '_>,
+    stream: &TokenStream,
+) -> compile::Result<TokenStream> {
+    use crate as rune;
+
+    let mut parser = Parser::from_token_stream(stream, cx.input_span());
+    let path = parser.parse::<ast::LitStr>()?;
+    parser.parse::<Option<ast::Comma>>()?;
+    parser.eof()?;
+
+    Ok(quote!(
+        #[builtin]
+        include_str!(#path)
+    )
+    .into_token_stream(cx))
+}
+
+/// Include the contents of a file as bytes.
+///
+/// The path is resolved relative to the file the macro is used in, and the file
+/// is loaded at compile time through the source loader used by the build.
+/// The contents are stored in the unit as static bytes.
+///
+/// # Examples
+///
+/// ```rune,ignore
+/// let image = include_bytes!("image.png");
+/// println!("{}", image.len());
+/// ```
+#[rune::macro_]
+pub(crate) fn include_bytes(
+    cx: &mut MacroContext<'_, '_, '_>,
+    stream: &TokenStream,
+) -> compile::Result<TokenStream> {
+    use crate as rune;
+
+    let mut parser = Parser::from_token_stream(stream, cx.input_span());
+    let path = parser.parse::<ast::LitStr>()?;
+    parser.parse::<Option<ast::Comma>>()?;
+    parser.eof()?;
+
+    Ok(quote!(
+        #[builtin]
+        include_bytes!(#path)
+    )
+    .into_token_stream(cx))
+}
@@ -0,0 +1,801 @@
+//! The `std::math` module.
+
+use core::fmt;
+
+use crate as rune;
+use crate::compile::ItemBuf;
+use crate::runtime::{Value, VmErrorKind, VmResult};
+use crate::{ContextError, Hash, Module};
+
+/// Construct the `std::math` module.
+///
+/// Calls to functions in this module with constant arguments can be evaluated
+/// in constant contexts, like when defining a `const` item.
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::with_crate_item("std", ["math"]);
+
+    m.function_meta(sin)?;
+    m.function_meta(cos)?;
+    m.function_meta(tan)?;
+    m.function_meta(asin)?;
+    m.function_meta(acos)?;
+    m.function_meta(atan)?;
+    m.function_meta(atan2)?;
+    m.function_meta(sinh)?;
+    m.function_meta(cosh)?;
+    m.function_meta(tanh)?;
+    m.function_meta(hypot)?;
+    m.function_meta(to_degrees)?;
+    m.function_meta(to_radians)?;
+
+    m.function_meta(sqrt)?;
+    m.function_meta(cbrt)?;
+    m.function_meta(exp)?;
+    m.function_meta(exp2)?;
+    m.function_meta(ln)?;
+    m.function_meta(log)?;
+    m.function_meta(log2)?;
+    m.function_meta(log10)?;
+
+    m.function_meta(floor)?;
+    m.function_meta(ceil)?;
+    m.function_meta(round)?;
+    m.function_meta(trunc)?;
+    m.function_meta(fract)?;
+
+    m.function_meta(abs)?;
+    m.function_meta(signum)?;
+    m.function_meta(min)?;
+    m.function_meta(max)?;
+    m.function_meta(clamp)?;
+    m.function_meta(pow)?;
+
+    m.constant(["PI"], core::f64::consts::PI)?
+        .docs(["Archimedes' constant (π)."]);
+    m.constant(["TAU"], core::f64::consts::TAU)?
+        .docs(["The full circle constant (τ = 2π)."]);
+    m.constant(["E"], core::f64::consts::E)?
+        .docs(["Euler's number (e)."]);
+    m.constant(["SQRT_2"], core::f64::consts::SQRT_2)?
+        .docs(["The square root of 2."]);
+    m.constant(["LN_2"], core::f64::consts::LN_2)?
+        .docs(["The natural logarithm of 2."]);
+    m.constant(["LN_10"], core::f64::consts::LN_10)?
+        .docs(["The natural logarithm of 10."]);
+    Ok(m)
+}
+
+/// Computes the sine of a number in radians.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::sin(0.0), 0.0);
+/// assert!((math::sin(math::PI / 2.0) - 1.0).abs() < 1e-10);
+/// ```
+#[rune::function]
+fn sin(x: f64) -> f64 {
+    x.sin()
+}
+
+/// Computes the cosine of a number in radians.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::cos(0.0), 1.0);
+/// assert!((math::cos(math::PI) + 1.0).abs() < 1e-10);
+/// ```
+#[rune::function]
+fn cos(x: f64) -> f64 {
+    x.cos()
+}
+
+/// Computes the tangent of a number in radians.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::tan(0.0), 0.0);
+/// assert!((math::tan(math::PI / 4.0) - 1.0).abs() < 1e-10);
+/// ```
+#[rune::function]
+fn tan(x: f64) -> f64 {
+    x.tan()
+}
+
+/// Computes the arcsine of a number, in radians in the range `[-π/2, π/2]`.
+///
+/// Returns NaN if the number is outside of the range `[-1, 1]`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::asin(0.0), 0.0);
+/// assert!(math::asin(2.0).is_nan());
+/// ```
+#[rune::function]
+fn asin(x: f64) -> f64 {
+    x.asin()
+}
+
+/// Computes the arccosine of a number, in radians in the range `[0, π]`.
+///
+/// Returns NaN if the number is outside of the range `[-1, 1]`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::acos(1.0), 0.0);
+/// assert!(math::acos(2.0).is_nan());
+/// ```
+#[rune::function]
+fn acos(x: f64) -> f64 {
+    x.acos()
+}
+
+/// Computes the arctangent of a number, in radians in the range `[-π/2,
+/// π/2]`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::atan(0.0), 0.0);
+/// assert!((math::atan(1.0) - math::PI / 4.0).abs() < 1e-10);
+/// ```
+#[rune::function]
+fn atan(x: f64) -> f64 {
+    x.atan()
+}
+
+/// Computes the four quadrant arctangent of `y` and `x` in radians.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::atan2(0.0, 1.0), 0.0);
+/// assert!((math::atan2(1.0, -1.0) - 3.0 * math::PI / 4.0).abs() < 1e-10);
+/// ```
+#[rune::function]
+fn atan2(y: f64, x: f64) -> f64 {
+    y.atan2(x)
+}
+
+/// Computes the hyperbolic sine of a number.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::sinh(0.0), 0.0);
+/// ```
+#[rune::function]
+fn sinh(x: f64) -> f64 {
+    x.sinh()
+}
+
+/// Computes the hyperbolic cosine of a number.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::cosh(0.0), 1.0);
+/// ```
+#[rune::function]
+fn cosh(x: f64) -> f64 {
+    x.cosh()
+}
+
+/// Computes the hyperbolic tangent of a number.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::tanh(0.0), 0.0);
+/// ```
+#[rune::function]
+fn tanh(x: f64) -> f64 {
+    x.tanh()
+}
+
+/// Computes the length of the hypotenuse of a right-angle triangle with the
+/// sides `x` and `y`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::hypot(3.0, 4.0), 5.0);
+/// ```
+#[rune::function]
+fn hypot(x: f64, y: f64) -> f64 {
+    x.hypot(y)
+}
+
+/// Converts radians to degrees.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::to_degrees(math::PI), 180.0);
+/// ```
+#[rune::function]
+fn to_degrees(x: f64) -> f64 {
+    x.to_degrees()
+}
+
+/// Converts degrees to radians.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::to_radians(180.0), math::PI);
+/// ```
+#[rune::function]
+fn to_radians(x: f64) -> f64 {
+    x.to_radians()
+}
+
+/// Computes the square root of a number.
+///
+/// Returns NaN if the number is negative.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::sqrt(4.0), 2.0);
+/// assert!(math::sqrt(-1.0).is_nan());
+/// ```
+#[rune::function]
+fn sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+
+/// Computes the cube root of a number.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::cbrt(27.0), 3.0);
+/// ```
+#[rune::function]
+fn cbrt(x: f64) -> f64 {
+    x.cbrt()
+}
+
+/// Computes `e^x`, where `e` is [`E`].
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::exp(0.0), 1.0);
+/// assert!((math::exp(1.0) - math::E).abs() < 1e-10);
+/// ```
+#[rune::function]
+fn exp(x: f64) -> f64 {
+    x.exp()
+}
+
+/// Computes `2^x`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::exp2(3.0), 8.0);
+/// ```
+#[rune::function]
+fn exp2(x: f64) -> f64 {
+    x.exp2()
+}
+
+/// Computes the natural logarithm of a number.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::ln(1.0), 0.0);
+/// assert!((math::ln(math::E) - 1.0).abs() < 1e-10);
+/// ```
+#[rune::function]
+fn ln(x: f64) -> f64 {
+    x.ln()
+}
+
+/// Computes the logarithm of a number with respect to an arbitrary base.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert!((math::log(8.0, 2.0) - 3.0).abs() < 1e-10);
+/// ```
+#[rune::function]
+fn log(x: f64, base: f64) -> f64 {
+    x.log(base)
+}
+
+/// Computes the base 2 logarithm of a number.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::log2(8.0), 3.0);
+/// ```
+#[rune::function]
+fn log2(x: f64) -> f64 {
+    x.log2()
+}
+
+/// Computes the base 10 logarithm of a number.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::log10(100.0), 2.0);
+/// ```
+#[rune::function]
+fn log10(x: f64) -> f64 {
+    x.log10()
+}
+
+/// Returns the largest integer less than or equal to a number.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::floor(3.7), 3.0);
+/// assert_eq!(math::floor(-3.2), -4.0);
+/// ```
+#[rune::function]
+fn floor(x: f64) -> f64 {
+    x.floor()
+}
+
+/// Returns the smallest integer greater than or equal to a number.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::ceil(3.2), 4.0);
+/// assert_eq!(math::ceil(-3.7), -3.0);
+/// ```
+#[rune::function]
+fn ceil(x: f64) -> f64 {
+    x.ceil()
+}
+
+/// Returns the nearest integer to a number, rounding half-way cases away from
+/// zero.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::round(2.5), 3.0);
+/// assert_eq!(math::round(-2.5), -3.0);
+/// assert_eq!(math::round(2.4), 2.0);
+/// ```
+#[rune::function]
+fn round(x: f64) -> f64 {
+    x.round()
+}
+
+/// Returns the integer part of a number, rounding towards zero.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::trunc(3.7), 3.0);
+/// assert_eq!(math::trunc(-3.7), -3.0);
+/// ```
+#[rune::function]
+fn trunc(x: f64) -> f64 {
+    x.trunc()
+}
+
+/// Returns the fractional part of a number.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::fract(3.5), 0.5);
+/// ```
+#[rune::function]
+fn fract(x: f64) -> f64 {
+    x.fract()
+}
+
+/// Computes the absolute value of an integer or a float.
+///
+/// Errors if the absolute value of an integer overflows.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::abs(-10), 10);
+/// assert_eq!(math::abs(-1.5), 1.5);
+/// ```
+#[rune::function]
+fn abs(value: Value) -> VmResult<Value> {
+    call(|| Number::from_value(value)?.abs())
+}
+
+/// Returns a number representing the sign of an integer or a float.
+///
+/// For integers this is `0` if the number is zero, `1` if it's positive and
+/// `-1` if it's negative. For floats this is `1.0` if the number is positive,
+/// `+0.0` or positive infinity, `-1.0` if it's negative, `-0.0` or negative
+/// infinity and NaN if it's NaN.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::signum(-10), -1);
+/// assert_eq!(math::signum(0), 0);
+/// assert_eq!(math::signum(2.5), 1.0);
+/// ```
+#[rune::function]
+fn signum(value: Value) -> VmResult<Value> {
+    call(|| Number::from_value(value)?.signum())
+}
+
+/// Returns the minimum of two integers or two floats.
+///
+/// If one of two floats is NaN, the other one is returned.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::min(1, 2), 1);
+/// assert_eq!(math::min(1.5, -2.5), -2.5);
+/// ```
+#[rune::function]
+fn min(a: Value, b: Value) -> VmResult<Value> {
+    call(|| Number::from_value(a)?.min(Number::from_value(b)?))
+}
+
+/// Returns the maximum of two integers or two floats.
+///
+/// If one of two floats is NaN, the other one is returned.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::max(1, 2), 2);
+/// assert_eq!(math::max(1.5, -2.5), 1.5);
+/// ```
+#[rune::function]
+fn max(a: Value, b: Value) -> VmResult<Value> {
+    call(|| Number::from_value(a)?.max(Number::from_value(b)?))
+}
+
+/// Restricts an integer or a float to the interval `[min, max]`.
+///
+/// Errors if `min` is greater than `max`, or if either of them is NaN.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::clamp(10, 0, 5), 5);
+/// assert_eq!(math::clamp(-1.5, 0.0, 1.0), 0.0);
+/// ```
+#[rune::function]
+fn clamp(value: Value, min: Value, max: Value) -> VmResult<Value> {
+    call(|| {
+        let value = Number::from_value(value)?;
+        value.clamp(Number::from_value(min)?, Number::from_value(max)?)
+    })
+}
+
+/// Raises an integer or a float to a power.
+///
+/// Integers can only be raised to a non-negative integer power, and floats
+/// can be raised to either an integer or a float power. Errors if raising an
+/// integer overflows.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::math;
+///
+/// assert_eq!(math::pow(2, 10), 1024);
+/// assert_eq!(math::pow(2.0, 3), 8.0);
+/// assert_eq!(math::pow(4.0, 0.5), 2.0);
+/// ```
+#[rune::function]
+fn pow(base: Value, exp: Value) -> VmResult<Value> {
+    call(|| Number::from_value(base)?.pow(Number::from_value(exp)?))
+}
+
+/// Call a function operating over numbers, converting its result into a
+/// value.
+fn call<F>(f: F) -> VmResult<Value>
+where
+    F: FnOnce() -> Result<Number, MathError>,
+{
+    match f() {
+        Ok(number) => VmResult::Ok(number.into_value()),
+        Err(MathError::Expected { actual }) => {
+            VmResult::err(VmErrorKind::expected::<f64>(actual.type_info()))
+        }
+        Err(MathError::Overflow) => VmResult::err(VmErrorKind::Overflow),
+        Err(error) => VmResult::panic(error.to_string()),
+    }
+}
+
+/// A function in this module which can be evaluated in constant contexts.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum MathFn {
+    Float(fn(f64) -> f64),
+    Float2(fn(f64, f64) -> f64),
+    Number(fn(Number) -> Result<Number, MathError>),
+    Number2(fn(Number, Number) -> Result<Number, MathError>),
+    Number3(fn(Number, Number, Number) -> Result<Number, MathError>),
+}
+
+/// Functions which can be evaluated in constant contexts by name.
+const MATH_FNS: &[(&str, MathFn)] = &[
+    ("sin", MathFn::Float(f64::sin)),
+    ("cos", MathFn::Float(f64::cos)),
+    ("tan", MathFn::Float(f64::tan)),
+    ("asin", MathFn::Float(f64::asin)),
+    ("acos", MathFn::Float(f64::acos)),
+    ("atan", MathFn::Float(f64::atan)),
+    ("atan2", MathFn::Float2(f64::atan2)),
+    ("sinh", MathFn::Float(f64::sinh)),
+    ("cosh", MathFn::Float(f64::cosh)),
+    ("tanh", MathFn::Float(f64::tanh)),
+    ("hypot", MathFn::Float2(f64::hypot)),
+    ("to_degrees", MathFn::Float(f64::to_degrees)),
+    ("to_radians", MathFn::Float(f64::to_radians)),
+    ("sqrt", MathFn::Float(f64::sqrt)),
+    ("cbrt", MathFn::Float(f64::cbrt)),
+    ("exp", MathFn::Float(f64::exp)),
+    ("exp2", MathFn::Float(f64::exp2)),
+    ("ln", MathFn::Float(f64::ln)),
+    ("log", MathFn::Float2(f64::log)),
+    ("log2", MathFn::Float(f64::log2)),
+    ("log10", MathFn::Float(f64::log10)),
+    ("floor", MathFn::Float(f64::floor)),
+    ("ceil", MathFn::Float(f64::ceil)),
+    ("round", MathFn::Float(f64::round)),
+    ("trunc", MathFn::Float(f64::trunc)),
+    ("fract", MathFn::Float(f64::fract)),
+    ("abs", MathFn::Number(Number::abs)),
+    ("signum", MathFn::Number(Number::signum)),
+    ("min", MathFn::Number2(Number::min)),
+    ("max", MathFn::Number2(Number::max)),
+    ("clamp", MathFn::Number3(Number::clamp)),
+    ("pow", MathFn::Number2(Number::pow)),
+];
+
+impl MathFn {
+    /// Look up the function in this module with the given hash.
+    pub(crate) fn lookup(hash: Hash) -> Option<Self> {
+        MATH_FNS.iter().find_map(|&(name, f)| {
+            let item = ItemBuf::with_crate_item("std", ["math", name]);
+            (Hash::type_hash(&item) == hash).then_some(f)
+        })
+    }
+
+    /// Call the function with the given arguments.
+    pub(crate) fn call(self, args: &[Number]) -> Result<Number, MathError> {
+        match (self, args) {
+            (MathFn::Float(f), &[a]) => Ok(Number::Float(f(a.into_float()?))),
+            (MathFn::Float2(f), &[a, b]) => Ok(Number::Float(f(a.into_float()?, b.into_float()?))),
+            (MathFn::Number(f), &[a]) => f(a),
+            (MathFn::Number2(f), &[a, b]) => f(a, b),
+            (MathFn::Number3(f), &[a, b, c]) => f(a, b, c),
+            _ => Err(MathError::ArgumentCount {
+                expected: self.args(),
+                actual: args.len(),
+            }),
+        }
+    }
+
+    /// The number of arguments the function takes.
+    fn args(self) -> usize {
+        match self {
+            MathFn::Float(..) | MathFn::Number(..) => 1,
+            MathFn::Float2(..) | MathFn::Number2(..) => 2,
+            MathFn::Number3(..) => 3,
+        }
+    }
+}
+
+/// A number, which is what functions that support both integers and floats
+/// operate over.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn from_value(value: Value) -> Result<Self, MathError> {
+        match value {
+            Value::Integer(n) => Ok(Self::Integer(n)),
+            Value::Float(n) => Ok(Self::Float(n)),
+            actual => Err(MathError::Expected { actual }),
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            Self::Integer(n) => Value::Integer(n),
+            Self::Float(n) => Value::Float(n),
+        }
+    }
+
+    fn into_float(self) -> Result<f64, MathError> {
+        match self {
+            Self::Float(n) => Ok(n),
+            Self::Integer(..) => Err(MathError::ExpectedFloat),
+        }
+    }
+
+    fn abs(self) -> Result<Self, MathError> {
+        match self {
+            Self::Integer(n) => Ok(Self::Integer(n.checked_abs().ok_or(MathError::Overflow)?)),
+            Self::Float(n) => Ok(Self::Float(n.abs())),
+        }
+    }
+
+    fn signum(self) -> Result<Self, MathError> {
+        match self {
+            Self::Integer(n) => Ok(Self::Integer(n.signum())),
+            Self::Float(n) => Ok(Self::Float(n.signum())),
+        }
+    }
+
+    fn min(self, other: Self) -> Result<Self, MathError> {
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => Ok(Self::Integer(a.min(b))),
+            (Self::Float(a), Self::Float(b)) => Ok(Self::Float(a.min(b))),
+            _ => Err(MathError::Mismatch),
+        }
+    }
+
+    fn max(self, other: Self) -> Result<Self, MathError> {
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => Ok(Self::Integer(a.max(b))),
+            (Self::Float(a), Self::Float(b)) => Ok(Self::Float(a.max(b))),
+            _ => Err(MathError::Mismatch),
+        }
+    }
+
+    fn clamp(self, min: Self, max: Self) -> Result<Self, MathError> {
+        match (self, min, max) {
+            (Self::Integer(n), Self::Integer(min), Self::Integer(max)) => {
+                if min > max {
+                    return Err(MathError::BadInterval);
+                }
+
+                Ok(Self::Integer(n.clamp(min, max)))
+            }
+            (Self::Float(n), Self::Float(min), Self::Float(max)) => {
+                if min > max || min.is_nan() || max.is_nan() {
+                    return Err(MathError::BadInterval);
+                }
+
+                Ok(Self::Float(n.clamp(min, max)))
+            }
+            _ => Err(MathError::Mismatch),
+        }
+    }
+
+    fn pow(self, exp: Self) -> Result<Self, MathError> {
+        match (self, exp) {
+            (Self::Integer(base), Self::Integer(exp)) => {
+                let exp = u32::try_from(exp).map_err(|_| MathError::NegativeExponent)?;
+                let n = base.checked_pow(exp).ok_or(MathError::Overflow)?;
+                Ok(Self::Integer(n))
+            }
+            (Self::Float(base), Self::Integer(exp)) => match i32::try_from(exp) {
+                Ok(exp) => Ok(Self::Float(base.powi(exp))),
+                Err(..) => Ok(Self::Float(base.powf(exp as f64))),
+            },
+            (Self::Float(base), Self::Float(exp)) => Ok(Self::Float(base.powf(exp))),
+            (Self::Integer(..), Self::Float(..)) => Err(MathError::Mismatch),
+        }
+    }
+}
+
+/// Error raised by functions operating over numbers.
+#[derive(Debug)]
+pub(crate) enum MathError {
+    /// The value is not a number.
+    Expected { actual: Value },
+    /// An integer was used where a float is expected.
+    ExpectedFloat,
+    /// Arguments are not the same kind of number.
+    Mismatch,
+    /// Integer arithmetic overflowed.
+    Overflow,
+    /// An integer was raised to a negative power.
+    NegativeExponent,
+    /// The lower bound of an interval is greater than its upper bound.
+    BadInterval,
+    /// A function was called with the wrong number of arguments.
+    ArgumentCount { expected: usize, actual: usize },
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MathError::Expected { actual } => {
+                write!(f, "Expected a number, but found `{}`", actual.type_info())
+            }
+            MathError::ExpectedFloat => write!(f, "Expected a float"),
+            MathError::Mismatch => {
+                write!(f, "Arguments must either all be integers or all be floats")
+            }
+            MathError::Overflow => write!(f, "Numerical overflow"),
+            MathError::NegativeExponent => {
+                write!(f, "Integers can't be raised to a negative power")
+            }
+            MathError::BadInterval => write!(f, "The minimum is greater than the maximum"),
+            MathError::ArgumentCount { expected, actual } => {
+                write!(f, "Expected {expected} arguments, but got {actual}")
+            }
+        }
+    }
+}
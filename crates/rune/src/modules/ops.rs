@@ -7,7 +7,8 @@ use crate as rune;
 use crate::runtime::Hasher;
 use crate::runtime::{
     ControlFlow, EnvProtocolCaller, Function, Generator, GeneratorState, Iterator, Range,
-    RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, Value, Vm, VmResult,
+    RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, Value, Vec, Vm, VmErrorKind,
+    VmResult,
 };
 use crate::{ContextError, Module};
 #[cfg(feature = "std")]
@@ -23,6 +24,10 @@ pub fn module() -> Result<Module, ContextError> {
         m.function_meta(RangeFrom::iter__meta)?;
         m.function_meta(RangeFrom::contains__meta)?;
         m.function_meta(RangeFrom::into_iter__meta)?;
+        m.function_meta(RangeFrom::step_by__meta)?;
+        m.function_meta(RangeFrom::len__meta)?;
+        m.function_meta(RangeFrom::rev__meta)?;
+        m.function_meta(range_from_collect_vec)?;
         m.function_meta(RangeFrom::partial_eq__meta)?;
         m.function_meta(RangeFrom::eq__meta)?;
         m.function_meta(RangeFrom::partial_cmp__meta)?;
@@ -32,6 +37,7 @@ pub fn module() -> Result<Module, ContextError> {
     {
         m.ty::<RangeFull>()?;
         m.function_meta(RangeFull::contains)?;
+        m.function_meta(RangeFull::len__meta)?;
     }
 
     {
@@ -39,6 +45,11 @@ pub fn module() -> Result<Module, ContextError> {
         m.function_meta(RangeInclusive::iter__meta)?;
         m.function_meta(RangeInclusive::contains__meta)?;
         m.function_meta(RangeInclusive::into_iter__meta)?;
+        m.function_meta(RangeInclusive::len__meta)?;
+        m.function_meta(RangeInclusive::is_empty__meta)?;
+        m.function_meta(RangeInclusive::rev__meta)?;
+        m.function_meta(RangeInclusive::step_by__meta)?;
+        m.function_meta(range_inclusive_collect_vec)?;
         m.function_meta(RangeInclusive::partial_eq__meta)?;
         m.function_meta(RangeInclusive::eq__meta)?;
         m.function_meta(RangeInclusive::partial_cmp__meta)?;
@@ -48,6 +59,7 @@ pub fn module() -> Result<Module, ContextError> {
     {
         m.ty::<RangeToInclusive>()?;
         m.function_meta(RangeToInclusive::contains__meta)?;
+        m.function_meta(RangeToInclusive::len__meta)?;
         m.function_meta(RangeToInclusive::partial_eq__meta)?;
         m.function_meta(RangeToInclusive::eq__meta)?;
         m.function_meta(RangeToInclusive::partial_cmp__meta)?;
@@ -57,6 +69,7 @@ pub fn module() -> Result<Module, ContextError> {
     {
         m.ty::<RangeTo>()?;
         m.function_meta(RangeTo::contains__meta)?;
+        m.function_meta(RangeTo::len__meta)?;
         m.function_meta(RangeTo::partial_eq__meta)?;
         m.function_meta(RangeTo::eq__meta)?;
         m.function_meta(RangeTo::partial_cmp__meta)?;
@@ -67,6 +80,11 @@ pub fn module() -> Result<Module, ContextError> {
         m.ty::<Range>()?;
         m.function_meta(Range::iter__meta)?;
         m.function_meta(Range::into_iter__meta)?;
+        m.function_meta(Range::len__meta)?;
+        m.function_meta(Range::is_empty__meta)?;
+        m.function_meta(Range::rev__meta)?;
+        m.function_meta(Range::step_by__meta)?;
+        m.function_meta(range_collect_vec)?;
         m.function_meta(Range::contains__meta)?;
         m.function_meta(Range::partial_eq__meta)?;
         m.function_meta(Range::eq__meta)?;
@@ -248,6 +266,57 @@ fn hash(value: Value) -> VmResult<i64> {
     VmResult::Ok(hasher.finish() as i64)
 }
 
+/// Collect the values produced by a range into a [`Vec`].
+///
+/// # Panics
+///
+/// This panics if the range is not a well-defined range.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!((0..4).collect::<Vec>(), [0, 1, 2, 3]);
+/// assert_eq!(('a'..'d').collect::<Vec>(), ['a', 'b', 'c']);
+/// ```
+#[rune::function(instance, path = collect::<Vec>)]
+fn range_collect_vec(this: &Range) -> VmResult<Vec> {
+    VmResult::Ok(Vec::from(vm_try!(vm_try!(this.iter()).collect::<Value>())))
+}
+
+/// Collect the values produced by an inclusive range into a [`Vec`].
+///
+/// # Panics
+///
+/// This panics if the range is not a well-defined range.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!((0..=3).collect::<Vec>(), [0, 1, 2, 3]);
+/// assert_eq!(('a'..='c').collect::<Vec>(), ['a', 'b', 'c']);
+/// ```
+#[rune::function(instance, path = collect::<Vec>)]
+fn range_inclusive_collect_vec(this: &RangeInclusive) -> VmResult<Vec> {
+    VmResult::Ok(Vec::from(vm_try!(vm_try!(this.iter()).collect::<Value>())))
+}
+
+/// Unbounded ranges can't be collected.
+///
+/// # Panics
+///
+/// This always panics.
+///
+/// ```rune,should_panic
+/// (0..).collect::<Vec>()
+/// ```
+#[rune::function(instance, path = collect::<Vec>)]
+fn range_from_collect_vec(_: &RangeFrom) -> VmResult<Vec> {
+    VmResult::err(VmErrorKind::UnboundedRange {
+        range: "std::ops::RangeFrom",
+        method: "collect",
+    })
+}
+
 /// Advance a generator producing the next value yielded.
 ///
 /// Unlike [`Generator::resume`], this can only consume the yielded values.
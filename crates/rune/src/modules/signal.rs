@@ -0,0 +1,157 @@
+//! The `std::signal` module, which lets scripts communicate through named
+//! signals.
+//!
+//! Handlers are connected to signals by the virtual machine they're running
+//! in, so independent scripts loaded into the same virtual machine can
+//! communicate without knowing about each other. Handlers stay connected until
+//! they are disconnected or the virtual machine is cleared through
+//! [Vm::clear][crate::Vm::clear].
+
+use crate as rune;
+use crate::runtime::{env, Function, Value, VmResult};
+use crate::{Any, ContextError, Module};
+
+/// Construct the `std::signal` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::with_crate_item("std", ["signal"]);
+    module.ty::<Connection>()?;
+    module.function_meta(connect)?;
+    module.function_meta(connect_with_priority)?;
+    module.function_meta(emit)?;
+    Ok(module)
+}
+
+/// A handler connected to a signal, which can be used to disconnect it.
+#[derive(Any, Debug, Clone, Copy)]
+#[rune(module = crate, item = ::std::signal, install_with = Connection::install)]
+struct Connection {
+    id: u64,
+}
+
+impl Connection {
+    /// Disconnect the handler from its signal, returning `true` if it was
+    /// connected.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::signal;
+    ///
+    /// let connection = signal::connect("on_disconnect", |_| panic!("not called"));
+    /// assert!(connection.disconnect());
+    /// assert!(!connection.disconnect());
+    /// assert_eq!(signal::emit("on_disconnect", ()), 0);
+    /// ```
+    #[rune::function(instance)]
+    fn disconnect(&self) -> VmResult<bool> {
+        env::with_signals(|signals| VmResult::Ok(signals.disconnect(self.id)))
+    }
+
+    /// Test if the handler is connected to its signal.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::signal;
+    ///
+    /// let connection = signal::connect("on_connected", |_| ());
+    /// assert!(connection.is_connected());
+    /// connection.disconnect();
+    /// assert!(!connection.is_connected());
+    /// ```
+    #[rune::function(instance)]
+    fn is_connected(&self) -> VmResult<bool> {
+        env::with_signals(|signals| VmResult::Ok(signals.is_connected(self.id)))
+    }
+
+    fn install(m: &mut Module) -> Result<(), ContextError> {
+        m.function_meta(Self::disconnect)?;
+        m.function_meta(Self::is_connected)?;
+        Ok(())
+    }
+}
+
+/// Connect a handler to the given signal, which is called with the payload of
+/// every emitted signal.
+///
+/// The handler has the default priority of `0`, see
+/// [`connect_with_priority`].
+///
+/// # Examples
+///
+/// ```rune
+/// use std::signal;
+///
+/// let saved = [];
+/// signal::connect("on_save", |path| saved.push(path));
+///
+/// signal::emit("on_save", "notes.txt");
+/// assert_eq!(saved, ["notes.txt"]);
+/// ```
+#[rune::function]
+fn connect(signal: &str, handler: Function) -> VmResult<Connection> {
+    connect_inner(signal, 0, handler)
+}
+
+/// Connect a handler to the given signal with a priority.
+///
+/// Handlers with a higher priority are called before handlers with a lower
+/// priority, and handlers with the same priority are called in the order they
+/// were connected.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::signal;
+///
+/// let calls = [];
+/// signal::connect_with_priority("on_load", -1, |_| calls.push("last"));
+/// signal::connect("on_load", |_| calls.push("second"));
+/// signal::connect_with_priority("on_load", 10, |_| calls.push("first"));
+///
+/// signal::emit("on_load", ());
+/// assert_eq!(calls, ["first", "second", "last"]);
+/// ```
+#[rune::function]
+fn connect_with_priority(signal: &str, priority: i64, handler: Function) -> VmResult<Connection> {
+    connect_inner(signal, priority, handler)
+}
+
+fn connect_inner(signal: &str, priority: i64, handler: Function) -> VmResult<Connection> {
+    env::with_signals(|signals| {
+        VmResult::Ok(Connection {
+            id: signals.connect(signal, priority, handler),
+        })
+    })
+}
+
+/// Emit the given signal, calling every handler connected to it with the
+/// payload and returning the number of handlers called.
+///
+/// The values returned by handlers are ignored, and an error raised by a
+/// handler stops the signal from being emitted to the remaining handlers.
+/// Handlers which are connected or disconnected while the signal is being
+/// emitted only take effect the next time it is emitted.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::signal;
+///
+/// assert_eq!(signal::emit("on_exit", ()), 0);
+///
+/// signal::connect("on_exit", |_| ());
+/// assert_eq!(signal::emit("on_exit", ()), 1);
+/// ```
+#[rune::function]
+fn emit(signal: &str, payload: Value) -> VmResult<usize> {
+    let handlers = vm_try!(env::with_signals(|signals| {
+        VmResult::Ok(signals.handlers(signal))
+    }));
+
+    for handler in &handlers {
+        vm_try!(handler.call::<_, Value>((payload.clone(),)));
+    }
+
+    VmResult::Ok(handlers.len())
+}
@@ -39,6 +39,8 @@ pub fn module() -> Result<Module, ContextError> {
     module.function_meta(from_utf8)?;
     module.function_meta(as_bytes)?;
     module.function_meta(into_bytes)?;
+    module.function_meta(from_utf8_lossy)?;
+    module.function_meta(to_bytes)?;
     module.function_meta(clone)?;
     module.function_meta(shrink_to_fit)?;
     module.function_meta(char_at)?;
@@ -58,6 +60,37 @@ pub fn module() -> Result<Module, ContextError> {
     module.associated_function(Protocol::ADD, add)?;
     module.associated_function(Protocol::ADD_ASSIGN, String::push_str)?;
     module.associated_function(Protocol::INDEX_GET, string_index_get)?;
+
+    module.ty::<StringBuilder>()?;
+    module.function_meta(StringBuilder::new)?;
+    module.function_meta(StringBuilder::with_capacity)?;
+    module.function_meta(StringBuilder::push)?;
+    module.function_meta(StringBuilder::push_str)?;
+    module.function_meta(StringBuilder::append)?;
+    module.function_meta(StringBuilder::len)?;
+    module.function_meta(StringBuilder::is_empty)?;
+    module.function_meta(StringBuilder::capacity)?;
+    module.function_meta(StringBuilder::reserve)?;
+    module.function_meta(StringBuilder::clear)?;
+    module.function_meta(StringBuilder::finish)?;
+    module.function_meta(StringBuilder::string_display)?;
+
+    module.ty::<FromUtf8Error>()?;
+    module.function_meta(from_utf8_error_valid_up_to)?;
+    module.function_meta(from_utf8_error_into_bytes)?;
+    module.function_meta(from_utf8_error_string_display)?;
+    module.function_meta(from_utf8_error_string_debug)?;
+
+    #[cfg(feature = "encoding")]
+    {
+        module.ty::<EncodingPolicy>()?;
+        module.ty::<EncodingError>()?;
+        module.function_meta(from_latin1)?;
+        module.function_meta(to_latin1)?;
+        module.function_meta(from_utf16le)?;
+        module.function_meta(to_utf16le)?;
+    }
+
     Ok(module)
 }
 
@@ -403,6 +436,11 @@ fn push(this: &mut String, c: char) {
 
 /// Appends a given string slice onto the end of this `String`.
 ///
+/// This reuses the existing allocation of the string, and only grows it when
+/// it runs out of capacity. Building a string by repeatedly appending to it in
+/// a loop is therefore linear in the size of the result, unlike `a = a + b`
+/// which allocates a new string every time. See also [`StringBuilder`].
+///
 /// # Examples
 ///
 /// Basic usage:
@@ -532,6 +570,63 @@ fn into_bytes(s: String) -> Bytes {
     Bytes::from_vec(s.into_bytes())
 }
 
+/// Converts a slice of bytes to a string, including invalid characters.
+///
+/// Not all byte slices are valid strings, since strings are required to be
+/// valid UTF-8. During this conversion, `from_utf8_lossy()` will replace any
+/// invalid UTF-8 sequences with `U+FFFD REPLACEMENT CHARACTER`, which looks
+/// like this: �
+///
+/// If you are sure that the byte slice is valid UTF-8, or you want the
+/// conversion to fail if it isn't, use [`from_utf8`] instead.
+///
+/// [`from_utf8`]: String::from_utf8
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// let sparkle_heart = Bytes::from_vec([240, 159, 146, 150]);
+/// let sparkle_heart = String::from_utf8_lossy(sparkle_heart);
+///
+/// assert_eq!("💖", sparkle_heart);
+/// ```
+///
+/// Incorrect bytes:
+///
+/// ```rune
+/// let input = b"Hello \xF0\x90\x80World";
+/// let output = String::from_utf8_lossy(input);
+///
+/// assert_eq!("Hello �World", output);
+/// ```
+#[rune::function(free, path = String::from_utf8_lossy)]
+fn from_utf8_lossy(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Returns the UTF-8 encoded bytes of the string without moving it.
+///
+/// The inverse of this method is [`from_utf8`].
+///
+/// [`from_utf8`]: String::from_utf8
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rune
+/// let s = "💖";
+///
+/// assert_eq!(b"\xF0\x9F\x92\x96", s.to_bytes());
+/// assert!(is_readable(s));
+/// ```
+#[rune::function(instance)]
+fn to_bytes(s: &str) -> Bytes {
+    Bytes::from_vec(s.as_bytes().to_vec())
+}
+
 /// Checks that `index`-th byte is the first byte in a UTF-8 code point sequence
 /// or the end of the string.
 ///
@@ -1073,4 +1168,487 @@ fn parse_char(s: &str) -> Result<char, char::ParseCharError> {
     str::parse::<char>(s)
 }
 
+/// A buffer for efficiently building a string out of many parts.
+///
+/// Unlike concatenating strings with `+`, appending to a builder reuses its
+/// allocation, so building a string in a loop is linear in the size of the
+/// result. Any value which implements the `STRING_DISPLAY` protocol can be
+/// appended with [`StringBuilder::append`].
+///
+/// # Examples
+///
+/// ```rune
+/// use std::string::StringBuilder;
+///
+/// let b = StringBuilder::new();
+///
+/// for n in 0..3 {
+///     b.append(n);
+///     b.push(',');
+/// }
+///
+/// b.push_str("end");
+/// assert_eq!(b.finish(), "0,1,2,end");
+/// ```
+#[derive(Any, Default)]
+#[rune(module = crate, item = ::std::string)]
+pub struct StringBuilder {
+    inner: Formatter,
+}
+
+impl StringBuilder {
+    /// Construct a new empty string builder.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::string::StringBuilder;
+    ///
+    /// let b = StringBuilder::new();
+    /// assert!(b.is_empty());
+    /// ```
+    #[rune::function(path = Self::new)]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Construct a new string builder with at least the given capacity, in
+    /// bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::string::StringBuilder;
+    ///
+    /// let b = StringBuilder::with_capacity(16);
+    /// assert!(b.capacity() >= 16);
+    /// ```
+    #[rune::function(path = Self::with_capacity)]
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Formatter::with_capacity(capacity),
+        }
+    }
+
+    /// Append a character to the builder.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::string::StringBuilder;
+    ///
+    /// let b = StringBuilder::new();
+    /// b.push('a');
+    /// b.push('b');
+    /// assert_eq!(b.finish(), "ab");
+    /// ```
+    #[rune::function(instance)]
+    fn push(&mut self, c: char) {
+        self.inner.push(c);
+    }
+
+    /// Append a string to the builder.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::string::StringBuilder;
+    ///
+    /// let b = StringBuilder::new();
+    /// b.push_str("hello ");
+    /// b.push_str("world");
+    /// assert_eq!(b.finish(), "hello world");
+    /// ```
+    #[rune::function(instance)]
+    fn push_str(&mut self, string: &str) {
+        self.inner.push_str(string);
+    }
+
+    /// Append the display representation of a value to the builder, as if it
+    /// was formatted with `{}`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::string::StringBuilder;
+    ///
+    /// let b = StringBuilder::new();
+    /// b.append(1);
+    /// b.append(" + ");
+    /// b.append(2.5);
+    /// b.append(' ');
+    /// b.append(true);
+    /// assert_eq!(b.finish(), "1 + 2.5 true");
+    /// ```
+    #[rune::function(instance)]
+    fn append(&mut self, value: Value) -> VmResult<()> {
+        if let Err(fmt::Error) = vm_try!(value.string_display(&mut self.inner)) {
+            return VmResult::err(Panic::custom("Failed to format value"));
+        }
+
+        VmResult::Ok(())
+    }
+
+    /// Get the length of the string being built, in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::string::StringBuilder;
+    ///
+    /// let b = StringBuilder::new();
+    /// b.push_str("abc");
+    /// assert_eq!(b.len(), 3);
+    /// ```
+    #[rune::function(instance)]
+    fn len(&self) -> usize {
+        self.inner.as_str().len()
+    }
+
+    /// Test if the builder is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::string::StringBuilder;
+    ///
+    /// let b = StringBuilder::new();
+    /// assert!(b.is_empty());
+    /// b.push('a');
+    /// assert!(!b.is_empty());
+    /// ```
+    #[rune::function(instance)]
+    fn is_empty(&self) -> bool {
+        self.inner.as_str().is_empty()
+    }
+
+    /// Get the capacity of the builder, in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::string::StringBuilder;
+    ///
+    /// let b = StringBuilder::with_capacity(10);
+    /// assert!(b.capacity() >= 10);
+    /// ```
+    #[rune::function(instance)]
+    fn capacity(&self) -> usize {
+        self.inner.string.capacity()
+    }
+
+    /// Reserve capacity for at least `additional` more bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::string::StringBuilder;
+    ///
+    /// let b = StringBuilder::new();
+    /// b.reserve(10);
+    /// assert!(b.capacity() >= 10);
+    /// ```
+    #[rune::function(instance)]
+    fn reserve(&mut self, additional: usize) {
+        self.inner.string.reserve(additional);
+    }
+
+    /// Clear the builder, keeping its capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::string::StringBuilder;
+    ///
+    /// let b = StringBuilder::new();
+    /// b.push_str("abc");
+    /// b.clear();
+    /// assert!(b.is_empty());
+    /// ```
+    #[rune::function(instance)]
+    fn clear(&mut self) {
+        self.inner.string.clear();
+    }
+
+    /// Consume the builder and return the string that was built.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::string::StringBuilder;
+    ///
+    /// let b = StringBuilder::new();
+    /// b.push_str("abc");
+    /// assert_eq!(b.finish(), "abc");
+    /// ```
+    #[rune::function(instance)]
+    fn finish(self) -> String {
+        self.inner.into_string()
+    }
+
+    /// Display the string built so far.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::string::StringBuilder;
+    ///
+    /// let b = StringBuilder::new();
+    /// b.push_str("abc");
+    /// assert_eq!(`${b}`, "abc");
+    /// ```
+    #[rune::function(instance, protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self.inner.as_str())
+    }
+}
+
+/// Returns the index in the given bytes up to which valid UTF-8 was verified.
+///
+/// # Examples
+///
+/// ```rune
+/// let bytes = Bytes::from_vec([104, 105, 0xff]);
+/// let error = String::from_utf8(bytes).unwrap_err();
+///
+/// assert_eq!(error.valid_up_to(), 2);
+/// ```
+#[rune::function(instance, path = valid_up_to)]
+fn from_utf8_error_valid_up_to(error: &FromUtf8Error) -> usize {
+    error.utf8_error().valid_up_to()
+}
+
+/// Returns the bytes that were attempted to convert to a string.
+///
+/// # Examples
+///
+/// ```rune
+/// let bytes = Bytes::from_vec([104, 105, 0xff]);
+/// let error = String::from_utf8(bytes).unwrap_err();
+///
+/// assert_eq!(error.into_bytes(), b"hi\xff");
+/// ```
+#[rune::function(instance, path = into_bytes)]
+fn from_utf8_error_into_bytes(error: FromUtf8Error) -> Bytes {
+    Bytes::from_vec(error.into_bytes())
+}
+
+/// Display the reason the bytes failed to convert to a string.
+///
+/// # Examples
+///
+/// ```rune
+/// let error = String::from_utf8(b"hi\xff").unwrap_err();
+///
+/// assert_eq!(`${error}`, "invalid utf-8 sequence of 1 bytes from index 2");
+/// ```
+#[rune::function(instance, protocol = STRING_DISPLAY)]
+fn from_utf8_error_string_display(error: &FromUtf8Error, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", error)
+}
+
+#[rune::function(instance, protocol = STRING_DEBUG)]
+fn from_utf8_error_string_debug(error: &FromUtf8Error, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{:?}", error)
+}
+
+/// The policy to use when input can't be converted between encodings.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::string::EncodingPolicy;
+///
+/// assert_eq!("ö€".to_latin1(EncodingPolicy::Replace)?, b"\xf6?");
+/// assert!("ö€".to_latin1(EncodingPolicy::Error).is_err());
+/// ```
+#[cfg(feature = "encoding")]
+#[derive(Any, Debug, Clone, Copy, PartialEq, Eq)]
+#[rune(module = crate, item = ::std::string)]
+enum EncodingPolicy {
+    /// Replace input which can't be converted. When decoding into a string
+    /// this is `U+FFFD REPLACEMENT CHARACTER`, and when encoding into bytes it
+    /// is `?`.
+    #[rune(constructor)]
+    Replace,
+    /// Fail with an [`EncodingError`].
+    #[rune(constructor)]
+    Error,
+}
+
+/// Error raised when converting between encodings with
+/// [`EncodingPolicy::Error`].
+#[cfg(feature = "encoding")]
+#[derive(Any, Debug, Clone, Copy)]
+#[rune(module = crate, item = ::std::string, install_with = EncodingError::install)]
+struct EncodingError {
+    encoding: &'static str,
+    valid_up_to: usize,
+}
+
+#[cfg(feature = "encoding")]
+impl EncodingError {
+    /// Returns the index in the input up to which it could be converted.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::string::EncodingPolicy;
+    ///
+    /// let error = "ab€".to_latin1(EncodingPolicy::Error).unwrap_err();
+    /// assert_eq!(error.valid_up_to(), 2);
+    /// ```
+    #[rune::function(instance)]
+    fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    #[rune::function(instance, protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "input can't be converted to or from {} after index {}",
+            self.encoding, self.valid_up_to
+        )
+    }
+
+    fn install(m: &mut Module) -> Result<(), ContextError> {
+        m.function_meta(Self::valid_up_to)?;
+        m.function_meta(Self::string_display)?;
+        Ok(())
+    }
+}
+
+/// Decodes latin-1 (ISO-8859-1) encoded bytes into a string.
+///
+/// Every byte is a valid latin-1 character, so this conversion can't fail.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(String::from_latin1(b"caf\xe9"), "café");
+/// ```
+#[cfg(feature = "encoding")]
+#[rune::function(free, path = String::from_latin1)]
+fn from_latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| b as char).collect()
+}
+
+/// Encodes the string as latin-1 (ISO-8859-1) bytes.
+///
+/// Characters which don't exist in latin-1 are handled according to the given
+/// `policy`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::string::EncodingPolicy;
+///
+/// assert_eq!("café".to_latin1(EncodingPolicy::Error)?, b"caf\xe9");
+/// assert_eq!("a€b".to_latin1(EncodingPolicy::Replace)?, b"a?b");
+/// ```
+#[cfg(feature = "encoding")]
+#[rune::function(instance)]
+fn to_latin1(s: &str, policy: &EncodingPolicy) -> Result<Bytes, EncodingError> {
+    let mut bytes = Vec::with_capacity(s.len());
+
+    for (index, c) in s.char_indices() {
+        match u8::try_from(c) {
+            Ok(b) => bytes.push(b),
+            Err(..) if *policy == EncodingPolicy::Replace => bytes.push(b'?'),
+            Err(..) => {
+                return Err(EncodingError {
+                    encoding: "latin-1",
+                    valid_up_to: index,
+                });
+            }
+        }
+    }
+
+    Ok(Bytes::from_vec(bytes))
+}
+
+/// Decodes UTF-16 little-endian encoded bytes into a string.
+///
+/// Unpaired surrogates and a trailing odd byte are handled according to the
+/// given `policy`.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::string::EncodingPolicy;
+///
+/// let bytes = b"h\0i\0";
+/// assert_eq!(String::from_utf16le(bytes, EncodingPolicy::Error)?, "hi");
+///
+/// let bytes = b"h\0\x00\xd8i\0";
+/// assert_eq!(String::from_utf16le(bytes, EncodingPolicy::Replace)?, "h�i");
+///
+/// let error = String::from_utf16le(bytes, EncodingPolicy::Error).unwrap_err();
+/// assert_eq!(error.valid_up_to(), 2);
+/// ```
+#[cfg(feature = "encoding")]
+#[rune::function(free, path = String::from_utf16le)]
+fn from_utf16le(bytes: &[u8], policy: &EncodingPolicy) -> Result<String, EncodingError> {
+    let chunks = bytes.chunks_exact(2);
+    let remainder = chunks.remainder();
+    let units = chunks.map(|c| u16::from_le_bytes([c[0], c[1]]));
+
+    let mut string = String::with_capacity(bytes.len() / 2);
+    let mut index = 0;
+
+    for c in char::decode_utf16(units) {
+        match c {
+            Ok(c) => {
+                string.push(c);
+                index += c.len_utf16() * 2;
+            }
+            Err(..) if *policy == EncodingPolicy::Replace => {
+                string.push(char::REPLACEMENT_CHARACTER);
+                index += 2;
+            }
+            Err(..) => {
+                return Err(EncodingError {
+                    encoding: "utf-16le",
+                    valid_up_to: index,
+                });
+            }
+        }
+    }
+
+    if !remainder.is_empty() {
+        if *policy != EncodingPolicy::Replace {
+            return Err(EncodingError {
+                encoding: "utf-16le",
+                valid_up_to: index,
+            });
+        }
+
+        string.push(char::REPLACEMENT_CHARACTER);
+    }
+
+    Ok(string)
+}
+
+/// Encodes the string as UTF-16 little-endian bytes.
+///
+/// Every string can be encoded as UTF-16, so this conversion can't fail.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!("hi".to_utf16le(), b"h\0i\0");
+/// ```
+#[cfg(feature = "encoding")]
+#[rune::function(instance)]
+fn to_utf16le(s: &str) -> Bytes {
+    let mut bytes = Vec::with_capacity(s.len() * 2);
+
+    for unit in s.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+
+    Bytes::from_vec(bytes)
+}
+
 crate::__internal_impl_any!(::std::string, FromUtf8Error);
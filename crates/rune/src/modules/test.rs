@@ -2,14 +2,19 @@
 //!
 //! [Rune Language]: https://rune-rs.github.io
 
-use crate::no_std::vec::Vec;
+use core::fmt::{self, Write};
+
+use crate::no_std::prelude::*;
 
 use crate as rune;
 use crate::ast;
+use crate::ast::{Span, Spanned};
 use crate::compile;
-use crate::macros::{quote, FormatArgs, MacroContext, TokenStream};
+use crate::macros::{quote, FormatArgs, MacroContext, ToTokens, TokenStream};
 use crate::parse::Parser;
-use crate::runtime::Function;
+use crate::runtime::{
+    self, env, Formatter, Function, Panic, Value, ValueDiff, VmErrorKind, VmResult,
+};
 use crate::{Any, ContextError, Module, T};
 
 /// A helper type to capture benchmarks.
@@ -38,20 +43,143 @@ pub fn module() -> Result<Module, ContextError> {
     module.macro_meta(assert)?;
     module.macro_meta(assert_eq)?;
     module.macro_meta(assert_ne)?;
+    module.function_meta(assertion_failed)?.hidden();
     module.ty::<Bencher>()?.docs([
         "A type to perform benchmarks.",
         "",
         "This is the type of the argument to any function which is annotated with `#[bench]`",
     ]);
     module.function_meta(Bencher::iter)?;
+    module.ty::<ValueDiff>()?.docs([
+        "The structural difference between two values.",
+        "",
+        "This is produced by `std::test::diff`.",
+    ]);
+    module.function_meta(diff)?;
+    module.function_meta(diff_is_empty)?;
+    module.function_meta(diff_len)?;
+    module.function_meta(diff_string_display)?;
+    module.function_meta(mock)?;
+    module.function_meta(unmock)?;
     Ok(module)
 }
 
+/// Compute the structural difference between two values.
+///
+/// Objects, structs, vectors, tuples and variants are compared field by field
+/// and element by element, while all other values are compared for equality.
+/// Formatting the difference lists one change per line, which is useful for
+/// reporting why two values aren't equal.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::test::diff;
+///
+/// let a = #{ name: "John", age: 42 };
+/// let b = #{ name: "John", age: 43, admin: true };
+///
+/// let d = diff(a, b);
+/// assert_eq!(d.len(), 2);
+/// assert_eq!(`${d}`, "+ admin: true\n~ age: 42 -> 43\n");
+///
+/// assert!(diff([1, 2], [1, 2]).is_empty());
+/// ```
+#[rune::function]
+fn diff(a: Value, b: Value) -> VmResult<ValueDiff> {
+    runtime::diff(&a, &b)
+}
+
+/// Test if the compared values are equal.
+#[rune::function(instance, path = is_empty)]
+fn diff_is_empty(diff: &ValueDiff) -> bool {
+    diff.is_empty()
+}
+
+/// The number of changes between the compared values.
+#[rune::function(instance, path = len)]
+fn diff_len(diff: &ValueDiff) -> usize {
+    diff.len()
+}
+
+/// Format the changes between the compared values, one per line.
+#[rune::function(instance, protocol = STRING_DISPLAY)]
+fn diff_string_display(diff: &ValueDiff, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{diff}")
+}
+
+/// Shadow the `target` function in the context with `replacement`, for the
+/// remainder of the current test.
+///
+/// Calls to the target by path call the replacement instead, which replaces
+/// any previous mock of the same function. Mocks are removed once the test
+/// completes, or through `std::test::unmock`.
+///
+/// Mocking is only possible when running in test mode, like through `rune
+/// test`, and is otherwise an error.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::test::{mock, unmock};
+///
+/// fn greet(name) {
+///     String::from(name) + "!"
+/// }
+///
+/// mock(String::from, |name| "mocked");
+/// assert_eq!(greet("John"), "mocked!");
+///
+/// unmock(String::from);
+/// assert_eq!(greet("John"), "John!");
+/// ```
+#[rune::function]
+fn mock(target: Function, replacement: Function) -> VmResult<()> {
+    let hash = target.type_hash();
+
+    vm_try!(env::with(|context, _| {
+        if context.function(hash).is_none() {
+            return VmResult::err(VmErrorKind::MockNotContextFunction { hash });
+        }
+
+        VmResult::Ok(())
+    }));
+
+    env::with_mocks(|mocks| {
+        if !mocks.insert(hash, replacement) {
+            return VmResult::err(VmErrorKind::MockOutsideTestMode);
+        }
+
+        VmResult::Ok(())
+    })
+}
+
+/// Restore the `target` function which was mocked through `std::test::mock`,
+/// returning `true` if it was mocked.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::test::{mock, unmock};
+///
+/// mock(String::from, |name| "mocked");
+/// assert!(unmock(String::from));
+/// assert!(!unmock(String::from));
+/// ```
+#[rune::function]
+fn unmock(target: Function) -> VmResult<bool> {
+    let hash = target.type_hash();
+    env::with_mocks(|mocks| VmResult::Ok(mocks.remove(hash)))
+}
+
 /// Assert that the expression provided as an argument is true, or cause a vm
 /// panic.
 ///
 /// The second argument can optionally be used to format a panic message.
 ///
+/// If the expression is a comparison like `a == b` or `a < b`, both sides are
+/// evaluated once and their values are included in the panic message.
+///
 /// This is useful when writing test cases.
 ///
 /// # Examples
@@ -77,18 +205,24 @@ pub(crate) fn assert(
         None
     };
 
+    if let ast::Expr::Binary(binary) = &expr {
+        if is_comparison(binary.op) {
+            return expand_assert(cx, &binary.lhs, &binary.op, &binary.rhs, message.as_ref());
+        }
+    }
+
+    let condition = format!("assertion `{}` failed", source(cx, &expr));
+    let condition = cx.lit(&condition);
+
     let output = if let Some(message) = &message {
         let expanded = message.expand(cx)?;
 
         quote!(if !(#expr) {
-            ::std::panic("assertion failed: " + (#expanded));
+            ::std::panic(#condition + ": " + (#expanded));
         })
     } else {
-        let message = format!("assertion failed: {}", cx.stringify(&expr));
-        let message = cx.lit(&message);
-
         quote!(if !(#expr) {
-            ::std::panic(#message);
+            ::std::panic(#condition);
         })
     };
 
@@ -99,6 +233,9 @@ pub(crate) fn assert(
 ///
 /// The third argument can optionally be used to format a panic message.
 ///
+/// On failure the panic message includes the source of both arguments as well
+/// as their debug representations.
+///
 /// # Examples
 ///
 /// ```rune
@@ -111,8 +248,6 @@ pub(crate) fn assert_eq(
     cx: &mut MacroContext<'_, '_, '_>,
     stream: &TokenStream,
 ) -> compile::Result<TokenStream> {
-    use crate as rune;
-
     let mut p = Parser::from_token_stream(stream, cx.input_span());
     let left = p.parse::<ast::Expr>()?;
     p.parse::<T![,]>()?;
@@ -124,43 +259,19 @@ pub(crate) fn assert_eq(
         None
     };
 
-    let output = if let Some(message) = &message {
-        let message = message.expand(cx)?;
-
-        quote! {{
-            let left = #left;
-            let right = #right;
-
-            if !(left == right) {
-                let message = #message;
-                message += ::std::fmt::format!("\nleft: {:?}", left);
-                message += ::std::fmt::format!("\nright: {:?}", right);
-                ::std::panic("assertion failed (left == right): " + message);
-            }
-        }}
-    } else {
-        let message = cx.lit("assertion failed (left == right):");
-
-        quote! {{
-            let left = #left;
-            let right = #right;
-
-            if !(left == right) {
-                let message = ::std::string::String::from(#message);
-                message += ::std::fmt::format!("\nleft: {:?}", left);
-                message += ::std::fmt::format!("\nright: {:?}", right);
-                ::std::panic(message);
-            }
-        }}
-    };
-
-    Ok(output.into_token_stream(cx))
+    let op = ast::BinOp::Eq(ast::EqEq {
+        span: cx.macro_span(),
+    });
+    expand_assert(cx, &left, &op, &right, message.as_ref())
 }
 
 /// Assert that the two arguments provided are not equal, or cause a vm panic.
 ///
 /// The third argument can optionally be used to format a panic message.
 ///
+/// On failure the panic message includes the source of both arguments as well
+/// as their debug representations.
+///
 /// # Examples
 ///
 /// ```rune
@@ -173,8 +284,6 @@ pub(crate) fn assert_ne(
     cx: &mut MacroContext<'_, '_, '_>,
     stream: &TokenStream,
 ) -> compile::Result<TokenStream> {
-    use crate as rune;
-
     let mut p = Parser::from_token_stream(stream, cx.input_span());
     let left = p.parse::<ast::Expr>()?;
     p.parse::<T![,]>()?;
@@ -186,35 +295,147 @@ pub(crate) fn assert_ne(
         None
     };
 
-    let output = if let Some(message) = &message {
-        let message = message.expand(cx)?;
-
-        quote! {{
-            let left = #left;
-            let right = #right;
-
-            if !(left != right) {
-                let message = #message;
-                message += ::std::fmt::format!("\nleft: {:?}", left);
-                message += ::std::fmt::format!("\nright: {:?}", right);
-                ::std::panic("assertion failed (left != right): " + message);
-            }
-        }}
-    } else {
-        let message = cx.lit("assertion failed (left != right):");
-
-        quote! {{
-            let left = #left;
-            let right = #right;
-
-            if !(left != right) {
-                let message = ::std::string::String::from(#message);
-                message += ::std::fmt::format!("\nleft: {:?}", left);
-                message += ::std::fmt::format!("\nright: {:?}", right);
-                ::std::panic(message);
-            }
-        }}
+    let op = ast::BinOp::Neq(ast::BangEq {
+        span: cx.macro_span(),
+    });
+    expand_assert(cx, &left, &op, &right, message.as_ref())
+}
+
+/// Test if the given binary operator is a comparison.
+fn is_comparison(op: ast::BinOp) -> bool {
+    matches!(
+        op,
+        ast::BinOp::Eq(..)
+            | ast::BinOp::Neq(..)
+            | ast::BinOp::Gt(..)
+            | ast::BinOp::Lt(..)
+            | ast::BinOp::Gte(..)
+            | ast::BinOp::Lte(..)
+    )
+}
+
+/// Expand an assertion comparing `left` and `right` using `op`.
+///
+/// Both operands are evaluated exactly once, and their debug representations
+/// are included in the panic message if the assertion fails:
+///
+/// ```text
+/// assertion `a == b` failed: optional message
+///   left: 1
+///  right: 2
+/// ```
+fn expand_assert(
+    cx: &mut MacroContext<'_, '_, '_>,
+    left: &ast::Expr,
+    op: &ast::BinOp,
+    right: &ast::Expr,
+    message: Option<&FormatArgs>,
+) -> compile::Result<TokenStream> {
+    use crate as rune;
+
+    let left_source = source(cx, left);
+    let op_source = source(cx, op);
+    let right_source = source(cx, right);
+
+    let condition = format!("assertion `{left_source} {op_source} {right_source}` failed");
+    let condition = cx.lit(&condition);
+
+    let message = match message {
+        Some(message) => {
+            let message = message.expand(cx)?;
+            quote!(#condition + ": " + (#message))
+        }
+        None => quote!(#condition),
     };
 
+    let left_span = span_lit(cx, left.span());
+    let right_span = span_lit(cx, right.span());
+
+    // NB: Both operands are evaluated in a single tuple expression, so that
+    // the bindings introduced here can't shadow variables referenced by the
+    // right-hand side.
+    let output = quote! {{
+        let (left, right) = (#left, #right);
+
+        if !(left #op right) {
+            ::std::test::assertion_failed(#message, left, right, #left_span, #right_span);
+        }
+    }};
+
     Ok(output.into_token_stream(cx))
 }
+
+/// Get the source text of the given tokens as written in the macro input.
+///
+/// Falls back to stringifying the tokens, which separates every token with a
+/// space, if they weren't written in the input.
+fn source<T>(cx: &mut MacroContext<'_, '_, '_>, tokens: &T) -> String
+where
+    T: Spanned + ToTokens,
+{
+    if let Some(source) = cx.input_source(tokens.span()) {
+        return source.to_owned();
+    }
+
+    cx.stringify(tokens).to_string()
+}
+
+/// Construct a tuple of the start and end of the given span, as expected by
+/// [assertion_failed].
+fn span_lit(cx: &mut MacroContext<'_, '_, '_>, span: Span) -> TokenStream {
+    let start = cx.lit(span.start.into_usize());
+    let end = cx.lit(span.end.into_usize());
+    quote!((#start, #end)).into_token_stream(cx)
+}
+
+/// Panic with the given `message` because an assertion comparing `left` and
+/// `right` failed.
+///
+/// The debug representations of both operands are appended to the message.
+/// Diagnostics for the panic point out the operands at `left_span` and
+/// `right_span`, which are the start and end byte offsets of the operands in
+/// the source the assertion is in.
+///
+/// This is what `assert_eq!`, `assert_ne!` and `assert!` with a comparison
+/// expand into, and it's hidden from generated documentation since it isn't
+/// meant to be called directly.
+///
+/// # Examples
+///
+/// ```rune,should_panic
+/// std::test::assertion_failed("assertion `1 == 2` failed", 1, 2, (0, 1), (5, 6));
+/// ```
+#[rune::function]
+fn assertion_failed(
+    message: &str,
+    left: Value,
+    right: Value,
+    left_span: (u32, u32),
+    right_span: (u32, u32),
+) -> VmResult<()> {
+    let left = vm_try!(debug(&left));
+    let right = vm_try!(debug(&right));
+
+    let mut message = message.to_owned();
+    let _ = write!(message, "\n  left: {left}\n right: {right}");
+
+    let left_span = Span::new(left_span.0, left_span.1);
+    let right_span = Span::new(right_span.0, right_span.1);
+
+    VmResult::err(
+        Panic::custom(message)
+            .with_label(left_span, format_args!("left: {left}"))
+            .with_label(right_span, format_args!("right: {right}")),
+    )
+}
+
+/// Format the debug representation of the given value.
+fn debug(value: &Value) -> VmResult<String> {
+    let mut f = Formatter::new();
+
+    if let Err(fmt::Error) = vm_try!(value.string_debug(&mut f)) {
+        return VmResult::err(Panic::msg("Failed to format value"));
+    }
+
+    VmResult::Ok(f.into_string())
+}
@@ -18,6 +18,8 @@ pub fn module() -> Result<Module, ContextError> {
     m.function_meta(get)?;
     m.function_meta(iter)?;
     m.function_meta(into_iter)?;
+    m.function_meta(enumerate)?;
+    m.function_meta(to_vec)?;
     m.function_meta(partial_eq)?;
     m.function_meta(eq)?;
     m.function_meta(partial_cmp)?;
@@ -110,6 +112,37 @@ fn into_iter(this: Ref<Tuple>) -> Iterator {
     Vec::iter_ref(Ref::map(this, |tuple| &**tuple))
 }
 
+/// Construct an iterator over the tuple which yields each element together
+/// with its index.
+///
+/// # Examples
+///
+/// ```rune
+/// let tuple = ('a', 'b');
+/// assert_eq!(tuple.enumerate().collect::<Vec>(), [(0, 'a'), (1, 'b')]);
+/// ```
+#[rune::function(instance)]
+fn enumerate(this: Ref<Tuple>) -> Iterator {
+    Vec::iter_ref(Ref::map(this, |tuple| &**tuple)).enumerate()
+}
+
+/// Copy the elements of the tuple into a new vector.
+///
+/// # Examples
+///
+/// ```rune
+/// let tuple = (1, 2, 3);
+/// let vec = tuple.to_vec();
+/// vec.push(4);
+///
+/// assert_eq!(vec, [1, 2, 3, 4]);
+/// assert_eq!(tuple, (1, 2, 3));
+/// ```
+#[rune::function(instance)]
+fn to_vec(this: &Tuple) -> Vec {
+    Vec::from(this.to_vec())
+}
+
 /// Perform a partial equality check with this tuple.
 ///
 /// This can take any argument which can be converted into an iterator using
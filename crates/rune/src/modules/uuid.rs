@@ -0,0 +1,281 @@
+//! The `std::uuid` module.
+
+use core::fmt::{self, Write};
+
+use crate::no_std::prelude::*;
+
+use crate as rune;
+use crate::runtime::{Bytes, Formatter};
+use crate::{Any, ContextError, Module};
+
+#[rune::module(::std::uuid)]
+/// Generating and parsing universally unique identifiers.
+///
+/// This module is only available if the `uuid` feature is enabled.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::from_meta(self::module_meta);
+    module.ty::<Uuid>()?;
+    module.ty::<ParseError>()?;
+    module.function_meta(Uuid::new_v4)?;
+    module.function_meta(Uuid::new_v7)?;
+    module.function_meta(Uuid::nil)?;
+    module.function_meta(Uuid::parse)?;
+    module.function_meta(Uuid::from_bytes)?;
+    module.function_meta(Uuid::as_bytes)?;
+    module.function_meta(Uuid::version)?;
+    module.function_meta(Uuid::is_nil)?;
+    module.function_meta(Uuid::to_simple)?;
+    module.function_meta(Uuid::string_display)?;
+    module.function_meta(Uuid::string_debug)?;
+    module.function_meta(Uuid::partial_eq)?;
+    module.function_meta(Uuid::eq)?;
+    module.function_meta(ParseError::string_display)?;
+    Ok(module)
+}
+
+/// A universally unique identifier.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::uuid::Uuid;
+///
+/// let id = Uuid::parse("67e55044-10b1-426f-9247-bb680e5fe0c8")?;
+/// assert_eq!(`${id}`, "67e55044-10b1-426f-9247-bb680e5fe0c8");
+/// assert_eq!(id.version(), 4);
+/// ```
+#[derive(Any, Debug, Clone, Copy, PartialEq, Eq)]
+#[rune(module = crate, item = ::std::uuid)]
+pub struct Uuid {
+    inner: uuid::Uuid,
+}
+
+impl Uuid {
+    /// Generate a new random (version 4) uuid.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::uuid::Uuid;
+    ///
+    /// let a = Uuid::new_v4();
+    /// let b = Uuid::new_v4();
+    /// assert!(a != b);
+    /// assert_eq!(a.version(), 4);
+    /// ```
+    #[rune::function(path = Self::new_v4)]
+    fn new_v4() -> Self {
+        Self {
+            inner: uuid::Uuid::new_v4(),
+        }
+    }
+
+    /// Generate a new time-ordered (version 7) uuid based on the current unix
+    /// timestamp.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::uuid::Uuid;
+    ///
+    /// let id = Uuid::new_v7();
+    /// assert_eq!(id.version(), 7);
+    /// ```
+    #[rune::function(path = Self::new_v7)]
+    fn new_v7() -> Self {
+        Self {
+            inner: uuid::Uuid::now_v7(),
+        }
+    }
+
+    /// Construct the nil uuid, where all bits are set to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::uuid::Uuid;
+    ///
+    /// assert!(Uuid::nil().is_nil());
+    /// ```
+    #[rune::function(path = Self::nil)]
+    fn nil() -> Self {
+        Self {
+            inner: uuid::Uuid::nil(),
+        }
+    }
+
+    /// Parse a uuid from a string.
+    ///
+    /// Both the hyphenated and the simple forms are supported, as are
+    /// surrounding braces and the `urn:uuid:` prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::uuid::Uuid;
+    ///
+    /// let a = Uuid::parse("67e55044-10b1-426f-9247-bb680e5fe0c8")?;
+    /// let b = Uuid::parse("67e5504410b1426f9247bb680e5fe0c8")?;
+    /// assert_eq!(a, b);
+    ///
+    /// assert!(Uuid::parse("not a uuid").is_err());
+    /// ```
+    #[rune::function(path = Self::parse)]
+    fn parse(string: &str) -> Result<Self, ParseError> {
+        match uuid::Uuid::parse_str(string) {
+            Ok(inner) => Ok(Self { inner }),
+            Err(error) => Err(ParseError { inner: error }),
+        }
+    }
+
+    /// Construct a uuid from exactly 16 bytes.
+    ///
+    /// Returns `None` if the byte array is of the wrong length.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::uuid::Uuid;
+    ///
+    /// let id = Uuid::new_v4();
+    /// assert_eq!(Uuid::from_bytes(id.as_bytes()), Some(id));
+    /// assert_eq!(Uuid::from_bytes(b"abc"), None);
+    /// ```
+    #[rune::function(path = Self::from_bytes)]
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let inner = uuid::Uuid::from_slice(bytes).ok()?;
+        Some(Self { inner })
+    }
+
+    /// Get the 16 bytes that make up the uuid.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::uuid::Uuid;
+    ///
+    /// assert_eq!(Uuid::nil().as_bytes().len(), 16);
+    /// ```
+    #[rune::function(instance)]
+    fn as_bytes(&self) -> Bytes {
+        Bytes::from_slice(self.inner.as_bytes())
+    }
+
+    /// Get the version number of the uuid, or `0` if it's not recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::uuid::Uuid;
+    ///
+    /// assert_eq!(Uuid::new_v4().version(), 4);
+    /// assert_eq!(Uuid::nil().version(), 0);
+    /// ```
+    #[rune::function(instance)]
+    fn version(&self) -> usize {
+        self.inner.get_version_num()
+    }
+
+    /// Test if this is the nil uuid.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::uuid::Uuid;
+    ///
+    /// assert!(Uuid::nil().is_nil());
+    /// assert!(!Uuid::new_v4().is_nil());
+    /// ```
+    #[rune::function(instance)]
+    fn is_nil(&self) -> bool {
+        self.inner.is_nil()
+    }
+
+    /// Format the uuid in its simple form, without hyphens.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::uuid::Uuid;
+    ///
+    /// let id = Uuid::parse("67e55044-10b1-426f-9247-bb680e5fe0c8")?;
+    /// assert_eq!(id.to_simple(), "67e5504410b1426f9247bb680e5fe0c8");
+    /// ```
+    #[rune::function(instance)]
+    fn to_simple(&self) -> String {
+        self.inner.simple().to_string()
+    }
+
+    /// Format the uuid in its hyphenated form.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::uuid::Uuid;
+    ///
+    /// let id = Uuid::nil();
+    /// assert_eq!(`${id}`, "00000000-0000-0000-0000-000000000000");
+    /// ```
+    #[rune::function(instance, protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.inner.hyphenated())
+    }
+
+    /// Debug format the uuid.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::uuid::Uuid;
+    ///
+    /// let id = Uuid::nil();
+    /// assert_eq!(format!("{:?}", id), "00000000-0000-0000-0000-000000000000");
+    /// ```
+    #[rune::function(instance, protocol = STRING_DEBUG)]
+    fn string_debug(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.inner)
+    }
+
+    /// Test two uuids for equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::uuid::Uuid;
+    ///
+    /// assert!(Uuid::nil() == Uuid::nil());
+    /// ```
+    #[rune::function(instance, protocol = PARTIAL_EQ)]
+    fn partial_eq(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    /// Test two uuids for total equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::eq;
+    /// use std::uuid::Uuid;
+    ///
+    /// assert!(eq(Uuid::nil(), Uuid::nil()));
+    /// ```
+    #[rune::function(instance, protocol = EQ)]
+    fn eq(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// An error raised when parsing a [`Uuid`] fails.
+#[derive(Any, Debug)]
+#[rune(module = crate, item = ::std::uuid)]
+pub struct ParseError {
+    inner: uuid::Error,
+}
+
+impl ParseError {
+    #[rune::function(instance, protocol = STRING_DISPLAY)]
+    fn string_display(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.inner)
+    }
+}
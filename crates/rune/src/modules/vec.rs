@@ -45,9 +45,19 @@ pub fn module() -> Result<Module, ContextError> {
     m.function_meta(push)?;
     m.function_meta(remove)?;
     m.function_meta(insert)?;
+    m.function_meta(swap_remove)?;
+    m.function_meta(rotate_left)?;
+    m.function_meta(rotate_right)?;
+    m.function_meta(fill)?;
+    m.function_meta(split_off)?;
+    m.function_meta(concat)?;
     m.function_meta(clone)?;
     m.function_meta(sort_by)?;
     m.function_meta(sort)?;
+    m.function_meta(sum)?;
+    m.function_meta(product)?;
+    m.function_meta(min)?;
+    m.function_meta(max)?;
     m.function_meta(into_iter)?;
     m.function_meta(index_set)?;
     m.function_meta(index_get)?;
@@ -444,6 +454,183 @@ fn insert(this: &mut Vec, index: usize, value: Value) -> VmResult<()> {
     VmResult::Ok(())
 }
 
+/// Removes an element from the vector and returns it.
+///
+/// The removed element is replaced by the last element of the vector.
+///
+/// This does not preserve ordering, but is *O*(1). If you need to preserve
+/// the element order, use [`remove`] instead.
+///
+/// [`remove`]: Vec::remove
+///
+/// # Panics
+///
+/// Panics if `index` is out of bounds.
+///
+/// ```rune,should_panic
+/// let v = [1, 2, 3];
+/// v.swap_remove(3);
+/// ```
+///
+/// # Examples
+///
+/// ```rune
+/// let v = ["foo", "bar", "baz", "qux"];
+///
+/// assert_eq!(v.swap_remove(1), "bar");
+/// assert_eq!(v, ["foo", "qux", "baz"]);
+///
+/// assert_eq!(v.swap_remove(0), "foo");
+/// assert_eq!(v, ["baz", "qux"]);
+/// ```
+#[rune::function(instance)]
+fn swap_remove(this: &mut Vec, index: usize) -> VmResult<Value> {
+    if index >= this.len() {
+        return VmResult::err(VmErrorKind::OutOfRange {
+            index: index.into(),
+            length: this.len().into(),
+        });
+    }
+
+    let value = this.swap_remove(index);
+    VmResult::Ok(value)
+}
+
+/// Rotates the vector in-place such that the first `mid` elements move to the
+/// end while the last `len - mid` elements move to the front.
+///
+/// # Panics
+///
+/// Panics if `mid` is greater than the length of the vector.
+///
+/// ```rune,should_panic
+/// let v = [1, 2, 3];
+/// v.rotate_left(4);
+/// ```
+///
+/// # Examples
+///
+/// ```rune
+/// let v = ['a', 'b', 'c', 'd', 'e', 'f'];
+/// v.rotate_left(2);
+/// assert_eq!(v, ['c', 'd', 'e', 'f', 'a', 'b']);
+/// ```
+#[rune::function(instance)]
+fn rotate_left(this: &mut Vec, mid: usize) -> VmResult<()> {
+    if mid > this.len() {
+        return VmResult::err(VmErrorKind::OutOfRange {
+            index: mid.into(),
+            length: this.len().into(),
+        });
+    }
+
+    this.rotate_left(mid);
+    VmResult::Ok(())
+}
+
+/// Rotates the vector in-place such that the first `len - k` elements move to
+/// the end while the last `k` elements move to the front.
+///
+/// # Panics
+///
+/// Panics if `k` is greater than the length of the vector.
+///
+/// ```rune,should_panic
+/// let v = [1, 2, 3];
+/// v.rotate_right(4);
+/// ```
+///
+/// # Examples
+///
+/// ```rune
+/// let v = ['a', 'b', 'c', 'd', 'e', 'f'];
+/// v.rotate_right(2);
+/// assert_eq!(v, ['e', 'f', 'a', 'b', 'c', 'd']);
+/// ```
+#[rune::function(instance)]
+fn rotate_right(this: &mut Vec, k: usize) -> VmResult<()> {
+    if k > this.len() {
+        return VmResult::err(VmErrorKind::OutOfRange {
+            index: k.into(),
+            length: this.len().into(),
+        });
+    }
+
+    this.rotate_right(k);
+    VmResult::Ok(())
+}
+
+/// Fills the vector with elements by cloning `value`.
+///
+/// Note that cloning a value like a vector or an object only clones the
+/// reference to it, so every element will refer to the same value.
+///
+/// # Examples
+///
+/// ```rune
+/// let v = [1, 2, 3];
+/// v.fill(0);
+/// assert_eq!(v, [0, 0, 0]);
+/// ```
+#[rune::function(instance)]
+fn fill(this: &mut Vec, value: Value) {
+    this.fill(value);
+}
+
+/// Splits the vector into two at the given index.
+///
+/// Returns a newly allocated vector containing the elements in the range
+/// `[at, len)`. After the call, the original vector will be left containing
+/// the elements `[0, at)`.
+///
+/// # Panics
+///
+/// Panics if `at > len`.
+///
+/// ```rune,should_panic
+/// let v = [1, 2, 3];
+/// v.split_off(4);
+/// ```
+///
+/// # Examples
+///
+/// ```rune
+/// let v = [1, 2, 3];
+/// let v2 = v.split_off(1);
+/// assert_eq!(v, [1]);
+/// assert_eq!(v2, [2, 3]);
+/// ```
+#[rune::function(instance)]
+fn split_off(this: &mut Vec, at: usize) -> VmResult<Vec> {
+    if at > this.len() {
+        return VmResult::err(VmErrorKind::OutOfRange {
+            index: at.into(),
+            length: this.len().into(),
+        });
+    }
+
+    VmResult::Ok(this.split_off(at))
+}
+
+/// Flattens a vector of iterable values into a single vector.
+///
+/// If every element is a vector, their elements are copied directly.
+/// Otherwise each element is iterated over.
+///
+/// # Examples
+///
+/// ```rune
+/// let v = [[1, 2], [3], []];
+/// assert_eq!(v.concat(), [1, 2, 3]);
+///
+/// let v = [[1, 2], (3, 4)];
+/// assert_eq!(v.concat(), [1, 2, 3, 4]);
+/// ```
+#[rune::function(instance)]
+fn concat(this: &Vec) -> VmResult<Vec> {
+    this.concat()
+}
+
 /// Clone the vector.
 ///
 /// # Examples
@@ -462,6 +649,89 @@ fn clone(this: &Vec) -> Vec {
     this.clone()
 }
 
+/// Sums the elements of the vector.
+///
+/// Vectors which only contain integers or only contain floats are summed
+/// directly, anything else is summed using the [`ADD`] protocol. An empty
+/// vector sums to `0`.
+///
+/// # Errors
+///
+/// Errors if summing integers overflows.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!([1, 2, 3].sum(), 6);
+/// assert_eq!([1.0, 2.5].sum(), 3.5);
+/// assert_eq!(["a", "b"].sum(), "ab");
+/// assert_eq!([].sum(), 0);
+/// ```
+#[rune::function(instance)]
+fn sum(this: &Vec) -> VmResult<Value> {
+    Vec::sum_with(this, &mut EnvProtocolCaller)
+}
+
+/// Multiplies the elements of the vector.
+///
+/// Vectors which only contain integers or only contain floats are multiplied
+/// directly, anything else is multiplied using the [`MUL`] protocol. An empty
+/// vector has a product of `1`.
+///
+/// # Errors
+///
+/// Errors if multiplying integers overflows.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!([1, 2, 3, 4].product(), 24);
+/// assert_eq!([1.5, 2.0].product(), 3.0);
+/// assert_eq!([].product(), 1);
+/// ```
+#[rune::function(instance)]
+fn product(this: &Vec) -> VmResult<Value> {
+    Vec::product_with(this, &mut EnvProtocolCaller)
+}
+
+/// Returns the smallest element of the vector, or `None` if it's empty.
+///
+/// If several elements are equally small, the first one is returned. Vectors
+/// which only contain integers or only contain floats are compared directly,
+/// anything else is compared using the [`CMP`] protocol.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!([3, 1, 2].min(), Some(1));
+/// assert_eq!([2.5, -1.0].min(), Some(-1.0));
+/// assert_eq!(["b", "a"].min(), Some("a"));
+/// assert_eq!([].min(), None);
+/// ```
+#[rune::function(instance)]
+fn min(this: &Vec) -> VmResult<Option<Value>> {
+    Vec::min_with(this, &mut EnvProtocolCaller)
+}
+
+/// Returns the largest element of the vector, or `None` if it's empty.
+///
+/// If several elements are equally large, the last one is returned. Vectors
+/// which only contain integers or only contain floats are compared directly,
+/// anything else is compared using the [`CMP`] protocol.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!([3, 1, 2].max(), Some(3));
+/// assert_eq!([2.5, -1.0].max(), Some(2.5));
+/// assert_eq!(["b", "a"].max(), Some("b"));
+/// assert_eq!([].max(), None);
+/// ```
+#[rune::function(instance)]
+fn max(this: &Vec) -> VmResult<Option<Value>> {
+    Vec::max_with(this, &mut EnvProtocolCaller)
+}
+
 /// Construct an iterator over the tuple.
 ///
 /// # Examples
@@ -6,6 +6,8 @@
 pub struct RawEnv {
     pub(crate) context: *const (),
     pub(crate) unit: *const (),
+    pub(crate) signals: *const (),
+    pub(crate) mocks: *const (),
 }
 
 impl RawEnv {
@@ -14,6 +16,8 @@ impl RawEnv {
         RawEnv {
             context: core::ptr::null(),
             unit: core::ptr::null(),
+            signals: core::ptr::null(),
+            mocks: core::ptr::null(),
         }
     }
 }
@@ -2,6 +2,7 @@
 
 mod expectation;
 mod id;
+mod incremental;
 mod lexer;
 mod opaque;
 mod parse;
@@ -12,7 +13,9 @@ mod resolve;
 pub use self::expectation::Expectation;
 pub(crate) use self::expectation::IntoExpectation;
 pub use self::id::{Id, NonZeroId};
-pub(crate) use self::lexer::{Lexer, LexerMode};
+pub use self::incremental::IncrementalLexer;
+pub use self::lexer::Lexer;
+pub(crate) use self::lexer::LexerMode;
 pub(crate) use self::opaque::Opaque;
 pub use self::parse::Parse;
 pub use self::parser::{Parser, Peeker};
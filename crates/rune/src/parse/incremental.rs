@@ -0,0 +1,213 @@
+use core::ops::Range;
+
+use crate::no_std::prelude::*;
+
+use crate::ast::{self, ByteIndex, Span};
+use crate::compile;
+use crate::parse::Lexer;
+use crate::SourceId;
+
+/// A lexer which preserves trivia, and which can be incrementally updated as
+/// the source it was lexed from is edited.
+///
+/// Tokens are produced like with [Lexer::with_trivia]. When the source is
+/// edited, only the tokens surrounding the edit are lexed again and the spans
+/// of the tokens after it are adjusted.
+///
+/// # Examples
+///
+/// ```
+/// use rune::parse::IncrementalLexer;
+/// use rune::SourceId;
+///
+/// let mut source = String::from("let a = 1;");
+/// let mut lexer = IncrementalLexer::new(&source, SourceId::empty(), false)?;
+/// assert_eq!(lexer.tokens().len(), 8);
+///
+/// // Replace `a` with `value`.
+/// source.replace_range(4..5, "value");
+/// let changed = lexer.edit(&source, 4..5, 5)?;
+///
+/// assert_eq!(changed, 1..3);
+/// assert_eq!(lexer.tokens()[2].span.range(), 4..9);
+/// assert_eq!(lexer.tokens()[7].span.range(), 13..14);
+/// # Ok::<_, rune::compile::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct IncrementalLexer {
+    source_id: SourceId,
+    shebang: bool,
+    tokens: Vec<ast::Token>,
+    /// Indicates if lexing can be resumed at the start of the token with the
+    /// same index.
+    resumable: Vec<bool>,
+}
+
+impl IncrementalLexer {
+    /// Lex the given source.
+    pub fn new(source: &str, source_id: SourceId, shebang: bool) -> compile::Result<Self> {
+        let mut this = Self {
+            source_id,
+            shebang,
+            tokens: Vec::new(),
+            resumable: Vec::new(),
+        };
+
+        let (tokens, resumable, _) = this.lex(source, 0, None)?;
+        this.tokens = tokens;
+        this.resumable = resumable;
+        Ok(this)
+    }
+
+    /// The tokens of the source, including trivia.
+    pub fn tokens(&self) -> &[ast::Token] {
+        &self.tokens
+    }
+
+    /// Update the tokens after `range` of the previously lexed source has been
+    /// replaced with `len` bytes, resulting in `source`.
+    ///
+    /// Returns the range of tokens which were lexed again. The tokens outside
+    /// of that range are unchanged, except that the spans of tokens after it
+    /// are adjusted to account for the edit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is not within the previously lexed source.
+    pub fn edit(
+        &mut self,
+        source: &str,
+        range: Range<usize>,
+        len: usize,
+    ) -> compile::Result<Range<usize>> {
+        let old_len = self.tokens.last().map_or(0, |t| t.span.end.into_usize());
+
+        assert!(
+            range.start <= range.end && range.end <= old_len,
+            "edit {range:?} out of bounds of source of length {old_len}"
+        );
+
+        // The first token which might be affected by the edit, which includes
+        // a token ending right where the edit starts since it might be
+        // extended by it.
+        let mut first = self
+            .tokens
+            .partition_point(|t| t.span.end.into_usize() < range.start);
+
+        while first > 0 && !self.resumable.get(first).copied().unwrap_or_default() {
+            first -= 1;
+        }
+
+        let start = self
+            .tokens
+            .get(first)
+            .map_or(0, |t| t.span.start.into_usize());
+
+        let edit = Edit {
+            range: range.clone(),
+            len,
+        };
+
+        let (tokens, resumable, reused) = self.lex(source, start, Some((first, &edit)))?;
+
+        for token in &mut self.tokens[reused..] {
+            edit.shift(token);
+        }
+
+        let changed = first..first + tokens.len();
+        self.tokens.splice(first..reused, tokens);
+        self.resumable.splice(first..reused, resumable);
+        Ok(changed)
+    }
+
+    /// Lex `source` from `start`, until the end or until lexing lines up with
+    /// an old token after the edit.
+    ///
+    /// Returns the new tokens, and the index of the first old token which can
+    /// be reused.
+    fn lex(
+        &self,
+        source: &str,
+        start: usize,
+        edit: Option<(usize, &Edit)>,
+    ) -> compile::Result<(Vec<ast::Token>, Vec<bool>, usize)> {
+        let mut lexer =
+            Lexer::with_trivia_at(source, self.source_id, start, self.shebang && start == 0);
+
+        let mut tokens = Vec::new();
+        let mut resumable = Vec::new();
+        let mut old = edit.map_or(0, |(first, _)| first);
+
+        loop {
+            let is_resumable = lexer.is_resumable();
+
+            if let (true, Some((_, edit))) = (is_resumable, edit) {
+                let pos = lexer.pos();
+
+                if pos >= edit.range.start + edit.len {
+                    while let Some(token) = self.tokens.get(old) {
+                        let token_start = token.span.start.into_usize();
+
+                        if token_start >= edit.range.end && edit.shift_pos(token_start) >= pos {
+                            break;
+                        }
+
+                        old += 1;
+                    }
+
+                    if let Some(token) = self.tokens.get(old) {
+                        if self.resumable[old]
+                            && edit.shift_pos(token.span.start.into_usize()) == pos
+                        {
+                            return Ok((tokens, resumable, old));
+                        }
+                    }
+                }
+            }
+
+            let Some(token) = lexer.next()? else {
+                break;
+            };
+
+            tokens.push(token);
+            resumable.push(is_resumable);
+        }
+
+        Ok((tokens, resumable, self.tokens.len()))
+    }
+}
+
+/// An edit, where `range` was replaced with `len` bytes.
+struct Edit {
+    range: Range<usize>,
+    len: usize,
+}
+
+impl Edit {
+    /// Shift a position after the edit.
+    fn shift_pos(&self, pos: usize) -> usize {
+        pos - self.range.end + self.range.start + self.len
+    }
+
+    /// Shift a token after the edit, including spans stored in its kind.
+    fn shift(&self, token: &mut ast::Token) {
+        token.span = self.shift_span(token.span);
+
+        if let ast::Kind::Number(ast::NumberSource::Text(text)) = &mut token.kind {
+            text.number = self.shift_span(text.number);
+            text.suffix = self.shift_span(text.suffix);
+        }
+    }
+
+    fn shift_span(&self, span: Span) -> Span {
+        Span {
+            start: self.shift_index(span.start),
+            end: self.shift_index(span.end),
+        }
+    }
+
+    fn shift_index(&self, index: ByteIndex) -> ByteIndex {
+        let pos = self.shift_pos(index.into_usize());
+        ByteIndex::try_from(pos).expect("byte index out of bounds")
+    }
+}
@@ -22,6 +22,8 @@ pub struct Lexer<'a> {
     buffer: VecDeque<ast::Token>,
     /// If the lexer should try and lex a shebang.
     shebang: bool,
+    /// If the lexer should preserve trivia instead of desugaring it.
+    trivia: bool,
 }
 
 impl<'a> Lexer<'a> {
@@ -33,9 +35,78 @@ impl<'a> Lexer<'a> {
             modes: LexerModes::default(),
             buffer: VecDeque::new(),
             shebang,
+            trivia: false,
         }
     }
 
+    /// Construct a lexer which preserves trivia over the given source.
+    ///
+    /// Every token is yielded with a span which exactly covers the source it
+    /// was lexed from, including whitespace and comments. Unlike the lexer used
+    /// by the parser, doc comments are yielded as comments and template
+    /// literals are yielded as their individual parts, instead of being
+    /// desugared into attributes and macro calls. This makes it suitable for
+    /// tools like formatters and syntax highlighters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::ast::Kind;
+    /// use rune::parse::Lexer;
+    /// use rune::SourceId;
+    ///
+    /// let source = "/// Docs.\nlet a = 1; // Comment.";
+    /// let mut lexer = Lexer::with_trivia(source, SourceId::empty(), false);
+    ///
+    /// let mut end = 0;
+    /// let mut comments = 0;
+    ///
+    /// while let Some(token) = lexer.next()? {
+    ///     assert_eq!(token.span.range().start, end);
+    ///     end = token.span.range().end;
+    ///
+    ///     if matches!(token.kind, Kind::Comment) {
+    ///         comments += 1;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(end, source.len());
+    /// assert_eq!(comments, 2);
+    /// # Ok::<_, rune::compile::Error>(())
+    /// ```
+    pub fn with_trivia(source: &'a str, source_id: SourceId, shebang: bool) -> Self {
+        Self::with_trivia_at(source, source_id, 0, shebang)
+    }
+
+    /// Construct a lexer which preserves trivia and starts lexing at the
+    /// given byte offset.
+    pub(super) fn with_trivia_at(
+        source: &'a str,
+        source_id: SourceId,
+        cursor: usize,
+        shebang: bool,
+    ) -> Self {
+        Self {
+            iter: SourceIter { source, cursor },
+            source_id,
+            modes: LexerModes::default(),
+            buffer: VecDeque::new(),
+            shebang,
+            trivia: true,
+        }
+    }
+
+    /// The byte offset at which the next character will be lexed.
+    pub(super) fn pos(&self) -> usize {
+        self.iter.pos()
+    }
+
+    /// Test if the lexer has no buffered tokens and is in its initial mode,
+    /// in which case lexing can be resumed from [Lexer::pos] by a new lexer.
+    pub(super) fn is_resumable(&self) -> bool {
+        self.buffer.is_empty() && self.modes.modes.is_empty()
+    }
+
     /// Access the span of the lexer.
     pub(crate) fn span(&self) -> Span {
         self.iter.span_to_len(0)
@@ -186,8 +257,13 @@ impl<'a> Lexer<'a> {
         while let Some(c) = self.iter.peek() {
             match c {
                 // NB: We need to avoid exponent check for hex number bases,
-                // since 'e' is a legal hex literal.
-                'e' if !has_exponent && !matches!(base, ast::NumberBase::Hex) => {
+                // since 'e' is a legal hex literal. An 'e' which isn't
+                // followed by the exponent is the start of a suffix like `em`.
+                'e' if split.is_none()
+                    && !has_exponent
+                    && !matches!(base, ast::NumberBase::Hex)
+                    && matches!(self.iter.peek2(), Some('0'..='9' | '-')) =>
+                {
                     self.iter.next();
                     has_exponent = true;
                     is_fractional = true;
@@ -197,7 +273,7 @@ impl<'a> Lexer<'a> {
                         self.iter.next();
                     }
                 }
-                '.' if !is_fractional => {
+                '.' if !is_fractional && split.is_none() => {
                     if let Some(p2) = self.iter.peek2() {
                         // NB: only skip if the next peek matches:
                         // * the beginning of an ident.
@@ -223,8 +299,11 @@ impl<'a> Lexer<'a> {
                     self.iter.next();
                 }
                 c if c.is_alphanumeric() => {
+                    // NB: the suffix starts at the first letter which isn't a
+                    // digit in the base of the number, like `px` in `10px`.
                     if split.is_none()
-                        && matches!((c, base), ('u' | 'i', _) | ('f', ast::NumberBase::Decimal))
+                        && c.is_alphabetic()
+                        && !(matches!(base, ast::NumberBase::Hex) && c.is_ascii_hexdigit())
                     {
                         split = Some(self.iter.pos());
                     }
@@ -463,6 +542,32 @@ impl<'a> Lexer<'a> {
                         }
                     }
 
+                    if self.trivia {
+                        if had_string {
+                            self.buffer.push_back(ast::Token {
+                                kind: ast::Kind::Str(ast::StrSource::Text(ast::StrText {
+                                    source_id: self.source_id,
+                                    escaped: take(&mut escaped),
+                                    wrapped: false,
+                                })),
+                                span,
+                            });
+                        }
+
+                        self.buffer.push_back(ast::Token {
+                            kind: K![$],
+                            span: Span::new(start, start + 1),
+                        });
+
+                        self.buffer.push_back(ast::Token {
+                            kind: K!['{'],
+                            span: Span::new(start + 1, self.iter.pos()),
+                        });
+
+                        self.modes.push(LexerMode::Default(1));
+                        return Ok(());
+                    }
+
                     if had_string {
                         if *expressions > 0 {
                             self.buffer.push_back(ast::Token {
@@ -513,6 +618,30 @@ impl<'a> Lexer<'a> {
 
                     let expressions = self.modes.expression_count(&self.iter, start)?;
 
+                    if self.trivia {
+                        if had_string {
+                            self.buffer.push_back(ast::Token {
+                                kind: ast::Kind::Str(ast::StrSource::Text(ast::StrText {
+                                    source_id: self.source_id,
+                                    escaped: take(&mut escaped),
+                                    wrapped: false,
+                                })),
+                                span,
+                            });
+                        }
+
+                        self.buffer.push_back(ast::Token {
+                            kind: ast::Kind::Close(ast::Delimiter::Empty),
+                            span: self.iter.span_to_pos(start),
+                        });
+
+                        let expressions = *expressions;
+                        self.modes
+                            .pop(&self.iter, LexerMode::Template(expressions))?;
+
+                        return Ok(());
+                    }
+
                     if had_string {
                         if *expressions > 0 {
                             self.buffer.push_back(ast::Token {
@@ -563,7 +692,7 @@ impl<'a> Lexer<'a> {
 
     /// Consume the next token from the lexer.
     #[allow(clippy::should_implement_trait)]
-    pub(crate) fn next(&mut self) -> compile::Result<Option<ast::Token>> {
+    pub fn next(&mut self) -> compile::Result<Option<ast::Token>> {
         'outer: loop {
             if let Some(token) = self.buffer.pop_front() {
                 return Ok(Some(token));
@@ -653,7 +782,7 @@ impl<'a> Lexer<'a> {
                             self.iter.next();
                             let (doc, inner) = self.check_doc_comment('/');
                             self.consume_line();
-                            if doc {
+                            if doc && !self.trivia {
                                 // docstring span drops the first 3 characters (/// or //!)
                                 let span = self.iter.span_to_pos(start);
                                 self.emit_doc_attribute(inner, span, span.trim_start(3));
@@ -670,7 +799,7 @@ impl<'a> Lexer<'a> {
                                 break ast::Kind::MultilineComment(false);
                             }
 
-                            if doc {
+                            if doc && !self.trivia {
                                 // docstring span drops the first 3 characters (/** or /*!)
                                 // drop the last two characters to remove */
                                 let span = self.iter.span_to_pos(start);
@@ -785,7 +914,10 @@ impl<'a> Lexer<'a> {
                             if level == 1 {
                                 let expressions = self.modes.expression_count(&self.iter, start)?;
                                 *expressions += 1;
-                                continue 'outer;
+
+                                if !self.trivia {
+                                    continue 'outer;
+                                }
                             }
                         }
 
@@ -830,6 +962,11 @@ impl<'a> Lexer<'a> {
                     '`' => {
                         let span = self.iter.span_to_pos(start);
 
+                        if self.trivia {
+                            self.modes.push(LexerMode::Template(0));
+                            break ast::Kind::Open(ast::Delimiter::Empty);
+                        }
+
                         self.buffer.push_back(ast::Token {
                             kind: ast::Kind::Open(ast::Delimiter::Empty),
                             span,
@@ -1111,6 +1248,51 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_number_suffixes() {
+        test_lexer! {
+            "2.5em",
+            ast::Token {
+                span: span!(0, 5),
+                kind: ast::Kind::Number(ast::NumberSource::Text(ast::NumberText {
+                    source_id: SourceId::EMPTY,
+                    is_fractional: true,
+                    base: ast::NumberBase::Decimal,
+                    number: span!(0, 3),
+                    suffix: span!(3, 5),
+                })),
+            },
+        };
+
+        test_lexer! {
+            "1e-3px",
+            ast::Token {
+                span: span!(0, 6),
+                kind: ast::Kind::Number(ast::NumberSource::Text(ast::NumberText {
+                    source_id: SourceId::EMPTY,
+                    is_fractional: true,
+                    base: ast::NumberBase::Decimal,
+                    number: span!(0, 4),
+                    suffix: span!(4, 6),
+                })),
+            },
+        };
+
+        test_lexer! {
+            "0xFFpx",
+            ast::Token {
+                span: span!(0, 6),
+                kind: ast::Kind::Number(ast::NumberSource::Text(ast::NumberText {
+                    source_id: SourceId::EMPTY,
+                    is_fractional: false,
+                    base: ast::NumberBase::Hex,
+                    number: span!(2, 4),
+                    suffix: span!(4, 6),
+                })),
+            },
+        };
+    }
+
     #[test]
     fn test_char_literal() {
         test_lexer! {
@@ -2,6 +2,7 @@ use core::fmt;
 use core::ops;
 
 use crate::no_std::collections::VecDeque;
+use crate::no_std::prelude::*;
 
 use crate::ast::{Kind, OptionSpanned, Span, Token};
 use crate::compile::{self, ErrorKind};
@@ -25,6 +26,9 @@ use crate::SourceId;
 #[derive(Debug)]
 pub struct Parser<'a> {
     peeker: Peeker<'a>,
+    /// Identifiers encountered which collide with keywords that are reserved
+    /// for future use.
+    reserved_idents: Vec<Span>,
 }
 
 impl<'a> Parser<'a> {
@@ -117,6 +121,7 @@ impl<'a> Parser<'a> {
                 last: None,
                 default_span,
             },
+            reserved_idents: Vec::new(),
         }
     }
 
@@ -214,6 +219,17 @@ impl<'a> Parser<'a> {
     pub(crate) fn last_span(&self) -> Span {
         self.peeker.last_span()
     }
+
+    /// Record that an identifier which collides with a keyword reserved for
+    /// future use was parsed.
+    pub(crate) fn reserved_ident(&mut self, span: Span) {
+        self.reserved_idents.push(span);
+    }
+
+    /// Identifiers parsed which collide with keywords reserved for future use.
+    pub(crate) fn reserved_idents(&self) -> &[Span] {
+        &self.reserved_idents
+    }
 }
 
 /// Construct used to peek a parser.
@@ -68,6 +68,9 @@ pub(crate) enum BuiltInMacro {
     Format(BuiltInFormat),
     File(BuiltInFile),
     Line(BuiltInLine),
+    IncludeData(BuiltInIncludeData),
+    IncludeStr(BuiltInIncludeStr),
+    IncludeBytes(BuiltInIncludeBytes),
 }
 
 /// An internally resolved template.
@@ -117,6 +120,30 @@ pub(crate) struct BuiltInLine {
     pub(crate) value: ast::Lit,
 }
 
+/// Macro data for `include_data!()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Spanned)]
+pub(crate) struct BuiltInIncludeData {
+    /// The span of the macro call.
+    #[rune(span)]
+    pub(crate) span: Span,
+    /// The static data slot the loaded data was stored in.
+    pub(crate) slot: usize,
+}
+
+/// Macro data for `include_str!()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Spanned)]
+pub(crate) struct BuiltInIncludeStr {
+    /// The contents of the included file.
+    pub(crate) value: ast::Lit,
+}
+
+/// Macro data for `include_bytes!()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Spanned)]
+pub(crate) struct BuiltInIncludeBytes {
+    /// The contents of the included file.
+    pub(crate) value: ast::Lit,
+}
+
 /// An entry in the build queue.
 #[derive(Debug, Clone)]
 pub(crate) enum Build {
@@ -125,6 +152,7 @@ pub(crate) enum Build {
     InstanceFunction(indexing::InstanceFunction),
     Closure(indexing::Closure),
     AsyncBlock(indexing::AsyncBlock),
+    EnumFromInt(indexing::EnumFromInt),
     Unused,
     Import(indexing::Import),
     /// A public re-export.
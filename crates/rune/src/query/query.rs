@@ -9,24 +9,27 @@ use crate::no_std::rc::Rc;
 use crate::no_std::sync::Arc;
 
 use crate::ast::{Span, Spanned};
+use crate::compile::attrs::StrictArgs;
 use crate::compile::context::ContextMeta;
 use crate::compile::ir;
 use crate::compile::meta::{self, FieldMeta};
+use crate::compile::purity::{self, Purity};
 use crate::compile::{
-    self, CompileVisitor, ComponentRef, Doc, DynLocation, ErrorKind, ImportStep, IntoComponent,
-    Item, ItemBuf, ItemId, ItemMeta, Located, Location, ModId, ModMeta, Names, Pool, Prelude,
-    SourceLoader, SourceMeta, UnitBuilder, Visibility, WithSpan,
+    self, Assembly, CompilePass, CompileVisitor, ComponentRef, Doc, DynLocation, ErrorKind,
+    ImportStep, IntoComponent, Item, ItemBuf, ItemId, ItemMeta, Located, Location, ModId, ModMeta,
+    Names, PassAssembly, PassBody, PassCall, PassContext, PassFn, Pool, Prelude, SourceLoader,
+    SourceMeta, UnitBuilder, Visibility, WithSpan,
 };
 use crate::hir;
 use crate::indexing::{self, Indexed, Items};
-use crate::macros::Storage;
+use crate::macros::{DeclarativeMacro, Storage};
 use crate::parse::{Id, NonZeroId, Opaque, Resolve, ResolveContext};
 use crate::query::{
     Build, BuildEntry, BuiltInMacro, ConstFn, GenericsParameters, Named, QueryPath, Used,
 };
 #[cfg(feature = "doc")]
 use crate::runtime::Call;
-use crate::runtime::ConstValue;
+use crate::runtime::{ConstValue, Derives, UnitFn};
 use crate::shared::{Consts, Gen};
 use crate::{ast, Options};
 use crate::{Context, Diagnostics, Hash, SourceId, Sources};
@@ -51,6 +54,15 @@ enum ContextMatch<'this, 'm> {
     None,
 }
 
+/// An item which could be imported to resolve a name.
+#[derive(Debug, Clone, Copy)]
+struct ImportCandidate {
+    item: ItemId,
+    /// The module and visibility of an item in the unit. Items in the context
+    /// are visible everywhere.
+    unit: Option<(ModId, Visibility)>,
+}
+
 /// The permitted number of import recursions when constructing a path.
 const IMPORT_RECURSION_LIMIT: usize = 128;
 
@@ -65,12 +77,25 @@ pub(crate) struct QueryInner<'arena> {
     indexed: BTreeMap<ItemId, Vec<indexing::Entry>>,
     /// Compiled constant functions.
     const_fns: HashMap<NonZeroId, Rc<ConstFn<'arena>>>,
+    /// Summaries of the effects of functions in the unit, indexed by their
+    /// hash.
+    purity_summaries: HashMap<Hash, purity::Summary>,
+    /// The purity of functions in the unit which has been determined.
+    purity: HashMap<Hash, Purity>,
+    /// The purity of functions in the unit which has been determined, where
+    /// operations which might call protocol functions count as side effects.
+    strict_purity: HashMap<Hash, Purity>,
+    /// Pure functions which have been compiled into constant functions, so
+    /// that they can be called in constant contexts.
+    pure_const_fns: HashMap<Hash, NonZeroId>,
     /// Indexed constant values.
     constants: HashMap<Hash, ConstValue>,
     /// Query paths.
     pub(crate) query_paths: HashMap<NonZeroId, QueryPath>,
     /// The result of internally resolved macros.
     internal_macros: HashMap<NonZeroId, Arc<BuiltInMacro>>,
+    /// Macros declared in sources.
+    declarative_macros: HashMap<ItemId, Arc<DeclarativeMacro>>,
     /// Associated between `id` and `Item`. Use to look up items through
     /// `item_for` with an opaque id.
     ///
@@ -81,6 +106,12 @@ pub(crate) struct QueryInner<'arena> {
     names: Names,
     /// Recorded captures.
     captures: HashMap<Hash, Vec<hir::OwnedName>>,
+    /// Items indexed by their last component, used to suggest imports for
+    /// names which can't be resolved.
+    import_candidates: HashMap<Box<str>, Vec<ImportCandidate>>,
+    /// Indicates if items in the context have been added to
+    /// `import_candidates`, which is done the first time they are needed.
+    context_import_candidates: bool,
 }
 
 impl QueryInner<'_> {
@@ -88,6 +119,15 @@ impl QueryInner<'_> {
     pub(crate) fn get_const_value(&self, hash: Hash) -> Option<&ConstValue> {
         self.constants.get(&hash)
     }
+
+    /// Get the purity of functions which has been determined.
+    fn purity_mut(&mut self, strict: bool) -> &mut HashMap<Hash, Purity> {
+        if strict {
+            &mut self.strict_purity
+        } else {
+            &mut self.purity
+        }
+    }
 }
 
 /// Query system of the rune compiler.
@@ -116,6 +156,8 @@ pub(crate) struct Query<'a, 'arena> {
     pub(crate) pool: &'a mut Pool,
     /// Visitor for the compiler meta.
     pub(crate) visitor: &'a mut dyn CompileVisitor,
+    /// User-provided compile passes.
+    pub(crate) passes: &'a mut dyn CompilePass,
     /// Compilation warnings.
     pub(crate) diagnostics: &'a mut Diagnostics,
     /// Source loader.
@@ -141,6 +183,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
         sources: &'a mut Sources,
         pool: &'a mut Pool,
         visitor: &'a mut dyn CompileVisitor,
+        passes: &'a mut dyn CompilePass,
         diagnostics: &'a mut Diagnostics,
         source_loader: &'a mut dyn SourceLoader,
         options: &'a Options,
@@ -157,6 +200,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
             sources,
             pool,
             visitor,
+            passes,
             diagnostics,
             source_loader,
             options,
@@ -177,6 +221,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
             pool: self.pool,
             sources: self.sources,
             visitor: self.visitor,
+            passes: self.passes,
             diagnostics: self.diagnostics,
             source_loader: self.source_loader,
             options: self.options,
@@ -192,6 +237,373 @@ impl<'a, 'arena> Query<'a, 'arena> {
         self.inner.queue.pop_front()
     }
 
+    /// Run user-provided compile passes over a function which has been
+    /// lowered.
+    pub(crate) fn pass_fn(
+        &mut self,
+        item_meta: &ItemMeta,
+        span: &dyn Spanned,
+        args: usize,
+        body: PassBody<'_>,
+    ) -> compile::Result<()> {
+        let hash = self.pool.item_type_hash(item_meta.item);
+        let purity = self.purity(hash);
+
+        let mut cx = PassContext {
+            source_id: item_meta.location.source_id,
+            diagnostics: self.diagnostics,
+            purity,
+        };
+
+        let f = PassFn {
+            item: self.pool.item(item_meta.item),
+            hash,
+            location: item_meta.location,
+            is_public: item_meta.is_public(self.pool),
+            span: span.span(),
+            args,
+            body,
+        };
+
+        self.passes.visit_fn(&mut cx, &f)
+    }
+
+    /// Run user-provided compile passes over a function which has been
+    /// assembled.
+    pub(crate) fn pass_assembly(
+        &mut self,
+        source_id: SourceId,
+        item: ItemId,
+        span: &dyn Spanned,
+        args: usize,
+        asm: &Assembly,
+    ) -> compile::Result<()> {
+        let purity = self.purity(self.pool.item_type_hash(item));
+
+        let mut cx = PassContext {
+            source_id,
+            diagnostics: self.diagnostics,
+            purity,
+        };
+
+        let f = PassAssembly {
+            item: self.pool.item(item),
+            span: span.span(),
+            args,
+            asm,
+        };
+
+        self.passes.visit_assembly(&mut cx, &f)
+    }
+
+    /// Run user-provided compile passes over a call to a statically known
+    /// item, returning the hash of the function to call.
+    pub(crate) fn pass_call(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+        meta: &meta::Meta,
+    ) -> compile::Result<Hash> {
+        let mut cx = PassContext {
+            source_id,
+            diagnostics: self.diagnostics,
+            purity: None,
+        };
+
+        let mut call = PassCall {
+            span: span.span(),
+            meta: meta.as_meta_ref(self.pool),
+            hash: meta.hash,
+        };
+
+        self.passes.visit_call(&mut cx, &mut call)?;
+        Ok(call.hash)
+    }
+
+    /// Record a summary of the effects of the function with the given hash,
+    /// unless one has already been recorded while analyzing one of its
+    /// callers.
+    pub(crate) fn insert_purity_summary(&mut self, hash: Hash, summary: purity::Summary) {
+        self.inner.purity_summaries.entry(hash).or_insert(summary);
+    }
+
+    /// Get the purity of the function with the given hash, or `None` if it's
+    /// not a function in the unit which can be analyzed.
+    ///
+    /// Functions which haven't been compiled yet are analyzed on demand.
+    pub(crate) fn purity(&mut self, hash: Hash) -> Option<Purity> {
+        self.analyze_purity(hash, false)
+    }
+
+    /// Test if the function with the given hash is pure, doesn't perform any
+    /// operations which might call protocol functions, and is total.
+    ///
+    /// Only calls to such functions can be eliminated, since protocol functions
+    /// might have side effects, and eliminating a call to a function which
+    /// doesn't return normally would change how the program behaves.
+    pub(crate) fn is_strictly_pure(&mut self, hash: Hash) -> bool {
+        matches!(self.analyze_purity(hash, true), Some(Purity::Pure))
+    }
+
+    fn analyze_purity(&mut self, hash: Hash, strict: bool) -> Option<Purity> {
+        if let Some(purity) = self.inner.purity_mut(strict).get(&hash) {
+            return Some(*purity);
+        }
+
+        if !self.load_purity_summary(hash) {
+            return None;
+        }
+
+        // The caller through which each function was first reached, and the
+        // span of the call.
+        let mut callers = HashMap::<Hash, (Hash, Span)>::new();
+        let mut visited = HashSet::new();
+        visited.insert(hash);
+
+        let mut queue = vec![hash];
+        let mut impure = None;
+
+        while let Some(current) = queue.pop() {
+            match self.inner.purity_mut(strict).get(&current) {
+                Some(Purity::Pure) => continue,
+                Some(Purity::Impure { .. }) => {
+                    impure = Some(current);
+                    break;
+                }
+                None => {}
+            }
+
+            if !self.load_purity_summary(current) {
+                impure = Some(current);
+                break;
+            }
+
+            let summary = &self.inner.purity_summaries[&current];
+
+            let effect = if strict {
+                summary.effect.or(summary.protocol).or(summary.partial)
+            } else {
+                summary.effect
+            };
+
+            if let Some(span) = effect {
+                self.inner
+                    .purity_mut(strict)
+                    .insert(current, Purity::Impure { span });
+                impure = Some(current);
+                break;
+            }
+
+            for &(callee, span) in summary.calls.iter().rev() {
+                if visited.insert(callee) {
+                    callers.insert(callee, (current, span));
+                    queue.push(callee);
+                }
+            }
+        }
+
+        let Some(mut current) = impure else {
+            // NB: recursive functions might never return, so they aren't
+            // total.
+            if strict {
+                if let Some(span) = self.find_recursion(hash) {
+                    let purity = Purity::Impure { span };
+                    self.inner.purity_mut(strict).insert(hash, purity);
+                    return Some(purity);
+                }
+            }
+
+            // NB: nothing reachable has side effects, so everything which was
+            // visited is pure.
+            for hash in visited {
+                self.inner.purity_mut(strict).insert(hash, Purity::Pure);
+            }
+
+            return Some(Purity::Pure);
+        };
+
+        // Every function leading up to the one with side effects is impure
+        // because of the call which reaches it.
+        while let Some(&(caller, span)) = callers.get(&current) {
+            self.inner
+                .purity_mut(strict)
+                .insert(caller, Purity::Impure { span });
+            current = caller;
+        }
+
+        self.inner.purity_mut(strict).get(&hash).copied()
+    }
+
+    /// Find a call which leads to recursion among the functions reachable
+    /// from the function with the given hash, whose summaries have been
+    /// loaded.
+    fn find_recursion(&self, hash: Hash) -> Option<Span> {
+        let mut path = HashSet::new();
+        let mut done = HashSet::new();
+        let mut stack = vec![(hash, 0)];
+        path.insert(hash);
+
+        while let Some(&(current, index)) = stack.last() {
+            let call = self
+                .inner
+                .purity_summaries
+                .get(&current)
+                .and_then(|summary| summary.calls.get(index));
+
+            let Some(&(callee, span)) = call else {
+                path.remove(&current);
+                done.insert(current);
+                stack.pop();
+                continue;
+            };
+
+            if let Some((_, index)) = stack.last_mut() {
+                *index += 1;
+            }
+
+            if path.contains(&callee) {
+                return Some(span);
+            }
+
+            if done.contains(&callee)
+                || matches!(self.inner.strict_purity.get(&callee), Some(Purity::Pure))
+            {
+                continue;
+            }
+
+            path.insert(callee);
+            stack.push((callee, 0));
+        }
+
+        None
+    }
+
+    /// Test if the effects in the given summary are all pure, are total, and
+    /// don't include any operations which might call protocol functions.
+    pub(crate) fn is_strictly_pure_summary(&mut self, summary: &purity::Summary) -> bool {
+        summary.effect.is_none()
+            && summary.protocol.is_none()
+            && summary.partial.is_none()
+            && summary
+                .calls
+                .iter()
+                .all(|&(hash, _)| self.is_strictly_pure(hash))
+    }
+
+    /// Make sure that a summary of the effects of the function with the given
+    /// hash is available, by analyzing the function if it's queued up to be
+    /// compiled.
+    fn load_purity_summary(&mut self, hash: Hash) -> bool {
+        if self.inner.purity_summaries.contains_key(&hash) {
+            return true;
+        }
+
+        let pool = &*self.pool;
+
+        let found = self
+            .inner
+            .queue
+            .iter()
+            .find_map(|entry| match &entry.build {
+                Build::Function(f) if pool.item_type_hash(entry.item_meta.item) == hash => {
+                    Some((entry.item_meta, f.ast.clone(), f.call))
+                }
+                _ => None,
+            });
+
+        let Some((item_meta, ast, call)) = found else {
+            return false;
+        };
+
+        let arena = hir::Arena::new();
+
+        let mut cx = hir::lowering::Ctxt::with_analysis(
+            &arena,
+            self.borrow(),
+            item_meta.location.source_id,
+            item_meta.module,
+        );
+
+        let summary = match hir::lowering::item_fn(&mut cx, &ast) {
+            Ok(hir) => purity::summarize_fn(item_meta, &ast, &hir, call),
+            // NB: the error is reported once the function is compiled.
+            Err(..) => purity::Summary {
+                effect: Some(ast.span()),
+                ..purity::Summary::default()
+            },
+        };
+
+        self.inner.purity_summaries.insert(hash, summary);
+        true
+    }
+
+    /// Get the constant function used to evaluate the pure function with the
+    /// given hash in constant contexts, compiling it the first time it's
+    /// needed.
+    ///
+    /// Returns `None` if the function isn't pure.
+    pub(crate) fn pure_const_fn(&mut self, hash: Hash) -> compile::Result<Option<NonZeroId>> {
+        if let Some(id) = self.inner.pure_const_fns.get(&hash) {
+            return Ok(Some(*id));
+        }
+
+        if !matches!(self.purity(hash), Some(Purity::Pure)) {
+            return Ok(None);
+        }
+
+        let Some((item_meta, ast)) = self
+            .inner
+            .purity_summaries
+            .get_mut(&hash)
+            .and_then(|summary| summary.item_fn.take())
+        else {
+            return Ok(None);
+        };
+
+        // NB: the identifier is reserved before the function is compiled, so
+        // that recursive calls can refer to it.
+        let id = self.gen.next();
+        self.inner.pure_const_fns.insert(hash, id);
+
+        let const_fn = match self.compile_pure_const_fn(item_meta, &ast) {
+            Ok(const_fn) => const_fn,
+            Err(error) => {
+                self.inner.pure_const_fns.remove(&hash);
+                return Err(error);
+            }
+        };
+
+        self.inner.const_fns.insert(id, Rc::new(const_fn));
+        Ok(Some(id))
+    }
+
+    fn compile_pure_const_fn(
+        &mut self,
+        item_meta: ItemMeta,
+        ast: &ast::ItemFn,
+    ) -> compile::Result<ConstFn<'arena>> {
+        let mut cx = hir::lowering::Ctxt::with_const(
+            self.const_arena,
+            self.borrow(),
+            item_meta.location.source_id,
+        );
+
+        let hir = hir::lowering::item_fn(&mut cx, ast)?;
+
+        let mut cx = ir::Ctxt {
+            source_id: item_meta.location.source_id,
+            q: self.borrow(),
+        };
+
+        let ir_fn = ir::IrFn::compile_ast(&hir, &mut cx)?;
+
+        Ok(ConstFn {
+            item_meta,
+            ir_fn,
+            hir,
+        })
+    }
+
     // Pick private metadata to compile for the item.
     fn select_context_meta<'this, 'm>(
         &'this self,
@@ -308,6 +720,18 @@ impl<'a, 'arena> Query<'a, 'arena> {
             }
         }
 
+        if parameters.is_empty() {
+            if let Some(meta) = self
+                .lookup_base_meta(item)
+                .with_span(location.as_spanned())?
+            {
+                tracing::trace!(?meta, "Found in base unit");
+                self.visitor
+                    .visit_meta(location, meta.as_meta_ref(self.pool));
+                return Ok(Some(meta));
+            }
+        }
+
         let Some(metas) = self.context.lookup_meta(self.pool.item(item)) else {
             return Ok(None);
         };
@@ -321,20 +745,22 @@ impl<'a, 'arena> Query<'a, 'arena> {
             ContextMatch::Context(meta, parameters) => (meta, parameters),
         };
 
-        let Some(item) = &meta.item else {
+        if meta.item.is_none() {
             return Err(compile::Error::new(
                 location.as_spanned(),
                 ErrorKind::MissingItemHash { hash: meta.hash },
             ));
-        };
+        }
 
+        // NB: the item looked up is used rather than the item of the context
+        // meta, since they differ if the item is an alias.
         let meta = meta::Meta {
             context: true,
             hash: meta.hash,
             item_meta: ItemMeta {
                 id: self.gen.next(),
                 location: Default::default(),
-                item: self.pool.alloc_item(item),
+                item,
                 visibility: Default::default(),
                 module: Default::default(),
             },
@@ -354,6 +780,57 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Ok(Some(meta))
     }
 
+    /// Look up a function defined in the base unit that the unit being built
+    /// is layered on top of.
+    fn lookup_base_meta(
+        &mut self,
+        item: ItemId,
+    ) -> Result<Option<meta::Meta>, compile::error::MetaConflict> {
+        let Some(base) = self.unit.base() else {
+            return Ok(None);
+        };
+
+        let hash = Hash::type_hash(self.pool.item(item));
+
+        let Some(UnitFn::Offset { args, .. }) = base.function(hash) else {
+            return Ok(None);
+        };
+
+        let meta = meta::Meta {
+            context: true,
+            hash,
+            item_meta: ItemMeta {
+                id: self.gen.next(),
+                location: Default::default(),
+                item,
+                visibility: Default::default(),
+                module: Default::default(),
+            },
+            kind: meta::Kind::Function {
+                signature: meta::Signature {
+                    #[cfg(feature = "doc")]
+                    is_async: false,
+                    #[cfg(feature = "doc")]
+                    deprecated: None,
+                    args: Some(args),
+                    #[cfg(feature = "doc")]
+                    return_type: None,
+                    #[cfg(feature = "doc")]
+                    argument_types: Box::from([]),
+                },
+                is_test: false,
+                test_params: meta::TestParams::default(),
+                is_bench: false,
+                parameters: Hash::EMPTY,
+            },
+            source: None,
+            parameters: Hash::EMPTY,
+        };
+
+        self.insert_meta(meta.clone())?;
+        Ok(Some(meta))
+    }
+
     /// Access the meta for the given language item.
     pub(crate) fn lookup_meta(
         &mut self,
@@ -367,6 +844,16 @@ impl<'a, 'arena> Query<'a, 'arena> {
             return Ok(meta);
         }
 
+        Err(self.missing_item(location.as_spanned(), item, parameters))
+    }
+
+    /// Construct an error for an item which is missing.
+    pub(crate) fn missing_item(
+        &self,
+        span: &dyn Spanned,
+        item: ItemId,
+        parameters: &GenericsParameters,
+    ) -> compile::Error {
         let kind = if !parameters.parameters.is_empty() {
             ErrorKind::MissingItemParameters {
                 item: self.pool.item(item).to_owned(),
@@ -378,7 +865,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
             }
         };
 
-        Err(compile::Error::new(location.as_spanned(), kind))
+        compile::Error::new(span, kind)
     }
 
     /// Insert path information.
@@ -421,6 +908,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
             item: item.item,
             visibility,
             parent: Some(parent),
+            strict: self.pool.module(parent).strict,
         });
 
         self.index_and_build(indexing::Entry {
@@ -445,6 +933,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
             item: ItemId::default(),
             visibility: Visibility::Public,
             parent: None,
+            strict: StrictArgs::default(),
         });
 
         self.inner.items.insert(
@@ -543,9 +1032,95 @@ impl<'a, 'arena> Query<'a, 'arena> {
         };
 
         self.inner.items.insert(id, item_meta);
+        self.insert_import_candidate(item, Some((module, visibility)));
         Ok(item_meta)
     }
 
+    /// Index an item by its last component, so that it can be suggested as an
+    /// import for names which can't be resolved.
+    fn insert_import_candidate(&mut self, item: ItemId, unit: Option<(ModId, Visibility)>) {
+        let Some(ComponentRef::Str(name)) = self.pool.item(item).last() else {
+            return;
+        };
+
+        self.inner
+            .import_candidates
+            .entry(name.into())
+            .or_default()
+            .push(ImportCandidate { item, unit });
+    }
+
+    /// Suggest items which could be imported to resolve the first component of
+    /// the given path.
+    pub(crate) fn import_suggestions(
+        &mut self,
+        path: &ast::Path,
+    ) -> compile::Result<Vec<compile::Suggestion>> {
+        let (None, ast::PathSegment::Ident(ident)) = (&path.global, &path.first) else {
+            return Ok(Vec::new());
+        };
+
+        let Some(&QueryPath { module, .. }) =
+            path.id.get().and_then(|id| self.inner.query_paths.get(&id))
+        else {
+            return Ok(Vec::new());
+        };
+
+        if !self.inner.context_import_candidates {
+            self.inner.context_import_candidates = true;
+
+            let context = self.context;
+
+            for meta in context.iter_meta() {
+                if matches!(
+                    meta.kind,
+                    meta::Kind::AssociatedFunction { .. }
+                        | meta::Kind::Macro
+                        | meta::Kind::AttributeMacro
+                ) {
+                    continue;
+                }
+
+                if let Some(item) = &meta.item {
+                    let item = self.pool.alloc_item(item);
+                    self.insert_import_candidate(item, None);
+                }
+            }
+        }
+
+        let name = ident.resolve(resolve_context!(self))?;
+
+        let Some(candidates) = self.inner.import_candidates.get(name) else {
+            return Ok(Vec::new());
+        };
+
+        let from = self.pool.module_item(module);
+        let mut paths = Vec::new();
+
+        for candidate in candidates {
+            if let Some((module, visibility)) = candidate.unit {
+                let module = self.pool.module_item(module);
+                let (common, _) = from.ancestry(module);
+
+                if from == module || !visibility.is_visible_inside(&common, module) {
+                    continue;
+                }
+            }
+
+            if let Some(path) = import_path(self.pool.item(candidate.item)) {
+                paths.push(path);
+            }
+        }
+
+        paths.sort();
+        paths.dedup();
+
+        Ok(paths
+            .into_iter()
+            .map(|path| compile::Suggestion::import(ident.span(), &path))
+            .collect())
+    }
+
     /// Insert a new expanded internal macro.
     pub(crate) fn insert_new_builtin_macro(
         &mut self,
@@ -558,6 +1133,44 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Ok(id)
     }
 
+    /// Insert a macro declared in a source.
+    pub(crate) fn insert_declarative_macro(
+        &mut self,
+        location: &dyn Located,
+        item: ItemId,
+        docs: &[Doc],
+        declarative_macro: DeclarativeMacro,
+    ) -> compile::Result<()> {
+        let location = location.location();
+
+        let hash_map::Entry::Vacant(e) = self.inner.declarative_macros.entry(item) else {
+            return Err(compile::Error::msg(
+                location.span,
+                format_args!("Macro `{}` is already defined", self.pool.item(item)),
+            ));
+        };
+
+        e.insert(Arc::new(declarative_macro));
+
+        let cx = resolve_context!(self);
+
+        for doc in docs {
+            self.visitor.visit_doc_comment(
+                &DynLocation::new(location.source_id, &doc.span),
+                self.pool.item(item),
+                self.pool.item_type_hash(item),
+                doc.doc_string.resolve(cx)?.as_ref(),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Get the macro declared in a source for the given item.
+    pub(crate) fn declarative_macro(&self, item: ItemId) -> Option<Arc<DeclarativeMacro>> {
+        self.inner.declarative_macros.get(&item).cloned()
+    }
+
     /// Get the item for the given identifier.
     pub(crate) fn item_for<T>(&self, ast: T) -> compile::Result<ItemMeta, MissingId>
     where
@@ -653,6 +1266,19 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Ok(())
     }
 
+    /// Index a constant provided by the host.
+    #[tracing::instrument(skip_all)]
+    pub(crate) fn index_extern_const(&mut self, item_meta: ItemMeta) -> compile::Result<()> {
+        tracing::trace!(item = ?self.pool.item(item_meta.item));
+
+        self.index(indexing::Entry {
+            item_meta,
+            indexed: Indexed::ExternConst,
+        });
+
+        Ok(())
+    }
+
     /// Index a constant expression.
     #[tracing::instrument(skip_all)]
     pub(crate) fn index_const_block(
@@ -708,12 +1334,13 @@ impl<'a, 'arena> Query<'a, 'arena> {
         &mut self,
         item_meta: ItemMeta,
         ast: Box<ast::ItemStruct>,
+        derives: Derives,
     ) -> compile::Result<()> {
         tracing::trace!(item = ?self.pool.item(item_meta.item));
 
         self.index(indexing::Entry {
             item_meta,
-            indexed: Indexed::Struct(indexing::Struct { ast }),
+            indexed: Indexed::Struct(indexing::Struct { ast, derives }),
         });
 
         Ok(())
@@ -727,6 +1354,8 @@ impl<'a, 'arena> Query<'a, 'arena> {
         enum_id: NonZeroId,
         ast: ast::ItemVariant,
         index: usize,
+        discriminant: i64,
+        derives: Derives,
     ) -> compile::Result<()> {
         tracing::trace!(item = ?self.pool.item(item_meta.item));
 
@@ -736,6 +1365,8 @@ impl<'a, 'arena> Query<'a, 'arena> {
                 enum_id,
                 ast,
                 index,
+                discriminant,
+                derives,
             }),
         });
 
@@ -1261,25 +1892,55 @@ impl<'a, 'arena> Query<'a, 'arena> {
                 meta::Kind::Variant {
                     enum_hash: enum_meta.hash,
                     index: variant.index,
+                    discriminant: Some(variant.discriminant),
+                    derives: variant.derives,
                     fields: convert_fields(resolve_context!(self), variant.ast.body)?,
                     constructor: None,
                 }
             }
+            Indexed::EnumFromInt(f) => {
+                let kind = meta::Kind::Function {
+                    is_test: false,
+                    test_params: meta::TestParams::default(),
+                    is_bench: false,
+                    signature: meta::Signature {
+                        #[cfg(feature = "doc")]
+                        is_async: false,
+                        #[cfg(feature = "doc")]
+                        deprecated: None,
+                        args: Some(1),
+                        #[cfg(feature = "doc")]
+                        return_type: None,
+                        #[cfg(feature = "doc")]
+                        argument_types: Box::from([]),
+                    },
+                    parameters: Hash::EMPTY,
+                };
+
+                self.inner.queue.push_back(BuildEntry {
+                    item_meta,
+                    build: Build::EnumFromInt(f),
+                    used,
+                });
+
+                kind
+            }
             Indexed::Struct(st) => meta::Kind::Struct {
                 fields: convert_fields(resolve_context!(self), st.ast.body)?,
                 constructor: None,
                 parameters: Hash::EMPTY,
+                derives: st.derives,
             },
             Indexed::EmptyFunction(f) => {
                 let kind = meta::Kind::Function {
                     is_test: false,
+                    test_params: meta::TestParams::default(),
                     is_bench: false,
                     signature: meta::Signature {
                         #[cfg(feature = "doc")]
                         is_async: matches!(f.call, Call::Async | Call::Stream),
                         #[cfg(feature = "doc")]
                         deprecated: None,
-                        #[cfg(feature = "doc")]
                         args: Some(0),
                         #[cfg(feature = "doc")]
                         return_type: None,
@@ -1300,13 +1961,13 @@ impl<'a, 'arena> Query<'a, 'arena> {
             Indexed::Function(f) => {
                 let kind = meta::Kind::Function {
                     is_test: f.is_test,
+                    test_params: f.test_params,
                     is_bench: f.is_bench,
                     signature: meta::Signature {
                         #[cfg(feature = "doc")]
                         is_async: matches!(f.call, Call::Async | Call::Stream),
                         #[cfg(feature = "doc")]
                         deprecated: None,
-                        #[cfg(feature = "doc")]
                         args: Some(f.ast.args.len()),
                         #[cfg(feature = "doc")]
                         return_type: None,
@@ -1334,7 +1995,6 @@ impl<'a, 'arena> Query<'a, 'arena> {
                         is_async: f.ast.async_token.is_some(),
                         #[cfg(feature = "doc")]
                         deprecated: None,
-                        #[cfg(feature = "doc")]
                         args: Some(f.ast.args.len()),
                         #[cfg(feature = "doc")]
                         return_type: None,
@@ -1374,11 +2034,15 @@ impl<'a, 'arena> Query<'a, 'arena> {
                 };
 
                 let mut const_compiler = ir::Interpreter {
-                    budget: ir::Budget::new(1_000_000),
+                    budget: ir::Budget::new(
+                        self.options.const_budget,
+                        self.options.const_recursion_limit,
+                    ),
                     scopes: Default::default(),
                     module: item_meta.module,
                     item: item_meta.item,
                     q: self.borrow(),
+                    trace: Vec::new(),
                 };
 
                 let const_value = const_compiler.eval_const(&ir, used)?;
@@ -1414,11 +2078,15 @@ impl<'a, 'arena> Query<'a, 'arena> {
                 };
 
                 let mut const_compiler = ir::Interpreter {
-                    budget: ir::Budget::new(1_000_000),
+                    budget: ir::Budget::new(
+                        self.options.const_budget,
+                        self.options.const_recursion_limit,
+                    ),
                     scopes: Default::default(),
                     module: item_meta.module,
                     item: item_meta.item,
                     q: self.borrow(),
+                    trace: Vec::new(),
                 };
 
                 let const_value = const_compiler.eval_const(&ir, used)?;
@@ -1436,6 +2104,31 @@ impl<'a, 'arena> Query<'a, 'arena> {
 
                 meta::Kind::Const
             }
+            Indexed::ExternConst => {
+                let item = self.pool.item(item_meta.item);
+
+                let Some(const_value) = self.options.extern_consts.get(&item.to_string()) else {
+                    return Err(compile::Error::new(
+                        item_meta.location.span,
+                        ErrorKind::MissingExternConst {
+                            item: item.to_owned(),
+                        },
+                    ));
+                };
+
+                let hash = self.pool.item_type_hash(item_meta.item);
+                self.inner.constants.insert(hash, const_value.clone());
+
+                if used.is_unused() {
+                    self.inner.queue.push_back(BuildEntry {
+                        item_meta,
+                        build: Build::Unused,
+                        used,
+                    });
+                }
+
+                meta::Kind::Const
+            }
             Indexed::ConstFn(c) => {
                 let (ir_fn, hir) = {
                     // TODO: avoid this arena?
@@ -1747,3 +2440,29 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Some(self.inner.captures.get(&hash)?)
     }
 }
+
+/// Format the path used to import the given item, where items in the unit are
+/// prefixed with `crate`.
+fn import_path(item: &Item) -> Option<String> {
+    let mut path = String::new();
+
+    for (index, component) in item.iter().enumerate() {
+        if index > 0 {
+            path.push_str("::");
+        }
+
+        match component {
+            ComponentRef::Crate(name) => path.push_str(name),
+            ComponentRef::Str(name) => {
+                if index == 0 {
+                    path.push_str("crate::");
+                }
+
+                path.push_str(name);
+            }
+            ComponentRef::Id(..) => return None,
+        }
+    }
+
+    Some(path)
+}
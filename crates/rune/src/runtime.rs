@@ -4,7 +4,7 @@
 mod tests;
 
 mod access;
-pub(crate) use self::access::{Access, AccessKind};
+pub(crate) use self::access::{Access, AccessGuard, AccessKind};
 pub use self::access::{
     AccessError, BorrowMut, BorrowRef, NotAccessibleMut, NotAccessibleRef, RawAccessGuard,
 };
@@ -18,6 +18,16 @@ pub use self::args::Args;
 mod awaited;
 pub(crate) use self::awaited::Awaited;
 
+mod backtrace;
+pub use self::backtrace::{Backtrace, BacktraceFrame};
+
+mod borrow;
+pub(crate) use self::borrow::BorrowWait;
+pub use self::borrow::{BorrowMode, BorrowSite};
+
+mod breakpoint;
+pub use self::breakpoint::Breakpoint;
+
 pub mod budget;
 
 mod bytes;
@@ -26,14 +36,23 @@ pub use self::bytes::Bytes;
 mod call;
 pub use self::call::Call;
 
+mod cancellation;
+pub use self::cancellation::CancellationToken;
+
 mod const_value;
 pub use self::const_value::ConstValue;
 
 pub mod debug;
-pub use self::debug::{DebugInfo, DebugInst};
+pub use self::debug::{DebugInfo, DebugInst, DebugVariable};
+
+mod derives;
+pub use self::derives::Derives;
 
 mod env;
 
+mod execution_report;
+pub use self::execution_report::ExecutionReport;
+
 pub mod format;
 pub use self::format::{Format, FormatSpec};
 
@@ -49,6 +68,11 @@ mod future;
 pub use self::future::Future;
 pub(crate) use self::future::SelectFuture;
 
+#[cfg(feature = "gc")]
+mod gc;
+#[cfg(feature = "gc")]
+pub use self::gc::{Gc, GcStats};
+
 mod generator;
 pub use self::generator::Generator;
 
@@ -64,6 +88,12 @@ pub use self::inst::{
     PanicReason, TypeCheck,
 };
 
+mod inst_meta;
+pub use self::inst_meta::{InstMeta, InstOperand, InstStackEffect};
+
+mod interner;
+pub use self::interner::{Interner, KeyStats};
+
 mod iterator;
 pub use self::iterator::{Iterator, IteratorTrait};
 
@@ -75,14 +105,39 @@ pub use self::key::Key;
 
 mod label;
 pub use self::label::DebugLabel;
-pub(crate) use self::label::Label;
+pub use self::label::Label;
+
+mod layered_vm;
+pub use self::layered_vm::{LayerError, LayeredVm};
+
+mod limits;
+pub use self::limits::{Limit, Limits};
+
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+mod memo;
+pub(crate) use self::memo::{Memo, MemoKey};
+
+pub(crate) mod message_catalog;
+pub use self::message_catalog::{EnglishCatalog, Message, MessageCatalog, TemplateCatalog};
+
+mod mock;
+pub(crate) use self::mock::Mocks;
+
+#[cfg(feature = "std")]
+mod native_error;
+#[cfg(feature = "std")]
+pub use self::native_error::NativeError;
 
 mod object;
-pub use self::object::Object;
+pub(crate) use self::object::Shape;
+pub use self::object::{Object, ObjectIterRef, ObjectKey};
 
 mod panic;
 pub(crate) use self::panic::{BoxedPanic, Panic};
 
+mod panic_hook;
+pub use self::panic_hook::{PanicFrame, PanicHook, PanicInfo};
+
 mod protocol;
 pub use self::protocol::Protocol;
 
@@ -112,7 +167,9 @@ pub use rune_core::RawStr;
 
 mod runtime_context;
 pub use self::runtime_context::RuntimeContext;
-pub(crate) use self::runtime_context::{AttributeMacroHandler, FunctionHandler, MacroHandler};
+pub(crate) use self::runtime_context::{
+    AttributeMacroHandler, FunctionHandler, MacroHandler, PrettyPrinterHandler,
+};
 
 mod select;
 pub(crate) use self::select::Select;
@@ -120,6 +177,11 @@ pub(crate) use self::select::Select;
 mod shared;
 pub use self::shared::{Mut, RawMut, RawRef, Ref, Shared, SharedPointerGuard};
 
+mod signal;
+pub(crate) use self::signal::Signals;
+
+mod sorted;
+
 mod stack;
 pub use self::stack::{Stack, StackError};
 
@@ -151,11 +213,17 @@ pub use self::unit::{Unit, UnitStorage};
 mod value;
 pub use self::value::{EmptyStruct, Rtti, Struct, TupleStruct, Value, VariantRtti};
 
+mod value_diff;
+pub use self::value_diff::{diff, Change, ChangeKind, ValueDiff};
+
+mod value_path;
+pub use self::value_path::{PathSegment, ValuePath, ValuePathError, ValuePathErrorKind};
+
 mod variant;
 pub use self::variant::{Variant, VariantData};
 
 mod vec;
-pub use self::vec::Vec;
+pub use self::vec::{Primitive, Vec};
 
 mod vec_tuple;
 pub use self::vec_tuple::VecTuple;
@@ -166,6 +234,10 @@ pub use self::vm::{CallFrame, Vm};
 mod vm_call;
 pub(crate) use self::vm_call::VmCall;
 
+mod vm_snapshot;
+pub(crate) use self::vm_snapshot::Fingerprint;
+pub use self::vm_snapshot::VmSnapshot;
+
 mod vm_error;
 #[cfg(feature = "emit")]
 pub(crate) use self::vm_error::VmErrorAt;
@@ -7,7 +7,10 @@ use core::pin::Pin;
 use core::ptr;
 use core::task::{Context, Poll};
 
-use crate::runtime::{AnyObjError, RawStr};
+use crate::no_std::prelude::*;
+
+use crate::runtime::borrow::{self, Release};
+use crate::runtime::{AnyObjError, BorrowSite, RawStr};
 
 /// Bitflag which if set indicates that the accessed value is an external
 /// reference (exclusive or not).
@@ -57,6 +60,35 @@ impl fmt::Display for AccessError {
     }
 }
 
+impl AccessError {
+    /// Get the site at which the conflicting borrow is held, if the access
+    /// failed because the value is borrowed by an execution.
+    ///
+    /// Borrows are only tracked by virtual machines using
+    /// [BorrowMode::Queue][crate::runtime::BorrowMode::Queue].
+    pub fn holder(&self) -> Option<&BorrowSite> {
+        match self {
+            AccessError::NotAccessibleRef { error } => error.holder(),
+            AccessError::NotAccessibleMut { error } => error.holder(),
+            AccessError::NotAccessibleTake { error } => error.holder(),
+            _ => None,
+        }
+    }
+
+    /// Get a notification for when the conflicting borrow is released, if the
+    /// access failed because the value is borrowed by an execution.
+    pub(crate) fn held_release(&self) -> Option<&Release> {
+        let snapshot = match self {
+            AccessError::NotAccessibleRef { error } => &error.0,
+            AccessError::NotAccessibleMut { error } => &error.0,
+            AccessError::NotAccessibleTake { error } => &error.0,
+            _ => return None,
+        };
+
+        Some(&snapshot.holder.as_ref()?.1)
+    }
+}
+
 impl From<NotAccessibleRef> for AccessError {
     #[inline]
     fn from(error: NotAccessibleRef) -> Self {
@@ -90,6 +122,14 @@ impl From<AnyObjError> for AccessError {
 #[derive(Debug)]
 pub struct NotAccessibleRef(Snapshot);
 
+impl NotAccessibleRef {
+    /// Get the site at which the conflicting borrow is held, if it's held by
+    /// an execution.
+    pub fn holder(&self) -> Option<&BorrowSite> {
+        self.0.holder.as_ref().map(|(site, _)| site)
+    }
+}
+
 impl fmt::Display for NotAccessibleRef {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -104,6 +144,14 @@ impl crate::no_std::error::Error for NotAccessibleRef {}
 #[derive(Debug)]
 pub struct NotAccessibleMut(Snapshot);
 
+impl NotAccessibleMut {
+    /// Get the site at which the conflicting borrow is held, if it's held by
+    /// an execution.
+    pub fn holder(&self) -> Option<&BorrowSite> {
+        self.0.holder.as_ref().map(|(site, _)| site)
+    }
+}
+
 impl fmt::Display for NotAccessibleMut {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -120,6 +168,14 @@ impl crate::no_std::error::Error for NotAccessibleMut {}
 #[derive(Debug)]
 pub struct NotAccessibleTake(Snapshot);
 
+impl NotAccessibleTake {
+    /// Get the site at which the conflicting borrow is held, if it's held by
+    /// an execution.
+    pub fn holder(&self) -> Option<&BorrowSite> {
+        self.0.holder.as_ref().map(|(site, _)| site)
+    }
+}
+
 impl fmt::Display for NotAccessibleTake {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -141,12 +197,25 @@ pub(crate) enum AccessKind {
 /// Snapshot that can be used to indicate how the value was being accessed at
 /// the time of an error.
 #[derive(Debug)]
-#[repr(transparent)]
-struct Snapshot(isize);
+struct Snapshot {
+    state: isize,
+    /// The site at which the access is held and a notification for when it's
+    /// released, if it's held by an execution.
+    holder: Option<(BorrowSite, Release)>,
+}
+
+impl Snapshot {
+    fn new(access: &Access) -> Self {
+        Self {
+            state: access.state.get(),
+            holder: access.holder(),
+        }
+    }
+}
 
 impl fmt::Display for Snapshot {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.0 >> 1 {
+        match self.state >> 1 {
             0 => write!(f, "fully accessible")?,
             1 => write!(f, "exclusively accessed")?,
             TAKEN => write!(f, "moved")?,
@@ -154,10 +223,14 @@ impl fmt::Display for Snapshot {
             n => write!(f, "invalidly marked ({})", n)?,
         }
 
-        if self.0 & IS_REF_MASK == 1 {
+        if self.state & IS_REF_MASK == 1 {
             write!(f, " (ref)")?;
         }
 
+        if let Some((site, _)) = &self.holder {
+            write!(f, " (borrowed at {site})")?;
+        }
+
         Ok(())
     }
 }
@@ -187,20 +260,24 @@ impl fmt::Display for Snapshot {
 /// `(1 << 62) - 1` uses.
 ///
 /// ```
-#[repr(transparent)]
-pub(crate) struct Access(Cell<isize>);
+pub(crate) struct Access {
+    state: Cell<isize>,
+}
 
 impl Access {
     /// Construct a new default access.
     pub(crate) const fn new(is_ref: bool) -> Self {
         let initial = if is_ref { 1 } else { 0 };
-        Self(Cell::new(initial))
+
+        Self {
+            state: Cell::new(initial),
+        }
     }
 
     /// Test if access is guarding a reference.
     #[inline]
     pub(crate) fn is_ref(&self) -> bool {
-        self.0.get() & IS_REF_MASK != 0
+        self.state.get() & IS_REF_MASK != 0
     }
 
     /// Test if we can have shared access without modifying the internal count.
@@ -234,7 +311,7 @@ impl Access {
     ) -> Result<AccessGuard<'_>, NotAccessibleRef> {
         if let AccessKind::Owned = kind {
             if self.is_ref() {
-                return Err(NotAccessibleRef(Snapshot(self.0.get())));
+                return Err(NotAccessibleRef(Snapshot::new(self)));
             }
         }
 
@@ -247,7 +324,7 @@ impl Access {
         let n = state.wrapping_sub(1);
 
         if n >= 0 {
-            return Err(NotAccessibleRef(Snapshot(self.0.get())));
+            return Err(NotAccessibleRef(Snapshot::new(self)));
         }
 
         self.set(n);
@@ -266,14 +343,14 @@ impl Access {
     ) -> Result<AccessGuard<'_>, NotAccessibleMut> {
         if let AccessKind::Owned = kind {
             if self.is_ref() {
-                return Err(NotAccessibleMut(Snapshot(self.0.get())));
+                return Err(NotAccessibleMut(Snapshot::new(self)));
             }
         }
 
         let n = self.get();
 
         if n != 0 {
-            return Err(NotAccessibleMut(Snapshot(self.0.get())));
+            return Err(NotAccessibleMut(Snapshot::new(self)));
         }
 
         self.set(n.wrapping_add(1));
@@ -291,14 +368,14 @@ impl Access {
     pub(crate) unsafe fn take(&self, kind: AccessKind) -> Result<RawTakeGuard, NotAccessibleTake> {
         if let AccessKind::Owned = kind {
             if self.is_ref() {
-                return Err(NotAccessibleTake(Snapshot(self.0.get())));
+                return Err(NotAccessibleTake(Snapshot::new(self)));
             }
         }
 
         let state = self.get();
 
         if state != 0 {
-            return Err(NotAccessibleTake(Snapshot(self.0.get())));
+            return Err(NotAccessibleTake(Snapshot::new(self)));
         }
 
         self.set(TAKEN);
@@ -329,22 +406,39 @@ impl Access {
         self.set(0);
     }
 
+    /// Record the access as held by the execution calling the current native
+    /// function, returning `true` if it is.
+    fn hold(&self) -> bool {
+        borrow::hold(self)
+    }
+
+    /// Release a borrow recorded through [Access::hold].
+    fn release_held(&self) {
+        borrow::release(self);
+    }
+
+    /// Get the site at which the access is held and a notification for when
+    /// it's released, if it's held by an execution.
+    fn holder(&self) -> Option<(BorrowSite, Release)> {
+        borrow::holder(self)
+    }
+
     /// Get the current value of the flag.
     #[inline]
     fn get(&self) -> isize {
-        self.0.get() >> 1
+        self.state.get() >> 1
     }
 
     /// Set the current value of the flag.
     #[inline]
     fn set(&self, value: isize) {
-        self.0.set(self.0.get() & IS_REF_MASK | value << 1);
+        self.state.set(self.state.get() & IS_REF_MASK | value << 1);
     }
 }
 
 impl fmt::Debug for Access {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", Snapshot(self.get()))
+        write!(f, "{}", Snapshot::new(self))
     }
 }
 
@@ -452,7 +546,26 @@ impl AccessGuard<'_> {
     /// Since we're losing track of the lifetime, caller must ensure that the
     /// access outlives the guard.
     pub unsafe fn into_raw(self) -> RawAccessGuard {
-        RawAccessGuard(ptr::NonNull::from(ManuallyDrop::new(self).0))
+        RawAccessGuard {
+            access: ptr::NonNull::from(ManuallyDrop::new(self).0),
+            held: false,
+        }
+    }
+
+    /// Convert into a raw guard like [AccessGuard::into_raw], which is recorded
+    /// as held by the execution calling the current native function, if any.
+    ///
+    /// # Safety
+    ///
+    /// Since we're losing track of the lifetime, caller must ensure that the
+    /// access outlives the guard.
+    pub(crate) unsafe fn into_held_raw(self) -> RawAccessGuard {
+        let access = ManuallyDrop::new(self).0;
+
+        RawAccessGuard {
+            access: ptr::NonNull::from(access),
+            held: access.hold(),
+        }
     }
 }
 
@@ -463,12 +576,21 @@ impl Drop for AccessGuard<'_> {
 }
 
 /// A raw guard around some level of access.
-#[repr(transparent)]
-pub struct RawAccessGuard(ptr::NonNull<Access>);
+pub struct RawAccessGuard {
+    access: ptr::NonNull<Access>,
+    /// If the access is recorded as held by an execution.
+    held: bool,
+}
 
 impl Drop for RawAccessGuard {
     fn drop(&mut self) {
-        unsafe { self.0.as_ref().release() }
+        let access = unsafe { self.access.as_ref() };
+
+        if self.held {
+            access.release_held();
+        }
+
+        access.release();
     }
 }
 
@@ -464,6 +464,12 @@ impl AnyObj {
         }
     }
 
+    /// Test if the data is owned, as opposed to being a pointer to data held
+    /// elsewhere.
+    pub(crate) fn is_owned(&self) -> bool {
+        matches!(self.vtable.kind, AnyObjKind::Owned)
+    }
+
     /// Debug format the current any type.
     pub fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         (self.vtable.debug)(f)
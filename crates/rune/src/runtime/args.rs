@@ -45,10 +45,7 @@ repeat_macro!(impl_into_args);
 
 impl Args for Vec<Value> {
     fn into_stack(self, stack: &mut Stack) -> VmResult<()> {
-        for value in self {
-            stack.push(value);
-        }
-
+        stack.extend(self);
         VmResult::Ok(())
     }
 
@@ -60,3 +57,20 @@ impl Args for Vec<Value> {
         self.len()
     }
 }
+
+/// Borrowed arguments are cloned directly onto the stack, which avoids
+/// collecting them into an intermediate [`Vec`] for every call.
+impl Args for &[Value] {
+    fn into_stack(self, stack: &mut Stack) -> VmResult<()> {
+        stack.extend(self.iter().cloned());
+        VmResult::Ok(())
+    }
+
+    fn into_vec(self) -> VmResult<Vec<Value>> {
+        VmResult::Ok(self.to_vec())
+    }
+
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
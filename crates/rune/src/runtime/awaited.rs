@@ -1,4 +1,5 @@
-use crate::runtime::{Future, Select, Shared, ToValue, Vm, VmResult};
+use crate::runtime::borrow;
+use crate::runtime::{BorrowWait, Future, Select, Shared, ToValue, Vm, VmResult};
 
 /// A stored await task.
 #[derive(Debug)]
@@ -7,6 +8,8 @@ pub(crate) enum Awaited {
     Future(Shared<Future>),
     /// A select to be awaited.
     Select(Select),
+    /// A borrow held by another execution to be released.
+    Borrow(BorrowWait),
 }
 
 impl Awaited {
@@ -14,7 +17,15 @@ impl Awaited {
     pub(crate) async fn into_vm(self, vm: &mut Vm) -> VmResult<()> {
         match self {
             Self::Future(future) => {
-                let value = vm_try!(vm_try!(future.borrow_mut()).await.with_vm(vm));
+                let future = {
+                    // NB: the future is borrowed for as long as it's awaited,
+                    // so it's recorded as held by this execution.
+                    let mode = vm.context().borrow_mode();
+                    let _site = borrow::enter(mode, vm.unit(), vm.last_ip());
+                    vm_try!(future.into_mut())
+                };
+
+                let value = vm_try!(future.await.with_vm(vm));
                 vm.stack_mut().push(value);
             }
             Self::Select(select) => {
@@ -22,6 +33,9 @@ impl Awaited {
                 vm.stack_mut().push(value);
                 vm.stack_mut().push(vm_try!(ToValue::to_value(branch)));
             }
+            Self::Borrow(wait) => {
+                wait.await;
+            }
         }
 
         VmResult::Ok(())
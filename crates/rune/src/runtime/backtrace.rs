@@ -0,0 +1,217 @@
+//! Backtraces of errors raised by the virtual machine.
+
+use core::fmt;
+
+use crate::no_std::prelude::*;
+use crate::no_std::sync::Arc;
+
+use crate::hash::Hash;
+use crate::runtime::debug::{DebugInst, DebugSignature};
+use crate::runtime::{Unit, UnitFn};
+use crate::Sources;
+
+/// The call frames which were active when a [VmError] was raised, starting
+/// with the frame which raised it.
+///
+/// This is captured through [VmError::backtrace], and can be rendered into
+/// source locations through [Backtrace::display] if the units involved have
+/// [DebugInfo][crate::runtime::DebugInfo].
+///
+/// [VmError]: crate::runtime::VmError
+/// [VmError::backtrace]: crate::runtime::VmError::backtrace
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Source, Sources, Vm};
+/// use std::sync::Arc;
+///
+/// let context = Context::with_default_modules()?;
+///
+/// let mut sources = Sources::new();
+///
+/// sources.insert(Source::new("main", r#"
+/// fn divide(a, b) {
+///     a / b
+/// }
+///
+/// pub fn main() {
+///     divide(1, 0)
+/// }
+/// "#));
+///
+/// let unit = rune::prepare(&mut sources).build()?;
+/// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+///
+/// let error = vm.call(["main"], ()).unwrap_err();
+/// let backtrace = error.backtrace();
+///
+/// assert_eq!(backtrace.frames().len(), 2);
+/// assert_eq!(
+///     backtrace.display(&sources).to_string(),
+///     "   0: divide(a, b) at main:3:5\n   1: main() at main:7:5\n"
+/// );
+/// # Ok::<_, rune::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Backtrace {
+    frames: Vec<BacktraceFrame>,
+}
+
+impl Backtrace {
+    pub(crate) fn new(frames: Vec<BacktraceFrame>) -> Self {
+        Self { frames }
+    }
+
+    /// Get the frames of the backtrace, starting with the frame which raised
+    /// the error.
+    pub fn frames(&self) -> &[BacktraceFrame] {
+        &self.frames
+    }
+
+    /// Test if the backtrace is empty.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Render the backtrace with one line for each frame, consisting of the
+    /// signature of the function and the file, line and column of the
+    /// instruction being executed in it.
+    ///
+    /// Parts which can't be resolved through the debug info of the unit or
+    /// the given `sources` are omitted.
+    pub fn display<'a>(&'a self, sources: &'a Sources) -> impl fmt::Display + 'a {
+        DisplayBacktrace {
+            backtrace: self,
+            sources,
+        }
+    }
+}
+
+struct DisplayBacktrace<'a> {
+    backtrace: &'a Backtrace,
+    sources: &'a Sources,
+}
+
+impl fmt::Display for DisplayBacktrace<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (n, frame) in self.backtrace.frames.iter().enumerate() {
+            write!(f, "{n:>4}: ")?;
+
+            match (frame.signature(), frame.function) {
+                (Some(signature), _) => write!(f, "{signature}")?,
+                (None, Some(hash)) => write!(f, "{hash}")?,
+                (None, None) => write!(f, "<unknown>")?,
+            }
+
+            if let Some((name, line, column)) = frame.location(self.sources) {
+                write!(f, " at {name}:{line}:{column}")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A single frame in a [Backtrace].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BacktraceFrame {
+    unit: Arc<Unit>,
+    ip: usize,
+    function: Option<Hash>,
+    returning: bool,
+}
+
+impl BacktraceFrame {
+    /// Construct a frame executing the instruction at `ip`, or which is about
+    /// to return to `ip` if `returning` is set.
+    pub(crate) fn new(unit: Arc<Unit>, ip: usize, returning: bool) -> Self {
+        let function = function_containing(&unit, ip);
+
+        Self {
+            unit,
+            ip,
+            function,
+            returning,
+        }
+    }
+
+    /// Get the unit the frame is executing.
+    pub fn unit(&self) -> &Arc<Unit> {
+        &self.unit
+    }
+
+    /// Get the instruction pointer of the frame.
+    ///
+    /// For the frame which raised the error this is the instruction which
+    /// raised it, for the frames calling it this is the instruction which
+    /// the call would have returned to.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// Get the hash of the function the frame is executing, if the
+    /// instruction pointer is inside of a function in the unit.
+    pub fn function(&self) -> Option<Hash> {
+        self.function
+    }
+
+    /// Get the signature of the function the frame is executing, if the unit
+    /// has debug info.
+    pub fn signature(&self) -> Option<&DebugSignature> {
+        let debug_info = self.unit.debug_info()?;
+        debug_info.functions.get(&self.function?)
+    }
+
+    /// Get the debug info of the instruction being executed by the frame,
+    /// which for calling frames is the instruction performing the call.
+    pub fn debug_inst(&self) -> Option<&DebugInst> {
+        let debug_info = self.unit.debug_info()?;
+
+        if !self.returning {
+            return debug_info.instruction_at(self.ip);
+        }
+
+        // NB: the instruction performing the call is the closest one with
+        // debug info before the instruction it returns to.
+        let (_, debug_inst) = debug_info
+            .instructions
+            .iter()
+            .filter(|(ip, _)| **ip < self.ip)
+            .max_by_key(|(ip, _)| **ip)?;
+
+        Some(debug_inst)
+    }
+
+    /// Resolve the name of the source, and the one-based line and column of
+    /// the instruction being executed by the frame.
+    pub fn location<'a>(&self, sources: &'a Sources) -> Option<(&'a str, usize, usize)> {
+        let debug_inst = self.debug_inst()?;
+        let source = sources.get(debug_inst.source_id)?;
+        let (line, column) = source.pos_to_utf8_linecol(debug_inst.span.start.into_usize());
+        Some((source.name(), line + 1, column + 1))
+    }
+}
+
+/// Find the function whose instructions contain `ip`, which is the one with
+/// the closest offset before it.
+fn function_containing(unit: &Unit, ip: usize) -> Option<Hash> {
+    let (offset, hash) = unit
+        .iter_functions()
+        .filter_map(|(hash, f)| match f {
+            UnitFn::Offset { offset, .. } if *offset <= ip => Some((*offset, hash)),
+            _ => None,
+        })
+        // NB: instance functions are registered under multiple hashes, so
+        // prefer the lowest hash to be deterministic.
+        .max_by_key(|&(offset, hash)| (offset, core::cmp::Reverse(hash)))?;
+
+    // Prefer the hash debug info associates with the function.
+    match unit.debug_info().and_then(|d| d.functions_rev.get(&offset)) {
+        Some(hash) => Some(*hash),
+        None => Some(hash),
+    }
+}
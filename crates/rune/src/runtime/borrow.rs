@@ -0,0 +1,347 @@
+//! Tracking of borrows which are held by executions.
+//!
+//! Owned guards like [Mut][crate::runtime::Mut] which are acquired while a
+//! virtual machine calls a native function are recorded as being held at the
+//! instruction which performed the call. This is used to report who holds a
+//! borrow when it conflicts with another one, and to suspend executions until
+//! such a borrow is released with [BorrowMode::Queue].
+//!
+//! Borrows are only tracked by virtual machines using [BorrowMode::Queue], so
+//! the default mode doesn't pay for recording them. Only owned exclusive
+//! guards are tracked, and their state is stored in a global table keyed by the
+//! address of the access being borrowed, so it's released no matter which
+//! thread drops the guard.
+//!
+//! Borrows are only tracked if the `std` feature is enabled, since the call
+//! site is stored in thread-local storage.
+
+#[cfg(feature = "std")]
+use core::cell::Cell;
+use core::fmt;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+#[cfg(feature = "std")]
+use core::task::Waker;
+use core::task::{Context, Poll};
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::sync::{Mutex, PoisonError};
+
+use crate::no_std::prelude::*;
+use crate::no_std::sync::Arc;
+
+use crate::ast::Span;
+use crate::compile::ItemBuf;
+use crate::runtime::access::Access;
+use crate::runtime::panic_hook::function_containing;
+use crate::runtime::{Unit, VmError};
+use crate::SourceId;
+
+/// How a virtual machine handles calls which fail because a value is borrowed
+/// by another execution.
+///
+/// This is configured for every virtual machine constructed from a runtime
+/// context through [Context::set_borrow_mode].
+///
+/// [Context::set_borrow_mode]: crate::Context::set_borrow_mode
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BorrowMode {
+    /// Raise an error when a value is borrowed by another execution.
+    ///
+    /// Borrows aren't tracked in this mode, so the error doesn't indicate
+    /// where the conflicting borrow is held.
+    #[default]
+    Error,
+    /// Suspend the execution until the conflicting borrow is released, after
+    /// which the operation is retried.
+    ///
+    /// Borrows acquired by native functions are tracked in this mode, so
+    /// errors indicate where the conflicting borrow is held.
+    ///
+    /// This only applies to asynchronous executions, and to borrows which are
+    /// known to be held by an execution. Other conflicts raise an error like
+    /// with [BorrowMode::Error].
+    Queue,
+}
+
+/// The instruction at which an execution acquired a borrow it holds.
+#[derive(Clone)]
+pub struct BorrowSite {
+    unit: Arc<Unit>,
+    ip: usize,
+}
+
+impl BorrowSite {
+    /// The instruction pointer of the instruction which acquired the borrow.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// The function the borrow was acquired in, if the unit has debug info.
+    pub fn function(&self) -> Option<&ItemBuf> {
+        let debug = self.unit.debug_info()?;
+        Some(&function_containing(debug, self.ip)?.path)
+    }
+
+    /// The source and span of the instruction which acquired the borrow, if
+    /// the unit has debug info.
+    pub fn location(&self) -> Option<(SourceId, Span)> {
+        let inst = self.unit.debug_info()?.instruction_at(self.ip)?;
+        Some((inst.source_id, inst.span))
+    }
+}
+
+impl fmt::Display for BorrowSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "instruction {}", self.ip)?;
+
+        if let Some(function) = self.function() {
+            write!(f, " in `{function}`")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for BorrowSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BorrowSite")
+            .field("ip", &self.ip)
+            .field("location", &self.location())
+            .finish()
+    }
+}
+
+/// A borrow held by an execution.
+struct Held {
+    site: BorrowSite,
+    /// Notified once the borrow is released, which is set up the first time
+    /// the borrow conflicts with another one.
+    release: Option<Release>,
+}
+
+impl Held {
+    /// Get the site at which the borrow is held, and a notification for when
+    /// it's released.
+    fn snapshot(&mut self) -> (BorrowSite, Release) {
+        let release = self.release.get_or_insert_with(Release::new).clone();
+        (self.site.clone(), release)
+    }
+
+    /// Release the borrow, waking up any executions waiting for it.
+    fn release(self) {
+        if let Some(release) = self.release {
+            release.notify();
+        }
+    }
+}
+
+/// A notification for when a borrow held by an execution is released.
+#[derive(Debug, Clone)]
+pub(crate) struct Release {
+    #[cfg(feature = "std")]
+    state: Arc<Mutex<ReleaseState>>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+struct ReleaseState {
+    released: bool,
+    waiters: Vec<Waker>,
+}
+
+impl Release {
+    fn new() -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            state: Arc::default(),
+        }
+    }
+
+    fn notify(&self) {
+        #[cfg(feature = "std")]
+        {
+            let waiters = {
+                let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+                state.released = true;
+                core::mem::take(&mut state.waiters)
+            };
+
+            for waker in waiters {
+                waker.wake();
+            }
+        }
+    }
+
+    fn poll(&self, cx: &mut Context<'_>) -> Poll<()> {
+        #[cfg(feature = "std")]
+        {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+            if !state.released {
+                state.waiters.push(cx.waker().clone());
+                return Poll::Pending;
+            }
+        }
+
+        #[cfg(not(feature = "std"))]
+        let _ = cx;
+
+        Poll::Ready(())
+    }
+}
+
+#[cfg(feature = "std")]
+type RawSite = (*const Arc<Unit>, usize);
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    /// The instruction currently calling a native function, if borrows are
+    /// tracked by the calling virtual machine.
+    static CURRENT: Cell<Option<RawSite>> = Cell::new(None);
+}
+
+/// Borrows held by executions, keyed by the address of their access.
+#[cfg(feature = "std")]
+static HELD: Mutex<Option<HashMap<usize, Held>>> = Mutex::new(None);
+
+/// Access the table of held borrows.
+#[cfg(feature = "std")]
+fn with_held<F, O>(f: F) -> O
+where
+    F: FnOnce(&mut HashMap<usize, Held>) -> O,
+{
+    let mut held = HELD.lock().unwrap_or_else(PoisonError::into_inner);
+    f(held.get_or_insert_with(HashMap::new))
+}
+
+#[cfg(feature = "std")]
+fn key(access: &Access) -> usize {
+    access as *const Access as usize
+}
+
+/// Guard returned by [enter], which restores the previous call site once
+/// dropped.
+pub(crate) struct SiteGuard<'a> {
+    #[cfg(feature = "std")]
+    old: Option<RawSite>,
+    _marker: PhantomData<&'a Arc<Unit>>,
+}
+
+impl Drop for SiteGuard<'_> {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        CURRENT.with(|current| current.set(self.old));
+    }
+}
+
+/// Enter the instruction at `ip` of `unit`, which borrows acquired while the
+/// returned guard is live are recorded as held at.
+///
+/// Borrows are only recorded with [BorrowMode::Queue]. Otherwise the call site
+/// is cleared, so that borrows acquired by a nested execution aren't recorded
+/// as held by an outer one.
+pub(crate) fn enter(mode: BorrowMode, unit: &Arc<Unit>, ip: usize) -> SiteGuard<'_> {
+    #[cfg(not(feature = "std"))]
+    let _ = (mode, unit, ip);
+
+    #[cfg(feature = "std")]
+    let site = match mode {
+        BorrowMode::Queue => Some((unit as *const _, ip)),
+        BorrowMode::Error => None,
+    };
+
+    SiteGuard {
+        #[cfg(feature = "std")]
+        old: CURRENT.with(|current| current.replace(site)),
+        _marker: PhantomData,
+    }
+}
+
+/// Record the given access as held at the current call site, returning `true`
+/// if a native function is being called by an execution which tracks borrows.
+pub(crate) fn hold(access: &Access) -> bool {
+    #[cfg(feature = "std")]
+    {
+        let Some((unit, ip)) = CURRENT.with(|current| current.get()) else {
+            return false;
+        };
+
+        // SAFETY: the site is only set while the guard returned by `enter` is
+        // live, which borrows the unit.
+        let unit = unsafe { (*unit).clone() };
+
+        let held = Held {
+            site: BorrowSite { unit, ip },
+            release: None,
+        };
+
+        with_held(|map| map.insert(key(access), held));
+        true
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = access;
+        false
+    }
+}
+
+/// Release a borrow recorded through [hold], waking up any executions waiting
+/// for it.
+pub(crate) fn release(access: &Access) {
+    #[cfg(feature = "std")]
+    if let Some(held) = with_held(|map| map.remove(&key(access))) {
+        held.release();
+    }
+
+    #[cfg(not(feature = "std"))]
+    let _ = access;
+}
+
+/// Get the site at which the given access is held and a notification for when
+/// it's released, if it's held by an execution.
+pub(crate) fn holder(access: &Access) -> Option<(BorrowSite, Release)> {
+    #[cfg(feature = "std")]
+    {
+        with_held(|map| Some(map.get_mut(&key(access))?.snapshot()))
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = access;
+        None
+    }
+}
+
+/// A call which failed because a value is borrowed by another execution, and
+/// which should be retried once the borrow is released.
+#[derive(Debug)]
+pub(crate) struct BorrowWait {
+    release: Release,
+    error: VmError,
+}
+
+impl BorrowWait {
+    pub(crate) fn new(release: Release, error: VmError) -> Self {
+        Self { release, error }
+    }
+
+    /// Convert into the error which caused the wait, which is used when the
+    /// execution can't be suspended.
+    pub(crate) fn into_error(self) -> VmError {
+        self.error
+    }
+}
+
+impl Future for BorrowWait {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.release.poll(cx)
+    }
+}
@@ -0,0 +1,175 @@
+use core::fmt::Write;
+
+use crate::no_std::prelude::*;
+use crate::no_std::sync::Arc;
+
+use crate::runtime::{Unit, Value, Vm, VmResult};
+use crate::{BuildError, Context, Source, Sources};
+
+/// A breakpoint at an instruction, which suspends an execution run with
+/// [VmExecution::run_to_breakpoint][crate::runtime::VmExecution::run_to_breakpoint]
+/// before the instruction is executed.
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Source, Sources, Vm};
+/// use rune::runtime::Breakpoint;
+/// use std::sync::Arc;
+///
+/// let context = Context::with_default_modules()?;
+///
+/// let source = r#"
+/// pub fn main() {
+///     let total = 0;
+///
+///     for n in 0..10 {
+///         total += n;
+///     }
+///
+///     total
+/// }
+/// "#;
+///
+/// let mut sources = Sources::new();
+/// sources.insert(Source::new("entry", source));
+///
+/// let unit = Arc::new(rune::prepare(&mut sources).with_context(&context).build()?);
+///
+/// // Find the first instruction of the `total += n` statement.
+/// let start = source.find("total += n").unwrap();
+///
+/// let debug = unit.debug_info().unwrap();
+///
+/// let ip = debug
+///     .instructions
+///     .iter()
+///     .filter(|(_, inst)| inst.span.start.into_usize() == start)
+///     .map(|(ip, _)| *ip)
+///     .min()
+///     .unwrap();
+///
+/// let mut breakpoints = [Breakpoint::with_condition(&context, &unit, ip, "n == 7")?];
+///
+/// let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+/// let mut execution = vm.execute(["main"], ())?;
+///
+/// // Execution is suspended at the breakpoint once `n` is 7.
+/// assert!(execution.run_to_breakpoint(&mut breakpoints).into_result()?.is_none());
+/// assert_eq!(execution.vm().ip(), ip);
+///
+/// // And then runs to completion, since the condition isn't met again.
+/// let output = execution.run_to_breakpoint(&mut breakpoints).into_result()?;
+/// assert_eq!(output.unwrap().as_integer().into_result()?, 45);
+/// # Ok::<_, rune::Error>(())
+/// ```
+pub struct Breakpoint {
+    ip: usize,
+    condition: Option<Condition>,
+}
+
+/// A compiled breakpoint condition.
+struct Condition {
+    /// Stack offsets of the variables passed to the condition.
+    offsets: Vec<usize>,
+    /// The virtual machine the condition is evaluated in.
+    vm: Vm,
+}
+
+impl Breakpoint {
+    /// Construct a breakpoint at the given instruction pointer.
+    pub fn new(ip: usize) -> Self {
+        Self {
+            ip,
+            condition: None,
+        }
+    }
+
+    /// Construct a breakpoint at the given instruction pointer, which only
+    /// suspends execution when `condition` evaluates to `true`.
+    ///
+    /// The condition is an expression which can refer to the variables which
+    /// have been declared before the instruction in the function containing
+    /// it, as described by [DebugInfo::variables_at]. It's compiled with the
+    /// given `context`, which should be the one `unit` was compiled with.
+    ///
+    /// Variables can only be referenced if the unit includes debug information
+    /// at [DebugLevel::Full]. Variables which are no longer on the stack when
+    /// the condition is evaluated are `()`.
+    ///
+    /// [DebugInfo::variables_at]: crate::runtime::DebugInfo::variables_at
+    /// [DebugLevel::Full]: crate::compile::DebugLevel::Full
+    pub fn with_condition(
+        context: &Context,
+        unit: &Unit,
+        ip: usize,
+        condition: &str,
+    ) -> Result<Self, BuildError> {
+        let variables = unit
+            .debug_info()
+            .map(|debug| debug.variables_at(ip))
+            .unwrap_or_default();
+
+        let mut source = String::from("pub fn condition(");
+        let mut offsets = Vec::with_capacity(variables.len());
+
+        for (index, variable) in variables.into_iter().enumerate() {
+            if index > 0 {
+                source.push_str(", ");
+            }
+
+            source.push_str(&variable.name);
+            offsets.push(variable.offset);
+        }
+
+        // NB: the condition is on its own line so that it can't comment out
+        // the end of the function.
+        let _ = write!(source, ") {{\n{condition}\n}}");
+
+        let mut sources = Sources::new();
+        sources.insert(Source::new("condition", source));
+
+        let condition = crate::prepare(&mut sources).with_context(context).build()?;
+
+        let vm = Vm::new(Arc::new(context.runtime()), Arc::new(condition));
+
+        Ok(Self {
+            ip,
+            condition: Some(Condition { offsets, vm }),
+        })
+    }
+
+    /// The instruction pointer of the breakpoint.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// Test if the breakpoint is hit by the given virtual machine, which is
+    /// the case if it's about to execute the instruction of the breakpoint and
+    /// its condition evaluates to `true`.
+    pub(crate) fn is_hit(&mut self, vm: &Vm) -> VmResult<bool> {
+        if vm.ip() != self.ip {
+            return VmResult::Ok(false);
+        }
+
+        let Some(condition) = &mut self.condition else {
+            return VmResult::Ok(true);
+        };
+
+        let stack = vm.stack();
+
+        let args = condition
+            .offsets
+            .iter()
+            .map(|offset| {
+                stack
+                    .get(stack.stack_bottom() + offset)
+                    .cloned()
+                    .unwrap_or(Value::EmptyTuple)
+            })
+            .collect::<Vec<_>>();
+
+        let value = vm_try!(condition.vm.call(["condition"], args));
+        value.as_bool()
+    }
+}
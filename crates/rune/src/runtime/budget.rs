@@ -35,6 +35,13 @@ pub fn with<T>(budget: usize, value: T) -> Budget<T> {
     Budget { budget, value }
 }
 
+/// Get the budget remaining for the current thread, which is `usize::MAX` if
+/// no budget is in effect.
+#[inline]
+pub(crate) fn get() -> usize {
+    self::no_std::rune_budget_get()
+}
+
 /// Take a ticket from the budget, indicating with `true` if the budget is
 /// maintained
 #[inline(never)]
@@ -11,7 +11,9 @@ use crate::no_std::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate as rune;
-use crate::runtime::{RawRef, Ref, UnsafeToRef, Value, VmResult};
+use crate::runtime::{
+    FromValue, Mut, RawMut, RawRef, Ref, UnsafeToMut, UnsafeToRef, Value, VmResult,
+};
 use crate::Any;
 
 /// A vector of bytes.
@@ -88,6 +90,22 @@ impl Bytes {
         &self.bytes
     }
 
+    /// Access bytes as a mutable slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::Bytes;
+    ///
+    /// let mut bytes = Bytes::from_vec(vec![b'a', b'b', b'c', b'd']);
+    /// bytes.as_mut_slice()[0] = b'b';
+    /// assert_eq!(bytes.as_slice(), &[b'b', b'b', b'c', b'd']);
+    /// ```
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+
     /// Convert a slice into bytes.
     ///
     /// Calling this function allocates bytes internally.
@@ -305,6 +323,37 @@ impl UnsafeToRef for [u8] {
     }
 }
 
+impl UnsafeToMut for [u8] {
+    type Guard = RawMut;
+
+    unsafe fn unsafe_to_mut<'a>(value: Value) -> VmResult<(&'a mut Self, Self::Guard)> {
+        let bytes = vm_try!(value.into_bytes());
+        let bytes = vm_try!(bytes.into_mut());
+        let (mut value, guard) = Mut::into_raw(bytes);
+        // Safety: we're holding onto the guard for the slice here, so it is
+        // live.
+        VmResult::Ok((value.as_mut().as_mut_slice(), guard))
+    }
+}
+
+impl FromValue for Ref<[u8]> {
+    fn from_value(value: Value) -> VmResult<Self> {
+        let bytes = vm_try!(value.into_bytes());
+        VmResult::Ok(Ref::map(vm_try!(bytes.into_ref()), |bytes| {
+            bytes.as_slice()
+        }))
+    }
+}
+
+impl FromValue for Mut<[u8]> {
+    fn from_value(value: Value) -> VmResult<Self> {
+        let bytes = vm_try!(value.into_bytes());
+        VmResult::Ok(Mut::map(vm_try!(bytes.into_mut()), |bytes| {
+            bytes.as_mut_slice()
+        }))
+    }
+}
+
 impl<const N: usize> cmp::PartialEq<[u8; N]> for Bytes {
     #[inline]
     fn eq(&self, other: &[u8; N]) -> bool {
@@ -0,0 +1,84 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::no_std::sync::Arc;
+
+/// A handle which can be used to cancel executions of a virtual machine, for
+/// example from another thread or task.
+///
+/// A token is installed through [Vm::set_cancellation_token], after which the
+/// virtual machine checks it whenever a function is called and whenever it
+/// jumps unconditionally, like at the end of every iteration in a loop. Once
+/// the token has been cancelled the execution fails with an error for which
+/// [VmError::is_cancelled] returns `true`.
+///
+/// Cloning a token produces a handle to the same token, so any clone can be
+/// used to cancel it. Virtual machines which are created to run async
+/// functions, generators and streams inherit the token of the virtual machine
+/// calling them.
+///
+/// [Vm::set_cancellation_token]: crate::Vm::set_cancellation_token
+/// [VmError::is_cancelled]: crate::runtime::VmError::is_cancelled
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Vm};
+/// use rune::runtime::CancellationToken;
+/// use std::sync::Arc;
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             loop {}
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+///
+/// let token = CancellationToken::new();
+///
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+/// vm.set_cancellation_token(Some(token.clone()));
+///
+/// let handle = thread::spawn(move || {
+///     thread::sleep(Duration::from_millis(10));
+///     token.cancel();
+/// });
+///
+/// let error = vm.call(["main"], ()).unwrap_err();
+/// assert!(error.is_cancelled());
+///
+/// handle.join().unwrap();
+/// # Ok::<_, rune::Error>(())
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Construct a new token which hasn't been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel every execution which uses this token.
+    ///
+    /// Cancelling a token can't be undone, so a new token has to be installed
+    /// to run the virtual machine again.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Test if the token has been cancelled.
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
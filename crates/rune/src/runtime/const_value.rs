@@ -33,7 +33,10 @@ pub enum ConstValue {
     /// An anonymous tuple.
     Tuple(Box<[ConstValue]>),
     /// An anonymous object.
-    Object(HashMap<String, ConstValue>),
+    Object(
+        #[serde(serialize_with = "crate::runtime::sorted::serialize_map")]
+        HashMap<String, ConstValue>,
+    ),
     /// An option.
     Option(Option<Box<ConstValue>>),
 }
@@ -87,6 +90,54 @@ impl ConstValue {
         }
     }
 
+    /// Convert a reference into a virtual machine value.
+    ///
+    /// This is equivalent to `self.clone().into_value()`, but copies the
+    /// contents of the constant value directly into the value instead of
+    /// cloning it first.
+    pub(crate) fn to_owned_value(&self) -> Value {
+        match self {
+            Self::Byte(b) => Value::Byte(*b),
+            Self::Char(c) => Value::Char(*c),
+            Self::Bool(b) => Value::Bool(*b),
+            Self::Integer(n) => Value::Integer(*n),
+            Self::Float(n) => Value::Float(*n),
+            Self::String(s) => Value::String(Shared::new(s.clone())),
+            Self::Bytes(b) => Value::Bytes(Shared::new(b.clone())),
+            Self::Option(option) => Value::Option(Shared::new(
+                option.as_ref().map(|some| some.to_owned_value()),
+            )),
+            Self::Vec(vec) => {
+                let mut v = Vec::with_capacity(vec.len());
+
+                for value in vec {
+                    v.push(value.to_owned_value());
+                }
+
+                Value::Vec(Shared::new(v))
+            }
+            Self::EmptyTuple => Value::EmptyTuple,
+            Self::Tuple(tuple) => {
+                let mut t = vec::Vec::with_capacity(tuple.len());
+
+                for value in tuple.iter() {
+                    t.push(value.to_owned_value());
+                }
+
+                Value::Tuple(Shared::new(OwnedTuple::from(t)))
+            }
+            Self::Object(object) => {
+                let mut o = Object::with_capacity(object.len());
+
+                for (key, value) in object {
+                    o.insert(key.clone(), value.to_owned_value());
+                }
+
+                Value::Object(Shared::new(o))
+            }
+        }
+    }
+
     /// Try to coerce into boolean.
     pub fn into_bool(self) -> Result<bool, Self> {
         match self {
@@ -112,6 +163,60 @@ impl ConstValue {
             Self::Option(..) => TypeInfo::StaticType(crate::runtime::static_type::OPTION_TYPE),
         }
     }
+
+    /// Construct a constant value by copying out of the given value, without
+    /// taking ownership of any shared data it refers to.
+    pub(crate) fn from_value_ref(value: &Value) -> VmResult<Self> {
+        VmResult::Ok(match value {
+            Value::EmptyTuple => Self::EmptyTuple,
+            Value::Byte(b) => Self::Byte(*b),
+            Value::Char(c) => Self::Char(*c),
+            Value::Bool(b) => Self::Bool(*b),
+            Value::Integer(n) => Self::Integer(*n),
+            Value::Float(f) => Self::Float(*f),
+            Value::String(s) => Self::String(vm_try!(s.borrow_ref()).clone()),
+            Value::Option(option) => Self::Option(match &*vm_try!(option.borrow_ref()) {
+                Some(some) => Some(Box::new(vm_try!(Self::from_value_ref(some)))),
+                None => None,
+            }),
+            Value::Bytes(b) => Self::Bytes(vm_try!(b.borrow_ref()).clone()),
+            Value::Vec(vec) => {
+                let vec = vm_try!(vec.borrow_ref());
+                let mut const_vec = vec::Vec::with_capacity(vec.len());
+
+                for value in &*vec {
+                    const_vec.push(vm_try!(Self::from_value_ref(value)));
+                }
+
+                Self::Vec(const_vec)
+            }
+            Value::Tuple(tuple) => {
+                let tuple = vm_try!(tuple.borrow_ref());
+                let mut const_tuple = vec::Vec::with_capacity(tuple.len());
+
+                for value in tuple.iter() {
+                    const_tuple.push(vm_try!(Self::from_value_ref(value)));
+                }
+
+                Self::Tuple(const_tuple.into_boxed_slice())
+            }
+            Value::Object(object) => {
+                let object = vm_try!(object.borrow_ref());
+                let mut const_object = HashMap::with_capacity(object.len());
+
+                for (key, value) in &*object {
+                    const_object.insert(key.clone(), vm_try!(Self::from_value_ref(value)));
+                }
+
+                Self::Object(const_object)
+            }
+            value => {
+                return VmResult::err(VmErrorKind::ConstNotSupported {
+                    actual: vm_try!(value.type_info()),
+                })
+            }
+        })
+    }
 }
 
 impl FromValue for ConstValue {
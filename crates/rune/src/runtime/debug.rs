@@ -2,7 +2,7 @@
 
 use core::fmt;
 
-use crate::no_std::collections::HashMap;
+use crate::no_std::collections::{BTreeMap, HashMap};
 use crate::no_std::prelude::*;
 
 use serde::{Deserialize, Serialize};
@@ -18,13 +18,20 @@ use crate::SourceId;
 #[non_exhaustive]
 pub struct DebugInfo {
     /// Debug information on each instruction.
+    #[serde(serialize_with = "crate::runtime::sorted::serialize_map")]
     pub instructions: HashMap<usize, DebugInst>,
     /// Function signatures.
+    #[serde(serialize_with = "crate::runtime::sorted::serialize_map")]
     pub functions: HashMap<Hash, DebugSignature>,
     /// Reverse lookup of a function.
+    #[serde(serialize_with = "crate::runtime::sorted::serialize_map")]
     pub functions_rev: HashMap<usize, Hash>,
     /// Hash to identifier.
+    #[serde(serialize_with = "crate::runtime::sorted::serialize_map")]
     pub hash_to_ident: HashMap<Hash, Box<str>>,
+    /// Variables by the instruction pointer they are declared at.
+    #[serde(default)]
+    pub variables: BTreeMap<usize, Vec<DebugVariable>>,
 }
 
 impl DebugInfo {
@@ -44,6 +51,48 @@ impl DebugInfo {
     pub fn ident_for_hash(&self, hash: Hash) -> Option<&str> {
         Some(self.hash_to_ident.get(&hash)?)
     }
+
+    /// Get the variables of the function containing the given instruction
+    /// pointer which have been declared before it.
+    ///
+    /// If a variable is shadowed, or its stack slot has been reused by a
+    /// variable declared later, only the most recently declared variable is
+    /// included.
+    pub fn variables_at(&self, ip: usize) -> Vec<&DebugVariable> {
+        let start = self
+            .functions_rev
+            .keys()
+            .copied()
+            .filter(|offset| *offset <= ip)
+            .max()
+            .unwrap_or_default();
+
+        let mut output = Vec::<&DebugVariable>::new();
+
+        for variable in self.variables.range(start..=ip).flat_map(|(_, v)| v) {
+            output.retain(|v| v.name != variable.name && v.offset != variable.offset);
+            output.push(variable);
+        }
+
+        output
+    }
+}
+
+/// Debug information on a variable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct DebugVariable {
+    /// The name of the variable.
+    pub name: Box<str>,
+    /// The offset of the variable from the bottom of the stack frame.
+    pub offset: usize,
+}
+
+impl DebugVariable {
+    /// Construct debug information on a variable.
+    pub fn new(name: Box<str>, offset: usize) -> Self {
+        Self { name, offset }
+    }
 }
 
 /// Debug information for every instruction.
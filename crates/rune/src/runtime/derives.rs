@@ -0,0 +1,116 @@
+use core::fmt;
+
+use crate::no_std::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::{Formatter, Object, ProtocolCaller, Value, VmResult};
+#[cfg(feature = "std")]
+use crate::{runtime::Hasher, Hash};
+
+/// The protocols which are derived for a type defined in a script, through an
+/// attribute like `#[derive(PartialEq, Hash, Debug)]`.
+///
+/// Derived protocols are implemented by visiting the fields of the value in
+/// the same way as the built-in types like tuples and objects do, so a derived
+/// protocol can only be used if it is supported by all fields.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Derives {
+    /// `PartialEq`, which derives the `PARTIAL_EQ` protocol.
+    ///
+    /// Values of types defined in scripts are always compared field by field,
+    /// so this is accepted to mirror Rust.
+    pub partial_eq: bool,
+    /// `Eq`, which derives the `EQ` protocol.
+    ///
+    /// Like `PartialEq` this is accepted to mirror Rust.
+    pub eq: bool,
+    /// `Hash`, which derives the `HASH` protocol.
+    pub hash: bool,
+    /// `Debug`, which derives the `STRING_DEBUG` protocol.
+    pub debug: bool,
+}
+
+/// Format the fields of a tuple type which derives `Debug`, like `(1, 2)`.
+pub(crate) fn debug_tuple(
+    values: &[Value],
+    f: &mut Formatter,
+    caller: &mut impl ProtocolCaller,
+) -> VmResult<fmt::Result> {
+    let mut it = values.iter().peekable();
+    vm_write!(f, "(");
+
+    while let Some(value) = it.next() {
+        if let Err(fmt::Error) = vm_try!(value.string_debug_with(f, caller)) {
+            return VmResult::Ok(Err(fmt::Error));
+        }
+
+        if it.peek().is_some() {
+            vm_write!(f, ", ");
+        }
+    }
+
+    vm_write!(f, ")");
+    VmResult::Ok(Ok(()))
+}
+
+/// Format the fields of a struct type which derives `Debug`, like
+/// ` { a: 1, b: 2 }`.
+pub(crate) fn debug_struct(
+    object: &Object,
+    f: &mut Formatter,
+    caller: &mut impl ProtocolCaller,
+) -> VmResult<fmt::Result> {
+    let mut it = object.iter().peekable();
+    vm_write!(f, " {{");
+
+    if it.peek().is_some() {
+        vm_write!(f, " ");
+    }
+
+    while let Some((key, value)) = it.next() {
+        vm_write!(f, "{key}: ");
+
+        if let Err(fmt::Error) = vm_try!(value.string_debug_with(f, caller)) {
+            return VmResult::Ok(Err(fmt::Error));
+        }
+
+        if it.peek().is_some() {
+            vm_write!(f, ", ");
+        } else {
+            vm_write!(f, " ");
+        }
+    }
+
+    vm_write!(f, "}}");
+    VmResult::Ok(Ok(()))
+}
+
+/// Hash the type of a value whose type derives `Hash`, so that values of
+/// different types with the same fields hash differently.
+#[cfg(feature = "std")]
+pub(crate) fn hash_type(hash: Hash, hasher: &mut Hasher) {
+    hasher.write(&hash.into_inner().to_le_bytes());
+}
+
+/// Hash the fields of a struct type which derives `Hash`.
+///
+/// Fields are hashed in order of their names, since structs are equal as long
+/// as all of their fields are.
+#[cfg(feature = "std")]
+pub(crate) fn hash_struct(
+    object: &Object,
+    hasher: &mut Hasher,
+    caller: &mut impl ProtocolCaller,
+) -> VmResult<()> {
+    let mut fields = object.iter().collect::<Vec<_>>();
+    fields.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (key, value) in fields {
+        hasher.write_str(key);
+        vm_try!(value.hash_with(hasher, caller));
+    }
+
+    VmResult::Ok(())
+}
@@ -12,7 +12,7 @@ mod no_std;
 
 use crate::no_std::sync::Arc;
 
-use crate::runtime::{RuntimeContext, Unit, VmErrorKind, VmResult};
+use crate::runtime::{Mocks, RuntimeContext, Signals, Unit, VmErrorKind, VmResult};
 
 /// Call the given closure with access to the checked environment.
 pub(crate) fn with<F, T>(c: F) -> VmResult<T>
@@ -20,7 +20,7 @@ where
     F: FnOnce(&Arc<RuntimeContext>, &Arc<Unit>) -> VmResult<T>,
 {
     let env = self::no_std::rune_env_get();
-    let Env { context, unit } = env;
+    let Env { context, unit, .. } = env;
 
     if context.is_null() || unit.is_null() {
         return VmResult::err(VmErrorKind::MissingInterfaceEnvironment);
@@ -32,18 +32,90 @@ where
     c(unsafe { &*context }, unsafe { &*unit })
 }
 
+/// Call the given closure with access to the signals of the virtual machine
+/// which is currently running.
+pub(crate) fn with_signals<F, T>(c: F) -> VmResult<T>
+where
+    F: FnOnce(&Signals) -> VmResult<T>,
+{
+    let Env { signals, .. } = self::no_std::rune_env_get();
+
+    if signals.is_null() {
+        return VmResult::err(VmErrorKind::MissingInterfaceEnvironment);
+    }
+
+    // Safety: signals can only be registered through [Guard], which makes
+    // sure that they are live for the duration of the registration.
+    c(unsafe { &*signals })
+}
+
+/// Call the given closure with access to the mocks of the virtual machine
+/// which is currently running.
+pub(crate) fn with_mocks<F, T>(c: F) -> VmResult<T>
+where
+    F: FnOnce(&Mocks) -> VmResult<T>,
+{
+    let Env { mocks, .. } = self::no_std::rune_env_get();
+
+    if mocks.is_null() {
+        return VmResult::err(VmErrorKind::MissingInterfaceEnvironment);
+    }
+
+    // Safety: mocks can only be registered through [Guard], which makes sure
+    // that they are live for the duration of the registration.
+    c(unsafe { &*mocks })
+}
+
+/// Get mocks sharing their functions with the ones of the virtual machine
+/// which is currently running, or disabled mocks if there is none.
+pub(crate) fn mocks() -> Mocks {
+    let Env { mocks, .. } = self::no_std::rune_env_get();
+
+    if mocks.is_null() {
+        return Mocks::new();
+    }
+
+    // Safety: mocks can only be registered through [Guard], which makes sure
+    // that they are live for the duration of the registration.
+    unsafe { (*mocks).share() }
+}
+
 pub(crate) struct Guard {
     old: Env,
 }
 
 impl Guard {
-    /// Construct a new environment guard with the given context and unit.
+    /// Construct a new environment guard with the given context, unit, signals
+    /// and mocks.
+    ///
+    /// Signals which haven't been used, like the ones of a virtual machine
+    /// constructed to call a function from native code, are replaced by the
+    /// signals of the environment which is already registered.
     ///
     /// # Safety
     ///
     /// The returned guard must be dropped before the pointed to elements are.
-    pub(crate) fn new(context: *const Arc<RuntimeContext>, unit: *const Arc<Unit>) -> Guard {
-        let old = self::no_std::rune_env_replace(Env { context, unit });
+    pub(crate) fn new(
+        context: *const Arc<RuntimeContext>,
+        unit: *const Arc<Unit>,
+        signals: &Signals,
+        mocks: &Mocks,
+    ) -> Guard {
+        let current = self::no_std::rune_env_get();
+
+        let signals = if signals.is_used() || current.signals.is_null() {
+            signals as *const _
+        } else {
+            current.signals
+        };
+
+        let old = self::no_std::rune_env_replace(Env {
+            context,
+            unit,
+            signals,
+            mocks,
+        });
+
         Guard { old }
     }
 }
@@ -58,6 +130,8 @@ impl Drop for Guard {
 struct Env {
     context: *const Arc<RuntimeContext>,
     unit: *const Arc<Unit>,
+    signals: *const Signals,
+    mocks: *const Mocks,
 }
 
 impl Env {
@@ -66,6 +140,8 @@ impl Env {
         Self {
             context: core::ptr::null(),
             unit: core::ptr::null(),
+            signals: core::ptr::null(),
+            mocks: core::ptr::null(),
         }
     }
 }
@@ -32,6 +32,8 @@ unsafe fn from_env(env: Env) -> RawEnv {
     RawEnv {
         context: env.context as *const _,
         unit: env.unit as *const _,
+        signals: env.signals as *const _,
+        mocks: env.mocks as *const _,
     }
 }
 
@@ -39,5 +41,7 @@ unsafe fn from_raw_env(env: RawEnv) -> Env {
     Env {
         context: env.context as *const _,
         unit: env.unit as *const _,
+        signals: env.signals as *const _,
+        mocks: env.mocks as *const _,
     }
 }
@@ -0,0 +1,119 @@
+use crate::runtime::debug::DebugSignature;
+use crate::runtime::{DebugInfo, Inst};
+use crate::Hash;
+
+/// Statistics collected while executing a function in a virtual machine.
+///
+/// Collecting these is cheap enough that it is always enabled, which makes
+/// them suitable for tracking performance budgets of scripts. The report of
+/// the most recent execution is available through [Vm::last_execution_report]
+/// or [VmExecution::report].
+///
+/// [Vm::last_execution_report]: crate::Vm::last_execution_report
+/// [VmExecution::report]: crate::runtime::VmExecution::report
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Vm};
+/// use std::sync::Arc;
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn add(a, b) {
+///             a + b
+///         }
+///
+///         pub fn main() {
+///             add(add(1, 2), 3)
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+///
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+/// let output: i64 = rune::from_value(vm.call(["main"], ())?)?;
+/// assert_eq!(output, 6);
+///
+/// let report = vm.last_execution_report();
+/// assert_eq!(report.calls, 2);
+/// assert!(report.instructions > 0);
+///
+/// let debug = vm.unit().debug_info().expect("debug info");
+/// let signature = report.signature(debug).expect("signature");
+/// assert_eq!(signature.path.to_string(), "main");
+/// # Ok::<_, rune::Error>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ExecutionReport {
+    /// The hash of the function the execution was started with, if it was
+    /// started through an entrypoint like [Vm::execute][crate::Vm::execute].
+    pub function: Option<Hash>,
+    /// The number of instructions executed.
+    ///
+    /// Instructions which are executed as native code, like in functions
+    /// compiled through `Unit::jit`, are not counted.
+    pub instructions: u64,
+    /// The largest number of values which were on the stack at once.
+    pub max_stack_depth: usize,
+    /// The number of call instructions executed.
+    pub calls: u64,
+    /// The number of futures awaited, including `select` expressions.
+    pub awaits: u64,
+    /// The number of values allocated, like vectors, tuples, objects and
+    /// instances of structs and variants.
+    pub allocations: u64,
+    /// The number of calls which were executed as native code generated
+    /// through `Unit::jit`. Calls made from within native code are not
+    /// counted.
+    pub native_calls: u64,
+}
+
+impl ExecutionReport {
+    /// Construct a new empty report for the given function.
+    pub(crate) const fn new(function: Option<Hash>) -> Self {
+        Self {
+            function,
+            instructions: 0,
+            max_stack_depth: 0,
+            calls: 0,
+            awaits: 0,
+            allocations: 0,
+            native_calls: 0,
+        }
+    }
+
+    /// Look up the signature of the function the execution was started with
+    /// in the given debug info.
+    pub fn signature<'a>(&self, debug: &'a DebugInfo) -> Option<&'a DebugSignature> {
+        debug.functions.get(&self.function?)
+    }
+
+    /// Record that the given instruction is about to be executed with a stack
+    /// of the given size.
+    #[inline]
+    pub(crate) fn record(&mut self, inst: &Inst, stack: usize) {
+        self.instructions = self.instructions.wrapping_add(1);
+        self.max_stack_depth = self.max_stack_depth.max(stack);
+
+        match inst {
+            Inst::Call { .. }
+            | Inst::CallOffset { .. }
+            | Inst::CallUnpack { .. }
+            | Inst::CallAssociated { .. }
+            | Inst::CallAssociatedTyped { .. }
+            | Inst::CallFn { .. } => {
+                self.calls = self.calls.wrapping_add(1);
+            }
+            Inst::Await | Inst::Select { .. } => {
+                self.awaits = self.awaits.wrapping_add(1);
+            }
+            _ => {}
+        }
+    }
+}
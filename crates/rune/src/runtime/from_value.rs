@@ -304,6 +304,17 @@ impl FromValue for Ref<str> {
     }
 }
 
+impl FromValue for Mut<str> {
+    fn from_value(value: Value) -> VmResult<Self> {
+        match value {
+            Value::String(string) => {
+                VmResult::Ok(Mut::map(vm_try!(string.into_mut()), |s| s.as_mut_str()))
+            }
+            actual => VmResult::err(VmErrorKind::expected::<String>(vm_try!(actual.type_info()))),
+        }
+    }
+}
+
 impl UnsafeToRef for str {
     type Guard = RawRef;
 
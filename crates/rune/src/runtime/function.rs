@@ -39,7 +39,7 @@ use crate::Hash;
 /// let build_some = build;
 /// assert_eq!(build_some(42), Some(42));
 /// ```
-#[derive(Any)]
+#[derive(Any, Clone)]
 #[repr(transparent)]
 #[rune(builtin, static_type = FUNCTION_TYPE)]
 pub struct Function(FunctionImpl<Value>);
@@ -558,7 +558,8 @@ where
     pub(crate) fn call_with_vm(&self, vm: &mut Vm, args: usize) -> VmResult<Option<VmHalt>> {
         let reason = match &self.inner {
             Inner::FnHandler(handler) => {
-                vm_try!((handler.handler)(vm.stack_mut(), args));
+                let result = (handler.handler)(vm.stack_mut(), args);
+                vm_try!(result.with_argument_names(vm.context(), handler.hash));
                 None
             }
             Inner::FnOffset(fn_offset) => {
@@ -837,11 +838,14 @@ impl FnOffset {
     {
         vm_try!(check_args(args.count(), self.args));
 
-        let mut vm = Vm::new(self.context.clone(), self.unit.clone());
+        // Size the stack up front so that pushing the arguments doesn't have
+        // to reallocate.
+        let mut stack = Stack::with_capacity(args.count() + extra.count());
+        vm_try!(args.into_stack(&mut stack));
+        vm_try!(extra.into_stack(&mut stack));
 
+        let mut vm = Vm::with_stack(self.context.clone(), self.unit.clone(), stack);
         vm.set_ip(self.offset);
-        vm_try!(args.into_stack(vm.stack_mut()));
-        vm_try!(extra.into_stack(vm.stack_mut()));
 
         self.call.call_with_vm(vm)
     }
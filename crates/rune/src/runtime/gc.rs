@@ -0,0 +1,419 @@
+use core::mem::{replace, take};
+use core::time::Duration;
+
+use std::time::Instant;
+
+use crate::no_std::collections::HashMap;
+use crate::no_std::vec;
+
+use crate::runtime::{Object, OwnedTuple, Shared, Value, VariantData, Vec};
+
+/// The number of allocations which triggers a collection of the young
+/// generation.
+const DEFAULT_THRESHOLD: usize = 700;
+
+/// The number of young collections performed between each full collection.
+const DEFAULT_MAJOR_INTERVAL: usize = 10;
+
+/// A generational cycle collector for values allocated by a virtual machine.
+///
+/// Values are reference counted, so reference cycles built by scripts, such as
+/// graphs where nodes point back to each other, are never freed on their own.
+/// When enabled, the collector keeps track of every container allocated by the
+/// virtual machine (vectors, tuples, objects, structs, variants, options and
+/// results) and
+/// periodically finds groups of them which are only referenced by each other.
+/// The contents of such containers are cleared, which breaks the cycles and
+/// allows the reference counts to free them.
+///
+/// Collections only happen at safepoints between instructions, or when
+/// explicitly requested through [Vm::collect_garbage]. Containers are first
+/// tracked in a young generation which is collected once it reaches the
+/// configured threshold, and those which survive are moved into an old
+/// generation which is only considered during every few collections.
+///
+/// A value which is referenced from outside of the tracked containers, such as
+/// from the stack, from the host or from a native type holding onto a
+/// [Shared] handle, is never collected. The same is true for any value which
+/// is being accessed at the time of the collection.
+///
+/// [Vm::collect_garbage]: crate::Vm::collect_garbage
+#[derive(Debug, Clone)]
+pub struct Gc {
+    /// If the collector is enabled.
+    enabled: bool,
+    /// The number of young containers which triggers a collection.
+    threshold: usize,
+    /// The number of young collections between full collections.
+    major_interval: usize,
+    /// The number of young collections since the last full collection.
+    minor_collections: usize,
+    /// Containers tracked since the last collection.
+    young: vec::Vec<Value>,
+    /// Containers which have survived a collection.
+    old: vec::Vec<Value>,
+    /// Statistics of performed collections.
+    stats: GcStats,
+}
+
+impl Default for Gc {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Gc {
+    /// Construct a new disabled collector.
+    pub(crate) const fn new() -> Self {
+        Self {
+            enabled: false,
+            threshold: DEFAULT_THRESHOLD,
+            major_interval: DEFAULT_MAJOR_INTERVAL,
+            minor_collections: 0,
+            young: vec::Vec::new(),
+            old: vec::Vec::new(),
+            stats: GcStats::new(),
+        }
+    }
+
+    /// Test if the collector is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable or disable the collector.
+    ///
+    /// Disabling the collector stops tracking of all containers, after which
+    /// they are only freed through reference counting.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+
+        if !enabled {
+            self.young.clear();
+            self.old.clear();
+            self.minor_collections = 0;
+        }
+    }
+
+    /// Set the number of allocated containers which triggers a collection of
+    /// the young generation. Defaults to `700` and is always at least `1`.
+    pub fn set_threshold(&mut self, threshold: usize) {
+        self.threshold = threshold.max(1);
+    }
+
+    /// Set the number of collections of the young generation which are
+    /// performed before the old generation is collected as well. Defaults to
+    /// `10` and is always at least `1`.
+    pub fn set_major_interval(&mut self, major_interval: usize) {
+        self.major_interval = major_interval.max(1);
+    }
+
+    /// The number of containers currently tracked by the collector.
+    pub fn tracked(&self) -> usize {
+        self.young.len() + self.old.len()
+    }
+
+    /// Statistics of collections performed so far.
+    pub fn stats(&self) -> &GcStats {
+        &self.stats
+    }
+
+    /// Start tracking a newly allocated value, if it is a container.
+    #[inline]
+    pub(crate) fn track(&mut self, value: &Value) {
+        if self.enabled && Header::new(value).is_some() {
+            self.young.push(value.clone());
+        }
+    }
+
+    /// Collect garbage if enough containers have been allocated since the
+    /// last collection.
+    ///
+    /// This must only be called at a point where the virtual machine holds no
+    /// unaccounted references to values, like in between instructions.
+    #[inline]
+    pub(crate) fn safepoint(&mut self) {
+        if self.young.len() >= self.threshold {
+            let major = self.minor_collections + 1 >= self.major_interval;
+            self.collect(major);
+        }
+    }
+
+    /// Perform a collection, returning the number of freed containers.
+    ///
+    /// A `major` collection considers all tracked containers, while a minor
+    /// one only considers the ones tracked since the last collection.
+    pub(crate) fn collect(&mut self, major: bool) -> usize {
+        if !self.enabled {
+            return 0;
+        }
+
+        let start = Instant::now();
+        let mut candidates = take(&mut self.young);
+
+        if major {
+            candidates.append(&mut self.old);
+            self.minor_collections = 0;
+        } else {
+            self.minor_collections += 1;
+        }
+
+        // Containers which have been taken can never be accessed again, so
+        // they are simply no longer tracked.
+        candidates.retain(|value| Header::new(value).map_or(false, |h| !h.taken));
+
+        let index = candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(n, value)| Some((Header::new(value)?.addr, n)))
+            .collect::<HashMap<_, _>>();
+
+        // The number of references to each candidate from outside of the
+        // candidates, not counting the reference held by the collector.
+        let mut external = candidates
+            .iter()
+            .map(|value| Header::new(value).map_or(0, |h| h.count.saturating_sub(1)))
+            .collect::<vec::Vec<_>>();
+
+        let mut pinned = vec![false; candidates.len()];
+
+        for (n, value) in candidates.iter().enumerate() {
+            let visited = children(value, |child| {
+                if let Some(&m) = Header::new(child).and_then(|h| index.get(&h.addr)) {
+                    external[m] = external[m].saturating_sub(1);
+                }
+            });
+
+            // NB: a container which can't be accessed is in use, so it's
+            // conservatively treated as reachable.
+            if !visited {
+                pinned[n] = true;
+            }
+        }
+
+        let mut reachable = vec![false; candidates.len()];
+        let mut queue = vec::Vec::new();
+
+        for n in 0..candidates.len() {
+            if external[n] > 0 || pinned[n] {
+                reachable[n] = true;
+                queue.push(n);
+            }
+        }
+
+        while let Some(n) = queue.pop() {
+            children(&candidates[n], |child| {
+                if let Some(&m) = Header::new(child).and_then(|h| index.get(&h.addr)) {
+                    if !reachable[m] {
+                        reachable[m] = true;
+                        queue.push(m);
+                    }
+                }
+            });
+        }
+
+        let mut freed = 0;
+
+        // NB: the collector keeps a reference to all unreachable containers
+        // until they've all been cleared, so none of them are freed while
+        // being accessed.
+        let mut garbage = vec::Vec::new();
+
+        for (value, reachable) in candidates.into_iter().zip(reachable) {
+            if !reachable && clear(&value) {
+                garbage.push(value);
+                freed += 1;
+            } else {
+                self.old.push(value);
+            }
+        }
+
+        drop(garbage);
+
+        let pause = start.elapsed();
+        self.stats.collections += 1;
+
+        if major {
+            self.stats.major_collections += 1;
+        }
+
+        self.stats.freed += freed;
+        self.stats.last_pause = pause;
+        self.stats.total_pause += pause;
+        self.stats.max_pause = self.stats.max_pause.max(pause);
+        freed
+    }
+}
+
+/// Statistics of the collections performed by a [Gc].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct GcStats {
+    /// The number of collections performed.
+    pub collections: usize,
+    /// The number of collections which considered all tracked containers.
+    pub major_collections: usize,
+    /// The number of containers freed.
+    pub freed: usize,
+    /// The time spent in the last collection.
+    pub last_pause: Duration,
+    /// The longest time spent in a single collection.
+    pub max_pause: Duration,
+    /// The total time spent in collections.
+    pub total_pause: Duration,
+}
+
+impl GcStats {
+    const fn new() -> Self {
+        Self {
+            collections: 0,
+            major_collections: 0,
+            freed: 0,
+            last_pause: Duration::ZERO,
+            max_pause: Duration::ZERO,
+            total_pause: Duration::ZERO,
+        }
+    }
+}
+
+impl Default for GcStats {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The reference counting header of a tracked container.
+struct Header {
+    addr: usize,
+    count: usize,
+    taken: bool,
+}
+
+impl Header {
+    fn new(value: &Value) -> Option<Self> {
+        fn header<T>(shared: &Shared<T>) -> Header {
+            Header {
+                addr: Shared::addr(shared),
+                count: Shared::strong_count(shared),
+                taken: Shared::is_taken(shared),
+            }
+        }
+
+        Some(match value {
+            Value::Vec(shared) => header(shared),
+            Value::Tuple(shared) => header(shared),
+            Value::Object(shared) => header(shared),
+            Value::Struct(shared) => header(shared),
+            Value::TupleStruct(shared) => header(shared),
+            Value::Variant(shared) => header(shared),
+            Value::Option(shared) => header(shared),
+            Value::Result(shared) => header(shared),
+            _ => return None,
+        })
+    }
+}
+
+/// Visit the values directly referenced by a container, returning `false` if
+/// the container can't be accessed.
+fn children(value: &Value, mut f: impl FnMut(&Value)) -> bool {
+    match value {
+        Value::Vec(vec) => {
+            let Ok(vec) = vec.borrow_ref() else {
+                return false;
+            };
+
+            vec.iter().for_each(f);
+        }
+        Value::Tuple(tuple) => {
+            let Ok(tuple) = tuple.borrow_ref() else {
+                return false;
+            };
+
+            tuple.iter().for_each(f);
+        }
+        Value::Object(object) => {
+            let Ok(object) = object.borrow_ref() else {
+                return false;
+            };
+
+            object.values().for_each(f);
+        }
+        Value::Struct(st) => {
+            let Ok(st) = st.borrow_ref() else {
+                return false;
+            };
+
+            st.data.values().for_each(f);
+        }
+        Value::TupleStruct(st) => {
+            let Ok(st) = st.borrow_ref() else {
+                return false;
+            };
+
+            st.data.iter().for_each(f);
+        }
+        Value::Variant(variant) => {
+            let Ok(variant) = variant.borrow_ref() else {
+                return false;
+            };
+
+            match &variant.data {
+                VariantData::Empty => {}
+                VariantData::Struct(object) => object.values().for_each(&mut f),
+                VariantData::Tuple(tuple) => tuple.iter().for_each(&mut f),
+            }
+        }
+        Value::Option(option) => {
+            let Ok(option) = option.borrow_ref() else {
+                return false;
+            };
+
+            option.iter().for_each(f);
+        }
+        Value::Result(result) => {
+            let Ok(result) = result.borrow_ref() else {
+                return false;
+            };
+
+            match &*result {
+                Ok(value) | Err(value) => f(value),
+            }
+        }
+        _ => {}
+    }
+
+    true
+}
+
+/// Clear the contents of a container, returning `false` if it can't be
+/// accessed.
+fn clear(value: &Value) -> bool {
+    fn clear<T, U>(shared: &Shared<T>, f: impl FnOnce(&mut T) -> U) -> bool {
+        let Ok(mut data) = shared.borrow_mut() else {
+            return false;
+        };
+
+        let contents = f(&mut data);
+        // NB: release access before dropping the contents, which might
+        // reference the container itself.
+        drop(data);
+        drop(contents);
+        true
+    }
+
+    match value {
+        Value::Vec(shared) => clear(shared, |vec| replace(vec, Vec::new())),
+        Value::Tuple(shared) => clear(shared, |tuple| replace(tuple, OwnedTuple::new())),
+        Value::Object(shared) => clear(shared, |object| replace(object, Object::new())),
+        Value::Struct(shared) => clear(shared, |st| replace(&mut st.data, Object::new())),
+        Value::TupleStruct(shared) => clear(shared, |st| replace(&mut st.data, OwnedTuple::new())),
+        Value::Variant(shared) => clear(shared, |variant| {
+            replace(&mut variant.data, VariantData::Empty)
+        }),
+        Value::Option(shared) => clear(shared, Option::take),
+        Value::Result(shared) => clear(shared, |result| replace(result, Ok(Value::EmptyTuple))),
+        _ => false,
+    }
+}
@@ -1,4 +1,6 @@
-use crate::runtime::{Stack, UnsafeToValue, VmResult};
+use crate::no_std::prelude::*;
+
+use crate::runtime::{Stack, UnsafeToValue, Value, VmResult};
 
 /// Trait for converting arguments onto the stack.
 ///
@@ -46,3 +48,33 @@ macro_rules! impl_into_args {
 }
 
 repeat_macro!(impl_into_args);
+
+impl GuardedArgs for &[Value] {
+    type Guard = ();
+
+    #[inline]
+    unsafe fn unsafe_into_stack(self, stack: &mut Stack) -> VmResult<Self::Guard> {
+        stack.extend(self.iter().cloned());
+        VmResult::Ok(())
+    }
+
+    #[inline]
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl GuardedArgs for Vec<Value> {
+    type Guard = ();
+
+    #[inline]
+    unsafe fn unsafe_into_stack(self, stack: &mut Stack) -> VmResult<Self::Guard> {
+        stack.extend(self);
+        VmResult::Ok(())
+    }
+
+    #[inline]
+    fn count(&self) -> usize {
+        self.len()
+    }
+}
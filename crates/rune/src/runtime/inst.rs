@@ -1,10 +1,11 @@
 use core::fmt;
 
 use musli::{Decode, Encode};
-use rune_macros::InstDisplay;
+use rune_macros::{InstDisplay, InstMeta};
 use serde::{Deserialize, Serialize};
 
-use crate::runtime::{Call, FormatSpec, Type, Value};
+use crate::runtime::message_catalog::{message, Localize};
+use crate::runtime::{Call, EnglishCatalog, FormatSpec, MessageCatalog, Type, Value};
 use crate::Hash;
 
 /// Pre-canned panic reasons.
@@ -34,16 +35,27 @@ impl PanicReason {
 }
 
 impl fmt::Display for PanicReason {
+    #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match *self {
-            Self::NotImplemented => write!(fmt, "functionality has not been implemented yet")?,
-            Self::UnmatchedPattern => write!(fmt, "pattern did not match")?,
-            Self::FutureCompleted => {
-                write!(fmt, "tried to poll future that has already been completed")?
-            }
-        }
+        self.localize(&EnglishCatalog, fmt)
+    }
+}
 
-        Ok(())
+impl Localize for PanicReason {
+    fn localize(&self, catalog: &dyn MessageCatalog, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (id, template) = match *self {
+            Self::NotImplemented => (
+                "panic-not-implemented",
+                "functionality has not been implemented yet",
+            ),
+            Self::UnmatchedPattern => ("panic-unmatched-pattern", "pattern did not match"),
+            Self::FutureCompleted => (
+                "panic-future-completed",
+                "tried to poll future that has already been completed",
+            ),
+        };
+
+        message(catalog, f, id, template, &[])
     }
 }
 
@@ -88,7 +100,7 @@ impl fmt::Display for TypeCheck {
 }
 
 /// An operation in the stack-based virtual machine.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Decode, Encode, InstDisplay)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Decode, Encode, InstDisplay, InstMeta)]
 pub enum Inst {
     /// Not operator. Takes a boolean from the top of the stack  and inverts its
     /// logical value.
@@ -149,6 +161,30 @@ pub enum Inst {
         /// The number of arguments expected on the stack for this call.
         args: usize,
     },
+    /// Perform a function call whose return value is a tuple which is
+    /// immediately unpacked into `count` values on the stack.
+    ///
+    /// If the function returns through [Inst::ReturnTuple], its values are
+    /// moved onto the stack directly without constructing a tuple. Otherwise
+    /// the returned value must be a tuple with exactly `count` items, or the
+    /// virtual machine panics since the pattern it's unpacked into doesn't
+    /// match.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <argument..>
+    /// => <value..>
+    /// ```
+    #[musli(packed)]
+    CallUnpack {
+        /// The hash of the function to call.
+        hash: Hash,
+        /// The number of arguments expected on the stack for this call.
+        args: usize,
+        /// The number of values the returned tuple is unpacked into.
+        count: usize,
+    },
     /// Perform a instance function call.
     ///
     /// The instance being called on should be on top of the stack, followed by
@@ -160,6 +196,19 @@ pub enum Inst {
         /// The number of arguments expected on the stack for this call.
         args: usize,
     },
+    /// Perform an early-bound instance function call, where the type of the
+    /// instance has been proven at compile time.
+    ///
+    /// This behaves like [`Inst::CallAssociated`], except that the type of the
+    /// instance isn't looked up, and the function identified by the
+    /// precomputed `hash` is called directly.
+    #[musli(packed)]
+    CallAssociatedTyped {
+        /// The hash of the associated function on the type of the instance.
+        hash: Hash,
+        /// The number of arguments expected on the stack for this call.
+        args: usize,
+    },
     /// Lookup the specified instance function and put it on the stack.
     /// This might help in cases where a single instance function is called many
     /// times (like in a loop) since it avoids calculating its full hash on
@@ -484,6 +533,48 @@ pub enum Inst {
     /// The stack frame will be cleared, and a unit value will be pushed to the
     /// top of the stack.
     ReturnUnit,
+    /// Pop the current stack frame and restore the instruction pointer from it,
+    /// returning the `count` values on top of the stack as a tuple.
+    ///
+    /// If the caller unpacks the returned tuple through [Inst::CallUnpack], the
+    /// values are moved onto its stack directly without constructing a tuple.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value..>
+    /// => <tuple>
+    /// ```
+    #[musli(packed)]
+    ReturnTuple {
+        /// The number of values in the returned tuple.
+        count: usize,
+        /// Number of variables to clean below the returned values.
+        clean: usize,
+    },
+    /// Look up the result of calling the memoized function `hash` with the
+    /// `args` arguments at the bottom of the current stack frame.
+    ///
+    /// If a result has been cached by the virtual machine it is returned
+    /// immediately as if through [Inst::Return]. Otherwise the value returned
+    /// from the current stack frame is cached once it returns.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// *nothing*
+    /// => *nothing*
+    /// ```
+    #[musli(packed)]
+    Memo {
+        /// The hash of the memoized function.
+        hash: Hash,
+        /// The number of arguments the function is memoized over.
+        args: usize,
+        /// The maximum number of results to cache for the function, or `0` to
+        /// use the default capacity of the virtual machine.
+        capacity: usize,
+    },
     /// Unconditionally jump to `offset` relative to the current instruction
     /// pointer.
     ///
@@ -670,6 +761,37 @@ pub enum Inst {
         /// The static slot of the object keys.
         slot: usize,
     },
+    /// Construct and push a vector by spreading `count` values popped from
+    /// the stack into it, in order. Each value is iterated over and every
+    /// item it produces is added to the vector.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value..>
+    /// => <vec>
+    /// ```
+    #[musli(packed)]
+    VecSpread {
+        /// The number of values being spread.
+        count: usize,
+    },
+    /// Construct and push an object by spreading `count` values popped from
+    /// the stack into it, in order. Each value is iterated over and every
+    /// `(key, value)` pair it produces is inserted into the object, with
+    /// later keys overriding earlier ones.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value..>
+    /// => <object>
+    /// ```
+    #[musli(packed)]
+    ObjectSpread {
+        /// The number of values being spread.
+        count: usize,
+    },
     /// Construct a range. This will pop the start and end of the range from the
     /// stack.
     ///
@@ -773,6 +895,19 @@ pub enum Inst {
         /// The static byte string slot to load the string from.
         slot: usize,
     },
+    /// Load a value from a static data slot, like the ones produced by the
+    /// `include_data!` macro.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <value>
+    /// ```
+    #[musli(packed)]
+    StaticData {
+        /// The static data slot to load the value from.
+        slot: usize,
+    },
     /// Pop the given number of values from the stack, and concatenate a string
     /// from them.
     ///
@@ -826,6 +961,28 @@ pub enum Inst {
         /// If the value on top of the stack should be preserved.
         preserve: bool,
     },
+    /// Perform the try operation like [`Inst::Try`], but instead of returning
+    /// from the current call frame the residual value is pushed on the stack
+    /// and a jump is performed.
+    ///
+    /// This is used when there are deferred blocks which need to run before
+    /// returning.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <value>
+    /// ```
+    #[musli(packed)]
+    TryOrJump {
+        /// Address to test if value.
+        address: InstAddress,
+        /// If the value on top of the stack should be preserved.
+        preserve: bool,
+        /// The jump to perform with the residual value on the stack.
+        jump: usize,
+    },
     /// Test if the top of the stack is a specific byte.
     ///
     /// # Operation
@@ -918,6 +1075,24 @@ pub enum Inst {
         /// The type hash to match against.
         hash: Hash,
     },
+    /// Check that the argument at the given offset in the current stack frame
+    /// has the type it's annotated with, and error if it doesn't.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// =>
+    /// ```
+    #[musli(packed)]
+    CheckArgument {
+        /// The offset of the argument in the current stack frame.
+        offset: usize,
+        /// The expected type hash of the argument.
+        hash: Hash,
+        /// The slot of the static string describing the annotated type, used
+        /// in the error raised if the check fails.
+        slot: usize,
+    },
     /// Test if the specified variant matches. This is distinct from
     /// [Inst::MatchType] because it will match immediately on the variant type
     /// if appropriate which is possible for internal types, but external types
@@ -1068,6 +1243,30 @@ pub enum Inst {
         /// A relative jump to perform if the iterator could not be advanced.
         jump: usize,
     },
+    /// Advance an integer counter at the given position by `step`, pushing its
+    /// current value or jumping once it has reached the integer at `end`.
+    ///
+    /// This is used by the compiler to implement `for` loops over integer
+    /// ranges without constructing an iterator.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <integer>
+    /// ```
+    #[musli(packed)]
+    IterStep {
+        /// The offset of the counter being advanced.
+        offset: usize,
+        /// The offset of the end of the range.
+        end: usize,
+        /// The amount to advance the counter by, which is never zero.
+        step: i64,
+        /// If the end of the range is inclusive.
+        inclusive: bool,
+        /// A relative jump to perform once the end has been reached.
+        jump: usize,
+    },
     /// Cause the VM to panic and error out without a reason.
     ///
     /// This should only be used during testing or extreme scenarios that are
@@ -1186,6 +1385,9 @@ pub enum InstTarget {
     /// Target a tuple field.
     #[musli(packed)]
     TupleField(usize),
+    /// Target a dynamic index, where both the value being indexed and the
+    /// index are on the stack.
+    Index,
 }
 
 impl fmt::Display for InstTarget {
@@ -1194,6 +1396,7 @@ impl fmt::Display for InstTarget {
             Self::Offset(offset) => write!(f, "offset({offset})"),
             Self::Field(slot) => write!(f, "field({slot})"),
             Self::TupleField(slot) => write!(f, "tuple-field({slot})"),
+            Self::Index => write!(f, "index"),
         }
     }
 }
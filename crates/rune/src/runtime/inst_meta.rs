@@ -0,0 +1,111 @@
+use core::fmt;
+
+use serde::Serialize;
+
+/// Metadata describing a single instruction of the virtual machine.
+///
+/// Metadata is generated from the documentation of the [`Inst`] enum, so it's
+/// always in sync with the instruction set. It's available for all
+/// instructions through [`Inst::METADATA`], or for a single instruction
+/// through [`Inst::meta`].
+///
+/// [`Inst`]: crate::runtime::Inst
+/// [`Inst::METADATA`]: crate::runtime::Inst::METADATA
+/// [`Inst::meta`]: crate::runtime::Inst::meta
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::Inst;
+///
+/// let meta = Inst::Not.meta();
+/// assert_eq!(meta.name, "Not");
+/// assert_eq!(meta.display, "not");
+///
+/// let stack = meta.stack.expect("not has a stack effect");
+/// assert_eq!(stack.inputs, ["<bool>"]);
+/// assert_eq!(stack.outputs, ["<bool>"]);
+/// ```
+#[derive(Debug, Clone, Copy, Serialize)]
+#[non_exhaustive]
+pub struct InstMeta {
+    /// The name of the instruction, like `CallOffset`.
+    pub name: &'static str,
+    /// The name of the instruction as it's displayed, like `call-offset`.
+    pub display: &'static str,
+    /// Notes on the semantics of the instruction.
+    pub docs: &'static str,
+    /// The operands of the instruction.
+    pub operands: &'static [InstOperand],
+    /// The effect the instruction has on the stack, if documented.
+    pub stack: Option<InstStackEffect>,
+}
+
+impl InstMeta {
+    /// Write a human-readable reference of the instruction in markdown.
+    pub fn write_markdown<W>(&self, out: &mut W) -> fmt::Result
+    where
+        W: fmt::Write,
+    {
+        writeln!(out, "## `{}`", self.display)?;
+        writeln!(out)?;
+
+        if !self.docs.is_empty() {
+            writeln!(out, "{}", self.docs)?;
+            writeln!(out)?;
+        }
+
+        if !self.operands.is_empty() {
+            writeln!(out, "Operands:")?;
+            writeln!(out)?;
+
+            for operand in self.operands {
+                let docs = operand.docs.replace('\n', " ");
+                writeln!(out, "* `{}: {}` - {docs}", operand.name, operand.ty)?;
+            }
+
+            writeln!(out)?;
+        }
+
+        if let Some(stack) = &self.stack {
+            writeln!(out, "Stack:")?;
+            writeln!(out)?;
+            writeln!(out, "```text")?;
+
+            for input in stack.inputs {
+                writeln!(out, "{input}")?;
+            }
+
+            writeln!(out, "=> {}", stack.outputs.join(", "))?;
+            writeln!(out, "```")?;
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An operand of an instruction.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[non_exhaustive]
+pub struct InstOperand {
+    /// The name of the operand.
+    pub name: &'static str,
+    /// The Rust type of the operand.
+    pub ty: &'static str,
+    /// Documentation for the operand.
+    pub docs: &'static str,
+}
+
+/// The effect an instruction has on the stack.
+///
+/// Inputs are listed in the order they are pushed, so the last input is the
+/// top of the stack. An empty list of outputs means that nothing is pushed.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[non_exhaustive]
+pub struct InstStackEffect {
+    /// The values consumed from the stack.
+    pub inputs: &'static [&'static str],
+    /// The values pushed onto the stack.
+    pub outputs: &'static [&'static str],
+}
@@ -0,0 +1,188 @@
+use crate::no_std::collections::BTreeSet;
+
+use crate::runtime::ObjectKey;
+
+/// The number of keys an interner holds onto by default.
+const DEFAULT_CAPACITY: usize = 4096;
+
+/// An interner of object keys.
+///
+/// Every virtual machine has an interner, which is used when keys are inserted
+/// into objects by scripts, like with `object["key"] = value` or `object.key =
+/// value`. Keys interned through the same interner share their storage, so
+/// objects which use the same keys don't store a copy of them each, and keys
+/// which share storage are compared without looking at their contents.
+///
+/// Keys which are part of the static object keys of the unit used by the
+/// virtual machine are always interned, other keys are only held onto until
+/// the interner reaches its capacity.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{Interner, ObjectKey};
+///
+/// let mut interner = Interner::new();
+///
+/// let a = interner.intern("name");
+/// let b = interner.intern("name");
+/// assert!(ObjectKey::ptr_eq(&a, &b));
+///
+/// let stats = interner.stats();
+/// assert_eq!(stats.keys, 2);
+/// assert_eq!(stats.unique, 1);
+/// assert_eq!(stats.saved_bytes(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Interner {
+    /// Interned keys.
+    keys: BTreeSet<ObjectKey>,
+    /// The number of keys held onto.
+    capacity: usize,
+    /// Statistics of interned keys.
+    stats: KeyStats,
+}
+
+impl Default for Interner {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Interner {
+    /// Construct a new empty interner.
+    pub const fn new() -> Self {
+        Self {
+            keys: BTreeSet::new(),
+            capacity: DEFAULT_CAPACITY,
+            stats: KeyStats::new(),
+        }
+    }
+
+    /// Set the number of keys the interner holds onto. Defaults to `4096`.
+    ///
+    /// Once the interner is full, keys which haven't been interned are
+    /// allocated separately every time they are used.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+    }
+
+    /// The number of keys held onto by the interner.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Test if the interner is empty.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Intern the given key.
+    pub fn intern(&mut self, key: &str) -> ObjectKey {
+        if let Some(key) = self.get(key) {
+            return key;
+        }
+
+        self.insert(ObjectKey::from(key))
+    }
+
+    /// Intern the given owned key, which reuses its allocation if the key isn't
+    /// already interned.
+    pub fn intern_string(&mut self, key: String) -> ObjectKey {
+        if let Some(key) = self.get(&key) {
+            return key;
+        }
+
+        self.insert(ObjectKey::from(key))
+    }
+
+    /// Statistics of keys interned so far.
+    pub fn stats(&self) -> &KeyStats {
+        &self.stats
+    }
+
+    /// Drop all interned keys and reset statistics.
+    ///
+    /// Objects keep the keys they've been constructed with.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.stats = KeyStats::new();
+    }
+
+    /// Intern a key, sharing the storage of the given key if it isn't
+    /// already interned.
+    pub(crate) fn intern_key(&mut self, key: &ObjectKey) -> ObjectKey {
+        if let Some(key) = self.get(key) {
+            return key;
+        }
+
+        self.insert(key.clone())
+    }
+
+    /// Look up an interned key without recording it in statistics.
+    pub(crate) fn lookup(&self, key: &str) -> Option<&ObjectKey> {
+        self.keys.get(key)
+    }
+
+    /// Get an interned key, recording it in statistics if it exists.
+    pub(crate) fn get(&mut self, key: &str) -> Option<ObjectKey> {
+        let key = self.keys.get(key)?.clone();
+        self.stats.keys += 1;
+        self.stats.bytes += key.size();
+        Some(key)
+    }
+
+    /// Record a key which was interned elsewhere, like in the static object
+    /// keys of a unit.
+    pub(crate) fn record(&mut self, key: &ObjectKey) {
+        self.stats.keys += 1;
+        self.stats.bytes += key.size();
+    }
+
+    /// Insert a key which isn't already interned, unless the interner is full.
+    pub(crate) fn insert(&mut self, key: ObjectKey) -> ObjectKey {
+        self.stats.keys += 1;
+        self.stats.bytes += key.size();
+        self.stats.unique += 1;
+        self.stats.unique_bytes += key.size();
+
+        if self.keys.len() < self.capacity {
+            self.keys.insert(key.clone());
+        }
+
+        key
+    }
+}
+
+/// Statistics of object keys, comparing how much memory they would use if
+/// they were stored separately with how much they use when interned.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct KeyStats {
+    /// The number of keys used.
+    pub keys: usize,
+    /// The number of keys which had to be allocated.
+    pub unique: usize,
+    /// The number of bytes used by keys if each was stored separately.
+    pub bytes: usize,
+    /// The number of bytes used by keys which had to be allocated.
+    pub unique_bytes: usize,
+}
+
+impl KeyStats {
+    /// Construct empty statistics.
+    pub(crate) const fn new() -> Self {
+        Self {
+            keys: 0,
+            unique: 0,
+            bytes: 0,
+            unique_bytes: 0,
+        }
+    }
+
+    /// The number of bytes saved by interning.
+    pub fn saved_bytes(&self) -> usize {
+        self.bytes.saturating_sub(self.unique_bytes)
+    }
+}
@@ -123,6 +123,11 @@ impl Iterator {
         self.iter.size_hint()
     }
 
+    #[inline]
+    pub(crate) fn len(&self) -> VmResult<usize> {
+        self.iter.len()
+    }
+
     #[inline]
     pub(crate) fn next(&mut self) -> VmResult<Option<Value>> {
         self.iter.next()
@@ -10,8 +10,11 @@ use crate::no_std::rc::Rc;
 use serde::{Deserialize, Serialize};
 
 /// A label that can be jumped to.
+///
+/// Labels are constructed through
+/// [FunctionAssembler::new_label][crate::compile::FunctionAssembler::new_label].
 #[derive(Debug, Clone)]
-pub(crate) struct Label {
+pub struct Label {
     pub(crate) name: &'static str,
     pub(crate) index: usize,
     jump: Rc<Cell<Option<NonZeroUsize>>>,
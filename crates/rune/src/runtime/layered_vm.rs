@@ -0,0 +1,158 @@
+use core::fmt;
+
+use crate::no_std::error;
+use crate::no_std::sync::Arc;
+
+use crate::runtime::{RuntimeContext, Unit, Vm};
+use crate::Hash;
+
+/// A virtual machine which runs short lived scratch units layered on top of a
+/// shared base unit.
+///
+/// This is intended for embedders who want to compile a large set of helpers
+/// once, and then run a small unit of untrusted code per request which is
+/// allowed to call into those helpers. The base unit is shared through an
+/// [Arc] and is never modified.
+///
+/// Scratch units should be built with [Build::with_base_unit], which makes the
+/// functions of the base unit visible to the compiler, and rejects any scratch
+/// unit which defines an item that is already defined in the base unit.
+/// Functions which are called by the scratch unit are first looked up in it,
+/// then in the base unit and finally in the runtime context.
+///
+/// Note that only functions of the base unit can be called from the scratch
+/// unit, types defined in the base unit have to be constructed by calling
+/// into functions of the base unit. Entrypoints called by the host, like
+/// through [Vm::call], are always looked up in the scratch unit.
+///
+/// Loading a scratch unit is cheap, since it only resets the existing virtual
+/// machine. This allows the same [LayeredVm] to be reused for every request.
+///
+/// [Build::with_base_unit]: crate::Build::with_base_unit
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Vm};
+/// use rune::runtime::LayeredVm;
+/// use std::sync::Arc;
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn greeting(name) {
+///             `Hello, ${name}!`
+///         }
+///     }
+/// };
+///
+/// let base = Arc::new(rune::prepare(&mut sources).with_context(&context).build()?);
+/// let mut vm = LayeredVm::new(runtime, base.clone());
+///
+/// for name in ["John", "Jane"] {
+///     let mut sources = rune::sources! {
+///         entry => {
+///             pub fn main(name) {
+///                 greeting(name)
+///             }
+///         }
+///     };
+///
+///     let scratch = rune::prepare(&mut sources)
+///         .with_context(&context)
+///         .with_base_unit(base.clone())
+///         .build()?;
+///
+///     let vm = vm.load(Arc::new(scratch))?;
+///     let output: String = rune::from_value(vm.call(["main"], (name,))?)?;
+///     assert_eq!(output, format!("Hello, {name}!"));
+/// }
+/// # Ok::<_, rune::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct LayeredVm {
+    /// The base unit shared by all scratch units.
+    base: Arc<Unit>,
+    /// The virtual machine running the current scratch unit.
+    vm: Vm,
+}
+
+impl LayeredVm {
+    /// Construct a new layered virtual machine on top of the given base unit.
+    ///
+    /// Until a scratch unit is loaded, the virtual machine runs an empty unit.
+    pub fn new(context: Arc<RuntimeContext>, base: Arc<Unit>) -> Self {
+        let mut vm = Vm::new(context, Arc::new(Unit::default()));
+        vm.set_base(Some(base.clone()));
+        Self { base, vm }
+    }
+
+    /// Access the base unit.
+    pub fn base(&self) -> &Arc<Unit> {
+        &self.base
+    }
+
+    /// Access the virtual machine running the current scratch unit.
+    pub fn vm(&self) -> &Vm {
+        &self.vm
+    }
+
+    /// Access the virtual machine running the current scratch unit mutably.
+    pub fn vm_mut(&mut self) -> &mut Vm {
+        &mut self.vm
+    }
+
+    /// Load the given scratch unit, returning the virtual machine to run it
+    /// with.
+    ///
+    /// This resets the state left behind by the previously loaded scratch
+    /// unit. Errors if the scratch unit defines a function or type which is
+    /// already defined in the base unit.
+    pub fn load(&mut self, scratch: Arc<Unit>) -> Result<&mut Vm, LayerError> {
+        for hash in scratch.iter_item_hashes() {
+            if self.base.function(hash).is_some()
+                || self.base.lookup_rtti(hash).is_some()
+                || self.base.lookup_variant_rtti(hash).is_some()
+            {
+                return Err(LayerError::BaseItemConflict { hash });
+            }
+        }
+
+        self.vm.clear();
+        *self.vm.unit_mut() = scratch;
+        self.vm.set_base(Some(self.base.clone()));
+        Ok(&mut self.vm)
+    }
+
+    /// Unload the current scratch unit, freeing all memory used by it.
+    pub fn unload(&mut self) {
+        self.vm.clear();
+        *self.vm.unit_mut() = Arc::new(Unit::default());
+    }
+}
+
+/// Error raised when loading a scratch unit through [LayeredVm::load].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LayerError {
+    /// The scratch unit defines an item which is already defined in the base
+    /// unit.
+    BaseItemConflict {
+        /// The hash of the item.
+        hash: Hash,
+    },
+}
+
+impl fmt::Display for LayerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayerError::BaseItemConflict { hash } => {
+                write!(f, "Item {hash} is already defined in the base unit")
+            }
+        }
+    }
+}
+
+impl error::Error for LayerError {}
@@ -0,0 +1,112 @@
+use core::fmt;
+
+/// Limits enforced on the execution of a virtual machine, which can be used
+/// to abort runaway scripts when running untrusted code.
+///
+/// Limits are set through [Vm::set_limits] or [VmExecution::set_limits], and
+/// are counted from the start of the execution just like the
+/// [ExecutionReport] of the virtual machine. When a limit is exceeded the
+/// execution fails with an error for which [VmError::exceeded_limit] returns
+/// the limit.
+///
+/// Virtual machines which are created to run async functions, generators and
+/// streams inherit the limits of the virtual machine calling them, but count
+/// their use separately.
+///
+/// Native code generated through `Unit::jit` doesn't count instructions, so
+/// functions which have been compiled to native code are interpreted while
+/// any limit is set.
+///
+/// Unlike [budget][crate::runtime::budget], which suspends an execution once
+/// its budget has been used up so that it can be resumed later, exceeding a
+/// limit is an error.
+///
+/// [Vm::set_limits]: crate::Vm::set_limits
+/// [VmExecution::set_limits]: crate::runtime::VmExecution::set_limits
+/// [ExecutionReport]: crate::runtime::ExecutionReport
+/// [VmError::exceeded_limit]: crate::runtime::VmError::exceeded_limit
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Vm};
+/// use rune::runtime::{Limit, Limits};
+/// use std::sync::Arc;
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn forever(n) {
+///             forever(n + 1)
+///         }
+///
+///         pub fn main() {
+///             forever(0)
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+///
+/// let mut limits = Limits::new();
+/// limits.call_depth = Some(64);
+///
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+/// vm.set_limits(limits);
+///
+/// let error = vm.call(["main"], ()).unwrap_err();
+/// assert_eq!(error.exceeded_limit(), Some(Limit::CallDepth));
+/// # Ok::<_, rune::Error>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Limits {
+    /// The maximum number of instructions which may be executed.
+    pub instructions: Option<u64>,
+    /// The maximum depth of the call stack, which is the number of calls to
+    /// functions in the unit which may be in progress at once.
+    pub call_depth: Option<usize>,
+    /// The maximum number of values which may be allocated, like vectors,
+    /// tuples, objects and instances of structs and variants.
+    ///
+    /// Only values constructed by instructions in the unit are counted.
+    /// Values allocated by native functions, like the string returned by
+    /// `String::repeat`, are not, so this doesn't bound the memory used by a
+    /// script on its own.
+    pub allocations: Option<u64>,
+}
+
+impl Limits {
+    /// Construct limits where nothing is limited.
+    pub const fn new() -> Self {
+        Self {
+            instructions: None,
+            call_depth: None,
+            allocations: None,
+        }
+    }
+}
+
+/// A limit in [Limits] which was exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Limit {
+    /// The limit of [Limits::instructions] was exceeded.
+    Instructions,
+    /// The limit of [Limits::call_depth] was exceeded.
+    CallDepth,
+    /// The limit of [Limits::allocations] was exceeded.
+    Allocations,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Limit::Instructions => write!(f, "instructions"),
+            Limit::CallDepth => write!(f, "nested calls"),
+            Limit::Allocations => write!(f, "allocations"),
+        }
+    }
+}
@@ -0,0 +1,206 @@
+#[cfg(feature = "std")]
+use core::hash::BuildHasherDefault;
+
+#[cfg(feature = "std")]
+use crate::no_std::collections::hash_map::DefaultHasher;
+use crate::no_std::collections::{BTreeMap, VecDeque};
+use crate::no_std::prelude::*;
+
+#[cfg(feature = "std")]
+use crate::runtime::Hasher;
+use crate::runtime::{ProtocolCaller, Value, VmResult};
+use crate::Hash;
+
+/// The number of results cached per function if neither the function nor the
+/// virtual machine specifies a capacity.
+const DEFAULT_CAPACITY: usize = 128;
+
+/// Storage for the results of functions marked with `#[memo]`, which is local
+/// to a single virtual machine.
+#[derive(Debug, Clone)]
+pub(crate) struct Memo {
+    /// The capacity of caches for functions which don't specify one.
+    capacity: usize,
+    /// Caches indexed by the hash of the memoized function.
+    caches: BTreeMap<Hash, Cache>,
+    /// Calls to memoized functions which have missed their cache, in the
+    /// order they were made.
+    pending: Vec<Pending>,
+}
+
+impl Default for Memo {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Memo {
+    /// Construct new empty memoization storage.
+    pub(crate) const fn new() -> Self {
+        Self {
+            capacity: DEFAULT_CAPACITY,
+            caches: BTreeMap::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Set the capacity used for functions which don't specify one.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+    }
+
+    /// Clear all cached results.
+    pub(crate) fn clear(&mut self) {
+        self.caches.clear();
+        self.pending.clear();
+    }
+
+    /// Clear calls which are waiting for their result, which is done when
+    /// the execution they belong to is abandoned.
+    pub(crate) fn clear_pending(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Look up the cached result of calling `hash` with the given arguments.
+    ///
+    /// The least recently used result is evicted once the cache of a
+    /// function is full, so a successful lookup marks the result as the most
+    /// recently used.
+    pub(crate) fn get(
+        &mut self,
+        hash: Hash,
+        key: &MemoKey,
+        caller: &mut impl ProtocolCaller,
+    ) -> VmResult<Option<Value>> {
+        // NB: the cache is temporarily removed since comparing arguments might
+        // call back into the virtual machine.
+        let Some(mut cache) = self.caches.remove(&hash) else {
+            return VmResult::Ok(None);
+        };
+
+        let value = cache.get(key, caller);
+        self.caches.insert(hash, cache);
+        value
+    }
+
+    /// Mark the call at the given call frame `depth` as waiting for its result
+    /// to be cached.
+    pub(crate) fn push_pending(&mut self, depth: usize, hash: Hash, capacity: usize, key: MemoKey) {
+        self.pending.push(Pending {
+            depth,
+            hash,
+            capacity,
+            key,
+        });
+    }
+
+    /// Test if a call at the given call frame `depth` is waiting for its
+    /// result.
+    pub(crate) fn is_pending(&self, depth: usize) -> bool {
+        matches!(self.pending.last(), Some(pending) if pending.depth == depth)
+    }
+
+    /// Cache the value returned from the call frame at `depth`, if the call is
+    /// waiting for its result.
+    pub(crate) fn complete(&mut self, depth: usize, value: &Value) {
+        if !self.is_pending(depth) {
+            return;
+        }
+
+        let Some(pending) = self.pending.pop() else {
+            return;
+        };
+
+        let capacity = match pending.capacity {
+            0 => self.capacity,
+            capacity => capacity,
+        };
+
+        let cache = self.caches.entry(pending.hash).or_default();
+        cache.insert(pending.key, value.clone(), capacity);
+    }
+}
+
+/// The arguments a memoized function was called with.
+#[derive(Debug, Clone)]
+pub(crate) struct MemoKey {
+    /// The combined hash of the arguments.
+    hash: u64,
+    /// The arguments.
+    args: Box<[Value]>,
+}
+
+impl MemoKey {
+    /// Construct a new key by hashing the given arguments.
+    #[cfg(feature = "std")]
+    pub(crate) fn new(args: Box<[Value]>, caller: &mut impl ProtocolCaller) -> VmResult<Self> {
+        let mut hasher = Hasher::new_with(&BuildHasherDefault::<DefaultHasher>::default());
+
+        for value in args.iter() {
+            vm_try!(value.hash_with(&mut hasher, caller));
+        }
+
+        VmResult::Ok(Self {
+            hash: hasher.finish(),
+            args,
+        })
+    }
+
+    fn eq_with(&self, other: &MemoKey, caller: &mut impl ProtocolCaller) -> VmResult<bool> {
+        if self.hash != other.hash || self.args.len() != other.args.len() {
+            return VmResult::Ok(false);
+        }
+
+        for (a, b) in self.args.iter().zip(other.args.iter()) {
+            if !vm_try!(a.eq_with(b, caller)) {
+                return VmResult::Ok(false);
+            }
+        }
+
+        VmResult::Ok(true)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Pending {
+    depth: usize,
+    hash: Hash,
+    capacity: usize,
+    key: MemoKey,
+}
+
+/// The cached results of a single function, ordered from least to most
+/// recently used.
+#[derive(Debug, Clone, Default)]
+struct Cache {
+    entries: VecDeque<(MemoKey, Value)>,
+}
+
+impl Cache {
+    fn get(&mut self, key: &MemoKey, caller: &mut impl ProtocolCaller) -> VmResult<Option<Value>> {
+        for index in 0..self.entries.len() {
+            if !vm_try!(self.entries[index].0.eq_with(key, caller)) {
+                continue;
+            }
+
+            let Some(entry) = self.entries.remove(index) else {
+                break;
+            };
+
+            let value = entry.1.clone();
+            self.entries.push_back(entry);
+            return VmResult::Ok(Some(value));
+        }
+
+        VmResult::Ok(None)
+    }
+
+    fn insert(&mut self, key: MemoKey, value: Value, capacity: usize) {
+        while self.entries.len() >= capacity {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back((key, value));
+    }
+}
@@ -0,0 +1,245 @@
+use core::fmt;
+
+use crate::no_std::collections::HashMap;
+use crate::no_std::prelude::*;
+
+/// A message produced by the runtime, like the description of a
+/// [VmError][crate::runtime::VmError] or a panic.
+///
+/// Every message has a stable id, like `divide-by-zero` or
+/// `bad-argument-count`, and named arguments which are referenced in its
+/// templates as `{name}`. Use `{{` and `}}` to write literal braces.
+///
+/// The English template of a message is what is used when it's displayed
+/// without a [MessageCatalog] providing a translation for it.
+pub struct Message<'a> {
+    id: &'static str,
+    template: &'static str,
+    args: &'a [(&'static str, &'a dyn fmt::Display)],
+}
+
+impl<'a> Message<'a> {
+    /// The stable id of the message.
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    /// The default English template of the message.
+    pub fn template(&self) -> &'static str {
+        self.template
+    }
+
+    /// Get the argument with the given name.
+    pub fn arg(&self, name: &str) -> Option<&'a dyn fmt::Display> {
+        let (_, arg) = self.args.iter().find(|(n, _)| *n == name)?;
+        Some(*arg)
+    }
+
+    /// Iterate over the names and values of the arguments of the message.
+    pub fn args(&self) -> impl Iterator<Item = (&'static str, &'a dyn fmt::Display)> + '_ {
+        self.args.iter().copied()
+    }
+
+    /// Write the given template, substituting `{name}` for the arguments of
+    /// this message.
+    ///
+    /// Placeholders which don't match an argument are written as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::{Message, MessageCatalog};
+    /// use std::fmt;
+    ///
+    /// struct Shouting;
+    ///
+    /// impl MessageCatalog for Shouting {
+    ///     fn format(&self, message: &Message<'_>, f: &mut fmt::Formatter<'_>) -> Option<fmt::Result> {
+    ///         let template = message.template().to_uppercase();
+    ///         Some(message.write_template(&template, f))
+    ///     }
+    /// }
+    /// ```
+    pub fn write_template(&self, template: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rest = template;
+
+        while let Some(n) = rest.find(|c: char| c == '{' || c == '}') {
+            f.write_str(&rest[..n])?;
+            let tail = &rest[n..];
+
+            if tail.starts_with("{{") || tail.starts_with("}}") {
+                f.write_str(&tail[..1])?;
+                rest = &tail[2..];
+                continue;
+            }
+
+            if let Some(end) = tail.find('}').filter(|_| tail.starts_with('{')) {
+                if let Some(arg) = self.arg(&tail[1..end]) {
+                    arg.fmt(f)?;
+                    rest = &tail[end + 1..];
+                    continue;
+                }
+            }
+
+            f.write_str(&tail[..1])?;
+            rest = &tail[1..];
+        }
+
+        f.write_str(rest)
+    }
+}
+
+impl fmt::Display for Message<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_template(self.template, f)
+    }
+}
+
+impl fmt::Debug for Message<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Message")
+            .field("id", &self.id)
+            .field("template", &self.template)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A catalog of translations for messages produced by the runtime.
+///
+/// A catalog is installed through [Context::set_message_catalog], and is used
+/// when an error is displayed through [RuntimeContext::localize]. Displaying
+/// a [VmError] directly always uses the [EnglishCatalog].
+///
+/// Messages are formatted by their stable id and arguments, so the structured
+/// data of errors is unaffected by which catalog is used. Custom panic
+/// messages raised by scripts are displayed as-is.
+///
+/// [Context::set_message_catalog]: crate::Context::set_message_catalog
+/// [RuntimeContext::localize]: crate::runtime::RuntimeContext::localize
+/// [VmError]: crate::runtime::VmError
+pub trait MessageCatalog: Send + Sync {
+    /// Format the given message, or return `None` if the catalog doesn't
+    /// translate it, in which case its default English template is used.
+    fn format(&self, message: &Message<'_>, f: &mut fmt::Formatter<'_>) -> Option<fmt::Result>;
+}
+
+/// The default catalog, which formats every message using its English
+/// template.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EnglishCatalog;
+
+impl MessageCatalog for EnglishCatalog {
+    #[inline]
+    fn format(&self, message: &Message<'_>, f: &mut fmt::Formatter<'_>) -> Option<fmt::Result> {
+        Some(message.write_template(message.template, f))
+    }
+}
+
+/// A catalog of templates keyed by message id, as could be loaded from a
+/// translation file.
+///
+/// Messages which don't have a template fall back to their default English
+/// template.
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Vm};
+/// use rune::runtime::TemplateCatalog;
+/// use std::sync::Arc;
+///
+/// let mut catalog = TemplateCatalog::new();
+/// catalog.insert("divide-by-zero", "Division par zéro");
+/// catalog.insert("panic", "Panique : {reason}");
+///
+/// let mut context = Context::with_default_modules()?;
+/// context.set_message_catalog(catalog);
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main(n) {
+///             10 / n
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+///
+/// let runtime = Arc::new(context.runtime());
+/// let mut vm = Vm::new(runtime.clone(), Arc::new(unit));
+///
+/// let error = vm.call(["main"], (0,)).unwrap_err();
+/// assert_eq!(error.to_string(), "Division by zero");
+/// assert_eq!(runtime.localize(&error).to_string(), "Division par zéro");
+/// # Ok::<_, rune::Error>(())
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct TemplateCatalog {
+    templates: HashMap<Box<str>, Box<str>>,
+}
+
+impl TemplateCatalog {
+    /// Construct a new empty catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert the template to use for the message with the given id,
+    /// replacing any previous one.
+    pub fn insert(&mut self, id: &str, template: &str) {
+        self.templates.insert(id.into(), template.into());
+    }
+}
+
+impl MessageCatalog for TemplateCatalog {
+    fn format(&self, message: &Message<'_>, f: &mut fmt::Formatter<'_>) -> Option<fmt::Result> {
+        let template = self.templates.get(message.id)?;
+        Some(message.write_template(template, f))
+    }
+}
+
+/// Something which is displayed through a [MessageCatalog].
+pub(crate) trait Localize {
+    /// Format the value using the given catalog.
+    fn localize(&self, catalog: &dyn MessageCatalog, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+/// Display a value through the given catalog.
+pub(crate) struct Localized<'a, T: ?Sized> {
+    catalog: &'a dyn MessageCatalog,
+    value: &'a T,
+}
+
+impl<'a, T: ?Sized> Localized<'a, T> {
+    pub(crate) fn new(catalog: &'a dyn MessageCatalog, value: &'a T) -> Self {
+        Self { catalog, value }
+    }
+}
+
+impl<T> fmt::Display for Localized<'_, T>
+where
+    T: ?Sized + Localize,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.localize(self.catalog, f)
+    }
+}
+
+/// Format the message with the given id and arguments through a catalog,
+/// falling back to its English `template`.
+pub(crate) fn message(
+    catalog: &dyn MessageCatalog,
+    f: &mut fmt::Formatter<'_>,
+    id: &'static str,
+    template: &'static str,
+    args: &[(&'static str, &dyn fmt::Display)],
+) -> fmt::Result {
+    let message = Message { id, template, args };
+
+    match catalog.format(&message, f) {
+        Some(result) => result,
+        None => message.write_template(template, f),
+    }
+}
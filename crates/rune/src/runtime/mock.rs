@@ -0,0 +1,78 @@
+use core::cell::RefCell;
+
+use crate::no_std::rc::Rc;
+
+use crate::hash;
+use crate::runtime::Function;
+use crate::Hash;
+
+/// Functions which shadow functions in the context of a virtual machine,
+/// installed by tests through `std::test::mock`.
+///
+/// Mocks are only enabled for virtual machines running in test mode. Virtual
+/// machines created to run async functions, generators and streams share the
+/// mocks of the virtual machine calling them.
+pub(crate) struct Mocks {
+    registry: Option<Rc<RefCell<hash::Map<Function>>>>,
+}
+
+impl Mocks {
+    /// Construct disabled mocks, which can't be installed.
+    pub(crate) const fn new() -> Self {
+        Self { registry: None }
+    }
+
+    /// Construct enabled mocks without any installed functions.
+    pub(crate) fn enabled() -> Self {
+        Self {
+            registry: Some(Rc::default()),
+        }
+    }
+
+    /// Test if mocks are enabled.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.registry.is_some()
+    }
+
+    /// Construct mocks which share their functions with these ones.
+    pub(crate) fn share(&self) -> Self {
+        Self {
+            registry: self.registry.clone(),
+        }
+    }
+
+    /// Install a function which shadows the context function with the given
+    /// hash, returning `false` if mocks are disabled.
+    pub(crate) fn insert(&self, hash: Hash, function: Function) -> bool {
+        let Some(registry) = &self.registry else {
+            return false;
+        };
+
+        registry.borrow_mut().insert(hash, function);
+        true
+    }
+
+    /// Remove the function shadowing the context function with the given hash,
+    /// returning `true` if one was installed.
+    pub(crate) fn remove(&self, hash: Hash) -> bool {
+        let Some(registry) = &self.registry else {
+            return false;
+        };
+
+        registry.borrow_mut().remove(&hash).is_some()
+    }
+
+    /// Get the function shadowing the context function with the given hash.
+    #[inline]
+    pub(crate) fn get(&self, hash: Hash) -> Option<Function> {
+        let registry = self.registry.as_ref()?;
+        registry.borrow().get(&hash).cloned()
+    }
+
+    /// Remove every installed function.
+    pub(crate) fn clear(&self) {
+        if let Some(registry) = &self.registry {
+            registry.borrow_mut().clear();
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use core::fmt;
+
+use std::error::Error;
+
+use crate::no_std::prelude::*;
+
+use crate as rune;
+use crate::Any;
+
+/// An error raised by a native function registered through
+/// [`Module::fallible_function`].
+///
+/// In scripts this is the `std::error::Error` type, which exposes the message
+/// of the error and the messages of the chain of errors which caused it. The
+/// original error is kept as-is, so once it's returned to the host it can be
+/// recovered through [`NativeError::downcast_ref`] or
+/// [`NativeError::downcast`].
+///
+/// [`Module::fallible_function`]: crate::Module::fallible_function
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Module, Vm};
+/// use rune::runtime::NativeError;
+/// use std::num::ParseIntError;
+/// use std::sync::Arc;
+///
+/// let mut module = Module::new();
+/// module.fallible_function(["parse"], |s: &str| s.parse::<i64>())?;
+///
+/// let mut context = Context::with_default_modules()?;
+/// context.install(module)?;
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             parse("not a number")
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+///
+/// let output: Result<i64, NativeError> = rune::from_value(vm.call(["main"], ())?)?;
+/// let error = output.unwrap_err();
+///
+/// assert_eq!(error.message(), "invalid digit found in string");
+/// assert!(error.downcast_ref::<ParseIntError>().is_some());
+/// # Ok::<_, rune::Error>(())
+/// ```
+#[derive(Any)]
+#[rune(module = crate, item = ::std::error, name = Error)]
+pub struct NativeError {
+    inner: Box<dyn Error + Send + Sync>,
+}
+
+impl NativeError {
+    /// Construct a new native error wrapping the given error.
+    pub fn new<E>(error: E) -> Self
+    where
+        E: 'static + Error + Send + Sync,
+    {
+        Self {
+            inner: Box::new(error),
+        }
+    }
+
+    /// The message of the error.
+    pub fn message(&self) -> String {
+        self.inner.to_string()
+    }
+
+    /// Iterate over the chain of errors, starting with the wrapped error
+    /// followed by its sources.
+    pub fn chain(&self) -> impl Iterator<Item = &(dyn Error + 'static)> + '_ {
+        let mut current = Some(&*self.inner as &(dyn Error + 'static));
+
+        core::iter::from_fn(move || {
+            let error = current?;
+            current = error.source();
+            Some(error)
+        })
+    }
+
+    /// Get a reference to the wrapped error if it's of type `E`.
+    pub fn downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: 'static + Error,
+    {
+        self.inner.downcast_ref()
+    }
+
+    /// Convert into the wrapped error if it's of type `E`, or return the
+    /// native error unchanged.
+    pub fn downcast<E>(self) -> Result<E, Self>
+    where
+        E: 'static + Error,
+    {
+        match self.inner.downcast() {
+            Ok(error) => Ok(*error),
+            Err(inner) => Err(Self { inner }),
+        }
+    }
+
+    /// Convert into the wrapped error.
+    pub fn into_inner(self) -> Box<dyn Error + Send + Sync> {
+        self.inner
+    }
+}
+
+impl fmt::Display for NativeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl fmt::Debug for NativeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl Error for NativeError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.inner.source()
+    }
+}
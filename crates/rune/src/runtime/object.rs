@@ -4,9 +4,14 @@ use core::cmp::Ordering;
 use core::fmt;
 use core::hash;
 use core::iter;
+use core::mem;
+use core::ops;
+use core::ops::Bound;
+use core::slice;
 
 use crate::no_std::collections::{btree_map, BTreeMap};
 use crate::no_std::prelude::*;
+use crate::no_std::sync::Arc;
 
 use crate as rune;
 use crate::compile::ItemBuf;
@@ -22,41 +27,423 @@ use crate::Any;
 /// [`Object`]: struct.Object.html
 pub type IntoIter = btree_map::IntoIter<String, Value>;
 
-/// A mutable iterator over the entries of a `Object`.
+/// An interned key of an [`Object`].
 ///
-/// This `struct` is created by the [`iter_mut`] method on [`Object`]. See its
-/// documentation for more.
+/// Keys are reference counted, so every object constructed from the same
+/// static object keys of a unit, or from keys interned through the same
+/// [`Interner`], shares the storage of its keys. Comparing two keys which
+/// share storage doesn't have to compare their contents.
 ///
-/// [`iter_mut`]: struct.Object.html#method.iter_mut
-/// [`Object`]: struct.Object.html
-pub type IterMut<'a> = btree_map::IterMut<'a, String, Value>;
-
-/// An iterator over the entries of a `Object`.
+/// [`Interner`]: crate::runtime::Interner
 ///
-/// This `struct` is created by the [`iter`] method on [`Object`]. See its
-/// documentation for more.
+/// # Examples
 ///
-/// [`iter`]: struct.Object.html#method.iter
-/// [`Object`]: struct.Object.html
-pub type Iter<'a> = btree_map::Iter<'a, String, Value>;
+/// ```
+/// use rune::runtime::ObjectKey;
+///
+/// let a = ObjectKey::from("name");
+/// let b = a.clone();
+/// assert!(ObjectKey::ptr_eq(&a, &b));
+/// assert_eq!(a, ObjectKey::from("name"));
+/// assert_eq!(a.as_str(), "name");
+/// ```
+#[derive(Clone)]
+pub struct ObjectKey(Arc<String>);
+
+impl ObjectKey {
+    /// Test if two keys share the same storage.
+    #[inline]
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.0, &b.0)
+    }
+
+    /// Access the key as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Convert the key into an owned string, which only allocates if the key
+    /// is shared.
+    #[inline]
+    pub fn into_string(self) -> String {
+        Arc::try_unwrap(self.0).unwrap_or_else(|key| (*key).clone())
+    }
+
+    /// The number of bytes used to store the contents of the key.
+    #[inline]
+    pub(crate) fn size(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl ops::Deref for ObjectKey {
+    type Target = String;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl borrow::Borrow<String> for ObjectKey {
+    #[inline]
+    fn borrow(&self) -> &String {
+        &self.0
+    }
+}
+
+impl borrow::Borrow<str> for ObjectKey {
+    #[inline]
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for ObjectKey {
+    #[inline]
+    fn from(key: String) -> Self {
+        Self(Arc::new(key))
+    }
+}
+
+impl From<&str> for ObjectKey {
+    #[inline]
+    fn from(key: &str) -> Self {
+        Self(Arc::new(key.to_owned()))
+    }
+}
+
+impl PartialEq for ObjectKey {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Self::ptr_eq(self, other) || self.0 == other.0
+    }
+}
+
+impl Eq for ObjectKey {}
+
+impl PartialOrd for ObjectKey {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ObjectKey {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        if Self::ptr_eq(self, other) {
+            return Ordering::Equal;
+        }
+
+        self.0.cmp(&other.0)
+    }
+}
 
-/// An iterator over the keys of a `HashMap`.
+impl hash::Hash for ObjectKey {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl fmt::Debug for ObjectKey {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for ObjectKey {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! iterator {
+    (
+        $(#[$meta:meta])*
+        $name:ident<$lt:lifetime>, $item:ty, $shaped:ty, $map:ty
+    ) => {
+        $(#[$meta])*
+        pub struct $name<$lt> {
+            inner: IterRepr<$shaped, $map>,
+        }
+
+        impl<$lt> iter::Iterator for $name<$lt> {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                match &mut self.inner {
+                    IterRepr::Shaped(iter) => iter.next(),
+                    IterRepr::Map(iter) => iter.next(),
+                }
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                match &self.inner {
+                    IterRepr::Shaped(iter) => iter.size_hint(),
+                    IterRepr::Map(iter) => iter.size_hint(),
+                }
+            }
+        }
+
+        impl<$lt> iter::DoubleEndedIterator for $name<$lt> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                match &mut self.inner {
+                    IterRepr::Shaped(iter) => iter.next_back(),
+                    IterRepr::Map(iter) => iter.next_back(),
+                }
+            }
+        }
+
+        impl<$lt> iter::ExactSizeIterator for $name<$lt> {}
+    };
+}
+
+/// The representation of an iterator, depending on the representation of the
+/// object it iterates over.
+enum IterRepr<S, M> {
+    Shaped(S),
+    Map(M),
+}
+
+iterator! {
+    /// A mutable iterator over the entries of a `Object`.
+    ///
+    /// This `struct` is created by the [`iter_mut`] method on [`Object`]. See its
+    /// documentation for more.
+    ///
+    /// [`iter_mut`]: struct.Object.html#method.iter_mut
+    /// [`Object`]: struct.Object.html
+    IterMut<'a>,
+    (&'a String, &'a mut Value),
+    iter::Zip<ShapeKeys<'a>, slice::IterMut<'a, Value>>,
+    iter::Map<btree_map::IterMut<'a, ObjectKey, Value>, fn((&'a ObjectKey, &'a mut Value)) -> (&'a String, &'a mut Value)>
+}
+
+iterator! {
+    /// An iterator over the entries of a `Object`.
+    ///
+    /// This `struct` is created by the [`iter`] method on [`Object`]. See its
+    /// documentation for more.
+    ///
+    /// [`iter`]: struct.Object.html#method.iter
+    /// [`Object`]: struct.Object.html
+    Iter<'a>,
+    (&'a String, &'a Value),
+    iter::Zip<ShapeKeys<'a>, slice::Iter<'a, Value>>,
+    iter::Map<btree_map::Iter<'a, ObjectKey, Value>, fn((&'a ObjectKey, &'a Value)) -> (&'a String, &'a Value)>
+}
+
+iterator! {
+    /// An iterator over the keys of a `HashMap`.
+    ///
+    /// This `struct` is created by the [`keys`] method on [`Object`]. See its
+    /// documentation for more.
+    ///
+    /// [`keys`]: struct.Object.html#method.keys
+    /// [`Object`]: struct.Object.html
+    Keys<'a>,
+    &'a String,
+    ShapeKeys<'a>,
+    iter::Map<btree_map::Keys<'a, ObjectKey, Value>, fn(&'a ObjectKey) -> &'a String>
+}
+
+iterator! {
+    /// An iterator over the values of a `HashMap`.
+    ///
+    /// This `struct` is created by the [`values`] method on [`Object`]. See its
+    /// documentation for more.
+    ///
+    /// [`values`]: struct.Object.html#method.values
+    /// [`Object`]: struct.Object.html
+    Values<'a>,
+    &'a Value,
+    slice::Iter<'a, Value>,
+    btree_map::Values<'a, ObjectKey, Value>
+}
+
+/// A borrowed iterator over the entries of an [`Object`], which holds on to
+/// the [`Ref`] guard of the object instead of borrowing from it.
 ///
-/// This `struct` is created by the [`keys`] method on [`Object`]. See its
-/// documentation for more.
+/// Entries are visited in the same order as [`Object::iter`], but since they
+/// borrow from the iterator itself it is advanced through
+/// [`ObjectIterRef::next_entry`] instead of implementing [`Iterator`]. Nothing
+/// is cloned or allocated while iterating.
 ///
-/// [`keys`]: struct.Object.html#method.keys
-/// [`Object`]: struct.Object.html
-pub type Keys<'a> = btree_map::Keys<'a, String, Value>;
-
-/// An iterator over the values of a `HashMap`.
+/// This `struct` is created by the [`Object::iter_ref`] function.
 ///
-/// This `struct` is created by the [`values`] method on [`Object`]. See its
-/// documentation for more.
+/// [`Iterator`]: core::iter::Iterator
 ///
-/// [`values`]: struct.Object.html#method.values
-/// [`Object`]: struct.Object.html
-pub type Values<'a> = btree_map::Values<'a, String, Value>;
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{Object, Shared};
+///
+/// let mut object = Object::new();
+/// object.insert_value(String::from("a"), 1).into_result()?;
+/// object.insert_value(String::from("b"), 2).into_result()?;
+///
+/// let object = Shared::new(object);
+/// let mut iter = Object::iter_ref(object.clone().into_ref()?);
+/// let mut sum = 0;
+///
+/// while let Some((_, value)) = iter.next_entry() {
+///     sum += value.as_integer().into_result()?;
+/// }
+///
+/// assert_eq!(sum, 3);
+///
+/// // The object can't be modified while it's being iterated over.
+/// assert!(object.borrow_mut().is_err());
+/// drop(iter);
+/// assert!(object.borrow_mut().is_ok());
+/// # Ok::<_, rune::Error>(())
+/// ```
+pub struct ObjectIterRef {
+    object: Ref<Object>,
+    cursor: Cursor,
+}
+
+/// The position of an [`ObjectIterRef`].
+enum Cursor {
+    /// Positioned at the given index of a shaped object.
+    Index(usize),
+    /// Positioned at the start of an object stored as a map.
+    First,
+    /// Positioned after the given key of an object stored as a map.
+    After(ObjectKey),
+}
+
+impl ObjectIterRef {
+    /// Advance the iterator, returning the next key and value of the object.
+    pub fn next_entry(&mut self) -> Option<(&str, &Value)> {
+        match &self.object.inner {
+            Repr::Shaped { shape, values } => {
+                let Cursor::Index(index) = &mut self.cursor else {
+                    return None;
+                };
+
+                let key = shape.keys.get(*index)?;
+                let value = values.get(*index)?;
+                *index += 1;
+                Some((key.as_str(), value))
+            }
+            Repr::Map(map) => {
+                let mut range = match &self.cursor {
+                    Cursor::First => map.range::<str, _>(..),
+                    Cursor::After(key) => {
+                        map.range::<str, _>((Bound::Excluded(key.as_str()), Bound::Unbounded))
+                    }
+                    Cursor::Index(..) => return None,
+                };
+
+                let (key, value) = range.next()?;
+                self.cursor = Cursor::After(key.clone());
+                Some((key.as_str(), value))
+            }
+        }
+    }
+
+    /// Access the object being iterated over.
+    pub fn object(&self) -> &Object {
+        &self.object
+    }
+}
+
+/// An iterator over the keys of a shape.
+type ShapeKeys<'a> = iter::Map<slice::Iter<'a, ObjectKey>, fn(&'a ObjectKey) -> &'a String>;
+
+/// Access the contents of a key.
+#[inline]
+fn key(k: &ObjectKey) -> &String {
+    &k.0
+}
+
+/// Access the contents of the key of an entry.
+#[inline]
+fn entry<V>((k, v): (&ObjectKey, V)) -> (&String, V) {
+    (&k.0, v)
+}
+
+/// The shape of an object, which is the collection of keys it was constructed
+/// with.
+///
+/// Shapes are cached for every collection of static object keys in a unit, so
+/// that objects constructed from them can store their values in a vector which
+/// is indexed by the position of the key in the shape instead of having to
+/// build a map.
+#[derive(Debug)]
+pub(crate) struct Shape {
+    /// The keys of the shape, sorted.
+    keys: Box<[ObjectKey]>,
+    /// For each sorted key, the position of the key in the order the shape
+    /// was constructed with, or `None` if they are the same.
+    order: Option<Box<[usize]>>,
+}
+
+impl Shape {
+    /// Construct the shape of objects constructed from the given keys in
+    /// order.
+    ///
+    /// Returns `None` if the keys contain duplicates, since objects
+    /// constructed from them don't have a shape.
+    pub(crate) fn new<K>(keys: &[K]) -> Option<Self>
+    where
+        K: Clone + Ord + Into<ObjectKey>,
+    {
+        let mut order = (0..keys.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&n| &keys[n]);
+
+        if order.windows(2).any(|w| keys[w[0]] == keys[w[1]]) {
+            return None;
+        }
+
+        let sorted = order.iter().map(|&n| keys[n].clone().into()).collect();
+
+        let order = if order.iter().enumerate().all(|(a, &b)| a == b) {
+            None
+        } else {
+            Some(order.into())
+        };
+
+        Some(Self {
+            keys: sorted,
+            order,
+        })
+    }
+
+    /// Get the index of the given key in the shape.
+    #[inline]
+    fn index_of<Q: ?Sized>(&self, k: &Q) -> Option<usize>
+    where
+        ObjectKey: borrow::Borrow<Q>,
+        Q: cmp::Ord,
+    {
+        self.keys
+            .binary_search_by(|key| borrow::Borrow::<Q>::borrow(key).cmp(k))
+            .ok()
+    }
+}
+
+/// The representation of an object.
+#[derive(Clone)]
+enum Repr {
+    /// An object with a shape, where values are stored in the same order as
+    /// the keys of the shape.
+    Shaped {
+        shape: Arc<Shape>,
+        values: Vec<Value>,
+    },
+    /// An object stored as a map.
+    Map(BTreeMap<ObjectKey, Value>),
+}
 
 /// Struct representing a dynamic anonymous object.
 ///
@@ -75,11 +462,11 @@ pub type Values<'a> = btree_map::Values<'a, String, Value>;
 /// assert_eq!(None::<bool>, object.get_value("baz").into_result()?);
 /// # Ok::<_, rune::Error>(())
 /// ```
-#[derive(Any, Default, Clone)]
+#[derive(Any, Clone)]
 #[repr(transparent)]
 #[rune(builtin, static_type = OBJECT_TYPE)]
 pub struct Object {
-    inner: BTreeMap<String, Value>,
+    inner: Repr,
 }
 
 impl Object {
@@ -95,7 +482,7 @@ impl Object {
     #[rune::function(keep, path = Self::new)]
     pub fn new() -> Self {
         Self {
-            inner: BTreeMap::new(),
+            inner: Repr::Map(BTreeMap::new()),
         }
     }
 
@@ -113,7 +500,41 @@ impl Object {
         // BTreeMap doesn't support setting capacity on creation but we keep
         // this here in case we want to switch store later.
         Self {
-            inner: BTreeMap::new(),
+            inner: Repr::Map(BTreeMap::new()),
+        }
+    }
+
+    /// Construct an object with the given shape, from values in the order of
+    /// the keys the shape was constructed with.
+    pub(crate) fn with_shape<I>(shape: &Arc<Shape>, values: I) -> Self
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        let mut values = values.into_iter().collect::<Vec<_>>();
+
+        if let Some(order) = &shape.order {
+            values = order.iter().map(|&n| mem::take(&mut values[n])).collect();
+        }
+
+        Self {
+            inner: Repr::Shaped {
+                shape: shape.clone(),
+                values,
+            },
+        }
+    }
+
+    /// Access the map of the object, converting it from a shaped object if
+    /// necessary.
+    fn map_mut(&mut self) -> &mut BTreeMap<ObjectKey, Value> {
+        if let Repr::Shaped { shape, values } = &mut self.inner {
+            let map = shape.keys.iter().cloned().zip(values.drain(..)).collect();
+            self.inner = Repr::Map(map);
+        }
+
+        match &mut self.inner {
+            Repr::Map(map) => map,
+            Repr::Shaped { .. } => unreachable!(),
         }
     }
 
@@ -129,7 +550,10 @@ impl Object {
     #[inline]
     #[rune::function(keep)]
     pub fn len(&self) -> usize {
-        self.inner.len()
+        match &self.inner {
+            Repr::Shaped { values, .. } => values.len(),
+            Repr::Map(map) => map.len(),
+        }
     }
 
     /// Returns `true` if the object is empty.
@@ -145,27 +569,54 @@ impl Object {
     #[inline]
     #[rune::function(keep)]
     pub fn is_empty(&self) -> bool {
-        self.inner.is_empty()
+        self.len() == 0
     }
 
     /// Returns a reference to the value corresponding to the key.
     #[inline]
     pub fn get<Q: ?Sized>(&self, k: &Q) -> Option<&Value>
     where
-        String: borrow::Borrow<Q>,
+        ObjectKey: borrow::Borrow<Q>,
         Q: hash::Hash + cmp::Eq + cmp::Ord,
     {
-        self.inner.get(k)
+        match &self.inner {
+            Repr::Shaped { shape, values } => values.get(shape.index_of(k)?),
+            Repr::Map(map) => map.get(k),
+        }
+    }
+
+    /// Returns a reference to the value corresponding to the key, which holds
+    /// on to the [`Ref`] guard of the object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::{Object, Shared};
+    ///
+    /// let mut object = Object::new();
+    /// object.insert_value(String::from("a"), 1).into_result()?;
+    ///
+    /// let object = Shared::new(object);
+    /// let value = Object::get_ref(object.into_ref()?, "a").expect("missing key");
+    /// assert_eq!(value.as_integer().into_result()?, 1);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn get_ref<Q: ?Sized>(this: Ref<Self>, k: &Q) -> Option<Ref<Value>>
+    where
+        ObjectKey: borrow::Borrow<Q>,
+        Q: hash::Hash + cmp::Eq + cmp::Ord,
+    {
+        Ref::try_map(this, |object| object.get(k))
     }
 
     /// Get the given value at the given index.
     pub fn get_value<Q: ?Sized, T>(&self, k: &Q) -> VmResult<Option<T>>
     where
-        String: borrow::Borrow<Q>,
+        ObjectKey: borrow::Borrow<Q>,
         Q: hash::Hash + cmp::Eq + cmp::Ord,
         T: FromValue,
     {
-        let value = match self.inner.get(k) {
+        let value = match self.get(k) {
             Some(value) => value.clone(),
             None => return VmResult::Ok(None),
         };
@@ -177,20 +628,23 @@ impl Object {
     #[inline]
     pub fn get_mut<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut Value>
     where
-        String: borrow::Borrow<Q>,
+        ObjectKey: borrow::Borrow<Q>,
         Q: hash::Hash + cmp::Eq + cmp::Ord,
     {
-        self.inner.get_mut(k)
+        match &mut self.inner {
+            Repr::Shaped { shape, values } => values.get_mut(shape.index_of(k)?),
+            Repr::Map(map) => map.get_mut(k),
+        }
     }
 
     /// Returns `true` if the map contains a value for the specified key.
     #[inline]
     pub fn contains_key<Q: ?Sized>(&self, k: &Q) -> bool
     where
-        String: borrow::Borrow<Q>,
+        ObjectKey: borrow::Borrow<Q>,
         Q: hash::Hash + cmp::Eq + cmp::Ord,
     {
-        self.inner.contains_key(k)
+        self.get(k).is_some()
     }
 
     /// Removes a key from the map, returning the value at the key if the key
@@ -198,10 +652,14 @@ impl Object {
     #[inline]
     pub fn remove<Q: ?Sized>(&mut self, k: &Q) -> Option<Value>
     where
-        String: borrow::Borrow<Q>,
+        ObjectKey: borrow::Borrow<Q>,
         Q: hash::Hash + cmp::Eq + cmp::Ord,
     {
-        self.inner.remove(k)
+        if !self.contains_key(k) {
+            return None;
+        }
+
+        self.map_mut().remove(k)
     }
 
     /// Inserts a key-value pair into the dynamic object, converting it as
@@ -211,7 +669,7 @@ impl Object {
     where
         T: ToValue,
     {
-        self.inner.insert(k, vm_try!(v.to_value()));
+        self.insert(k, vm_try!(v.to_value()));
         VmResult::Ok(())
     }
 
@@ -233,7 +691,11 @@ impl Object {
     #[inline]
     #[rune::function(keep)]
     pub fn insert(&mut self, k: String, v: Value) -> Option<Value> {
-        self.inner.insert(k, v)
+        if let Some(value) = self.get_mut(&k) {
+            return Some(mem::replace(value, v));
+        }
+
+        self.map_mut().insert(ObjectKey::from(k), v)
     }
 
     /// Clears the object, removing all key-value pairs. Keeps the allocated
@@ -241,30 +703,98 @@ impl Object {
     #[inline]
     #[rune::function(keep)]
     pub fn clear(&mut self) {
-        self.inner.clear();
+        self.map_mut().clear();
+    }
+
+    /// Inserts an interned key-value pair into the map.
+    ///
+    /// Unlike [`insert`][Object::insert], this shares the storage of the key
+    /// with every other object it's inserted into.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    pub fn insert_key(&mut self, k: ObjectKey, v: Value) -> Option<Value> {
+        if let Some(value) = self.get_mut(&k) {
+            return Some(mem::replace(value, v));
+        }
+
+        self.map_mut().insert(k, v)
     }
 
     /// Convert into inner.
-    pub fn into_inner(self) -> BTreeMap<String, Value> {
-        self.inner
+    pub fn into_inner(mut self) -> BTreeMap<String, Value> {
+        mem::take(self.map_mut())
+            .into_iter()
+            .map(|(k, v)| (k.into_string(), v))
+            .collect()
+    }
+
+    /// Iterate over the interned keys of the object in order.
+    pub fn object_keys(&self) -> impl iter::Iterator<Item = &ObjectKey> + '_ {
+        let (shaped, map) = match &self.inner {
+            Repr::Shaped { shape, .. } => (Some(shape.keys.iter()), None),
+            Repr::Map(map) => (None, Some(map.keys())),
+        };
+
+        shaped
+            .into_iter()
+            .flatten()
+            .chain(map.into_iter().flatten())
     }
 
     /// An iterator visiting all key-value pairs in arbitrary order.
     /// The iterator element type is `(&'a String, &'a Value)`.
     pub fn iter(&self) -> Iter<'_> {
-        self.inner.iter()
+        let inner = match &self.inner {
+            Repr::Shaped { shape, values } => {
+                IterRepr::Shaped(shape.keys.iter().map(key as fn(_) -> _).zip(values.iter()))
+            }
+            Repr::Map(map) => IterRepr::Map(map.iter().map(entry as fn(_) -> _)),
+        };
+
+        Iter { inner }
+    }
+
+    /// Construct a borrowed iterator over the entries of the object, which
+    /// holds on to the [`Ref`] guard of the object.
+    ///
+    /// Unlike [`Object::iter`] the iterator doesn't borrow from a local
+    /// reference, so it can be stored or returned by host code without
+    /// cloning the entries of the object. See [`ObjectIterRef`] for an
+    /// example.
+    pub fn iter_ref(this: Ref<Self>) -> ObjectIterRef {
+        let cursor = match &this.inner {
+            Repr::Shaped { .. } => Cursor::Index(0),
+            Repr::Map(..) => Cursor::First,
+        };
+
+        ObjectIterRef {
+            object: this,
+            cursor,
+        }
     }
 
     /// An iterator visiting all keys in arbitrary order.
     /// The iterator element type is `&'a String`.
     pub fn keys(&self) -> Keys<'_> {
-        self.inner.keys()
+        let inner = match &self.inner {
+            Repr::Shaped { shape, .. } => {
+                IterRepr::Shaped(shape.keys.iter().map(key as fn(_) -> _))
+            }
+            Repr::Map(map) => IterRepr::Map(map.keys().map(key as fn(_) -> _)),
+        };
+
+        Keys { inner }
     }
 
     /// An iterator visiting all values in arbitrary order.
     /// The iterator element type is `&'a Value`.
     pub fn values(&self) -> Values<'_> {
-        self.inner.values()
+        let inner = match &self.inner {
+            Repr::Shaped { values, .. } => IterRepr::Shaped(values.iter()),
+            Repr::Map(map) => IterRepr::Map(map.values()),
+        };
+
+        Values { inner }
     }
 
     /// An iterator visiting all key-value pairs in arbitrary order,
@@ -272,7 +802,18 @@ impl Object {
     ///
     /// The iterator element type is `(&'a String, &'a mut Value)`.
     pub fn iter_mut(&mut self) -> IterMut<'_> {
-        self.inner.iter_mut()
+        let inner = match &mut self.inner {
+            Repr::Shaped { shape, values } => IterRepr::Shaped(
+                shape
+                    .keys
+                    .iter()
+                    .map(key as fn(_) -> _)
+                    .zip(values.iter_mut()),
+            ),
+            Repr::Map(map) => IterRepr::Map(map.iter_mut().map(entry as fn(_) -> _)),
+        };
+
+        IterMut { inner }
     }
 
     /// An iterator visiting all keys and values in arbitrary order.
@@ -334,12 +875,12 @@ impl Object {
     where
         P: ProtocolCaller,
     {
-        if a.inner.len() != b.inner.len() {
+        if a.len() != b.len() {
             return VmResult::Ok(false);
         }
 
-        for (key, a) in a.inner.iter() {
-            let Some(b) = b.inner.get(key) else {
+        for (key, a) in a.iter() {
+            let Some(b) = b.get(key) else {
                 return VmResult::Ok(false);
             };
 
@@ -356,9 +897,9 @@ impl Object {
         b: &Self,
         caller: &mut impl ProtocolCaller,
     ) -> VmResult<Option<Ordering>> {
-        let mut b = b.inner.iter();
+        let mut b = b.iter();
 
-        for (k1, v1) in a.inner.iter() {
+        for (k1, v1) in a.iter() {
             let Some((k2, v2)) = b.next() else {
                 return VmResult::Ok(Some(Ordering::Greater));
             };
@@ -386,9 +927,9 @@ impl Object {
         b: &Self,
         caller: &mut impl ProtocolCaller,
     ) -> VmResult<Ordering> {
-        let mut b = b.inner.iter();
+        let mut b = b.iter();
 
-        for (k1, v1) in a.inner.iter() {
+        for (k1, v1) in a.iter() {
             let Some((k2, v2)) = b.next() else {
                 return VmResult::Ok(Ordering::Greater);
             };
@@ -444,24 +985,39 @@ impl IntoIterator for Object {
     /// pair out of the object in arbitrary order. The object cannot be used
     /// after calling this.
     fn into_iter(self) -> Self::IntoIter {
-        self.inner.into_iter()
+        self.into_inner().into_iter()
     }
 }
 
 impl fmt::Debug for Object {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_map().entries(self.inner.iter()).finish()
+        f.debug_map().entries(self.iter()).finish()
     }
 }
 
 impl iter::FromIterator<(String, Value)> for Object {
     fn from_iter<T: IntoIterator<Item = (String, Value)>>(src: T) -> Self {
+        src.into_iter()
+            .map(|(k, v)| (ObjectKey::from(k), v))
+            .collect()
+    }
+}
+
+impl iter::FromIterator<(ObjectKey, Value)> for Object {
+    fn from_iter<T: IntoIterator<Item = (ObjectKey, Value)>>(src: T) -> Self {
         Self {
-            inner: src.into_iter().collect(),
+            inner: Repr::Map(src.into_iter().collect()),
         }
     }
 }
 
+impl Default for Object {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 from_value!(Object, into_object);
 
 pub struct DebugStruct<'a> {
@@ -1,7 +1,10 @@
 use core::fmt;
 
 use crate::no_std::prelude::*;
-use crate::runtime::PanicReason;
+
+use crate::ast::Span;
+use crate::runtime::message_catalog::{Localize, Localized};
+use crate::runtime::{EnglishCatalog, MessageCatalog, PanicReason};
 
 pub trait BoxedPanic: fmt::Display + fmt::Debug + Send + Sync {}
 impl<T> BoxedPanic for T where T: ?Sized + fmt::Display + fmt::Debug + Send + Sync {}
@@ -12,7 +15,18 @@ impl<T> BoxedPanic for T where T: ?Sized + fmt::Display + fmt::Debug + Send + Sy
 /// able to panic.
 #[derive(Debug)]
 pub struct Panic {
-    inner: Box<dyn BoxedPanic>,
+    inner: PanicKind,
+    /// Spans in the source which panicked that are pointed out along with the
+    /// panic, and their messages.
+    labels: Vec<(Span, String)>,
+}
+
+#[derive(Debug)]
+enum PanicKind {
+    /// A built-in reason, whose message can be translated.
+    Reason(PanicReason),
+    /// A custom message, which is displayed as-is.
+    Custom(Box<dyn BoxedPanic>),
 }
 
 impl Panic {
@@ -22,7 +36,8 @@ impl Panic {
         D: fmt::Display,
     {
         Self {
-            inner: Box::new(message.to_string()),
+            inner: PanicKind::Custom(Box::new(message.to_string())),
+            labels: Vec::new(),
         }
     }
 
@@ -32,21 +47,49 @@ impl Panic {
         D: 'static + BoxedPanic,
     {
         Self {
-            inner: Box::new(message),
+            inner: PanicKind::Custom(Box::new(message)),
+            labels: Vec::new(),
         }
     }
+
+    /// Point out the given span in the source which panicked along with the
+    /// panic.
+    pub(crate) fn with_label<D>(mut self, span: Span, message: D) -> Self
+    where
+        D: fmt::Display,
+    {
+        self.labels.push((span, message.to_string()));
+        self
+    }
+
+    /// Spans in the source which panicked that are pointed out along with the
+    /// panic.
+    pub(crate) fn labels(&self) -> &[(Span, String)] {
+        &self.labels
+    }
 }
 
 impl fmt::Display for Panic {
+    #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(fmt, "{}", self.inner)
+        self.localize(&EnglishCatalog, fmt)
+    }
+}
+
+impl Localize for Panic {
+    fn localize(&self, catalog: &dyn MessageCatalog, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.inner {
+            PanicKind::Reason(reason) => write!(f, "{}", Localized::new(catalog, reason)),
+            PanicKind::Custom(message) => write!(f, "{}", message),
+        }
     }
 }
 
 impl From<PanicReason> for Panic {
     fn from(value: PanicReason) -> Self {
         Self {
-            inner: Box::new(value),
+            inner: PanicKind::Reason(value),
+            labels: Vec::new(),
         }
     }
 }
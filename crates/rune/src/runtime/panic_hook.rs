@@ -0,0 +1,178 @@
+use core::fmt;
+
+use crate::no_std::prelude::*;
+
+use crate::ast::Span;
+use crate::compile::ItemBuf;
+use crate::runtime::debug::DebugSignature;
+use crate::runtime::{DebugInfo, Vm, VmError};
+use crate::SourceId;
+
+/// A hook which is called when a script panics without the error being
+/// handled.
+///
+/// Any error which aborts the execution of a virtual machine is considered a
+/// panic, be it an explicit call to `panic!` or an error raised by an
+/// instruction or a native function. The hook is called once for every such
+/// error, before it's returned to the caller. This makes it possible to
+/// centralize crash reporting for scripts driven by a host, like callbacks
+/// called from an event loop.
+///
+/// A hook is installed through [Context::set_panic_hook], and applies to all
+/// virtual machines constructed from its [RuntimeContext].
+///
+/// [Context::set_panic_hook]: crate::Context::set_panic_hook
+/// [RuntimeContext]: crate::runtime::RuntimeContext
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Vm};
+/// use rune::runtime::PanicInfo;
+/// use std::sync::{Arc, Mutex};
+///
+/// let panics = Arc::new(Mutex::new(Vec::new()));
+///
+/// let mut context = Context::with_default_modules()?;
+///
+/// context.set_panic_hook({
+///     let panics = panics.clone();
+///
+///     move |info: &PanicInfo<'_>| {
+///         let function = info.frames.first().and_then(|f| f.function.as_ref());
+///         let function = function.map(|f| f.to_string());
+///         let payload = info.payload().map(|p| p.to_string());
+///         panics.lock().unwrap().push((info.vm, function, payload));
+///     }
+/// });
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             panic!("oh no");
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+///
+/// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+/// vm.set_id(42);
+/// assert!(vm.call(["main"], ()).is_err());
+///
+/// let panics = panics.lock().unwrap();
+///
+/// assert_eq!(
+///     &panics[..],
+///     [(42, Some(String::from("main")), Some(String::from("oh no")))]
+/// );
+/// # Ok::<_, rune::Error>(())
+/// ```
+pub trait PanicHook: Send + Sync {
+    /// Called when a virtual machine panics.
+    fn panic(&self, info: &PanicInfo<'_>);
+}
+
+impl<F> PanicHook for F
+where
+    F: Fn(&PanicInfo<'_>) + Send + Sync,
+{
+    #[inline]
+    fn panic(&self, info: &PanicInfo<'_>) {
+        self(info)
+    }
+}
+
+/// Information on a panic which is passed to a [PanicHook].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct PanicInfo<'a> {
+    /// The identity of the virtual machine which panicked, as set through
+    /// [Vm::set_id].
+    pub vm: u64,
+    /// The error the virtual machine panicked with.
+    pub error: &'a VmError,
+    /// The resolved frames of the backtrace, starting with the frame in which
+    /// the panic happened.
+    pub frames: &'a [PanicFrame],
+}
+
+impl PanicInfo<'_> {
+    /// The payload of the panic, if it was raised explicitly like through
+    /// `panic!`.
+    pub fn payload(&self) -> Option<&dyn fmt::Display> {
+        Some(self.error.panic_payload()?)
+    }
+}
+
+/// A single frame in the backtrace of a panic.
+///
+/// Frames are resolved using the debug info of the unit they belong to, so the
+/// function and location are only available if the unit has debug info.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PanicFrame {
+    /// The instruction pointer of the frame.
+    pub ip: usize,
+    /// The function the frame belongs to.
+    pub function: Option<ItemBuf>,
+    /// The source and span of the instruction being executed.
+    pub location: Option<(SourceId, Span)>,
+}
+
+/// Report the given error to the panic hook installed in the context of the
+/// virtual machine, unless it's already been reported.
+pub(crate) fn report(vm: &Vm, error: &mut VmError) {
+    if error.inner.panic_reported {
+        return;
+    }
+
+    let Some(hook) = vm.context().panic_hook() else {
+        return;
+    };
+
+    error.inner.panic_reported = true;
+
+    let mut frames = Vec::new();
+
+    for l in &error.inner.stacktrace {
+        let debug = l.unit.debug_info();
+
+        for ip in [l.ip]
+            .into_iter()
+            .chain(l.frames.iter().rev().map(|f| f.ip))
+        {
+            let function = debug
+                .and_then(|debug| function_containing(debug, ip))
+                .map(|signature| signature.path.clone());
+
+            let location = debug
+                .and_then(|debug| debug.instruction_at(ip))
+                .map(|inst| (inst.source_id, inst.span));
+
+            frames.push(PanicFrame {
+                ip,
+                function,
+                location,
+            });
+        }
+    }
+
+    hook.panic(&PanicInfo {
+        vm: vm.id(),
+        error,
+        frames: &frames,
+    });
+}
+
+/// Find the signature of the function which contains the given instruction,
+/// which is the function with the closest entry at or before it.
+pub(super) fn function_containing(debug: &DebugInfo, ip: usize) -> Option<&DebugSignature> {
+    let (_, hash) = debug
+        .functions_rev
+        .iter()
+        .filter(|(offset, _)| **offset <= ip)
+        .max_by_key(|(offset, _)| **offset)?;
+
+    debug.functions.get(hash)
+}
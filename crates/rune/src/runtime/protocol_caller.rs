@@ -1,6 +1,8 @@
+use core::fmt;
+
 use crate::runtime::vm::CallResult;
 use crate::runtime::{
-    GuardedArgs, Protocol, Stack, UnitFn, Value, Vm, VmError, VmErrorKind, VmResult,
+    Formatter, GuardedArgs, Protocol, Stack, UnitFn, Value, Vm, VmError, VmErrorKind, VmResult,
 };
 use crate::Hash;
 
@@ -31,6 +33,10 @@ pub(crate) trait ProtocolCaller: Sized {
             self.call_protocol_fn(protocol, target, args)
         )))
     }
+
+    /// Format the given value using the pretty printer registered for its
+    /// type, returning `None` if there is no such printer.
+    fn pretty_print(&mut self, value: &Value, f: &mut Formatter) -> VmResult<Option<fmt::Result>>;
 }
 
 /// Use the global environment caller.
@@ -79,7 +85,7 @@ impl ProtocolCaller for EnvProtocolCaller {
             // Safety: We hold onto the guard until the vm has completed.
             let _guard = unsafe { vm_try!(args.unsafe_into_stack(&mut stack)) };
 
-            vm_try!(handler(&mut stack, count));
+            vm_try!(handler(&mut stack, count).with_argument_names(context, hash));
             VmResult::Ok(vm_try!(stack.pop()))
         });
 
@@ -95,6 +101,16 @@ impl ProtocolCaller for EnvProtocolCaller {
             Ok(())
         }
     }
+
+    fn pretty_print(&mut self, value: &Value, f: &mut Formatter) -> VmResult<Option<fmt::Result>> {
+        crate::runtime::env::with(|context, _| {
+            let Some(printer) = context.pretty_printer(vm_try!(value.type_hash())) else {
+                return VmResult::Ok(None);
+            };
+
+            VmResult::Ok(Some(vm_try!(printer(value, f))))
+        })
+    }
 }
 
 impl ProtocolCaller for Vm {
@@ -111,4 +127,12 @@ impl ProtocolCaller for Vm {
 
         VmResult::Ok(vm_try!(self.stack_mut().pop()))
     }
+
+    fn pretty_print(&mut self, value: &Value, f: &mut Formatter) -> VmResult<Option<fmt::Result>> {
+        let Some(printer) = self.context().pretty_printer(vm_try!(value.type_hash())) else {
+            return VmResult::Ok(None);
+        };
+
+        VmResult::Ok(Some(vm_try!(printer(value, f))))
+    }
 }
@@ -137,6 +137,132 @@ impl Range {
         self.iter()
     }
 
+    /// Get the number of values produced when iterating over the range.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the range is not a well-defined range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// assert_eq!((0..10).len(), 10);
+    /// assert_eq!(('a'..'e').len(), 4);
+    /// assert_eq!((10..0).len(), 0);
+    /// ```
+    #[rune::function(keep)]
+    pub fn len(&self) -> VmResult<usize> {
+        vm_try!(self.iter()).len()
+    }
+
+    /// Test if the range produces no values when iterated over.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the range is not a well-defined range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// assert!(!('a'..'e').is_empty());
+    /// assert!((10..0).is_empty());
+    /// ```
+    #[rune::function(keep)]
+    pub fn is_empty(&self) -> VmResult<bool> {
+        VmResult::Ok(vm_try!(self.len()) == 0)
+    }
+
+    /// Iterate over the range in reverse.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the range is not a well-defined range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let range = 'a'..'e';
+    /// assert_eq!(range.rev().collect::<Vec>(), ['d', 'c', 'b', 'a']);
+    /// ```
+    #[rune::function(keep)]
+    pub fn rev(&self) -> VmResult<Iterator> {
+        vm_try!(self.iter()).rev()
+    }
+
+    /// Iterate over the range, stepping by the given amount for each
+    /// iteration.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the range is not a well-defined range, or if the step
+    /// is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let range = 0..7;
+    /// assert_eq!(range.step_by(2).collect::<Vec>(), [0, 2, 4, 6]);
+    /// ```
+    ///
+    /// A negative step iterates from the start of the range down towards its
+    /// end:
+    ///
+    /// ```rune
+    /// let range = 7..0;
+    /// assert_eq!(range.step_by(-2).collect::<Vec>(), [7, 5, 3, 1]);
+    /// ```
+    ///
+    /// The step must be non-zero:
+    ///
+    /// ```rune,should_panic
+    /// let range = 0..7;
+    /// range.step_by(0);
+    /// ```
+    #[rune::function(keep)]
+    pub fn step_by(&self, step: i64) -> VmResult<Iterator> {
+        const NAME: &str = "std::iter::StepBy";
+
+        if step == 0 {
+            return VmResult::panic("`step_by` requires a non-zero step");
+        }
+
+        let n = usize::try_from(step.unsigned_abs()).unwrap_or(usize::MAX);
+
+        if step < 0 {
+            return match (&self.start, &self.end) {
+                (Value::Byte(start), Value::Byte(end)) => {
+                    VmResult::Ok(Iterator::from(NAME, step_down(*start, *end, false, n)))
+                }
+                (Value::Char(start), Value::Char(end)) => {
+                    VmResult::Ok(Iterator::from(NAME, step_down(*start, *end, false, n)))
+                }
+                (Value::Integer(start), Value::Integer(end)) => {
+                    VmResult::Ok(Iterator::from(NAME, step_down(*start, *end, false, n)))
+                }
+                (start, end) => VmResult::err(VmErrorKind::UnsupportedIterRange {
+                    start: vm_try!(start.type_info()),
+                    end: vm_try!(end.type_info()),
+                }),
+            };
+        }
+
+        match (&self.start, &self.end) {
+            (Value::Byte(start), Value::Byte(end)) => {
+                VmResult::Ok(Iterator::from(NAME, (*start..*end).step_by(n)))
+            }
+            (Value::Char(start), Value::Char(end)) => {
+                VmResult::Ok(Iterator::from(NAME, (*start..*end).step_by(n)))
+            }
+            (Value::Integer(start), Value::Integer(end)) => {
+                VmResult::Ok(Iterator::from(NAME, (*start..*end).step_by(n)))
+            }
+            (start, end) => VmResult::err(VmErrorKind::UnsupportedIterRange {
+                start: vm_try!(start.type_info()),
+                end: vm_try!(end.type_info()),
+            }),
+        }
+    }
+
     /// Test the range for partial equality.
     ///
     /// # Examples
@@ -317,3 +443,20 @@ where
         VmResult::Ok(ops::Range { start, end })
     }
 }
+
+/// Iterate from `start` down towards `end`, taking every `n`th value.
+pub(crate) fn step_down<T>(
+    start: T,
+    end: T,
+    inclusive: bool,
+    n: usize,
+) -> impl core::iter::Iterator<Item = T>
+where
+    T: Copy + PartialEq,
+    ops::RangeInclusive<T>: DoubleEndedIterator<Item = T>,
+{
+    (end..=start)
+        .rev()
+        .take_while(move |value| inclusive || *value != end)
+        .step_by(n)
+}
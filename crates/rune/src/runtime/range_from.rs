@@ -130,6 +130,72 @@ impl RangeFrom {
         self.iter()
     }
 
+    /// Iterate over the range, stepping by the given amount for each
+    /// iteration.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the range is not a well-defined range, or if the step
+    /// is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let range = 'a'..;
+    /// assert_eq!(range.step_by(2).take(3).collect::<Vec>(), ['a', 'c', 'e']);
+    /// ```
+    #[rune::function(keep)]
+    pub fn step_by(&self, step: usize) -> VmResult<Iterator> {
+        const NAME: &str = "std::iter::StepBy";
+
+        if step == 0 {
+            return VmResult::panic("`step_by` requires a non-zero step");
+        }
+
+        match &self.start {
+            Value::Byte(start) => VmResult::Ok(Iterator::from(NAME, (*start..).step_by(step))),
+            Value::Char(start) => VmResult::Ok(Iterator::from(NAME, (*start..).step_by(step))),
+            Value::Integer(start) => VmResult::Ok(Iterator::from(NAME, (*start..).step_by(step))),
+            start => VmResult::err(VmErrorKind::UnsupportedIterRangeFrom {
+                start: vm_try!(start.type_info()),
+            }),
+        }
+    }
+
+    /// Unbounded ranges don't have a length.
+    ///
+    /// # Panics
+    ///
+    /// This always panics.
+    ///
+    /// ```rune,should_panic
+    /// (0..).len()
+    /// ```
+    #[rune::function(keep)]
+    pub(crate) fn len(&self) -> VmResult<usize> {
+        VmResult::err(VmErrorKind::UnboundedRange {
+            range: "std::ops::RangeFrom",
+            method: "len",
+        })
+    }
+
+    /// Unbounded ranges can't be iterated over in reverse.
+    ///
+    /// # Panics
+    ///
+    /// This always panics.
+    ///
+    /// ```rune,should_panic
+    /// (0..).rev()
+    /// ```
+    #[rune::function(keep)]
+    pub(crate) fn rev(&self) -> VmResult<Iterator> {
+        VmResult::err(VmErrorKind::UnboundedRange {
+            range: "std::ops::RangeFrom",
+            method: "rev",
+        })
+    }
+
     /// Test the range for partial equality.
     ///
     /// # Examples
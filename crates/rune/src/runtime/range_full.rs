@@ -3,7 +3,7 @@ use core::fmt;
 use core::ops;
 
 use crate as rune;
-use crate::runtime::{FromValue, ProtocolCaller, ToValue, Value, VmResult};
+use crate::runtime::{FromValue, ProtocolCaller, ToValue, Value, VmErrorKind, VmResult};
 use crate::Any;
 
 /// Type for a full range expression `..`.
@@ -83,6 +83,23 @@ impl RangeFull {
     pub(crate) fn contains(&self, _: Value) -> VmResult<bool> {
         VmResult::Ok(true)
     }
+
+    /// Unbounded ranges don't have a length.
+    ///
+    /// # Panics
+    ///
+    /// This always panics.
+    ///
+    /// ```rune,should_panic
+    /// (..).len()
+    /// ```
+    #[rune::function(keep)]
+    pub(crate) fn len(&self) -> VmResult<usize> {
+        VmResult::err(VmErrorKind::UnboundedRange {
+            range: "std::ops::RangeFull",
+            method: "len",
+        })
+    }
 }
 
 impl fmt::Debug for RangeFull {
@@ -3,7 +3,9 @@ use core::fmt;
 use core::ops;
 
 use crate as rune;
-use crate::runtime::{EnvProtocolCaller, FromValue, ProtocolCaller, ToValue, Value, VmResult};
+use crate::runtime::{
+    EnvProtocolCaller, FromValue, ProtocolCaller, ToValue, Value, VmErrorKind, VmResult,
+};
 use crate::Any;
 
 /// Type for an inclusive range expression `..end`.
@@ -177,6 +179,23 @@ impl RangeTo {
             Some(Ordering::Greater)
         ))
     }
+
+    /// Unbounded ranges don't have a length.
+    ///
+    /// # Panics
+    ///
+    /// This always panics.
+    ///
+    /// ```rune,should_panic
+    /// (..10).len()
+    /// ```
+    #[rune::function(keep)]
+    pub(crate) fn len(&self) -> VmResult<usize> {
+        VmResult::err(VmErrorKind::UnboundedRange {
+            range: "std::ops::RangeTo",
+            method: "len",
+        })
+    }
 }
 
 impl fmt::Debug for RangeTo {
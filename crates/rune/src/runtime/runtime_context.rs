@@ -1,11 +1,16 @@
 use core::fmt;
 
+use crate::no_std::prelude::*;
 use crate::no_std::sync::Arc;
 
 use crate::compile;
 use crate::hash;
 use crate::macros::{MacroContext, TokenStream};
-use crate::runtime::{ConstValue, Stack, VmResult};
+use crate::runtime::message_catalog::Localized;
+use crate::runtime::{
+    BorrowMode, ConstValue, EnglishCatalog, Fingerprint, Formatter, MessageCatalog, PanicHook,
+    Stack, Value, VmError, VmResult,
+};
 use crate::Hash;
 
 /// A type-reduced function handler.
@@ -20,6 +25,10 @@ pub(crate) type AttributeMacroHandler = dyn Fn(&mut MacroContext, &TokenStream,
     + Send
     + Sync;
 
+/// A (type erased) pretty printer.
+pub(crate) type PrettyPrinterHandler =
+    dyn Fn(&Value, &mut Formatter) -> VmResult<fmt::Result> + Send + Sync;
+
 /// Static run context visible to the virtual machine.
 ///
 /// This contains:
@@ -30,18 +39,44 @@ pub(crate) type AttributeMacroHandler = dyn Fn(&mut MacroContext, &TokenStream,
 pub struct RuntimeContext {
     /// Registered native function handlers.
     functions: hash::Map<Arc<FunctionHandler>>,
+    /// Argument names of native functions.
+    argument_names: hash::Map<&'static [&'static str]>,
     /// Named constant values
     constants: hash::Map<ConstValue>,
+    /// Hook called when a virtual machine panics.
+    panic_hook: Option<Arc<dyn PanicHook>>,
+    /// Catalog used to localize runtime messages.
+    message_catalog: Option<Arc<dyn MessageCatalog>>,
+    /// Registered pretty printers, keyed by the type hash of the type they
+    /// format.
+    pretty_printers: hash::Map<Arc<PrettyPrinterHandler>>,
+    /// The hashes of native types registered in the context.
+    types: hash::Set,
+    /// How calls failing because of borrows held by other executions are
+    /// handled.
+    borrow_mode: BorrowMode,
 }
 
 impl RuntimeContext {
     pub(crate) fn new(
         functions: hash::Map<Arc<FunctionHandler>>,
+        argument_names: hash::Map<&'static [&'static str]>,
         constants: hash::Map<ConstValue>,
+        panic_hook: Option<Arc<dyn PanicHook>>,
+        message_catalog: Option<Arc<dyn MessageCatalog>>,
+        pretty_printers: hash::Map<Arc<PrettyPrinterHandler>>,
+        types: hash::Set,
+        borrow_mode: BorrowMode,
     ) -> Self {
         Self {
             functions,
+            argument_names,
             constants,
+            panic_hook,
+            message_catalog,
+            pretty_printers,
+            types,
+            borrow_mode,
         }
     }
 
@@ -50,10 +85,77 @@ impl RuntimeContext {
         self.functions.get(&hash)
     }
 
+    /// Get the names of the arguments of the native function with the given
+    /// hash, which is empty if they aren't known.
+    pub(crate) fn argument_names(&self, hash: Hash) -> &'static [&'static str] {
+        self.argument_names.get(&hash).copied().unwrap_or_default()
+    }
+
     /// Read a constant value from the unit.
     pub fn constant(&self, hash: Hash) -> Option<&ConstValue> {
         self.constants.get(&hash)
     }
+
+    /// Get the hook to call when a virtual machine panics, if any.
+    pub(crate) fn panic_hook(&self) -> Option<&dyn PanicHook> {
+        self.panic_hook.as_deref()
+    }
+
+    /// Display the given error using the message catalog of the context, as
+    /// set through [Context::set_message_catalog].
+    ///
+    /// If no catalog has been set, this displays the error the same way as
+    /// its [Display][fmt::Display] implementation does.
+    ///
+    /// [Context::set_message_catalog]: crate::Context::set_message_catalog
+    pub fn localize<'a>(&'a self, error: &'a VmError) -> impl fmt::Display + 'a {
+        let catalog = self.message_catalog.as_deref().unwrap_or(&EnglishCatalog);
+        Localized::new(catalog, error)
+    }
+
+    /// Get how calls failing because of borrows held by other executions are
+    /// handled.
+    pub(crate) fn borrow_mode(&self) -> BorrowMode {
+        self.borrow_mode
+    }
+
+    /// Test if a native type with the given hash is registered in the context.
+    pub(crate) fn has_type(&self, hash: Hash) -> bool {
+        self.types.contains(&hash)
+    }
+
+    /// Get the pretty printer registered for the given type hash, if any.
+    pub(crate) fn pretty_printer(&self, hash: Hash) -> Option<&PrettyPrinterHandler> {
+        self.pretty_printers.get(&hash).map(|printer| &**printer)
+    }
+
+    /// Calculate a fingerprint of the functions, constants and types available
+    /// in the context, which is used to check that a snapshot of a virtual
+    /// machine is restored with a compatible context.
+    ///
+    /// Native functions can't be compared, so only the hashes they are
+    /// registered under are covered.
+    pub(crate) fn fingerprint(&self) -> Hash {
+        let mut fingerprint = Fingerprint::new();
+
+        let mut functions = self.functions.keys().collect::<Vec<_>>();
+        functions.sort();
+        fingerprint.add(functions);
+
+        let mut types = self.types.iter().collect::<Vec<_>>();
+        types.sort();
+        fingerprint.add(types);
+
+        let mut constants = self.constants.iter().collect::<Vec<_>>();
+        constants.sort_by_key(|(hash, _)| **hash);
+
+        for (hash, value) in constants {
+            fingerprint.add(hash);
+            fingerprint.add_const(value);
+        }
+
+        fingerprint.finish()
+    }
 }
 
 impl fmt::Debug for RuntimeContext {
@@ -16,7 +16,8 @@ use alloc::sync::Arc;
 use crate::no_std::prelude::*;
 
 use crate::runtime::{
-    Access, AccessError, AccessKind, AnyObj, AnyObjError, BorrowMut, BorrowRef, RawAccessGuard,
+    Access, AccessError, AccessGuard, AccessKind, AnyObj, AnyObjError, BorrowMut, BorrowRef,
+    RawAccessGuard,
 };
 use crate::Any;
 
@@ -263,7 +264,7 @@ impl<T> Shared<T> {
         //
         // Appropriate access is checked when constructing the guards.
         unsafe {
-            let guard = self.inner.as_ref().access.exclusive(kind)?.into_raw();
+            let guard = held_raw(self.inner.as_ref().access.exclusive(kind)?, kind);
 
             // NB: we need to prevent the Drop impl for Shared from being called,
             // since we are deconstructing its internals.
@@ -361,6 +362,30 @@ impl<T: ?Sized> Shared<T> {
             Ok(BorrowMut::new(&mut *inner.data.get(), &inner.access))
         }
     }
+
+    /// Get the number of strong references to the shared value.
+    #[cfg(feature = "gc")]
+    pub(crate) fn strong_count(this: &Self) -> usize {
+        // Safety: We know that interior value is alive since this container is
+        // alive.
+        unsafe { this.inner.as_ref().count.get() }
+    }
+
+    /// Get the address of the shared value, which uniquely identifies it for
+    /// as long as it's alive.
+    #[cfg(feature = "gc")]
+    pub(crate) fn addr(this: &Self) -> usize {
+        this.inner.as_ptr() as *const () as usize
+    }
+
+    /// Test if the shared value has been taken, in which case it can never be
+    /// accessed again.
+    #[cfg(feature = "gc")]
+    pub(crate) fn is_taken(this: &Self) -> bool {
+        // Safety: Since we have a reference to this shared, we know that the
+        // inner is available.
+        unsafe { this.inner.as_ref().access.is_taken() }
+    }
 }
 
 impl Shared<AnyObj> {
@@ -664,7 +689,7 @@ impl Shared<AnyObj> {
                 }
             };
 
-            let guard = guard.into_raw();
+            let guard = held_raw(guard, kind);
             // NB: we need to prevent the Drop impl for Shared from being called,
             // since we are deconstructing its internals.
             let this = ManuallyDrop::new(self);
@@ -747,6 +772,21 @@ where
     }
 }
 
+/// Convert an exclusive guard into a raw guard.
+///
+/// Owned guards can be held across await points, so they are recorded as held
+/// by the execution which acquired them.
+///
+/// # Safety
+///
+/// The access must outlive the returned guard.
+unsafe fn held_raw(guard: AccessGuard<'_>, kind: AccessKind) -> RawAccessGuard {
+    match kind {
+        AccessKind::Owned => guard.into_held_raw(),
+        AccessKind::Any => guard.into_raw(),
+    }
+}
+
 /// The boxed internals of [Shared].
 #[repr(C)]
 struct SharedBox<T: ?Sized> {
@@ -0,0 +1,126 @@
+use core::cell::{OnceCell, RefCell};
+
+use crate::no_std::prelude::*;
+use crate::no_std::rc::Rc;
+
+use crate::runtime::Function;
+
+/// Handlers connected to signals through the `std::signal` module, which are
+/// owned by a virtual machine.
+///
+/// Virtual machines created to run async functions, generators and streams
+/// share the signals of the virtual machine calling them.
+pub(crate) struct Signals {
+    registry: OnceCell<Rc<RefCell<Registry>>>,
+}
+
+#[derive(Default)]
+struct Registry {
+    /// The identifier of the last connected handler.
+    last_id: u64,
+    /// Handlers ordered by descending priority, and in the order they were
+    /// connected for handlers with the same priority.
+    handlers: Vec<Handler>,
+}
+
+struct Handler {
+    id: u64,
+    signal: Box<str>,
+    priority: i64,
+    function: Function,
+}
+
+impl Signals {
+    /// Construct signals without any connected handlers.
+    pub(crate) const fn new() -> Self {
+        Self {
+            registry: OnceCell::new(),
+        }
+    }
+
+    fn registry(&self) -> &Rc<RefCell<Registry>> {
+        self.registry.get_or_init(Rc::default)
+    }
+
+    /// Construct signals which share their handlers with these ones.
+    pub(crate) fn share(&self) -> Self {
+        Self {
+            registry: OnceCell::from(self.registry().clone()),
+        }
+    }
+
+    /// Test if the signals have been used, or are shared.
+    pub(crate) fn is_used(&self) -> bool {
+        self.registry.get().is_some()
+    }
+
+    /// Connect a handler to the given signal, returning its identifier.
+    pub(crate) fn connect(&self, signal: &str, priority: i64, function: Function) -> u64 {
+        let mut registry = self.registry().borrow_mut();
+        registry.last_id += 1;
+        let id = registry.last_id;
+
+        let at = registry
+            .handlers
+            .iter()
+            .position(|h| h.priority < priority)
+            .unwrap_or(registry.handlers.len());
+
+        registry.handlers.insert(
+            at,
+            Handler {
+                id,
+                signal: signal.into(),
+                priority,
+                function,
+            },
+        );
+
+        id
+    }
+
+    /// Disconnect the handler with the given identifier, returning `true` if
+    /// it was connected.
+    pub(crate) fn disconnect(&self, id: u64) -> bool {
+        let Some(registry) = self.registry.get() else {
+            return false;
+        };
+
+        let mut registry = registry.borrow_mut();
+        let len = registry.handlers.len();
+        registry.handlers.retain(|h| h.id != id);
+        registry.handlers.len() != len
+    }
+
+    /// Test if the handler with the given identifier is connected.
+    pub(crate) fn is_connected(&self, id: u64) -> bool {
+        let Some(registry) = self.registry.get() else {
+            return false;
+        };
+
+        registry.borrow().handlers.iter().any(|h| h.id == id)
+    }
+
+    /// Get the handlers connected to the given signal, in the order they
+    /// should be called.
+    pub(crate) fn handlers(&self, signal: &str) -> Vec<Function> {
+        let Some(registry) = self.registry.get() else {
+            return Vec::new();
+        };
+
+        registry
+            .borrow()
+            .handlers
+            .iter()
+            .filter(|h| *h.signal == *signal)
+            .map(|h| h.function.clone())
+            .collect()
+    }
+
+    /// Disconnect every handler.
+    pub(crate) fn clear(&self) {
+        if let Some(registry) = self.registry.get() {
+            registry.borrow_mut().handlers.clear();
+        }
+    }
+}
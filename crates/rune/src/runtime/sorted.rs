@@ -0,0 +1,25 @@
+//! Serialization of hash maps in a deterministic order.
+//!
+//! The iteration order of hash maps depends on how they were constructed and
+//! on the state of their hasher, so maps which are part of a serialized
+//! [Unit][crate::Unit] are serialized with their entries sorted by key. This
+//! doesn't affect deserialization, since the same map representation is used.
+
+use crate::no_std::prelude::*;
+
+use serde::{Serialize, Serializer};
+
+/// Serialize a map with its entries sorted by key.
+///
+/// Use with `#[serde(serialize_with = "crate::runtime::sorted::serialize_map")]`.
+pub(crate) fn serialize_map<'a, M, K, V, S>(map: &'a M, serializer: S) -> Result<S::Ok, S::Error>
+where
+    &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+    K: 'a + Ord + Serialize,
+    V: 'a + Serialize,
+    S: Serializer,
+{
+    let mut entries = map.into_iter().collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    serializer.collect_map(entries)
+}
@@ -73,6 +73,14 @@ impl Stack {
         }
     }
 
+    /// Construct a stack from its raw parts.
+    pub(crate) fn from_parts(stack: Vec<Value>, stack_bottom: usize) -> Self {
+        Self {
+            stack,
+            stack_bottom,
+        }
+    }
+
     /// Check if the stack is empty.
     ///
     /// This ignores [stack_bottom] and will just check if the full stack is
@@ -386,6 +394,23 @@ impl Stack {
         self.stack_bottom = stack_bottom;
         Ok(())
     }
+
+    /// Pop the current stack frame, except for the `count` values on top of it
+    /// which are moved into the frame being restored.
+    pub(crate) fn pop_stack_top_keep(
+        &mut self,
+        stack_bottom: usize,
+        count: usize,
+    ) -> Result<(), StackError> {
+        let start = match self.stack.len().checked_sub(count) {
+            Some(start) if start >= self.stack_bottom => start,
+            _ => return Err(StackError),
+        };
+
+        self.stack.drain(self.stack_bottom..start);
+        self.stack_bottom = stack_bottom;
+        Ok(())
+    }
 }
 
 impl iter::FromIterator<Value> for Stack {
@@ -286,7 +286,7 @@ macro_rules! impl_tuple {
     };
 }
 
-repeat_macro!(impl_tuple);
+repeat_macro_extended!(impl_tuple);
 
 impl FromValue for Mut<Tuple> {
     fn from_value(value: Value) -> VmResult<Self> {
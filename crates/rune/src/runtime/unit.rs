@@ -5,7 +5,15 @@
 
 #[cfg(feature = "byte-code")]
 mod byte_code;
+#[cfg(feature = "jit")]
+mod jit;
+#[cfg(feature = "lazy-unit")]
+mod lazy;
+mod patch;
+#[cfg(feature = "serialize")]
+mod serialize;
 mod storage;
+mod verify;
 
 use core::fmt;
 
@@ -15,9 +23,10 @@ use crate::no_std::sync::Arc;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use crate::hash;
+use crate::hash::{self, ToTypeHash};
 use crate::runtime::{
-    Call, ConstValue, DebugInfo, Inst, Rtti, StaticString, VariantRtti, VmError, VmErrorKind,
+    Call, ConstValue, DebugInfo, Fingerprint, Inst, Interner, KeyStats, Object, ObjectKey, Rtti,
+    Shape, StaticString, Value, VariantRtti, VmError, VmErrorKind,
 };
 use crate::Hash;
 
@@ -28,12 +37,26 @@ pub use self::storage::{
 #[cfg(feature = "byte-code")]
 pub use self::byte_code::ByteCodeUnit;
 
+#[cfg(feature = "lazy-unit")]
+pub use self::lazy::LazyUnit;
+
+#[cfg(feature = "jit")]
+pub use self::jit::JitError;
+
+pub use self::patch::PatchError;
+#[cfg(feature = "serialize")]
+pub use self::serialize::{UnitFormatError, UNIT_FORMAT_VERSION, UNIT_MAGIC};
+pub use self::verify::VerifyError;
+
 /// Default storage implementation to use.
-#[cfg(not(rune_byte_code))]
+#[cfg(not(any(rune_byte_code, rune_lazy_unit)))]
 pub type DefaultStorage = ArrayUnit;
 /// Default storage implementation to use.
 #[cfg(rune_byte_code)]
 pub type DefaultStorage = ByteCodeUnit;
+/// Default storage implementation to use.
+#[cfg(all(rune_lazy_unit, not(rune_byte_code)))]
+pub type DefaultStorage = LazyUnit;
 
 /// Instructions and debug info from a single source file.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -44,15 +67,20 @@ pub struct Unit<S = DefaultStorage> {
     logic: Logic<S>,
     /// Debug info if available for unit.
     debug: Option<Box<DebugInfo>>,
+    /// Native code compiled through [Unit::jit].
+    #[cfg(feature = "jit")]
+    #[serde(skip)]
+    native: Option<Arc<jit::NativeCode>>,
 }
 
 /// Instructions from a single source file.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename = "Unit")]
 pub struct Logic<S = DefaultStorage> {
     /// Storage for the unit.
     storage: S,
     /// Where functions are located in the collection of instructions.
+    #[serde(serialize_with = "crate::runtime::sorted::serialize_map")]
     functions: hash::Map<UnitFn>,
     /// A static string.
     static_strings: Vec<Arc<StaticString>>,
@@ -64,13 +92,23 @@ pub struct Logic<S = DefaultStorage> {
     /// to send the collection of keys to the virtual machine.
     ///
     /// All keys are sorted with the default string sort.
-    static_object_keys: Vec<Box<[String]>>,
+    static_object_keys: StaticObjectKeys,
+    /// Static data, like the ones loaded through `include_data!`.
+    ///
+    /// A fresh value is constructed from the data every time it is loaded.
+    static_data: Vec<ConstValue>,
     /// Runtime information for types.
+    #[serde(serialize_with = "crate::runtime::sorted::serialize_map")]
     rtti: hash::Map<Arc<Rtti>>,
     /// Runtime information for variants.
+    #[serde(serialize_with = "crate::runtime::sorted::serialize_map")]
     variant_rtti: hash::Map<Arc<VariantRtti>>,
     /// Named constants
+    #[serde(serialize_with = "crate::runtime::sorted::serialize_map")]
     constants: hash::Map<ConstValue>,
+    /// Fingerprint of the logic above, calculated once when the unit is
+    /// constructed.
+    fingerprint: Hash,
 }
 
 impl<S> Unit<S> {
@@ -79,6 +117,8 @@ impl<S> Unit<S> {
         Self {
             logic: data,
             debug: debug.map(Box::new),
+            #[cfg(feature = "jit")]
+            native: None,
         }
     }
 
@@ -90,23 +130,35 @@ impl<S> Unit<S> {
         static_strings: Vec<Arc<StaticString>>,
         static_bytes: Vec<Vec<u8>>,
         static_object_keys: Vec<Box<[String]>>,
+        static_data: Vec<ConstValue>,
         rtti: hash::Map<Arc<Rtti>>,
         variant_rtti: hash::Map<Arc<VariantRtti>>,
         debug: Option<Box<DebugInfo>>,
         constants: hash::Map<ConstValue>,
-    ) -> Self {
+    ) -> Self
+    where
+        S: UnitStorage,
+    {
+        let mut logic = Logic {
+            storage,
+            functions,
+            static_strings,
+            static_bytes,
+            static_object_keys: StaticObjectKeys::from(static_object_keys),
+            static_data,
+            rtti,
+            variant_rtti,
+            constants,
+            fingerprint: Hash::EMPTY,
+        };
+
+        logic.fingerprint = logic.calculate_fingerprint();
+
         Self {
-            logic: Logic {
-                storage,
-                functions,
-                static_strings,
-                static_bytes,
-                static_object_keys,
-                rtti,
-                variant_rtti,
-                constants,
-            },
+            logic,
             debug,
+            #[cfg(feature = "jit")]
+            native: None,
         }
     }
 
@@ -121,8 +173,8 @@ impl<S> Unit<S> {
         Some(&**debug)
     }
 
-    /// Get raw underlying instructions storage.
-    pub(crate) fn instructions(&self) -> &S {
+    /// Access the storage of the instructions in the unit.
+    pub fn storage(&self) -> &S {
         &self.logic.storage
     }
 
@@ -140,14 +192,16 @@ impl<S> Unit<S> {
 
     /// Iterate over all static object keys in the unit.
     #[cfg(feature = "cli")]
-    pub(crate) fn iter_static_object_keys(&self) -> impl Iterator<Item = (usize, &[String])> + '_ {
+    pub(crate) fn iter_static_object_keys(
+        &self,
+    ) -> impl Iterator<Item = (usize, &[ObjectKey])> + '_ {
         use core::iter;
 
-        let mut it = self.logic.static_object_keys.iter().enumerate();
+        let mut it = self.logic.static_object_keys.slots.iter().enumerate();
 
         iter::from_fn(move || {
             let (n, s) = it.next()?;
-            Some((n, &s[..]))
+            Some((n, &s.keys[..]))
         })
     }
 
@@ -157,6 +211,14 @@ impl<S> Unit<S> {
         self.logic.functions.iter().map(|(h, f)| (*h, f))
     }
 
+    /// Iterate over the hashes of all functions and types defined in the unit.
+    pub(crate) fn iter_item_hashes(&self) -> impl Iterator<Item = Hash> + '_ {
+        let functions = self.logic.functions.keys();
+        let rtti = self.logic.rtti.keys();
+        let variant_rtti = self.logic.variant_rtti.keys();
+        functions.chain(rtti).chain(variant_rtti).copied()
+    }
+
     /// Lookup the static string by slot, if it exists.
     pub(crate) fn lookup_string(&self, slot: usize) -> Result<&Arc<StaticString>, VmError> {
         Ok(self
@@ -177,11 +239,80 @@ impl<S> Unit<S> {
     }
 
     /// Lookup the static object keys by slot, if it exists.
-    pub(crate) fn lookup_object_keys(&self, slot: usize) -> Option<&[String]> {
+    pub(crate) fn lookup_object_keys(&self, slot: usize) -> Option<&[ObjectKey]> {
         self.logic
             .static_object_keys
+            .slots
+            .get(slot)
+            .map(|keys| &keys.keys[..])
+    }
+
+    /// Construct an object from the static object keys in the given slot,
+    /// from values in the same order as the keys.
+    ///
+    /// This uses the cached shape of the keys, so that keys don't have to be
+    /// inserted one by one.
+    pub(crate) fn new_object<I>(&self, slot: usize, values: I) -> Option<Object>
+    where
+        I: IntoIterator<Item = Value>,
+    {
+        let keys = self.logic.static_object_keys.slots.get(slot)?;
+
+        let Some(shape) = &keys.shape else {
+            return Some(keys.keys.iter().cloned().zip(values).collect());
+        };
+
+        Some(Object::with_shape(shape, values))
+    }
+
+    /// Intern the given object key, sharing the storage of the key if it's
+    /// part of the static object keys of the unit.
+    pub(crate) fn intern_key(&self, interner: &mut Interner, key: &str) -> ObjectKey {
+        if let Some(key) = self.logic.static_object_keys.interner.lookup(key) {
+            interner.record(key);
+            return key.clone();
+        }
+
+        interner.intern(key)
+    }
+
+    /// Statistics of the static object keys in the unit.
+    ///
+    /// Every key is only stored once, no matter how many collections of
+    /// static object keys it's part of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             let a = #{ name: 1, age: 2 };
+    ///             let b = #{ name: 3 };
+    ///             match a { #{ name, .. } => name }
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    ///
+    /// let stats = unit.object_key_stats();
+    /// assert_eq!(stats.unique, 2);
+    /// assert!(stats.keys > stats.unique);
+    /// assert!(stats.saved_bytes() > 0);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn object_key_stats(&self) -> &KeyStats {
+        self.logic.static_object_keys.interner.stats()
+    }
+
+    /// Lookup the static data by slot, if it exists.
+    pub(crate) fn lookup_static_data(&self, slot: usize) -> Result<&ConstValue, VmError> {
+        Ok(self
+            .logic
+            .static_data
             .get(slot)
-            .map(|keys| &keys[..])
+            .ok_or(VmErrorKind::MissingStaticData { slot })?)
     }
 
     /// Lookup run-time information for the given type hash.
@@ -203,12 +334,222 @@ impl<S> Unit<S> {
     pub(crate) fn constant(&self, hash: Hash) -> Option<&ConstValue> {
         self.logic.constants.get(&hash)
     }
+
+    /// Test if the function with the given name runs as native code, after
+    /// the unit has been compiled through [Unit::jit].
+    #[cfg(feature = "jit")]
+    pub fn is_native<N>(&self, name: N) -> bool
+    where
+        N: ToTypeHash,
+    {
+        match self.function(name.to_type_hash()) {
+            Some(UnitFn::Offset { offset, .. }) => self.native_function(offset).is_some(),
+            _ => false,
+        }
+    }
+
+    /// Lookup the native code of the function at the given offset.
+    #[cfg(feature = "jit")]
+    pub(crate) fn native_function(&self, offset: usize) -> Option<&jit::NativeFunction> {
+        self.native.as_ref()?.function(offset)
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl<S> Unit<S>
+where
+    S: Serialize + DeserializeOwned,
+{
+    /// Serialize the unit into the given writer.
+    ///
+    /// The unit is preceded by a header consisting of [UNIT_MAGIC], the
+    /// [UNIT_FORMAT_VERSION] and a hash identifying the version of the
+    /// compiler and the kind of storage used, which is checked by
+    /// [Unit::deserialize_from]. Serializing the same unit always produces
+    /// the same bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Unit;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() { 42 }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    ///
+    /// let mut bytes = Vec::new();
+    /// unit.serialize_into(&mut bytes)?;
+    ///
+    /// let unit: Unit = Unit::deserialize_from(&bytes[..])?;
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn serialize_into<W>(&self, writer: W) -> Result<(), UnitFormatError>
+    where
+        W: crate::no_std::io::Write,
+    {
+        serialize::serialize_into(self, writer)
+    }
+
+    /// Deserialize a unit which was serialized with [Unit::serialize_into].
+    ///
+    /// This fails with a [UnitFormatError] instead of deserializing the unit
+    /// if it was serialized in an unsupported format, by a different version
+    /// of the compiler or with a different kind of storage.
+    pub fn deserialize_from<R>(reader: R) -> Result<Self, UnitFormatError>
+    where
+        R: crate::no_std::io::Read,
+    {
+        serialize::deserialize_from(reader)
+    }
 }
 
 impl<S> Unit<S>
 where
     S: UnitStorage,
 {
+    /// Verify the bytecode of the unit.
+    ///
+    /// This checks that all jumps and function offsets land on instruction
+    /// boundaries, that all static slots referenced by instructions exist, and
+    /// that the stack effects of every function are balanced so that no
+    /// instruction accesses values outside of its stack frame.
+    ///
+    /// Units produced by the compiler always pass verification, so this is
+    /// intended for units which have been deserialized or constructed by other
+    /// means before they are executed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main(n) {
+    ///             let f = |a| a + n;
+    ///             f(1)
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// unit.verify()?;
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn verify(&self) -> Result<(), VerifyError> {
+        verify::verify(self)
+    }
+
+    /// Replace the body of the function with the given `name` with the one
+    /// compiled into `patch`.
+    ///
+    /// The function in the patch must take the same number of arguments and
+    /// use the same calling convention as the function being replaced. Other
+    /// functions in the unit are preserved, and calls to the patched function
+    /// from them are redirected to the new body. Closures and async blocks
+    /// defined in the new body are patched in along with it, while other
+    /// functions it references are resolved against this unit and must exist
+    /// in it with the same signature.
+    ///
+    /// The unit is left untouched if patching fails.
+    ///
+    /// Note that private functions which aren't used are not compiled, so the
+    /// function in the patch usually has to be declared `pub`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::new(context.runtime());
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         fn add(a, b) { a + b }
+    ///         pub fn main() { add(1, 2) }
+    ///     }
+    /// };
+    ///
+    /// let mut unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn add(a, b) { a * b }
+    ///     }
+    /// };
+    ///
+    /// let patch = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// unit.patch_function(["add"], &patch)?;
+    ///
+    /// let mut vm = Vm::new(runtime, Arc::new(unit));
+    /// let output: i64 = rune::from_value(vm.call(["main"], ())?)?;
+    /// assert_eq!(output, 2);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn patch_function<N, P>(&mut self, name: N, patch: &Unit<P>) -> Result<(), PatchError>
+    where
+        S: UnitEncoder,
+        N: ToTypeHash,
+        P: UnitStorage,
+    {
+        *self = patch::patch(self, name.to_type_hash(), patch)?;
+        Ok(())
+    }
+
+    /// Compile the functions of the unit to native code.
+    ///
+    /// Only functions which operate on integers and booleans are compiled,
+    /// all other functions continue to be interpreted. Native code is used
+    /// when a compiled function is called from the virtual machine with
+    /// integer arguments, and the call falls back to being interpreted from
+    /// the start if it encounters something native code doesn't handle, like
+    /// an arithmetic overflow or an exhausted [budget]. This is always
+    /// possible since compiled functions have no side effects, so the result
+    /// is the same regardless of how a function is executed.
+    ///
+    /// Native code is discarded if the unit is patched through
+    /// [Unit::patch_function], and is not serialized along with the unit.
+    ///
+    /// [budget]: crate::runtime::budget
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::new(context.runtime());
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         fn fib(n) {
+    ///             if n <= 1 { n } else { fib(n - 1) + fib(n - 2) }
+    ///         }
+    ///
+    ///         pub fn main(n) { fib(n) }
+    ///     }
+    /// };
+    ///
+    /// let mut unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// unit.jit()?;
+    /// assert!(unit.is_native(["fib"]));
+    ///
+    /// let mut vm = Vm::new(runtime, Arc::new(unit));
+    /// let output: i64 = rune::from_value(vm.call(["main"], (20,))?)?;
+    /// assert_eq!(output, 6765);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    #[cfg(feature = "jit")]
+    pub fn jit(&mut self) -> Result<(), JitError> {
+        self.native = Some(Arc::new(jit::compile(self)?));
+        Ok(())
+    }
+
     #[inline]
     pub(crate) fn translate(&self, jump: usize) -> Result<usize, BadJump> {
         self.logic.storage.translate(jump)
@@ -227,10 +568,200 @@ where
     pub(crate) fn iter_instructions(&self) -> impl Iterator<Item = (usize, Inst)> + '_ {
         self.logic.storage.iter()
     }
+
+    /// Get the fingerprint of the unit, which is used to check that a
+    /// snapshot of a virtual machine is restored with the same unit that it
+    /// was taken from.
+    ///
+    /// The fingerprint is calculated once when the unit is constructed and is
+    /// stored along with it, so this doesn't need to inspect any instructions.
+    #[inline]
+    pub(crate) fn fingerprint(&self) -> Hash {
+        self.logic.fingerprint
+    }
+}
+
+impl<S> Default for Logic<S>
+where
+    S: Default,
+{
+    fn default() -> Self {
+        Self {
+            storage: S::default(),
+            functions: hash::Map::default(),
+            static_strings: Vec::new(),
+            static_bytes: Vec::new(),
+            static_object_keys: StaticObjectKeys::default(),
+            static_data: Vec::new(),
+            rtti: hash::Map::default(),
+            variant_rtti: hash::Map::default(),
+            constants: hash::Map::default(),
+            // NB: an empty unit has no logic to fingerprint.
+            fingerprint: Hash::EMPTY,
+        }
+    }
+}
+
+impl<S> Logic<S>
+where
+    S: UnitStorage,
+{
+    /// Calculate the fingerprint of the unit logic.
+    ///
+    /// This covers every instruction, function, constant and piece of static
+    /// data in the unit, but not its debug information.
+    fn calculate_fingerprint(&self) -> Hash {
+        let mut fingerprint = Fingerprint::new();
+
+        for (ip, inst) in self.storage.iter() {
+            fingerprint.add(ip);
+            fingerprint.add_debug(&inst);
+        }
+
+        let mut functions = self.functions.iter().collect::<Vec<_>>();
+        functions.sort_by_key(|(hash, _)| **hash);
+
+        for (hash, f) in functions {
+            fingerprint.add(hash);
+            fingerprint.add_debug(f);
+        }
+
+        for string in &self.static_strings {
+            fingerprint.add(string.as_str());
+        }
+
+        for bytes in &self.static_bytes {
+            fingerprint.add(bytes);
+        }
+
+        for slot in &self.static_object_keys.slots {
+            fingerprint.add(slot.keys.len());
+
+            for key in slot.keys.iter() {
+                fingerprint.add(key.as_str());
+            }
+        }
+
+        for value in &self.static_data {
+            fingerprint.add_const(value);
+        }
+
+        let mut rtti = self.rtti.keys().collect::<Vec<_>>();
+        rtti.sort();
+        fingerprint.add(rtti);
+
+        let mut variant_rtti = self.variant_rtti.keys().collect::<Vec<_>>();
+        variant_rtti.sort();
+        fingerprint.add(variant_rtti);
+
+        let mut constants = self.constants.iter().collect::<Vec<_>>();
+        constants.sort_by_key(|(hash, _)| **hash);
+
+        for (hash, value) in constants {
+            fingerprint.add(hash);
+            fingerprint.add_const(value);
+        }
+
+        fingerprint.finish()
+    }
+}
+
+/// The static object keys of a unit, where every key is interned so that
+/// its storage is shared between slots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "Vec<Box<[String]>>", into = "Vec<Box<[String]>>")]
+struct StaticObjectKeys {
+    /// Collections of keys by slot.
+    slots: Vec<ObjectKeys>,
+    /// Interner holding onto every key.
+    interner: Interner,
+}
+
+impl StaticObjectKeys {
+    /// Construct an empty collection of static object keys.
+    fn new() -> Self {
+        let mut interner = Interner::new();
+        interner.set_capacity(usize::MAX);
+
+        Self {
+            slots: Vec::new(),
+            interner,
+        }
+    }
+
+    /// Insert a collection of keys from another unit unless an equal
+    /// collection exists, returning its slot.
+    fn insert(&mut self, keys: &ObjectKeys) -> usize {
+        if let Some(n) = self.slots.iter().position(|e| e.keys == keys.keys) {
+            return n;
+        }
+
+        let keys = keys
+            .keys
+            .iter()
+            .map(|key| self.interner.intern_key(key))
+            .collect::<Box<[_]>>();
+
+        let shape = Shape::new(&keys).map(Arc::new);
+        self.slots.push(ObjectKeys { keys, shape });
+        self.slots.len() - 1
+    }
+}
+
+impl Default for StaticObjectKeys {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Vec<Box<[String]>>> for StaticObjectKeys {
+    fn from(slots: Vec<Box<[String]>>) -> Self {
+        let mut this = Self::new();
+
+        for keys in slots {
+            let keys = keys
+                .into_vec()
+                .into_iter()
+                .map(|key| this.interner.intern_string(key))
+                .collect::<Box<[_]>>();
+
+            let shape = Shape::new(&keys).map(Arc::new);
+            this.slots.push(ObjectKeys { keys, shape });
+        }
+
+        this
+    }
+}
+
+impl From<StaticObjectKeys> for Vec<Box<[String]>> {
+    #[inline]
+    fn from(keys: StaticObjectKeys) -> Self {
+        keys.slots
+            .into_iter()
+            .map(|keys| {
+                keys.keys
+                    .iter()
+                    .map(|key| key.as_str().to_owned())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// A collection of static object keys, and the cached shape of objects
+/// constructed from them.
+#[derive(Debug, Clone)]
+struct ObjectKeys {
+    /// The keys.
+    keys: Box<[ObjectKey]>,
+    /// The shape of objects constructed from the keys, unless the keys
+    /// contain duplicates.
+    shape: Option<Arc<Shape>>,
 }
 
 /// The kind and necessary information on registered functions.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub(crate) enum UnitFn {
     /// Instruction offset of a function inside of the unit.
@@ -68,6 +68,9 @@ impl UnitEncoder for ByteCodeUnit {
     fn label_jump(&self, base: usize, _: usize, jump: usize) -> usize {
         base.wrapping_add(jump)
     }
+
+    #[inline]
+    fn begin_function(&mut self) {}
 }
 
 impl UnitStorage for ByteCodeUnit {
@@ -0,0 +1,1156 @@
+use core::fmt;
+
+use crate::no_std::collections::{BTreeMap, HashMap, HashSet};
+use crate::no_std::error;
+use crate::no_std::prelude::*;
+
+use cranelift_codegen::ir::condcodes::IntCC;
+use cranelift_codegen::ir::{
+    types, AbiParam, Block, FuncRef, InstBuilder, MemFlags, StackSlotData, StackSlotKind, Type,
+    Value as Reg,
+};
+use cranelift_codegen::settings::{self, Configurable};
+use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext, Variable};
+use cranelift_jit::{JITBuilder, JITModule};
+use cranelift_module::{FuncId, Linkage, Module};
+
+use crate::runtime::budget;
+use crate::runtime::unit::{Unit, UnitFn, UnitStorage};
+use crate::runtime::{Call, Inst, InstAddress, InstAssignOp, InstOp, InstTarget, InstValue, Value};
+use crate::Hash;
+
+/// The maximum number of arguments a function can take to be compiled.
+const MAX_ARGS: usize = 16;
+
+/// The maximum depth of nested calls in native code. Calls which are nested
+/// deeper than this are interpreted, since the stack of the virtual machine
+/// can grow beyond what's available to native code.
+const MAX_DEPTH: i64 = 256;
+
+/// Status returned from native code when the call completed.
+const STATUS_OK: i64 = 0;
+
+/// Status returned from native code when the call has to be interpreted.
+const STATUS_DEOPT: i64 = 1;
+
+/// The name of the helper used to take from the execution budget.
+const BUDGET_TAKE: &str = "rune_jit_budget_take";
+
+/// Error raised when compiling a [Unit] to native code through [Unit::jit].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JitError {
+    /// Native code can't be generated for the host.
+    UnsupportedHost {
+        /// The error raised by the code generator.
+        message: String,
+    },
+    /// Code generation failed for the function with the given hash.
+    Codegen {
+        /// The hash of the function.
+        hash: Hash,
+        /// The error raised by the code generator.
+        message: String,
+    },
+    /// The generated code couldn't be made executable.
+    Finalize {
+        /// The error raised by the code generator.
+        message: String,
+    },
+}
+
+impl fmt::Display for JitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JitError::UnsupportedHost { message } => {
+                write!(f, "Native code is not supported on this host: {message}")
+            }
+            JitError::Codegen { hash, message } => {
+                write!(f, "Failed to generate native code for {hash}: {message}")
+            }
+            JitError::Finalize { message } => {
+                write!(f, "Failed to finalize native code: {message}")
+            }
+        }
+    }
+}
+
+impl error::Error for JitError {}
+
+/// The signature of a function in native code.
+///
+/// Arguments are read from the array pointed to by the first argument, and
+/// the return value is written to the second one. The third argument is the
+/// depth of nested native calls. The status returned is either
+/// [STATUS_OK] or [STATUS_DEOPT].
+type NativeFn = unsafe extern "C" fn(*const i64, *mut i64, i64) -> u8;
+
+/// Native code for the functions in a unit.
+pub(crate) struct NativeCode {
+    /// The module owning the memory of the compiled functions.
+    module: Option<JITModule>,
+    /// Compiled functions by their instruction offset.
+    functions: HashMap<usize, NativeFunction>,
+}
+
+// SAFETY: The module is never modified once its definitions are finalized, so
+// the code it owns can be executed from any thread.
+unsafe impl Send for NativeCode {}
+unsafe impl Sync for NativeCode {}
+
+impl NativeCode {
+    /// Lookup the native function at the given instruction offset.
+    pub(crate) fn function(&self, offset: usize) -> Option<&NativeFunction> {
+        self.functions.get(&offset)
+    }
+}
+
+impl fmt::Debug for NativeCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NativeCode")
+            .field("functions", &self.functions.len())
+            .finish()
+    }
+}
+
+impl Drop for NativeCode {
+    fn drop(&mut self) {
+        self.functions.clear();
+
+        if let Some(module) = self.module.take() {
+            // SAFETY: The function pointers into the module have been cleared
+            // above, and the module is only dropped once the unit owning it
+            // is no longer being executed.
+            unsafe {
+                module.free_memory();
+            }
+        }
+    }
+}
+
+/// A function compiled to native code.
+pub(crate) struct NativeFunction {
+    function: NativeFn,
+    args: usize,
+    ret: Ty,
+}
+
+impl NativeFunction {
+    /// Call the function with the given arguments.
+    ///
+    /// Returns `None` if the arguments are not supported by the native code,
+    /// or if the call deoptimized. In that case the call has to be
+    /// interpreted from the start, which is always possible since native
+    /// functions don't have side effects.
+    pub(crate) fn call(&self, args: &[Value]) -> Option<Value> {
+        if args.len() != self.args {
+            return None;
+        }
+
+        let mut input = [0i64; MAX_ARGS];
+
+        for (slot, value) in input.iter_mut().zip(args) {
+            let Value::Integer(value) = value else {
+                return None;
+            };
+
+            *slot = *value;
+        }
+
+        let mut output = 0i64;
+
+        // SAFETY: The native code only reads the declared number of arguments
+        // and writes a single value to the output.
+        let status = unsafe { (self.function)(input.as_ptr(), &mut output, 0) };
+
+        if i64::from(status) != STATUS_OK {
+            return None;
+        }
+
+        Some(match self.ret {
+            Ty::Bool => Value::Bool(output != 0),
+            Ty::Integer | Ty::Unknown => Value::Integer(output),
+        })
+    }
+}
+
+/// Helper called from native code to take from the execution budget.
+extern "C" fn budget_take() -> u8 {
+    u8::from(budget::take())
+}
+
+/// Compile the functions in the given unit which are supported to native
+/// code.
+pub(super) fn compile<S>(unit: &Unit<S>) -> Result<NativeCode, JitError>
+where
+    S: UnitStorage,
+{
+    let program = Program::new(unit);
+    let (signatures, analyses) = program.infer();
+
+    let mut flags = settings::builder();
+
+    for (name, value) in [
+        ("opt_level", "speed"),
+        ("use_colocated_libcalls", "false"),
+        ("is_pic", "false"),
+    ] {
+        flags
+            .set(name, value)
+            .map_err(|error| JitError::UnsupportedHost {
+                message: error.to_string(),
+            })?;
+    }
+
+    let isa = cranelift_native::builder()
+        .map_err(|message| JitError::UnsupportedHost {
+            message: message.into(),
+        })?
+        .finish(settings::Flags::new(flags))
+        .map_err(|error| JitError::UnsupportedHost {
+            message: error.to_string(),
+        })?;
+
+    let mut builder = JITBuilder::with_isa(isa, cranelift_module::default_libcall_names());
+    builder.symbol(BUDGET_TAKE, budget_take as *const u8);
+    let mut module = JITModule::new(builder);
+
+    let finalize = |error: cranelift_module::ModuleError| JitError::Finalize {
+        message: error.to_string(),
+    };
+
+    let pointer = module.target_config().pointer_type();
+
+    let mut native = module.make_signature();
+    native.params.push(AbiParam::new(pointer));
+    native.params.push(AbiParam::new(pointer));
+    native.params.push(AbiParam::new(types::I64));
+    native.returns.push(AbiParam::new(types::I8));
+
+    let mut take = module.make_signature();
+    take.returns.push(AbiParam::new(types::I8));
+
+    let budget = module
+        .declare_function(BUDGET_TAKE, Linkage::Import, &take)
+        .map_err(finalize)?;
+
+    let mut ids = BTreeMap::new();
+
+    for (&offset, signature) in &signatures {
+        let id = module
+            .declare_function(&format!("rune_{offset}"), Linkage::Local, &native)
+            .map_err(|error| JitError::Codegen {
+                hash: signature.hash,
+                message: error.to_string(),
+            })?;
+
+        ids.insert(offset, id);
+    }
+
+    let mut context = module.make_context();
+    let mut builder_context = FunctionBuilderContext::new();
+
+    for (&offset, &id) in &ids {
+        let signature = &signatures[&offset];
+        context.func.signature = native.clone();
+
+        let mut translator = Translator::new(
+            &program,
+            &signatures,
+            &analyses[&offset],
+            FunctionBuilder::new(&mut context.func, &mut builder_context),
+        );
+
+        translator.prepare(&mut module, &ids, budget);
+        translator.function(offset, signature.args);
+
+        module
+            .define_function(id, &mut context)
+            .map_err(|error| JitError::Codegen {
+                hash: signature.hash,
+                message: error.to_string(),
+            })?;
+
+        module.clear_context(&mut context);
+    }
+
+    module.finalize_definitions().map_err(finalize)?;
+
+    let mut functions = HashMap::new();
+
+    for (offset, id) in ids {
+        let signature = &signatures[&offset];
+        let pointer = module.get_finalized_function(id);
+
+        // SAFETY: The function was declared with the signature of a native
+        // function above.
+        let function = unsafe { core::mem::transmute::<*const u8, NativeFn>(pointer) };
+
+        functions.insert(
+            offset,
+            NativeFunction {
+                function,
+                args: signature.args,
+                ret: signature.ret,
+            },
+        );
+    }
+
+    Ok(NativeCode {
+        module: Some(module),
+        functions,
+    })
+}
+
+/// The type of a value in native code, every value is represented as a
+/// 64-bit integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ty {
+    /// The type is not yet known, like the return value of a function which
+    /// is still being inferred.
+    Unknown,
+    /// An integer.
+    Integer,
+    /// A boolean, represented as `0` or `1`.
+    Bool,
+}
+
+impl Ty {
+    /// Join two types, returning `None` if they conflict.
+    fn join(self, other: Ty) -> Option<Ty> {
+        match (self, other) {
+            (Ty::Unknown, ty) | (ty, Ty::Unknown) => Some(ty),
+            (a, b) if a == b => Some(a),
+            _ => None,
+        }
+    }
+
+    fn integer(self) -> Option<Ty> {
+        matches!(self, Ty::Unknown | Ty::Integer).then_some(Ty::Integer)
+    }
+
+    fn bool(self) -> Option<Ty> {
+        matches!(self, Ty::Unknown | Ty::Bool).then_some(Ty::Bool)
+    }
+}
+
+/// The type produced by a binary operation between operands of the given
+/// types, or `None` if the operation isn't supported in native code.
+fn binary(op: InstOp, a: Ty, b: Ty) -> Option<Ty> {
+    let ty = a.join(b)?;
+
+    match op {
+        InstOp::Add
+        | InstOp::Sub
+        | InstOp::Mul
+        | InstOp::Div
+        | InstOp::Rem
+        | InstOp::Shl
+        | InstOp::Shr => ty.integer(),
+        InstOp::BitAnd | InstOp::BitXor | InstOp::BitOr => Some(ty),
+        InstOp::Lt | InstOp::Gt | InstOp::Lte | InstOp::Gte => {
+            ty.integer()?;
+            Some(Ty::Bool)
+        }
+        InstOp::Eq | InstOp::Neq => Some(Ty::Bool),
+        InstOp::And | InstOp::Or => ty.bool(),
+        _ => None,
+    }
+}
+
+/// The binary operation performed by an assignment.
+fn assign(op: InstAssignOp) -> InstOp {
+    match op {
+        InstAssignOp::Add => InstOp::Add,
+        InstAssignOp::Sub => InstOp::Sub,
+        InstAssignOp::Mul => InstOp::Mul,
+        InstAssignOp::Div => InstOp::Div,
+        InstAssignOp::Rem => InstOp::Rem,
+        InstAssignOp::BitAnd => InstOp::BitAnd,
+        InstAssignOp::BitXor => InstOp::BitXor,
+        InstAssignOp::BitOr => InstOp::BitOr,
+        InstAssignOp::Shl => InstOp::Shl,
+        InstAssignOp::Shr => InstOp::Shr,
+    }
+}
+
+/// The inferred signature of a function.
+struct FnSignature {
+    hash: Hash,
+    args: usize,
+    ret: Ty,
+}
+
+/// The result of analyzing a single function.
+struct Analysis {
+    /// The types on the stack when entering every reachable instruction.
+    states: BTreeMap<usize, Vec<Ty>>,
+    /// The type returned from the function.
+    ret: Ty,
+}
+
+/// The decoded instructions of a unit.
+struct Program<'a, S> {
+    unit: &'a Unit<S>,
+    /// Map from instruction pointer to the instruction and the instruction
+    /// pointer following it.
+    index: HashMap<usize, (Inst, usize)>,
+}
+
+impl<'a, S> Program<'a, S>
+where
+    S: UnitStorage,
+{
+    fn new(unit: &'a Unit<S>) -> Self {
+        let mut index = HashMap::new();
+        let mut ip = 0;
+
+        while let Ok(Some((inst, len))) = unit.instruction_at(ip) {
+            index.insert(ip, (inst, ip.wrapping_add(len)));
+            ip = ip.wrapping_add(len);
+        }
+
+        Self { unit, index }
+    }
+
+    /// Infer the signatures of all functions which can be compiled.
+    ///
+    /// All functions called immediately are candidates, and are optimistically
+    /// assumed to be supported until analysis shows otherwise. This is
+    /// repeated until the return types of all functions are known, since they
+    /// depend on the return types of the functions they call.
+    fn infer(&self) -> (BTreeMap<usize, FnSignature>, HashMap<usize, Analysis>) {
+        let closures = self
+            .index
+            .values()
+            .filter_map(|(inst, _)| match inst {
+                Inst::Closure { hash, .. } => Some(*hash),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+
+        let mut signatures = BTreeMap::new();
+
+        for (hash, f) in &self.unit.logic.functions {
+            if let UnitFn::Offset {
+                offset,
+                call: Call::Immediate,
+                args,
+            } = *f
+            {
+                if args <= MAX_ARGS && !closures.contains(hash) {
+                    signatures.entry(offset).or_insert(FnSignature {
+                        hash: *hash,
+                        args,
+                        ret: Ty::Unknown,
+                    });
+                }
+            }
+        }
+
+        loop {
+            let mut changed = false;
+            let mut analyses = HashMap::new();
+
+            for offset in signatures.keys().copied().collect::<Vec<_>>() {
+                let args = signatures[&offset].args;
+
+                let Some(analysis) = self.analyze(&signatures, offset, args) else {
+                    signatures.remove(&offset);
+                    changed = true;
+                    continue;
+                };
+
+                let signature = signatures.get_mut(&offset).expect("signature must exist");
+
+                if signature.ret != analysis.ret {
+                    signature.ret = analysis.ret;
+                    changed = true;
+                }
+
+                analyses.insert(offset, analysis);
+            }
+
+            if changed {
+                continue;
+            }
+
+            // NB: functions which never return normally can't be compiled,
+            // and neither can the functions calling them.
+            let len = signatures.len();
+            signatures.retain(|_, signature| signature.ret != Ty::Unknown);
+
+            if signatures.len() == len {
+                return (signatures, analyses);
+            }
+        }
+    }
+
+    /// Resolve the offset of the function called by an instruction, if it's
+    /// a candidate for native code.
+    fn callee(&self, inst: Inst, signatures: &BTreeMap<usize, FnSignature>) -> Option<usize> {
+        let (offset, args) = match inst {
+            Inst::Call { hash, args } => match self.unit.function(hash)? {
+                UnitFn::Offset {
+                    offset,
+                    call: Call::Immediate,
+                    ..
+                } => (offset, args),
+                _ => return None,
+            },
+            Inst::CallOffset {
+                offset,
+                call: Call::Immediate,
+                args,
+            } => (offset, args),
+            _ => return None,
+        };
+
+        (signatures.get(&offset)?.args == args).then_some(offset)
+    }
+
+    /// Translate a jump into the instruction pointer it lands on.
+    fn target(&self, jump: usize) -> Option<usize> {
+        let target = self.unit.translate(jump).ok()?;
+        self.index.contains_key(&target).then_some(target)
+    }
+
+    /// Infer the types of the stack of a single function, returning `None` if
+    /// the function can't be compiled.
+    fn analyze(
+        &self,
+        signatures: &BTreeMap<usize, FnSignature>,
+        offset: usize,
+        args: usize,
+    ) -> Option<Analysis> {
+        let mut states = BTreeMap::new();
+        states.insert(offset, vec![Ty::Integer; args]);
+
+        let mut queue = vec![offset];
+        let mut ret = Ty::Unknown;
+
+        while let Some(ip) = queue.pop() {
+            let &(inst, next) = self.index.get(&ip)?;
+            let mut stack = states.get(&ip)?.clone();
+            let mut edges = Vec::new();
+            let mut exit = false;
+
+            match inst {
+                Inst::Push { value } => {
+                    stack.push(match value {
+                        InstValue::Integer(..) => Ty::Integer,
+                        InstValue::Bool(..) => Ty::Bool,
+                        _ => return None,
+                    });
+                }
+                Inst::Pop => {
+                    stack.pop()?;
+                }
+                Inst::PopN { count } => {
+                    truncate(&mut stack, count)?;
+                }
+                Inst::Clean { count } => {
+                    let ty = stack.pop()?;
+                    truncate(&mut stack, count)?;
+                    stack.push(ty);
+                }
+                Inst::Copy { offset } | Inst::Move { offset } => {
+                    let ty = *stack.get(offset)?;
+                    stack.push(ty);
+                }
+                Inst::Drop { offset } => {
+                    stack.get(offset)?;
+                }
+                Inst::Replace { offset } => {
+                    let ty = stack.pop()?;
+                    *stack.get_mut(offset)? = ty;
+                }
+                Inst::Swap { a, b } => {
+                    if a >= stack.len() || b >= stack.len() {
+                        return None;
+                    }
+
+                    stack.swap(a, b);
+                }
+                Inst::Not => {
+                    let ty = stack.pop()?;
+                    stack.push(ty);
+                }
+                Inst::Neg => {
+                    let ty = stack.pop()?.integer()?;
+                    stack.push(ty);
+                }
+                Inst::Op { op, a, b } => {
+                    let b = address(&mut stack, b)?;
+                    let a = address(&mut stack, a)?;
+                    stack.push(binary(op, a, b)?);
+                }
+                Inst::Assign {
+                    target: InstTarget::Offset(offset),
+                    op,
+                } => {
+                    let rhs = stack.pop()?;
+                    let lhs = stack.get_mut(offset)?;
+                    *lhs = binary(assign(op), *lhs, rhs)?.integer()?;
+                }
+                Inst::EqInteger { .. } => {
+                    stack.pop()?.integer()?;
+                    stack.push(Ty::Bool);
+                }
+                Inst::EqBool { .. } => {
+                    stack.pop()?.bool()?;
+                    stack.push(Ty::Bool);
+                }
+                Inst::Call { .. } | Inst::CallOffset { .. } => {
+                    let signature = &signatures[&self.callee(inst, signatures)?];
+
+                    for _ in 0..signature.args {
+                        stack.pop()?.integer()?;
+                    }
+
+                    stack.push(signature.ret);
+                }
+                Inst::Jump { jump } => {
+                    edges.push((self.target(jump)?, stack.clone()));
+                    exit = true;
+                }
+                Inst::JumpIf { jump } => {
+                    stack.pop()?.bool()?;
+                    edges.push((self.target(jump)?, stack.clone()));
+                }
+                Inst::PopAndJumpIfNot { count, jump } => {
+                    stack.pop()?.bool()?;
+                    let mut taken = stack.clone();
+                    truncate(&mut taken, count)?;
+                    edges.push((self.target(jump)?, taken));
+                }
+                Inst::JumpIfOrPop { jump } | Inst::JumpIfNotOrPop { jump } => {
+                    stack.last()?.bool()?;
+                    edges.push((self.target(jump)?, stack.clone()));
+                    stack.pop();
+                }
+                Inst::Return { address: a, .. } => {
+                    ret = ret.join(address(&mut stack, a)?)?;
+                    exit = true;
+                }
+                _ => return None,
+            }
+
+            if !exit {
+                edges.push((next, stack));
+            }
+
+            for (target, stack) in edges {
+                match states.get_mut(&target) {
+                    Some(existing) => {
+                        if existing.len() != stack.len() {
+                            return None;
+                        }
+
+                        let mut changed = false;
+
+                        for (existing, ty) in existing.iter_mut().zip(stack) {
+                            let joined = existing.join(ty)?;
+                            changed |= joined != *existing;
+                            *existing = joined;
+                        }
+
+                        if changed {
+                            queue.push(target);
+                        }
+                    }
+                    None => {
+                        states.insert(target, stack);
+                        queue.push(target);
+                    }
+                }
+            }
+        }
+
+        Some(Analysis { states, ret })
+    }
+}
+
+/// Pop `count` types from the stack.
+fn truncate(stack: &mut Vec<Ty>, count: usize) -> Option<()> {
+    let len = stack.len().checked_sub(count)?;
+    stack.truncate(len);
+    Some(())
+}
+
+/// The type of the value at the given address, popping it if it's on top of
+/// the stack.
+fn address(stack: &mut Vec<Ty>, address: InstAddress) -> Option<Ty> {
+    match address {
+        InstAddress::Top => stack.pop(),
+        InstAddress::Offset(offset) => stack.get(offset).copied(),
+    }
+}
+
+/// Translates a single function which has been analyzed into native code.
+struct Translator<'a, 'b, S> {
+    program: &'a Program<'a, S>,
+    signatures: &'a BTreeMap<usize, FnSignature>,
+    analysis: &'a Analysis,
+    b: FunctionBuilder<'b>,
+    /// The pointer type of the host.
+    pointer: Type,
+    /// References to native functions by their offset.
+    functions: HashMap<usize, FuncRef>,
+    /// Reference to the budget helper.
+    budget: Option<FuncRef>,
+    /// Blocks of every reachable instruction.
+    blocks: HashMap<usize, Block>,
+    /// The block which returns [STATUS_DEOPT].
+    deopt: Option<Block>,
+    output: Option<Reg>,
+    depth: Option<Reg>,
+}
+
+impl<'a, 'b, S> Translator<'a, 'b, S>
+where
+    S: UnitStorage,
+{
+    fn new(
+        program: &'a Program<'a, S>,
+        signatures: &'a BTreeMap<usize, FnSignature>,
+        analysis: &'a Analysis,
+        b: FunctionBuilder<'b>,
+    ) -> Self {
+        Self {
+            program,
+            signatures,
+            analysis,
+            b,
+            pointer: types::I64,
+            functions: HashMap::new(),
+            budget: None,
+            blocks: HashMap::new(),
+            deopt: None,
+            output: None,
+            depth: None,
+        }
+    }
+
+    /// Import the functions referenced by the function being translated.
+    fn prepare(&mut self, module: &mut JITModule, ids: &BTreeMap<usize, FuncId>, budget: FuncId) {
+        self.pointer = module.target_config().pointer_type();
+
+        for (&offset, &id) in ids {
+            let reference = module.declare_func_in_func(id, self.b.func);
+            self.functions.insert(offset, reference);
+        }
+
+        self.budget = Some(module.declare_func_in_func(budget, self.b.func));
+    }
+
+    fn function(mut self, offset: usize, args: usize) {
+        let analysis = self.analysis;
+
+        let entry = self.b.create_block();
+        self.b.append_block_params_for_function_params(entry);
+
+        let deopt = self.b.create_block();
+        self.deopt = Some(deopt);
+
+        for &ip in analysis.states.keys() {
+            let block = self.b.create_block();
+            self.blocks.insert(ip, block);
+        }
+
+        let slots = analysis
+            .states
+            .values()
+            .map(Vec::len)
+            .max()
+            .unwrap_or_default()
+            + 1;
+
+        for slot in 0..slots {
+            self.b.declare_var(Variable::new(slot), types::I64);
+        }
+
+        self.b.switch_to_block(entry);
+
+        let params = self.b.block_params(entry).to_vec();
+        let [input, output, depth] = params[..] else {
+            unreachable!("native functions take three parameters");
+        };
+
+        self.output = Some(output);
+        self.depth = Some(depth);
+
+        let limited = self
+            .b
+            .ins()
+            .icmp_imm(IntCC::SignedGreaterThanOrEqual, depth, MAX_DEPTH);
+        let next = self.b.create_block();
+        self.b.ins().brif(limited, deopt, &[], next, &[]);
+        self.b.switch_to_block(next);
+        self.take_budget();
+
+        for slot in 0..args {
+            let value =
+                self.b
+                    .ins()
+                    .load(types::I64, MemFlags::trusted(), input, (slot * 8) as i32);
+
+            self.set(slot, value);
+        }
+
+        let block = self.blocks[&offset];
+        self.b.ins().jump(block, &[]);
+
+        for (&ip, stack) in &analysis.states {
+            let (inst, next) = self.program.index[&ip];
+            self.b.switch_to_block(self.blocks[&ip]);
+            self.inst(ip, inst, next, stack.clone());
+        }
+
+        self.b.switch_to_block(deopt);
+        let status = self.b.ins().iconst(types::I8, STATUS_DEOPT);
+        self.b.ins().return_(&[status]);
+
+        self.b.seal_all_blocks();
+        self.b.finalize();
+    }
+
+    /// Translate a single instruction.
+    fn inst(&mut self, ip: usize, inst: Inst, next: usize, mut stack: Vec<Ty>) {
+        match inst {
+            Inst::Push { value } => {
+                let (value, ty) = match value {
+                    InstValue::Bool(value) => (i64::from(value), Ty::Bool),
+                    InstValue::Integer(value) => (value, Ty::Integer),
+                    _ => unreachable!("unsupported value"),
+                };
+
+                let value = self.b.ins().iconst(types::I64, value);
+                self.set(stack.len(), value);
+                stack.push(ty);
+            }
+            Inst::Pop => {
+                stack.pop();
+            }
+            Inst::PopN { count } => {
+                stack.truncate(stack.len() - count);
+            }
+            Inst::Clean { count } => {
+                let value = self.get(stack.len() - 1);
+                let ty = stack.pop().unwrap_or(Ty::Unknown);
+                stack.truncate(stack.len() - count);
+                self.set(stack.len(), value);
+                stack.push(ty);
+            }
+            Inst::Copy { offset } | Inst::Move { offset } => {
+                let value = self.get(offset);
+                self.set(stack.len(), value);
+                stack.push(stack[offset]);
+            }
+            Inst::Drop { .. } => {}
+            Inst::Replace { offset } => {
+                let value = self.get(stack.len() - 1);
+                let ty = stack.pop().unwrap_or(Ty::Unknown);
+                self.set(offset, value);
+                stack[offset] = ty;
+            }
+            Inst::Swap { a, b } => {
+                let (va, vb) = (self.get(a), self.get(b));
+                self.set(a, vb);
+                self.set(b, va);
+                stack.swap(a, b);
+            }
+            Inst::Not => {
+                let top = stack.len() - 1;
+                let value = self.get(top);
+
+                let value = match stack[top] {
+                    Ty::Bool => self.b.ins().bxor_imm(value, 1),
+                    _ => self.b.ins().bnot(value),
+                };
+
+                self.set(top, value);
+            }
+            Inst::Neg => {
+                let top = stack.len() - 1;
+                let value = self.get(top);
+                let overflow = self.b.ins().icmp_imm(IntCC::Equal, value, i64::MIN);
+                self.deopt_if(overflow);
+                let value = self.b.ins().ineg(value);
+                self.set(top, value);
+            }
+            Inst::Op { op, a, b } => {
+                let (b, tb) = self.address(&mut stack, b);
+                let (a, ta) = self.address(&mut stack, a);
+                let ty = ta.join(tb).unwrap_or(Ty::Integer);
+                let value = self.binary(op, ty, a, b);
+                self.set(stack.len(), value);
+                stack.push(binary(op, ta, tb).unwrap_or(Ty::Integer));
+            }
+            Inst::Assign {
+                target: InstTarget::Offset(offset),
+                op,
+            } => {
+                let rhs = self.get(stack.len() - 1);
+                stack.pop();
+                let lhs = self.get(offset);
+                let value = self.binary(assign(op), Ty::Integer, lhs, rhs);
+                self.set(offset, value);
+                stack[offset] = Ty::Integer;
+            }
+            Inst::EqInteger { integer } => {
+                let top = stack.len() - 1;
+                let value = self.get(top);
+                let value = self.compare_imm(IntCC::Equal, value, integer);
+                self.set(top, value);
+                stack[top] = Ty::Bool;
+            }
+            Inst::EqBool { boolean } => {
+                let top = stack.len() - 1;
+                let value = self.get(top);
+                let value = self.compare_imm(IntCC::Equal, value, i64::from(boolean));
+                self.set(top, value);
+                stack[top] = Ty::Bool;
+            }
+            Inst::Call { .. } | Inst::CallOffset { .. } => {
+                let offset = self
+                    .program
+                    .callee(inst, self.signatures)
+                    .expect("callee must be native");
+                let signature = &self.signatures[&offset];
+                let base = stack.len() - signature.args;
+                self.call(offset, base, signature.args);
+                stack.truncate(base);
+                stack.push(signature.ret);
+            }
+            Inst::Jump { jump } => {
+                let target = self.target(jump);
+
+                if target <= ip {
+                    self.take_budget();
+                }
+
+                self.b.ins().jump(self.blocks[&target], &[]);
+                return;
+            }
+            Inst::JumpIf { jump } => {
+                let target = self.target(jump);
+                let condition = self.get(stack.len() - 1);
+
+                if target <= ip {
+                    self.take_budget();
+                }
+
+                self.b.ins().brif(
+                    condition,
+                    self.blocks[&target],
+                    &[],
+                    self.blocks[&next],
+                    &[],
+                );
+                return;
+            }
+            Inst::PopAndJumpIfNot { jump, .. } => {
+                let target = self.target(jump);
+                let condition = self.get(stack.len() - 1);
+
+                if target <= ip {
+                    self.take_budget();
+                }
+
+                self.b.ins().brif(
+                    condition,
+                    self.blocks[&next],
+                    &[],
+                    self.blocks[&target],
+                    &[],
+                );
+                return;
+            }
+            Inst::JumpIfOrPop { jump } | Inst::JumpIfNotOrPop { jump } => {
+                let target = self.target(jump);
+                let condition = self.get(stack.len() - 1);
+
+                if target <= ip {
+                    self.take_budget();
+                }
+
+                let (then, otherwise) = match inst {
+                    Inst::JumpIfOrPop { .. } => (self.blocks[&target], self.blocks[&next]),
+                    _ => (self.blocks[&next], self.blocks[&target]),
+                };
+
+                self.b.ins().brif(condition, then, &[], otherwise, &[]);
+                return;
+            }
+            Inst::Return { address, .. } => {
+                let (value, _) = self.address(&mut stack, address);
+                let output = self.output.expect("output must be set");
+                self.b.ins().store(MemFlags::trusted(), value, output, 0);
+                let status = self.b.ins().iconst(types::I8, STATUS_OK);
+                self.b.ins().return_(&[status]);
+                return;
+            }
+            _ => unreachable!("unsupported instruction"),
+        }
+
+        self.b.ins().jump(self.blocks[&next], &[]);
+    }
+
+    /// Emit a binary operation between two operands of type `ty`.
+    fn binary(&mut self, op: InstOp, ty: Ty, a: Reg, b: Reg) -> Reg {
+        match op {
+            InstOp::Add => {
+                // Overflow if both operands have a different sign than the
+                // result.
+                let value = self.b.ins().iadd(a, b);
+                let x = self.b.ins().bxor(a, value);
+                let y = self.b.ins().bxor(b, value);
+                let sign = self.b.ins().band(x, y);
+                let overflow = self.b.ins().icmp_imm(IntCC::SignedLessThan, sign, 0);
+                self.deopt_if(overflow);
+                value
+            }
+            InstOp::Sub => {
+                // Overflow if the operands have different signs, and the
+                // result has a different sign than the first one.
+                let value = self.b.ins().isub(a, b);
+                let x = self.b.ins().bxor(a, b);
+                let y = self.b.ins().bxor(a, value);
+                let sign = self.b.ins().band(x, y);
+                let overflow = self.b.ins().icmp_imm(IntCC::SignedLessThan, sign, 0);
+                self.deopt_if(overflow);
+                value
+            }
+            InstOp::Mul => {
+                // Overflow if the high half of the product isn't the sign
+                // extension of the low half.
+                let value = self.b.ins().imul(a, b);
+                let high = self.b.ins().smulhi(a, b);
+                let sign = self.b.ins().sshr_imm(value, 63);
+                let overflow = self.b.ins().icmp(IntCC::NotEqual, high, sign);
+                self.deopt_if(overflow);
+                value
+            }
+            InstOp::Div | InstOp::Rem => {
+                let zero = self.b.ins().icmp_imm(IntCC::Equal, b, 0);
+                self.deopt_if(zero);
+                let min = self.b.ins().icmp_imm(IntCC::Equal, a, i64::MIN);
+                let negative = self.b.ins().icmp_imm(IntCC::Equal, b, -1);
+                let overflow = self.b.ins().band(min, negative);
+                self.deopt_if(overflow);
+
+                match op {
+                    InstOp::Div => self.b.ins().sdiv(a, b),
+                    _ => self.b.ins().srem(a, b),
+                }
+            }
+            InstOp::Shl | InstOp::Shr => {
+                // NB: shifts which are out of range are left to the
+                // interpreter to report.
+                let range = self
+                    .b
+                    .ins()
+                    .icmp_imm(IntCC::UnsignedGreaterThanOrEqual, b, 64);
+                self.deopt_if(range);
+
+                match op {
+                    InstOp::Shl => self.b.ins().ishl(a, b),
+                    _ => self.b.ins().sshr(a, b),
+                }
+            }
+            InstOp::BitAnd | InstOp::And => self.b.ins().band(a, b),
+            InstOp::BitXor => self.b.ins().bxor(a, b),
+            InstOp::BitOr | InstOp::Or => self.b.ins().bor(a, b),
+            InstOp::Lt => self.compare(IntCC::SignedLessThan, a, b),
+            InstOp::Gt => self.compare(IntCC::SignedGreaterThan, a, b),
+            InstOp::Lte => self.compare(IntCC::SignedLessThanOrEqual, a, b),
+            InstOp::Gte => self.compare(IntCC::SignedGreaterThanOrEqual, a, b),
+            InstOp::Eq => self.compare(IntCC::Equal, a, b),
+            InstOp::Neq => self.compare(IntCC::NotEqual, a, b),
+            _ => unreachable!("unsupported operation on {ty:?}"),
+        }
+    }
+
+    fn compare(&mut self, cc: IntCC, a: Reg, b: Reg) -> Reg {
+        let value = self.b.ins().icmp(cc, a, b);
+        self.b.ins().uextend(types::I64, value)
+    }
+
+    fn compare_imm(&mut self, cc: IntCC, a: Reg, b: i64) -> Reg {
+        let value = self.b.ins().icmp_imm(cc, a, b);
+        self.b.ins().uextend(types::I64, value)
+    }
+
+    /// Call the native function at the given offset with the `args` values
+    /// starting at `base`, storing the returned value at `base`.
+    fn call(&mut self, offset: usize, base: usize, args: usize) {
+        let size = (args.max(1) * 8) as u32;
+        let input = self
+            .b
+            .create_sized_stack_slot(StackSlotData::new(StackSlotKind::ExplicitSlot, size));
+        let output = self
+            .b
+            .create_sized_stack_slot(StackSlotData::new(StackSlotKind::ExplicitSlot, 8));
+
+        for slot in 0..args {
+            let value = self.get(base + slot);
+            self.b.ins().stack_store(value, input, (slot * 8) as i32);
+        }
+
+        let input_address = self.b.ins().stack_addr(self.pointer, input, 0);
+        let output_address = self.b.ins().stack_addr(self.pointer, output, 0);
+        let depth = self.depth.expect("depth must be set");
+        let depth = self.b.ins().iadd_imm(depth, 1);
+
+        let call = self.b.ins().call(
+            self.functions[&offset],
+            &[input_address, output_address, depth],
+        );
+
+        let status = self.b.inst_results(call)[0];
+        self.deopt_if(status);
+
+        let value = self.b.ins().stack_load(types::I64, output, 0);
+        self.set(base, value);
+    }
+
+    /// Take from the execution budget, and deoptimize if it's exhausted.
+    fn take_budget(&mut self) {
+        let budget = self.budget.expect("budget must be imported");
+        let call = self.b.ins().call(budget, &[]);
+        let taken = self.b.inst_results(call)[0];
+        let exhausted = self.b.ins().icmp_imm(IntCC::Equal, taken, 0);
+        self.deopt_if(exhausted);
+    }
+
+    /// Deoptimize if the given condition is non-zero.
+    fn deopt_if(&mut self, condition: Reg) {
+        let deopt = self.deopt.expect("deopt block must be set");
+        let next = self.b.create_block();
+        self.b.ins().brif(condition, deopt, &[], next, &[]);
+        self.b.switch_to_block(next);
+    }
+
+    fn target(&self, jump: usize) -> usize {
+        self.program.target(jump).expect("jump must be valid")
+    }
+
+    fn address(&mut self, stack: &mut Vec<Ty>, address: InstAddress) -> (Reg, Ty) {
+        match address {
+            InstAddress::Top => {
+                let value = self.get(stack.len() - 1);
+                (value, stack.pop().unwrap_or(Ty::Unknown))
+            }
+            InstAddress::Offset(offset) => (self.get(offset), stack[offset]),
+        }
+    }
+
+    fn get(&mut self, slot: usize) -> Reg {
+        self.b.use_var(Variable::new(slot))
+    }
+
+    fn set(&mut self, slot: usize, value: Reg) {
+        self.b.def_var(Variable::new(slot), value);
+    }
+}
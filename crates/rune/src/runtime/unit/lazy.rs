@@ -0,0 +1,213 @@
+use std::sync::OnceLock;
+
+use crate::no_std::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::runtime::unit::{BadInstruction, BadJump, EncodeError, UnitEncoder, UnitStorage};
+use crate::runtime::Inst;
+
+/// Unit stored as byte code which is decoded lazily, one function at a time.
+///
+/// Instructions are stored in one chunk of byte code per function. When the
+/// unit is deserialized, chunks are kept in their encoded form until an
+/// instruction in them is first accessed, so loading a large unit only costs
+/// decoding the functions which are actually called.
+///
+/// Like [`ArrayUnit`][super::ArrayUnit], instruction pointers correspond to
+/// the index of instructions, so decoded instructions are executed as fast as
+/// with an array unit.
+///
+/// Note that [`Unit::verify`][super::Unit::verify] decodes every function in
+/// the unit, so verification can be skipped or performed eagerly depending on
+/// how much the unit is trusted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LazyUnit {
+    /// Chunks of instructions, sorted by the instruction pointer they start
+    /// at.
+    chunks: Vec<Chunk>,
+    /// The total number of instructions.
+    end: usize,
+}
+
+impl LazyUnit {
+    /// The number of chunks in the unit, which corresponds to the number of
+    /// functions it contains.
+    pub fn chunks(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// The number of chunks which have been decoded.
+    pub fn loaded(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|c| c.decoded.get().is_some())
+            .count()
+    }
+
+    /// Eagerly decode every chunk in the unit.
+    ///
+    /// This is useful to avoid paying the cost of decoding functions while a
+    /// script is running.
+    pub fn load_all(&self) -> Result<(), BadInstruction> {
+        for chunk in &self.chunks {
+            chunk.load()?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the chunk which contains the given instruction pointer.
+    fn chunk(&self, ip: usize) -> Option<&Chunk> {
+        let index = self
+            .chunks
+            .partition_point(|c| c.start <= ip)
+            .checked_sub(1)?;
+        self.chunks.get(index)
+    }
+}
+
+/// A chunk of instructions belonging to a single function.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Chunk {
+    /// The instruction pointer of the first instruction in the chunk.
+    start: usize,
+    /// The number of instructions in the chunk.
+    len: usize,
+    /// The encoded instructions.
+    #[serde(with = "serde_bytes")]
+    bytes: Vec<u8>,
+    /// Instructions which are decoded on first access.
+    #[serde(skip)]
+    decoded: OnceLock<Vec<Inst>>,
+}
+
+impl Chunk {
+    /// Load the instructions of the chunk, decoding them if necessary.
+    fn load(&self) -> Result<&[Inst], BadInstruction> {
+        if let Some(decoded) = self.decoded.get() {
+            return Ok(decoded);
+        }
+
+        let mut bytes = &self.bytes[..];
+        let mut decoded = Vec::with_capacity(self.len);
+
+        for n in 0..self.len {
+            let inst = musli_storage::decode(&mut bytes).map_err(|_| BadInstruction {
+                ip: self.start.wrapping_add(n),
+            })?;
+
+            decoded.push(inst);
+        }
+
+        // If another thread raced us, the instructions it decoded are
+        // identical so it doesn't matter which one is stored.
+        Ok(self.decoded.get_or_init(|| decoded))
+    }
+}
+
+/// Iterator for [`LazyUnit`].
+pub struct LazyUnitIter<'a> {
+    unit: &'a LazyUnit,
+    ip: usize,
+}
+
+impl<'a> Iterator for LazyUnitIter<'a> {
+    type Item = (usize, Inst);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let ip = self.ip;
+        let (inst, len) = self.unit.get(ip).ok()??;
+        self.ip = ip.wrapping_add(len);
+        Some((ip, inst))
+    }
+}
+
+impl UnitEncoder for LazyUnit {
+    #[inline]
+    fn offset(&self) -> usize {
+        self.end
+    }
+
+    fn encode(&mut self, inst: Inst) -> Result<(), EncodeError> {
+        if self.chunks.is_empty() {
+            self.begin_function();
+        }
+
+        if let Some(chunk) = self.chunks.last_mut() {
+            musli_storage::encode(&mut chunk.bytes, &inst)?;
+
+            if let Some(decoded) = chunk.decoded.get_mut() {
+                decoded.push(inst);
+            }
+
+            chunk.len = chunk.len.wrapping_add(1);
+        }
+
+        self.end = self.end.wrapping_add(1);
+        Ok(())
+    }
+
+    #[inline]
+    fn extend_offsets(&mut self, _: usize) -> usize {
+        self.end
+    }
+
+    #[inline]
+    fn mark_offset(&mut self, _: usize) {}
+
+    #[inline]
+    fn label_jump(&self, base: usize, offset: usize, _: usize) -> usize {
+        base.wrapping_add(offset)
+    }
+
+    fn begin_function(&mut self) {
+        if matches!(self.chunks.last(), Some(chunk) if chunk.len == 0) {
+            return;
+        }
+
+        self.chunks.push(Chunk {
+            start: self.end,
+            len: 0,
+            bytes: Vec::new(),
+            decoded: OnceLock::from(Vec::new()),
+        });
+    }
+}
+
+impl UnitStorage for LazyUnit {
+    type Iter<'this> = LazyUnitIter<'this>;
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.end
+    }
+
+    #[inline]
+    fn bytes(&self) -> usize {
+        self.chunks.iter().map(|c| c.bytes.len()).sum()
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        LazyUnitIter { unit: self, ip: 0 }
+    }
+
+    fn get(&self, ip: usize) -> Result<Option<(Inst, usize)>, BadInstruction> {
+        let Some(chunk) = self.chunk(ip) else {
+            return Ok(None);
+        };
+
+        let Some(inst) = chunk.load()?.get(ip.wrapping_sub(chunk.start)) else {
+            return Ok(None);
+        };
+
+        Ok(Some((*inst, 1)))
+    }
+
+    #[inline]
+    fn translate(&self, jump: usize) -> Result<usize, BadJump> {
+        Ok(jump)
+    }
+}
@@ -0,0 +1,731 @@
+use core::fmt;
+
+use crate::no_std::collections::{BTreeMap, HashMap, HashSet};
+use crate::no_std::error;
+use crate::no_std::prelude::*;
+
+use crate::runtime::unit::{Unit, UnitEncoder, UnitFn, UnitStorage, VerifyError};
+use crate::runtime::{Call, DebugInfo, Inst, InstTarget};
+use crate::Hash;
+
+/// Error raised when patching a function through [Unit::patch_function].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PatchError {
+    /// The function being patched doesn't exist in the unit.
+    MissingFunction {
+        /// The hash of the function.
+        hash: Hash,
+    },
+    /// The function being patched doesn't exist in the patch.
+    MissingPatchFunction {
+        /// The hash of the function.
+        hash: Hash,
+    },
+    /// The function in the patch takes a different number of arguments than
+    /// the function being patched.
+    ArgumentsMismatch {
+        /// The hash of the function.
+        hash: Hash,
+        /// The number of arguments taken by the function being patched.
+        expected: usize,
+        /// The number of arguments taken by the function in the patch.
+        actual: usize,
+    },
+    /// The function in the patch uses a different calling convention than the
+    /// function being patched, like being `async` when the original function
+    /// isn't.
+    CallMismatch {
+        /// The hash of the function.
+        hash: Hash,
+        /// The calling convention of the function being patched.
+        expected: Call,
+        /// The calling convention of the function in the patch.
+        actual: Call,
+    },
+    /// The patched function references a function which either doesn't exist
+    /// in the unit being patched, or which has a different signature in it.
+    MissingReference {
+        /// The hash of the referenced function.
+        hash: Hash,
+    },
+    /// The instruction at `ip` couldn't be decoded.
+    BadInstruction {
+        /// The instruction pointer.
+        ip: usize,
+    },
+    /// A jump which doesn't land on an instruction in the function it belongs
+    /// to.
+    BadJump {
+        /// The instruction pointer of the jump.
+        ip: usize,
+        /// The jump that was performed.
+        jump: usize,
+    },
+    /// A call to an offset which doesn't correspond to a function.
+    BadCallOffset {
+        /// The instruction pointer of the call.
+        ip: usize,
+        /// The offset being called.
+        offset: usize,
+    },
+    /// The patch failed [verification][Unit::verify].
+    InvalidPatch {
+        /// The error raised when verifying the patch.
+        error: VerifyError,
+    },
+    /// Instructions couldn't be encoded into the patched unit.
+    EncodeFailed,
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::MissingFunction { hash } => {
+                write!(f, "Function {hash} is missing from the unit")
+            }
+            PatchError::MissingPatchFunction { hash } => {
+                write!(f, "Function {hash} is missing from the patch")
+            }
+            PatchError::ArgumentsMismatch {
+                hash,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "Function {hash} takes {actual} arguments in the patch, expected {expected}"
+                )
+            }
+            PatchError::CallMismatch {
+                hash,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "Function {hash} is {actual} in the patch, expected {expected}"
+                )
+            }
+            PatchError::MissingReference { hash } => {
+                write!(
+                    f,
+                    "Referenced function {hash} is missing from the unit or has a different signature"
+                )
+            }
+            PatchError::BadInstruction { ip } => {
+                write!(f, "Bad instruction at {ip}")
+            }
+            PatchError::BadJump { ip, jump } => {
+                write!(f, "Bad jump {jump} at {ip}")
+            }
+            PatchError::BadCallOffset { ip, offset } => {
+                write!(f, "Bad call offset {offset} at {ip}")
+            }
+            PatchError::InvalidPatch { error } => {
+                write!(f, "Invalid patch: {error}")
+            }
+            PatchError::EncodeFailed => {
+                write!(f, "Failed to encode instructions")
+            }
+        }
+    }
+}
+
+impl error::Error for PatchError {}
+
+/// Construct a copy of `unit` where the function identified by `hash` has
+/// been replaced with the one in `patch`.
+pub(super) fn patch<S, P>(
+    unit: &Unit<S>,
+    hash: Hash,
+    patch: &Unit<P>,
+) -> Result<Unit<S>, PatchError>
+where
+    S: UnitStorage + UnitEncoder,
+    P: UnitStorage,
+{
+    let Some(UnitFn::Offset { offset, call, args }) = unit.function(hash) else {
+        return Err(PatchError::MissingFunction { hash });
+    };
+
+    let Some(UnitFn::Offset {
+        offset: patch_offset,
+        call: patch_call,
+        args: patch_args,
+    }) = patch.function(hash)
+    else {
+        return Err(PatchError::MissingPatchFunction { hash });
+    };
+
+    if args != patch_args {
+        return Err(PatchError::ArgumentsMismatch {
+            hash,
+            expected: args,
+            actual: patch_args,
+        });
+    }
+
+    if call != patch_call {
+        return Err(PatchError::CallMismatch {
+            hash,
+            expected: call,
+            actual: patch_call,
+        });
+    }
+
+    patch
+        .verify()
+        .map_err(|error| PatchError::InvalidPatch { error })?;
+
+    let layout = Layout::new(unit)?;
+    let patch_layout = Layout::new(patch)?;
+    let nested = nested(unit, patch, &patch_layout, hash, patch_offset)?;
+
+    let mut logic = unit.logic.clone();
+    logic.storage = S::default();
+
+    let debug = unit.debug.as_deref().map(|debug| DebugInfo {
+        instructions: HashMap::new(),
+        functions: debug.functions.clone(),
+        functions_rev: HashMap::new(),
+        hash_to_ident: debug.hash_to_ident.clone(),
+        variables: BTreeMap::new(),
+    });
+
+    let mut encoder = Encoder {
+        logic,
+        debug,
+        offsets: HashMap::new(),
+        strings: HashMap::new(),
+        bytes: HashMap::new(),
+        object_keys: HashMap::new(),
+        data: HashMap::new(),
+    };
+
+    for (&at, function) in &layout.functions {
+        if at == offset {
+            let patched = patch_layout.get(patch_offset)?;
+            let offset = encoder.function(patch, &patch_layout, patched, true)?;
+
+            // Other names of the function, like the ones registered for
+            // instance functions, are moved along with it.
+            encoder.relocate(&function.hashes, offset);
+            continue;
+        }
+
+        // Nested functions which are replaced by ones in the patch are
+        // dropped.
+        if function.hashes.iter().all(|h| nested.contains(h)) {
+            continue;
+        }
+
+        encoder.function(unit, &layout, function, false)?;
+    }
+
+    let mut encoded = HashSet::new();
+
+    for h in &nested {
+        let Some(UnitFn::Offset { offset, .. }) = patch.function(*h) else {
+            continue;
+        };
+
+        if !encoded.insert(offset) {
+            continue;
+        }
+
+        let function = patch_layout.get(offset)?;
+        encoder.function(patch, &patch_layout, function, true)?;
+    }
+
+    let Encoder {
+        mut logic,
+        mut debug,
+        ..
+    } = encoder;
+
+    for (h, f) in &patch.logic.functions {
+        if !matches!(f, UnitFn::Offset { .. }) {
+            logic.functions.entry(*h).or_insert(*f);
+        }
+    }
+
+    for (h, rtti) in &patch.logic.rtti {
+        logic.rtti.entry(*h).or_insert_with(|| rtti.clone());
+    }
+
+    for (h, rtti) in &patch.logic.variant_rtti {
+        logic.variant_rtti.entry(*h).or_insert_with(|| rtti.clone());
+    }
+
+    for (h, value) in &patch.logic.constants {
+        logic.constants.entry(*h).or_insert_with(|| value.clone());
+    }
+
+    if let (Some(debug), Some(patch_debug)) = (&mut debug, patch.debug_info()) {
+        for h in nested.iter().chain([&hash]) {
+            if let Some(signature) = patch_debug.functions.get(h) {
+                debug.functions.insert(*h, signature.clone());
+            }
+        }
+
+        for (h, ident) in &patch_debug.hash_to_ident {
+            debug
+                .hash_to_ident
+                .entry(*h)
+                .or_insert_with(|| ident.clone());
+        }
+    }
+
+    logic.fingerprint = logic.calculate_fingerprint();
+    Ok(Unit::from_parts(logic, debug))
+}
+
+/// Collect functions in the patch which are nested inside of the patched
+/// function, and which are replaced along with it.
+///
+/// These are closures and async blocks, or any other function referenced by
+/// the patched function which doesn't exist in the unit being patched.
+fn nested<S, P>(
+    unit: &Unit<S>,
+    patch: &Unit<P>,
+    layout: &Layout,
+    hash: Hash,
+    offset: usize,
+) -> Result<Vec<Hash>, PatchError> {
+    let path = patch
+        .debug_info()
+        .and_then(|debug| debug.functions.get(&hash))
+        .map(|signature| &signature.path);
+
+    let mut nested = Vec::new();
+    let mut seen = HashSet::new();
+    seen.insert(hash);
+
+    let mut queue = vec![offset];
+
+    while let Some(offset) = queue.pop() {
+        for &(ip, inst) in &layout.get(offset)?.instructions {
+            let (referenced, is_closure) = match inst {
+                Inst::Call { hash, .. } | Inst::CallUnpack { hash, .. } | Inst::LoadFn { hash } => {
+                    (hash, false)
+                }
+                Inst::Closure { hash, .. } => (hash, true),
+                Inst::CallOffset { offset, .. } => match layout.functions.get(&offset) {
+                    Some(function) => (function.hashes[0], false),
+                    None => return Err(PatchError::BadCallOffset { ip, offset }),
+                },
+                _ => continue,
+            };
+
+            if !seen.insert(referenced) {
+                continue;
+            }
+
+            let Some(f) = patch.function(referenced) else {
+                continue;
+            };
+
+            let UnitFn::Offset { offset, .. } = f else {
+                // Constructors which are missing are added from the patch.
+                match unit.function(referenced) {
+                    Some(existing) if existing != f => {
+                        return Err(PatchError::MissingReference { hash: referenced })
+                    }
+                    _ => continue,
+                }
+            };
+
+            let is_nested = path.is_some_and(|path| {
+                patch
+                    .debug_info()
+                    .and_then(|debug| debug.functions.get(&referenced))
+                    .is_some_and(|signature| signature.path.starts_with(path))
+            });
+
+            match unit.function(referenced) {
+                Some(existing) if !is_closure && !is_nested => {
+                    if !is_compatible(existing, f) {
+                        return Err(PatchError::MissingReference { hash: referenced });
+                    }
+                }
+                _ => {
+                    nested.push(referenced);
+                    queue.push(offset);
+                }
+            }
+        }
+    }
+
+    Ok(nested)
+}
+
+/// Test if two functions can be called in the same way.
+fn is_compatible(a: UnitFn, b: UnitFn) -> bool {
+    match (a, b) {
+        (
+            UnitFn::Offset { call, args, .. },
+            UnitFn::Offset {
+                call: b_call,
+                args: b_args,
+                ..
+            },
+        ) => call == b_call && args == b_args,
+        (a, b) => a == b,
+    }
+}
+
+/// The functions in a unit and the instructions they consist of.
+struct Layout {
+    functions: BTreeMap<usize, Function>,
+}
+
+impl Layout {
+    fn new<S>(unit: &Unit<S>) -> Result<Self, PatchError>
+    where
+        S: UnitStorage,
+    {
+        let mut functions = BTreeMap::<usize, Function>::new();
+
+        for (hash, f) in &unit.logic.functions {
+            if let UnitFn::Offset { offset, .. } = *f {
+                functions.entry(offset).or_default().hashes.push(*hash);
+            }
+        }
+
+        for function in functions.values_mut() {
+            function.hashes.sort();
+        }
+
+        let mut ip = 0;
+
+        while let Some((inst, len)) = unit
+            .instruction_at(ip)
+            .map_err(|_| PatchError::BadInstruction { ip })?
+        {
+            // Instructions which aren't part of any function are unreachable.
+            if let Some((_, function)) = functions.range_mut(..=ip).next_back() {
+                function.instructions.push((ip, inst));
+            }
+
+            ip = ip.wrapping_add(len);
+        }
+
+        Ok(Self { functions })
+    }
+
+    fn get(&self, offset: usize) -> Result<&Function, PatchError> {
+        self.functions
+            .get(&offset)
+            .ok_or(PatchError::BadInstruction { ip: offset })
+    }
+}
+
+/// A single function in a [Layout].
+#[derive(Default)]
+struct Function {
+    /// The hashes the function is registered under.
+    hashes: Vec<Hash>,
+    /// The instructions of the function and their instruction pointers.
+    instructions: Vec<(usize, Inst)>,
+}
+
+/// Encodes functions into the patched unit.
+struct Encoder<S> {
+    logic: super::Logic<S>,
+    debug: Option<DebugInfo>,
+    /// The offsets of functions which have been encoded.
+    offsets: HashMap<Hash, usize>,
+    /// Static slots in the patch and the slots they were copied to.
+    strings: HashMap<usize, usize>,
+    bytes: HashMap<usize, usize>,
+    object_keys: HashMap<usize, usize>,
+    data: HashMap<usize, usize>,
+}
+
+impl<S> Encoder<S>
+where
+    S: UnitStorage + UnitEncoder,
+{
+    /// Encode a single function from the given unit.
+    ///
+    /// Static slots are copied over if `is_patch` is set.
+    fn function<P>(
+        &mut self,
+        from: &Unit<P>,
+        layout: &Layout,
+        function: &Function,
+        is_patch: bool,
+    ) -> Result<usize, PatchError>
+    where
+        P: UnitStorage,
+    {
+        let positions = function
+            .instructions
+            .iter()
+            .enumerate()
+            .map(|(pos, &(ip, _))| (ip, pos))
+            .collect::<HashMap<_, _>>();
+
+        // Jump targets and the label index they are assigned.
+        let mut labels = HashMap::new();
+
+        for &(ip, inst) in &function.instructions {
+            if let Some(jump) = jump(inst) {
+                let target = match from.translate(jump) {
+                    Ok(target) if positions.contains_key(&target) => target,
+                    _ => return Err(PatchError::BadJump { ip, jump }),
+                };
+
+                let index = labels.len();
+                labels.entry(target).or_insert(index);
+            }
+        }
+
+        self.logic.storage.begin_function();
+        let base = self.logic.storage.extend_offsets(labels.len());
+        let offset = self.logic.storage.offset();
+
+        if is_patch {
+            for h in &function.hashes {
+                if let Some(f) = from.function(*h) {
+                    self.logic.functions.insert(*h, f);
+                }
+            }
+        }
+
+        self.relocate(&function.hashes, offset);
+
+        let from_debug = from.debug_info();
+
+        if let Some(debug) = &mut self.debug {
+            let first = function.instructions.first().map(|&(ip, _)| ip);
+
+            let hash = first
+                .and_then(|ip| from_debug?.functions_rev.get(&ip).copied())
+                .unwrap_or(function.hashes[0]);
+
+            debug.functions_rev.insert(offset, hash);
+        }
+
+        for &(ip, inst) in &function.instructions {
+            if let Some(index) = labels.get(&ip) {
+                self.logic.storage.mark_offset(base.wrapping_add(*index));
+            }
+
+            let at = self.logic.storage.offset();
+
+            let inst = match set_jump(inst, |jump| {
+                let target = from.translate(jump).ok()?;
+                let index = *labels.get(&target)?;
+                let pos = *positions.get(&target)?;
+                Some(self.logic.storage.label_jump(base, pos, index))
+            }) {
+                Some(inst) => inst,
+                None => {
+                    return Err(PatchError::BadJump {
+                        ip,
+                        jump: jump(inst).unwrap_or_default(),
+                    })
+                }
+            };
+
+            let inst = match inst {
+                Inst::CallOffset { offset, call, args } => {
+                    let Some(target) = layout.functions.get(&offset) else {
+                        return Err(PatchError::BadCallOffset { ip, offset });
+                    };
+
+                    let hash = target.hashes[0];
+
+                    match self.offsets.get(&hash) {
+                        Some(&offset) => Inst::CallOffset { offset, call, args },
+                        None => Inst::Call { hash, args },
+                    }
+                }
+                inst if is_patch => self.copy_slots(from, inst),
+                inst => inst,
+            };
+
+            self.logic
+                .storage
+                .encode(inst)
+                .map_err(|_| PatchError::EncodeFailed)?;
+
+            if let Some(debug) = &mut self.debug {
+                if let Some(inst) = from_debug.and_then(|d| d.instruction_at(ip)) {
+                    debug.instructions.insert(at, inst.clone());
+                }
+
+                if let Some(variables) = from_debug.and_then(|d| d.variables.get(&ip)) {
+                    debug.variables.insert(at, variables.clone());
+                }
+            }
+        }
+
+        Ok(offset)
+    }
+
+    /// Move the given functions to a new offset.
+    fn relocate(&mut self, hashes: &[Hash], offset: usize) {
+        for h in hashes {
+            self.offsets.insert(*h, offset);
+
+            if let Some(UnitFn::Offset { call, args, .. }) = self.logic.functions.get(h).copied() {
+                self.logic
+                    .functions
+                    .insert(*h, UnitFn::Offset { offset, call, args });
+            }
+        }
+    }
+
+    /// Copy static slots referenced by an instruction in the patch.
+    fn copy_slots<P>(&mut self, from: &Unit<P>, inst: Inst) -> Inst {
+        let logic = &mut self.logic;
+        let from = &from.logic;
+
+        let mut string = |slot: usize| {
+            *self.strings.entry(slot).or_insert_with(|| {
+                let s = &from.static_strings[slot];
+
+                match logic.static_strings.iter().position(|e| e == s) {
+                    Some(n) => n,
+                    None => {
+                        logic.static_strings.push(s.clone());
+                        logic.static_strings.len() - 1
+                    }
+                }
+            })
+        };
+
+        match inst {
+            Inst::String { slot } => Inst::String { slot: string(slot) },
+            Inst::EqString { slot } => Inst::EqString { slot: string(slot) },
+            Inst::ObjectIndexGet { slot } => Inst::ObjectIndexGet { slot: string(slot) },
+            Inst::ObjectIndexSet { slot } => Inst::ObjectIndexSet { slot: string(slot) },
+            Inst::ObjectIndexGetAt { offset, slot } => Inst::ObjectIndexGetAt {
+                offset,
+                slot: string(slot),
+            },
+            Inst::CheckArgument { offset, hash, slot } => Inst::CheckArgument {
+                offset,
+                hash,
+                slot: string(slot),
+            },
+            Inst::Assign {
+                target: InstTarget::Field(slot),
+                op,
+            } => Inst::Assign {
+                target: InstTarget::Field(string(slot)),
+                op,
+            },
+            Inst::Bytes { slot } | Inst::EqBytes { slot } => {
+                let slot = *self.bytes.entry(slot).or_insert_with(|| {
+                    let b = &from.static_bytes[slot];
+
+                    match logic.static_bytes.iter().position(|e| e == b) {
+                        Some(n) => n,
+                        None => {
+                            logic.static_bytes.push(b.clone());
+                            logic.static_bytes.len() - 1
+                        }
+                    }
+                });
+
+                match inst {
+                    Inst::Bytes { .. } => Inst::Bytes { slot },
+                    _ => Inst::EqBytes { slot },
+                }
+            }
+            Inst::Object { slot }
+            | Inst::Struct { slot, .. }
+            | Inst::StructVariant { slot, .. }
+            | Inst::MatchObject { slot, .. } => {
+                let slot = *self.object_keys.entry(slot).or_insert_with(|| {
+                    let keys = &from.static_object_keys.slots[slot];
+                    logic.static_object_keys.insert(keys)
+                });
+
+                match inst {
+                    Inst::Object { .. } => Inst::Object { slot },
+                    Inst::Struct { hash, .. } => Inst::Struct { hash, slot },
+                    Inst::StructVariant { hash, .. } => Inst::StructVariant { hash, slot },
+                    Inst::MatchObject { exact, .. } => Inst::MatchObject { slot, exact },
+                    inst => inst,
+                }
+            }
+            Inst::StaticData { slot } => {
+                let slot = *self.data.entry(slot).or_insert_with(|| {
+                    logic.static_data.push(from.static_data[slot].clone());
+                    logic.static_data.len() - 1
+                });
+
+                Inst::StaticData { slot }
+            }
+            inst => inst,
+        }
+    }
+}
+
+/// Get the jump of an instruction, if it has one.
+fn jump(inst: Inst) -> Option<usize> {
+    match inst {
+        Inst::PopAndJumpIfNot { jump, .. }
+        | Inst::Jump { jump }
+        | Inst::JumpIf { jump }
+        | Inst::JumpIfOrPop { jump }
+        | Inst::JumpIfNotOrPop { jump }
+        | Inst::JumpIfBranch { jump, .. }
+        | Inst::IterNext { jump, .. }
+        | Inst::IterStep { jump, .. }
+        | Inst::TryOrJump { jump, .. } => Some(jump),
+        _ => None,
+    }
+}
+
+/// Replace the jump of an instruction if it has one.
+fn set_jump(inst: Inst, f: impl FnOnce(usize) -> Option<usize>) -> Option<Inst> {
+    let inst = match inst {
+        Inst::PopAndJumpIfNot { count, jump } => Inst::PopAndJumpIfNot {
+            count,
+            jump: f(jump)?,
+        },
+        Inst::Jump { jump } => Inst::Jump { jump: f(jump)? },
+        Inst::JumpIf { jump } => Inst::JumpIf { jump: f(jump)? },
+        Inst::JumpIfOrPop { jump } => Inst::JumpIfOrPop { jump: f(jump)? },
+        Inst::JumpIfNotOrPop { jump } => Inst::JumpIfNotOrPop { jump: f(jump)? },
+        Inst::JumpIfBranch { branch, jump } => Inst::JumpIfBranch {
+            branch,
+            jump: f(jump)?,
+        },
+        Inst::IterNext { offset, jump } => Inst::IterNext {
+            offset,
+            jump: f(jump)?,
+        },
+        Inst::IterStep {
+            offset,
+            end,
+            step,
+            inclusive,
+            jump,
+        } => Inst::IterStep {
+            offset,
+            end,
+            step,
+            inclusive,
+            jump: f(jump)?,
+        },
+        Inst::TryOrJump {
+            address,
+            preserve,
+            jump,
+        } => Inst::TryOrJump {
+            address,
+            preserve,
+            jump: f(jump)?,
+        },
+        inst => inst,
+    };
+
+    Some(inst)
+}
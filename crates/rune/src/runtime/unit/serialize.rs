@@ -0,0 +1,172 @@
+use core::any;
+use core::fmt;
+
+use crate::no_std::error;
+use crate::no_std::io::{self, Read, Write};
+use crate::no_std::prelude::*;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::runtime::unit::{Logic, Unit};
+use crate::runtime::DebugInfo;
+use crate::Hash;
+
+/// The magic bytes at the start of a serialized [Unit].
+pub const UNIT_MAGIC: [u8; 8] = *b"\0RUNEUNT";
+
+/// The version of the format used to serialize a [Unit], which is bumped
+/// whenever the layout of the header changes.
+pub const UNIT_FORMAT_VERSION: u32 = 1;
+
+/// Error raised when serializing or deserializing a [Unit] through
+/// [Unit::serialize_into] or [Unit::deserialize_from].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum UnitFormatError {
+    /// The data doesn't start with [UNIT_MAGIC], so it's not a serialized
+    /// unit.
+    BadMagic,
+    /// The unit was serialized in a version of the format which isn't
+    /// supported.
+    UnsupportedVersion {
+        /// The version of the format the unit was serialized with.
+        version: u32,
+    },
+    /// The unit was serialized by a different version of the compiler, or
+    /// with a different kind of storage, whose layout can't be relied on.
+    CompilerMismatch {
+        /// The hash of the current compiler.
+        expected: Hash,
+        /// The hash of the compiler the unit was serialized by.
+        actual: Hash,
+    },
+    /// Reading or writing the header failed.
+    Io {
+        /// The underlying error.
+        error: io::Error,
+    },
+    /// The body of the unit couldn't be serialized or deserialized.
+    Body {
+        /// The underlying error.
+        error: bincode::Error,
+    },
+}
+
+impl fmt::Display for UnitFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitFormatError::BadMagic => {
+                write!(f, "Not a serialized unit")
+            }
+            UnitFormatError::UnsupportedVersion { version } => {
+                write!(
+                    f,
+                    "Unsupported unit format version {version}, expected {UNIT_FORMAT_VERSION}"
+                )
+            }
+            UnitFormatError::CompilerMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Unit was serialized by compiler {actual}, expected {expected}"
+                )
+            }
+            UnitFormatError::Io { error } => {
+                write!(f, "I/O error: {error}")
+            }
+            UnitFormatError::Body { error } => {
+                write!(f, "Bad unit: {error}")
+            }
+        }
+    }
+}
+
+impl error::Error for UnitFormatError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            UnitFormatError::Io { error } => Some(error),
+            UnitFormatError::Body { error } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for UnitFormatError {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        UnitFormatError::Io { error }
+    }
+}
+
+impl From<bincode::Error> for UnitFormatError {
+    #[inline]
+    fn from(error: bincode::Error) -> Self {
+        UnitFormatError::Body { error }
+    }
+}
+
+/// The hash identifying the compiler which serializes units with storage `S`.
+///
+/// This covers both the version of rune and the storage, since either of them
+/// changes the layout of a serialized unit.
+pub(super) fn compiler_hash<S>() -> Hash {
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(env!("CARGO_PKG_VERSION").as_bytes());
+    bytes.push(0);
+    bytes.extend_from_slice(any::type_name::<S>().as_bytes());
+    Hash::static_bytes(&bytes)
+}
+
+/// Serialize the unit, preceded by its header.
+pub(super) fn serialize_into<S, W>(unit: &Unit<S>, mut writer: W) -> Result<(), UnitFormatError>
+where
+    S: Serialize + DeserializeOwned,
+    W: Write,
+{
+    writer.write_all(&UNIT_MAGIC)?;
+    writer.write_all(&UNIT_FORMAT_VERSION.to_le_bytes())?;
+    writer.write_all(&compiler_hash::<S>().into_inner().to_le_bytes())?;
+
+    // NB: the parts of the unit are serialized separately, since the unit
+    // flattens its logic which bincode doesn't support.
+    bincode::serialize_into(writer, &(unit.logic(), unit.debug_info()))?;
+    Ok(())
+}
+
+/// Deserialize a unit, checking its header before the unit itself.
+pub(super) fn deserialize_from<S, R>(mut reader: R) -> Result<Unit<S>, UnitFormatError>
+where
+    S: Serialize + DeserializeOwned,
+    R: Read,
+{
+    let mut magic = [0; 8];
+
+    match reader.read_exact(&mut magic) {
+        Ok(()) if magic == UNIT_MAGIC => {}
+        Ok(()) => return Err(UnitFormatError::BadMagic),
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => {
+            return Err(UnitFormatError::BadMagic)
+        }
+        Err(error) => return Err(error.into()),
+    }
+
+    let mut version = [0; 4];
+    reader.read_exact(&mut version)?;
+    let version = u32::from_le_bytes(version);
+
+    if version != UNIT_FORMAT_VERSION {
+        return Err(UnitFormatError::UnsupportedVersion { version });
+    }
+
+    let mut actual = [0; 8];
+    reader.read_exact(&mut actual)?;
+    let actual = Hash::new(u64::from_le_bytes(actual));
+    let expected = compiler_hash::<S>();
+
+    if actual != expected {
+        return Err(UnitFormatError::CompilerMismatch { expected, actual });
+    }
+
+    let (logic, debug): (Logic<S>, Option<DebugInfo>) = bincode::deserialize_from(reader)?;
+    Ok(Unit::from_parts(logic, debug))
+}
@@ -6,7 +6,7 @@ use core::slice;
 use crate::no_std::error;
 use crate::no_std::vec::Vec;
 
-#[cfg(feature = "byte-code")]
+#[cfg(any(feature = "byte-code", feature = "lazy-unit"))]
 use musli_storage::error::BufferError;
 use serde::{Deserialize, Serialize};
 
@@ -17,6 +17,8 @@ mod sealed {
 
     #[cfg(feature = "byte-code")]
     impl Sealed for crate::runtime::unit::ByteCodeUnit {}
+    #[cfg(feature = "lazy-unit")]
+    impl Sealed for crate::runtime::unit::LazyUnit {}
     impl Sealed for crate::runtime::unit::ArrayUnit {}
 }
 
@@ -42,6 +44,10 @@ pub trait UnitEncoder: self::sealed::Sealed {
     /// Calculate label jump.
     #[doc(hidden)]
     fn label_jump(&self, base: usize, offset: usize, jump: usize) -> usize;
+
+    /// Mark the beginning of a new function at the current offset.
+    #[doc(hidden)]
+    fn begin_function(&mut self);
 }
 
 /// Instruction storage used by a [`Unit`][super::Unit].
@@ -98,6 +104,9 @@ impl UnitEncoder for ArrayUnit {
     fn label_jump(&self, base: usize, offset: usize, _: usize) -> usize {
         base.wrapping_add(offset)
     }
+
+    #[inline]
+    fn begin_function(&mut self) {}
 }
 
 impl UnitStorage for ArrayUnit {
@@ -140,7 +149,7 @@ pub struct EncodeError {
     kind: EncodeErrorKind,
 }
 
-#[cfg(feature = "byte-code")]
+#[cfg(any(feature = "byte-code", feature = "lazy-unit"))]
 impl From<BufferError> for EncodeError {
     #[inline]
     fn from(error: BufferError) -> Self {
@@ -154,10 +163,11 @@ impl fmt::Display for EncodeError {
     #[inline]
     fn fmt(
         &self,
-        #[cfg_attr(not(feature = "byte-code"), allow(unused))] f: &mut fmt::Formatter<'_>,
+        #[cfg_attr(not(any(feature = "byte-code", feature = "lazy-unit")), allow(unused))]
+        f: &mut fmt::Formatter<'_>,
     ) -> fmt::Result {
         match self.kind {
-            #[cfg(feature = "byte-code")]
+            #[cfg(any(feature = "byte-code", feature = "lazy-unit"))]
             EncodeErrorKind::BufferError { ref error } => error.fmt(f),
         }
     }
@@ -167,7 +177,7 @@ impl error::Error for EncodeError {}
 
 #[derive(Debug)]
 enum EncodeErrorKind {
-    #[cfg(feature = "byte-code")]
+    #[cfg(any(feature = "byte-code", feature = "lazy-unit"))]
     BufferError { error: BufferError },
 }
 
@@ -0,0 +1,726 @@
+use core::fmt;
+
+use crate::no_std::collections::HashMap;
+use crate::no_std::error;
+use crate::no_std::prelude::*;
+
+use crate::runtime::unit::{Unit, UnitFn, UnitStorage};
+use crate::runtime::{Inst, InstAddress, InstRange, InstTarget, InstVariant};
+use crate::Hash;
+
+/// Error raised when verifying a [Unit] through [Unit::verify].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VerifyError {
+    /// The instruction at `ip` couldn't be decoded.
+    BadInstruction {
+        /// The instruction pointer.
+        ip: usize,
+    },
+    /// A jump which is out of bounds or doesn't land on an instruction.
+    BadJump {
+        /// The instruction pointer of the jump.
+        ip: usize,
+        /// The jump that was performed.
+        jump: usize,
+    },
+    /// A call to an offset which doesn't land on an instruction.
+    BadCallOffset {
+        /// The instruction pointer of the call.
+        ip: usize,
+        /// The offset being called.
+        offset: usize,
+    },
+    /// A function registered in the unit whose offset doesn't land on an
+    /// instruction.
+    BadFunctionOffset {
+        /// The hash of the function.
+        hash: Hash,
+        /// The offset of the function.
+        offset: usize,
+    },
+    /// A reference to a static string which doesn't exist.
+    MissingStaticString {
+        /// The instruction pointer.
+        ip: usize,
+        /// The missing slot.
+        slot: usize,
+    },
+    /// A reference to a static byte string which doesn't exist.
+    MissingStaticBytes {
+        /// The instruction pointer.
+        ip: usize,
+        /// The missing slot.
+        slot: usize,
+    },
+    /// A reference to static object keys which don't exist.
+    MissingStaticObjectKeys {
+        /// The instruction pointer.
+        ip: usize,
+        /// The missing slot.
+        slot: usize,
+    },
+    /// A reference to static data which doesn't exist.
+    MissingStaticData {
+        /// The instruction pointer.
+        ip: usize,
+        /// The missing slot.
+        slot: usize,
+    },
+    /// An instruction pops more values than what's available in the current
+    /// stack frame.
+    StackUnderflow {
+        /// The instruction pointer.
+        ip: usize,
+        /// The depth of the stack frame before the instruction.
+        depth: usize,
+    },
+    /// An instruction addresses an offset which is outside of the current
+    /// stack frame.
+    BadFrameOffset {
+        /// The instruction pointer.
+        ip: usize,
+        /// The offset being addressed.
+        offset: usize,
+        /// The depth of the stack frame when the offset is addressed.
+        depth: usize,
+    },
+    /// An instruction is reachable with different stack depths.
+    StackMismatch {
+        /// The instruction pointer.
+        ip: usize,
+        /// The depth the instruction was first reached with.
+        expected: usize,
+        /// The conflicting depth.
+        actual: usize,
+    },
+    /// Execution runs past the last instruction in the unit.
+    FallsThrough {
+        /// The instruction pointer of the last instruction executed.
+        ip: usize,
+    },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::BadInstruction { ip } => {
+                write!(f, "Bad instruction at {ip}")
+            }
+            VerifyError::BadJump { ip, jump } => {
+                write!(f, "Bad jump {jump} at {ip}")
+            }
+            VerifyError::BadCallOffset { ip, offset } => {
+                write!(f, "Bad call offset {offset} at {ip}")
+            }
+            VerifyError::BadFunctionOffset { hash, offset } => {
+                write!(f, "Function {hash} has bad offset {offset}")
+            }
+            VerifyError::MissingStaticString { ip, slot } => {
+                write!(f, "Missing static string {slot} at {ip}")
+            }
+            VerifyError::MissingStaticBytes { ip, slot } => {
+                write!(f, "Missing static bytes {slot} at {ip}")
+            }
+            VerifyError::MissingStaticObjectKeys { ip, slot } => {
+                write!(f, "Missing static object keys {slot} at {ip}")
+            }
+            VerifyError::MissingStaticData { ip, slot } => {
+                write!(f, "Missing static data {slot} at {ip}")
+            }
+            VerifyError::StackUnderflow { ip, depth } => {
+                write!(f, "Stack underflow at {ip} with depth {depth}")
+            }
+            VerifyError::BadFrameOffset { ip, offset, depth } => {
+                write!(
+                    f,
+                    "Offset {offset} at {ip} is outside of stack frame with depth {depth}"
+                )
+            }
+            VerifyError::StackMismatch {
+                ip,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "Instruction at {ip} is reached with stack depth {actual}, expected {expected}"
+                )
+            }
+            VerifyError::FallsThrough { ip } => {
+                write!(
+                    f,
+                    "Execution falls through past the end of the unit at {ip}"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for VerifyError {}
+
+/// Verify the given unit.
+pub(super) fn verify<S>(unit: &Unit<S>) -> Result<(), VerifyError>
+where
+    S: UnitStorage,
+{
+    let mut instructions = Vec::new();
+    let mut ip = 0;
+
+    while let Some((inst, len)) = unit
+        .instruction_at(ip)
+        .map_err(|_| VerifyError::BadInstruction { ip })?
+    {
+        instructions.push((ip, inst, ip.wrapping_add(len)));
+        ip = ip.wrapping_add(len);
+    }
+
+    // Map from instruction pointer to the instruction and the instruction
+    // pointer following it.
+    let index = instructions
+        .iter()
+        .map(|&(ip, inst, next)| (ip, (inst, next)))
+        .collect::<HashMap<_, _>>();
+
+    let mut closures = HashMap::new();
+
+    for &(ip, inst, _) in &instructions {
+        if let Inst::Closure { hash, count } = inst {
+            closures.insert(hash, count);
+        }
+
+        verify_inst(unit, &index, ip, inst)?;
+    }
+
+    for (hash, f) in &unit.logic.functions {
+        let UnitFn::Offset { offset, args, .. } = *f else {
+            continue;
+        };
+
+        if !index.contains_key(&offset) {
+            return Err(VerifyError::BadFunctionOffset {
+                hash: *hash,
+                offset,
+            });
+        }
+
+        let environment = closures.get(hash).copied();
+        let depth = args + usize::from(environment.is_some());
+
+        let mut cx = Function {
+            unit,
+            index: &index,
+            environment,
+            depths: HashMap::new(),
+            queue: Vec::new(),
+        };
+
+        cx.run(offset, depth)?;
+    }
+
+    Ok(())
+}
+
+/// Verify jumps and static slots of a single instruction.
+fn verify_inst<S>(
+    unit: &Unit<S>,
+    index: &HashMap<usize, (Inst, usize)>,
+    ip: usize,
+    inst: Inst,
+) -> Result<(), VerifyError>
+where
+    S: UnitStorage,
+{
+    let logic = &unit.logic;
+
+    match inst {
+        Inst::PopAndJumpIfNot { jump, .. }
+        | Inst::Jump { jump }
+        | Inst::JumpIf { jump }
+        | Inst::JumpIfOrPop { jump }
+        | Inst::JumpIfNotOrPop { jump }
+        | Inst::JumpIfBranch { jump, .. }
+        | Inst::IterNext { jump, .. }
+        | Inst::IterStep { jump, .. }
+        | Inst::TryOrJump { jump, .. } => {
+            jump_target(unit, index, ip, jump)?;
+        }
+        Inst::CallOffset { offset, .. } => {
+            if !index.contains_key(&offset) {
+                return Err(VerifyError::BadCallOffset { ip, offset });
+            }
+        }
+        Inst::String { slot }
+        | Inst::EqString { slot }
+        | Inst::ObjectIndexGet { slot }
+        | Inst::ObjectIndexSet { slot }
+        | Inst::ObjectIndexGetAt { slot, .. }
+        | Inst::CheckArgument { slot, .. }
+        | Inst::Assign {
+            target: InstTarget::Field(slot),
+            ..
+        } => {
+            if slot >= logic.static_strings.len() {
+                return Err(VerifyError::MissingStaticString { ip, slot });
+            }
+        }
+        Inst::Bytes { slot } | Inst::EqBytes { slot } => {
+            if slot >= logic.static_bytes.len() {
+                return Err(VerifyError::MissingStaticBytes { ip, slot });
+            }
+        }
+        Inst::Object { slot }
+        | Inst::Struct { slot, .. }
+        | Inst::StructVariant { slot, .. }
+        | Inst::MatchObject { slot, .. } => {
+            if slot >= logic.static_object_keys.len() {
+                return Err(VerifyError::MissingStaticObjectKeys { ip, slot });
+            }
+        }
+        Inst::StaticData { slot } => {
+            if slot >= logic.static_data.len() {
+                return Err(VerifyError::MissingStaticData { ip, slot });
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Translate a jump into the instruction pointer it lands on.
+fn jump_target<S>(
+    unit: &Unit<S>,
+    index: &HashMap<usize, (Inst, usize)>,
+    ip: usize,
+    jump: usize,
+) -> Result<usize, VerifyError>
+where
+    S: UnitStorage,
+{
+    match unit.translate(jump) {
+        Ok(target) if index.contains_key(&target) => Ok(target),
+        _ => Err(VerifyError::BadJump { ip, jump }),
+    }
+}
+
+/// Stack effect analysis of a single function.
+///
+/// Depths are tracked relative to the bottom of the stack frame. A depth of
+/// `None` means that it can't be statically determined, in which case stack
+/// checks are skipped until the depth is known again.
+struct Function<'a, S> {
+    unit: &'a Unit<S>,
+    index: &'a HashMap<usize, (Inst, usize)>,
+    /// The number of values captured in the environment of the function if
+    /// it's a closure.
+    environment: Option<usize>,
+    /// Depths that instructions have been reached with.
+    depths: HashMap<usize, Option<usize>>,
+    /// Instructions to visit.
+    queue: Vec<usize>,
+}
+
+impl<S> Function<'_, S>
+where
+    S: UnitStorage,
+{
+    fn run(&mut self, offset: usize, depth: usize) -> Result<(), VerifyError> {
+        self.enter(offset, Some(depth))?;
+
+        while let Some(ip) = self.queue.pop() {
+            let Some(&(inst, next)) = self.index.get(&ip) else {
+                return Err(VerifyError::BadInstruction { ip });
+            };
+
+            let mut stack = Stack {
+                ip,
+                depth: self.depths.get(&ip).copied().flatten(),
+            };
+
+            match stack.step(self, inst)? {
+                Flow::Exit => {}
+                Flow::Next { jump } => {
+                    if !self.index.contains_key(&next) {
+                        return Err(VerifyError::FallsThrough { ip });
+                    }
+
+                    self.enter(next, stack.depth)?;
+
+                    if let Some((target, depth)) = jump {
+                        self.enter(target, depth)?;
+                    }
+                }
+                Flow::Jump { target, depth } => {
+                    self.enter(target, depth)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Enter the given instruction with the given depth.
+    fn enter(&mut self, ip: usize, depth: Option<usize>) -> Result<(), VerifyError> {
+        match self.depths.get(&ip) {
+            Some(&Some(expected)) => {
+                if let Some(actual) = depth {
+                    if actual != expected {
+                        return Err(VerifyError::StackMismatch {
+                            ip,
+                            expected,
+                            actual,
+                        });
+                    }
+                }
+            }
+            Some(None) => {}
+            None => {
+                self.depths.insert(ip, depth);
+                self.queue.push(ip);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// How control flows out of an instruction.
+enum Flow {
+    /// The function exits.
+    Exit,
+    /// Control continues to the next instruction, and optionally to the
+    /// target of a conditional jump with the depth of the stack when taken.
+    Next {
+        jump: Option<(usize, Option<usize>)>,
+    },
+    /// Control unconditionally jumps to the given target.
+    Jump { target: usize, depth: Option<usize> },
+}
+
+/// The simulated stack of a single instruction.
+struct Stack {
+    ip: usize,
+    depth: Option<usize>,
+}
+
+impl Stack {
+    /// Simulate the stack effects of a single instruction.
+    ///
+    fn step<S>(&mut self, cx: &Function<'_, S>, inst: Inst) -> Result<Flow, VerifyError>
+    where
+        S: UnitStorage,
+    {
+        let mut jump = None;
+
+        match inst {
+            Inst::Not
+            | Inst::Neg
+            | Inst::LoadInstanceFn { .. }
+            | Inst::TupleIndexGet { .. }
+            | Inst::ObjectIndexGet { .. }
+            | Inst::Await
+            | Inst::Format { .. }
+            | Inst::IsUnit
+            | Inst::EqByte { .. }
+            | Inst::EqChar { .. }
+            | Inst::EqInteger { .. }
+            | Inst::EqBool { .. }
+            | Inst::EqString { .. }
+            | Inst::EqBytes { .. }
+            | Inst::MatchType { .. }
+            | Inst::MatchVariant { .. }
+            | Inst::MatchBuiltIn { .. }
+            | Inst::MatchSequence { .. }
+            | Inst::MatchObject { .. } => {
+                self.pop(1)?;
+                self.push(1);
+            }
+            Inst::Closure { count, .. } => {
+                self.pop(count)?;
+                self.push(1);
+            }
+            Inst::CallOffset { args, .. } | Inst::Call { args, .. } => {
+                self.pop(args)?;
+                self.push(1);
+            }
+            Inst::CallUnpack { args, count, .. } => {
+                self.pop(args)?;
+                self.push(count);
+            }
+            Inst::CallAssociated { args, .. }
+            | Inst::CallAssociatedTyped { args, .. }
+            | Inst::CallFn { args } => {
+                self.pop(args + 1)?;
+                self.push(1);
+            }
+            Inst::IndexGet { target, index } => {
+                self.address(index)?;
+                self.address(target)?;
+                self.push(1);
+            }
+            Inst::TupleIndexSet { .. } | Inst::ObjectIndexSet { .. } => {
+                self.pop(2)?;
+            }
+            Inst::TupleIndexGetAt { offset, .. } | Inst::ObjectIndexGetAt { offset, .. } => {
+                self.offset(offset)?;
+                self.push(1);
+            }
+            Inst::IndexSet => {
+                self.pop(3)?;
+            }
+            Inst::Return { address, .. } => {
+                self.address(address)?;
+                return Ok(Flow::Exit);
+            }
+            Inst::ReturnTuple { count, .. } => {
+                self.pop(count)?;
+                return Ok(Flow::Exit);
+            }
+            Inst::ReturnUnit | Inst::Panic { .. } => {
+                return Ok(Flow::Exit);
+            }
+            Inst::Memo { args, .. } => {
+                if let Some(offset) = args.checked_sub(1) {
+                    self.offset(offset)?;
+                }
+            }
+            Inst::Select { len } => {
+                // Pushes the value produced and the index of the branch.
+                self.pop(len)?;
+                self.push(2);
+            }
+            Inst::LoadFn { .. }
+            | Inst::Push { .. }
+            | Inst::EmptyStruct { .. }
+            | Inst::UnitVariant { .. }
+            | Inst::String { .. }
+            | Inst::Bytes { .. }
+            | Inst::StaticData { .. }
+            | Inst::YieldUnit => {
+                self.push(1);
+            }
+            Inst::Pop => {
+                self.pop(1)?;
+            }
+            Inst::PopN { count } | Inst::Clean { count } => {
+                self.pop(count)?;
+            }
+            Inst::PopAndJumpIfNot {
+                count,
+                jump: target,
+            } => {
+                self.pop(1)?;
+                let depth = self.depth;
+                self.pop(count)?;
+                jump = Some((target, self.depth));
+                self.depth = depth;
+            }
+            Inst::Copy { offset } | Inst::Move { offset } => {
+                self.offset(offset)?;
+                self.push(1);
+            }
+            Inst::Drop { offset } | Inst::CheckArgument { offset, .. } => {
+                self.offset(offset)?;
+            }
+            Inst::Replace { offset } => {
+                self.pop(1)?;
+                self.offset(offset)?;
+            }
+            Inst::Swap { a, b } => {
+                self.offset(a)?;
+                self.offset(b)?;
+            }
+            Inst::Jump { jump } => {
+                let target = jump_target(cx.unit, cx.index, self.ip, jump)?;
+
+                return Ok(Flow::Jump {
+                    target,
+                    depth: self.depth,
+                });
+            }
+            Inst::JumpIf { jump: target } => {
+                self.pop(1)?;
+                jump = Some((target, self.depth));
+            }
+            Inst::JumpIfOrPop { jump: target } | Inst::JumpIfNotOrPop { jump: target } => {
+                self.pop(1)?;
+                jump = Some((target, self.depth.map(|depth| depth + 1)));
+            }
+            Inst::JumpIfBranch { jump: target, .. } => {
+                self.pop(1)?;
+                jump = Some((target, self.depth));
+                self.push(1);
+            }
+            Inst::Vec { count }
+            | Inst::VecSpread { count }
+            | Inst::ObjectSpread { count }
+            | Inst::Tuple { count } => {
+                self.pop(count)?;
+                self.push(1);
+            }
+            Inst::Tuple1 { args } => self.tuple(&args)?,
+            Inst::Tuple2 { args } => self.tuple(&args)?,
+            Inst::Tuple3 { args } => self.tuple(&args)?,
+            Inst::Tuple4 { args } => self.tuple(&args)?,
+            Inst::PushTuple => {
+                self.pop(1)?;
+
+                match cx.environment {
+                    Some(count) => self.push(count),
+                    None => self.depth = None,
+                }
+            }
+            Inst::Object { slot }
+            | Inst::Struct { slot, .. }
+            | Inst::StructVariant { slot, .. } => {
+                let keys = cx.unit.lookup_object_keys(slot).map(<[_]>::len);
+
+                match keys {
+                    Some(len) => self.pop(len)?,
+                    None => self.depth = None,
+                }
+
+                self.push(1);
+            }
+            Inst::Range { range } => {
+                let count = match range {
+                    InstRange::RangeFull => 0,
+                    InstRange::RangeFrom | InstRange::RangeTo | InstRange::RangeToInclusive => 1,
+                    InstRange::RangeInclusive | InstRange::Range => 2,
+                };
+
+                self.pop(count)?;
+                self.push(1);
+            }
+            Inst::StringConcat { len, .. } => {
+                self.pop(len)?;
+                self.push(1);
+            }
+            Inst::Try {
+                address, preserve, ..
+            } => {
+                self.address(address)?;
+
+                if preserve {
+                    self.push(1);
+                }
+            }
+            Inst::TryOrJump {
+                address,
+                preserve,
+                jump: target,
+            } => {
+                self.address(address)?;
+                jump = Some((target, self.depth.map(|depth| depth + 1)));
+
+                if preserve {
+                    self.push(1);
+                }
+            }
+            Inst::Yield => {
+                // The yielded value is replaced with the value the generator
+                // is resumed with.
+                self.pop(1)?;
+                self.push(1);
+            }
+            Inst::Variant { variant } => {
+                if !matches!(variant, InstVariant::None) {
+                    self.pop(1)?;
+                }
+
+                self.push(1);
+            }
+            Inst::Op { a, b, .. } => {
+                self.address(b)?;
+                self.address(a)?;
+                self.push(1);
+            }
+            Inst::Assign { target, .. } => {
+                self.pop(1)?;
+
+                match target {
+                    InstTarget::Offset(offset) => self.offset(offset)?,
+                    InstTarget::Field(..) | InstTarget::TupleField(..) => self.pop(1)?,
+                    InstTarget::Index => self.pop(2)?,
+                }
+            }
+            Inst::IterNext {
+                offset,
+                jump: target,
+            } => {
+                self.offset(offset)?;
+                jump = Some((target, self.depth));
+            }
+            Inst::IterStep {
+                offset,
+                end,
+                jump: target,
+                ..
+            } => {
+                self.offset(offset)?;
+                self.offset(end)?;
+                jump = Some((target, self.depth));
+                self.push(1);
+            }
+        }
+
+        let jump = match jump {
+            Some((jump, depth)) => Some((jump_target(cx.unit, cx.index, self.ip, jump)?, depth)),
+            None => None,
+        };
+
+        Ok(Flow::Next { jump })
+    }
+
+    fn push(&mut self, count: usize) {
+        if let Some(depth) = &mut self.depth {
+            *depth += count;
+        }
+    }
+
+    fn pop(&mut self, count: usize) -> Result<(), VerifyError> {
+        if let Some(depth) = &mut self.depth {
+            let Some(new) = depth.checked_sub(count) else {
+                return Err(VerifyError::StackUnderflow {
+                    ip: self.ip,
+                    depth: *depth,
+                });
+            };
+
+            *depth = new;
+        }
+
+        Ok(())
+    }
+
+    fn offset(&self, offset: usize) -> Result<(), VerifyError> {
+        if let Some(depth) = self.depth {
+            if offset >= depth {
+                return Err(VerifyError::BadFrameOffset {
+                    ip: self.ip,
+                    offset,
+                    depth,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn address(&mut self, address: InstAddress) -> Result<(), VerifyError> {
+        match address {
+            InstAddress::Top => self.pop(1),
+            InstAddress::Offset(offset) => self.offset(offset),
+        }
+    }
+
+    fn tuple(&mut self, args: &[InstAddress]) -> Result<(), VerifyError> {
+        for address in args.iter().rev() {
+            self.address(*address)?;
+        }
+
+        self.push(1);
+        Ok(())
+    }
+}
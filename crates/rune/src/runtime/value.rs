@@ -13,11 +13,12 @@ use crate::no_std::vec;
 use crate::compile::ItemBuf;
 use crate::runtime::vm::CallResult;
 use crate::runtime::{
-    AccessKind, AnyObj, Bytes, ConstValue, ControlFlow, EnvProtocolCaller, Format, Formatter,
-    FromValue, FullTypeOf, Function, Future, Generator, GeneratorState, Iterator, MaybeTypeOf, Mut,
-    Object, OwnedTuple, Protocol, ProtocolCaller, Range, RangeFrom, RangeFull, RangeInclusive,
-    RangeTo, RangeToInclusive, RawMut, RawRef, Ref, Shared, Stream, ToValue, Type, TypeInfo,
-    Variant, Vec, Vm, VmError, VmErrorKind, VmIntegerRepr, VmResult,
+    derives, AccessKind, AnyObj, Bytes, ConstValue, ControlFlow, Derives, EnvProtocolCaller,
+    Format, Formatter, FromValue, FullTypeOf, Function, Future, Generator, GeneratorState,
+    Iterator, MaybeTypeOf, Mut, Object, ObjectKey, OwnedTuple, Protocol, ProtocolCaller, Range,
+    RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, RawMut, RawRef, Ref, Shared,
+    Stream, ToValue, Type, TypeInfo, ValuePath, ValuePathError, Variant, VariantData, Vec, Vm,
+    VmError, VmErrorKind, VmIntegerRepr, VmResult,
 };
 #[cfg(feature = "std")]
 use crate::runtime::{Hasher, Tuple};
@@ -141,7 +142,7 @@ impl Struct {
     /// Get the given key in the object.
     pub fn get<Q: ?Sized>(&self, k: &Q) -> Option<&Value>
     where
-        String: Borrow<Q>,
+        ObjectKey: Borrow<Q>,
         Q: hash::Hash + Eq + Ord,
     {
         self.data.get(k)
@@ -150,7 +151,7 @@ impl Struct {
     /// Get the given mutable value by key in the object.
     pub fn get_mut<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut Value>
     where
-        String: Borrow<Q>,
+        ObjectKey: Borrow<Q>,
         Q: hash::Hash + Eq + Ord,
     {
         self.data.get_mut(k)
@@ -173,6 +174,11 @@ pub struct VariantRtti {
     pub hash: Hash,
     /// The name of the variant.
     pub item: ItemBuf,
+    /// The discriminant of the variant, if it has one.
+    pub discriminant: Option<i64>,
+    /// The protocols derived for the enum of the variant.
+    #[serde(default)]
+    pub derives: Derives,
 }
 
 impl PartialEq for VariantRtti {
@@ -209,6 +215,9 @@ pub struct Rtti {
     pub hash: Hash,
     /// The item of the type.
     pub item: ItemBuf,
+    /// The protocols derived for the type.
+    #[serde(default)]
+    pub derives: Derives,
 }
 
 impl PartialEq for Rtti {
@@ -359,6 +368,10 @@ impl Value {
                 f.push_str(buffer.format(*byte));
             }
             value => {
+                if let Some(result) = vm_try!(caller.pretty_print(value, f)) {
+                    return VmResult::Ok(result);
+                }
+
                 let result = vm_try!(caller.call_protocol_fn(
                     Protocol::STRING_DISPLAY,
                     value.clone(),
@@ -475,13 +488,39 @@ impl Value {
                 write!(f, "{:?}", value)
             }
             Value::TupleStruct(value) => {
-                write!(f, "{:?}", value)
+                let value = vm_try!(value.borrow_ref());
+
+                if !value.rtti.derives.debug {
+                    return VmResult::Ok(write!(f, "{:?}", *value));
+                }
+
+                vm_write!(f, "{}", value.rtti.item);
+                return derives::debug_tuple(&value.data, f, caller);
             }
             Value::Struct(value) => {
-                write!(f, "{:?}", value)
+                let value = vm_try!(value.borrow_ref());
+
+                if !value.rtti.derives.debug {
+                    return VmResult::Ok(write!(f, "{:?}", *value));
+                }
+
+                vm_write!(f, "{}", value.rtti.item);
+                return derives::debug_struct(&value.data, f, caller);
             }
             Value::Variant(value) => {
-                write!(f, "{:?}", value)
+                let value = vm_try!(value.borrow_ref());
+
+                if !value.rtti.derives.debug {
+                    return VmResult::Ok(write!(f, "{:?}", *value));
+                }
+
+                vm_write!(f, "{}", value.rtti.item);
+
+                return match &value.data {
+                    VariantData::Empty => VmResult::Ok(Ok(())),
+                    VariantData::Tuple(data) => derives::debug_tuple(data, f, caller),
+                    VariantData::Struct(data) => derives::debug_struct(data, f, caller),
+                };
             }
             Value::Function(value) => {
                 write!(f, "{:?}", value)
@@ -493,6 +532,10 @@ impl Value {
                 write!(f, "{:?}", value)
             }
             value => {
+                if let Some(result) = vm_try!(caller.pretty_print(value, f)) {
+                    return VmResult::Ok(result);
+                }
+
                 let result =
                     vm_try!(caller.call_protocol_fn(Protocol::STRING_DEBUG, value.clone(), (f,),));
 
@@ -587,6 +630,51 @@ impl Value {
         })
     }
 
+    /// Get a nested value using a path like `a.b[2].c` or a JSON pointer like
+    /// `/a/b/2/c`.
+    ///
+    /// See [ValuePath] for the supported syntax.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the path is invalid or if it doesn't lead to a value, in which
+    /// case [ValuePathError::path] identifies the segment which failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::Object;
+    ///
+    /// let mut inner = Object::new();
+    /// inner.insert(String::from("c"), rune::to_value((1, 2))?);
+    ///
+    /// let mut object = Object::new();
+    /// object.insert(String::from("b"), rune::to_value(vec![inner])?);
+    ///
+    /// let value = rune::to_value(object)?;
+    ///
+    /// let n: i64 = value.get_path_as("b[0].c.1")?;
+    /// assert_eq!(n, 2);
+    ///
+    /// let error = value.get_path("b[0].d.1").unwrap_err();
+    /// assert_eq!(error.path(), "b[0].d");
+    /// assert_eq!(error.to_string(), "Missing field `d` at `b[0].d`");
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn get_path(&self, path: &str) -> Result<Value, ValuePathError> {
+        ValuePath::parse(path)?.get(self)
+    }
+
+    /// Get a nested value using a path and convert it into `T`.
+    ///
+    /// See [Value::get_path].
+    pub fn get_path_as<T>(&self, path: &str) -> Result<T, ValuePathError>
+    where
+        T: FromValue,
+    {
+        ValuePath::parse(path)?.get_as(self)
+    }
+
     /// Construct a vector.
     pub fn vec(vec: vec::Vec<Value>) -> Self {
         Self::Vec(Shared::new(Vec::from(vec)))
@@ -662,6 +750,247 @@ impl Value {
         })
     }
 
+    /// Transfer the value so that it can be used by the `target` virtual
+    /// machine.
+    ///
+    /// Strings, collections and the values of types declared in scripts are
+    /// deeply copied, so that the transferred value doesn't share any state
+    /// with the virtual machine it was produced by. The types of structs and
+    /// variants are looked up again in the unit of the target, and native
+    /// types are checked against the context of the target, erroring if they
+    /// are not available there. Since native values can't be copied they are
+    /// moved instead, so any other references to them become inaccessible.
+    ///
+    /// Sharing between values is not preserved, and functions, futures,
+    /// streams, generators and iterators can't be transferred since they are
+    /// bound to the virtual machine they were created in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::new(context.runtime());
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn produce() {
+    ///             #{ numbers: [1, 2, 3], name: "numbers" }
+    ///         }
+    ///
+    ///         pub fn consume(object) {
+    ///             object.numbers.push(4);
+    ///             object.numbers.len()
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = Arc::new(rune::prepare(&mut sources).with_context(&context).build()?);
+    ///
+    /// let mut a = Vm::new(runtime.clone(), unit.clone());
+    /// let mut b = Vm::new(runtime, unit);
+    ///
+    /// let value = a.call(["produce"], ())?;
+    /// let transferred = value.clone().transfer(&b)?;
+    ///
+    /// let len: usize = rune::from_value(b.call(["consume"], (transferred,))?)?;
+    /// assert_eq!(len, 4);
+    ///
+    /// // The original value is not affected.
+    /// let len: usize = rune::from_value(a.call(["consume"], (value,))?)?;
+    /// assert_eq!(len, 4);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn transfer(self, target: &Vm) -> VmResult<Value> {
+        VmResult::Ok(match self {
+            Self::Bool(value) => Self::Bool(value),
+            Self::Byte(value) => Self::Byte(value),
+            Self::Char(value) => Self::Char(value),
+            Self::Integer(value) => Self::Integer(value),
+            Self::Float(value) => Self::Float(value),
+            Self::Type(value) => Self::Type(value),
+            Self::Ordering(value) => Self::Ordering(value),
+            Self::String(value) => Self::String(Shared::new(vm_try!(value.borrow_ref()).clone())),
+            Self::Bytes(value) => Self::Bytes(Shared::new(vm_try!(value.borrow_ref()).clone())),
+            Self::Vec(value) => {
+                let value = vm_try!(value.borrow_ref());
+                let mut vec = Vec::with_capacity(value.len());
+
+                for value in value.iter() {
+                    vec.push(vm_try!(value.clone().transfer(target)));
+                }
+
+                Self::Vec(Shared::new(vec))
+            }
+            Self::EmptyTuple => Self::EmptyTuple,
+            Self::Tuple(value) => {
+                let value = vm_try!(value.borrow_ref());
+                Self::Tuple(Shared::new(vm_try!(transfer_tuple(&value, target))))
+            }
+            Self::Object(value) => {
+                let value = vm_try!(value.borrow_ref());
+                Self::Object(Shared::new(vm_try!(transfer_object(&value, target))))
+            }
+            Self::RangeFrom(value) => {
+                let value = vm_try!(value.borrow_ref());
+                Self::RangeFrom(Shared::new(RangeFrom::new(vm_try!(value
+                    .start
+                    .clone()
+                    .transfer(target)))))
+            }
+            Self::RangeFull(..) => Self::RangeFull(Shared::new(RangeFull::new())),
+            Self::RangeInclusive(value) => {
+                let value = vm_try!(value.borrow_ref());
+                Self::RangeInclusive(Shared::new(RangeInclusive::new(
+                    vm_try!(value.start.clone().transfer(target)),
+                    vm_try!(value.end.clone().transfer(target)),
+                )))
+            }
+            Self::RangeToInclusive(value) => {
+                let value = vm_try!(value.borrow_ref());
+                Self::RangeToInclusive(Shared::new(RangeToInclusive::new(vm_try!(value
+                    .end
+                    .clone()
+                    .transfer(target)))))
+            }
+            Self::RangeTo(value) => {
+                let value = vm_try!(value.borrow_ref());
+                Self::RangeTo(Shared::new(RangeTo::new(vm_try!(value
+                    .end
+                    .clone()
+                    .transfer(target)))))
+            }
+            Self::Range(value) => {
+                let value = vm_try!(value.borrow_ref());
+                Self::Range(Shared::new(Range::new(
+                    vm_try!(value.start.clone().transfer(target)),
+                    vm_try!(value.end.clone().transfer(target)),
+                )))
+            }
+            Self::ControlFlow(value) => {
+                let value = match &*vm_try!(value.borrow_ref()) {
+                    ControlFlow::Continue(value) => {
+                        ControlFlow::Continue(vm_try!(value.clone().transfer(target)))
+                    }
+                    ControlFlow::Break(value) => {
+                        ControlFlow::Break(vm_try!(value.clone().transfer(target)))
+                    }
+                };
+
+                Self::ControlFlow(Shared::new(value))
+            }
+            Self::GeneratorState(value) => {
+                let value = match &*vm_try!(value.borrow_ref()) {
+                    GeneratorState::Yielded(value) => {
+                        GeneratorState::Yielded(vm_try!(value.clone().transfer(target)))
+                    }
+                    GeneratorState::Complete(value) => {
+                        GeneratorState::Complete(vm_try!(value.clone().transfer(target)))
+                    }
+                };
+
+                Self::GeneratorState(Shared::new(value))
+            }
+            Self::Option(value) => {
+                let value = match &*vm_try!(value.borrow_ref()) {
+                    Some(value) => Some(vm_try!(value.clone().transfer(target))),
+                    None => None,
+                };
+
+                Self::Option(Shared::new(value))
+            }
+            Self::Result(value) => {
+                let value = match &*vm_try!(value.borrow_ref()) {
+                    Ok(value) => Ok(vm_try!(value.clone().transfer(target))),
+                    Err(value) => Err(vm_try!(value.clone().transfer(target))),
+                };
+
+                Self::Result(Shared::new(value))
+            }
+            Self::EmptyStruct(value) => {
+                let value = vm_try!(value.borrow_ref());
+
+                Self::EmptyStruct(Shared::new(EmptyStruct {
+                    rtti: vm_try!(transfer_rtti(&value.rtti, target)),
+                }))
+            }
+            Self::TupleStruct(value) => {
+                let value = vm_try!(value.borrow_ref());
+
+                Self::TupleStruct(Shared::new(TupleStruct {
+                    rtti: vm_try!(transfer_rtti(&value.rtti, target)),
+                    data: vm_try!(transfer_tuple(&value.data, target)),
+                }))
+            }
+            Self::Struct(value) => {
+                let value = vm_try!(value.borrow_ref());
+
+                Self::Struct(Shared::new(Struct {
+                    rtti: vm_try!(transfer_rtti(&value.rtti, target)),
+                    data: vm_try!(transfer_object(&value.data, target)),
+                }))
+            }
+            Self::Variant(value) => {
+                let value = vm_try!(value.borrow_ref());
+
+                let Some(rtti) = target.unit().lookup_variant_rtti(value.rtti.hash) else {
+                    return err(VmErrorKind::TransferMissingType {
+                        actual: value.type_info(),
+                    });
+                };
+
+                let data = match &value.data {
+                    VariantData::Empty => VariantData::Empty,
+                    VariantData::Struct(data) => {
+                        VariantData::Struct(vm_try!(transfer_object(data, target)))
+                    }
+                    VariantData::Tuple(data) => {
+                        VariantData::Tuple(vm_try!(transfer_tuple(data, target)))
+                    }
+                };
+
+                Self::Variant(Shared::new(Variant {
+                    rtti: rtti.clone(),
+                    data,
+                }))
+            }
+            Self::Format(value) => Self::Format(Box::new(Format {
+                value: vm_try!(value.value.transfer(target)),
+                spec: value.spec,
+            })),
+            Self::Any(value) => {
+                {
+                    let any = vm_try!(value.borrow_ref());
+
+                    if !target.context().has_type(any.type_hash()) {
+                        return err(VmErrorKind::TransferMissingType {
+                            actual: any.type_info(),
+                        });
+                    }
+
+                    if !any.is_owned() {
+                        return err(VmErrorKind::TransferNotSupported {
+                            actual: any.type_info(),
+                        });
+                    }
+                }
+
+                Self::Any(Shared::new(vm_try!(value.take())))
+            }
+            value @ (Self::Future(..)
+            | Self::Stream(..)
+            | Self::Generator(..)
+            | Self::Function(..)
+            | Self::Iterator(..)) => {
+                return err(VmErrorKind::TransferNotSupported {
+                    actual: vm_try!(value.type_info()),
+                });
+            }
+        })
+    }
+
     /// Try to coerce value into a unit.
     #[inline]
     pub fn into_unit(self) -> VmResult<()> {
@@ -1392,6 +1721,10 @@ impl Value {
                 return Vec::hash_with(&vec, hasher, caller);
             }
             value => {
+                if vm_try!(value.hash_derived_with(hasher, caller)) {
+                    return VmResult::Ok(());
+                }
+
                 match vm_try!(caller.try_call_protocol_fn(Protocol::HASH, value.clone(), (hasher,)))
                 {
                     CallResult::Ok(value) => return <()>::from_value(value),
@@ -1406,6 +1739,67 @@ impl Value {
         })
     }
 
+    /// Hash a value of a type defined in a script which derives `Hash`.
+    ///
+    /// Returns `false` if the value is not of such a type.
+    #[cfg(feature = "std")]
+    fn hash_derived_with(
+        &self,
+        hasher: &mut Hasher,
+        caller: &mut impl ProtocolCaller,
+    ) -> VmResult<bool> {
+        match self {
+            Value::EmptyStruct(value) => {
+                let value = vm_try!(value.borrow_ref());
+
+                if value.rtti.derives.hash {
+                    derives::hash_type(value.rtti.hash, hasher);
+                    return VmResult::Ok(true);
+                }
+            }
+            Value::TupleStruct(value) => {
+                let value = vm_try!(value.borrow_ref());
+
+                if value.rtti.derives.hash {
+                    derives::hash_type(value.rtti.hash, hasher);
+                    vm_try!(Tuple::hash_with(&value.data, hasher, caller));
+                    return VmResult::Ok(true);
+                }
+            }
+            Value::Struct(value) => {
+                let value = vm_try!(value.borrow_ref());
+
+                if value.rtti.derives.hash {
+                    derives::hash_type(value.rtti.hash, hasher);
+                    vm_try!(derives::hash_struct(&value.data, hasher, caller));
+                    return VmResult::Ok(true);
+                }
+            }
+            Value::Variant(value) => {
+                let value = vm_try!(value.borrow_ref());
+
+                if value.rtti.derives.hash {
+                    derives::hash_type(value.rtti.hash, hasher);
+
+                    match &value.data {
+                        VariantData::Empty => {}
+                        VariantData::Tuple(data) => {
+                            vm_try!(Tuple::hash_with(data, hasher, caller));
+                        }
+                        VariantData::Struct(data) => {
+                            vm_try!(derives::hash_struct(data, hasher, caller));
+                        }
+                    }
+
+                    return VmResult::Ok(true);
+                }
+            }
+            _ => {}
+        }
+
+        VmResult::Ok(false)
+    }
+
     /// Perform a total equality test between two values.
     ///
     /// This is the basis for the eq operation (`==`).
@@ -1903,6 +2297,39 @@ impl Value {
     }
 }
 
+/// Look up the runtime type information of a struct in the unit of the target
+/// virtual machine.
+fn transfer_rtti(rtti: &Arc<Rtti>, target: &Vm) -> VmResult<Arc<Rtti>> {
+    match target.unit().lookup_rtti(rtti.hash) {
+        Some(rtti) => VmResult::Ok(rtti.clone()),
+        None => err(VmErrorKind::TransferMissingType {
+            actual: TypeInfo::Typed(rtti.clone()),
+        }),
+    }
+}
+
+/// Transfer every value in a tuple to the target virtual machine.
+fn transfer_tuple(tuple: &OwnedTuple, target: &Vm) -> VmResult<OwnedTuple> {
+    let mut values = vec::Vec::with_capacity(tuple.len());
+
+    for value in tuple.iter() {
+        values.push(vm_try!(value.clone().transfer(target)));
+    }
+
+    VmResult::Ok(OwnedTuple::from(values))
+}
+
+/// Transfer every value in an object to the target virtual machine.
+fn transfer_object(object: &Object, target: &Vm) -> VmResult<Object> {
+    let mut values = Object::with_capacity(object.len());
+
+    for (key, value) in object.iter() {
+        values.insert(key.clone(), vm_try!(value.clone().transfer(target)));
+    }
+
+    VmResult::Ok(values)
+}
+
 impl fmt::Debug for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
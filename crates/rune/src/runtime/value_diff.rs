@@ -0,0 +1,327 @@
+use core::fmt;
+
+use crate::no_std::collections::BTreeSet;
+use crate::no_std::prelude::*;
+
+use crate as rune;
+use crate::runtime::{
+    EnvProtocolCaller, Object, PathSegment, ProtocolCaller, Value, ValuePath, VariantData, VmResult,
+};
+use crate::Any;
+
+/// Compute the structural difference between two values.
+///
+/// Objects, structs, vectors, tuples and variants are compared field by field
+/// and element by element, while all other values are compared using the
+/// `PARTIAL_EQ` protocol. Values of different types are always considered to
+/// be changed.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{self, ChangeKind};
+///
+/// let a = rune::to_value(vec![1, 2, 3])?;
+/// let b = rune::to_value(vec![1, 4])?;
+///
+/// let diff = runtime::diff(&a, &b).into_result()?;
+/// assert_eq!(diff.len(), 2);
+/// assert!(matches!(diff.changes()[1].kind(), ChangeKind::Removed { .. }));
+///
+/// assert_eq!(diff.to_string(), "~ [1]: 2 -> 4\n- [2]: 3\n");
+/// # Ok::<_, rune::Error>(())
+/// ```
+pub fn diff(a: &Value, b: &Value) -> VmResult<ValueDiff> {
+    diff_with(a, b, &mut EnvProtocolCaller)
+}
+
+/// Compute the structural difference between two values using the given
+/// protocol caller.
+fn diff_with(a: &Value, b: &Value, caller: &mut impl ProtocolCaller) -> VmResult<ValueDiff> {
+    let mut differ = Differ {
+        path: Vec::new(),
+        changes: Vec::new(),
+        caller,
+    };
+
+    vm_try!(differ.value(a, b));
+
+    VmResult::Ok(ValueDiff {
+        changes: differ.changes,
+    })
+}
+
+/// The structural difference between two values, as produced by [diff].
+///
+/// The difference consists of the [Change]s needed to turn the first value
+/// into the second one, in the order of their paths. The [Display]
+/// implementation renders one change per line, which is suitable for
+/// reporting test failures:
+///
+/// ```text
+/// ~ a.b: 1 -> 2
+/// + a.c: 3
+/// - d[1]: "removed"
+/// ```
+///
+/// In scripts this is the `std::test::Diff` type.
+///
+/// [Display]: fmt::Display
+#[derive(Debug, Clone, Default, Any)]
+#[rune(module = crate, item = ::std::test, name = Diff)]
+pub struct ValueDiff {
+    changes: Vec<Change>,
+}
+
+impl ValueDiff {
+    /// Test if the values are equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The number of changes.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// The changes between the values.
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+}
+
+impl fmt::Display for ValueDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A single change in a [ValueDiff].
+#[derive(Debug, Clone)]
+pub struct Change {
+    path: ValuePath,
+    kind: ChangeKind,
+}
+
+impl Change {
+    /// The path of the value which changed.
+    ///
+    /// This is empty if the values themselves differ.
+    pub fn path(&self) -> &ValuePath {
+        &self.path
+    }
+
+    /// The kind of the change.
+    pub fn kind(&self) -> &ChangeKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = match &self.kind {
+            ChangeKind::Added { .. } => '+',
+            ChangeKind::Removed { .. } => '-',
+            ChangeKind::Changed { .. } => '~',
+        };
+
+        write!(f, "{sign} ")?;
+
+        if !self.path.segments().is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+
+        match &self.kind {
+            ChangeKind::Added { value } | ChangeKind::Removed { value } => write!(f, "{value:?}"),
+            ChangeKind::Changed { from, to } => write!(f, "{from:?} -> {to:?}"),
+        }
+    }
+}
+
+/// The kind of a [Change].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ChangeKind {
+    /// The value is only present in the second value.
+    Added {
+        /// The added value.
+        value: Value,
+    },
+    /// The value is only present in the first value.
+    Removed {
+        /// The removed value.
+        value: Value,
+    },
+    /// The value differs between the two values.
+    Changed {
+        /// The value in the first value.
+        from: Value,
+        /// The value in the second value.
+        to: Value,
+    },
+}
+
+struct Differ<'a, C> {
+    path: Vec<PathSegment>,
+    changes: Vec<Change>,
+    caller: &'a mut C,
+}
+
+impl<C> Differ<'_, C>
+where
+    C: ProtocolCaller,
+{
+    fn value(&mut self, a: &Value, b: &Value) -> VmResult<()> {
+        match (a, b) {
+            (Value::Vec(a), Value::Vec(b)) => {
+                let a = vm_try!(a.borrow_ref());
+                let b = vm_try!(b.borrow_ref());
+                return self.sequence(&a, &b);
+            }
+            (Value::Tuple(a), Value::Tuple(b)) => {
+                let a = vm_try!(a.borrow_ref());
+                let b = vm_try!(b.borrow_ref());
+                return self.sequence(&a, &b);
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                let a = vm_try!(a.borrow_ref());
+                let b = vm_try!(b.borrow_ref());
+                return self.fields(&a, &b);
+            }
+            (Value::Struct(a), Value::Struct(b)) => {
+                let a = vm_try!(a.borrow_ref());
+                let b = vm_try!(b.borrow_ref());
+
+                if a.rtti.hash == b.rtti.hash {
+                    return self.fields(&a.data, &b.data);
+                }
+            }
+            (Value::TupleStruct(a), Value::TupleStruct(b)) => {
+                let a = vm_try!(a.borrow_ref());
+                let b = vm_try!(b.borrow_ref());
+
+                if a.rtti.hash == b.rtti.hash {
+                    return self.sequence(&a.data, &b.data);
+                }
+            }
+            (Value::Variant(a), Value::Variant(b)) => {
+                let a = vm_try!(a.borrow_ref());
+                let b = vm_try!(b.borrow_ref());
+
+                if a.rtti().hash == b.rtti().hash {
+                    match (a.data(), b.data()) {
+                        (VariantData::Tuple(a), VariantData::Tuple(b)) => {
+                            return self.sequence(a, b);
+                        }
+                        (VariantData::Struct(a), VariantData::Struct(b)) => {
+                            return self.fields(a, b);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if !vm_try!(self.equal(a, b)) {
+            self.change(ChangeKind::Changed {
+                from: a.clone(),
+                to: b.clone(),
+            });
+        }
+
+        VmResult::Ok(())
+    }
+
+    fn sequence(&mut self, a: &[Value], b: &[Value]) -> VmResult<()> {
+        for (index, (a, b)) in a.iter().zip(b).enumerate() {
+            self.path.push(PathSegment::Index(index));
+            let result = self.value(a, b);
+            self.path.pop();
+            vm_try!(result);
+        }
+
+        for (index, value) in a.iter().enumerate().skip(b.len()) {
+            self.change_at(
+                PathSegment::Index(index),
+                ChangeKind::Removed {
+                    value: value.clone(),
+                },
+            );
+        }
+
+        for (index, value) in b.iter().enumerate().skip(a.len()) {
+            self.change_at(
+                PathSegment::Index(index),
+                ChangeKind::Added {
+                    value: value.clone(),
+                },
+            );
+        }
+
+        VmResult::Ok(())
+    }
+
+    fn fields(&mut self, a: &Object, b: &Object) -> VmResult<()> {
+        // NB: fields are visited in order, so that the changes are stable.
+        let keys = a.keys().chain(b.keys()).collect::<BTreeSet<_>>();
+
+        for key in keys {
+            let segment = PathSegment::Field(key.clone());
+
+            match (a.get(key.as_str()), b.get(key.as_str())) {
+                (Some(a), Some(b)) => {
+                    self.path.push(segment);
+                    let result = self.value(a, b);
+                    self.path.pop();
+                    vm_try!(result);
+                }
+                (Some(value), None) => {
+                    self.change_at(
+                        segment,
+                        ChangeKind::Removed {
+                            value: value.clone(),
+                        },
+                    );
+                }
+                (None, Some(value)) => {
+                    self.change_at(
+                        segment,
+                        ChangeKind::Added {
+                            value: value.clone(),
+                        },
+                    );
+                }
+                (None, None) => {}
+            }
+        }
+
+        VmResult::Ok(())
+    }
+
+    fn equal(&mut self, a: &Value, b: &Value) -> VmResult<bool> {
+        // NB: comparing values of different types is an error.
+        if vm_try!(a.type_hash()) != vm_try!(b.type_hash()) {
+            return VmResult::Ok(false);
+        }
+
+        Value::partial_eq_with(a, b, &mut *self.caller)
+    }
+
+    fn change_at(&mut self, segment: PathSegment, kind: ChangeKind) {
+        self.path.push(segment);
+        self.change(kind);
+        self.path.pop();
+    }
+
+    fn change(&mut self, kind: ChangeKind) {
+        self.changes.push(Change {
+            path: ValuePath::from_segments(self.path.clone()),
+            kind,
+        });
+    }
+}
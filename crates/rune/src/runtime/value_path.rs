@@ -0,0 +1,514 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::no_std::prelude::*;
+
+use crate::runtime::{AccessError, FromValue, TypeInfo, Value, VariantData, VmError};
+
+/// A path into nested values, like `a.b[2].c`.
+///
+/// Paths can either be written using a dotted syntax or as a [JSON pointer].
+///
+/// In the dotted syntax fields are separated by `.` and indexes are written
+/// inside of brackets like `[2]`. Fields which contain special characters can
+/// be quoted, like `a["b.c"]`.
+///
+/// Paths which start with `/` are parsed as [JSON pointers], like `/a/b/2/c`.
+///
+/// Fields which are numbers can also be used to index into sequences, so `a.0`
+/// and `/a/0` both access the first element in the tuple or vector `a`.
+///
+/// [JSON pointer]: https://datatracker.ietf.org/doc/html/rfc6901
+/// [JSON pointers]: https://datatracker.ietf.org/doc/html/rfc6901
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::{PathSegment, ValuePath};
+///
+/// let path = ValuePath::parse("a.b[2].c")?;
+///
+/// assert_eq!(path.segments(), &[
+///     PathSegment::Field(String::from("a")),
+///     PathSegment::Field(String::from("b")),
+///     PathSegment::Index(2),
+///     PathSegment::Field(String::from("c")),
+/// ]);
+///
+/// assert_eq!(ValuePath::parse("/a/b/2/c")?.to_string(), "a.b.2.c");
+/// # Ok::<_, rune::runtime::ValuePathError>(())
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ValuePath {
+    segments: Vec<PathSegment>,
+}
+
+impl ValuePath {
+    /// Parse a path.
+    pub fn parse(path: &str) -> Result<Self, ValuePathError> {
+        let segments = if path.starts_with('/') {
+            parse_pointer(path)
+        } else {
+            parse_dotted(path)
+        };
+
+        let segments = segments.map_err(|(position, message)| {
+            ValuePathError::new(
+                path.into(),
+                ValuePathErrorKind::Syntax { position, message },
+            )
+        })?;
+
+        Ok(Self { segments })
+    }
+
+    /// Construct a path from its segments.
+    pub(crate) fn from_segments(segments: Vec<PathSegment>) -> Self {
+        Self { segments }
+    }
+
+    /// Access the segments of the path.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Get the value at this path inside of `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::ValuePath;
+    ///
+    /// let value = rune::to_value(vec![(1, 2), (3, 4)])?;
+    /// let path = ValuePath::parse("[1].0")?;
+    /// assert_eq!(path.get(&value)?.into_integer().into_result()?, 3);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn get(&self, value: &Value) -> Result<Value, ValuePathError> {
+        let mut current = value.clone();
+
+        for (n, segment) in self.segments.iter().enumerate() {
+            current = match segment_get(&current, segment) {
+                Ok(value) => value,
+                Err(kind) => {
+                    let path = ValuePath {
+                        segments: self.segments[..=n].to_vec(),
+                    };
+
+                    return Err(ValuePathError::new(path.to_string(), kind));
+                }
+            };
+        }
+
+        Ok(current)
+    }
+
+    /// Get the value at this path inside of `value` and convert it into `T`.
+    pub fn get_as<T>(&self, value: &Value) -> Result<T, ValuePathError>
+    where
+        T: FromValue,
+    {
+        let value = self.get(value)?;
+
+        match T::from_value(value).into_result() {
+            Ok(value) => Ok(value),
+            Err(error) => Err(ValuePathError::new(
+                self.to_string(),
+                ValuePathErrorKind::VmError { error },
+            )),
+        }
+    }
+}
+
+impl FromStr for ValuePath {
+    type Err = ValuePathError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for ValuePath {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_segments(f, &self.segments)
+    }
+}
+
+/// A single segment in a [ValuePath].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PathSegment {
+    /// Access a field in an object or a struct, like `.name` or `["name"]`.
+    Field(String),
+    /// Access an element in a vector or a tuple, like `[2]`.
+    Index(usize),
+}
+
+/// An error raised when parsing or accessing a [ValuePath].
+#[derive(Debug)]
+pub struct ValuePathError {
+    path: String,
+    kind: ValuePathErrorKind,
+}
+
+impl ValuePathError {
+    fn new(path: String, kind: ValuePathErrorKind) -> Self {
+        Self { path, kind }
+    }
+
+    /// The path up until and including the segment which failed.
+    ///
+    /// For syntax errors this is the whole path being parsed.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The kind of the error.
+    pub fn kind(&self) -> &ValuePathErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ValuePathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ValuePathErrorKind::Syntax { position, message } => {
+                write!(
+                    f,
+                    "Bad path `{}` at position {position}: {message}",
+                    self.path
+                )
+            }
+            kind => write!(f, "{kind} at `{}`", self.path),
+        }
+    }
+}
+
+impl crate::no_std::error::Error for ValuePathError {
+    fn source(&self) -> Option<&(dyn crate::no_std::error::Error + 'static)> {
+        match &self.kind {
+            ValuePathErrorKind::AccessError { error } => Some(error),
+            ValuePathErrorKind::VmError { error } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// The kind of a [ValuePathError].
+#[derive(Debug)]
+#[allow(missing_docs)]
+#[non_exhaustive]
+pub enum ValuePathErrorKind {
+    /// The path could not be parsed.
+    Syntax {
+        position: usize,
+        message: &'static str,
+    },
+    /// The field is missing.
+    MissingField { field: String },
+    /// The index is out of bounds.
+    MissingIndex { index: usize, length: usize },
+    /// The value can't be accessed using the segment.
+    Unsupported {
+        segment: PathSegment,
+        actual: TypeInfo,
+    },
+    /// The value could not be accessed.
+    AccessError { error: AccessError },
+    /// An error raised by the virtual machine, like when converting the value.
+    VmError { error: VmError },
+}
+
+impl fmt::Display for ValuePathErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValuePathErrorKind::Syntax { message, .. } => message.fmt(f),
+            ValuePathErrorKind::MissingField { field } => {
+                write!(f, "Missing field `{field}`")
+            }
+            ValuePathErrorKind::MissingIndex { index, length } => {
+                write!(f, "Index `{index}` out of bounds for length `{length}`")
+            }
+            ValuePathErrorKind::Unsupported { segment, actual } => match segment {
+                PathSegment::Field(field) => {
+                    write!(f, "Can't get field `{field}` from value of type `{actual}`")
+                }
+                PathSegment::Index(index) => {
+                    write!(f, "Can't get index `{index}` from value of type `{actual}`")
+                }
+            },
+            ValuePathErrorKind::AccessError { error } => error.fmt(f),
+            ValuePathErrorKind::VmError { error } => error.fmt(f),
+        }
+    }
+}
+
+impl From<AccessError> for ValuePathErrorKind {
+    #[inline]
+    fn from(error: AccessError) -> Self {
+        ValuePathErrorKind::AccessError { error }
+    }
+}
+
+/// Get the value of a single segment.
+fn segment_get(value: &Value, segment: &PathSegment) -> Result<Value, ValuePathErrorKind> {
+    match (value, segment) {
+        (Value::Object(object), PathSegment::Field(field)) => {
+            field_get(object.borrow_ref()?.get(field.as_str()), field)
+        }
+        (Value::Struct(object), PathSegment::Field(field)) => {
+            field_get(object.borrow_ref()?.get(field.as_str()), field)
+        }
+        (Value::Variant(variant), segment) => match (&variant.borrow_ref()?.data, segment) {
+            (VariantData::Struct(object), PathSegment::Field(field)) => {
+                field_get(object.get(field.as_str()), field)
+            }
+            (VariantData::Tuple(tuple), segment) => match index_of(segment) {
+                Some(index) => index_get(tuple, index),
+                None => unsupported(value, segment),
+            },
+            _ => unsupported(value, segment),
+        },
+        (Value::Vec(vec), segment) => match index_of(segment) {
+            Some(index) => index_get(&vec.borrow_ref()?, index),
+            None => unsupported(value, segment),
+        },
+        (Value::Tuple(tuple), segment) => match index_of(segment) {
+            Some(index) => index_get(&tuple.borrow_ref()?, index),
+            None => unsupported(value, segment),
+        },
+        (Value::TupleStruct(tuple), segment) => match index_of(segment) {
+            Some(index) => index_get(tuple.borrow_ref()?.data(), index),
+            None => unsupported(value, segment),
+        },
+        (Value::EmptyTuple, segment) => match index_of(segment) {
+            Some(index) => index_get(&[], index),
+            None => unsupported(value, segment),
+        },
+        _ => unsupported(value, segment),
+    }
+}
+
+fn field_get(value: Option<&Value>, field: &str) -> Result<Value, ValuePathErrorKind> {
+    match value {
+        Some(value) => Ok(value.clone()),
+        None => Err(ValuePathErrorKind::MissingField {
+            field: field.into(),
+        }),
+    }
+}
+
+fn index_get(values: &[Value], index: usize) -> Result<Value, ValuePathErrorKind> {
+    match values.get(index) {
+        Some(value) => Ok(value.clone()),
+        None => Err(ValuePathErrorKind::MissingIndex {
+            index,
+            length: values.len(),
+        }),
+    }
+}
+
+/// Fields which are numbers can be used to index into sequences.
+fn index_of(segment: &PathSegment) -> Option<usize> {
+    match segment {
+        PathSegment::Field(field) => parse_index(field),
+        PathSegment::Index(index) => Some(*index),
+    }
+}
+
+fn unsupported(value: &Value, segment: &PathSegment) -> Result<Value, ValuePathErrorKind> {
+    let actual = match value.type_info().into_result() {
+        Ok(actual) => actual,
+        Err(error) => return Err(ValuePathErrorKind::VmError { error }),
+    };
+
+    Err(ValuePathErrorKind::Unsupported {
+        segment: segment.clone(),
+        actual,
+    })
+}
+
+/// Parse an index, which must be a plain decimal number.
+fn parse_index(s: &str) -> Option<usize> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    s.parse().ok()
+}
+
+/// Parse a path using the dotted syntax.
+fn parse_dotted(path: &str) -> Result<Vec<PathSegment>, (usize, &'static str)> {
+    let mut segments = Vec::new();
+    let mut it = path.char_indices().peekable();
+
+    if path.is_empty() {
+        return Ok(segments);
+    }
+
+    loop {
+        let first = segments.is_empty();
+
+        let Some(&(start, c)) = it.peek() else {
+            return Err((path.len(), "unexpected end of path"));
+        };
+
+        if c == '[' {
+            it.next();
+            segments.push(parse_bracket(path, start, &mut it)?);
+        } else {
+            if !first {
+                match c {
+                    '.' => {
+                        it.next();
+                    }
+                    _ => return Err((start, "expected `.` or `[`")),
+                }
+            }
+
+            let start = it.peek().map_or(path.len(), |&(n, _)| n);
+            let mut end = path.len();
+
+            while let Some(&(n, c)) = it.peek() {
+                match c {
+                    '.' | '[' => {
+                        end = n;
+                        break;
+                    }
+                    ']' | '"' => return Err((n, "unexpected character in field")),
+                    _ => {
+                        it.next();
+                    }
+                }
+            }
+
+            if start == end {
+                return Err((start, "expected field"));
+            }
+
+            segments.push(PathSegment::Field(path[start..end].into()));
+        }
+
+        if it.peek().is_none() {
+            return Ok(segments);
+        }
+    }
+}
+
+/// Parse the inside of a bracket, like `[2]` or `["field"]`.
+fn parse_bracket(
+    path: &str,
+    start: usize,
+    it: &mut core::iter::Peekable<core::str::CharIndices<'_>>,
+) -> Result<PathSegment, (usize, &'static str)> {
+    let segment = if let Some(&(_, '"')) = it.peek() {
+        it.next();
+        let mut field = String::new();
+
+        loop {
+            match it.next() {
+                Some((_, '"')) => break,
+                Some((_, '\\')) => match it.next() {
+                    Some((_, c @ ('"' | '\\'))) => field.push(c),
+                    Some((n, _)) => return Err((n, "unsupported escape sequence")),
+                    None => return Err((path.len(), "unterminated string")),
+                },
+                Some((_, c)) => field.push(c),
+                None => return Err((path.len(), "unterminated string")),
+            }
+        }
+
+        PathSegment::Field(field)
+    } else {
+        let index_start = start + 1;
+        let mut index_end = path.len();
+
+        while let Some(&(n, c)) = it.peek() {
+            if c == ']' {
+                index_end = n;
+                break;
+            }
+
+            it.next();
+        }
+
+        match parse_index(&path[index_start..index_end]) {
+            Some(index) => PathSegment::Index(index),
+            None => return Err((index_start, "expected index or quoted field")),
+        }
+    };
+
+    match it.next() {
+        Some((_, ']')) => Ok(segment),
+        Some((n, _)) => Err((n, "expected `]`")),
+        None => Err((path.len(), "expected `]`")),
+    }
+}
+
+/// Parse a path as a JSON pointer.
+fn parse_pointer(path: &str) -> Result<Vec<PathSegment>, (usize, &'static str)> {
+    let mut segments = Vec::new();
+    let mut offset = 1;
+
+    for token in path[1..].split('/') {
+        let mut field = String::with_capacity(token.len());
+        let mut it = token.char_indices();
+
+        while let Some((n, c)) = it.next() {
+            if c != '~' {
+                field.push(c);
+                continue;
+            }
+
+            match it.next() {
+                Some((_, '0')) => field.push('~'),
+                Some((_, '1')) => field.push('/'),
+                _ => return Err((offset + n, "expected `~0` or `~1`")),
+            }
+        }
+
+        segments.push(PathSegment::Field(field));
+        offset += token.len() + 1;
+    }
+
+    Ok(segments)
+}
+
+fn write_segments(f: &mut fmt::Formatter<'_>, segments: &[PathSegment]) -> fmt::Result {
+    for (n, segment) in segments.iter().enumerate() {
+        match segment {
+            PathSegment::Field(field) if is_plain(field) => {
+                if n != 0 {
+                    f.write_str(".")?;
+                }
+
+                f.write_str(field)?;
+            }
+            PathSegment::Field(field) => {
+                f.write_str("[\"")?;
+
+                for c in field.chars() {
+                    if matches!(c, '"' | '\\') {
+                        f.write_str("\\")?;
+                    }
+
+                    write!(f, "{c}")?;
+                }
+
+                f.write_str("\"]")?;
+            }
+            PathSegment::Index(index) => {
+                write!(f, "[{index}]")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Test if the field can be written without quotes.
+fn is_plain(field: &str) -> bool {
+    !field.is_empty() && !field.contains(['.', '[', ']', '"'])
+}
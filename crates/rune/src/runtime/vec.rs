@@ -14,8 +14,8 @@ use crate as rune;
 #[cfg(feature = "std")]
 use crate::runtime::Hasher;
 use crate::runtime::{
-    Formatter, FromValue, Iterator, ProtocolCaller, RawRef, Ref, Shared, ToValue, UnsafeToRef,
-    Value, VmErrorKind, VmResult,
+    Formatter, FromValue, Iterator, Protocol, ProtocolCaller, RawRef, Ref, Shared, ToValue,
+    UnsafeToRef, Value, VmErrorKind, VmResult,
 };
 use crate::Any;
 
@@ -84,6 +84,61 @@ impl Vec {
         self.inner
     }
 
+    /// Construct a vector by copying a slice of primitive values in bulk.
+    ///
+    /// This avoids converting each element individually through [`ToValue`],
+    /// which is considerably faster for large numeric slices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::Vec;
+    ///
+    /// let vec = Vec::from_slice(&[1.0f64, 2.0, 3.0]);
+    /// assert_eq!(vec.len(), 3);
+    /// assert_eq!(vec.to_primitives::<f64>().into_result()?, [1.0, 2.0, 3.0]);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn from_slice<T>(values: &[T]) -> Self
+    where
+        T: Primitive,
+    {
+        Self {
+            inner: values.iter().map(|&value| value.into()).collect(),
+        }
+    }
+
+    /// Copy the elements of the vector into a vector of primitive values in
+    /// bulk.
+    ///
+    /// Unlike converting through [`FromValue`], this leaves the vector intact
+    /// and errors if any element is not of the expected type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::runtime::{Value, Vec};
+    ///
+    /// let mut vec = Vec::from_slice(&[1i64, 2, 3]);
+    /// assert_eq!(vec.to_primitives::<i64>().into_result()?, [1, 2, 3]);
+    ///
+    /// vec.push(Value::Bool(true));
+    /// assert!(vec.to_primitives::<i64>().into_result().is_err());
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn to_primitives<T>(&self) -> VmResult<vec::Vec<T>>
+    where
+        T: Primitive,
+    {
+        let mut output = vec::Vec::with_capacity(self.inner.len());
+
+        for value in &self.inner {
+            output.push(vm_try!(T::from_value_ref(value)));
+        }
+
+        VmResult::Ok(output)
+    }
+
     /// Returns `true` if the vector contains no elements.
     ///
     /// # Examples
@@ -178,6 +233,22 @@ impl Vec {
         self.inner.remove(index)
     }
 
+    /// Removes the element at the specified index from a dynamic vector,
+    /// replacing it with the last element.
+    ///
+    /// This does not preserve ordering, but is *O*(1).
+    pub fn swap_remove(&mut self, index: usize) -> Value {
+        self.inner.swap_remove(index)
+    }
+
+    /// Splits the vector into two at the given index, returning a newly
+    /// allocated vector containing the elements in the range `[at, len)`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        Self {
+            inner: self.inner.split_off(at),
+        }
+    }
+
     /// Clears the vector, removing all values.
     ///
     /// Note that this method has no effect on the allocated capacity of the
@@ -204,6 +275,43 @@ impl Vec {
         VmResult::Ok(())
     }
 
+    /// Flatten a vector of values which implement the into_iter protocol
+    /// into a single vector.
+    ///
+    /// If every element is itself a vector, their elements are copied over
+    /// directly without going through the into_iter protocol.
+    pub fn concat(&self) -> VmResult<Self> {
+        let mut vecs = vec::Vec::with_capacity(self.inner.len());
+
+        for value in &self.inner {
+            let Value::Vec(vec) = value else {
+                vecs.clear();
+                break;
+            };
+
+            vecs.push(vm_try!(vec.borrow_ref()));
+        }
+
+        if vecs.len() == self.inner.len() {
+            let len = vecs.iter().map(|vec| vec.len()).sum();
+            let mut output = vec::Vec::with_capacity(len);
+
+            for vec in &vecs {
+                output.extend_from_slice(vec);
+            }
+
+            return VmResult::Ok(Self { inner: output });
+        }
+
+        let mut output = Self::new();
+
+        for value in &self.inner {
+            vm_try!(output.extend(value.clone()));
+        }
+
+        VmResult::Ok(output)
+    }
+
     /// Convert into a rune iterator.
     pub fn iter_ref(this: Ref<[Value]>) -> Iterator {
         Iterator::from_double_ended("std::vec::Iter", Iter::new(this))
@@ -332,6 +440,82 @@ impl Vec {
         VmResult::Ok(Ordering::Equal)
     }
 
+    /// Sum the values in the slice.
+    ///
+    /// Slices which only contain integers or only contain floats are summed
+    /// directly, anything else is summed using the [`Protocol::ADD`] protocol.
+    pub(crate) fn sum_with(this: &[Value], caller: &mut impl ProtocolCaller) -> VmResult<Value> {
+        match Numeric::of(this) {
+            Some(Numeric::Integer) => {
+                let mut sum = 0i64;
+
+                for n in integers(this) {
+                    let Some(out) = sum.checked_add(n) else {
+                        return VmResult::err(VmErrorKind::Overflow);
+                    };
+
+                    sum = out;
+                }
+
+                VmResult::Ok(Value::Integer(sum))
+            }
+            Some(Numeric::Float) => VmResult::Ok(Value::Float(floats(this).sum())),
+            None => fold_with(this, Protocol::ADD, caller),
+        }
+    }
+
+    /// Multiply the values in the slice.
+    ///
+    /// Slices which only contain integers or only contain floats are
+    /// multiplied directly, anything else is multiplied using the
+    /// [`Protocol::MUL`] protocol.
+    pub(crate) fn product_with(
+        this: &[Value],
+        caller: &mut impl ProtocolCaller,
+    ) -> VmResult<Value> {
+        match Numeric::of(this) {
+            Some(Numeric::Integer) => {
+                let mut product = 1i64;
+
+                for n in integers(this) {
+                    let Some(out) = product.checked_mul(n) else {
+                        return VmResult::err(VmErrorKind::Overflow);
+                    };
+
+                    product = out;
+                }
+
+                VmResult::Ok(Value::Integer(product))
+            }
+            Some(Numeric::Float) => VmResult::Ok(Value::Float(floats(this).product())),
+            None => fold_with(this, Protocol::MUL, caller),
+        }
+    }
+
+    /// Find the smallest value in the slice, preferring the first one if
+    /// several are equally small.
+    ///
+    /// Slices which only contain integers or only contain floats are compared
+    /// directly, anything else is compared using [`Value::cmp_with`].
+    pub(crate) fn min_with(
+        this: &[Value],
+        caller: &mut impl ProtocolCaller,
+    ) -> VmResult<Option<Value>> {
+        select_with(this, Ordering::is_lt, caller)
+    }
+
+    /// Find the largest value in the slice, preferring the last one if
+    /// several are equally large.
+    ///
+    /// Slices which only contain integers or only contain floats are compared
+    /// directly, anything else is compared using [`Value::cmp_with`].
+    pub(crate) fn max_with(
+        this: &[Value],
+        caller: &mut impl ProtocolCaller,
+    ) -> VmResult<Option<Value>> {
+        select_with(this, Ordering::is_ge, caller)
+    }
+
     /// This is a common get implementation that can be used across linear
     /// types, such as vectors and tuples.
     pub(crate) fn index_get(this: &[Value], index: Value) -> VmResult<Option<Value>> {
@@ -396,6 +580,120 @@ impl Vec {
     }
 }
 
+/// The numeric type all values in a slice share, if any.
+enum Numeric {
+    Integer,
+    Float,
+}
+
+impl Numeric {
+    fn of(values: &[Value]) -> Option<Self> {
+        match values.first() {
+            None | Some(Value::Integer(..)) => values
+                .iter()
+                .all(|v| matches!(v, Value::Integer(..)))
+                .then_some(Numeric::Integer),
+            Some(Value::Float(..)) => values
+                .iter()
+                .all(|v| matches!(v, Value::Float(..)))
+                .then_some(Numeric::Float),
+            Some(..) => None,
+        }
+    }
+}
+
+/// Iterate over the integers in a slice which is known to only contain
+/// integers.
+fn integers(values: &[Value]) -> impl core::iter::Iterator<Item = i64> + '_ {
+    values.iter().filter_map(|v| match v {
+        Value::Integer(n) => Some(*n),
+        _ => None,
+    })
+}
+
+/// Iterate over the floats in a slice which is known to only contain floats.
+fn floats(values: &[Value]) -> impl core::iter::Iterator<Item = f64> + '_ {
+    values.iter().filter_map(|v| match v {
+        Value::Float(n) => Some(*n),
+        _ => None,
+    })
+}
+
+/// Fold a non-empty slice of values using the given binary protocol.
+fn fold_with(
+    values: &[Value],
+    protocol: Protocol,
+    caller: &mut impl ProtocolCaller,
+) -> VmResult<Value> {
+    let mut it = values.iter();
+
+    let Some(first) = it.next() else {
+        return VmResult::Ok(Value::EmptyTuple);
+    };
+
+    let mut acc = first.clone();
+
+    for value in it {
+        acc = vm_try!(caller.call_protocol_fn(protocol, acc, (value.clone(),)));
+    }
+
+    VmResult::Ok(acc)
+}
+
+/// Select a value from the slice, where `replace` determines if a value
+/// replaces the current selection depending on how it compares to it.
+fn select_with(
+    values: &[Value],
+    replace: fn(Ordering) -> bool,
+    caller: &mut impl ProtocolCaller,
+) -> VmResult<Option<Value>> {
+    match Numeric::of(values) {
+        Some(Numeric::Integer) => {
+            let selected = vm_try!(select(integers(values), replace, |a, b| {
+                VmResult::Ok(a.cmp(b))
+            }));
+
+            VmResult::Ok(selected.map(Value::Integer))
+        }
+        Some(Numeric::Float) => {
+            let selected = vm_try!(select(floats(values), replace, |a, b| {
+                let Some(ordering) = a.partial_cmp(b) else {
+                    return VmResult::err(VmErrorKind::IllegalFloatComparison { lhs: *a, rhs: *b });
+                };
+
+                VmResult::Ok(ordering)
+            }));
+
+            VmResult::Ok(selected.map(Value::Float))
+        }
+        None => {
+            let selected = vm_try!(select(values.iter(), replace, |a, b| {
+                Value::cmp_with(a, b, caller)
+            }));
+
+            VmResult::Ok(selected.cloned())
+        }
+    }
+}
+
+fn select<T>(
+    mut values: impl core::iter::Iterator<Item = T>,
+    replace: fn(Ordering) -> bool,
+    mut cmp: impl FnMut(&T, &T) -> VmResult<Ordering>,
+) -> VmResult<Option<T>> {
+    let Some(mut selected) = values.next() else {
+        return VmResult::Ok(None);
+    };
+
+    for value in values {
+        if replace(vm_try!(cmp(&value, &selected))) {
+            selected = value;
+        }
+    }
+
+    VmResult::Ok(Some(selected))
+}
+
 impl fmt::Debug for Vec {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(&*self.inner).finish()
@@ -464,6 +762,42 @@ impl From<Box<[Value]>> for Vec {
     }
 }
 
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A primitive type which can be copied in and out of a [`Vec`] in bulk.
+///
+/// See [`Vec::from_slice`] and [`Vec::to_primitives`].
+pub trait Primitive: Copy + Into<Value> + sealed::Sealed {
+    /// Read the primitive from a value.
+    #[doc(hidden)]
+    fn from_value_ref(value: &Value) -> VmResult<Self>;
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty => $as:ident),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl Primitive for $ty {
+                #[inline]
+                fn from_value_ref(value: &Value) -> VmResult<Self> {
+                    value.$as()
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive! {
+    bool => as_bool,
+    u8 => as_byte,
+    char => as_char,
+    i64 => as_integer,
+    f64 => as_float,
+}
+
 impl<T> FromValue for vec::Vec<T>
 where
     T: FromValue,
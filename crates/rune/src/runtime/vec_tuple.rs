@@ -52,4 +52,4 @@ macro_rules! impl_from_value_tuple_vec {
     };
 }
 
-repeat_macro!(impl_from_value_tuple_vec);
+repeat_macro_extended!(impl_from_value_tuple_vec);
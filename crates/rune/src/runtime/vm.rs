@@ -1,6 +1,6 @@
 use core::cmp::Ordering;
 use core::fmt;
-use core::mem::{replace, swap};
+use core::mem::{replace, swap, take};
 use core::ops;
 use core::slice;
 
@@ -9,19 +9,28 @@ use crate::modules::{option, result};
 use crate::no_std::prelude::*;
 use crate::no_std::sync::Arc;
 use crate::no_std::vec;
+use crate::runtime::borrow;
 use crate::runtime::budget;
 use crate::runtime::future::SelectFuture;
-use crate::runtime::unit::{UnitFn, UnitStorage};
+use crate::runtime::unit::{PatchError, UnitFn, UnitStorage, VerifyError};
 use crate::runtime::{
-    self, Args, Awaited, BorrowMut, Bytes, Call, ControlFlow, EmptyStruct, Format, FormatSpec,
-    Formatter, FromValue, Function, Future, Generator, GuardedArgs, Inst, InstAddress,
-    InstAssignOp, InstOp, InstRange, InstTarget, InstValue, InstVariant, Object, OwnedTuple, Panic,
-    Protocol, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
-    RuntimeContext, Select, Shared, Stack, Stream, Struct, Type, TypeCheck, TypeOf, Unit, Value,
-    Variant, VariantData, Vec, VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmResult,
-    VmSendExecution,
+    self, Args, Awaited, BorrowMode, BorrowMut, BorrowWait, Bytes, Call, CancellationToken,
+    ControlFlow, EmptyStruct, ExecutionReport, Format, FormatSpec, Formatter, FromValue, Function,
+    FunctionHandler, Future, Generator, GuardedArgs, Inst, InstAddress, InstAssignOp, InstOp,
+    InstRange, InstTarget, InstValue, InstVariant, Interner, Limit, Limits, Memo, Mocks, Object,
+    ObjectKey, OwnedTuple, Panic, PanicReason, Protocol, Range, RangeFrom, RangeFull,
+    RangeInclusive, RangeTo, RangeToInclusive, RuntimeContext, Select, Shared, Signals, Stack,
+    Stream, Struct, Type, TypeCheck, TypeOf, Unit, Value, Variant, VariantData, Vec, VmCall,
+    VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmResult, VmSendExecution,
+    VmSnapshot,
 };
 
+#[cfg(feature = "gc")]
+use crate::runtime::Gc;
+
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
 /// Small helper function to build errors.
 fn err<T, E>(error: E) -> VmResult<T>
 where
@@ -52,6 +61,9 @@ enum TargetValue<'a, 'b> {
     Value(&'a mut Value, Value),
     /// Fallback to a different kind of operation.
     Fallback(TargetFallback<'b>),
+    /// An index which can only be accessed through the [Protocol::INDEX_GET]
+    /// and [Protocol::INDEX_SET] protocols.
+    Index(&'b Value, Value, Value),
 }
 
 macro_rules! target_value {
@@ -83,6 +95,17 @@ macro_rules! target_value {
                     TargetValue::Fallback(TargetFallback::Field(&$lhs, field.hash(), rhs))
                 }
             }
+            InstTarget::Index => {
+                let index = vm_try!($vm.stack.pop());
+                $lhs = vm_try!($vm.stack.pop());
+
+                if let Some(value) = vm_try!(Vm::try_index_get_mut(&$lhs, &index)) {
+                    $guard = value;
+                    TargetValue::Value(&mut *$guard, rhs)
+                } else {
+                    TargetValue::Index(&$lhs, index, rhs)
+                }
+            }
         }
     }};
 }
@@ -102,6 +125,32 @@ pub struct Vm {
     stack: Stack,
     /// Frames relative to the stack.
     call_frames: vec::Vec<CallFrame>,
+    /// Statistics of the current execution.
+    report: ExecutionReport,
+    /// Identity of the virtual machine, passed to panic hooks.
+    id: u64,
+    /// Cached results of memoized functions.
+    memo: Memo,
+    /// Collector of cyclic garbage.
+    #[cfg(feature = "gc")]
+    gc: Gc,
+    /// Limits enforced on executions.
+    limits: Limits,
+    /// The number of instructions after which asynchronous executions yield.
+    yield_interval: Option<usize>,
+    /// Token checked to cancel executions.
+    cancellation: Option<CancellationToken>,
+    /// The deadline of the current call, as set by [Vm::call_with_timeout].
+    #[cfg(feature = "std")]
+    deadline: Option<Instant>,
+    /// Interner of object keys inserted by scripts.
+    interner: Interner,
+    /// The base unit which the unit is layered on top of.
+    base: Option<Arc<Unit>>,
+    /// Handlers connected to signals by scripts.
+    signals: Signals,
+    /// Context functions shadowed by tests.
+    mocks: Mocks,
 }
 
 impl Vm {
@@ -110,6 +159,19 @@ impl Vm {
         Self::with_stack(context, unit, Stack::new())
     }
 
+    /// Construct a new virtual machine, requiring that the unit passes
+    /// [Unit::verify].
+    ///
+    /// This should be used over [Vm::new] when the unit hasn't been produced
+    /// by the compiler, like when it has been deserialized.
+    pub fn new_verified(
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+    ) -> Result<Self, VerifyError> {
+        unit.verify()?;
+        Ok(Self::new(context, unit))
+    }
+
     /// Construct a new virtual machine with a custom stack.
     pub const fn with_stack(context: Arc<RuntimeContext>, unit: Arc<Unit>, stack: Stack) -> Self {
         Self {
@@ -119,9 +181,67 @@ impl Vm {
             last_ip_len: 0,
             stack,
             call_frames: vec::Vec::new(),
+            report: ExecutionReport::new(None),
+            id: 0,
+            memo: Memo::new(),
+            #[cfg(feature = "gc")]
+            gc: Gc::new(),
+            limits: Limits::new(),
+            yield_interval: None,
+            cancellation: None,
+            #[cfg(feature = "std")]
+            deadline: None,
+            interner: Interner::new(),
+            base: None,
+            signals: Signals::new(),
+            mocks: Mocks::new(),
         }
     }
 
+    /// Construct a virtual machine from its raw parts.
+    pub(crate) fn from_parts(
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+        ip: usize,
+        last_ip_len: u8,
+        stack: Stack,
+        call_frames: vec::Vec<CallFrame>,
+    ) -> Self {
+        Self {
+            context,
+            unit,
+            ip,
+            last_ip_len,
+            stack,
+            call_frames,
+            report: ExecutionReport::new(None),
+            id: 0,
+            memo: Memo::new(),
+            #[cfg(feature = "gc")]
+            gc: Gc::new(),
+            limits: Limits::new(),
+            yield_interval: None,
+            cancellation: None,
+            #[cfg(feature = "std")]
+            deadline: None,
+            interner: Interner::new(),
+            base: None,
+            signals: Signals::new(),
+            mocks: Mocks::new(),
+        }
+    }
+
+    /// Restore a virtual machine from a [VmSnapshot].
+    ///
+    /// This fails if the snapshot was taken with a different unit or context.
+    pub fn restore(
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+        snapshot: &VmSnapshot,
+    ) -> Result<Self, VmError> {
+        snapshot.restore(context, unit).into_result()
+    }
+
     /// Construct a vm with a default empty [RuntimeContext]. This is useful
     /// when the [Unit] was constructed with an empty
     /// [Context][crate::compile::Context].
@@ -129,6 +249,20 @@ impl Vm {
         Self::new(Default::default(), unit)
     }
 
+    /// Set the identity of the virtual machine.
+    ///
+    /// This is passed to the [PanicHook][crate::runtime::PanicHook] of the
+    /// context to identify which virtual machine panicked, and defaults to
+    /// `0`.
+    pub fn set_id(&mut self, id: u64) {
+        self.id = id;
+    }
+
+    /// Get the identity of the virtual machine.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
     /// Test if the virtual machine is the same context and unit as specified.
     pub fn is_same(&self, context: &Arc<RuntimeContext>, unit: &Arc<Unit>) -> bool {
         Arc::ptr_eq(&self.context, context) && Arc::ptr_eq(&self.unit, unit)
@@ -192,6 +326,31 @@ impl Vm {
         &self.unit
     }
 
+    /// Replace the body of the function with the given `name` in the unit of
+    /// the virtual machine, as with [Unit::patch_function].
+    ///
+    /// If the unit is shared with other virtual machines, the patch is applied
+    /// to a copy of it so that they keep running the original unit. Since
+    /// instruction pointers into the original unit are no longer valid, any
+    /// execution in progress is abandoned like with [Vm::clear].
+    pub fn patch_function<N>(&mut self, name: N, patch: &Unit) -> Result<(), PatchError>
+    where
+        N: ToTypeHash,
+    {
+        Arc::make_mut(&mut self.unit).patch_function(name, patch)?;
+        self.clear();
+        Ok(())
+    }
+
+    /// Access statistics collected during the most recent execution.
+    ///
+    /// The report is reset every time a new function is executed by name, like
+    /// with [Vm::execute] or [Vm::call]. See [ExecutionReport] for an example.
+    #[inline]
+    pub fn last_execution_report(&self) -> &ExecutionReport {
+        &self.report
+    }
+
     /// Access the current instruction pointer.
     #[inline]
     pub fn ip(&self) -> usize {
@@ -204,11 +363,386 @@ impl Vm {
         self.ip.wrapping_sub(self.last_ip_len as usize)
     }
 
+    /// Take a snapshot of the current state of the virtual machine, which can
+    /// later be restored with [Vm::restore].
+    ///
+    /// This fails if any value on the stack can't be represented as a
+    /// constant value.
+    pub fn snapshot(&self) -> Result<VmSnapshot, VmError> {
+        VmSnapshot::new(
+            &self.context,
+            &self.unit,
+            self.ip,
+            self.last_ip_len,
+            &self.stack,
+            &self.call_frames,
+        )
+        .into_result()
+    }
+
     /// Reset this virtual machine, freeing all memory used.
+    ///
+    /// This also clears the results cached for memoized functions, and
+    /// disconnects every handler connected to a signal through
+    /// `std::signal` and removes every function mocked through
+    /// `std::test::mock`.
     pub fn clear(&mut self) {
         self.ip = 0;
         self.stack.clear();
         self.call_frames.clear();
+        self.memo.clear();
+        self.signals.clear();
+        self.mocks.clear();
+
+        #[cfg(feature = "gc")]
+        self.gc.collect(true);
+    }
+
+    /// Clear the results cached for functions marked with `#[memo]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::new(context.runtime());
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         #[memo]
+    ///         pub fn square(n) {
+    ///             n * n
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let mut vm = Vm::new(runtime, Arc::new(unit));
+    ///
+    /// let output: i64 = rune::from_value(vm.call(["square"], (4,))?)?;
+    /// assert_eq!(output, 16);
+    ///
+    /// vm.clear_memo();
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn clear_memo(&mut self) {
+        self.memo.clear();
+    }
+
+    /// Set the number of results cached for each memoized function which
+    /// doesn't specify a capacity through `#[memo(capacity = <n>)]`.
+    ///
+    /// When a cache is full, the least recently used result is evicted. The
+    /// capacity defaults to `128` and is always at least `1`.
+    pub fn set_memo_capacity(&mut self, capacity: usize) {
+        self.memo.set_capacity(capacity);
+    }
+
+    /// Set the limits enforced on executions of the virtual machine.
+    ///
+    /// See [Limits] for an example.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    /// Get the limits enforced on executions of the virtual machine.
+    #[inline]
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Set the number of instructions after which an asynchronous execution,
+    /// like one started through [Vm::async_call], yields back to the executor
+    /// polling it. The interval is always at least `1`, and `None` disables
+    /// yielding which is the default.
+    ///
+    /// This allows long-running synchronous loops in scripts to be interleaved
+    /// with other tasks running on the same executor, without having to spawn
+    /// blocking threads for them. Executions yield by waking their task and
+    /// returning [Poll::Pending][core::task::Poll::Pending], so they are
+    /// resumed as soon as the executor gets around to it.
+    ///
+    /// Virtual machines which are created to run async functions, generators
+    /// and streams inherit the interval of the virtual machine calling them.
+    /// If the execution is polled inside of a [budget][crate::runtime::budget],
+    /// the budget takes precedence and no additional yielding takes place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::new(context.runtime());
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             let n = 0;
+    ///
+    ///             while n < 100000 {
+    ///                 n += 1;
+    ///             }
+    ///
+    ///             n
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    ///
+    /// let mut vm = Vm::new(runtime, Arc::new(unit));
+    /// vm.set_yield_interval(Some(1000));
+    ///
+    /// let output = futures_executor::block_on(vm.async_call(["main"], ()))?;
+    /// let output: i64 = rune::from_value(output)?;
+    /// assert_eq!(output, 100000);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn set_yield_interval(&mut self, interval: Option<usize>) {
+        self.yield_interval = interval.map(|interval| interval.max(1));
+    }
+
+    /// Get the number of instructions after which asynchronous executions
+    /// yield back to the executor, as set through [Vm::set_yield_interval].
+    #[inline]
+    pub fn yield_interval(&self) -> Option<usize> {
+        self.yield_interval
+    }
+
+    /// Set the token used to cancel executions of the virtual machine, or
+    /// `None` to remove it.
+    ///
+    /// See [CancellationToken] for an example.
+    pub fn set_cancellation_token(&mut self, token: Option<CancellationToken>) {
+        self.cancellation = token;
+    }
+
+    /// Get the token used to cancel executions of the virtual machine.
+    #[inline]
+    pub fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    /// Set whether the virtual machine runs in test mode, which is disabled by
+    /// default.
+    ///
+    /// In test mode, scripts can shadow functions in the context through
+    /// `std::test::mock`, which is otherwise an error. Disabling test mode
+    /// removes every mocked function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             std::test::mock(std::string::String::from, |s| "mocked");
+    ///             String::from("hello")
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    ///
+    /// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    /// assert!(vm.call(["main"], ()).is_err());
+    ///
+    /// vm.set_test_mode(true);
+    /// let output: String = rune::from_value(vm.call(["main"], ())?)?;
+    /// assert_eq!(output, "mocked");
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn set_test_mode(&mut self, test_mode: bool) {
+        if test_mode != self.mocks.is_enabled() {
+            self.mocks = if test_mode {
+                Mocks::enabled()
+            } else {
+                Mocks::new()
+            };
+        }
+    }
+
+    /// Test if the virtual machine runs in test mode, as set through
+    /// [Vm::set_test_mode].
+    #[inline]
+    pub fn is_test_mode(&self) -> bool {
+        self.mocks.is_enabled()
+    }
+
+    /// Get the deadline of the current call made through
+    /// [Vm::call_with_timeout].
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Set the deadline after which executions are cancelled.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
+    /// Access the base unit that the unit of this virtual machine is layered
+    /// on top of.
+    pub(crate) fn base(&self) -> Option<&Arc<Unit>> {
+        self.base.as_ref()
+    }
+
+    /// Layer the unit of this virtual machine on top of the given base unit.
+    pub(crate) fn set_base(&mut self, base: Option<Arc<Unit>>) {
+        self.base = base;
+    }
+
+    /// Access the handlers connected to signals by scripts.
+    pub(crate) fn signals(&self) -> &Signals {
+        &self.signals
+    }
+
+    /// Set the handlers connected to signals by scripts.
+    pub(crate) fn set_signals(&mut self, signals: Signals) {
+        self.signals = signals;
+    }
+
+    /// Access the context functions shadowed by tests.
+    pub(crate) fn mocks(&self) -> &Mocks {
+        &self.mocks
+    }
+
+    /// Set the context functions shadowed by tests.
+    pub(crate) fn set_mocks(&mut self, mocks: Mocks) {
+        self.mocks = mocks;
+    }
+
+    /// Access the interner of object keys used by this virtual machine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use rune::{Context, Vm};
+    ///
+    /// let context = Context::with_default_modules()?;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             let objects = [];
+    ///
+    ///             for n in 0..100 {
+    ///                 let object = #{};
+    ///                 object["value"] = n;
+    ///                 objects.push(object);
+    ///             }
+    ///
+    ///             objects
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    /// vm.call(["main"], ())?;
+    ///
+    /// let stats = vm.interner().stats();
+    /// assert_eq!(stats.keys, 100);
+    /// assert_eq!(stats.unique, 1);
+    /// assert_eq!(stats.saved_bytes(), 99 * "value".len());
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn interner(&self) -> &Interner {
+        &self.interner
+    }
+
+    /// Access the interner of object keys used by this virtual machine
+    /// mutably, which can be used to configure it.
+    pub fn interner_mut(&mut self) -> &mut Interner {
+        &mut self.interner
+    }
+
+    /// Access the collector of cyclic garbage used by this virtual machine.
+    #[cfg(feature = "gc")]
+    pub fn gc(&self) -> &Gc {
+        &self.gc
+    }
+
+    /// Access the collector of cyclic garbage used by this virtual machine
+    /// mutably, which can be used to enable and configure it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::new(context.runtime());
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             for n in 0..10 {
+    ///                 let a = #{ next: None };
+    ///                 let b = #{ next: Some(a) };
+    ///                 a.next = Some(b);
+    ///             }
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let mut vm = Vm::new(runtime, Arc::new(unit));
+    /// vm.gc_mut().set_enabled(true);
+    ///
+    /// vm.call(["main"], ())?;
+    /// assert!(vm.collect_garbage() > 0);
+    /// assert_eq!(vm.gc().stats().collections, 1);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    #[cfg(feature = "gc")]
+    pub fn gc_mut(&mut self) -> &mut Gc {
+        &mut self.gc
+    }
+
+    /// Perform a full collection of cyclic garbage, returning the number of
+    /// containers which were freed.
+    ///
+    /// This does nothing unless the collector has been enabled through
+    /// [Gc::set_enabled].
+    #[cfg(feature = "gc")]
+    pub fn collect_garbage(&mut self) -> usize {
+        self.gc.collect(true)
+    }
+
+    /// Push a newly allocated container on the stack, tracking it for
+    /// collection if enabled.
+    #[inline]
+    fn push_container(&mut self, value: Value) -> Result<(), VmErrorKind> {
+        self.report.allocations = self.report.allocations.wrapping_add(1);
+
+        if let Some(max) = self.limits.allocations {
+            if self.report.allocations > max {
+                return Err(VmErrorKind::BudgetExceeded {
+                    limit: Limit::Allocations,
+                    max,
+                });
+            }
+        }
+
+        #[cfg(feature = "gc")]
+        self.gc.track(&value);
+        self.stack.push(value);
+        Ok(())
     }
 
     /// Look up a function in the virtual machine by its name.
@@ -365,6 +899,8 @@ impl Vm {
         // Safety: make sure the stack is clear, preventing any values from
         // being sent along with the virtual machine.
         self.stack.clear();
+        self.signals = Signals::new();
+        self.mocks = Mocks::new();
 
         self.set_entrypoint(name, args.count())?;
         args.into_stack(&mut self.stack).into_result()?;
@@ -411,6 +947,99 @@ impl Vm {
         Result::Ok(value)
     }
 
+    /// Call the given function immediately like [Vm::call], but cancel the
+    /// execution if it doesn't complete within the given `timeout`.
+    ///
+    /// The deadline is checked at the same points as a [CancellationToken],
+    /// and exceeding it results in an error for which
+    /// [VmError::is_cancelled] returns `true`. Time spent in native functions
+    /// counts towards the timeout, but they are never interrupted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Vm};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let context = Context::with_default_modules()?;
+    /// let runtime = Arc::new(context.runtime());
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             loop {}
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    ///
+    /// let mut vm = Vm::new(runtime, Arc::new(unit));
+    ///
+    /// let error = vm
+    ///     .call_with_timeout(["main"], (), Duration::from_millis(10))
+    ///     .unwrap_err();
+    ///
+    /// assert!(error.is_cancelled());
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn call_with_timeout<A, N>(
+        &mut self,
+        name: N,
+        args: A,
+        timeout: Duration,
+    ) -> Result<Value, VmError>
+    where
+        N: ToTypeHash,
+        A: GuardedArgs,
+    {
+        // NB: a timeout too large to represent is the same as no timeout.
+        let deadline = Instant::now().checked_add(timeout);
+        let previous = replace(&mut self.deadline, deadline);
+        let result = self.call(name, args);
+        self.deadline = previous;
+        result
+    }
+
+    /// Call the given function immediately with a borrowed slice of
+    /// arguments, returning the produced value.
+    ///
+    /// Arguments are cloned directly onto the stack of the virtual machine,
+    /// so callers which already hold their arguments as values don't have to
+    /// collect them into a [`Vec`] for every call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Hash, Value, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn add(a, b) { a + b }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let mut vm = Vm::without_runtime(Arc::new(unit));
+    ///
+    /// let add = Hash::type_hash(["add"]);
+    /// let args = [Value::from(1i64), Value::from(2i64)];
+    ///
+    /// let output = vm.call_with_slice(add, &args)?;
+    /// let output: i64 = rune::from_value(output)?;
+    /// assert_eq!(output, 3);
+    /// # Ok::<_, rune::Error>(())
+    /// ```
+    pub fn call_with_slice<N>(&mut self, name: N, args: &[Value]) -> Result<Value, VmError>
+    where
+        N: ToTypeHash,
+    {
+        self.call(name, args)
+    }
+
     /// Call the given function immediately asynchronously, returning the
     /// produced value.
     ///
@@ -490,6 +1119,8 @@ impl Vm {
         self.ip = offset;
         self.stack.clear();
         self.call_frames.clear();
+        self.memo.clear_pending();
+        self.report = ExecutionReport::new(Some(hash));
         Ok(())
     }
 
@@ -524,10 +1155,12 @@ impl Vm {
         }
 
         if let Some(handler) = self.context.function(hash) {
+            let ip = self.last_ip();
             self.stack.push(target);
             // Safety: We hold onto the guard for the duration of this call.
             let _guard = unsafe { vm_try!(args.unsafe_into_stack(&mut self.stack)) };
-            vm_try!(handler(&mut self.stack, count));
+            let _site = borrow::enter(self.context.borrow_mode(), &self.unit, ip);
+            vm_try!(handler(&mut self.stack, count).with_argument_names(&self.context, hash));
             return VmResult::Ok(CallResult::Ok(()));
         }
 
@@ -551,9 +1184,11 @@ impl Vm {
         let hash = Hash::field_function(protocol, vm_try!(target.type_hash()), name);
 
         if let Some(handler) = self.context.function(hash) {
+            let ip = self.last_ip();
             self.stack.push(target);
             let _guard = unsafe { vm_try!(args.unsafe_into_stack(&mut self.stack)) };
-            vm_try!(handler(&mut self.stack, count));
+            let _site = borrow::enter(self.context.borrow_mode(), &self.unit, ip);
+            vm_try!(handler(&mut self.stack, count).with_argument_names(&self.context, hash));
             return VmResult::Ok(CallResult::Ok(()));
         }
 
@@ -576,9 +1211,11 @@ impl Vm {
         let hash = Hash::index_function(protocol, vm_try!(target.type_hash()), Hash::index(index));
 
         if let Some(handler) = self.context.function(hash) {
+            let ip = self.last_ip();
             self.stack.push(target);
             let _guard = unsafe { vm_try!(args.unsafe_into_stack(&mut self.stack)) };
-            vm_try!(handler(&mut self.stack, count));
+            let _site = borrow::enter(self.context.borrow_mode(), &self.unit, ip);
+            vm_try!(handler(&mut self.stack, count).with_argument_names(&self.context, hash));
             return VmResult::Ok(CallResult::Ok(()));
         }
 
@@ -626,6 +1263,17 @@ impl Vm {
     ) -> Result<(), VmErrorKind> {
         tracing::trace!("pushing call frame");
 
+        self.check_cancelled()?;
+
+        if let Some(max) = self.limits.call_depth {
+            if self.call_frames.len() >= max {
+                return Err(VmErrorKind::BudgetExceeded {
+                    limit: Limit::CallDepth,
+                    max: max as u64,
+                });
+            }
+        }
+
         let stack_bottom = self.stack.swap_stack_bottom(args)?;
         let ip = replace(&mut self.ip, ip);
 
@@ -633,6 +1281,7 @@ impl Vm {
             ip,
             stack_bottom,
             isolated,
+            unpack: None,
         };
 
         self.call_frames.push(frame);
@@ -824,6 +1473,47 @@ impl Vm {
         VmResult::Ok(Some(value))
     }
 
+    /// Implementation of getting a mutable value out of a built-in type using
+    /// a dynamic index, like `target[index]`.
+    fn try_index_get_mut<'a>(
+        target: &'a Value,
+        index: &Value,
+    ) -> VmResult<Option<BorrowMut<'a, Value>>> {
+        match index {
+            Value::String(string) => {
+                let string = vm_try!(string.borrow_ref());
+                Self::try_object_like_index_get_mut(target, string.as_str())
+            }
+            Value::Integer(index) => {
+                let Ok(index) = (*index).try_into() else {
+                    return err(VmErrorKind::MissingIndexInteger {
+                        target: vm_try!(target.type_info()),
+                        index: VmIntegerRepr::from(*index),
+                    });
+                };
+
+                // NB: vectors are the only built-in type which can have an
+                // arbitrary number of elements.
+                if let Value::Vec(vec) = target {
+                    let vec = vm_try!(vec.borrow_mut());
+                    let length = vec.len();
+
+                    let Some(value) = BorrowMut::try_map(vec, |vec| vec.get_mut(index)) else {
+                        return err(VmErrorKind::OutOfRange {
+                            index: index.into(),
+                            length: length.into(),
+                        });
+                    };
+
+                    return VmResult::Ok(Some(value));
+                }
+
+                Self::try_tuple_like_index_get_mut(target, index)
+            }
+            _ => VmResult::Ok(None),
+        }
+    }
+
     /// Implementation of getting a mutable string index on an object-like type.
     fn try_object_like_index_get_mut<'a>(
         target: &'a Value,
@@ -991,7 +1681,8 @@ impl Vm {
         VmResult::Ok(match target {
             Value::Object(object) => {
                 let mut object = vm_try!(object.borrow_mut());
-                object.insert(field.as_str().to_owned(), value);
+                let key = self.unit.intern_key(&mut self.interner, field.as_str());
+                object.insert_key(key, value);
                 return VmResult::Ok(CallResult::Ok(()));
             }
             Value::Struct(typed_object) => {
@@ -1111,6 +1802,23 @@ impl Vm {
             Value::Integer(a) => convert!(i64, a, ty),
             Value::Float(a) => convert!(f64, a, ty),
             Value::Byte(a) => convert!(u8, a, ty),
+            Value::Variant(variant) => {
+                let variant = vm_try!(variant.borrow_ref());
+
+                match (&variant.data, variant.rtti.discriminant, ty.into_hash()) {
+                    (
+                        VariantData::Empty,
+                        Some(discriminant),
+                        runtime::static_type::INTEGER_TYPE_HASH,
+                    ) => Value::Integer(discriminant),
+                    (.., ty) => {
+                        return err(VmErrorKind::UnsupportedAs {
+                            value: variant.type_info(),
+                            type_hash: ty,
+                        });
+                    }
+                }
+            }
             value => {
                 return err(VmErrorKind::UnsupportedAs {
                     value: vm_try!(value.type_info()),
@@ -1163,29 +1871,44 @@ impl Vm {
         VmResult::Ok(())
     }
 
-    /// Construct a future from calling an async function.
-    fn call_generator_fn(&mut self, offset: usize, args: usize) -> Result<(), VmErrorKind> {
+    /// Construct a virtual machine which calls the function at the given
+    /// offset with arguments from the top of the stack, and which is limited
+    /// and cancelled the same way as this one and shares its signal handlers
+    /// and mocks.
+    fn child_vm(&mut self, offset: usize, args: usize) -> Result<Self, VmErrorKind> {
         let stack = self.stack.drain(args)?.collect::<Stack>();
         let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
+        vm.base = self.base.clone();
+        vm.limits = self.limits;
+        vm.yield_interval = self.yield_interval;
+        vm.cancellation = self.cancellation.clone();
+        #[cfg(feature = "std")]
+        {
+            vm.deadline = self.deadline;
+        }
+        vm.signals = self.signals.share();
+        vm.mocks = self.mocks.share();
         vm.ip = offset;
+        Ok(vm)
+    }
+
+    /// Construct a future from calling an async function.
+    fn call_generator_fn(&mut self, offset: usize, args: usize) -> Result<(), VmErrorKind> {
+        let vm = self.child_vm(offset, args)?;
         self.stack.push(Generator::new(vm));
         Ok(())
     }
 
     /// Construct a stream from calling a function.
     fn call_stream_fn(&mut self, offset: usize, args: usize) -> Result<(), VmErrorKind> {
-        let stack = self.stack.drain(args)?.collect::<Stack>();
-        let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
-        vm.ip = offset;
+        let vm = self.child_vm(offset, args)?;
         self.stack.push(Stream::new(vm));
         Ok(())
     }
 
     /// Construct a future from calling a function.
     fn call_async_fn(&mut self, offset: usize, args: usize) -> Result<(), VmErrorKind> {
-        let stack = self.stack.drain(args)?.collect::<Stack>();
-        let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
-        vm.ip = offset;
+        let vm = self.child_vm(offset, args)?;
         let mut execution = vm.into_execution();
         self.stack
             .push(Future::new(async move { execution.async_complete().await }));
@@ -1205,6 +1928,11 @@ impl Vm {
                 false
             }
             Call::Immediate => {
+                #[cfg(feature = "jit")]
+                if self.call_native(offset, args)? {
+                    return Ok(false);
+                }
+
                 self.push_call_frame(offset, args, false)?;
                 true
             }
@@ -1221,6 +1949,48 @@ impl Vm {
         Ok(moved)
     }
 
+    /// Call the native code of the function at the given offset if it has
+    /// been compiled through [Unit::jit], returning `false` if the call has to
+    /// be interpreted.
+    ///
+    /// Native code doesn't check for cancellation or count instructions, so
+    /// calls are interpreted while a [CancellationToken], a deadline or any
+    /// [Limits] are set. It does take from the [budget] when it's entered and
+    /// at every back-edge, and deoptimizes once the budget is used up. Since
+    /// executions are stepped with a budget of one instruction, this also
+    /// means that calls are interpreted when running to a breakpoint.
+    ///
+    /// Calls which are executed natively are recorded in
+    /// [ExecutionReport::native_calls].
+    #[cfg(feature = "jit")]
+    fn call_native(&mut self, offset: usize, args: usize) -> Result<bool, VmErrorKind> {
+        if self.cancellation.is_some() || self.limits != Limits::new() {
+            return Ok(false);
+        }
+
+        #[cfg(feature = "std")]
+        if self.deadline.is_some() {
+            return Ok(false);
+        }
+
+        let Some(function) = self.unit.native_function(offset) else {
+            return Ok(false);
+        };
+
+        let Some(start) = self.stack.len().checked_sub(args) else {
+            return Ok(false);
+        };
+
+        let Some(value) = self.stack.get(start..).and_then(|args| function.call(args)) else {
+            return Ok(false);
+        };
+
+        self.stack.popn(args)?;
+        self.stack.push(value);
+        self.report.native_calls = self.report.native_calls.wrapping_add(1);
+        Ok(true)
+    }
+
     fn internal_num_assign(
         &mut self,
         target: InstTarget,
@@ -1229,29 +1999,90 @@ impl Vm {
         integer_op: fn(i64, i64) -> Option<i64>,
         float_op: fn(f64, f64) -> f64,
     ) -> VmResult<()> {
-        let lhs;
-        let mut guard;
-
-        let fallback = match target_value!(self, target, guard, lhs) {
-            TargetValue::Value(lhs, rhs) => match (lhs, rhs) {
+        self.target_assign(target, protocol, |lhs, rhs| {
+            match (lhs, rhs) {
                 (Value::Integer(lhs), Value::Integer(rhs)) => {
-                    let out = vm_try!(integer_op(*lhs, rhs).ok_or_else(error));
-                    *lhs = out;
-                    return VmResult::Ok(());
+                    *lhs = vm_try!(integer_op(*lhs, rhs).ok_or_else(error));
                 }
                 (Value::Float(lhs), Value::Float(rhs)) => {
-                    let out = float_op(*lhs, rhs);
-                    *lhs = out;
-                    return VmResult::Ok(());
+                    *lhs = float_op(*lhs, rhs);
                 }
-                (lhs, rhs) => TargetFallback::Value(lhs.clone(), rhs),
+                (lhs, rhs) => return VmResult::Ok(Some((lhs.clone(), rhs))),
+            }
+
+            VmResult::Ok(None)
+        })
+    }
+
+    /// Apply an assignment operation to the given target.
+    ///
+    /// The operation `op` is applied to the value being assigned to, and
+    /// returns the operands back if it doesn't support them, in which case the
+    /// given `protocol` is called instead.
+    fn target_assign(
+        &mut self,
+        target: InstTarget,
+        protocol: Protocol,
+        op: impl FnOnce(&mut Value, Value) -> VmResult<Option<(Value, Value)>>,
+    ) -> VmResult<()> {
+        let lhs;
+        let mut guard;
+
+        let fallback = match target_value!(self, target, guard, lhs) {
+            TargetValue::Value(lhs, rhs) => match vm_try!(op(lhs, rhs)) {
+                Some((lhs, rhs)) => TargetFallback::Value(lhs, rhs),
+                None => return VmResult::Ok(()),
             },
             TargetValue::Fallback(fallback) => fallback,
+            TargetValue::Index(target, index, rhs) => {
+                return self.index_fallback_assign(target.clone(), index, rhs, protocol, op);
+            }
         };
 
         self.target_fallback_assign(fallback, protocol)
     }
 
+    /// Apply an assignment operation to an index which can only be accessed
+    /// through protocols, like the key of a map, by getting its value with
+    /// [Protocol::INDEX_GET], applying the operation to it and storing the
+    /// result back with [Protocol::INDEX_SET].
+    fn index_fallback_assign(
+        &mut self,
+        target: Value,
+        index: Value,
+        rhs: Value,
+        protocol: Protocol,
+        op: impl FnOnce(&mut Value, Value) -> VmResult<Option<(Value, Value)>>,
+    ) -> VmResult<()> {
+        if let CallResult::Unsupported(target) =
+            vm_try!(self.call_instance_fn(target.clone(), Protocol::INDEX_GET, (&index,)))
+        {
+            return err(VmErrorKind::UnsupportedIndexGet {
+                target: vm_try!(target.type_info()),
+                index: vm_try!(index.type_info()),
+            });
+        }
+
+        let mut value = vm_try!(self.stack.pop());
+
+        if let Some((lhs, rhs)) = vm_try!(op(&mut value, rhs)) {
+            vm_try!(self.target_fallback_assign(TargetFallback::Value(lhs, rhs), protocol));
+        }
+
+        if let CallResult::Unsupported(target) =
+            vm_try!(self.call_instance_fn(target, Protocol::INDEX_SET, (&index, &value)))
+        {
+            return err(VmErrorKind::UnsupportedIndexSet {
+                target: vm_try!(target.type_info()),
+                index: vm_try!(index.type_info()),
+                value: vm_try!(value.type_info()),
+            });
+        }
+
+        vm_try!(<()>::from_value(vm_try!(self.stack.pop())));
+        VmResult::Ok(())
+    }
+
     /// Execute a fallback operation.
     fn target_fallback_assign(
         &mut self,
@@ -1413,21 +2244,16 @@ impl Vm {
         protocol: Protocol,
         integer_op: fn(&mut i64, i64),
     ) -> VmResult<()> {
-        let lhs;
-        let mut guard;
-
-        let fallback = match target_value!(self, target, guard, lhs) {
-            TargetValue::Value(lhs, rhs) => match (lhs, rhs) {
+        self.target_assign(target, protocol, |lhs, rhs| {
+            match (lhs, rhs) {
                 (Value::Integer(lhs), Value::Integer(rhs)) => {
                     integer_op(lhs, rhs);
-                    return VmResult::Ok(());
                 }
-                (lhs, rhs) => TargetFallback::Value(lhs.clone(), rhs),
-            },
-            TargetValue::Fallback(fallback) => fallback,
-        };
+                (lhs, rhs) => return VmResult::Ok(Some((lhs.clone(), rhs))),
+            }
 
-        self.target_fallback_assign(fallback, protocol)
+            VmResult::Ok(None)
+        })
     }
 
     fn internal_bitwise(
@@ -1469,22 +2295,16 @@ impl Vm {
         error: fn() -> VmErrorKind,
         integer_op: fn(i64, i64) -> Option<i64>,
     ) -> VmResult<()> {
-        let lhs;
-        let mut guard;
-
-        let fallback = match target_value!(self, target, guard, lhs) {
-            TargetValue::Value(lhs, rhs) => match (lhs, rhs) {
+        self.target_assign(target, protocol, |lhs, rhs| {
+            match (lhs, rhs) {
                 (Value::Integer(lhs), Value::Integer(rhs)) => {
-                    let out = vm_try!(integer_op(*lhs, rhs).ok_or_else(error));
-                    *lhs = out;
-                    return VmResult::Ok(());
+                    *lhs = vm_try!(integer_op(*lhs, rhs).ok_or_else(error));
                 }
-                (lhs, rhs) => TargetFallback::Value(lhs.clone(), rhs),
-            },
-            TargetValue::Fallback(fallback) => fallback,
-        };
+                (lhs, rhs) => return VmResult::Ok(Some((lhs.clone(), rhs))),
+            }
 
-        self.target_fallback_assign(fallback, protocol)
+            VmResult::Ok(None)
+        })
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
@@ -1598,10 +2418,31 @@ impl Vm {
     /// Perform a jump operation.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_jump(&mut self, jump: usize) -> VmResult<()> {
+        vm_try!(self.check_cancelled());
         self.ip = vm_try!(self.unit.translate(jump));
         VmResult::Ok(())
     }
 
+    /// Check if the current execution has been cancelled, either through its
+    /// [CancellationToken] or because its deadline has passed.
+    #[inline]
+    fn check_cancelled(&self) -> Result<(), VmErrorKind> {
+        if let Some(token) = &self.cancellation {
+            if token.is_cancelled() {
+                return Err(VmErrorKind::Cancelled);
+            }
+        }
+
+        #[cfg(feature = "std")]
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return Err(VmErrorKind::Cancelled);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Perform a conditional jump operation.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_jump_if(&mut self, jump: usize) -> VmResult<()> {
@@ -1655,7 +2496,26 @@ impl Vm {
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_vec(&mut self, count: usize) -> VmResult<()> {
         let vec = Vec::from(vm_try!(self.stack.pop_sequence(count)));
-        self.stack.push(Shared::new(vec));
+        vm_try!(self.push_container(Value::Vec(Shared::new(vec))));
+        VmResult::Ok(())
+    }
+
+    /// Construct a new vector by spreading the items of the values on the
+    /// stack into it.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_vec_spread(&mut self, count: usize) -> VmResult<()> {
+        let values = vm_try!(self.stack.pop_sequence(count));
+        let mut vec = Vec::new();
+
+        for value in values {
+            let mut iter = vm_try!(value.into_iter());
+
+            while let Some(value) = vm_try!(iter.next()) {
+                vec.push(value);
+            }
+        }
+
+        vm_try!(self.push_container(Value::Vec(Shared::new(vec))));
         VmResult::Ok(())
     }
 
@@ -1663,7 +2523,7 @@ impl Vm {
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_tuple(&mut self, count: usize) -> VmResult<()> {
         let tuple = vm_try!(self.stack.pop_sequence(count));
-        self.stack.push(OwnedTuple::from(tuple));
+        vm_try!(self.push_container(Value::Tuple(Shared::new(OwnedTuple::from(tuple)))));
         VmResult::Ok(())
     }
 
@@ -1676,7 +2536,7 @@ impl Vm {
             tuple[n] = vm_try!(self.stack.address(*arg));
         }
 
-        self.stack.push(OwnedTuple::from(tuple));
+        vm_try!(self.push_container(Value::Tuple(Shared::new(OwnedTuple::from(tuple)))));
         VmResult::Ok(())
     }
 
@@ -1996,7 +2856,8 @@ impl Vm {
             match &target {
                 Value::Object(object) => {
                     let mut object = vm_try!(object.borrow_mut());
-                    object.insert(field.to_owned(), value);
+                    let key = self.unit.intern_key(&mut self.interner, field);
+                    object.insert_key(key, value);
                     return VmResult::Ok(());
                 }
                 Value::Struct(typed_object) => {
@@ -2056,11 +2917,36 @@ impl Vm {
             self.stack.popn(clean)?;
         }
 
+        self.memo.complete(self.call_frames.len(), &return_value);
+
+        let unpack = self.call_frames.last().and_then(|frame| frame.unpack);
         let exit = self.pop_call_frame()?;
-        self.stack.push(return_value);
+
+        match unpack {
+            Some(count) => self.unpack_tuple(return_value, count)?,
+            None => self.stack.push(return_value),
+        }
+
         Ok(exit)
     }
 
+    /// Push the items of a tuple which is being unpacked into `count` values
+    /// onto the stack.
+    fn unpack_tuple(&mut self, value: Value, count: usize) -> Result<(), VmErrorKind> {
+        if let Value::Tuple(tuple) = &value {
+            let tuple = tuple.borrow_ref()?;
+
+            if tuple.len() == count {
+                self.stack.extend(tuple.iter().cloned());
+                return Ok(());
+            }
+        }
+
+        Err(VmErrorKind::Panic {
+            reason: Panic::from(PanicReason::UnmatchedPattern),
+        })
+    }
+
     fn lookup_function_by_hash(&self, hash: Hash) -> Result<Function, VmErrorKind> {
         Ok(match self.unit.function(hash) {
             Some(info) => match info {
@@ -2106,6 +2992,19 @@ impl Vm {
                 }
             },
             None => {
+                if let Some(base) = &self.base {
+                    if let Some(UnitFn::Offset { offset, call, args }) = base.function(hash) {
+                        return Ok(Function::from_vm_offset(
+                            self.context.clone(),
+                            base.clone(),
+                            offset,
+                            call,
+                            args,
+                            hash,
+                        ));
+                    }
+                }
+
                 let handler = self
                     .context
                     .function(hash)
@@ -2125,9 +3024,28 @@ impl Vm {
     #[cfg_attr(feature = "bench", inline(never))]
     #[tracing::instrument(skip(self))]
     fn op_return_unit(&mut self) -> Result<bool, VmErrorKind> {
-        let exit = self.pop_call_frame()?;
-        self.stack.push(());
-        Ok(exit)
+        self.op_return_internal(Value::EmptyTuple, 0)
+    }
+
+    #[cfg_attr(feature = "bench", inline(never))]
+    #[tracing::instrument(skip(self))]
+    fn op_return_tuple(&mut self, count: usize, clean: usize) -> Result<bool, VmErrorKind> {
+        match self.call_frames.last().copied() {
+            // NB: the caller unpacks the tuple, so the values are moved into its
+            // stack frame as they are.
+            Some(frame)
+                if frame.unpack == Some(count) && !self.memo.is_pending(self.call_frames.len()) =>
+            {
+                self.call_frames.pop();
+                self.stack.pop_stack_top_keep(frame.stack_bottom, count)?;
+                self.ip = frame.ip;
+                Ok(frame.isolated)
+            }
+            _ => {
+                let tuple = self.stack.pop_sequence(count)?;
+                self.op_return_internal(Value::tuple(tuple), clean)
+            }
+        }
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
@@ -2315,22 +3233,51 @@ impl Vm {
     /// Operation to allocate an object.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_object(&mut self, slot: usize) -> VmResult<()> {
-        let keys = vm_try!(self
-            .unit
-            .lookup_object_keys(slot)
-            .ok_or(VmErrorKind::MissingStaticObjectKeys { slot }));
+        let object = vm_try!(self.object_from_slot(slot));
+        vm_try!(self.push_container(Value::Object(Shared::new(object))));
+        VmResult::Ok(())
+    }
+
+    /// Construct a new object by spreading the key-value pairs of the values
+    /// on the stack into it.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_object_spread(&mut self, count: usize) -> VmResult<()> {
+        let values = vm_try!(self.stack.pop_sequence(count));
+        let mut object = Object::new();
 
-        let mut object = Object::with_capacity(keys.len());
-        let values = vm_try!(self.stack.drain(keys.len()));
+        for value in values {
+            let mut iter = vm_try!(value.into_iter());
 
-        for (key, value) in keys.iter().zip(values) {
-            object.insert(key.clone(), value);
+            while let Some(value) = vm_try!(iter.next()) {
+                let (key, value) = vm_try!(<(String, Value)>::from_value(value));
+                let key = self.unit.intern_key(&mut self.interner, &key);
+                object.insert_key(key, value);
+            }
         }
 
-        self.stack.push(Shared::new(object));
+        vm_try!(self.push_container(Value::Object(Shared::new(object))));
         VmResult::Ok(())
     }
 
+    /// Construct an object from the static object keys in the given slot,
+    /// taking its values from the stack.
+    fn object_from_slot(&mut self, slot: usize) -> VmResult<Object> {
+        let count = vm_try!(self
+            .unit
+            .lookup_object_keys(slot)
+            .ok_or(VmErrorKind::MissingStaticObjectKeys { slot }))
+        .len();
+
+        let values = vm_try!(self.stack.drain(count));
+
+        let object = vm_try!(self
+            .unit
+            .new_object(slot, values)
+            .ok_or(VmErrorKind::MissingStaticObjectKeys { slot }));
+
+        VmResult::Ok(object)
+    }
+
     /// Operation to allocate an object.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_range(&mut self, range: InstRange) -> VmResult<()> {
@@ -2379,28 +3326,14 @@ impl Vm {
     /// Operation to allocate an object struct.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_struct(&mut self, hash: Hash, slot: usize) -> VmResult<()> {
-        let keys = vm_try!(self
-            .unit
-            .lookup_object_keys(slot)
-            .ok_or(VmErrorKind::MissingStaticObjectKeys { slot }));
-
         let rtti = vm_try!(self
             .unit
             .lookup_rtti(hash)
-            .ok_or(VmErrorKind::MissingRtti { hash }));
-
-        let values = vm_try!(self.stack.drain(keys.len()));
-        let mut data = Object::with_capacity(keys.len());
-
-        for (key, value) in keys.iter().zip(values) {
-            data.insert(key.clone(), value);
-        }
-
-        self.stack.push(Struct {
-            rtti: rtti.clone(),
-            data,
-        });
+            .ok_or(VmErrorKind::MissingRtti { hash }))
+        .clone();
 
+        let data = vm_try!(self.object_from_slot(slot));
+        vm_try!(self.push_container(Value::Struct(Shared::new(Struct { rtti, data }))));
         VmResult::Ok(())
     }
 
@@ -2419,24 +3352,14 @@ impl Vm {
     /// Operation to allocate an object variant.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_object_variant(&mut self, hash: Hash, slot: usize) -> VmResult<()> {
-        let keys = vm_try!(self
-            .unit
-            .lookup_object_keys(slot)
-            .ok_or(VmErrorKind::MissingStaticObjectKeys { slot }));
-
         let rtti = vm_try!(self
             .unit
             .lookup_variant_rtti(hash)
-            .ok_or(VmErrorKind::MissingVariantRtti { hash }));
-
-        let mut data = Object::with_capacity(keys.len());
-        let values = vm_try!(self.stack.drain(keys.len()));
-
-        for (key, value) in keys.iter().zip(values) {
-            data.insert(key.clone(), value);
-        }
+            .ok_or(VmErrorKind::MissingVariantRtti { hash }))
+        .clone();
 
-        self.stack.push(Variant::struct_(rtti.clone(), data));
+        let data = vm_try!(self.object_from_slot(slot));
+        vm_try!(self.push_container(Value::Variant(Shared::new(Variant::struct_(rtti, data)))));
         VmResult::Ok(())
     }
 
@@ -2454,6 +3377,14 @@ impl Vm {
         VmResult::Ok(())
     }
 
+    /// Load a value from a static data slot.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_static_data(&mut self, slot: usize) -> VmResult<()> {
+        let value = vm_try!(self.unit.lookup_static_data(slot)).to_owned_value();
+        self.stack.push(value);
+        VmResult::Ok(())
+    }
+
     /// Optimize operation to perform string concatenation.
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_string_concat(&mut self, len: usize, size_hint: usize) -> VmResult<()> {
@@ -2487,12 +3418,11 @@ impl Vm {
         VmResult::Ok(())
     }
 
-    /// Perform the try operation on the given stack location.
-    #[cfg_attr(feature = "bench", inline(never))]
-    fn op_try(&mut self, address: InstAddress, clean: usize, preserve: bool) -> VmResult<bool> {
+    /// Test the value at the given stack location for the try operation.
+    fn try_control_flow(&mut self, address: InstAddress) -> VmResult<ControlFlow> {
         let value = vm_try!(self.stack.address(address));
 
-        let result = match value {
+        VmResult::Ok(match value {
             Value::Result(result) => result::result_try(vm_try!(result.take())),
             Value::Option(option) => option::option_try(vm_try!(option.take())),
             value => {
@@ -2507,9 +3437,13 @@ impl Vm {
                 let value = vm_try!(self.stack.pop());
                 vm_try!(ControlFlow::from_value(value))
             }
-        };
+        })
+    }
 
-        match result {
+    /// Perform the try operation on the given stack location.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_try(&mut self, address: InstAddress, clean: usize, preserve: bool) -> VmResult<bool> {
+        match vm_try!(self.try_control_flow(address)) {
             ControlFlow::Continue(value) => {
                 if preserve {
                     self.stack.push(value);
@@ -2523,6 +3457,30 @@ impl Vm {
         }
     }
 
+    /// Perform the try operation on the given stack location, jumping with the
+    /// residual value on the stack instead of returning.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_try_or_jump(
+        &mut self,
+        address: InstAddress,
+        preserve: bool,
+        jump: usize,
+    ) -> VmResult<()> {
+        match vm_try!(self.try_control_flow(address)) {
+            ControlFlow::Continue(value) => {
+                if preserve {
+                    self.stack.push(value);
+                }
+            }
+            ControlFlow::Break(error) => {
+                self.stack.push(error);
+                self.ip = vm_try!(self.unit.translate(jump));
+            }
+        }
+
+        VmResult::Ok(())
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_eq_byte(&mut self, byte: u8) -> VmResult<()> {
         let value = vm_try!(self.stack.pop());
@@ -2621,6 +3579,25 @@ impl Vm {
         VmResult::Ok(())
     }
 
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_check_argument(&mut self, offset: usize, hash: Hash, slot: usize) -> VmResult<()> {
+        let value = vm_try!(self.stack.at_offset(offset));
+
+        if vm_try!(value.type_hash()) != hash {
+            let expected = vm_try!(self.unit.lookup_string(slot));
+
+            // Arguments are the first values in a stack frame, so the offset
+            // is also the index of the argument.
+            return err(VmErrorKind::BadArgumentAnnotation {
+                arg: offset,
+                expected: expected.as_str().to_owned(),
+                actual: vm_try!(value.type_info()),
+            });
+        }
+
+        VmResult::Ok(())
+    }
+
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_match_variant(
         &mut self,
@@ -2714,7 +3691,7 @@ impl Vm {
         self.stack.push(is_match);
         return VmResult::Ok(());
 
-        fn test(object: &Object, keys: &[String], exact: bool) -> bool {
+        fn test(object: &Object, keys: &[ObjectKey], exact: bool) -> bool {
             if exact {
                 if object.len() != keys.len() {
                     return false;
@@ -2739,20 +3716,18 @@ impl Vm {
         match variant {
             InstVariant::Some => {
                 let some = vm_try!(self.stack.pop());
-                self.stack.push(Value::Option(Shared::new(Some(some))));
+                vm_try!(self.push_container(Value::Option(Shared::new(Some(some)))));
             }
             InstVariant::None => {
                 self.stack.push(Value::Option(Shared::new(None)));
             }
             InstVariant::Ok => {
                 let some = vm_try!(self.stack.pop());
-                self.stack
-                    .push(Value::Result(Shared::new(Result::Ok(some))));
+                vm_try!(self.push_container(Value::Result(Shared::new(Result::Ok(some)))));
             }
             InstVariant::Err => {
                 let some = vm_try!(self.stack.pop());
-                self.stack
-                    .push(Value::Result(Shared::new(Result::Err(some))));
+                vm_try!(self.push_container(Value::Result(Shared::new(Result::Err(some)))));
             }
         }
 
@@ -2796,9 +3771,123 @@ impl Vm {
         VmResult::Ok(())
     }
 
+    /// Look up the cached result of a call to a memoized function, returning
+    /// it from the current call frame if it's present.
+    #[cfg_attr(feature = "bench", inline(never))]
+    #[cfg(feature = "std")]
+    fn op_memo(&mut self, hash: Hash, args: usize, capacity: usize) -> VmResult<bool> {
+        let mut values = vec::Vec::with_capacity(args);
+
+        for offset in 0..args {
+            values.push(vm_try!(self.stack.at_offset(offset)).clone());
+        }
+
+        let key = vm_try!(runtime::MemoKey::new(values.into(), self));
+
+        // NB: the memoization storage is temporarily taken, since comparing
+        // arguments might call back into the virtual machine.
+        let mut memo = take(&mut self.memo);
+        let cached = memo.get(hash, &key, self);
+        self.memo = memo;
+
+        if let Some(value) = vm_try!(cached) {
+            return VmResult::Ok(vm_try!(self.op_return_internal(value, args)));
+        }
+
+        self.memo
+            .push_pending(self.call_frames.len(), hash, capacity, key);
+        VmResult::Ok(false)
+    }
+
+    /// Memoization requires the `std` feature, without it results are never
+    /// cached.
+    #[cfg_attr(feature = "bench", inline(never))]
+    #[cfg(not(feature = "std"))]
+    fn op_memo(&mut self, _: Hash, _: usize, _: usize) -> VmResult<bool> {
+        VmResult::Ok(false)
+    }
+
+    /// Implementation of a function call whose returned tuple is unpacked.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_call_unpack(
+        &mut self,
+        hash: Hash,
+        args: usize,
+        count: usize,
+    ) -> VmResult<Option<VmHalt>> {
+        if let Some(UnitFn::Offset {
+            offset,
+            call: Call::Immediate,
+            args: expected,
+        }) = self.unit.function(hash)
+        {
+            vm_try!(check_args(args, expected));
+            vm_try!(self.push_call_frame(offset, args, false));
+
+            if let Some(frame) = self.call_frames.last_mut() {
+                frame.unpack = Some(count);
+            }
+
+            return VmResult::Ok(None);
+        }
+
+        let frames = self.call_frames.len();
+
+        if let Some(halt) = vm_try!(self.op_call(hash, args)) {
+            return VmResult::Ok(Some(halt));
+        }
+
+        // NB: a mocked function might have been called by pushing a call frame,
+        // in which case the returned tuple is unpacked when it returns.
+        if self.call_frames.len() > frames {
+            if let Some(frame) = self.call_frames.last_mut() {
+                frame.unpack = Some(count);
+            }
+
+            return VmResult::Ok(None);
+        }
+
+        let value = vm_try!(self.stack.pop());
+        vm_try!(self.unpack_tuple(value, count));
+        VmResult::Ok(None)
+    }
+
+    /// Call a function defined in the base unit which the current unit is
+    /// layered on top of, unless the current unit defines it.
+    ///
+    /// Since the function lives in another unit, this always halts the virtual
+    /// machine so that the execution can switch to it. Returns `None` if the
+    /// function isn't defined in the base unit.
+    fn call_base_fn(&mut self, hash: Hash, args: usize) -> VmResult<Option<VmHalt>> {
+        let Some(base) = &self.base else {
+            return VmResult::Ok(None);
+        };
+
+        if self.unit.function(hash).is_some() {
+            return VmResult::Ok(None);
+        }
+
+        let Some(UnitFn::Offset {
+            offset,
+            call,
+            args: expected,
+        }) = base.function(hash)
+        else {
+            return VmResult::Ok(None);
+        };
+
+        vm_try!(check_args(args, expected));
+        let base = base.clone();
+
+        // NB: the call frame is isolated, so that execution switches back to
+        // the current unit once the function returns.
+        vm_try!(self.push_call_frame(offset, args, true));
+        VmResult::Ok(Some(VmHalt::VmCall(VmCall::new(call, None, Some(base)))))
+    }
+
     /// Implementation of a function call.
     #[cfg_attr(feature = "bench", inline(never))]
-    fn op_call(&mut self, hash: Hash, args: usize) -> VmResult<()> {
+    fn op_call(&mut self, hash: Hash, args: usize) -> VmResult<Option<VmHalt>> {
         match self.unit.function(hash) {
             Some(info) => match info {
                 UnitFn::Offset {
@@ -2831,7 +3920,7 @@ impl Vm {
                         .lookup_rtti(hash)
                         .ok_or(VmErrorKind::MissingRtti { hash }));
 
-                    self.stack.push(Value::tuple_struct(rtti.clone(), tuple));
+                    vm_try!(self.push_container(Value::tuple_struct(rtti.clone(), tuple)));
                 }
                 UnitFn::TupleVariant {
                     hash,
@@ -2845,7 +3934,7 @@ impl Vm {
                         .ok_or(VmErrorKind::MissingVariantRtti { hash }));
 
                     let tuple = vm_try!(self.stack.pop_sequence(args));
-                    self.stack.push(Value::tuple_variant(rtti.clone(), tuple));
+                    vm_try!(self.push_container(Value::tuple_variant(rtti.clone(), tuple)));
                 }
                 UnitFn::UnitVariant { hash } => {
                     vm_try!(check_args(args, 0));
@@ -2859,16 +3948,40 @@ impl Vm {
                 }
             },
             None => {
+                if let Some(function) = self.mocks.get(hash) {
+                    return function.call_with_vm(self, args);
+                }
+
                 let handler = vm_try!(self
                     .context
                     .function(hash)
                     .ok_or(VmErrorKind::MissingFunction { hash }));
 
-                vm_try!(handler(&mut self.stack, args));
+                let ip = self.last_ip();
+
+                if let Some(wait) = vm_try!(call_native(
+                    &self.context,
+                    &self.unit,
+                    ip,
+                    &mut self.stack,
+                    handler,
+                    hash,
+                    args
+                )) {
+                    return VmResult::Ok(Some(self.wait_for_borrow(wait)));
+                }
             }
         }
 
-        VmResult::Ok(())
+        VmResult::Ok(None)
+    }
+
+    /// Suspend the execution until the borrow is released, after which the
+    /// current instruction is retried.
+    fn wait_for_borrow(&mut self, wait: BorrowWait) -> VmHalt {
+        self.ip = self.last_ip();
+        self.last_ip_len = 0;
+        VmHalt::Awaited(Awaited::Borrow(wait))
     }
 
     /// Call a function at the given offset with the given number of arguments.
@@ -2879,13 +3992,26 @@ impl Vm {
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
-    fn op_call_associated(&mut self, hash: Hash, args: usize) -> VmResult<()> {
+    fn op_call_associated(&mut self, hash: Hash, args: usize) -> VmResult<Option<VmHalt>> {
         // NB: +1 to include the instance itself.
         let args = args + 1;
         let instance = vm_try!(self.stack.at_offset_from_top(args));
         let type_hash = vm_try!(instance.type_hash());
         let hash = Hash::associated_function(type_hash, hash);
+        self.call_associated_fn(hash, args)
+    }
+
+    /// Call an associated function where the type of the instance has been
+    /// proven at compile time, so it doesn't have to be looked up.
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_call_associated_typed(&mut self, hash: Hash, args: usize) -> VmResult<Option<VmHalt>> {
+        // NB: +1 to include the instance itself.
+        self.call_associated_fn(hash, args + 1)
+    }
 
+    /// Call the associated function with the given hash, where `args` includes
+    /// the instance.
+    fn call_associated_fn(&mut self, hash: Hash, args: usize) -> VmResult<Option<VmHalt>> {
         if let Some(UnitFn::Offset {
             offset,
             call,
@@ -2894,14 +4020,37 @@ impl Vm {
         {
             vm_try!(check_args(args, expected));
             vm_try!(self.call_offset_fn(offset, call, args));
-            return VmResult::Ok(());
+            return VmResult::Ok(None);
+        }
+
+        if let Some(halt) = vm_try!(self.call_base_fn(hash, args)) {
+            return VmResult::Ok(Some(halt));
+        }
+
+        if let Some(function) = self.mocks.get(hash) {
+            return function.call_with_vm(self, args);
         }
 
         if let Some(handler) = self.context.function(hash) {
-            vm_try!(handler(&mut self.stack, args));
-            return VmResult::Ok(());
+            let ip = self.last_ip();
+
+            if let Some(wait) = vm_try!(call_native(
+                &self.context,
+                &self.unit,
+                ip,
+                &mut self.stack,
+                handler,
+                hash,
+                args
+            )) {
+                return VmResult::Ok(Some(self.wait_for_borrow(wait)));
+            }
+
+            return VmResult::Ok(None);
         }
 
+        let instance = vm_try!(self.stack.at_offset_from_top(args));
+
         err(VmErrorKind::MissingInstanceFunction {
             instance: vm_try!(instance.type_info()),
             hash,
@@ -2925,7 +4074,19 @@ impl Vm {
             }
         };
 
-        vm_try!(self.op_call(ty.into_hash(), args));
+        let hash = ty.into_hash();
+
+        if let Some(halt) = vm_try!(self.call_base_fn(hash, args)) {
+            return VmResult::Ok(Some(halt));
+        }
+
+        if let Some(halt) = vm_try!(self.op_call(hash, args)) {
+            // NB: the function is put back so that the instruction can be
+            // retried.
+            self.stack.push(Value::Type(ty));
+            return VmResult::Ok(Some(halt));
+        }
+
         VmResult::Ok(None)
     }
 
@@ -2956,6 +4117,58 @@ impl Vm {
         VmResult::Ok(())
     }
 
+    #[cfg_attr(feature = "bench", inline(never))]
+    fn op_iter_step(
+        &mut self,
+        offset: usize,
+        end: usize,
+        step: i64,
+        inclusive: bool,
+        jump: usize,
+    ) -> VmResult<()> {
+        let end = match vm_try!(self.stack.at_offset(end)) {
+            Value::Integer(end) => *end,
+            end => {
+                let start = runtime::TypeInfo::StaticType(runtime::static_type::INTEGER_TYPE);
+                let end = vm_try!(end.type_info());
+
+                return err(if inclusive {
+                    VmErrorKind::UnsupportedIterRangeInclusive { start, end }
+                } else {
+                    VmErrorKind::UnsupportedIterRange { start, end }
+                });
+            }
+        };
+
+        let counter = vm_try!(self.stack.at_offset_mut(offset));
+
+        // NB: the counter is set to unit once stepping it would overflow.
+        let Value::Integer(current) = *counter else {
+            self.ip = vm_try!(self.unit.translate(jump));
+            return VmResult::Ok(());
+        };
+
+        let done = match (step > 0, inclusive) {
+            (true, false) => current >= end,
+            (true, true) => current > end,
+            (false, false) => current <= end,
+            (false, true) => current < end,
+        };
+
+        if done {
+            self.ip = vm_try!(self.unit.translate(jump));
+            return VmResult::Ok(());
+        }
+
+        *counter = match current.checked_add(step) {
+            Some(next) => Value::Integer(next),
+            None => Value::EmptyTuple,
+        };
+
+        self.stack.push(Value::Integer(current));
+        VmResult::Ok(())
+    }
+
     /// Call the provided closure within the context of this virtual machine.
     ///
     /// This allows for calling protocol function helpers like
@@ -2987,37 +4200,58 @@ impl Vm {
     /// vm.with(|| output.string_display(&mut f)).into_result()?.expect("formatting should succeed");
     /// # Ok::<_, rune::Error>(())
     /// ```
-    pub fn with<F, T>(&mut self, f: F) -> T
+    pub fn with<F, T>(&self, f: F) -> T
     where
         F: FnOnce() -> T,
     {
-        let _guard = crate::runtime::env::Guard::new(&self.context, &self.unit);
+        let _guard =
+            crate::runtime::env::Guard::new(&self.context, &self.unit, &self.signals, &self.mocks);
         f()
     }
 
     /// Evaluate a single instruction.
     pub(crate) fn run(&mut self) -> VmResult<VmHalt> {
+        // NB: a virtual machine constructed to call a function from native
+        // code uses the mocks of the virtual machine which is calling it.
+        if !self.mocks.is_enabled() {
+            self.mocks = crate::runtime::env::mocks();
+        }
+
         // NB: set up environment so that native function can access context and
         // unit.
-        let _guard = crate::runtime::env::Guard::new(&self.context, &self.unit);
+        let _guard =
+            crate::runtime::env::Guard::new(&self.context, &self.unit, &self.signals, &self.mocks);
 
         loop {
             if !budget::take() {
                 return VmResult::Ok(VmHalt::Limited);
             }
 
+            #[cfg(feature = "gc")]
+            self.gc.safepoint();
+
             let Some((inst, inst_len)) = vm_try!(self.unit.instruction_at(self.ip)) else {
                 return VmResult::err(VmErrorKind::IpOutOfBounds {
                     ip: self.ip,
-                    length: self.unit.instructions().end(),
+                    length: self.unit.storage().end(),
                 });
             };
 
             tracing::trace!(ip = ?self.ip, ?inst);
 
+            self.report.record(&inst, self.stack.len());
             self.ip = self.ip.wrapping_add(inst_len);
             self.last_ip_len = inst_len as u8;
 
+            if let Some(max) = self.limits.instructions {
+                if self.report.instructions > max {
+                    return VmResult::err(VmErrorKind::BudgetExceeded {
+                        limit: Limit::Instructions,
+                        max,
+                    });
+                }
+            }
+
             match inst {
                 Inst::Not => {
                     vm_try!(self.op_not());
@@ -3029,13 +4263,31 @@ impl Vm {
                     vm_try!(self.op_closure(hash, count));
                 }
                 Inst::Call { hash, args } => {
-                    vm_try!(self.op_call(hash, args));
+                    if let Some(halt) = vm_try!(self.call_base_fn(hash, args)) {
+                        return VmResult::Ok(halt);
+                    }
+
+                    if let Some(halt) = vm_try!(self.op_call(hash, args)) {
+                        return VmResult::Ok(halt);
+                    }
                 }
                 Inst::CallOffset { offset, call, args } => {
                     vm_try!(self.op_call_offset(offset, call, args));
                 }
+                Inst::CallUnpack { hash, args, count } => {
+                    if let Some(halt) = vm_try!(self.op_call_unpack(hash, args, count)) {
+                        return VmResult::Ok(halt);
+                    }
+                }
                 Inst::CallAssociated { hash, args } => {
-                    vm_try!(self.op_call_associated(hash, args));
+                    if let Some(halt) = vm_try!(self.op_call_associated(hash, args)) {
+                        return VmResult::Ok(halt);
+                    }
+                }
+                Inst::CallAssociatedTyped { hash, args } => {
+                    if let Some(halt) = vm_try!(self.op_call_associated_typed(hash, args)) {
+                        return VmResult::Ok(halt);
+                    }
                 }
                 Inst::CallFn { args } => {
                     if let Some(reason) = vm_try!(self.op_call_fn(args)) {
@@ -3079,6 +4331,20 @@ impl Vm {
                         return VmResult::Ok(VmHalt::Exited);
                     }
                 }
+                Inst::ReturnTuple { count, clean } => {
+                    if vm_try!(self.op_return_tuple(count, clean)) {
+                        return VmResult::Ok(VmHalt::Exited);
+                    }
+                }
+                Inst::Memo {
+                    hash,
+                    args,
+                    capacity,
+                } => {
+                    if vm_try!(self.op_memo(hash, args, capacity)) {
+                        return VmResult::Ok(VmHalt::Exited);
+                    }
+                }
                 Inst::Await => {
                     let future = vm_try!(self.op_await());
                     return VmResult::Ok(VmHalt::Awaited(Awaited::Future(future)));
@@ -3139,6 +4405,9 @@ impl Vm {
                 Inst::Vec { count } => {
                     vm_try!(self.op_vec(count));
                 }
+                Inst::VecSpread { count } => {
+                    vm_try!(self.op_vec_spread(count));
+                }
                 Inst::Tuple { count } => {
                     vm_try!(self.op_tuple(count));
                 }
@@ -3160,6 +4429,9 @@ impl Vm {
                 Inst::Object { slot } => {
                     vm_try!(self.op_object(slot));
                 }
+                Inst::ObjectSpread { count } => {
+                    vm_try!(self.op_object_spread(count));
+                }
                 Inst::Range { range } => {
                     vm_try!(self.op_range(range));
                 }
@@ -3181,6 +4453,9 @@ impl Vm {
                 Inst::Bytes { slot } => {
                     vm_try!(self.op_bytes(slot));
                 }
+                Inst::StaticData { slot } => {
+                    vm_try!(self.op_static_data(slot));
+                }
                 Inst::StringConcat { len, size_hint } => {
                     vm_try!(self.op_string_concat(len, size_hint));
                 }
@@ -3199,6 +4474,13 @@ impl Vm {
                         return VmResult::Ok(VmHalt::Exited);
                     }
                 }
+                Inst::TryOrJump {
+                    address,
+                    preserve,
+                    jump,
+                } => {
+                    vm_try!(self.op_try_or_jump(address, preserve, jump));
+                }
                 Inst::EqByte { byte } => {
                     vm_try!(self.op_eq_byte(byte));
                 }
@@ -3224,6 +4506,9 @@ impl Vm {
                 } => {
                     vm_try!(self.op_match_sequence(type_check, len, exact));
                 }
+                Inst::CheckArgument { offset, hash, slot } => {
+                    vm_try!(self.op_check_argument(offset, hash, slot));
+                }
                 Inst::MatchType { hash } => {
                     vm_try!(self.op_match_type(hash));
                 }
@@ -3259,6 +4544,15 @@ impl Vm {
                 Inst::IterNext { offset, jump } => {
                     vm_try!(self.op_iter_next(offset, jump));
                 }
+                Inst::IterStep {
+                    offset,
+                    end,
+                    step,
+                    inclusive,
+                    jump,
+                } => {
+                    vm_try!(self.op_iter_step(offset, end, step, inclusive, jump));
+                }
                 Inst::Panic { reason } => {
                     return err(VmErrorKind::Panic {
                         reason: Panic::from(reason),
@@ -3300,6 +4594,9 @@ pub struct CallFrame {
     /// Indicates that the call frame is isolated and should force an exit into
     /// the vm execution context.
     pub isolated: bool,
+    /// The number of values the tuple returned from the call frame is unpacked
+    /// into, if the caller unpacks it.
+    pub unpack: Option<usize>,
 }
 
 /// Clear stack on drop.
@@ -3311,6 +4608,43 @@ impl Drop for ClearStack<'_> {
     }
 }
 
+/// Call a native function handler with `args` arguments on the stack for the
+/// instruction at `ip` of `unit`.
+///
+/// With [BorrowMode::Queue], a call which fails because a value is borrowed by
+/// another execution has its arguments restored, and returns a wait for the
+/// borrow to be released after which the call should be retried.
+fn call_native(
+    context: &RuntimeContext,
+    unit: &Arc<Unit>,
+    ip: usize,
+    stack: &mut Stack,
+    handler: &FunctionHandler,
+    hash: Hash,
+    args: usize,
+) -> VmResult<Option<BorrowWait>> {
+    let _site = borrow::enter(context.borrow_mode(), unit, ip);
+
+    if context.borrow_mode() != BorrowMode::Queue {
+        vm_try!(handler(stack, args).with_argument_names(context, hash));
+        return VmResult::Ok(None);
+    }
+
+    let base = stack.len().saturating_sub(args);
+    let saved = stack.get(base..).map(<[Value]>::to_vec).unwrap_or_default();
+
+    match handler(stack, args).with_argument_names(context, hash) {
+        VmResult::Ok(()) => VmResult::Ok(None),
+        VmResult::Err(error) => match error.held_release().cloned() {
+            Some(release) if stack.len() == base => {
+                stack.extend(saved);
+                VmResult::Ok(Some(BorrowWait::new(release, error)))
+            }
+            _ => VmResult::Err(error),
+        },
+    }
+}
+
 /// Check that arguments matches expected or raise the appropriate error.
 fn check_args(args: usize, expected: usize) -> Result<(), VmErrorKind> {
     if args != expected {
@@ -82,8 +82,24 @@ impl VmCall {
 
         let context = self.context.unwrap_or_else(|| vm.context().clone());
         let unit = self.unit.unwrap_or_else(|| vm.unit().clone());
+        let base = vm.base().cloned();
+        let limits = *vm.limits();
+        let yield_interval = vm.yield_interval();
+        let cancellation = vm.cancellation_token().cloned();
+        let signals = vm.signals().share();
+        let mocks = vm.mocks().share();
+        #[cfg(feature = "std")]
+        let deadline = vm.deadline();
 
         let mut vm = Vm::with_stack(context, unit, new_stack);
+        vm.set_base(base);
+        vm.set_limits(limits);
+        vm.set_yield_interval(yield_interval);
+        vm.set_cancellation_token(cancellation);
+        vm.set_signals(signals);
+        vm.set_mocks(mocks);
+        #[cfg(feature = "std")]
+        vm.set_deadline(deadline);
         vm.set_ip(ip);
         VmResult::Ok(vm)
     }
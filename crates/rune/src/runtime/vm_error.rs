@@ -5,10 +5,13 @@ use crate::no_std::sync::Arc;
 
 use crate::compile::ItemBuf;
 use crate::hash::Hash;
+use crate::runtime::borrow::Release;
+use crate::runtime::message_catalog::{message, Localize, Localized};
 use crate::runtime::unit::{BadInstruction, BadJump};
 use crate::runtime::{
-    AccessError, BoxedPanic, CallFrame, ExecutionState, FullTypeOf, MaybeTypeOf, Panic, StackError,
-    TypeInfo, TypeOf, Unit, Vm, VmHaltInfo,
+    panic_hook, AccessError, Backtrace, BacktraceFrame, BoxedPanic, CallFrame, EnglishCatalog,
+    ExecutionState, FullTypeOf, Limit, MaybeTypeOf, MessageCatalog, Panic, RuntimeContext,
+    StackError, TypeInfo, TypeOf, Unit, Vm, VmHaltInfo,
 };
 
 /// Trait used to convert result types to [`VmResult`].
@@ -116,6 +119,8 @@ pub(crate) struct VmErrorInner {
     pub(crate) error: VmErrorAt,
     pub(crate) chain: Vec<VmErrorAt>,
     pub(crate) stacktrace: Vec<VmErrorLocation>,
+    /// If the error has been reported to a panic hook.
+    pub(crate) panic_reported: bool,
 }
 
 /// A virtual machine error which includes tracing information.
@@ -161,6 +166,58 @@ impl VmError {
         self.inner.stacktrace.first()
     }
 
+    /// Get the backtrace of the call frames which were active when the error
+    /// was raised, starting with the frame which raised it.
+    ///
+    /// This includes the frames of every virtual machine the error unwound
+    /// through. See [Backtrace] for how to render it.
+    pub fn backtrace(&self) -> Backtrace {
+        let mut frames = Vec::new();
+
+        for l in &self.inner.stacktrace {
+            frames.push(BacktraceFrame::new(l.unit.clone(), l.ip, false));
+
+            for frame in l.frames.iter().rev() {
+                frames.push(BacktraceFrame::new(l.unit.clone(), frame.ip, true));
+            }
+        }
+
+        Backtrace::new(frames)
+    }
+
+    /// Get the limit which was exceeded, if the error was raised because the
+    /// execution exceeded one of its [Limits][crate::runtime::Limits].
+    pub fn exceeded_limit(&self) -> Option<Limit> {
+        match &self.inner.error.kind {
+            VmErrorKind::BudgetExceeded { limit, .. } => Some(*limit),
+            _ => None,
+        }
+    }
+
+    /// Test if the error was raised because the execution was cancelled,
+    /// either through a [CancellationToken][crate::runtime::CancellationToken]
+    /// or because it timed out.
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.inner.error.kind, VmErrorKind::Cancelled)
+    }
+
+    /// Get the reason of the panic, if the error is an explicit panic.
+    pub(crate) fn panic_payload(&self) -> Option<&Panic> {
+        match &self.inner.error.kind {
+            VmErrorKind::Panic { reason } => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Get a notification for when the borrow which caused the error is
+    /// released, if the error is caused by a borrow held by an execution.
+    pub(crate) fn held_release(&self) -> Option<&Release> {
+        match &self.inner.error.kind {
+            VmErrorKind::AccessError { error } => error.held_release(),
+            _ => None,
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn into_kind(self) -> VmErrorKind {
         self.inner.error.kind
@@ -247,6 +304,18 @@ impl<T> VmResult<T> {
         }
     }
 
+    /// Report the error to the panic hook of the virtual machine, if one is
+    /// installed.
+    pub(crate) fn report_panic(self, vm: &Vm) -> Self {
+        match self {
+            Self::Ok(ok) => Self::Ok(ok),
+            Self::Err(mut err) => {
+                panic_hook::report(vm, &mut err);
+                Self::Err(err)
+            }
+        }
+    }
+
     /// Add auxilliary errors if appropriate.
     #[inline]
     pub(crate) fn with_error<E, O>(self, error: E) -> Self
@@ -271,6 +340,63 @@ impl<T> VmResult<T> {
         }
     }
 
+    /// Mark the error as being caused by the argument at position `arg`.
+    ///
+    /// Type mismatches are folded into a single
+    /// [`VmErrorKind::BadArgumentType`] error, everything else gets a
+    /// [`VmErrorKind::BadArgument`] added to its chain.
+    #[inline]
+    pub(crate) fn with_argument(self, arg: usize) -> Self {
+        match self {
+            Self::Ok(ok) => Self::Ok(ok),
+            Self::Err(mut err) => {
+                if let VmErrorKind::Expected { expected, actual } = &err.inner.error.kind {
+                    err.inner.error.kind = VmErrorKind::BadArgumentType {
+                        arg,
+                        name: None,
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    };
+
+                    return Self::Err(err);
+                }
+
+                Self::Err(err).with_error(|| VmErrorKind::bad_argument(arg))
+            }
+        }
+    }
+
+    /// Attach the argument names of the native function `hash` to any
+    /// argument errors raised directly by calling it.
+    ///
+    /// Errors which have already unwound through a virtual machine are left
+    /// alone, since their argument positions refer to some other function.
+    pub(crate) fn with_argument_names(self, context: &RuntimeContext, hash: Hash) -> Self {
+        match self {
+            Self::Ok(ok) => Self::Ok(ok),
+            Self::Err(mut err) => {
+                if !err.inner.stacktrace.is_empty() {
+                    return Self::Err(err);
+                }
+
+                let names = context.argument_names(hash);
+                let inner = &mut *err.inner;
+
+                for at in [&mut inner.error].into_iter().chain(&mut inner.chain) {
+                    if let VmErrorKind::BadArgument { arg, name }
+                    | VmErrorKind::BadArgumentType { arg, name, .. } = &mut at.kind
+                    {
+                        if name.is_none() {
+                            *name = names.get(*arg).copied();
+                        }
+                    }
+                }
+
+                Self::Err(err)
+            }
+        }
+    }
+
     /// Expect a value or panic.
     #[doc(hidden)]
     pub fn expect(self, msg: &str) -> T {
@@ -372,6 +498,7 @@ where
                 },
                 chain: Vec::new(),
                 stacktrace: Vec::new(),
+                panic_reported: false,
             }),
         }
     }
@@ -407,6 +534,7 @@ impl<const N: usize> From<[VmErrorKind; N]> for VmError {
                 },
                 chain,
                 stacktrace: Vec::new(),
+                panic_reported: false,
             }),
         }
     }
@@ -483,6 +611,9 @@ pub(crate) enum VmErrorKind {
     MissingStaticObjectKeys {
         slot: usize,
     },
+    MissingStaticData {
+        slot: usize,
+    },
     MissingVariantRtti {
         hash: Hash,
     },
@@ -495,6 +626,18 @@ pub(crate) enum VmErrorKind {
     },
     BadArgument {
         arg: usize,
+        name: Option<&'static str>,
+    },
+    BadArgumentType {
+        arg: usize,
+        name: Option<&'static str>,
+        expected: TypeInfo,
+        actual: TypeInfo,
+    },
+    BadArgumentAnnotation {
+        arg: usize,
+        expected: String,
+        actual: TypeInfo,
     },
     UnsupportedIndexSet {
         target: TypeInfo,
@@ -561,6 +704,10 @@ pub(crate) enum VmErrorKind {
         start: TypeInfo,
         end: TypeInfo,
     },
+    UnboundedRange {
+        range: &'static str,
+        method: &'static str,
+    },
     UnsupportedIterNextOperand {
         actual: TypeInfo,
     },
@@ -586,9 +733,18 @@ pub(crate) enum VmErrorKind {
     ConstNotSupported {
         actual: TypeInfo,
     },
+    SnapshotMismatch {
+        what: &'static str,
+    },
     KeyNotSupported {
         actual: TypeInfo,
     },
+    TransferNotSupported {
+        actual: TypeInfo,
+    },
+    TransferMissingType {
+        actual: TypeInfo,
+    },
     MissingInterfaceEnvironment,
     ExpectedExecutionState {
         expected: ExecutionState,
@@ -628,209 +784,438 @@ pub(crate) enum VmErrorKind {
         value: f64,
     },
     MissingCallFrame,
+    BudgetExceeded {
+        limit: Limit,
+        max: u64,
+    },
+    Cancelled,
+    MockOutsideTestMode,
+    MockNotContextFunction {
+        hash: Hash,
+    },
+}
+
+impl Localize for VmError {
+    #[inline]
+    fn localize(&self, catalog: &dyn MessageCatalog, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.error.kind.localize(catalog, f)
+    }
 }
 
 impl fmt::Display for VmErrorKind {
+    #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.localize(&EnglishCatalog, f)
+    }
+}
+
+impl Localize for VmErrorKind {
+    fn localize(&self, catalog: &dyn MessageCatalog, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        macro_rules! message {
+            ($id:literal, $template:literal $(, $arg:ident)* $(,)?) => {
+                message(catalog, f, $id, $template, &[$((stringify!($arg), $arg)),*])
+            };
+        }
+
         match self {
-            VmErrorKind::AccessError { error } => {
-                write!(f, "{error}")
-            }
-            VmErrorKind::StackError { error } => write!(f, "Stack error: {error}",),
-            VmErrorKind::BadInstruction { error } => {
-                write!(f, "{error}")
-            }
-            VmErrorKind::BadJump { error } => {
-                write!(f, "{error}")
-            }
-            VmErrorKind::Panic { reason } => write!(f, "Panicked: {reason}",),
-            VmErrorKind::NoRunningVm {} => write!(f, "No running virtual machines"),
-            VmErrorKind::Halted { halt } => write!(f, "Halted for unexpected reason `{halt}`",),
-            VmErrorKind::FormatError {} => write!(f, "Failed to format argument"),
-            VmErrorKind::Overflow {} => write!(f, "Numerical overflow"),
-            VmErrorKind::Underflow {} => write!(f, "Numerical underflow"),
-            VmErrorKind::DivideByZero {} => write!(f, "Division by zero"),
-            VmErrorKind::MissingEntry { item, hash } => {
-                write!(f, "Missing entry `{item}` with hash `{hash}`",)
-            }
-            VmErrorKind::MissingEntryHash { hash } => {
-                write!(f, "Missing entry with hash `{hash}`",)
-            }
-            VmErrorKind::MissingFunction { hash } => {
-                write!(f, "Missing function with hash `{hash}`",)
-            }
-            VmErrorKind::MissingContextFunction { hash } => {
-                write!(f, "Missing context function with hash `{hash}`",)
-            }
-            VmErrorKind::MissingInstanceFunction { hash, instance } => {
-                write!(f, "Missing instance function `{hash}` for `{instance}`",)
+            VmErrorKind::AccessError { error } => message!("access-error", "{error}", error),
+            VmErrorKind::StackError { error } => message!(
+                "stack-error",
+                "Stack error: {error}",
+                error,
+            ),
+            VmErrorKind::BadInstruction { error } => message!("bad-instruction", "{error}", error),
+            VmErrorKind::BadJump { error } => message!("bad-jump", "{error}", error),
+            VmErrorKind::Panic { reason } => {
+                let reason = &Localized::new(catalog, reason);
+                message!("panic", "Panicked: {reason}", reason)
             }
-            VmErrorKind::IpOutOfBounds { ip, length } => write!(
-                f,
+            VmErrorKind::NoRunningVm => message!("no-running-vm", "No running virtual machines"),
+            VmErrorKind::Halted { halt } => message!(
+                "halted",
+                "Halted for unexpected reason `{halt}`",
+                halt,
+            ),
+            VmErrorKind::FormatError => message!("format-error", "Failed to format argument"),
+            VmErrorKind::Overflow => message!("overflow", "Numerical overflow"),
+            VmErrorKind::Underflow => message!("underflow", "Numerical underflow"),
+            VmErrorKind::DivideByZero => message!("divide-by-zero", "Division by zero"),
+            VmErrorKind::MissingEntry { item, hash } => message!(
+                "missing-entry",
+                "Missing entry `{item}` with hash `{hash}`",
+                item,
+                hash,
+            ),
+            VmErrorKind::MissingEntryHash { hash } => message!(
+                "missing-entry-hash",
+                "Missing entry with hash `{hash}`",
+                hash,
+            ),
+            VmErrorKind::MissingFunction { hash } => message!(
+                "missing-function",
+                "Missing function with hash `{hash}`",
+                hash,
+            ),
+            VmErrorKind::MissingContextFunction { hash } => message!(
+                "missing-context-function",
+                "Missing context function with hash `{hash}`",
+                hash,
+            ),
+            VmErrorKind::MissingInstanceFunction { hash, instance } => message!(
+                "missing-instance-function",
+                "Missing instance function `{hash}` for `{instance}`",
+                hash,
+                instance,
+            ),
+            VmErrorKind::IpOutOfBounds { ip, length } => message!(
+                "ip-out-of-bounds",
                 "Instruction pointer `{ip}` is out-of-bounds `0-{length}`",
+                ip,
+                length,
             ),
-            VmErrorKind::UnsupportedBinaryOperation { op, lhs, rhs } => {
-                write!(
-                    f,
-                    "Unsupported binary operation `{op}` on `{lhs}` and `{rhs}`",
-                )
+            VmErrorKind::UnsupportedBinaryOperation { op, lhs, rhs } => message!(
+                "unsupported-binary-operation",
+                "Unsupported binary operation `{op}` on `{lhs}` and `{rhs}`",
+                op,
+                lhs,
+                rhs,
+            ),
+            VmErrorKind::UnsupportedUnaryOperation { op, operand } => message!(
+                "unsupported-unary-operation",
+                "Unsupported unary operation `{op}` on {operand}",
+                op,
+                operand,
+            ),
+            VmErrorKind::MissingStaticString { slot } => message!(
+                "missing-static-string",
+                "Static string slot `{slot}` does not exist",
+                slot,
+            ),
+            VmErrorKind::MissingStaticObjectKeys { slot } => message!(
+                "missing-static-object-keys",
+                "Static object keys slot `{slot}` does not exist",
+                slot,
+            ),
+            VmErrorKind::MissingStaticData { slot } => message!(
+                "missing-static-data",
+                "Static data slot `{slot}` does not exist",
+                slot,
+            ),
+            VmErrorKind::MissingVariantRtti { hash } => message!(
+                "missing-variant-rtti",
+                "Missing runtime information for variant with hash `{hash}`",
+                hash,
+            ),
+            VmErrorKind::MissingRtti { hash } => message!(
+                "missing-rtti",
+                "Missing runtime information for type with hash `{hash}`",
+                hash,
+            ),
+            VmErrorKind::BadArgumentCount { actual, expected } => message!(
+                "bad-argument-count",
+                "Wrong number of arguments `{actual}`, expected `{expected}`",
+                actual,
+                expected,
+            ),
+            // NB: argument indexes are zero-based, but they're displayed
+            // counting from one.
+            VmErrorKind::BadArgument { arg, name: None } => {
+                let arg = &arg.wrapping_add(1);
+                message!("bad-argument", "Bad argument #{arg}", arg)
             }
-            VmErrorKind::UnsupportedUnaryOperation { op, operand } => {
-                write!(f, "Unsupported unary operation `{op}` on {operand}",)
+            VmErrorKind::BadArgument { arg, name: Some(name) } => {
+                let arg = &arg.wrapping_add(1);
+                message!(
+                    "bad-argument-named",
+                    "Bad argument #{arg} `{name}`",
+                    arg,
+                    name,
+                )
             }
-            VmErrorKind::MissingStaticString { slot } => {
-                write!(f, "Static string slot `{slot}` does not exist",)
+            VmErrorKind::BadArgumentType { arg, name: None, expected, actual } => {
+                let arg = &arg.wrapping_add(1);
+                message!(
+                    "bad-argument-type",
+                    "Argument #{arg}: expected `{expected}`, got `{actual}`",
+                    arg,
+                    expected,
+                    actual,
+                )
             }
-            VmErrorKind::MissingStaticObjectKeys { slot } => {
-                write!(f, "Static object keys slot `{slot}` does not exist",)
+            VmErrorKind::BadArgumentType { arg, name: Some(name), expected, actual } => {
+                let arg = &arg.wrapping_add(1);
+                message!(
+                    "bad-argument-type-named",
+                    "Argument #{arg} `{name}`: expected `{expected}`, got `{actual}`",
+                    arg,
+                    name,
+                    expected,
+                    actual,
+                )
             }
-            VmErrorKind::MissingVariantRtti { hash } => write!(
-                f,
-                "Missing runtime information for variant with hash `{hash}`",
-            ),
-            VmErrorKind::MissingRtti { hash } => {
-                write!(f, "Missing runtime information for type with hash `{hash}`",)
+            VmErrorKind::BadArgumentAnnotation { arg, expected, actual } => {
+                let arg = &arg.wrapping_add(1);
+                message!(
+                    "bad-argument-annotation",
+                    "Argument #{arg}: expected `{expected}` according to its type annotation, got `{actual}`",
+                    arg,
+                    expected,
+                    actual,
+                )
             }
-            VmErrorKind::BadArgumentCount { actual, expected } => write!(
-                f,
-                "Wrong number of arguments `{actual}`, expected `{expected}`",
-            ),
-            VmErrorKind::BadArgument { arg } => write!(f, "Bad argument #{arg}"),
-            VmErrorKind::UnsupportedIndexSet {
+            VmErrorKind::UnsupportedIndexSet { target, index, value } => message!(
+                "unsupported-index-set",
+                "The index set operation `{target}[{index}] = {value}` is not supported",
                 target,
                 index,
                 value,
-            } => write!(
-                f,
-                "The index set operation `{target}[{index}] = {value}` is not supported",
             ),
-            VmErrorKind::UnsupportedIndexGet { target, index } => write!(
-                f,
+            VmErrorKind::UnsupportedIndexGet { target, index } => message!(
+                "unsupported-index-get",
                 "The index get operation `{target}[{index}]` is not supported",
+                target,
+                index,
             ),
-            VmErrorKind::UnsupportedTupleIndexGet { target, index } => write!(
-                f,
+            VmErrorKind::UnsupportedTupleIndexGet { target, index } => message!(
+                "unsupported-tuple-index-get",
                 "The tuple index get {index} operation is not supported on `{target}`",
+                target,
+                index,
             ),
-            VmErrorKind::UnsupportedTupleIndexSet { target } => write!(
-                f,
+            VmErrorKind::UnsupportedTupleIndexSet { target } => message!(
+                "unsupported-tuple-index-set",
                 "The tuple index set operation is not supported on `{target}`",
+                target,
             ),
-            VmErrorKind::UnsupportedObjectSlotIndexGet { target } => {
-                write!(f, "Field not available to get on `{target}`",)
-            }
-            VmErrorKind::UnsupportedObjectSlotIndexSet { target } => {
-                write!(f, "Field not available to set on `{target}`",)
-            }
-            VmErrorKind::UnsupportedIs { value, test_type } => {
-                write!(f, "Operation `{value} is {test_type}` is not supported",)
-            }
-            VmErrorKind::UnsupportedAs { value, type_hash } => {
-                write!(f, "Operation `{value} as {type_hash}` is not supported",)
-            }
-            VmErrorKind::UnsupportedCallFn { actual } => write!(
-                f,
+            VmErrorKind::UnsupportedObjectSlotIndexGet { target } => message!(
+                "unsupported-object-slot-index-get",
+                "Field not available to get on `{target}`",
+                target,
+            ),
+            VmErrorKind::UnsupportedObjectSlotIndexSet { target } => message!(
+                "unsupported-object-slot-index-set",
+                "Field not available to set on `{target}`",
+                target,
+            ),
+            VmErrorKind::UnsupportedIs { value, test_type } => message!(
+                "unsupported-is",
+                "Operation `{value} is {test_type}` is not supported",
+                value,
+                test_type,
+            ),
+            VmErrorKind::UnsupportedAs { value, type_hash } => message!(
+                "unsupported-as",
+                "Operation `{value} as {type_hash}` is not supported",
+                value,
+                type_hash,
+            ),
+            VmErrorKind::UnsupportedCallFn { actual } => message!(
+                "unsupported-call-fn",
                 "Type `{actual}` cannot be called since it's not a function",
+                actual,
+            ),
+            VmErrorKind::ObjectIndexMissing { slot } => message!(
+                "object-index-missing",
+                "Missing index by static string slot `{slot}`",
+                slot,
+            ),
+            VmErrorKind::MissingIndex { target } => message!(
+                "missing-index",
+                "Type `{target}` missing index",
+                target,
+            ),
+            VmErrorKind::MissingIndexInteger { target, index } => message!(
+                "missing-index-integer",
+                "Type `{target}` missing integer index `{index}`",
+                target,
+                index,
             ),
-            VmErrorKind::ObjectIndexMissing { slot } => {
-                write!(f, "Missing index by static string slot `{slot}`",)
-            }
-            VmErrorKind::MissingIndex { target } => {
-                write!(f, "Type `{target}` missing index",)
-            }
-            VmErrorKind::MissingIndexInteger { target, index } => {
-                write!(f, "Type `{target}` missing integer index `{index}`",)
-            }
             #[cfg(feature = "std")]
-            VmErrorKind::MissingIndexKey { target } => {
-                write!(f, "Type `{target}` missing index",)
-            }
-            VmErrorKind::OutOfRange { index, length } => write!(
-                f,
+            VmErrorKind::MissingIndexKey { target } => message!(
+                "missing-index-key",
+                "Type `{target}` missing index",
+                target,
+            ),
+            VmErrorKind::OutOfRange { index, length } => message!(
+                "out-of-range",
                 "Index out of bounds, the length is `{length}` but the index is `{index}`",
+                index,
+                length,
             ),
-            VmErrorKind::UnsupportedTryOperand { actual } => {
-                write!(f, "Type `{actual}` is not supported as try operand",)
-            }
-            VmErrorKind::UnsupportedIterRangeInclusive { start, end } => {
-                write!(f, "Cannot build an iterator out of {start}..={end}")
-            }
-            VmErrorKind::UnsupportedIterRangeFrom { start } => {
-                write!(f, "Cannot build an iterator out of {start}..")
-            }
-            VmErrorKind::UnsupportedIterRange { start, end } => {
-                write!(f, "Cannot build an iterator out of {start}..{end}")
-            }
-            VmErrorKind::UnsupportedIterNextOperand { actual } => {
-                write!(f, "Type `{actual}` is not supported as iter-next operand",)
-            }
-            VmErrorKind::Expected { expected, actual } => {
-                write!(f, "Expected type `{expected}` but found `{actual}`",)
-            }
-            VmErrorKind::ExpectedAny { actual } => {
-                write!(f, "Expected `Any` type, but found `{actual}`",)
-            }
-            VmErrorKind::ValueToIntegerCoercionError { from, to } => {
-                write!(f, "Failed to convert value `{from}` to integer `{to}`",)
-            }
-            VmErrorKind::IntegerToValueCoercionError { from, to } => {
-                write!(f, "Failed to convert integer `{from}` to value `{to}`",)
-            }
-            VmErrorKind::ExpectedTupleLength { actual, expected } => write!(
-                f,
+            VmErrorKind::UnsupportedTryOperand { actual } => message!(
+                "unsupported-try-operand",
+                "Type `{actual}` is not supported as try operand",
+                actual,
+            ),
+            VmErrorKind::UnsupportedIterRangeInclusive { start, end } => message!(
+                "unsupported-iter-range-inclusive",
+                "Cannot build an iterator out of {start}..={end}",
+                start,
+                end,
+            ),
+            VmErrorKind::UnsupportedIterRangeFrom { start } => message!(
+                "unsupported-iter-range-from",
+                "Cannot build an iterator out of {start}..",
+                start,
+            ),
+            VmErrorKind::UnsupportedIterRange { start, end } => message!(
+                "unsupported-iter-range",
+                "Cannot build an iterator out of {start}..{end}",
+                start,
+                end,
+            ),
+            VmErrorKind::UnboundedRange { range, method } => message!(
+                "unbounded-range",
+                "Cannot call `{method}` on unbounded range `{range}`",
+                range,
+                method,
+            ),
+            VmErrorKind::UnsupportedIterNextOperand { actual } => message!(
+                "unsupported-iter-next-operand",
+                "Type `{actual}` is not supported as iter-next operand",
+                actual,
+            ),
+            VmErrorKind::Expected { expected, actual } => message!(
+                "expected",
+                "Expected type `{expected}` but found `{actual}`",
+                expected,
+                actual,
+            ),
+            VmErrorKind::ExpectedAny { actual } => message!(
+                "expected-any",
+                "Expected `Any` type, but found `{actual}`",
+                actual,
+            ),
+            VmErrorKind::ValueToIntegerCoercionError { from, to } => message!(
+                "value-to-integer-coercion-error",
+                "Failed to convert value `{from}` to integer `{to}`",
+                from,
+                to,
+            ),
+            VmErrorKind::IntegerToValueCoercionError { from, to } => message!(
+                "integer-to-value-coercion-error",
+                "Failed to convert integer `{from}` to value `{to}`",
+                from,
+                to,
+            ),
+            VmErrorKind::ExpectedTupleLength { actual, expected } => message!(
+                "expected-tuple-length",
                 "Expected a tuple of length `{expected}`, but found one with length `{actual}`",
+                actual,
+                expected,
             ),
-            VmErrorKind::ConstNotSupported { actual } => {
-                write!(f, "Type `{actual}` can't be converted to a constant value",)
-            }
-            VmErrorKind::KeyNotSupported { actual } => {
-                write!(f, "Type `{actual}` can't be converted to a hash key",)
-            }
-            VmErrorKind::MissingInterfaceEnvironment {} => {
-                write!(f, "Missing interface environment")
-            }
-            VmErrorKind::ExpectedExecutionState { expected, actual } => {
-                write!(f, "Expected execution to be {expected}, but was {actual}",)
-            }
-            VmErrorKind::GeneratorComplete {} => {
-                write!(f, "Cannot resume a generator that has completed")
-            }
-            VmErrorKind::FutureCompleted {} => write!(f, "Future already completed"),
-            VmErrorKind::MissingVariant { name } => write!(f, "No variant matching `{name}`",),
-            VmErrorKind::MissingField { target, field } => {
-                write!(f, "Missing field `{field}` on `{target}`",)
-            }
-            VmErrorKind::MissingVariantName {} => {
-                write!(f, "missing variant name in runtime information")
-            }
-            VmErrorKind::MissingStructField { target, name } => write!(
-                f,
+            VmErrorKind::ConstNotSupported { actual } => message!(
+                "const-not-supported",
+                "Type `{actual}` can't be converted to a constant value",
+                actual,
+            ),
+            VmErrorKind::SnapshotMismatch { what } => message!(
+                "snapshot-mismatch",
+                "Snapshot was taken with a different {what}",
+                what,
+            ),
+            VmErrorKind::KeyNotSupported { actual } => message!(
+                "key-not-supported",
+                "Type `{actual}` can't be converted to a hash key",
+                actual,
+            ),
+            VmErrorKind::TransferNotSupported { actual } => message!(
+                "transfer-not-supported",
+                "Type `{actual}` can't be transferred to another virtual machine",
+                actual,
+            ),
+            VmErrorKind::TransferMissingType { actual } => message!(
+                "transfer-missing-type",
+                "Type `{actual}` is not available in the target virtual machine",
+                actual,
+            ),
+            VmErrorKind::MissingInterfaceEnvironment => message!(
+                "missing-interface-environment",
+                "Missing interface environment",
+            ),
+            VmErrorKind::ExpectedExecutionState { expected, actual } => message!(
+                "expected-execution-state",
+                "Expected execution to be {expected}, but was {actual}",
+                expected,
+                actual,
+            ),
+            VmErrorKind::GeneratorComplete => message!(
+                "generator-complete",
+                "Cannot resume a generator that has completed",
+            ),
+            VmErrorKind::FutureCompleted => message!(
+                "future-completed",
+                "Future already completed",
+            ),
+            VmErrorKind::MissingVariant { name } => message!(
+                "missing-variant",
+                "No variant matching `{name}`",
+                name,
+            ),
+            VmErrorKind::MissingField { target, field } => message!(
+                "missing-field",
+                "Missing field `{field}` on `{target}`",
+                target,
+                field,
+            ),
+            VmErrorKind::MissingVariantName => message!(
+                "missing-variant-name",
+                "missing variant name in runtime information",
+            ),
+            VmErrorKind::MissingStructField { target, name } => message!(
+                "missing-struct-field",
                 "missing dynamic field for struct field `{target}::{name}`",
+                target,
+                name,
             ),
-            VmErrorKind::MissingTupleIndex { target, index } => write!(
-                f,
+            VmErrorKind::MissingTupleIndex { target, index } => message!(
+                "missing-tuple-index",
                 "missing dynamic index #{index} in tuple struct `{target}`",
+                target,
+                index,
             ),
-            VmErrorKind::ExpectedVariant { actual } => {
-                write!(f, "Expected an enum variant, but got `{actual}`",)
-            }
-            VmErrorKind::UnsupportedObjectFieldGet { target } => write!(
-                f,
+            VmErrorKind::ExpectedVariant { actual } => message!(
+                "expected-variant",
+                "Expected an enum variant, but got `{actual}`",
+                actual,
+            ),
+            VmErrorKind::UnsupportedObjectFieldGet { target } => message!(
+                "unsupported-object-field-get",
                 "The object field get operation is not supported on `{target}`",
+                target,
+            ),
+            VmErrorKind::IllegalFloatComparison { lhs, rhs } => message!(
+                "illegal-float-comparison",
+                "Cannot perform a comparison of the floats {lhs} and {rhs}",
+                lhs,
+                rhs,
             ),
-            VmErrorKind::IllegalFloatComparison { lhs, rhs } => {
-                write!(
-                    f,
-                    "Cannot perform a comparison of the floats {lhs} and {rhs}",
-                )
-            }
             #[cfg(feature = "std")]
-            VmErrorKind::IllegalFloatOperation { value } => {
-                write!(f, "Cannot perform operation on float `{value}`",)
-            }
-            VmErrorKind::MissingCallFrame => {
-                write!(f, "Missing call frame for internal vm call")
-            }
+            VmErrorKind::IllegalFloatOperation { value } => message!(
+                "illegal-float-operation",
+                "Cannot perform operation on float `{value}`",
+                value,
+            ),
+            VmErrorKind::MissingCallFrame => message!(
+                "missing-call-frame",
+                "Missing call frame for internal vm call",
+            ),
+            VmErrorKind::BudgetExceeded { limit, max } => message!(
+                "budget-exceeded",
+                "Execution exceeded the limit of {max} {limit}",
+                limit,
+                max,
+            ),
+            VmErrorKind::Cancelled => message!("cancelled", "Execution was cancelled"),
+            VmErrorKind::MockOutsideTestMode => message!(
+                "mock-outside-test-mode",
+                "Functions can only be mocked in test mode",
+            ),
+            VmErrorKind::MockNotContextFunction { hash } => message!(
+                "mock-not-context-function",
+                "Only functions in the context can be mocked, but `{hash}` is not one",
+                hash,
+            ),
         }
     }
 }
@@ -866,7 +1251,7 @@ impl From<BadJump> for VmErrorKind {
 impl VmErrorKind {
     /// Bad argument.
     pub fn bad_argument(arg: usize) -> Self {
-        Self::BadArgument { arg }
+        Self::BadArgument { arg, name: None }
     }
 
     /// Construct an expected error.
@@ -1,14 +1,16 @@
 use core::fmt;
 use core::future::Future;
 use core::mem::{replace, take};
+use core::pin::Pin;
+use core::task::{Context, Poll};
 
 use crate::no_std::prelude::*;
 use crate::no_std::sync::Arc;
 
 use crate::runtime::budget;
 use crate::runtime::{
-    Generator, GeneratorState, RuntimeContext, Stream, Unit, Value, Vm, VmErrorKind, VmHalt,
-    VmHaltInfo, VmResult,
+    Awaited, Breakpoint, CancellationToken, ExecutionReport, Generator, GeneratorState, Limits,
+    RuntimeContext, Stream, Unit, Value, Vm, VmErrorKind, VmHalt, VmHaltInfo, VmResult,
 };
 use crate::shared::AssertSend;
 
@@ -54,6 +56,8 @@ where
     state: ExecutionState,
     /// Indicates the current stack of suspended contexts.
     states: Vec<VmExecutionState>,
+    /// If the execution is suspended at a breakpoint.
+    at_breakpoint: bool,
 }
 
 impl<T> VmExecution<T>
@@ -66,6 +70,7 @@ where
             head,
             state: ExecutionState::Initial,
             states: vec![],
+            at_breakpoint: false,
         }
     }
 
@@ -153,6 +158,37 @@ where
         self.head.as_mut()
     }
 
+    /// Access statistics collected so far by this execution.
+    ///
+    /// See [ExecutionReport] for an example.
+    pub fn report(&self) -> &ExecutionReport {
+        self.head.as_ref().last_execution_report()
+    }
+
+    /// Set the limits enforced on the rest of this execution, which are
+    /// counted from the start of it.
+    ///
+    /// This replaces the limits of the virtual machine running the execution.
+    /// See [Limits] for more information.
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.head.as_mut().set_limits(limits);
+    }
+
+    /// Set the number of instructions after which the rest of this execution
+    /// yields back to the executor when it's run asynchronously.
+    ///
+    /// See [Vm::set_yield_interval] for more information.
+    pub fn set_yield_interval(&mut self, interval: Option<usize>) {
+        self.head.as_mut().set_yield_interval(interval);
+    }
+
+    /// Set the token used to cancel the rest of this execution.
+    ///
+    /// See [CancellationToken] for more information.
+    pub fn set_cancellation_token(&mut self, token: Option<CancellationToken>) {
+        self.head.as_mut().set_cancellation_token(token);
+    }
+
     /// Complete the current execution without support for async instructions.
     ///
     /// This will error if the execution is suspended through yielding.
@@ -210,12 +246,24 @@ where
         loop {
             let vm = self.head.as_mut();
 
-            match vm_try!(vm.run().with_vm(vm)) {
+            // NB: an enclosing budget takes precedence over yielding.
+            let interval = vm.yield_interval().filter(|_| budget::get() == usize::MAX);
+
+            let halt = match interval {
+                Some(interval) => budget::with(interval, || vm.run()).call(),
+                None => vm.run(),
+            };
+
+            match vm_try!(halt.with_vm(vm).report_panic(vm)) {
                 VmHalt::Exited => (),
                 VmHalt::Awaited(awaited) => {
                     vm_try!(awaited.into_vm(vm).await);
                     continue;
                 }
+                VmHalt::Limited if interval.is_some() => {
+                    YieldNow(false).await;
+                    continue;
+                }
                 VmHalt::VmCall(vm_call) => {
                     vm_try!(vm_call.into_execution(self));
                     continue;
@@ -277,8 +325,15 @@ where
             let len = self.states.len();
             let vm = self.head.as_mut();
 
-            match vm_try!(vm.run().with_vm(vm)) {
+            match vm_try!(vm.run().with_vm(vm).report_panic(vm)) {
                 VmHalt::Exited => (),
+                VmHalt::Awaited(Awaited::Borrow(wait)) => {
+                    // NB: waiting for a borrow requires suspending the
+                    // execution, so it's reported as an error instead.
+                    return VmResult::Err(wait.into_error())
+                        .with_vm(vm)
+                        .report_panic(vm);
+                }
                 VmHalt::VmCall(vm_call) => {
                     vm_try!(vm_call.into_execution(self));
                     continue;
@@ -311,8 +366,13 @@ where
         let len = self.states.len();
         let vm = self.head.as_mut();
 
-        match vm_try!(budget::with(1, || vm.run().with_vm(vm)).call()) {
+        match vm_try!(budget::with(1, || vm.run().with_vm(vm).report_panic(vm)).call()) {
             VmHalt::Exited => (),
+            VmHalt::Awaited(Awaited::Borrow(wait)) => {
+                return VmResult::Err(wait.into_error())
+                    .with_vm(vm)
+                    .report_panic(vm);
+            }
             VmHalt::VmCall(vm_call) => {
                 vm_try!(vm_call.into_execution(self));
                 return VmResult::Ok(None);
@@ -334,12 +394,46 @@ where
         VmResult::Ok(None)
     }
 
+    /// Run the execution until it completes or hits one of the given
+    /// breakpoints, without support for async instructions.
+    ///
+    /// Returns the value produced if the execution completes, or `None` if it
+    /// has been suspended at a breakpoint. Breakpoints are checked before each
+    /// instruction is executed, including the first one. When resuming an
+    /// execution suspended at a breakpoint, the instruction it's suspended at
+    /// is executed first, so that the breakpoint which was hit doesn't
+    /// immediately suspend it again.
+    ///
+    /// See [Breakpoint] for an example.
+    pub fn run_to_breakpoint(&mut self, breakpoints: &mut [Breakpoint]) -> VmResult<Option<Value>> {
+        let mut check = !take(&mut self.at_breakpoint);
+
+        loop {
+            if check {
+                let vm = self.head.as_ref();
+
+                for breakpoint in breakpoints.iter_mut() {
+                    if vm_try!(breakpoint.is_hit(vm)) {
+                        self.at_breakpoint = true;
+                        return VmResult::Ok(None);
+                    }
+                }
+            }
+
+            check = true;
+
+            if let Some(value) = vm_try!(self.step()) {
+                return VmResult::Ok(Some(value));
+            }
+        }
+    }
+
     /// Step the single execution for one step with support for async
     /// instructions.
     pub async fn async_step(&mut self) -> VmResult<Option<Value>> {
         let vm = self.head.as_mut();
 
-        match vm_try!(budget::with(1, || vm.run().with_vm(vm)).call()) {
+        match vm_try!(budget::with(1, || vm.run().with_vm(vm).report_panic(vm)).call()) {
             VmHalt::Exited => (),
             VmHalt::Awaited(awaited) => {
                 vm_try!(awaited.into_vm(vm).await);
@@ -409,16 +503,39 @@ impl VmExecution<&mut Vm> {
     /// Convert the current execution into one which owns its virtual machine.
     pub fn into_owned(self) -> VmExecution<Vm> {
         let stack = take(self.head.stack_mut());
-        let head = Vm::with_stack(self.head.context().clone(), self.head.unit().clone(), stack);
+        let mut head = Vm::with_stack(self.head.context().clone(), self.head.unit().clone(), stack);
+        head.set_limits(*self.head.limits());
+        head.set_yield_interval(self.head.yield_interval());
+        head.set_cancellation_token(self.head.cancellation_token().cloned());
+        head.set_signals(self.head.signals().share());
+        head.set_mocks(self.head.mocks().share());
 
         VmExecution {
             head,
             states: self.states,
             state: self.state,
+            at_breakpoint: self.at_breakpoint,
         }
     }
 }
 
+/// A future which yields to the executor once before completing.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
 /// A wrapper that makes [`VmExecution`] [`Send`].
 ///
 /// This is accomplished by preventing any [`Value`] from escaping the [`Vm`].
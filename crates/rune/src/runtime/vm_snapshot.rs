@@ -0,0 +1,253 @@
+use core::fmt::{self, Write};
+use core::hash;
+
+use serde::{Deserialize, Serialize};
+
+use crate::no_std::prelude::*;
+use crate::no_std::sync::Arc;
+
+use crate::hash::ParametersBuilder;
+use crate::runtime::{
+    CallFrame, ConstValue, RuntimeContext, Stack, Unit, Vm, VmErrorKind, VmResult,
+};
+use crate::Hash;
+
+/// A snapshot of the state of a virtual machine.
+///
+/// Snapshots are taken with [Vm::snapshot] and restored with [Vm::restore].
+/// They can be serialized using any serde format, which allows an embedder to
+/// run expensive initialization once, and then cheaply restore the warmed up
+/// state into new virtual machines. A snapshot can only be restored with a
+/// unit and context which are identical to the ones it was taken with.
+///
+/// Values on the stack are copied into the snapshot, so only values which can
+/// be represented as constants are supported. Any sharing between values on
+/// the stack is not preserved.
+///
+/// # Examples
+///
+/// ```
+/// use rune::{Context, Vm};
+/// use rune::runtime::VmSnapshot;
+/// use std::sync::Arc;
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime());
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main(table) {
+///             table.len()
+///         }
+///     }
+/// };
+///
+/// let unit = Arc::new(rune::prepare(&mut sources).with_context(&context).build()?);
+///
+/// let mut vm = Vm::new(runtime.clone(), unit.clone());
+/// let table = vec![1, 2, 3];
+/// let execution = vm.execute(["main"], (table,))?;
+/// let snapshot: VmSnapshot = execution.vm().snapshot()?;
+///
+/// let vm = Vm::restore(runtime, unit, &snapshot)?;
+/// let output: i64 = rune::from_value(vm.complete()?)?;
+/// assert_eq!(output, 3);
+/// # Ok::<_, rune::Error>(())
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VmSnapshot {
+    /// Fingerprint of the unit the snapshot was taken with.
+    unit: Hash,
+    /// Fingerprint of the context the snapshot was taken with.
+    context: Hash,
+    /// The instruction pointer.
+    ip: usize,
+    /// The length of the last executed instruction.
+    last_ip_len: u8,
+    /// Values on the stack.
+    stack: Vec<ConstValue>,
+    /// The bottom of the current stack frame.
+    stack_bottom: usize,
+    /// Call frames.
+    call_frames: Vec<SnapshotCallFrame>,
+}
+
+/// A serializable [CallFrame].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct SnapshotCallFrame {
+    ip: usize,
+    stack_bottom: usize,
+    isolated: bool,
+    unpack: Option<usize>,
+}
+
+impl VmSnapshot {
+    /// Take a snapshot of the given parts of a virtual machine.
+    pub(crate) fn new(
+        context: &RuntimeContext,
+        unit: &Unit,
+        ip: usize,
+        last_ip_len: u8,
+        stack: &Stack,
+        call_frames: &[CallFrame],
+    ) -> VmResult<Self> {
+        let mut values = Vec::with_capacity(stack.len());
+
+        for value in stack.iter() {
+            values.push(vm_try!(ConstValue::from_value_ref(value)));
+        }
+
+        let call_frames = call_frames
+            .iter()
+            .map(|frame| SnapshotCallFrame {
+                ip: frame.ip,
+                stack_bottom: frame.stack_bottom,
+                isolated: frame.isolated,
+                unpack: frame.unpack,
+            })
+            .collect();
+
+        VmResult::Ok(Self {
+            unit: unit.fingerprint(),
+            context: context.fingerprint(),
+            ip,
+            last_ip_len,
+            stack: values,
+            stack_bottom: stack.stack_bottom(),
+            call_frames,
+        })
+    }
+
+    /// Restore the snapshot into a new virtual machine.
+    pub(crate) fn restore(&self, context: Arc<RuntimeContext>, unit: Arc<Unit>) -> VmResult<Vm> {
+        if self.unit != unit.fingerprint() {
+            return VmResult::err(VmErrorKind::SnapshotMismatch { what: "unit" });
+        }
+
+        if self.context != context.fingerprint() {
+            return VmResult::err(VmErrorKind::SnapshotMismatch { what: "context" });
+        }
+
+        let values = self
+            .stack
+            .iter()
+            .cloned()
+            .map(ConstValue::into_value)
+            .collect();
+        let stack = Stack::from_parts(values, self.stack_bottom);
+
+        let call_frames = self
+            .call_frames
+            .iter()
+            .map(|frame| CallFrame {
+                ip: frame.ip,
+                stack_bottom: frame.stack_bottom,
+                isolated: frame.isolated,
+                unpack: frame.unpack,
+            })
+            .collect();
+
+        VmResult::Ok(Vm::from_parts(
+            context,
+            unit,
+            self.ip,
+            self.last_ip_len,
+            stack,
+            call_frames,
+        ))
+    }
+}
+
+/// Builder for the fingerprints which are used to check that a snapshot is
+/// restored with the same unit and context that it was taken with.
+pub(crate) struct Fingerprint {
+    builder: ParametersBuilder,
+}
+
+impl Fingerprint {
+    /// Construct a new empty fingerprint.
+    pub(crate) fn new() -> Self {
+        Self {
+            builder: ParametersBuilder::new(),
+        }
+    }
+
+    /// Add a hashable value to the fingerprint.
+    pub(crate) fn add<T>(&mut self, value: T)
+    where
+        T: hash::Hash,
+    {
+        self.builder.add(value);
+    }
+
+    /// Add a value to the fingerprint through its debug representation, which
+    /// must only depend on the value itself.
+    pub(crate) fn add_debug<T>(&mut self, value: &T)
+    where
+        T: ?Sized + fmt::Debug,
+    {
+        // NB: writing to a fingerprint never fails.
+        let _ = write!(self, "{:?}", value);
+        self.builder.add(());
+    }
+
+    /// Add a constant value to the fingerprint.
+    pub(crate) fn add_const(&mut self, value: &ConstValue) {
+        match value {
+            ConstValue::Vec(values) => {
+                self.add("vec");
+                self.add_consts(values);
+            }
+            ConstValue::Tuple(values) => {
+                self.add("tuple");
+                self.add_consts(values);
+            }
+            ConstValue::Object(object) => {
+                // NB: objects are hashed in key order, since the order in which
+                // they're iterated over is unspecified.
+                let mut entries = object.iter().collect::<Vec<_>>();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+
+                self.add("object");
+                self.add(entries.len());
+
+                for (key, value) in entries {
+                    self.add(key);
+                    self.add_const(value);
+                }
+            }
+            ConstValue::Option(option) => {
+                self.add("option");
+                self.add(option.is_some());
+
+                if let Some(value) = option {
+                    self.add_const(value);
+                }
+            }
+            value => {
+                self.add_debug(value);
+            }
+        }
+    }
+
+    fn add_consts(&mut self, values: &[ConstValue]) {
+        self.add(values.len());
+
+        for value in values {
+            self.add_const(value);
+        }
+    }
+
+    /// Finish building the fingerprint.
+    pub(crate) fn finish(&self) -> Hash {
+        self.builder.finish()
+    }
+}
+
+impl fmt::Write for Fingerprint {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.builder.add(s);
+        Ok(())
+    }
+}
@@ -12,6 +12,7 @@ use crate::no_std::prelude::*;
 
 #[cfg(feature = "emit")]
 use crate::ast::Span;
+use crate::compile::Location;
 
 /// A single source file.
 #[derive(Default, Clone)]
@@ -24,6 +25,8 @@ pub struct Source {
     path: Option<Box<Path>>,
     /// The starting byte indices in the source code.
     line_starts: Box<[usize]>,
+    /// The macro invocation this source was expanded from.
+    expanded_from: Option<Location>,
 }
 
 impl Source {
@@ -37,6 +40,7 @@ impl Source {
             source: source.into(),
             path: None,
             line_starts,
+            expanded_from: None,
         }
     }
 
@@ -58,6 +62,7 @@ impl Source {
             source: source.into(),
             path: None,
             line_starts,
+            expanded_from: None,
         }
     }
 
@@ -78,6 +83,7 @@ impl Source {
             source: source.into(),
             path: Some(path.as_ref().into()),
             line_starts,
+            expanded_from: None,
         })
     }
 
@@ -106,6 +112,7 @@ impl Source {
             source: source.into(),
             path: Some(path.as_ref().into()),
             line_starts,
+            expanded_from: None,
         }
     }
 
@@ -152,6 +159,23 @@ impl Source {
         self.path.as_deref()
     }
 
+    /// Get the location of the macro invocation this source was expanded
+    /// from, if it was inserted by a macro through
+    /// [MacroContext::insert_source][crate::macros::MacroContext::insert_source].
+    ///
+    /// Diagnostics for a source which was expanded from a macro also point out
+    /// the invocation.
+    pub fn expanded_from(&self) -> Option<Location> {
+        self.expanded_from
+    }
+
+    /// Mark the source as expanded from the macro invocation at the given
+    /// location.
+    pub(crate) fn with_expanded_from(mut self, location: Location) -> Self {
+        self.expanded_from = Some(location);
+        self
+    }
+
     /// Convert the given offset to a utf-16 line and character.
     pub(crate) fn pos_to_utf16cu_linecol(&self, offset: usize) -> (usize, usize) {
         let (line, offset, rest) = self.position(offset);
@@ -92,6 +92,15 @@ pub fn vm(
         return Err(RunError::BuildError(buffer));
     };
 
+    // NB: the test suite is run against native code when testing with the
+    // `jit` feature, which must produce the same results as the interpreter.
+    #[cfg(feature = "jit")]
+    let unit = {
+        let mut unit = unit;
+        unit.jit().expect("Failed to compile unit to native code");
+        unit
+    };
+
     let context = Arc::new(context.runtime());
     Ok(Vm::new(context, Arc::new(unit)))
 }
@@ -351,8 +360,17 @@ macro_rules! prelude {
     };
 }
 
+mod assembler;
 mod attribute;
+mod backtrace;
+mod bad_argument;
+#[cfg(feature = "std")]
+mod batch_build;
 mod binary;
+#[cfg(feature = "std")]
+mod borrow_mode;
+mod borrowed_args;
+mod breakpoints;
 mod bug_326;
 mod bug_344;
 mod bug_417;
@@ -360,10 +378,12 @@ mod bug_422;
 mod bug_428;
 mod bug_454;
 mod bugfixes;
+mod call_with_slice;
 mod capture;
 mod char;
 mod collections;
 mod comments;
+mod compile_pass;
 mod compiler_docs;
 mod compiler_expr_assign;
 mod compiler_fn;
@@ -374,42 +394,121 @@ mod compiler_patterns;
 mod compiler_use;
 mod compiler_visibility;
 mod compiler_warnings;
+mod const_eval;
+mod const_templates;
+#[cfg(feature = "doc")]
+mod context_aliases;
+mod context_docs;
+mod contextual_keywords;
 mod continue_;
 mod core_macros;
 mod custom_macros;
+mod debug_level;
+mod declarative_macros;
+mod defer;
+mod derive;
 mod derive_from_to_value;
 mod destructuring;
+#[cfg(feature = "emit")]
+mod diagnostics_json;
+mod early_bound;
+mod enum_discriminants;
+mod env;
+mod error_codes;
+mod execution_report;
+mod extern_const;
 mod external_constructor;
 mod external_generic;
 mod external_match;
 mod external_ops;
 mod float;
+mod folding_ranges;
 mod for_loop;
+#[cfg(feature = "gc")]
+mod gc;
 mod generics;
 mod getter_setter;
+mod hash;
+mod i18n;
+mod impl_consts;
+mod import_suggestions;
+#[cfg(feature = "json")]
+mod include_data;
+mod inst_meta;
 mod instance;
 mod int;
 mod iter;
 mod iterator;
+#[cfg(feature = "jit")]
+mod jit;
+mod layered_vm;
+#[cfg(feature = "lazy-unit")]
+mod lazy_unit;
+mod let_else;
+mod lexer_trivia;
+mod limits;
+mod literal_suffixes;
 mod macros;
+#[cfg(feature = "std")]
+mod math;
+mod memo;
+mod message_catalog;
+mod meta_query;
+mod mock;
+mod module_conflicts;
 mod moved;
+mod multi_return;
+mod native_error;
+mod object_interning;
+mod object_iter_ref;
+mod object_shapes;
 mod option;
+mod panic_hook;
+mod pass_manager;
+mod pattern_bindings;
 mod patterns;
+mod pretty_printer;
+mod purity;
 mod quote;
 mod range;
+mod range_loops;
 mod reference_error;
+mod references;
 mod rename_type;
+#[cfg(feature = "cli")]
+mod repl;
 mod result;
+mod semantic_tokens;
+mod signal;
+mod source_overlay;
+mod spread;
 mod stmt_reordering;
+mod strict;
+mod string_builder;
+mod string_encoding;
+#[cfg(feature = "cli")]
+mod test_runner;
 mod tuple;
+mod type_annotations;
 mod type_name_native;
 mod type_name_rune;
 mod unit_constants;
+#[cfg(feature = "serialize")]
+mod unit_format;
+mod unit_patch;
+mod unit_verify;
+mod value_diff;
+mod value_path;
+mod variadic_function;
 mod variants;
+mod vec_operations;
+mod vec_reductions;
 mod vm_arithmetic;
 mod vm_assign_exprs;
 mod vm_async_block;
 mod vm_blocks;
+#[cfg(feature = "std")]
+mod vm_cancellation;
 mod vm_closures;
 mod vm_const_exprs;
 mod vm_early_termination;
@@ -425,14 +524,17 @@ mod vm_not_used;
 mod vm_option;
 mod vm_pat;
 mod vm_result;
+mod vm_snapshot;
 mod vm_streams;
 mod vm_test_from_value_derive;
 mod vm_test_imports;
 mod vm_test_instance_fns;
 mod vm_test_linked_list;
 mod vm_test_mod;
+mod vm_transfer;
 mod vm_try;
 mod vm_tuples;
 mod vm_typed_tuple;
 mod vm_types;
+mod vm_yield_interval;
 mod wildcard_imports;
@@ -0,0 +1,118 @@
+prelude!();
+
+use crate::compile::{AssemblerError, UnitAssembler};
+use crate::no_std::sync::Arc;
+use crate::runtime::unit::VerifyError;
+use crate::runtime::{Call, Inst, InstAddress, InstOp};
+use crate::Unit;
+
+fn vm(unit: Unit) -> Vm {
+    let context = Context::new();
+    Vm::new(Arc::new(context.runtime()), Arc::new(unit))
+}
+
+#[test]
+fn assemble_functions() -> Result<()> {
+    let mut unit = UnitAssembler::new();
+
+    let mut f = unit.new_function();
+    let other = f.new_label("other");
+    f.push(Inst::Copy { offset: 0 });
+    f.push(Inst::Copy { offset: 1 });
+    f.push(Inst::Op {
+        op: InstOp::Lt,
+        a: InstAddress::Top,
+        b: InstAddress::Top,
+    });
+    f.jump_if(&other);
+    f.push(Inst::Return {
+        address: InstAddress::Offset(0),
+        clean: 2,
+    });
+    f.label(&other)?;
+    f.push(Inst::Return {
+        address: InstAddress::Offset(1),
+        clean: 2,
+    });
+    unit.declare_function(["max"], &["a", "b"], Call::Immediate, f)?;
+
+    let slot = unit.new_static_string("hello")?;
+    assert_eq!(unit.new_static_string("hello")?, slot);
+
+    let mut f = unit.new_function();
+    f.push(Inst::String { slot });
+    f.push(Inst::Return {
+        address: InstAddress::Top,
+        clean: 0,
+    });
+    unit.declare_function(["greeting"], &[], Call::Immediate, f)?;
+
+    let mut vm = vm(unit.build()?);
+
+    let out: i64 = from_value(vm.call(["max"], (10i64, 42i64))?)?;
+    assert_eq!(out, 42);
+    let out: i64 = from_value(vm.call(["max"], (42i64, 10i64))?)?;
+    assert_eq!(out, 42);
+    let out: String = from_value(vm.call(["greeting"], ())?)?;
+    assert_eq!(out, "hello");
+    Ok(())
+}
+
+#[test]
+fn assemble_errors() -> Result<()> {
+    // Jumps to labels which are never placed.
+    let mut unit = UnitAssembler::new();
+    let mut f = unit.new_function();
+    let label = f.new_label("missing");
+    f.jump(&label);
+    assert!(unit
+        .declare_function(["main"], &[], Call::Immediate, f)
+        .is_err());
+
+    // Functions declared twice.
+    let mut unit = UnitAssembler::new();
+
+    let mut f = unit.new_function();
+    f.push(Inst::ReturnUnit);
+    unit.declare_function(["main"], &[], Call::Immediate, f)?;
+
+    let mut f = unit.new_function();
+    f.push(Inst::ReturnUnit);
+    assert!(unit
+        .declare_function(["main"], &[], Call::Immediate, f)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn assemble_verifies() -> Result<()> {
+    let mut unit = UnitAssembler::new();
+
+    let mut f = unit.new_function();
+    f.push(Inst::Pop);
+    f.push(Inst::ReturnUnit);
+    unit.declare_function(["main"], &[], Call::Immediate, f)?;
+
+    assert_matches!(
+        unit.build(),
+        Err(AssemblerError::Verify {
+            error: VerifyError::StackUnderflow { ip: 0, depth: 0 }
+        })
+    );
+
+    let mut unit = UnitAssembler::new();
+
+    let mut f = unit.new_function();
+    f.push(Inst::String { slot: 0 });
+    f.push(Inst::ReturnUnit);
+    unit.declare_function(["main"], &[], Call::Immediate, f)?;
+
+    assert_matches!(
+        unit.build(),
+        Err(AssemblerError::Verify {
+            error: VerifyError::MissingStaticString { slot: 0, .. }
+        })
+    );
+
+    Ok(())
+}
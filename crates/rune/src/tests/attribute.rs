@@ -17,6 +17,18 @@ fn basic_use() {
             assert_eq!(1 + 1, 2);
         }
 
+        #[test]
+        #[ignore]
+        fn ignored_test_case() {
+            assert_eq!(1 + 1, 3);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panicking_test_case() {
+            panic!("expected");
+        }
+
         pub fn main() {
         }
     };
@@ -81,6 +93,23 @@ fn deny_nested_bench() {
     }
 }
 
+#[test]
+fn deny_test_params_without_test() {
+    assert_errors! {
+        "#[ignore] fn function() {}",
+        span!(0, 9), Custom { message } => {
+            assert_eq!(message.as_ref(), "The #[ignore] attribute is only supported on #[test] functions");
+        }
+    }
+
+    assert_errors! {
+        "#[should_panic] fn function() {}",
+        span!(0, 15), Custom { message } => {
+            assert_eq!(message.as_ref(), "The #[should_panic] attribute is only supported on #[test] functions");
+        }
+    }
+}
+
 #[test]
 fn deny_struct_attributes() {
     assert_errors! {
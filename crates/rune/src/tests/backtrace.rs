@@ -0,0 +1,85 @@
+prelude!();
+
+use std::sync::Arc;
+
+use crate::compile::DebugLevel;
+use crate::runtime::VmError;
+use crate::Options;
+
+const SOURCE: &str = r#"
+fn outer(n) {
+    inner(n, 0)
+}
+
+fn inner(a, b) {
+    let c = a / b;
+    c
+}
+
+pub fn main() {
+    outer(1)
+}
+"#;
+
+fn error(level: DebugLevel) -> Result<(VmError, Sources)> {
+    let mut options = Options::default();
+    options.debug_level(level);
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+
+    let context = Context::with_default_modules()?;
+    let unit = prepare(&mut sources).with_options(&options).build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let error = vm.call(["main"], ()).expect_err("expected an error");
+    Ok((error, sources))
+}
+
+#[test]
+fn backtrace_frames() -> Result<()> {
+    let (error, sources) = error(DebugLevel::Full)?;
+    let backtrace = error.backtrace();
+
+    let functions = backtrace
+        .frames()
+        .iter()
+        .map(|frame| frame.function())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        functions,
+        [
+            Some(Hash::type_hash(["inner"])),
+            Some(Hash::type_hash(["outer"])),
+            Some(Hash::type_hash(["main"])),
+        ]
+    );
+
+    assert_eq!(
+        backtrace.display(&sources).to_string(),
+        "   0: inner(a, b) at main:7:13\n   1: outer(n) at main:3:5\n   2: main() at main:12:5\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn backtrace_without_debug_info() -> Result<()> {
+    let (error, sources) = error(DebugLevel::None)?;
+    let backtrace = error.backtrace();
+
+    assert_eq!(backtrace.frames().len(), 3);
+
+    let frame = &backtrace.frames()[0];
+    assert_eq!(frame.function(), Some(Hash::type_hash(["inner"])));
+    assert!(frame.signature().is_none());
+    assert!(frame.location(&sources).is_none());
+
+    assert_eq!(
+        backtrace.display(&sources).to_string().lines().next(),
+        Some(format!("   0: {}", Hash::type_hash(["inner"])).as_str())
+    );
+
+    Ok(())
+}
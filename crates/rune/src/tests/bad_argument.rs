@@ -0,0 +1,63 @@
+prelude!();
+
+use VmErrorKind::*;
+
+#[rune::function]
+fn repeat(string: &str, count: usize) -> String {
+    string.repeat(count)
+}
+
+fn run_repeat(source: &str) -> runtime::VmError {
+    let mut module = Module::new();
+    module.function_meta(repeat).unwrap();
+
+    let mut context = Context::with_default_modules().unwrap();
+    context.install(module).unwrap();
+
+    let mut diagnostics = Default::default();
+    let mut sources = crate::tests::sources(source);
+
+    match crate::tests::run_helper::<_, _, String>(
+        &context,
+        &mut sources,
+        &mut diagnostics,
+        ["main"],
+        (),
+    ) {
+        Err(crate::tests::RunError::VmError(error)) => error,
+        Err(crate::tests::RunError::BuildError(error)) => panic!("build error: {error}"),
+        Ok(value) => panic!("expected error, got {value:?}"),
+    }
+}
+
+#[test]
+fn test_bad_argument_type_is_named() {
+    let error = run_repeat(r#"pub fn main() { repeat("a", "b") }"#);
+
+    assert_eq!(
+        error.to_string(),
+        "Argument #2 `count`: expected `i64`, got `String`"
+    );
+
+    assert_matches!(
+        error.into_kind(),
+        BadArgumentType {
+            arg: 1,
+            name: Some("count"),
+            ..
+        }
+    );
+}
+
+#[test]
+fn test_bad_argument_count() {
+    let error = run_repeat(r#"pub fn main() { repeat("a") }"#);
+
+    assert_matches!(
+        error.into_kind(),
+        BadArgumentCount {
+            actual: 1,
+            expected: 2
+        }
+    );
+}
@@ -0,0 +1,67 @@
+prelude!();
+
+use std::sync::Arc;
+
+use crate::diagnostics::Diagnostic;
+use crate::{prepare_batch, SourceId};
+
+fn batch(count: usize) -> Vec<Sources> {
+    (0..count)
+        .map(|n| {
+            let source = if n % 3 == 1 {
+                format!("pub fn main() {{ {n} + }}")
+            } else {
+                format!("pub fn main() {{ {n} * 2 }}")
+            };
+
+            let mut sources = Sources::new();
+            sources.insert(Source::new(format!("script{n}"), source));
+            sources
+        })
+        .collect()
+}
+
+#[test]
+fn batch_build() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+
+    for jobs in [1, 4] {
+        let mut sources = batch(10);
+
+        let outputs = prepare_batch(&mut sources)
+            .with_context(&context)
+            .with_jobs(jobs)
+            .build();
+
+        assert_eq!(outputs.len(), 10);
+
+        for (n, output) in outputs.into_iter().enumerate() {
+            if n % 3 == 1 {
+                assert!(output.unit.is_err(), "{n} should fail");
+
+                // Diagnostics only refer to the sources they were built from.
+                let [Diagnostic::Fatal(error)] = output.diagnostics.diagnostics() else {
+                    panic!("expected a single error: {:?}", output.diagnostics);
+                };
+
+                assert_eq!(error.source_id(), SourceId::new(0));
+                continue;
+            }
+
+            assert!(!output.diagnostics.has_error(), "{n} should build");
+
+            let mut vm = Vm::new(runtime.clone(), Arc::new(output.unit?));
+            let value: i64 = from_value(vm.call(["main"], ())?)?;
+            assert_eq!(value, n as i64 * 2);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn batch_build_empty() {
+    let outputs = prepare_batch(&mut []).build();
+    assert!(outputs.is_empty());
+}
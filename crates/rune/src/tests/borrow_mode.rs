@@ -0,0 +1,128 @@
+prelude!();
+
+use std::future::{poll_fn, Future};
+use std::sync::Arc;
+use std::task::Poll;
+
+use crate::runtime::{BorrowMode, VmError};
+
+#[derive(Any)]
+struct Counter {
+    value: i64,
+}
+
+/// Increment the counter after yielding a couple of times, holding onto the
+/// borrow while doing so.
+async fn bump(mut counter: Mut<Counter>) {
+    for _ in 0..3 {
+        tokio::task::yield_now().await;
+    }
+
+    counter.value += 1;
+}
+
+fn vm(mode: BorrowMode) -> Result<(Vm, Vm)> {
+    let mut module = Module::new();
+    module.ty::<Counter>()?;
+    module.function(["bump"], bump)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+    context.set_borrow_mode(mode);
+
+    let mut sources = sources! {
+        entry => {
+            pub async fn main(counter) {
+                bump(counter).await;
+            }
+        }
+    };
+
+    let unit = Arc::new(prepare(&mut sources).with_context(&context).build()?);
+    let runtime = Arc::new(context.runtime());
+    Ok((
+        Vm::new(runtime.clone(), unit.clone()),
+        Vm::new(runtime, unit),
+    ))
+}
+
+type Outcome = (Value, Result<Value, VmError>, Result<Value, VmError>);
+
+/// Run two executions which both bump the same counter concurrently.
+async fn run_both(mode: BorrowMode) -> Result<Outcome> {
+    let (mut vm_a, mut vm_b) = vm(mode)?;
+    let counter = crate::to_value(Counter { value: 0 })?;
+
+    let mut a = vm_a.execute(["main"], (counter.clone(),))?;
+    let mut b = vm_b.execute(["main"], (counter.clone(),))?;
+
+    let (a, b) = tokio::join!(a.async_complete(), b.async_complete());
+    Ok((counter, a.into_result(), b.into_result()))
+}
+
+#[tokio::test]
+async fn borrow_error_is_untracked() -> Result<()> {
+    let (counter, a, b) = run_both(BorrowMode::Error).await?;
+
+    a?;
+    let error = b.unwrap_err();
+
+    let VmErrorKind::AccessError { error: access } = error.into_kind() else {
+        panic!("expected access error");
+    };
+
+    // Borrows aren't tracked by default, so the holder isn't known.
+    assert!(access.holder().is_none());
+    assert!(!access.to_string().contains("borrowed at"));
+
+    let counter = from_value::<Ref<Counter>>(counter)?;
+    assert_eq!(counter.value, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn borrow_queue_reports_holder() -> Result<()> {
+    let (mut vm, _) = vm(BorrowMode::Queue)?;
+    let counter = crate::to_value(Counter { value: 0 })?;
+
+    let mut execution = vm.execute(["main"], (counter.clone(),))?;
+    let mut complete = Box::pin(execution.async_complete());
+
+    // Poll the execution once, which suspends it while `bump` holds onto the
+    // borrow of the counter.
+    let poll = poll_fn(|cx| Poll::Ready(complete.as_mut().poll(cx))).await;
+    assert!(poll.is_pending());
+
+    let error = from_value::<Mut<Counter>>(counter.clone()).unwrap_err();
+
+    let VmErrorKind::AccessError { error: access } = error.into_kind() else {
+        panic!("expected access error");
+    };
+
+    let holder = access.holder().expect("borrow should have a holder");
+    assert_eq!(
+        holder.function().map(|f| f.to_string()).as_deref(),
+        Some("main")
+    );
+    assert!(holder.location().is_some());
+    assert!(access.to_string().contains("borrowed at instruction"));
+
+    complete.await.into_result()?;
+
+    // The borrow is released once the execution completes.
+    let counter = from_value::<Mut<Counter>>(counter)?;
+    assert_eq!(counter.value, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn borrow_queue_waits_for_release() -> Result<()> {
+    let (counter, a, b) = run_both(BorrowMode::Queue).await?;
+
+    a?;
+    b?;
+
+    let counter = from_value::<Ref<Counter>>(counter)?;
+    assert_eq!(counter.value, 2);
+    Ok(())
+}
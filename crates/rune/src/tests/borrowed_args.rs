@@ -0,0 +1,79 @@
+prelude!();
+
+use crate::no_std::sync::Arc;
+
+fn vm(source: &str) -> Result<Vm> {
+    let mut module = Module::new();
+    module.function(["str_ptr"], |s: &str| s.as_ptr() as usize)?;
+    module.function(["bytes_ptr"], |b: &[u8]| b.as_ptr() as usize)?;
+    module.function(["upper"], |mut s: Mut<str>| s.make_ascii_uppercase())?;
+    module.function(["zero"], |b: &mut [u8]| b.fill(0))?;
+    module.function(["str_len"], str_len)?;
+    module.function(["bytes_len"], bytes_len)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let mut sources = crate::tests::sources(source);
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    return Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)));
+
+    async fn str_len(s: Ref<str>) -> usize {
+        s.len()
+    }
+
+    async fn bytes_len(b: Ref<[u8]>) -> usize {
+        b.len()
+    }
+}
+
+#[test]
+fn borrowed_args_are_not_copied() -> Result<()> {
+    let mut vm = vm(r#"
+        pub fn main(s, b) {
+            let ptrs = (str_ptr(s), bytes_ptr(b));
+            upper(s);
+            zero(b);
+            (ptrs, s, b)
+        }
+    "#)?;
+
+    let s = crate::to_value(String::from("hello"))?;
+    let b = crate::to_value(Bytes::from_vec(vec![1, 2, 3]))?;
+
+    let s_ptr = s
+        .clone()
+        .into_string()
+        .into_result()?
+        .borrow_ref()?
+        .as_ptr() as usize;
+    let b_ptr = b.clone().into_bytes().into_result()?.borrow_ref()?.as_ptr() as usize;
+
+    let output = vm.call(["main"], (s.clone(), b.clone()))?;
+    let ((s_out, b_out), s, b): ((usize, usize), String, Bytes) = from_value(output)?;
+
+    assert_eq!(s_out, s_ptr);
+    assert_eq!(b_out, b_ptr);
+    assert_eq!(s, "HELLO");
+    assert_eq!(b, [0, 0, 0]);
+    Ok(())
+}
+
+#[test]
+fn borrowed_args_async() -> Result<()> {
+    let mut vm = vm(r#"
+        pub async fn main(s, b) {
+            let n = str_len(s).await + bytes_len(b).await;
+            // The arguments are still usable after the calls.
+            (n, s, b)
+        }
+    "#)?;
+
+    let output = block_on(vm.async_call(["main"], ("hello", Bytes::from_vec(vec![1, 2]))))?;
+    let (n, s, b): (usize, String, Bytes) = from_value(output)?;
+
+    assert_eq!(n, 7);
+    assert_eq!(s, "hello");
+    assert_eq!(b, [1, 2]);
+    Ok(())
+}
@@ -0,0 +1,114 @@
+prelude!();
+
+use std::sync::Arc;
+
+use crate::runtime::Breakpoint;
+use crate::Unit;
+
+const SOURCE: &str = r#"
+pub fn main() {
+    let total = 0;
+
+    for n in 0..10 {
+        total += n;
+    }
+
+    total
+}
+"#;
+
+fn build(context: &Context) -> Result<(Arc<Unit>, usize)> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+
+    let unit = prepare(&mut sources).with_context(context).build()?;
+
+    let start = SOURCE.find("total += n").expect("missing statement");
+
+    let ip = unit
+        .debug_info()
+        .expect("missing debug info")
+        .instructions
+        .iter()
+        .filter(|(_, inst)| inst.span.start.into_usize() == start)
+        .map(|(ip, _)| *ip)
+        .min()
+        .expect("missing instruction");
+
+    Ok((Arc::new(unit), ip))
+}
+
+/// Run `main` to completion, returning its output and the number of times a
+/// breakpoint was hit.
+fn run(context: &Context, unit: Arc<Unit>, breakpoints: &mut [Breakpoint]) -> Result<(i64, usize)> {
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+    let mut execution = vm.execute(["main"], ())?;
+    let mut hits = 0;
+
+    loop {
+        match execution.run_to_breakpoint(breakpoints).into_result()? {
+            Some(value) => return Ok((from_value(value)?, hits)),
+            None => hits += 1,
+        }
+    }
+}
+
+#[test]
+fn test_variables_at() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let (unit, ip) = build(&context)?;
+
+    let debug = unit.debug_info().expect("missing debug info");
+
+    let names = debug
+        .variables_at(ip)
+        .into_iter()
+        .map(|v| &*v.name)
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, ["total", "n"]);
+    Ok(())
+}
+
+#[test]
+fn test_breakpoints() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let (unit, ip) = build(&context)?;
+
+    let mut breakpoints = [Breakpoint::new(ip)];
+    assert_eq!(run(&context, unit.clone(), &mut breakpoints)?, (45, 10));
+
+    let mut breakpoints = [Breakpoint::with_condition(
+        &context,
+        &unit,
+        ip,
+        "n % 3 == 0",
+    )?];
+    assert_eq!(run(&context, unit.clone(), &mut breakpoints)?, (45, 4));
+
+    let mut breakpoints = [Breakpoint::with_condition(
+        &context,
+        &unit,
+        ip,
+        "total > 100",
+    )?];
+    assert_eq!(run(&context, unit.clone(), &mut breakpoints)?, (45, 0));
+
+    assert!(Breakpoint::with_condition(&context, &unit, ip, "missing == 1").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_breakpoint_at_entry() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let (unit, _) = build(&context)?;
+
+    // NB: `main` is the only function in the unit, so it starts at the first
+    // instruction.
+    let mut breakpoints = [Breakpoint::new(0)];
+    assert_eq!(run(&context, unit.clone(), &mut breakpoints)?, (45, 1));
+
+    let mut breakpoints = [Breakpoint::with_condition(&context, &unit, 0, "true")?];
+    assert_eq!(run(&context, unit, &mut breakpoints)?, (45, 1));
+    Ok(())
+}
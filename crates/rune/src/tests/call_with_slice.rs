@@ -0,0 +1,63 @@
+prelude!();
+
+use crate::runtime::SyncFunction;
+use crate::Hash;
+
+fn vm(source: &str) -> Result<Vm> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+    let unit = prepare(&mut sources).build()?;
+    Ok(Vm::without_runtime(Arc::new(unit)))
+}
+
+#[test]
+fn call_with_slice() -> Result<()> {
+    let mut vm = vm("pub fn add(a, b, c) { a + b * c }")?;
+    let add = Hash::type_hash(["add"]);
+
+    let args = [Value::from(1i64), Value::from(2i64), Value::from(3i64)];
+
+    for _ in 0..4 {
+        let output = vm.call_with_slice(add, &args)?;
+        assert_eq!(from_value::<i64>(output)?, 7);
+    }
+
+    let output = vm.call(add, &args[..])?;
+    assert_eq!(from_value::<i64>(output)?, 7);
+
+    let output = vm.call(add, args.to_vec())?;
+    assert_eq!(from_value::<i64>(output)?, 7);
+    Ok(())
+}
+
+#[test]
+fn call_with_slice_bad_argument_count() -> Result<()> {
+    let mut vm = vm("pub fn add(a, b) { a + b }")?;
+    let add = Hash::type_hash(["add"]);
+
+    assert!(vm.call_with_slice(add, &[Value::from(1i64)]).is_err());
+    Ok(())
+}
+
+#[test]
+fn function_call_with_slice() -> Result<()> {
+    let mut vm = vm(r#"
+        struct Pair(a, b);
+        fn add(a, b) { a + b }
+        pub fn main() { (add, |a, b| a * b, Pair) }
+        pub fn unpack(pair) { (pair.0, pair.1) }
+    "#)?;
+
+    let output = vm.call(["main"], ())?;
+    let (add, mul, pair): (SyncFunction, SyncFunction, SyncFunction) = from_value(output)?;
+
+    let args = [Value::from(6i64), Value::from(7i64)];
+
+    assert_eq!(add.call::<_, i64>(&args[..]).into_result()?, 13);
+    assert_eq!(mul.call::<_, i64>(&args[..]).into_result()?, 42);
+
+    let pair = pair.call::<_, Value>(&args[..]).into_result()?;
+    let (a, b): (i64, i64) = from_value(vm.call(["unpack"], (pair,))?)?;
+    assert_eq!((a, b), (6, 7));
+    Ok(())
+}
@@ -41,3 +41,64 @@ fn test_hash_set_tuple() {
         }
     };
 }
+
+#[test]
+fn test_btree_map_ordered() {
+    let _: () = rune! {
+        pub fn main() {
+            use std::collections::BTreeMap;
+
+            let m = BTreeMap::new();
+
+            m.insert((1, 2), "c");
+            m.insert((0, 3), "b");
+            m[(0, 1)] = "a";
+
+            assert_eq!(m.keys().collect::<Vec>(), [(0, 1), (0, 3), (1, 2)]);
+            assert_eq!(m.values().collect::<Vec>(), ["a", "b", "c"]);
+            assert_eq!(m[(0, 3)], "b");
+            assert_eq!(m.remove((0, 3)), Some("b"));
+            assert_eq!(m.get((0, 3)), None);
+
+            let pairs = [];
+
+            for pair in m {
+                pairs.push(pair);
+            }
+
+            assert_eq!(pairs, [((0, 1), "a"), ((1, 2), "c")]);
+            assert_eq!(m, BTreeMap::from([((1, 2), "c"), ((0, 1), "a")]));
+        }
+    };
+}
+
+#[test]
+fn test_btree_map_keys() {
+    let _: () = rune! {
+        pub fn main() {
+            use std::collections::BTreeMap;
+
+            let key = [1, 2];
+            let m = BTreeMap::from([(key, "a"), ([1], "b")]);
+
+            // Keys are copied, so modifying them doesn't affect the map.
+            key.push(3);
+
+            assert_eq!(m.keys().collect::<Vec>(), [[1], [1, 2]]);
+            assert_eq!(m[[1, 2]], "a");
+            assert_eq!(m.get([1, 2, 3]), None);
+        }
+    };
+
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let m = std::collections::BTreeMap::new();
+            m.insert(1.0, "a");
+        }
+        "#,
+        KeyNotSupported { actual } => {
+            assert_eq!(actual.to_string(), "f64");
+        }
+    );
+}
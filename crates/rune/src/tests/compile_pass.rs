@@ -0,0 +1,379 @@
+prelude!();
+
+use std::sync::Arc;
+
+use crate::ast::Spanned;
+use crate::compile::{
+    CompilePass, PassAssembly, PassCall, PassContext, PassExprKind, PassFn, PassInst,
+};
+use crate::diagnostics::{Diagnostic, FatalDiagnosticKind, WarningDiagnosticKind};
+use crate::runtime::Inst;
+
+/// Forbids calls to the given item, and warns about calls to another.
+struct Policy {
+    banned: ItemBuf,
+    discouraged: ItemBuf,
+    functions: Vec<String>,
+}
+
+impl Policy {
+    fn new() -> Self {
+        Self {
+            banned: ItemBuf::with_item(["banned"]),
+            discouraged: ItemBuf::with_item(["discouraged"]),
+            functions: Vec::new(),
+        }
+    }
+}
+
+impl CompilePass for Policy {
+    fn visit_fn(&mut self, _: &mut PassContext<'_>, f: &PassFn<'_>) -> compile::Result<()> {
+        self.functions.push(f.item().to_string());
+        Ok(())
+    }
+
+    fn visit_call(
+        &mut self,
+        cx: &mut PassContext<'_>,
+        call: &mut PassCall<'_>,
+    ) -> compile::Result<()> {
+        if call.item() == self.banned {
+            return Err(compile::Error::msg(call, "Call to banned function"));
+        }
+
+        if call.item() == self.discouraged {
+            cx.warning(
+                &*call,
+                format_args!("Call to discouraged function `{}`", call.item()),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Forbids functions which await anything.
+struct NoAwait;
+
+impl CompilePass for NoAwait {
+    fn visit_assembly(
+        &mut self,
+        _: &mut PassContext<'_>,
+        f: &PassAssembly<'_>,
+    ) -> compile::Result<()> {
+        for (inst, span) in f.instructions() {
+            if let PassInst::Inst(Inst::Await) = inst {
+                return Err(compile::Error::msg(span, "Awaiting is not allowed"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Warns about loops in public functions, and records the functions they
+/// call.
+#[derive(Default)]
+struct PublicLoops {
+    calls: Vec<Hash>,
+}
+
+impl CompilePass for PublicLoops {
+    fn visit_fn(&mut self, cx: &mut PassContext<'_>, f: &PassFn<'_>) -> compile::Result<()> {
+        for expr in f.exprs() {
+            match expr.kind() {
+                PassExprKind::Loop | PassExprKind::For if f.is_public() => {
+                    cx.warning(expr, format_args!("Loop in public function `{}`", f.item()));
+                }
+                PassExprKind::Call { hash: Some(hash) } => {
+                    self.calls.push(hash);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Records the jumps and labels of the `main` function.
+#[derive(Default)]
+struct Jumps {
+    len: usize,
+    jumps: Vec<(usize, usize)>,
+    labels: Vec<usize>,
+}
+
+impl CompilePass for Jumps {
+    fn visit_assembly(
+        &mut self,
+        _: &mut PassContext<'_>,
+        f: &PassAssembly<'_>,
+    ) -> compile::Result<()> {
+        if f.item() != ItemBuf::with_item(["main"]) {
+            return Ok(());
+        }
+
+        for (offset, (inst, _)) in f.instructions().enumerate() {
+            self.len += 1;
+
+            match inst {
+                PassInst::Jump { target }
+                | PassInst::JumpIf { target }
+                | PassInst::JumpIfOrPop { target }
+                | PassInst::JumpIfNotOrPop { target }
+                | PassInst::JumpIfBranch { target, .. }
+                | PassInst::PopAndJumpIfNot { target, .. }
+                | PassInst::IterNext { target, .. }
+                | PassInst::IterStep { target, .. }
+                | PassInst::TryOrJump { target, .. } => {
+                    self.jumps.push((offset, target));
+                }
+                _ => {}
+            }
+        }
+
+        self.labels.extend(f.labels().map(|(offset, _)| offset));
+        Ok(())
+    }
+}
+
+/// Redirects every call to `original` to call `replacement` instead.
+struct Redirect {
+    from: ItemBuf,
+    to: Hash,
+}
+
+impl CompilePass for Redirect {
+    fn visit_call(
+        &mut self,
+        _: &mut PassContext<'_>,
+        call: &mut PassCall<'_>,
+    ) -> compile::Result<()> {
+        if call.item() == self.from {
+            call.redirect(self.to);
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_pass_visits_functions() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let mut sources = crate::tests::sources(
+        r#"
+        fn discouraged() {}
+
+        pub fn main() {
+            let f = || 1;
+            let a = async { 2 };
+            f()
+        }
+        "#,
+    );
+
+    let mut policy = Policy::new();
+
+    prepare(&mut sources)
+        .with_context(&context)
+        .with_pass(&mut policy)
+        .build()?;
+
+    assert_eq!(policy.functions.len(), 4);
+    assert!(policy.functions.iter().any(|f| f == "discouraged"));
+    assert!(policy.functions.iter().any(|f| f == "main"));
+    Ok(())
+}
+
+#[test]
+fn test_pass_errors_and_warnings() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let mut diagnostics = Diagnostics::new();
+    let mut sources = crate::tests::sources(
+        r#"
+        fn discouraged() {}
+        pub fn main() { discouraged() }
+        "#,
+    );
+
+    let mut policy = Policy::new();
+
+    prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .with_pass(&mut policy)
+        .build()?;
+
+    let [Diagnostic::Warning(warning)] = diagnostics.diagnostics() else {
+        panic!("expected a single warning: {:?}", diagnostics.diagnostics());
+    };
+
+    assert!(matches!(
+        &warning.kind,
+        WarningDiagnosticKind::Custom { message, .. } if &**message == "Call to discouraged function `discouraged`"
+    ));
+
+    let mut diagnostics = Diagnostics::new();
+    let mut sources = crate::tests::sources(
+        r#"
+        fn banned() {}
+        pub fn main() { banned() }
+        "#,
+    );
+
+    let result = prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .with_pass(&mut policy)
+        .build();
+
+    assert!(result.is_err());
+    assert!(diagnostics.has_error());
+    Ok(())
+}
+
+#[test]
+fn test_pass_instructions() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let mut diagnostics = Diagnostics::new();
+    let mut sources = crate::tests::sources(
+        r#"
+        async fn work() { 1 }
+        pub async fn main() { work().await }
+        "#,
+    );
+
+    let result = prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .with_pass(&mut NoAwait)
+        .build();
+
+    assert!(result.is_err());
+
+    let Some(Diagnostic::Fatal(error)) = diagnostics.into_diagnostics().into_iter().next() else {
+        panic!("expected a fatal diagnostic");
+    };
+
+    let FatalDiagnosticKind::CompileError(error) = error.into_kind() else {
+        panic!("expected a compile error");
+    };
+
+    assert_eq!(error.span(), span!(61, 73));
+
+    let mut sources = crate::tests::sources(
+        r#"
+        async fn work() { 1 }
+        pub async fn main() { work() }
+        "#,
+    );
+
+    prepare(&mut sources)
+        .with_context(&context)
+        .with_pass(&mut NoAwait)
+        .build()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_pass_redirect() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let mut sources = crate::tests::sources(
+        r#"
+        fn original(n) { n + 1 }
+        fn replacement(n) { n * 10 }
+        pub fn main() { original(4) + replacement(0) }
+        "#,
+    );
+
+    let mut redirect = Redirect {
+        from: ItemBuf::with_item(["original"]),
+        to: Hash::type_hash(["replacement"]),
+    };
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_pass(&mut redirect)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let value: i64 = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(value, 40);
+    Ok(())
+}
+
+#[test]
+fn test_pass_exprs() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let mut diagnostics = Diagnostics::new();
+    let mut sources = crate::tests::sources(
+        r#"
+        fn private() { loop { break; } }
+        pub fn main() { for i in 0..2 {} while false {} private() }
+        "#,
+    );
+
+    let mut pass = PublicLoops::default();
+
+    prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .with_pass(&mut pass)
+        .build()?;
+
+    let warnings = diagnostics
+        .diagnostics()
+        .iter()
+        .filter_map(|diagnostic| match diagnostic {
+            Diagnostic::Warning(warning) => match &warning.kind {
+                WarningDiagnosticKind::Custom { span, message } => Some((*span, &**message)),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        warnings,
+        [
+            (span!(66, 82), "Loop in public function `main`"),
+            (span!(83, 97), "Loop in public function `main`"),
+        ]
+    );
+
+    assert_eq!(pass.calls, [Hash::type_hash(["private"])]);
+    Ok(())
+}
+
+#[test]
+fn test_pass_jumps() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let mut sources = crate::tests::sources(
+        r#"
+        pub fn main(n) {
+            let i = 0;
+            while i < n { i += 1; }
+            i
+        }
+        "#,
+    );
+
+    let mut jumps = Jumps::default();
+
+    prepare(&mut sources)
+        .with_context(&context)
+        .with_pass(&mut jumps)
+        .build()?;
+
+    assert!(!jumps.jumps.is_empty());
+    assert!(jumps.jumps.iter().any(|&(offset, target)| target < offset));
+
+    for (_, target) in &jumps.jumps {
+        assert!(*target <= jumps.len);
+        assert!(jumps.labels.contains(target));
+    }
+
+    Ok(())
+}
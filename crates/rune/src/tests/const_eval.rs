@@ -0,0 +1,181 @@
+prelude!();
+
+use crate::compile::{IrErrorKind, Options};
+use crate::termcolor;
+
+use ErrorKind::*;
+
+#[test]
+fn const_eval_trace() {
+    assert_errors! {
+        r#"
+        const fn inner() { loop {} }
+        const fn outer() { inner() }
+        const VALUE = outer();
+        pub fn main() { VALUE }
+        "#,
+        _, ConstEvaluation { error, trace } => {
+            assert!(matches!(*error, IrError(IrErrorKind::BudgetExceeded)));
+
+            let items = trace.iter().map(|frame| frame.item.clone()).collect::<Vec<_>>();
+            assert_eq!(items, [ItemBuf::with_item(["outer"]), ItemBuf::with_item(["inner"])]);
+        }
+    };
+
+    // Constant functions called from regular functions are traced as well.
+    assert_errors! {
+        r#"
+        const fn inner() { loop {} }
+        pub fn main() { inner() }
+        "#,
+        _, ConstEvaluation { trace, .. } => {
+            assert_eq!(trace.len(), 1);
+            assert_eq!(trace[0].item, ItemBuf::with_item(["inner"]));
+        }
+    };
+
+    // Errors outside of constant functions have no trace.
+    assert_errors! {
+        r#"
+        const VALUE = { loop {} };
+        pub fn main() { VALUE }
+        "#,
+        _, IrError(IrErrorKind::BudgetExceeded)
+    };
+}
+
+#[test]
+fn const_eval_recursion_limit() {
+    assert_errors! {
+        r#"
+        const fn recurse(n) { recurse(n + 1) }
+        const VALUE = recurse(0);
+        pub fn main() { VALUE }
+        "#,
+        _, ConstEvaluation { error, trace } => {
+            assert!(matches!(*error, IrError(IrErrorKind::RecursionLimitExceeded { limit: 64 })));
+            assert_eq!(trace.len(), 64);
+        }
+    };
+}
+
+#[test]
+fn const_eval_arithmetic() {
+    assert_errors! {
+        "const VALUE = 9223372036854775807 + 1; pub fn main() { VALUE }",
+        span!(14, 37), IrError(IrErrorKind::Overflow)
+    };
+
+    assert_errors! {
+        "const VALUE = 0 - 9223372036854775807 - 2; pub fn main() { VALUE }",
+        span!(14, 41), IrError(IrErrorKind::Underflow)
+    };
+
+    assert_errors! {
+        "const VALUE = 4294967296 * 4294967296; pub fn main() { VALUE }",
+        span!(14, 37), IrError(IrErrorKind::Overflow)
+    };
+
+    assert_errors! {
+        "const VALUE = 10 / 0; pub fn main() { VALUE }",
+        span!(14, 20), IrError(IrErrorKind::DivideByZero)
+    };
+
+    assert_errors! {
+        "const VALUE = 1 << 64; pub fn main() { VALUE }",
+        span!(14, 21), IrError(IrErrorKind::Overflow)
+    };
+
+    // Assignments are checked as well.
+    assert_errors! {
+        r#"
+        const fn double(n) { n *= 2; n }
+        const VALUE = double(9223372036854775807);
+        pub fn main() { VALUE }
+        "#,
+        _, ConstEvaluation { error, .. } => {
+            assert!(matches!(*error, IrError(IrErrorKind::Overflow)));
+        }
+    };
+
+    assert_errors! {
+        r#"
+        const fn divide(n) { n /= 0; n }
+        const VALUE = divide(10);
+        pub fn main() { VALUE }
+        "#,
+        _, ConstEvaluation { error, .. } => {
+            assert!(matches!(*error, IrError(IrErrorKind::DivideByZero)));
+        }
+    };
+}
+
+#[test]
+fn const_eval_options() -> Result<()> {
+    let source = r#"
+    const fn count(to) {
+        let n = 0;
+        while n < to { n += 1; }
+        n
+    }
+
+    const fn depth(n) {
+        if n == 0 { 0 } else { depth(n - 1) + 1 }
+    }
+
+    pub fn main() { count(100) + depth(10) }
+    "#;
+
+    let build = |options: &Options| {
+        let mut sources = crate::tests::sources(source);
+        prepare(&mut sources).with_options(options).build()
+    };
+
+    assert!(build(&Options::default()).is_ok());
+
+    let mut options = Options::default();
+    options.const_budget(100);
+    assert!(build(&options).is_err());
+
+    let mut options = Options::default();
+    options.const_recursion_limit(10);
+    assert!(build(&options).is_err());
+
+    let mut options = Options::default();
+    options.parse_option("const-budget=100000")?;
+    options.parse_option("const-recursion-limit=11")?;
+    assert!(build(&options).is_ok());
+
+    assert!(options.parse_option("const-budget").is_err());
+    assert!(options.parse_option("const-recursion-limit=many").is_err());
+    Ok(())
+}
+
+#[test]
+fn const_eval_trace_diagnostics() -> Result<()> {
+    let mut sources = crate::tests::sources(
+        r#"
+        const fn inner() { loop {} }
+        const fn outer() { inner() }
+        const VALUE = outer();
+        pub fn main() { VALUE }
+        "#,
+    );
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = prepare(&mut sources)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    assert!(result.is_err());
+
+    let mut out = termcolor::Buffer::no_color();
+    diagnostics.emit(&mut out, &sources)?;
+    let out = String::from_utf8(out.into_inner())?;
+
+    assert!(out.contains("Evaluation budget exceeded"), "{out}");
+    assert!(out.contains("In call to `outer`"), "{out}");
+    assert!(out.contains("In call to `inner`"), "{out}");
+    Ok(())
+}
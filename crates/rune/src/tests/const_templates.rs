@@ -0,0 +1,97 @@
+prelude!();
+
+use crate::compile::Options;
+use crate::no_std::sync::Arc;
+use crate::runtime::Inst;
+
+fn build(source: &str) -> Result<(crate::Unit, Context)> {
+    let context = Context::with_default_modules()?;
+    let mut sources = crate::tests::sources(source);
+
+    let unit = crate::prepare(&mut sources)
+        .with_context(&context)
+        .build()?;
+
+    Ok((unit, context))
+}
+
+fn formats(unit: &crate::Unit) -> usize {
+    unit.iter_instructions()
+        .filter(|(_, inst)| matches!(inst, Inst::StringConcat { .. } | Inst::Format { .. }))
+        .count()
+}
+
+#[test]
+fn test_const_template() -> Result<()> {
+    let (unit, context) = build(
+        r#"
+        const NAME = "world";
+        const COUNT = 3;
+        const RATIO = 0.5;
+
+        pub fn main() {
+            let a = `Hello ${NAME}, ${COUNT} times at ${RATIO} and ${true} ${42}`;
+            let b = format!("{} is {}", NAME, COUNT);
+            (a, b)
+        }
+        "#,
+    )?;
+
+    assert_eq!(formats(&unit), 0);
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let value: (String, String) = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(
+        value,
+        (
+            "Hello world, 3 times at 0.5 and true 42".to_owned(),
+            "world is 3".to_owned()
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn test_runtime_template() -> Result<()> {
+    let (unit, context) = build(
+        r#"
+        const NAME = "world";
+        const CH = 'a';
+
+        pub fn main(n) {
+            (`Hello ${NAME} ${n}`, format!("Hello {:?}", NAME), `${CH}`)
+        }
+        "#,
+    )?;
+
+    assert_eq!(formats(&unit), 4);
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let value: (String, String, String) = from_value(vm.call(["main"], (1,))?)?;
+    assert_eq!(
+        value,
+        (
+            "Hello world 1".to_owned(),
+            "Hello \"world\"".to_owned(),
+            "a".to_owned()
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn test_const_template_budget() -> Result<()> {
+    let build = |options: &Options| {
+        let mut sources = crate::tests::sources("pub fn main() { `Hello ${1} ${true}` }");
+        prepare(&mut sources).with_options(options).build()
+    };
+
+    assert!(build(&Options::default()).is_ok());
+
+    // Folding templates is constant evaluation, so it's subject to the
+    // configured budget.
+    let mut options = Options::default();
+    options.const_budget(0);
+    assert!(build(&options).is_err());
+    Ok(())
+}
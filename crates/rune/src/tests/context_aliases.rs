@@ -0,0 +1,163 @@
+prelude!();
+
+use crate::compile::ItemBuf;
+
+#[derive(Any, Debug, Clone, Copy)]
+#[rune(item = ::internal::math)]
+struct Vec3 {
+    #[rune(get)]
+    x: i64,
+    #[rune(get)]
+    y: i64,
+    #[rune(get)]
+    z: i64,
+}
+
+impl Vec3 {
+    fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    fn sum(&self) -> i64 {
+        self.x + self.y + self.z
+    }
+}
+
+fn dot(a: &Vec3, b: &Vec3) -> i64 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+fn item(item: &str) -> ItemBuf {
+    item.parse().expect("valid item")
+}
+
+fn internal() -> Result<Module> {
+    let mut module = Module::with_crate_item("internal", ["math"]);
+    module.ty::<Vec3>()?;
+    module.function(["Vec3", "new"], Vec3::new)?;
+    module.associated_function("sum", Vec3::sum)?;
+    module.function(["dot"], dot)?;
+    module.constant(["ORIGIN"], 0i64)?;
+    Ok(module)
+}
+
+fn context() -> Result<Context> {
+    let mut engine = Module::with_crate("engine");
+    engine.alias(["Vec3"], item("::internal::math::Vec3"))?;
+    engine.alias(["dot"], item("::internal::math::dot"))?;
+    engine.alias(["math"], item("::internal::math"))?;
+    // Aliases can refer to items through other aliases.
+    engine.alias(["ZERO"], item("::engine::math::ORIGIN"))?;
+
+    let mut context = Context::new();
+    context.install(internal()?)?;
+    context.install(engine)?;
+    Ok(context)
+}
+
+#[test]
+fn alias_items() -> Result<()> {
+    let context = context()?;
+
+    let output: (i64, i64, bool, i64) = run(
+        &context,
+        r#"
+        use engine::Vec3;
+
+        pub fn main() {
+            let a = Vec3::new(1, 2, 3);
+            let b = engine::Vec3::new(4, 5, 6);
+            (a.sum(), engine::dot(a, b), b is engine::Vec3, engine::ZERO)
+        }
+        "#,
+        ["main"],
+        (),
+    )?;
+
+    assert_eq!(output, (6, 32, true, 0));
+    Ok(())
+}
+
+#[test]
+fn alias_modules() -> Result<()> {
+    let context = context()?;
+
+    let output: i64 = run(
+        &context,
+        r#"
+        use engine::math::*;
+
+        pub fn main() {
+            let a = Vec3::new(1, 2, 3);
+            dot(a, a) + engine::math::ORIGIN + a.x
+        }
+        "#,
+        ["main"],
+        (),
+    )?;
+
+    assert_eq!(output, 15);
+    Ok(())
+}
+
+#[test]
+fn alias_listing() -> Result<()> {
+    let context = context()?;
+
+    let aliases = context.aliases().map(|a| a.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(
+        aliases,
+        [
+            "::engine::Vec3 => ::internal::math::Vec3",
+            "::engine::dot => ::internal::math::dot",
+            "::engine::math => ::internal::math",
+            "::engine::ZERO => ::internal::math::ORIGIN",
+        ]
+    );
+
+    // Aliases resolve to the metadata of the item they refer to, rather than
+    // being registered as items of their own.
+    let metas = context
+        .lookup_meta(&item("::engine::math::dot"))
+        .into_iter()
+        .flatten()
+        .map(|meta| meta.item.as_ref().map(|item| item.to_string()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(metas, [Some(String::from("::internal::math::dot"))]);
+    Ok(())
+}
+
+#[test]
+fn alias_errors() -> Result<()> {
+    let mut module = Module::with_crate("engine");
+    module.alias(["dot"], item("::internal::math::dot"))?;
+
+    assert!(matches!(
+        module.alias(["dot"], item("::internal::math::Vec3")),
+        Err(ContextError::ConflictingAlias { .. })
+    ));
+
+    let mut context = Context::new();
+    context.install(internal()?)?;
+
+    let mut module = Module::with_crate("engine");
+    module.alias(["cross"], item("::internal::math::cross"))?;
+
+    let Err(ContextError::MissingAliasTarget { item, target }) = context.install(module) else {
+        panic!("expected missing alias target");
+    };
+
+    assert_eq!(item.to_string(), "::engine::cross");
+    assert_eq!(target.to_string(), "::internal::math::cross");
+
+    let mut module = Module::with_crate_item("internal", ["math"]);
+    module.alias(["dot"], item("::internal::math::Vec3"))?;
+
+    assert!(matches!(
+        context.install(module),
+        Err(ContextError::ConflictingAlias { .. })
+    ));
+    Ok(())
+}
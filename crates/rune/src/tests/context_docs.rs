@@ -0,0 +1,53 @@
+prelude!();
+
+/// Add two numbers.
+#[rune::function]
+fn add(a: i64, b: i64) -> i64 {
+    a + b
+}
+
+fn context() -> Result<Context> {
+    let mut module = Module::new();
+    module.function_meta(add)?;
+    module
+        .function(["sub"], |a: i64, b: i64| a - b)?
+        .docs([" Subtract two numbers."])
+        .argument_names(["a", "b"]);
+
+    let mut context = Context::new();
+    context.install(module)?;
+    Ok(context)
+}
+
+#[test]
+fn context_function_docs() -> Result<()> {
+    let context = context()?;
+
+    let meta = context.lookup_meta_by_hash(Hash::type_hash(["add"])).next();
+    let meta = meta.expect("missing add");
+    assert_eq!(meta.docs.lines(), [" Add two numbers."]);
+    assert_eq!(meta.docs.args().unwrap_or_default(), ["a", "b"]);
+
+    let meta = context.lookup_meta_by_hash(Hash::type_hash(["sub"])).next();
+    let meta = meta.expect("missing sub");
+    assert_eq!(meta.docs.lines(), [" Subtract two numbers."]);
+    assert_eq!(meta.docs.args().unwrap_or_default(), ["a", "b"]);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "doc")]
+fn context_hidden_function() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let hash = Hash::type_hash(["std", "test", "assertion_failed"]);
+    let meta = context.lookup_meta_by_hash(hash).next();
+    let meta = meta.expect("missing assertion_failed");
+    assert!(meta.docs.is_hidden());
+
+    let hash = Hash::type_hash(["std", "test", "diff"]);
+    let meta = context.lookup_meta_by_hash(hash).next();
+    let meta = meta.expect("missing diff");
+    assert!(!meta.docs.is_hidden());
+    Ok(())
+}
@@ -0,0 +1,85 @@
+prelude!();
+
+use diagnostics::WarningDiagnosticKind::*;
+
+#[test]
+fn contextual_keywords_as_identifiers() {
+    let out: (i64, i64, i64, i64) = rune_s! { r#"
+        struct Task { select, yield }
+
+        fn select(default) {
+            default * 2
+        }
+
+        pub fn main() {
+            let defer = 1;
+            let task = Task { select: 2, yield: 3 };
+            let object = #{ default: 4 };
+            (select(defer), task.select, task.yield, object.default)
+        }
+    "# };
+
+    assert_eq!(out, (2, 2, 3, 4));
+}
+
+#[test]
+fn contextual_keywords_in_position() {
+    let out: Vec<i64> = rune_s! { r#"
+        fn defer(out, n) {
+            out.push(n);
+        }
+
+        pub fn main() {
+            let out = [];
+
+            {
+                defer { out.push(2); }
+                defer(out, 1);
+            }
+
+            out
+        }
+    "# };
+
+    assert_eq!(out, [1, 2]);
+
+    let out: i64 = rune_s! { r#"
+        async fn value(n) {
+            n
+        }
+
+        pub async fn main() {
+            let default = value(1);
+
+            select {
+                default = default => default,
+            }
+        }
+    "# };
+
+    assert_eq!(out, 1);
+}
+
+#[test]
+fn reserved_keywords_as_identifiers() {
+    let out: (i64, i64) = rune_s! { r#"
+        fn typeof(abstract) {
+            abstract + 1
+        }
+
+        pub fn main() {
+            let static = 1;
+            (typeof(static), static)
+        }
+    "# };
+
+    assert_eq!(out, (2, 1));
+}
+
+#[test]
+fn reserved_keywords_warning() {
+    assert_warnings! {
+        r#"pub fn main() { #{abstract: 1} }"#,
+        span!(18, 26), FutureKeyword { .. }
+    };
+}
@@ -1,5 +1,7 @@
 prelude!();
 
+use VmErrorKind::*;
+
 macro_rules! test_case {
     ($($tt:tt)*) => {
         let out: String = rune!(pub fn main() { format!($($tt)*) });
@@ -21,6 +23,75 @@ fn test_asserts() {
     );
 }
 
+#[test]
+fn test_assert_failure_messages() {
+    assert_vm_error!(
+        r#"pub fn main() { let a = 1; assert_eq!(a + 1, 3) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "assertion `a + 1 == 3` failed\n  left: 2\n right: 3");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { assert_ne!(1, 1, "expected {}", "difference") }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "assertion `1 != 1` failed: expected difference\n  left: 1\n right: 1");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let x = 5; assert!(x < 2) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "assertion `x < 2` failed\n  left: 5\n right: 2");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { assert!(false) }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "assertion `false` failed");
+        }
+    );
+
+    // The source of the assertion is used as written.
+    assert_vm_error!(
+        r#"pub fn main() { assert!(["hello", "world"] is String, "vectors should be strings") }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), r#"assertion `["hello", "world"] is String` failed: vectors should be strings"#);
+        }
+    );
+}
+
+#[test]
+fn test_assert_operand_spans() {
+    let source = r#"pub fn main() { let a = 1; assert_eq!(a + 1, 3) }"#;
+
+    assert_vm_error!(
+        source,
+        Panic { reason } => {
+            let labels = reason
+                .labels()
+                .iter()
+                .map(|(span, message)| (&source[span.range()], message.as_str()))
+                .collect::<Vec<_>>();
+
+            assert_eq!(labels, [("a + 1", "left: 2"), ("3", "right: 3")]);
+        }
+    );
+}
+
+#[test]
+fn test_assert_operands_do_not_shadow() {
+    let _: () = rune!(
+        pub fn main() {
+            let left = 2;
+            let right = 1;
+            assert_eq!(right, left - 1);
+            assert_ne!(left, right);
+        }
+    );
+}
+
 #[test]
 fn test_stringify() {
     let out: String = rune!(
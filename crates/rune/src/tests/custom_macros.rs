@@ -2,7 +2,7 @@ prelude!();
 
 use std::sync::Arc;
 
-use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
+use codespan_reporting::term::termcolor::{Buffer, ColorChoice, StandardStream};
 use macros::quote;
 use parse::Parser;
 
@@ -52,6 +52,103 @@ fn test_parse_in_macro() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn parse_error_in_macro_expansion() -> Result<()> {
+    let mut m = Module::default();
+
+    m.macro_(["broken_code"], |cx, _| {
+        let id = cx.insert_source("broken_code", "1 + + 2");
+        let expr = cx.parse_source::<ast::Expr>(id)?;
+        Ok(quote!(#expr).into_token_stream(cx))
+    })?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(m)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                broken_code!()
+            }
+        }
+    };
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    assert!(result.is_err());
+
+    let mut out = Buffer::no_color();
+    diagnostics.emit(&mut out, &sources)?;
+    let out = String::from_utf8(out.into_inner())?;
+
+    // Both the invocation and the expanded source are pointed out.
+    assert!(out.contains("broken_code!()"), "{out}");
+    assert!(out.contains("1 + + 2"), "{out}");
+    assert!(out.contains("In this macro expansion"), "{out}");
+
+    let expanded = sources
+        .iter()
+        .find(|source| source.name() == "broken_code")
+        .and_then(|source| source.expanded_from());
+
+    assert!(expanded.is_some());
+    Ok(())
+}
+
+#[test]
+fn parse_error_in_quoted_expansion() -> Result<()> {
+    let mut m = Module::default();
+
+    m.macro_(["broken_quote"], |cx, _| {
+        let ident = cx.ident("value");
+        Ok(quote!(#ident + + 2).into_token_stream(cx))
+    })?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(m)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() {
+                let value = 1;
+                broken_quote!()
+            }
+        }
+    };
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    assert!(result.is_err());
+
+    let mut out = Buffer::no_color();
+    diagnostics.emit(&mut out, &sources)?;
+    let out = String::from_utf8(out.into_inner())?;
+
+    // The synthesized tokens are pointed out in the stringified expansion,
+    // and the invocation they were synthesized by.
+    assert!(out.contains("broken_quote!()"), "{out}");
+    assert!(out.contains("value + + 2"), "{out}");
+    assert!(out.contains("In this macro expansion"), "{out}");
+
+    let expanded = sources
+        .iter()
+        .find(|source| source.name() == "macro expansion")
+        .and_then(|source| source.expanded_from());
+
+    assert!(expanded.is_some());
+    Ok(())
+}
+
 #[test]
 fn conflicting_attribute_function() -> Result<()> {
     let mut m = Module::default();
@@ -0,0 +1,114 @@
+prelude!();
+
+use crate::compile::DebugLevel;
+use crate::runtime::debug::DebugArgs;
+use crate::{Options, Unit};
+
+const SOURCE: &str = r#"
+fn add(a, b) {
+    if a > 0 {
+        a + b
+    } else {
+        b
+    }
+}
+
+pub fn main() {
+    add(1, "two")
+}
+"#;
+
+fn build(level: DebugLevel) -> Result<Unit> {
+    let mut options = Options::default();
+    options.debug_level(level);
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+
+    let unit = prepare(&mut sources).with_options(&options).build()?;
+    Ok(unit)
+}
+
+fn debug_size(unit: &Unit) -> Result<u64> {
+    match unit.debug_info() {
+        Some(debug) => Ok(bincode::serialized_size(debug)?),
+        None => Ok(0),
+    }
+}
+
+#[test]
+fn debug_level_none() -> Result<()> {
+    let unit = build(DebugLevel::None)?;
+    assert!(unit.debug_info().is_none());
+    Ok(())
+}
+
+#[test]
+fn debug_level_lines() -> Result<()> {
+    let unit = build(DebugLevel::Lines)?;
+    let debug = unit.debug_info().expect("missing debug info");
+
+    assert!(!debug.instructions.is_empty());
+    assert!(!debug.functions_rev.is_empty());
+    assert!(debug.hash_to_ident.is_empty());
+
+    for inst in debug.instructions.values() {
+        assert!(inst.comment.is_none());
+        assert!(inst.labels.is_empty());
+    }
+
+    let add = debug
+        .functions
+        .values()
+        .find(|f| f.path.to_string() == "add")
+        .expect("missing add");
+
+    assert!(matches!(add.args, DebugArgs::TupleArgs(2)));
+    Ok(())
+}
+
+#[test]
+fn debug_level_full() -> Result<()> {
+    let unit = build(DebugLevel::Full)?;
+    let debug = unit.debug_info().expect("missing debug info");
+
+    assert!(debug.instructions.values().any(|i| !i.labels.is_empty()));
+
+    let add = debug
+        .functions
+        .values()
+        .find(|f| f.path.to_string() == "add")
+        .expect("missing add");
+
+    assert!(matches!(&add.args, DebugArgs::Named(args) if args.len() == 2));
+    Ok(())
+}
+
+#[test]
+fn debug_level_sizes() -> Result<()> {
+    let none = debug_size(&build(DebugLevel::None)?)?;
+    let lines = debug_size(&build(DebugLevel::Lines)?)?;
+    let full = debug_size(&build(DebugLevel::Full)?)?;
+
+    assert!(none < lines, "{none} < {lines}");
+    assert!(lines < full, "{lines} < {full}");
+    Ok(())
+}
+
+#[test]
+fn debug_level_backtrace() -> Result<()> {
+    for level in [DebugLevel::None, DebugLevel::Lines, DebugLevel::Full] {
+        let unit = build(level)?;
+        let context = Context::with_default_modules()?;
+
+        let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+        let error = vm.call(["main"], ()).expect_err("expected an error");
+
+        let l = error.first_location().expect("missing location");
+        let located = matches!(l.unit.debug_info(), Some(d) if d.instruction_at(l.ip).is_some());
+
+        assert_eq!(located, level != DebugLevel::None, "{level:?}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn declarative_macros() {
+    let out: (i64, i64, i64) = rune_s! {
+        r#"
+        macro square($e:expr) { $e * $e }
+
+        macro swap($a:ident, $b:ident) {{
+            let tmp = $a;
+            $a = $b;
+            $b = tmp;
+        }}
+
+        pub fn main() {
+            let a = square!(1 + 2);
+            let b = 1;
+            swap!(a, b);
+            (a, b, square!(square!(2)))
+        }
+        "#
+    };
+    assert_eq!(out, (1, 9, 16));
+
+    let out: i64 = rune_s! {
+        r#"
+        pub fn main() {
+            // Declared after it's used, but in the same block.
+            let value = add!(20 plus 22);
+            macro add($a:lit plus $b:lit) { $a + $b }
+            value
+        }
+        "#
+    };
+    assert_eq!(out, 42);
+}
+
+#[test]
+fn declarative_macro_items() {
+    let out: (i64, String) = rune_s! {
+        r#"
+        mod util {
+            macro getter($name:ident, $value:expr) {
+                pub fn $name() { $value }
+            }
+
+            getter!(answer, 40 + 2);
+        }
+
+        macro first($first:tt $rest:tt) { $first }
+
+        pub fn main() {
+            (util::answer(), first!("first" [1, 2, 3]))
+        }
+        "#
+    };
+    assert_eq!(out, (42, String::from("first")));
+
+    let out: i64 = rune_s! {
+        r#"
+        mod util {
+            macro double($e:expr) { $e * 2 }
+        }
+
+        pub fn main() {
+            crate::util::double!(21)
+        }
+        "#
+    };
+    assert_eq!(out, 42);
+}
+
+#[test]
+fn declarative_macro_errors() {
+    assert_errors! {
+        r#"macro m($a:expr) { $b }"#,
+        span!(19, 21), Custom { message } => {
+            assert_eq!(message.as_ref(), "Missing macro parameter `$b`");
+        }
+    };
+
+    assert_errors! {
+        r#"macro m() {} macro m() {}"#,
+        span!(13, 20), Custom { message } => {
+            assert_eq!(message.as_ref(), "Macro `m` is already defined");
+        }
+    };
+
+    assert_errors! {
+        r#"macro pair($a:expr, $b:expr) { ($a, $b) } pub fn main() { pair!(1; 2) }"#,
+        _, Expected { .. }
+    };
+}
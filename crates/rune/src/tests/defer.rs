@@ -0,0 +1,196 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn defer_scope_exit() {
+    let out: Vec<i64> = rune_s! { r#"
+        pub fn main() {
+            let out = [];
+
+            {
+                defer { out.push(1); }
+                defer { out.push(2); }
+                out.push(0);
+            }
+
+            out.push(3);
+            out
+        }
+    "# };
+
+    assert_eq!(out, [0, 2, 1, 3]);
+}
+
+#[test]
+fn defer_return() {
+    let out: (i64, Vec<String>) = rune_s! { r#"
+        fn work(out) {
+            defer { out.push("deferred"); }
+
+            if out.len() == 0 {
+                out.push("body");
+                return 42;
+            }
+
+            0
+        }
+
+        pub fn main() {
+            let out = [];
+            let value = work(out);
+            (value, out)
+        }
+    "# };
+
+    assert_eq!(
+        out,
+        (42, vec![String::from("body"), String::from("deferred")])
+    );
+}
+
+#[test]
+fn defer_value_is_evaluated_first() {
+    let out: (i64, i64) = rune_s! { r#"
+        fn produce() {
+            let n = 1;
+            defer { n = 2; }
+            n
+        }
+
+        fn returned() {
+            let n = 1;
+            defer { n = 2; }
+            return n;
+        }
+
+        pub fn main() {
+            (produce(), returned())
+        }
+    "# };
+
+    assert_eq!(out, (1, 1));
+}
+
+#[test]
+fn defer_try() {
+    let out: (Vec<String>, Vec<String>) = rune_s! { r#"
+        fn work(out, value) {
+            defer { out.push("deferred"); }
+            let value = value?;
+            out.push(`value ${value}`);
+            Ok(value)
+        }
+
+        pub fn main() {
+            let a = [];
+            let b = [];
+            assert_eq!(work(a, Ok(1)), Ok(1));
+            assert_eq!(work(b, Err("bad")), Err("bad"));
+            (a, b)
+        }
+    "# };
+
+    assert_eq!(out.0, ["value 1", "deferred"]);
+    assert_eq!(out.1, ["deferred"]);
+}
+
+#[test]
+fn defer_loops() {
+    let out: Vec<i64> = rune_s! { r#"
+        pub fn main() {
+            let out = [];
+
+            for n in 0..10 {
+                defer { out.push(n); }
+
+                if n % 2 == 0 {
+                    continue;
+                }
+
+                if n > 4 {
+                    break;
+                }
+
+                out.push(n * 10);
+            }
+
+            out
+        }
+    "# };
+
+    assert_eq!(out, [0, 10, 1, 2, 30, 3, 4, 5]);
+}
+
+#[test]
+fn defer_break_value() {
+    let out: (i64, Vec<i64>) = rune_s! { r#"
+        pub fn main() {
+            let out = [];
+
+            let value = loop {
+                defer { out.push(1); }
+                break 42;
+            };
+
+            (value, out)
+        }
+    "# };
+
+    assert_eq!(out, (42, vec![1]));
+}
+
+#[test]
+fn defer_captures_declaration_scope() {
+    let out: Vec<i64> = rune_s! { r#"
+        pub fn main() {
+            let out = [];
+
+            {
+                let x = 1;
+                defer { out.push(x); }
+                let x = 2;
+                out.push(x);
+            }
+
+            out
+        }
+    "# };
+
+    assert_eq!(out, [2, 1]);
+}
+
+#[test]
+fn defer_async() {
+    let out: Vec<i64> = rune_s! { r#"
+        async fn value(n) {
+            n
+        }
+
+        async fn work(out) {
+            defer { out.push(value(2).await); }
+            out.push(value(1).await);
+        }
+
+        pub async fn main() {
+            let out = [];
+            work(out).await;
+            out
+        }
+    "# };
+
+    assert_eq!(out, [1, 2]);
+}
+
+#[test]
+fn defer_control_flow_escape() {
+    assert_errors! {
+        r#"pub fn main() { defer { return; } }"#,
+        span!(24, 30), ControlFlowInDefer
+    };
+
+    assert_errors! {
+        r#"pub fn main() { loop { defer { break; } } }"#,
+        span!(31, 36), ControlFlowInDefer
+    };
+}
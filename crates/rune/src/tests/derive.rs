@@ -0,0 +1,105 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn derive_hash() {
+    let _: () = rune_s! { r#"
+        use std::collections::{HashMap, HashSet};
+
+        #[derive(PartialEq, Eq, Hash)]
+        struct Point { x, y }
+
+        #[derive(PartialEq, Eq, Hash)]
+        struct Pair(a, b);
+
+        #[derive(PartialEq, Eq, Hash)]
+        enum Shape { Empty, Circle(radius), Rect { w, h } }
+
+        pub fn main() {
+            let points = HashSet::new();
+            points.insert(Point { x: 1, y: 2 });
+            points.insert(Point { y: 2, x: 1 });
+            points.insert(Point { x: 2, y: 1 });
+            assert_eq!(points.len(), 2);
+            assert!(points.contains(Point { x: 2, y: 1 }));
+
+            let pairs = HashMap::new();
+            pairs.insert(Pair(1, "a"), 1);
+            pairs.insert(Pair(1, "a"), 2);
+            assert_eq!(pairs.len(), 1);
+            assert_eq!(pairs.get(Pair(1, "a")), Some(2));
+
+            let shapes = HashSet::new();
+            shapes.insert(Shape::Empty);
+            shapes.insert(Shape::Circle(1));
+            shapes.insert(Shape::Circle(1));
+            shapes.insert(Shape::Rect { w: 1, h: 2 });
+            assert_eq!(shapes.len(), 3);
+            assert!(shapes.contains(Shape::Rect { h: 2, w: 1 }));
+            assert!(!shapes.contains(Shape::Circle(2)));
+        }
+    "# };
+}
+
+#[test]
+fn derive_hash_missing() {
+    assert_vm_error!(
+        r#"
+        struct Point { x, y }
+
+        pub fn main() {
+            let points = std::collections::HashSet::new();
+            points.insert(Point { x: 1, y: 2 });
+        }
+        "#,
+        VmErrorKind::UnsupportedUnaryOperation { op, .. } => {
+            assert_eq!(op, "hash");
+        }
+    );
+}
+
+#[test]
+fn derive_debug() {
+    let out: (String, String, String, String) = rune_s! { r#"
+        #[derive(Debug)]
+        struct Point { x, y }
+
+        #[derive(Debug)]
+        struct Pair(a, b);
+
+        #[derive(Debug)]
+        enum Shape { Circle(radius), Rect { w, h } }
+
+        pub fn main() {
+            (
+                format!("{:?}", Point { x: 1, y: "a" }),
+                format!("{:?}", Pair(Some(1), [2])),
+                format!("{:?}", Shape::Circle(1.5)),
+                format!("{:?}", Shape::Rect { w: 1, h: 2 }),
+            )
+        }
+    "# };
+
+    assert_eq!(out.0, "Point { x: 1, y: \"a\" }");
+    assert_eq!(out.1, "Pair(Some(1), [2])");
+    assert_eq!(out.2, "Shape::Circle(1.5)");
+    assert!(out.3 == "Shape::Rect { w: 1, h: 2 }" || out.3 == "Shape::Rect { h: 2, w: 1 }");
+}
+
+#[test]
+fn derive_errors() {
+    assert_errors! {
+        "#[derive(Clone)] struct Struct {}",
+        span!(9, 14), Custom { message } => {
+            assert_eq!(message.as_ref(), "unsupported derive");
+        }
+    }
+
+    assert_errors! {
+        "#[derive(Hash, Default)] enum Enum {}",
+        span!(15, 22), Custom { message } => {
+            assert_eq!(message.as_ref(), "unsupported derive");
+        }
+    }
+}
@@ -0,0 +1,59 @@
+prelude!();
+
+use crate::ast::Span;
+use crate::diagnostics::{JsonDiagnostic, Severity};
+
+/// Compile the given source and convert its diagnostics to json.
+fn to_json(source: &str) -> Result<Vec<JsonDiagnostic>> {
+    let mut sources = crate::tests::sources(source);
+    let mut diagnostics = Diagnostics::new();
+
+    let _ = prepare(&mut sources)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    Ok(diagnostics.to_json(&sources)?)
+}
+
+#[test]
+fn test_json_errors() -> Result<()> {
+    let json = to_json("pub fn main() { x }")?;
+    assert_eq!(json.len(), 1);
+
+    let error = &json[0];
+    assert_eq!(error.severity, Severity::Error);
+    assert_eq!(error.kind, "compile-error");
+    assert_eq!(error.code.as_deref(), Some("E0020"));
+    assert_eq!(error.title, Some("Missing local variable"));
+    assert_eq!(error.file.as_deref(), Some("main"));
+    assert_eq!(error.span, Some(span!(16, 17)));
+
+    let primary = error.labels.iter().find(|label| label.primary);
+    assert_eq!(primary.map(|label| label.span), Some(span!(16, 17)));
+    Ok(())
+}
+
+#[test]
+fn test_json_warnings() -> Result<()> {
+    let json = to_json("pub fn main() { let [0, 1, 3] = []; }")?;
+    assert_eq!(json.len(), 1);
+
+    let warning = &json[0];
+    assert_eq!(warning.severity, Severity::Warning);
+    assert_eq!(warning.kind, "let-pattern-might-panic");
+    assert_eq!(warning.code.as_deref(), Some("W0002"));
+    assert_eq!(warning.title, Some("Pattern might panic"));
+    assert_eq!(warning.span, Some(span!(16, 35)));
+    assert!(warning
+        .help
+        .iter()
+        .any(|help| help.starts_with("Hint: Rewrite to")));
+
+    let spans = warning
+        .labels
+        .iter()
+        .map(|label| label.span)
+        .collect::<Vec<Span>>();
+    assert_eq!(spans, [span!(16, 35), span!(14, 37)]);
+    Ok(())
+}
@@ -0,0 +1,174 @@
+prelude!();
+
+use crate::compile::Options;
+use crate::no_std::sync::Arc;
+use crate::runtime::Inst;
+
+fn build(source: &str) -> Result<crate::Unit> {
+    let context = Context::with_default_modules()?;
+
+    let mut options = Options::default();
+    options.early_bound(true);
+
+    let mut sources = crate::tests::sources(source);
+
+    let unit = crate::prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .build()?;
+
+    Ok(unit)
+}
+
+fn typed_calls(unit: &crate::Unit) -> usize {
+    unit.iter_instructions()
+        .filter(|(_, inst)| matches!(inst, Inst::CallAssociatedTyped { .. }))
+        .count()
+}
+
+#[test]
+fn test_early_bound_literals() -> Result<()> {
+    let unit = build(
+        r#"
+        pub fn main() {
+            let a = "hello".len();
+            let b = [1, 2, 3].len();
+            let c = (1, 2).len();
+            let d = #{a: 1}.len();
+            a + b + c + d
+        }
+        "#,
+    )?;
+
+    assert_eq!(typed_calls(&unit), 4);
+
+    let context = Arc::new(Context::with_default_modules()?.runtime());
+    let mut vm = Vm::new(context, Arc::new(unit));
+    let output: i64 = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 11);
+    Ok(())
+}
+
+#[test]
+fn test_early_bound_struct() -> Result<()> {
+    let unit = build(
+        r#"
+        struct Foo { value }
+
+        impl Foo {
+            fn get(self) { self.value }
+        }
+
+        pub fn main() {
+            let a = (Foo { value: 42 }).get();
+            let b = Foo { value: 1 };
+            a + b.get()
+        }
+        "#,
+    )?;
+
+    assert_eq!(typed_calls(&unit), 2);
+
+    let context = Arc::new(Context::with_default_modules()?.runtime());
+    let mut vm = Vm::new(context, Arc::new(unit));
+    let output: i64 = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 43);
+    Ok(())
+}
+
+#[test]
+fn test_early_bound_variables() -> Result<()> {
+    let unit = build(
+        r#"
+        pub fn main() {
+            let s = "hello";
+            let t = s;
+            let a = s.len() + t.len();
+
+            let v = 42;
+            v = [1, 2];
+            let b = v.len();
+
+            let n = 0;
+
+            for _ in 0..2 {
+                n += v.len();
+                v = "abc";
+            }
+
+            a + b + n
+        }
+        "#,
+    )?;
+
+    assert_eq!(typed_calls(&unit), 2);
+
+    let context = Arc::new(Context::with_default_modules()?.runtime());
+    let mut vm = Vm::new(context, Arc::new(unit));
+    let output: i64 = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 17);
+    Ok(())
+}
+
+#[test]
+fn test_early_bound_reassigned() -> Result<()> {
+    let unit = build(
+        r#"
+        pub fn main() {
+            let v = [1, 2];
+            let n = 0;
+
+            for _ in 0..2 {
+                n += v.len();
+                v = "abc";
+            }
+
+            let s = "hello";
+
+            {
+                let s = [1];
+                s = 2;
+            }
+
+            n + s.len()
+        }
+        "#,
+    )?;
+
+    assert_eq!(typed_calls(&unit), 0);
+
+    let context = Arc::new(Context::with_default_modules()?.runtime());
+    let mut vm = Vm::new(context, Arc::new(unit));
+    let output: i64 = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 10);
+    Ok(())
+}
+
+#[test]
+fn test_early_bound_missing() -> Result<()> {
+    let unit = build(r#"pub fn main() { "hello".missing() }"#)?;
+    assert_eq!(typed_calls(&unit), 1);
+
+    let context = Arc::new(Context::with_default_modules()?.runtime());
+    let mut vm = Vm::new(context, Arc::new(unit));
+    let error = vm.call(["main"], ()).expect_err("call should fail");
+
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::MissingInstanceFunction { instance, .. } if instance == String::type_info()
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_late_bound_by_default() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let mut sources = crate::tests::sources(r#"pub fn main() { "hello".len() }"#);
+
+    let unit = crate::prepare(&mut sources)
+        .with_context(&context)
+        .build()?;
+    assert_eq!(typed_calls(&unit), 0);
+    Ok(())
+}
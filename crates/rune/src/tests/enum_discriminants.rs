@@ -0,0 +1,70 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn enum_discriminants() {
+    let out: (i64, i64, i64, i64) = rune_s! { r#"
+        enum Color { Red = 1, Green, Blue = -4, Black }
+
+        pub fn main() {
+            (Color::Red as i64, Color::Green as i64, Color::Blue as i64, Color::Black as i64)
+        }
+    "# };
+
+    assert_eq!(out, (1, 2, -4, -3));
+}
+
+#[test]
+fn enum_implicit_discriminants() {
+    let out: (i64, i64, bool) = rune_s! { r#"
+        enum Foo { A, B(b), C }
+
+        pub fn main() {
+            (Foo::A as i64, Foo::C as i64, Foo::from_int(1).is_none())
+        }
+    "# };
+
+    assert_eq!(out, (0, 2, true));
+}
+
+#[test]
+fn enum_from_int() {
+    let _: () = rune_s! { r#"
+        enum Color { Red = 1, Green, Blue = 10 }
+
+        pub fn main() {
+            assert_eq!(Color::from_int(1), Some(Color::Red));
+            assert_eq!(Color::from_int(2), Some(Color::Green));
+            assert_eq!(Color::from_int(10), Some(Color::Blue));
+            assert_eq!(Color::from_int(3), None);
+            assert_eq!(Color::from_int("red"), None);
+
+            let from_int = Color::from_int;
+            assert_eq!(from_int(Color::Blue as i64), Some(Color::Blue));
+        }
+    "# };
+}
+
+#[test]
+fn enum_discriminant_errors() {
+    assert_errors! {
+        r#"enum Foo { A(a) = 1 }"#,
+        span!(18, 19), UnsupportedDiscriminant
+    };
+
+    assert_errors! {
+        r#"enum Foo { A = "a" }"#,
+        span!(15, 18), UnsupportedDiscriminant
+    };
+
+    assert_errors! {
+        r#"enum Foo { A = 1, B = 1 }"#,
+        span!(18, 19), DuplicateDiscriminant { discriminant: 1, .. }
+    };
+
+    assert_errors! {
+        r#"enum Foo { A = 1, B, C = 2 }"#,
+        span!(21, 22), DuplicateDiscriminant { discriminant: 2, .. }
+    };
+}
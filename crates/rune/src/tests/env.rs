@@ -0,0 +1,77 @@
+prelude!();
+
+use std::sync::Arc;
+
+use crate::modules::env::{self, ProcessEnv, VirtualEnv};
+
+fn run_with<T>(module: Module, source: &str) -> Result<T>
+where
+    T: FromValue,
+{
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let mut sources = crate::tests::sources(source);
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    Ok(from_value(vm.call(["main"], ())?)?)
+}
+
+#[test]
+fn test_virtual_env() -> Result<()> {
+    let env = VirtualEnv::new()
+        .with_var("HOME", "/home/rune")
+        .with_args(["script.rn", "--verbose"])
+        .with_current_dir("/home/rune/project");
+
+    let out: (Option<String>, Option<String>, Vec<String>, Option<String>) = run_with(
+        env::module(env)?,
+        r#"
+        pub fn main() {
+            (std::env::var("HOME"), std::env::var("PATH"), std::env::args(), std::env::current_dir())
+        }
+        "#,
+    )?;
+
+    assert_eq!(
+        out,
+        (
+            Some(String::from("/home/rune")),
+            None,
+            vec![String::from("script.rn"), String::from("--verbose")],
+            Some(String::from("/home/rune/project")),
+        )
+    );
+
+    let out: (Vec<String>, Option<String>) = run_with(
+        env::module(VirtualEnv::new())?,
+        r#"pub fn main() { (std::env::args(), std::env::current_dir()) }"#,
+    )?;
+
+    assert_eq!(out, (Vec::new(), None));
+    Ok(())
+}
+
+#[test]
+fn test_process_env() -> Result<()> {
+    let out: (Option<String>, usize) = run_with(
+        env::module(ProcessEnv::new())?,
+        r#"pub fn main() { (std::env::current_dir(), std::env::args().len()) }"#,
+    )?;
+
+    let expected = std::env::current_dir()?;
+    assert_eq!(out.0.as_deref(), expected.to_str());
+    assert_eq!(out.1, std::env::args_os().count());
+    Ok(())
+}
+
+#[test]
+fn test_env_not_installed() {
+    let result = crate::tests::compile_helper(
+        r#"pub fn main() { std::env::var("HOME") }"#,
+        &mut Diagnostics::new(),
+    );
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,187 @@
+prelude!();
+
+use crate::diagnostics::{self, Diagnostic, ErrorCode};
+use crate::termcolor;
+
+/// Compile the given source and collect the codes of all errors.
+fn codes(source: &str) -> Vec<Option<ErrorCode>> {
+    let diagnostics = compile(source);
+
+    diagnostics
+        .diagnostics()
+        .iter()
+        .flat_map(|d| match d {
+            Diagnostic::Fatal(fatal) => Some(fatal.code()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Compile the given source and collect the codes of all warnings.
+fn warning_codes(source: &str) -> Vec<ErrorCode> {
+    let diagnostics = compile(source);
+
+    diagnostics
+        .diagnostics()
+        .iter()
+        .flat_map(|d| match d {
+            Diagnostic::Warning(warning) => Some(warning.code()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn compile(source: &str) -> Diagnostics {
+    let mut sources = crate::tests::sources(source);
+    let mut diagnostics = Diagnostics::new();
+
+    let _ = prepare(&mut sources)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    diagnostics
+}
+
+#[test]
+fn error_codes_explained() {
+    let mut expected = ErrorCode::new(1);
+
+    for explanation in diagnostics::explanations() {
+        // Warning codes follow error codes, and also start from one.
+        if explanation.code.is_warning() && !expected.is_warning() {
+            expected = ErrorCode::warning(1);
+        }
+
+        assert_eq!(explanation.code, expected, "codes must be contiguous");
+        assert!(!explanation.title.is_empty());
+        assert!(!explanation.description.is_empty());
+
+        let code = explanation.code.to_string();
+        assert_eq!(ErrorCode::parse(&code), Some(explanation.code));
+        assert!(diagnostics::explain(&code).is_some());
+
+        expected = if expected.is_warning() {
+            ErrorCode::warning(expected.get() + 1)
+        } else {
+            ErrorCode::new(expected.get() + 1)
+        };
+    }
+
+    assert!(expected.is_warning(), "expected warning codes");
+    assert!(diagnostics::explain("e20").is_some());
+    assert!(diagnostics::explain("w1").is_some());
+    assert!(diagnostics::explain("E").is_none());
+    assert!(diagnostics::explain("E-1").is_none());
+    assert!(diagnostics::explain("E99999999").is_none());
+
+    assigned_codes_explained();
+}
+
+/// Every code assigned in a `code` function must have an explanation.
+fn assigned_codes_explained() {
+    let sources: [(&str, fn(u16) -> ErrorCode); 3] = [
+        (include_str!("../compile/error.rs"), ErrorCode::new),
+        (include_str!("../compile/unit_builder.rs"), ErrorCode::new),
+        (
+            include_str!("../diagnostics/warning.rs"),
+            ErrorCode::warning,
+        ),
+    ];
+
+    let mut count = 0;
+
+    for (source, new) in sources {
+        for line in source.lines() {
+            let Some((kind, code)) = line.trim().split_once(" => ") else {
+                continue;
+            };
+
+            let Some(code) = code.strip_suffix(',').and_then(|code| code.parse().ok()) else {
+                continue;
+            };
+
+            let code = new(code);
+
+            assert!(
+                code.explanation().is_some(),
+                "{kind} is assigned the code {code} which has no explanation"
+            );
+
+            count += 1;
+        }
+    }
+
+    assert!(count > 100, "expected to find assigned codes");
+}
+
+#[test]
+fn error_codes_of_diagnostics() {
+    let code = |code| ErrorCode::parse(code);
+
+    assert_eq!(codes("pub fn main() { break; }"), [code("E0047")]);
+    assert_eq!(codes("pub fn main() { let mut var = 1; }"), [code("E0125")]);
+
+    // Constant evaluation errors have the code of the error which caused them.
+    assert_eq!(
+        codes("const fn inner() { loop {} } const VALUE = inner(); pub fn main() { VALUE }"),
+        [code("E0132")]
+    );
+
+    assert_eq!(
+        codes("const VALUE = 10 / 0; pub fn main() { VALUE }"),
+        [code("E0146")]
+    );
+
+    let explanation = diagnostics::explain("E0047").expect("explanation");
+    assert_eq!(explanation.title, "`break` outside of loop");
+}
+
+#[test]
+fn warning_codes_of_diagnostics() {
+    let code = |code| ErrorCode::parse(code).expect("valid code");
+
+    assert_eq!(warning_codes("pub fn main() { None() }"), [code("W0004")]);
+    assert_eq!(
+        warning_codes("pub fn main() { #{abstract: 1} }"),
+        [code("W0006")]
+    );
+
+    let explanation = diagnostics::explain("W0004").expect("explanation");
+    assert_eq!(explanation.title, "Unnecessary call parentheses");
+}
+
+#[test]
+fn error_codes_emitted() -> Result<()> {
+    let mut sources = crate::tests::sources("pub fn main() { break; }");
+    let mut diagnostics = Diagnostics::new();
+
+    let result = prepare(&mut sources)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    assert!(result.is_err());
+
+    let mut out = termcolor::Buffer::no_color();
+    diagnostics.emit(&mut out, &sources)?;
+    let out = String::from_utf8(out.into_inner())?;
+
+    assert!(out.contains("error[E0047]: Break outside of loop"), "{out}");
+    Ok(())
+}
+
+#[test]
+fn warning_codes_emitted() -> Result<()> {
+    let mut sources = crate::tests::sources("pub fn main() { None() }");
+    let mut diagnostics = Diagnostics::new();
+
+    prepare(&mut sources)
+        .with_diagnostics(&mut diagnostics)
+        .build()?;
+
+    let mut out = termcolor::Buffer::no_color();
+    diagnostics.emit(&mut out, &sources)?;
+    let out = String::from_utf8(out.into_inner())?;
+
+    assert!(out.contains("warning[W0004]: Warning"), "{out}");
+    Ok(())
+}
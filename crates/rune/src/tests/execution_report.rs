@@ -0,0 +1,85 @@
+prelude!();
+
+use std::sync::Arc;
+
+fn vm(context: &Context, source: &str) -> Result<Vm> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let unit = prepare(&mut sources).with_context(context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+#[test]
+fn execution_report_counts() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = vm(
+        &context,
+        r#"
+        fn inc(n) {
+            n + 1
+        }
+
+        pub fn main(n) {
+            let out = 0;
+
+            while out < n {
+                out = inc(out);
+            }
+
+            out
+        }
+        "#,
+    )?;
+
+    let output: i64 = from_value(vm.call(["main"], (10,))?)?;
+    assert_eq!(output, 10);
+
+    let first = *vm.last_execution_report();
+    assert_eq!(first.function, Some(Hash::type_hash(["main"])));
+    assert_eq!(first.calls, 10);
+    assert!(first.instructions > first.calls);
+    assert!(first.max_stack_depth > 0);
+    assert_eq!(first.awaits, 0);
+
+    let debug = vm.unit().debug_info().expect("debug info");
+    let signature = first.signature(debug).expect("signature");
+    assert_eq!(signature.path.to_string(), "main");
+
+    // The report is reset for every execution.
+    let output: i64 = from_value(vm.call(["main"], (20,))?)?;
+    assert_eq!(output, 20);
+
+    let second = *vm.last_execution_report();
+    assert_eq!(second.calls, 20);
+    assert!(second.instructions > first.instructions);
+    Ok(())
+}
+
+#[test]
+fn execution_report_awaits() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = vm(
+        &context,
+        r#"
+        async fn value(n) {
+            n
+        }
+
+        pub async fn main() {
+            value(1).await + value(2).await
+        }
+        "#,
+    )?;
+
+    let mut execution = vm.execute(["main"], ())?;
+    let output: i64 = from_value(block_on(execution.async_complete()).into_result()?)?;
+    assert_eq!(output, 3);
+
+    let report = execution.report();
+    assert_eq!(report.awaits, 2);
+    assert!(report.calls >= 2);
+    Ok(())
+}
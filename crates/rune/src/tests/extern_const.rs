@@ -0,0 +1,68 @@
+prelude!();
+
+use crate::no_std::sync::Arc;
+use crate::runtime::ConstValue;
+
+use ErrorKind::*;
+
+fn build(source: &str, options: &Options) -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let mut sources = crate::tests::sources(source);
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(options)
+        .build()?;
+
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+#[test]
+fn extern_const() -> Result<()> {
+    let mut options = Options::default();
+    options.extern_const("BUILD_ID", ConstValue::Integer(42));
+    options.extern_const("config::NAME", ConstValue::String("staging".into()));
+
+    let mut vm = build(
+        r#"
+        extern const BUILD_ID;
+
+        mod config {
+            pub extern const NAME;
+        }
+
+        const NEXT = BUILD_ID + 1;
+
+        pub fn main() {
+            (BUILD_ID, NEXT, config::NAME)
+        }
+        "#,
+        &options,
+    )?;
+
+    let output: (i64, i64, String) = crate::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, (42, 43, String::from("staging")));
+    Ok(())
+}
+
+#[test]
+fn extern_const_option() -> Result<()> {
+    let mut options = Options::default();
+    options.parse_option("extern-const=ENV=a=b")?;
+    assert!(options.parse_option("extern-const=ENV").is_err());
+
+    let mut vm = build("extern const ENV; pub fn main() { ENV }", &options)?;
+    let output: String = crate::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, "a=b");
+    Ok(())
+}
+
+#[test]
+fn extern_const_missing() {
+    assert_errors! {
+        "extern const BUILD_ID;",
+        span!(0, 21), MissingExternConst { item } => {
+            assert_eq!(item.to_string(), "BUILD_ID");
+        }
+    };
+}
@@ -0,0 +1,42 @@
+prelude!();
+
+use crate::compile::{folding_ranges, FoldingRangeKind};
+
+fn ranges(code: &str) -> Vec<(FoldingRangeKind, &str)> {
+    folding_ranges(code)
+        .into_iter()
+        .map(|range| (range.kind, &code[range.span.range()]))
+        .collect()
+}
+
+#[test]
+fn folding_ranges_blocks_and_comments() {
+    let code = "use std::iter;\nuse std::string;\n\n// first\n// second\n\n// separate\nfn main() {\n    let a = [\n        1,\n    ];\n    /* one\n       two */\n}\n";
+
+    assert_eq!(
+        ranges(code),
+        [
+            (
+                FoldingRangeKind::Imports,
+                "use std::iter;\nuse std::string;"
+            ),
+            (FoldingRangeKind::Comment, "// first\n// second"),
+            (
+                FoldingRangeKind::Region,
+                "{\n    let a = [\n        1,\n    ];\n    /* one\n       two */\n}"
+            ),
+            (FoldingRangeKind::Region, "[\n        1,\n    ]"),
+            (FoldingRangeKind::Comment, "/* one\n       two */"),
+        ]
+    );
+}
+
+#[test]
+fn folding_ranges_without_parsing() {
+    let code = "fn main() {\n    let a = ;\n}\n";
+
+    assert_eq!(
+        ranges(code),
+        [(FoldingRangeKind::Region, "{\n    let a = ;\n}")]
+    );
+}
@@ -0,0 +1,109 @@
+prelude!();
+
+use crate::no_std::sync::Arc;
+
+fn vm(source: &str) -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let mut sources = crate::tests::sources(source);
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+const CYCLES: &str = r#"
+struct Node { value, edges }
+
+pub fn main(n) {
+    for value in 0..n {
+        let a = Node { value, edges: [] };
+        let b = Node { value, edges: [a] };
+        a.edges.push(b);
+        let c = #{ next: None };
+        c.next = Some(c);
+    }
+}
+"#;
+
+#[test]
+fn gc_disabled_by_default() -> Result<()> {
+    let mut vm = vm(CYCLES)?;
+    vm.call(["main"], (10i64,))?;
+
+    assert!(!vm.gc().is_enabled());
+    assert_eq!(vm.gc().tracked(), 0);
+    assert_eq!(vm.collect_garbage(), 0);
+    assert_eq!(vm.gc().stats().collections, 0);
+    Ok(())
+}
+
+#[test]
+fn gc_collects_cycles() -> Result<()> {
+    let mut vm = vm(CYCLES)?;
+    vm.gc_mut().set_enabled(true);
+    vm.gc_mut().set_threshold(16);
+    vm.gc_mut().set_major_interval(2);
+    vm.call(["main"], (100i64,))?;
+
+    let stats = *vm.gc().stats();
+    assert!(stats.collections > 0);
+    assert!(stats.major_collections > 0);
+    assert!(stats.major_collections <= stats.collections);
+    assert!(stats.freed > 0);
+    assert!(stats.max_pause >= stats.last_pause);
+    assert!(stats.total_pause >= stats.max_pause);
+
+    // Everything allocated by the script is garbage once it has returned.
+    vm.collect_garbage();
+    assert_eq!(vm.gc().tracked(), 0);
+    Ok(())
+}
+
+#[test]
+fn gc_keeps_reachable_values() -> Result<()> {
+    let mut vm = vm(r#"
+        struct Node { value, edges }
+
+        pub fn main() {
+            let a = Node { value: 1, edges: [] };
+            let b = Node { value: 2, edges: [a] };
+            a.edges.push(b);
+
+            // Allocate enough garbage to trigger a number of collections.
+            for n in 0..100 {
+                let c = #{ next: None };
+                c.next = Some(c);
+            }
+
+            a.edges[0].edges[0].value + a.edges[0].value
+        }
+
+        pub fn graph() {
+            let a = Node { value: 1, edges: [] };
+            let b = Node { value: 2, edges: [a] };
+            a.edges.push(b);
+            a
+        }
+        "#)?;
+
+    vm.gc_mut().set_enabled(true);
+    vm.gc_mut().set_threshold(1);
+    vm.gc_mut().set_major_interval(1);
+
+    let output: i64 = crate::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 3);
+    assert!(vm.gc().stats().freed > 0);
+
+    // Values held by the host are roots, and so is everything they reference.
+    let graph = vm.call(["graph"], ())?;
+    vm.collect_garbage();
+    assert!(vm.gc().tracked() > 0);
+
+    let graph = graph.into_struct().into_result()?;
+    let graph = graph.borrow_ref()?;
+    let edges = graph.get("edges").expect("edges").clone();
+    assert_eq!(edges.into_vec().into_result()?.borrow_ref()?.len(), 1);
+    drop(graph);
+
+    vm.collect_garbage();
+    assert_eq!(vm.gc().tracked(), 0);
+    Ok(())
+}
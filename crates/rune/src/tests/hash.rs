@@ -0,0 +1,59 @@
+prelude!();
+
+#[test]
+fn test_xxh64() {
+    let result: (i64, i64, bool) = rune! {
+        use std::hash::xxh64;
+
+        pub fn main() {
+            (xxh64("", 0), xxh64(b"hello", 0), xxh64("hello", 0) == xxh64("hello", 1))
+        }
+    };
+
+    assert_eq!(
+        result,
+        (
+            xxhash_rust::xxh64::xxh64(b"", 0) as i64,
+            xxhash_rust::xxh64::xxh64(b"hello", 0) as i64,
+            false
+        )
+    );
+}
+
+#[test]
+#[cfg(feature = "crypto")]
+fn test_sha256_known_vectors() {
+    let result: (String, String, String, Bytes) = rune! {
+        use std::hash::{sha256, sha256_hex};
+
+        pub fn main() {
+            (
+                sha256_hex(""),
+                sha256_hex("abc"),
+                sha256_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+                sha256(b"abc"),
+            )
+        }
+    };
+
+    assert_eq!(
+        result.0,
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        result.1,
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        result.2,
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+    assert_eq!(
+        result.3,
+        [
+            0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+            0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+            0xf2, 0x00, 0x15, 0xad,
+        ]
+    );
+}
@@ -0,0 +1,97 @@
+prelude!();
+
+use std::sync::Arc;
+
+use crate::compile::{CompileVisitor, TemplatePart};
+use crate::modules::i18n;
+
+const SOURCE: &str = r#"
+pub fn greet(name, count) {
+    `Hello ${name}, you have ${count + 1} messages\n`
+}
+
+mod inner {
+    pub fn label(value) {
+        `${value}`
+    }
+}
+
+pub fn main() {
+    tr!("greeting") + tr!("missing")
+}
+"#;
+
+#[derive(Default)]
+struct TemplateVisitor {
+    templates: Vec<(String, Vec<String>)>,
+}
+
+impl CompileVisitor for TemplateVisitor {
+    fn visit_template(&mut self, _: &dyn Located, item: &Item, parts: &[TemplatePart<'_>]) {
+        let parts = parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Str(string) => string.to_string(),
+                TemplatePart::Slot(span) => format!("{{{}}}", &SOURCE[span.range()]),
+            })
+            .collect();
+
+        self.templates.push((item.to_string(), parts));
+    }
+}
+
+#[test]
+fn extract_templates() -> Result<()> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+
+    let mut context = Context::with_default_modules()?;
+    context.install(i18n::module(|_| None)?)?;
+
+    let mut visitor = TemplateVisitor::default();
+
+    let _ = prepare(&mut sources)
+        .with_context(&context)
+        .with_visitor(&mut visitor)
+        .build()?;
+
+    assert_eq!(
+        visitor.templates,
+        [
+            (
+                String::from("greet"),
+                vec![
+                    String::from("Hello "),
+                    String::from("{name}"),
+                    String::from(", you have "),
+                    String::from("{count + 1}"),
+                    String::from(" messages\n"),
+                ]
+            ),
+            (String::from("inner::label"), vec![String::from("{value}")]),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn translate() -> Result<()> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+
+    let mut context = Context::with_default_modules()?;
+
+    context.install(i18n::module(|key| match key {
+        "greeting" => Some(String::from("Hej ")),
+        _ => None,
+    })?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+
+    // Missing translations fall back to the key.
+    let output: String = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, "Hej missing");
+    Ok(())
+}
@@ -0,0 +1,83 @@
+prelude!();
+
+#[test]
+fn impl_consts_enum() {
+    let out: (i64, String, String) = rune! {
+        enum Color {
+            Red,
+            Green,
+            Blue,
+        }
+
+        impl Color {
+            const COUNT = 3;
+
+            fn name(self) {
+                match self {
+                    Color::Red => "red",
+                    Color::Green => "green",
+                    Color::Blue => "blue",
+                }
+            }
+
+            fn all() {
+                [Color::Red, Color::Green, Color::Blue]
+            }
+        }
+
+        pub fn main() {
+            let names = Color::all().iter().map(|c| c.name()).collect::<Vec>();
+            (Color::COUNT, Color::Green.name(), names[Color::COUNT - 1])
+        }
+    };
+
+    assert_eq!(out, (3, String::from("green"), String::from("blue")));
+}
+
+#[test]
+fn impl_consts_const_context() {
+    let out: (i64, i64, i64) = rune! {
+        struct Grid;
+
+        impl Grid {
+            const WIDTH = 4;
+            const HEIGHT = 3;
+            const AREA = Self::WIDTH * Self::HEIGHT;
+
+            fn area() {
+                Self::AREA
+            }
+        }
+
+        const CELLS = Grid::AREA * 2;
+
+        pub fn main() {
+            (Grid::AREA, Grid::area(), CELLS)
+        }
+    };
+
+    assert_eq!(out, (12, 12, 24));
+}
+
+#[test]
+fn impl_consts_const_fn() {
+    let out: i64 = rune! {
+        enum Unit {
+            Meter,
+        }
+
+        impl Unit {
+            const SCALE = 100;
+
+            const fn scaled(n) {
+                n * 100
+            }
+        }
+
+        pub fn main() {
+            Unit::scaled(2) + Unit::SCALE
+        }
+    };
+
+    assert_eq!(out, 300);
+}
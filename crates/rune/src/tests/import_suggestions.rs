@@ -0,0 +1,83 @@
+prelude!();
+
+use crate::ast::Span;
+use crate::compile::Suggestion;
+use crate::diagnostics::{Diagnostic, FatalDiagnosticKind};
+
+/// Compile the given source, returning the error it fails with.
+fn compile_error(source: &str) -> compile::Error {
+    let mut diagnostics = Diagnostics::new();
+    let _ = crate::tests::compile_helper(source, &mut diagnostics).unwrap_err();
+
+    let Some(Diagnostic::Fatal(error)) = diagnostics.into_diagnostics().into_iter().next() else {
+        panic!("expected a fatal diagnostic");
+    };
+
+    match error.into_kind() {
+        FatalDiagnosticKind::CompileError(error) => error,
+        actual => panic!("expected a compile error, got {actual}"),
+    }
+}
+
+fn replacements(suggestions: &[Suggestion]) -> Vec<&str> {
+    suggestions.iter().map(|s| s.replacement()).collect()
+}
+
+#[test]
+fn suggest_context_items() {
+    let error = compile_error("pub fn main() { HashMap::new() }");
+    assert_eq!(
+        replacements(error.suggestions()),
+        ["std::collections::HashMap"]
+    );
+    assert_eq!(error.suggestions()[0].span(), Span::new(16, 23));
+    assert_eq!(
+        error.to_string(),
+        "Missing item `HashMap::new`, consider importing `std::collections::HashMap`"
+    );
+
+    let error = compile_error("pub fn main() { let map = HashMap; }");
+    assert_eq!(
+        replacements(error.suggestions()),
+        ["std::collections::HashMap"]
+    );
+}
+
+#[test]
+fn suggest_unit_items() {
+    let error = compile_error(
+        r#"
+        mod a {
+            pub fn helper() { 42 }
+            fn hidden() { 42 }
+        }
+
+        pub fn main() { helper() + hidden() }
+        "#,
+    );
+
+    assert_eq!(replacements(error.suggestions()), ["crate::a::helper"]);
+
+    let error = compile_error(
+        r#"
+        mod a {
+            fn hidden() { 42 }
+        }
+
+        pub fn main() { hidden() }
+        "#,
+    );
+
+    assert!(error.suggestions().is_empty());
+}
+
+#[test]
+fn no_suggestions() {
+    let error = compile_error("pub fn main() { DoesNotExist::new() }");
+    assert!(error.suggestions().is_empty());
+    assert_eq!(error.to_string(), "Missing item `DoesNotExist::new`");
+
+    // Paths which are already qualified aren't suggested for.
+    let error = compile_error("pub fn main() { crate::HashMap::new() }");
+    assert!(error.suggestions().is_empty());
+}
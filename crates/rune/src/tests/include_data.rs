@@ -0,0 +1,142 @@
+prelude!();
+
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::ast::Spanned;
+use crate::compile::SourceLoader;
+
+/// A source loader which serves data files from memory.
+struct DataLoader {
+    files: Vec<(&'static str, &'static str)>,
+    loaded: Vec<String>,
+}
+
+impl SourceLoader for DataLoader {
+    fn load(&mut self, _: &Path, _: &Item, span: &dyn Spanned) -> compile::Result<Source> {
+        Err(compile::Error::msg(span, "Modules are not supported"))
+    }
+
+    fn load_data(
+        &mut self,
+        root: &Path,
+        path: &str,
+        span: &dyn Spanned,
+    ) -> compile::Result<Vec<u8>> {
+        let path = root.with_file_name(path);
+        self.loaded.push(path.display().to_string());
+
+        for (name, data) in &self.files {
+            if path == Path::new(name) {
+                return Ok(data.as_bytes().to_vec());
+            }
+        }
+
+        Err(compile::Error::msg(span, "Missing data file"))
+    }
+}
+
+fn build(source: &str, loader: &mut DataLoader) -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::with_path("main", source, "data/main.rn"));
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_source_loader(loader)
+        .build()?;
+
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+#[test]
+fn test_include_data() -> Result<()> {
+    let mut loader = DataLoader {
+        files: vec![(
+            "data/table.json",
+            r#"[{"name": "first", "value": 1}, {"name": "second", "value": 2.5, "extra": null}]"#,
+        )],
+        loaded: Vec::new(),
+    };
+
+    let mut vm = build(
+        r#"
+        pub fn main() {
+            let table = include_data!("table.json");
+            table[0].value = 10;
+            let again = include_data!("table.json");
+            (table[0].name, table[0].value, table[1].value, table[1].extra, again[0].value, table.len())
+        }
+        "#,
+        &mut loader,
+    )?;
+
+    let out: (String, i64, f64, (), i64, usize) = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(out, ("first".to_owned(), 10, 2.5, (), 1, 2));
+    assert_eq!(loader.loaded, ["data/table.json", "data/table.json"]);
+    Ok(())
+}
+
+#[test]
+fn test_include_data_errors() {
+    let mut loader = DataLoader {
+        files: vec![
+            ("data/broken.json", r#"{"a": [1, 2"#),
+            ("data/table.csv", "a,b,c"),
+        ],
+        loaded: Vec::new(),
+    };
+
+    let sources = [
+        r#"pub fn main() { include_data!("missing.json") }"#,
+        r#"pub fn main() { include_data!("broken.json") }"#,
+        r#"pub fn main() { include_data!("table.csv") }"#,
+    ];
+
+    for source in sources {
+        assert!(build(source, &mut loader).is_err(), "{source}");
+    }
+}
+
+#[test]
+fn test_include_str_and_bytes() -> Result<()> {
+    let mut loader = DataLoader {
+        files: vec![("data/hello.txt", "Hello\nWorld")],
+        loaded: Vec::new(),
+    };
+
+    let mut vm = build(
+        r#"
+        pub fn main() {
+            let string = include_str!("hello.txt");
+            let bytes = include_bytes!("hello.txt");
+            (string, bytes == b"Hello\nWorld", bytes.len())
+        }
+        "#,
+        &mut loader,
+    )?;
+
+    let out: (String, bool, usize) = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(out, ("Hello\nWorld".to_owned(), true, 11));
+    assert_eq!(loader.loaded, ["data/hello.txt", "data/hello.txt"]);
+    Ok(())
+}
+
+#[test]
+fn test_include_str_errors() {
+    let mut loader = DataLoader {
+        files: vec![],
+        loaded: Vec::new(),
+    };
+
+    let sources = [
+        r#"pub fn main() { include_str!("missing.txt") }"#,
+        r#"pub fn main() { include_bytes!("missing.bin") }"#,
+        r#"pub fn main() { include_str!() }"#,
+    ];
+
+    for source in sources {
+        assert!(build(source, &mut loader).is_err(), "{source}");
+    }
+}
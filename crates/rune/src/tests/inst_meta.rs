@@ -0,0 +1,86 @@
+prelude!();
+
+use std::collections::HashSet;
+
+use crate::runtime::{Call, Inst};
+
+#[test]
+fn inst_meta_unique() {
+    let mut names = HashSet::new();
+    let mut displays = HashSet::new();
+
+    for meta in Inst::METADATA {
+        assert!(names.insert(meta.name), "duplicate name {}", meta.name);
+        assert!(displays.insert(meta.display), "duplicate {}", meta.display);
+        assert!(!meta.docs.contains("# Operation"), "{}", meta.name);
+
+        for operand in meta.operands {
+            assert!(!operand.docs.is_empty(), "{}::{}", meta.name, operand.name);
+        }
+    }
+}
+
+#[test]
+fn inst_meta_lookup() {
+    let instructions = [
+        Inst::Not,
+        Inst::PopN { count: 2 },
+        Inst::CallOffset {
+            offset: 10,
+            call: Call::Immediate,
+            args: 2,
+        },
+        Inst::Clean { count: 1 },
+        Inst::Copy { offset: 0 },
+    ];
+
+    for inst in instructions {
+        let meta = inst.meta();
+        assert!(inst.to_string().starts_with(meta.display));
+    }
+
+    let meta = Inst::PopN { count: 2 }.meta();
+    assert_eq!(meta.name, "PopN");
+    assert_eq!(
+        meta.docs,
+        "Pop the given number of elements from the stack."
+    );
+
+    let stack = meta.stack.expect("missing stack effect");
+    assert_eq!(stack.inputs, ["<value..>"]);
+    assert!(stack.outputs.is_empty());
+
+    let meta = Inst::Clean { count: 1 }.meta();
+    let stack = meta.stack.expect("missing stack effect");
+    assert_eq!(stack.inputs, ["<top>", "<value..>"]);
+    assert_eq!(stack.outputs, ["<top>"]);
+
+    let meta = Inst::CallOffset {
+        offset: 0,
+        call: Call::Immediate,
+        args: 0,
+    }
+    .meta();
+
+    let operands = meta
+        .operands
+        .iter()
+        .map(|o| (o.name, o.ty))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        operands,
+        [("offset", "usize"), ("call", "Call"), ("args", "usize")]
+    );
+    assert!(meta.stack.is_none());
+}
+
+#[test]
+fn inst_meta_markdown() -> Result<()> {
+    let mut out = String::new();
+    Inst::Not.meta().write_markdown(&mut out)?;
+
+    assert!(out.starts_with("## `not`\n"));
+    assert!(out.contains("```text\n<bool>\n=> <bool>\n```"));
+    Ok(())
+}
@@ -0,0 +1,218 @@
+prelude!();
+
+use crate::no_std::sync::Arc;
+use crate::runtime::{Breakpoint, CancellationToken, Limit, Limits};
+use crate::Unit;
+
+const SOURCE: &str = r#"
+fn fib(n) {
+    if n <= 1 { n } else { fib(n - 1) + fib(n - 2) }
+}
+
+fn sum(n) {
+    let total = 0;
+    let i = 0;
+
+    while i < n {
+        total += i;
+        i += 1;
+    }
+
+    total
+}
+
+fn is_even(n) { n % 2 == 0 }
+fn add(a, b) { a + b }
+fn greet(n) { `Hello ${n}` }
+fn greet_twice(n) { greet(n) + greet(n) }
+
+pub fn main(n) { (fib(n), sum(n), is_even(n)) }
+pub fn call_add(a, b) { add(a, b) }
+pub fn hello(n) { greet_twice(n) }
+"#;
+
+fn build(jit: bool) -> Result<(Context, Unit)> {
+    let context = Context::with_default_modules()?;
+    let mut sources = crate::tests::sources(SOURCE);
+    let mut unit = prepare(&mut sources).with_context(&context).build()?;
+
+    if jit {
+        unit.jit()?;
+    }
+
+    Ok((context, unit))
+}
+
+fn vm(jit: bool) -> Result<Vm> {
+    let (context, unit) = build(jit)?;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+#[test]
+fn jit_compiled_functions() -> Result<()> {
+    let (_, unit) = build(true)?;
+
+    assert!(unit.is_native(["fib"]));
+    assert!(unit.is_native(["sum"]));
+    assert!(unit.is_native(["is_even"]));
+    assert!(unit.is_native(["add"]));
+    assert!(!unit.is_native(["greet"]));
+    assert!(!unit.is_native(["greet_twice"]));
+
+    let (_, unit) = build(false)?;
+    assert!(!unit.is_native(["fib"]));
+    Ok(())
+}
+
+#[test]
+fn jit_parity() -> Result<()> {
+    let mut native = vm(true)?;
+    let mut interpreted = vm(false)?;
+
+    for n in [0i64, 1, 2, 7, 20] {
+        let a: (i64, i64, bool) = from_value(native.call(["main"], (n,))?)?;
+        let b: (i64, i64, bool) = from_value(interpreted.call(["main"], (n,))?)?;
+        assert_eq!(a, b);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn jit_deoptimize() -> Result<()> {
+    let mut vm = vm(true)?;
+
+    // Overflow is reported by the interpreter.
+    let error = vm.call(["call_add"], (i64::MAX, 1i64)).unwrap_err();
+    assert_matches!(error.into_kind(), VmErrorKind::Overflow);
+
+    // Arguments which aren't integers are interpreted.
+    let out: f64 = from_value(vm.call(["call_add"], (1.5f64, 2.0f64))?)?;
+    assert_eq!(out, 3.5);
+
+    let out: String = from_value(vm.call(["call_add"], ("a", "b"))?)?;
+    assert_eq!(out, "ab");
+    Ok(())
+}
+
+#[test]
+fn jit_budget() -> Result<()> {
+    // Stepping runs with a budget of one instruction, which deoptimizes
+    // native code as soon as it's entered.
+    let mut vm = vm(true)?;
+    let mut execution = vm.execute(["main"], (10i64,))?;
+
+    let output = loop {
+        if let Some(output) = execution.step().into_result()? {
+            break output;
+        }
+    };
+
+    let output: (i64, i64, bool) = from_value(output)?;
+    assert_eq!(output, (55, 45, true));
+    Ok(())
+}
+
+#[test]
+fn jit_limits() -> Result<()> {
+    // Native code doesn't count instructions, so it's not used while limits
+    // are set.
+    let mut vm = vm(true)?;
+
+    let mut limits = Limits::new();
+    limits.instructions = Some(1000);
+    vm.set_limits(limits);
+
+    let error = vm.call(["main"], (20i64,)).unwrap_err();
+    assert_eq!(error.exceeded_limit(), Some(Limit::Instructions));
+
+    vm.set_limits(Limits::new());
+    let output: (i64, i64, bool) = from_value(vm.call(["main"], (20i64,))?)?;
+    assert_eq!(output, (6765, 190, true));
+    Ok(())
+}
+
+#[test]
+fn jit_breakpoint() -> Result<()> {
+    // Running to a breakpoint steps through the execution, so breakpoints in
+    // functions compiled to native code are still hit.
+    let (context, unit) = build(true)?;
+    let unit = Arc::new(unit);
+
+    let start = SOURCE.find("total += i").expect("missing statement");
+    let debug = unit.debug_info().expect("missing debug info");
+
+    let ip = debug
+        .instructions
+        .iter()
+        .filter(|(_, inst)| inst.span.start.into_usize() == start)
+        .map(|(ip, _)| *ip)
+        .min()
+        .expect("missing instruction");
+
+    let mut breakpoints = [Breakpoint::with_condition(&context, &unit, ip, "i == 3")?];
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), unit);
+    let mut execution = vm.execute(["main"], (10i64,))?;
+
+    assert!(execution
+        .run_to_breakpoint(&mut breakpoints)
+        .into_result()?
+        .is_none());
+    assert_eq!(execution.vm().ip(), ip);
+
+    let output = execution
+        .run_to_breakpoint(&mut breakpoints)
+        .into_result()?;
+    let output: (i64, i64, bool) = from_value(output.expect("missing output"))?;
+    assert_eq!(output, (55, 45, true));
+    Ok(())
+}
+
+#[test]
+fn jit_cancellation() -> Result<()> {
+    // Native code doesn't check for cancellation, so it's not used while a
+    // cancellation token is set.
+    let mut vm = vm(true)?;
+
+    let token = CancellationToken::new();
+    vm.set_cancellation_token(Some(token.clone()));
+
+    let output: (i64, i64, bool) = from_value(vm.call(["main"], (20i64,))?)?;
+    assert_eq!(output, (6765, 190, true));
+    assert_eq!(vm.last_execution_report().native_calls, 0);
+
+    token.cancel();
+    let error = vm.call(["main"], (20i64,)).unwrap_err();
+    assert!(error.is_cancelled());
+    Ok(())
+}
+
+#[test]
+fn jit_execution_report() -> Result<()> {
+    let mut native = vm(true)?;
+    let output: (i64, i64, bool) = from_value(native.call(["main"], (20i64,))?)?;
+    assert_eq!(output, (6765, 190, true));
+    assert_eq!(native.last_execution_report().native_calls, 3);
+
+    let mut interpreted = vm(false)?;
+    let _: (i64, i64, bool) = from_value(interpreted.call(["main"], (20i64,))?)?;
+    assert_eq!(interpreted.last_execution_report().native_calls, 0);
+    Ok(())
+}
+
+#[test]
+fn jit_patch_discards_native_code() -> Result<()> {
+    let (context, mut unit) = build(true)?;
+
+    let mut sources = crate::tests::sources("pub fn add(a, b) { a * b }");
+    let patch = prepare(&mut sources).with_context(&context).build()?;
+    unit.patch_function(["add"], &patch)?;
+
+    assert!(!unit.is_native(["add"]));
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let out: i64 = from_value(vm.call(["call_add"], (3i64, 4i64))?)?;
+    assert_eq!(out, 12);
+    Ok(())
+}
@@ -0,0 +1,158 @@
+prelude!();
+
+use crate::diagnostics::{Diagnostic, ErrorCode};
+use crate::no_std::sync::Arc;
+use crate::runtime::{LayerError, LayeredVm};
+use crate::Unit;
+
+const BASE: &str = r#"
+struct Counter { count }
+
+impl Counter {
+    fn get(self) { self.count }
+    fn bump(self) { self.count += 1; self }
+}
+
+pub fn counter(count) { Counter { count } }
+pub fn double(n) { n * 2 }
+pub fn quadruple(n) { double(double(n)) }
+pub async fn later(n) { n + 1 }
+"#;
+
+fn build(context: &Context, base: Option<&Arc<Unit>>, source: &str) -> Result<Unit> {
+    let mut sources = crate::tests::sources(source);
+    let mut build = prepare(&mut sources).with_context(context);
+
+    if let Some(base) = base {
+        build = build.with_base_unit(base.clone());
+    }
+
+    Ok(build.build()?)
+}
+
+#[test]
+fn layered_calls_into_base() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let base = Arc::new(build(&context, None, BASE)?);
+    let mut vm = LayeredVm::new(Arc::new(context.runtime()), base.clone());
+
+    let scratch = build(
+        &context,
+        Some(&base),
+        r#"
+        pub fn main(n) {
+            let c = counter(n).bump();
+            (double(n), quadruple(n), c.get())
+        }
+        "#,
+    )?;
+
+    let vm = vm.load(Arc::new(scratch))?;
+    let out: (i64, i64, i64) = crate::from_value(vm.call(["main"], (3,))?)?;
+    assert_eq!(out, (6, 12, 4));
+
+    // Base functions can be referenced as values.
+    let scratch = build(
+        &context,
+        Some(&base),
+        r#"
+        pub fn main() {
+            let f = double;
+            [1, 2, 3].iter().map(f).collect::<Vec>()
+        }
+        "#,
+    )?;
+
+    let vm = vm.load(Arc::new(scratch))?;
+    let out: Vec<i64> = crate::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(out, [2, 4, 6]);
+    Ok(())
+}
+
+#[test]
+fn layered_async_base() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let base = Arc::new(build(&context, None, BASE)?);
+    let mut vm = LayeredVm::new(Arc::new(context.runtime()), base.clone());
+
+    let scratch = build(
+        &context,
+        Some(&base),
+        r#"
+        pub async fn main(n) {
+            later(n).await + double(n)
+        }
+        "#,
+    )?;
+
+    let vm = vm.load(Arc::new(scratch))?;
+    let out = block_on(vm.async_call(["main"], (1,)))?;
+    let out: i64 = crate::from_value(out)?;
+    assert_eq!(out, 4);
+    Ok(())
+}
+
+#[test]
+fn layered_load_and_unload() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let base = Arc::new(build(&context, None, BASE)?);
+    let mut vm = LayeredVm::new(Arc::new(context.runtime()), base.clone());
+
+    for n in 0..4i64 {
+        let scratch = build(
+            &context,
+            Some(&base),
+            &format!("pub fn main() {{ double({n}) }}"),
+        )?;
+
+        let vm = vm.load(Arc::new(scratch))?;
+        let out: i64 = crate::from_value(vm.call(["main"], ())?)?;
+        assert_eq!(out, n * 2);
+    }
+
+    vm.unload();
+    assert!(vm.vm_mut().call(["main"], ()).is_err());
+    assert!(Arc::ptr_eq(vm.base(), &base));
+    Ok(())
+}
+
+#[test]
+fn layered_redefinition() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let base = Arc::new(build(&context, None, BASE)?);
+
+    let mut sources = crate::tests::sources("pub fn double(n) { n * 3 }");
+    let mut diagnostics = Diagnostics::new();
+
+    let result = prepare(&mut sources)
+        .with_context(&context)
+        .with_base_unit(base.clone())
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    assert!(result.is_err());
+
+    let codes = diagnostics
+        .diagnostics()
+        .iter()
+        .flat_map(|d| match d {
+            Diagnostic::Fatal(fatal) => Some(fatal.code()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(codes, [ErrorCode::parse("E0141")]);
+
+    // Units built without the base are checked when loaded.
+    let scratch = build(&context, None, "pub fn double(n) { n * 3 }")?;
+    let mut vm = LayeredVm::new(Arc::new(context.runtime()), base);
+
+    assert_eq!(
+        vm.load(Arc::new(scratch)).map(|_| ()),
+        Err(LayerError::BaseItemConflict {
+            hash: Hash::type_hash(["double"]),
+        })
+    );
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+prelude!();
+
+use crate::runtime::unit::{ArrayUnit, LazyUnit, UnitFn, UnitStorage};
+use crate::Unit;
+
+const SOURCE: &str = r#"
+fn add(a, b) {
+    a + b
+}
+
+fn mul(a, b) {
+    a * b
+}
+
+pub fn main(n) {
+    let f = |v| mul(v, 2);
+    add(f(n), 1)
+}
+"#;
+
+fn build<S>() -> Result<Unit<S>>
+where
+    S: Default + rune::runtime::unit::UnitEncoder + UnitStorage,
+{
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+    Ok(rune::prepare_with::<S>(&mut sources).build()?)
+}
+
+fn offset<S>(unit: &Unit<S>, name: &str) -> usize {
+    match unit.function(Hash::type_hash([name])) {
+        Some(UnitFn::Offset { offset, .. }) => offset,
+        _ => panic!("missing function `{name}`"),
+    }
+}
+
+#[test]
+fn lazy_unit_instructions() -> Result<()> {
+    let array = build::<ArrayUnit>()?;
+    let lazy = build::<LazyUnit>()?;
+
+    // One chunk for each function and the closure.
+    assert_eq!(lazy.storage().chunks(), 4);
+    assert_eq!(lazy.storage().end(), array.storage().end());
+
+    let expected = array
+        .storage()
+        .iter()
+        .map(|(ip, inst)| (ip, inst.to_string()))
+        .collect::<Vec<_>>();
+
+    let actual = lazy
+        .storage()
+        .iter()
+        .map(|(ip, inst)| (ip, inst.to_string()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(actual, expected);
+    assert_eq!(offset(&lazy, "main"), offset(&array, "main"));
+    lazy.verify()?;
+    Ok(())
+}
+
+#[test]
+fn lazy_unit_decodes_on_access() -> Result<()> {
+    let unit = build::<LazyUnit>()?;
+    let bytes = bincode::serialize(&unit)?;
+
+    let unit: Unit<LazyUnit> = bincode::deserialize(&bytes)?;
+    assert_eq!(unit.storage().chunks(), 4);
+    assert_eq!(unit.storage().loaded(), 0);
+
+    let add = offset(&unit, "add");
+    assert!(unit.storage().get(add)?.is_some());
+    assert_eq!(unit.storage().loaded(), 1);
+
+    // Accessing the same function again doesn't decode anything new.
+    assert!(unit.storage().get(add + 1)?.is_some());
+    assert_eq!(unit.storage().loaded(), 1);
+
+    unit.storage().load_all()?;
+    assert_eq!(unit.storage().loaded(), 4);
+
+    let original = build::<LazyUnit>()?;
+    assert_eq!(unit.storage().bytes(), original.storage().bytes());
+    assert!(unit.storage().get(unit.storage().end())?.is_none());
+    Ok(())
+}
+
+#[test]
+fn lazy_unit_verify_loads_everything() -> Result<()> {
+    let unit = build::<LazyUnit>()?;
+    let bytes = bincode::serialize(&unit)?;
+
+    let unit: Unit<LazyUnit> = bincode::deserialize(&bytes)?;
+    assert_eq!(unit.storage().loaded(), 0);
+
+    unit.verify()?;
+    assert_eq!(unit.storage().loaded(), 4);
+    Ok(())
+}
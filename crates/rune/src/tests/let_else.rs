@@ -0,0 +1,152 @@
+prelude!();
+
+#[test]
+fn test_let_else() {
+    let out: (i64, i64) = rune! {
+        fn unwrap_or(value, default) {
+            let Some(value) = value else {
+                return default;
+            };
+
+            value
+        }
+
+        pub fn main() {
+            (unwrap_or(Some(1), 2), unwrap_or(None, 2))
+        }
+    };
+
+    assert_eq!(out, (1, 2));
+}
+
+#[test]
+fn test_let_else_runs_else() {
+    let out: Vec<i64> = rune! {
+        fn describe(value, log) {
+            let Some(n) = value else {
+                log.push(0);
+                return log;
+            };
+
+            log.push(n);
+            log
+        }
+
+        pub fn main() {
+            let log = describe(Some(1), []);
+            describe(None, log)
+        }
+    };
+
+    assert_eq!(out, [1, 0]);
+}
+
+#[test]
+fn test_let_else_bindings() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let values = [[1, 2], [3], [4, 5], 6];
+            let sum = 0;
+
+            for value in values {
+                let offset = 10;
+
+                let [a, b] = value else {
+                    continue;
+                };
+
+                sum += a * b + offset;
+            }
+
+            sum
+        }
+    };
+
+    assert_eq!(out, 42);
+}
+
+#[test]
+fn test_let_else_break() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let values = [Ok(1), Ok(2), Err(3), Ok(4)];
+            let sum = 0;
+
+            for value in values {
+                let Ok(n) = value else {
+                    break;
+                };
+
+                sum += n;
+            }
+
+            sum
+        }
+    };
+
+    assert_eq!(out, 3);
+}
+
+#[test]
+fn test_let_else_panic() {
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let Some(value) = None else {
+                panic("no value");
+            };
+
+            value
+        }
+        "#,
+        VmErrorKind::Panic { reason } => {
+            assert_eq!(reason.to_string(), "no value");
+        }
+    );
+}
+
+#[test]
+fn test_let_else_must_diverge() {
+    assert_errors! {
+        r#"pub fn main() { let Some(a) = None else { 1 }; a }"#,
+        span!(35, 45), ErrorKind::LetElseMustDiverge
+    };
+
+    assert_errors! {
+        r#"pub fn main() { let Some(a) = None else { if true { return; } }; a }"#,
+        span!(35, 63), ErrorKind::LetElseMustDiverge
+    };
+
+    assert_errors! {
+        r#"pub fn main() { let Some(a) = None else { match 1 { 2 => return, } }; a }"#,
+        span!(35, 68), ErrorKind::LetElseMustDiverge
+    };
+}
+
+#[test]
+fn test_let_else_match() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let Some(a) = None else {
+                match 1 {
+                    2 => return 2,
+                    n => return n,
+                }
+            };
+
+            a
+        }
+    };
+
+    assert_eq!(out, 1);
+}
+
+#[test]
+fn test_let_else_scope() {
+    assert_errors! {
+        r#"pub fn main() { let Some(a) = None else { return a; }; a }"#,
+        span, ErrorKind::MissingLocal { .. } => {
+            assert_eq!(span, span!(49, 50));
+        }
+    };
+}
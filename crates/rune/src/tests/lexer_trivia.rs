@@ -0,0 +1,126 @@
+prelude!();
+
+use crate::ast::{Delimiter, Kind};
+use crate::parse::{IncrementalLexer, Lexer};
+use crate::SourceId;
+
+const SOURCE: &str = r#"#!/usr/bin/env rune
+//! Module docs.
+
+/// Function docs.
+pub fn main(a) {
+    /* block */ let b = 0x10u8;
+    `${a} and ${ #{ b }.b }!`
+}
+"#;
+
+fn lex(source: &str) -> Result<Vec<ast::Token>> {
+    let mut lexer = Lexer::with_trivia(source, SourceId::empty(), true);
+    let mut tokens = Vec::new();
+
+    while let Some(token) = lexer.next()? {
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+#[test]
+fn trivia_covers_source() -> Result<()> {
+    let tokens = lex(SOURCE)?;
+
+    let mut end = 0;
+
+    for token in &tokens {
+        assert_eq!(token.span.range().start, end, "{token:?}");
+        end = token.span.range().end;
+    }
+
+    assert_eq!(end, SOURCE.len());
+
+    let kinds = tokens
+        .iter()
+        .map(|t| t.kind)
+        .filter(|kind| !matches!(kind, Kind::Whitespace))
+        .collect::<Vec<_>>();
+
+    assert!(matches!(kinds[0], Kind::Shebang(..)));
+    assert!(matches!(kinds[1..3], [Kind::Comment, Kind::Comment]));
+    assert!(kinds.contains(&Kind::MultilineComment(true)));
+
+    let template = kinds
+        .iter()
+        .position(|kind| *kind == Kind::Open(Delimiter::Empty))
+        .expect("template literal");
+
+    assert!(matches!(
+        kinds[template..template + 6],
+        [
+            Kind::Open(Delimiter::Empty),
+            Kind::Dollar,
+            Kind::Open(Delimiter::Brace),
+            Kind::Ident(..),
+            Kind::Close(Delimiter::Brace),
+            Kind::Str(..),
+        ]
+    ));
+
+    assert!(matches!(
+        kinds[kinds.len() - 3..],
+        [
+            Kind::Str(..),
+            Kind::Close(Delimiter::Empty),
+            Kind::Close(Delimiter::Brace),
+        ]
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn incremental_lexing_reuses_tokens() -> Result<()> {
+    let mut source = String::from(SOURCE);
+    let mut lexer = IncrementalLexer::new(&source, SourceId::empty(), true)?;
+    let before = lexer.tokens().to_vec();
+
+    let start = source.find("main").expect("function name");
+    source.replace_range(start..start + 4, "run");
+
+    // Only the whitespace before the name and the name itself are lexed again.
+    let changed = lexer.edit(&source, start..start + 4, 3)?;
+    assert_eq!(changed.len(), 2);
+    assert_eq!(lexer.tokens().len(), before.len());
+    assert_eq!(lexer.tokens()[..changed.start], before[..changed.start]);
+    assert_eq!(lexer.tokens(), lex(&source)?);
+    Ok(())
+}
+
+#[test]
+fn incremental_lexing() -> Result<()> {
+    let edits: &[(&str, &str)] = &[
+        ("let b", "let bb"),
+        ("0x10u8", "42"),
+        ("/* block */", "/* block"),
+        ("/* block", "/* block */"),
+        (" and ", " and ${b} "),
+        ("{ #{ b }.b }", "{b}"),
+        ("/// Function docs.\n", ""),
+        ("pub fn", "fn main() {}\npub fn"),
+        ("}\n", "} // end\n"),
+    ];
+
+    let mut source = String::from(SOURCE);
+    let mut lexer = IncrementalLexer::new(&source, SourceId::empty(), true)?;
+    assert_eq!(lexer.tokens(), lex(&source)?);
+
+    for (from, to) in edits {
+        let start = source.find(from).expect("edit to apply");
+        let range = start..start + from.len();
+        source.replace_range(range.clone(), to);
+
+        lexer.edit(&source, range, to.len())?;
+        assert_eq!(lexer.tokens(), lex(&source)?, "after replacing {from:?}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+prelude!();
+
+use std::sync::Arc;
+
+use crate::runtime::{Limit, Limits};
+
+fn vm(context: &Context, source: &str) -> Result<Vm> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let unit = prepare(&mut sources).with_context(context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+#[test]
+fn limit_instructions() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = vm(
+        &context,
+        r#"
+        pub fn main(n) {
+            let out = 0;
+
+            while out < n {
+                out += 1;
+            }
+
+            out
+        }
+        "#,
+    )?;
+
+    let mut limits = Limits::new();
+    limits.instructions = Some(1000);
+    vm.set_limits(limits);
+
+    let output: i64 = from_value(vm.call(["main"], (10,))?)?;
+    assert_eq!(output, 10);
+
+    // The limit applies to each execution separately.
+    let output: i64 = from_value(vm.call(["main"], (10,))?)?;
+    assert_eq!(output, 10);
+
+    let error = vm.call(["main"], (1000,)).unwrap_err();
+    assert_eq!(error.exceeded_limit(), Some(Limit::Instructions));
+    assert_eq!(
+        error.to_string(),
+        "Execution exceeded the limit of 1000 instructions"
+    );
+    assert_eq!(vm.last_execution_report().instructions, 1001);
+    Ok(())
+}
+
+#[test]
+fn limit_call_depth() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = vm(
+        &context,
+        r#"
+        fn depth(n) {
+            if n == 0 { 0 } else { depth(n - 1) + 1 }
+        }
+
+        pub fn main(n) {
+            depth(n)
+        }
+        "#,
+    )?;
+
+    let mut limits = Limits::new();
+    limits.call_depth = Some(10);
+    vm.set_limits(limits);
+
+    // The call to `main` itself doesn't push a call frame.
+    let output: i64 = from_value(vm.call(["main"], (9,))?)?;
+    assert_eq!(output, 9);
+
+    let error = vm.call(["main"], (10,)).unwrap_err();
+    assert_eq!(error.exceeded_limit(), Some(Limit::CallDepth));
+    Ok(())
+}
+
+#[test]
+fn limit_allocations() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = vm(
+        &context,
+        r#"
+        pub fn main(n) {
+            let out = [];
+
+            for i in 0..n {
+                out.push((i, #{ value: i }));
+            }
+
+            out.len()
+        }
+        "#,
+    )?;
+
+    let output: i64 = from_value(vm.call(["main"], (4,))?)?;
+    assert_eq!(output, 4);
+    assert_eq!(vm.last_execution_report().allocations, 9);
+
+    let mut limits = Limits::new();
+    limits.allocations = Some(9);
+    vm.set_limits(limits);
+
+    let output: i64 = from_value(vm.call(["main"], (4,))?)?;
+    assert_eq!(output, 4);
+
+    let error = vm.call(["main"], (5,)).unwrap_err();
+    assert_eq!(error.exceeded_limit(), Some(Limit::Allocations));
+    Ok(())
+}
+
+#[test]
+fn limit_execution() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = vm(
+        &context,
+        r#"
+        async fn spin() {
+            loop {}
+        }
+
+        pub async fn main() {
+            spin().await
+        }
+        "#,
+    )?;
+
+    let mut limits = Limits::new();
+    limits.instructions = Some(10_000);
+
+    let mut execution = vm.execute(["main"], ())?;
+    execution.set_limits(limits);
+
+    // The limits are inherited by the virtual machine running `spin`.
+    let error = block_on(execution.async_complete())
+        .into_result()
+        .unwrap_err();
+    assert_eq!(error.exceeded_limit(), Some(Limit::Instructions));
+    Ok(())
+}
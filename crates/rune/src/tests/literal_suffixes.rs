@@ -0,0 +1,165 @@
+prelude!();
+
+use crate::ast::{Span, Spanned};
+use crate::diagnostics::{Diagnostic, FatalDiagnosticKind};
+use crate::macros::{quote, MacroContext, TokenStream};
+use crate::no_std::sync::Arc;
+use crate::parse::Parser;
+use crate::{BuildError, Unit};
+
+#[derive(Debug, PartialEq, Any)]
+struct Length {
+    #[rune(get)]
+    px: i64,
+}
+
+fn px(cx: &mut MacroContext<'_, '_, '_>, stream: &TokenStream) -> compile::Result<TokenStream> {
+    let mut p = Parser::from_token_stream(stream, cx.input_span());
+    let lit = p.parse_all::<ast::LitNumber>()?;
+    Ok(quote!(Length::px(#lit)).into_token_stream(cx))
+}
+
+fn ms(cx: &mut MacroContext<'_, '_, '_>, stream: &TokenStream) -> compile::Result<TokenStream> {
+    let mut p = Parser::from_token_stream(stream, cx.input_span());
+    let lit = p.parse_all::<ast::LitNumber>()?;
+    Ok(quote!(#lit / 1000.0).into_token_stream(cx))
+}
+
+fn pct(cx: &mut MacroContext<'_, '_, '_>, stream: &TokenStream) -> compile::Result<TokenStream> {
+    let mut p = Parser::from_token_stream(stream, cx.input_span());
+    let lit = p.parse_all::<ast::LitNumber>()?;
+
+    let ast::NumberValue::Integer(n) = cx.resolve(lit)?.value else {
+        return Err(compile::Error::msg(lit, "percentages must be integers"));
+    };
+
+    if n > 100.into() {
+        return Err(compile::Error::msg(lit, "percentages can't exceed 100"));
+    }
+
+    Ok(quote!(#lit).into_token_stream(cx))
+}
+
+fn context() -> Result<Context> {
+    let mut module = Module::new();
+    module.ty::<Length>()?;
+    module.function(["Length", "px"], |px: i64| Length { px })?;
+    module.literal_suffix("px", px)?;
+    module.literal_suffix("ms", ms)?;
+    module.literal_suffix("pct", pct)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+    Ok(context)
+}
+
+fn build(source: &str) -> (Result<Unit, BuildError>, Diagnostics) {
+    let context = context().expect("setting up context");
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let mut diagnostics = Diagnostics::new();
+
+    let unit = crate::prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    (unit, diagnostics)
+}
+
+fn eval<T>(source: &str) -> Result<T>
+where
+    T: FromValue,
+{
+    let context = context()?;
+    let (unit, _) = build(source);
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit?));
+    Ok(from_value(vm.call(["main"], ())?)?)
+}
+
+/// Compile the given source, returning the error it fails with.
+fn compile_error(source: &str) -> compile::Error {
+    let (unit, diagnostics) = build(source);
+    assert!(unit.is_err());
+
+    let Some(Diagnostic::Fatal(error)) = diagnostics.into_diagnostics().into_iter().next() else {
+        panic!("expected a fatal diagnostic");
+    };
+
+    match error.into_kind() {
+        FatalDiagnosticKind::CompileError(error) => error,
+        actual => panic!("expected a compile error, got {actual}"),
+    }
+}
+
+#[test]
+fn literal_suffixes() -> Result<()> {
+    let length: Length = eval("pub fn main() { 10px }")?;
+    assert_eq!(length, Length { px: 10 });
+
+    let length: Length = eval("pub fn main() { 0xFFpx }")?;
+    assert_eq!(length, Length { px: 255 });
+
+    let seconds: f64 = eval("pub fn main() { 2500.0ms }")?;
+    assert_eq!(seconds, 2.5);
+
+    let seconds: f64 = eval("pub fn main() { -1.5e3ms }")?;
+    assert_eq!(seconds, -1.5);
+
+    let n: i64 = eval("pub fn main() { 1_000px.px + 50pct }")?;
+    assert_eq!(n, 1050);
+
+    let byte: u8 = eval("pub fn main() { 0xFFu8 }")?;
+    assert_eq!(byte, 255);
+    Ok(())
+}
+
+#[test]
+fn literal_suffix_diagnostics() {
+    let error = compile_error("pub fn main() { 10em }");
+    assert_eq!(error.span(), Span::new(18, 20));
+    assert!(matches!(
+        error.into_kind(),
+        ErrorKind::UnsupportedSuffix { suffix } if &*suffix == "em"
+    ));
+
+    let error = compile_error("pub fn main() { 120pct }");
+    assert_eq!(error.span(), Span::new(16, 19));
+    assert_eq!(error.to_string(), "percentages can't exceed 100");
+
+    let error = compile_error("pub fn main() { 1.5pct }");
+    assert_eq!(error.to_string(), "percentages must be integers");
+}
+
+#[test]
+fn literal_suffix_registration() -> Result<()> {
+    let mut module = Module::new();
+
+    assert!(matches!(
+        module.literal_suffix("i64", px),
+        Err(ContextError::InvalidLiteralSuffix { .. })
+    ));
+
+    assert!(matches!(
+        module.literal_suffix("1x", px),
+        Err(ContextError::InvalidLiteralSuffix { .. })
+    ));
+
+    module.literal_suffix("px", px)?;
+
+    assert!(matches!(
+        module.literal_suffix("px", px),
+        Err(ContextError::ConflictingLiteralSuffix { .. })
+    ));
+
+    let mut context = context()?;
+
+    assert!(matches!(
+        context.install(&module),
+        Err(ContextError::ConflictingLiteralSuffix { .. })
+    ));
+
+    Ok(())
+}
@@ -138,7 +138,29 @@ fn test_rename() -> rune::Result<()> {
     let unit = result?;
     let unit = Arc::new(unit);
 
-    let mut vm = Vm::new(runtime, unit);
+    let mut vm = Vm::new(runtime.clone(), unit);
+    let value = vm.call(["main"], ())?;
+    let value: u32 = rune::from_value(value)?;
+
+    assert_eq!(value, 42);
+
+    // Attribute macros are also expanded for items declared in blocks.
+    let mut sources = rune::sources! {
+        entry => {
+            pub fn main() {
+                #[rename = foobar]
+                fn renamed() {
+                    42
+                }
+
+                foobar()
+            }
+        }
+    };
+
+    let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+
+    let mut vm = Vm::new(runtime, Arc::new(unit));
     let value = vm.call(["main"], ())?;
     let value: u32 = rune::from_value(value)?;
 
@@ -0,0 +1,125 @@
+prelude!();
+
+#[test]
+fn math_functions() {
+    let out: (f64, f64, f64, f64, f64) = rune! {
+        use std::math;
+
+        pub fn main() {
+            (
+                math::sqrt(16.0),
+                math::atan2(0.0, 1.0),
+                math::floor(2.5) + math::ceil(2.5) + math::round(2.5),
+                math::pow(2.0, 10),
+                math::hypot(3.0, 4.0),
+            )
+        }
+    };
+
+    assert_eq!(out, (4.0, 0.0, 8.0, 1024.0, 5.0));
+
+    let out: (i64, i64, i64, i64, i64, f64) = rune! {
+        use std::math;
+
+        pub fn main() {
+            (
+                math::abs(-3),
+                math::min(3, -2),
+                math::max(3, -2),
+                math::clamp(10, 0, 5),
+                math::pow(3, 4),
+                math::clamp(-1.5, 0.0, 1.0),
+            )
+        }
+    };
+
+    assert_eq!(out, (3, -2, 3, 5, 81, 0.0));
+}
+
+#[test]
+fn math_constants() {
+    let out: (f64, f64, f64) = rune! {
+        use std::math;
+
+        pub fn main() {
+            (math::PI, math::TAU, math::E)
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            core::f64::consts::PI,
+            core::f64::consts::TAU,
+            core::f64::consts::E
+        )
+    );
+}
+
+#[test]
+fn math_const_eval() {
+    let out: (f64, i64, f64) = rune! {
+        use std::math;
+
+        const RADIUS = math::sqrt(16.0);
+        const AREA = math::round(math::PI * math::pow(RADIUS, 2));
+        const BITS = math::max(math::pow(2, 8), math::abs(0 - 300));
+
+        const fn degrees(turns) {
+            math::to_degrees(turns * math::TAU)
+        }
+
+        const HALF = degrees(0.5);
+
+        pub fn main() {
+            (AREA, BITS, HALF)
+        }
+    };
+
+    assert_eq!(out, (50.0, 300, 180.0));
+}
+
+#[test]
+fn math_const_eval_errors() {
+    assert_errors! {
+        "use std::math; const VALUE = math::clamp(1, 5, 0);",
+        _, Custom { message } => {
+            assert_eq!(message.as_ref(), "The minimum is greater than the maximum");
+        }
+    };
+
+    assert_errors! {
+        "use std::math; const VALUE = math::sqrt(4);",
+        _, Custom { message } => {
+            assert_eq!(message.as_ref(), "Expected a float");
+        }
+    };
+}
+
+#[test]
+fn math_errors() {
+    assert_vm_error!(
+        r#"pub fn main() { std::math::abs(-9223372036854775807 - 1) }"#,
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { std::math::pow(2, 64) }"#,
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { std::math::abs("hello") }"#,
+        Expected { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { std::math::min(1, 2.0) }"#,
+        Panic { reason } => {
+            assert_eq!(
+                reason.to_string(),
+                "Arguments must either all be integers or all be floats"
+            );
+        }
+    );
+}
@@ -0,0 +1,223 @@
+prelude!();
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::no_std::sync::Arc;
+use crate::runtime::Args;
+
+use ErrorKind::*;
+
+fn vm(calls: &Arc<AtomicUsize>, source: &str) -> Result<Vm> {
+    let mut module = Module::new();
+
+    module.function(["tick"], {
+        let calls = calls.clone();
+        move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+        }
+    })?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let mut sources = crate::tests::sources(source);
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+fn call<T>(vm: &mut Vm, name: &str, args: impl Args) -> Result<T>
+where
+    T: FromValue,
+{
+    Ok(crate::from_value(vm.call([name], args)?)?)
+}
+
+#[test]
+fn memo_caches_results() -> Result<()> {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let mut vm = vm(
+        &calls,
+        r#"
+        #[memo]
+        fn square(n) {
+            tick();
+            n * n
+        }
+
+        pub fn main() {
+            square(2) + square(3) + square(2) + square(3)
+        }
+        "#,
+    )?;
+
+    assert_eq!(call::<i64>(&mut vm, "main", ())?, 26);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // Results are kept across executions in the same virtual machine.
+    assert_eq!(call::<i64>(&mut vm, "main", ())?, 26);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    vm.clear_memo();
+    assert_eq!(call::<i64>(&mut vm, "main", ())?, 26);
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+    Ok(())
+}
+
+#[test]
+fn memo_keys() -> Result<()> {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let mut vm = vm(
+        &calls,
+        r#"
+        #[memo]
+        fn describe(key, value) {
+            tick();
+            `${key}=${value.len()}`
+        }
+
+        pub fn main() {
+            [
+                describe("a", [1, 2]),
+                describe("a", [1, 2]),
+                describe("a", [1, 2, 3]),
+                describe("b", [(1, 2)]),
+                describe("b", [(1, 2)]),
+            ]
+        }
+        "#,
+    )?;
+
+    let out: Vec<String> = call(&mut vm, "main", ())?;
+    assert_eq!(out, ["a=2", "a=2", "a=3", "b=1", "b=1"]);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    Ok(())
+}
+
+#[test]
+fn memo_recursive() -> Result<()> {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let mut vm = vm(
+        &calls,
+        r#"
+        #[memo]
+        pub fn fib(n) {
+            tick();
+
+            if n < 2 {
+                n
+            } else {
+                fib(n - 1) + fib(n - 2)
+            }
+        }
+        "#,
+    )?;
+
+    assert_eq!(call::<i64>(&mut vm, "fib", (80,))?, 23416728348467685);
+    assert_eq!(calls.load(Ordering::SeqCst), 81);
+
+    // Functions called directly from the host are memoized as well.
+    assert_eq!(call::<i64>(&mut vm, "fib", (80,))?, 23416728348467685);
+    assert_eq!(calls.load(Ordering::SeqCst), 81);
+    Ok(())
+}
+
+#[test]
+fn memo_capacity() -> Result<()> {
+    let source = r#"
+    #[memo(capacity = 2)]
+    fn two(n) {
+        tick();
+        n
+    }
+
+    #[memo]
+    fn one(n) {
+        tick();
+        n
+    }
+
+    pub fn main() {
+        two(1) + two(2) + two(1) + two(3) + two(2) + two(1)
+    }
+
+    pub fn main_one() {
+        one(1) + one(2) + one(1) + one(3) + one(2) + one(1)
+    }
+    "#;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut vm = vm(&calls, source)?;
+
+    // The least recently used result is evicted, so calling `two(3)` evicts
+    // `two(2)` since `two(1)` was used more recently.
+    assert_eq!(call::<i64>(&mut vm, "main", ())?, 10);
+    assert_eq!(calls.load(Ordering::SeqCst), 5);
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut vm = self::vm(&calls, source)?;
+    vm.set_memo_capacity(1);
+
+    assert_eq!(call::<i64>(&mut vm, "main_one", ())?, 10);
+    assert_eq!(calls.load(Ordering::SeqCst), 6);
+    Ok(())
+}
+
+#[test]
+fn memo_unpacked_tuple() -> Result<()> {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let mut vm = vm(
+        &calls,
+        r#"
+        #[memo]
+        fn pair(n) {
+            tick();
+            (n, n * 2)
+        }
+
+        pub fn main() {
+            let (a, b) = pair(1);
+            let (c, d) = pair(1);
+            a + b + c + d
+        }
+        "#,
+    )?;
+
+    assert_eq!(call::<i64>(&mut vm, "main", ())?, 6);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[test]
+fn memo_errors() {
+    assert_errors! {
+        "#[memo] async fn f() {}",
+        _, Custom { message } => {
+            assert_eq!(message.as_ref(), "The #[memo] attribute is not supported on async functions or generators");
+        }
+    };
+
+    assert_errors! {
+        "struct Foo; impl Foo { #[memo] fn f(self) {} }",
+        _, Custom { message } => {
+            assert_eq!(message.as_ref(), "The #[memo] attribute is not supported on member functions");
+        }
+    };
+
+    assert_errors! {
+        "#[memo(capacity = 0)] fn f() {}",
+        _, Custom { message } => {
+            assert_eq!(message.as_ref(), "capacity must be a positive integer");
+        }
+    };
+
+    assert_errors! {
+        "#[memo(size = 1)] fn f() {}",
+        _, Custom { message } => {
+            assert_eq!(message.as_ref(), "unsupported attribute");
+        }
+    };
+}
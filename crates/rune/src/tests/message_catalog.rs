@@ -0,0 +1,111 @@
+prelude!();
+
+use core::fmt;
+use std::sync::Arc;
+
+use crate::runtime::{Message, MessageCatalog, RuntimeContext, TemplateCatalog, VmError};
+
+#[rune::function]
+fn repeat(string: &str, count: usize) -> String {
+    string.repeat(count)
+}
+
+/// A catalog which writes out the id and arguments of every message.
+struct Ids;
+
+impl MessageCatalog for Ids {
+    fn format(&self, message: &Message<'_>, f: &mut fmt::Formatter<'_>) -> Option<fmt::Result> {
+        Some(write_ids(message, f))
+    }
+}
+
+fn write_ids(message: &Message<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", message.id())?;
+
+    for (name, value) in message.args() {
+        write!(f, " {name}={value}")?;
+    }
+
+    Ok(())
+}
+
+fn run<C>(catalog: C, source: &str) -> Result<(RuntimeContext, VmError)>
+where
+    C: 'static + MessageCatalog,
+{
+    let mut module = Module::new();
+    module.function_meta(repeat)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+    context.set_message_catalog(catalog);
+
+    let mut sources = crate::tests::sources(source);
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+
+    let runtime = context.runtime();
+    let mut vm = Vm::new(Arc::new(runtime.clone()), Arc::new(unit));
+    let error = vm.call(["main"], ()).unwrap_err();
+    Ok((runtime, error))
+}
+
+#[test]
+fn test_template_catalog() -> Result<()> {
+    let mut catalog = TemplateCatalog::new();
+    catalog.insert("divide-by-zero", "Division par zéro");
+    catalog.insert("panic", "Panique : {reason}");
+    catalog.insert("panic-unmatched-pattern", "motif non reconnu");
+    catalog.insert(
+        "bad-argument-type-named",
+        "Argument n°{arg} `{name}` : `{expected}` attendu, `{actual}` obtenu",
+    );
+
+    let (runtime, error) = run(catalog.clone(), "pub fn main() { 10 / 0 }")?;
+    assert_eq!(runtime.localize(&error).to_string(), "Division par zéro");
+    assert_eq!(error.to_string(), "Division by zero");
+    assert_matches!(error.into_kind(), VmErrorKind::DivideByZero);
+
+    let (runtime, error) = run(catalog.clone(), "pub fn main() { let [] = [1]; }")?;
+    assert_eq!(
+        runtime.localize(&error).to_string(),
+        "Panique : motif non reconnu"
+    );
+    assert_eq!(error.to_string(), "Panicked: pattern did not match");
+
+    let (runtime, error) = run(catalog.clone(), r#"pub fn main() { repeat("a", "b") }"#)?;
+    assert_eq!(
+        runtime.localize(&error).to_string(),
+        "Argument n°2 `count` : `i64` attendu, `String` obtenu"
+    );
+
+    // Messages without a template fall back to English, and custom panic
+    // messages are never translated.
+    let (runtime, error) = run(catalog, r#"pub fn main() { panic("oh no") }"#)?;
+    assert_eq!(runtime.localize(&error).to_string(), "Panique : oh no");
+
+    let (runtime, error) = run(TemplateCatalog::new(), "pub fn main() { 10 / 0 }")?;
+    assert_eq!(runtime.localize(&error).to_string(), "Division by zero");
+    Ok(())
+}
+
+#[test]
+fn test_message_ids() -> Result<()> {
+    let (runtime, error) = run(Ids, r#"pub fn main() { repeat("a") }"#)?;
+    assert_eq!(
+        runtime.localize(&error).to_string(),
+        "bad-argument-count actual=1 expected=2"
+    );
+
+    let (runtime, error) = run(Ids, "pub fn main() { let [] = [1]; }")?;
+    assert_eq!(
+        runtime.localize(&error).to_string(),
+        "panic reason=panic-unmatched-pattern"
+    );
+
+    // Without a catalog, errors are displayed the same way as through their
+    // `Display` implementation.
+    let (_, error) = run(TemplateCatalog::new(), "pub fn main() { let [] = [1]; }")?;
+    let runtime = RuntimeContext::default();
+    assert_eq!(runtime.localize(&error).to_string(), error.to_string());
+    Ok(())
+}
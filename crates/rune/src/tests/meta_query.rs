@@ -0,0 +1,96 @@
+prelude!();
+
+use crate::compile::{MetaAttribute, MetaIndex, MetaKind};
+
+fn index(source: &str) -> Result<MetaIndex> {
+    let context = Context::with_default_modules()?;
+    let mut sources = crate::tests::sources(source);
+    let mut index = MetaIndex::new().with_context(&context);
+
+    prepare(&mut sources)
+        .with_context(&context)
+        .with_visitor(&mut index)
+        .build()?;
+
+    Ok(index)
+}
+
+fn items<'a>(entries: impl Iterator<Item = &'a crate::compile::MetaEntry>) -> Vec<String> {
+    let mut items = entries.map(|e| e.item().to_string()).collect::<Vec<_>>();
+    items.sort();
+    items
+}
+
+#[test]
+fn test_meta_query() -> Result<()> {
+    let index = index(
+        r#"
+        mod handlers {
+            /// Serve the index.
+            pub fn get_index(request, response) {}
+            pub fn get_health(request) {}
+            pub fn post_upload(request, response) {}
+
+            pub mod admin {
+                pub fn get_users(request, response) {}
+            }
+
+            pub struct Request;
+        }
+
+        #[test]
+        fn test_handlers() {}
+
+        pub fn main() {}
+        "#,
+    )?;
+
+    let found = index.query("handlers::*").kind(MetaKind::Function).args(2);
+
+    assert_eq!(
+        items(found),
+        ["handlers::get_index", "handlers::post_upload"]
+    );
+
+    let found = index.query("handlers::**::get_*").kind(MetaKind::Function);
+
+    assert_eq!(
+        items(found),
+        [
+            "handlers::admin::get_users",
+            "handlers::get_health",
+            "handlers::get_index"
+        ]
+    );
+
+    let found = index.query("handlers::*").kind(MetaKind::Struct);
+    assert_eq!(items(found), ["handlers::Request"]);
+
+    let found = index.query("**").attribute(MetaAttribute::Test);
+    assert_eq!(items(found), ["test_handlers"]);
+
+    let found = index.query("**").attribute(MetaAttribute::Doc);
+    assert_eq!(items(found), ["handlers::get_index"]);
+
+    let entry = index.query("handlers::get_index").next().unwrap();
+    assert_eq!(entry.docs(), [" Serve the index.\n"]);
+    assert!(!entry.is_from_context());
+    Ok(())
+}
+
+#[test]
+fn test_meta_query_context() -> Result<()> {
+    let index = index("pub fn main() {}")?;
+
+    let entry = index
+        .query("::std::io::dbg")
+        .kind(MetaKind::Function)
+        .next()
+        .unwrap();
+
+    assert!(entry.is_from_context());
+
+    let found = index.query("**").kind(MetaKind::Function).sources_only();
+    assert_eq!(items(found), ["main"]);
+    Ok(())
+}
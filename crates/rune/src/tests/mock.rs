@@ -0,0 +1,130 @@
+prelude!();
+
+use std::sync::Arc;
+
+use VmErrorKind::*;
+
+#[rune::function]
+fn get(url: &str) -> String {
+    format!("real {url}")
+}
+
+const SOURCE: &str = r#"
+use std::test::{mock, unmock};
+
+fn fetch_all(urls) {
+    let out = [];
+
+    for body in urls.iter().map(|url| http::get(url)) {
+        out.push(body);
+    }
+
+    out
+}
+
+pub fn mocked() {
+    mock(http::get, |url| `mocked ${url}`);
+    (http::get("a"), fetch_all(["b", "c"]))
+}
+
+pub async fn mocked_async() {
+    mock(http::get, |url| `mocked ${url}`);
+    let url = "a";
+    async { http::get(url) }.await
+}
+
+async fn fetch(url) {
+    http::get(url)
+}
+
+pub async fn mocked_async_fn() {
+    mock(http::get, |url| `mocked ${url}`);
+    fetch("a").await
+}
+
+pub fn restored() {
+    mock(http::get, |url| `mocked ${url}`);
+    let removed = unmock(http::get);
+    (removed, unmock(http::get), http::get("a"))
+}
+
+pub fn not_context() {
+    mock(restored, || ());
+}
+
+pub fn unmocked() {
+    http::get("a")
+}
+"#;
+
+fn vm() -> Result<Vm> {
+    let mut module = Module::with_item(["http"]);
+    module.function_meta(get)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let mut sources = crate::tests::sources(SOURCE);
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+#[test]
+fn test_mock() -> Result<()> {
+    let mut vm = vm()?;
+    vm.set_test_mode(true);
+
+    let output: (String, Vec<String>) = from_value(vm.call(["mocked"], ())?)?;
+    assert_eq!(output.0, "mocked a");
+    assert_eq!(output.1, ["mocked b", "mocked c"]);
+
+    // Mocks remain installed until the virtual machine is cleared.
+    let output: String = from_value(vm.call(["unmocked"], ())?)?;
+    assert_eq!(output, "mocked a");
+
+    vm.clear();
+    let output: String = from_value(vm.call(["unmocked"], ())?)?;
+    assert_eq!(output, "real a");
+
+    let output: String = from_value(block_on(vm.async_call(["mocked_async"], ()))?)?;
+    assert_eq!(output, "mocked a");
+
+    // Mocks can be installed by async functions, and are shared with the
+    // virtual machines running the async functions they call.
+    vm.clear();
+    let output: String = from_value(block_on(vm.async_call(["mocked_async_fn"], ()))?)?;
+    assert_eq!(output, "mocked a");
+
+    vm.set_test_mode(false);
+    let output: String = from_value(vm.call(["unmocked"], ())?)?;
+    assert_eq!(output, "real a");
+    Ok(())
+}
+
+#[test]
+fn test_unmock() -> Result<()> {
+    let mut vm = vm()?;
+    vm.set_test_mode(true);
+
+    let output: (bool, bool, String) = from_value(vm.call(["restored"], ())?)?;
+    assert_eq!(output, (true, false, String::from("real a")));
+    Ok(())
+}
+
+#[test]
+fn test_mock_errors() -> Result<()> {
+    let mut vm = vm()?;
+
+    let error = vm.call(["mocked"], ()).unwrap_err();
+    assert_matches!(error.into_kind(), MockOutsideTestMode);
+
+    vm.set_test_mode(true);
+
+    let error = vm.call(["not_context"], ()).unwrap_err();
+    assert!(matches!(
+        error.into_kind(),
+        MockNotContextFunction { hash } if hash == Hash::type_hash(["restored"])
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+prelude!();
+
+use crate::compile::{ConflictPolicy, ItemBuf};
+
+#[derive(Any)]
+#[rune(item = ::http)]
+struct Client;
+
+fn http(version: &str, value: i64) -> Result<Module> {
+    let mut module = Module::with_crate("http").with_version(version);
+    module.function(["get"], move || value)?;
+    module.function(["post"], move || value)?;
+    Ok(module)
+}
+
+fn client(version: &str, method: &'static str) -> Result<Module> {
+    let mut module = Module::with_crate("http").with_version(version);
+    module.ty::<Client>()?;
+    module.function(["Client", "new"], || Client)?;
+    module.associated_function(method, |_: &Client| 1i64)?;
+    Ok(module)
+}
+
+#[test]
+fn conflict_report() -> Result<()> {
+    let mut context = Context::new();
+    context.install(http("1.0.0", 1)?)?;
+
+    let module = http("2.0.0", 2)?;
+    assert_eq!(context.conflicts(&module).len(), 2);
+
+    let Err(ContextError::ConflictingItems { conflicts }) = context.install(&module) else {
+        panic!("expected conflicting items");
+    };
+
+    let items = conflicts
+        .iter()
+        .map(|c| c.item().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(items, ["::http::get", "::http::post"]);
+
+    for conflict in &conflicts {
+        assert_eq!(conflict.existing().version(), Some("1.0.0"));
+        assert_eq!(conflict.module().version(), Some("2.0.0"));
+    }
+
+    // Nothing from the failed module was installed.
+    assert_eq!(context.modules().count(), 1);
+    let value: i64 = run(&context, "pub fn main() { http::get() }", ["main"], ())?;
+    assert_eq!(value, 1);
+    Ok(())
+}
+
+#[test]
+fn conflict_first_wins() -> Result<()> {
+    let mut context = Context::new();
+    context.install(http("1.0.0", 1)?)?;
+    context.install_with_policy(http("2.0.0", 2)?, ConflictPolicy::FirstWins)?;
+
+    let value: i64 = run(&context, "pub fn main() { http::get() }", ["main"], ())?;
+    assert_eq!(value, 1);
+    Ok(())
+}
+
+#[test]
+fn conflict_last_wins() -> Result<()> {
+    let mut context = Context::new();
+    context.install(http("1.0.0", 1)?)?;
+    context.install_with_policy(http("2.0.0", 2)?, ConflictPolicy::LastWins)?;
+
+    let value: i64 = run(&context, "pub fn main() { http::post() }", ["main"], ())?;
+    assert_eq!(value, 2);
+
+    // The replaced items are now attributed to the new module.
+    let conflicts = context.conflicts(&http("3.0.0", 3)?);
+    assert_eq!(conflicts.len(), 2);
+    assert_eq!(conflicts[0].existing().version(), Some("2.0.0"));
+    Ok(())
+}
+
+#[test]
+fn conflict_last_wins_removes_instance_functions() -> Result<()> {
+    let mut context = Context::new();
+    context.install(client("1.0.0", "legacy")?)?;
+    context.install_with_policy(client("2.0.0", "current")?, ConflictPolicy::LastWins)?;
+
+    let value: i64 = run(
+        &context,
+        "pub fn main() { http::Client::new().current() }",
+        ["main"],
+        (),
+    )?;
+
+    assert_eq!(value, 1);
+
+    // Instance functions of the replaced type are removed along with it.
+    let result = run::<_, _, i64>(
+        &context,
+        "pub fn main() { http::Client::new().legacy() }",
+        ["main"],
+        (),
+    );
+
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[test]
+fn conflict_last_wins_validates_first() -> Result<()> {
+    let mut context = Context::new();
+    context.install(http("1.0.0", 1)?)?;
+
+    let mut module = http("2.0.0", 2)?;
+    let target = "::http::missing".parse::<ItemBuf>().expect("valid item");
+    module.alias(["missing"], target)?;
+
+    let result = context.install_with_policy(module, ConflictPolicy::LastWins);
+    assert!(matches!(
+        result,
+        Err(ContextError::MissingAliasTarget { .. })
+    ));
+
+    // The existing items weren't removed by the failed install.
+    assert_eq!(context.modules().count(), 1);
+    let value: i64 = run(&context, "pub fn main() { http::get() }", ["main"], ())?;
+    assert_eq!(value, 1);
+    Ok(())
+}
+
+#[test]
+fn conflict_macro_namespace() -> Result<()> {
+    let mut functions = Module::with_crate("io");
+    functions.function(["print"], || ())?;
+
+    let mut context = Context::new();
+    context.install(functions)?;
+
+    // Macros don't conflict with functions of the same name.
+    let mut macros = Module::with_crate("io");
+    macros.macro_(["print"], |_, stream| Ok(stream.clone()))?;
+    assert!(context.conflicts(&macros).is_empty());
+    Ok(())
+}
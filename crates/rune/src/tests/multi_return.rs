@@ -0,0 +1,109 @@
+prelude!();
+
+use VmErrorKind::*;
+
+#[test]
+fn multi_return_destructure() {
+    let out: (i64, i64, i64) = rune! {
+        fn split(n) {
+            let half = n / 2;
+            (half, n - half)
+        }
+
+        fn early(n) {
+            if n < 0 {
+                return (0, 0);
+            }
+
+            (n, n * 2)
+        }
+
+        pub fn main() {
+            let (a, b) = split(7);
+            let (c, _) = early(-1);
+            let (_, d) = early(4);
+            (a * 100 + b, c, d)
+        }
+    };
+
+    assert_eq!(out, (304, 0, 8));
+}
+
+#[test]
+fn multi_return_as_value() {
+    let out: (i64, (i64, i64)) = rune! {
+        fn pair(a, b) {
+            (a, b)
+        }
+
+        pub fn main() {
+            let t = pair(1, 2);
+            let (a, b) = pair(t.0 + 10, t.1 + 20);
+            (a + b, pair(b, a))
+        }
+    };
+
+    assert_eq!(out, (33, (22, 11)));
+}
+
+#[test]
+fn multi_return_fallback() {
+    let out: i64 = rune! {
+        fn computed(v) {
+            let t = (v, v + 1);
+            t
+        }
+
+        fn deferred(v) {
+            defer { v.push(3); }
+            (v.len(), 2)
+        }
+
+        async fn later() {
+            (1, 2)
+        }
+
+        pub async fn main() {
+            let (a, b) = computed(1);
+            let (c, d) = deferred([1, 2]);
+            let (e, f) = later().await;
+            a + b + c + d + e + f
+        }
+    };
+
+    assert_eq!(out, 10);
+}
+
+#[test]
+fn multi_return_mismatch() {
+    assert_vm_error!(
+        r#"
+        fn computed() {
+            let t = (1, 2, 3);
+            t
+        }
+
+        pub fn main() {
+            let (a, b) = computed();
+        }
+        "#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "pattern did not match");
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        fn literal() {
+            (1, 2, 3)
+        }
+
+        pub fn main() {
+            let (a, b) = literal();
+        }
+        "#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "pattern did not match");
+        }
+    );
+}
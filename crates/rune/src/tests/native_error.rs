@@ -0,0 +1,101 @@
+prelude!();
+
+use core::fmt;
+use std::error::Error;
+use std::num::ParseIntError;
+
+use crate::runtime::NativeError;
+
+#[derive(Debug)]
+struct ConfigError {
+    key: String,
+    source: ParseIntError,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bad value for `{}`", self.key)
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+
+    module.fallible_function(["parse"], |s: &str| s.parse::<i64>())?;
+
+    module.fallible_function(["config"], |key: &str, value: &str| {
+        value.parse::<i64>().map_err(|source| ConfigError {
+            key: key.to_owned(),
+            source,
+        })
+    })?;
+
+    Ok(module)
+}
+
+#[test]
+fn native_error_ok() {
+    let m = make_module().expect("Failed to make module");
+
+    let out: i64 = rune_n! {
+        &m,
+        (),
+        i64 => pub fn main() {
+            parse("42")? + config("port", "8080")?
+        }
+    };
+
+    assert_eq!(out, 8122);
+}
+
+#[test]
+fn native_error_in_script() {
+    let m = make_module().expect("Failed to make module");
+
+    let out: (String, Vec<String>, String) = rune_n! {
+        &m,
+        (),
+        (String, Vec<String>, String) => pub fn main() {
+            match config("port", "eighty") {
+                Ok(..) => panic!("expected an error"),
+                Err(error) => (error.message(), error.chain(), format!("{}", error)),
+            }
+        }
+    };
+
+    assert_eq!(out.0, "bad value for `port`");
+    assert_eq!(
+        out.1,
+        ["bad value for `port`", "invalid digit found in string"]
+    );
+    assert_eq!(out.2, "bad value for `port`");
+}
+
+#[test]
+fn native_error_downcast() {
+    let m = make_module().expect("Failed to make module");
+
+    let out: Result<i64, NativeError> = rune_n! {
+        &m,
+        (),
+        Result<i64, NativeError> => pub fn main() {
+            config("timeout", "soon")
+        }
+    };
+
+    let error = out.expect_err("expected an error");
+    assert!(error.downcast_ref::<ParseIntError>().is_none());
+    assert_eq!(error.chain().count(), 2);
+
+    let error = error
+        .downcast::<ConfigError>()
+        .expect("error to be a ConfigError");
+
+    assert_eq!(error.key, "timeout");
+}
@@ -0,0 +1,125 @@
+prelude!();
+
+use crate::no_std::sync::Arc;
+use crate::runtime::{Object, ObjectKey};
+
+fn vm(source: &str) -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let mut sources = crate::tests::sources(source);
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+fn keys(object: &Object) -> Vec<ObjectKey> {
+    object.object_keys().cloned().collect()
+}
+
+#[test]
+fn object_interning_static_keys() -> Result<()> {
+    let mut vm = vm(r#"
+        pub fn main() {
+            let a = #{ name: 1, age: 2 };
+            let b = #{ name: 3 };
+            b.age = 4;
+            (a, b)
+        }
+    "#)?;
+
+    let (a, b): (Object, Object) = from_value(vm.call(["main"], ())?)?;
+    let (a, b) = (keys(&a), keys(&b));
+
+    assert_eq!(a, b);
+    assert!(ObjectKey::ptr_eq(&a[0], &b[0]));
+    assert!(ObjectKey::ptr_eq(&a[1], &b[1]));
+
+    let stats = vm.unit().object_key_stats();
+    assert_eq!(stats.unique, 2);
+    assert_eq!(stats.saved_bytes(), "name".len());
+
+    // `age` is assigned through a static object key of the unit.
+    let stats = vm.interner().stats();
+    assert_eq!(stats.keys, 1);
+    assert_eq!(stats.unique, 0);
+    Ok(())
+}
+
+#[test]
+fn object_interning_dynamic_keys() -> Result<()> {
+    let mut vm = vm(r#"
+        pub fn main(n) {
+            let objects = [];
+
+            for i in 0..n {
+                let object = #{};
+                object[`key${i % 2}`] = i;
+                objects.push(object);
+            }
+
+            objects
+        }
+    "#)?;
+
+    let objects: Vec<Object> = from_value(vm.call(["main"], (10i64,))?)?;
+
+    assert!(ObjectKey::ptr_eq(
+        &keys(&objects[0])[0],
+        &keys(&objects[2])[0]
+    ));
+    assert!(!ObjectKey::ptr_eq(
+        &keys(&objects[0])[0],
+        &keys(&objects[1])[0]
+    ));
+
+    let stats = vm.interner().stats();
+    assert_eq!(stats.keys, 10);
+    assert_eq!(stats.unique, 2);
+    assert_eq!(stats.bytes, 10 * "key0".len());
+    assert_eq!(stats.unique_bytes, 2 * "key0".len());
+    assert_eq!(vm.interner().len(), 2);
+    Ok(())
+}
+
+#[test]
+fn object_interning_capacity() -> Result<()> {
+    let mut vm = vm(r#"
+        pub fn main(n) {
+            let object = #{};
+
+            for i in 0..n {
+                object[`key${i % 4}`] = i;
+            }
+
+            object
+        }
+    "#)?;
+
+    vm.interner_mut().set_capacity(2);
+
+    let object: Object = from_value(vm.call(["main"], (8i64,))?)?;
+    assert_eq!(object.len(), 4);
+    assert_eq!(object.get_value::<_, i64>("key3").into_result()?, Some(7));
+
+    let stats = vm.interner().stats();
+    assert_eq!(vm.interner().len(), 2);
+    assert_eq!(stats.keys, 8);
+    assert_eq!(stats.unique, 6);
+    Ok(())
+}
+
+#[test]
+fn object_interning_insert_key() {
+    let key = ObjectKey::from("shared");
+
+    let mut a = Object::new();
+    let mut b = Object::new();
+    assert!(a.insert_key(key.clone(), Value::from(1i64)).is_none());
+    assert!(b.insert_key(key.clone(), Value::from(2i64)).is_none());
+    assert!(a
+        .insert(String::from("shared"), Value::from(3i64))
+        .is_some());
+
+    assert!(ObjectKey::ptr_eq(&keys(&a)[0], &keys(&b)[0]));
+    assert!(a.contains_key("shared"));
+    assert!(a.contains_key(&key));
+    assert_eq!(a.into_inner().into_keys().collect::<Vec<_>>(), ["shared"]);
+}
@@ -0,0 +1,79 @@
+prelude!();
+
+use crate::runtime::Object;
+
+fn entries(value: Value) -> Result<Vec<(String, i64)>> {
+    let object = value.into_object().into_result()?;
+    let mut iter = Object::iter_ref(object.into_ref()?);
+    let mut entries = Vec::new();
+
+    while let Some((key, value)) = iter.next_entry() {
+        entries.push((key.to_owned(), value.as_integer().into_result()?));
+    }
+
+    // The iterator stays exhausted.
+    assert!(iter.next_entry().is_none());
+    Ok(entries)
+}
+
+#[test]
+fn object_iter_ref() -> Result<()> {
+    let expected = [
+        (String::from("a"), 1),
+        (String::from("b"), 2),
+        (String::from("c"), 3),
+    ];
+
+    // A shaped object constructed from a literal.
+    let value: Value = rune! {
+        pub fn main() {
+            #{ c: 3, a: 1, b: 2 }
+        }
+    };
+
+    assert_eq!(entries(value)?, expected);
+
+    // An object stored as a map.
+    let value: Value = rune! {
+        pub fn main() {
+            let object = Object::new();
+            object.insert("b", 2);
+            object.insert("c", 3);
+            object.insert("a", 1);
+            object
+        }
+    };
+
+    assert_eq!(entries(value)?, expected);
+
+    let value: Value = rune! {
+        pub fn main() {
+            #{}
+        }
+    };
+
+    assert!(entries(value)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn object_get_ref() -> Result<()> {
+    let value: Value = rune! {
+        pub fn main() {
+            #{ a: 1, b: 2 }
+        }
+    };
+
+    let object = value.into_object().into_result()?;
+
+    let b = Object::get_ref(object.clone().into_ref()?, "b").expect("missing key");
+    assert_eq!(b.as_integer().into_result()?, 2);
+
+    // The object stays borrowed for as long as the value is.
+    assert!(object.borrow_mut().is_err());
+    drop(b);
+
+    assert!(Object::get_ref(object.clone().into_ref()?, "c").is_none());
+    assert!(object.borrow_mut().is_ok());
+    Ok(())
+}
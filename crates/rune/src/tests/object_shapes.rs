@@ -0,0 +1,93 @@
+prelude!();
+
+use crate::no_std::sync::Arc;
+use crate::runtime::{EnvProtocolCaller, Object, Shape};
+
+#[test]
+fn object_shapes_literal() {
+    let out: Vec<String> = rune! {
+        pub fn main() {
+            let object = #{ c: 3, a: 1, b: 2 };
+            object.keys().collect::<Vec>()
+        }
+    };
+
+    assert_eq!(out, ["a", "b", "c"]);
+
+    let out: (i64, i64, i64, bool) = rune! {
+        pub fn main() {
+            let a = #{ c: 3, a: 1, b: 2 };
+            let b = #{ a: 1, b: 2, c: 3 };
+            (a.a, a.b, a.c, a == b)
+        }
+    };
+
+    assert_eq!(out, (1, 2, 3, true));
+}
+
+#[test]
+fn object_shapes_mutation() {
+    let out: (Vec<String>, i64, Option<i64>, bool) = rune! {
+        pub fn main() {
+            let object = #{ b: 2, a: 1 };
+            object.a = 10;
+            object.insert("c", 3);
+            let removed = object.remove("b");
+            let keys = object.keys().collect::<Vec>();
+            (keys, object.a + object.c, removed, object.contains_key("b"))
+        }
+    };
+
+    assert_eq!(
+        out,
+        (
+            vec![String::from("a"), String::from("c")],
+            13,
+            Some(2),
+            false
+        )
+    );
+}
+
+#[test]
+fn object_shapes_structs() {
+    let out: (i64, i64, bool) = rune! {
+        struct Point { y, x }
+
+        enum Shape { Rect { w, h } }
+
+        pub fn main() {
+            let p = Point { y: 2, x: 1 };
+            let r = Shape::Rect { h: 4, w: 3 };
+
+            let area = match r {
+                Shape::Rect { w, h } => w * h,
+            };
+
+            (p.x * 10 + p.y, area, p == Point { x: 1, y: 2 })
+        }
+    };
+
+    assert_eq!(out, (12, 12, true));
+}
+
+#[test]
+fn object_shapes_compare_with_map() {
+    let keys = [String::from("b"), String::from("a")];
+    let shape = Arc::new(Shape::new(&keys).expect("keys are unique"));
+
+    let shaped = Object::with_shape(&shape, [Value::from(2i64), Value::from(1i64)]);
+
+    let mut map = Object::new();
+    map.insert(String::from("a"), Value::from(1i64));
+    map.insert(String::from("b"), Value::from(2i64));
+
+    assert_eq!(shaped.keys().collect::<Vec<_>>(), ["a", "b"]);
+    assert_eq!(shaped.len(), 2);
+    assert!(shaped.contains_key("a"));
+    assert!(!shaped.contains_key("c"));
+    let eq = Object::eq_with(&shaped, &map, Value::eq_with, &mut EnvProtocolCaller);
+    assert!(eq.into_result().expect("comparison to succeed"));
+
+    assert!(Shape::new(&[String::from("a"), String::from("a")]).is_none());
+}
@@ -0,0 +1,184 @@
+prelude!();
+
+use std::sync::{Arc, Mutex};
+
+use crate::runtime::{PanicInfo, VmError};
+
+#[derive(Debug)]
+struct Report {
+    vm: u64,
+    payload: Option<String>,
+    functions: Vec<Option<String>>,
+    located: bool,
+}
+
+fn context(reports: &Arc<Mutex<Vec<Report>>>) -> Result<Context> {
+    let mut module = Module::new();
+
+    module.function(["invoke"], |f: Function| f.call::<_, Value>(()))?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    context.set_panic_hook({
+        let reports = reports.clone();
+
+        move |info: &PanicInfo<'_>| {
+            reports.lock().unwrap().push(Report {
+                vm: info.vm,
+                payload: info.payload().map(|p| p.to_string()),
+                functions: info
+                    .frames
+                    .iter()
+                    .map(|f| f.function.as_ref().map(|f| f.to_string()))
+                    .collect(),
+                located: info.frames.iter().all(|f| f.location.is_some()),
+            });
+        }
+    });
+
+    Ok(context)
+}
+
+fn call(context: &Context, id: u64, source: &str) -> Result<Result<Value, VmError>> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let unit = prepare(&mut sources).with_context(context).build()?;
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    vm.set_id(id);
+    Ok(vm.call(["main"], ()))
+}
+
+#[test]
+fn panic_hook_explicit_panic() -> Result<()> {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let context = context(&reports)?;
+
+    let result = call(
+        &context,
+        7,
+        r#"
+        fn inner(n) {
+            panic!("bad value {}", n);
+        }
+
+        fn outer() {
+            inner(42)
+        }
+
+        pub fn main() {
+            outer()
+        }
+        "#,
+    )?;
+
+    assert!(result.is_err());
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+
+    let report = &reports[0];
+    assert_eq!(report.vm, 7);
+    assert_eq!(report.payload.as_deref(), Some("bad value 42"));
+    assert_eq!(
+        report.functions,
+        [
+            Some(String::from("inner")),
+            Some(String::from("outer")),
+            Some(String::from("main")),
+        ]
+    );
+    assert!(report.located);
+    Ok(())
+}
+
+#[test]
+fn panic_hook_runtime_error() -> Result<()> {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let context = context(&reports)?;
+
+    let result = call(
+        &context,
+        0,
+        r#"
+        fn add(a, b) {
+            a + b
+        }
+
+        pub fn main() {
+            add(1, "two")
+        }
+        "#,
+    )?;
+
+    assert!(result.is_err());
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].payload, None);
+    assert_eq!(
+        reports[0].functions,
+        [Some(String::from("add")), Some(String::from("main"))]
+    );
+    Ok(())
+}
+
+#[test]
+fn panic_hook_not_called() -> Result<()> {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let context = context(&reports)?;
+
+    let result = call(
+        &context,
+        0,
+        r#"
+        fn fallible() {
+            Err("handled")
+        }
+
+        pub fn main() {
+            let n = 0;
+
+            if let Err(..) = fallible() {
+                n += 1;
+            }
+
+            n
+        }
+        "#,
+    )?;
+
+    let output: i64 = from_value(result?)?;
+    assert_eq!(output, 1);
+    assert!(reports.lock().unwrap().is_empty());
+    Ok(())
+}
+
+#[test]
+fn panic_hook_nested_reported_once() -> Result<()> {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let context = context(&reports)?;
+
+    let result = call(
+        &context,
+        0,
+        r#"
+        fn callback() {
+            panic!("in callback");
+        }
+
+        pub fn main() {
+            invoke(callback)
+        }
+        "#,
+    )?;
+
+    assert!(result.is_err());
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].payload.as_deref(), Some("in callback"));
+    assert_eq!(reports[0].functions, [Some(String::from("callback"))]);
+    Ok(())
+}
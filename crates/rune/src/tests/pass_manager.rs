@@ -0,0 +1,158 @@
+prelude!();
+
+use std::sync::Arc;
+
+use crate::compile::{CompileVisitor, Optimization};
+use crate::Options;
+
+const SOURCE: &str = r#"
+fn classify(n) {
+    loop {
+        if n > 10 {
+            return "big";
+        }
+
+        if n > 5 {
+            break;
+        }
+
+        return "small";
+    }
+
+    "medium"
+}
+
+fn early() {
+    return 1;
+    2
+}
+
+pub fn main() {
+    let out = [];
+
+    for n in [1, 7, 20] {
+        out.push(classify(n));
+    }
+
+    out.push(`${early()}`);
+    out
+}
+"#;
+
+fn run(options: &Options, visitor: &mut dyn CompileVisitor) -> Result<Vec<String>> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+
+    let context = Context::with_default_modules()?;
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(options)
+        .with_visitor(visitor)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    Ok(from_value(vm.call(["main"], ())?)?)
+}
+
+#[derive(Default)]
+struct DumpVisitor {
+    dumps: Vec<(String, Option<Optimization>, String)>,
+}
+
+impl CompileVisitor for DumpVisitor {
+    fn visit_assembly_dump(
+        &mut self,
+        _: &dyn Located,
+        item: &Item,
+        pass: Option<Optimization>,
+        assembly: &str,
+    ) {
+        self.dumps
+            .push((item.to_string(), pass, assembly.to_string()));
+    }
+}
+
+#[test]
+fn passes_preserve_behavior() -> Result<()> {
+    let expected = ["small", "medium", "big", "1"];
+
+    let mut options = Options::default();
+    assert_eq!(run(&options, &mut DumpVisitor::default())?, expected);
+
+    for optimization in Optimization::ALL {
+        options.passes_mut().enable(optimization, false);
+        assert_eq!(run(&options, &mut DumpVisitor::default())?, expected);
+    }
+
+    options
+        .passes_mut()
+        .enable(Optimization::JumpThreading, true);
+    options.passes_mut().jump_threading_depth(1);
+    assert_eq!(run(&options, &mut DumpVisitor::default())?, expected);
+    Ok(())
+}
+
+#[test]
+fn dump_passes() -> Result<()> {
+    let mut options = Options::default();
+    options.parse_option("dump-passes=true")?;
+
+    let mut visitor = DumpVisitor::default();
+    run(&options, &mut visitor)?;
+
+    let passes = visitor
+        .dumps
+        .iter()
+        .filter(|(item, ..)| item == "early")
+        .map(|(_, pass, _)| *pass)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        passes,
+        [
+            None,
+            Some(Optimization::JumpThreading),
+            Some(Optimization::DeadCode)
+        ]
+    );
+
+    let lines = |pass| {
+        visitor
+            .dumps
+            .iter()
+            .find(|(item, p, _)| item == "early" && *p == pass)
+            .map(|(.., assembly)| assembly.lines().count())
+    };
+
+    // The code following the `return` is removed.
+    assert!(lines(Some(Optimization::DeadCode)) < lines(None));
+
+    // Nothing is dumped unless enabled.
+    let mut visitor = DumpVisitor::default();
+    run(&Options::default(), &mut visitor)?;
+    assert!(visitor.dumps.is_empty());
+    Ok(())
+}
+
+#[test]
+fn parse_pass_options() -> Result<()> {
+    let mut options = Options::default();
+
+    options.parse_option("pass-dead-code=false")?;
+    assert!(!options.passes().is_enabled(Optimization::DeadCode));
+    assert!(options.passes().is_enabled(Optimization::ConstFold));
+
+    options.parse_option("jump-threading-depth=2")?;
+    assert!(options.parse_option("jump-threading-depth=many").is_err());
+    assert!(options.parse_option("pass-inline=true").is_err());
+
+    for optimization in Optimization::ALL {
+        assert_eq!(
+            Optimization::from_name(optimization.name()),
+            Some(optimization)
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn pattern_bindings_match() {
+    let out: (i64, i64, i64, i64) = rune_s! { r#"
+        fn classify(value) {
+            match value {
+                n @ 0 => n,
+                n @ Some(x) if x > 2 => x * 10,
+                n @ Some(..) => n.unwrap() * 100,
+                n @ _ => -1,
+            }
+        }
+
+        pub fn main() {
+            (classify(0), classify(Some(3)), classify(Some(1)), classify("other"))
+        }
+    "# };
+
+    assert_eq!(out, (0, 30, 100, -1));
+}
+
+#[test]
+fn pattern_bindings_sequences() {
+    let out: (Vec<i64>, i64, (i64, i64), i64) = rune_s! { r#"
+        struct Point { x, y }
+
+        fn sum(value) {
+            match value {
+                p @ Point { x, .. } if x > 0 => p.x + p.y,
+                _ => 0,
+            }
+        }
+
+        pub fn main() {
+            let all = match [1, 2, 3] {
+                all @ [1, ..] => all,
+                _ => [],
+            };
+
+            let first = match (1, 2) {
+                t @ (a, _) if a == t.1 => 0,
+                t @ (a, _) => a,
+            };
+
+            let pair @ (a, b) = (3, 4);
+
+            let object = match #{ a: 5 } {
+                #{ a: n @ 5 } => n,
+                _ => 0,
+            };
+
+            (all, first, pair, sum(Point { x: 1, y: 2 }) + object)
+        }
+    "# };
+
+    assert_eq!(out, (vec![1, 2, 3], 1, (3, 4), 8));
+}
+
+#[test]
+fn pattern_bindings_nested() {
+    let out: Vec<i64> = rune_s! { r#"
+        pub fn main() {
+            let out = [];
+
+            for value in [Some(Some(1)), Some(None), None, Some(Some(5))] {
+                match value {
+                    outer @ Some(inner @ Some(n)) if n > 2 => {
+                        out.push(outer.unwrap().unwrap() + inner.unwrap());
+                    }
+                    Some(inner @ Some(_)) => out.push(inner.unwrap()),
+                    Some(_) => out.push(0),
+                    None => out.push(-1),
+                }
+            }
+
+            out
+        }
+    "# };
+
+    assert_eq!(out, [1, 0, -1, 10]);
+}
+
+#[test]
+fn pattern_bindings_errors() {
+    assert_errors! {
+        r#"pub fn main() { match 1 { a::b @ 1 => 0 } }"#,
+        span!(26, 30), UnsupportedBinding
+    };
+}
@@ -0,0 +1,97 @@
+prelude!();
+
+use core::fmt::{self, Write as _};
+
+use std::sync::Arc;
+
+use crate::runtime::Formatter;
+
+#[derive(Any)]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+impl Point {
+    fn string_debug(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Point {{ x: {}, y: {} }}", self.x, self.y)
+    }
+}
+
+fn context(pretty: bool) -> Result<Context> {
+    let mut module = Module::new();
+    module.ty::<Point>()?;
+    module.function(["point"], |x: i64, y: i64| Point { x, y })?;
+    module.associated_function(Protocol::STRING_DEBUG, Point::string_debug)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    if pretty {
+        context.set_pretty_printer(|point: &Point, f: &mut Formatter| {
+            write!(f, "({}, {})", point.x, point.y)
+        });
+    }
+
+    Ok(context)
+}
+
+fn call(context: &Context, source: &str) -> Result<Value> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let unit = prepare(&mut sources).with_context(context).build()?;
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    Ok(vm.call(["main"], ())?)
+}
+
+#[test]
+fn pretty_printer_formatting() -> Result<()> {
+    let context = context(true)?;
+
+    let output = call(
+        &context,
+        r#"
+        pub fn main() {
+            let p = point(1, 2);
+            [`${p}`, format!("{:?}", p), format!("{:?}", [p, 3]), `${"plain"}`]
+        }
+        "#,
+    )?;
+
+    let output: Vec<String> = from_value(output)?;
+    assert_eq!(output, ["(1, 2)", "(1, 2)", "[(1, 2), 3]", "plain"]);
+    Ok(())
+}
+
+#[test]
+fn pretty_printer_fallback() -> Result<()> {
+    let context = context(false)?;
+
+    let output = call(
+        &context,
+        r#"pub fn main() { format!("{:?}", point(1, 2)) }"#,
+    )?;
+
+    let output: String = from_value(output)?;
+    assert_eq!(output, "Point { x: 1, y: 2 }");
+    Ok(())
+}
+
+#[test]
+fn pretty_printer_with_vm() -> Result<()> {
+    let context = context(true)?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", r#"pub fn main() { point(3, 4) }"#));
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let output = vm.call(["main"], ())?;
+
+    let mut f = Formatter::new();
+    vm.with(|| output.string_debug(&mut f)).into_result()??;
+    assert_eq!(f.as_str(), "(3, 4)");
+    assert_eq!(vm.with(|| format!("{:?}", output)), "(3, 4)");
+    Ok(())
+}
@@ -0,0 +1,203 @@
+prelude!();
+
+use crate::compile::{CompilePass, PassContext, PassFn, Purity};
+use crate::runtime::Inst;
+
+use diagnostics::WarningDiagnosticKind::*;
+
+/// Records the purity of every function being compiled.
+#[derive(Default)]
+struct Record {
+    functions: Vec<(String, Option<Purity>)>,
+}
+
+impl CompilePass for Record {
+    fn visit_fn(&mut self, cx: &mut PassContext<'_>, f: &PassFn<'_>) -> compile::Result<()> {
+        self.functions.push((f.item().to_string(), cx.purity()));
+        Ok(())
+    }
+}
+
+#[test]
+fn test_pass_purity() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let mut sources = crate::tests::sources(
+        r#"
+        struct Point { x, y }
+
+        fn fib(n) { if n < 2 { n } else { fib(n - 1) + fib(n - 2) } }
+        fn point(n) { Point { x: n, y: fib(n) } }
+        fn log(n) { dbg(n); n }
+        fn indirect(n) { log(n) }
+        pub async fn later(n) { n }
+
+        pub fn main() { indirect(point(10).y) }
+        "#,
+    );
+
+    let mut record = Record::default();
+
+    prepare(&mut sources)
+        .with_context(&context)
+        .with_pass(&mut record)
+        .build()?;
+
+    let purity = |name: &str| {
+        record
+            .functions
+            .iter()
+            .find(|(item, _)| item == name)
+            .and_then(|(_, purity)| *purity)
+            .map(|purity| purity.is_pure())
+    };
+
+    assert_eq!(purity("fib"), Some(true));
+    assert_eq!(purity("point"), Some(true));
+    assert_eq!(purity("log"), Some(false));
+    assert_eq!(purity("indirect"), Some(false));
+    assert_eq!(purity("later"), Some(false));
+    assert_eq!(purity("main"), Some(false));
+    Ok(())
+}
+
+#[test]
+fn test_unused_pure_call() {
+    assert_warnings! {
+        r#"fn square(n) { n * n } pub fn main() { square(2); 1 }"#,
+        span!(39, 48), UnusedPureCall { context: Some(span!(37, 53)), .. }
+    };
+}
+
+#[test]
+fn test_impure_memo() {
+    assert_warnings! {
+        r#"#[memo] fn log(n) { dbg(n); n } pub fn main() { log(1) }"#,
+        span!(11, 14), ImpureMemo { effect: span!(20, 26), .. }
+    };
+}
+
+#[test]
+fn test_unused_pure_call_arguments() {
+    // The call is eliminated, but its arguments still have to be evaluated.
+    let out: Vec<i64> = rune! {
+        fn id(n) { n }
+
+        pub fn main() {
+            let out = [];
+            id(out.push(1));
+            out
+        }
+    };
+
+    assert_eq!(out, [1]);
+}
+
+#[test]
+fn test_pure_const_fn() {
+    let out: i64 = rune! {
+        fn square(n) { n * n }
+        const SQUARE = square(4);
+        pub fn main() { SQUARE }
+    };
+
+    assert_eq!(out, 16);
+
+    let out: i64 = rune! {
+        fn fib(n) { if n < 2 { n } else { fib(n - 1) + fib(n - 2) } }
+        const FIB = fib(10);
+        pub fn main() { FIB }
+    };
+
+    assert_eq!(out, 55);
+}
+
+#[test]
+fn test_unused_pure_call_protocols() {
+    // Indexing might fail or call a protocol function, so the call is kept
+    // even though its result is unused.
+    assert_vm_error!(
+        r#"fn first(v) { v[0] } pub fn main() { first([]); 1 }"#,
+        _ => {}
+    );
+
+    assert_warnings! {
+        r#"fn first(v) { v[0] } pub fn main() { first([]); 1 }"#,
+        span!(37, 46), UnusedPureCall { .. }
+    };
+}
+
+/// Count the calls to the function with the given name in the unit compiled
+/// from `source`.
+fn calls(source: &str, name: &str) -> Result<usize> {
+    let context = Context::with_default_modules()?;
+    let mut sources = crate::tests::sources(source);
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+
+    let hash = Hash::type_hash([name]);
+
+    Ok(unit
+        .iter_instructions()
+        .filter(|(_, inst)| matches!(inst, Inst::Call { hash: h, .. } if *h == hash))
+        .count())
+}
+
+#[test]
+fn test_unused_total_call() -> Result<()> {
+    let source = r#"fn id(n) { n } pub fn main() { id(1); 1 }"#;
+    assert_eq!(calls(source, "id")?, 0);
+    Ok(())
+}
+
+#[test]
+fn test_unused_partial_calls() -> Result<()> {
+    // Loops might not terminate.
+    let source = r#"fn spin() { loop {} } pub fn main() { spin(); 1 }"#;
+    assert_eq!(calls(source, "spin")?, 1);
+
+    let source = r#"fn spin() { while true {} } pub fn main() { spin(); 1 }"#;
+    assert_eq!(calls(source, "spin")?, 1);
+
+    // Arithmetic might overflow or divide by zero.
+    let source = r#"fn f() { 1 / 0 } pub fn main() { f(); 1 }"#;
+    assert_eq!(calls(source, "f")?, 1);
+
+    let source = r#"fn f() { 1 % 0 } pub fn main() { f(); 1 }"#;
+    assert_eq!(calls(source, "f")?, 1);
+
+    let source = r#"fn f() { 9223372036854775807 + 1 } pub fn main() { f(); 1 }"#;
+    assert_eq!(calls(source, "f")?, 1);
+
+    // Refutable patterns might not match.
+    let source = r#"fn g() { let 1 = 2; } pub fn main() { g(); 1 }"#;
+    assert_eq!(calls(source, "g")?, 1);
+
+    let source = r#"fn g((a, b)) { a } pub fn main() { g(1); 1 }"#;
+    assert_eq!(calls(source, "g")?, 1);
+
+    // Recursive functions might not terminate.
+    let source = r#"fn r() { r() } pub fn main() { r(); 1 }"#;
+    assert_eq!(calls(source, "r")?, 1);
+
+    let source = r#"fn a() { b() } fn b() { a() } pub fn main() { a(); 1 }"#;
+    assert_eq!(calls(source, "a")?, 1);
+    Ok(())
+}
+
+#[test]
+fn test_unused_partial_call_errors() {
+    assert_vm_error!(
+        r#"fn f() { 1 / 0 } pub fn main() { f(); 1 }"#,
+        VmErrorKind::DivideByZero => {}
+    );
+
+    assert_vm_error!(
+        r#"fn f() { 9223372036854775807 + 1 } pub fn main() { f(); 1 }"#,
+        VmErrorKind::Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"fn g() { let 1 = 2; } pub fn main() { g(); 1 }"#,
+        VmErrorKind::Panic { .. } => {}
+    );
+}
@@ -174,3 +174,103 @@ fn unsupported_iter_range() {
         }
     );
 }
+
+#[test]
+fn range_methods() {
+    let _: () = rune! {
+        pub fn main() {
+            assert_eq!((0..10).len(), 10);
+            assert_eq!((0..=10).len(), 11);
+            assert_eq!(('a'..'e').len(), 4);
+            assert_eq!((5..0).len(), 0);
+            assert!((5..5).is_empty());
+            assert!(!(5..=5).is_empty());
+
+            assert_eq!((1..4).rev().collect::<Vec>(), [3, 2, 1]);
+            assert_eq!((1..=4).rev().collect::<Vec>(), [4, 3, 2, 1]);
+
+            assert_eq!((0..10).step_by(3).collect::<Vec>(), [0, 3, 6, 9]);
+            assert_eq!((0..=9).step_by(3).collect::<Vec>(), [0, 3, 6, 9]);
+            assert_eq!((b'a'..=b'e').step_by(2).collect::<Vec>(), [b'a', b'c', b'e']);
+            assert_eq!((10..).step_by(5).take(3).collect::<Vec>(), [10, 15, 20]);
+
+            assert_eq!((10..0).step_by(-3).collect::<Vec>(), [10, 7, 4, 1]);
+            assert_eq!((10..=0).step_by(-5).collect::<Vec>(), [10, 5, 0]);
+            assert_eq!(('e'..'a').step_by(-2).collect::<Vec>(), ['e', 'c']);
+            assert_eq!((0..10).step_by(-1).collect::<Vec>(), []);
+
+            assert_eq!((1..4).collect::<Vec>(), [1, 2, 3]);
+            assert_eq!((1..=4).collect::<Vec>(), [1, 2, 3, 4]);
+        }
+    };
+}
+
+#[test]
+fn range_contains_in_match_guards() {
+    let out: Vec<i64> = rune! {
+        fn classify(n) {
+            match n {
+                n if (..0).contains(n) => 0,
+                n if (0..10).contains(n) => 1,
+                n if (10..=20).contains(n) => 2,
+                n if (..=30).contains(n) => 3,
+                n if (31..).contains(n) => 4,
+                _ => 5,
+            }
+        }
+
+        pub fn main() {
+            [-5, 0, 9, 10, 20, 25, 31, 100].iter().map(classify).collect::<Vec>()
+        }
+    };
+
+    assert_eq!(out, [0, 1, 1, 2, 2, 3, 4, 4]);
+}
+
+#[test]
+fn unbounded_range_methods() {
+    assert_vm_error!(
+        r#"pub fn main() { (0..).len() }"#,
+        UnboundedRange { range, method } => {
+            assert_eq!(range, "std::ops::RangeFrom");
+            assert_eq!(method, "len");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { (0..).rev() }"#,
+        UnboundedRange { range, method } => {
+            assert_eq!(range, "std::ops::RangeFrom");
+            assert_eq!(method, "rev");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { (0..).collect::<Vec>() }"#,
+        UnboundedRange { range, method } => {
+            assert_eq!(range, "std::ops::RangeFrom");
+            assert_eq!(method, "collect");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { (..10).len() }"#,
+        UnboundedRange { range, .. } => {
+            assert_eq!(range, "std::ops::RangeTo");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { (..=10).len() }"#,
+        UnboundedRange { range, .. } => {
+            assert_eq!(range, "std::ops::RangeToInclusive");
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { (..).len() }"#,
+        UnboundedRange { range, .. } => {
+            assert_eq!(range, "std::ops::RangeFull");
+        }
+    );
+}
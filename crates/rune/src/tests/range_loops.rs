@@ -0,0 +1,139 @@
+prelude!();
+
+use std::sync::Arc;
+
+use ErrorKind::*;
+use VmErrorKind::*;
+
+use crate::compile::Optimization;
+use crate::Options;
+
+const SOURCE: &str = r#"
+fn collect(n) {
+    let out = [];
+
+    for i in 0..n {
+        out.push(i);
+    }
+
+    for i in (10..0).step_by(-3) {
+        out.push(i);
+    }
+
+    for i in (0..=n).step_by(2) {
+        out.push(i);
+    }
+
+    for i in (n..=0).step_by(-2) {
+        out.push(i);
+    }
+
+    for _ in 5..0 {
+        out.push(-1);
+    }
+
+    out
+}
+
+fn control_flow() {
+    let out = [];
+
+    'outer: for i in 0..10 {
+        if i % 2 == 0 {
+            continue;
+        }
+
+        for j in (i..=0).step_by(-1) {
+            if i == 7 {
+                break 'outer;
+            }
+
+            out.push((i, j));
+
+            if j == i - 1 {
+                break;
+            }
+        }
+    }
+
+    out
+}
+
+fn overflow() {
+    let out = [];
+
+    for i in 9223372036854775805..=9223372036854775807 {
+        out.push(i);
+    }
+
+    for i in (-9223372036854775807..=-9223372036854775807).step_by(-5) {
+        out.push(i);
+    }
+
+    out.len()
+}
+
+pub fn main() {
+    (collect(4), control_flow(), overflow())
+}
+"#;
+
+type Output = (Vec<i64>, Vec<(i64, i64)>, usize);
+
+fn run(options: &Options) -> Result<Output> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+
+    let context = Context::with_default_modules()?;
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(options)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    Ok(from_value(vm.call(["main"], ())?)?)
+}
+
+#[test]
+fn range_loops() -> Result<()> {
+    let expected: Output = (
+        vec![0, 1, 2, 3, 10, 7, 4, 1, 0, 2, 4, 4, 2, 0],
+        vec![(1, 1), (1, 0), (3, 3), (3, 2), (5, 5), (5, 4)],
+        4,
+    );
+
+    let mut options = Options::default();
+    assert_eq!(run(&options)?, expected);
+
+    // Loops compiled through iterators behave the same.
+    options.passes_mut().enable(Optimization::RangeLoops, false);
+    assert_eq!(run(&options)?, expected);
+    Ok(())
+}
+
+#[test]
+fn range_loop_errors() {
+    assert_errors! {
+        r#"pub fn main() { for _ in (0..10).step_by(0) {} }"#,
+        span!(41, 42), Custom { message } => {
+            assert_eq!(message.as_ref(), "`step_by` requires a non-zero step");
+        }
+    };
+
+    assert_vm_error!(
+        r#"pub fn main() { for _ in 0..2.0 {} }"#,
+        UnsupportedIterRange { start, end } => {
+            assert_eq!(start, i64::type_info());
+            assert_eq!(end, f64::type_info());
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { for _ in (0..="end").step_by(-1) {} }"#,
+        UnsupportedIterRangeInclusive { start, end } => {
+            assert_eq!(start, i64::type_info());
+            assert_eq!(end, String::type_info());
+        }
+    );
+}
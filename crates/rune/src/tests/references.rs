@@ -0,0 +1,112 @@
+prelude!();
+
+use crate::compile::{ReferenceKind, References};
+
+fn references(code: &str, needle: &str) -> Result<Vec<(String, ReferenceKind, bool)>> {
+    let mut sources = Sources::new();
+    let source_id = sources.insert(Source::new("main", code));
+
+    let mut references = References::new();
+
+    let _ = prepare(&mut sources)
+        .with_visitor(&mut references)
+        .build()?;
+
+    let offset = code.find(needle).expect("needle not in code");
+
+    let Some(symbol) = references.symbol_at(&sources, source_id, offset) else {
+        return Ok(Vec::new());
+    };
+
+    let found = references.references(&sources, &symbol);
+
+    Ok(found
+        .into_iter()
+        .map(|r| (code[r.span.range()].to_owned(), r.kind, r.in_macro))
+        .collect())
+}
+
+#[test]
+fn item_references() -> Result<()> {
+    let code = r#"
+        mod math {
+            pub fn add(a, b) { a + b }
+        }
+
+        fn add(a, b) { math::add(a, b) }
+
+        pub fn main() { math::add(1, 2) + add(3, 4) }
+    "#;
+
+    let found = references(code, "add(1, 2)")?;
+
+    assert_eq!(
+        found,
+        [
+            ("add".to_owned(), ReferenceKind::Definition, false),
+            ("add".to_owned(), ReferenceKind::Use, false),
+            ("add".to_owned(), ReferenceKind::Use, false),
+        ]
+    );
+
+    let found = references(code, "add(3, 4)")?;
+
+    assert_eq!(
+        found,
+        [
+            ("add".to_owned(), ReferenceKind::Definition, false),
+            ("add".to_owned(), ReferenceKind::Use, false),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn local_references() -> Result<()> {
+    let code = r#"
+        pub fn main() {
+            let value = 10;
+            let other = value + 1;
+            value * other
+        }
+    "#;
+
+    let found = references(code, "value * other")?;
+
+    assert_eq!(
+        found,
+        [
+            ("value".to_owned(), ReferenceKind::Definition, false),
+            ("value".to_owned(), ReferenceKind::Use, false),
+            ("value".to_owned(), ReferenceKind::Use, false),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn macro_references_are_flagged() -> Result<()> {
+    let code = r#"
+        fn name() { "world" }
+
+        pub fn main() {
+            let greeting = format!("hello {}", name());
+            greeting + name()
+        }
+    "#;
+
+    let found = references(code, "name())")?;
+
+    assert_eq!(
+        found,
+        [
+            ("name".to_owned(), ReferenceKind::Definition, false),
+            ("name".to_owned(), ReferenceKind::Use, true),
+            ("name".to_owned(), ReferenceKind::Use, false),
+        ]
+    );
+
+    Ok(())
+}
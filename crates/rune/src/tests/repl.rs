@@ -0,0 +1,77 @@
+prelude!();
+
+use crate::cli::{Repl, ReplError};
+
+fn eval(repl: &mut Repl, input: &str) -> Result<String, ReplError> {
+    let value = block_on(repl.eval(input))?;
+    Ok(repl.format(&value).expect("value should format"))
+}
+
+#[test]
+fn repl_persists_variables() -> Result<()> {
+    let mut repl = Repl::new(Context::with_default_modules()?);
+
+    assert_eq!(eval(&mut repl, "let a = 1;")?, "()");
+    assert_eq!(
+        eval(
+            &mut repl,
+            "let (b, #{c, d: [e, ..]}) = (2, #{c: 3, d: [4, 5]});"
+        )?,
+        "()"
+    );
+    assert_eq!(eval(&mut repl, "a + b + c + e")?, "10");
+
+    // Variables can be reassigned and shadowed.
+    assert_eq!(eval(&mut repl, "a = 10; let b = \"b\";")?, "()");
+    assert_eq!(eval(&mut repl, "(a, b)")?, "(10, \"b\")");
+
+    let mut variables = repl.variables().collect::<Vec<_>>();
+    variables.sort();
+    assert_eq!(variables, ["a", "b", "c", "e"]);
+    Ok(())
+}
+
+#[test]
+fn repl_defines_items() -> Result<()> {
+    let mut repl = Repl::new(Context::with_default_modules()?);
+
+    assert_eq!(eval(&mut repl, "struct Point { x, y }")?, "()");
+    assert_eq!(eval(&mut repl, "fn point(x, y) { Point { x, y } }")?, "()");
+    assert_eq!(
+        eval(
+            &mut repl,
+            "fn sum(p) { p.x + p.y } let p = point(1, 2); sum(p)"
+        )?,
+        "3"
+    );
+    assert_eq!(eval(&mut repl, "sum(point(p.y, 40))")?, "42");
+    Ok(())
+}
+
+#[test]
+fn repl_errors() -> Result<()> {
+    let mut repl = Repl::new(Context::with_default_modules()?);
+
+    assert!(matches!(
+        block_on(repl.eval("let a = ;")),
+        Err(ReplError::Compile { .. })
+    ));
+
+    // Items aren't defined if the input fails to compile.
+    assert!(matches!(
+        block_on(repl.eval("fn f() { 1 } missing")),
+        Err(ReplError::Compile { .. })
+    ));
+    assert!(block_on(repl.eval("f()")).is_err());
+
+    // Variables aren't defined if evaluation fails.
+    assert!(matches!(
+        block_on(repl.eval("let a = 1; panic!(\"boom\")")),
+        Err(ReplError::Vm { .. })
+    ));
+    assert_eq!(repl.variables().count(), 0);
+
+    // The session can be used after an error.
+    assert_eq!(eval(&mut repl, "let a = 2; a * 2")?, "4");
+    Ok(())
+}
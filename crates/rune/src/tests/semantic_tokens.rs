@@ -0,0 +1,98 @@
+prelude!();
+
+use crate::compile::{References, SemanticTokenKind};
+
+fn tokens(code: &str) -> Result<Vec<(String, SemanticTokenKind, bool)>> {
+    let mut sources = Sources::new();
+    let source_id = sources.insert(Source::new("main", code));
+
+    let mut references = References::new();
+
+    let _ = prepare(&mut sources)
+        .with_visitor(&mut references)
+        .build()?;
+
+    Ok(references
+        .semantic_tokens(&sources, source_id)
+        .into_iter()
+        .map(|t| (code[t.span.range()].to_owned(), t.kind, t.definition))
+        .collect())
+}
+
+#[test]
+fn semantic_tokens_classify_names() -> Result<()> {
+    let code = r#"
+        const LIMIT = 10;
+
+        struct Point { x, y }
+
+        fn clamp(value) {
+            if value > LIMIT { LIMIT } else { value }
+        }
+
+        pub fn main() {
+            let point = Point { x: clamp(20), y: 0 };
+            println!("{}", point.x);
+        }
+    "#;
+
+    let tokens = tokens(code)?;
+
+    let expected = [
+        ("LIMIT", SemanticTokenKind::Constant, true),
+        ("Point", SemanticTokenKind::Type, true),
+        ("clamp", SemanticTokenKind::Function, true),
+        ("value", SemanticTokenKind::Variable, true),
+        ("value", SemanticTokenKind::Variable, false),
+        ("LIMIT", SemanticTokenKind::Constant, false),
+        ("point", SemanticTokenKind::Variable, true),
+        ("Point", SemanticTokenKind::Type, false),
+        ("clamp", SemanticTokenKind::Function, false),
+        ("println", SemanticTokenKind::Macro, false),
+        ("point", SemanticTokenKind::Variable, false),
+    ];
+
+    for (name, kind, definition) in expected {
+        assert!(
+            tokens
+                .iter()
+                .any(|t| t.0 == name && t.1 == kind && t.2 == definition),
+            "missing {name} ({kind}, definition: {definition}) in {tokens:?}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn semantic_tokens_do_not_overlap() -> Result<()> {
+    let code = r#"
+        mod math {
+            pub fn add(a, b) { a + b }
+        }
+
+        pub fn main() { math::add(1, 2) }
+    "#;
+
+    let mut sources = Sources::new();
+    let source_id = sources.insert(Source::new("main", code));
+    let mut references = References::new();
+
+    let _ = prepare(&mut sources)
+        .with_visitor(&mut references)
+        .build()?;
+
+    let tokens = references.semantic_tokens(&sources, source_id);
+
+    for pair in tokens.windows(2) {
+        assert!(pair[0].span.end <= pair[1].span.start);
+    }
+
+    let names = tokens
+        .iter()
+        .map(|t| &code[t.span.range()])
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, ["add", "a", "b", "a", "b", "main", "add"]);
+    Ok(())
+}
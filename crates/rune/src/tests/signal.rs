@@ -0,0 +1,136 @@
+prelude!();
+
+use std::sync::Arc;
+
+fn vm(context: &Context, source: &str) -> Result<Vm> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let unit = prepare(&mut sources).with_context(context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+const SOURCE: &str = r#"
+use std::signal;
+
+mod logger {
+    pub fn init(log) {
+        signal::connect("on_save", |path| log.push(`saved ${path}`));
+    }
+}
+
+mod backup {
+    pub fn init(log) {
+        signal::connect_with_priority("on_save", 10, |path| {
+            log.push(`backup ${path}`);
+            signal::emit("on_backup", path);
+        });
+
+        signal::connect("on_backup", |path| log.push(`backed up ${path}`));
+    }
+}
+
+pub fn init() {
+    let log = [];
+    logger::init(log);
+    backup::init(log);
+    log
+}
+
+pub fn save(path) {
+    signal::emit("on_save", path)
+}
+
+pub async fn save_async(path) {
+    let save = async { signal::emit("on_save", path) };
+    save.await
+}
+"#;
+
+#[test]
+fn signals() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let mut vm = vm(&context, SOURCE)?;
+
+    let log = vm.call(["init"], ())?;
+
+    let called: usize = from_value(vm.call(["save"], ("a.txt",))?)?;
+    assert_eq!(called, 2);
+
+    // Handlers are shared with the virtual machines running async blocks.
+    let called: usize = from_value(block_on(vm.async_call(["save_async"], ("b.txt",)))?)?;
+    assert_eq!(called, 2);
+
+    let log: Vec<String> = from_value(log)?;
+    assert_eq!(
+        log,
+        [
+            "backup a.txt",
+            "backed up a.txt",
+            "saved a.txt",
+            "backup b.txt",
+            "backed up b.txt",
+            "saved b.txt",
+        ]
+    );
+
+    // Clearing the virtual machine disconnects every handler.
+    vm.clear();
+    let called: usize = from_value(vm.call(["save"], ("c.txt",))?)?;
+    assert_eq!(called, 0);
+    Ok(())
+}
+
+#[test]
+fn signals_are_owned_per_vm() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let mut a = vm(&context, SOURCE)?;
+    let mut b = vm(&context, SOURCE)?;
+
+    a.call(["init"], ())?;
+
+    let called: usize = from_value(a.call(["save"], ("a.txt",))?)?;
+    assert_eq!(called, 2);
+
+    let called: usize = from_value(b.call(["save"], ("b.txt",))?)?;
+    assert_eq!(called, 0);
+    Ok(())
+}
+
+#[test]
+fn signals_in_async_functions_and_generators() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut vm = vm(
+        &context,
+        r#"
+        use std::signal;
+
+        async fn emit_async(name) {
+            signal::emit(name, ())
+        }
+
+        fn emit_generator(name) {
+            yield signal::emit(name, ());
+        }
+
+        pub fn init() {
+            signal::connect("ping", |_| ());
+        }
+
+        pub async fn main() {
+            let a = emit_async("ping").await;
+            let b = emit_generator("ping").next();
+            (a, b)
+        }
+        "#,
+    )?;
+
+    vm.call(["init"], ())?;
+
+    // Handlers are shared with the virtual machines running async functions
+    // and generators.
+    let called: (usize, Option<usize>) = from_value(block_on(vm.async_call(["main"], ()))?)?;
+    assert_eq!(called, (1, Some(1)));
+    Ok(())
+}
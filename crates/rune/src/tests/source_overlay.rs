@@ -0,0 +1,86 @@
+prelude!();
+
+use std::sync::Arc;
+
+use crate::compile::{NoopSourceLoader, OverlaySourceLoader, SourceOverlay};
+
+const MAIN: &str = r#"
+mod foo;
+
+pub fn main() {
+    (foo::value(), include_str!("data.txt"))
+}
+"#;
+
+fn build(overlay: &SourceOverlay) -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(overlay.source("project/main.rn")?);
+
+    let mut source_loader = OverlaySourceLoader::with_base(overlay, NoopSourceLoader);
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_source_loader(&mut source_loader)
+        .build()?;
+
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+#[test]
+fn source_overlay_takes_precedence() -> Result<()> {
+    let mut overlay = SourceOverlay::new();
+    overlay.insert("project/main.rn", MAIN);
+    overlay.insert("project/foo.rn", "pub fn value() { 1 }");
+    overlay.insert("project/data.txt", "first");
+
+    let mut vm = build(&overlay)?;
+    let out: (i64, String) = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(out, (1, "first".to_owned()));
+
+    // A module directory takes precedence over a file.
+    overlay.insert("project/foo/mod.rn", "pub fn value() { 2 }");
+    overlay.insert("project/data.txt", "second");
+
+    let mut vm = build(&overlay)?;
+    let out: (i64, String) = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(out, (2, "second".to_owned()));
+    Ok(())
+}
+
+#[test]
+fn source_overlay_falls_back() -> Result<()> {
+    let mut overlay = SourceOverlay::new();
+    overlay.insert("project/main.rn", MAIN);
+    overlay.insert("project/data.txt", "data");
+
+    // The module isn't in the overlay and the base loader can't load it.
+    assert!(build(&overlay).is_err());
+
+    overlay.insert("project/foo.rn", "pub fn value() { 1 }");
+    assert!(build(&overlay).is_ok());
+    Ok(())
+}
+
+#[test]
+fn source_overlay_version() {
+    let mut overlay = SourceOverlay::new();
+    assert_eq!(overlay.version(), 0);
+
+    assert!(overlay.insert("main.rn", "pub fn main() {}"));
+    assert!(!overlay.insert("main.rn", "pub fn main() {}"));
+    assert_eq!(overlay.version(), 1);
+
+    assert!(overlay.insert("main.rn", "pub fn main() { 42 }"));
+    assert_eq!(overlay.get("main.rn"), Some("pub fn main() { 42 }"));
+    assert_eq!(overlay.version(), 2);
+
+    assert!(overlay.remove("main.rn"));
+    assert!(!overlay.remove("main.rn"));
+    assert!(!overlay.contains("main.rn"));
+    assert_eq!(overlay.version(), 3);
+
+    overlay.clear();
+    assert_eq!(overlay.version(), 3);
+}
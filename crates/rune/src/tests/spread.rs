@@ -0,0 +1,88 @@
+prelude!();
+
+use ErrorKind::*;
+use VmErrorKind::*;
+
+#[test]
+fn vec_spread() {
+    let _: () = rune! {
+        pub fn main() {
+            let a = [1, 2];
+            let b = [4, 5];
+
+            assert_eq!([..a, 3, ..b], [1, 2, 3, 4, 5]);
+            assert_eq!([..a], [1, 2]);
+            assert_eq!([..a, ..a], [1, 2, 1, 2]);
+            assert_eq!([0, ..[]], [0]);
+            assert_eq!([..(1, 2), ..Some(3), ..None], [1, 2, 3]);
+            assert_eq!([..(0..3), ..(3..=4)], [0, 1, 2, 3, 4]);
+            assert_eq!([..a.iter().map(|n| n * 10)], [10, 20]);
+
+            // Spreading copies the items, leaving the original untouched.
+            let c = [..a];
+            c.push(3);
+            assert_eq!(a, [1, 2]);
+        }
+    };
+}
+
+#[test]
+fn vec_spread_ranges_are_not_spreads() {
+    let _: () = rune! {
+        pub fn main() {
+            let a = 3;
+            assert_eq!([(..a)], [(..3)]);
+            assert_eq!([1..a], [1..3]);
+            assert_eq!([..].len(), 1);
+        }
+    };
+}
+
+#[test]
+fn object_spread() {
+    let _: () = rune! {
+        pub fn main() {
+            let defaults = #{ x: 0, y: 0, z: 0 };
+
+            let o = #{ ..defaults, x: 1 };
+            assert_eq!(o, #{ x: 1, y: 0, z: 0 });
+
+            // Later entries override earlier ones.
+            let o = #{ x: 1, ..defaults };
+            assert_eq!(o, #{ x: 0, y: 0, z: 0 });
+
+            let y = 2;
+            let o = #{ x: 1, ..defaults, y, ..#{ z: 3 } };
+            assert_eq!(o, #{ x: 0, y: 2, z: 3 });
+
+            let o = #{ ..[("a", 1), ("b", 2)], c: 3 };
+            assert_eq!(o, #{ a: 1, b: 2, c: 3 });
+
+            // Spreading copies the fields, leaving the original untouched.
+            o.d = 4;
+            assert_eq!(defaults, #{ x: 0, y: 0, z: 0 });
+        }
+    };
+}
+
+#[test]
+fn spread_errors() {
+    assert_errors! {
+        r#"struct Foo { a } pub fn main() { let a = 1; Foo { ..a } }"#,
+        span!(50, 53), UnsupportedObjectSpread
+    };
+
+    assert_vm_error!(
+        r#"pub fn main() { [..1] }"#,
+        MissingInstanceFunction { instance, .. } => {
+            assert_eq!(instance, i64::type_info());
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { #{ ..1 } }"#,
+        MissingInstanceFunction { instance, .. } => {
+            assert_eq!(instance, i64::type_info());
+        }
+    );
+}
@@ -0,0 +1,84 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn strict_shadowing() {
+    assert_errors! {
+        "#![strict(shadowing)] pub fn main() { let a = 1; let a = 2; a }",
+        span!(53, 54), StrictShadowing { name } => {
+            assert_eq!(&*name, "a");
+        }
+    };
+
+    let out: i64 = rune! {
+        #![strict(shadowing)]
+
+        pub fn main() {
+            let a = { let b = 1; b };
+            let b = 2;
+            a + b
+        }
+    };
+
+    assert_eq!(out, 3);
+}
+
+#[test]
+fn strict_annotations() {
+    assert_errors! {
+        "#![strict(annotations)] fn add(a, b: i64) { a + b } pub fn main() { add(1, 2) }",
+        span!(31, 32), StrictMissingAnnotation
+    };
+
+    let out: i64 = rune! {
+        #![strict(annotations)]
+
+        struct Foo { value }
+
+        impl Foo {
+            fn add(self, n: i64) { self.value + n }
+        }
+
+        pub fn main() {
+            let f = |a| a + 1;
+            Foo { value: f(1) }.add(2)
+        }
+    };
+
+    assert_eq!(out, 4);
+}
+
+#[test]
+fn strict_is_scoped_to_module() {
+    let out: i64 = rune! {
+        mod strict {
+            #![strict(shadowing)]
+
+            pub mod nested {
+                pub fn get() { let a = 1; a }
+            }
+        }
+
+        pub fn main() {
+            let a = strict::nested::get();
+            let a = a + 1;
+            a
+        }
+    };
+
+    assert_eq!(out, 2);
+
+    assert_errors! {
+        "mod a { #![strict(shadowing)] mod b { fn f() { let x = 1; let x = 2; } } }",
+        span!(62, 63), StrictShadowing { .. }
+    };
+}
+
+#[test]
+fn strict_unsupported() {
+    assert_errors! {
+        "#![strict(coercions)]",
+        span!(10, 19), Custom { .. }
+    };
+}
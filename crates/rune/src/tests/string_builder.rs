@@ -0,0 +1,43 @@
+prelude!();
+
+use VmErrorKind::*;
+
+#[test]
+fn test_string_builder() {
+    let result: String = rune! {
+        use std::string::StringBuilder;
+
+        pub fn main() {
+            let b = StringBuilder::with_capacity(64);
+
+            for n in 0..3 {
+                if !b.is_empty() {
+                    b.push_str(", ");
+                }
+
+                b.append(n);
+            }
+
+            b.push('!');
+            b.finish()
+        }
+    };
+
+    assert_eq!(result, "0, 1, 2!");
+}
+
+#[test]
+fn test_string_builder_finished() {
+    assert_vm_error!(
+        r#"
+        use std::string::StringBuilder;
+
+        pub fn main() {
+            let b = StringBuilder::new();
+            b.finish();
+            b.push('a');
+        }
+        "#,
+        AccessError { .. } => {}
+    );
+}
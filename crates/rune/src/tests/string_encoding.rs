@@ -0,0 +1,103 @@
+prelude!();
+
+#[test]
+fn string_utf8() {
+    let result: (String, String, usize, Bytes) = rune_s! { r#"
+        pub fn main() {
+            let bytes = b"hi\xff";
+
+            let lossy = String::from_utf8_lossy(bytes);
+            let error = String::from_utf8(bytes).unwrap_err();
+            let message = `${error}`;
+
+            (lossy, message, error.valid_up_to(), error.into_bytes())
+        }
+    "# };
+
+    assert_eq!(
+        result,
+        (
+            String::from("hi\u{fffd}"),
+            String::from("invalid utf-8 sequence of 1 bytes from index 2"),
+            2,
+            Bytes::from_vec(b"hi\xff".to_vec()),
+        )
+    );
+
+    let result: (Bytes, String) = rune! {
+        pub fn main() {
+            let s = "💖";
+            let bytes = s.to_bytes();
+            (bytes, String::from_utf8(bytes)?)
+        }
+    };
+
+    assert_eq!(
+        result,
+        (
+            Bytes::from_vec("💖".as_bytes().to_vec()),
+            String::from("💖")
+        )
+    );
+}
+
+#[test]
+#[cfg(feature = "encoding")]
+fn string_latin1() {
+    let result: (String, Bytes, Bytes, usize) = rune! {
+        use std::string::EncodingPolicy;
+
+        pub fn main() {
+            let decoded = String::from_latin1(b"caf\xe9");
+            let encoded = decoded.to_latin1(EncodingPolicy::Error)?;
+            let replaced = "a€b".to_latin1(EncodingPolicy::Replace)?;
+            let error = "a€b".to_latin1(EncodingPolicy::Error).unwrap_err();
+            (decoded, encoded, replaced, error.valid_up_to())
+        }
+    };
+
+    assert_eq!(
+        result,
+        (
+            String::from("café"),
+            Bytes::from_vec(b"caf\xe9".to_vec()),
+            Bytes::from_vec(b"a?b".to_vec()),
+            1
+        )
+    );
+}
+
+#[test]
+#[cfg(feature = "encoding")]
+fn string_utf16le() {
+    let result: (String, Bytes) = rune! {
+        use std::string::EncodingPolicy;
+
+        pub fn main() {
+            let bytes = "h💖".to_utf16le();
+            (String::from_utf16le(bytes, EncodingPolicy::Error)?, bytes)
+        }
+    };
+
+    assert_eq!(
+        result,
+        (
+            String::from("h💖"),
+            Bytes::from_vec(b"h\0\x3d\xd8\x96\xdc".to_vec())
+        )
+    );
+
+    // An unpaired surrogate followed by a trailing odd byte.
+    let result: (String, usize, usize) = rune! {
+        use std::string::EncodingPolicy;
+
+        pub fn main() {
+            let replaced = String::from_utf16le(b"h\0\x00\xd8i\0!", EncodingPolicy::Replace)?;
+            let surrogate = String::from_utf16le(b"h\0\x00\xd8i\0", EncodingPolicy::Error).unwrap_err();
+            let odd = String::from_utf16le(b"h\0i\0!", EncodingPolicy::Error).unwrap_err();
+            (replaced, surrogate.valid_up_to(), odd.valid_up_to())
+        }
+    };
+
+    assert_eq!(result, (String::from("h\u{fffd}i\u{fffd}"), 2, 4));
+}
@@ -0,0 +1,175 @@
+prelude!();
+
+use crate::cli::{Test, TestOutcome, TestRunner};
+use crate::modules::capture_io;
+use crate::no_std::sync::Arc;
+
+fn runner() -> Result<TestRunner> {
+    let mut sources = crate::tests::sources(
+        r#"
+        pub fn test_add() { println!("add"); assert_eq!(1 + 1, 2); }
+        pub fn test_sub() { println!("sub"); assert_eq!(1 - 1, 1); }
+        pub fn test_parse() { Err("bad") }
+        pub fn bench_add() {}
+        "#,
+    );
+
+    let context = context(&capture_io::CaptureIo::new())?;
+    let unit = Arc::new(prepare(&mut sources).with_context(&context).build()?);
+    let sources = Arc::new(sources);
+
+    let mut runner = TestRunner::new().with_jobs(2);
+
+    for name in ["test_add", "test_sub", "test_parse", "bench_add"] {
+        let item = ItemBuf::with_item([name]);
+        let test = Test::new(Hash::type_hash([name]), item, unit.clone(), sources.clone());
+        runner.push(test.should_panic(name == "bench_add"));
+    }
+
+    Ok(runner)
+}
+
+fn context(capture: &capture_io::CaptureIo) -> Result<Context> {
+    let mut context = Context::with_config(false)?;
+    context.install(capture_io::module(capture)?)?;
+    Ok(context)
+}
+
+#[test]
+fn test_runner_report() -> Result<()> {
+    let report = runner()?.with_filter("test_").run(context)?;
+
+    let names = report
+        .results()
+        .iter()
+        .map(|r| r.item().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, ["test_add", "test_sub", "test_parse"]);
+    assert_eq!(
+        (report.passed(), report.failed(), report.filtered()),
+        (1, 2, 1)
+    );
+
+    let [add, sub, parse] = report.results() else {
+        panic!("expected three results");
+    };
+
+    assert!(add.outcome().is_passed());
+    assert_eq!(add.output(), b"add\n");
+    assert!(matches!(sub.outcome(), TestOutcome::Panicked(..)));
+    assert_eq!(sub.output(), b"sub\n");
+    assert!(matches!(parse.outcome(), TestOutcome::ReturnedErr(error) if error == "\"bad\""));
+
+    let mut junit = Vec::new();
+    report.write_junit(&mut junit)?;
+    let junit = String::from_utf8(junit)?;
+
+    assert!(junit.contains(r#"<testsuite name="rune" tests="3" failures="2" skipped="0""#));
+    assert!(junit.contains(r#"<testcase name="test_parse" classname="""#));
+    assert!(junit.contains(r#"<failure message="err: &quot;bad&quot;">"#));
+
+    let mut json = Vec::new();
+    report.write_json(&mut json)?;
+    let json: serde_json::Value = serde_json::from_slice(&json)?;
+    assert_eq!(json["failed"], 2);
+    assert_eq!(json["tests"][0]["output"], "add\n");
+    Ok(())
+}
+
+#[test]
+fn test_runner_filters() -> Result<()> {
+    let report = runner()?.with_filter("*_add").run(context)?;
+    assert_eq!(report.results().len(), 2);
+    assert_eq!(report.filtered(), 2);
+    // `bench_add` doesn't panic although it should.
+    assert!(matches!(
+        report.results()[1].outcome(),
+        TestOutcome::ExpectedPanic
+    ));
+
+    let report = runner()?
+        .with_filter("test_?dd")
+        .with_filter("parse")
+        .run(context)?;
+    assert_eq!(report.results().len(), 2);
+
+    let report = runner()?.with_jobs(1).with_fail_fast(true).run(context)?;
+    assert_eq!((report.results().len(), report.skipped()), (2, 2));
+    Ok(())
+}
+
+#[test]
+fn test_runner_ignored() -> Result<()> {
+    let mut sources = crate::tests::sources(
+        r#"
+        #[test] #[ignore] fn test_ignored() { panic!("ignored") }
+        #[test] #[should_panic] fn test_panics() { panic!("panics") }
+        "#,
+    );
+
+    let context = context(&capture_io::CaptureIo::new())?;
+    let unit = Arc::new(prepare(&mut sources).with_context(&context).build()?);
+    let sources = Arc::new(sources);
+
+    let runner = |include_ignored| {
+        let mut runner = TestRunner::new().with_include_ignored(include_ignored);
+
+        for (name, ignore, should_panic) in
+            [("test_ignored", true, false), ("test_panics", false, true)]
+        {
+            let item = ItemBuf::with_item([name]);
+            let test = Test::new(Hash::type_hash([name]), item, unit.clone(), sources.clone());
+            runner.push(test.ignore(ignore).should_panic(should_panic));
+        }
+
+        runner
+    };
+
+    let report = runner(false).run(context)?;
+    assert_eq!((report.passed(), report.ignored()), (1, 1));
+
+    let report = runner(true).run(context)?;
+    assert_eq!(
+        (report.passed(), report.failed(), report.ignored()),
+        (1, 1, 0)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_runner_mocks() -> Result<()> {
+    let mut sources = crate::tests::sources(
+        r#"
+        #[test] fn test_mocked() {
+            std::test::mock(String::from, |s| "mocked");
+            assert_eq!(String::from("a"), "mocked");
+        }
+
+        #[test] fn test_unmocked() {
+            assert_eq!(String::from("a"), "a");
+        }
+        "#,
+    );
+
+    let context = context(&capture_io::CaptureIo::new())?;
+    let unit = Arc::new(prepare(&mut sources).with_context(&context).build()?);
+    let sources = Arc::new(sources);
+
+    // NB: a single job runs both tests on the same worker thread.
+    let mut runner = TestRunner::new().with_jobs(1);
+
+    for name in ["test_mocked", "test_unmocked"] {
+        let item = ItemBuf::with_item([name]);
+        runner.push(Test::new(
+            Hash::type_hash([name]),
+            item,
+            unit.clone(),
+            sources.clone(),
+        ));
+    }
+
+    let report = runner.run(context)?;
+    assert_eq!((report.passed(), report.failed()), (2, 0));
+    Ok(())
+}
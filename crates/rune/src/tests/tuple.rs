@@ -44,3 +44,50 @@ fn test_tuple_ownership() {
         }
     };
 }
+
+/// Tuples beyond the arity of function arguments can still be converted to and
+/// from Rust tuples.
+#[test]
+fn test_large_tuple_conversion() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    type Large = (
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+        i64,
+    );
+
+    let value: Large = run(
+        &context,
+        "pub fn main() { (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19) }",
+        ["main"],
+        (),
+    )?;
+
+    assert_eq!(value.0, 0);
+    assert_eq!(value.19, 19);
+
+    let value = rune::to_value(value)?;
+    let tuple = value.into_tuple().into_result()?;
+    let tuple = tuple.borrow_ref()?;
+    assert_eq!(tuple.len(), 20);
+    assert_eq!(tuple[19].as_integer().into_result()?, 19);
+    Ok(())
+}
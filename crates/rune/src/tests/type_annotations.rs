@@ -0,0 +1,146 @@
+prelude!();
+
+use std::sync::Arc;
+
+use crate::compile::{ErrorKind, Options};
+use crate::runtime::VmErrorKind;
+
+#[test]
+fn test_type_annotations() {
+    let out: (i64, String, f64) = rune! {
+        struct Point { x, y }
+
+        fn add(a: i64, b: i64) -> i64 {
+            a + b
+        }
+
+        fn greet(name: String) -> String {
+            "Hello " + name
+        }
+
+        fn norm(point: Point, scale: f64) -> f64 {
+            (point.x + point.y) * scale
+        }
+
+        pub fn main() {
+            let point = Point { x: 1.0, y: 2.0 };
+            (add(1, 2), greet("World"), norm(point, 2.0))
+        }
+    };
+
+    assert_eq!(out, (3, "Hello World".to_owned(), 6.0));
+}
+
+#[test]
+fn test_type_annotations_closures() {
+    let out: i64 = rune! {
+        pub fn main() {
+            let n = 10;
+            let f = |a: i64, (b, c): Tuple| a + b + c + n;
+            f(1, (2, 3))
+        }
+    };
+
+    assert_eq!(out, 16);
+}
+
+#[test]
+fn test_type_annotation_violations() {
+    assert_vm_error!(
+        r#"
+        fn add(a: i64, b: i64) { a + b }
+        pub fn main() { add(1, "two") }
+        "#,
+        VmErrorKind::BadArgumentAnnotation { arg, expected, actual } => {
+            assert_eq!(arg, 1);
+            assert_eq!(expected, "i64");
+            assert_eq!(actual.to_string(), "String");
+
+            // Arguments are displayed counting from one.
+            let error = VmErrorKind::BadArgumentAnnotation { arg, expected, actual };
+            assert_eq!(
+                error.to_string(),
+                "Argument #2: expected `i64` according to its type annotation, got `String`"
+            );
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        struct Foo;
+        struct Bar;
+
+        struct Baz;
+
+        impl Baz {
+            fn take(self, foo: Foo) {}
+        }
+
+        pub fn main() { Baz.take(Bar) }
+        "#,
+        VmErrorKind::BadArgumentAnnotation { arg, expected, .. } => {
+            assert_eq!(arg, 1);
+            assert_eq!(expected, "Foo");
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        pub fn main() {
+            let f = |a: String| a;
+            f(1)
+        }
+        "#,
+        VmErrorKind::BadArgumentAnnotation { arg, expected, .. } => {
+            assert_eq!(arg, 0);
+            assert_eq!(expected, "String");
+        }
+    );
+}
+
+#[test]
+fn test_type_annotation_errors() {
+    assert_errors! {
+        r#"fn foo(a: Missing) {}"#,
+        span!(10, 17),
+        ErrorKind::MissingItem { .. }
+    };
+
+    assert_errors! {
+        r#"fn foo() -> Missing {}"#,
+        span!(12, 19),
+        ErrorKind::MissingItem { .. }
+    };
+
+    assert_errors! {
+        r#"fn bar() {} fn foo(a: bar) {}"#,
+        span!(22, 25),
+        ErrorKind::ExpectedMeta { .. }
+    };
+}
+
+#[test]
+fn test_type_checks_disabled() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut options = Options::default();
+    options.type_checks(false);
+
+    let mut sources = crate::tests::sources(
+        r#"
+        fn describe(value: Missing) -> i64 { `${value}` }
+        fn double(a: i64) { a * 2 }
+        pub fn main() { (describe(1), double(2.5)) }
+        "#,
+    );
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .build()?;
+
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let out: (String, f64) = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(out, ("1".to_owned(), 5.0));
+    Ok(())
+}
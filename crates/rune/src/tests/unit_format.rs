@@ -0,0 +1,96 @@
+prelude!();
+
+use crate::no_std::sync::Arc;
+use crate::runtime::unit::{ArrayUnit, UnitFormatError, UNIT_MAGIC};
+use crate::Unit;
+
+const SOURCE: &str = r#"
+const GREETING = #{ hello: "world", answer: 42, nested: #{ a: 1, b: 2 } };
+
+struct Point { x, y }
+
+enum Shape { Circle(radius), Square { side } }
+
+fn add(a, b) {
+    a + b
+}
+
+pub fn main(n) {
+    let f = |v| add(v, 2);
+    let _ = (GREETING, Point { x: 1, y: 2 }, Shape::Circle(1));
+    f(n)
+}
+"#;
+
+fn build() -> Result<Unit> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", SOURCE));
+    Ok(rune::prepare(&mut sources).build()?)
+}
+
+fn serialize(unit: &Unit) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    unit.serialize_into(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[test]
+fn unit_format_round_trip() -> Result<()> {
+    let unit = build()?;
+    let bytes = serialize(&unit)?;
+    assert!(bytes.starts_with(&UNIT_MAGIC));
+
+    let loaded = Unit::<ArrayUnit>::deserialize_from(&bytes[..])?;
+    assert_eq!(serialize(&loaded)?, bytes);
+
+    let context = Context::with_default_modules()?;
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(loaded));
+    let n: i64 = from_value(vm.call(["main"], (40i64,))?)?;
+    assert_eq!(n, 42);
+    Ok(())
+}
+
+#[test]
+fn unit_format_deterministic() -> Result<()> {
+    let a = serialize(&build()?)?;
+
+    for _ in 0..8 {
+        assert_eq!(serialize(&build()?)?, a);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn unit_format_bad_magic() -> Result<()> {
+    let unit = build()?;
+
+    // A unit serialized without a header, like the ones written by older
+    // versions of the CLI.
+    let bytes = bincode::serialize(&(unit.logic(), unit.debug_info()))?;
+    let error = Unit::<ArrayUnit>::deserialize_from(&bytes[..]).unwrap_err();
+    assert!(matches!(error, UnitFormatError::BadMagic));
+
+    let error = Unit::<ArrayUnit>::deserialize_from(&b"RUNE"[..]).unwrap_err();
+    assert!(matches!(error, UnitFormatError::BadMagic));
+    Ok(())
+}
+
+#[test]
+fn unit_format_mismatch() -> Result<()> {
+    let bytes = serialize(&build()?)?;
+
+    let mut version = bytes.clone();
+    version[UNIT_MAGIC.len()..UNIT_MAGIC.len() + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    let error = Unit::<ArrayUnit>::deserialize_from(&version[..]).unwrap_err();
+    assert!(matches!(
+        error,
+        UnitFormatError::UnsupportedVersion { version: u32::MAX }
+    ));
+
+    let mut compiler = bytes;
+    compiler[UNIT_MAGIC.len() + 4] ^= 0xff;
+    let error = Unit::<ArrayUnit>::deserialize_from(&compiler[..]).unwrap_err();
+    assert!(matches!(error, UnitFormatError::CompilerMismatch { .. }));
+    Ok(())
+}
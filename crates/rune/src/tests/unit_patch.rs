@@ -0,0 +1,168 @@
+prelude!();
+
+use crate::no_std::sync::Arc;
+use crate::runtime::unit::PatchError;
+use crate::runtime::{Args, Call};
+use crate::Unit;
+
+fn build(context: &Context, source: &str) -> Result<Unit> {
+    let mut sources = crate::tests::sources(source);
+    Ok(prepare(&mut sources).with_context(context).build()?)
+}
+
+fn call<T>(context: &Context, unit: Unit, args: impl Args) -> Result<T>
+where
+    T: FromValue,
+{
+    let mut vm = Vm::new(Arc::new(context.runtime()), Arc::new(unit));
+    let value = vm.call(["main"], args)?;
+    Ok(crate::from_value(value)?)
+}
+
+#[test]
+fn patch_function() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut unit = build(
+        &context,
+        r#"
+        fn scale(n) { n * 2 }
+
+        fn fib(n) {
+            if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+        }
+
+        pub fn main(n) {
+            (scale(n), fib(n), `n is ${n}`)
+        }
+        "#,
+    )?;
+
+    let patch = build(
+        &context,
+        r#"
+        pub fn scale(n) {
+            let o = #{ factor: 10 };
+            let f = |v| v * o.factor;
+            f(n)
+        }
+        "#,
+    )?;
+
+    unit.patch_function(["scale"], &patch)?;
+    unit.verify()?;
+
+    let out: (i64, i64, String) = call(&context, unit.clone(), (5,))?;
+    assert_eq!(out, (50, 5, String::from("n is 5")));
+
+    // Recursive functions call the new body.
+    let patch = build(
+        &context,
+        r#"
+        pub fn fib(n) {
+            if n < 2 { 1 } else { fib(n - 1) + fib(n - 2) }
+        }
+        "#,
+    )?;
+
+    unit.patch_function(["fib"], &patch)?;
+    unit.verify()?;
+
+    let out: (i64, i64, String) = call(&context, unit, (5,))?;
+    assert_eq!(out, (50, 8, String::from("n is 5")));
+    Ok(())
+}
+
+#[test]
+fn patch_function_errors() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let unit = build(
+        &context,
+        r#"
+        fn helper(a, b) { a + b }
+        pub fn main(n) { helper(n, 1) }
+        "#,
+    )?;
+
+    let patch = build(&context, r#"pub fn main(a, b) { a + b }"#)?;
+
+    assert_eq!(
+        unit.clone().patch_function(["main"], &patch),
+        Err(PatchError::ArgumentsMismatch {
+            hash: Hash::type_hash(["main"]),
+            expected: 1,
+            actual: 2,
+        })
+    );
+
+    let patch = build(&context, r#"pub async fn main(n) { n }"#)?;
+
+    assert_eq!(
+        unit.clone().patch_function(["main"], &patch),
+        Err(PatchError::CallMismatch {
+            hash: Hash::type_hash(["main"]),
+            expected: Call::Immediate,
+            actual: Call::Async,
+        })
+    );
+
+    assert_eq!(
+        unit.clone().patch_function(["missing"], &patch),
+        Err(PatchError::MissingFunction {
+            hash: Hash::type_hash(["missing"]),
+        })
+    );
+
+    // The patch calls a function which has a different signature in the unit.
+    let patch = build(
+        &context,
+        r#"
+        fn helper(a) { a }
+        pub fn main(n) { helper(n) }
+        "#,
+    )?;
+
+    let mut patched = unit.clone();
+
+    assert_eq!(
+        patched.patch_function(["main"], &patch),
+        Err(PatchError::MissingReference {
+            hash: Hash::type_hash(["helper"]),
+        })
+    );
+
+    // The unit is untouched after a failed patch.
+    let out: i64 = call(&context, patched, (1,))?;
+    assert_eq!(out, 2);
+    Ok(())
+}
+
+#[test]
+fn patch_vm() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+
+    let unit = Arc::new(build(
+        &context,
+        r#"
+        fn greeting(name) { `Hello, ${name}` }
+        pub fn main() { greeting("World") }
+        "#,
+    )?);
+
+    let mut vm = Vm::new(runtime.clone(), unit.clone());
+
+    let patch = build(&context, r#"pub fn greeting(name) { `Goodbye, ${name}` }"#)?;
+
+    vm.patch_function(["greeting"], &patch)?;
+
+    let out: String = crate::from_value(vm.call(["main"], ())?)?;
+    assert_eq!(out, "Goodbye, World");
+
+    // Other virtual machines sharing the unit are unaffected.
+    let mut other = Vm::new(runtime, unit);
+    let out: String = crate::from_value(other.call(["main"], ())?)?;
+    assert_eq!(out, "Hello, World");
+    Ok(())
+}
@@ -0,0 +1,207 @@
+prelude!();
+
+use crate::no_std::sync::Arc;
+use crate::runtime::unit::{ArrayUnit, UnitEncoder, VerifyError};
+use crate::runtime::{Call, Inst, InstAddress, Unit, UnitFn};
+
+/// Construct a unit with a single function `main` taking `args` arguments
+/// from the given instructions.
+fn unit(args: usize, instructions: &[Inst]) -> Result<Unit<ArrayUnit>> {
+    let mut storage = ArrayUnit::default();
+
+    for inst in instructions {
+        storage.encode(*inst)?;
+    }
+
+    let mut functions = crate::hash::Map::default();
+
+    functions.insert(
+        Hash::type_hash(["main"]),
+        UnitFn::Offset {
+            offset: 0,
+            call: Call::Immediate,
+            args,
+        },
+    );
+
+    Ok(Unit::new(
+        storage,
+        functions,
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+        Default::default(),
+        Default::default(),
+        None,
+        Default::default(),
+    ))
+}
+
+#[test]
+fn verify_compiled() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = crate::tests::sources(
+        r#"
+        struct Point { x, y }
+
+        enum Shape { Circle { radius }, Square(side) }
+
+        fn area(shape) {
+            match shape {
+                Shape::Circle { radius } => radius * radius * 3,
+                Shape::Square(side) if side > 0 => side * side,
+                _ => 0,
+            }
+        }
+
+        fn first(values) {
+            let value = values.iter().next()?;
+            Some(value)
+        }
+
+        fn counter() {
+            let n = 0;
+
+            loop {
+                n += 1;
+                yield n;
+
+                if n > 2 {
+                    break;
+                }
+            }
+        }
+
+        async fn later(n) {
+            n + 1
+        }
+
+        pub async fn main(n) {
+            let p = Point { x: n, y: 2 };
+            let add = |a| a + p.x + p.y;
+            let object = #{ "a": add(1), "b": [1, 2, 3] };
+            let sum = 0;
+
+            for v in object.b {
+                sum += v;
+            }
+
+            while sum > 10 {
+                sum -= 1;
+            }
+
+            let label = `sum is ${sum}`;
+            let total = later(sum).await + area(Shape::Square(2));
+            let generated = counter().next();
+            (label, total, generated, first([1]), 1..2, object["a"] && true)
+        }
+        "#,
+    );
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    unit.verify()?;
+
+    let vm = Vm::new_verified(Arc::new(context.runtime()), Arc::new(unit));
+    assert!(vm.is_ok());
+    Ok(())
+}
+
+#[test]
+fn verify_bad_jump() -> Result<()> {
+    let unit = unit(0, &[Inst::Jump { jump: 10 }])?;
+    assert_eq!(unit.verify(), Err(VerifyError::BadJump { ip: 0, jump: 10 }));
+    Ok(())
+}
+
+#[test]
+fn verify_missing_static_string() -> Result<()> {
+    let unit = unit(
+        0,
+        &[
+            Inst::String { slot: 0 },
+            Inst::Return {
+                address: InstAddress::Top,
+                clean: 0,
+            },
+        ],
+    )?;
+
+    assert_eq!(
+        unit.verify(),
+        Err(VerifyError::MissingStaticString { ip: 0, slot: 0 })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn verify_stack_underflow() -> Result<()> {
+    let unit = unit(0, &[Inst::Pop, Inst::ReturnUnit])?;
+
+    assert_eq!(
+        unit.verify(),
+        Err(VerifyError::StackUnderflow { ip: 0, depth: 0 })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn verify_bad_frame_offset() -> Result<()> {
+    let unit = unit(
+        1,
+        &[
+            Inst::Copy { offset: 1 },
+            Inst::Return {
+                address: InstAddress::Top,
+                clean: 1,
+            },
+        ],
+    )?;
+
+    assert_eq!(
+        unit.verify(),
+        Err(VerifyError::BadFrameOffset {
+            ip: 0,
+            offset: 1,
+            depth: 1,
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn verify_stack_mismatch() -> Result<()> {
+    // The jump skips over the push, so the return is reached with two
+    // different stack depths.
+    let unit = unit(
+        0,
+        &[
+            Inst::bool(true),
+            Inst::JumpIf { jump: 3 },
+            Inst::unit(),
+            Inst::ReturnUnit,
+        ],
+    )?;
+
+    assert_eq!(
+        unit.verify(),
+        Err(VerifyError::StackMismatch {
+            ip: 3,
+            expected: 0,
+            actual: 1,
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn verify_falls_through() -> Result<()> {
+    let unit = unit(0, &[Inst::unit()])?;
+    assert_eq!(unit.verify(), Err(VerifyError::FallsThrough { ip: 0 }));
+    Ok(())
+}
@@ -0,0 +1,96 @@
+prelude!();
+
+use crate::runtime::{self, ChangeKind, ValueDiff};
+
+#[test]
+fn value_diff_changes() -> Result<()> {
+    let (a, b): (Value, Value) = rune_s! {
+        r#"
+        struct Point { x, y }
+        enum Shape { Circle(center, radius), Rect { origin, size } }
+
+        pub fn main() {
+            let a = #{
+                name: "data",
+                shapes: [Shape::Circle(Point { x: 1, y: 2 }, 3.0)],
+                tags: ["a", "b"],
+                removed: 1,
+            };
+
+            let b = #{
+                name: "data",
+                shapes: [Shape::Circle(Point { x: 1, y: 5 }, 3.0), Shape::Rect { origin: Point { x: 0, y: 0 }, size: (1, 1) }],
+                tags: ["a"],
+                added: (),
+            };
+
+            (a, b)
+        }
+        "#
+    };
+
+    let diff = runtime::diff(&a, &b).into_result()?;
+
+    let paths = diff
+        .changes()
+        .iter()
+        .map(|change| change.path().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        paths,
+        ["added", "removed", "shapes[0][0].y", "shapes[1]", "tags[1]"]
+    );
+
+    assert!(matches!(
+        diff.changes()[2].kind(),
+        ChangeKind::Changed {
+            from: Value::Integer(2),
+            to: Value::Integer(5)
+        }
+    ));
+
+    let out = diff.to_string();
+    assert!(
+        out.starts_with("+ added: ()\n- removed: 1\n~ shapes[0][0].y: 2 -> 5\n"),
+        "{out}"
+    );
+
+    assert!(runtime::diff(&a, &a).into_result()?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn value_diff_types() -> Result<()> {
+    let a = crate::to_value((1i64, String::from("a")))?;
+    let b = crate::to_value(vec![1i64])?;
+
+    let diff = runtime::diff(&a, &b).into_result()?;
+    assert_eq!(diff.len(), 1);
+    assert!(diff.changes()[0].path().segments().is_empty());
+
+    // Only the values of the same type are compared for equality.
+    let a = crate::to_value((1i64, 2.0f64))?;
+    let b = crate::to_value((1.0f64, 2.0f64))?;
+
+    let diff = runtime::diff(&a, &b).into_result()?;
+    assert_eq!(diff.to_string(), "~ [0]: 1 -> 1.0\n");
+    Ok(())
+}
+
+#[test]
+fn value_diff_script() {
+    let diff: ValueDiff = rune_s! {
+        r#"
+        pub fn main() {
+            let d = std::test::diff(#{ a: [1, 2] }, #{ a: [1, 3] });
+            assert!(!d.is_empty());
+            assert_eq!(d.len(), 1);
+            assert_eq!(`${d}`, "~ a[1]: 2 -> 3\n");
+            d
+        }
+        "#
+    };
+
+    assert_eq!(diff.len(), 1);
+}
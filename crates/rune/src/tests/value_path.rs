@@ -0,0 +1,110 @@
+prelude!();
+
+use crate::runtime::{PathSegment, ValuePath, ValuePathErrorKind};
+
+fn data() -> Value {
+    rune_s! {
+        r#"
+        struct Point { x, y }
+        enum Shape { Circle(center, radius), Rect { origin, size } }
+
+        pub fn main() {
+            #{
+                "name": "data",
+                "shapes": [
+                    Shape::Circle(Point { x: 1, y: 2 }, 3.0),
+                    Shape::Rect { origin: Point { x: 4, y: 5 }, size: (6, 7) },
+                ],
+                "a.b": #{ "~/": [1, 2, 3] },
+            }
+        }
+        "#
+    }
+}
+
+#[test]
+fn value_path_get() -> Result<()> {
+    let value = data();
+
+    assert_eq!(value.get_path_as::<String>("name")?, "data");
+    assert_eq!(value.get_path_as::<f64>("shapes[0][1]")?, 3.0);
+    assert_eq!(value.get_path_as::<i64>("shapes[0].0.y")?, 2);
+    assert_eq!(value.get_path_as::<i64>("shapes[1].origin.x")?, 4);
+    assert_eq!(value.get_path_as::<i64>("shapes.1.size.1")?, 7);
+    assert_eq!(value.get_path_as::<i64>(r#"["a.b"]["~/"][2]"#)?, 3);
+
+    // JSON pointers.
+    assert_eq!(value.get_path_as::<i64>("/shapes/1/size/0")?, 6);
+    assert_eq!(value.get_path_as::<i64>("/a.b/~0~1/0")?, 1);
+
+    let path = ValuePath::parse("shapes[1].size")?;
+    assert_eq!(path.get_as::<(i64, i64)>(&value)?, (6, 7));
+    assert!(matches!(
+        ValuePath::parse("")?.get(&value)?,
+        Value::Object(..)
+    ));
+    Ok(())
+}
+
+#[test]
+fn value_path_display() -> Result<()> {
+    let path = ValuePath::parse(r#"a["b.c"][1].d["e\"f"]"#)?;
+
+    assert_eq!(
+        path.segments(),
+        [
+            PathSegment::Field("a".into()),
+            PathSegment::Field("b.c".into()),
+            PathSegment::Index(1),
+            PathSegment::Field("d".into()),
+            PathSegment::Field("e\"f".into()),
+        ]
+    );
+
+    assert_eq!(path.to_string(), r#"a["b.c"][1].d["e\"f"]"#);
+    assert_eq!(ValuePath::parse(&path.to_string())?, path);
+    Ok(())
+}
+
+#[test]
+fn value_path_errors() {
+    let value = data();
+
+    let error = value.get_path("shapes[1].origin.z").unwrap_err();
+    assert_eq!(error.path(), "shapes[1].origin.z");
+    assert!(matches!(error.kind(), ValuePathErrorKind::MissingField { field } if field == "z"));
+
+    let error = value.get_path("shapes[2].origin").unwrap_err();
+    assert_eq!(error.path(), "shapes[2]");
+    assert_eq!(
+        error.to_string(),
+        "Index `2` out of bounds for length `2` at `shapes[2]`"
+    );
+
+    let error = value.get_path("name.first").unwrap_err();
+    assert_eq!(error.path(), "name.first");
+    assert!(matches!(
+        error.kind(),
+        ValuePathErrorKind::Unsupported { .. }
+    ));
+
+    let error = value.get_path("shapes[0].radius").unwrap_err();
+    assert_eq!(error.path(), "shapes[0].radius");
+    assert!(matches!(
+        error.kind(),
+        ValuePathErrorKind::Unsupported { .. }
+    ));
+
+    let error = value.get_path_as::<i64>("name").unwrap_err();
+    assert!(matches!(error.kind(), ValuePathErrorKind::VmError { .. }));
+
+    for path in [
+        "a.", ".a", "a..b", "a[", "a[b]", "a[1", r#"a["b"#, "a[0]b", "/a~2",
+    ] {
+        let error = ValuePath::parse(path).unwrap_err();
+        assert!(
+            matches!(error.kind(), ValuePathErrorKind::Syntax { .. }),
+            "{path}: {error}"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+prelude!();
+
+fn make_module() -> Result<Module, ContextError> {
+    let mut module = Module::new();
+
+    module.variadic_function(["count"], |args: &[Value]| args.len() as i64)?;
+
+    module.variadic_function(["join"], |args: &[Value]| -> VmResult<String> {
+        let mut out = String::new();
+
+        for value in args {
+            let string = vm_try!(value.clone().into_string());
+            out.push_str(&vm_try!(string.borrow_ref()));
+        }
+
+        VmResult::Ok(out)
+    })?;
+
+    Ok(module)
+}
+
+#[test]
+fn variadic_function() {
+    let m = make_module().expect("Failed to make module");
+
+    let out: (i64, i64, i64) = rune_n! {
+        &m,
+        (),
+        (i64, i64, i64) => pub fn main() {
+            (count(), count(1), count(1, "two", [3]))
+        }
+    };
+
+    assert_eq!(out, (0, 1, 3));
+
+    let out: String = rune_n! {
+        &m,
+        (),
+        String => pub fn main() {
+            join("a", "b", "c")
+        }
+    };
+
+    assert_eq!(out, "abc");
+
+    // Only the arguments are popped, so surrounding locals are left intact.
+    let out: (i64, i64, i64) = rune_n! {
+        &m,
+        (),
+        (i64, i64, i64) => pub fn main() {
+            let a = 10;
+            let b = count(a, a, a);
+            (a, b, count(a, b))
+        }
+    };
+
+    assert_eq!(out, (10, 3, 2));
+}
+
+#[test]
+fn variadic_function_pointer() {
+    let m = make_module().expect("Failed to make module");
+
+    let out: (i64, i64) = rune_n! {
+        &m,
+        (),
+        (i64, i64) => pub fn main() {
+            let f = count;
+            (f(), f(1, 2))
+        }
+    };
+
+    assert_eq!(out, (0, 2));
+}
+
+#[test]
+fn variadic_function_errors() {
+    let m = make_module().expect("Failed to make module");
+    let mut context = Context::with_default_modules().expect("Failed to build context");
+    context.install(&m).expect("Failed to install module");
+
+    let result: Result<String> = run(&context, r#"pub fn main() { join("a", 1) }"#, ["main"], ());
+    assert!(result.is_err());
+}
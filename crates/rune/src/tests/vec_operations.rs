@@ -0,0 +1,75 @@
+prelude!();
+
+use VmErrorKind::*;
+
+#[test]
+fn vec_operations() {
+    let _: () = rune! {
+        pub fn main() {
+            let v = [1, 2, 3, 4];
+            assert_eq!(v.swap_remove(0), 1);
+            assert_eq!(v, [4, 2, 3]);
+
+            v.rotate_left(1);
+            assert_eq!(v, [2, 3, 4]);
+            v.rotate_right(2);
+            assert_eq!(v, [3, 4, 2]);
+            v.rotate_left(3);
+            assert_eq!(v, [3, 4, 2]);
+
+            let tail = v.split_off(1);
+            assert_eq!(v, [3]);
+            assert_eq!(tail, [4, 2]);
+            assert_eq!(v.split_off(1), []);
+
+            tail.fill("x");
+            assert_eq!(tail, ["x", "x"]);
+        }
+    };
+}
+
+#[test]
+fn vec_concat() {
+    let _: () = rune! {
+        pub fn main() {
+            assert_eq!([].concat(), []);
+            assert_eq!([[], []].concat(), []);
+            assert_eq!([[1], [2, 3], [[4]]].concat(), [1, 2, 3, [4]]);
+
+            let v = [1, 2];
+            assert_eq!([v, v].concat(), [1, 2, 1, 2]);
+
+            assert_eq!([[1], (2, 3), #{}].concat(), [1, 2, 3]);
+        }
+    };
+}
+
+#[test]
+fn vec_operations_errors() {
+    assert_vm_error!(
+        r#"pub fn main() { [].swap_remove(0) }"#,
+        OutOfRange { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { [1, 2].rotate_left(3) }"#,
+        OutOfRange { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { [1, 2].rotate_right(3) }"#,
+        OutOfRange { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { [1, 2].split_off(3) }"#,
+        OutOfRange { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { [[1], 2].concat() }"#,
+        MissingInstanceFunction { instance, .. } => {
+            assert_eq!(instance, i64::type_info());
+        }
+    );
+}
@@ -0,0 +1,64 @@
+prelude!();
+
+use VmErrorKind::*;
+
+#[test]
+fn vec_reductions_numeric() {
+    let _: () = rune! {
+        pub fn main() {
+            let ints = [3, 1, 4, 1, 5];
+            assert_eq!(ints.sum(), 14);
+            assert_eq!(ints.product(), 60);
+            assert_eq!(ints.min(), Some(1));
+            assert_eq!(ints.max(), Some(5));
+
+            let floats = [1.5, -2.0, 4.0];
+            assert_eq!(floats.sum(), 3.5);
+            assert_eq!(floats.product(), -12.0);
+            assert_eq!(floats.min(), Some(-2.0));
+            assert_eq!(floats.max(), Some(4.0));
+
+            assert_eq!([].sum(), 0);
+            assert_eq!([].product(), 1);
+            assert_eq!([].min(), None);
+            assert_eq!([].max(), None);
+        }
+    };
+}
+
+#[test]
+fn vec_reductions_fallback() {
+    let _: () = rune! {
+        pub fn main() {
+            assert_eq!(["a", "b", "c"].sum(), "abc");
+            assert_eq!([(1, 2), (0, 3), (1, 1)].min(), Some((0, 3)));
+            assert_eq!([(1, 2), (0, 3), (1, 1)].max(), Some((1, 2)));
+            assert_eq!([["b"], ["a"], ["c"]].min(), Some(["a"]));
+        }
+    };
+}
+
+#[test]
+fn vec_reductions_errors() {
+    assert_vm_error!(
+        r#"pub fn main() { [9223372036854775807, 1].sum() }"#,
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { [4611686018427387904, 2].product() }"#,
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { [1.0, f64::NAN].max() }"#,
+        IllegalFloatComparison { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { [1, 2.0].sum() }"#,
+        MissingInstanceFunction { instance, .. } => {
+            assert_eq!(instance, i64::type_info());
+        }
+    );
+}
@@ -93,3 +93,89 @@ fn test_assign_assign_exprs() {
     };
     assert_eq!(out, (4, (), ()));
 }
+
+#[test]
+fn test_assign_index_binop() {
+    let out: (i64, i64, i64) = rune_s! {
+        r#"
+        pub fn main() {
+            let v = [1, 2, 3];
+            let i = 1;
+            v[i] += 10;
+            v[i + 1] *= 2;
+
+            let obj = #{k: 3};
+            obj["k"] *= 2;
+            obj.k += 1;
+            (v[1], v[2], obj.k)
+        }
+        "#
+    };
+    assert_eq!(out, (12, 6, 7));
+}
+
+#[test]
+fn test_assign_index_binop_const() {
+    let out: (i64, i64) = rune_s! {
+        r#"
+        const fn compute() {
+            let v = [1, 2, 3];
+            let i = 0;
+            v[i] += 10;
+            v[2] *= 3;
+
+            let obj = #{k: 2};
+            obj["k"] *= 5;
+            obj.k += 1;
+            (v[0] + v[2], obj.k)
+        }
+
+        const VALUE = compute();
+
+        pub fn main() {
+            VALUE
+        }
+        "#
+    };
+    assert_eq!(out, (20, 11));
+}
+
+#[test]
+fn test_assign_index_binop_errors() {
+    use VmErrorKind::*;
+
+    assert_vm_error!(
+        r#"pub fn main() { let v = [1]; v[-1] += 1; }"#,
+        MissingIndexInteger { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let v = [1]; v[3] += 1; }"#,
+        OutOfRange { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { let n = 1; n["k"] += 1; }"#,
+        UnsupportedIndexGet { .. } => {}
+    );
+}
+
+#[test]
+fn test_assign_index_binop_protocols() {
+    let out: (i64, String) = rune! {
+        use std::collections::HashMap;
+
+        pub fn main() {
+            let map = HashMap::new();
+            map.insert("n", 1);
+            map.insert("s", "hello");
+
+            map["n"] += 10;
+            map["n"] *= 2;
+            map["s"] += " world";
+            (map["n"], map["s"])
+        }
+    };
+
+    assert_eq!(out, (22, String::from("hello world")));
+}
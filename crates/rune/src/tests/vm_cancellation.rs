@@ -0,0 +1,171 @@
+prelude!();
+
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::runtime::CancellationToken;
+
+fn vm(source: &str) -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+#[test]
+fn vm_cancellation_token() -> Result<()> {
+    let mut vm = vm(r#"
+        pub fn main() {
+            loop {}
+        }
+
+        pub fn add(a, b) {
+            a + b
+        }
+        "#)?;
+
+    let token = CancellationToken::new();
+    vm.set_cancellation_token(Some(token.clone()));
+
+    // Functions which don't call or loop run to completion.
+    let output: i64 = from_value(vm.call(["add"], (1, 2))?)?;
+    assert_eq!(output, 3);
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        token.cancel();
+    });
+
+    let error = vm.call(["main"], ()).unwrap_err();
+    assert!(error.is_cancelled());
+    assert_eq!(error.to_string(), "Execution was cancelled");
+
+    handle.join().unwrap();
+    Ok(())
+}
+
+#[test]
+fn vm_cancellation_calls() -> Result<()> {
+    let mut vm = vm(r#"
+        fn inner() {
+            1
+        }
+
+        pub fn main() {
+            inner()
+        }
+        "#)?;
+
+    let token = CancellationToken::new();
+    token.cancel();
+    vm.set_cancellation_token(Some(token));
+
+    let error = vm.call(["main"], ()).unwrap_err();
+    assert!(error.is_cancelled());
+
+    vm.set_cancellation_token(None);
+    let output: i64 = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 1);
+    Ok(())
+}
+
+#[test]
+fn vm_cancellation_async() -> Result<()> {
+    let mut vm = vm(r#"
+        async fn spin() {
+            loop {}
+        }
+
+        pub async fn main() {
+            spin().await
+        }
+        "#)?;
+
+    let token = CancellationToken::new();
+    vm.set_cancellation_token(Some(token.clone()));
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        token.cancel();
+    });
+
+    // The token is inherited by the virtual machine running `spin`.
+    let error = block_on(vm.async_call(["main"], ())).unwrap_err();
+    assert!(error.is_cancelled());
+
+    handle.join().unwrap();
+    Ok(())
+}
+
+#[test]
+fn vm_cancellation_generator() -> Result<()> {
+    let mut vm = vm(r#"
+        fn spin() {
+            yield 1;
+            loop {}
+        }
+
+        pub fn main() {
+            let generator = spin();
+            generator.next();
+            generator.next()
+        }
+        "#)?;
+
+    let token = CancellationToken::new();
+    vm.set_cancellation_token(Some(token.clone()));
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        token.cancel();
+    });
+
+    // The token is inherited by the virtual machine running the generator.
+    let error = vm.call(["main"], ()).unwrap_err();
+    assert!(error.is_cancelled());
+
+    handle.join().unwrap();
+
+    // Deadlines are inherited as well.
+    vm.set_cancellation_token(None);
+
+    let error = vm
+        .call_with_timeout(["main"], (), Duration::from_millis(10))
+        .unwrap_err();
+    assert!(error.is_cancelled());
+    Ok(())
+}
+
+#[test]
+fn vm_call_with_timeout() -> Result<()> {
+    let mut vm = vm(r#"
+        pub fn main(n) {
+            let out = 0;
+
+            while n != 0 {
+                out += 1;
+                n -= 1;
+            }
+
+            out
+        }
+        "#)?;
+
+    let output: i64 =
+        from_value(vm.call_with_timeout(["main"], (10,), Duration::from_secs(60))?)?;
+    assert_eq!(output, 10);
+
+    let error = vm
+        .call_with_timeout(["main"], (-1,), Duration::from_millis(10))
+        .unwrap_err();
+    assert!(error.is_cancelled());
+
+    // The timeout only applies to the call it was specified for.
+    let output: i64 = from_value(vm.call(["main"], (10,))?)?;
+    assert_eq!(output, 10);
+    Ok(())
+}
@@ -264,3 +264,125 @@ fn test_const_block() {
 
     assert_eq!(result, "Hello World");
 }
+
+#[test]
+fn test_const_match() {
+    let result: i64 = rune! {
+        const fn classify(value) {
+            match value {
+                0 => 0,
+                (a, b) => a + b,
+                [first, ..] => first,
+                "ten" => 10,
+                n if n < 0 => -1,
+                _ => 1,
+            }
+        }
+
+        const VALUE = [
+            classify(0),
+            classify(-10),
+            classify((2, 3)),
+            classify([7, 8, 9]),
+            classify("ten"),
+            classify(5),
+        ];
+
+        pub fn main() {
+            let sum = 0;
+
+            for n in VALUE {
+                sum += n;
+            }
+
+            sum
+        }
+    };
+
+    assert_eq!(result, 0 - 1 + 5 + 7 + 10 + 1);
+
+    let result: String = rune_s! { r#"
+        const VALUE = match #{name: "rune", version: 1} {
+            #{name, version: 0} => `${name} unreleased`,
+            #{name, ..} => `${name} released`,
+        };
+
+        pub fn main() { VALUE }
+    "#};
+
+    assert_eq!(result, "rune released");
+}
+
+#[test]
+fn test_const_let_destructuring() {
+    let result: i64 = rune_s! { r#"
+        const VALUE = {
+            let (a, b) = (1, 2);
+            let [c, d, ..] = [3, 4, 5];
+            let #{e, f: (g, _)} = #{e: 6, f: (7, 8)};
+            let h @ [i, ..] = [9];
+
+            let j = loop {
+                let (j, 0) = (h, 1) else { break 10; };
+                break j;
+            };
+
+            a + b + c + d + e + g + i + j
+        };
+
+        pub fn main() { VALUE }
+    "#};
+
+    assert_eq!(result, 1 + 2 + 3 + 4 + 6 + 7 + 9 + 10);
+}
+
+#[test]
+fn test_const_for() {
+    let result: i64 = rune! {
+        const VALUE = {
+            let sum = 0;
+
+            for n in 0..10 {
+                sum += n;
+            }
+
+            for (a, b) in [(1, 2), (3, 4)] {
+                sum += a * b;
+            }
+
+            'outer: for n in 1..=3 {
+                for m in (10, 20, 30) {
+                    if n == 3 {
+                        break 'outer;
+                    }
+
+                    sum += m;
+                }
+            }
+
+            sum
+        };
+
+        pub fn main() { VALUE }
+    };
+
+    assert_eq!(result, 45 + 2 + 12 + 120);
+
+    let result: i64 = rune! {
+        const VALUE = {
+            let n = 1;
+
+            loop {
+                n *= 2;
+
+                if n > 100 {
+                    break n;
+                }
+            }
+        };
+
+        pub fn main() { VALUE }
+    };
+
+    assert_eq!(result, 128);
+}
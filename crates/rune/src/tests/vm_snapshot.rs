@@ -0,0 +1,128 @@
+prelude!();
+
+use std::sync::Arc;
+
+use crate::runtime::{RuntimeContext, Unit};
+
+fn build(context: &Context, source: &str) -> Result<Arc<Unit>> {
+    let mut sources = crate::tests::sources(source);
+    let unit = prepare(&mut sources).with_context(context).build()?;
+    Ok(Arc::new(unit))
+}
+
+fn runtime(context: &Context) -> Arc<RuntimeContext> {
+    Arc::new(context.runtime())
+}
+
+#[test]
+fn test_snapshot_restore() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let unit = build(
+        &context,
+        r#"
+        pub fn main(config, n) {
+            config.base + config.items.len() * n
+        }
+        "#,
+    )?;
+
+    let mut vm = Vm::new(runtime(&context), unit.clone());
+
+    let snapshot = {
+        let mut object = Object::new();
+        object.insert(String::from("base"), Value::Integer(10));
+        object.insert(
+            String::from("items"),
+            vec![1i64, 2, 3].to_value().into_result()?,
+        );
+        let execution = vm.execute(["main"], (object, 2i64))?;
+        execution.vm().snapshot()?
+    };
+
+    // A snapshot can be restored any number of times.
+    for _ in 0..2 {
+        let vm = Vm::restore(runtime(&context), unit.clone(), &snapshot)?;
+        let output: i64 = from_value(vm.complete()?)?;
+        assert_eq!(output, 16);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_mismatch() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let unit = build(&context, "pub fn main(n) { n + 1 }")?;
+    let other = build(&context, "pub fn main(n) { n + 2 }")?;
+
+    let mut vm = Vm::new(runtime(&context), unit.clone());
+    let snapshot = vm.execute(["main"], (1i64,))?.vm().snapshot()?;
+
+    let error = Vm::restore(runtime(&context), other, &snapshot).unwrap_err();
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::SnapshotMismatch { what: "unit" }
+    ));
+
+    let error = Vm::restore(Arc::new(Context::new().runtime()), unit, &snapshot).unwrap_err();
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::SnapshotMismatch { what: "context" }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_unsupported_value() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let unit = build(&context, "pub fn main(f) { f() }")?;
+
+    let mut vm = Vm::new(runtime(&context), unit.clone());
+    let function = vm.lookup_function(["main"])?;
+    let execution = vm.execute(["main"], (function,))?;
+
+    assert!(matches!(
+        execution.vm().snapshot().unwrap_err().into_kind(),
+        VmErrorKind::ConstNotSupported { .. }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_rebuilt_unit() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let source = "pub fn main(n) { n + 1 }";
+    let unit = build(&context, source)?;
+    let rebuilt = build(&context, source)?;
+
+    let mut vm = Vm::new(runtime(&context), unit);
+    let snapshot = vm.execute(["main"], (1i64,))?.vm().snapshot()?;
+
+    // Building the same source again produces the same fingerprint.
+    let vm = Vm::restore(runtime(&context), rebuilt, &snapshot)?;
+    let output: i64 = from_value(vm.complete()?)?;
+    assert_eq!(output, 2);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "serialize")]
+fn test_snapshot_deserialized_unit() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let unit = build(&context, "pub fn main(n) { n + 1 }")?;
+
+    let mut bytes = Vec::new();
+    unit.serialize_into(&mut bytes)?;
+    let deserialized: Unit = Unit::deserialize_from(&bytes[..])?;
+
+    let mut vm = Vm::new(runtime(&context), unit);
+    let snapshot = vm.execute(["main"], (1i64,))?.vm().snapshot()?;
+
+    // The fingerprint is stored in the unit, so it survives serialization.
+    let vm = Vm::restore(runtime(&context), Arc::new(deserialized), &snapshot)?;
+    let output: i64 = from_value(vm.complete()?)?;
+    assert_eq!(output, 2);
+    Ok(())
+}
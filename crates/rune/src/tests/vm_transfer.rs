@@ -0,0 +1,149 @@
+prelude!();
+
+use std::sync::Arc;
+
+use crate::runtime::Unit;
+
+#[derive(Any, Debug, PartialEq)]
+struct Counter {
+    count: i64,
+}
+
+fn build(context: &Context, source: &str) -> Result<Arc<Unit>> {
+    let mut sources = crate::tests::sources(source);
+    let unit = prepare(&mut sources).with_context(context).build()?;
+    Ok(Arc::new(unit))
+}
+
+#[test]
+fn test_transfer_copies() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+
+    let a = build(
+        &context,
+        r#"
+        struct Point { x, y }
+
+        pub fn produce() {
+            let point = Point { x: 1, y: 2 };
+            [point, Some("hello"), (1, 2.0)]
+        }
+
+        pub fn mutate(values) {
+            values[0].x = 10;
+            values.push(42);
+            values.len()
+        }
+        "#,
+    )?;
+
+    let b = build(
+        &context,
+        r#"
+        struct Point { x, y }
+
+        pub fn consume(values) {
+            let point = values[0];
+            values.push(42);
+
+            match point {
+                Point { x, y } => x + y + values.len(),
+                _ => 0,
+            }
+        }
+        "#,
+    )?;
+
+    let mut a = Vm::new(runtime.clone(), a);
+    let mut b = Vm::new(runtime, b);
+
+    let value = a.call(["produce"], ())?;
+    let transferred = value.clone().transfer(&b).into_result()?;
+
+    let len: usize = from_value(a.call(["mutate"], (value,))?)?;
+    assert_eq!(len, 4);
+
+    // The transferred value is not affected by mutations in the source, and
+    // the struct matches the type declared in the target.
+    let output: i64 = from_value(b.call(["consume"], (transferred,))?)?;
+    assert_eq!(output, 7);
+    Ok(())
+}
+
+#[test]
+fn test_transfer_missing_type() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::new(context.runtime());
+
+    let a = build(
+        &context,
+        r#"
+        struct Point { x, y }
+        pub fn main() { Point { x: 1, y: 2 } }
+        "#,
+    )?;
+    let b = build(&context, "pub fn main() {}")?;
+
+    let mut a = Vm::new(runtime.clone(), a);
+    let b = Vm::new(runtime, b);
+
+    let value = a.call(["main"], ())?;
+    let error = value.transfer(&b).into_result().unwrap_err();
+
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::TransferMissingType { .. }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_transfer_native() -> Result<()> {
+    let mut module = Module::new();
+    module.ty::<Counter>()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let unit = Arc::new(Unit::default());
+    let with = Vm::new(Arc::new(context.runtime()), unit.clone());
+    let without = Vm::new(Arc::new(Context::with_default_modules()?.runtime()), unit);
+
+    let value = Value::vec(vec![Value::from(AnyObj::new(Counter { count: 1 }))]);
+
+    let error = value.clone().transfer(&without).into_result().unwrap_err();
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::TransferMissingType { .. }
+    ));
+
+    // Native values are moved into the target.
+    let transferred = value.clone().transfer(&with).into_result()?;
+    let counters: Vec<Counter> = from_value(transferred)?;
+    assert_eq!(counters, [Counter { count: 1 }]);
+
+    assert!(value.transfer(&with).into_result().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_transfer_unsupported() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let unit = build(&context, "pub fn main() {}")?;
+
+    let vm = Vm::new(Arc::new(context.runtime()), unit);
+    let function = vm.lookup_function(["main"])?;
+    let error = Value::from(function)
+        .transfer(&vm)
+        .into_result()
+        .unwrap_err();
+
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::TransferNotSupported { .. }
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+prelude!();
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{self, Poll};
+use std::sync::Arc;
+
+use crate::runtime::budget;
+
+/// Counts the number of times the wrapped future is polled.
+struct CountPolls<F> {
+    future: Pin<Box<F>>,
+    polls: usize,
+}
+
+impl<F> Future for CountPolls<F>
+where
+    F: Future,
+{
+    type Output = (F::Output, usize);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        self.polls += 1;
+
+        match self.future.as_mut().poll(cx) {
+            Poll::Ready(output) => Poll::Ready((output, self.polls)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+fn count_polls<F>(future: F) -> CountPolls<F> {
+    CountPolls {
+        future: Box::pin(future),
+        polls: 0,
+    }
+}
+
+fn vm() -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new(
+        "main",
+        r#"
+        async fn count(n) {
+            let out = 0;
+
+            while out < n {
+                out += 1;
+            }
+
+            out
+        }
+
+        pub async fn main(n) {
+            count(n).await
+        }
+        "#,
+    ));
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+#[test]
+fn vm_yield_interval() -> Result<()> {
+    let mut vm = vm()?;
+
+    let (output, polls) = block_on(count_polls(vm.async_call(["main"], (1000,))));
+    assert_eq!(from_value::<i64>(output?)?, 1000);
+    assert_eq!(polls, 1);
+
+    vm.set_yield_interval(Some(100));
+
+    // The interval is inherited by the virtual machine running `count`.
+    let (output, polls) = block_on(count_polls(vm.async_call(["main"], (1000,))));
+    assert_eq!(from_value::<i64>(output?)?, 1000);
+    assert!(
+        polls > 10,
+        "expected the execution to yield, got {polls} polls"
+    );
+    Ok(())
+}
+
+#[test]
+fn vm_yield_interval_budget() -> Result<()> {
+    let mut vm = vm()?;
+    vm.set_yield_interval(Some(100));
+
+    // An enclosing budget takes precedence over yielding.
+    let (output, polls) = block_on(count_polls(budget::with(
+        10,
+        vm.async_call(["main"], (1000,)),
+    )));
+
+    assert!(output.is_err());
+    assert_eq!(polls, 1);
+    Ok(())
+}
@@ -11,6 +11,7 @@ use crate::compile::ModId;
 use crate::indexing::index;
 use crate::indexing::items::Items;
 use crate::indexing::{IndexItem, Indexer, Scopes};
+use crate::parse::Parser;
 use crate::query::Query;
 use crate::SourceId;
 
@@ -89,12 +90,10 @@ impl<'a, 'arena> Worker<'a, 'arena> {
                         };
                     }
 
+                    let mut parser = Parser::new(source.as_str(), source_id, true);
+
                     if self.q.options.function_body {
-                        let ast = match crate::parse::parse_all::<ast::EmptyBlock>(
-                            source.as_str(),
-                            source_id,
-                            true,
-                        ) {
+                        let ast = match parser.parse_all::<ast::EmptyBlock>() {
                             Ok(ast) => ast,
                             Err(error) => {
                                 self.q.diagnostics.error(source_id, error);
@@ -102,6 +101,10 @@ impl<'a, 'arena> Worker<'a, 'arena> {
                             }
                         };
 
+                        for &span in parser.reserved_idents() {
+                            self.q.diagnostics.future_keyword(source_id, span);
+                        }
+
                         let span = Span::new(0, source.len());
                         let mut idx = indexer!();
 
@@ -109,11 +112,7 @@ impl<'a, 'arena> Worker<'a, 'arena> {
                             idx.q.diagnostics.error(source_id, error);
                         }
                     } else {
-                        let mut ast = match crate::parse::parse_all::<ast::File>(
-                            source.as_str(),
-                            source_id,
-                            true,
-                        ) {
+                        let mut ast = match parser.parse_all::<ast::File>() {
                             Ok(ast) => ast,
                             Err(error) => {
                                 self.q.diagnostics.error(source_id, error);
@@ -121,6 +120,10 @@ impl<'a, 'arena> Worker<'a, 'arena> {
                             }
                         };
 
+                        for &span in parser.reserved_idents() {
+                            self.q.diagnostics.future_keyword(source_id, span);
+                        }
+
                         let mut idx = indexer!();
 
                         if let Err(error) = index::file(&mut idx, &mut ast) {
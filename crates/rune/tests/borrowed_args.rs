@@ -0,0 +1,97 @@
+//! Test that passing script strings and bytes to native functions which take
+//! `&str` or `&[u8]` borrows them instead of copying them.
+//!
+//! This is an integration test of its own, since it counts allocations
+//! through a global allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use rune::runtime::Bytes;
+use rune::{Context, Diagnostics, Hash, Module, Source, Sources, Vm};
+
+/// Allocator which counts the number of allocations performed.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn vm() -> rune::Result<Vm> {
+    let mut module = Module::new();
+    module.function(["str_len"], |s: &str| s.len())?;
+    module.function(["string_len"], |s: String| s.len())?;
+    module.function(["bytes_len"], |b: &[u8]| b.len())?;
+    module.function(["owned_bytes_len"], |b: Bytes| b.len())?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let mut sources = Sources::new();
+
+    sources.insert(Source::new(
+        "main",
+        r#"
+        pub fn borrowed(s, b) {
+            let n = 0;
+            for _ in 0..100 { n += str_len(s) + bytes_len(b); }
+            n
+        }
+
+        pub fn owned(s, b) {
+            let n = 0;
+            for _ in 0..100 { n += string_len(s.clone()) + owned_bytes_len(b.clone()); }
+            n
+        }
+        "#,
+    ));
+
+    let mut diagnostics = Diagnostics::new();
+
+    let unit = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .build()?;
+
+    Ok(Vm::new(Arc::new(context.runtime()), Arc::new(unit)))
+}
+
+fn allocations(vm: &mut Vm, entry: Hash, s: &str, b: &[u8]) -> rune::Result<usize> {
+    let s = rune::to_value(s)?;
+    let b = rune::to_value(Bytes::from_slice(b))?;
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    vm.call(entry, (s, b))?;
+    Ok(ALLOCATIONS.load(Ordering::Relaxed) - before)
+}
+
+#[test]
+fn borrowed_args_do_not_copy() -> rune::Result<()> {
+    let mut vm = vm()?;
+
+    let text = "a".repeat(1024);
+    let bytes = vec![0u8; 1024];
+
+    let borrowed = allocations(&mut vm, Hash::type_hash(["borrowed"]), &text, &bytes)?;
+    let owned = allocations(&mut vm, Hash::type_hash(["owned"]), &text, &bytes)?;
+
+    assert!(
+        borrowed < owned,
+        "borrowed arguments allocated {borrowed} times, owned {owned} times"
+    );
+
+    Ok(())
+}
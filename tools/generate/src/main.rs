@@ -10,6 +10,10 @@ struct Keyword {
     variant: String,
     doc: String,
     keyword: String,
+    #[serde(default)]
+    contextual: bool,
+    #[serde(default)]
+    reserved: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -67,6 +71,10 @@ fn main() -> Result<()> {
         })
         .collect::<Vec<_>>();
 
+    let contextual = keywords.iter().filter(|k| k.contextual).collect::<Vec<_>>();
+
+    let reserved = keywords.iter().filter(|k| k.reserved).collect::<Vec<_>>();
+
     let punctuations = tokens
         .iter()
         .flat_map(|t| match t {
@@ -322,6 +330,23 @@ fn main() -> Result<()> {
                         _ => None,
                     }
                 }
+
+                $("/// If this is a contextual keyword, get the identifier it corresponds to.")
+                $("///")
+                $("/// Contextual keywords are only special in specific syntactic positions,")
+                $("/// and can otherwise be used as identifiers.")
+                pub(crate) fn as_contextual_keyword(&self) -> Option<&'static str> {
+                    match self {
+                        $(for k in &contextual join ($['\r']) => Self::$(&k.variant) => Some($(quoted(&k.keyword))),)
+                        _ => None,
+                    }
+                }
+
+                $("/// Test if this is a keyword which is reserved to become a hard keyword in")
+                $("/// the future.")
+                pub(crate) fn is_reserved_keyword(&self) -> bool {
+                    matches!(self, $(for k in &reserved join ( | ) => Self::$(&k.variant)))
+                }
             }
 
             impl $display for Kind {